@@ -11,6 +11,8 @@ export interface InterchangeBundle {
   constructs: InterchangeConstruct[];
   id: string;
   kind: "Bundle";
+  /** Optional evaluator features used by the bundle (omitted when none). */
+  requires?: string[];
   tenor: string;
   tenor_version: string;
 }
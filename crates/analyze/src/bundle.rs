@@ -17,6 +17,16 @@ pub enum AnalysisError {
     MissingField { construct: String, field: String },
 }
 
+impl AnalysisError {
+    /// The stable error code (see `tenor_core::error_codes`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            AnalysisError::InvalidBundle(_) => "TEN-E-0200",
+            AnalysisError::MissingField { .. } => "TEN-E-0201",
+        }
+    }
+}
+
 impl fmt::Display for AnalysisError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -41,6 +51,9 @@ impl From<tenor_interchange::InterchangeError> for AnalysisError {
 pub struct Transition {
     pub from: String,
     pub to: String,
+    /// Raw guard predicate JSON, if the transition is guarded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guard: Option<serde_json::Value>,
 }
 
 /// An Entity construct extracted from interchange JSON.
@@ -195,6 +208,7 @@ impl AnalysisBundle {
                             .map(|t| Transition {
                                 from: t.from.clone(),
                                 to: t.to.clone(),
+                                guard: t.guard.clone(),
                             })
                             .collect(),
                         parent: e.parent.clone(),
@@ -295,7 +309,10 @@ impl AnalysisBundle {
                             .collect(),
                     });
                 }
-                InterchangeConstruct::Source(_) | InterchangeConstruct::TypeDecl(_) => {}
+                InterchangeConstruct::Source(_)
+                | InterchangeConstruct::Test(_)
+                | InterchangeConstruct::Assert(_)
+                | InterchangeConstruct::TypeDecl(_) => {}
             }
         }
 
@@ -365,6 +382,20 @@ mod tests {
         })
     }
 
+    #[test]
+    fn analysis_error_codes_are_catalogued() {
+        let errors = [
+            AnalysisError::InvalidBundle(String::new()),
+            AnalysisError::MissingField {
+                construct: String::new(),
+                field: String::new(),
+            },
+        ];
+        for e in &errors {
+            assert!(tenor_core::error_codes::lookup(e.code()).is_some());
+        }
+    }
+
     #[test]
     fn test_parse_entity() {
         let bundle = make_bundle(vec![json!({
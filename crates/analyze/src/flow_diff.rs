@@ -0,0 +1,335 @@
+//! Semantic flow diff -- compares flows across two contract versions by
+//! their S6 execution paths rather than by step JSON.
+//!
+//! Two versions of a flow are *path-equivalent* when S6 enumerates the
+//! same multiset of paths for both, where each step is identified by its
+//! content (kind, operation, persona, conditions, handlers) with step ids
+//! abstracted away, and each path ends in the same terminal outcome.
+//! Renaming steps, reordering the step list, or dropping unreachable
+//! steps therefore leaves a flow equivalent; changing what any reachable
+//! step does, or where it leads, does not.
+
+use crate::bundle::{AnalysisBundle, AnalysisError, AnalysisFlow};
+use crate::s5_verdicts::S5Result;
+use crate::s6_flow_paths::{analyze_flow_paths, FlowPath};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Placeholder substituted for step-id references when fingerprinting.
+const STEP_REF: &str = "#step";
+
+/// Path-level comparison of one flow present in both versions.
+#[derive(Debug, Clone, Serialize)]
+pub struct FlowPathDiff {
+    pub flow_id: String,
+    /// Same paths and terminal outcomes in both versions.
+    pub equivalent: bool,
+    /// Old step id -> new step id, for steps that were only renamed.
+    /// Populated when `equivalent` is true.
+    pub renamed_steps: BTreeMap<String, String>,
+    /// Terminal outcomes reachable only in the new version.
+    pub outcomes_added: BTreeSet<String>,
+    /// Terminal outcomes no longer reachable in the new version.
+    pub outcomes_removed: BTreeSet<String>,
+    pub paths_before: usize,
+    pub paths_after: usize,
+    /// S6 hit its path or depth limit on either side, so equivalence
+    /// could not be established.
+    pub truncated: bool,
+}
+
+/// Compare every flow declared in both bundles.
+///
+/// Flows that only exist on one side are left to the structural diff.
+pub fn diff_flow_paths(
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+) -> Result<Vec<FlowPathDiff>, AnalysisError> {
+    let before = AnalysisBundle::from_interchange(before)?;
+    let after = AnalysisBundle::from_interchange(after)?;
+    let after_flows: BTreeMap<&str, &AnalysisFlow> =
+        after.flows.iter().map(|f| (f.id.as_str(), f)).collect();
+
+    let mut diffs: Vec<FlowPathDiff> = before
+        .flows
+        .iter()
+        .filter_map(|b| after_flows.get(b.id.as_str()).map(|a| compare_flows(b, a)))
+        .collect();
+    diffs.sort_by(|a, b| a.flow_id.cmp(&b.flow_id));
+    Ok(diffs)
+}
+
+/// A path reduced to step fingerprints plus its terminal outcome, with the
+/// step ids it visited kept alongside for rename reporting.
+struct PathSignature {
+    key: (Vec<String>, Option<String>),
+    step_ids: Vec<String>,
+}
+
+fn compare_flows(before: &AnalysisFlow, after: &AnalysisFlow) -> FlowPathDiff {
+    let (before_paths, before_truncated) = flow_paths(before);
+    let (after_paths, after_truncated) = flow_paths(after);
+    let truncated = before_truncated || after_truncated;
+
+    let mut before_sigs = signatures(before, &before_paths);
+    let mut after_sigs = signatures(after, &after_paths);
+    before_sigs.sort_by(|a, b| a.key.cmp(&b.key));
+    after_sigs.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let outcomes = |sigs: &[PathSignature]| -> BTreeSet<String> {
+        sigs.iter().filter_map(|s| s.key.1.clone()).collect()
+    };
+    let before_outcomes = outcomes(&before_sigs);
+    let after_outcomes = outcomes(&after_sigs);
+
+    let same_paths = before_sigs.len() == after_sigs.len()
+        && before_sigs
+            .iter()
+            .zip(&after_sigs)
+            .all(|(b, a)| b.key == a.key);
+    let equivalent = same_paths && !truncated;
+
+    let mut renamed_steps = BTreeMap::new();
+    if equivalent {
+        for (b, a) in before_sigs.iter().zip(&after_sigs) {
+            for (old, new) in b.step_ids.iter().zip(&a.step_ids) {
+                if old != new {
+                    renamed_steps.insert(old.clone(), new.clone());
+                }
+            }
+        }
+    }
+
+    FlowPathDiff {
+        flow_id: before.id.clone(),
+        equivalent,
+        renamed_steps,
+        outcomes_added: after_outcomes
+            .difference(&before_outcomes)
+            .cloned()
+            .collect(),
+        outcomes_removed: before_outcomes
+            .difference(&after_outcomes)
+            .cloned()
+            .collect(),
+        paths_before: before_sigs.len(),
+        paths_after: after_sigs.len(),
+        truncated,
+    }
+}
+
+/// Run S6 on a single flow. Step weights are metadata, so they are
+/// dropped first: they must not add or remove enumerated failure paths.
+fn flow_paths(flow: &AnalysisFlow) -> (Vec<FlowPath>, bool) {
+    let mut flow = flow.clone();
+    for step in &mut flow.steps {
+        if let Some(step) = step.as_object_mut() {
+            step.remove("weights");
+        }
+    }
+    let bundle = AnalysisBundle {
+        entities: vec![],
+        facts: vec![],
+        rules: vec![],
+        operations: vec![],
+        flows: vec![flow.clone()],
+        personas: vec![],
+        systems: vec![],
+    };
+    // S6 does not consult S5 when enumerating paths.
+    let s5 = S5Result {
+        verdict_types: vec![],
+        operation_outcomes: BTreeMap::new(),
+        total_verdict_types: 0,
+        total_operations_with_outcomes: 0,
+    };
+    let mut s6 = analyze_flow_paths(&bundle, &s5);
+    match s6.flows.remove(&flow.id) {
+        Some(result) => (result.paths, result.truncated),
+        None => (Vec::new(), false),
+    }
+}
+
+fn signatures(flow: &AnalysisFlow, paths: &[FlowPath]) -> Vec<PathSignature> {
+    let step_ids: BTreeSet<&str> = flow
+        .steps
+        .iter()
+        .filter_map(|s| s.get("id").and_then(|i| i.as_str()))
+        .collect();
+    let fingerprints: BTreeMap<&str, String> = flow
+        .steps
+        .iter()
+        .filter_map(|s| {
+            let id = s.get("id").and_then(|i| i.as_str())?;
+            Some((id, fingerprint(s, &step_ids)))
+        })
+        .collect();
+
+    paths
+        .iter()
+        .map(|path| {
+            let steps = path
+                .steps
+                .iter()
+                .map(|s| {
+                    format!(
+                        "{}[{}]",
+                        fingerprints
+                            .get(s.step_id.as_str())
+                            .map(String::as_str)
+                            .unwrap_or(STEP_REF),
+                        s.outcome.as_deref().unwrap_or("")
+                    )
+                })
+                .collect();
+            PathSignature {
+                key: (steps, path.terminal_outcome.clone()),
+                step_ids: path.steps.iter().map(|s| s.step_id.clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Keys whose string values name the step to continue with.
+const STEP_REF_KEYS: &[&str] = &["next", "if_true", "if_false", "on_success"];
+
+/// Canonical JSON of a step with step-id references, provenance and
+/// weights removed.
+///
+/// Only successor positions are abstracted -- an `op` that happens to
+/// share its name with a step id is still part of the fingerprint.
+fn fingerprint(step: &serde_json::Value, step_ids: &BTreeSet<&str>) -> String {
+    fn strip(v: &serde_json::Value, is_ref: bool, step_ids: &BTreeSet<&str>) -> serde_json::Value {
+        match v {
+            serde_json::Value::String(s) if is_ref && step_ids.contains(s.as_str()) => {
+                serde_json::Value::String(STEP_REF.to_string())
+            }
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(|i| strip(i, false, step_ids)).collect())
+            }
+            serde_json::Value::Object(map) => serde_json::Value::Object(
+                map.iter()
+                    .filter(|(k, _)| !matches!(k.as_str(), "id" | "provenance" | "weights"))
+                    .map(|(k, v)| {
+                        let value = if k == "outcomes" {
+                            // outcome label -> successor
+                            match v.as_object() {
+                                Some(outcomes) => serde_json::Value::Object(
+                                    outcomes
+                                        .iter()
+                                        .map(|(label, t)| (label.clone(), strip(t, true, step_ids)))
+                                        .collect(),
+                                ),
+                                None => strip(v, false, step_ids),
+                            }
+                        } else {
+                            strip(v, STEP_REF_KEYS.contains(&k.as_str()), step_ids)
+                        };
+                        (k.clone(), value)
+                    })
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+    strip(step, false, step_ids).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle(entry: &str, steps: serde_json::Value) -> serde_json::Value {
+        json!({
+            "id": "c",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": tenor_core::TENOR_BUNDLE_VERSION,
+            "constructs": [{
+                "id": "f",
+                "kind": "Flow",
+                "tenor": "1.0",
+                "entry": entry,
+                "snapshot": "at_initiation",
+                "steps": steps,
+                "provenance": { "file": "c.tenor", "line": 1 },
+            }],
+        })
+    }
+
+    fn two_step(first: &str, second: &str, outcome: &str) -> serde_json::Value {
+        bundle(
+            first,
+            json!([
+                {"id": first, "kind": "OperationStep", "op": "submit", "persona": "clerk",
+                 "outcomes": {"success": second},
+                 "on_failure": {"kind": "Terminate", "outcome": "failed"}},
+                {"id": second, "kind": "OperationStep", "op": "approve", "persona": "manager",
+                 "outcomes": {"success": {"kind": "Terminal", "outcome": outcome}},
+                 "on_failure": {"kind": "Terminate", "outcome": "failed"}},
+            ]),
+        )
+    }
+
+    #[test]
+    fn renamed_steps_are_equivalent() {
+        let diffs = diff_flow_paths(
+            &two_step("submit", "approve", "done"),
+            &two_step("step_submit", "step_approve", "done"),
+        )
+        .unwrap();
+        assert_eq!(diffs.len(), 1);
+        let d = &diffs[0];
+        assert!(d.equivalent, "{:?}", d);
+        assert_eq!(d.renamed_steps["submit"], "step_submit");
+        assert_eq!(d.renamed_steps["approve"], "step_approve");
+    }
+
+    #[test]
+    fn changed_terminal_outcome_is_not_equivalent() {
+        let diffs = diff_flow_paths(
+            &two_step("submit", "approve", "done"),
+            &two_step("submit", "approve", "finished"),
+        )
+        .unwrap();
+        let d = &diffs[0];
+        assert!(!d.equivalent);
+        assert!(d.outcomes_removed.contains("done"));
+        assert!(d.outcomes_added.contains("finished"));
+        assert!(d.renamed_steps.is_empty());
+    }
+
+    #[test]
+    fn changed_step_content_is_not_equivalent() {
+        let before = two_step("submit", "approve", "done");
+        let mut after = before.clone();
+        after["constructs"][0]["steps"][1]["persona"] = json!("director");
+        let diffs = diff_flow_paths(&before, &after).unwrap();
+        assert!(!diffs[0].equivalent);
+        assert!(diffs[0].outcomes_added.is_empty());
+        assert!(diffs[0].outcomes_removed.is_empty());
+    }
+
+    #[test]
+    fn unreachable_steps_do_not_matter() {
+        let before = two_step("submit", "approve", "done");
+        let mut after = before.clone();
+        after["constructs"][0]["steps"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({"id": "orphan", "kind": "HandoffStep",
+                         "from_persona": "clerk", "to_persona": "manager", "next": "approve"}));
+        assert!(diff_flow_paths(&before, &after).unwrap()[0].equivalent);
+    }
+
+    #[test]
+    fn weight_changes_do_not_matter() {
+        let before = two_step("submit", "approve", "done");
+        let mut after = before.clone();
+        after["constructs"][0]["steps"][0]["weights"] = json!({ "failure": 0.3 });
+        let d = &diff_flow_paths(&before, &after).unwrap()[0];
+        assert!(d.equivalent, "{:?}", d);
+        assert_eq!(d.paths_after, d.paths_before);
+    }
+}
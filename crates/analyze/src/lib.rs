@@ -1,4 +1,5 @@
-//! Tenor static analyzer -- S1-S8 analysis suite with structured output.
+//! Tenor static analyzer -- S1-S8 analysis suite with structured output,
+//! plus S12 temporal property checking over flow paths.
 //!
 //! The analyzer consumes interchange JSON (same pattern as tenor-eval),
 //! not the raw DSL AST. Each analysis is a separate module producing
@@ -8,7 +9,9 @@
 //! Implementation: Phase 4.
 
 pub mod bundle;
+pub mod flow_diff;
 pub mod report;
+pub mod s12_temporal;
 pub mod s1_state_space;
 pub mod s2_reachability;
 pub mod s3a_admissibility;
@@ -17,29 +20,53 @@ pub mod s5_verdicts;
 pub mod s6_flow_paths;
 pub mod s7_complexity;
 pub mod s8_verdict_uniqueness;
+pub mod system;
 
 pub use bundle::{
     AnalysisBundle, AnalysisError, AnalysisSystem, FlowTrigger, SharedEntity, SharedPersona,
     SystemMember,
 };
+pub use flow_diff::{diff_flow_paths, FlowPathDiff};
 pub use report::{AnalysisReport, Finding, FindingSeverity};
-pub use s1_state_space::{S1Result, StateSpaceResult};
+pub use s12_temporal::{
+    parse_formula, parse_properties, Counterexample, PathEvent, PropertyResult, PropertyStatus,
+    S12Result, TemporalFormula, TemporalProperty,
+};
+pub use s1_state_space::{
+    ProductContribution, ProductStateSpace, S1Result, StateSpaceConfig, StateSpaceResult,
+};
 pub use s2_reachability::{ReachabilityResult, S2Result};
 pub use s3a_admissibility::{AdmissibilityKey, S3aResult};
-pub use s4_authority::{AuthorityMap, CrossContractAuthority, S4Result, TransitionAuthority};
+pub use s4_authority::{
+    admissibility_matrix, AdmissibilityCell, AdmissibilityMatrix, AuthorityMap,
+    CrossContractAuthority, S4Result, TransitionAuthority,
+};
 pub use s5_verdicts::{S5Result, VerdictTypeInfo};
 pub use s6_flow_paths::{CrossContractFlowPath, FlowPathConfig, FlowPathResult, S6Result};
 pub use s7_complexity::{FlowDepthBound, PredicateComplexity, S7Result};
 pub use s8_verdict_uniqueness::S8Result;
+pub use system::{
+    analyze_system, AuthorityClaim, SharedTransitionAuthority, SystemAnalysis, SystemConflict,
+    TriggerPath,
+};
 
 /// Run the full S1-S8 analysis suite on an interchange JSON bundle.
 ///
 /// Deserializes the bundle, runs all analyses in dependency order,
 /// extracts findings, and returns the aggregated report.
 pub fn analyze(bundle: &serde_json::Value) -> Result<AnalysisReport, AnalysisError> {
+    analyze_with_config(bundle, &StateSpaceConfig::default())
+}
+
+/// Run the full analysis suite with a caller-supplied S1 product state
+/// space limit.
+pub fn analyze_with_config(
+    bundle: &serde_json::Value,
+    state_space: &StateSpaceConfig,
+) -> Result<AnalysisReport, AnalysisError> {
     let analysis_bundle = AnalysisBundle::from_interchange(bundle)?;
 
-    let s1 = s1_state_space::analyze_state_space(&analysis_bundle);
+    let s1 = s1_state_space::analyze_state_space_with_config(&analysis_bundle, state_space);
     let s2 = s2_reachability::analyze_reachability(&analysis_bundle);
     let s3a = s3a_admissibility::analyze_admissibility(&analysis_bundle);
     let s4 = s4_authority::analyze_authority(&analysis_bundle, &s3a);
@@ -73,6 +100,32 @@ pub fn analyze(bundle: &serde_json::Value) -> Result<AnalysisReport, AnalysisErr
     Ok(report)
 }
 
+/// Build the dense persona x operation x entity-state admissibility matrix
+/// (S3a, arranged for authorization tables) for an interchange JSON bundle.
+pub fn export_admissibility_matrix(
+    bundle: &serde_json::Value,
+) -> Result<AdmissibilityMatrix, AnalysisError> {
+    let analysis_bundle = AnalysisBundle::from_interchange(bundle)?;
+    let s3a = s3a_admissibility::analyze_admissibility(&analysis_bundle);
+    Ok(s4_authority::admissibility_matrix(&analysis_bundle, &s3a))
+}
+
+/// Check temporal properties (S12) against the flow paths of an
+/// interchange JSON bundle.
+pub fn check_temporal_properties(
+    bundle: &serde_json::Value,
+    properties: &[TemporalProperty],
+) -> Result<S12Result, AnalysisError> {
+    let analysis_bundle = AnalysisBundle::from_interchange(bundle)?;
+    let s5 = s5_verdicts::analyze_verdict_space(&analysis_bundle);
+    let s6 = s6_flow_paths::analyze_flow_paths(&analysis_bundle, &s5);
+    Ok(s12_temporal::check_temporal_properties(
+        &analysis_bundle,
+        &s6,
+        properties,
+    ))
+}
+
 /// Run selected analyses on an interchange JSON bundle.
 ///
 /// Only runs the requested analyses (and their dependencies).
@@ -80,6 +133,16 @@ pub fn analyze(bundle: &serde_json::Value) -> Result<AnalysisReport, AnalysisErr
 pub fn analyze_selected(
     bundle: &serde_json::Value,
     analyses: &[&str],
+) -> Result<AnalysisReport, AnalysisError> {
+    analyze_selected_with_config(bundle, analyses, &StateSpaceConfig::default())
+}
+
+/// Run selected analyses with a caller-supplied S1 product state space
+/// limit.
+pub fn analyze_selected_with_config(
+    bundle: &serde_json::Value,
+    analyses: &[&str],
+    state_space: &StateSpaceConfig,
 ) -> Result<AnalysisReport, AnalysisError> {
     let analysis_bundle = AnalysisBundle::from_interchange(bundle)?;
 
@@ -100,7 +163,7 @@ pub fn analyze_selected(
 
     // Run analyses in dependency order, only if needed
     let s1 = if needed.contains("s1") {
-        let result = s1_state_space::analyze_state_space(&analysis_bundle);
+        let result = s1_state_space::analyze_state_space_with_config(&analysis_bundle, state_space);
         report.analyses_run.push("s1".to_string());
         Some(result)
     } else {
@@ -292,6 +355,23 @@ mod tests {
         assert!(report.analyses_run.contains(&"s7".to_string()));
     }
 
+    #[test]
+    fn test_analyze_with_config_flags_product_state_space() {
+        let bundle = make_test_bundle();
+        let config = StateSpaceConfig { product_limit: 2 };
+        let report = analyze_selected_with_config(&bundle, &["s1"], &config).unwrap();
+
+        let s1 = report.s1_state_space.as_ref().unwrap();
+        assert_eq!(s1.product.combined_states, 3);
+        assert!(s1.product.exceeds_limit);
+        let finding = report.findings.iter().find(|f| f.analysis == "s1").unwrap();
+        assert_eq!(finding.severity, FindingSeverity::Warning);
+        assert!(finding.message.contains("Order (3 states)"));
+
+        let report = analyze(&bundle).unwrap();
+        assert!(report.findings.iter().all(|f| f.analysis != "s1"));
+    }
+
     #[test]
     fn test_analyze_report_serializable() {
         let bundle = make_test_bundle();
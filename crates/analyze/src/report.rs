@@ -1,10 +1,13 @@
-//! AnalysisReport — aggregated output from all S1-S8 analyses.
+//! AnalysisReport — aggregated output from the S1-S8 analyses and, when
+//! properties are given, S12.
 //!
 //! The report collects results from each analysis module and extracts
 //! notable findings (warnings, info) for summary display.
 //!
 //! Spec reference: Section 15.
 
+use crate::bundle::AnalysisError;
+use crate::s12_temporal::{PropertyStatus, S12Result};
 use crate::s1_state_space::S1Result;
 use crate::s2_reachability::S2Result;
 use crate::s3a_admissibility::S3aResult;
@@ -14,6 +17,7 @@ use crate::s6_flow_paths::S6Result;
 use crate::s7_complexity::S7Result;
 use crate::s8_verdict_uniqueness::S8Result;
 use serde::Serialize;
+use std::collections::BTreeSet;
 
 /// Severity level for an analysis finding.
 #[derive(Debug, Clone, Serialize, PartialEq)]
@@ -32,6 +36,23 @@ pub struct Finding {
     pub details: Option<serde_json::Value>,
 }
 
+impl Finding {
+    /// The construct this finding is about, as `(kind, id)`: the entity for
+    /// entity findings, the flow for flow findings, the persona for
+    /// authority findings. `None` for contract-wide findings.
+    pub fn subject(&self) -> Option<(&'static str, &str)> {
+        if let Some(ref entity_id) = self.entity_id {
+            return Some(("Entity", entity_id));
+        }
+        let details = self.details.as_ref()?;
+        let field = |pointer: &str| details.pointer(pointer).and_then(|v| v.as_str());
+        if let Some(flow_id) = field("/flow_id").or_else(|| field("/counterexample/flow_id")) {
+            return Some(("Flow", flow_id));
+        }
+        field("/persona_id").map(|persona_id| ("Persona", persona_id))
+    }
+}
+
 /// Aggregated analysis report containing all S1-S8 results and findings.
 #[derive(Debug, Clone, Serialize)]
 pub struct AnalysisReport {
@@ -43,6 +64,7 @@ pub struct AnalysisReport {
     pub s6_flow_paths: Option<S6Result>,
     pub s7_complexity: Option<S7Result>,
     pub s8_verdict_uniqueness: Option<S8Result>,
+    pub s12_temporal: Option<S12Result>,
     pub analyses_run: Vec<String>,
     pub findings: Vec<Finding>,
 }
@@ -59,6 +81,7 @@ impl AnalysisReport {
             s6_flow_paths: None,
             s7_complexity: None,
             s8_verdict_uniqueness: None,
+            s12_temporal: None,
             analyses_run: Vec::new(),
             findings: Vec::new(),
         }
@@ -68,6 +91,37 @@ impl AnalysisReport {
     pub fn extract_findings(&mut self) {
         self.findings.clear();
 
+        // S1: Product state space explosion warning
+        if let Some(ref s1) = self.s1_state_space {
+            let product = &s1.product;
+            if product.exceeds_limit {
+                let dominant: Vec<String> = product
+                    .dominant_entities
+                    .iter()
+                    .take(3)
+                    .map(|c| format!("{} ({} states)", c.entity_id, c.state_count))
+                    .collect();
+                self.findings.push(Finding {
+                    analysis: "s1".to_string(),
+                    severity: FindingSeverity::Warning,
+                    message: format!(
+                        "Combined state space of {} entities is ~10^{:.1} states (limit {}); dominated by {}",
+                        s1.entities.len(),
+                        product.log10_combined_states,
+                        product.limit,
+                        dominant.join(", ")
+                    ),
+                    entity_id: None,
+                    details: Some(serde_json::json!({
+                        "combined_states": product.combined_states,
+                        "log10_combined_states": product.log10_combined_states,
+                        "limit": product.limit,
+                        "dominant_entities": product.dominant_entities,
+                    })),
+                });
+            }
+        }
+
         // S2: Dead state warnings
         if let Some(ref s2) = self.s2_reachability {
             if s2.has_dead_states {
@@ -132,6 +186,35 @@ impl AnalysisReport {
                         })),
                     });
                 }
+
+                // Weighted flows: hottest outcome and expected path length
+                if let Some(ref outcome) = flow_result.most_likely_outcome {
+                    let probability = flow_result.outcome_probabilities[outcome];
+                    let expected_depth = self
+                        .s7_complexity
+                        .as_ref()
+                        .and_then(|s7| s7.flow_depth_bounds.get(flow_id))
+                        .and_then(|bound| bound.expected_path_depth);
+                    let mut message = format!(
+                        "Flow '{}' most likely ends in '{}' (p={:.2})",
+                        flow_id, outcome, probability
+                    );
+                    if let Some(depth) = expected_depth {
+                        message.push_str(&format!("; expected path length {:.1} steps", depth));
+                    }
+                    self.findings.push(Finding {
+                        analysis: "s6".to_string(),
+                        severity: FindingSeverity::Info,
+                        message,
+                        entity_id: None,
+                        details: Some(serde_json::json!({
+                            "flow_id": flow_id,
+                            "most_likely_outcome": outcome,
+                            "outcome_probabilities": flow_result.outcome_probabilities,
+                            "expected_path_depth": expected_depth,
+                        })),
+                    });
+                }
             }
         }
 
@@ -234,6 +317,30 @@ impl AnalysisReport {
             }
         }
 
+        // S12: Violated temporal properties
+        if let Some(ref s12) = self.s12_temporal {
+            for property in &s12.properties {
+                if let (PropertyStatus::Violated, Some(cex)) =
+                    (&property.status, &property.counterexample)
+                {
+                    let trace: Vec<&str> = cex.steps.iter().map(|s| s.step_id.as_str()).collect();
+                    self.findings.push(Finding {
+                        analysis: "s12".to_string(),
+                        severity: FindingSeverity::Warning,
+                        message: format!(
+                            "Property '{}' violated in flow '{}': {} -> {}",
+                            property.name,
+                            cex.flow_id,
+                            trace.join(" -> "),
+                            cex.terminal_outcome.as_deref().unwrap_or("(no outcome)")
+                        ),
+                        entity_id: None,
+                        details: Some(serde_json::json!({ "counterexample": cex })),
+                    });
+                }
+            }
+        }
+
         // Sort findings for deterministic output
         self.findings.sort_by(|a, b| {
             a.analysis
@@ -244,6 +351,27 @@ impl AnalysisReport {
     }
 }
 
+impl AnalysisReport {
+    /// Keep only the findings about constructs that `bundle` annotates with
+    /// `@tag(tag)`. Contract-wide findings are dropped.
+    pub fn retain_tagged_findings(
+        &mut self,
+        bundle: &serde_json::Value,
+        tag: &str,
+    ) -> Result<(), AnalysisError> {
+        let parsed = tenor_interchange::from_interchange(bundle)?;
+        let tagged: BTreeSet<(&str, &str)> = parsed
+            .constructs
+            .iter()
+            .filter(|c| c.annotations().has_tag(tag))
+            .map(|c| (c.kind(), c.id()))
+            .collect();
+        self.findings
+            .retain(|f| f.subject().is_some_and(|subject| tagged.contains(&subject)));
+        Ok(())
+    }
+}
+
 impl Default for AnalysisReport {
     fn default() -> Self {
         Self::new()
@@ -317,6 +445,38 @@ mod tests {
         assert!(report.findings.is_empty());
     }
 
+    #[test]
+    fn test_retain_tagged_findings() {
+        let finding = |entity_id: Option<&str>, details: Option<serde_json::Value>| Finding {
+            analysis: "s2".to_string(),
+            severity: FindingSeverity::Warning,
+            message: String::new(),
+            entity_id: entity_id.map(str::to_string),
+            details,
+        };
+        let mut report = AnalysisReport::new();
+        report.findings = vec![
+            finding(Some("Payment"), None),
+            finding(Some("Order"), None),
+            finding(None, Some(serde_json::json!({ "flow_id": "Payment" }))),
+            finding(None, None),
+        ];
+        let bundle = serde_json::json!({
+            "id": "c", "kind": "Bundle", "tenor": "1.0",
+            "constructs": [
+                { "annotations": { "tags": ["payments"] }, "id": "Payment",
+                  "initial": "a", "kind": "Entity", "states": ["a"], "tenor": "1.0",
+                  "transitions": [] },
+                { "id": "Order", "initial": "a", "kind": "Entity", "states": ["a"],
+                  "tenor": "1.0", "transitions": [] }
+            ]
+        });
+
+        report.retain_tagged_findings(&bundle, "payments").unwrap();
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].subject(), Some(("Entity", "Payment")));
+    }
+
     #[test]
     fn test_extract_findings_dead_states() {
         let mut entities = BTreeMap::new();
@@ -334,6 +494,7 @@ mod tests {
                     s.insert("archived".to_string());
                     s
                 },
+                guarded_states: BTreeSet::new(),
                 initial_state: "draft".to_string(),
             },
         );
@@ -366,6 +527,7 @@ mod tests {
                     s
                 },
                 unreachable_states: BTreeSet::new(),
+                guarded_states: BTreeSet::new(),
                 initial_state: "draft".to_string(),
             },
         );
@@ -0,0 +1,562 @@
+//! S12 — Bounded temporal property checking over flow paths.
+//!
+//! Checks simple temporal properties against every path S6 enumerates and
+//! reports a counterexample path for each property that fails. Properties
+//! are written in a small DSL, one per string:
+//!
+//! ```text
+//! never operation cancel_order
+//! operation capture_payment never before verdict kyc_passed
+//! reaching refunded passes through handoff manager
+//! ```
+//!
+//! Events name what happens at a step: `operation <id>` (the step runs the
+//! operation), `step <id>`, `persona <id>` (a step acts as the persona),
+//! `handoff <persona>` (control is handed to the persona) and
+//! `verdict <id>` (the path has established the verdict: a branch took the
+//! side requiring it, or an operation step whose precondition requires it
+//! ran). `before` and `passes through` are filler-tolerant: "never
+//! executes before" and "every flow reaching" read the same.
+//!
+//! The check is bounded by S6: when a flow's enumeration was truncated a
+//! property without a counterexample holds only within the bound.
+
+use crate::bundle::{AnalysisBundle, AnalysisFlow};
+use crate::s6_flow_paths::{FlowPath, FlowPathStep, S6Result};
+use serde::Serialize;
+use std::fmt;
+
+/// Something that happens at a step of a flow path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum PathEvent {
+    Operation(String),
+    Step(String),
+    Persona(String),
+    Handoff(String),
+    Verdict(String),
+}
+
+impl fmt::Display for PathEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathEvent::Operation(id) => write!(f, "operation {}", id),
+            PathEvent::Step(id) => write!(f, "step {}", id),
+            PathEvent::Persona(id) => write!(f, "persona {}", id),
+            PathEvent::Handoff(id) => write!(f, "handoff {}", id),
+            PathEvent::Verdict(id) => write!(f, "verdict {}", id),
+        }
+    }
+}
+
+/// The temporal formula of a property.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TemporalFormula {
+    /// `event` occurs on no path.
+    Never { event: PathEvent },
+    /// On every path, `event` does not occur before `before` has.
+    NeverBefore { event: PathEvent, before: PathEvent },
+    /// Every path ending in `outcome` contains `event`.
+    PassesThrough { outcome: String, event: PathEvent },
+}
+
+/// A named temporal property, optionally restricted to one flow.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemporalProperty {
+    pub name: String,
+    /// Flow to check; every flow when absent.
+    pub flow: Option<String>,
+    pub formula: TemporalFormula,
+}
+
+/// Outcome of checking one property.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyStatus {
+    Holds,
+    /// No counterexample among the enumerated paths, but at least one
+    /// checked flow's enumeration was truncated.
+    HoldsWithinBound,
+    Violated,
+}
+
+/// A path on which a property fails.
+#[derive(Debug, Clone, Serialize)]
+pub struct Counterexample {
+    pub flow_id: String,
+    pub steps: Vec<FlowPathStep>,
+    pub terminal_outcome: Option<String>,
+    /// Step at which the property failed; `None` when the failure is the
+    /// path ending without the required event.
+    pub violation_step: Option<String>,
+}
+
+/// Result of checking one property.
+#[derive(Debug, Clone, Serialize)]
+pub struct PropertyResult {
+    pub name: String,
+    pub formula: TemporalFormula,
+    pub status: PropertyStatus,
+    pub paths_checked: usize,
+    pub counterexample: Option<Counterexample>,
+}
+
+/// Aggregated S12 result.
+#[derive(Debug, Clone, Serialize)]
+pub struct S12Result {
+    pub properties: Vec<PropertyResult>,
+    pub violated_count: usize,
+}
+
+/// Parse one property in the S12 DSL.
+pub fn parse_formula(text: &str) -> Result<TemporalFormula, String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut rest: &[&str] = &words;
+    // "every flow reaching ..." / "every path reaching ..."
+    if let ["every", "flow" | "path", tail @ ..] = rest {
+        rest = tail;
+    }
+
+    match rest {
+        ["never", event @ ..] => Ok(TemporalFormula::Never {
+            event: parse_event(event, text)?,
+        }),
+        ["reaching", outcome, "passes", "through", event @ ..] => {
+            Ok(TemporalFormula::PassesThrough {
+                outcome: outcome.to_string(),
+                event: parse_event(event, text)?,
+            })
+        }
+        _ => {
+            let never = rest.iter().position(|w| *w == "never");
+            let before = rest.iter().position(|w| *w == "before");
+            match (never, before) {
+                (Some(n), Some(b)) if n < b && rest[n + 1..b].len() <= 1 => {
+                    Ok(TemporalFormula::NeverBefore {
+                        event: parse_event(&rest[..n], text)?,
+                        before: parse_event(&rest[b + 1..], text)?,
+                    })
+                }
+                _ => Err(format!(
+                    "unrecognized property '{}': expected 'never <event>', '<event> never before <event>' or 'reaching <outcome> passes through <event>'",
+                    text
+                )),
+            }
+        }
+    }
+}
+
+fn parse_event(words: &[&str], property: &str) -> Result<PathEvent, String> {
+    let event = match words {
+        ["operation", id] => PathEvent::Operation(id.to_string()),
+        ["step", id] => PathEvent::Step(id.to_string()),
+        ["persona", id] => PathEvent::Persona(id.to_string()),
+        ["handoff", id] => PathEvent::Handoff(id.to_string()),
+        ["verdict", id] => PathEvent::Verdict(id.to_string()),
+        _ => {
+            return Err(format!(
+                "invalid event '{}' in property '{}': expected operation, step, persona, handoff or verdict followed by an id",
+                words.join(" "),
+                property
+            ))
+        }
+    };
+    Ok(event)
+}
+
+/// Parse a properties document: a JSON array whose entries are either a
+/// DSL string or an object `{ "property": "...", "name": "...", "flow":
+/// "..." }` (`name` and `flow` optional).
+pub fn parse_properties(json: &serde_json::Value) -> Result<Vec<TemporalProperty>, String> {
+    let entries = json
+        .as_array()
+        .ok_or_else(|| "properties must be a JSON array".to_string())?;
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let (text, name, flow) = match entry {
+                serde_json::Value::String(text) => (text.as_str(), None, None),
+                serde_json::Value::Object(obj) => {
+                    let text = obj
+                        .get("property")
+                        .and_then(|p| p.as_str())
+                        .ok_or_else(|| format!("properties[{}] is missing 'property'", i))?;
+                    let name = obj.get("name").and_then(|n| n.as_str());
+                    let flow = obj.get("flow").and_then(|f| f.as_str());
+                    (text, name, flow)
+                }
+                _ => return Err(format!("properties[{}] must be a string or object", i)),
+            };
+            Ok(TemporalProperty {
+                name: name.unwrap_or(text).to_string(),
+                flow: flow.map(str::to_string),
+                formula: parse_formula(text)?,
+            })
+        })
+        .collect()
+}
+
+/// S12 — Check temporal properties over the S6 path enumeration.
+pub fn check_temporal_properties(
+    bundle: &AnalysisBundle,
+    s6: &S6Result,
+    properties: &[TemporalProperty],
+) -> S12Result {
+    let results: Vec<PropertyResult> = properties
+        .iter()
+        .map(|property| check_property(bundle, s6, property))
+        .collect();
+    let violated_count = results
+        .iter()
+        .filter(|r| r.status == PropertyStatus::Violated)
+        .count();
+
+    S12Result {
+        properties: results,
+        violated_count,
+    }
+}
+
+fn check_property(
+    bundle: &AnalysisBundle,
+    s6: &S6Result,
+    property: &TemporalProperty,
+) -> PropertyResult {
+    let mut paths_checked = 0;
+    let mut truncated = false;
+    let mut counterexample = None;
+
+    'flows: for (flow_id, flow_result) in &s6.flows {
+        if property.flow.as_ref().is_some_and(|f| f != flow_id) {
+            continue;
+        }
+        let Some(flow) = bundle.flows.iter().find(|f| f.id == *flow_id) else {
+            continue;
+        };
+        truncated |= flow_result.truncated;
+
+        for path in &flow_result.paths {
+            paths_checked += 1;
+            if let Some(violation_step) = find_violation(bundle, flow, path, &property.formula) {
+                counterexample = Some(Counterexample {
+                    flow_id: flow_id.clone(),
+                    steps: path.steps.clone(),
+                    terminal_outcome: path.terminal_outcome.clone(),
+                    violation_step,
+                });
+                break 'flows;
+            }
+        }
+    }
+
+    let status = match (&counterexample, truncated) {
+        (Some(_), _) => PropertyStatus::Violated,
+        (None, true) => PropertyStatus::HoldsWithinBound,
+        (None, false) => PropertyStatus::Holds,
+    };
+
+    PropertyResult {
+        name: property.name.clone(),
+        formula: property.formula.clone(),
+        status,
+        paths_checked,
+        counterexample,
+    }
+}
+
+/// Where `path` violates `formula`: `Some(Some(step))` at a step,
+/// `Some(None)` by ending, `None` when it satisfies the formula.
+fn find_violation(
+    bundle: &AnalysisBundle,
+    flow: &AnalysisFlow,
+    path: &FlowPath,
+    formula: &TemporalFormula,
+) -> Option<Option<String>> {
+    match formula {
+        TemporalFormula::Never { event } => path
+            .steps
+            .iter()
+            .find(|step| step_events(bundle, flow, step).contains(event))
+            .map(|step| Some(step.step_id.clone())),
+        TemporalFormula::NeverBefore { event, before } => {
+            for step in &path.steps {
+                // Events at a step are in order, so a precondition verdict
+                // is established before the operation it guards runs.
+                for e in step_events(bundle, flow, step) {
+                    if e == *before {
+                        return None;
+                    }
+                    if e == *event {
+                        return Some(Some(step.step_id.clone()));
+                    }
+                }
+            }
+            None
+        }
+        TemporalFormula::PassesThrough { outcome, event } => {
+            if path.terminal_outcome.as_deref() != Some(outcome.as_str()) {
+                return None;
+            }
+            let passes = path
+                .steps
+                .iter()
+                .any(|step| step_events(bundle, flow, step).contains(event));
+            if passes {
+                None
+            } else {
+                Some(None)
+            }
+        }
+    }
+}
+
+/// The events of one path step, in the order they happen.
+fn step_events(
+    bundle: &AnalysisBundle,
+    flow: &AnalysisFlow,
+    step: &FlowPathStep,
+) -> Vec<PathEvent> {
+    let mut events = Vec::new();
+    let step_json = flow
+        .steps
+        .iter()
+        .find(|s| s.get("id").and_then(|i| i.as_str()) == Some(step.step_id.as_str()));
+
+    match step.step_type.as_str() {
+        "operation" => {
+            if let Some(op_id) = &step.operation_id {
+                let precondition = bundle
+                    .operations
+                    .iter()
+                    .find(|o| o.id == *op_id)
+                    .and_then(|o| o.precondition.as_ref());
+                if let Some(precondition) = precondition {
+                    for verdict in required_verdicts(precondition, true) {
+                        events.push(PathEvent::Verdict(verdict));
+                    }
+                }
+            }
+        }
+        "branch" => {
+            let taken = step.outcome.as_deref() == Some("true");
+            if let Some(condition) = step_json.and_then(|s| s.get("condition")) {
+                for verdict in required_verdicts(condition, taken) {
+                    events.push(PathEvent::Verdict(verdict));
+                }
+            }
+        }
+        "handoff" => {
+            if let Some(to) = step_json
+                .and_then(|s| s.get("to_persona"))
+                .and_then(|p| p.as_str())
+            {
+                events.push(PathEvent::Handoff(to.to_string()));
+            }
+        }
+        _ => {}
+    }
+
+    events.push(PathEvent::Step(step.step_id.clone()));
+    if let Some(persona) = &step.persona {
+        events.push(PathEvent::Persona(persona.clone()));
+    }
+    if step.step_type == "operation" {
+        if let Some(op_id) = &step.operation_id {
+            events.push(PathEvent::Operation(op_id.clone()));
+        }
+    }
+    events
+}
+
+/// Verdicts that must be present for `expr` to evaluate to `holds`:
+/// `verdict_present` atoms reached through conjunctions (or, for a false
+/// result, disjunctions) and negations.
+fn required_verdicts(expr: &serde_json::Value, holds: bool) -> Vec<String> {
+    if let Some(id) = expr.get("verdict_present").and_then(|v| v.as_str()) {
+        return if holds { vec![id.to_string()] } else { vec![] };
+    }
+    match expr.get("op").and_then(|o| o.as_str()) {
+        Some("not") => expr
+            .get("operand")
+            .map(|operand| required_verdicts(operand, !holds))
+            .unwrap_or_default(),
+        Some(op @ ("and" | "or")) if (op == "and") == holds => expr
+            .get("operands")
+            .and_then(|o| o.as_array())
+            .into_iter()
+            .flatten()
+            .flat_map(|operand| required_verdicts(operand, holds))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::{AnalysisOperation, AnalysisPersona};
+    use crate::s5_verdicts::analyze_verdict_space;
+    use crate::s6_flow_paths::analyze_flow_paths;
+    use serde_json::json;
+
+    fn operation(id: &str, precondition: Option<serde_json::Value>) -> AnalysisOperation {
+        AnalysisOperation {
+            id: id.to_string(),
+            allowed_personas: vec!["clerk".to_string()],
+            precondition,
+            effects: vec![],
+            outcomes: vec![],
+            error_contract: None,
+        }
+    }
+
+    /// check_risk branches on `low_risk`: true captures the payment, false
+    /// hands off to a manager and refunds.
+    fn make_bundle(capture_precondition: Option<serde_json::Value>) -> AnalysisBundle {
+        AnalysisBundle {
+            entities: vec![],
+            facts: vec![],
+            rules: vec![],
+            operations: vec![
+                operation("capture_payment", capture_precondition),
+                operation("refund_payment", None),
+            ],
+            flows: vec![AnalysisFlow {
+                id: "checkout".to_string(),
+                entry: "check_risk".to_string(),
+                snapshot: "at_initiation".to_string(),
+                steps: vec![
+                    json!({
+                        "id": "check_risk", "kind": "BranchStep", "persona": "clerk",
+                        "condition": {"verdict_present": "low_risk"},
+                        "if_true": "capture",
+                        "if_false": "escalate"
+                    }),
+                    json!({
+                        "id": "capture", "kind": "OperationStep", "op": "capture_payment",
+                        "persona": "clerk",
+                        "outcomes": {"success": {"kind": "Terminal", "outcome": "captured"}}
+                    }),
+                    json!({
+                        "id": "escalate", "kind": "HandoffStep",
+                        "from_persona": "clerk", "to_persona": "manager", "next": "refund"
+                    }),
+                    json!({
+                        "id": "refund", "kind": "OperationStep", "op": "refund_payment",
+                        "persona": "manager",
+                        "outcomes": {"success": {"kind": "Terminal", "outcome": "refunded"}}
+                    }),
+                ],
+            }],
+            personas: vec![AnalysisPersona {
+                id: "clerk".to_string(),
+            }],
+            systems: vec![],
+        }
+    }
+
+    fn check(bundle: &AnalysisBundle, properties: &[&str]) -> S12Result {
+        let s5 = analyze_verdict_space(bundle);
+        let s6 = analyze_flow_paths(bundle, &s5);
+        let properties = parse_properties(&json!(properties)).unwrap();
+        check_temporal_properties(bundle, &s6, &properties)
+    }
+
+    #[test]
+    fn test_parse_formulas() {
+        assert_eq!(
+            parse_formula("operation capture_payment never executes before verdict kyc_passed")
+                .unwrap(),
+            TemporalFormula::NeverBefore {
+                event: PathEvent::Operation("capture_payment".to_string()),
+                before: PathEvent::Verdict("kyc_passed".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_formula("every flow reaching refunded passes through handoff manager").unwrap(),
+            TemporalFormula::PassesThrough {
+                outcome: "refunded".to_string(),
+                event: PathEvent::Handoff("manager".to_string()),
+            }
+        );
+        assert!(parse_formula("never capture_payment")
+            .unwrap_err()
+            .contains("invalid event"));
+        assert!(parse_formula("capture before kyc").is_err());
+    }
+
+    #[test]
+    fn test_branch_establishes_verdict() {
+        let bundle = make_bundle(None);
+        let result = check(
+            &bundle,
+            &[
+                "operation capture_payment never before verdict low_risk",
+                "reaching refunded passes through handoff manager",
+            ],
+        );
+        assert_eq!(result.violated_count, 0);
+        assert_eq!(result.properties[0].status, PropertyStatus::Holds);
+        assert_eq!(result.properties[0].paths_checked, 2);
+    }
+
+    #[test]
+    fn test_precondition_establishes_verdict() {
+        let bundle = make_bundle(Some(json!({
+            "op": "and",
+            "operands": [{"verdict_present": "kyc_passed"}, {"fact_ref": "amount"}]
+        })));
+        let result = check(
+            &bundle,
+            &["operation capture_payment never before verdict kyc_passed"],
+        );
+        assert_eq!(result.properties[0].status, PropertyStatus::Holds);
+    }
+
+    #[test]
+    fn test_violation_reports_counterexample() {
+        let bundle = make_bundle(None);
+        let result = check(
+            &bundle,
+            &[
+                "operation capture_payment never before verdict kyc_passed",
+                "reaching refunded passes through persona clerk_supervisor",
+                "never operation refund_payment",
+            ],
+        );
+        assert_eq!(result.violated_count, 3);
+
+        let cex = result.properties[0].counterexample.as_ref().unwrap();
+        assert_eq!(cex.flow_id, "checkout");
+        assert_eq!(cex.violation_step.as_deref(), Some("capture"));
+        assert_eq!(cex.terminal_outcome.as_deref(), Some("captured"));
+        let steps: Vec<&str> = cex.steps.iter().map(|s| s.step_id.as_str()).collect();
+        assert_eq!(steps, vec!["check_risk", "capture"]);
+
+        let cex = result.properties[1].counterexample.as_ref().unwrap();
+        assert_eq!(cex.violation_step, None);
+        assert_eq!(cex.terminal_outcome.as_deref(), Some("refunded"));
+    }
+
+    #[test]
+    fn test_flow_filter_and_object_entries() {
+        let bundle = make_bundle(None);
+        let s5 = analyze_verdict_space(&bundle);
+        let s6 = analyze_flow_paths(&bundle, &s5);
+        let properties = parse_properties(&json!([
+            {"name": "no_refunds", "flow": "other_flow", "property": "never operation refund_payment"}
+        ]))
+        .unwrap();
+        let result = check_temporal_properties(&bundle, &s6, &properties);
+        assert_eq!(result.properties[0].name, "no_refunds");
+        assert_eq!(result.properties[0].status, PropertyStatus::Holds);
+        assert_eq!(result.properties[0].paths_checked, 0);
+
+        assert!(parse_properties(&json!([{"name": "x"}]))
+            .unwrap_err()
+            .contains("missing 'property'"));
+    }
+}
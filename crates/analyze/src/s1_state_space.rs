@@ -6,6 +6,11 @@
 //! This is a straightforward extraction: each entity's `states` array
 //! is the complete state space. The analysis also captures the initial
 //! state and transition relation for downstream analyses (S2, S3a).
+//!
+//! S1 also sizes the cross-entity product state space -- the number of
+//! combined states an executor tracking every entity at once could see --
+//! and flags it when it exceeds a configurable limit, together with the
+//! entities contributing most of it.
 
 use crate::bundle::AnalysisBundle;
 use serde::Serialize;
@@ -21,11 +26,51 @@ pub struct StateSpaceResult {
     pub state_count: usize,
 }
 
+/// Configuration for the product state space bound.
+pub struct StateSpaceConfig {
+    /// Combined state count above which the product state space is
+    /// reported as a combinatorial explosion.
+    pub product_limit: u64,
+}
+
+impl Default for StateSpaceConfig {
+    fn default() -> Self {
+        Self {
+            product_limit: 1_000_000,
+        }
+    }
+}
+
+/// One entity's contribution to the product state space.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductContribution {
+    pub entity_id: String,
+    pub state_count: usize,
+    /// Fraction of the product's magnitude (its logarithm) this entity
+    /// accounts for.
+    pub share: f64,
+}
+
+/// Size of the cross-entity product state space.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductStateSpace {
+    /// Product of every entity's state count, saturating at `u64::MAX`.
+    pub combined_states: u64,
+    /// Base-10 logarithm of the exact product; meaningful even when
+    /// `combined_states` saturates.
+    pub log10_combined_states: f64,
+    pub limit: u64,
+    pub exceeds_limit: bool,
+    /// Entities with more than one state, largest contribution first.
+    pub dominant_entities: Vec<ProductContribution>,
+}
+
 /// Aggregated S1 result across all entities.
 #[derive(Debug, Clone, Serialize)]
 pub struct S1Result {
     /// Keyed by entity ID for deterministic output ordering.
     pub entities: BTreeMap<String, StateSpaceResult>,
+    pub product: ProductStateSpace,
 }
 
 /// S1 — Enumerate the complete state space for every Entity in the bundle.
@@ -33,6 +78,14 @@ pub struct S1Result {
 /// For each Entity, builds a `StateSpaceResult` containing all declared
 /// states, the initial state, and the transition relation.
 pub fn analyze_state_space(bundle: &AnalysisBundle) -> S1Result {
+    analyze_state_space_with_config(bundle, &StateSpaceConfig::default())
+}
+
+/// S1 with a caller-supplied product state space limit.
+pub fn analyze_state_space_with_config(
+    bundle: &AnalysisBundle,
+    config: &StateSpaceConfig,
+) -> S1Result {
     let mut entities = BTreeMap::new();
 
     for entity in &bundle.entities {
@@ -53,7 +106,43 @@ pub fn analyze_state_space(bundle: &AnalysisBundle) -> S1Result {
         entities.insert(entity.id.clone(), result);
     }
 
-    S1Result { entities }
+    let product = product_state_space(&entities, config.product_limit);
+    S1Result { entities, product }
+}
+
+/// Size the product of the entities' state spaces against `limit`.
+fn product_state_space(
+    entities: &BTreeMap<String, StateSpaceResult>,
+    limit: u64,
+) -> ProductStateSpace {
+    let combined_states = entities
+        .values()
+        .fold(1u64, |acc, e| acc.saturating_mul(e.state_count as u64));
+    let log10_combined_states: f64 = entities
+        .values()
+        .filter(|e| e.state_count > 0)
+        .map(|e| (e.state_count as f64).log10())
+        .sum();
+
+    let mut dominant_entities: Vec<ProductContribution> = entities
+        .values()
+        .filter(|e| e.state_count > 1)
+        .map(|e| ProductContribution {
+            entity_id: e.entity_id.clone(),
+            state_count: e.state_count,
+            share: (e.state_count as f64).log10() / log10_combined_states,
+        })
+        .collect();
+    // Stable sort keeps entity ID order among equal contributions.
+    dominant_entities.sort_by_key(|c| std::cmp::Reverse(c.state_count));
+
+    ProductStateSpace {
+        combined_states,
+        log10_combined_states,
+        limit,
+        exceeds_limit: combined_states > limit,
+        dominant_entities,
+    }
 }
 
 #[cfg(test)]
@@ -87,10 +176,12 @@ mod tests {
                 Transition {
                     from: "draft".to_string(),
                     to: "submitted".to_string(),
+                    guard: None,
                 },
                 Transition {
                     from: "submitted".to_string(),
                     to: "approved".to_string(),
+                    guard: None,
                 },
             ],
             parent: None,
@@ -121,6 +212,7 @@ mod tests {
                 transitions: vec![Transition {
                     from: "draft".to_string(),
                     to: "done".to_string(),
+                    guard: None,
                 }],
                 parent: None,
             },
@@ -136,10 +228,12 @@ mod tests {
                     Transition {
                         from: "pending".to_string(),
                         to: "paid".to_string(),
+                        guard: None,
                     },
                     Transition {
                         from: "paid".to_string(),
                         to: "refunded".to_string(),
+                        guard: None,
                     },
                 ],
                 parent: None,
@@ -177,6 +271,71 @@ mod tests {
         let bundle = make_bundle(vec![]);
         let result = analyze_state_space(&bundle);
         assert!(result.entities.is_empty());
+        assert_eq!(result.product.combined_states, 1);
+        assert!(!result.product.exceeds_limit);
+    }
+
+    fn entity_with_states(id: &str, count: usize) -> AnalysisEntity {
+        let states: Vec<String> = (0..count).map(|i| format!("s{}", i)).collect();
+        AnalysisEntity {
+            id: id.to_string(),
+            initial: states[0].clone(),
+            states,
+            transitions: vec![],
+            parent: None,
+        }
+    }
+
+    #[test]
+    fn test_product_state_space_within_limit() {
+        let bundle = make_bundle(vec![
+            entity_with_states("Order", 4),
+            entity_with_states("Payment", 3),
+            entity_with_states("Flag", 1),
+        ]);
+
+        let product = analyze_state_space(&bundle).product;
+        assert_eq!(product.combined_states, 12);
+        assert!((product.log10_combined_states - 12f64.log10()).abs() < 1e-9);
+        assert!(!product.exceeds_limit);
+        // Single-state entities do not contribute to the product.
+        let ids: Vec<&str> = product
+            .dominant_entities
+            .iter()
+            .map(|c| c.entity_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["Order", "Payment"]);
+    }
+
+    #[test]
+    fn test_product_state_space_exceeds_configured_limit() {
+        let bundle = make_bundle(vec![
+            entity_with_states("Small", 2),
+            entity_with_states("Large", 50),
+            entity_with_states("Medium", 10),
+        ]);
+
+        let config = StateSpaceConfig { product_limit: 500 };
+        let product = analyze_state_space_with_config(&bundle, &config).product;
+        assert_eq!(product.combined_states, 1_000);
+        assert_eq!(product.limit, 500);
+        assert!(product.exceeds_limit);
+        assert_eq!(product.dominant_entities[0].entity_id, "Large");
+        assert!(product.dominant_entities[0].share > 0.5);
+        let total: f64 = product.dominant_entities.iter().map(|c| c.share).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_product_state_space_saturates() {
+        let entities: Vec<AnalysisEntity> = (0..30)
+            .map(|i| entity_with_states(&format!("E{:02}", i), 10))
+            .collect();
+
+        let product = analyze_state_space(&make_bundle(entities)).product;
+        assert_eq!(product.combined_states, u64::MAX);
+        assert!((product.log10_combined_states - 30.0).abs() < 1e-9);
+        assert!(product.exceeds_limit);
     }
 
     #[test]
@@ -213,6 +372,7 @@ mod tests {
             transitions: vec![Transition {
                 from: "open".to_string(),
                 to: "closed".to_string(),
+                guard: None,
             }],
             parent: Some("Order".to_string()),
         }]);
@@ -4,9 +4,14 @@
 //! state via the declared transition relation using BFS. Also reports
 //! dead states (declared but unreachable from initial).
 //!
+//! Guarded transitions whose guard is structurally unsatisfiable (S3a's
+//! type-level check) are never taken. States that can only be reached
+//! through a satisfiable guard are reported as guarded.
+//!
 //! Spec reference: Section 15, S2.
 
-use crate::bundle::AnalysisBundle;
+use crate::bundle::{AnalysisBundle, AnalysisEntity, AnalysisFact};
+use crate::s3a_admissibility::is_structurally_satisfiable;
 use serde::Serialize;
 use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 
@@ -16,6 +21,8 @@ pub struct ReachabilityResult {
     pub entity_id: String,
     pub reachable_states: BTreeSet<String>,
     pub unreachable_states: BTreeSet<String>,
+    /// Reachable states that every path reaches through a guarded transition.
+    pub guarded_states: BTreeSet<String>,
     pub initial_state: String,
 }
 
@@ -38,35 +45,13 @@ pub fn analyze_reachability(bundle: &AnalysisBundle) -> S2Result {
     let mut has_dead_states = false;
 
     for entity in &bundle.entities {
-        // Build adjacency list: from_state -> [to_states]
-        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
-        for t in &entity.transitions {
-            adjacency
-                .entry(t.from.as_str())
-                .or_default()
-                .push(t.to.as_str());
-        }
-
-        // BFS from initial state
-        let mut visited = BTreeSet::new();
-        let mut queue = VecDeque::new();
-
-        visited.insert(entity.initial.clone());
-        queue.push_back(entity.initial.as_str());
-
-        while let Some(state) = queue.pop_front() {
-            if let Some(neighbors) = adjacency.get(state) {
-                for &next in neighbors {
-                    if visited.insert(next.to_string()) {
-                        queue.push_back(next);
-                    }
-                }
-            }
-        }
+        let visited = reachable_from_initial(entity, &bundle.facts, true);
+        let unguarded = reachable_from_initial(entity, &bundle.facts, false);
 
         // Compute unreachable states: declared - reachable
         let declared: BTreeSet<String> = entity.states.iter().cloned().collect();
         let unreachable: BTreeSet<String> = declared.difference(&visited).cloned().collect();
+        let guarded: BTreeSet<String> = visited.difference(&unguarded).cloned().collect();
 
         if !unreachable.is_empty() {
             has_dead_states = true;
@@ -76,6 +61,7 @@ pub fn analyze_reachability(bundle: &AnalysisBundle) -> S2Result {
             entity_id: entity.id.clone(),
             reachable_states: visited,
             unreachable_states: unreachable,
+            guarded_states: guarded,
             initial_state: entity.initial.clone(),
         };
 
@@ -88,10 +74,52 @@ pub fn analyze_reachability(bundle: &AnalysisBundle) -> S2Result {
     }
 }
 
+/// BFS from the entity's initial state. Guarded transitions are followed
+/// only if `follow_guarded` is set and the guard is structurally
+/// satisfiable.
+fn reachable_from_initial(
+    entity: &AnalysisEntity,
+    facts: &[AnalysisFact],
+    follow_guarded: bool,
+) -> BTreeSet<String> {
+    // Build adjacency list: from_state -> [to_states]
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for t in &entity.transitions {
+        if let Some(guard) = &t.guard {
+            if !follow_guarded || !is_structurally_satisfiable(guard, facts) {
+                continue;
+            }
+        }
+        adjacency
+            .entry(t.from.as_str())
+            .or_default()
+            .push(t.to.as_str());
+    }
+
+    // BFS from initial state
+    let mut visited = BTreeSet::new();
+    let mut queue = VecDeque::new();
+
+    visited.insert(entity.initial.clone());
+    queue.push_back(entity.initial.as_str());
+
+    while let Some(state) = queue.pop_front() {
+        if let Some(neighbors) = adjacency.get(state) {
+            for &next in neighbors {
+                if visited.insert(next.to_string()) {
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    visited
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::bundle::{AnalysisEntity, Transition};
+    use serde_json::json;
 
     fn make_bundle(entities: Vec<AnalysisEntity>) -> AnalysisBundle {
         AnalysisBundle {
@@ -119,10 +147,12 @@ mod tests {
                 Transition {
                     from: "draft".to_string(),
                     to: "submitted".to_string(),
+                    guard: None,
                 },
                 Transition {
                     from: "submitted".to_string(),
                     to: "approved".to_string(),
+                    guard: None,
                 },
             ],
             parent: None,
@@ -149,6 +179,7 @@ mod tests {
                 Transition {
                     from: "draft".to_string(),
                     to: "submitted".to_string(),
+                    guard: None,
                 },
                 // No path to "archived" from "draft"
             ],
@@ -199,11 +230,13 @@ mod tests {
                 Transition {
                     from: "a".to_string(),
                     to: "b".to_string(),
+                    guard: None,
                 },
                 // d -> e is a disconnected subgraph, unreachable from a
                 Transition {
                     from: "d".to_string(),
                     to: "e".to_string(),
+                    guard: None,
                 },
             ],
             parent: None,
@@ -229,14 +262,17 @@ mod tests {
                 Transition {
                     from: "a".to_string(),
                     to: "b".to_string(),
+                    guard: None,
                 },
                 Transition {
                     from: "b".to_string(),
                     to: "c".to_string(),
+                    guard: None,
                 },
                 Transition {
                     from: "c".to_string(),
                     to: "a".to_string(),
+                    guard: None,
                 }, // cycle
             ],
             parent: None,
@@ -260,6 +296,7 @@ mod tests {
                 transitions: vec![Transition {
                     from: "a".to_string(),
                     to: "b".to_string(),
+                    guard: None,
                 }],
                 parent: None,
             },
@@ -270,6 +307,7 @@ mod tests {
                 transitions: vec![Transition {
                     from: "x".to_string(),
                     to: "y".to_string(),
+                    guard: None,
                 }],
                 parent: None,
             },
@@ -280,4 +318,50 @@ mod tests {
         assert!(result.entities["Dirty"].unreachable_states.contains("z"));
         assert!(result.has_dead_states);
     }
+
+    #[test]
+    fn test_guarded_transitions() {
+        let mut bundle = make_bundle(vec![AnalysisEntity {
+            id: "Order".to_string(),
+            states: vec![
+                "draft".to_string(),
+                "submitted".to_string(),
+                "rejected".to_string(),
+            ],
+            initial: "draft".to_string(),
+            transitions: vec![
+                Transition {
+                    from: "draft".to_string(),
+                    to: "submitted".to_string(),
+                    guard: Some(json!({"verdict_present": "order_processable"})),
+                },
+                Transition {
+                    from: "draft".to_string(),
+                    to: "rejected".to_string(),
+                    guard: Some(json!({
+                        "left": {"fact_ref": "status"},
+                        "op": "=",
+                        "right": {"literal": "void", "type": {"base": "Enum", "values": ["void"]}}
+                    })),
+                },
+            ],
+            parent: None,
+        }]);
+        bundle.facts.push(AnalysisFact {
+            id: "status".to_string(),
+            fact_type: json!({"base": "Enum", "values": ["open", "closed"]}),
+        });
+
+        let result = analyze_reachability(&bundle);
+        let order = &result.entities["Order"];
+        // The satisfiable guard makes "submitted" reachable, but only guarded.
+        assert!(order.reachable_states.contains("submitted"));
+        assert_eq!(
+            order.guarded_states,
+            BTreeSet::from(["submitted".to_string()])
+        );
+        // The guard on draft -> rejected can never hold.
+        assert!(order.unreachable_states.contains("rejected"));
+        assert!(result.has_dead_states);
+    }
 }
@@ -2,13 +2,14 @@
 //!
 //! For each Entity state and each persona, determines which Operations
 //! are structurally admissible: the persona is authorized, the operation
-//! has an effect transitioning from that state, and the precondition is
-//! structurally satisfiable by type-level analysis.
+//! has an effect transitioning from that state, and the precondition and
+//! the guard on that transition are structurally satisfiable by type-level
+//! analysis.
 //!
 //! Spec reference: Section 15, S3a.
 //! Complexity: O(|expression tree|) per precondition -- always feasible.
 
-use crate::bundle::{AnalysisBundle, AnalysisFact};
+use crate::bundle::{AnalysisBundle, AnalysisEntity, AnalysisFact, Effect};
 use serde::Serialize;
 use std::collections::BTreeMap;
 use std::fmt;
@@ -57,7 +58,9 @@ where
 ///
 /// For each (entity E, state S, persona P) triple, finds Operations where:
 /// 1. P is in operation.allowed_personas
-/// 2. At least one effect has entity_id == E.id AND from_state == S
+/// 2. At least one effect has entity_id == E.id AND from_state == S, and
+///    the guard on the transition it takes (if any) is structurally
+///    satisfiable
 /// 3. The precondition (if present) is structurally satisfiable
 pub fn analyze_admissibility(bundle: &AnalysisBundle) -> S3aResult {
     let mut admissible_operations = BTreeMap::new();
@@ -82,10 +85,12 @@ pub fn analyze_admissibility(bundle: &AnalysisBundle) -> S3aResult {
                     }
 
                     // Check 2: operation has an effect from this state on this entity
-                    let has_matching_effect = operation
-                        .effects
-                        .iter()
-                        .any(|e| e.entity_id == entity.id && e.from_state == *state);
+                    // whose transition guard can hold
+                    let has_matching_effect = operation.effects.iter().any(|e| {
+                        e.entity_id == entity.id
+                            && e.from_state == *state
+                            && guard_satisfiable(entity, e, &bundle.facts)
+                    });
                     if !has_matching_effect {
                         continue;
                     }
@@ -114,6 +119,20 @@ pub fn analyze_admissibility(bundle: &AnalysisBundle) -> S3aResult {
     }
 }
 
+/// Whether the guard on the transition `effect` takes (if any) is
+/// structurally satisfiable.
+fn guard_satisfiable(entity: &AnalysisEntity, effect: &Effect, facts: &[AnalysisFact]) -> bool {
+    let guard = entity
+        .transitions
+        .iter()
+        .find(|t| t.from == effect.from_state && t.to == effect.to_state)
+        .and_then(|t| t.guard.as_ref());
+    match guard {
+        Some(g) => is_structurally_satisfiable(g, facts),
+        None => true,
+    }
+}
+
 /// Check if a predicate expression is structurally satisfiable by type-level analysis.
 ///
 /// Walks the expression tree checking type compatibility:
@@ -125,7 +144,10 @@ pub fn analyze_admissibility(bundle: &AnalysisBundle) -> S3aResult {
 /// - fact_ref: always satisfiable (fact exists)
 ///
 /// Conservative: returns true when uncertain (structural satisfiability only).
-fn is_structurally_satisfiable(expr: &serde_json::Value, facts: &[AnalysisFact]) -> bool {
+pub(crate) fn is_structurally_satisfiable(
+    expr: &serde_json::Value,
+    facts: &[AnalysisFact],
+) -> bool {
     // Dispatch based on expression structure
     if expr.is_null() {
         return true;
@@ -313,6 +335,7 @@ mod tests {
                 transitions: vec![Transition {
                     from: "draft".to_string(),
                     to: "submitted".to_string(),
+                    guard: None,
                 }],
                 parent: None,
             }],
@@ -612,4 +635,54 @@ mod tests {
                 persona_id: "admin".to_string(),
             }));
     }
+
+    #[test]
+    fn test_unsatisfiable_transition_guard_not_admissible() {
+        let bundle = make_bundle_with(
+            vec![AnalysisEntity {
+                id: "Order".to_string(),
+                states: vec!["draft".to_string(), "submitted".to_string()],
+                initial: "draft".to_string(),
+                transitions: vec![Transition {
+                    from: "draft".to_string(),
+                    to: "submitted".to_string(),
+                    guard: Some(json!({
+                        "left": {"fact_ref": "status"},
+                        "op": "=",
+                        "right": {"literal": "approved", "type": {"base": "Enum", "values": ["approved"]}}
+                    })),
+                }],
+                parent: None,
+            }],
+            vec![AnalysisPersona {
+                id: "admin".to_string(),
+            }],
+            vec![AnalysisOperation {
+                id: "submit".to_string(),
+                allowed_personas: vec!["admin".to_string()],
+                precondition: None,
+                effects: vec![Effect {
+                    entity_id: "Order".to_string(),
+                    from_state: "draft".to_string(),
+                    to_state: "submitted".to_string(),
+                    outcome: None,
+                }],
+                outcomes: vec![],
+                error_contract: None,
+            }],
+            vec![AnalysisFact {
+                id: "status".to_string(),
+                fact_type: json!({"base": "Enum", "values": ["pending", "confirmed"]}),
+            }],
+        );
+
+        let result = analyze_admissibility(&bundle);
+        assert!(!result
+            .admissible_operations
+            .contains_key(&AdmissibilityKey {
+                entity_id: "Order".to_string(),
+                state: "draft".to_string(),
+                persona_id: "admin".to_string(),
+            }));
+    }
 }
@@ -4,10 +4,14 @@
 //! Operations P can invoke in S. Also derives whether a persona can
 //! cause a specific state transition S -> S'.
 //!
+//! The admissibility matrix flattens S3a into a dense persona x operation x
+//! entity-state table -- every combination, admissible or not -- for
+//! callers that render authorization tables.
+//!
 //! Spec reference: Section 15, S4.
 
 use crate::bundle::AnalysisBundle;
-use crate::s3a_admissibility::S3aResult;
+use crate::s3a_admissibility::{AdmissibilityKey, S3aResult};
 use serde::Serialize;
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -49,6 +53,98 @@ pub struct S4Result {
     pub cross_contract_authorities: Vec<CrossContractAuthority>,
 }
 
+/// One cell of the admissibility matrix.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdmissibilityCell {
+    pub persona_id: String,
+    pub operation_id: String,
+    pub entity_id: String,
+    pub state: String,
+    /// The persona is in the operation's `allowed_personas`.
+    pub authorized: bool,
+    /// S3a admits the operation for the persona in this entity state.
+    pub admissible: bool,
+}
+
+/// Dense persona x operation x entity-state admissibility matrix.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdmissibilityMatrix {
+    pub personas: Vec<String>,
+    pub operations: Vec<String>,
+    /// One cell per (operation, entity state it could transition from,
+    /// persona), in bundle order.
+    pub cells: Vec<AdmissibilityCell>,
+}
+
+impl AdmissibilityMatrix {
+    /// Render as CSV with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("persona,operation,entity,state,authorized,admissible\n");
+        for cell in &self.cells {
+            out.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                cell.persona_id,
+                cell.operation_id,
+                cell.entity_id,
+                cell.state,
+                cell.authorized,
+                cell.admissible
+            ));
+        }
+        out
+    }
+}
+
+/// Build the admissibility matrix from S3a results.
+///
+/// Each operation is paired with every state of every entity it has an
+/// effect on, and each pair with every declared persona.
+pub fn admissibility_matrix(bundle: &AnalysisBundle, s3a: &S3aResult) -> AdmissibilityMatrix {
+    let mut cells = Vec::new();
+
+    for operation in &bundle.operations {
+        let mut entity_ids: Vec<&str> = Vec::new();
+        for effect in &operation.effects {
+            if !entity_ids.contains(&effect.entity_id.as_str()) {
+                entity_ids.push(&effect.entity_id);
+            }
+        }
+
+        for entity in entity_ids
+            .iter()
+            .filter_map(|id| bundle.entities.iter().find(|e| e.id == *id))
+        {
+            for state in &entity.states {
+                for persona in &bundle.personas {
+                    let key = AdmissibilityKey {
+                        entity_id: entity.id.clone(),
+                        state: state.clone(),
+                        persona_id: persona.id.clone(),
+                    };
+                    let admissible = s3a
+                        .admissible_operations
+                        .get(&key)
+                        .is_some_and(|ops| ops.contains(&operation.id));
+                    cells.push(AdmissibilityCell {
+                        persona_id: persona.id.clone(),
+                        operation_id: operation.id.clone(),
+                        entity_id: entity.id.clone(),
+                        state: state.clone(),
+                        authorized: operation.allowed_personas.contains(&persona.id),
+                        admissible,
+                    });
+                }
+            }
+        }
+    }
+
+    AdmissibilityMatrix {
+        personas: bundle.personas.iter().map(|p| p.id.clone()).collect(),
+        operations: bundle.operations.iter().map(|o| o.id.clone()).collect(),
+        cells,
+    }
+}
+
 /// S4 — Derive the authority topology from S3a results.
 ///
 /// Reorganizes S3a's (entity, state, persona) -> [ops] into
@@ -220,6 +316,7 @@ mod tests {
                 transitions: vec![Transition {
                     from: "draft".to_string(),
                     to: "submitted".to_string(),
+                    guard: None,
                 }],
                 parent: None,
             }],
@@ -263,6 +360,7 @@ mod tests {
                 transitions: vec![Transition {
                     from: "draft".to_string(),
                     to: "submitted".to_string(),
+                    guard: None,
                 }],
                 parent: None,
             }],
@@ -376,4 +474,69 @@ mod tests {
         assert!(result.persona_authority.contains_key("admin"));
         assert!(!result.persona_authority.contains_key("viewer"));
     }
+
+    #[test]
+    fn test_admissibility_matrix_is_dense() {
+        let bundle = make_bundle_with(
+            vec![AnalysisEntity {
+                id: "Order".to_string(),
+                states: vec!["draft".to_string(), "submitted".to_string()],
+                initial: "draft".to_string(),
+                transitions: vec![Transition {
+                    from: "draft".to_string(),
+                    to: "submitted".to_string(),
+                    guard: None,
+                }],
+                parent: None,
+            }],
+            vec![
+                AnalysisPersona {
+                    id: "admin".to_string(),
+                },
+                AnalysisPersona {
+                    id: "user".to_string(),
+                },
+            ],
+            vec![AnalysisOperation {
+                id: "submit".to_string(),
+                allowed_personas: vec!["admin".to_string()],
+                precondition: None,
+                effects: vec![Effect {
+                    entity_id: "Order".to_string(),
+                    from_state: "draft".to_string(),
+                    to_state: "submitted".to_string(),
+                    outcome: None,
+                }],
+                outcomes: vec![],
+                error_contract: None,
+            }],
+        );
+
+        let s3a = analyze_admissibility(&bundle);
+        let matrix = admissibility_matrix(&bundle, &s3a);
+
+        // 1 operation x 2 states x 2 personas
+        assert_eq!(matrix.cells.len(), 4);
+        let admissible: Vec<(&str, &str)> = matrix
+            .cells
+            .iter()
+            .filter(|c| c.admissible)
+            .map(|c| (c.persona_id.as_str(), c.state.as_str()))
+            .collect();
+        assert_eq!(admissible, vec![("admin", "draft")]);
+        // Authorized in a state with no transition, but not admissible.
+        assert!(matrix
+            .cells
+            .iter()
+            .any(|c| c.persona_id == "admin" && c.state == "submitted" && c.authorized));
+
+        let csv = matrix.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "persona,operation,entity,state,authorized,admissible"
+        );
+        assert_eq!(lines[1], "admin,submit,Order,draft,true,true");
+        assert_eq!(lines.len(), 5);
+    }
 }
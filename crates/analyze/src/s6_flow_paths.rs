@@ -4,6 +4,13 @@
 //! OperationStep outcome branching, BranchStep (true/false),
 //! ParallelStep (branch and rejoin), and SubFlowStep (recursive).
 //!
+//! Each path carries the probability of being taken, from the `weights`
+//! declared on OperationSteps and BranchSteps. A step without weights is
+//! taken to follow each of its outcomes equally often and never to fail;
+//! on a weighted step, outcomes without a weight (including `failure`)
+//! share whatever the declared weights leave. Flows with any weights also
+//! report the probability of each terminal outcome.
+//!
 //! Spec reference: Section 15, S6.
 
 use crate::bundle::AnalysisBundle;
@@ -47,6 +54,9 @@ pub struct FlowPath {
     pub steps: Vec<FlowPathStep>,
     pub terminal_outcome: Option<String>,
     pub depth: usize,
+    /// Probability of taking this path: the product of the probabilities
+    /// of the outcome chosen at each step.
+    pub probability: f64,
 }
 
 /// Flow path enumeration result for a single flow.
@@ -59,6 +69,16 @@ pub struct FlowPathResult {
     pub truncated: bool,
     pub reachable_steps: BTreeSet<String>,
     pub unreachable_steps: BTreeSet<String>,
+    /// Whether any step of the flow declares `weights`.
+    pub weighted: bool,
+    /// Probability of ending in each terminal outcome. Empty for
+    /// unweighted flows.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub outcome_probabilities: BTreeMap<String, f64>,
+    /// The terminal outcome with the highest probability, for weighted
+    /// flows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub most_likely_outcome: Option<String>,
 }
 
 /// A cross-contract flow path created by a System trigger.
@@ -190,6 +210,7 @@ fn enumerate_flow_paths(
             // Record path as-is at max depth
             paths.push(FlowPath {
                 depth: current_path.len(),
+                probability: path_probability(&current_path, &step_index),
                 steps: current_path,
                 terminal_outcome: Some("max_depth_exceeded".to_string()),
             });
@@ -200,6 +221,7 @@ fn enumerate_flow_paths(
         if visited.contains(&current_step_id) {
             paths.push(FlowPath {
                 depth: current_path.len(),
+                probability: path_probability(&current_path, &step_index),
                 steps: current_path,
                 terminal_outcome: Some("cycle_detected".to_string()),
             });
@@ -214,6 +236,7 @@ fn enumerate_flow_paths(
                 // Step not found -- could be a terminal reference
                 paths.push(FlowPath {
                     depth: current_path.len(),
+                    probability: path_probability(&current_path, &step_index),
                     steps: current_path,
                     terminal_outcome: Some(current_step_id),
                 });
@@ -258,6 +281,7 @@ fn enumerate_flow_paths(
                             let terminal = extract_terminal_outcome(target);
                             paths.push(FlowPath {
                                 depth: path.len(),
+                                probability: path_probability(&path, &step_index),
                                 steps: path,
                                 terminal_outcome: terminal,
                             });
@@ -275,15 +299,36 @@ fn enumerate_flow_paths(
                     });
                     paths.push(FlowPath {
                         depth: path.len(),
+                        probability: path_probability(&path, &step_index),
                         steps: path,
                         terminal_outcome: None,
                     });
                 }
 
-                // Follow Escalate handler's next target for failure path reachability
+                // Follow Escalate handler's next target for failure path
+                // reachability. Terminate and Compensate failures are only
+                // enumerated when the step's weights give them a chance.
                 if let Some(failure_handler) = step.get("on_failure") {
                     if let Some(handler_kind) = failure_handler.get("kind").and_then(|k| k.as_str())
                     {
+                        if handler_kind != "Escalate"
+                            && outcome_probability(step, FAILURE_WEIGHT) > 0.0
+                        {
+                            let mut failure_path = current_path.clone();
+                            failure_path.push(FlowPathStep {
+                                step_id: current_step_id.clone(),
+                                step_type: "operation".to_string(),
+                                persona: persona.clone(),
+                                operation_id: op_id.clone(),
+                                outcome: Some(FAILURE_WEIGHT.to_string()),
+                            });
+                            paths.push(FlowPath {
+                                depth: failure_path.len(),
+                                probability: path_probability(&failure_path, &step_index),
+                                terminal_outcome: extract_failure_outcome(failure_handler),
+                                steps: failure_path,
+                            });
+                        }
                         if handler_kind == "Escalate" {
                             if let Some(next_id) =
                                 failure_handler.get("next").and_then(|n| n.as_str())
@@ -327,6 +372,7 @@ fn enumerate_flow_paths(
                         let terminal = extract_terminal_outcome(true_target);
                         paths.push(FlowPath {
                             depth: true_path.len(),
+                            probability: path_probability(&true_path, &step_index),
                             steps: true_path,
                             terminal_outcome: terminal,
                         });
@@ -344,6 +390,7 @@ fn enumerate_flow_paths(
                         let terminal = extract_terminal_outcome(false_target);
                         paths.push(FlowPath {
                             depth: false_path.len(),
+                            probability: path_probability(&false_path, &step_index),
                             steps: false_path,
                             terminal_outcome: terminal,
                         });
@@ -366,6 +413,7 @@ fn enumerate_flow_paths(
                 } else {
                     paths.push(FlowPath {
                         depth: path.len(),
+                        probability: path_probability(&path, &step_index),
                         steps: path,
                         terminal_outcome: None,
                     });
@@ -394,6 +442,7 @@ fn enumerate_flow_paths(
                         let terminal = extract_terminal_outcome(success_target);
                         paths.push(FlowPath {
                             depth: success_path.len(),
+                            probability: path_probability(&success_path, &step_index),
                             steps: success_path,
                             terminal_outcome: terminal,
                         });
@@ -433,6 +482,7 @@ fn enumerate_flow_paths(
                         let terminal = extract_failure_outcome(failure_handler);
                         paths.push(FlowPath {
                             depth: failure_path.len(),
+                            probability: path_probability(&failure_path, &step_index),
                             steps: failure_path,
                             terminal_outcome: terminal,
                         });
@@ -466,6 +516,7 @@ fn enumerate_flow_paths(
                         // No join targets, treat as terminal
                         paths.push(FlowPath {
                             depth: path.len(),
+                            probability: path_probability(&path, &step_index),
                             steps: path,
                             terminal_outcome: Some("parallel_complete".to_string()),
                         });
@@ -473,6 +524,7 @@ fn enumerate_flow_paths(
                 } else {
                     paths.push(FlowPath {
                         depth: path.len(),
+                        probability: path_probability(&path, &step_index),
                         steps: path,
                         terminal_outcome: Some("parallel_complete".to_string()),
                     });
@@ -491,6 +543,7 @@ fn enumerate_flow_paths(
                 });
                 paths.push(FlowPath {
                     depth: path.len(),
+                    probability: path_probability(&path, &step_index),
                     steps: path,
                     terminal_outcome: None,
                 });
@@ -503,6 +556,26 @@ fn enumerate_flow_paths(
         all_step_ids.difference(&reachable_steps).cloned().collect();
     let path_count = paths.len();
 
+    let weighted = flow.steps.iter().any(has_weights);
+    let mut outcome_probabilities = BTreeMap::new();
+    if weighted {
+        for path in &paths {
+            if let Some(outcome) = &path.terminal_outcome {
+                *outcome_probabilities.entry(outcome.clone()).or_insert(0.0) += path.probability;
+            }
+        }
+    }
+    let most_likely_outcome = outcome_probabilities
+        .iter()
+        .fold(
+            None,
+            |best: Option<(&String, f64)>, (outcome, &p)| match best {
+                Some((_, best_p)) if best_p >= p => best,
+                _ => Some((outcome, p)),
+            },
+        )
+        .map(|(outcome, _)| outcome.clone());
+
     FlowPathResult {
         flow_id: flow.id.clone(),
         paths,
@@ -511,9 +584,92 @@ fn enumerate_flow_paths(
         truncated,
         reachable_steps,
         unreachable_steps,
+        weighted,
+        outcome_probabilities,
+        most_likely_outcome,
     }
 }
 
+/// Weight key for an OperationStep's failure handler.
+const FAILURE_WEIGHT: &str = "failure";
+
+/// Whether a step declares any `weights`.
+fn has_weights(step: &serde_json::Value) -> bool {
+    step.get("weights")
+        .and_then(|w| w.as_object())
+        .is_some_and(|w| !w.is_empty())
+}
+
+/// Probability of a path: the product of its step probabilities.
+fn path_probability(
+    path: &[FlowPathStep],
+    step_index: &BTreeMap<String, &serde_json::Value>,
+) -> f64 {
+    path.iter()
+        .map(|path_step| match step_index.get(&path_step.step_id) {
+            Some(step) => step_probability(step, path_step.outcome.as_deref()),
+            None => 1.0,
+        })
+        .product()
+}
+
+/// Probability that `step` is left through `outcome`.
+fn step_probability(step: &serde_json::Value, outcome: Option<&str>) -> f64 {
+    match step.get("kind").and_then(|k| k.as_str()).unwrap_or("") {
+        "OperationStep" | "BranchStep" => match outcome {
+            Some("escalate") => outcome_probability(step, FAILURE_WEIGHT),
+            Some(label) => outcome_probability(step, label),
+            None => 1.0,
+        },
+        // Sub-flows carry no weights: assume they succeed.
+        "SubFlowStep" => match outcome {
+            Some("success") => 1.0,
+            _ => 0.0,
+        },
+        // Each followed join target is equally likely.
+        "ParallelStep" => {
+            let targets = step
+                .get("join")
+                .map(|join| {
+                    ["on_all_success", "on_all_complete"]
+                        .iter()
+                        .filter(|key| join.get(**key).and_then(extract_step_target).is_some())
+                        .count()
+                })
+                .unwrap_or(0);
+            1.0 / targets.max(1) as f64
+        }
+        _ => 1.0,
+    }
+}
+
+/// Probability of `label` among the outcomes of an OperationStep or
+/// BranchStep, from the step's declared weights.
+fn outcome_probability(step: &serde_json::Value, label: &str) -> f64 {
+    let mut labels: Vec<&str> = match step.get("outcomes").and_then(|o| o.as_object()) {
+        Some(outcomes) => outcomes.keys().map(String::as_str).collect(),
+        None => vec!["true", "false"],
+    };
+    let weights = match step.get("weights").and_then(|w| w.as_object()) {
+        Some(weights) if !weights.is_empty() => weights,
+        // Unweighted: outcomes are equally likely and the step never fails.
+        _ if labels.contains(&label) => return 1.0 / labels.len() as f64,
+        _ => return 0.0,
+    };
+    if let Some(weight) = weights.get(label).and_then(|w| w.as_f64()) {
+        return weight;
+    }
+    if step.get("outcomes").is_some() {
+        labels.push(FAILURE_WEIGHT);
+    }
+    if !labels.contains(&label) {
+        return 0.0;
+    }
+    let declared: f64 = weights.values().filter_map(|w| w.as_f64()).sum();
+    let unweighted = labels.iter().filter(|l| !weights.contains_key(**l)).count();
+    (1.0 - declared).max(0.0) / unweighted as f64
+}
+
 /// Extract a step ID from a StepTarget (string reference or terminal object).
 fn extract_step_target(target: &serde_json::Value) -> Option<String> {
     // String target: "step_id"
@@ -727,4 +883,61 @@ mod tests {
         assert_eq!(flow_result.paths[0].steps[0].step_type, "handoff");
         assert_eq!(flow_result.paths[0].steps[1].step_type, "operation");
     }
+
+    #[test]
+    fn test_weighted_flow_outcome_probabilities() {
+        let flow = AnalysisFlow {
+            id: "weighted".to_string(),
+            entry: "submit".to_string(),
+            steps: vec![
+                json!({"id": "submit", "kind": "OperationStep", "op": "submit", "persona": "admin",
+                        "outcomes": {"success": "check"},
+                        "on_failure": {"kind": "Terminate", "outcome": "failed"},
+                        "weights": {"failure": 0.2}}),
+                json!({"id": "check", "kind": "BranchStep", "persona": "admin",
+                        "condition": {"fact_ref": "flag"},
+                        "if_true": {"kind": "Terminal", "outcome": "approved"},
+                        "if_false": {"kind": "Terminal", "outcome": "rejected"},
+                        "weights": {"true": 0.75}}),
+            ],
+            snapshot: "at_initiation".to_string(),
+        };
+
+        let bundle = make_flow_bundle(vec![flow]);
+        let result = analyze_flow_paths(&bundle, &empty_s5());
+
+        let flow_result = &result.flows["weighted"];
+        assert!(flow_result.weighted);
+        // The weighted failure is enumerated as its own path.
+        assert_eq!(flow_result.path_count, 3);
+        let p = &flow_result.outcome_probabilities;
+        assert!((p["approved"] - 0.6).abs() < 1e-9);
+        assert!((p["rejected"] - 0.2).abs() < 1e-9);
+        assert!((p["failed"] - 0.2).abs() < 1e-9);
+        assert_eq!(flow_result.most_likely_outcome.as_deref(), Some("approved"));
+    }
+
+    #[test]
+    fn test_unweighted_flow_splits_outcomes_evenly() {
+        let flow = AnalysisFlow {
+            id: "conditional".to_string(),
+            entry: "branch".to_string(),
+            steps: vec![
+                json!({"id": "branch", "kind": "BranchStep", "persona": "admin",
+                        "condition": {"fact_ref": "flag"},
+                        "if_true": {"kind": "Terminal", "outcome": "success"},
+                        "if_false": {"kind": "Terminal", "outcome": "failure"}}),
+            ],
+            snapshot: "at_initiation".to_string(),
+        };
+
+        let bundle = make_flow_bundle(vec![flow]);
+        let result = analyze_flow_paths(&bundle, &empty_s5());
+
+        let flow_result = &result.flows["conditional"];
+        assert!(!flow_result.weighted);
+        assert!(flow_result.outcome_probabilities.is_empty());
+        assert!(flow_result.most_likely_outcome.is_none());
+        assert!(flow_result.paths.iter().all(|p| p.probability == 0.5));
+    }
 }
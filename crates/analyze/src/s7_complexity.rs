@@ -6,7 +6,7 @@
 //! Spec reference: Section 15, S7.
 
 use crate::bundle::AnalysisBundle;
-use crate::s6_flow_paths::S6Result;
+use crate::s6_flow_paths::{FlowPathResult, S6Result};
 use serde::Serialize;
 use std::collections::BTreeMap;
 
@@ -27,6 +27,9 @@ pub struct FlowDepthBound {
     pub max_path_depth: usize,
     pub max_step_count: usize,
     pub has_cycles: bool,
+    /// Probability-weighted mean path depth, for flows with step weights.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_path_depth: Option<f64>,
 }
 
 /// Aggregated S7 result.
@@ -97,6 +100,7 @@ pub fn analyze_complexity(bundle: &AnalysisBundle, s6: &S6Result) -> S7Result {
             max_path_depth: flow_result.max_depth,
             max_step_count: flow_result.reachable_steps.len(),
             has_cycles,
+            expected_path_depth: expected_path_depth(flow_result),
         };
 
         max_flow_depth = max_flow_depth.max(flow_result.max_depth);
@@ -111,6 +115,24 @@ pub fn analyze_complexity(bundle: &AnalysisBundle, s6: &S6Result) -> S7Result {
     }
 }
 
+/// Mean path depth weighted by path probability. `None` for unweighted
+/// flows or when no enumerated path has a chance of being taken.
+fn expected_path_depth(flow_result: &FlowPathResult) -> Option<f64> {
+    if !flow_result.weighted {
+        return None;
+    }
+    let total: f64 = flow_result.paths.iter().map(|p| p.probability).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let weighted_depth: f64 = flow_result
+        .paths
+        .iter()
+        .map(|p| p.probability * p.depth as f64)
+        .sum();
+    Some(weighted_depth / total)
+}
+
 /// Walk an expression tree and return (node_count, max_depth).
 fn walk_expression_tree(expr: &serde_json::Value) -> (usize, usize) {
     if expr.is_null() || expr.is_boolean() || expr.is_number() || expr.is_string() {
@@ -234,7 +256,7 @@ fn classify_complexity(node_count: usize, has_quantifiers: bool) -> String {
 mod tests {
     use super::*;
     use crate::bundle::*;
-    use crate::s6_flow_paths::FlowPathResult;
+    use crate::s6_flow_paths::FlowPath;
     use serde_json::json;
     use std::collections::BTreeSet;
 
@@ -330,6 +352,9 @@ mod tests {
                     s
                 },
                 unreachable_steps: BTreeSet::new(),
+                weighted: false,
+                outcome_probabilities: BTreeMap::new(),
+                most_likely_outcome: None,
             },
         );
 
@@ -349,6 +374,43 @@ mod tests {
         assert!(!bound.has_cycles);
     }
 
+    #[test]
+    fn test_expected_path_depth_weights_paths() {
+        let path = |depth: usize, probability: f64| FlowPath {
+            steps: vec![],
+            terminal_outcome: Some("done".to_string()),
+            depth,
+            probability,
+        };
+        let mut flows = BTreeMap::new();
+        flows.insert(
+            "main".to_string(),
+            FlowPathResult {
+                flow_id: "main".to_string(),
+                paths: vec![path(1, 0.75), path(5, 0.25)],
+                path_count: 2,
+                max_depth: 5,
+                truncated: false,
+                reachable_steps: BTreeSet::new(),
+                unreachable_steps: BTreeSet::new(),
+                weighted: true,
+                outcome_probabilities: BTreeMap::new(),
+                most_likely_outcome: None,
+            },
+        );
+        let s6 = S6Result {
+            flows,
+            total_paths: 2,
+            cross_contract_paths: vec![],
+        };
+
+        let result = analyze_complexity(&make_bundle_with(vec![], vec![]), &s6);
+        let expected = result.flow_depth_bounds["main"]
+            .expected_path_depth
+            .unwrap();
+        assert!((expected - 2.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_empty_predicates() {
         let bundle = make_bundle_with(vec![], vec![]);
@@ -0,0 +1,508 @@
+//! System-level analysis -- S4/S6 across all member contracts of a System.
+//!
+//! Single-bundle analysis only sees the System construct itself, so it
+//! can report *that* personas and entities are shared but not whether the
+//! members agree about them. This module analyzes every member contract
+//! and cross-checks the results:
+//!
+//! - shared entities must be declared identically in every member;
+//! - a transition of a shared entity should be claimed (S4) by operations
+//!   in at most one member contract;
+//! - shared personas must be declared in every member that shares them;
+//! - flow triggers must name existing flows, and the triggering outcome
+//!   must be a reachable terminal outcome of the source flow (S6).
+//!
+//! Spec reference: Section 15, S4 and S6.
+
+use crate::bundle::{AnalysisBundle, AnalysisError, AnalysisSystem};
+use crate::report::AnalysisReport;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// One member contract's claim on a shared-entity transition.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorityClaim {
+    pub contract_id: String,
+    pub operation_id: String,
+    pub persona_id: String,
+}
+
+/// Every claim on one transition of a shared entity, across members.
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedTransitionAuthority {
+    pub entity_id: String,
+    pub from_state: String,
+    pub to_state: String,
+    pub claims: Vec<AuthorityClaim>,
+}
+
+/// A flow trigger resolved against the S6 paths of both member flows.
+#[derive(Debug, Clone, Serialize)]
+pub struct TriggerPath {
+    pub source_contract: String,
+    pub source_flow: String,
+    pub on: String,
+    pub target_contract: String,
+    pub target_flow: String,
+    pub persona: String,
+    /// Source-flow paths that end in the triggering outcome.
+    pub source_paths: usize,
+    /// Paths through the target flow.
+    pub target_paths: usize,
+}
+
+/// A disagreement between member contracts of a System.
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemConflict {
+    /// Analysis that detected the conflict: "s4", "s6", or "system".
+    pub analysis: String,
+    pub kind: String,
+    pub message: String,
+    pub contracts: Vec<String>,
+}
+
+/// Aggregated System analysis result.
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemAnalysis {
+    pub system_id: String,
+    /// member contract id -> full S1-S8 report for that member.
+    pub members: BTreeMap<String, AnalysisReport>,
+    pub shared_transition_authorities: Vec<SharedTransitionAuthority>,
+    pub trigger_paths: Vec<TriggerPath>,
+    pub conflicts: Vec<SystemConflict>,
+}
+
+/// Analyze a System over the interchange bundles of its member contracts.
+///
+/// `members` maps each member id declared by the System to that member's
+/// elaborated interchange bundle.
+pub fn analyze_system(
+    system: &AnalysisSystem,
+    members: &BTreeMap<String, serde_json::Value>,
+) -> Result<SystemAnalysis, AnalysisError> {
+    let mut bundles = BTreeMap::new();
+    let mut reports = BTreeMap::new();
+    for member in &system.members {
+        let json = members.get(&member.id).ok_or_else(|| {
+            AnalysisError::InvalidBundle(format!(
+                "no bundle provided for member contract '{}'",
+                member.id
+            ))
+        })?;
+        bundles.insert(member.id.clone(), AnalysisBundle::from_interchange(json)?);
+        reports.insert(member.id.clone(), crate::analyze(json)?);
+    }
+
+    let mut conflicts = Vec::new();
+    check_shared_entities(system, &bundles, &mut conflicts);
+    check_shared_personas(system, &bundles, &mut conflicts);
+    let shared_transition_authorities = shared_authorities(system, &reports, &mut conflicts);
+    let trigger_paths = resolve_triggers(system, &bundles, &reports, &mut conflicts);
+
+    Ok(SystemAnalysis {
+        system_id: system.id.clone(),
+        members: reports,
+        shared_transition_authorities,
+        trigger_paths,
+        conflicts,
+    })
+}
+
+/// Shared entities must exist, with the same states, initial state, and
+/// transitions, in every contract that shares them.
+fn check_shared_entities(
+    system: &AnalysisSystem,
+    bundles: &BTreeMap<String, AnalysisBundle>,
+    conflicts: &mut Vec<SystemConflict>,
+) {
+    for shared in &system.shared_entities {
+        let mut shapes = Vec::new();
+        for contract_id in &shared.contracts {
+            let entity = bundles
+                .get(contract_id)
+                .and_then(|b| b.entities.iter().find(|e| e.id == shared.entity));
+            match entity {
+                Some(e) => {
+                    let states: BTreeSet<&str> = e.states.iter().map(String::as_str).collect();
+                    let transitions: BTreeSet<(&str, &str)> = e
+                        .transitions
+                        .iter()
+                        .map(|t| (t.from.as_str(), t.to.as_str()))
+                        .collect();
+                    shapes.push((contract_id, (states, e.initial.as_str(), transitions)));
+                }
+                None => conflicts.push(SystemConflict {
+                    analysis: "system".to_string(),
+                    kind: "shared_entity_missing".to_string(),
+                    message: format!(
+                        "Shared entity '{}' is not declared in contract '{}'",
+                        shared.entity, contract_id
+                    ),
+                    contracts: vec![contract_id.clone()],
+                }),
+            }
+        }
+
+        if let Some((first_id, first)) = shapes.first() {
+            for (contract_id, shape) in &shapes[1..] {
+                if shape != first {
+                    conflicts.push(SystemConflict {
+                        analysis: "system".to_string(),
+                        kind: "shared_entity_mismatch".to_string(),
+                        message: format!(
+                            "Shared entity '{}' is declared differently in '{}' and '{}'",
+                            shared.entity, first_id, contract_id
+                        ),
+                        contracts: vec![(*first_id).clone(), (*contract_id).clone()],
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Shared personas must be declared in every contract that shares them.
+fn check_shared_personas(
+    system: &AnalysisSystem,
+    bundles: &BTreeMap<String, AnalysisBundle>,
+    conflicts: &mut Vec<SystemConflict>,
+) {
+    for shared in &system.shared_personas {
+        for contract_id in &shared.contracts {
+            let declared = bundles
+                .get(contract_id)
+                .is_some_and(|b| b.personas.iter().any(|p| p.id == shared.persona));
+            if !declared {
+                conflicts.push(SystemConflict {
+                    analysis: "s4".to_string(),
+                    kind: "shared_persona_missing".to_string(),
+                    message: format!(
+                        "Shared persona '{}' is not declared in contract '{}'",
+                        shared.persona, contract_id
+                    ),
+                    contracts: vec![contract_id.clone()],
+                });
+            }
+        }
+    }
+}
+
+/// Collect every member's S4 transition authorities over shared entities
+/// and flag transitions claimed by more than one contract.
+fn shared_authorities(
+    system: &AnalysisSystem,
+    reports: &BTreeMap<String, AnalysisReport>,
+    conflicts: &mut Vec<SystemConflict>,
+) -> Vec<SharedTransitionAuthority> {
+    let mut by_transition: BTreeMap<(String, String, String), Vec<AuthorityClaim>> =
+        BTreeMap::new();
+    for shared in &system.shared_entities {
+        for contract_id in &shared.contracts {
+            let Some(s4) = reports
+                .get(contract_id)
+                .and_then(|r| r.s4_authority.as_ref())
+            else {
+                continue;
+            };
+            for ta in s4
+                .transition_authorities
+                .iter()
+                .filter(|ta| ta.entity_id == shared.entity)
+            {
+                by_transition
+                    .entry((
+                        ta.entity_id.clone(),
+                        ta.from_state.clone(),
+                        ta.to_state.clone(),
+                    ))
+                    .or_default()
+                    .push(AuthorityClaim {
+                        contract_id: contract_id.clone(),
+                        operation_id: ta.via_operation.clone(),
+                        persona_id: ta.persona_id.clone(),
+                    });
+            }
+        }
+    }
+
+    let mut results = Vec::new();
+    for ((entity_id, from_state, to_state), mut claims) in by_transition {
+        claims.sort_by(|a, b| {
+            (&a.contract_id, &a.operation_id, &a.persona_id).cmp(&(
+                &b.contract_id,
+                &b.operation_id,
+                &b.persona_id,
+            ))
+        });
+        claims.dedup_by(|a, b| {
+            a.contract_id == b.contract_id
+                && a.operation_id == b.operation_id
+                && a.persona_id == b.persona_id
+        });
+
+        let contracts: BTreeSet<&str> = claims.iter().map(|c| c.contract_id.as_str()).collect();
+        if contracts.len() > 1 {
+            let mut claimants: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+            for claim in &claims {
+                claimants
+                    .entry(claim.contract_id.as_str())
+                    .or_default()
+                    .insert(claim.operation_id.as_str());
+            }
+            let listed: Vec<String> = claimants
+                .iter()
+                .map(|(contract, ops)| {
+                    format!(
+                        "{} ({})",
+                        contract,
+                        ops.iter().copied().collect::<Vec<_>>().join(", ")
+                    )
+                })
+                .collect();
+            conflicts.push(SystemConflict {
+                analysis: "s4".to_string(),
+                kind: "transition_authority_conflict".to_string(),
+                message: format!(
+                    "Transition {}: {} -> {} is claimed by multiple contracts: {}",
+                    entity_id,
+                    from_state,
+                    to_state,
+                    listed.join(", ")
+                ),
+                contracts: contracts.iter().map(|c| c.to_string()).collect(),
+            });
+        }
+
+        results.push(SharedTransitionAuthority {
+            entity_id,
+            from_state,
+            to_state,
+            claims,
+        });
+    }
+    results
+}
+
+/// Resolve each flow trigger against the member flows' S6 paths.
+fn resolve_triggers(
+    system: &AnalysisSystem,
+    bundles: &BTreeMap<String, AnalysisBundle>,
+    reports: &BTreeMap<String, AnalysisReport>,
+    conflicts: &mut Vec<SystemConflict>,
+) -> Vec<TriggerPath> {
+    let flow_result = |contract: &str, flow: &str| {
+        reports
+            .get(contract)
+            .and_then(|r| r.s6_flow_paths.as_ref())
+            .and_then(|s6| s6.flows.get(flow))
+    };
+
+    let mut paths = Vec::new();
+    for trigger in &system.flow_triggers {
+        let contracts = vec![
+            trigger.source_contract.clone(),
+            trigger.target_contract.clone(),
+        ];
+        let source = flow_result(&trigger.source_contract, &trigger.source_flow);
+        let target = flow_result(&trigger.target_contract, &trigger.target_flow);
+        let (Some(source), Some(target)) = (source, target) else {
+            let (contract, flow) = if source.is_none() {
+                (&trigger.source_contract, &trigger.source_flow)
+            } else {
+                (&trigger.target_contract, &trigger.target_flow)
+            };
+            conflicts.push(SystemConflict {
+                analysis: "s6".to_string(),
+                kind: "trigger_unresolved".to_string(),
+                message: format!(
+                    "Trigger {}.{} -> {}.{} names flow '{}' which contract '{}' does not declare",
+                    trigger.source_contract,
+                    trigger.source_flow,
+                    trigger.target_contract,
+                    trigger.target_flow,
+                    flow,
+                    contract
+                ),
+                contracts,
+            });
+            continue;
+        };
+
+        let source_paths = source
+            .paths
+            .iter()
+            .filter(|p| p.terminal_outcome.as_deref() == Some(trigger.on.as_str()))
+            .count();
+        if source_paths == 0 && !source.truncated {
+            conflicts.push(SystemConflict {
+                analysis: "s6".to_string(),
+                kind: "trigger_unreachable".to_string(),
+                message: format!(
+                    "Trigger {}.{} -> {}.{} fires on '{}', which no path of the source flow reaches",
+                    trigger.source_contract,
+                    trigger.source_flow,
+                    trigger.target_contract,
+                    trigger.target_flow,
+                    trigger.on
+                ),
+                contracts: contracts.clone(),
+            });
+        }
+
+        let persona_known = bundles
+            .get(&trigger.target_contract)
+            .is_some_and(|b| b.personas.iter().any(|p| p.id == trigger.persona));
+        if !persona_known {
+            conflicts.push(SystemConflict {
+                analysis: "s4".to_string(),
+                kind: "trigger_persona_unknown".to_string(),
+                message: format!(
+                    "Trigger persona '{}' is not declared in target contract '{}'",
+                    trigger.persona, trigger.target_contract
+                ),
+                contracts: vec![trigger.target_contract.clone()],
+            });
+        }
+
+        paths.push(TriggerPath {
+            source_contract: trigger.source_contract.clone(),
+            source_flow: trigger.source_flow.clone(),
+            on: trigger.on.clone(),
+            target_contract: trigger.target_contract.clone(),
+            target_flow: trigger.target_flow.clone(),
+            persona: trigger.persona.clone(),
+            source_paths,
+            target_paths: target.path_count,
+        });
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::{FlowTrigger, SharedEntity, SharedPersona, SystemMember};
+    use serde_json::json;
+
+    fn member(persona: &str, op: &str, states: &[&str], flow_outcome: &str) -> serde_json::Value {
+        json!({
+            "id": "member",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": tenor_core::TENOR_BUNDLE_VERSION,
+            "constructs": [
+                {"id": persona, "kind": "Persona", "tenor": "1.0",
+                 "provenance": {"file": "m.tenor", "line": 1}},
+                {"id": "Order", "kind": "Entity", "tenor": "1.0",
+                 "states": states, "initial": "draft",
+                 "transitions": [{"from": "draft", "to": "submitted"}],
+                 "provenance": {"file": "m.tenor", "line": 2}},
+                {"id": op, "kind": "Operation", "tenor": "1.0",
+                 "allowed_personas": [persona], "precondition": null,
+                 "effects": [{"entity_id": "Order", "from": "draft", "to": "submitted"}],
+                 "error_contract": [],
+                 "provenance": {"file": "m.tenor", "line": 3}},
+                {"id": "main", "kind": "Flow", "tenor": "1.0",
+                 "entry": "s", "snapshot": "at_initiation",
+                 "steps": [{"id": "s", "kind": "OperationStep", "op": op, "persona": persona,
+                            "outcomes": {"success": {"kind": "Terminal", "outcome": flow_outcome}},
+                            "on_failure": {"kind": "Terminate", "outcome": "failed"}}],
+                 "provenance": {"file": "m.tenor", "line": 4}},
+            ],
+        })
+    }
+
+    fn system(on: &str) -> AnalysisSystem {
+        AnalysisSystem {
+            id: "sys".to_string(),
+            members: vec![
+                SystemMember {
+                    id: "a".to_string(),
+                    path: "a.tenor".to_string(),
+                },
+                SystemMember {
+                    id: "b".to_string(),
+                    path: "b.tenor".to_string(),
+                },
+            ],
+            shared_personas: vec![SharedPersona {
+                persona: "clerk".to_string(),
+                contracts: vec!["a".to_string(), "b".to_string()],
+            }],
+            flow_triggers: vec![FlowTrigger {
+                source_contract: "a".to_string(),
+                source_flow: "main".to_string(),
+                on: on.to_string(),
+                target_contract: "b".to_string(),
+                target_flow: "main".to_string(),
+                persona: "clerk".to_string(),
+            }],
+            shared_entities: vec![SharedEntity {
+                entity: "Order".to_string(),
+                contracts: vec!["a".to_string(), "b".to_string()],
+            }],
+        }
+    }
+
+    fn kinds(analysis: &SystemAnalysis) -> Vec<&str> {
+        analysis.conflicts.iter().map(|c| c.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn two_contracts_claiming_one_transition_conflict() {
+        let members = BTreeMap::from([
+            (
+                "a".to_string(),
+                member("clerk", "submit", &["draft", "submitted"], "done"),
+            ),
+            (
+                "b".to_string(),
+                member("clerk", "file", &["draft", "submitted"], "done"),
+            ),
+        ]);
+        let result = analyze_system(&system("done"), &members).unwrap();
+
+        assert_eq!(kinds(&result), vec!["transition_authority_conflict"]);
+        assert!(result.conflicts[0].message.contains("a (submit)"));
+        assert!(result.conflicts[0].message.contains("b (file)"));
+        assert_eq!(result.shared_transition_authorities.len(), 1);
+        assert_eq!(result.shared_transition_authorities[0].claims.len(), 2);
+        assert_eq!(result.trigger_paths.len(), 1);
+        assert_eq!(result.trigger_paths[0].source_paths, 1);
+    }
+
+    #[test]
+    fn mismatched_entity_persona_and_trigger_outcome_are_reported() {
+        let members = BTreeMap::from([
+            (
+                "a".to_string(),
+                member("clerk", "submit", &["draft", "submitted"], "done"),
+            ),
+            (
+                "b".to_string(),
+                member(
+                    "manager",
+                    "file",
+                    &["draft", "submitted", "archived"],
+                    "done",
+                ),
+            ),
+        ]);
+        let result = analyze_system(&system("approved"), &members).unwrap();
+        let kinds = kinds(&result);
+
+        assert!(kinds.contains(&"shared_entity_mismatch"), "{:?}", kinds);
+        assert!(kinds.contains(&"shared_persona_missing"), "{:?}", kinds);
+        assert!(kinds.contains(&"trigger_unreachable"), "{:?}", kinds);
+        assert!(kinds.contains(&"trigger_persona_unknown"), "{:?}", kinds);
+    }
+
+    #[test]
+    fn missing_member_bundle_is_an_error() {
+        let members = BTreeMap::from([(
+            "a".to_string(),
+            member("clerk", "submit", &["draft", "submitted"], "done"),
+        )]);
+        assert!(analyze_system(&system("done"), &members).is_err());
+    }
+}
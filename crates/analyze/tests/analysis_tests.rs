@@ -73,7 +73,7 @@ fn test_s2_all_reachable() {
         !s2.has_dead_states,
         "entity_basic should have no dead states"
     );
-    for (_, result) in &s2.entities {
+    for result in s2.entities.values() {
         assert!(
             result.unreachable_states.is_empty(),
             "entity {} should have no unreachable states",
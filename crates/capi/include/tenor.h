@@ -0,0 +1,109 @@
+/*
+ * tenor.h — C API for the Tenor contract evaluator.
+ *
+ * Link against libtenor_capi (cdylib or staticlib, built from crates/capi).
+ *
+ * Conventions:
+ *  - Every fallible function returns a TenorStatus. On TENOR_OK it writes its
+ *    result through the last (out) argument; on failure it writes NULL and
+ *    tenor_last_error() describes the failure.
+ *  - Strings in and out are NUL-terminated UTF-8. JSON results have the same
+ *    shapes the WASM evaluator returns and must be released with
+ *    tenor_string_free().
+ *  - A TenorContract is immutable once loaded and may be shared between
+ *    threads. Release it with tenor_contract_free().
+ */
+
+#ifndef TENOR_H
+#define TENOR_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Version of this header. Compare with tenor_abi_version() at startup. */
+#define TENOR_ABI_VERSION 1
+
+typedef enum TenorStatus {
+    TENOR_OK = 0,
+    /* A required pointer argument was NULL. */
+    TENOR_NULL_ARGUMENT = 1,
+    /* A string argument was not valid UTF-8. */
+    TENOR_INVALID_UTF8 = 2,
+    /* A JSON argument could not be parsed, or had the wrong shape. */
+    TENOR_INVALID_JSON = 3,
+    /* The bundle is not a valid contract, or needs unsupported features. */
+    TENOR_INVALID_CONTRACT = 4,
+    /* Fact assembly or rule evaluation failed. */
+    TENOR_EVALUATION = 5,
+    /* The contract declares no flow with the given id. */
+    TENOR_FLOW_NOT_FOUND = 6,
+    /* Flow execution failed. */
+    TENOR_FLOW_EXECUTION = 7,
+    /* The evaluator panicked. This is a bug. */
+    TENOR_PANIC = 8
+} TenorStatus;
+
+/* A loaded contract. */
+typedef struct TenorContract TenorContract;
+
+/* ── Library ── */
+
+/* The C API version the library implements. */
+uint32_t tenor_abi_version(void);
+
+/* Message for the last failed call on this thread, or NULL if it succeeded.
+ * Valid until the next call on this thread. */
+const char *tenor_last_error(void);
+
+/* Free a string returned by this library. NULL is a no-op. */
+void tenor_string_free(char *s);
+
+/* ── Contracts ── */
+
+/* Load a contract from interchange bundle JSON. */
+TenorStatus tenor_load_contract(const char *bundle_json, TenorContract **out);
+
+/* Load a contract from a CBOR-encoded interchange bundle. */
+TenorStatus tenor_load_contract_cbor(const uint8_t *bytes, size_t len, TenorContract **out);
+
+/* Free a loaded contract. NULL is a no-op. */
+void tenor_contract_free(TenorContract *contract);
+
+/* ── Evaluation ── */
+
+/* Evaluate rules against a fact document. Writes the VerdictSet JSON. */
+TenorStatus tenor_evaluate(const TenorContract *contract,
+                           const char *facts_json,
+                           char **out_json);
+
+/* Simulate a flow. entity_states_json accepts the flat ({"Order": "pending"})
+ * and nested ({"Order": {"ord-1": "pending"}}) formats and is overlaid on the
+ * contract's initial states. instance_bindings_json (entity id -> instance
+ * id) may be NULL. Writes the simulation result JSON. */
+TenorStatus tenor_simulate_flow(const TenorContract *contract,
+                                const char *flow_id,
+                                const char *persona_id,
+                                const char *facts_json,
+                                const char *entity_states_json,
+                                const char *instance_bindings_json,
+                                char **out_json);
+
+/* Compute the action space for a persona. Writes the ActionSpace JSON. */
+TenorStatus tenor_compute_action_space(const TenorContract *contract,
+                                       const char *facts_json,
+                                       const char *entity_states_json,
+                                       const char *persona_id,
+                                       char **out_json);
+
+/* Summarize the contract's constructs, as `tenor inspect` does. */
+TenorStatus tenor_inspect_contract(const TenorContract *contract, char **out_json);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* TENOR_H */
@@ -0,0 +1,412 @@
+//! C API for the Tenor evaluator.
+//!
+//! Built as a cdylib and staticlib with the header `include/tenor.h`, so C,
+//! C++ and JVM (JNI or Panama) services can embed the evaluator directly
+//! instead of going through the WASM module.
+//!
+//! # Conventions
+//!
+//! - Every fallible function returns a [`TenorStatus`]. On [`TenorStatus::Ok`]
+//!   it writes its result through the `out` pointer; on failure it writes
+//!   `NULL` and [`tenor_last_error`] describes the failure.
+//! - Strings in and out are NUL-terminated UTF-8. JSON results have the same
+//!   shapes the WASM evaluator returns and must be released with
+//!   [`tenor_string_free`].
+//! - A contract from `tenor_load_contract*` is immutable and may be shared
+//!   between threads; release it with [`tenor_contract_free`].
+//! - Panics never cross the boundary: they are reported as
+//!   [`TenorStatus::Panic`].
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use serde_json::Value;
+
+/// Version of this C API. Bumped on any incompatible change to `tenor.h`.
+pub const TENOR_ABI_VERSION: u32 = 1;
+
+/// Result of a C API call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenorStatus {
+    Ok = 0,
+    /// A required pointer argument was `NULL`.
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// A JSON argument could not be parsed, or had the wrong shape.
+    InvalidJson = 3,
+    /// The bundle is not a valid contract, or needs unsupported features.
+    InvalidContract = 4,
+    /// Fact assembly or rule evaluation failed.
+    Evaluation = 5,
+    /// The contract declares no flow with the given id.
+    FlowNotFound = 6,
+    /// Flow execution failed.
+    FlowExecution = 7,
+    /// The evaluator panicked. This is a bug.
+    Panic = 8,
+}
+
+/// A loaded contract. Opaque to C callers.
+pub struct TenorContract {
+    contract: tenor_eval::Contract,
+    bundle: Value,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A failed call: its status and a message for [`tenor_last_error`].
+struct Failure(TenorStatus, String);
+
+impl Failure {
+    fn new(status: TenorStatus, context: &str, e: impl std::fmt::Display) -> Self {
+        Failure(status, format!("{}: {}", context, e))
+    }
+}
+
+fn set_last_error(msg: Option<String>) {
+    // Interior NULs cannot appear in a C string; replace rather than drop
+    let msg = msg.map(|m| CString::new(m.replace('\0', "\u{FFFD}")).unwrap_or_default());
+    LAST_ERROR.with(|e| *e.borrow_mut() = msg);
+}
+
+/// Run `f`, store its result through `out` and record its error, if any.
+///
+/// # Safety
+///
+/// `out` must be `NULL` or valid for a write of `T`.
+unsafe fn call<T>(out: *mut T, empty: T, f: impl FnOnce() -> Result<T, Failure>) -> TenorStatus {
+    if out.is_null() {
+        set_last_error(Some("out pointer is NULL".to_string()));
+        return TenorStatus::NullArgument;
+    }
+    *out = empty;
+    let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let msg = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(Failure(
+            TenorStatus::Panic,
+            format!("evaluator panicked: {}", msg),
+        ))
+    });
+    match result {
+        Ok(value) => {
+            set_last_error(None);
+            *out = value;
+            TenorStatus::Ok
+        }
+        Err(Failure(status, msg)) => {
+            set_last_error(Some(msg));
+            status
+        }
+    }
+}
+
+/// Read a required string argument.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or point to a NUL-terminated string.
+unsafe fn str_arg<'a>(s: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if s.is_null() {
+        return Err(Failure(
+            TenorStatus::NullArgument,
+            format!("{} is NULL", name),
+        ));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| Failure::new(TenorStatus::InvalidUtf8, name, e))
+}
+
+/// Read a required JSON argument.
+///
+/// # Safety
+///
+/// As for [`str_arg`].
+unsafe fn json_arg(s: *const c_char, name: &str) -> Result<Value, Failure> {
+    let text = str_arg(s, name)?;
+    serde_json::from_str(text).map_err(|e| Failure::new(TenorStatus::InvalidJson, name, e))
+}
+
+/// Read a required contract argument.
+///
+/// # Safety
+///
+/// `contract` must be `NULL` or a live pointer from `tenor_load_contract*`.
+unsafe fn contract_arg<'a>(contract: *const TenorContract) -> Result<&'a TenorContract, Failure> {
+    contract
+        .as_ref()
+        .ok_or_else(|| Failure(TenorStatus::NullArgument, "contract is NULL".to_string()))
+}
+
+fn json_out(value: &Value) -> Result<*mut c_char, Failure> {
+    CString::new(value.to_string())
+        .map(CString::into_raw)
+        .map_err(|e| Failure::new(TenorStatus::Panic, "serialization error", e))
+}
+
+fn load(bundle: Value) -> Result<*mut TenorContract, Failure> {
+    // Keep the plain form so inspection never sees type_refs
+    let bundle = tenor_interchange::into_inlined(bundle)
+        .map_err(|e| Failure::new(TenorStatus::InvalidContract, "invalid contract", e))?;
+    let contract = tenor_eval::Contract::from_interchange(&bundle)
+        .map_err(|e| Failure::new(TenorStatus::InvalidContract, "invalid contract", e))?;
+    Ok(Box::into_raw(Box::new(TenorContract { contract, bundle })))
+}
+
+fn evaluate_facts(
+    stored: &TenorContract,
+    facts: &Value,
+) -> Result<(tenor_eval::FactSet, tenor_eval::VerdictSet), Failure> {
+    let fact_set = tenor_eval::assemble::assemble_facts(&stored.contract, facts)
+        .map_err(|e| Failure::new(TenorStatus::Evaluation, "fact assembly error", e))?;
+    let verdict_set = tenor_eval::rules::eval_strata(&stored.contract, &fact_set)
+        .map_err(|e| Failure::new(TenorStatus::Evaluation, "evaluation error", e))?;
+    Ok((fact_set, verdict_set))
+}
+
+// ── Library ──
+
+/// The C API version this library implements; compare with
+/// `TENOR_ABI_VERSION` from the header the caller was compiled against.
+#[no_mangle]
+pub extern "C" fn tenor_abi_version() -> u32 {
+    TENOR_ABI_VERSION
+}
+
+/// The message describing the last failed call on this thread, or `NULL`
+/// if the last call succeeded. Valid until the next call on this thread.
+#[no_mangle]
+pub extern "C" fn tenor_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+/// Free a string returned by this library. `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a string returned by this library, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn tenor_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+// ── Contracts ──
+
+/// Load a contract from interchange bundle JSON.
+///
+/// # Safety
+///
+/// `bundle_json` must be `NULL` or a NUL-terminated string; `out` must be
+/// `NULL` or valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn tenor_load_contract(
+    bundle_json: *const c_char,
+    out: *mut *mut TenorContract,
+) -> TenorStatus {
+    call(out, ptr::null_mut(), || {
+        load(json_arg(bundle_json, "bundle_json")?)
+    })
+}
+
+/// Load a contract from a CBOR-encoded interchange bundle.
+///
+/// # Safety
+///
+/// `bytes` must be `NULL` or valid for reads of `len` bytes; `out` must be
+/// `NULL` or valid for a pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn tenor_load_contract_cbor(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut TenorContract,
+) -> TenorStatus {
+    call(out, ptr::null_mut(), || {
+        if bytes.is_null() {
+            return Err(Failure(
+                TenorStatus::NullArgument,
+                "bytes is NULL".to_string(),
+            ));
+        }
+        let bundle = tenor_interchange::from_cbor(std::slice::from_raw_parts(bytes, len))
+            .map_err(|e| Failure(TenorStatus::InvalidJson, e.to_string()))?;
+        load(bundle)
+    })
+}
+
+/// Free a loaded contract. `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `contract` must be `NULL` or a contract from `tenor_load_contract*`, not
+/// yet freed and no longer in use on any thread.
+#[no_mangle]
+pub unsafe extern "C" fn tenor_contract_free(contract: *mut TenorContract) {
+    if !contract.is_null() {
+        drop(Box::from_raw(contract));
+    }
+}
+
+// ── Evaluation ──
+
+/// Evaluate rules against a fact document. Writes the VerdictSet JSON.
+///
+/// # Safety
+///
+/// `contract` must be `NULL` or a live contract; `facts_json` must be `NULL`
+/// or a NUL-terminated string; `out_json` must be `NULL` or valid for a
+/// pointer write.
+#[no_mangle]
+pub unsafe extern "C" fn tenor_evaluate(
+    contract: *const TenorContract,
+    facts_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> TenorStatus {
+    call(out_json, ptr::null_mut(), || {
+        let stored = contract_arg(contract)?;
+        let facts = json_arg(facts_json, "facts_json")?;
+        let (_, verdicts) = evaluate_facts(stored, &facts)?;
+        json_out(&verdicts.to_json())
+    })
+}
+
+/// Simulate a flow. `entity_states_json` accepts the flat and the nested
+/// format and is overlaid on the contract's initial states;
+/// `instance_bindings_json` may be `NULL` for no bindings. Writes the
+/// simulation result JSON.
+///
+/// # Safety
+///
+/// As for [`tenor_evaluate`], for every string argument.
+#[no_mangle]
+pub unsafe extern "C" fn tenor_simulate_flow(
+    contract: *const TenorContract,
+    flow_id: *const c_char,
+    persona_id: *const c_char,
+    facts_json: *const c_char,
+    entity_states_json: *const c_char,
+    instance_bindings_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> TenorStatus {
+    call(out_json, ptr::null_mut(), || {
+        let stored = contract_arg(contract)?;
+        let flow_id = str_arg(flow_id, "flow_id")?;
+        let persona_id = str_arg(persona_id, "persona_id")?;
+        let facts = json_arg(facts_json, "facts_json")?;
+        let entity_states = tenor_eval::simulate::parse_entity_states(&json_arg(
+            entity_states_json,
+            "entity_states_json",
+        )?)
+        .map_err(|e| Failure::new(TenorStatus::InvalidJson, "entity_states_json", e))?;
+        let instance_bindings = if instance_bindings_json.is_null() {
+            tenor_eval::InstanceBindingMap::new()
+        } else {
+            tenor_eval::simulate::parse_instance_bindings(&json_arg(
+                instance_bindings_json,
+                "instance_bindings_json",
+            )?)
+            .map_err(|e| Failure::new(TenorStatus::InvalidJson, "instance_bindings_json", e))?
+        };
+
+        let (fact_set, verdict_set) = evaluate_facts(stored, &facts)?;
+        let snapshot = tenor_eval::Snapshot {
+            facts: fact_set,
+            verdicts: verdict_set.clone(),
+        };
+        let mut merged_entity_states = tenor_eval::operation::init_entity_states(&stored.contract);
+        merged_entity_states.extend(entity_states);
+
+        let target_flow = stored.contract.get_flow(flow_id).ok_or_else(|| {
+            Failure(
+                TenorStatus::FlowNotFound,
+                format!("flow '{}' not found", flow_id),
+            )
+        })?;
+        let flow_result = tenor_eval::flow::execute_flow(
+            target_flow,
+            &stored.contract,
+            &snapshot,
+            &mut merged_entity_states,
+            &mut tenor_eval::EntityAttributeMap::new(),
+            &instance_bindings,
+            tenor_eval::FlowOptions::default(),
+        )
+        .map_err(|e| Failure::new(TenorStatus::FlowExecution, "flow execution error", e))?;
+
+        let result = tenor_eval::FlowEvalResult {
+            verdicts: verdict_set,
+            flow_result,
+        };
+        json_out(&tenor_eval::simulate::simulation_json(
+            flow_id,
+            persona_id,
+            &result,
+            &instance_bindings,
+        ))
+    })
+}
+
+/// Compute the action space for a persona. Writes the ActionSpace JSON.
+///
+/// # Safety
+///
+/// As for [`tenor_evaluate`], for every string argument.
+#[no_mangle]
+pub unsafe extern "C" fn tenor_compute_action_space(
+    contract: *const TenorContract,
+    facts_json: *const c_char,
+    entity_states_json: *const c_char,
+    persona_id: *const c_char,
+    out_json: *mut *mut c_char,
+) -> TenorStatus {
+    call(out_json, ptr::null_mut(), || {
+        let stored = contract_arg(contract)?;
+        let facts = json_arg(facts_json, "facts_json")?;
+        let entity_states = tenor_eval::simulate::parse_entity_states(&json_arg(
+            entity_states_json,
+            "entity_states_json",
+        )?)
+        .map_err(|e| Failure::new(TenorStatus::InvalidJson, "entity_states_json", e))?;
+        let persona_id = str_arg(persona_id, "persona_id")?;
+        let action_space = tenor_eval::action_space::compute_action_space(
+            &stored.contract,
+            &facts,
+            &entity_states,
+            persona_id,
+        )
+        .map_err(|e| Failure::new(TenorStatus::Evaluation, "action space error", e))?;
+        let value = serde_json::to_value(&action_space)
+            .map_err(|e| Failure::new(TenorStatus::Panic, "serialization error", e))?;
+        json_out(&value)
+    })
+}
+
+/// Summarize the contract's constructs, as `tenor inspect` does. Writes the
+/// inspect JSON.
+///
+/// # Safety
+///
+/// As for [`tenor_evaluate`].
+#[no_mangle]
+pub unsafe extern "C" fn tenor_inspect_contract(
+    contract: *const TenorContract,
+    out_json: *mut *mut c_char,
+) -> TenorStatus {
+    call(out_json, ptr::null_mut(), || {
+        let stored = contract_arg(contract)?;
+        let inspect = tenor_interchange::build_inspect(&stored.bundle)
+            .map_err(|e| Failure::new(TenorStatus::InvalidContract, "inspect error", e))?;
+        json_out(&inspect)
+    })
+}
@@ -0,0 +1,241 @@
+//! Exercises the C API through its exported functions, as a C caller would.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use tenor_capi::*;
+
+// Same entity_operation_basic fixture as crates/tenor-eval-wasm/tests/wasm.rs.
+const BASIC_BUNDLE: &str = r#"{
+  "constructs": [
+    {
+      "id": "is_active",
+      "kind": "Fact",
+      "provenance": { "file": "test.tenor", "line": 11 },
+      "source": { "field": "active", "system": "account" },
+      "tenor": "1.0",
+      "type": { "base": "Bool" }
+    },
+    {
+      "id": "Order",
+      "initial": "pending",
+      "kind": "Entity",
+      "provenance": { "file": "test.tenor", "line": 3 },
+      "states": ["pending", "approved"],
+      "tenor": "1.0",
+      "transitions": [{ "from": "pending", "to": "approved" }]
+    },
+    {
+      "body": {
+        "produce": {
+          "payload": { "type": { "base": "Bool" }, "value": true },
+          "verdict_type": "account_active"
+        },
+        "when": {
+          "left": { "fact_ref": "is_active" },
+          "op": "=",
+          "right": { "literal": true, "type": { "base": "Bool" } }
+        }
+      },
+      "id": "check_active",
+      "kind": "Rule",
+      "provenance": { "file": "test.tenor", "line": 16 },
+      "stratum": 0,
+      "tenor": "1.0"
+    },
+    {
+      "allowed_personas": ["admin"],
+      "effects": [{ "entity_id": "Order", "from": "pending", "to": "approved" }],
+      "error_contract": ["precondition_failed"],
+      "id": "approve_order",
+      "kind": "Operation",
+      "precondition": { "verdict_present": "account_active" },
+      "provenance": { "file": "test.tenor", "line": 22 },
+      "tenor": "1.0"
+    },
+    {
+      "entry": "step_approve",
+      "id": "approval_flow",
+      "kind": "Flow",
+      "provenance": { "file": "test.tenor", "line": 29 },
+      "snapshot": "at_initiation",
+      "steps": [
+        {
+          "id": "step_approve",
+          "kind": "OperationStep",
+          "on_failure": { "kind": "Terminate", "outcome": "approval_failed" },
+          "op": "approve_order",
+          "outcomes": {
+            "success": { "kind": "Terminal", "outcome": "order_approved" }
+          },
+          "persona": "admin"
+        }
+      ],
+      "tenor": "1.0"
+    }
+  ],
+  "id": "entity_operation_basic",
+  "kind": "Bundle",
+  "tenor": "1.0",
+  "tenor_version": "1.0.0"
+}"#;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+fn load() -> *mut TenorContract {
+    let mut contract = ptr::null_mut();
+    let status = unsafe { tenor_load_contract(c(BASIC_BUNDLE).as_ptr(), &mut contract) };
+    assert_eq!(status, TenorStatus::Ok);
+    assert!(!contract.is_null());
+    contract
+}
+
+/// Take ownership of a JSON result string.
+fn take_json(out: *mut c_char) -> serde_json::Value {
+    assert!(!out.is_null());
+    let value = serde_json::from_str(unsafe { CStr::from_ptr(out) }.to_str().unwrap()).unwrap();
+    unsafe { tenor_string_free(out) };
+    value
+}
+
+fn last_error() -> String {
+    let msg = tenor_last_error();
+    assert!(!msg.is_null(), "expected an error message");
+    unsafe { CStr::from_ptr(msg) }.to_str().unwrap().to_string()
+}
+
+#[test]
+fn evaluate_and_simulate_through_c_api() {
+    let contract = load();
+    let facts = c(r#"{"is_active": true}"#);
+
+    let mut out = ptr::null_mut();
+    let status = unsafe { tenor_evaluate(contract, facts.as_ptr(), &mut out) };
+    assert_eq!(status, TenorStatus::Ok);
+    assert!(tenor_last_error().is_null());
+    let verdicts = take_json(out);
+    assert_eq!(verdicts["verdicts"][0]["type"], "account_active");
+
+    let status = unsafe {
+        tenor_simulate_flow(
+            contract,
+            c("approval_flow").as_ptr(),
+            c("admin").as_ptr(),
+            facts.as_ptr(),
+            c("{}").as_ptr(),
+            ptr::null(),
+            &mut out,
+        )
+    };
+    assert_eq!(status, TenorStatus::Ok);
+    let flow = take_json(out);
+    assert_eq!(flow["outcome"], "order_approved");
+    assert_eq!(flow["would_transition"][0]["to_state"], "approved");
+
+    let status = unsafe {
+        tenor_compute_action_space(
+            contract,
+            facts.as_ptr(),
+            c(r#"{"Order": "pending"}"#).as_ptr(),
+            c("guest").as_ptr(),
+            &mut out,
+        )
+    };
+    assert_eq!(status, TenorStatus::Ok);
+    let space = take_json(out);
+    assert_eq!(
+        space["blocked_actions"][0]["reason"]["type"],
+        "PersonaNotAuthorized"
+    );
+
+    let status = unsafe { tenor_inspect_contract(contract, &mut out) };
+    assert_eq!(status, TenorStatus::Ok);
+    assert_eq!(take_json(out)["entities"][0]["id"], "Order");
+
+    unsafe { tenor_contract_free(contract) };
+}
+
+#[test]
+fn failures_return_status_and_last_error() {
+    let mut contract = ptr::null_mut();
+    let status = unsafe { tenor_load_contract(c("not json").as_ptr(), &mut contract) };
+    assert_eq!(status, TenorStatus::InvalidJson);
+    assert!(contract.is_null());
+    assert!(last_error().starts_with("bundle_json:"));
+
+    let status = unsafe { tenor_load_contract(c(r#"{"kind": "Bundle"}"#).as_ptr(), &mut contract) };
+    assert_eq!(status, TenorStatus::InvalidContract);
+
+    let contract = load();
+    let mut out = ptr::null_mut();
+    let status = unsafe { tenor_evaluate(contract, c("{}").as_ptr(), &mut out) };
+    assert_eq!(status, TenorStatus::Evaluation);
+    assert!(out.is_null());
+    assert!(last_error().starts_with("fact assembly error"));
+
+    let status = unsafe {
+        tenor_simulate_flow(
+            contract,
+            c("missing").as_ptr(),
+            c("admin").as_ptr(),
+            c(r#"{"is_active": true}"#).as_ptr(),
+            c("{}").as_ptr(),
+            ptr::null(),
+            &mut out,
+        )
+    };
+    assert_eq!(status, TenorStatus::FlowNotFound);
+    assert_eq!(last_error(), "flow 'missing' not found");
+
+    let status = unsafe { tenor_evaluate(ptr::null(), c("{}").as_ptr(), &mut out) };
+    assert_eq!(status, TenorStatus::NullArgument);
+    let status = unsafe { tenor_evaluate(contract, ptr::null(), ptr::null_mut()) };
+    assert_eq!(status, TenorStatus::NullArgument);
+
+    unsafe {
+        tenor_contract_free(contract);
+        tenor_contract_free(ptr::null_mut());
+        tenor_string_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn header_declares_every_export_and_status() {
+    let header = include_str!("../include/tenor.h");
+    let source = include_str!("../src/lib.rs");
+
+    let exports: Vec<&str> = source
+        .split("extern \"C\" fn ")
+        .skip(1)
+        .map(|rest| &rest[..rest.find('(').unwrap()])
+        .collect();
+    assert!(exports.len() >= 10, "found exports {:?}", exports);
+    for name in exports {
+        assert!(
+            header.contains(&format!(" {}(", name)) || header.contains(&format!("*{}(", name)),
+            "tenor.h does not declare {}",
+            name
+        );
+    }
+
+    assert!(header.contains(&format!("#define TENOR_ABI_VERSION {}", TENOR_ABI_VERSION)));
+    for (name, status) in [
+        ("TENOR_OK", TenorStatus::Ok),
+        ("TENOR_NULL_ARGUMENT", TenorStatus::NullArgument),
+        ("TENOR_INVALID_UTF8", TenorStatus::InvalidUtf8),
+        ("TENOR_INVALID_JSON", TenorStatus::InvalidJson),
+        ("TENOR_INVALID_CONTRACT", TenorStatus::InvalidContract),
+        ("TENOR_EVALUATION", TenorStatus::Evaluation),
+        ("TENOR_FLOW_NOT_FOUND", TenorStatus::FlowNotFound),
+        ("TENOR_FLOW_EXECUTION", TenorStatus::FlowExecution),
+        ("TENOR_PANIC", TenorStatus::Panic),
+    ] {
+        assert!(
+            header.contains(&format!("{} = {}", name, status as i32)),
+            "tenor.h value of {} differs",
+            name
+        );
+    }
+}
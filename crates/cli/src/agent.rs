@@ -4,16 +4,35 @@
 //! The REPL discovers the contract's facts, operations, and flows
 //! automatically and lets the user set facts, evaluate, run flows,
 //! list operations, and get plain-language explanations.
+//!
+//! Session state (fact values, entity states, and the command transcript)
+//! can be saved to and loaded from a JSON file, so entity states carry
+//! over between flow runs and between invocations. A `--script` file
+//! replays commands non-interactively, and `export-transcript` writes an
+//! audit record of every command and its result.
+//!
+//! `set fact` and `unset fact` are the what-if forms of `set`/`unset`:
+//! they re-evaluate the contract and print which verdicts appeared,
+//! disappeared, or changed, and which flows each persona gained or lost
+//! in its action space.
 
-use std::io::{self, BufRead, Write};
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
 
 use super::explain;
 
-/// Run the interactive agent REPL for the given `.tenor` file.
-pub fn run_agent(file: &Path) {
+/// Run the agent REPL for the given `.tenor` file.
+///
+/// With `session`, state is loaded from that file if it exists and saved
+/// back to it on exit. With `script`, commands are read from the script
+/// file instead of stdin; the first failing command aborts with exit 1.
+pub fn run_agent(file: &Path, session_path: Option<&Path>, script: Option<&Path>) {
     // Step 1: Elaborate the .tenor file to get the interchange bundle.
-    let bundle = match tenor_core::elaborate::elaborate(file) {
+    let bundle = match crate::elaborate(file) {
         Ok(b) => b,
         Err(e) => {
             eprintln!("error: failed to elaborate '{}': {:?}", file.display(), e);
@@ -22,116 +41,58 @@ pub fn run_agent(file: &Path) {
     };
 
     // Step 2: Extract contract metadata from the interchange bundle.
-    let contract_id = bundle
-        .get("id")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown");
-
-    let constructs = bundle
-        .get("constructs")
-        .and_then(|c| c.as_array())
-        .cloned()
-        .unwrap_or_default();
-
-    let mut fact_decls: Vec<FactInfo> = Vec::new();
-    let mut operations: Vec<OpInfo> = Vec::new();
-    let mut flows: Vec<FlowInfo> = Vec::new();
-    let mut entity_count = 0;
-    let mut persona_count = 0;
-
-    for c in &constructs {
-        let kind = c.get("kind").and_then(|k| k.as_str()).unwrap_or("");
-        let cid = c.get("id").and_then(|i| i.as_str()).unwrap_or("");
-        match kind {
-            "Fact" => {
-                let type_base = c
-                    .get("type")
-                    .and_then(|t| t.get("base"))
-                    .and_then(|b| b.as_str())
-                    .unwrap_or("?");
-                let has_default =
-                    c.get("default").is_some() && !c.get("default").unwrap().is_null();
-                fact_decls.push(FactInfo {
-                    id: cid.to_string(),
-                    type_base: type_base.to_string(),
-                    has_default,
-                });
-            }
-            "Operation" => {
-                let personas: Vec<String> = c
-                    .get("allowed_personas")
-                    .and_then(|a| a.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                            .collect()
-                    })
-                    .unwrap_or_default();
-                let effects: Vec<String> = c
-                    .get("effects")
-                    .and_then(|e| e.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .map(|eff| {
-                                let eid =
-                                    eff.get("entity_id").and_then(|v| v.as_str()).unwrap_or("?");
-                                let from = eff.get("from").and_then(|v| v.as_str()).unwrap_or("?");
-                                let to = eff.get("to").and_then(|v| v.as_str()).unwrap_or("?");
-                                format!("{}: {} -> {}", eid, from, to)
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
-                operations.push(OpInfo {
-                    id: cid.to_string(),
-                    personas,
-                    effects,
-                });
-            }
-            "Flow" => {
-                let entry = c
-                    .get("entry")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("?")
-                    .to_string();
-                let step_count = c
-                    .get("steps")
-                    .and_then(|s| s.as_array())
-                    .map(|a| a.len())
-                    .unwrap_or(0);
-                flows.push(FlowInfo {
-                    id: cid.to_string(),
-                    entry,
-                    step_count,
-                });
+    let contract = ContractInfo::from_bundle(&bundle);
+
+    // Step 3: Restore or start the session.
+    let mut session = match session_path.filter(|p| p.exists()) {
+        Some(path) => match Session::load(path, &contract) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
             }
-            "Entity" => entity_count += 1,
-            "Persona" => persona_count += 1,
-            _ => {}
-        }
-    }
+        },
+        None => Session::new(&contract),
+    };
 
-    // Step 3: Print welcome banner.
+    // Step 4: Print welcome banner.
     println!();
-    println!("  Tenor Agent: {}", contract_id);
+    println!("  Tenor Agent: {}", contract.id);
     println!(
         "  {} facts, {} operations, {} flows, {} entities, {} personas",
-        fact_decls.len(),
-        operations.len(),
-        flows.len(),
-        entity_count,
-        persona_count,
+        contract.facts.len(),
+        contract.operations.len(),
+        contract.flows.len(),
+        contract.entities.len(),
+        contract.personas.len(),
     );
+    if let Some(path) = session_path.filter(|p| p.exists()) {
+        println!("  Resumed session from {}", path.display());
+    }
     println!();
-    println!("  Commands: help, facts, set, unset, eval, flow, operations, explain, reset, quit");
+    println!(
+        "  Commands: help, facts, set, unset, entities, eval, flow, operations, explain, \
+         save, load, export-transcript, reset, quit"
+    );
     println!();
 
-    // Step 4: Enter the REPL loop.
-    let mut current_facts: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
-
-    let stdin = io::stdin();
-    let mut reader = stdin.lock();
+    // Step 5: Enter the REPL loop.
+    let script_source;
+    let mut reader: Box<dyn BufRead + '_> = match script {
+        Some(path) => {
+            script_source = match std::fs::read_to_string(path) {
+                Ok(s) => s,
+                Err(e) => {
+                    eprintln!("error: failed to read script '{}': {}", path.display(), e);
+                    std::process::exit(1);
+                }
+            };
+            Box::new(script_source.as_bytes())
+        }
+        None => Box::new(io::stdin().lock()),
+    };
     let mut line = String::new();
+    let mut failed = false;
 
     loop {
         // Print prompt
@@ -143,7 +104,7 @@ pub fn run_agent(file: &Path) {
         line.clear();
         match reader.read_line(&mut line) {
             Ok(0) => {
-                // EOF (Ctrl-D)
+                // EOF (Ctrl-D or end of script)
                 println!();
                 break;
             }
@@ -155,80 +116,197 @@ pub fn run_agent(file: &Path) {
         }
 
         let trimmed = line.trim();
-        if trimmed.is_empty() {
+        if script.is_some() {
+            // Echo scripted commands so the output reads like a session.
+            println!("{}", trimmed);
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
             continue;
         }
 
         let parts: Vec<&str> = trimmed.splitn(3, char::is_whitespace).collect();
         let cmd = parts[0].to_lowercase();
+        if cmd == "quit" || cmd == "exit" {
+            break;
+        }
 
-        match cmd.as_str() {
-            "help" => {
-                print_help();
-            }
-            "facts" => {
-                print_facts(&fact_decls, &current_facts);
-            }
-            "set" => {
-                if parts.len() < 3 {
-                    eprintln!("usage: set <fact_id> <value>");
-                    continue;
-                }
-                let fact_id = parts[1];
-                let raw_value = parts[2];
-                set_fact(fact_id, raw_value, &fact_decls, &mut current_facts);
+        let outcome = run_command(&cmd, &parts, &bundle, &contract, &mut session);
+        if let Err(ref e) = outcome {
+            eprintln!("  {}", e);
+        }
+        if !matches!(cmd.as_str(), "help" | "export-transcript") {
+            session.record(trimmed, &outcome);
+        }
+        if outcome.is_err() && script.is_some() {
+            failed = true;
+            break;
+        }
+    }
+
+    if let Some(path) = session_path {
+        match session.save(path) {
+            Ok(()) => println!("  session saved to {}", path.display()),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failed = true;
             }
-            "unset" => {
-                if parts.len() < 2 {
-                    eprintln!("usage: unset <fact_id>");
-                    continue;
-                }
-                let fact_id = parts[1];
-                if current_facts.remove(fact_id).is_some() {
-                    println!("  unset {}", fact_id);
-                } else {
-                    eprintln!("  {} was not set", fact_id);
-                }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Dispatch one REPL command. `Ok` carries the structured result recorded
+/// in the transcript (if any).
+fn run_command(
+    cmd: &str,
+    parts: &[&str],
+    bundle: &serde_json::Value,
+    contract: &ContractInfo,
+    session: &mut Session,
+) -> CommandResult {
+    match cmd {
+        "help" => {
+            print_help();
+            Ok(None)
+        }
+        "facts" => {
+            print_facts(&contract.facts, &session.facts);
+            Ok(None)
+        }
+        "set" if parts.get(1) == Some(&"fact") => {
+            let mut rest = parts
+                .get(2)
+                .copied()
+                .unwrap_or("")
+                .splitn(2, char::is_whitespace);
+            let (Some(fact_id), Some(raw_value)) = (rest.next(), rest.next()) else {
+                return Err("usage: set fact <fact_id> <value>".to_string());
+            };
+            let raw_value = raw_value.trim();
+            what_if(bundle, contract, session, |facts| {
+                Ok(set_fact(fact_id, raw_value, &contract.facts, facts))
+            })
+            .map(Some)
+        }
+        "set" => {
+            if parts.len() < 3 {
+                return Err("usage: set <fact_id> <value>".to_string());
             }
-            "eval" => {
-                run_eval(&bundle, &current_facts);
+            let fact_id = parts[1];
+            let raw_value = parts[2];
+            Ok(Some(set_fact(
+                fact_id,
+                raw_value,
+                &contract.facts,
+                &mut session.facts,
+            )))
+        }
+        "unset" if parts.get(1) == Some(&"fact") => {
+            let Some(fact_id) = parts.get(2).map(|s| s.trim()) else {
+                return Err("usage: unset fact <fact_id>".to_string());
+            };
+            what_if(bundle, contract, session, |facts| {
+                unset_fact(fact_id, facts)
+            })
+            .map(Some)
+        }
+        "unset" => {
+            if parts.len() < 2 {
+                return Err("usage: unset <fact_id>".to_string());
             }
-            "flow" => {
-                if parts.len() < 3 {
-                    eprintln!("usage: flow <flow_id> <persona>");
-                    if !flows.is_empty() {
-                        eprintln!("available flows:");
-                        for f in &flows {
-                            eprintln!("  {} ({} steps, entry: {})", f.id, f.step_count, f.entry);
-                        }
+            unset_fact(parts[1], &mut session.facts).map(Some)
+        }
+        "entities" => {
+            print_entities(&session.entity_states);
+            Ok(None)
+        }
+        "eval" => run_eval(bundle, &session.facts).map(Some),
+        "flow" => {
+            if parts.len() < 3 {
+                let mut msg = "usage: flow <flow_id> <persona>".to_string();
+                if !contract.flows.is_empty() {
+                    msg.push_str("\navailable flows:");
+                    for f in &contract.flows {
+                        msg.push_str(&format!(
+                            "\n  {} ({} steps, entry: {})",
+                            f.id, f.step_count, f.entry
+                        ));
                     }
-                    continue;
                 }
-                let flow_id = parts[1];
-                let persona = parts[2];
-                run_flow(&bundle, &current_facts, flow_id, persona);
-            }
-            "operations" | "ops" => {
-                print_operations(&operations);
-            }
-            "explain" => {
-                run_explain(&bundle);
-            }
-            "reset" => {
-                current_facts.clear();
-                println!("  all facts cleared");
-            }
-            "quit" | "exit" => {
-                break;
-            }
-            _ => {
-                eprintln!(
-                    "unknown command: {}. Type 'help' for available commands.",
-                    cmd
-                );
+                return Err(msg);
             }
+            let flow_id = parts[1];
+            let persona = parts[2];
+            run_flow(bundle, session, flow_id, persona).map(Some)
+        }
+        "operations" | "ops" => {
+            print_operations(&contract.operations);
+            Ok(None)
         }
+        "explain" => {
+            run_explain(bundle)?;
+            Ok(None)
+        }
+        "save" => {
+            let path = path_arg(parts, "save <path>")?;
+            session.save(&path)?;
+            println!("  session saved to {}", path.display());
+            Ok(Some(
+                serde_json::json!({ "path": path.display().to_string() }),
+            ))
+        }
+        "load" => {
+            let path = path_arg(parts, "load <path>")?;
+            *session = Session::load(&path, contract)?;
+            println!(
+                "  session loaded from {} ({} facts, {} transcript entries)",
+                path.display(),
+                session.facts.len(),
+                session.transcript.len()
+            );
+            Ok(Some(
+                serde_json::json!({ "path": path.display().to_string() }),
+            ))
+        }
+        "export-transcript" => {
+            let path = path_arg(parts, "export-transcript <path>")?;
+            let transcript = session.transcript_export(bundle);
+            let json = serde_json::to_string_pretty(&transcript)
+                .map_err(|e| format!("failed to serialize transcript: {}", e))?;
+            std::fs::write(&path, json + "\n")
+                .map_err(|e| format!("failed to write '{}': {}", path.display(), e))?;
+            println!(
+                "  transcript ({} entries) written to {}",
+                session.transcript.len(),
+                path.display()
+            );
+            Ok(None)
+        }
+        "reset" => {
+            session.facts.clear();
+            session.entity_states = contract.initial_entity_states();
+            println!("  all facts cleared, entities reset to initial states");
+            Ok(Some(serde_json::json!({})))
+        }
+        _ => Err(format!(
+            "unknown command: {}. Type 'help' for available commands.",
+            cmd
+        )),
+    }
+}
+
+/// Outcome of one REPL command: a structured result for the transcript,
+/// or an error message.
+type CommandResult = Result<Option<serde_json::Value>, String>;
+
+/// The path argument of `save`, `load`, and `export-transcript`.
+fn path_arg(parts: &[&str], usage: &str) -> Result<PathBuf, String> {
+    if parts.len() < 2 {
+        return Err(format!("usage: {}", usage));
     }
+    Ok(PathBuf::from(parts[1..].join(" ")))
 }
 
 // ─── Data structures ─────────────────────────────────────────────────────────
@@ -251,6 +329,270 @@ struct FlowInfo {
     step_count: usize,
 }
 
+struct EntityInfo {
+    id: String,
+    initial: String,
+}
+
+/// Contract metadata the REPL needs, extracted once from the bundle.
+struct ContractInfo {
+    id: String,
+    facts: Vec<FactInfo>,
+    operations: Vec<OpInfo>,
+    flows: Vec<FlowInfo>,
+    entities: Vec<EntityInfo>,
+    personas: Vec<String>,
+}
+
+impl ContractInfo {
+    fn from_bundle(bundle: &serde_json::Value) -> Self {
+        let mut info = ContractInfo {
+            id: bundle
+                .get("id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            facts: Vec::new(),
+            operations: Vec::new(),
+            flows: Vec::new(),
+            entities: Vec::new(),
+            personas: Vec::new(),
+        };
+
+        let constructs = bundle
+            .get("constructs")
+            .and_then(|c| c.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        for c in &constructs {
+            let kind = c.get("kind").and_then(|k| k.as_str()).unwrap_or("");
+            let cid = c.get("id").and_then(|i| i.as_str()).unwrap_or("");
+            match kind {
+                "Fact" => {
+                    let type_base = c
+                        .get("type")
+                        .and_then(|t| t.get("base"))
+                        .and_then(|b| b.as_str())
+                        .unwrap_or("?");
+                    let has_default = c.get("default").is_some_and(|d| !d.is_null());
+                    info.facts.push(FactInfo {
+                        id: cid.to_string(),
+                        type_base: type_base.to_string(),
+                        has_default,
+                    });
+                }
+                "Operation" => {
+                    let personas: Vec<String> = c
+                        .get("allowed_personas")
+                        .and_then(|a| a.as_array())
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    let effects: Vec<String> = c
+                        .get("effects")
+                        .and_then(|e| e.as_array())
+                        .map(|arr| {
+                            arr.iter()
+                                .map(|eff| {
+                                    let eid = eff
+                                        .get("entity_id")
+                                        .and_then(|v| v.as_str())
+                                        .unwrap_or("?");
+                                    let from =
+                                        eff.get("from").and_then(|v| v.as_str()).unwrap_or("?");
+                                    let to = eff.get("to").and_then(|v| v.as_str()).unwrap_or("?");
+                                    format!("{}: {} -> {}", eid, from, to)
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    info.operations.push(OpInfo {
+                        id: cid.to_string(),
+                        personas,
+                        effects,
+                    });
+                }
+                "Flow" => {
+                    let entry = c
+                        .get("entry")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("?")
+                        .to_string();
+                    let step_count = c
+                        .get("steps")
+                        .and_then(|s| s.as_array())
+                        .map(|a| a.len())
+                        .unwrap_or(0);
+                    info.flows.push(FlowInfo {
+                        id: cid.to_string(),
+                        entry,
+                        step_count,
+                    });
+                }
+                "Entity" => {
+                    let initial = c
+                        .get("initial")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("?")
+                        .to_string();
+                    info.entities.push(EntityInfo {
+                        id: cid.to_string(),
+                        initial,
+                    });
+                }
+                "Persona" => info.personas.push(cid.to_string()),
+                _ => {}
+            }
+        }
+        info
+    }
+
+    /// Every entity in its initial state, as the `_default` instance.
+    fn initial_entity_states(&self) -> EntityStates {
+        self.entities
+            .iter()
+            .map(|e| {
+                let mut instances = BTreeMap::new();
+                instances.insert(
+                    tenor_eval::DEFAULT_INSTANCE_ID.to_string(),
+                    e.initial.clone(),
+                );
+                (e.id.clone(), instances)
+            })
+            .collect()
+    }
+}
+
+/// entity_id -> instance_id -> state. Nested rather than keyed by tuple so
+/// the session file is plain JSON objects.
+type EntityStates = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Everything that persists across REPL commands and, via `save`/`load`,
+/// across invocations.
+#[derive(Debug, Serialize, Deserialize)]
+struct Session {
+    contract_id: String,
+    facts: serde_json::Map<String, serde_json::Value>,
+    entity_states: EntityStates,
+    transcript: Vec<TranscriptEntry>,
+}
+
+/// One executed command in the session transcript.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TranscriptEntry {
+    seq: usize,
+    timestamp: String,
+    command: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Session {
+    fn new(contract: &ContractInfo) -> Self {
+        Session {
+            contract_id: contract.id.clone(),
+            facts: serde_json::Map::new(),
+            entity_states: contract.initial_entity_states(),
+            transcript: Vec::new(),
+        }
+    }
+
+    /// Load a saved session, rejecting sessions recorded against a
+    /// different contract. Entities added to the contract since the
+    /// session was saved start in their initial state.
+    fn load(path: &Path, contract: &ContractInfo) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read session '{}': {}", path.display(), e))?;
+        let mut session: Session = serde_json::from_str(&text)
+            .map_err(|e| format!("invalid session file '{}': {}", path.display(), e))?;
+        if session.contract_id != contract.id {
+            return Err(format!(
+                "session '{}' belongs to contract '{}', not '{}'",
+                path.display(),
+                session.contract_id,
+                contract.id
+            ));
+        }
+        for (entity_id, instances) in contract.initial_entity_states() {
+            session.entity_states.entry(entity_id).or_insert(instances);
+        }
+        Ok(session)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("failed to serialize session: {}", e))?;
+        std::fs::write(path, json + "\n")
+            .map_err(|e| format!("failed to write session '{}': {}", path.display(), e))
+    }
+
+    fn record(&mut self, command: &str, outcome: &CommandResult) {
+        let (status, result, error) = match outcome {
+            Ok(result) => ("ok", result.clone(), None),
+            Err(e) => ("error", None, Some(e.clone())),
+        };
+        self.transcript.push(TranscriptEntry {
+            seq: self.transcript.len() + 1,
+            timestamp: rfc3339_now(),
+            command: command.to_string(),
+            status: status.to_string(),
+            result,
+            error,
+        });
+    }
+
+    fn state_map(&self) -> tenor_eval::EntityStateMap {
+        self.entity_states
+            .iter()
+            .flat_map(|(entity_id, instances)| {
+                instances.iter().map(move |(instance_id, state)| {
+                    (
+                        tenor_eval::instance_key(entity_id, instance_id),
+                        state.into(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn apply_changes(&mut self, changes: &[tenor_eval::EffectRecord]) {
+        for change in changes {
+            let instances = self
+                .entity_states
+                .entry(change.entity_id.to_string())
+                .or_default();
+            if change.kind == tenor_eval::types::EffectKind::DestroyInstance {
+                instances.remove(change.instance_id.as_str());
+            } else {
+                instances.insert(change.instance_id.to_string(), change.to_state.to_string());
+            }
+        }
+    }
+
+    /// The audit record written by `export-transcript`: every command with
+    /// its outcome, pinned to the contract's etag, plus the final state.
+    fn transcript_export(&self, bundle: &serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "kind": "AgentTranscript",
+            "contract_id": self.contract_id,
+            "contract_etag": crate::manifest::compute_etag(bundle),
+            "exported_at": rfc3339_now(),
+            "entries": self.transcript,
+            "final_state": {
+                "facts": self.facts,
+                "entity_states": self.entity_states,
+            },
+        })
+    }
+}
+
 // ─── Command handlers ────────────────────────────────────────────────────────
 
 fn print_help() {
@@ -259,11 +601,17 @@ fn print_help() {
     println!("  facts                   List all facts with types and current values");
     println!("  set <fact_id> <value>   Set a fact value (JSON or bare value)");
     println!("  unset <fact_id>         Remove a fact value");
+    println!("  set fact <fact_id> <value>  Set a fact and show verdict/action changes");
+    println!("  unset fact <fact_id>    Remove a fact and show verdict/action changes");
+    println!("  entities                Show current entity states");
     println!("  eval                    Evaluate the contract with current facts");
     println!("  flow <flow_id> <persona>  Execute a flow as a persona");
     println!("  operations              List all operations with personas and effects");
     println!("  explain                 Show a plain-language explanation of the contract");
-    println!("  reset                   Clear all fact values");
+    println!("  save <path>             Save facts, entity states, and transcript");
+    println!("  load <path>             Restore a saved session");
+    println!("  export-transcript <path>  Write an audit record of this session");
+    println!("  reset                   Clear facts and reset entities to initial states");
     println!("  quit                    Exit the REPL");
     println!();
 }
@@ -290,12 +638,30 @@ fn print_facts(
     println!();
 }
 
+fn print_entities(entity_states: &EntityStates) {
+    if entity_states.is_empty() {
+        println!("  no entities declared");
+        return;
+    }
+    println!();
+    for (entity_id, instances) in entity_states {
+        for (instance_id, state) in instances {
+            if instance_id == tenor_eval::DEFAULT_INSTANCE_ID {
+                println!("  {} : {}", entity_id, state);
+            } else {
+                println!("  {}[{}] : {}", entity_id, instance_id, state);
+            }
+        }
+    }
+    println!();
+}
+
 fn set_fact(
     fact_id: &str,
     raw_value: &str,
     fact_decls: &[FactInfo],
     current_facts: &mut serde_json::Map<String, serde_json::Value>,
-) {
+) -> serde_json::Value {
     // Check if fact exists in declarations
     let fact_info = fact_decls.iter().find(|f| f.id == fact_id);
     if fact_info.is_none() {
@@ -315,6 +681,8 @@ fn set_fact(
                 // Try as integer
                 if let Ok(n) = raw_value.parse::<i64>() {
                     serde_json::Value::Number(serde_json::Number::from(n))
+                } else if let Some(money) = parse_money(raw_value) {
+                    money
                 } else {
                     // Treat as a bare string (e.g., enum value)
                     serde_json::Value::String(raw_value.to_string())
@@ -324,110 +692,351 @@ fn set_fact(
     };
 
     println!("  {} = {}", fact_id, value);
-    current_facts.insert(fact_id.to_string(), value);
+    current_facts.insert(fact_id.to_string(), value.clone());
+    serde_json::json!({ "fact": fact_id, "value": value })
+}
+
+fn unset_fact(
+    fact_id: &str,
+    current_facts: &mut serde_json::Map<String, serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    if current_facts.remove(fact_id).is_some() {
+        println!("  unset {}", fact_id);
+        Ok(serde_json::json!({ "fact": fact_id }))
+    } else {
+        Err(format!("{} was not set", fact_id))
+    }
+}
+
+/// Parse a bare `<amount> <CURRENCY>` value such as `12000 USD`.
+fn parse_money(raw_value: &str) -> Option<serde_json::Value> {
+    let mut words = raw_value.split_whitespace();
+    let (amount, currency) = (words.next()?, words.next()?);
+    if words.next().is_some()
+        || amount.parse::<f64>().is_err()
+        || currency.len() != 3
+        || !currency.chars().all(|c| c.is_ascii_uppercase())
+    {
+        return None;
+    }
+    Some(serde_json::json!({ "amount": amount, "currency": currency }))
+}
+
+// ─── What-if exploration ─────────────────────────────────────────────────────
+
+/// Verdicts and available actions at one point in the session.
+#[derive(Default)]
+struct Observation {
+    /// verdict_type -> payload
+    verdicts: BTreeMap<String, serde_json::Value>,
+    /// (persona, flow) pairs in the action space
+    actions: BTreeSet<(String, String)>,
+    /// Set when the current facts do not evaluate (e.g. a required fact
+    /// is still missing).
+    error: Option<String>,
+}
+
+fn observe(bundle: &serde_json::Value, contract: &ContractInfo, session: &Session) -> Observation {
+    let facts = serde_json::Value::Object(session.facts.clone());
+    let mut obs = Observation::default();
+    match tenor_eval::evaluate(bundle, &facts) {
+        Ok(result) => {
+            for v in &result.verdicts.0 {
+                obs.verdicts
+                    .insert(v.verdict_type.to_string(), plain_payload(&v.payload));
+            }
+        }
+        Err(e) => {
+            obs.error = Some(e.to_string());
+            return obs;
+        }
+    }
+    let Ok(typed) = tenor_eval::Contract::from_interchange(bundle) else {
+        return obs;
+    };
+    let states = session.state_map();
+    // Operations may name personas the contract never declares.
+    let personas: BTreeSet<&String> = contract
+        .personas
+        .iter()
+        .chain(contract.operations.iter().flat_map(|op| &op.personas))
+        .collect();
+    for persona in personas {
+        if let Ok(space) = tenor_eval::compute_action_space(&typed, &facts, &states, persona) {
+            for action in space.actions {
+                obs.actions.insert((persona.clone(), action.flow_id));
+            }
+        }
+    }
+    obs
+}
+
+/// What changed between two observations.
+#[derive(Debug, Default, PartialEq)]
+struct WhatIfDiff {
+    verdicts_added: Vec<(String, serde_json::Value)>,
+    verdicts_removed: Vec<(String, serde_json::Value)>,
+    verdicts_changed: Vec<(String, serde_json::Value, serde_json::Value)>,
+    actions_enabled: Vec<(String, String)>,
+    actions_disabled: Vec<(String, String)>,
+}
+
+impl WhatIfDiff {
+    fn between(before: &Observation, after: &Observation) -> Self {
+        let mut diff = WhatIfDiff::default();
+        for (verdict_type, payload) in &after.verdicts {
+            match before.verdicts.get(verdict_type) {
+                None => diff
+                    .verdicts_added
+                    .push((verdict_type.clone(), payload.clone())),
+                Some(old) if old != payload => {
+                    diff.verdicts_changed
+                        .push((verdict_type.clone(), old.clone(), payload.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        for (verdict_type, payload) in &before.verdicts {
+            if !after.verdicts.contains_key(verdict_type) {
+                diff.verdicts_removed
+                    .push((verdict_type.clone(), payload.clone()));
+            }
+        }
+        diff.actions_enabled = after.actions.difference(&before.actions).cloned().collect();
+        diff.actions_disabled = before.actions.difference(&after.actions).cloned().collect();
+        diff
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == WhatIfDiff::default()
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let verdicts = |list: &[(String, serde_json::Value)]| -> Vec<serde_json::Value> {
+            list.iter()
+                .map(|(t, p)| serde_json::json!({ "verdict_type": t, "payload": p }))
+                .collect()
+        };
+        let actions = |list: &[(String, String)]| -> Vec<serde_json::Value> {
+            list.iter()
+                .map(|(persona, flow)| serde_json::json!({ "persona": persona, "flow": flow }))
+                .collect()
+        };
+        serde_json::json!({
+            "verdicts_added": verdicts(&self.verdicts_added),
+            "verdicts_removed": verdicts(&self.verdicts_removed),
+            "verdicts_changed": self.verdicts_changed.iter().map(|(t, from, to)| {
+                serde_json::json!({ "verdict_type": t, "from": from, "to": to })
+            }).collect::<Vec<_>>(),
+            "actions_enabled": actions(&self.actions_enabled),
+            "actions_disabled": actions(&self.actions_disabled),
+        })
+    }
+}
+
+/// Apply a fact mutation, re-evaluate, and print which verdicts and
+/// actions appeared, disappeared, or changed.
+fn what_if(
+    bundle: &serde_json::Value,
+    contract: &ContractInfo,
+    session: &mut Session,
+    mutate: impl FnOnce(
+        &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<serde_json::Value, String>,
+) -> Result<serde_json::Value, String> {
+    let before = observe(bundle, contract, session);
+    let mut result = mutate(&mut session.facts)?;
+    let after = observe(bundle, contract, session);
+
+    if let Some(e) = &after.error {
+        println!("  evaluation incomplete: {}", e);
+        result["evaluation_error"] = serde_json::Value::String(e.clone());
+        return Ok(result);
+    }
+
+    let diff = WhatIfDiff::between(&before, &after);
+    let color = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let paint = |code: &str, text: String| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text
+        }
+    };
+    if diff.is_empty() {
+        println!("  no verdict or action changes");
+    }
+    for (t, p) in &diff.verdicts_added {
+        println!("  {}", paint("32", format!("+ verdict {} = {}", t, p)));
+    }
+    for (t, p) in &diff.verdicts_removed {
+        println!("  {}", paint("31", format!("- verdict {} = {}", t, p)));
+    }
+    for (t, from, to) in &diff.verdicts_changed {
+        println!(
+            "  {}",
+            paint("33", format!("~ verdict {}: {} -> {}", t, from, to))
+        );
+    }
+    for (persona, flow) in &diff.actions_enabled {
+        println!(
+            "  {}",
+            paint("32", format!("+ action {} can start {}", persona, flow))
+        );
+    }
+    for (persona, flow) in &diff.actions_disabled {
+        println!(
+            "  {}",
+            paint(
+                "31",
+                format!("- action {} can no longer start {}", persona, flow)
+            )
+        );
+    }
+
+    result["diff"] = diff.to_json();
+    Ok(result)
+}
+
+fn verdicts_json(verdicts: &[tenor_eval::VerdictInstance]) -> serde_json::Value {
+    verdicts
+        .iter()
+        .map(|v| {
+            serde_json::json!({
+                "verdict_type": v.verdict_type,
+                "payload": v.payload.to_json(),
+                "rule": v.provenance.rule_id,
+                "stratum": v.provenance.stratum,
+            })
+        })
+        .collect()
 }
 
 fn run_eval(
     bundle: &serde_json::Value,
     current_facts: &serde_json::Map<String, serde_json::Value>,
-) {
+) -> Result<serde_json::Value, String> {
     let facts_json = serde_json::Value::Object(current_facts.clone());
 
-    match tenor_eval::evaluate(bundle, &facts_json) {
-        Ok(result) => {
-            let verdicts = &result.verdicts.0;
-            if verdicts.is_empty() {
-                println!("  no verdicts produced");
-            } else {
-                println!();
-                println!("  {} verdict(s) produced:", verdicts.len());
-                for v in verdicts {
-                    println!(
-                        "  [{}] {} (rule: {}, stratum: {})",
-                        v.verdict_type,
-                        format_payload(&v.payload),
-                        v.provenance.rule_id,
-                        v.provenance.stratum,
-                    );
-                    if !v.provenance.facts_used.is_empty() {
-                        println!("    facts used: {}", v.provenance.facts_used.join(", "));
-                    }
-                    if !v.provenance.verdicts_used.is_empty() {
-                        println!(
-                            "    verdicts used: {}",
-                            v.provenance.verdicts_used.join(", ")
-                        );
-                    }
-                }
-                println!();
+    let result = tenor_eval::evaluate(bundle, &facts_json)
+        .map_err(|e| format!("evaluation error: {}", e))?;
+    let verdicts = &result.verdicts.0;
+    if verdicts.is_empty() {
+        println!("  no verdicts produced");
+    } else {
+        println!();
+        println!("  {} verdict(s) produced:", verdicts.len());
+        for v in verdicts {
+            println!(
+                "  [{}] {} (rule: {}, stratum: {})",
+                v.verdict_type,
+                format_payload(&v.payload),
+                v.provenance.rule_id,
+                v.provenance.stratum,
+            );
+            if !v.provenance.facts_used.is_empty() {
+                println!("    facts used: {}", v.provenance.facts_used.join(", "));
+            }
+            if !v.provenance.verdicts_used.is_empty() {
+                println!(
+                    "    verdicts used: {}",
+                    v.provenance.verdicts_used.join(", ")
+                );
             }
         }
-        Err(e) => {
-            eprintln!("  evaluation error: {}", e);
-        }
+        println!();
     }
+    Ok(serde_json::json!({ "verdicts": verdicts_json(verdicts) }))
 }
 
+/// Execute a flow against the session's current entity states, then carry
+/// the resulting state changes forward into the session.
 fn run_flow(
     bundle: &serde_json::Value,
-    current_facts: &serde_json::Map<String, serde_json::Value>,
+    session: &mut Session,
     flow_id: &str,
     persona: &str,
-) {
-    let facts_json = serde_json::Value::Object(current_facts.clone());
+) -> Result<serde_json::Value, String> {
+    let facts_json = serde_json::Value::Object(session.facts.clone());
 
-    match tenor_eval::evaluate_flow(
+    let result = tenor_eval::evaluate_flow(
         bundle,
         &facts_json,
         flow_id,
         persona,
-        None,
+        Some(&session.state_map()),
         &tenor_eval::InstanceBindingMap::new(),
-    ) {
-        Ok(result) => {
-            println!();
-            println!("  Flow: {}", flow_id);
-            println!("  Outcome: {}", result.flow_result.outcome);
-            if let Some(ref p) = result.flow_result.initiating_persona {
-                println!("  Persona: {}", p);
-            }
+    )
+    .map_err(|e| format!("flow evaluation error: {}", e))?;
 
-            if !result.flow_result.steps_executed.is_empty() {
-                println!(
-                    "  Steps executed: {}",
-                    result.flow_result.steps_executed.len()
-                );
-                for s in &result.flow_result.steps_executed {
-                    println!("    {} -> {}", s.step_id, s.result);
-                }
-            }
+    println!();
+    println!("  Flow: {}", flow_id);
+    println!("  Outcome: {}", result.flow_result.outcome);
+    if let Some(ref p) = result.flow_result.initiating_persona {
+        println!("  Persona: {}", p);
+    }
 
-            if !result.flow_result.entity_state_changes.is_empty() {
-                println!("  Entity state changes:");
-                for e in &result.flow_result.entity_state_changes {
-                    println!("    {} : {} -> {}", e.entity_id, e.from_state, e.to_state);
-                }
-            }
+    if !result.flow_result.steps_executed.is_empty() {
+        println!(
+            "  Steps executed: {}",
+            result.flow_result.steps_executed.len()
+        );
+        for s in &result.flow_result.steps_executed {
+            println!("    {} -> {}", s.step_id, s.result);
+        }
+    }
 
-            let verdicts = &result.verdicts.0;
-            if !verdicts.is_empty() {
-                println!("  {} verdict(s):", verdicts.len());
-                for v in verdicts {
-                    println!(
-                        "    [{}] {} (rule: {}, stratum: {})",
-                        v.verdict_type,
-                        format_payload(&v.payload),
-                        v.provenance.rule_id,
-                        v.provenance.stratum,
-                    );
-                }
-            }
-            println!();
+    if !result.flow_result.entity_state_changes.is_empty() {
+        println!("  Entity state changes:");
+        for e in &result.flow_result.entity_state_changes {
+            println!("    {} : {} -> {}", e.entity_id, e.from_state, e.to_state);
         }
-        Err(e) => {
-            eprintln!("  flow evaluation error: {}", e);
+    }
+
+    let verdicts = &result.verdicts.0;
+    if !verdicts.is_empty() {
+        println!("  {} verdict(s):", verdicts.len());
+        for v in verdicts {
+            println!(
+                "    [{}] {} (rule: {}, stratum: {})",
+                v.verdict_type,
+                format_payload(&v.payload),
+                v.provenance.rule_id,
+                v.provenance.stratum,
+            );
         }
     }
+    println!();
+
+    session.apply_changes(&result.flow_result.entity_state_changes);
+
+    let steps: Vec<serde_json::Value> = result
+        .flow_result
+        .steps_executed
+        .iter()
+        .map(|s| serde_json::json!({ "step_id": s.step_id, "result": s.result }))
+        .collect();
+    let changes: Vec<serde_json::Value> = result
+        .flow_result
+        .entity_state_changes
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "entity_id": e.entity_id,
+                "instance_id": e.instance_id,
+                "from": e.from_state,
+                "to": e.to_state,
+            })
+        })
+        .collect();
+    Ok(serde_json::json!({
+        "flow": flow_id,
+        "persona": persona,
+        "outcome": result.flow_result.outcome,
+        "steps": steps,
+        "entity_state_changes": changes,
+        "verdicts": verdicts_json(verdicts),
+    }))
 }
 
 fn print_operations(operations: &[OpInfo]) {
@@ -446,16 +1055,12 @@ fn print_operations(operations: &[OpInfo]) {
     println!();
 }
 
-fn run_explain(bundle: &serde_json::Value) {
-    match explain::explain(bundle, explain::ExplainFormat::Terminal, false) {
-        Ok(result) => {
-            println!();
-            print!("{}", result);
-        }
-        Err(e) => {
-            eprintln!("  explain error: {}", e);
-        }
-    }
+fn run_explain(bundle: &serde_json::Value) -> Result<(), String> {
+    let result = explain::explain(bundle, explain::ExplainFormat::Terminal, false)
+        .map_err(|e| format!("explain error: {}", e))?;
+    println!();
+    print!("{}", result);
+    Ok(())
 }
 
 /// Format a verdict payload for display.
@@ -470,3 +1075,164 @@ fn format_payload(v: &tenor_eval::Value) -> String {
         _ => format!("{:?}", v),
     }
 }
+
+/// A verdict payload as a bare JSON value where one exists, so what-if
+/// output reads `= true` rather than the tagged interchange form.
+fn plain_payload(v: &tenor_eval::Value) -> serde_json::Value {
+    match v {
+        tenor_eval::Value::Bool(b) => serde_json::json!(b),
+        tenor_eval::Value::Int(i) => serde_json::json!(i),
+        tenor_eval::Value::Decimal(d) => serde_json::json!(d.to_string()),
+        tenor_eval::Value::Text(t) | tenor_eval::Value::Enum(t) => serde_json::json!(t),
+        other => other.to_json(),
+    }
+}
+
+/// Current UTC time as an RFC 3339 timestamp (second precision).
+fn rfc3339_now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    rfc3339(secs)
+}
+
+fn rfc3339(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let rem = unix_secs % 86_400;
+    // Civil-from-days (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        (rem % 3_600) / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc3339_formats_known_instants() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_700_000_000), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn parse_money_accepts_amount_and_currency() {
+        assert_eq!(
+            parse_money("12000 USD"),
+            Some(serde_json::json!({ "amount": "12000", "currency": "USD" }))
+        );
+        assert_eq!(
+            parse_money("99.50 EUR"),
+            Some(serde_json::json!({ "amount": "99.50", "currency": "EUR" }))
+        );
+        assert_eq!(parse_money("12000 usd"), None);
+        assert_eq!(parse_money("many USD"), None);
+        assert_eq!(parse_money("1 USD extra"), None);
+    }
+
+    #[test]
+    fn what_if_diff_classifies_verdicts_and_actions() {
+        let before = Observation {
+            verdicts: [
+                ("kept".to_string(), serde_json::json!(true)),
+                ("gone".to_string(), serde_json::json!(1)),
+                ("tier".to_string(), serde_json::json!("gold")),
+            ]
+            .into_iter()
+            .collect(),
+            actions: [("admin".to_string(), "old_flow".to_string())]
+                .into_iter()
+                .collect(),
+            error: None,
+        };
+        let after = Observation {
+            verdicts: [
+                ("kept".to_string(), serde_json::json!(true)),
+                ("tier".to_string(), serde_json::json!("platinum")),
+                ("new".to_string(), serde_json::json!(false)),
+            ]
+            .into_iter()
+            .collect(),
+            actions: [("admin".to_string(), "new_flow".to_string())]
+                .into_iter()
+                .collect(),
+            error: None,
+        };
+        let diff = WhatIfDiff::between(&before, &after);
+        assert_eq!(
+            diff.verdicts_added,
+            vec![("new".to_string(), serde_json::json!(false))]
+        );
+        assert_eq!(
+            diff.verdicts_removed,
+            vec![("gone".to_string(), serde_json::json!(1))]
+        );
+        assert_eq!(
+            diff.verdicts_changed,
+            vec![(
+                "tier".to_string(),
+                serde_json::json!("gold"),
+                serde_json::json!("platinum")
+            )]
+        );
+        assert_eq!(
+            diff.actions_enabled,
+            vec![("admin".to_string(), "new_flow".to_string())]
+        );
+        assert_eq!(
+            diff.actions_disabled,
+            vec![("admin".to_string(), "old_flow".to_string())]
+        );
+        assert!(WhatIfDiff::between(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn session_applies_flow_changes_and_round_trips() {
+        let contract = ContractInfo {
+            id: "c".to_string(),
+            facts: Vec::new(),
+            operations: Vec::new(),
+            flows: Vec::new(),
+            entities: vec![EntityInfo {
+                id: "Order".to_string(),
+                initial: "draft".to_string(),
+            }],
+            personas: Vec::new(),
+        };
+        let mut session = Session::new(&contract);
+        session.apply_changes(&[tenor_eval::EffectRecord {
+            entity_id: "Order".into(),
+            instance_id: tenor_eval::DEFAULT_INSTANCE_ID.into(),
+            kind: tenor_eval::types::EffectKind::Transition,
+            from_state: "draft".into(),
+            to_state: "submitted".into(),
+            attributes: Default::default(),
+        }]);
+        let map = session.state_map();
+        assert_eq!(
+            tenor_eval::get_instance_state(&map, "Order", "_default").map(|s| s.as_str()),
+            Some("submitted")
+        );
+
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.entity_states, session.entity_states);
+    }
+}
@@ -1,14 +1,11 @@
-//! Anthropic Messages API client with retry and rate-limit handling.
+//! LLM calls for ambiguity testing, with retry and rate-limit handling.
+//!
+//! Requests go to the configured [`LlmBackend`] (Anthropic, an
+//! OpenAI-compatible server, or a local Ollama).
 
-use serde::{Deserialize, Serialize};
+use crate::llm::{Completion, LlmBackend};
 
-/// Anthropic Messages API endpoint.
-const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
-
-/// Required API version header value.
-const ANTHROPIC_VERSION: &str = "2023-06-01";
-
-/// Default model to use for ambiguity testing.
+/// Default model when the Anthropic backend is used.
 const DEFAULT_MODEL: &str = "claude-sonnet-4-5";
 
 /// Default maximum retries for transient errors.
@@ -17,108 +14,31 @@ const DEFAULT_MAX_RETRIES: u32 = 3;
 /// Initial backoff delay in milliseconds (doubles each retry).
 const INITIAL_BACKOFF_MS: u64 = 1000;
 
-// ── Request / Response types ─────────────────────────────────────────────────
-
-#[derive(Serialize)]
-struct MessagesRequest {
-    model: String,
-    max_tokens: u32,
-    system: String,
-    messages: Vec<Message>,
-}
-
-#[derive(Serialize)]
-struct Message {
-    role: String,
-    content: String,
-}
-
-#[derive(Deserialize)]
-struct MessagesResponse {
-    content: Vec<ContentBlock>,
-    #[allow(dead_code)] // Deserialized from API response; required by serde for correct parsing
-    stop_reason: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct ContentBlock {
-    #[allow(dead_code)] // Deserialized from API response; required by serde for correct parsing
-    #[serde(rename = "type")]
-    block_type: String,
-    text: Option<String>,
-}
-
 // ── Public API ───────────────────────────────────────────────────────────────
 
-/// Read the Anthropic API key from the `ANTHROPIC_API_KEY` environment variable.
-pub fn get_api_key() -> Result<String, String> {
-    std::env::var("ANTHROPIC_API_KEY").map_err(|_| {
-        "ANTHROPIC_API_KEY environment variable is not set. \
-         Set it to your Anthropic API key to run ambiguity tests."
-            .to_string()
-    })
-}
-
-/// Return the default model identifier.
+/// Return the default model identifier for the Anthropic backend.
 pub fn default_model() -> &'static str {
     DEFAULT_MODEL
 }
 
-/// Call the Anthropic Messages API with the given system prompt, user prompt,
-/// and model. Returns the text content from the first content block.
+/// Send the system and user prompts to `model` on `backend`. Returns the
+/// reply and its token usage.
 ///
 /// Retries on 429 (rate limit), 500, and 503 errors with exponential backoff.
-pub fn call_anthropic(
-    api_key: &str,
+pub fn call_llm(
+    backend: &LlmBackend,
     system: &str,
     user_prompt: &str,
     model: &str,
-) -> Result<String, String> {
+) -> Result<Completion, String> {
     with_retry(
-        || call_anthropic_once(api_key, system, user_prompt, model),
+        || backend.complete(model, system, user_prompt),
         DEFAULT_MAX_RETRIES,
     )
 }
 
 // ── Internal ─────────────────────────────────────────────────────────────────
 
-/// Make a single API call (no retry).
-fn call_anthropic_once(
-    api_key: &str,
-    system: &str,
-    user_prompt: &str,
-    model: &str,
-) -> Result<String, String> {
-    let request_body = MessagesRequest {
-        model: model.to_string(),
-        max_tokens: 4096,
-        system: system.to_string(),
-        messages: vec![Message {
-            role: "user".to_string(),
-            content: user_prompt.to_string(),
-        }],
-    };
-
-    let agent = ureq::Agent::new_with_defaults();
-    let response = agent
-        .post(ANTHROPIC_API_URL)
-        .header("x-api-key", api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("content-type", "application/json")
-        .send_json(&request_body)
-        .map_err(|e| format!("API request failed: {}", e))?;
-
-    let resp: MessagesResponse = response
-        .into_body()
-        .read_json()
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
-
-    resp.content
-        .first()
-        .and_then(|block| block.text.clone())
-        .ok_or_else(|| "API response contained no text content".to_string())
-}
-
 /// Retry a fallible operation with exponential backoff.
 ///
 /// Retries only on errors deemed retryable (429, 500, 503).
@@ -0,0 +1,128 @@
+//! JSON record of an ambiguity run.
+//!
+//! Each run writes the model, prompts, raw LLM responses, per-case
+//! outcomes and token usage to an artifact. `tenor ambiguity --replay`
+//! re-scores the recorded responses against the current expected
+//! verdicts without calling the model again, so ambiguity regressions
+//! can be compared release over release.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::llm::TokenUsage;
+
+/// A recorded ambiguity run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunArtifact {
+    /// Version of the `tenor` binary that made the run.
+    pub tenor_version: String,
+    /// LLM provider and endpoint (`anthropic at https://...`).
+    pub provider: String,
+    pub model: String,
+    /// Sampling seed requested, if any.
+    pub seed: Option<u64>,
+    pub system_prompt: String,
+    pub cases: Vec<CaseRecord>,
+    /// Token usage summed over the cases that reported it.
+    pub usage: TokenUsage,
+    pub summary: RunSummary,
+}
+
+/// One test case: its prompt, the raw response and how it scored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseRecord {
+    pub name: String,
+    pub user_prompt: String,
+    pub expected_verdicts: Vec<String>,
+    /// Raw response text; absent when the API call failed.
+    pub response: Option<String>,
+    pub usage: Option<TokenUsage>,
+    pub outcome: CaseOutcome,
+    /// Verdicts parsed from the response.
+    #[serde(default)]
+    pub actual_verdicts: Vec<String>,
+    /// API or parse error, for `error` outcomes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// How a case scored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseOutcome {
+    Match,
+    Mismatch,
+    Error,
+}
+
+/// Counts over a run's cases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub total: usize,
+    pub matches: usize,
+    pub mismatches: usize,
+    pub hard_errors: usize,
+}
+
+impl RunArtifact {
+    /// Read an artifact written by a previous run.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read artifact {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid artifact {}: {}", path.display(), e))
+    }
+
+    /// Write the artifact as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Cannot serialize artifact: {}", e))?;
+        std::fs::write(path, json + "\n")
+            .map_err(|e| format!("Cannot write artifact {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifact_round_trips() {
+        let artifact = RunArtifact {
+            tenor_version: "1.0.0".to_string(),
+            provider: "ollama at http://localhost:11434".to_string(),
+            model: "llama3.1".to_string(),
+            seed: Some(42),
+            system_prompt: "system".to_string(),
+            cases: vec![CaseRecord {
+                name: "rule_basic_all_satisfied".to_string(),
+                user_prompt: "user".to_string(),
+                expected_verdicts: vec!["a".to_string()],
+                response: None,
+                usage: None,
+                outcome: CaseOutcome::Error,
+                actual_verdicts: Vec::new(),
+                error: Some("API request failed".to_string()),
+            }],
+            usage: TokenUsage::default(),
+            summary: RunSummary {
+                total: 1,
+                hard_errors: 1,
+                ..RunSummary::default()
+            },
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.json");
+        artifact.write(&path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["cases"][0]["outcome"], "error");
+        assert_eq!(json["usage"]["input_tokens"], 0);
+
+        let loaded = RunArtifact::load(&path).unwrap();
+        assert_eq!(loaded.seed, Some(42));
+        assert_eq!(loaded.cases[0].outcome, CaseOutcome::Error);
+        assert_eq!(loaded.summary, artifact.summary);
+    }
+}
@@ -5,14 +5,17 @@
 //! the same verdicts as the reference elaborator.
 
 pub mod api;
+pub mod artifact;
 pub mod compare;
 pub mod fixtures;
 pub mod prompt;
 pub mod report;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
+use artifact::{CaseOutcome, CaseRecord, RunArtifact, RunSummary};
+
 /// A single ambiguity test case: contract + facts + expected verdicts.
 pub struct AmbiguityTestCase {
     pub name: String,
@@ -44,32 +47,59 @@ pub struct AmbiguityRunResult {
     pub hard_errors: usize,
 }
 
+impl From<RunSummary> for AmbiguityRunResult {
+    fn from(summary: RunSummary) -> Self {
+        AmbiguityRunResult {
+            total: summary.total,
+            matches: summary.matches,
+            mismatches: summary.mismatches,
+            hard_errors: summary.hard_errors,
+        }
+    }
+}
+
+/// Options for a live ambiguity run.
+pub struct AmbiguityRunOptions<'a> {
+    /// LLM model; defaults to the configured or provider's model.
+    pub model: Option<&'a str>,
+    /// Sampling seed, for providers that accept one.
+    pub seed: Option<u64>,
+    /// Where to write the run artifact.
+    pub artifact: &'a Path,
+}
+
 /// Run the ambiguity test suite end-to-end.
 ///
-/// 1. Checks for ANTHROPIC_API_KEY (skips gracefully if absent).
+/// 1. Resolves the LLM backend (skips gracefully if none is configured).
 /// 2. Loads test cases from `suite_dir/ambiguity/`.
 /// 3. Builds prompts from the spec at `spec_path`.
-/// 4. Calls the Anthropic API for each test case.
+/// 4. Calls the LLM for each test case.
 /// 5. Compares LLM verdicts against expected ground truth.
-/// 6. Prints TAP report to stdout.
+/// 6. Prints TAP report to stdout and writes the run artifact.
 ///
 /// Returns an `AmbiguityRunResult` with match/mismatch/error counts.
 pub fn run_ambiguity_suite(
     suite_dir: &Path,
     spec_path: &Path,
-    model: Option<&str>,
+    options: &AmbiguityRunOptions,
 ) -> AmbiguityRunResult {
-    // 1. Check for API key
-    let api_key = match api::get_api_key() {
-        Ok(key) => key,
-        Err(_) => {
-            eprintln!("# Skipping ambiguity tests: ANTHROPIC_API_KEY not set");
-            return AmbiguityRunResult {
-                total: 0,
-                matches: 0,
-                mismatches: 0,
-                hard_errors: 0,
-            };
+    // 1. Resolve the LLM backend
+    let backend = match crate::llm::LlmBackend::from_env() {
+        Ok(Some(backend)) => backend.with_seed(options.seed),
+        Ok(None) => {
+            eprintln!(
+                "# Skipping ambiguity tests: no LLM provider configured \
+                 (set ANTHROPIC_API_KEY or TENOR_LLM_PROVIDER)"
+            );
+            return RunSummary::default().into();
+        }
+        Err(e) => {
+            eprintln!("# LLM configuration error: {}", e);
+            return RunSummary {
+                hard_errors: 1,
+                ..RunSummary::default()
+            }
+            .into();
         }
     };
 
@@ -80,24 +110,18 @@ pub fn run_ambiguity_suite(
             "# No ambiguity test cases found in {}/ambiguity",
             suite_dir.display()
         );
-        return AmbiguityRunResult {
-            total: 0,
-            matches: 0,
-            mismatches: 0,
-            hard_errors: 0,
-        };
+        return RunSummary::default().into();
     }
 
     let test_cases = match fixtures::load_test_cases(&ambiguity_dir, suite_dir) {
         Ok(cases) => cases,
         Err(e) => {
             eprintln!("# Error loading test cases: {}", e);
-            return AmbiguityRunResult {
-                total: 0,
-                matches: 0,
-                mismatches: 0,
+            return RunSummary {
                 hard_errors: 1,
-            };
+                ..RunSummary::default()
+            }
+            .into();
         }
     };
 
@@ -106,12 +130,7 @@ pub fn run_ambiguity_suite(
             "# No ambiguity test cases found in {}/ambiguity",
             suite_dir.display()
         );
-        return AmbiguityRunResult {
-            total: 0,
-            matches: 0,
-            mismatches: 0,
-            hard_errors: 0,
-        };
+        return RunSummary::default().into();
     }
 
     // 3. Build system prompt from spec
@@ -119,92 +138,291 @@ pub fn run_ambiguity_suite(
         Ok(p) => p,
         Err(e) => {
             eprintln!("# Error reading spec file: {}", e);
-            return AmbiguityRunResult {
+            return RunSummary {
                 total: test_cases.len(),
-                matches: 0,
-                mismatches: 0,
                 hard_errors: 1,
-            };
+                ..RunSummary::default()
+            }
+            .into();
         }
     };
 
-    let model_name = model.unwrap_or_else(|| api::default_model());
+    let model_name = backend.model(options.model, api::default_model());
 
-    // 4. Initialize report
+    // 4. Call the LLM for each test case and score the response
     let mut report = report::AmbiguityReport::new();
-    let mut hard_errors: usize = 0;
+    let mut cases = Vec::with_capacity(test_cases.len());
     let total = test_cases.len();
 
-    // 5. Process each test case
     for (i, test) in test_cases.iter().enumerate() {
-        // a. Build user prompt
         let user_prompt = prompt::build_user_prompt(&test.contract_source, &test.facts);
-
-        // b. Call API
-        let response_text =
-            match api::call_anthropic(&api_key, &system_prompt, &user_prompt, model_name) {
-                Ok(text) => text,
-                Err(e) => {
-                    eprintln!("# [{}/{}] {} -- API ERROR: {}", i + 1, total, test.name, e);
-                    hard_errors += 1;
-                    continue;
-                }
+        let (response, usage, error) =
+            match api::call_llm(&backend, &system_prompt, &user_prompt, model_name) {
+                Ok(completion) => (Some(completion.text), completion.usage, None),
+                Err(e) => (None, None, Some(e)),
             };
 
-        // c. Parse LLM response
-        let llm_response = match compare::parse_llm_response(&response_text) {
-            Ok(resp) => resp,
+        let mut case = CaseRecord {
+            name: test.name.clone(),
+            user_prompt,
+            expected_verdicts: test.expected_verdicts.clone(),
+            response,
+            usage,
+            outcome: CaseOutcome::Error,
+            actual_verdicts: Vec::new(),
+            error,
+        };
+        score_case(&mut case, i, total, &mut report);
+        cases.push(case);
+    }
+
+    // 5. Print TAP report
+    report.print_tap();
+
+    // 6. Write the artifact
+    let mut summary = summarize(&cases);
+    let artifact = RunArtifact {
+        tenor_version: env!("CARGO_PKG_VERSION").to_string(),
+        provider: backend.describe(),
+        model: model_name.to_string(),
+        seed: options.seed,
+        system_prompt,
+        usage: cases.iter().filter_map(|c| c.usage).fold(
+            crate::llm::TokenUsage::default(),
+            |mut sum, usage| {
+                sum += usage;
+                sum
+            },
+        ),
+        cases,
+        summary,
+    };
+    match artifact.write(options.artifact) {
+        Ok(()) => eprintln!("# Wrote run artifact to {}", options.artifact.display()),
+        Err(e) => {
+            eprintln!("# {}", e);
+            summary.hard_errors += 1;
+        }
+    }
+
+    summary.into()
+}
+
+/// Re-score the responses recorded in an artifact without calling the LLM.
+///
+/// Expected verdicts come from the current fixtures in `suite_dir/ambiguity/`
+/// where a case still exists there, so a recorded run can be checked against
+/// updated ground truth; other cases keep their recorded expectations. The
+/// re-scored artifact is written to `out` when given.
+pub fn replay_ambiguity_run(
+    artifact_path: &Path,
+    suite_dir: &Path,
+    out: Option<&Path>,
+) -> AmbiguityRunResult {
+    let mut artifact = match RunArtifact::load(artifact_path) {
+        Ok(artifact) => artifact,
+        Err(e) => {
+            eprintln!("# {}", e);
+            return RunSummary {
+                hard_errors: 1,
+                ..RunSummary::default()
+            }
+            .into();
+        }
+    };
+
+    let current: BTreeMap<String, Vec<String>> =
+        fixtures::load_test_cases(&suite_dir.join("ambiguity"), suite_dir)
+            .map(|cases| {
+                cases
+                    .into_iter()
+                    .map(|case| (case.name, case.expected_verdicts))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+    eprintln!(
+        "# Replaying {} cases from {} (model: {}, recorded with tenor {})",
+        artifact.cases.len(),
+        artifact_path.display(),
+        artifact.model,
+        artifact.tenor_version
+    );
+
+    let mut report = report::AmbiguityReport::new();
+    let total = artifact.cases.len();
+    for (i, case) in artifact.cases.iter_mut().enumerate() {
+        if let Some(expected) = current.get(&case.name) {
+            case.expected_verdicts = expected.clone();
+        }
+        score_case(case, i, total, &mut report);
+    }
+    report.print_tap();
+
+    let mut summary = summarize(&artifact.cases);
+    artifact.summary = summary;
+    if let Some(out) = out {
+        match artifact.write(out) {
+            Ok(()) => eprintln!("# Wrote run artifact to {}", out.display()),
             Err(e) => {
-                eprintln!(
-                    "# [{}/{}] {} -- PARSE ERROR: {}",
-                    i + 1,
-                    total,
-                    test.name,
-                    e
-                );
-                hard_errors += 1;
-                continue;
+                eprintln!("# {}", e);
+                summary.hard_errors += 1;
             }
-        };
+        }
+    }
 
-        // d. Compare verdicts
-        let comparison =
-            compare::compare_verdicts(&test.expected_verdicts, &llm_response.verdicts_produced);
+    summary.into()
+}
 
-        let status = if comparison.is_match {
-            "match"
-        } else {
-            "MISMATCH"
-        };
+/// Parse a case's recorded response, compare it with the expected verdicts,
+/// and record the outcome on the case and in the report.
+fn score_case(
+    case: &mut CaseRecord,
+    index: usize,
+    total: usize,
+    report: &mut report::AmbiguityReport,
+) {
+    let label = format!("# [{}/{}] {}", index + 1, total, case.name);
 
-        eprintln!("# [{}/{}] {} -- {}", i + 1, total, test.name, status);
+    let Some(response_text) = &case.response else {
+        case.outcome = CaseOutcome::Error;
+        eprintln!(
+            "{} -- API ERROR: {}",
+            label,
+            case.error.as_deref().unwrap_or("no response recorded")
+        );
+        return;
+    };
 
-        // e. Build AmbiguityResult
-        let expected_set: BTreeSet<String> = test.expected_verdicts.iter().cloned().collect();
-        let llm_set: BTreeSet<String> = llm_response.verdicts_produced.iter().cloned().collect();
+    let llm_response = match compare::parse_llm_response(response_text) {
+        Ok(resp) => resp,
+        Err(e) => {
+            eprintln!("{} -- PARSE ERROR: {}", label, e);
+            case.outcome = CaseOutcome::Error;
+            case.actual_verdicts.clear();
+            case.error = Some(e);
+            return;
+        }
+    };
 
-        let result = AmbiguityResult {
-            test_name: test.name.clone(),
-            expected_verdicts: expected_set,
-            llm_verdicts: llm_set,
-            llm_reasoning: llm_response.reasoning,
-            ambiguities_noted: llm_response.ambiguities_noted,
-            confidence: llm_response.confidence,
-        };
+    let comparison =
+        compare::compare_verdicts(&case.expected_verdicts, &llm_response.verdicts_produced);
+    let llm_set: BTreeSet<String> = llm_response.verdicts_produced.iter().cloned().collect();
+
+    case.outcome = if comparison.is_match {
+        CaseOutcome::Match
+    } else {
+        CaseOutcome::Mismatch
+    };
+    case.actual_verdicts = llm_set.iter().cloned().collect();
+    case.error = None;
+
+    let status = if comparison.is_match {
+        "match"
+    } else {
+        "MISMATCH"
+    };
+    eprintln!("{} -- {}", label, status);
+
+    report.add_result(AmbiguityResult {
+        test_name: case.name.clone(),
+        expected_verdicts: case.expected_verdicts.iter().cloned().collect(),
+        llm_verdicts: llm_set,
+        llm_reasoning: llm_response.reasoning,
+        ambiguities_noted: llm_response.ambiguities_noted,
+        confidence: llm_response.confidence,
+    });
+}
+
+/// Count the outcomes of a run's cases.
+fn summarize(cases: &[CaseRecord]) -> RunSummary {
+    let count = |outcome| cases.iter().filter(|c| c.outcome == outcome).count();
+    RunSummary {
+        total: cases.len(),
+        matches: count(CaseOutcome::Match),
+        mismatches: count(CaseOutcome::Mismatch),
+        hard_errors: count(CaseOutcome::Error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::TokenUsage;
 
-        // f. Add to report
-        report.add_result(result);
+    fn case(name: &str, response: Option<&str>) -> CaseRecord {
+        CaseRecord {
+            name: name.to_string(),
+            user_prompt: "prompt".to_string(),
+            expected_verdicts: vec!["approved".to_string()],
+            response: response.map(str::to_string),
+            usage: Some(TokenUsage {
+                input_tokens: 100,
+                output_tokens: 20,
+            }),
+            outcome: CaseOutcome::Error,
+            actual_verdicts: Vec::new(),
+            error: response.is_none().then(|| "API request failed".to_string()),
+        }
     }
 
-    // 6. Print TAP report
-    report.print_tap();
+    #[test]
+    fn replay_rescores_against_current_fixtures() {
+        let suite = tempfile::tempdir().unwrap();
+        let ambiguity_dir = suite.path().join("ambiguity");
+        let positive_dir = suite.path().join("positive");
+        std::fs::create_dir_all(&ambiguity_dir).unwrap();
+        std::fs::create_dir_all(&positive_dir).unwrap();
+        std::fs::write(positive_dir.join("rule_basic.tenor"), "contract").unwrap();
+        std::fs::write(ambiguity_dir.join("rule_basic_ok.facts.json"), "{}").unwrap();
+        // Ground truth changed since the run: `approved` is no longer produced.
+        std::fs::write(
+            ambiguity_dir.join("rule_basic_ok.verdicts.json"),
+            r#"{"verdicts": []}"#,
+        )
+        .unwrap();
+
+        let verdicts = |v: &str| {
+            format!(
+                r#"{{"verdicts_produced":[{}],"reasoning":"r","confidence":"high","ambiguities_noted":[]}}"#,
+                v
+            )
+        };
+        let artifact = RunArtifact {
+            tenor_version: "0.0.0".to_string(),
+            provider: "ollama at http://localhost:11434".to_string(),
+            model: "llama3.1".to_string(),
+            seed: None,
+            system_prompt: "system".to_string(),
+            cases: vec![
+                case("rule_basic_ok", Some(&verdicts(""))),
+                case("removed_case", Some(&verdicts(r#""approved""#))),
+                case("unparseable", Some("not json")),
+                case("failed", None),
+            ],
+            usage: TokenUsage::default(),
+            summary: RunSummary::default(),
+        };
+        let recorded = suite.path().join("run.json");
+        artifact.write(&recorded).unwrap();
+
+        let rescored = suite.path().join("rescored.json");
+        let result = replay_ambiguity_run(&recorded, suite.path(), Some(&rescored));
+        assert_eq!(result.total, 4);
+        assert_eq!(result.matches, 2);
+        assert_eq!(result.mismatches, 0);
+        assert_eq!(result.hard_errors, 2);
 
-    // 7. Return result
-    let (_, matches, mismatches) = report.summary();
-    AmbiguityRunResult {
-        total,
-        matches,
-        mismatches,
-        hard_errors,
+        let rescored = RunArtifact::load(&rescored).unwrap();
+        assert!(rescored.cases[0].expected_verdicts.is_empty());
+        assert_eq!(rescored.cases[0].outcome, CaseOutcome::Match);
+        assert_eq!(rescored.cases[1].actual_verdicts, vec!["approved"]);
+        assert!(rescored.cases[2]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("parse"));
+        assert_eq!(rescored.cases[3].outcome, CaseOutcome::Error);
+        assert_eq!(rescored.summary.matches, 2);
     }
 }
@@ -0,0 +1,139 @@
+use std::path::Path;
+use std::process;
+
+use tenor_eval::{ActionSpace, BlockedReason};
+
+use super::read_json;
+use crate::{report_error, OutputFormat};
+
+/// Print the action space of `persona`: the flows it can start given the
+/// facts and entity states, the instances each would target, and why the
+/// others are blocked.
+pub(crate) fn cmd_actions(
+    bundle: &Path,
+    facts: &Path,
+    entity_states: Option<&Path>,
+    persona: &str,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let fail = |msg: String| -> ! {
+        report_error(&msg, output, quiet);
+        process::exit(1);
+    };
+
+    let bundle = super::generate::load_bundle(bundle, output, quiet);
+    let contract = tenor_eval::Contract::from_interchange(&bundle)
+        .unwrap_or_else(|e| fail(format!("error loading contract: {}", e)));
+    let facts = read_json(facts, "facts").unwrap_or_else(|e| fail(e));
+
+    // Start from the initial states; the file overrides individual instances.
+    let mut states = tenor_eval::operation::init_entity_states(&contract);
+    if let Some(path) = entity_states {
+        let overrides = read_json(path, "entity states")
+            .and_then(|v| tenor_eval::simulate::parse_entity_states(&v))
+            .unwrap_or_else(|e| fail(format!("invalid entity states: {}", e)));
+        states.extend(overrides);
+    }
+
+    match tenor_eval::compute_action_space(&contract, &facts, &states, persona) {
+        Ok(space) if !quiet => print_action_space(&space, None, output),
+        Ok(_) => {}
+        Err(e) => fail(format!("action space error: {}", e)),
+    }
+}
+
+/// Print an action space; `as_of` labels one computed from historical
+/// entity states.
+pub(crate) fn print_action_space(space: &ActionSpace, as_of: Option<&str>, output: OutputFormat) {
+    match output {
+        OutputFormat::Json => {
+            let mut json = serde_json::to_value(space).unwrap_or_default();
+            if let Some(as_of) = as_of {
+                json["as_of"] = serde_json::json!(as_of);
+            }
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json)
+                    .unwrap_or_else(|e| format!("serialization error: {}", e))
+            );
+        }
+        OutputFormat::Text => {
+            match as_of {
+                Some(as_of) => println!("Persona: {} (as of {})", space.persona_id, as_of),
+                None => println!("Persona: {}", space.persona_id),
+            }
+            if space.actions.is_empty() {
+                println!("no actions available");
+            } else {
+                println!("{} action(s) available:", space.actions.len());
+                for action in &space.actions {
+                    println!(
+                        "  {} (entry: {})",
+                        action.flow_id, action.entry_operation_id
+                    );
+                    for entity in &action.affected_entities {
+                        println!(
+                            "    {}: {} -> {}",
+                            entity.entity_id,
+                            entity.current_state,
+                            entity.possible_transitions.join(" | ")
+                        );
+                    }
+                    for (entity_id, instances) in &action.instance_bindings {
+                        let instances: Vec<&str> = instances.iter().map(String::as_str).collect();
+                        println!("    instances of {}: {}", entity_id, instances.join(", "));
+                    }
+                }
+            }
+            if !space.blocked_actions.is_empty() {
+                println!("{} action(s) blocked:", space.blocked_actions.len());
+                for blocked in &space.blocked_actions {
+                    println!(
+                        "  {}: {}",
+                        blocked.flow_id,
+                        describe_reason(&blocked.reason)
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn describe_reason(reason: &BlockedReason) -> String {
+    match reason {
+        BlockedReason::PersonaNotAuthorized { allowed_personas } => format!(
+            "persona not authorized (allowed: {})",
+            allowed_personas.join(", ")
+        ),
+        BlockedReason::PreconditionNotMet {
+            missing_verdicts,
+            failed_clauses,
+        } => {
+            let mut parts = Vec::new();
+            if !missing_verdicts.is_empty() {
+                parts.push(format!("missing verdicts: {}", missing_verdicts.join(", ")));
+            }
+            for clause in failed_clauses {
+                parts.push(format!("failed: {}", clause.clause));
+            }
+            if parts.is_empty() {
+                "precondition not met".to_string()
+            } else {
+                format!("precondition not met ({})", parts.join("; "))
+            }
+        }
+        BlockedReason::EntityNotInSourceState {
+            entity_id,
+            current_state,
+            required_state,
+            ..
+        } => format!(
+            "{} is in '{}', requires '{}'",
+            entity_id, current_state, required_state
+        ),
+        BlockedReason::MissingFacts { fact_ids } => {
+            format!("missing facts: {}", fact_ids.join(", "))
+        }
+    }
+}
@@ -1,7 +1,19 @@
 use std::path::Path;
 use std::process;
 
-pub(crate) fn cmd_ambiguity(suite_dir: &Path, spec: Option<&Path>, model: Option<&str>) {
+use crate::ambiguity::AmbiguityRunOptions;
+
+/// Default artifact path for a live run.
+const DEFAULT_ARTIFACT: &str = "ambiguity-run.json";
+
+pub(crate) fn cmd_ambiguity(
+    suite_dir: &Path,
+    spec: Option<&Path>,
+    model: Option<&str>,
+    seed: Option<u64>,
+    artifact: Option<&Path>,
+    replay: Option<&Path>,
+) {
     let spec_path = spec.map(|p| p.to_path_buf()).unwrap_or_else(|| {
         suite_dir
             .parent()
@@ -17,7 +29,18 @@ pub(crate) fn cmd_ambiguity(suite_dir: &Path, spec: Option<&Path>, model: Option
         process::exit(1);
     }
 
-    let result = crate::ambiguity::run_ambiguity_suite(suite_dir, &spec_path, model);
+    let result = match replay {
+        Some(recorded) => crate::ambiguity::replay_ambiguity_run(recorded, suite_dir, artifact),
+        None => crate::ambiguity::run_ambiguity_suite(
+            suite_dir,
+            &spec_path,
+            &AmbiguityRunOptions {
+                model,
+                seed,
+                artifact: artifact.unwrap_or(Path::new(DEFAULT_ARTIFACT)),
+            },
+        ),
+    };
     eprintln!(
         "\nAmbiguity test summary: {} total, {} matches, {} mismatches, {} hard errors",
         result.total, result.matches, result.mismatches, result.hard_errors
@@ -1,16 +1,17 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::process;
 
-use crate::{report_error, OutputFormat};
+use crate::{report_error, MatrixFormat, OutputFormat};
 
-pub(crate) fn cmd_check(file: &Path, analysis: Option<&str>, output: OutputFormat, quiet: bool) {
-    // Step 1: Elaborate the .tenor file
-    let bundle = match tenor_core::elaborate::elaborate(file) {
+/// Elaborate a .tenor file, reporting the error and exiting on failure.
+fn elaborate_or_exit(file: &Path, output: OutputFormat, quiet: bool) -> serde_json::Value {
+    match crate::elaborate(file) {
         Ok(b) => b,
         Err(e) => {
             match output {
                 OutputFormat::Json => {
-                    let err_json = serde_json::to_string_pretty(&e.to_json_value())
+                    let err_json = serde_json::to_string_pretty(&e.to_json_value_with_code())
                         .unwrap_or_else(|_| format!("{{\"error\": \"{:?}\"}}", e));
                     eprintln!("{}", err_json);
                 }
@@ -22,13 +23,31 @@ pub(crate) fn cmd_check(file: &Path, analysis: Option<&str>, output: OutputForma
             }
             process::exit(1);
         }
-    };
+    }
+}
 
-    // Step 2: Parse analysis selection
+pub(crate) fn cmd_check(
+    file: &Path,
+    analysis: Option<&str>,
+    state_space_limit: Option<u64>,
+    properties: Option<&Path>,
+    only_tag: Option<&str>,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    // Step 1: Elaborate the .tenor file
+    let bundle = elaborate_or_exit(file, output, quiet);
+
+    // Step 2: Parse analysis selection; `[check] analyses` in tenor.toml
+    // is the default when --analysis is not given
     let valid_analyses = ["s1", "s2", "s3a", "s4", "s5", "s6", "s7", "s8"];
-    let selected: Option<Vec<&str>> = analysis.map(|a| {
-        let selected: Vec<&str> = a.split(',').map(|s| s.trim()).collect();
-        for s in &selected {
+    let project_analyses = crate::project().check.analyses.as_ref();
+    let selected: Option<Vec<&str>> = match analysis {
+        Some(a) => Some(a.split(',').map(|s| s.trim()).collect()),
+        None => project_analyses.map(|list| list.iter().map(|s| s.as_str()).collect()),
+    };
+    if let Some(selected) = &selected {
+        for s in selected {
             if !valid_analyses.contains(s) {
                 let msg = format!(
                     "invalid analysis '{}'. Valid: {}",
@@ -39,16 +58,22 @@ pub(crate) fn cmd_check(file: &Path, analysis: Option<&str>, output: OutputForma
                 process::exit(1);
             }
         }
-        selected
-    });
+    }
 
-    // Step 3: Run analysis
+    // Step 3: Run analysis; --state-space-limit overrides `[check]
+    // state_space_limit`
+    let mut state_space = tenor_analyze::StateSpaceConfig::default();
+    if let Some(limit) = state_space_limit.or(crate::project().check.state_space_limit) {
+        state_space.product_limit = limit;
+    }
     let report = match &selected {
-        None => tenor_analyze::analyze(&bundle),
-        Some(analyses) => tenor_analyze::analyze_selected(&bundle, analyses),
+        None => tenor_analyze::analyze_with_config(&bundle, &state_space),
+        Some(analyses) => {
+            tenor_analyze::analyze_selected_with_config(&bundle, analyses, &state_space)
+        }
     };
 
-    let report = match report {
+    let mut report = match report {
         Ok(r) => r,
         Err(e) => {
             let msg = format!("analysis error: {}", e);
@@ -57,6 +82,40 @@ pub(crate) fn cmd_check(file: &Path, analysis: Option<&str>, output: OutputForma
         }
     };
 
+    // Step 3b: Temporal properties (S12), when a properties file is given
+    if let Some(path) = properties {
+        let parsed = std::fs::read_to_string(path)
+            .map_err(|e| format!("error reading properties '{}': {}", path.display(), e))
+            .and_then(|s| {
+                serde_json::from_str(&s)
+                    .map_err(|e| format!("error parsing properties '{}': {}", path.display(), e))
+            })
+            .and_then(|json| tenor_analyze::parse_properties(&json));
+        let s12 = parsed.and_then(|props| {
+            tenor_analyze::check_temporal_properties(&bundle, &props)
+                .map_err(|e| format!("analysis error: {}", e))
+        });
+        match s12 {
+            Ok(s12) => {
+                report.s12_temporal = Some(s12);
+                report.analyses_run.push("s12".to_string());
+                report.extract_findings();
+            }
+            Err(msg) => {
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+        }
+    }
+
+    // Step 3c: --only-tag narrows findings to tagged constructs
+    if let Some(tag) = only_tag {
+        if let Err(e) = report.retain_tagged_findings(&bundle, tag) {
+            report_error(&format!("analysis error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    }
+
     // Step 4: Format output
     if !quiet {
         match output {
@@ -77,6 +136,17 @@ pub(crate) fn cmd_check(file: &Path, analysis: Option<&str>, output: OutputForma
                         s1.entities.len(),
                         total_states
                     );
+                    let product = &s1.product;
+                    let warning = if product.exceeds_limit {
+                        format!("WARNING: exceeds limit {}", product.limit)
+                    } else {
+                        format!("within limit {}", product.limit)
+                    };
+                    println!(
+                        "  Product State Space: {} combined states ({})",
+                        format_combined_states(product),
+                        warning
+                    );
                 }
 
                 if let Some(ref s2) = report.s2_reachability {
@@ -186,8 +256,30 @@ pub(crate) fn cmd_check(file: &Path, analysis: Option<&str>, output: OutputForma
                     }
                 }
 
+                if let Some(ref s12) = report.s12_temporal {
+                    let bounded = s12
+                        .properties
+                        .iter()
+                        .filter(|p| p.status == tenor_analyze::PropertyStatus::HoldsWithinBound)
+                        .count();
+                    let bound_msg = if bounded > 0 {
+                        format!(" ({} within the path bound only)", bounded)
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "  Temporal Properties: {} checked, {} violated{}",
+                        s12.properties.len(),
+                        s12.violated_count,
+                        bound_msg
+                    );
+                }
+
                 println!();
-                println!("Findings:");
+                match only_tag {
+                    Some(tag) => println!("Findings (tagged '{}'):", tag),
+                    None => println!("Findings:"),
+                }
 
                 let has_findings = !report.findings.is_empty();
                 if has_findings {
@@ -223,3 +315,176 @@ pub(crate) fn cmd_check(file: &Path, analysis: Option<&str>, output: OutputForma
         process::exit(1);
     }
 }
+
+/// Print the persona x operation x entity-state admissibility matrix of a
+/// contract as CSV or JSON.
+pub(crate) fn cmd_admissibility_matrix(
+    file: &Path,
+    format: MatrixFormat,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let bundle = elaborate_or_exit(file, output, quiet);
+    let matrix = match tenor_analyze::export_admissibility_matrix(&bundle) {
+        Ok(m) => m,
+        Err(e) => {
+            report_error(&format!("analysis error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+    match format {
+        MatrixFormat::Csv => print!("{}", matrix.to_csv()),
+        MatrixFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&matrix)
+                .unwrap_or_else(|e| format!("{{\"error\": \"serialization: {}\"}}", e))
+        ),
+    }
+}
+
+/// Analyze a System file together with every member contract it declares.
+///
+/// Member paths are resolved relative to the System file. Exits 1 when the
+/// members conflict or any member analysis reports a warning.
+pub(crate) fn cmd_check_system(system_file: &Path, output: OutputFormat, quiet: bool) {
+    // Step 1: Elaborate the System file and extract its System construct
+    let system_bundle = elaborate_or_exit(system_file, output, quiet);
+    let system = match tenor_analyze::AnalysisBundle::from_interchange(&system_bundle) {
+        Ok(b) => b.systems.into_iter().next(),
+        Err(e) => {
+            report_error(&format!("analysis error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+    let Some(system) = system else {
+        let msg = format!("'{}' does not declare a system", system_file.display());
+        report_error(&msg, output, quiet);
+        process::exit(1);
+    };
+
+    // Step 2: Elaborate every member contract
+    let base = system_file.parent().unwrap_or_else(|| Path::new("."));
+    let members: BTreeMap<String, serde_json::Value> = system
+        .members
+        .iter()
+        .map(|m| {
+            (
+                m.id.clone(),
+                elaborate_or_exit(&base.join(&m.path), output, quiet),
+            )
+        })
+        .collect();
+
+    // Step 3: Run S1-S8 per member and the cross-contract checks
+    let analysis = match tenor_analyze::analyze_system(&system, &members) {
+        Ok(a) => a,
+        Err(e) => {
+            report_error(&format!("analysis error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let member_warnings: Vec<(&String, &tenor_analyze::Finding)> = analysis
+        .members
+        .iter()
+        .flat_map(|(id, report)| report.findings.iter().map(move |f| (id, f)))
+        .filter(|(_, f)| f.severity == tenor_analyze::FindingSeverity::Warning)
+        .collect();
+
+    // Step 4: Format output
+    if !quiet {
+        match output {
+            OutputFormat::Json => {
+                let json = serde_json::to_string_pretty(&analysis)
+                    .unwrap_or_else(|e| format!("{{\"error\": \"serialization: {}\"}}", e));
+                println!("{}", json);
+            }
+            OutputFormat::Text => {
+                println!("System Analysis Report: {}", analysis.system_id);
+                println!("======================");
+                println!();
+
+                println!("  Members:");
+                for member in &system.members {
+                    let Some(report) = analysis.members.get(&member.id) else {
+                        continue;
+                    };
+                    let authority = report
+                        .s4_authority
+                        .as_ref()
+                        .map_or(0, |s4| s4.transition_authorities.len());
+                    let paths = report.s6_flow_paths.as_ref().map_or(0, |s6| s6.total_paths);
+                    println!(
+                        "    {} ({}): {} transition authorities, {} flow paths",
+                        member.id, member.path, authority, paths
+                    );
+                }
+
+                println!(
+                    "  Shared Transition Authority (S4): {} transitions of shared entities",
+                    analysis.shared_transition_authorities.len()
+                );
+                println!(
+                    "  Cross-Contract Flow Paths (S6): {} triggers",
+                    analysis.trigger_paths.len()
+                );
+                for tp in &analysis.trigger_paths {
+                    println!(
+                        "    {}.{} --[{}]--> {}.{}: {} source path(s) x {} target path(s)",
+                        tp.source_contract,
+                        tp.source_flow,
+                        tp.on,
+                        tp.target_contract,
+                        tp.target_flow,
+                        tp.source_paths,
+                        tp.target_paths
+                    );
+                }
+
+                println!();
+                println!("Conflicts:");
+                if analysis.conflicts.is_empty() {
+                    println!("  No conflicts.");
+                } else {
+                    for conflict in &analysis.conflicts {
+                        println!(
+                            "  [{}/{}]: {}",
+                            conflict.analysis, conflict.kind, conflict.message
+                        );
+                    }
+                }
+
+                if !member_warnings.is_empty() {
+                    println!();
+                    println!("Member Findings:");
+                    for (contract_id, finding) in &member_warnings {
+                        let context = finding
+                            .entity_id
+                            .as_ref()
+                            .map(|id| format!(" [{}]", id))
+                            .unwrap_or_default();
+                        println!(
+                            "  {}: [{}/WARNING]{}: {}",
+                            contract_id, finding.analysis, context, finding.message
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Step 5: Exit code based on conflicts and member warnings
+    if !analysis.conflicts.is_empty() || !member_warnings.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// The combined state count, in scientific notation once it is too large
+/// to read at a glance or has saturated.
+fn format_combined_states(product: &tenor_analyze::ProductStateSpace) -> String {
+    if product.log10_combined_states < 9.0 {
+        product.combined_states.to_string()
+    } else {
+        format!("~10^{:.1}", product.log10_combined_states)
+    }
+}
@@ -0,0 +1,9 @@
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+/// Print the completion script for `shell` to stdout.
+pub(crate) fn cmd_completions(shell: Shell) {
+    let mut cmd = crate::Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
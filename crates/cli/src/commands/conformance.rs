@@ -0,0 +1,43 @@
+use std::process;
+
+use tenor_executor_conformance::{run_suite, ConformanceReport, HttpExecutor};
+
+use crate::tap::Tap;
+use crate::ConformanceOutputFormat;
+
+/// Run E1-E20 against the executor at `url` and print the report as TAP,
+/// JSON or JUnit XML.
+pub(crate) fn cmd_conformance(url: &str, api_key: Option<String>, format: ConformanceOutputFormat) {
+    let mut executor = HttpExecutor::new(url);
+    if let Some(key) = api_key.or_else(|| std::env::var("TENOR_API_KEY").ok()) {
+        executor = executor.with_api_key(key);
+    }
+
+    let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime");
+    let report = ConformanceReport::new(url, rt.block_on(run_suite(&executor)));
+
+    match format {
+        ConformanceOutputFormat::Tap => {
+            let mut tap = Tap::new();
+            for test in &report.tests {
+                let desc = format!("{}: {}", test.obligation, test.name);
+                match &test.message {
+                    None => tap.ok(desc),
+                    Some(diag) => tap.not_ok(desc, diag.as_str()),
+                }
+            }
+            tap.finish();
+        }
+        ConformanceOutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report.to_json()).unwrap_or_default()
+            );
+        }
+        ConformanceOutputFormat::Junit => print!("{}", report.to_junit_xml()),
+    }
+
+    if !report.is_success() {
+        process::exit(1);
+    }
+}
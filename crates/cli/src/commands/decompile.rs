@@ -0,0 +1,50 @@
+use std::path::Path;
+use std::process;
+
+use crate::{report_error, OutputFormat};
+
+pub(crate) fn cmd_decompile(bundle: &Path, out: Option<&Path>, output: OutputFormat, quiet: bool) {
+    let json_str = match std::fs::read_to_string(bundle) {
+        Ok(s) => s,
+        Err(e) => {
+            let msg = format!("error reading '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+    let value: serde_json::Value = match serde_json::from_str(&json_str) {
+        Ok(v) => v,
+        Err(e) => {
+            let msg = format!("error parsing JSON in '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let source = match crate::decompile::decompile(&value) {
+        Ok(s) => s,
+        Err(e) => {
+            let msg = format!("decompile error: {}", e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    match out {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, &source) {
+                let msg = format!("error writing '{}': {}", path.display(), e);
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+            if !quiet {
+                println!("wrote {}", path.display());
+            }
+        }
+        None => {
+            if !quiet {
+                print!("{}", source);
+            }
+        }
+    }
+}
@@ -72,7 +72,8 @@ pub(crate) fn cmd_diff(
             return;
         }
 
-        let classified = crate::diff::classify_diff(&bundle_diff);
+        let mut classified = crate::diff::classify_diff(&bundle_diff);
+        crate::diff::refine_flow_changes(&mut classified, &t1, &t2);
         if !quiet {
             match output {
                 OutputFormat::Json => {
@@ -1,13 +1,35 @@
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::process;
 
-use crate::OutputFormat;
+use crate::{report_error, OutputFormat};
 
-pub(crate) fn cmd_elaborate(file: &Path, manifest: bool, output: OutputFormat, quiet: bool) {
-    match tenor_core::elaborate::elaborate(file) {
-        Ok(bundle) => {
+pub(crate) fn cmd_elaborate(
+    file: &Path,
+    manifest: bool,
+    features: &BTreeSet<String>,
+    source_map: Option<&Path>,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let provider = crate::project().source_provider();
+    match tenor_core::elaborate_with_source_map(file, &provider, features) {
+        Ok((bundle, map)) => {
+            if let Some(map_path) = source_map {
+                let pretty = serde_json::to_string_pretty(&map)
+                    .unwrap_or_else(|e| format!("serialization error: {}", e));
+                if let Err(e) = std::fs::write(map_path, format!("{}\n", pretty)) {
+                    let msg = format!(
+                        "error: failed to write source map to {}: {}",
+                        map_path.display(),
+                        e
+                    );
+                    report_error(&msg, output, quiet);
+                    process::exit(1);
+                }
+            }
             let output_value = if manifest {
-                crate::manifest::build_manifest(bundle)
+                crate::manifest::build_manifest_with_features(bundle, features)
             } else {
                 bundle
             };
@@ -18,13 +40,13 @@ pub(crate) fn cmd_elaborate(file: &Path, manifest: bool, output: OutputFormat, q
         Err(e) => {
             match output {
                 OutputFormat::Json => {
-                    let err_json = serde_json::to_string_pretty(&e.to_json_value())
+                    let err_json = serde_json::to_string_pretty(&e.to_json_value_with_code())
                         .unwrap_or_else(|_| format!("{{\"error\": \"{:?}\"}}", e));
                     eprintln!("{}", err_json);
                 }
                 OutputFormat::Text => {
                     if !quiet {
-                        let err_json = serde_json::to_string_pretty(&e.to_json_value())
+                        let err_json = serde_json::to_string_pretty(&e.to_json_value_with_code())
                             .unwrap_or_else(|_| format!("{:?}", e));
                         eprintln!("{}", err_json);
                     }
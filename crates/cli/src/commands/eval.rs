@@ -1,13 +1,25 @@
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process;
 
+use rust_decimal::Decimal;
+use tenor_core::ast::{MONEY_DEFAULT_PRECISION, MONEY_DEFAULT_SCALE};
+
+use crate::otlp;
 use crate::{report_error, OutputFormat};
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn cmd_eval(
     bundle_path: &Path,
-    facts_path: &Path,
+    facts_paths: &[PathBuf],
+    fact_overrides: &[String],
     flow_id: Option<&str>,
     persona: Option<&str>,
+    export_trace: Option<&Path>,
+    profile: bool,
+    trace: bool,
+    partial: bool,
+    as_of: Option<(&str, &Path)>,
     output: OutputFormat,
     quiet: bool,
 ) {
@@ -31,25 +43,21 @@ pub(crate) fn cmd_eval(
         }
     };
 
-    // Read facts file
-    let facts_str = match std::fs::read_to_string(facts_path) {
-        Ok(s) => s,
-        Err(_) => {
-            let msg = format!("error: facts file not found: {}", facts_path.display());
+    // Read and merge facts files, then apply --fact overrides
+    let fetch_window = otlp::Window::begin();
+    let facts = match read_facts(&bundle, facts_paths, fact_overrides) {
+        Ok(facts) => facts,
+        Err(msg) => {
             report_error(&msg, output, quiet);
             process::exit(1);
         }
     };
+    let fetch_window = fetch_window.finish();
+    let facts_source = facts_source(facts_paths);
 
-    // Parse facts JSON
-    let facts: serde_json::Value = match serde_json::from_str(&facts_str) {
-        Ok(v) => v,
-        Err(e) => {
-            let msg = format!("error: invalid JSON in {}: {}", facts_path.display(), e);
-            report_error(&msg, output, quiet);
-            process::exit(1);
-        }
-    };
+    // Entity states reconstructed from storage history for --as-of
+    let historical_states = as_of
+        .map(|(timestamp, history_path)| historical_states(timestamp, history_path, output, quiet));
 
     // Flow evaluation mode
     if let Some(fid) = flow_id {
@@ -62,15 +70,51 @@ pub(crate) fn cmd_eval(
             }
         };
 
-        match tenor_eval::evaluate_flow(
-            &bundle,
-            &facts,
-            fid,
-            p,
-            None,
-            &tenor_eval::InstanceBindingMap::new(),
-        ) {
+        let eval_window = otlp::Window::begin();
+        let flow_eval = profiled(profile && !quiet, output, || {
+            tenor_eval::evaluate_flow(
+                &bundle,
+                &facts,
+                fid,
+                p,
+                historical_states.as_ref(),
+                &tenor_eval::InstanceBindingMap::new(),
+            )
+        });
+        let eval_window = eval_window.finish();
+
+        match flow_eval {
             Ok(result) => {
+                if let Some(trace_path) = export_trace {
+                    let contract = match tenor_eval::Contract::from_interchange(&bundle) {
+                        Ok(c) => c,
+                        Err(e) => {
+                            report_error(&format!("error: {}", e), output, quiet);
+                            process::exit(1);
+                        }
+                    };
+                    let trace = otlp::export_flow_trace(&otlp::FlowTrace {
+                        flow_id: fid,
+                        persona: p,
+                        contract: &contract,
+                        result: &result,
+                        facts_source: &facts_source,
+                        fact_count: facts.as_object().map(|o| o.len()).unwrap_or(0),
+                        fetch: fetch_window,
+                        eval: eval_window,
+                    });
+                    let pretty = serde_json::to_string_pretty(&trace)
+                        .unwrap_or_else(|e| format!("serialization error: {}", e));
+                    if let Err(e) = std::fs::write(trace_path, pretty) {
+                        let msg = format!(
+                            "error: failed to write trace to {}: {}",
+                            trace_path.display(),
+                            e
+                        );
+                        report_error(&msg, output, quiet);
+                        process::exit(1);
+                    }
+                }
                 if !quiet {
                     match output {
                         OutputFormat::Json => {
@@ -89,11 +133,23 @@ pub(crate) fn cmd_eval(
                                 .entity_state_changes
                                 .iter()
                                 .map(|e| {
-                                    serde_json::json!({
+                                    let mut change = serde_json::json!({
                                         "entity_id": e.entity_id,
                                         "from": e.from_state,
                                         "to": e.to_state
-                                    })
+                                    });
+                                    if e.kind != tenor_eval::types::EffectKind::Transition {
+                                        change["kind"] = serde_json::json!(e.kind.as_str());
+                                    }
+                                    if !e.attributes.is_empty() {
+                                        change["attributes"] = e
+                                            .attributes
+                                            .iter()
+                                            .map(|(name, value)| (name.clone(), value.to_json()))
+                                            .collect::<serde_json::Map<_, _>>()
+                                            .into();
+                                    }
+                                    change
                                 })
                                 .collect();
                             json_output.insert("entity_state_changes".to_string(), entity_changes);
@@ -165,6 +221,7 @@ pub(crate) fn cmd_eval(
                         if !quiet {
                             let err_json = serde_json::json!({
                                 "error": format!("{}", e),
+                                "code": e.code(),
                             });
                             eprintln!(
                                 "{}",
@@ -184,8 +241,48 @@ pub(crate) fn cmd_eval(
         return;
     }
 
+    // Historical action space: what the persona could do at --as-of
+    if let (Some(states), Some((timestamp, _))) = (&historical_states, as_of) {
+        let Some(p) = persona else {
+            report_error(
+                "error: --persona is required with --as-of unless --flow is specified",
+                output,
+                quiet,
+            );
+            process::exit(1);
+        };
+        let space = tenor_eval::Contract::from_interchange(&bundle)
+            .and_then(|contract| tenor_eval::compute_action_space(&contract, &facts, states, p));
+        match space {
+            Ok(space) if !quiet => {
+                super::actions::print_action_space(&space, Some(timestamp), output)
+            }
+            Ok(_) => {}
+            Err(e) => evaluation_failed(&e, output, quiet),
+        }
+        return;
+    }
+
+    // Partial evaluation: missing facts block verdicts instead of failing
+    if partial {
+        match profiled(profile && !quiet, output, || {
+            tenor_eval::evaluate_partial(&bundle, &facts)
+        }) {
+            Ok(result) if !quiet => print_partial(&result, output),
+            Ok(_) => {}
+            Err(e) => evaluation_failed(&e, output, quiet),
+        }
+        return;
+    }
+
     // Rule-only evaluation (default)
-    match tenor_eval::evaluate(&bundle, &facts) {
+    match profiled(profile && !quiet, output, || {
+        if trace {
+            tenor_eval::evaluate_traced(&bundle, &facts)
+        } else {
+            tenor_eval::evaluate(&bundle, &facts)
+        }
+    }) {
         Ok(result) => {
             if !quiet {
                 match output {
@@ -213,37 +310,510 @@ pub(crate) fn cmd_eval(
                                 );
                             }
                         }
+                        let traces = result.verdicts.traces();
+                        if !traces.is_empty() {
+                            println!("Rule trace:");
+                            for t in traces {
+                                print!("{}", t.render());
+                            }
+                        }
                     }
                 }
             }
         }
-        Err(e) => {
-            match output {
-                OutputFormat::Json => {
-                    if !quiet {
-                        let err_json = serde_json::json!({
-                            "error": format!("{}", e),
-                            "details": {
-                                "type": format!("{:?}", e).split('{').next().unwrap_or("Unknown").trim().to_string(),
-                            }
-                        });
-                        eprintln!(
-                            "{}",
-                            serde_json::to_string_pretty(&err_json).unwrap_or_default()
-                        );
-                    }
+        Err(e) => evaluation_failed(&e, output, quiet),
+    }
+}
+
+/// Evaluate the rules against two facts files and report how the verdicts
+/// differ: which facts changed, and which verdicts were added, removed, or
+/// changed payload, with the rules responsible.
+pub(crate) fn cmd_eval_compare(
+    bundle_path: &Path,
+    old_facts_path: &Path,
+    new_facts_path: &Path,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let bundle = read_json_or_exit(bundle_path, "bundle", output, quiet);
+    let old_facts = read_json_or_exit(old_facts_path, "facts", output, quiet);
+    let new_facts = read_json_or_exit(new_facts_path, "facts", output, quiet);
+
+    let contract = match tenor_eval::Contract::from_interchange(&bundle) {
+        Ok(c) => c,
+        Err(e) => evaluation_failed(&e, output, quiet),
+    };
+    let evaluate = |facts: &serde_json::Value| match tenor_eval::evaluate_contract(&contract, facts)
+    {
+        Ok(result) => result.verdicts,
+        Err(e) => evaluation_failed(&e, output, quiet),
+    };
+    let diff = evaluate(&old_facts).diff(&evaluate(&new_facts));
+    if quiet {
+        return;
+    }
+
+    let changed_facts = changed_facts(&old_facts, &new_facts);
+    match output {
+        OutputFormat::Json => {
+            let facts: Vec<serde_json::Value> = changed_facts
+                .iter()
+                .map(|(id, old, new)| serde_json::json!({ "fact": id, "old": old, "new": new }))
+                .collect();
+            let json = serde_json::json!({
+                "changed_facts": facts,
+                "verdicts": diff.to_json(),
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json)
+                    .unwrap_or_else(|e| format!("serialization error: {}", e))
+            );
+        }
+        OutputFormat::Text => {
+            if !changed_facts.is_empty() {
+                println!("{} fact(s) changed:", changed_facts.len());
+                let show = |v: &Option<&serde_json::Value>| match v {
+                    Some(v) => v.to_string(),
+                    None => "(absent)".to_string(),
+                };
+                for (id, old, new) in &changed_facts {
+                    println!("  {}: {} -> {}", id, show(old), show(new));
                 }
-                OutputFormat::Text => {
-                    if !quiet {
-                        eprintln!("evaluation error: {}", e);
+            }
+            if diff.is_empty() {
+                println!("no verdict changes");
+                return;
+            }
+            let changed_ids: Vec<&str> = changed_facts.iter().map(|(id, _, _)| *id).collect();
+            let cause = |c: &tenor_eval::VerdictChange| {
+                let read: Vec<&str> = c
+                    .facts_used
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|f| changed_ids.contains(f))
+                    .collect();
+                if read.is_empty() {
+                    format!("rule: {}, stratum: {}", c.rule_id, c.stratum)
+                } else {
+                    format!(
+                        "rule: {}, stratum: {}, reads {}",
+                        c.rule_id,
+                        c.stratum,
+                        read.join(", ")
+                    )
+                }
+            };
+            println!("verdict changes:");
+            for c in &diff.added {
+                let payload = c.new_payload.as_ref().map(format_verdict_payload);
+                println!(
+                    "  + [{}] {} ({})",
+                    c.verdict_type,
+                    payload.unwrap_or_default(),
+                    cause(c)
+                );
+            }
+            for c in &diff.removed {
+                let payload = c.old_payload.as_ref().map(format_verdict_payload);
+                println!(
+                    "  - [{}] {} ({})",
+                    c.verdict_type,
+                    payload.unwrap_or_default(),
+                    cause(c)
+                );
+            }
+            for c in &diff.changed {
+                let old = c.old_payload.as_ref().map(format_verdict_payload);
+                let new = c.new_payload.as_ref().map(format_verdict_payload);
+                println!(
+                    "  ~ [{}] {} -> {} ({})",
+                    c.verdict_type,
+                    old.unwrap_or_default(),
+                    new.unwrap_or_default(),
+                    cause(c)
+                );
+            }
+        }
+    }
+}
+
+/// Check the facts against the contract without evaluating, reporting
+/// every missing or ill-typed fact with a JSON pointer into the facts
+/// document. Exits 1 if there are any.
+pub(crate) fn cmd_validate_facts(
+    bundle_path: &Path,
+    facts_paths: &[PathBuf],
+    fact_overrides: &[String],
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let bundle = read_json_or_exit(bundle_path, "bundle", output, quiet);
+    let facts = match read_facts(&bundle, facts_paths, fact_overrides) {
+        Ok(facts) => facts,
+        Err(msg) => {
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+    let contract = match tenor_eval::Contract::from_interchange(&bundle) {
+        Ok(c) => c,
+        Err(e) => evaluation_failed(&e, output, quiet),
+    };
+    let errors = tenor_eval::validate_facts(&contract, &facts);
+
+    if !quiet {
+        match output {
+            OutputFormat::Json => {
+                let errors: Vec<serde_json::Value> =
+                    errors.iter().map(tenor_eval::FactError::to_json).collect();
+                let json = serde_json::json!({
+                    "valid": errors.is_empty(),
+                    "errors": errors,
+                });
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json)
+                        .unwrap_or_else(|e| format!("serialization error: {}", e))
+                );
+            }
+            OutputFormat::Text => {
+                if errors.is_empty() {
+                    println!("facts are valid");
+                } else {
+                    println!("{} fact error(s):", errors.len());
+                    for e in &errors {
+                        let pointer = if e.pointer.is_empty() {
+                            "(document)"
+                        } else {
+                            e.pointer.as_str()
+                        };
+                        println!("  {}: {} [{}]", pointer, e.error, e.error.code());
                     }
                 }
             }
+        }
+    }
+    if !errors.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Top-level facts whose values differ between two facts objects, as
+/// (fact id, old value, new value); a value is `None` where the fact is absent.
+fn changed_facts<'a>(
+    old: &'a serde_json::Value,
+    new: &'a serde_json::Value,
+) -> Vec<(
+    &'a str,
+    Option<&'a serde_json::Value>,
+    Option<&'a serde_json::Value>,
+)> {
+    let ids: std::collections::BTreeSet<&'a str> = [old, new]
+        .into_iter()
+        .filter_map(|facts| facts.as_object())
+        .flat_map(|facts| facts.keys().map(String::as_str))
+        .collect();
+    ids.into_iter()
+        .map(|id| (id, old.get(id), new.get(id)))
+        .filter(|(_, o, n)| o != n)
+        .collect()
+}
+
+/// Read and parse a JSON file, or report the error and exit.
+fn read_json_or_exit(
+    path: &Path,
+    what: &str,
+    output: OutputFormat,
+    quiet: bool,
+) -> serde_json::Value {
+    super::read_json(path, what).unwrap_or_else(|e| {
+        report_error(&format!("error: {}", e), output, quiet);
+        process::exit(1);
+    })
+}
+
+/// Assemble the facts object for `tenor eval`: each `--facts` file in
+/// order (`-` reads stdin), later files replacing facts of the same id,
+/// then every `--fact ID=VALUE` coerced to the fact's declared type.
+fn read_facts(
+    bundle: &serde_json::Value,
+    paths: &[PathBuf],
+    overrides: &[String],
+) -> Result<serde_json::Value, String> {
+    let mut facts = serde_json::Map::new();
+    let mut read_stdin = false;
+    for path in paths {
+        let (name, value) = if path.as_os_str() == "-" {
+            if std::mem::replace(&mut read_stdin, true) {
+                return Err("error: --facts - can only be given once".to_string());
+            }
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("error: failed to read facts from stdin: {}", e))?;
+            let value = serde_json::from_str(&text)
+                .map_err(|e| format!("error: invalid JSON in stdin: {}", e))?;
+            ("stdin".to_string(), value)
+        } else {
+            let value = super::read_json(path, "facts").map_err(|e| format!("error: {}", e))?;
+            (path.display().to_string(), value)
+        };
+        match value {
+            serde_json::Value::Object(file_facts) => facts.extend(file_facts),
+            _ => return Err(format!("error: facts in {} must be a JSON object", name)),
+        }
+    }
+
+    if !overrides.is_empty() {
+        let contract =
+            tenor_eval::Contract::from_interchange(bundle).map_err(|e| format!("error: {}", e))?;
+        for fact in overrides {
+            let (id, raw) = fact
+                .split_once('=')
+                .ok_or_else(|| format!("error: --fact '{}' must be ID=VALUE", fact))?;
+            let decl = contract
+                .facts
+                .iter()
+                .find(|f| f.id == id)
+                .ok_or_else(|| format!("error: --fact {}: no such fact in the contract", id))?;
+            facts.insert(id.to_string(), coerce_fact(decl, raw)?);
+        }
+    }
+    Ok(serde_json::Value::Object(facts))
+}
+
+/// Convert the text of a `--fact` override into the JSON form of the
+/// fact's declared type: `true`/`false`, integers, decimals as strings,
+/// Money as `AMOUNT [CURRENCY]`, Duration as `N UNIT`, and JSON for lists
+/// and records.
+fn coerce_fact(decl: &tenor_eval::types::FactDecl, raw: &str) -> Result<serde_json::Value, String> {
+    let ty = &decl.fact_type;
+    let invalid = |expected: &str| {
+        format!(
+            "error: --fact {}: '{}' is not a valid {} ({})",
+            decl.id, raw, ty.base, expected
+        )
+    };
+    match ty.base.as_str() {
+        "Bool" => raw
+            .parse::<bool>()
+            .map(serde_json::Value::from)
+            .map_err(|_| invalid("true or false")),
+        "Int" => raw
+            .parse::<i64>()
+            .map(serde_json::Value::from)
+            .map_err(|_| invalid("an integer")),
+        "Decimal" => {
+            let declared = ty.precision.zip(ty.scale);
+            if fits_decimal(raw, declared, ty.rounding.as_deref()) {
+                Ok(serde_json::json!(raw))
+            } else {
+                Err(invalid(&decimal_expectation(declared)))
+            }
+        }
+        "Money" => {
+            let declared = Some((
+                ty.precision.unwrap_or(MONEY_DEFAULT_PRECISION),
+                ty.scale.unwrap_or(MONEY_DEFAULT_SCALE),
+            ));
+            let mut parts = raw.split_whitespace();
+            let amount = parts
+                .next()
+                .filter(|a| fits_decimal(a, declared, ty.rounding.as_deref()));
+            let currency = parts.next().or(ty.currency.as_deref());
+            match (amount, currency, parts.next()) {
+                (Some(amount), Some(currency), None) => {
+                    Ok(serde_json::json!({ "amount": amount, "currency": currency }))
+                }
+                _ => Err(invalid(&format!(
+                    "AMOUNT [CURRENCY], the amount {}",
+                    decimal_expectation(declared)
+                ))),
+            }
+        }
+        "Duration" => {
+            let mut parts = raw.split_whitespace();
+            let value = parts.next().and_then(|v| v.parse::<i64>().ok());
+            match (value, parts.next(), parts.next()) {
+                (Some(value), Some(unit), None) => {
+                    Ok(serde_json::json!({ "value": value, "unit": unit }))
+                }
+                _ => Err(invalid("N UNIT, such as 30 days")),
+            }
+        }
+        "Text" | "Enum" | "Date" | "DateTime" => Ok(serde_json::json!(raw)),
+        _ => serde_json::from_str(raw).map_err(|_| invalid("JSON")),
+    }
+}
+
+/// Whether `raw` is an exact decimal that fits the declared `(precision,
+/// scale)`, once rounded with the declared `rounding` mode if it has one.
+fn fits_decimal(raw: &str, declared: Option<(u32, u32)>, rounding: Option<&str>) -> bool {
+    match (Decimal::from_str_exact(raw), declared) {
+        (Ok(value), Some((precision, scale))) => {
+            tenor_eval::numeric::fit_to_scale(value, precision, scale, rounding).is_some()
+        }
+        (Ok(_), None) => true,
+        (Err(_), _) => false,
+    }
+}
+
+fn decimal_expectation(declared: Option<(u32, u32)>) -> String {
+    match declared {
+        Some((precision, scale)) => format!(
+            "a decimal number of precision {} and scale {}",
+            precision, scale
+        ),
+        None => "a decimal number".to_string(),
+    }
+}
+
+/// How the facts were supplied, for the trace's `facts.source` attribute.
+fn facts_source(paths: &[PathBuf]) -> String {
+    if paths.is_empty() {
+        return "--fact".to_string();
+    }
+    paths
+        .iter()
+        .map(|p| {
+            if p.as_os_str() == "-" {
+                "stdin".to_string()
+            } else {
+                p.display().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Reconstruct entity states as of `timestamp` from an exported entity
+/// history, or report the error and exit.
+fn historical_states(
+    timestamp: &str,
+    history_path: &Path,
+    output: OutputFormat,
+    quiet: bool,
+) -> tenor_eval::EntityStateMap {
+    let history = std::fs::read_to_string(history_path)
+        .map_err(|_| format!("error: history file not found: {}", history_path.display()))
+        .and_then(|s| {
+            serde_json::from_str::<serde_json::Value>(&s)
+                .map_err(|e| format!("error: invalid JSON in {}: {}", history_path.display(), e))
+        })
+        .and_then(|v| tenor_eval::EntityHistory::from_json(&v).map_err(|e| format!("error: {}", e)))
+        .and_then(|h| {
+            h.entity_states_as_of(timestamp)
+                .map_err(|e| format!("error: {}", e))
+        });
+    match history {
+        Ok(states) => states,
+        Err(msg) => {
+            report_error(&msg, output, quiet);
             process::exit(1);
         }
     }
 }
 
+/// Report a rule evaluation error and exit.
+fn evaluation_failed(e: &tenor_eval::EvalError, output: OutputFormat, quiet: bool) -> ! {
+    match output {
+        OutputFormat::Json => {
+            if !quiet {
+                let err_json = serde_json::json!({
+                    "error": format!("{}", e),
+                    "code": e.code(),
+                    "details": {
+                        "type": format!("{:?}", e).split('{').next().unwrap_or("Unknown").trim().to_string(),
+                    }
+                });
+                eprintln!(
+                    "{}",
+                    serde_json::to_string_pretty(&err_json).unwrap_or_default()
+                );
+            }
+        }
+        OutputFormat::Text => {
+            if !quiet {
+                eprintln!("evaluation error: {}", e);
+            }
+        }
+    }
+    process::exit(1);
+}
+
+/// Print the result of `--partial` evaluation.
+fn print_partial(result: &tenor_eval::PartialEvalResult, output: OutputFormat) {
+    match output {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&result.to_json())
+                .unwrap_or_else(|e| format!("serialization error: {}", e))
+        ),
+        OutputFormat::Text => {
+            let verdicts = &result.verdicts.0;
+            if verdicts.is_empty() {
+                println!("no verdicts produced");
+            } else {
+                println!("{} verdict(s) produced:", verdicts.len());
+                for v in verdicts {
+                    println!(
+                        "  [{}] {} (rule: {}, stratum: {})",
+                        v.verdict_type,
+                        format_verdict_payload(&v.payload),
+                        v.provenance.rule_id,
+                        v.provenance.stratum,
+                    );
+                }
+            }
+            if !result.blocked.is_empty() {
+                println!(
+                    "{} verdict(s) blocked on missing facts:",
+                    result.blocked.len()
+                );
+                for b in &result.blocked {
+                    let what = if b.condition_holds {
+                        "payload needs"
+                    } else {
+                        "needs"
+                    };
+                    println!(
+                        "  [{}] {} {} (rule: {})",
+                        b.verdict_type,
+                        what,
+                        b.missing_facts.join(", "),
+                        b.rule_id,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Number of rows shown in the `--profile` hot-spot table.
+const PROFILE_TABLE_ROWS: usize = 20;
+
+/// Run `f`, and when `enabled` print its profile to stderr: a hot-spot
+/// table in text mode, the full `ProfileReport` in JSON mode.
+fn profiled<T>(enabled: bool, output: OutputFormat, f: impl FnOnce() -> T) -> T {
+    if !enabled {
+        return f();
+    }
+    let (value, report) = tenor_eval::profile(f);
+    match output {
+        OutputFormat::Json => eprintln!(
+            "{}",
+            serde_json::to_string_pretty(&report)
+                .unwrap_or_else(|e| format!("serialization error: {}", e))
+        ),
+        OutputFormat::Text => {
+            eprintln!("Profile (hot spots by total time):");
+            eprint!("{}", report.render_table(PROFILE_TABLE_ROWS));
+        }
+    }
+    value
+}
+
 /// Format a verdict payload for text output.
 fn format_verdict_payload(v: &tenor_eval::Value) -> String {
     match v {
@@ -33,12 +33,12 @@ pub(crate) fn cmd_explain(
         }
     } else {
         // Elaborate .tenor file first
-        match tenor_core::elaborate::elaborate(file) {
+        match crate::elaborate(file) {
             Ok(b) => b,
             Err(e) => {
                 match output {
                     OutputFormat::Json => {
-                        let err_json = serde_json::to_string_pretty(&e.to_json_value())
+                        let err_json = serde_json::to_string_pretty(&e.to_json_value_with_code())
                             .unwrap_or_else(|_| format!("{{\"error\": \"{:?}\"}}", e));
                         eprintln!("{}", err_json);
                     }
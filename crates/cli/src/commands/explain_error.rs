@@ -0,0 +1,64 @@
+use std::process;
+
+use tenor_core::error_codes::{self, ErrorCodeInfo};
+
+use crate::{report_error, OutputFormat};
+
+/// Print the catalog entry for `code`, or list every code when `code` is
+/// `None`.
+pub(crate) fn cmd_explain_error(code: Option<&str>, output: OutputFormat, quiet: bool) {
+    let Some(code) = code else {
+        match output {
+            OutputFormat::Text => {
+                for entry in error_codes::CATALOG {
+                    println!("{}  {}", entry.code, entry.title);
+                }
+            }
+            OutputFormat::Json => {
+                let entries: Vec<serde_json::Value> =
+                    error_codes::CATALOG.iter().map(entry_json).collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&entries).unwrap_or_default()
+                );
+            }
+        }
+        return;
+    };
+
+    let Some(entry) = error_codes::lookup(code) else {
+        let msg = format!(
+            "unknown error code '{}'; run `tenor explain-error` to list codes",
+            code
+        );
+        report_error(&msg, output, quiet);
+        process::exit(1);
+    };
+    match output {
+        OutputFormat::Text => {
+            println!("{}: {}", entry.code, entry.title);
+            println!();
+            println!("{}", entry.description);
+            println!();
+            println!("Common fixes:");
+            for fix in entry.fixes {
+                println!("  - {}", fix);
+            }
+        }
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&entry_json(entry)).unwrap_or_default()
+            );
+        }
+    }
+}
+
+fn entry_json(entry: &ErrorCodeInfo) -> serde_json::Value {
+    serde_json::json!({
+        "code": entry.code,
+        "title": entry.title,
+        "description": entry.description,
+        "fixes": entry.fixes,
+    })
+}
@@ -1,5 +1,8 @@
+use std::path::{Path, PathBuf};
 use std::process;
 
+use clap::ValueEnum;
+
 use crate::{report_error, GenerateCommands, OutputFormat};
 
 pub(crate) fn cmd_generate(command: GenerateCommands, output: OutputFormat, quiet: bool) {
@@ -9,71 +12,71 @@ pub(crate) fn cmd_generate(command: GenerateCommands, output: OutputFormat, quie
             out,
             sdk_import,
         } => {
-            // Determine input type by extension
-            let ext = input.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let bundle_json = load_bundle(&crate::project().find_contract(&input), output, quiet);
+
+            // Flags override [generate.typescript] in tenor.toml
+            let project = crate::project();
+            let target = project.generate.typescript.clone().unwrap_or_default();
+            let config = tenor_codegen::TypeScriptConfig {
+                out_dir: out
+                    .or_else(|| target.out.map(|p| project.resolve(&p)))
+                    .unwrap_or_else(|| PathBuf::from("./generated")),
+                sdk_import: sdk_import
+                    .or(target.sdk_import)
+                    .unwrap_or_else(|| "@tenor/sdk".to_string()),
+            };
 
-            let bundle_json: serde_json::Value = match ext {
-                "tenor" => {
-                    // Elaborate .tenor file first
-                    match tenor_core::elaborate::elaborate(&input) {
-                        Ok(b) => b,
-                        Err(e) => {
-                            match output {
-                                OutputFormat::Json => {
-                                    let err_json = serde_json::to_string_pretty(&e.to_json_value())
-                                        .unwrap_or_else(|_| format!("{{\"error\": \"{:?}\"}}", e));
-                                    eprintln!("{}", err_json);
-                                }
-                                OutputFormat::Text => {
-                                    if !quiet {
-                                        eprintln!("elaboration error: {:?}", e);
-                                    }
-                                }
+            match tenor_codegen::generate_typescript(&bundle_json, &config) {
+                Ok(output_dir) => {
+                    if !quiet {
+                        match output {
+                            OutputFormat::Text => {
+                                println!("Generated TypeScript files in {}", output_dir.display());
+                            }
+                            OutputFormat::Json => {
+                                println!("{{\"output_dir\": \"{}\"}}", output_dir.display());
                             }
-                            process::exit(1);
-                        }
-                    }
-                }
-                "json" => {
-                    // Read and parse interchange JSON
-                    let json_str = match std::fs::read_to_string(&input) {
-                        Ok(s) => s,
-                        Err(e) => {
-                            let msg = format!("error reading '{}': {}", input.display(), e);
-                            report_error(&msg, output, quiet);
-                            process::exit(1);
-                        }
-                    };
-                    match serde_json::from_str(&json_str) {
-                        Ok(v) => v,
-                        Err(e) => {
-                            let msg = format!("error parsing JSON in '{}': {}", input.display(), e);
-                            report_error(&msg, output, quiet);
-                            process::exit(1);
                         }
                     }
                 }
-                _ => {
-                    let msg = format!(
-                        "unsupported input file type '{}': expected .tenor or .json",
-                        input.display()
-                    );
+                Err(e) => {
+                    let msg = format!("code generation error: {}", e);
                     report_error(&msg, output, quiet);
                     process::exit(1);
                 }
-            };
+            }
+        }
+        GenerateCommands::Docs { input, out, format } => {
+            let bundle_json = load_bundle(&crate::project().find_contract(&input), output, quiet);
 
-            let config = tenor_codegen::TypeScriptConfig {
-                out_dir: out,
-                sdk_import,
+            // Flags override [generate.docs] in tenor.toml
+            let project = crate::project();
+            let target = project.generate.docs.clone().unwrap_or_default();
+            let out = out
+                .or_else(|| target.out.map(|p| project.resolve(&p)))
+                .unwrap_or_else(|| PathBuf::from("./docs"));
+            let format = match (format, target.format) {
+                (Some(f), _) => f,
+                (None, Some(name)) => match crate::docs::DocsFormat::from_str(&name, true) {
+                    Ok(f) => f,
+                    Err(_) => {
+                        let msg = format!(
+                            "invalid [generate.docs] format '{}' in tenor.toml: expected markdown or html",
+                            name
+                        );
+                        report_error(&msg, output, quiet);
+                        process::exit(1);
+                    }
+                },
+                (None, None) => crate::docs::DocsFormat::Markdown,
             };
 
-            match tenor_codegen::generate_typescript(&bundle_json, &config) {
+            match crate::docs::generate_docs(&bundle_json, &out, format) {
                 Ok(output_dir) => {
                     if !quiet {
                         match output {
                             OutputFormat::Text => {
-                                println!("Generated TypeScript files in {}", output_dir.display());
+                                println!("Generated documentation in {}", output_dir.display());
                             }
                             OutputFormat::Json => {
                                 println!("{{\"output_dir\": \"{}\"}}", output_dir.display());
@@ -82,11 +85,70 @@ pub(crate) fn cmd_generate(command: GenerateCommands, output: OutputFormat, quie
                     }
                 }
                 Err(e) => {
-                    let msg = format!("code generation error: {}", e);
+                    let msg = format!("documentation generation error: {}", e);
+                    report_error(&msg, output, quiet);
+                    process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// Load an interchange bundle from a `.tenor` source file (elaborating it)
+/// or an interchange `.json` file. Exits the process on failure.
+pub(crate) fn load_bundle(input: &Path, output: OutputFormat, quiet: bool) -> serde_json::Value {
+    // Determine input type by extension
+    let ext = input.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    match ext {
+        "tenor" => {
+            // Elaborate .tenor file first
+            match crate::elaborate(input) {
+                Ok(b) => b,
+                Err(e) => {
+                    match output {
+                        OutputFormat::Json => {
+                            let err_json =
+                                serde_json::to_string_pretty(&e.to_json_value_with_code())
+                                    .unwrap_or_else(|_| format!("{{\"error\": \"{:?}\"}}", e));
+                            eprintln!("{}", err_json);
+                        }
+                        OutputFormat::Text => {
+                            if !quiet {
+                                eprintln!("elaboration error: {:?}", e);
+                            }
+                        }
+                    }
+                    process::exit(1);
+                }
+            }
+        }
+        "json" => {
+            // Read and parse interchange JSON
+            let json_str = match std::fs::read_to_string(input) {
+                Ok(s) => s,
+                Err(e) => {
+                    let msg = format!("error reading '{}': {}", input.display(), e);
+                    report_error(&msg, output, quiet);
+                    process::exit(1);
+                }
+            };
+            match serde_json::from_str(&json_str) {
+                Ok(v) => v,
+                Err(e) => {
+                    let msg = format!("error parsing JSON in '{}': {}", input.display(), e);
                     report_error(&msg, output, quiet);
                     process::exit(1);
                 }
             }
         }
+        _ => {
+            let msg = format!(
+                "unsupported input file type '{}': expected .tenor or .json",
+                input.display()
+            );
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
     }
 }
@@ -0,0 +1,151 @@
+use std::path::Path;
+use std::process;
+
+use serde_json::Value;
+
+use crate::{report_error, OutputFormat};
+
+/// Print the construct summary of a contract: facts, entities, rules,
+/// personas, operations and flows.
+pub(crate) fn cmd_inspect(file: &Path, output: OutputFormat, quiet: bool) {
+    let bundle = super::generate::load_bundle(file, output, quiet);
+
+    let summary = match tenor_interchange::build_inspect(&bundle) {
+        Ok(s) => s,
+        Err(e) => {
+            let msg = format!("inspect error: {}", e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    match output {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&summary).unwrap_or_default()
+            );
+        }
+        OutputFormat::Text => {
+            if !quiet {
+                print!("{}", render_text(&summary));
+            }
+        }
+    }
+}
+
+fn render_text(summary: &Value) -> String {
+    let mut out = String::new();
+    let section = |out: &mut String, title: &str, key: &str| -> Vec<Value> {
+        let items = summary[key].as_array().cloned().unwrap_or_default();
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("{} ({})\n", title, items.len()));
+        items
+    };
+
+    for fact in section(&mut out, "Facts", "facts") {
+        out.push_str(&format!("  {}: {}", str_of(&fact["id"]), type_label(&fact)));
+        if let Some(source) = fact.get("source") {
+            out.push_str(&format!("  source: {}", source_label(source)));
+        }
+        if fact.get("has_default").is_some() {
+            out.push_str("  (has default)");
+        }
+        out.push('\n');
+    }
+
+    for entity in section(&mut out, "Entities", "entities") {
+        out.push_str(&format!(
+            "  {}: {}  initial: {}\n",
+            str_of(&entity["id"]),
+            join(&entity["states"]),
+            str_of(&entity["initial"])
+        ));
+    }
+
+    for rule in section(&mut out, "Rules", "rules") {
+        out.push_str(&format!(
+            "  {} [stratum {}] -> {}  when {}\n",
+            str_of(&rule["id"]),
+            rule["stratum"],
+            str_of(&rule["produces"]),
+            str_of(&rule["condition_summary"])
+        ));
+    }
+
+    let personas = section(&mut out, "Personas", "personas");
+    if !personas.is_empty() {
+        let ids: Vec<&str> = personas.iter().map(|p| str_of(&p["id"])).collect();
+        out.push_str(&format!("  {}\n", ids.join(", ")));
+    }
+
+    for op in section(&mut out, "Operations", "operations") {
+        out.push_str(&format!(
+            "  {}  personas: {}\n",
+            str_of(&op["id"]),
+            join(&op["allowed_personas"])
+        ));
+        for effect in op["effects"].as_array().into_iter().flatten() {
+            out.push_str(&format!(
+                "    effect: {} {} -> {}\n",
+                str_of(&effect["entity_id"]),
+                str_of(&effect["from"]),
+                str_of(&effect["to"])
+            ));
+        }
+        if op["outcomes"].as_array().is_some_and(|o| !o.is_empty()) {
+            out.push_str(&format!("    outcomes: {}\n", join(&op["outcomes"])));
+        }
+    }
+
+    for flow in section(&mut out, "Flows", "flows") {
+        out.push_str(&format!(
+            "  {}  entry: {}  steps: {}\n",
+            str_of(&flow["id"]),
+            str_of(&flow["entry"]),
+            join(&flow["steps"])
+        ));
+    }
+
+    out
+}
+
+fn str_of(v: &Value) -> &str {
+    v.as_str().unwrap_or("?")
+}
+
+fn join(v: &Value) -> String {
+    v.as_array()
+        .map(|items| items.iter().map(str_of).collect::<Vec<_>>().join(", "))
+        .unwrap_or_default()
+}
+
+/// The fact's base type, with the parameters a reader most often needs.
+fn type_label(fact: &Value) -> String {
+    let base = str_of(&fact["type"]).to_string();
+    let spec = &fact["type_spec"];
+    if let Some(currency) = spec.get("currency").and_then(Value::as_str) {
+        format!("{}({})", base, currency)
+    } else if let Some(unit) = spec.get("unit").and_then(Value::as_str) {
+        format!("{}({})", base, unit)
+    } else {
+        base
+    }
+}
+
+fn source_label(source: &Value) -> String {
+    let part = |key: &str| source.get(key).and_then(Value::as_str);
+    match (
+        part("system"),
+        part("field"),
+        part("source_id"),
+        part("path"),
+    ) {
+        (Some(system), Some(field), _, _) => format!("{}.{}", system, field),
+        (_, _, Some(id), Some(path)) => format!("{}.{}", id, path),
+        (_, _, Some(id), None) => id.to_string(),
+        _ => source.to_string(),
+    }
+}
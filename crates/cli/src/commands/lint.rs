@@ -0,0 +1,115 @@
+use std::path::Path;
+use std::process;
+
+use crate::lint::{LintConfig, RULES};
+use crate::{report_error, OutputFormat};
+
+pub(crate) fn cmd_lint_list_rules(output: OutputFormat) {
+    match output {
+        OutputFormat::Text => {
+            for (name, description) in RULES {
+                println!("{:<26} {}", name, description);
+            }
+        }
+        OutputFormat::Json => {
+            let rules: Vec<serde_json::Value> = RULES
+                .iter()
+                .map(|(name, description)| {
+                    serde_json::json!({ "description": description, "rule": name })
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&rules).expect("rule list serializes")
+            );
+        }
+    }
+}
+
+pub(crate) fn cmd_lint(file: &Path, config: Option<&Path>, output: OutputFormat, quiet: bool) {
+    // --config names a project file to use instead of the discovered one
+    let project = match config {
+        Some(path) => tenor_core::ProjectConfig::load(path),
+        None => Ok(crate::project().clone()),
+    };
+    let project = match project {
+        Ok(p) => p,
+        Err(e) => {
+            report_error(&e, output, quiet);
+            process::exit(1);
+        }
+    };
+    let config = match LintConfig::from_section(&project.lint) {
+        Ok(c) => c,
+        Err(e) => {
+            report_error(&e, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let bundle = if file.extension().and_then(|e| e.to_str()) == Some("tenor") {
+        match crate::elaborate(file) {
+            Ok(b) => b,
+            Err(e) => {
+                report_error(&format!("elaboration error: {:?}", e), output, quiet);
+                process::exit(1);
+            }
+        }
+    } else {
+        let json_str = match std::fs::read_to_string(file) {
+            Ok(s) => s,
+            Err(e) => {
+                let msg = format!("error reading '{}': {}", file.display(), e);
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+        };
+        match serde_json::from_str(&json_str) {
+            Ok(v) => v,
+            Err(e) => {
+                let msg = format!("error parsing JSON in '{}': {}", file.display(), e);
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+        }
+    };
+
+    let findings = match crate::lint::lint(&bundle, &config) {
+        Ok(f) => f,
+        Err(e) => {
+            report_error(&format!("lint error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+
+    if !quiet {
+        match output {
+            OutputFormat::Json => {
+                let json = serde_json::json!({ "findings": findings });
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json).expect("findings serialize")
+                );
+            }
+            OutputFormat::Text => {
+                for f in &findings {
+                    let location = match (&f.file, f.line) {
+                        (Some(file), Some(line)) => format!("{}:{}: ", file, line),
+                        _ => String::new(),
+                    };
+                    println!("{}[{}] {}", location, f.rule, f.message);
+                }
+                if findings.is_empty() {
+                    println!("No lint findings.");
+                } else {
+                    println!();
+                    println!("{} lint finding(s)", findings.len());
+                }
+            }
+        }
+    }
+
+    if !findings.is_empty() {
+        process::exit(1);
+    }
+}
@@ -1,9 +1,31 @@
+pub(crate) mod actions;
 pub(crate) mod ambiguity;
 pub(crate) mod check;
+pub(crate) mod completions;
+pub(crate) mod conformance;
+pub(crate) mod decompile;
 pub(crate) mod diff;
 pub(crate) mod elaborate;
 pub(crate) mod eval;
 pub(crate) mod explain;
+pub(crate) mod explain_error;
 pub(crate) mod generate;
+pub(crate) mod inspect;
+pub(crate) mod lint;
+pub(crate) mod new;
+pub(crate) mod optimize;
+pub(crate) mod repl;
+pub(crate) mod simulate;
+pub(crate) mod solve;
 pub(crate) mod test;
+pub(crate) mod upgrade_bundle;
 pub(crate) mod validate;
+
+/// Read and parse a JSON file; `what` names the file in errors.
+pub(crate) fn read_json(path: &std::path::Path, what: &str) -> Result<serde_json::Value, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("{} file not found: {}", what, path.display()),
+        _ => format!("could not read {} file {}: {}", what, path.display(), e),
+    })?;
+    serde_json::from_str(&text).map_err(|e| format!("invalid JSON in {}: {}", path.display(), e))
+}
@@ -0,0 +1,86 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process;
+
+use crate::scaffold::{self, Starter};
+use crate::{report_error, OutputFormat};
+
+/// Names given on the command line; any left `None` are prompted for.
+pub(crate) struct NewArgs<'a> {
+    pub name: Option<&'a str>,
+    pub entity: Option<&'a str>,
+    pub requester: Option<&'a str>,
+    pub approver: Option<&'a str>,
+    /// Take the default for every missing name instead of prompting.
+    pub yes: bool,
+}
+
+pub(crate) fn cmd_new(dir: &Path, args: NewArgs<'_>, output: OutputFormat, quiet: bool) {
+    let default_name = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.replace('-', "_"))
+        .filter(|n| scaffold::is_identifier(n))
+        .unwrap_or_else(|| "contract".to_string());
+
+    let mut stdin = io::stdin().lock();
+    let mut ask = |given: Option<&str>, prompt: &str, default: &str| -> String {
+        if let Some(value) = given {
+            return value.to_string();
+        }
+        if args.yes {
+            return default.to_string();
+        }
+        eprint!("{} [{}]: ", prompt, default);
+        let _ = io::stderr().flush();
+        let mut line = String::new();
+        match stdin.read_line(&mut line) {
+            Ok(n) if n > 0 && !line.trim().is_empty() => line.trim().to_string(),
+            _ => default.to_string(),
+        }
+    };
+
+    let starter = Starter {
+        name: ask(args.name, "Contract name", &default_name),
+        entity: ask(args.entity, "Entity", "Request"),
+        requester: ask(args.requester, "Persona that submits", "requester"),
+        approver: ask(args.approver, "Persona that approves", "approver"),
+    };
+
+    let written = match scaffold::write_starter(dir, &starter) {
+        Ok(w) => w,
+        Err(e) => {
+            report_error(&e, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    if quiet {
+        return;
+    }
+    match output {
+        OutputFormat::Json => {
+            let files: Vec<String> = written.iter().map(|p| p.display().to_string()).collect();
+            let report = serde_json::json!({
+                "dir": dir.display().to_string(),
+                "contract": format!("{}.tenor", starter.name),
+                "files": files,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+        }
+        OutputFormat::Text => {
+            println!("created {}:", dir.display());
+            for path in &written {
+                println!("  {}", path.display());
+            }
+            println!();
+            println!("next steps:");
+            println!("  cd {}", dir.display());
+            println!("  tenor test --unit {}.tenor", starter.name);
+            println!("  tenor check {}.tenor", starter.name);
+        }
+    }
+}
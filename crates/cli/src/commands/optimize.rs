@@ -0,0 +1,67 @@
+use std::path::Path;
+use std::process;
+
+use crate::{report_error, OutputFormat};
+
+pub(crate) fn cmd_optimize(
+    bundle: &Path,
+    out: Option<&Path>,
+    strip_provenance: bool,
+    report: bool,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let json_str = match std::fs::read_to_string(bundle) {
+        Ok(s) => s,
+        Err(e) => {
+            let msg = format!("error reading '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+    let mut value: serde_json::Value = match serde_json::from_str(&json_str) {
+        Ok(v) => v,
+        Err(e) => {
+            let msg = format!("error parsing JSON in '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let stats = match crate::optimize::optimize(&mut value, strip_provenance) {
+        Ok(r) => r,
+        Err(e) => {
+            report_error(&format!("optimize error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+
+    // Refuse to emit a bundle the evaluator cannot load
+    if let Err(e) = tenor_eval::Contract::from_interchange(&value) {
+        let msg = format!("optimized bundle failed to load: {}", e);
+        report_error(&msg, output, quiet);
+        process::exit(1);
+    }
+
+    let optimized = serde_json::to_string(&value).expect("bundle serializes");
+    match out {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, &optimized) {
+                let msg = format!("error writing '{}': {}", path.display(), e);
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+            if !quiet {
+                println!("wrote {}", path.display());
+            }
+        }
+        None => println!("{}", optimized),
+    }
+
+    if report && !quiet {
+        match output {
+            OutputFormat::Text => eprint!("{}", stats.to_text()),
+            OutputFormat::Json => eprintln!("{}", stats.to_json()),
+        }
+    }
+}
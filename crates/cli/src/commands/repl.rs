@@ -0,0 +1,25 @@
+use std::path::Path;
+use std::process;
+
+use crate::{report_error, OutputFormat};
+
+pub(crate) fn cmd_repl(
+    bundle: &Path,
+    facts: &Path,
+    script: Option<&Path>,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let bundle = super::generate::load_bundle(bundle, output, quiet);
+    let facts = std::fs::read_to_string(facts)
+        .map_err(|e| format!("error reading '{}': {}", facts.display(), e))
+        .and_then(|s| {
+            serde_json::from_str(&s)
+                .map_err(|e| format!("error parsing JSON in '{}': {}", facts.display(), e))
+        });
+    let result = facts.and_then(|facts| crate::repl::run_repl(&bundle, &facts, script));
+    if let Err(e) = result {
+        report_error(&e, output, quiet);
+        process::exit(1);
+    }
+}
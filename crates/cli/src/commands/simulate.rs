@@ -0,0 +1,187 @@
+use std::path::Path;
+use std::process;
+
+use super::read_json;
+use crate::{report_error, OutputFormat};
+
+pub(crate) fn cmd_simulate(
+    bundle: &Path,
+    seed: u64,
+    count: usize,
+    flow: Option<&str>,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let json_str = match std::fs::read_to_string(bundle) {
+        Ok(s) => s,
+        Err(e) => {
+            let msg = format!("error reading '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+    let value: serde_json::Value = match serde_json::from_str(&json_str) {
+        Ok(v) => v,
+        Err(e) => {
+            let msg = format!("error parsing JSON in '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+    let contract = match tenor_eval::Contract::from_interchange(&value) {
+        Ok(c) => c,
+        Err(e) => {
+            report_error(&format!("error loading contract: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let samples = match crate::random_walk::random_walk(&contract, seed, count, flow) {
+        Ok(s) => s,
+        Err(e) => {
+            report_error(&e, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    if quiet {
+        return;
+    }
+    match output {
+        OutputFormat::Json => {
+            let report = serde_json::json!({ "seed": seed, "count": count, "flows": samples });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+        }
+        OutputFormat::Text => {
+            println!("random walk: seed {}, {} walks per flow", seed, count);
+            for sample in &samples {
+                println!();
+                println!("flow {} (persona {})", sample.flow_id, sample.persona);
+                for (outcome, n) in &sample.outcomes {
+                    println!("  {:<12} {:>5}  {}", outcome, n, percent(*n, count));
+                }
+                if sample.inadmissible > 0 {
+                    println!(
+                        "  {:<12} {:>5}  {}",
+                        "inadmissible",
+                        sample.inadmissible,
+                        percent(sample.inadmissible, count)
+                    );
+                }
+                if sample.errors > 0 {
+                    println!(
+                        "  {:<12} {:>5}  {}",
+                        "error",
+                        sample.errors,
+                        percent(sample.errors, count)
+                    );
+                }
+                println!("  paths:");
+                for (path, n) in &sample.paths {
+                    println!("    {:>5}  {}", n, path);
+                }
+            }
+        }
+    }
+}
+
+/// Run `flow_id` once against the given facts, entity states and instance
+/// bindings, reporting what would happen without persisting anything. The
+/// JSON output matches the WASM `simulate_flow_with_bindings` result.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn cmd_simulate_flow(
+    bundle: &Path,
+    flow_id: &str,
+    persona: &str,
+    facts: &Path,
+    entity_states: Option<&Path>,
+    bindings: Option<&Path>,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let fail = |msg: String| -> ! {
+        report_error(&msg, output, quiet);
+        process::exit(1);
+    };
+
+    let contract = read_json(bundle, "bundle")
+        .and_then(|v| tenor_eval::Contract::from_interchange(&v).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| fail(e));
+    let facts = read_json(facts, "facts").unwrap_or_else(|e| fail(e));
+
+    // Start from the initial states; the file overrides individual instances.
+    let mut states = tenor_eval::operation::init_entity_states(&contract);
+    if let Some(path) = entity_states {
+        let overrides = read_json(path, "entity states")
+            .and_then(|v| tenor_eval::simulate::parse_entity_states(&v))
+            .unwrap_or_else(|e| fail(format!("invalid entity states: {}", e)));
+        states.extend(overrides);
+    }
+    let instance_bindings = match bindings {
+        Some(path) => read_json(path, "instance bindings")
+            .and_then(|v| tenor_eval::simulate::parse_instance_bindings(&v))
+            .unwrap_or_else(|e| fail(format!("invalid instance bindings: {}", e))),
+        None => tenor_eval::InstanceBindingMap::new(),
+    };
+
+    let result = tenor_eval::evaluate_contract_flow(
+        &contract,
+        &facts,
+        flow_id,
+        persona,
+        Some(&states),
+        &instance_bindings,
+    )
+    .unwrap_or_else(|e| fail(format!("simulation error: {}", e)));
+
+    if quiet {
+        return;
+    }
+    let report =
+        tenor_eval::simulate::simulation_json(flow_id, persona, &result, &instance_bindings);
+    match output {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        ),
+        OutputFormat::Text => {
+            println!("simulation: flow {} (persona {})", flow_id, persona);
+            println!("outcome: {}", result.flow_result.outcome);
+            println!("path:");
+            for step in &result.flow_result.steps_executed {
+                println!(
+                    "  {:<24} {:<16} {}",
+                    step.step_id, step.step_type, step.result
+                );
+            }
+            if !result.flow_result.entity_state_changes.is_empty() {
+                println!("would transition:");
+                for e in &result.flow_result.entity_state_changes {
+                    println!(
+                        "  {}[{}] {} -> {}",
+                        e.entity_id, e.instance_id, e.from_state, e.to_state
+                    );
+                }
+            }
+            let verdicts: Vec<&str> = result
+                .verdicts
+                .0
+                .iter()
+                .map(|v| v.verdict_type.as_str())
+                .collect();
+            if !verdicts.is_empty() {
+                println!("verdicts: {}", verdicts.join(", "));
+            }
+        }
+    }
+}
+
+fn percent(n: usize, total: usize) -> String {
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!("{:.1}%", n as f64 * 100.0 / total as f64)
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process;
+
+use super::read_json;
+use crate::{report_error, OutputFormat};
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn cmd_solve(
+    bundle: &Path,
+    flow_id: &str,
+    outcome: &str,
+    persona: Option<&str>,
+    entity_states: Option<&Path>,
+    max_assignments: usize,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let contract = match read_json(bundle, "bundle")
+        .and_then(|v| tenor_eval::Contract::from_interchange(&v).map_err(|e| e.to_string()))
+    {
+        Ok(c) => c,
+        Err(e) => {
+            report_error(&e, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    // Start from the initial states; the file overrides entity_id -> state.
+    let mut states = tenor_eval::operation::init_entity_states(&contract);
+    if let Some(path) = entity_states {
+        let overrides = read_json(path, "entity states").and_then(|v| {
+            serde_json::from_value::<BTreeMap<String, String>>(v)
+                .map_err(|e| format!("invalid entity states in '{}': {}", path.display(), e))
+        });
+        match overrides {
+            Ok(overrides) => states.extend(tenor_eval::single_instance(overrides)),
+            Err(e) => {
+                report_error(&e, output, quiet);
+                process::exit(1);
+            }
+        }
+    }
+
+    let persona = persona
+        .map(str::to_string)
+        .unwrap_or_else(|| crate::random_walk::entry_persona(&contract, flow_id));
+    let result = match tenor_eval::solve_flow_outcome(
+        &contract,
+        flow_id,
+        &persona,
+        outcome,
+        &states,
+        max_assignments,
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            report_error(&format!("solve error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+
+    if quiet {
+        return;
+    }
+    match output {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&result).unwrap_or_default()
+        ),
+        OutputFormat::Text => {
+            println!(
+                "flow {} -> {} (persona {})",
+                result.flow_id, result.outcome, result.persona
+            );
+            match result.status {
+                tenor_eval::SolveStatus::Satisfiable => {
+                    println!(
+                        "satisfiable after {} assignment(s)",
+                        result.assignments_tried
+                    );
+                    if let Some(facts) = &result.facts {
+                        println!(
+                            "facts: {}",
+                            serde_json::to_string_pretty(facts).unwrap_or_default()
+                        );
+                    }
+                    println!("path: {}", result.steps.join(" -> "));
+                }
+                tenor_eval::SolveStatus::Unsatisfiable => println!(
+                    "unsatisfiable: no facts reach '{}' from these entity states ({} assignment(s) checked)",
+                    result.outcome, result.assignments_tried
+                ),
+                tenor_eval::SolveStatus::Unknown => {
+                    println!(
+                        "unknown: no facts found in {} assignment(s), search not exhaustive",
+                        result.assignments_tried
+                    );
+                    if !result.approximate_facts.is_empty() {
+                        println!(
+                            "approximate domains: {}",
+                            result.approximate_facts.join(", ")
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,18 +1,99 @@
 use std::path::Path;
 use std::process;
 
-pub(crate) fn cmd_test(suite_dir: &Path, quiet: bool) {
+use crate::tap::Tap;
+use crate::{report_error, OutputFormat};
+
+/// Run the conformance suite in `suite_dir`, or the cases matching `filter`,
+/// and print the report as TAP (text output) or as JSON.
+pub(crate) fn cmd_test(suite_dir: &Path, filter: Option<&str>, output: OutputFormat, quiet: bool) {
     if !suite_dir.exists() {
-        eprintln!(
+        let msg = format!(
             "error: conformance suite directory not found: {}",
             suite_dir.display()
         );
+        report_error(&msg, output, quiet);
+        process::exit(1);
+    }
+
+    let report = crate::runner::run_suite(suite_dir, filter);
+    if let (Some(filter), 0) = (filter, report.tests.len()) {
+        let msg = format!("error: no conformance cases match filter '{}'", filter);
+        report_error(&msg, output, quiet);
         process::exit(1);
     }
 
-    let _ = quiet; // TAP output is the primary output; quiet has no effect on test runner
-    let result = crate::runner::run_suite(suite_dir);
-    if result.failed > 0 {
+    match output {
+        OutputFormat::Text => {
+            let mut tap = Tap::new();
+            for test in &report.tests {
+                match test.diagnostics() {
+                    None => tap.ok(&test.name),
+                    Some(diag) => tap.not_ok(&test.name, diag),
+                }
+            }
+            tap.finish();
+        }
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+        }
+    }
+
+    if report.failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Run the `test` constructs of a `.tenor` file or interchange bundle as TAP.
+pub(crate) fn cmd_test_unit(path: &Path, output: OutputFormat, quiet: bool) {
+    let bundle = if path.extension().and_then(|e| e.to_str()) == Some("tenor") {
+        match crate::elaborate(path) {
+            Ok(b) => b,
+            Err(e) => {
+                report_error(&format!("elaboration error: {:?}", e), output, quiet);
+                process::exit(1);
+            }
+        }
+    } else {
+        let json_str = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => {
+                let msg = format!("error reading '{}': {}", path.display(), e);
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+        };
+        match serde_json::from_str(&json_str) {
+            Ok(v) => v,
+            Err(e) => {
+                let msg = format!("error parsing JSON in '{}': {}", path.display(), e);
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+        }
+    };
+
+    let outcomes = match crate::unit_test::run_unit_tests(&bundle) {
+        Ok(o) => o,
+        Err(e) => {
+            report_error(&format!("error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let mut tap = Tap::new();
+    for outcome in outcomes {
+        match outcome.failure {
+            None => tap.ok(outcome.id),
+            Some(diag) => tap.not_ok(outcome.id, diag),
+        }
+    }
+    let failed = tap.failure_count();
+    tap.finish();
+    if failed > 0 {
         process::exit(1);
     }
 }
@@ -0,0 +1,62 @@
+use std::path::Path;
+use std::process;
+
+use crate::{report_error, OutputFormat};
+
+pub(crate) fn cmd_upgrade_bundle(
+    bundle: &Path,
+    to: Option<&str>,
+    out: Option<&Path>,
+    output: OutputFormat,
+    quiet: bool,
+) {
+    let json_str = match std::fs::read_to_string(bundle) {
+        Ok(s) => s,
+        Err(e) => {
+            let msg = format!("error reading '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+    let value: serde_json::Value = match serde_json::from_str(&json_str) {
+        Ok(v) => v,
+        Err(e) => {
+            let msg = format!("error parsing JSON in '{}': {}", bundle.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let from = match value.get("tenor_version").and_then(|v| v.as_str()) {
+        Some(v) => v.to_string(),
+        None => {
+            let msg = format!("'{}' has no tenor_version", bundle.display());
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+    };
+    let to = to.unwrap_or(tenor_interchange::INTERCHANGE_VERSION);
+
+    let upgraded = match tenor_interchange::migrate_bundle(&value, &from, to) {
+        Ok(v) => v,
+        Err(e) => {
+            report_error(&format!("upgrade error: {}", e), output, quiet);
+            process::exit(1);
+        }
+    };
+
+    let upgraded_str = serde_json::to_string_pretty(&upgraded).expect("bundle serializes");
+    match out {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, format!("{}\n", upgraded_str)) {
+                let msg = format!("error writing '{}': {}", path.display(), e);
+                report_error(&msg, output, quiet);
+                process::exit(1);
+            }
+            if !quiet {
+                eprintln!("upgraded {} -> {}: wrote {}", from, to, path.display());
+            }
+        }
+        None => println!("{}", upgraded_str),
+    }
+}
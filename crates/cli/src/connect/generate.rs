@@ -1,10 +1,13 @@
-//! Adapter scaffolding generation.
+//! Adapter generation.
 //!
 //! Produces:
 //! - Adapter configuration TOML template
+//! - Runnable adapters for HTTP sources (Rust, optionally TypeScript)
 //! - Test file validating each adapter's return type
 //! - Mapping documentation for human review
 
+mod adapters;
+
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
@@ -13,13 +16,20 @@ use super::matching::FactMapping;
 use super::StructuredFact;
 use tenor_interchange::SourceConstruct;
 
-/// Generate scaffolding files in the output directory.
+/// Generate adapter files in the output directory.
+///
+/// The adapter config goes to `config_path` when given (`[connect]
+/// adapter_config` in tenor.toml), otherwise into the output directory.
+/// `adapters.rs`, and `adapters.ts` with `typescript`, are written when the
+/// contract has HTTP sources.
 pub fn generate_scaffolding(
     sources: &[SourceConstruct],
     facts: &[StructuredFact],
     mappings: &[FactMapping],
     schemas: &BTreeMap<String, ExternalSchema>,
     output_dir: &Path,
+    config_path: Option<&Path>,
+    typescript: bool,
 ) -> Result<Vec<PathBuf>, String> {
     std::fs::create_dir_all(output_dir)
         .map_err(|e| format!("could not create output directory: {}", e))?;
@@ -27,20 +37,44 @@ pub fn generate_scaffolding(
     let mut files = Vec::new();
 
     // 1. Generate adapter config TOML
-    let config_path = output_dir.join("tenor-adapters.toml");
-    let config_content = generate_config_toml(sources);
+    let config_path = match config_path {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("could not create config directory: {}", e))?;
+            }
+            path.to_path_buf()
+        }
+        None => output_dir.join("tenor-adapters.toml"),
+    };
+    let http = adapters::http_facts(sources, facts, mappings);
+    let config_content = generate_config_toml(sources, &http);
     std::fs::write(&config_path, &config_content)
         .map_err(|e| format!("could not write config: {}", e))?;
     files.push(config_path);
 
-    // 2. Generate test file
+    // 2. Generate adapters for HTTP sources
+    if !http.is_empty() {
+        let rust_path = output_dir.join("adapters.rs");
+        std::fs::write(&rust_path, adapters::generate_rust_adapters(sources, &http))
+            .map_err(|e| format!("could not write adapters: {}", e))?;
+        files.push(rust_path);
+        if typescript {
+            let ts_path = output_dir.join("adapters.ts");
+            std::fs::write(&ts_path, adapters::generate_ts_adapters(sources, &http))
+                .map_err(|e| format!("could not write adapters: {}", e))?;
+            files.push(ts_path);
+        }
+    }
+
+    // 3. Generate test file
     let test_path = output_dir.join("adapter_tests.rs");
     let test_content = generate_test_file(sources, facts, mappings);
     std::fs::write(&test_path, &test_content)
         .map_err(|e| format!("could not write tests: {}", e))?;
     files.push(test_path);
 
-    // 3. Generate mapping documentation
+    // 4. Generate mapping documentation
     let doc_path = output_dir.join("MAPPINGS.md");
     let doc_content = generate_mapping_docs(sources, facts, mappings, schemas);
     std::fs::write(&doc_path, &doc_content).map_err(|e| format!("could not write docs: {}", e))?;
@@ -50,13 +84,17 @@ pub fn generate_scaffolding(
 }
 
 /// Generate a TOML configuration template for adapter setup.
-fn generate_config_toml(sources: &[SourceConstruct]) -> String {
+///
+/// HTTP sources list the endpoint placeholders their generated adapter
+/// fills in.
+fn generate_config_toml(sources: &[SourceConstruct], http: &[adapters::HttpFact]) -> String {
     let mut out = String::new();
     out.push_str("# Tenor Adapter Configuration\n");
     out.push_str("# Generated by `tenor connect`\n");
     out.push_str("#\n");
     out.push_str("# Fill in connection details for each source.\n");
-    out.push_str("# Credentials should come from environment variables,\n");
+    out.push_str("# Credentials should come from environment variables or secret\n");
+    out.push_str("# references (env://VAR, vault://path#field, aws-sm://id#field),\n");
     out.push_str("# not from this file (see comments below).\n\n");
 
     for source in sources {
@@ -81,6 +119,19 @@ fn generate_config_toml(sources: &[SourceConstruct]) -> String {
                 if let Some(schema_ref) = source.fields.get("schema_ref") {
                     out.push_str(&format!("schema_ref = \"{}\"\n", schema_ref));
                 }
+                let mut params: Vec<&str> = http
+                    .iter()
+                    .filter(|f| f.source_id == source.id)
+                    .flat_map(|f| adapters::placeholders(&f.endpoint))
+                    .collect();
+                params.sort_unstable();
+                params.dedup();
+                if !params.is_empty() {
+                    out.push_str("# Endpoint parameters\n");
+                    for param in params {
+                        out.push_str(&format!("{} = \"TODO\"\n", param));
+                    }
+                }
             }
             "database" => {
                 let dialect = source
@@ -112,6 +163,18 @@ fn generate_config_toml(sources: &[SourceConstruct]) -> String {
                     .unwrap_or("localhost:50051");
                 out.push_str(&format!("endpoint = \"{}\"\n", endpoint));
             }
+            "file" => {
+                let format = source
+                    .fields
+                    .get("format")
+                    .map(|s| s.as_str())
+                    .unwrap_or("csv");
+                out.push_str(&format!("path = \"TODO.{}\"\n", format));
+                out.push_str("# Select the row whose key_column equals key\n");
+                out.push_str("# (both may be omitted for a single-row file)\n");
+                out.push_str("key_column = \"TODO\"\n");
+                out.push_str("key = \"TODO\"\n");
+            }
             "static" => {
                 out.push_str("# Static values: set TENOR_STATIC_");
                 out.push_str(&source.id.to_uppercase());
@@ -517,16 +580,45 @@ mod tests {
             },
             description: Some("Order API".to_string()),
             provenance: None,
+            annotations: Default::default(),
             tenor: None,
         };
 
-        let toml = generate_config_toml(&[source]);
+        let toml = generate_config_toml(&[source], &[]);
         assert!(toml.contains("[source.order_service]"));
         assert!(toml.contains("protocol = \"http\""));
         assert!(toml.contains("base_url = \"https://api.example.com\""));
         assert!(toml.contains("TENOR_SOURCE_ORDER_SERVICE_AUTH_TOKEN"));
     }
 
+    #[test]
+    fn test_generate_config_toml_endpoint_parameters() {
+        let source = SourceConstruct {
+            id: "order_service".to_string(),
+            protocol: "http".to_string(),
+            fields: BTreeMap::new(),
+            description: None,
+            provenance: None,
+            annotations: Default::default(),
+            tenor: None,
+        };
+        let fact = |fact_id, endpoint: &str| adapters::HttpFact {
+            fact_id,
+            source_id: "order_service",
+            endpoint: endpoint.to_string(),
+            field: "",
+            fact_type: "Int",
+        };
+        let http = [
+            fact("a", "/orders/{order_id}/lines/{line}"),
+            fact("b", "/orders/{order_id}"),
+        ];
+
+        let toml = generate_config_toml(&[source], &http);
+        assert!(toml.contains("# Endpoint parameters\nline = \"TODO\"\norder_id = \"TODO\"\n"));
+        let _: toml::Value = toml::from_str(&toml).expect("valid TOML");
+    }
+
     #[test]
     fn test_generate_config_toml_database() {
         let source = SourceConstruct {
@@ -539,12 +631,35 @@ mod tests {
             },
             description: None,
             provenance: None,
+            annotations: Default::default(),
             tenor: None,
         };
 
-        let toml = generate_config_toml(&[source]);
+        let toml = generate_config_toml(&[source], &[]);
         assert!(toml.contains("[source.compliance_db]"));
         assert!(toml.contains("dialect = \"postgres\""));
         assert!(toml.contains("TENOR_SOURCE_COMPLIANCE_DB_CONNECTION_STRING"));
     }
+
+    #[test]
+    fn test_generate_config_toml_file() {
+        let source = SourceConstruct {
+            id: "ledger_extract".to_string(),
+            protocol: "file".to_string(),
+            fields: {
+                let mut m = BTreeMap::new();
+                m.insert("format".to_string(), "jsonl".to_string());
+                m
+            },
+            description: None,
+            provenance: None,
+            annotations: Default::default(),
+            tenor: None,
+        };
+
+        let toml = generate_config_toml(&[source], &[]);
+        assert!(toml.contains("[source.ledger_extract]"));
+        assert!(toml.contains("path = \"TODO.jsonl\""));
+        assert!(toml.contains("key_column = \"TODO\""));
+    }
 }
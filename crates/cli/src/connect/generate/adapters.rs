@@ -0,0 +1,615 @@
+//! Runnable adapter generation for HTTP sources.
+//!
+//! Emits a Rust module of [`FactAdapter`](tenor_eval::FactAdapter)
+//! implementations to register over the built-in adapters, and optionally a
+//! TypeScript module fetching the same facts into a fact document. Both GET
+//! the endpoint mapped to each fact, extract the mapped response field and
+//! check it against the fact's declared type.
+
+use std::collections::BTreeMap;
+
+use super::super::matching::FactMapping;
+use super::super::StructuredFact;
+use tenor_interchange::SourceConstruct;
+
+/// Where a generated adapter reads one fact.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct HttpFact<'a> {
+    pub fact_id: &'a str,
+    pub source_id: &'a str,
+    /// Endpoint path relative to the source's `base_url`, or an absolute URL
+    pub endpoint: String,
+    /// Dot path into the response; empty for the whole response
+    pub field: &'a str,
+    pub fact_type: &'a str,
+}
+
+/// The facts of every HTTP source, with their endpoints. A fact without a
+/// reviewed endpoint reads its declared path as URL segments, as the
+/// built-in HTTP adapter does.
+pub(super) fn http_facts<'a>(
+    sources: &'a [SourceConstruct],
+    facts: &'a [StructuredFact],
+    mappings: &'a [FactMapping],
+) -> Vec<HttpFact<'a>> {
+    let http_sources: BTreeMap<&str, &SourceConstruct> = sources
+        .iter()
+        .filter(|s| s.protocol == "http")
+        .map(|s| (s.id.as_str(), s))
+        .collect();
+    let mapping_map: BTreeMap<&str, &FactMapping> =
+        mappings.iter().map(|m| (m.fact_id.as_str(), m)).collect();
+
+    facts
+        .iter()
+        .filter_map(|fact| {
+            let source = http_sources.get(fact.source_id.as_str())?;
+            let base_url = source.fields.get("base_url").map(|s| s.as_str());
+            let mapped = mapping_map.get(fact.id.as_str()).and_then(|m| {
+                let endpoint = m.endpoint.as_deref()?;
+                Some((relative_endpoint(endpoint, base_url)?, m.path.as_str()))
+            });
+            let (endpoint, field) =
+                mapped.unwrap_or_else(|| (format!("/{}", fact.path.replace('.', "/")), ""));
+            Some(HttpFact {
+                fact_id: &fact.id,
+                source_id: &fact.source_id,
+                endpoint,
+                field,
+                fact_type: fact
+                    .fact_type
+                    .get("base")
+                    .and_then(|b| b.as_str())
+                    .unwrap_or("Unknown"),
+            })
+        })
+        .collect()
+}
+
+/// An endpoint relative to `base_url` when it lies under it. `None` when
+/// the endpoint is neither a path nor a URL (e.g. an unmatched description).
+fn relative_endpoint(endpoint: &str, base_url: Option<&str>) -> Option<String> {
+    if let Some(rest) = base_url
+        .map(|b| b.trim_end_matches('/'))
+        .filter(|b| !b.is_empty())
+        .and_then(|b| endpoint.strip_prefix(b))
+    {
+        return Some(format!("/{}", rest.trim_start_matches('/')));
+    }
+    let is_url = endpoint.starts_with("http://") || endpoint.starts_with("https://");
+    (endpoint.starts_with('/') || is_url).then(|| endpoint.to_string())
+}
+
+/// The `{name}` placeholders in an endpoint.
+pub(super) fn placeholders(endpoint: &str) -> impl Iterator<Item = &str> + '_ {
+    endpoint
+        .split('{')
+        .skip(1)
+        .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+}
+
+/// Generate `adapters.rs`: one adapter per HTTP source and a
+/// `register_adapters` function installing them in an `AdapterRegistry`.
+pub(super) fn generate_rust_adapters(sources: &[SourceConstruct], http: &[HttpFact]) -> String {
+    let mut out = String::new();
+    out.push_str(RUST_HEADER);
+
+    out.push_str("/// Register an adapter for each HTTP source, replacing the built-in one.\n");
+    out.push_str(
+        "pub fn register_adapters(registry: &mut AdapterRegistry, config: &AdapterConfig) {\n",
+    );
+    for source in http_sources(sources, http) {
+        let base_url = source
+            .fields
+            .get("base_url")
+            .map(|s| s.as_str())
+            .unwrap_or("");
+        out.push_str("    registry.register(\n");
+        out.push_str(&format!("        {:?}.to_string(),\n", source.id));
+        out.push_str("        BTreeMap::new(),\n");
+        out.push_str("        Box::new(HttpSourceAdapter::new(\n");
+        out.push_str(&format!("            {:?},\n", source.id));
+        out.push_str(&format!("            {:?},\n", base_url));
+        out.push_str(&format!(
+            "            {}_FACTS,\n",
+            source.id.to_uppercase()
+        ));
+        out.push_str("            config,\n");
+        out.push_str("        )),\n");
+        out.push_str("    );\n");
+    }
+    out.push_str("}\n\n");
+
+    for source in http_sources(sources, http) {
+        match &source.description {
+            Some(desc) => out.push_str(&format!(
+                "/// Facts read from `{}` ({}).\n",
+                source.id, desc
+            )),
+            None => out.push_str(&format!("/// Facts read from `{}`.\n", source.id)),
+        }
+        out.push_str(&format!(
+            "const {}_FACTS: &[FactEndpoint] = &[\n",
+            source.id.to_uppercase()
+        ));
+        for fact in http.iter().filter(|f| f.source_id == source.id) {
+            out.push_str("    FactEndpoint {\n");
+            out.push_str(&format!("        fact_id: {:?},\n", fact.fact_id));
+            out.push_str(&format!("        endpoint: {:?},\n", fact.endpoint));
+            out.push_str(&format!("        field: {:?},\n", fact.field));
+            out.push_str(&format!("        fact_type: {:?},\n", fact.fact_type));
+            out.push_str("    },\n");
+        }
+        out.push_str("];\n\n");
+    }
+
+    out.push_str(RUST_RUNTIME);
+    out
+}
+
+/// Generate `adapters.ts`: `fetchFact` and `fetchFacts` over the same
+/// endpoints as the Rust adapters.
+pub(super) fn generate_ts_adapters(sources: &[SourceConstruct], http: &[HttpFact]) -> String {
+    let js = |s: &str| serde_json::to_string(s).unwrap_or_default();
+    let mut out = String::new();
+    out.push_str(TS_HEADER);
+
+    out.push_str("const BASE_URLS: Record<string, string> = {\n");
+    for source in http_sources(sources, http) {
+        let base_url = source
+            .fields
+            .get("base_url")
+            .map(|s| s.as_str())
+            .unwrap_or("");
+        out.push_str(&format!("  {}: {},\n", js(&source.id), js(base_url)));
+    }
+    out.push_str("};\n\n");
+
+    out.push_str("const FACTS: FactEndpoint[] = [\n");
+    for fact in http {
+        out.push_str("  {\n");
+        out.push_str(&format!("    factId: {},\n", js(fact.fact_id)));
+        out.push_str(&format!("    sourceId: {},\n", js(fact.source_id)));
+        out.push_str(&format!("    endpoint: {},\n", js(&fact.endpoint)));
+        out.push_str(&format!("    field: {},\n", js(fact.field)));
+        out.push_str(&format!("    factType: {},\n", js(fact.fact_type)));
+        out.push_str("  },\n");
+    }
+    out.push_str("];\n\n");
+
+    out.push_str(TS_RUNTIME);
+    out
+}
+
+/// HTTP sources that have at least one fact, in declaration order.
+fn http_sources<'a>(
+    sources: &'a [SourceConstruct],
+    http: &'a [HttpFact],
+) -> impl Iterator<Item = &'a SourceConstruct> {
+    sources
+        .iter()
+        .filter(move |s| http.iter().any(|f| f.source_id == s.id))
+}
+
+const RUST_HEADER: &str = r#"//! Fact adapters for the contract's HTTP sources
+//! Generated by `tenor connect`
+//!
+//! Each adapter GETs the endpoint mapped to a fact, extracts the mapped
+//! response field and checks it against the fact's declared type. Register
+//! them over the built-in adapters:
+//!
+//! ```ignore
+//! let config = AdapterConfig::from_toml("tenor-adapters.toml".as_ref())?;
+//! let mut registry = AdapterRegistry::from_sources(&sources, &config);
+//! adapters::register_adapters(&mut registry, &config);
+//! ```
+//!
+//! Requires tenor-eval (feature "adapter"), async-trait, serde_json, tokio
+//! and ureq 3 (feature "json"). Sources with other protocols keep their
+//! built-in adapters.
+
+use std::collections::{BTreeMap, HashMap};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tenor_eval::{AdapterConfig, AdapterError, AdapterRegistry, FactAdapter, StructuredSourceRef};
+
+"#;
+
+const RUST_RUNTIME: &str = r#"/// Where an adapter reads one fact. `field` is a dot path into the
+/// response; empty for the whole response.
+struct FactEndpoint {
+    fact_id: &'static str,
+    endpoint: &'static str,
+    field: &'static str,
+    fact_type: &'static str,
+}
+
+/// Fetches a source's facts from their endpoints with HTTP GET.
+pub struct HttpSourceAdapter {
+    source_id: String,
+    base_url: String,
+    auth_token: Option<String>,
+    /// Values for `{name}` placeholders in endpoints
+    params: HashMap<String, String>,
+    facts: &'static [FactEndpoint],
+}
+
+impl HttpSourceAdapter {
+    /// `base_url`, `auth_token` and endpoint placeholders are read from the
+    /// source's adapter config. The token falls back to the
+    /// `TENOR_SOURCE_<ID>_AUTH_TOKEN` env var, `base_url` to the contract's.
+    fn new(
+        source_id: &str,
+        base_url: &str,
+        facts: &'static [FactEndpoint],
+        config: &AdapterConfig,
+    ) -> Self {
+        let params = facts
+            .iter()
+            .flat_map(|f| placeholders(f.endpoint))
+            .filter_map(|name| {
+                let value = config.get(source_id, name)?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        let auth_token = config
+            .get(source_id, "auth_token")
+            .map(str::to_string)
+            .or_else(|| {
+                let env_key = format!("TENOR_SOURCE_{}_AUTH_TOKEN", source_id.to_uppercase());
+                std::env::var(env_key).ok()
+            });
+        HttpSourceAdapter {
+            source_id: source_id.to_string(),
+            base_url: config.get(source_id, "base_url").unwrap_or(base_url).to_string(),
+            auth_token,
+            params,
+            facts,
+        }
+    }
+
+    /// The URL of `endpoint` with its placeholders filled in.
+    fn url(&self, endpoint: &str) -> Result<String, AdapterError> {
+        let mut path = endpoint.to_string();
+        for name in placeholders(endpoint) {
+            let value = self.params.get(name).ok_or_else(|| AdapterError::ConfigError {
+                message: format!(
+                    "source '{}' needs '{}' in its adapter config",
+                    self.source_id, name
+                ),
+            })?;
+            path = path.replace(&format!("{{{}}}", name), value);
+        }
+        if path.starts_with("http://") || path.starts_with("https://") {
+            return Ok(path);
+        }
+        Ok(format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        ))
+    }
+
+    async fn get(&self, url: String) -> Result<Value, AdapterError> {
+        let auth_token = self.auth_token.clone();
+        let source_id = self.source_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let failed = |message: String| AdapterError::FetchFailed {
+                source_id: source_id.clone(),
+                message,
+            };
+            let mut request = ureq::Agent::new_with_defaults().get(&url);
+            if let Some(token) = &auth_token {
+                request = request.header("Authorization", &format!("Bearer {}", token));
+            }
+            let response = request.call().map_err(|e| failed(e.to_string()))?;
+            response
+                .into_body()
+                .read_json()
+                .map_err(|e| failed(format!("failed to parse response as JSON: {}", e)))
+        })
+        .await
+        .map_err(|e| AdapterError::FetchFailed {
+            source_id: self.source_id.clone(),
+            message: format!("task join error: {}", e),
+        })?
+    }
+}
+
+#[async_trait]
+impl FactAdapter for HttpSourceAdapter {
+    async fn fetch(
+        &self,
+        fact_id: &str,
+        _source: &StructuredSourceRef,
+        _source_fields: &BTreeMap<String, String>,
+    ) -> Result<Value, AdapterError> {
+        let fact = self
+            .facts
+            .iter()
+            .find(|f| f.fact_id == fact_id)
+            .ok_or_else(|| AdapterError::FetchFailed {
+                source_id: self.source_id.clone(),
+                message: format!("no endpoint mapped for fact '{}'", fact_id),
+            })?;
+        let body = self.get(self.url(fact.endpoint)?).await?;
+        let value = extract(&body, fact.field).ok_or_else(|| AdapterError::FetchFailed {
+            source_id: self.source_id.clone(),
+            message: format!(
+                "response from {} has no field '{}'",
+                fact.endpoint, fact.field
+            ),
+        })?;
+        check_type(fact_id, fact.fact_type, value)
+    }
+
+    fn adapter_id(&self) -> &str {
+        "http"
+    }
+}
+
+/// The `{name}` placeholders in an endpoint.
+fn placeholders(endpoint: &str) -> impl Iterator<Item = &str> + '_ {
+    endpoint
+        .split('{')
+        .skip(1)
+        .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+}
+
+/// The value at dot path `field` in `body`. A response that is a bare
+/// value is the fact value itself.
+fn extract<'a>(body: &'a Value, field: &str) -> Option<&'a Value> {
+    if field.is_empty() || !(body.is_object() || body.is_array()) {
+        return Some(body);
+    }
+    field.split('.').try_fold(body, |v, key| v.get(key))
+}
+
+/// Check that `value` has the JSON shape of a `fact_type` fact.
+fn check_type(fact_id: &str, fact_type: &str, value: &Value) -> Result<Value, AdapterError> {
+    let ok = match fact_type {
+        "Int" => value.is_i64() || value.is_u64(),
+        "Decimal" => value.is_number() || value.is_string(),
+        "Bool" => value.is_boolean(),
+        "Text" | "Enum" | "Date" | "DateTime" => value.is_string(),
+        "Money" => value.get("amount").is_some() && value.get("currency").is_some(),
+        "List" => value.is_array(),
+        "Record" => value.is_object(),
+        _ => true,
+    };
+    if ok {
+        Ok(value.clone())
+    } else {
+        Err(AdapterError::TypeMismatch {
+            fact_id: fact_id.to_string(),
+            message: format!("expected {}, got {}", fact_type, value),
+        })
+    }
+}
+"#;
+
+const TS_HEADER: &str = r#"// Fact adapters for the contract's HTTP sources
+// Generated by `tenor connect`
+//
+// fetchFacts() GETs the endpoint mapped to each fact, extracts the mapped
+// response field and checks it against the fact's declared type. Pass the
+// result to TenorEvaluator.evaluate().
+
+/** Per-source settings. Unset values fall back to the contract's. */
+export interface SourceConfig {
+  baseUrl?: string;
+  /** Defaults to the TENOR_SOURCE_<ID>_AUTH_TOKEN env var */
+  authToken?: string;
+  /** Values for `{name}` placeholders in endpoints */
+  params?: Record<string, string>;
+}
+
+/** Settings keyed by source id. */
+export type AdapterConfig = Record<string, SourceConfig>;
+
+interface FactEndpoint {
+  factId: string;
+  sourceId: string;
+  endpoint: string;
+  /** Dot path into the response; empty for the whole response */
+  field: string;
+  factType: string;
+}
+
+"#;
+
+const TS_RUNTIME: &str = r#"/** Fetch one fact's value. */
+export async function fetchFact(factId: string, config: AdapterConfig = {}): Promise<unknown> {
+  const fact = FACTS.find((f) => f.factId === factId);
+  if (!fact) {
+    throw new Error(`no endpoint mapped for fact '${factId}'`);
+  }
+  const source = config[fact.sourceId] ?? {};
+  const response = await fetch(url(fact, source), { headers: headers(fact.sourceId, source) });
+  if (!response.ok) {
+    throw new Error(`fetch failed for source '${fact.sourceId}': HTTP ${response.status}`);
+  }
+  const value = extract(await response.json(), fact.field);
+  if (value === undefined) {
+    throw new Error(`response from ${fact.endpoint} has no field '${fact.field}'`);
+  }
+  if (!hasType(value, fact.factType)) {
+    throw new Error(
+      `type mismatch for fact '${fact.factId}': expected ${fact.factType}, got ${JSON.stringify(value)}`,
+    );
+  }
+  return value;
+}
+
+/** Fetch every fact, as a fact document for TenorEvaluator.evaluate(). */
+export async function fetchFacts(config: AdapterConfig = {}): Promise<Record<string, unknown>> {
+  const entries = await Promise.all(
+    FACTS.map(async (f) => [f.factId, await fetchFact(f.factId, config)] as const),
+  );
+  return Object.fromEntries(entries);
+}
+
+function url(fact: FactEndpoint, source: SourceConfig): string {
+  const path = fact.endpoint.replace(/\{(\w+)\}/g, (_, name: string) => {
+    const value = source.params?.[name];
+    if (value === undefined) {
+      throw new Error(`source '${fact.sourceId}' needs params.${name}`);
+    }
+    return value;
+  });
+  if (/^https?:\/\//.test(path)) {
+    return path;
+  }
+  const base = source.baseUrl ?? BASE_URLS[fact.sourceId];
+  return `${base.replace(/\/+$/, "")}/${path.replace(/^\/+/, "")}`;
+}
+
+function headers(sourceId: string, source: SourceConfig): Record<string, string> {
+  const env = (globalThis as { process?: { env: Record<string, string | undefined> } }).process
+    ?.env;
+  const token = source.authToken ?? env?.[`TENOR_SOURCE_${sourceId.toUpperCase()}_AUTH_TOKEN`];
+  return token ? { Authorization: `Bearer ${token}` } : {};
+}
+
+/** The value at dot path `field` in `body`. A bare value is the fact value itself. */
+function extract(body: unknown, field: string): unknown {
+  if (field === "" || typeof body !== "object" || body === null) {
+    return body;
+  }
+  return field
+    .split(".")
+    .reduce<unknown>(
+      (v, key) =>
+        typeof v === "object" && v !== null ? (v as Record<string, unknown>)[key] : undefined,
+      body,
+    );
+}
+
+/** Whether `value` has the JSON shape of a `factType` fact. */
+function hasType(value: unknown, factType: string): boolean {
+  switch (factType) {
+    case "Int":
+      return Number.isInteger(value);
+    case "Decimal":
+      return typeof value === "number" || typeof value === "string";
+    case "Bool":
+      return typeof value === "boolean";
+    case "Text":
+    case "Enum":
+    case "Date":
+    case "DateTime":
+      return typeof value === "string";
+    case "Money":
+      return typeof value === "object" && value !== null && "amount" in value && "currency" in value;
+    case "List":
+      return Array.isArray(value);
+    case "Record":
+      return typeof value === "object" && value !== null && !Array.isArray(value);
+    default:
+      return true;
+  }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connect::matching::Confidence;
+
+    fn http_source() -> SourceConstruct {
+        SourceConstruct {
+            id: "order_service".to_string(),
+            protocol: "http".to_string(),
+            fields: BTreeMap::from([(
+                "base_url".to_string(),
+                "https://api.example.com".to_string(),
+            )]),
+            description: Some("Order API".to_string()),
+            provenance: None,
+            annotations: Default::default(),
+            tenor: None,
+        }
+    }
+
+    fn fact(id: &str, base: &str, path: &str) -> StructuredFact {
+        StructuredFact {
+            id: id.to_string(),
+            fact_type: serde_json::json!({ "base": base }),
+            source_id: "order_service".to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    fn mapping(fact_id: &str, endpoint: &str, path: &str) -> FactMapping {
+        FactMapping {
+            fact_id: fact_id.to_string(),
+            source_id: "order_service".to_string(),
+            path: path.to_string(),
+            confidence: Confidence::High,
+            description: format!("{} -> {}", endpoint, path),
+            note: None,
+            endpoint: Some(endpoint.to_string()),
+        }
+    }
+
+    #[test]
+    fn reviewed_endpoints_are_used_and_unmapped_facts_read_their_path() {
+        let sources = [http_source()];
+        let facts = [
+            fact("order_balance", "Int", "orders.balance"),
+            fact("order_status", "Text", "orders.status"),
+            fact("customer_tier", "Text", "customers.tier"),
+        ];
+        let mappings = [
+            mapping("order_balance", "/orders/{id}", "balance"),
+            mapping(
+                "order_status",
+                "https://api.example.com/orders/status",
+                "status",
+            ),
+        ];
+        let http = http_facts(&sources, &facts, &mappings);
+        let endpoints: Vec<(&str, &str)> = http
+            .iter()
+            .map(|f| (f.endpoint.as_str(), f.field))
+            .collect();
+        assert_eq!(
+            endpoints,
+            vec![
+                ("/orders/{id}", "balance"),
+                ("/orders/status", "status"),
+                ("/customers/tier", ""),
+            ]
+        );
+        assert_eq!(
+            placeholders("/orders/{id}/lines/{line}").collect::<Vec<_>>(),
+            ["id", "line"]
+        );
+    }
+
+    #[test]
+    fn rust_adapters_register_each_http_source() {
+        let sources = [http_source()];
+        let facts = [fact("order_balance", "Int", "orders.balance")];
+        let mappings = [mapping("order_balance", "/orders/{id}", "balance")];
+        let out = generate_rust_adapters(&sources, &http_facts(&sources, &facts, &mappings));
+        assert!(out.contains("impl FactAdapter for HttpSourceAdapter"));
+        assert!(out.contains("            \"order_service\",\n            \"https://api.example.com\",\n            ORDER_SERVICE_FACTS,"));
+        assert!(out.contains(
+            "/// Facts read from `order_service` (Order API).\nconst ORDER_SERVICE_FACTS"
+        ));
+        assert!(out.contains("        endpoint: \"/orders/{id}\",\n        field: \"balance\",\n        fact_type: \"Int\","));
+        assert!(!out.contains("TODO"));
+    }
+
+    #[test]
+    fn ts_adapters_list_every_fact() {
+        let sources = [http_source()];
+        let facts = [fact("order_balance", "Int", "orders.balance")];
+        let out = generate_ts_adapters(&sources, &http_facts(&sources, &facts, &[]));
+        assert!(out.contains("  \"order_service\": \"https://api.example.com\",\n"));
+        assert!(out.contains("    endpoint: \"/orders/balance\",\n    field: \"\",\n"));
+        assert!(out.contains("export async function fetchFacts("));
+    }
+}
@@ -4,23 +4,34 @@
 use async_trait::async_trait;
 use tenor_interchange::SourceConstruct;
 
-use super::matching::{self, FactMapping};
+use super::matching::{self, FactMapping, Synonyms};
 use super::provider::{
     EnvironmentInventory, FactDeclaration, MappingProposal, MatchingError, MatchingProvider,
 };
 use super::StructuredFact;
 
-/// A matching provider that uses heuristic path similarity and type
-/// compatibility checks (the original `match_facts` algorithm).
+/// A matching provider that scores name similarity (with synonyms), type
+/// compatibility and path structure (the `match_facts` algorithm).
 pub struct HeuristicMatchingProvider {
     /// Source constructs needed by `match_facts`.
     sources: Vec<SourceConstruct>,
+    /// Synonym dictionary used when comparing names.
+    synonyms: Synonyms,
 }
 
 impl HeuristicMatchingProvider {
     /// Create a new heuristic provider with the given source constructs.
     pub fn new(sources: Vec<SourceConstruct>) -> Self {
-        Self { sources }
+        Self {
+            sources,
+            synonyms: Synonyms::default(),
+        }
+    }
+
+    /// Use `synonyms` instead of the built-in synonym dictionary.
+    pub fn with_synonyms(mut self, synonyms: Synonyms) -> Self {
+        self.synonyms = synonyms;
+        self
     }
 }
 
@@ -49,8 +60,12 @@ impl MatchingProvider for HeuristicMatchingProvider {
             .collect();
 
         // Call the existing heuristic matcher
-        let mappings =
-            matching::match_facts(&self.sources, &structured_facts, &environment.schemas);
+        let mappings = matching::match_facts(
+            &self.sources,
+            &structured_facts,
+            &environment.schemas,
+            &self.synonyms,
+        );
 
         // Convert FactMapping -> MappingProposal
         let proposals = mappings.into_iter().map(fact_mapping_to_proposal).collect();
@@ -124,6 +139,7 @@ mod tests {
             },
             description: Some("Test source".to_string()),
             provenance: None,
+            annotations: Default::default(),
             tenor: None,
         }
     }
@@ -240,6 +256,7 @@ mod tests {
             confidence: Confidence::High,
             description: "/orders/{id} http -> field 'balance'".to_string(),
             note: None,
+            endpoint: None,
         };
 
         let proposal = fact_mapping_to_proposal(mapping);
@@ -259,6 +276,7 @@ mod tests {
             confidence: Confidence::Medium,
             description: "/orders/{id} http -> field 'balance'".to_string(),
             note: Some("type mismatch: tenor Int vs external string".to_string()),
+            endpoint: None,
         };
 
         let proposal = fact_mapping_to_proposal(mapping);
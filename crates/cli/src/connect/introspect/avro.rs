@@ -0,0 +1,465 @@
+//! Avro introspection: `.avsc` schemas and `.avpr` protocols.
+//!
+//! A protocol's messages become endpoints whose parameters are the request
+//! parameters and whose response fields are the response record flattened
+//! into dot paths. A plain schema has no messages, so each top-level record
+//! becomes an endpoint of its own.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::{Endpoint, ExternalSchema, ExternalType, SchemaField, SchemaFormat};
+
+/// Parse an Avro schema or protocol (JSON) and extract endpoints.
+///
+/// Handles:
+/// - Protocols (`"protocol"` with `"types"` and `"messages"`)
+/// - Schemas that are a single named type or a union of them
+/// - Named type references, namespaces and nullable unions (`["null", T]`)
+/// - Logical types (`decimal` as a number; dates and timestamps as strings)
+pub(crate) fn introspect_avro(schema_path: &Path) -> Result<ExternalSchema, String> {
+    let content = std::fs::read_to_string(schema_path).map_err(|e| {
+        format!(
+            "could not read schema file '{}': {}",
+            schema_path.display(),
+            e
+        )
+    })?;
+
+    let doc: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("invalid JSON in '{}': {}", schema_path.display(), e))?;
+
+    let endpoints = if doc.get("protocol").is_some() {
+        protocol_endpoints(&doc)
+    } else {
+        schema_endpoints(&doc)
+    };
+
+    if endpoints.is_empty() {
+        return Err(format!(
+            "no Avro records or messages found in '{}'",
+            schema_path.display()
+        ));
+    }
+
+    Ok(ExternalSchema {
+        format: SchemaFormat::Avro,
+        endpoints,
+    })
+}
+
+/// One endpoint per message of an Avro protocol.
+fn protocol_endpoints(doc: &Value) -> Vec<Endpoint> {
+    let namespace = doc.get("namespace").and_then(|n| n.as_str()).unwrap_or("");
+    let mut named = NamedTypes::default();
+    for ty in doc
+        .get("types")
+        .and_then(|t| t.as_array())
+        .into_iter()
+        .flatten()
+    {
+        named.collect(ty, namespace);
+    }
+
+    let messages = doc.get("messages").and_then(|m| m.as_object());
+    let mut endpoints = Vec::new();
+    for (name, message) in messages.into_iter().flatten() {
+        let request = message.get("request").and_then(|r| r.as_array());
+        let parameters = request
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
+            .map(str::to_string)
+            .collect();
+
+        let mut response_fields = Vec::new();
+        if let Some(response) = message.get("response") {
+            match named.resolve(response, namespace) {
+                (_, Some((record, ns))) => named.flatten(record, &ns, "", &mut response_fields, 0),
+                (ExternalType::Unknown(t), None) if t == "null" => {}
+                (field_type, None) => response_fields.push(SchemaField {
+                    path: name.clone(),
+                    field_type,
+                }),
+            }
+        }
+
+        endpoints.push(Endpoint {
+            method: "MESSAGE".to_string(),
+            path: name.clone(),
+            parameters,
+            response_fields,
+        });
+    }
+    endpoints
+}
+
+/// One endpoint per top-level record of an Avro schema.
+fn schema_endpoints(doc: &Value) -> Vec<Endpoint> {
+    let top_level: Vec<&Value> = match doc {
+        Value::Array(types) => types.iter().collect(),
+        other => vec![other],
+    };
+
+    let mut named = NamedTypes::default();
+    for ty in &top_level {
+        named.collect(ty, "");
+    }
+
+    let mut endpoints = Vec::new();
+    for ty in top_level {
+        if let (_, Some((record, ns))) = named.resolve(ty, "") {
+            let mut response_fields = Vec::new();
+            named.flatten(record, &ns, "", &mut response_fields, 0);
+            endpoints.push(Endpoint {
+                method: "RECORD".to_string(),
+                path: full_name(record, "").0,
+                parameters: vec![],
+                response_fields,
+            });
+        }
+    }
+    endpoints
+}
+
+/// Named types (records, enums, fixed) by full name, with the namespace
+/// their own references resolve in.
+#[derive(Default)]
+struct NamedTypes<'a> {
+    types: BTreeMap<String, (&'a Value, String)>,
+}
+
+impl<'a> NamedTypes<'a> {
+    /// Register every named type defined in `ty`, including nested ones.
+    fn collect(&mut self, ty: &'a Value, namespace: &str) {
+        match ty {
+            Value::Array(branches) => {
+                for branch in branches {
+                    self.collect(branch, namespace);
+                }
+            }
+            Value::Object(obj) => match obj.get("type").and_then(|t| t.as_str()) {
+                Some("record" | "error" | "enum" | "fixed") => {
+                    let (name, ns) = full_name(ty, namespace);
+                    for field in obj
+                        .get("fields")
+                        .and_then(|f| f.as_array())
+                        .into_iter()
+                        .flatten()
+                    {
+                        if let Some(field_type) = field.get("type") {
+                            self.collect(field_type, &ns);
+                        }
+                    }
+                    self.types.insert(name, (ty, ns));
+                }
+                Some("array") => {
+                    if let Some(items) = obj.get("items") {
+                        self.collect(items, namespace);
+                    }
+                }
+                Some("map") => {
+                    if let Some(values) = obj.get("values") {
+                        self.collect(values, namespace);
+                    }
+                }
+                _ => {
+                    if let Some(inner @ (Value::Object(_) | Value::Array(_))) = obj.get("type") {
+                        self.collect(inner, namespace);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Look up a named type reference made inside `namespace`.
+    fn lookup(&self, name: &str, namespace: &str) -> Option<&(&'a Value, String)> {
+        if name.contains('.') || namespace.is_empty() {
+            return self.types.get(name);
+        }
+        self.types
+            .get(&format!("{}.{}", namespace, name))
+            .or_else(|| self.types.get(name))
+    }
+
+    /// The external type of an Avro type, and the record it names (with
+    /// the record's namespace) if it is one.
+    fn resolve(
+        &self,
+        ty: &'a Value,
+        namespace: &str,
+    ) -> (ExternalType, Option<(&'a Value, String)>) {
+        match ty {
+            Value::String(name) => match map_avro_primitive(name) {
+                Some(t) => (t, None),
+                None => match self.lookup(name, namespace) {
+                    Some((def, ns)) => self.resolve(def, ns),
+                    None => (ExternalType::Unknown(name.clone()), None),
+                },
+            },
+            Value::Array(branches) => {
+                let non_null: Vec<&Value> = branches
+                    .iter()
+                    .filter(|b| b.as_str() != Some("null"))
+                    .collect();
+                match non_null.as_slice() {
+                    [single] => self.resolve(single, namespace),
+                    _ => (ExternalType::Unknown("union".to_string()), None),
+                }
+            }
+            Value::Object(obj) => {
+                if let Some(t) = obj
+                    .get("logicalType")
+                    .and_then(|l| l.as_str())
+                    .and_then(map_avro_logical_type)
+                {
+                    return (t, None);
+                }
+                match obj.get("type") {
+                    Some(Value::String(t)) => match t.as_str() {
+                        "record" | "error" => {
+                            let (_, ns) = full_name(ty, namespace);
+                            (ExternalType::Object, Some((ty, ns)))
+                        }
+                        "enum" => (ExternalType::String, None),
+                        "array" => (ExternalType::Array, None),
+                        "map" => (ExternalType::Object, None),
+                        "fixed" => (ExternalType::Unknown("binary".to_string()), None),
+                        _ => self.resolve(&obj["type"], namespace),
+                    },
+                    Some(inner) => self.resolve(inner, namespace),
+                    None => (ExternalType::Unknown("unspecified".to_string()), None),
+                }
+            }
+            other => (ExternalType::Unknown(other.to_string()), None),
+        }
+    }
+
+    /// Flatten the fields of a record into dot-path notation.
+    fn flatten(
+        &self,
+        record: &'a Value,
+        namespace: &str,
+        prefix: &str,
+        fields: &mut Vec<SchemaField>,
+        depth: usize,
+    ) {
+        if depth > 3 {
+            return; // Depth limit to prevent infinite recursion
+        }
+
+        let record_fields = record.get("fields").and_then(|f| f.as_array());
+        for field in record_fields.into_iter().flatten() {
+            let (Some(name), Some(ty)) = (
+                field.get("name").and_then(|n| n.as_str()),
+                field.get("type"),
+            ) else {
+                continue;
+            };
+            let full_path = if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+
+            let (field_type, nested) = self.resolve(ty, namespace);
+            fields.push(SchemaField {
+                path: full_path.clone(),
+                field_type,
+            });
+            if let Some((nested, ns)) = nested {
+                self.flatten(nested, &ns, &full_path, fields, depth + 1);
+            }
+        }
+    }
+}
+
+/// The full name of a named type and the namespace it defines: a dotted
+/// `name` is already full, otherwise `namespace` (its own or the
+/// enclosing one) qualifies it.
+fn full_name(ty: &Value, enclosing: &str) -> (String, String) {
+    let name = ty.get("name").and_then(|n| n.as_str()).unwrap_or("");
+    if let Some((ns, _)) = name.rsplit_once('.') {
+        return (name.to_string(), ns.to_string());
+    }
+    let ns = ty
+        .get("namespace")
+        .and_then(|n| n.as_str())
+        .unwrap_or(enclosing);
+    if ns.is_empty() {
+        (name.to_string(), String::new())
+    } else {
+        (format!("{}.{}", ns, name), ns.to_string())
+    }
+}
+
+/// Map an Avro primitive type name to ExternalType.
+fn map_avro_primitive(name: &str) -> Option<ExternalType> {
+    match name {
+        "null" => Some(ExternalType::Unknown("null".to_string())),
+        "boolean" => Some(ExternalType::Boolean),
+        "int" | "long" => Some(ExternalType::Integer),
+        "float" | "double" => Some(ExternalType::Number),
+        "string" => Some(ExternalType::String),
+        "bytes" => Some(ExternalType::Unknown("binary".to_string())),
+        _ => None,
+    }
+}
+
+/// Map an Avro logical type to the type of the value it represents.
+fn map_avro_logical_type(logical_type: &str) -> Option<ExternalType> {
+    match logical_type {
+        "decimal" => Some(ExternalType::Number),
+        "uuid"
+        | "date"
+        | "time-millis"
+        | "time-micros"
+        | "timestamp-millis"
+        | "timestamp-micros"
+        | "local-timestamp-millis"
+        | "local-timestamp-micros" => Some(ExternalType::String),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field<'a>(endpoint: &'a Endpoint, path: &str) -> &'a SchemaField {
+        endpoint
+            .response_fields
+            .iter()
+            .find(|f| f.path == path)
+            .unwrap_or_else(|| panic!("no field '{}'", path))
+    }
+
+    #[test]
+    fn test_introspect_avro_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("order.avsc");
+        std::fs::write(
+            &schema_path,
+            r#"{
+  "type": "record",
+  "name": "Order",
+  "namespace": "com.example.shop",
+  "fields": [
+    { "name": "id", "type": "string" },
+    { "name": "quantity", "type": "int" },
+    { "name": "amount", "type": { "type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2 } },
+    { "name": "placed_at", "type": { "type": "long", "logicalType": "timestamp-millis" } },
+    { "name": "paid", "type": ["null", "boolean"], "default": null },
+    { "name": "status", "type": { "type": "enum", "name": "Status", "symbols": ["OPEN", "CLOSED"] } },
+    { "name": "customer", "type": {
+        "type": "record", "name": "Customer",
+        "fields": [
+          { "name": "email", "type": "string" },
+          { "name": "tier", "type": "Status" }
+        ]
+    } },
+    { "name": "tags", "type": { "type": "array", "items": "string" } },
+    { "name": "attributes", "type": { "type": "map", "values": "string" } },
+    { "name": "referrer", "type": ["null", "Customer"] }
+  ]
+}"#,
+        )
+        .unwrap();
+
+        let result = introspect_avro(&schema_path).unwrap();
+        assert_eq!(result.format, SchemaFormat::Avro);
+        assert_eq!(result.endpoints.len(), 1);
+
+        let order = &result.endpoints[0];
+        assert_eq!(order.method, "RECORD");
+        assert_eq!(order.path, "com.example.shop.Order");
+        assert_eq!(field(order, "id").field_type, ExternalType::String);
+        assert_eq!(field(order, "quantity").field_type, ExternalType::Integer);
+        assert_eq!(field(order, "amount").field_type, ExternalType::Number);
+        assert_eq!(field(order, "placed_at").field_type, ExternalType::String);
+        assert_eq!(field(order, "paid").field_type, ExternalType::Boolean);
+        assert_eq!(field(order, "status").field_type, ExternalType::String);
+        assert_eq!(field(order, "customer").field_type, ExternalType::Object);
+        assert_eq!(
+            field(order, "customer.email").field_type,
+            ExternalType::String
+        );
+        assert_eq!(
+            field(order, "customer.tier").field_type,
+            ExternalType::String
+        );
+        assert_eq!(field(order, "tags").field_type, ExternalType::Array);
+        assert_eq!(field(order, "attributes").field_type, ExternalType::Object);
+        assert_eq!(
+            field(order, "referrer.email").field_type,
+            ExternalType::String
+        );
+    }
+
+    #[test]
+    fn test_introspect_avro_protocol() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("ledger.avpr");
+        std::fs::write(
+            &schema_path,
+            r#"{
+  "protocol": "Ledger",
+  "namespace": "com.example.ledger",
+  "types": [
+    { "type": "record", "name": "Balance", "fields": [
+        { "name": "amount", "type": "long" },
+        { "name": "currency", "type": "string" }
+    ] }
+  ],
+  "messages": {
+    "getBalance": {
+      "request": [{ "name": "account_id", "type": "string" }],
+      "response": "Balance"
+    },
+    "isFrozen": {
+      "request": [{ "name": "account_id", "type": "string" }],
+      "response": "boolean"
+    },
+    "ping": { "request": [], "response": "null" }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let result = introspect_avro(&schema_path).unwrap();
+        assert_eq!(result.endpoints.len(), 3);
+
+        let balance = result
+            .endpoints
+            .iter()
+            .find(|e| e.path == "getBalance")
+            .unwrap();
+        assert_eq!(balance.method, "MESSAGE");
+        assert_eq!(balance.parameters, vec!["account_id".to_string()]);
+        assert_eq!(field(balance, "amount").field_type, ExternalType::Integer);
+        assert_eq!(field(balance, "currency").field_type, ExternalType::String);
+
+        let frozen = result
+            .endpoints
+            .iter()
+            .find(|e| e.path == "isFrozen")
+            .unwrap();
+        assert_eq!(field(frozen, "isFrozen").field_type, ExternalType::Boolean);
+
+        let ping = result.endpoints.iter().find(|e| e.path == "ping").unwrap();
+        assert!(ping.response_fields.is_empty());
+    }
+
+    #[test]
+    fn test_introspect_avro_no_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("plain.avsc");
+        std::fs::write(&schema_path, r#""string""#).unwrap();
+
+        let err = introspect_avro(&schema_path).unwrap_err();
+        assert!(err.contains("no Avro records"), "got: {}", err);
+    }
+}
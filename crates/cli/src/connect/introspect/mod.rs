@@ -2,17 +2,22 @@
 //!
 //! When a Source has a `schema_ref` field, this module fetches and parses
 //! the external schema to extract endpoints, field types, and structure.
-//! Supports OpenAPI 3.x (JSON), GraphQL SDL, and SQL DDL.
+//! Supports OpenAPI 3.x (JSON), GraphQL SDL, SQL DDL, Protocol Buffers
+//! (`.proto` files and descriptor sets), and Avro schemas and protocols.
 
+mod avro;
 mod graphql;
 mod openapi;
+mod protobuf;
 mod sql;
 
 use std::fmt;
 use std::path::Path;
 
+use avro::introspect_avro;
 use graphql::introspect_graphql;
 use openapi::introspect_openapi;
+use protobuf::{introspect_descriptor_set, introspect_proto, introspect_protobuf};
 use sql::introspect_sql_ddl;
 
 /// An introspected external schema.
@@ -32,6 +37,8 @@ pub enum SchemaFormat {
     OpenApi3,
     GraphQl,
     SqlDdl,
+    Protobuf,
+    Avro,
 }
 
 /// An endpoint extracted from an external schema.
@@ -109,9 +116,13 @@ pub fn introspect_schema(protocol: &str, schema_path: &Path) -> Result<ExternalS
     match ext {
         "graphql" | "gql" => introspect_graphql(schema_path),
         "sql" => introspect_sql_ddl(schema_path),
+        "proto" => introspect_proto(schema_path),
+        "desc" | "pb" | "binpb" | "protoset" => introspect_descriptor_set(schema_path),
+        "avsc" | "avpr" => introspect_avro(schema_path),
         "json" => {
-            // Could be OpenAPI -- check content
+            // Could be OpenAPI or an Avro schema -- check content
             introspect_openapi(schema_path)
+                .or_else(|err| introspect_avro(schema_path).map_err(|_| err))
         }
         _ => {
             // Fall back to protocol hint
@@ -119,7 +130,7 @@ pub fn introspect_schema(protocol: &str, schema_path: &Path) -> Result<ExternalS
                 "http" => introspect_openapi(schema_path),
                 "graphql" => introspect_graphql(schema_path),
                 "database" => introspect_sql_ddl(schema_path),
-                "grpc" => Err("gRPC proto introspection not yet implemented".to_string()),
+                "grpc" => introspect_protobuf(schema_path),
                 _ => Err(format!(
                     "schema introspection not supported for protocol '{}'",
                     protocol
@@ -133,9 +144,14 @@ pub fn introspect_schema(protocol: &str, schema_path: &Path) -> Result<ExternalS
 ///
 /// Detection logic:
 /// - `.json` extension + contains `"openapi"` key -> OpenAPI
+/// - `.json` extension otherwise -> Avro
 /// - `.graphql` or `.gql` extension -> GraphQL SDL
 /// - `.sql` extension -> SQL DDL
-/// - Otherwise -> try each parser in order (OpenAPI, GraphQL, SQL), return first success
+/// - `.proto` extension -> protobuf source
+/// - `.desc`, `.pb`, `.binpb` or `.protoset` extension -> protobuf descriptor set
+/// - `.avsc` or `.avpr` extension -> Avro schema or protocol
+/// - Otherwise -> try each parser in order (OpenAPI, GraphQL, SQL, protobuf,
+///   Avro), return first success
 pub fn detect_and_introspect(schema_path: &Path) -> Result<ExternalSchema, String> {
     let ext = schema_path
         .extension()
@@ -155,13 +171,18 @@ pub fn detect_and_introspect(schema_path: &Path) -> Result<ExternalSchema, Strin
             if content.contains("\"openapi\"") {
                 return introspect_openapi(schema_path);
             }
-            Err(format!(
-                "JSON file '{}' does not appear to be an OpenAPI document",
-                schema_path.display()
-            ))
+            introspect_avro(schema_path).map_err(|_| {
+                format!(
+                    "JSON file '{}' does not appear to be an OpenAPI document or Avro schema",
+                    schema_path.display()
+                )
+            })
         }
         "graphql" | "gql" => introspect_graphql(schema_path),
         "sql" => introspect_sql_ddl(schema_path),
+        "proto" => introspect_proto(schema_path),
+        "desc" | "pb" | "binpb" | "protoset" => introspect_descriptor_set(schema_path),
+        "avsc" | "avpr" => introspect_avro(schema_path),
         _ => {
             // Try each parser in order
             if let Ok(schema) = introspect_openapi(schema_path) {
@@ -173,6 +194,12 @@ pub fn detect_and_introspect(schema_path: &Path) -> Result<ExternalSchema, Strin
             if let Ok(schema) = introspect_sql_ddl(schema_path) {
                 return Ok(schema);
             }
+            if let Ok(schema) = introspect_protobuf(schema_path) {
+                return Ok(schema);
+            }
+            if let Ok(schema) = introspect_avro(schema_path) {
+                return Ok(schema);
+            }
             Err(format!(
                 "could not detect schema format for '{}'",
                 schema_path.display()
@@ -284,6 +311,56 @@ type Query {
         assert_eq!(result.format, SchemaFormat::OpenApi3);
     }
 
+    #[test]
+    fn test_detect_and_introspect_proto_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("orders.proto");
+        std::fs::write(
+            &schema_path,
+            r#"
+syntax = "proto3";
+service Orders { rpc Get(Req) returns (Resp); }
+message Req { string id = 1; }
+message Resp { int64 total = 1; }
+"#,
+        )
+        .unwrap();
+
+        let result = detect_and_introspect(&schema_path).unwrap();
+        assert_eq!(result.format, SchemaFormat::Protobuf);
+    }
+
+    #[test]
+    fn test_detect_and_introspect_avro_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("order.json");
+        let schema = serde_json::json!({
+            "type": "record",
+            "name": "Order",
+            "fields": [{ "name": "total", "type": "long" }]
+        });
+        std::fs::write(&schema_path, serde_json::to_string_pretty(&schema).unwrap()).unwrap();
+
+        let result = detect_and_introspect(&schema_path).unwrap();
+        assert_eq!(result.format, SchemaFormat::Avro);
+    }
+
+    #[test]
+    fn test_introspect_schema_grpc_protocol_fallback() {
+        // A proto file without a recognized extension is found via the grpc protocol
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("orders.schema");
+        std::fs::write(
+            &schema_path,
+            "service Orders { rpc Get(Req) returns (Req); }\nmessage Req { string id = 1; }\n",
+        )
+        .unwrap();
+
+        let result = introspect_schema("grpc", &schema_path).unwrap();
+        assert_eq!(result.format, SchemaFormat::Protobuf);
+        assert_eq!(result.endpoints[0].path, "/Orders/Get");
+    }
+
     #[test]
     fn test_introspect_schema_extension_override() {
         // Even with protocol "http", a .graphql file should use the GraphQL parser
@@ -0,0 +1,1071 @@
+//! Protocol Buffers introspection: `.proto` files and compiled descriptor
+//! sets (`protoc --descriptor_set_out`).
+//!
+//! Each RPC of each service becomes an endpoint whose path is the gRPC
+//! method path (`/package.Service/Method`), whose parameters are the
+//! request message's fields, and whose response fields are the response
+//! message's fields flattened into dot paths.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use super::{Endpoint, ExternalSchema, ExternalType, SchemaField, SchemaFormat};
+
+/// Message, enum and service definitions gathered from a schema.
+#[derive(Debug, Default)]
+struct ProtoSchema {
+    /// Messages by fully-qualified name (without the leading dot).
+    messages: BTreeMap<String, ProtoMessage>,
+    /// Fully-qualified enum names.
+    enums: BTreeSet<String>,
+    services: Vec<ProtoService>,
+}
+
+/// A message definition.
+#[derive(Debug, Default)]
+struct ProtoMessage {
+    fields: Vec<ProtoField>,
+    /// Entry message synthesized for a `map<K, V>` field (descriptor sets only).
+    map_entry: bool,
+}
+
+/// A field within a message definition.
+#[derive(Debug)]
+struct ProtoField {
+    name: String,
+    /// Scalar keyword (`int64`, `string`, ...) or a message/enum reference.
+    type_name: String,
+    repeated: bool,
+    /// Declared as `map<K, V>`; `type_name` is the value type.
+    map: bool,
+}
+
+/// A service definition.
+#[derive(Debug)]
+struct ProtoService {
+    /// Fully-qualified service name.
+    name: String,
+    methods: Vec<ProtoMethod>,
+}
+
+/// An RPC within a service.
+#[derive(Debug)]
+struct ProtoMethod {
+    name: String,
+    input_type: String,
+    output_type: String,
+}
+
+/// Parse a `.proto` file and extract one endpoint per RPC.
+///
+/// This is a simple token-based parser that handles:
+/// - `package`, `message`, `enum`, `service` and `rpc` declarations
+/// - Nested messages and enums, `oneof` groups and `map<K, V>` fields
+/// - `repeated`/`optional`/`required` labels and streaming RPCs
+/// - Options, `reserved`, `import` and `extend` are skipped
+pub(crate) fn introspect_proto(schema_path: &Path) -> Result<ExternalSchema, String> {
+    let content = std::fs::read_to_string(schema_path).map_err(|e| {
+        format!(
+            "could not read schema file '{}': {}",
+            schema_path.display(),
+            e
+        )
+    })?;
+
+    let schema = ProtoParser::new(&content)
+        .parse()
+        .map_err(|e| format!("invalid proto file '{}': {}", schema_path.display(), e))?;
+    proto_endpoints(&schema, schema_path)
+}
+
+/// Parse a binary `FileDescriptorSet` (as written by `protoc
+/// --descriptor_set_out`) and extract one endpoint per RPC.
+pub(crate) fn introspect_descriptor_set(schema_path: &Path) -> Result<ExternalSchema, String> {
+    let bytes = std::fs::read(schema_path).map_err(|e| {
+        format!(
+            "could not read schema file '{}': {}",
+            schema_path.display(),
+            e
+        )
+    })?;
+
+    let mut schema = ProtoSchema::default();
+    decode_file_set(&bytes, &mut schema)
+        .map_err(|e| format!("invalid descriptor set '{}': {}", schema_path.display(), e))?;
+    proto_endpoints(&schema, schema_path)
+}
+
+/// Introspect a protobuf schema of unknown form: `.proto` source if it
+/// parses as one, otherwise a descriptor set.
+pub(crate) fn introspect_protobuf(schema_path: &Path) -> Result<ExternalSchema, String> {
+    introspect_proto(schema_path)
+        .or_else(|err| introspect_descriptor_set(schema_path).map_err(|_| err))
+}
+
+/// Build the endpoints of every RPC in `schema`.
+fn proto_endpoints(schema: &ProtoSchema, schema_path: &Path) -> Result<ExternalSchema, String> {
+    if schema.services.is_empty() {
+        return Err(format!(
+            "no service definitions found in '{}'",
+            schema_path.display()
+        ));
+    }
+
+    let mut endpoints = Vec::new();
+    for service in &schema.services {
+        for method in &service.methods {
+            let parameters = schema
+                .resolve(&service.name, &method.input_type)
+                .and_then(|name| schema.messages.get(&name))
+                .map(|m| m.fields.iter().map(|f| f.name.clone()).collect())
+                .unwrap_or_default();
+
+            let mut response_fields = Vec::new();
+            if let Some(output) = schema.resolve(&service.name, &method.output_type) {
+                schema.flatten(&output, "", &mut response_fields, 0);
+            }
+
+            endpoints.push(Endpoint {
+                method: "RPC".to_string(),
+                path: format!("/{}/{}", service.name, method.name),
+                parameters,
+                response_fields,
+            });
+        }
+    }
+
+    Ok(ExternalSchema {
+        format: SchemaFormat::Protobuf,
+        endpoints,
+    })
+}
+
+impl ProtoSchema {
+    /// Resolve a type reference made inside `scope` to the fully-qualified
+    /// name of a message or enum, following protobuf's scoping rules: the
+    /// innermost enclosing scope that defines the name wins.
+    fn resolve(&self, scope: &str, name: &str) -> Option<String> {
+        let defined = |full: &str| self.messages.contains_key(full) || self.enums.contains(full);
+        if let Some(full) = name.strip_prefix('.') {
+            return defined(full).then(|| full.to_string());
+        }
+
+        let mut scope = scope;
+        loop {
+            let candidate = qualify(scope, name);
+            if defined(&candidate) {
+                return Some(candidate);
+            }
+            if scope.is_empty() {
+                return None;
+            }
+            scope = scope.rfind('.').map_or("", |i| &scope[..i]);
+        }
+    }
+
+    /// The external type of a field declared inside message `scope`.
+    fn field_type(&self, scope: &str, field: &ProtoField) -> ExternalType {
+        if field.map || self.is_map_entry(scope, field) {
+            return ExternalType::Object;
+        }
+        if field.repeated {
+            return ExternalType::Array;
+        }
+        if let Some(t) = map_proto_scalar(&field.type_name) {
+            return t;
+        }
+        if let Some(t) = map_well_known_type(field.type_name.trim_start_matches('.')) {
+            return t;
+        }
+        match self.resolve(scope, &field.type_name) {
+            Some(name) if self.enums.contains(&name) => ExternalType::String,
+            Some(_) => ExternalType::Object,
+            None => ExternalType::Unknown(field.type_name.clone()),
+        }
+    }
+
+    /// Whether `field` is the repeated entry message a descriptor set uses
+    /// to encode a `map<K, V>` field.
+    fn is_map_entry(&self, scope: &str, field: &ProtoField) -> bool {
+        field.repeated
+            && self
+                .resolve(scope, &field.type_name)
+                .and_then(|name| self.messages.get(&name))
+                .is_some_and(|m| m.map_entry)
+    }
+
+    /// Flatten the fields of message `name` into dot-path notation.
+    fn flatten(&self, name: &str, prefix: &str, fields: &mut Vec<SchemaField>, depth: usize) {
+        if depth > 3 {
+            return; // Depth limit to prevent infinite recursion
+        }
+
+        let message = match self.messages.get(name) {
+            Some(m) => m,
+            None => return,
+        };
+
+        for field in &message.fields {
+            let full_path = if prefix.is_empty() {
+                field.name.clone()
+            } else {
+                format!("{}.{}", prefix, field.name)
+            };
+
+            let field_type = self.field_type(name, field);
+            let nested = match field_type {
+                ExternalType::Object if !field.map && !field.repeated => {
+                    self.resolve(name, &field.type_name)
+                }
+                _ => None,
+            };
+            fields.push(SchemaField {
+                path: full_path.clone(),
+                field_type,
+            });
+            if let Some(nested) = nested {
+                self.flatten(&nested, &full_path, fields, depth + 1);
+            }
+        }
+    }
+}
+
+/// `scope.name`, or `name` in the root scope.
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+/// Map a protobuf scalar type keyword to ExternalType.
+fn map_proto_scalar(type_name: &str) -> Option<ExternalType> {
+    match type_name {
+        "double" | "float" => Some(ExternalType::Number),
+        "int32" | "int64" | "uint32" | "uint64" | "sint32" | "sint64" | "fixed32" | "fixed64"
+        | "sfixed32" | "sfixed64" => Some(ExternalType::Integer),
+        "bool" => Some(ExternalType::Boolean),
+        "string" => Some(ExternalType::String),
+        "bytes" => Some(ExternalType::Unknown("binary".to_string())),
+        _ => None,
+    }
+}
+
+/// Map a `google.protobuf` well-known type to the type of its JSON form.
+fn map_well_known_type(type_name: &str) -> Option<ExternalType> {
+    let name = type_name.strip_prefix("google.protobuf.")?;
+    match name {
+        "Timestamp" | "Duration" | "StringValue" | "FieldMask" => Some(ExternalType::String),
+        "Int32Value" | "Int64Value" | "UInt32Value" | "UInt64Value" => Some(ExternalType::Integer),
+        "DoubleValue" | "FloatValue" => Some(ExternalType::Number),
+        "BoolValue" => Some(ExternalType::Boolean),
+        "BytesValue" => Some(ExternalType::Unknown("binary".to_string())),
+        "Struct" | "Any" => Some(ExternalType::Object),
+        "ListValue" => Some(ExternalType::Array),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// .proto source
+// ---------------------------------------------------------------------------
+
+/// Recursive-descent parser over the tokens of a `.proto` file.
+struct ProtoParser {
+    tokens: Vec<String>,
+    pos: usize,
+    package: String,
+    schema: ProtoSchema,
+}
+
+impl ProtoParser {
+    fn new(content: &str) -> Self {
+        ProtoParser {
+            tokens: tokenize_proto(content),
+            pos: 0,
+            package: String::new(),
+            schema: ProtoSchema::default(),
+        }
+    }
+
+    fn parse(mut self) -> Result<ProtoSchema, String> {
+        while let Some(token) = self.next_token() {
+            match token.as_str() {
+                "syntax" | "edition" | "import" | "option" => self.skip_statement()?,
+                "package" => {
+                    self.package = self.ident()?;
+                    self.expect(";")?;
+                }
+                "message" => {
+                    let scope = self.package.clone();
+                    self.parse_message(&scope)?;
+                }
+                "enum" => {
+                    let scope = self.package.clone();
+                    self.parse_enum(&scope)?;
+                }
+                "service" => self.parse_service()?,
+                "extend" => {
+                    self.ident()?;
+                    self.skip_block()?;
+                }
+                ";" => {}
+                other => return Err(format!("unexpected '{}'", other)),
+            }
+        }
+        Ok(self.schema)
+    }
+
+    fn parse_message(&mut self, scope: &str) -> Result<(), String> {
+        let name = qualify(scope, &self.ident()?);
+        self.expect("{")?;
+
+        let mut fields = Vec::new();
+        loop {
+            let token = self.expect_token()?;
+            match token.as_str() {
+                "}" => break,
+                "message" => self.parse_message(&name)?,
+                "enum" => self.parse_enum(&name)?,
+                "oneof" => {
+                    self.ident()?;
+                    self.expect("{")?;
+                    loop {
+                        let token = self.expect_token()?;
+                        match token.as_str() {
+                            "}" => break,
+                            "option" => self.skip_statement()?,
+                            _ => fields.push(self.parse_field(token, false)?),
+                        }
+                    }
+                }
+                "option" | "reserved" | "extensions" => self.skip_statement()?,
+                "extend" => {
+                    self.ident()?;
+                    self.skip_block()?;
+                }
+                ";" => {}
+                "map" if self.peek() == Some("<") => {
+                    self.expect("<")?;
+                    self.ident()?;
+                    self.expect(",")?;
+                    let value_type = self.ident()?;
+                    self.expect(">")?;
+                    let mut field = self.parse_field(value_type, false)?;
+                    field.map = true;
+                    fields.push(field);
+                }
+                "repeated" => {
+                    let type_name = self.ident()?;
+                    fields.push(self.parse_field(type_name, true)?);
+                }
+                "optional" | "required" => {
+                    let type_name = self.ident()?;
+                    fields.push(self.parse_field(type_name, false)?);
+                }
+                _ => fields.push(self.parse_field(token, false)?),
+            }
+        }
+
+        self.schema.messages.insert(
+            name,
+            ProtoMessage {
+                fields,
+                map_entry: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Parse `name = N [options];` after a field's type.
+    fn parse_field(&mut self, type_name: String, repeated: bool) -> Result<ProtoField, String> {
+        let name = self.ident()?;
+        self.expect("=")?;
+        self.skip_statement()?;
+        Ok(ProtoField {
+            name,
+            type_name,
+            repeated,
+            map: false,
+        })
+    }
+
+    fn parse_enum(&mut self, scope: &str) -> Result<(), String> {
+        let name = qualify(scope, &self.ident()?);
+        self.schema.enums.insert(name);
+        self.skip_block()
+    }
+
+    fn parse_service(&mut self) -> Result<(), String> {
+        let name = self.ident()?;
+        let name = qualify(&self.package, &name);
+        self.expect("{")?;
+
+        let mut methods = Vec::new();
+        loop {
+            let token = self.expect_token()?;
+            match token.as_str() {
+                "}" => break,
+                "rpc" => {
+                    let method = self.ident()?;
+                    let input_type = self.rpc_type()?;
+                    self.expect("returns")?;
+                    let output_type = self.rpc_type()?;
+                    if self.peek() == Some("{") {
+                        self.skip_block()?;
+                    } else {
+                        self.expect(";")?;
+                    }
+                    methods.push(ProtoMethod {
+                        name: method,
+                        input_type,
+                        output_type,
+                    });
+                }
+                "option" => self.skip_statement()?,
+                ";" => {}
+                other => return Err(format!("unexpected '{}' in service '{}'", other, name)),
+            }
+        }
+
+        self.schema.services.push(ProtoService { name, methods });
+        Ok(())
+    }
+
+    /// Parse `([stream] Type)` in an RPC signature.
+    fn rpc_type(&mut self) -> Result<String, String> {
+        self.expect("(")?;
+        let mut type_name = self.ident()?;
+        if type_name == "stream" && self.peek() != Some(")") {
+            type_name = self.ident()?;
+        }
+        self.expect(")")?;
+        Ok(type_name)
+    }
+
+    /// Skip to the end of a statement, past any `{ ... }` aggregate values.
+    fn skip_statement(&mut self) -> Result<(), String> {
+        let mut depth = 0;
+        loop {
+            match self.expect_token()?.as_str() {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                ";" if depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip a `{ ... }` block, including nested blocks.
+    fn skip_block(&mut self) -> Result<(), String> {
+        self.expect("{")?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.expect_token()?.as_str() {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next_token(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect_token(&mut self) -> Result<String, String> {
+        self.next_token()
+            .ok_or_else(|| "unexpected end of file".to_string())
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        let token = self.expect_token()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(format!("expected '{}', found '{}'", expected, token))
+        }
+    }
+
+    /// A (possibly dotted) identifier.
+    fn ident(&mut self) -> Result<String, String> {
+        let token = self.expect_token()?;
+        if token.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '.') {
+            Ok(token)
+        } else {
+            Err(format!("expected identifier, found '{}'", token))
+        }
+    }
+}
+
+/// Split `.proto` source into tokens: identifiers and numbers (dotted
+/// names are one token), string literals, and single-character symbols.
+/// Comments are dropped.
+fn tokenize_proto(content: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '"' | '\'' => {
+                let mut literal = String::from(ch);
+                while let Some(c) = chars.next() {
+                    literal.push(c);
+                    if c == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            literal.push(escaped);
+                        }
+                    } else if c == ch {
+                        break;
+                    }
+                }
+                tokens.push(literal);
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut word = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' {
+                        word.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(word);
+            }
+            c => tokens.push(c.to_string()),
+        }
+    }
+
+    tokens
+}
+
+// ---------------------------------------------------------------------------
+// Descriptor sets
+// ---------------------------------------------------------------------------
+
+/// A field value in protobuf wire format.
+enum WireValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// Reader over the fields of one encoded message.
+struct WireReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> WireReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        WireReader { buf, pos: 0 }
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .buf
+                .get(self.pos)
+                .ok_or_else(|| "truncated varint".to_string())?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("varint too long".to_string())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.buf.len())
+            .ok_or_else(|| "truncated field".to_string())?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// The next field number and value, or `None` at the end of the message.
+    fn next_field(&mut self) -> Result<Option<(u64, WireValue<'a>)>, String> {
+        if self.pos >= self.buf.len() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let value = match key & 7 {
+            0 => WireValue::Varint(self.varint()?),
+            1 => {
+                self.take(8)?;
+                WireValue::Fixed
+            }
+            2 => {
+                let len = usize::try_from(self.varint()?).map_err(|e| e.to_string())?;
+                WireValue::Bytes(self.take(len)?)
+            }
+            5 => {
+                self.take(4)?;
+                WireValue::Fixed
+            }
+            wire_type => return Err(format!("unsupported wire type {}", wire_type)),
+        };
+        Ok(Some((key >> 3, value)))
+    }
+}
+
+fn wire_string(bytes: &[u8]) -> Result<String, String> {
+    String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+}
+
+/// Decode a `FileDescriptorSet` (field 1: repeated `FileDescriptorProto`).
+fn decode_file_set(buf: &[u8], schema: &mut ProtoSchema) -> Result<(), String> {
+    let mut reader = WireReader::new(buf);
+    while let Some((number, value)) = reader.next_field()? {
+        if let (1, WireValue::Bytes(file)) = (number, value) {
+            decode_file(file, schema)?;
+        }
+    }
+    Ok(())
+}
+
+/// Decode a `FileDescriptorProto`: package (2), message_type (4),
+/// enum_type (5) and service (6).
+fn decode_file(buf: &[u8], schema: &mut ProtoSchema) -> Result<(), String> {
+    let mut package = String::new();
+    let mut messages = Vec::new();
+    let mut enums = Vec::new();
+    let mut services = Vec::new();
+
+    let mut reader = WireReader::new(buf);
+    while let Some((number, value)) = reader.next_field()? {
+        match (number, value) {
+            (2, WireValue::Bytes(b)) => package = wire_string(b)?,
+            (4, WireValue::Bytes(b)) => messages.push(b),
+            (5, WireValue::Bytes(b)) => enums.push(b),
+            (6, WireValue::Bytes(b)) => services.push(b),
+            _ => {}
+        }
+    }
+
+    for message in messages {
+        decode_message(message, &package, schema)?;
+    }
+    for e in enums {
+        decode_enum(e, &package, schema)?;
+    }
+    for service in services {
+        decode_service(service, &package, schema)?;
+    }
+    Ok(())
+}
+
+/// Decode a `DescriptorProto`: name (1), field (2), nested_type (3),
+/// enum_type (4) and options (7, for `map_entry`).
+fn decode_message(buf: &[u8], scope: &str, schema: &mut ProtoSchema) -> Result<(), String> {
+    let mut name = String::new();
+    let mut fields = Vec::new();
+    let mut nested = Vec::new();
+    let mut enums = Vec::new();
+    let mut map_entry = false;
+
+    let mut reader = WireReader::new(buf);
+    while let Some((number, value)) = reader.next_field()? {
+        match (number, value) {
+            (1, WireValue::Bytes(b)) => name = wire_string(b)?,
+            (2, WireValue::Bytes(b)) => fields.push(decode_field(b)?),
+            (3, WireValue::Bytes(b)) => nested.push(b),
+            (4, WireValue::Bytes(b)) => enums.push(b),
+            (7, WireValue::Bytes(b)) => {
+                // MessageOptions.map_entry = 7
+                let mut options = WireReader::new(b);
+                while let Some((number, value)) = options.next_field()? {
+                    if let (7, WireValue::Varint(v)) = (number, value) {
+                        map_entry = v != 0;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let name = qualify(scope, &name);
+    for message in nested {
+        decode_message(message, &name, schema)?;
+    }
+    for e in enums {
+        decode_enum(e, &name, schema)?;
+    }
+    schema
+        .messages
+        .insert(name, ProtoMessage { fields, map_entry });
+    Ok(())
+}
+
+/// Decode a `FieldDescriptorProto`: name (1), label (4), type (5) and
+/// type_name (6).
+fn decode_field(buf: &[u8]) -> Result<ProtoField, String> {
+    let mut name = String::new();
+    let mut label = 0;
+    let mut field_type = 0;
+    let mut type_name = String::new();
+
+    let mut reader = WireReader::new(buf);
+    while let Some((number, value)) = reader.next_field()? {
+        match (number, value) {
+            (1, WireValue::Bytes(b)) => name = wire_string(b)?,
+            (4, WireValue::Varint(v)) => label = v,
+            (5, WireValue::Varint(v)) => field_type = v,
+            (6, WireValue::Bytes(b)) => type_name = wire_string(b)?,
+            _ => {}
+        }
+    }
+
+    // FieldDescriptorProto.Type: message (11) and enum (14) carry a type_name
+    let type_name = match field_type {
+        1 => "double",
+        2 => "float",
+        3 => "int64",
+        4 => "uint64",
+        5 => "int32",
+        6 => "fixed64",
+        7 => "fixed32",
+        8 => "bool",
+        9 => "string",
+        12 => "bytes",
+        13 => "uint32",
+        15 => "sfixed32",
+        16 => "sfixed64",
+        17 => "sint32",
+        18 => "sint64",
+        _ => type_name.as_str(),
+    }
+    .to_string();
+
+    Ok(ProtoField {
+        name,
+        type_name,
+        // LABEL_REPEATED = 3
+        repeated: label == 3,
+        map: false,
+    })
+}
+
+/// Decode an `EnumDescriptorProto`: name (1).
+fn decode_enum(buf: &[u8], scope: &str, schema: &mut ProtoSchema) -> Result<(), String> {
+    let mut reader = WireReader::new(buf);
+    while let Some((number, value)) = reader.next_field()? {
+        if let (1, WireValue::Bytes(b)) = (number, value) {
+            schema.enums.insert(qualify(scope, &wire_string(b)?));
+        }
+    }
+    Ok(())
+}
+
+/// Decode a `ServiceDescriptorProto`: name (1) and method (2), each a
+/// `MethodDescriptorProto` with name (1), input_type (2), output_type (3).
+fn decode_service(buf: &[u8], package: &str, schema: &mut ProtoSchema) -> Result<(), String> {
+    let mut name = String::new();
+    let mut methods = Vec::new();
+
+    let mut reader = WireReader::new(buf);
+    while let Some((number, value)) = reader.next_field()? {
+        match (number, value) {
+            (1, WireValue::Bytes(b)) => name = wire_string(b)?,
+            (2, WireValue::Bytes(b)) => {
+                let mut method = ProtoMethod {
+                    name: String::new(),
+                    input_type: String::new(),
+                    output_type: String::new(),
+                };
+                let mut fields = WireReader::new(b);
+                while let Some((number, value)) = fields.next_field()? {
+                    match (number, value) {
+                        (1, WireValue::Bytes(b)) => method.name = wire_string(b)?,
+                        (2, WireValue::Bytes(b)) => method.input_type = wire_string(b)?,
+                        (3, WireValue::Bytes(b)) => method.output_type = wire_string(b)?,
+                        _ => {}
+                    }
+                }
+                methods.push(method);
+            }
+            _ => {}
+        }
+    }
+
+    schema.services.push(ProtoService {
+        name: qualify(package, &name),
+        methods,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDERS_PROTO: &str = r#"
+syntax = "proto3";
+
+package shop.v1;
+
+import "google/protobuf/timestamp.proto";
+
+option go_package = "example.com/shop/v1;shopv1";
+
+// Order lookups
+service OrderService {
+  rpc GetOrder(GetOrderRequest) returns (Order);
+  rpc WatchOrders(stream GetOrderRequest) returns (stream Order) {
+    option (google.api.http) = { get: "/v1/orders/{order_id}" };
+  }
+}
+
+message GetOrderRequest {
+  string order_id = 1;
+}
+
+message Order {
+  enum Status {
+    STATUS_UNSPECIFIED = 0;
+    STATUS_OPEN = 1;
+  }
+  message Money {
+    int64 units = 1;
+    string currency_code = 2;
+  }
+
+  string id = 1;
+  Status status = 2;
+  Money total = 3 [json_name = "orderTotal"];
+  repeated string tags = 4;
+  map<string, string> labels = 5;
+  google.protobuf.Timestamp created_at = 6;
+  /* paid orders only */
+  oneof payment {
+    bool prepaid = 7;
+    double deposit = 8;
+  }
+  bytes receipt = 9;
+}
+"#;
+
+    fn field<'a>(endpoint: &'a Endpoint, path: &str) -> &'a SchemaField {
+        endpoint
+            .response_fields
+            .iter()
+            .find(|f| f.path == path)
+            .unwrap_or_else(|| panic!("no field '{}'", path))
+    }
+
+    #[test]
+    fn test_introspect_proto_services() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("orders.proto");
+        std::fs::write(&schema_path, ORDERS_PROTO).unwrap();
+
+        let result = introspect_proto(&schema_path).unwrap();
+        assert_eq!(result.format, SchemaFormat::Protobuf);
+        assert_eq!(result.endpoints.len(), 2);
+
+        let get = &result.endpoints[0];
+        assert_eq!(get.method, "RPC");
+        assert_eq!(get.path, "/shop.v1.OrderService/GetOrder");
+        assert_eq!(get.parameters, vec!["order_id".to_string()]);
+        assert_eq!(
+            result.endpoints[1].path,
+            "/shop.v1.OrderService/WatchOrders"
+        );
+
+        assert_eq!(field(get, "id").field_type, ExternalType::String);
+        assert_eq!(field(get, "status").field_type, ExternalType::String);
+        assert_eq!(field(get, "total").field_type, ExternalType::Object);
+        assert_eq!(field(get, "total.units").field_type, ExternalType::Integer);
+        assert_eq!(
+            field(get, "total.currency_code").field_type,
+            ExternalType::String
+        );
+        assert_eq!(field(get, "tags").field_type, ExternalType::Array);
+        assert_eq!(field(get, "labels").field_type, ExternalType::Object);
+        assert_eq!(field(get, "created_at").field_type, ExternalType::String);
+        assert_eq!(field(get, "prepaid").field_type, ExternalType::Boolean);
+        assert_eq!(field(get, "deposit").field_type, ExternalType::Number);
+        assert_eq!(
+            field(get, "receipt").field_type,
+            ExternalType::Unknown("binary".to_string())
+        );
+    }
+
+    #[test]
+    fn test_introspect_proto_no_services() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("types.proto");
+        std::fs::write(
+            &schema_path,
+            "syntax = \"proto3\";\nmessage A { int32 x = 1; }\n",
+        )
+        .unwrap();
+
+        let err = introspect_proto(&schema_path).unwrap_err();
+        assert!(err.contains("no service definitions"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_introspect_proto_malformed() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("bad.proto");
+        std::fs::write(&schema_path, "message Order { string id = 1;\n").unwrap();
+
+        let err = introspect_proto(&schema_path).unwrap_err();
+        assert!(err.contains("unexpected end of file"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_resolve_follows_scopes() {
+        let mut schema = ProtoSchema::default();
+        schema
+            .messages
+            .insert("a.Outer".into(), ProtoMessage::default());
+        schema
+            .messages
+            .insert("a.Outer.Inner".into(), ProtoMessage::default());
+        schema
+            .messages
+            .insert("a.Inner".into(), ProtoMessage::default());
+
+        assert_eq!(
+            schema.resolve("a.Outer", "Inner").as_deref(),
+            Some("a.Outer.Inner")
+        );
+        assert_eq!(schema.resolve("a", "Inner").as_deref(), Some("a.Inner"));
+        assert_eq!(schema.resolve("b", ".a.Inner").as_deref(), Some("a.Inner"));
+        assert_eq!(schema.resolve("a", "Missing"), None);
+    }
+
+    // Minimal protobuf encoder for building descriptor sets by hand.
+
+    fn varint(mut v: u64, out: &mut Vec<u8>) {
+        while v >= 0x80 {
+            out.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        out.push(v as u8);
+    }
+
+    fn bytes_field(number: u64, bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        varint(number << 3 | 2, &mut out);
+        varint(bytes.len() as u64, &mut out);
+        out.extend_from_slice(bytes);
+        out
+    }
+
+    fn varint_field(number: u64, v: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        varint(number << 3, &mut out);
+        varint(v, &mut out);
+        out
+    }
+
+    fn proto_field(name: &str, label: u64, field_type: u64, type_name: &str) -> Vec<u8> {
+        let mut out = bytes_field(1, name.as_bytes());
+        out.extend(varint_field(4, label));
+        out.extend(varint_field(5, field_type));
+        if !type_name.is_empty() {
+            out.extend(bytes_field(6, type_name.as_bytes()));
+        }
+        bytes_field(2, &out)
+    }
+
+    #[test]
+    fn test_introspect_descriptor_set() {
+        // message Balance { int64 amount = 1; map<string, int64> by_currency = 2; }
+        let mut entry = bytes_field(1, b"ByCurrencyEntry");
+        entry.extend(proto_field("key", 1, 9, ""));
+        entry.extend(proto_field("value", 1, 3, ""));
+        entry.extend(bytes_field(7, &varint_field(7, 1)));
+
+        let mut balance = bytes_field(1, b"Balance");
+        balance.extend(proto_field("amount", 1, 3, ""));
+        balance.extend(proto_field(
+            "by_currency",
+            3,
+            11,
+            ".ledger.Balance.ByCurrencyEntry",
+        ));
+        balance.extend(proto_field("flags", 3, 8, ""));
+        balance.extend(bytes_field(3, &entry));
+
+        let mut request = bytes_field(1, b"BalanceRequest");
+        request.extend(proto_field("account_id", 1, 9, ""));
+
+        let mut method = bytes_field(1, b"GetBalance");
+        method.extend(bytes_field(2, b".ledger.BalanceRequest"));
+        method.extend(bytes_field(3, b".ledger.Balance"));
+        let mut service = bytes_field(1, b"Ledger");
+        service.extend(bytes_field(2, &method));
+
+        let mut file = bytes_field(1, b"ledger.proto");
+        file.extend(bytes_field(2, b"ledger"));
+        file.extend(bytes_field(4, &balance));
+        file.extend(bytes_field(4, &request));
+        file.extend(bytes_field(6, &service));
+
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("ledger.desc");
+        std::fs::write(&schema_path, bytes_field(1, &file)).unwrap();
+
+        let result = introspect_descriptor_set(&schema_path).unwrap();
+        assert_eq!(result.format, SchemaFormat::Protobuf);
+        let endpoint = &result.endpoints[0];
+        assert_eq!(endpoint.path, "/ledger.Ledger/GetBalance");
+        assert_eq!(endpoint.parameters, vec!["account_id".to_string()]);
+        assert_eq!(field(endpoint, "amount").field_type, ExternalType::Integer);
+        assert_eq!(
+            field(endpoint, "by_currency").field_type,
+            ExternalType::Object
+        );
+        assert_eq!(field(endpoint, "flags").field_type, ExternalType::Array);
+
+        // The same file is found when its form is unknown
+        let result = introspect_protobuf(&schema_path).unwrap();
+        assert_eq!(result.endpoints.len(), 1);
+    }
+
+    #[test]
+    fn test_introspect_descriptor_set_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema_path = dir.path().join("bad.desc");
+        std::fs::write(&schema_path, [0x0a, 0x10, 0x0a]).unwrap();
+
+        let err = introspect_descriptor_set(&schema_path).unwrap_err();
+        assert!(err.contains("truncated"), "got: {}", err);
+    }
+}
@@ -1,57 +1,30 @@
-//! LLM-based matching provider — uses the Anthropic Messages API to propose
-//! fact-to-source mappings with semantic understanding.
+//! LLM-based matching provider — asks the configured LLM backend (see
+//! [`crate::llm`]) to propose fact-to-source mappings with semantic
+//! understanding.
 
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
 use super::matching::Confidence;
 use super::provider::{
     EnvironmentInventory, FactDeclaration, MappingProposal, MatchingError, MatchingProvider,
 };
+use crate::llm::LlmBackend;
 
-/// Anthropic Messages API endpoint.
-const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+/// Default model when the Anthropic backend is used.
+pub(crate) const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
 
-/// Required API version header value.
-const ANTHROPIC_VERSION: &str = "2023-06-01";
-
-/// Default model to use for LLM matching.
-const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
-
-/// Configuration for the LLM matching provider.
-#[derive(Debug, Clone)]
-pub struct LlmMatchingConfig {
-    /// Anthropic API key.
-    pub api_key: String,
-    /// Model identifier (defaults to claude-sonnet-4-20250514).
-    pub model: String,
-}
-
-impl LlmMatchingConfig {
-    /// Create a new config with the given API key and default model.
-    pub fn new(api_key: String) -> Self {
-        Self {
-            api_key,
-            model: DEFAULT_MODEL.to_string(),
-        }
-    }
-
-    /// Create a new config with the given API key and model.
-    pub fn with_model(api_key: String, model: String) -> Self {
-        Self { api_key, model }
-    }
-}
-
-/// An LLM-powered matching provider that calls the Anthropic Messages API
-/// to propose fact-to-source mappings.
+/// An LLM-powered matching provider that sends the facts and environment
+/// inventory to an [`LlmBackend`] and parses the proposed mappings.
 pub struct LlmMatchingProvider {
-    config: LlmMatchingConfig,
+    backend: LlmBackend,
+    model: String,
 }
 
 impl LlmMatchingProvider {
-    /// Create a new LLM matching provider with the given configuration.
-    pub fn new(config: LlmMatchingConfig) -> Self {
-        Self { config }
+    /// Create a provider calling `model` on `backend`.
+    pub(crate) fn new(backend: LlmBackend, model: String) -> Self {
+        Self { backend, model }
     }
 }
 
@@ -71,16 +44,17 @@ impl MatchingProvider for LlmMatchingProvider {
         let system_prompt = build_system_prompt();
         let user_prompt = build_user_prompt(facts, environment);
 
-        // ureq is synchronous, so wrap in spawn_blocking
-        let api_key = self.config.api_key.clone();
-        let model = self.config.model.clone();
+        // The backend call is synchronous, so wrap in spawn_blocking
+        let backend = self.backend.clone();
+        let model = self.model.clone();
 
         let response_text = tokio::task::spawn_blocking(move || {
-            call_anthropic_api(&api_key, &model, &system_prompt, &user_prompt)
+            backend.complete(&model, &system_prompt, &user_prompt)
         })
         .await
         .map_err(|e| MatchingError::Internal(format!("task join error: {}", e)))?
-        .map_err(|e| MatchingError::ApiError(e.to_string()))?;
+        .map_err(MatchingError::ApiError)?
+        .text;
 
         parse_llm_response(&response_text)
     }
@@ -173,74 +147,6 @@ fn build_user_prompt(facts: &[FactDeclaration], environment: &EnvironmentInvento
     prompt
 }
 
-// ── API call ─────────────────────────────────────────────────────────────────
-
-#[derive(Serialize)]
-struct MessagesRequest {
-    model: String,
-    max_tokens: u32,
-    system: String,
-    messages: Vec<ApiMessage>,
-}
-
-#[derive(Serialize)]
-struct ApiMessage {
-    role: String,
-    content: String,
-}
-
-#[derive(Deserialize)]
-struct MessagesResponse {
-    content: Vec<ContentBlock>,
-}
-
-#[derive(Deserialize)]
-struct ContentBlock {
-    #[allow(dead_code)] // Required by serde for correct JSON deserialization
-    #[serde(rename = "type")]
-    block_type: String,
-    text: Option<String>,
-}
-
-/// Make a synchronous call to the Anthropic Messages API.
-fn call_anthropic_api(
-    api_key: &str,
-    model: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-) -> Result<String, MatchingError> {
-    let request_body = MessagesRequest {
-        model: model.to_string(),
-        max_tokens: 4096,
-        system: system_prompt.to_string(),
-        messages: vec![ApiMessage {
-            role: "user".to_string(),
-            content: user_prompt.to_string(),
-        }],
-    };
-
-    let agent = ureq::Agent::new_with_defaults();
-    let response = agent
-        .post(ANTHROPIC_API_URL)
-        .header("x-api-key", api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("content-type", "application/json")
-        .send_json(&request_body)
-        .map_err(|e| MatchingError::ApiError(format!("API request failed: {}", e)))?;
-
-    let resp: MessagesResponse = response
-        .into_body()
-        .read_json()
-        .map_err(|e| MatchingError::ParseError(format!("failed to parse API response: {}", e)))?;
-
-    resp.content
-        .first()
-        .and_then(|block| block.text.clone())
-        .ok_or_else(|| {
-            MatchingError::ParseError("API response contained no text content".to_string())
-        })
-}
-
 // ── Response parsing ─────────────────────────────────────────────────────────
 
 /// A single mapping entry from the LLM's JSON response.
@@ -542,14 +448,16 @@ mod tests {
         assert_eq!(truncate("hello world", 5), "hello...");
     }
 
-    // ── Integration test (requires API key, skipped in CI) ───────────────────
+    // ── Integration test (requires an LLM provider, skipped in CI) ──────────
 
     #[tokio::test]
     #[ignore]
     async fn test_llm_provider_integration() {
-        let api_key = std::env::var("ANTHROPIC_API_KEY").expect("ANTHROPIC_API_KEY not set");
-        let config = LlmMatchingConfig::new(api_key);
-        let provider = LlmMatchingProvider::new(config);
+        let backend = LlmBackend::from_env()
+            .expect("invalid LLM configuration")
+            .expect("no LLM provider configured");
+        let model = backend.model(None, DEFAULT_MODEL).to_string();
+        let provider = LlmMatchingProvider::new(backend, model);
 
         let facts = vec![FactDeclaration {
             fact_id: "order_balance".to_string(),
@@ -1,7 +1,8 @@
 //! Fact-to-source matching: propose mappings between declared Facts
 //! and external schema fields.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 
 use super::introspect::{ExternalSchema, ExternalType, SchemaField};
 use super::StructuredFact;
@@ -10,9 +11,9 @@ use tenor_interchange::SourceConstruct;
 /// Confidence level for a proposed mapping.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Confidence {
-    /// Type match and path match.
+    /// Name match backed by type or path structure, or all three agree.
     High,
-    /// Partial match (type or path, not both).
+    /// Partial match (a single signal, or signals that disagree).
     Medium,
     /// Inferred from path only, no schema to validate.
     Low,
@@ -27,6 +28,94 @@ pub struct FactMapping {
     pub confidence: Confidence,
     pub description: String,
     pub note: Option<String>,
+    /// The endpoint a reviewed mapping reads (e.g. `/orders/{id}`). `None`
+    /// for proposals that no one has accepted yet.
+    pub endpoint: Option<String>,
+}
+
+/// Groups of words that name the same thing, consulted when comparing
+/// fact and field names (e.g. `amount`, `total` and `value`).
+#[derive(Debug, Clone)]
+pub struct Synonyms {
+    groups: Vec<BTreeSet<String>>,
+}
+
+/// The built-in synonym groups.
+const DEFAULT_SYNONYMS: &[&[&str]] = &[
+    &["amount", "total", "value", "sum"],
+    &["balance", "fund"],
+    &["status", "state"],
+    &["quantity", "qty", "count"],
+    &["price", "cost", "rate"],
+    &["customer", "client", "buyer"],
+    &["email", "mail"],
+    &["active", "enabled"],
+    &["currency", "ccy"],
+    &["id", "identifier"],
+    &["created", "creation", "created_at"],
+    &["updated", "modified", "updated_at"],
+];
+
+impl Default for Synonyms {
+    fn default() -> Self {
+        Synonyms {
+            groups: DEFAULT_SYNONYMS
+                .iter()
+                .map(|group| group.iter().map(|w| normalize_word(w)).collect())
+                .collect(),
+        }
+    }
+}
+
+impl Synonyms {
+    /// The built-in groups extended with a TOML synonym dictionary, where
+    /// each key lists the words it is synonymous with:
+    ///
+    /// ```toml
+    /// amount = ["principal", "outstanding"]
+    /// ```
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+        let mut synonyms = Synonyms::default();
+        for (word, others) in table {
+            let others = others
+                .as_array()
+                .ok_or_else(|| format!("synonyms for '{}' must be an array of strings", word))?;
+            let mut group: BTreeSet<String> = BTreeSet::new();
+            group.insert(normalize_word(&word));
+            for other in others {
+                let other = other.as_str().ok_or_else(|| {
+                    format!("synonyms for '{}' must be an array of strings", word)
+                })?;
+                group.insert(normalize_word(other));
+            }
+            synonyms.groups.push(group);
+        }
+        Ok(synonyms)
+    }
+
+    /// Load a synonym dictionary file (see [`Synonyms::from_toml`]).
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read synonyms file '{}': {}", path.display(), e))?;
+        Synonyms::from_toml(&content)
+            .map_err(|e| format!("invalid synonyms file '{}': {}", path.display(), e))
+    }
+
+    /// Whether two normalized words are the same or synonyms.
+    fn equivalent(&self, a: &str, b: &str) -> bool {
+        a == b
+            || self
+                .groups
+                .iter()
+                .any(|group| group.contains(a) && group.contains(b))
+    }
+}
+
+/// Normalize a dictionary word the way [`words`] normalizes names, so
+/// `Totals` in a dictionary matches a `total` field.
+fn normalize_word(word: &str) -> String {
+    words(word).concat()
 }
 
 /// Match facts against external schemas to produce proposed mappings.
@@ -34,6 +123,7 @@ pub fn match_facts(
     sources: &[SourceConstruct],
     facts: &[StructuredFact],
     schemas: &BTreeMap<String, ExternalSchema>,
+    synonyms: &Synonyms,
 ) -> Vec<FactMapping> {
     let source_map: BTreeMap<&str, &SourceConstruct> =
         sources.iter().map(|s| (s.id.as_str(), s)).collect();
@@ -48,7 +138,7 @@ pub fn match_facts(
 
         if let Some(schema) = schemas.get(&fact.source_id) {
             // Rich mode: match against introspected schema
-            let mapping = match_with_schema(fact, source, schema);
+            let mapping = match_with_schema(fact, source, schema, synonyms);
             mappings.push(mapping);
         } else {
             // Degraded mode: infer from protocol and path
@@ -61,38 +151,48 @@ pub fn match_facts(
 }
 
 /// Match a fact against an introspected external schema.
+///
+/// Every response field is scored on three signals — name similarity of
+/// the leaf segments (with synonyms), type compatibility, and how much of
+/// the fact path's structure appears in the endpoint path — and the best
+/// field wins. Confidence is High only when several signals agree.
 fn match_with_schema(
     fact: &StructuredFact,
     source: &SourceConstruct,
     schema: &ExternalSchema,
+    synonyms: &Synonyms,
 ) -> FactMapping {
     let tenor_type = extract_tenor_base_type(&fact.fact_type);
 
     // Try to find a matching field in any endpoint's response
-    let mut best_match: Option<(&SchemaField, &str, Confidence)> = None;
+    let mut best_match: Option<(&SchemaField, &str, MatchScore)> = None;
 
     for endpoint in &schema.endpoints {
         for field in &endpoint.response_fields {
-            let path_similarity = path_similarity(&fact.path, &field.path);
-            let type_compat = type_compatible(&tenor_type, &field.field_type);
-
-            let confidence = match (path_similarity >= 0.5, type_compat) {
-                (true, true) => Confidence::High,
-                (true, false) | (false, true) => Confidence::Medium,
-                (false, false) => continue,
+            let score = MatchScore {
+                name: name_similarity(&fact.path, &field.path, synonyms),
+                type_match: type_match(&tenor_type, &field.field_type),
+                structure: structure_similarity(&fact.path, &endpoint.path, &field.path, synonyms),
+            };
+            let Some(confidence) = score.confidence() else {
+                continue;
             };
 
-            let dominated = best_match
+            let better = best_match
                 .as_ref()
-                .map(|(_, _, c)| confidence_ord(confidence) > confidence_ord(*c))
+                .map(|(_, _, best)| {
+                    let best_confidence = best.confidence().map_or(0, confidence_ord);
+                    (confidence_ord(confidence), score.total()) > (best_confidence, best.total())
+                })
                 .unwrap_or(true);
-            if dominated {
-                best_match = Some((field, &endpoint.path, confidence));
+            if better {
+                best_match = Some((field, &endpoint.path, score));
             }
         }
     }
 
-    if let Some((field, endpoint_path, confidence)) = best_match {
+    if let Some((field, endpoint_path, score)) = best_match {
+        let confidence = score.confidence().unwrap_or(Confidence::Low);
         FactMapping {
             fact_id: fact.id.clone(),
             source_id: fact.source_id.clone(),
@@ -102,11 +202,9 @@ fn match_with_schema(
                 "{} {} -> field '{}'",
                 endpoint_path, source.protocol, field.path
             ),
+            endpoint: None,
             note: if confidence == Confidence::Medium {
-                Some(format!(
-                    "type mismatch or partial path match: tenor {} vs external {}",
-                    tenor_type, field.field_type
-                ))
+                Some(score.weak_signals(&tenor_type, &field.field_type))
             } else {
                 None
             },
@@ -122,7 +220,85 @@ fn match_with_schema(
                 source.protocol, fact.path
             ),
             note: Some("manual mapping required".to_string()),
+            endpoint: None,
+        }
+    }
+}
+
+/// How well a Tenor type fits an external field type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypeMatch {
+    /// The external type is the natural encoding of the Tenor type.
+    Exact,
+    /// The value can be converted, with some loss or parsing.
+    Compatible,
+    /// The schema does not say.
+    Unknown,
+    Mismatch,
+}
+
+/// The signals scored for one candidate field.
+#[derive(Debug, Clone, Copy)]
+struct MatchScore {
+    /// Leaf name similarity, 0.0 to 1.0.
+    name: f64,
+    type_match: TypeMatch,
+    /// Share of the fact path's parent segments found in the endpoint
+    /// path; `None` for a single-segment fact path.
+    structure: Option<f64>,
+}
+
+impl MatchScore {
+    /// Confidence when the signals agree, or `None` when nothing matches.
+    fn confidence(&self) -> Option<Confidence> {
+        let name_agrees = self.name >= 0.75;
+        let name_partial = self.name >= 0.5;
+        let type_agrees = matches!(self.type_match, TypeMatch::Exact | TypeMatch::Compatible);
+        let structure_agrees = self.structure.is_some_and(|s| s >= 0.5);
+
+        if self.type_match != TypeMatch::Mismatch
+            && ((name_agrees && (type_agrees || structure_agrees))
+                || (name_partial && type_agrees && structure_agrees))
+        {
+            Some(Confidence::High)
+        } else if name_partial || type_agrees {
+            Some(Confidence::Medium)
+        } else {
+            None
+        }
+    }
+
+    /// Weighted sum of the signals, for ranking fields of equal confidence.
+    fn total(&self) -> f64 {
+        let type_score = match self.type_match {
+            TypeMatch::Exact => 1.0,
+            TypeMatch::Compatible => 0.6,
+            TypeMatch::Unknown => 0.4,
+            TypeMatch::Mismatch => 0.0,
+        };
+        0.5 * self.name + 0.3 * type_score + 0.2 * self.structure.unwrap_or(0.5)
+    }
+
+    /// Describe the signals that kept a match from High confidence.
+    fn weak_signals(&self, tenor_type: &str, external_type: &ExternalType) -> String {
+        let mut weak = Vec::new();
+        if self.name < 0.5 {
+            weak.push("field name does not match".to_string());
+        } else if self.name < 0.75 {
+            weak.push("partial name match".to_string());
+        }
+        match self.type_match {
+            TypeMatch::Mismatch => weak.push(format!(
+                "type mismatch: tenor {} vs external {}",
+                tenor_type, external_type
+            )),
+            TypeMatch::Unknown => weak.push(format!("external type {} unverified", external_type)),
+            TypeMatch::Exact | TypeMatch::Compatible => {}
         }
+        if self.structure.is_some_and(|s| s < 0.5) {
+            weak.push("path structure differs".to_string());
+        }
+        weak.join("; ")
     }
 }
 
@@ -161,6 +337,17 @@ fn match_degraded(fact: &StructuredFact, source: &SourceConstruct) -> FactMappin
         "grpc" => {
             format!("gRPC call for {}", fact.path)
         }
+        "file" => {
+            format!(
+                "column {} of {} file",
+                fact.path.rsplit('.').next().unwrap_or(&fact.path),
+                source
+                    .fields
+                    .get("format")
+                    .map(|s| s.as_str())
+                    .unwrap_or("data")
+            )
+        }
         "static" => {
             format!("static value: {}", fact.path)
         }
@@ -179,6 +366,7 @@ fn match_degraded(fact: &StructuredFact, source: &SourceConstruct) -> FactMappin
         confidence: Confidence::Low,
         description,
         note: Some("no schema_ref available; mapping inferred from protocol and path".to_string()),
+        endpoint: None,
     }
 }
 
@@ -191,50 +379,127 @@ fn extract_tenor_base_type(fact_type: &serde_json::Value) -> String {
         .to_string()
 }
 
-/// Compute a simple path similarity score (0.0 to 1.0).
-/// Compares the leaf segments of both paths.
-fn path_similarity(tenor_path: &str, schema_path: &str) -> f64 {
-    let tenor_parts: Vec<&str> = tenor_path.split('.').collect();
-    let schema_parts: Vec<&str> = schema_path.split('.').collect();
-
-    if tenor_parts.is_empty() || schema_parts.is_empty() {
+/// Compare the leaf segments of a fact path and a schema field path
+/// (0.0 to 1.0): 1.0 for the same name, 0.8 for synonyms, 0.5 for names
+/// that contain one another or share a word.
+fn name_similarity(tenor_path: &str, schema_path: &str, synonyms: &Synonyms) -> f64 {
+    let tenor_leaf = tenor_path.rsplit('.').next().unwrap_or(tenor_path);
+    let schema_leaf = schema_path.rsplit('.').next().unwrap_or(schema_path);
+    let tenor_words = words(tenor_leaf);
+    let schema_words = words(schema_leaf);
+    if tenor_words.is_empty() || schema_words.is_empty() {
         return 0.0;
     }
 
-    // Check if the leaf (last segment) matches
-    let tenor_leaf = tenor_parts.last().unwrap().to_lowercase();
-    let schema_leaf = schema_parts.last().unwrap().to_lowercase();
+    if tenor_words == schema_words {
+        return 1.0;
+    }
+    let same_words = tenor_words.len() == schema_words.len()
+        && tenor_words
+            .iter()
+            .zip(&schema_words)
+            .all(|(a, b)| synonyms.equivalent(a, b));
+    if same_words || synonyms.equivalent(&tenor_words.concat(), &schema_words.concat()) {
+        return 0.8;
+    }
 
-    if tenor_leaf == schema_leaf {
-        // Exact leaf match
-        if tenor_parts.len() == schema_parts.len() {
-            1.0
-        } else {
-            0.75
-        }
-    } else if tenor_leaf.contains(&schema_leaf) || schema_leaf.contains(&tenor_leaf) {
+    let tenor_joined = tenor_words.concat();
+    let schema_joined = schema_words.concat();
+    let shares_word = tenor_words
+        .iter()
+        .any(|a| schema_words.iter().any(|b| synonyms.equivalent(a, b)));
+    if tenor_joined.contains(&schema_joined) || schema_joined.contains(&tenor_joined) || shares_word
+    {
         0.5
     } else {
         0.0
     }
 }
 
-/// Check if a Tenor type is compatible with an external schema type.
-fn type_compatible(tenor_type: &str, external_type: &ExternalType) -> bool {
+/// The share of a fact path's parent segments (everything but the leaf)
+/// named somewhere in the endpoint path or the field's parent segments,
+/// e.g. `accounts` in `accounts.balance` against `/accounts/{id}`.
+fn structure_similarity(
+    tenor_path: &str,
+    endpoint_path: &str,
+    field_path: &str,
+    synonyms: &Synonyms,
+) -> Option<f64> {
+    let (parents, _) = tenor_path.rsplit_once('.')?;
+    let field_parents = field_path.rsplit_once('.').map_or("", |(p, _)| p);
+    let context: Vec<String> = endpoint_path
+        .split(['/', '.'])
+        .chain(field_parents.split('.'))
+        .filter(|segment| !segment.starts_with('{'))
+        .flat_map(words)
+        .collect();
+
+    let segments: Vec<&str> = parents.split('.').collect();
+    let found = segments
+        .iter()
+        .filter(|segment| {
+            words(segment)
+                .iter()
+                .any(|w| context.iter().any(|c| synonyms.equivalent(w, c)))
+        })
+        .count();
+    Some(found as f64 / segments.len() as f64)
+}
+
+/// Split a name into lowercase, singular words at underscores, hyphens and
+/// camelCase boundaries: `orderTotal_amounts` -> `order`, `total`, `amount`.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for ch in name.chars() {
+        if !ch.is_alphanumeric() {
+            words.push(std::mem::take(&mut current));
+            prev_lower = false;
+            continue;
+        }
+        if ch.is_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        current.extend(ch.to_lowercase());
+    }
+    words.push(current);
+    words
+        .into_iter()
+        .filter(|w| !w.is_empty())
+        .map(|w| singular(&w).to_string())
+        .collect()
+}
+
+/// Strip a plural `s` so `orders` and `order` compare equal (but leave
+/// `status`, `address` and `analysis` alone).
+fn singular(word: &str) -> &str {
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() >= 3 && !stem.ends_with(['s', 'u', 'i']) => stem,
+        _ => word,
+    }
+}
+
+/// How well a Tenor base type fits an external schema type.
+fn type_match(tenor_type: &str, external_type: &ExternalType) -> TypeMatch {
     match (tenor_type, external_type) {
-        ("Int", ExternalType::Integer) => true,
-        ("Int", ExternalType::Number) => true,
-        ("Decimal", ExternalType::Number) => true,
-        ("Decimal", ExternalType::Integer) => true,
-        ("Money", ExternalType::Number) => true,
-        ("Money", ExternalType::Integer) => true,
-        ("Money", ExternalType::Object) => true, // Money often serialized as { amount, currency }
-        ("Text", ExternalType::String) => true,
-        ("Bool", ExternalType::Boolean) => true,
-        ("Date", ExternalType::String) => true, // Dates often transmitted as strings
-        ("DateTime", ExternalType::String) => true,
-        (_, ExternalType::Unknown(_)) => true, // Unknown external type — assume compatible
-        _ => false,
+        (_, ExternalType::Unknown(_)) => TypeMatch::Unknown, // Schema does not say
+        ("Int", ExternalType::Integer) => TypeMatch::Exact,
+        ("Int", ExternalType::Number) => TypeMatch::Compatible,
+        ("Decimal", ExternalType::Number) => TypeMatch::Exact,
+        ("Decimal", ExternalType::Integer) => TypeMatch::Compatible,
+        ("Money", ExternalType::Number) => TypeMatch::Exact,
+        ("Money", ExternalType::Integer) => TypeMatch::Exact, // Minor units
+        ("Money", ExternalType::Object) => TypeMatch::Compatible, // Often { amount, currency }
+        ("Text", ExternalType::String) => TypeMatch::Exact,
+        ("Bool", ExternalType::Boolean) => TypeMatch::Exact,
+        ("Enum", ExternalType::String) => TypeMatch::Exact,
+        ("Date", ExternalType::String) => TypeMatch::Compatible, // Dates often transmitted as strings
+        ("DateTime", ExternalType::String) => TypeMatch::Compatible,
+        ("List", ExternalType::Array) => TypeMatch::Exact,
+        ("Record", ExternalType::Object) => TypeMatch::Exact,
+        _ => TypeMatch::Mismatch,
     }
 }
 
@@ -251,24 +516,187 @@ fn confidence_ord(c: Confidence) -> u8 {
 mod tests {
     use super::*;
 
+    use crate::connect::introspect::{Endpoint, SchemaFormat};
+
+    #[test]
+    fn test_name_similarity_exact_leaf() {
+        let synonyms = Synonyms::default();
+        assert_eq!(name_similarity("orders.balance", "balance", &synonyms), 1.0);
+        assert_eq!(name_similarity("orderTotal", "order_total", &synonyms), 1.0);
+        assert_eq!(name_similarity("items", "item", &synonyms), 1.0);
+    }
+
+    #[test]
+    fn test_name_similarity_synonyms_and_partial() {
+        let synonyms = Synonyms::default();
+        assert_eq!(name_similarity("order.amount", "total", &synonyms), 0.8);
+        assert_eq!(
+            name_similarity("order_amount", "order_total", &synonyms),
+            0.8
+        );
+        assert_eq!(
+            name_similarity("balance", "account_balance", &synonyms),
+            0.5
+        );
+        assert_eq!(name_similarity("orders.balance", "status", &synonyms), 0.0);
+    }
+
+    #[test]
+    fn test_structure_similarity() {
+        let synonyms = Synonyms::default();
+        assert_eq!(
+            structure_similarity("accounts.balance", "/accounts/{id}", "balance", &synonyms),
+            Some(1.0)
+        );
+        assert_eq!(
+            structure_similarity(
+                "account.balance",
+                "/v1/ledger",
+                "account.balance",
+                &synonyms
+            ),
+            Some(1.0)
+        );
+        assert_eq!(
+            structure_similarity("orders.status", "/accounts/{id}", "status", &synonyms),
+            Some(0.0)
+        );
+        assert_eq!(
+            structure_similarity("balance", "/accounts/{id}", "balance", &synonyms),
+            None
+        );
+    }
+
+    #[test]
+    fn test_type_match() {
+        assert_eq!(type_match("Int", &ExternalType::Integer), TypeMatch::Exact);
+        assert_eq!(
+            type_match("Money", &ExternalType::Integer),
+            TypeMatch::Exact
+        );
+        assert_eq!(type_match("Bool", &ExternalType::Boolean), TypeMatch::Exact);
+        assert_eq!(type_match("Text", &ExternalType::String), TypeMatch::Exact);
+        assert_eq!(
+            type_match("Int", &ExternalType::Number),
+            TypeMatch::Compatible
+        );
+        assert_eq!(
+            type_match("Int", &ExternalType::Unknown("binary".into())),
+            TypeMatch::Unknown
+        );
+        assert_eq!(
+            type_match("Int", &ExternalType::String),
+            TypeMatch::Mismatch
+        );
+        assert_eq!(
+            type_match("Bool", &ExternalType::Integer),
+            TypeMatch::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_confidence_needs_agreeing_signals() {
+        let score = |name, type_match, structure| MatchScore {
+            name,
+            type_match,
+            structure,
+        };
+        let high = Some(Confidence::High);
+        let medium = Some(Confidence::Medium);
+        assert_eq!(score(1.0, TypeMatch::Exact, None).confidence(), high);
+        assert_eq!(score(0.8, TypeMatch::Unknown, Some(1.0)).confidence(), high);
+        assert_eq!(score(0.5, TypeMatch::Exact, Some(1.0)).confidence(), high);
+        assert_eq!(score(1.0, TypeMatch::Unknown, None).confidence(), medium);
+        assert_eq!(
+            score(1.0, TypeMatch::Mismatch, Some(1.0)).confidence(),
+            medium
+        );
+        assert_eq!(score(0.0, TypeMatch::Exact, Some(0.0)).confidence(), medium);
+        assert_eq!(score(0.0, TypeMatch::Unknown, Some(1.0)).confidence(), None);
+    }
+
+    fn source() -> SourceConstruct {
+        SourceConstruct {
+            id: "ledger".to_string(),
+            protocol: "http".to_string(),
+            fields: BTreeMap::new(),
+            description: None,
+            provenance: None,
+            annotations: Default::default(),
+            tenor: None,
+        }
+    }
+
+    fn endpoint(path: &str, fields: &[(&str, ExternalType)]) -> Endpoint {
+        Endpoint {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            parameters: vec![],
+            response_fields: fields
+                .iter()
+                .map(|(path, field_type)| SchemaField {
+                    path: path.to_string(),
+                    field_type: field_type.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn fact(path: &str, base: &str) -> StructuredFact {
+        StructuredFact {
+            id: "f".to_string(),
+            fact_type: serde_json::json!({ "base": base }),
+            source_id: "ledger".to_string(),
+            path: path.to_string(),
+        }
+    }
+
     #[test]
-    fn test_path_similarity_exact_leaf() {
-        assert!(path_similarity("orders.balance", "balance") >= 0.75);
-        assert!(path_similarity("balance", "balance") >= 1.0);
+    fn test_match_prefers_field_under_matching_endpoint() {
+        let schema = ExternalSchema {
+            format: SchemaFormat::OpenApi3,
+            endpoints: vec![
+                endpoint("/orders/{id}", &[("total", ExternalType::Number)]),
+                endpoint("/invoices/{id}", &[("total", ExternalType::Number)]),
+            ],
+        };
+        let mapping = match_with_schema(
+            &fact("invoices.amount", "Money"),
+            &source(),
+            &schema,
+            &Synonyms::default(),
+        );
+        assert_eq!(mapping.confidence, Confidence::High);
+        assert_eq!(mapping.description, "/invoices/{id} http -> field 'total'");
     }
 
     #[test]
-    fn test_path_similarity_no_match() {
-        assert_eq!(path_similarity("orders.balance", "status"), 0.0);
+    fn test_match_with_custom_synonyms() {
+        let schema = ExternalSchema {
+            format: SchemaFormat::OpenApi3,
+            endpoints: vec![endpoint(
+                "/loans/{id}",
+                &[
+                    ("principal", ExternalType::Number),
+                    ("open", ExternalType::Boolean),
+                ],
+            )],
+        };
+        let fact = fact("loan.outstanding", "Decimal");
+
+        let default = match_with_schema(&fact, &source(), &schema, &Synonyms::default());
+        assert_eq!(default.confidence, Confidence::Medium);
+
+        let synonyms = Synonyms::from_toml("Outstanding = [\"principal\"]\n").unwrap();
+        let custom = match_with_schema(&fact, &source(), &schema, &synonyms);
+        assert_eq!(custom.confidence, Confidence::High);
+        assert!(custom.description.ends_with("field 'principal'"));
     }
 
     #[test]
-    fn test_type_compatible() {
-        assert!(type_compatible("Int", &ExternalType::Integer));
-        assert!(type_compatible("Bool", &ExternalType::Boolean));
-        assert!(type_compatible("Text", &ExternalType::String));
-        assert!(!type_compatible("Int", &ExternalType::String));
-        assert!(!type_compatible("Bool", &ExternalType::Integer));
+    fn test_synonyms_from_toml_rejects_non_arrays() {
+        let err = Synonyms::from_toml("amount = \"total\"\n").unwrap_err();
+        assert!(err.contains("array of strings"), "got: {}", err);
     }
 
     #[test]
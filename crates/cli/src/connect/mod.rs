@@ -27,7 +27,11 @@ pub(crate) struct ConnectOptions<'a> {
     pub apply: Option<&'a Path>,
     pub model: Option<&'a str>,
     pub heuristic: bool,
+    /// Synonym dictionary (TOML) extending the heuristic matcher's built-in one
+    pub synonyms: Option<&'a Path>,
     pub verbose: bool,
+    /// Also generate TypeScript adapters
+    pub typescript: bool,
     pub output: OutputFormat,
     pub quiet: bool,
 }
@@ -40,6 +44,7 @@ pub(crate) fn cmd_connect(opts: ConnectOptions<'_>) {
             review_path,
             opts.contract,
             opts.output_dir,
+            opts.typescript,
             opts.output,
             opts.quiet,
         );
@@ -66,10 +71,21 @@ pub(crate) fn cmd_connect(opts: ConnectOptions<'_>) {
     let schemas = introspect_schemas(&sources, opts.environment, opts.quiet);
 
     // Step 4: Run matching via provider
+    let synonyms = match opts.synonyms {
+        Some(path) => match matching::Synonyms::load(path) {
+            Ok(s) => s,
+            Err(e) => {
+                report_error(&e, opts.output, opts.quiet);
+                std::process::exit(1);
+            }
+        },
+        None => matching::Synonyms::default(),
+    };
     let proposals = run_matching(
         &sources,
         &facts,
         &schemas,
+        &synonyms,
         opts.heuristic,
         opts.model,
         opts.verbose,
@@ -103,6 +119,7 @@ pub(crate) fn cmd_connect(opts: ConnectOptions<'_>) {
         &facts,
         &schemas,
         opts.output_dir,
+        opts.typescript,
         opts.output,
         opts.quiet,
     );
@@ -113,6 +130,7 @@ fn run_apply_mode(
     review_path: &Path,
     contract: &Path,
     output_dir: &Path,
+    typescript: bool,
     output: OutputFormat,
     quiet: bool,
 ) {
@@ -147,11 +165,12 @@ fn run_apply_mode(
             confidence: parse_confidence(&r.confidence),
             description: format!("{} -> {}", r.endpoint, r.field_path),
             note: None,
+            endpoint: Some(r.endpoint.clone()),
         })
         .collect();
 
     generate_and_report(
-        &sources, &facts, &mappings, &schemas, output_dir, output, quiet,
+        &sources, &facts, &mappings, &schemas, output_dir, typescript, output, quiet,
     );
 
     if !quiet {
@@ -163,7 +182,8 @@ fn run_apply_mode(
     }
 }
 
-/// Introspect external schemas for all sources that have a schema_ref.
+/// Introspect external schemas for all sources that have a schema_ref (or,
+/// for gRPC sources, a proto_ref).
 fn introspect_schemas(
     sources: &[SourceConstruct],
     environment: Option<&Path>,
@@ -176,7 +196,11 @@ fn introspect_schemas(
         let schema_path = if let Some(env_path) = environment {
             Some(env_path.to_path_buf())
         } else {
-            source.fields.get("schema_ref").map(PathBuf::from)
+            source
+                .fields
+                .get("schema_ref")
+                .or_else(|| source.fields.get("proto_ref"))
+                .map(PathBuf::from)
         };
 
         if let Some(path) = schema_path {
@@ -209,6 +233,7 @@ fn run_matching(
     sources: &[SourceConstruct],
     facts: &[StructuredFact],
     schemas: &BTreeMap<String, introspect::ExternalSchema>,
+    synonyms: &matching::Synonyms,
     force_heuristic: bool,
     model: Option<&str>,
     verbose: bool,
@@ -237,27 +262,35 @@ fn run_matching(
     };
 
     // Determine provider
-    let api_key = std::env::var("ANTHROPIC_API_KEY").ok();
-    let use_llm = !force_heuristic && api_key.is_some();
+    let backend = if force_heuristic {
+        None
+    } else {
+        match crate::llm::LlmBackend::from_env() {
+            Ok(backend) => backend,
+            Err(e) => {
+                report_error(&format!("LLM configuration error: {}", e), output, quiet);
+                std::process::exit(1);
+            }
+        }
+    };
 
-    if !use_llm && !force_heuristic && !quiet {
-        eprintln!("No ANTHROPIC_API_KEY set. Using heuristic matching. Set ANTHROPIC_API_KEY for LLM-powered matching.");
+    if backend.is_none() && !force_heuristic && !quiet {
+        eprintln!("No LLM provider configured. Using heuristic matching. Set ANTHROPIC_API_KEY, or TENOR_LLM_PROVIDER (openai, ollama), for LLM-powered matching.");
     }
 
-    if use_llm {
+    if let Some(backend) = backend {
+        let model = backend
+            .model(model, llm_provider::DEFAULT_MODEL)
+            .to_string();
         if verbose && !quiet {
             eprintln!(
-                "Using LLM matching (model: {})",
-                model.unwrap_or("claude-sonnet-4-20250514")
+                "Using LLM matching (model: {}, provider: {})",
+                model,
+                backend.describe()
             );
         }
 
-        let api_key = api_key.unwrap();
-        let config = match model {
-            Some(m) => llm_provider::LlmMatchingConfig::with_model(api_key, m.to_string()),
-            None => llm_provider::LlmMatchingConfig::new(api_key),
-        };
-        let provider = llm_provider::LlmMatchingProvider::new(config);
+        let provider = llm_provider::LlmMatchingProvider::new(backend, model);
 
         // Run async matching in a tokio runtime
         let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime");
@@ -271,14 +304,30 @@ fn run_matching(
                 if !quiet {
                     eprintln!("LLM matching failed: {}. Falling back to heuristic.", e);
                 }
-                run_heuristic_matching(sources, &fact_decls, &environment, verbose, output, quiet)
+                run_heuristic_matching(
+                    sources,
+                    &fact_decls,
+                    &environment,
+                    synonyms,
+                    verbose,
+                    output,
+                    quiet,
+                )
             }
         }
     } else {
         if verbose && !quiet {
             eprintln!("Using heuristic matching");
         }
-        run_heuristic_matching(sources, &fact_decls, &environment, verbose, output, quiet)
+        run_heuristic_matching(
+            sources,
+            &fact_decls,
+            &environment,
+            synonyms,
+            verbose,
+            output,
+            quiet,
+        )
     }
 }
 
@@ -287,11 +336,13 @@ fn run_heuristic_matching(
     sources: &[SourceConstruct],
     fact_decls: &[provider::FactDeclaration],
     environment: &provider::EnvironmentInventory,
+    synonyms: &matching::Synonyms,
     _verbose: bool,
     _output: OutputFormat,
     _quiet: bool,
 ) -> Vec<provider::MappingProposal> {
-    let provider = heuristic_provider::HeuristicMatchingProvider::new(sources.to_vec());
+    let provider = heuristic_provider::HeuristicMatchingProvider::new(sources.to_vec())
+        .with_synonyms(synonyms.clone());
     let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime");
     match rt.block_on(provider::MatchingProvider::propose_mappings(
         &provider,
@@ -341,12 +392,14 @@ fn to_workflow_proposals(
 }
 
 /// Interactive mode: prompt user, generate from accepted.
+#[allow(clippy::too_many_arguments)]
 fn run_interactive_mode(
     proposals: &[provider::MappingProposal],
     sources: &[SourceConstruct],
     facts: &[StructuredFact],
     schemas: &BTreeMap<String, introspect::ExternalSchema>,
     output_dir: &Path,
+    typescript: bool,
     output: OutputFormat,
     quiet: bool,
 ) {
@@ -378,11 +431,12 @@ fn run_interactive_mode(
             confidence: parse_confidence(&r.confidence),
             description: format!("{} -> {}", r.endpoint, r.field_path),
             note: None,
+            endpoint: Some(r.endpoint.clone()),
         })
         .collect();
 
     generate_and_report(
-        sources, facts, &mappings, schemas, output_dir, output, quiet,
+        sources, facts, &mappings, schemas, output_dir, typescript, output, quiet,
     );
 }
 
@@ -451,17 +505,33 @@ fn run_batch_mode(
     }
 }
 
-/// Generate scaffolding and report results.
+/// Generate adapters and report results.
+#[allow(clippy::too_many_arguments)]
 fn generate_and_report(
     sources: &[SourceConstruct],
     facts: &[StructuredFact],
     mappings: &[matching::FactMapping],
     schemas: &BTreeMap<String, introspect::ExternalSchema>,
     output_dir: &Path,
+    typescript: bool,
     output: OutputFormat,
     quiet: bool,
 ) {
-    match generate::generate_scaffolding(sources, facts, mappings, schemas, output_dir) {
+    let project = crate::project();
+    let adapter_config = project
+        .connect
+        .adapter_config
+        .as_ref()
+        .map(|p| project.resolve(p));
+    match generate::generate_scaffolding(
+        sources,
+        facts,
+        mappings,
+        schemas,
+        output_dir,
+        adapter_config.as_deref(),
+        typescript,
+    ) {
         Ok(files) => {
             if !quiet {
                 match output {
@@ -645,7 +715,7 @@ fn load_interchange_bundle(path: &Path, output: OutputFormat, quiet: bool) -> In
             }
         }
     } else {
-        match tenor_core::elaborate::elaborate(path) {
+        match crate::elaborate(path) {
             Ok(b) => b,
             Err(e) => {
                 report_error(&format!("elaboration error: {:?}", e), output, quiet);
@@ -0,0 +1,1251 @@
+//! `tenor decompile` — reconstruct `.tenor` source from an interchange bundle.
+//!
+//! Decompilation is the best-effort inverse of elaboration. The output
+//! re-elaborates to the same bundle (modulo provenance), but anything the
+//! elaborator does not carry into interchange is gone for good: comments,
+//! named `type` declarations (inlined during Pass 4), import structure, and
+//! the original formatting. Source `description` fields and construct
+//! annotations are preserved.
+//!
+//! Uses typed structs from `tenor-interchange` for the construct layer so
+//! that interchange format changes surface as compile errors here too.
+//! Predicate trees, types, and flow steps are walked as raw JSON, mirroring
+//! how the interchange crate keeps them.
+
+use serde_json::Value;
+use tenor_interchange::{
+    Annotations, AssertConstruct, EntityConstruct, FactConstruct, FlowConstruct,
+    InterchangeConstruct, OperationConstruct, PersonaConstruct, RuleConstruct, SourceConstruct,
+    SystemConstruct, TestConstruct, TypeDeclConstruct,
+};
+
+/// Indentation unit used throughout the generated source.
+const INDENT: &str = "  ";
+
+/// Reconstruct `.tenor` source text from an interchange bundle.
+///
+/// `raw_bundle` is the interchange JSON value (kind: "Bundle"). A
+/// TenorManifest wrapping a bundle is accepted as well.
+pub fn decompile(raw_bundle: &Value) -> Result<String, String> {
+    let raw_bundle = match raw_bundle.get("bundle") {
+        Some(inner) if raw_bundle.get("etag").is_some() => inner,
+        _ => raw_bundle,
+    };
+    let bundle = tenor_interchange::from_interchange(raw_bundle)
+        .map_err(|e| format!("failed to parse interchange bundle: {}", e))?;
+
+    let mut type_decls: Vec<&TypeDeclConstruct> = Vec::new();
+    let mut personas: Vec<&PersonaConstruct> = Vec::new();
+    let mut sources: Vec<&SourceConstruct> = Vec::new();
+    let mut facts: Vec<&FactConstruct> = Vec::new();
+    let mut entities: Vec<&EntityConstruct> = Vec::new();
+    let mut rules: Vec<&RuleConstruct> = Vec::new();
+    let mut operations: Vec<&OperationConstruct> = Vec::new();
+    let mut flows: Vec<&FlowConstruct> = Vec::new();
+    let mut systems: Vec<&SystemConstruct> = Vec::new();
+    let mut tests: Vec<&TestConstruct> = Vec::new();
+    let mut asserts: Vec<&AssertConstruct> = Vec::new();
+
+    for c in &bundle.constructs {
+        match c {
+            InterchangeConstruct::TypeDecl(t) => type_decls.push(t),
+            InterchangeConstruct::Persona(p) => personas.push(p),
+            InterchangeConstruct::Source(s) => sources.push(s),
+            InterchangeConstruct::Fact(f) => facts.push(f),
+            InterchangeConstruct::Entity(e) => entities.push(e),
+            InterchangeConstruct::Rule(r) => rules.push(r),
+            InterchangeConstruct::Operation(o) => operations.push(o),
+            InterchangeConstruct::Flow(f) => flows.push(f),
+            InterchangeConstruct::System(s) => systems.push(s),
+            InterchangeConstruct::Test(t) => tests.push(t),
+            InterchangeConstruct::Assert(a) => asserts.push(a),
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "// Decompiled from interchange bundle '{}' (tenor_version {}).\n",
+        bundle.id, bundle.tenor_version
+    ));
+    out.push_str("// Comments, named types, and imports from the original source are not\n");
+    out.push_str("// recoverable; types are shown inline.\n");
+
+    let mut sections: Vec<(&str, Vec<String>)> = Vec::new();
+    sections.push((
+        "Types",
+        type_decls
+            .iter()
+            .map(|t| render_type_decl(t).map(|block| annotated(&t.annotations, block)))
+            .collect::<Result<_, _>>()?,
+    ));
+    // Personas are one-liners; group them into a single block.
+    if !personas.is_empty() {
+        let block = personas
+            .iter()
+            .map(|p| annotated(&p.annotations, format!("persona {}", p.id)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(("Personas", vec![block]));
+    }
+    sections.push((
+        "Sources",
+        sources
+            .iter()
+            .map(|s| annotated(&s.annotations, render_source(s)))
+            .collect(),
+    ));
+    sections.push((
+        "Facts",
+        facts
+            .iter()
+            .map(|f| render_fact(f).map(|block| annotated(&f.annotations, block)))
+            .collect::<Result<_, _>>()?,
+    ));
+    sections.push((
+        "Entities",
+        entities
+            .iter()
+            .map(|e| render_entity(e).map(|block| annotated(&e.annotations, block)))
+            .collect::<Result<_, _>>()?,
+    ));
+    sections.push((
+        "Rules",
+        rules
+            .iter()
+            .map(|r| render_rule(r).map(|block| annotated(&r.annotations, block)))
+            .collect::<Result<_, _>>()?,
+    ));
+    sections.push((
+        "Operations",
+        operations
+            .iter()
+            .map(|o| render_operation(o).map(|block| annotated(&o.annotations, block)))
+            .collect::<Result<_, _>>()?,
+    ));
+    sections.push((
+        "Flows",
+        flows
+            .iter()
+            .map(|f| render_flow(f).map(|block| annotated(&f.annotations, block)))
+            .collect::<Result<_, _>>()?,
+    ));
+    sections.push((
+        "Systems",
+        systems
+            .iter()
+            .map(|s| annotated(&s.annotations, render_system(s)))
+            .collect(),
+    ));
+    sections.push((
+        "Tests",
+        tests
+            .iter()
+            .map(|t| render_test(t).map(|block| annotated(&t.annotations, block)))
+            .collect::<Result<_, _>>()?,
+    ));
+    sections.push((
+        "Assertions",
+        asserts
+            .iter()
+            .map(|a| annotated(&a.annotations, render_assert(a)))
+            .collect(),
+    ));
+
+    for (title, blocks) in sections {
+        if blocks.is_empty() {
+            continue;
+        }
+        out.push('\n');
+        out.push_str(&section_banner(title));
+        for block in blocks {
+            out.push('\n');
+            out.push_str(&block);
+            out.push('\n');
+        }
+    }
+
+    Ok(out)
+}
+
+// ── Layout helpers ───────────────────────────────────────────────────
+
+/// A `key: value` line inside a construct or step body.
+enum Field {
+    /// Single-line value. Keys of consecutive inline fields are
+    /// column-aligned. Continuation lines (predicate chains) are hung
+    /// two columns left of the value so `∧`/`∨` line up under it.
+    Inline(String, String),
+    /// Multi-line value whose inner lines already carry their indentation.
+    Nested(String, String),
+}
+
+fn inline(key: &str, value: impl Into<String>) -> Field {
+    Field::Inline(key.to_string(), value.into())
+}
+
+fn nested(key: &str, value: impl Into<String>) -> Field {
+    Field::Nested(key.to_string(), value.into())
+}
+
+fn pad(depth: usize) -> String {
+    INDENT.repeat(depth)
+}
+
+/// Render `fields` as the body of a `{ ... }` block at nesting `depth`.
+fn write_fields(out: &mut String, depth: usize, fields: &[Field]) {
+    let width = fields
+        .iter()
+        .filter_map(|f| match f {
+            Field::Inline(k, _) => Some(k.len() + 1),
+            Field::Nested(..) => None,
+        })
+        .max()
+        .unwrap_or(0);
+    for field in fields {
+        out.push_str(&pad(depth));
+        match field {
+            Field::Inline(key, value) => {
+                let label = format!("{}:", key);
+                out.push_str(&format!("{:<width$} ", label, width = width));
+                let hang = " ".repeat(pad(depth).len() + width - 1);
+                for (i, line) in value.lines().enumerate() {
+                    if i > 0 {
+                        out.push('\n');
+                        out.push_str(&hang);
+                    }
+                    out.push_str(line);
+                }
+            }
+            Field::Nested(key, value) => {
+                out.push_str(&format!("{}: {}", key, value));
+            }
+        }
+        out.push('\n');
+    }
+}
+
+/// `head {` + fields + `}` with the closing brace at `depth`.
+fn braced(head: &str, depth: usize, fields: &[Field]) -> String {
+    let mut out = format!("{}{{\n", head);
+    write_fields(&mut out, depth + 1, fields);
+    out.push_str(&pad(depth));
+    out.push('}');
+    out
+}
+
+/// `[` + one item per line + `]` with the closing bracket at `depth`.
+fn bracketed_lines(items: &[String], depth: usize) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    let inner = pad(depth + 1);
+    let body = items
+        .iter()
+        .map(|i| format!("{}{}", inner, i))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!("[\n{}\n{}]", body, pad(depth))
+}
+
+/// Prefix a construct block with its `@owner`/`@description`/`@tag` lines.
+fn annotated(annotations: &Annotations, block: String) -> String {
+    let mut out = String::new();
+    if let Some(owner) = &annotations.owner {
+        out.push_str(&format!("@owner({})\n", quote(owner)));
+    }
+    if let Some(description) = &annotations.description {
+        out.push_str(&format!("@description({})\n", quote(description)));
+    }
+    if !annotations.tags.is_empty() {
+        let tags: Vec<String> = annotations.tags.iter().map(|t| quote(t)).collect();
+        out.push_str(&format!("@tag({})\n", tags.join(", ")));
+    }
+    out.push_str(&block);
+    out
+}
+
+fn section_banner(title: &str) -> String {
+    let head = format!("// ── {} ", title);
+    let fill = 79usize.saturating_sub(head.chars().count());
+    format!("{}{}\n", head, "─".repeat(fill))
+}
+
+fn ident_list(items: &[String]) -> String {
+    format!("[{}]", items.join(", "))
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn str_field<'a>(v: &'a Value, key: &str, ctx: &str) -> Result<&'a str, String> {
+    v.get(key)
+        .and_then(|x| x.as_str())
+        .ok_or_else(|| format!("{}: missing string field '{}'", ctx, key))
+}
+
+// ── Constructs ───────────────────────────────────────────────────────
+
+fn render_type_decl(t: &TypeDeclConstruct) -> Result<String, String> {
+    let fields = t
+        .type_def
+        .get("fields")
+        .and_then(|f| f.as_object())
+        .ok_or_else(|| format!("type '{}': expected a Record definition", t.id))?;
+    let mut rendered = Vec::new();
+    for (name, ty) in fields {
+        rendered.push(inline(name, render_type(ty)?));
+    }
+    Ok(braced(&format!("type {} ", t.id), 0, &rendered))
+}
+
+fn render_source(s: &SourceConstruct) -> String {
+    let mut fields = vec![inline("protocol", s.protocol.clone())];
+    for (key, value) in &s.fields {
+        fields.push(inline(key, quote(value)));
+    }
+    if let Some(desc) = &s.description {
+        fields.push(inline("description", quote(desc)));
+    }
+    braced(&format!("source {} ", s.id), 0, &fields)
+}
+
+fn render_fact(f: &FactConstruct) -> Result<String, String> {
+    let ctx = format!("fact '{}'", f.id);
+    let mut fields = vec![inline("type", render_type(&f.fact_type)?)];
+    if let Some(source) = &f.source {
+        fields.push(inline("source", render_fact_source(source, &ctx)?));
+    }
+    if let Some(default) = &f.default {
+        fields.push(inline("default", render_default(default, &ctx)?));
+    }
+    Ok(braced(&format!("fact {} ", f.id), 0, &fields))
+}
+
+fn render_fact_source(source: &Value, ctx: &str) -> Result<String, String> {
+    if let Some(s) = source.as_str() {
+        return Ok(quote(s));
+    }
+    if let Some(source_id) = source.get("source_id").and_then(|v| v.as_str()) {
+        let path = str_field(source, "path", ctx)?;
+        return Ok(format!("{} {{ path: {} }}", source_id, quote(path)));
+    }
+    let system = str_field(source, "system", ctx)?;
+    let field = str_field(source, "field", ctx)?;
+    Ok(quote(&format!("{}.{}", system, field)))
+}
+
+fn render_default(default: &Value, ctx: &str) -> Result<String, String> {
+    match default {
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) => Ok(quote(s)),
+        Value::Object(_) => match default.get("kind").and_then(|k| k.as_str()) {
+            Some("bool_literal") | Some("int_literal") => default
+                .get("value")
+                .map(|v| v.to_string())
+                .ok_or_else(|| format!("{}: literal default missing 'value'", ctx)),
+            // Decimal defaults are written as strings so scale-0 values
+            // are not re-read as Int literals.
+            Some("decimal_value") => Ok(quote(str_field(default, "value", ctx)?)),
+            Some("money_value") => render_money(default, ctx),
+            other => Err(format!("{}: unsupported default kind {:?}", ctx, other)),
+        },
+        _ => Err(format!("{}: unsupported default value {}", ctx, default)),
+    }
+}
+
+fn render_money(money: &Value, ctx: &str) -> Result<String, String> {
+    let amount = money
+        .get("amount")
+        .ok_or_else(|| format!("{}: money value missing 'amount'", ctx))?;
+    let amount = match amount {
+        Value::String(s) => s.as_str(),
+        _ => str_field(amount, "value", ctx)?,
+    };
+    let currency = str_field(money, "currency", ctx)?;
+    Ok(format!(
+        "Money {{ amount: {}, currency: {} }}",
+        quote(amount),
+        quote(currency)
+    ))
+}
+
+fn render_entity(e: &EntityConstruct) -> Result<String, String> {
+    let mut fields = vec![
+        inline("states", ident_list(&e.states)),
+        inline("initial", e.initial.clone()),
+    ];
+    if let Some(parent) = &e.parent {
+        fields.push(inline("parent", parent.clone()));
+    }
+    let transitions = e
+        .transitions
+        .iter()
+        .map(|t| match &t.guard {
+            Some(guard) => Ok(format!(
+                "({}, {} when {})",
+                t.from,
+                t.to,
+                render_predicate(guard)?
+            )),
+            None => Ok(format!("({}, {})", t.from, t.to)),
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    fields.push(nested("transitions", bracketed_lines(&transitions, 1)));
+    if !e.attributes.is_empty() {
+        let mut attributes = Vec::new();
+        for (name, ty) in &e.attributes {
+            attributes.push(inline(name, render_type(ty)?));
+        }
+        fields.push(nested("attributes", braced("", 1, &attributes)));
+    }
+    Ok(braced(&format!("entity {} ", e.id), 0, &fields))
+}
+
+fn render_rule(r: &RuleConstruct) -> Result<String, String> {
+    let ctx = format!("rule '{}'", r.id);
+    let when = r.when().ok_or_else(|| format!("{}: missing 'when'", ctx))?;
+    let verdict = r
+        .verdict_type()
+        .ok_or_else(|| format!("{}: missing produce.verdict_type", ctx))?;
+    let payload = r
+        .produce_payload()
+        .ok_or_else(|| format!("{}: missing produce.payload", ctx))?;
+    let fields = vec![
+        inline("stratum", r.stratum.to_string()),
+        inline("when", render_predicate(when)?),
+        inline(
+            "produce",
+            format!(
+                "verdict {} {{ payload: {} }}",
+                verdict,
+                render_payload(payload, &ctx)?
+            ),
+        ),
+    ];
+    Ok(braced(&format!("rule {} ", r.id), 0, &fields))
+}
+
+fn render_payload(payload: &Value, ctx: &str) -> Result<String, String> {
+    let ty = payload
+        .get("type")
+        .ok_or_else(|| format!("{}: payload missing 'type'", ctx))?;
+    let value = payload
+        .get("value")
+        .ok_or_else(|| format!("{}: payload missing 'value'", ctx))?;
+
+    // The elaborator widens an unbounded Text payload to the literal's
+    // length; print the bare `Text` back when that is all the bound says.
+    let ty_str = match (ty.get("base").and_then(|b| b.as_str()), value) {
+        (Some("Text"), Value::String(s))
+            if ty.get("max_length").and_then(|m| m.as_u64()) == Some(s.len() as u64) =>
+        {
+            "Text".to_string()
+        }
+        _ => render_type(ty)?,
+    };
+
+    let value_str = match value {
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => quote(s),
+        Value::Object(_) if value.get("op").is_some() => render_term(value)?,
+        Value::Object(_) => match value.get("kind").and_then(|k| k.as_str()) {
+            Some("decimal_value") => str_field(value, "value", ctx)?.to_string(),
+            Some("money_value") => render_money(value, ctx)?,
+            Some("bool_literal") | Some("int_literal") => value
+                .get("value")
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            other => return Err(format!("{}: unsupported payload kind {:?}", ctx, other)),
+        },
+        _ => return Err(format!("{}: unsupported payload value {}", ctx, value)),
+    };
+    Ok(format!("{} = {}", ty_str, value_str))
+}
+
+fn render_operation(o: &OperationConstruct) -> Result<String, String> {
+    let ctx = format!("operation '{}'", o.id);
+    let precondition = o
+        .precondition
+        .as_ref()
+        .ok_or_else(|| format!("{}: missing precondition", ctx))?;
+    let effects = o
+        .effects
+        .iter()
+        .map(|e| {
+            let mut parts = match e.kind.as_deref() {
+                Some("create_instance") => vec![format!("create {}", e.entity_id)],
+                Some("destroy_instance") => {
+                    vec![format!("destroy {}", e.entity_id), e.from.clone()]
+                }
+                _ => vec![e.entity_id.clone(), e.from.clone(), e.to.clone()],
+            };
+            if let Some(outcome) = &e.outcome {
+                parts.push(outcome.clone());
+            }
+            if !e.set.is_empty() {
+                let assignments = e
+                    .set
+                    .iter()
+                    .map(|(name, term)| Ok(format!("{}: {}", name, render_term(term)?)))
+                    .collect::<Result<Vec<String>, String>>()?;
+                parts.push(format!("set {{ {} }}", assignments.join(", ")));
+            }
+            Ok(format!("({})", parts.join(", ")))
+        })
+        .collect::<Result<Vec<String>, String>>()?;
+    let error_contract: Vec<String> = o
+        .error_contract
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut fields = vec![
+        inline("allowed_personas", ident_list(&o.allowed_personas)),
+        inline("precondition", render_predicate(precondition)?),
+    ];
+    if effects.len() <= 1 {
+        fields.push(inline("effects", format!("[{}]", effects.join(""))));
+    } else {
+        fields.push(nested("effects", bracketed_lines(&effects, 1)));
+    }
+    if !o.outcomes.is_empty() {
+        fields.push(inline("outcomes", ident_list(&o.outcomes)));
+    }
+    fields.push(inline("error_contract", ident_list(&error_contract)));
+    Ok(braced(&format!("operation {} ", o.id), 0, &fields))
+}
+
+fn render_flow(f: &FlowConstruct) -> Result<String, String> {
+    let ctx = format!("flow '{}'", f.id);
+    let mut fields = Vec::new();
+    if !f.snapshot.is_empty() {
+        fields.push(inline("snapshot", f.snapshot.clone()));
+    }
+    fields.push(inline("entry", f.entry.clone()));
+    fields.push(nested("steps", render_steps(&f.steps, 1, &ctx)?));
+    Ok(braced(&format!("flow {} ", f.id), 0, &fields))
+}
+
+fn render_system(s: &SystemConstruct) -> String {
+    let members: Vec<String> = s
+        .members
+        .iter()
+        .map(|m| format!("{}: {}", m.id, quote(&m.path)))
+        .collect();
+    let shared_personas: Vec<String> = s
+        .shared_personas
+        .iter()
+        .map(|p| {
+            format!(
+                "{{ persona: {}, contracts: {} }}",
+                p.persona,
+                ident_list(&p.contracts)
+            )
+        })
+        .collect();
+    let triggers: Vec<String> = s
+        .flow_triggers
+        .iter()
+        .map(|t| {
+            format!(
+                "{{ source: {}.{}, on: {}, target: {}.{}, persona: {} }}",
+                t.source_contract, t.source_flow, t.on, t.target_contract, t.target_flow, t.persona
+            )
+        })
+        .collect();
+    let shared_entities: Vec<String> = s
+        .shared_entities
+        .iter()
+        .map(|e| {
+            format!(
+                "{{ entity: {}, contracts: {} }}",
+                e.entity,
+                ident_list(&e.contracts)
+            )
+        })
+        .collect();
+    let fields = vec![
+        nested("members", bracketed_lines(&members, 1)),
+        nested("shared_personas", bracketed_lines(&shared_personas, 1)),
+        nested("triggers", bracketed_lines(&triggers, 1)),
+        nested("shared_entities", bracketed_lines(&shared_entities, 1)),
+    ];
+    braced(&format!("system {} ", s.id), 0, &fields)
+}
+
+fn render_test(t: &TestConstruct) -> Result<String, String> {
+    let ctx = format!("test '{}'", t.id);
+    let mut out = format!("test {} {{\n", t.id);
+    if !t.given.is_empty() {
+        let given = t
+            .given
+            .iter()
+            .map(|(fact, value)| Ok(inline(fact, render_fact_input(value, &ctx)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        out.push_str(&pad(1));
+        out.push_str(&braced("given facts ", 1, &given));
+        out.push('\n');
+    }
+    for exp in &t.expect {
+        out.push_str(&pad(1));
+        out.push_str(&format!(
+            "expect verdict {} {}\n",
+            exp.verdict,
+            if exp.present { "present" } else { "absent" }
+        ));
+    }
+    out.push('}');
+    Ok(out)
+}
+
+fn render_assert(a: &AssertConstruct) -> String {
+    format!("assert forall states of {}: {}", a.entity, a.property)
+}
+
+/// A given fact value in facts-file shape, as a DSL literal.
+fn render_fact_input(value: &Value, ctx: &str) -> Result<String, String> {
+    match value {
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) => Ok(quote(s)),
+        Value::Object(_) => render_money(value, ctx),
+        _ => Err(format!("{}: unsupported given value {}", ctx, value)),
+    }
+}
+
+// ── Flow steps ───────────────────────────────────────────────────────
+
+/// Render a `{ step_id: Kind { ... } ... }` map; the closing brace sits at `depth`.
+fn render_steps(steps: &[Value], depth: usize, ctx: &str) -> Result<String, String> {
+    let mut out = String::from("{\n");
+    for (i, step) in steps.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let id = str_field(step, "id", ctx)?;
+        out.push_str(&pad(depth + 1));
+        out.push_str(&format!("{}: ", id));
+        out.push_str(&render_step(
+            step,
+            depth + 1,
+            &format!("{} step '{}'", ctx, id),
+        )?);
+        out.push('\n');
+    }
+    out.push_str(&pad(depth));
+    out.push('}');
+    Ok(out)
+}
+
+fn render_step(step: &Value, depth: usize, ctx: &str) -> Result<String, String> {
+    let kind = str_field(step, "kind", ctx)?;
+    let mut fields = Vec::new();
+    match kind {
+        "OperationStep" => {
+            fields.push(inline("op", str_field(step, "op", ctx)?));
+            fields.push(inline("persona", str_field(step, "persona", ctx)?));
+            let outcomes = step
+                .get("outcomes")
+                .and_then(|o| o.as_object())
+                .ok_or_else(|| format!("{}: missing 'outcomes'", ctx))?;
+            let mut outcome_fields = Vec::new();
+            for (label, target) in outcomes {
+                outcome_fields.push(inline(label, render_target(target, ctx)?));
+            }
+            fields.push(nested("outcomes", braced("", depth + 1, &outcome_fields)));
+            if let Some(handler) = step.get("on_failure") {
+                fields.push(render_handler_field("on_failure", handler, depth + 1, ctx)?);
+            }
+            if let Some(timeout) = step.get("timeout") {
+                fields.push(render_timeout_field(timeout, depth + 1, ctx)?);
+            }
+            if let Some(weights) = step.get("weights") {
+                fields.push(render_weights_field(weights, depth + 1, ctx)?);
+            }
+        }
+        "BranchStep" => {
+            let condition = step
+                .get("condition")
+                .ok_or_else(|| format!("{}: missing 'condition'", ctx))?;
+            fields.push(inline("condition", render_predicate(condition)?));
+            fields.push(inline("persona", str_field(step, "persona", ctx)?));
+            fields.push(inline(
+                "if_true",
+                render_target(field(step, "if_true", ctx)?, ctx)?,
+            ));
+            fields.push(inline(
+                "if_false",
+                render_target(field(step, "if_false", ctx)?, ctx)?,
+            ));
+            if let Some(weights) = step.get("weights") {
+                fields.push(render_weights_field(weights, depth + 1, ctx)?);
+            }
+        }
+        "HandoffStep" => {
+            fields.push(inline(
+                "from_persona",
+                str_field(step, "from_persona", ctx)?,
+            ));
+            fields.push(inline("to_persona", str_field(step, "to_persona", ctx)?));
+            fields.push(inline("next", str_field(step, "next", ctx)?));
+            if step.get("pause").and_then(|v| v.as_bool()) == Some(true) {
+                fields.push(inline("pause", "true"));
+            }
+            if let Some(timeout) = step.get("timeout") {
+                fields.push(render_timeout_field(timeout, depth + 1, ctx)?);
+            }
+        }
+        "SubFlowStep" => {
+            fields.push(inline("flow", str_field(step, "flow", ctx)?));
+            fields.push(inline("persona", str_field(step, "persona", ctx)?));
+            fields.push(inline(
+                "on_success",
+                render_target(field(step, "on_success", ctx)?, ctx)?,
+            ));
+            fields.push(render_handler_field(
+                "on_failure",
+                field(step, "on_failure", ctx)?,
+                depth + 1,
+                ctx,
+            )?);
+        }
+        "ParallelStep" => {
+            let branches = step
+                .get("branches")
+                .and_then(|b| b.as_array())
+                .ok_or_else(|| format!("{}: missing 'branches'", ctx))?;
+            let mut rendered = Vec::new();
+            for branch in branches {
+                let id = str_field(branch, "id", ctx)?;
+                let branch_ctx = format!("{} branch '{}'", ctx, id);
+                let steps = branch
+                    .get("steps")
+                    .and_then(|s| s.as_array())
+                    .ok_or_else(|| format!("{}: missing 'steps'", branch_ctx))?;
+                rendered.push(braced(
+                    "Branch ",
+                    depth + 2,
+                    &[
+                        inline("id", id),
+                        inline("entry", str_field(branch, "entry", &branch_ctx)?),
+                        nested("steps", render_steps(steps, depth + 3, &branch_ctx)?),
+                    ],
+                ));
+            }
+            fields.push(nested("branches", bracketed_lines(&rendered, depth + 1)));
+
+            let join = field(step, "join", ctx)?;
+            let mut join_fields = Vec::new();
+            if let Some(t) = join.get("on_all_success") {
+                join_fields.push(inline("on_all_success", render_target(t, ctx)?));
+            }
+            if let Some(h) = join.get("on_any_failure") {
+                join_fields.push(render_handler_field("on_any_failure", h, depth + 2, ctx)?);
+            }
+            if let Some(t) = join.get("on_all_complete").filter(|t| !t.is_null()) {
+                join_fields.push(inline("on_all_complete", render_target(t, ctx)?));
+            }
+            fields.push(nested(
+                "join",
+                braced("JoinPolicy ", depth + 1, &join_fields),
+            ));
+        }
+        other => return Err(format!("{}: unknown step kind '{}'", ctx, other)),
+    }
+    Ok(braced(&format!("{} ", kind), depth, &fields))
+}
+
+fn field<'a>(v: &'a Value, key: &str, ctx: &str) -> Result<&'a Value, String> {
+    v.get(key)
+        .ok_or_else(|| format!("{}: missing '{}'", ctx, key))
+}
+
+fn render_target(target: &Value, ctx: &str) -> Result<String, String> {
+    if let Some(step_id) = target.as_str() {
+        return Ok(step_id.to_string());
+    }
+    Ok(format!("Terminal({})", str_field(target, "outcome", ctx)?))
+}
+
+fn terminal_outcome<'a>(v: &'a Value, key: &str, ctx: &str) -> Result<&'a str, String> {
+    str_field(field(v, key, ctx)?, "outcome", ctx)
+}
+
+/// A failure handler as a step field. Compensation spans several lines;
+/// the other handlers fit inline.
+fn render_handler_field(
+    key: &str,
+    handler: &Value,
+    depth: usize,
+    ctx: &str,
+) -> Result<Field, String> {
+    match str_field(handler, "kind", ctx)? {
+        "Terminate" => Ok(inline(
+            key,
+            format!(
+                "Terminate(outcome: {})",
+                str_field(handler, "outcome", ctx)?
+            ),
+        )),
+        "Escalate" => Ok(inline(
+            key,
+            format!(
+                "Escalate(to_persona: {} next: {})",
+                str_field(handler, "to_persona", ctx)?,
+                str_field(handler, "next", ctx)?
+            ),
+        )),
+        "Compensate" => {
+            let steps = handler
+                .get("steps")
+                .and_then(|s| s.as_array())
+                .ok_or_else(|| format!("{}: Compensate missing 'steps'", ctx))?;
+            let mut rendered = Vec::new();
+            for s in steps {
+                rendered.push(braced(
+                    "",
+                    depth + 2,
+                    &[
+                        inline("op", str_field(s, "op", ctx)?),
+                        inline("persona", str_field(s, "persona", ctx)?),
+                        inline(
+                            "on_failure",
+                            format!("Terminal({})", terminal_outcome(s, "on_failure", ctx)?),
+                        ),
+                    ],
+                ));
+            }
+            let mut out = String::from("Compensate(\n");
+            out.push_str(&pad(depth + 1));
+            out.push_str(&format!(
+                "steps: {}\n",
+                bracketed_lines(&rendered, depth + 1)
+            ));
+            out.push_str(&pad(depth + 1));
+            out.push_str(&format!(
+                "then:  Terminal({})\n",
+                terminal_outcome(handler, "then", ctx)?
+            ));
+            out.push_str(&pad(depth));
+            out.push(')');
+            Ok(nested(key, out))
+        }
+        other => Err(format!("{}: unknown failure handler '{}'", ctx, other)),
+    }
+}
+
+/// A step `timeout` field. `on_timeout` is either a step target or a
+/// failure handler (distinguished by `kind`).
+fn render_timeout_field(timeout: &Value, depth: usize, ctx: &str) -> Result<Field, String> {
+    let action = field(timeout, "on_timeout", ctx)?;
+    let on_timeout = match action.get("kind").and_then(|k| k.as_str()) {
+        Some("Terminate" | "Compensate" | "Escalate") => {
+            match render_handler_field("on_timeout", action, depth + 1, ctx)? {
+                Field::Inline(_, v) | Field::Nested(_, v) => v,
+            }
+        }
+        _ => render_target(action, ctx)?,
+    };
+    let mut out = String::from("Timeout(\n");
+    out.push_str(&pad(depth + 1));
+    out.push_str(&format!(
+        "deadline:   {}\n",
+        str_field(timeout, "deadline", ctx)?
+    ));
+    out.push_str(&pad(depth + 1));
+    out.push_str(&format!("on_timeout: {}\n", on_timeout));
+    out.push_str(&pad(depth));
+    out.push(')');
+    Ok(nested("timeout", out))
+}
+
+/// A step `weights` field: `{ label: probability ... }`.
+fn render_weights_field(weights: &Value, depth: usize, ctx: &str) -> Result<Field, String> {
+    let weights = weights
+        .as_object()
+        .ok_or_else(|| format!("{}: 'weights' must be an object", ctx))?;
+    let mut weight_fields = Vec::new();
+    for (label, weight) in weights {
+        let weight = weight
+            .as_f64()
+            .ok_or_else(|| format!("{}: weight '{}' must be a number", ctx, label))?;
+        weight_fields.push(inline(label, weight.to_string()));
+    }
+    Ok(nested("weights", braced("", depth, &weight_fields)))
+}
+
+// ── Types ────────────────────────────────────────────────────────────
+
+fn render_type(ty: &Value) -> Result<String, String> {
+    let base = str_field(ty, "base", "type")?;
+    let int = |key: &str| ty.get(key).and_then(|v| v.as_i64());
+    let rounding = match ty.get("rounding").and_then(|v| v.as_str()) {
+        Some(mode) => format!(", rounding: {}", mode),
+        None => String::new(),
+    };
+    Ok(match base {
+        "Bool" | "Date" | "DateTime" => base.to_string(),
+        "Int" => match (int("min"), int("max")) {
+            (Some(i64::MIN), Some(i64::MAX)) | (None, None) => "Int".to_string(),
+            (min, max) => format!(
+                "Int(min: {}, max: {})",
+                min.unwrap_or(i64::MIN),
+                max.unwrap_or(i64::MAX)
+            ),
+        },
+        "Decimal" => format!(
+            "Decimal(precision: {}, scale: {}{}{})",
+            int("precision").unwrap_or(0),
+            int("scale").unwrap_or(0),
+            match ty.get("unit").and_then(|v| v.as_str()) {
+                Some(unit) => format!(", unit: {}", quote(unit)),
+                None => String::new(),
+            },
+            rounding
+        ),
+        "Text" => match int("max_length") {
+            Some(n) if n > 0 => format!("Text(max_length: {})", n),
+            _ => "Text".to_string(),
+        },
+        "Money" => {
+            let mut params = vec![format!(
+                "currency: {}",
+                quote(str_field(ty, "currency", "Money")?)
+            )];
+            for key in ["precision", "scale"] {
+                if let Some(n) = int(key) {
+                    params.push(format!("{}: {}", key, n));
+                }
+            }
+            format!("Money({}{})", params.join(", "), rounding)
+        }
+        "Duration" => {
+            let mut params = vec![format!(
+                "unit: {}",
+                quote(str_field(ty, "unit", "Duration")?)
+            )];
+            if let Some(min) = int("min").filter(|m| *m != 0) {
+                params.push(format!("min: {}", min));
+            }
+            if let Some(max) = int("max").filter(|m| *m != i64::MAX) {
+                params.push(format!("max: {}", max));
+            }
+            format!("Duration({})", params.join(", "))
+        }
+        "Enum" => {
+            let values: Vec<String> = ty
+                .get("values")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_str()).map(quote).collect())
+                .unwrap_or_default();
+            format!("Enum(values: [{}])", values.join(", "))
+        }
+        "List" => {
+            let element = field(ty, "element_type", "List")?;
+            format!(
+                "List(element_type: {}, max: {})",
+                render_type(element)?,
+                int("max").unwrap_or(0)
+            )
+        }
+        "Record" => format!("Record(fields: {})", render_type_map(ty, "fields")?),
+        "TaggedUnion" => format!("TaggedUnion {}", render_type_map(ty, "variants")?),
+        "TypeRef" => str_field(ty, "id", "TypeRef")?.to_string(),
+        other => return Err(format!("unknown base type '{}'", other)),
+    })
+}
+
+fn render_type_map(ty: &Value, key: &str) -> Result<String, String> {
+    let map = ty
+        .get(key)
+        .and_then(|m| m.as_object())
+        .ok_or_else(|| format!("type missing '{}'", key))?;
+    let mut parts = Vec::new();
+    for (name, t) in map {
+        parts.push(format!("{}: {}", name, render_type(t)?));
+    }
+    Ok(format!("{{ {} }}", parts.join(", ")))
+}
+
+// ── Predicates ───────────────────────────────────────────────────────
+
+/// Render a top-level predicate. A left-nested chain of `∧` (or `∨`) is
+/// broken onto continuation lines, each starting with the connective.
+fn render_predicate(expr: &Value) -> Result<String, String> {
+    let op = expr.get("op").and_then(|o| o.as_str());
+    let (op, symbol) = match op {
+        Some("and") => ("and", "∧"),
+        Some("or") => ("or", "∨"),
+        _ => return render_expr(expr),
+    };
+    let mut rights = Vec::new();
+    let mut node = expr;
+    while node.get("op").and_then(|o| o.as_str()) == Some(op) {
+        rights.push(field(node, "right", "predicate")?);
+        node = field(node, "left", "predicate")?;
+    }
+    let mut lines = vec![render_operand(node, op, false)?];
+    for right in rights.into_iter().rev() {
+        lines.push(format!("{} {}", symbol, render_operand(right, op, true)?));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn render_expr(expr: &Value) -> Result<String, String> {
+    if let Some(verdict) = expr.get("verdict_present").and_then(|v| v.as_str()) {
+        return Ok(format!("verdict_present({})", verdict));
+    }
+    if let Some(quantifier) = expr.get("quantifier").and_then(|q| q.as_str()) {
+        let symbol = match quantifier {
+            "forall" => "∀",
+            "exists" => "∃",
+            other => return Err(format!("unknown quantifier '{}'", other)),
+        };
+        let domain = field(expr, "domain", "quantifier")?;
+        let domain = match domain.get("instances_of").and_then(|e| e.as_str()) {
+            Some(entity) => format!("instances({})", entity),
+            None => str_field(domain, "fact_ref", "quantifier")?.to_string(),
+        };
+        return Ok(format!(
+            "{} {} ∈ {} . {}",
+            symbol,
+            str_field(expr, "variable", "quantifier")?,
+            domain,
+            render_expr(field(expr, "body", "quantifier")?)?
+        ));
+    }
+    match expr.get("op").and_then(|o| o.as_str()) {
+        Some(op @ ("and" | "or")) => {
+            let symbol = if op == "and" { "∧" } else { "∨" };
+            Ok(format!(
+                "{} {} {}",
+                render_operand(field(expr, "left", op)?, op, false)?,
+                symbol,
+                render_operand(field(expr, "right", op)?, op, true)?
+            ))
+        }
+        Some("not") => Ok(format!(
+            "¬{}",
+            render_operand(field(expr, "operand", "not")?, "not", false)?
+        )),
+        Some(op) => Ok(format!(
+            "{} {} {}",
+            render_term(field(expr, "left", op)?)?,
+            op,
+            render_term(field(expr, "right", op)?)?
+        )),
+        None => Err(format!("unrecognized predicate expression {}", expr)),
+    }
+}
+
+/// Render `expr` as an operand of `parent`, parenthesizing where the
+/// parser's precedence (`¬` > `∧` > `∨`, left-associative) would
+/// otherwise regroup it. Quantifier bodies extend as far right as
+/// possible, so nested quantifiers are always parenthesized.
+fn render_operand(expr: &Value, parent: &str, is_right: bool) -> Result<String, String> {
+    let rendered = render_expr(expr)?;
+    let needs_parens = if expr.get("quantifier").is_some() {
+        true
+    } else if expr.get("verdict_present").is_some() {
+        false
+    } else {
+        match (expr.get("op").and_then(|o| o.as_str()), parent) {
+            (_, "not") => true,
+            (Some("or"), "and") => true,
+            (Some(op), p) if op == p => is_right,
+            _ => false,
+        }
+    };
+    Ok(if needs_parens {
+        format!("({})", rendered)
+    } else {
+        rendered
+    })
+}
+
+fn render_term(term: &Value) -> Result<String, String> {
+    if let Some(name) = term.get("fact_ref").and_then(|f| f.as_str()) {
+        return Ok(name.to_string());
+    }
+    if let Some(var) = term.get("instance_state").and_then(|v| v.as_str()) {
+        return Ok(format!("state({})", var));
+    }
+    if let Some(amount) = term.get("convert") {
+        return Ok(format!(
+            "convert({}, {})",
+            render_term(amount)?,
+            render_term(field(term, "rate", "convert")?)?
+        ));
+    }
+    if let Some(field_ref) = term.get("field_ref") {
+        return Ok(format!(
+            "{}.{}",
+            str_field(field_ref, "var", "field_ref")?,
+            str_field(field_ref, "field", "field_ref")?
+        ));
+    }
+    if term.get("op").and_then(|o| o.as_str()) == Some("*") {
+        let left = render_term(field(term, "left", "*")?)?;
+        let right = match term.get("literal") {
+            Some(n) => n.to_string(),
+            None => render_term(field(term, "right", "*")?)?,
+        };
+        return Ok(format!("{} * {}", left, right));
+    }
+    if let Some(literal) = term.get("literal") {
+        let base = term
+            .get("type")
+            .and_then(|t| t.get("base"))
+            .and_then(|b| b.as_str());
+        return match literal {
+            Value::Bool(b) => Ok(b.to_string()),
+            Value::Number(n) => Ok(n.to_string()),
+            // Decimal literals are written bare so they lex as decimals.
+            Value::String(s) if base == Some("Decimal") => Ok(s.clone()),
+            Value::String(s) => Ok(quote(s)),
+            Value::Object(_) => render_money(literal, "literal"),
+            other => Err(format!("unsupported literal {}", other)),
+        };
+    }
+    Err(format!("unrecognized term {}", term))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use tenor_core::source::InMemoryProvider;
+
+    fn workspace_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .and_then(|p| p.parent())
+            .expect("workspace root")
+            .to_path_buf()
+    }
+
+    /// Remove provenance blocks, which legitimately differ after a round trip.
+    fn strip_provenance(v: &mut Value) {
+        match v {
+            Value::Object(map) => {
+                map.remove("provenance");
+                for child in map.values_mut() {
+                    strip_provenance(child);
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(strip_provenance),
+            _ => {}
+        }
+    }
+
+    fn reelaborate(bundle_id: &str, source: &str) -> Value {
+        let path = PathBuf::from(format!("/decompiled/{}.tenor", bundle_id));
+        let mut files = HashMap::new();
+        files.insert(path.clone(), source.to_string());
+        let provider = InMemoryProvider::new(files);
+        tenor_core::elaborate::elaborate_with_provider(&path, &provider).unwrap_or_else(|e| {
+            panic!(
+                "decompiled '{}' failed to elaborate: {:?}\n---\n{}",
+                bundle_id, e, source
+            )
+        })
+    }
+
+    #[test]
+    fn round_trip_conformance_positive_corpus() {
+        let dir = workspace_root().join("conformance/positive");
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .expect("read conformance/positive")
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == "tenor"))
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty(), "no positive conformance fixtures found");
+
+        for path in paths {
+            let mut original = tenor_core::elaborate::elaborate(&path)
+                .unwrap_or_else(|e| panic!("{} failed to elaborate: {:?}", path.display(), e));
+            let source = decompile(&original)
+                .unwrap_or_else(|e| panic!("{} failed to decompile: {}", path.display(), e));
+            let bundle_id = original["id"].as_str().unwrap().to_string();
+            let mut round_tripped = reelaborate(&bundle_id, &source);
+
+            strip_provenance(&mut original);
+            strip_provenance(&mut round_tripped);
+            assert_eq!(
+                original,
+                round_tripped,
+                "round trip changed bundle for {}\n---\n{}",
+                path.display(),
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn decompile_escrow_is_readable() {
+        let path = workspace_root().join("conformance/positive/integration_escrow.tenor");
+        let bundle = tenor_core::elaborate::elaborate(&path).unwrap();
+        let source = decompile(&bundle).unwrap();
+
+        assert!(source.contains("allowed_personas: [buyer, seller]\n"));
+        assert!(source.contains("default: Money { amount: \"10000.00\", currency: \"USD\" }"));
+        assert!(source.contains("when:    verdict_present(line_items_validated)\n"));
+        assert!(source.contains("         ∧ verdict_present(delivery_confirmed)\n"));
+        assert!(source.contains("produce: verdict release_approved { payload: Text = \"auto\" }"));
+        assert!(source.contains("on_failure: Terminate(outcome: failure)"));
+    }
+
+    #[test]
+    fn decompile_preserves_source_descriptions() {
+        let path = workspace_root().join("conformance/positive/source_basic.tenor");
+        let bundle = tenor_core::elaborate::elaborate(&path).unwrap();
+        let source = decompile(&bundle).unwrap();
+        assert!(source.contains("description: \"Order management REST API\""));
+        assert!(source.contains("source: order_service { path: \"orders.balance\" }"));
+    }
+
+    #[test]
+    fn decompile_accepts_manifest() {
+        let path = workspace_root().join("conformance/positive/persona_basic.tenor");
+        let bundle = tenor_core::elaborate::elaborate(&path).unwrap();
+        let manifest = crate::manifest::build_manifest(bundle.clone());
+        assert_eq!(decompile(&manifest).unwrap(), decompile(&bundle).unwrap());
+    }
+
+    #[test]
+    fn parenthesizes_by_precedence() {
+        let a = serde_json::json!({"verdict_present": "a"});
+        let b = serde_json::json!({"verdict_present": "b"});
+        let c = serde_json::json!({"verdict_present": "c"});
+        let or = serde_json::json!({"left": a, "op": "or", "right": b});
+        let and = serde_json::json!({"left": or, "op": "and", "right": c});
+        assert_eq!(
+            render_expr(&and).unwrap(),
+            "(verdict_present(a) ∨ verdict_present(b)) ∧ verdict_present(c)"
+        );
+        let not = serde_json::json!({"op": "not", "operand": and});
+        assert!(render_expr(&not).unwrap().starts_with("¬(("));
+    }
+}
@@ -1,8 +1,82 @@
 //! Thin wrapper around `tenor_eval::migration` for CLI diff commands.
 //!
-//! All diff and classification logic lives in `tenor_eval::migration`.
-//! This module re-exports the public API so that `main.rs` continues
-//! to work unchanged.
+//! All structural diff and classification logic lives in
+//! `tenor_eval::migration`. This module re-exports the public API so that
+//! `main.rs` continues to work unchanged, and layers the S6 flow-path
+//! comparison from `tenor_analyze` on top of the structural taxonomy.
 
 pub use tenor_eval::migration::classify::classify_diff;
 pub use tenor_eval::migration::diff::diff_bundles;
+
+use tenor_eval::migration::classify::{ChangeClassification, ChangeSeverity, ClassifiedDiff};
+
+/// Refine a classified diff using S6 flow-path comparison.
+///
+/// A flow whose `entry`/`steps` changed but whose reachable paths and
+/// terminal outcomes are identical (e.g. steps were only renamed) is a
+/// structural refactor and is reclassified as non-breaking. A flow that
+/// can no longer reach a terminal outcome it used to reach is breaking.
+/// Anything else keeps its structural classification.
+pub fn refine_flow_changes(
+    classified: &mut ClassifiedDiff,
+    t1: &serde_json::Value,
+    t2: &serde_json::Value,
+) {
+    let Ok(flow_diffs) = tenor_analyze::diff_flow_paths(t1, t2) else {
+        return;
+    };
+    for flow in flow_diffs {
+        let changed_fields: Vec<String> = classified
+            .changed
+            .iter()
+            .filter(|c| c.kind == "Flow" && c.id == flow.flow_id)
+            .flat_map(|c| c.fields.iter())
+            .filter(|f| matches!(f.field.as_str(), "entry" | "steps"))
+            .map(|f| f.field.clone())
+            .collect();
+        if changed_fields.is_empty() {
+            continue;
+        }
+
+        let classification = if flow.equivalent {
+            let renames: Vec<String> = flow
+                .renamed_steps
+                .iter()
+                .map(|(old, new)| format!("{} → {}", old, new))
+                .collect();
+            let mut reason =
+                "Structural refactor: reachable paths and terminal outcomes unchanged (S6)"
+                    .to_string();
+            if !renames.is_empty() {
+                reason.push_str(&format!("; renamed steps: {}", renames.join(", ")));
+            }
+            ChangeClassification {
+                severity: ChangeSeverity::NonBreaking,
+                reason,
+                migration_action: (!renames.is_empty())
+                    .then(|| "Remap in-flight instances positioned at renamed steps".to_string()),
+            }
+        } else if !flow.outcomes_removed.is_empty() {
+            ChangeClassification {
+                severity: ChangeSeverity::Breaking,
+                reason: format!(
+                    "Flow can no longer reach terminal outcome(s): {}",
+                    flow.outcomes_removed
+                        .iter()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                migration_action: Some(
+                    "Version the flow or update consumers of the removed outcomes".to_string(),
+                ),
+            }
+        } else {
+            continue;
+        };
+
+        for field in changed_fields {
+            classified.reclassify_field("Flow", &flow.flow_id, &field, classification.clone());
+        }
+    }
+}
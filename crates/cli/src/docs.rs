@@ -0,0 +1,783 @@
+//! `tenor generate docs` — publishable contract documentation.
+//!
+//! Produces one page per construct family for a contract:
+//! - `index`      — contract overview and navigation
+//! - `facts`      — facts with types, sources, and defaults
+//! - `entities`   — entities with Mermaid state diagrams
+//! - `rules`      — rules grouped by stratum
+//! - `operations` — operations with personas, preconditions, and effects
+//! - `flows`      — flows with execution paths from S6
+//! - `analysis`   — S1-S8 findings
+//!
+//! Construct annotations (`@owner`, `@description`, `@tag`) are shown
+//! alongside the construct they annotate.
+//!
+//! Pages are built as a small block model and rendered to either a
+//! Markdown bundle or a static HTML site, so both outputs always carry
+//! the same content.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use tenor_interchange::{
+    Annotations, EntityConstruct, FactConstruct, FlowConstruct, InterchangeConstruct,
+    OperationConstruct, PersonaConstruct, RuleConstruct,
+};
+
+use crate::explain::{describe_condition, describe_default, describe_fact_type, describe_source};
+
+/// Output format for generated documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum DocsFormat {
+    Markdown,
+    Html,
+}
+
+impl DocsFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        }
+    }
+}
+
+/// A renderable documentation block.
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    List(Vec<String>),
+    Table {
+        headers: Vec<&'static str>,
+        rows: Vec<Vec<String>>,
+    },
+    /// Mermaid diagram source.
+    Diagram(String),
+    /// Links to sibling pages: (label, page stem).
+    Nav(Vec<(String, String)>),
+}
+
+/// A single documentation page.
+struct Page {
+    stem: &'static str,
+    title: String,
+    blocks: Vec<Block>,
+}
+
+/// Constructs of one bundle, classified by kind.
+struct Classified<'a> {
+    facts: Vec<&'a FactConstruct>,
+    entities: Vec<&'a EntityConstruct>,
+    personas: Vec<&'a PersonaConstruct>,
+    rules: Vec<&'a RuleConstruct>,
+    operations: Vec<&'a OperationConstruct>,
+    flows: Vec<&'a FlowConstruct>,
+}
+
+/// Generate documentation for a bundle into `{out_dir}/{bundle_id}/`.
+///
+/// Returns the directory containing the generated pages.
+pub(crate) fn generate_docs(
+    raw_bundle: &serde_json::Value,
+    out_dir: &Path,
+    format: DocsFormat,
+) -> Result<PathBuf, String> {
+    let pages = build_pages(raw_bundle)?;
+    let bundle_id = raw_bundle
+        .get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("contract");
+
+    let contract_dir = out_dir.join(bundle_id.replace('_', "-"));
+    std::fs::create_dir_all(&contract_dir).map_err(|e| {
+        format!(
+            "failed to create directory '{}': {}",
+            contract_dir.display(),
+            e
+        )
+    })?;
+
+    for page in &pages {
+        let content = match format {
+            DocsFormat::Markdown => render_markdown(page),
+            DocsFormat::Html => render_html(page, bundle_id, &pages),
+        };
+        let path = contract_dir.join(format!("{}.{}", page.stem, format.extension()));
+        std::fs::write(&path, content)
+            .map_err(|e| format!("failed to write '{}': {}", path.display(), e))?;
+    }
+
+    Ok(contract_dir)
+}
+
+/// Build all documentation pages for a bundle.
+fn build_pages(raw_bundle: &serde_json::Value) -> Result<Vec<Page>, String> {
+    let bundle = tenor_interchange::from_interchange(raw_bundle)
+        .map_err(|e| format!("failed to parse interchange bundle: {}", e))?;
+
+    let mut c = Classified {
+        facts: Vec::new(),
+        entities: Vec::new(),
+        personas: Vec::new(),
+        rules: Vec::new(),
+        operations: Vec::new(),
+        flows: Vec::new(),
+    };
+    for construct in &bundle.constructs {
+        match construct {
+            InterchangeConstruct::Fact(f) => c.facts.push(f),
+            InterchangeConstruct::Entity(e) => c.entities.push(e),
+            InterchangeConstruct::Persona(p) => c.personas.push(p),
+            InterchangeConstruct::Rule(r) => c.rules.push(r),
+            InterchangeConstruct::Operation(o) => c.operations.push(o),
+            InterchangeConstruct::Flow(f) => c.flows.push(f),
+            // Sources, type declarations, and systems are summarized via facts;
+            // tests and asserts are not part of the documented contract surface
+            InterchangeConstruct::Source(_)
+            | InterchangeConstruct::TypeDecl(_)
+            | InterchangeConstruct::System(_)
+            | InterchangeConstruct::Test(_)
+            | InterchangeConstruct::Assert(_) => {}
+        }
+    }
+
+    // Analysis is best-effort: documentation is still useful without it.
+    let report = tenor_analyze::analyze(raw_bundle).ok();
+
+    Ok(vec![
+        index_page(&bundle.id, &bundle.tenor_version, &c),
+        facts_page(&c),
+        entities_page(&c),
+        rules_page(&c),
+        operations_page(&c),
+        flows_page(&c, report.as_ref()),
+        analysis_page(report.as_ref()),
+    ])
+}
+
+// ─── Pages ───────────────────────────────────────────────────────────────────
+
+fn index_page(bundle_id: &str, tenor_version: &str, c: &Classified<'_>) -> Page {
+    let mut blocks = vec![
+        Block::Paragraph(format!(
+            "Contract `{}` (Tenor {}).",
+            bundle_id, tenor_version
+        )),
+        Block::Table {
+            headers: vec!["Construct", "Count"],
+            rows: vec![
+                vec!["Facts".to_string(), c.facts.len().to_string()],
+                vec!["Entities".to_string(), c.entities.len().to_string()],
+                vec!["Personas".to_string(), c.personas.len().to_string()],
+                vec!["Rules".to_string(), c.rules.len().to_string()],
+                vec!["Operations".to_string(), c.operations.len().to_string()],
+                vec!["Flows".to_string(), c.flows.len().to_string()],
+            ],
+        },
+    ];
+    if !c.personas.is_empty() {
+        blocks.push(Block::Heading(2, "Personas".to_string()));
+        blocks.push(Block::List(
+            c.personas
+                .iter()
+                .map(|p| match annotation_summary(&p.annotations) {
+                    Some(summary) => format!("`{}` — {}", p.id, summary),
+                    None => format!("`{}`", p.id),
+                })
+                .collect(),
+        ));
+    }
+    blocks.push(Block::Heading(2, "Contents".to_string()));
+    blocks.push(Block::Nav(
+        [
+            ("Facts", "facts"),
+            ("Entities", "entities"),
+            ("Rules", "rules"),
+            ("Operations", "operations"),
+            ("Flows", "flows"),
+            ("Analysis", "analysis"),
+        ]
+        .iter()
+        .map(|(label, stem)| (label.to_string(), stem.to_string()))
+        .collect(),
+    ));
+    Page {
+        stem: "index",
+        title: bundle_id.to_string(),
+        blocks,
+    }
+}
+
+fn facts_page(c: &Classified<'_>) -> Page {
+    let blocks = if c.facts.is_empty() {
+        vec![Block::Paragraph(
+            "This contract declares no facts.".to_string(),
+        )]
+    } else {
+        let annotated = c.facts.iter().any(|f| !f.annotations.is_empty());
+        let mut headers = vec!["Fact", "Type", "Source", "Default"];
+        if annotated {
+            headers.push("Notes");
+        }
+        vec![Block::Table {
+            headers,
+            rows: c
+                .facts
+                .iter()
+                .map(|f| {
+                    let mut row = vec![
+                        format!("`{}`", f.id),
+                        describe_fact_type(&f.fact_type, true),
+                        describe_source(&f.source),
+                        describe_default(f.default.as_ref()),
+                    ];
+                    if annotated {
+                        row.push(annotation_summary(&f.annotations).unwrap_or_default());
+                    }
+                    row
+                })
+                .collect(),
+        }]
+    };
+    Page {
+        stem: "facts",
+        title: "Facts".to_string(),
+        blocks,
+    }
+}
+
+fn entities_page(c: &Classified<'_>) -> Page {
+    let mut blocks = Vec::new();
+    if c.entities.is_empty() {
+        blocks.push(Block::Paragraph(
+            "This contract declares no entities.".to_string(),
+        ));
+    }
+    for entity in &c.entities {
+        blocks.push(Block::Heading(2, entity.id.clone()));
+        blocks.extend(description_block(&entity.annotations));
+        let mut facts = vec![
+            format!("States: {}", code_list(&entity.states)),
+            format!("Initial state: `{}`", entity.initial),
+        ];
+        if let Some(ref parent) = entity.parent {
+            facts.push(format!("Parent entity: `{}`", parent));
+        }
+        facts.extend(annotation_items(&entity.annotations));
+        blocks.push(Block::List(facts));
+        blocks.push(Block::Diagram(state_diagram(entity)));
+    }
+    Page {
+        stem: "entities",
+        title: "Entities".to_string(),
+        blocks,
+    }
+}
+
+fn rules_page(c: &Classified<'_>) -> Page {
+    let mut by_stratum: BTreeMap<u64, Vec<&RuleConstruct>> = BTreeMap::new();
+    for rule in &c.rules {
+        by_stratum.entry(rule.stratum).or_default().push(rule);
+    }
+
+    let mut blocks = Vec::new();
+    if by_stratum.is_empty() {
+        blocks.push(Block::Paragraph(
+            "This contract declares no rules.".to_string(),
+        ));
+    }
+    for (stratum, rules) in &by_stratum {
+        blocks.push(Block::Heading(2, format!("Stratum {}", stratum)));
+        let annotated = rules.iter().any(|r| !r.annotations.is_empty());
+        let mut headers = vec!["Rule", "When", "Produces"];
+        if annotated {
+            headers.push("Notes");
+        }
+        blocks.push(Block::Table {
+            headers,
+            rows: rules
+                .iter()
+                .map(|r| {
+                    let mut row = vec![
+                        format!("`{}`", r.id),
+                        r.when()
+                            .map(describe_condition)
+                            .unwrap_or_else(|| "-".to_string()),
+                        r.verdict_type()
+                            .map(|v| format!("`{}`", v))
+                            .unwrap_or_else(|| "-".to_string()),
+                    ];
+                    if annotated {
+                        row.push(annotation_summary(&r.annotations).unwrap_or_default());
+                    }
+                    row
+                })
+                .collect(),
+        });
+    }
+    Page {
+        stem: "rules",
+        title: "Rules".to_string(),
+        blocks,
+    }
+}
+
+fn operations_page(c: &Classified<'_>) -> Page {
+    let mut blocks = Vec::new();
+    if c.operations.is_empty() {
+        blocks.push(Block::Paragraph(
+            "This contract declares no operations.".to_string(),
+        ));
+    }
+    for op in &c.operations {
+        blocks.push(Block::Heading(2, op.id.clone()));
+        blocks.extend(description_block(&op.annotations));
+        let mut items = vec![format!(
+            "Allowed personas: {}",
+            code_list(&op.allowed_personas)
+        )];
+        items.push(format!(
+            "Precondition: {}",
+            op.precondition
+                .as_ref()
+                .filter(|p| !p.is_null())
+                .map(describe_condition)
+                .unwrap_or_else(|| "none".to_string())
+        ));
+        if !op.outcomes.is_empty() {
+            items.push(format!("Outcomes: {}", code_list(&op.outcomes)));
+        }
+        if let Some(errors) = op.error_contract.as_ref().and_then(|e| e.as_array()) {
+            let errors: Vec<String> = errors
+                .iter()
+                .filter_map(|e| e.as_str().map(|s| s.to_string()))
+                .collect();
+            if !errors.is_empty() {
+                items.push(format!("Error contract: {}", code_list(&errors)));
+            }
+        }
+        items.extend(annotation_items(&op.annotations));
+        blocks.push(Block::List(items));
+        if !op.effects.is_empty() {
+            blocks.push(Block::Table {
+                headers: vec!["Entity", "From", "To", "Outcome"],
+                rows: op
+                    .effects
+                    .iter()
+                    .map(|e| {
+                        vec![
+                            format!("`{}`", e.entity_id),
+                            e.from.clone(),
+                            e.to.clone(),
+                            e.outcome.clone().unwrap_or_else(|| "-".to_string()),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+    }
+    Page {
+        stem: "operations",
+        title: "Operations".to_string(),
+        blocks,
+    }
+}
+
+fn flows_page(c: &Classified<'_>, report: Option<&tenor_analyze::AnalysisReport>) -> Page {
+    let s6 = report.and_then(|r| r.s6_flow_paths.as_ref());
+    let mut blocks = Vec::new();
+    if c.flows.is_empty() {
+        blocks.push(Block::Paragraph(
+            "This contract declares no flows.".to_string(),
+        ));
+    }
+    for flow in &c.flows {
+        blocks.push(Block::Heading(2, flow.id.clone()));
+        blocks.extend(description_block(&flow.annotations));
+        let mut items = vec![
+            format!("Entry step: `{}`", flow.entry),
+            format!("Snapshot: {}", flow.snapshot),
+            format!("Steps: {}", flow.steps.len()),
+        ];
+        items.extend(annotation_items(&flow.annotations));
+        blocks.push(Block::List(items));
+
+        let Some(paths) = s6.and_then(|s6| s6.flows.get(&flow.id)) else {
+            continue;
+        };
+        blocks.push(Block::Heading(3, "Execution paths".to_string()));
+        if paths.truncated {
+            blocks.push(Block::Paragraph(format!(
+                "Path enumeration was truncated at {} paths.",
+                paths.path_count
+            )));
+        }
+        blocks.push(Block::Table {
+            headers: vec!["#", "Steps", "Outcome"],
+            rows: paths
+                .paths
+                .iter()
+                .enumerate()
+                .map(|(i, path)| {
+                    let steps: Vec<String> = path
+                        .steps
+                        .iter()
+                        .map(|s| match (&s.operation_id, &s.outcome) {
+                            (Some(op), Some(outcome)) => {
+                                format!("`{}` ({} → {})", s.step_id, op, outcome)
+                            }
+                            (Some(op), None) => format!("`{}` ({})", s.step_id, op),
+                            _ => format!("`{}`", s.step_id),
+                        })
+                        .collect();
+                    vec![
+                        (i + 1).to_string(),
+                        steps.join(" → "),
+                        path.terminal_outcome
+                            .clone()
+                            .unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect(),
+        });
+        if !paths.unreachable_steps.is_empty() {
+            let unreachable: Vec<String> = paths.unreachable_steps.iter().cloned().collect();
+            blocks.push(Block::Paragraph(format!(
+                "Unreachable steps: {}",
+                code_list(&unreachable)
+            )));
+        }
+    }
+    Page {
+        stem: "flows",
+        title: "Flows".to_string(),
+        blocks,
+    }
+}
+
+fn analysis_page(report: Option<&tenor_analyze::AnalysisReport>) -> Page {
+    let blocks = match report {
+        None => vec![Block::Paragraph(
+            "Static analysis could not be run on this bundle.".to_string(),
+        )],
+        Some(r) if r.findings.is_empty() => vec![Block::Paragraph(
+            "No findings. All analyses passed cleanly.".to_string(),
+        )],
+        Some(r) => vec![Block::Table {
+            headers: vec!["Analysis", "Severity", "Finding"],
+            rows: r
+                .findings
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.analysis.to_uppercase(),
+                        format!("{:?}", f.severity),
+                        f.message.clone(),
+                    ]
+                })
+                .collect(),
+        }],
+    };
+    Page {
+        stem: "analysis",
+        title: "Analysis findings".to_string(),
+        blocks,
+    }
+}
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+fn state_diagram(entity: &EntityConstruct) -> String {
+    let mut out = String::from("stateDiagram-v2\n");
+    out.push_str(&format!("    [*] --> {}\n", entity.initial));
+    for t in &entity.transitions {
+        let label = if t.guard.is_some() { " : guarded" } else { "" };
+        out.push_str(&format!("    {} --> {}{}\n", t.from, t.to, label));
+    }
+    out
+}
+
+/// The `@description` of a construct as a paragraph under its heading.
+fn description_block(annotations: &Annotations) -> Option<Block> {
+    annotations.description.clone().map(Block::Paragraph)
+}
+
+/// Owner and tag list items for a construct with its own section.
+fn annotation_items(annotations: &Annotations) -> Vec<String> {
+    let mut items = Vec::new();
+    if let Some(ref owner) = annotations.owner {
+        items.push(format!("Owner: {}", owner));
+    }
+    if !annotations.tags.is_empty() {
+        items.push(format!("Tags: {}", code_list(&annotations.tags)));
+    }
+    items
+}
+
+/// All annotations on one line, for constructs listed in a table or list.
+fn annotation_summary(annotations: &Annotations) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(ref description) = annotations.description {
+        parts.push(description.clone());
+    }
+    parts.extend(annotation_items(annotations));
+    (!parts.is_empty()).then(|| parts.join("; "))
+}
+
+fn code_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|i| format!("`{}`", i))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// ─── Markdown rendering ──────────────────────────────────────────────────────
+
+fn render_markdown(page: &Page) -> String {
+    let mut out = format!("# {}\n\n", page.title);
+    if page.stem != "index" {
+        out.push_str("[← Back to contract overview](index.md)\n\n");
+    }
+    for block in &page.blocks {
+        match block {
+            Block::Heading(level, text) => {
+                out.push_str(&format!("{} {}\n\n", "#".repeat(*level), text));
+            }
+            Block::Paragraph(text) => {
+                out.push_str(text);
+                out.push_str("\n\n");
+            }
+            Block::List(items) => {
+                for item in items {
+                    out.push_str(&format!("- {}\n", item));
+                }
+                out.push('\n');
+            }
+            Block::Table { headers, rows } => {
+                out.push_str(&format!("| {} |\n", headers.join(" | ")));
+                out.push_str(&format!(
+                    "|{}|\n",
+                    headers
+                        .iter()
+                        .map(|_| " --- ")
+                        .collect::<Vec<_>>()
+                        .join("|")
+                ));
+                for row in rows {
+                    let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+                    out.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                out.push('\n');
+            }
+            Block::Diagram(source) => {
+                out.push_str("```mermaid\n");
+                out.push_str(source);
+                out.push_str("```\n\n");
+            }
+            Block::Nav(links) => {
+                for (label, stem) in links {
+                    out.push_str(&format!("- [{}]({}.md)\n", label, stem));
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+// ─── HTML rendering ──────────────────────────────────────────────────────────
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;color:#222}\
+nav a{margin-right:1rem}table{border-collapse:collapse;width:100%;margin:1rem 0}\
+th,td{border:1px solid #ddd;padding:.4rem .6rem;text-align:left;vertical-align:top}\
+th{background:#f5f5f5}code{background:#f2f2f2;padding:0 .2rem;border-radius:3px}";
+
+fn render_html(page: &Page, bundle_id: &str, pages: &[Page]) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!(
+        "<title>{} — {}</title>\n",
+        escape_html(&page.title),
+        escape_html(bundle_id)
+    ));
+    out.push_str(&format!("<style>{}</style>\n", HTML_STYLE));
+    out.push_str("</head>\n<body>\n<nav>");
+    for p in pages {
+        out.push_str(&format!(
+            "<a href=\"{}.html\">{}</a>",
+            p.stem,
+            escape_html(&p.title)
+        ));
+    }
+    out.push_str("</nav>\n");
+    out.push_str(&format!("<h1>{}</h1>\n", inline_html(&page.title)));
+
+    let mut has_diagram = false;
+    for block in &page.blocks {
+        match block {
+            Block::Heading(level, text) => {
+                out.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inline_html(text)));
+            }
+            Block::Paragraph(text) => {
+                out.push_str(&format!("<p>{}</p>\n", inline_html(text)));
+            }
+            Block::List(items) => {
+                out.push_str("<ul>\n");
+                for item in items {
+                    out.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+                }
+                out.push_str("</ul>\n");
+            }
+            Block::Table { headers, rows } => {
+                out.push_str("<table>\n<tr>");
+                for h in headers {
+                    out.push_str(&format!("<th>{}</th>", escape_html(h)));
+                }
+                out.push_str("</tr>\n");
+                for row in rows {
+                    out.push_str("<tr>");
+                    for cell in row {
+                        out.push_str(&format!("<td>{}</td>", inline_html(cell)));
+                    }
+                    out.push_str("</tr>\n");
+                }
+                out.push_str("</table>\n");
+            }
+            Block::Diagram(source) => {
+                has_diagram = true;
+                out.push_str(&format!(
+                    "<pre class=\"mermaid\">\n{}</pre>\n",
+                    escape_html(source)
+                ));
+            }
+            Block::Nav(links) => {
+                out.push_str("<ul>\n");
+                for (label, stem) in links {
+                    out.push_str(&format!(
+                        "<li><a href=\"{}.html\">{}</a></li>\n",
+                        stem,
+                        escape_html(label)
+                    ));
+                }
+                out.push_str("</ul>\n");
+            }
+        }
+    }
+
+    if has_diagram {
+        out.push_str(
+            "<script type=\"module\">import mermaid from \
+             'https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs';\
+             mermaid.initialize({ startOnLoad: true });</script>\n",
+        );
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escape text and convert `backtick` spans into `<code>` elements.
+fn inline_html(s: &str) -> String {
+    let escaped = escape_html(s);
+    let mut out = String::new();
+    for (i, part) in escaped.split('`').enumerate() {
+        if i % 2 == 1 {
+            out.push_str(&format!("<code>{}</code>", part));
+        } else {
+            out.push_str(part);
+        }
+    }
+    out
+}
+
+// ─── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow_bundle() -> serde_json::Value {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../conformance/eval/positive/escrow_release.tenor");
+        tenor_core::elaborate::elaborate(&path).expect("elaborate fixture")
+    }
+
+    #[test]
+    fn builds_all_pages() {
+        let pages = build_pages(&escrow_bundle()).unwrap();
+        let stems: Vec<&str> = pages.iter().map(|p| p.stem).collect();
+        assert_eq!(
+            stems,
+            vec![
+                "index",
+                "facts",
+                "entities",
+                "rules",
+                "operations",
+                "flows",
+                "analysis"
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_entities_include_state_diagram() {
+        let pages = build_pages(&escrow_bundle()).unwrap();
+        let entities = pages.iter().find(|p| p.stem == "entities").unwrap();
+        let md = render_markdown(entities);
+        assert!(md.contains("```mermaid\nstateDiagram-v2\n"));
+        assert!(md.contains("[*] -->"));
+    }
+
+    #[test]
+    fn markdown_flows_include_s6_paths() {
+        let pages = build_pages(&escrow_bundle()).unwrap();
+        let flows = pages.iter().find(|p| p.stem == "flows").unwrap();
+        let md = render_markdown(flows);
+        assert!(md.contains("### Execution paths"));
+        assert!(md.contains("| # | Steps | Outcome |"));
+    }
+
+    #[test]
+    fn markdown_shows_construct_annotations() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../conformance/positive/construct_annotations.tenor");
+        let bundle = tenor_core::elaborate::elaborate(&path).expect("elaborate fixture");
+        let pages = build_pages(&bundle).unwrap();
+        let md = |stem: &str| render_markdown(pages.iter().find(|p| p.stem == stem).unwrap());
+        assert!(md("entities").contains("- Owner: payments-team\n- Tags: `payments`\n"));
+        assert!(md("operations").contains("## capture\n\nCapture an authorized payment\n"));
+        assert!(md("facts").contains("| Notes |"));
+    }
+
+    #[test]
+    fn html_escapes_and_converts_inline_code() {
+        assert_eq!(inline_html("a `<b>` c"), "a <code>&lt;b&gt;</code> c");
+    }
+
+    #[test]
+    fn generate_docs_writes_html_site() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = generate_docs(&escrow_bundle(), tmp.path(), DocsFormat::Html).unwrap();
+        for stem in [
+            "index",
+            "facts",
+            "entities",
+            "rules",
+            "operations",
+            "flows",
+            "analysis",
+        ] {
+            assert!(dir.join(format!("{}.html", stem)).exists(), "{}", stem);
+        }
+        let index = std::fs::read_to_string(dir.join("index.html")).unwrap();
+        assert!(index.contains("<nav>"));
+    }
+}
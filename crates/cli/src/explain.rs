@@ -54,10 +54,12 @@ pub fn explain(
             InterchangeConstruct::Rule(r) => rules.push(r),
             InterchangeConstruct::Operation(o) => operations.push(o),
             InterchangeConstruct::Flow(f) => flows.push(f),
-            // Source, TypeDecl, and System are not rendered in explain output
+            // Source, TypeDecl, System, Test, and Assert are not rendered in explain output
             InterchangeConstruct::Source(_)
             | InterchangeConstruct::TypeDecl(_)
-            | InterchangeConstruct::System(_) => {}
+            | InterchangeConstruct::System(_)
+            | InterchangeConstruct::Test(_)
+            | InterchangeConstruct::Assert(_) => {}
         }
     }
 
@@ -76,6 +78,7 @@ pub fn explain(
         &rules,
         &operations,
         &flows,
+        &bundle.constructs,
         verbose,
     );
 
@@ -105,6 +108,7 @@ fn section_contract_summary(
     rules: &[&RuleConstruct],
     operations: &[&OperationConstruct],
     flows: &[&FlowConstruct],
+    constructs: &[InterchangeConstruct],
     verbose: bool,
 ) {
     heading(out, format, "CONTRACT SUMMARY");
@@ -180,9 +184,41 @@ fn section_contract_summary(
         }
     }
 
+    emit_annotations(out, format, constructs);
+
     out.push('\n');
 }
 
+/// List annotated constructs with their owner, tags and description.
+fn emit_annotations(out: &mut String, format: ExplainFormat, constructs: &[InterchangeConstruct]) {
+    let annotated: Vec<&InterchangeConstruct> = constructs
+        .iter()
+        .filter(|c| !c.annotations().is_empty())
+        .collect();
+    if annotated.is_empty() {
+        return;
+    }
+    emit_line(out, format, "Annotated constructs:");
+    for c in annotated {
+        let annotations = c.annotations();
+        let mut parts = Vec::new();
+        if let Some(owner) = &annotations.owner {
+            parts.push(format!("owner {}", owner));
+        }
+        if !annotations.tags.is_empty() {
+            parts.push(format!("tags {}", annotations.tags.join(", ")));
+        }
+        let mut line = format!("  {} {}", c.kind(), styled_name(format, c.id()));
+        if !parts.is_empty() {
+            line.push_str(&format!(" ({})", parts.join("; ")));
+        }
+        if let Some(description) = &annotations.description {
+            line.push_str(&format!(": {}", description));
+        }
+        emit_line(out, format, &line);
+    }
+}
+
 fn count_strata(rules: &[&RuleConstruct]) -> usize {
     let mut strata = std::collections::BTreeSet::new();
     for r in rules {
@@ -410,16 +446,17 @@ fn describe_operation_step(
                 );
             }
             for eff in &op.effects {
+                let effect = match eff.kind.as_deref() {
+                    Some("create_instance") => format!("creates a new {}", eff.entity_id),
+                    Some("destroy_instance") => {
+                        format!("destroys {} in {}", eff.entity_id, eff.from)
+                    }
+                    _ => format!("{} transitions {} -> {}", eff.entity_id, eff.from, eff.to),
+                };
                 emit_line(
                     out,
                     format,
-                    &format!(
-                        "{}  Effect: {} transitions {} -> {}",
-                        indent(depth),
-                        eff.entity_id,
-                        eff.from,
-                        eff.to,
-                    ),
+                    &format!("{}  Effect: {}", indent(depth), effect),
                 );
             }
         }
@@ -635,7 +672,7 @@ fn describe_parallel_step(
     }
 }
 
-fn describe_condition(cond: &serde_json::Value) -> String {
+pub(crate) fn describe_condition(cond: &serde_json::Value) -> String {
     if let Some(vp) = cond.get("verdict_present").and_then(|v| v.as_str()) {
         return format!("verdict '{}' is present", vp);
     }
@@ -677,6 +714,13 @@ fn describe_expr(expr: Option<&serde_json::Value>) -> String {
             if let Some(fact_ref) = v.get("fact_ref").and_then(|v| v.as_str()) {
                 return fact_ref.to_string();
             }
+            if let Some(amount) = v.get("convert") {
+                return format!(
+                    "convert({}, {})",
+                    describe_expr(Some(amount)),
+                    describe_expr(v.get("rate"))
+                );
+            }
             if let Some(lit) = v.get("literal") {
                 if let Some(b) = lit.as_bool() {
                     return format!("{}", b);
@@ -845,7 +889,7 @@ fn categorize_fact_type(type_val: &serde_json::Value) -> &'static str {
     }
 }
 
-fn describe_fact_type(type_val: &serde_json::Value, verbose: bool) -> String {
+pub(crate) fn describe_fact_type(type_val: &serde_json::Value, verbose: bool) -> String {
     if type_val.is_null() {
         return "?".to_string();
     }
@@ -901,7 +945,7 @@ fn describe_fact_type(type_val: &serde_json::Value, verbose: bool) -> String {
     }
 }
 
-fn describe_source(source: &Option<serde_json::Value>) -> String {
+pub(crate) fn describe_source(source: &Option<serde_json::Value>) -> String {
     match source {
         None => "-".to_string(),
         Some(s) => {
@@ -912,7 +956,7 @@ fn describe_source(source: &Option<serde_json::Value>) -> String {
     }
 }
 
-fn describe_default(default: Option<&serde_json::Value>) -> String {
+pub(crate) fn describe_default(default: Option<&serde_json::Value>) -> String {
     match default {
         None => "-".to_string(),
         Some(default) => {
@@ -1229,6 +1273,26 @@ mod tests {
         assert!(output.contains("## FACT INVENTORY"));
     }
 
+    #[test]
+    fn summary_lists_annotated_constructs() {
+        let bundle_json = serde_json::json!({
+            "kind": "Bundle", "id": "test_contract", "tenor": "1.0",
+            "constructs": [
+                { "kind": "Persona", "id": "user",
+                  "provenance": { "file": "test.tenor", "line": 1 }, "tenor": "1.0" },
+                { "kind": "Persona", "id": "auditor",
+                  "annotations": { "owner": "risk-team", "description": "Reviews payouts",
+                                   "tags": ["payments", "pci"] },
+                  "provenance": { "file": "test.tenor", "line": 2 }, "tenor": "1.0" }
+            ]
+        });
+        let output = explain(&bundle_json, ExplainFormat::Markdown, false).unwrap();
+        assert!(output.contains(
+            "  Persona `auditor` (owner risk-team; tags payments, pci): Reviews payouts\n"
+        ));
+        assert!(!output.contains("Persona `user`"));
+    }
+
     #[test]
     fn deserialization_fails_on_wrong_type() {
         let bad_json = serde_json::json!({
@@ -0,0 +1,381 @@
+//! `tenor lint` -- style and best-practice rules for contracts.
+//!
+//! Lint rules are advisory and separate from the S1-S8 static analyses:
+//! a contract that fails lint is still well-formed. Each rule can be
+//! switched off in the `[lint.rules]` table of the project's `tenor.toml`.
+//!
+//! ```toml
+//! [lint]
+//! max_predicate_depth = 6
+//!
+//! [lint.rules]
+//! operation-error-contract = false
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde_json::Value;
+use tenor_core::project::LintSection;
+use tenor_interchange::{InterchangeConstruct, Provenance};
+
+/// Default for `[lint] max_predicate_depth`.
+const DEFAULT_MAX_PREDICATE_DEPTH: usize = 5;
+
+/// Every lint rule, with a one-line description for `--list-rules`.
+pub const RULES: &[(&str, &str)] = &[
+    (
+        "verdict-type-naming",
+        "verdict types are snake_case (lowercase letters, digits, underscores)",
+    ),
+    (
+        "persona-unused-in-flows",
+        "personas allowed on an operation are used by at least one flow step",
+    ),
+    (
+        "operation-error-contract",
+        "operations declare an error_contract",
+    ),
+    (
+        "predicate-depth",
+        "rule and precondition predicates nest no deeper than max_predicate_depth",
+    ),
+];
+
+/// Lint settings, from the `[lint]` table of `tenor.toml`.
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    pub max_predicate_depth: usize,
+    /// Rule name -> enabled. Rules not listed are enabled.
+    pub rules: BTreeMap<String, bool>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            max_predicate_depth: DEFAULT_MAX_PREDICATE_DEPTH,
+            rules: BTreeMap::new(),
+        }
+    }
+}
+
+impl LintConfig {
+    /// Build the config from a project's `[lint]` table, rejecting unknown
+    /// rule names.
+    pub fn from_section(section: &LintSection) -> Result<Self, String> {
+        for name in section.rules.keys() {
+            if !RULES.iter().any(|(rule, _)| rule == name) {
+                return Err(format!("unknown lint rule '{}' in tenor.toml", name));
+            }
+        }
+        Ok(LintConfig {
+            max_predicate_depth: section
+                .max_predicate_depth
+                .unwrap_or(DEFAULT_MAX_PREDICATE_DEPTH),
+            rules: section.rules.clone(),
+        })
+    }
+
+    fn enabled(&self, rule: &str) -> bool {
+        self.rules.get(rule).copied().unwrap_or(true)
+    }
+}
+
+/// One lint finding.
+#[derive(Debug, Clone, Serialize)]
+pub struct LintFinding {
+    pub rule: &'static str,
+    pub construct_kind: &'static str,
+    pub construct_id: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+}
+
+impl LintFinding {
+    fn new(
+        rule: &'static str,
+        construct_kind: &'static str,
+        construct_id: &str,
+        provenance: Option<&Provenance>,
+        message: String,
+    ) -> Self {
+        LintFinding {
+            rule,
+            construct_kind,
+            construct_id: construct_id.to_string(),
+            message,
+            file: provenance.map(|p| p.file.clone()),
+            line: provenance.map(|p| p.line),
+        }
+    }
+}
+
+/// Run every enabled rule over an interchange bundle.
+///
+/// Findings are ordered by file, line, then rule.
+pub fn lint(bundle: &Value, config: &LintConfig) -> Result<Vec<LintFinding>, String> {
+    let parsed = tenor_interchange::from_interchange(bundle).map_err(|e| e.to_string())?;
+    let mut findings = Vec::new();
+
+    if config.enabled("verdict-type-naming") {
+        for c in &parsed.constructs {
+            let InterchangeConstruct::Rule(r) = c else {
+                continue;
+            };
+            let Some(verdict) = r
+                .body
+                .get("produce")
+                .and_then(|p| p.get("verdict_type"))
+                .and_then(|v| v.as_str())
+            else {
+                continue;
+            };
+            if !is_snake_case(verdict) {
+                findings.push(LintFinding::new(
+                    "verdict-type-naming",
+                    "Rule",
+                    &r.id,
+                    r.provenance.as_ref(),
+                    format!("verdict type '{}' is not snake_case", verdict),
+                ));
+            }
+        }
+    }
+
+    if config.enabled("persona-unused-in-flows") {
+        let mut flow_personas: BTreeSet<String> = BTreeSet::new();
+        let mut has_flows = false;
+        for c in &parsed.constructs {
+            if let InterchangeConstruct::Flow(f) = c {
+                has_flows = true;
+                for step in &f.steps {
+                    collect_step_personas(step, &mut flow_personas);
+                }
+            }
+        }
+        // A contract without flows drives its operations directly
+        if has_flows {
+            let mut reported: BTreeSet<&str> = BTreeSet::new();
+            for c in &parsed.constructs {
+                let InterchangeConstruct::Operation(op) = c else {
+                    continue;
+                };
+                for persona in &op.allowed_personas {
+                    if !flow_personas.contains(persona) && reported.insert(persona) {
+                        findings.push(LintFinding::new(
+                            "persona-unused-in-flows",
+                            "Operation",
+                            &op.id,
+                            op.provenance.as_ref(),
+                            format!(
+                                "persona '{}' is allowed on operation '{}' but no flow step uses it",
+                                persona, op.id
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if config.enabled("operation-error-contract") {
+        for c in &parsed.constructs {
+            let InterchangeConstruct::Operation(op) = c else {
+                continue;
+            };
+            let declared = match &op.error_contract {
+                Some(Value::Array(labels)) => !labels.is_empty(),
+                Some(Value::Null) | None => false,
+                Some(_) => true,
+            };
+            if !declared {
+                findings.push(LintFinding::new(
+                    "operation-error-contract",
+                    "Operation",
+                    &op.id,
+                    op.provenance.as_ref(),
+                    format!("operation '{}' declares no error_contract", op.id),
+                ));
+            }
+        }
+    }
+
+    if config.enabled("predicate-depth") {
+        let report = tenor_analyze::analyze_selected(bundle, &["s7"]).map_err(|e| e.to_string())?;
+        let depths: BTreeMap<&str, usize> = report
+            .s7_complexity
+            .as_ref()
+            .map(|s7| {
+                s7.predicate_complexities
+                    .iter()
+                    .map(|p| (p.source_id.as_str(), p.max_depth))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for c in &parsed.constructs {
+            let (kind, id, provenance) = match c {
+                InterchangeConstruct::Rule(r) => ("Rule", &r.id, r.provenance.as_ref()),
+                InterchangeConstruct::Operation(o) => ("Operation", &o.id, o.provenance.as_ref()),
+                _ => continue,
+            };
+            if let Some(&depth) = depths.get(id.as_str()) {
+                if depth > config.max_predicate_depth {
+                    findings.push(LintFinding::new(
+                        "predicate-depth",
+                        kind,
+                        id,
+                        provenance,
+                        format!(
+                            "predicate nests {} levels deep (max {})",
+                            depth, config.max_predicate_depth
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    findings.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then(a.line.cmp(&b.line))
+            .then(a.rule.cmp(b.rule))
+            .then(a.message.cmp(&b.message))
+    });
+    Ok(findings)
+}
+
+fn is_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Personas named anywhere in a flow step, including parallel branches and
+/// escalation handlers.
+fn collect_step_personas(value: &Value, out: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(obj) => {
+            for (key, v) in obj {
+                match (key.as_str(), v) {
+                    ("persona" | "to_persona", Value::String(p)) => {
+                        out.insert(p.clone());
+                    }
+                    _ => collect_step_personas(v, out),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for v in items {
+                collect_step_personas(v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle(src: &str) -> Value {
+        tenor_core::elaborate::elaborate_with_provider(
+            std::path::Path::new("lint.tenor"),
+            &tenor_core::InMemoryProvider::new(
+                [(std::path::PathBuf::from("lint.tenor"), src.to_string())]
+                    .into_iter()
+                    .collect(),
+            ),
+        )
+        .expect("elaborates")
+    }
+
+    const CONTRACT: &str = r#"
+persona clerk
+persona auditor
+
+fact amount {
+  type:   Int(min: 0, max: 1000)
+  source: "ledger.amount"
+}
+
+entity Claim {
+  states:  [open, paid]
+  initial: open
+  transitions: [(open, paid)]
+}
+
+rule small {
+  stratum: 0
+  when:    amount < 100
+  produce: verdict SmallClaim { payload: Bool = true }
+}
+
+operation pay {
+  allowed_personas: [clerk, auditor]
+  precondition:     verdict_present(SmallClaim)
+  effects:          [(Claim, open, paid)]
+}
+
+flow settle {
+  snapshot: at_initiation
+  entry:    step_pay
+
+  steps: {
+    step_pay: OperationStep {
+      op:      pay
+      persona: clerk
+      outcomes: {
+        success: Terminal(success)
+      }
+      on_failure: Terminate(outcome: failure)
+    }
+  }
+}
+"#;
+
+    #[test]
+    fn reports_each_rule() {
+        let findings = lint(&bundle(CONTRACT), &LintConfig::default()).unwrap();
+        let rules: BTreeSet<&str> = findings.iter().map(|f| f.rule).collect();
+        assert!(rules.contains("verdict-type-naming"), "{:?}", findings);
+        assert!(rules.contains("persona-unused-in-flows"), "{:?}", findings);
+        assert!(rules.contains("operation-error-contract"), "{:?}", findings);
+        let persona = findings
+            .iter()
+            .find(|f| f.rule == "persona-unused-in-flows")
+            .unwrap();
+        assert!(persona.message.contains("'auditor'"), "{}", persona.message);
+    }
+
+    #[test]
+    fn disabled_rules_are_skipped() {
+        let mut config = LintConfig::default();
+        config
+            .rules
+            .insert("verdict-type-naming".to_string(), false);
+        let findings = lint(&bundle(CONTRACT), &config).unwrap();
+        assert!(findings.iter().all(|f| f.rule != "verdict-type-naming"));
+    }
+
+    #[test]
+    fn predicate_depth_uses_configured_limit() {
+        let config = LintConfig {
+            max_predicate_depth: 0,
+            rules: BTreeMap::new(),
+        };
+        let findings = lint(&bundle(CONTRACT), &config).unwrap();
+        assert!(findings.iter().any(|f| f.rule == "predicate-depth"));
+    }
+
+    #[test]
+    fn snake_case() {
+        assert!(is_snake_case("small_claim_2"));
+        assert!(!is_snake_case("SmallClaim"));
+        assert!(!is_snake_case("_small"));
+    }
+}
@@ -0,0 +1,486 @@
+//! LLM backends for the commands that call a model (`tenor connect` and
+//! `tenor ambiguity`).
+//!
+//! The provider is chosen by `TENOR_LLM_PROVIDER`, else `provider` in the
+//! `[llm]` section of `tenor.toml`, else Anthropic when `ANTHROPIC_API_KEY`
+//! is set:
+//!
+//! - `anthropic`: the Anthropic Messages API (`ANTHROPIC_API_KEY`).
+//! - `openai`: any OpenAI-compatible Chat Completions API — OpenAI itself,
+//!   or a self-hosted server such as vLLM or llama.cpp. `OPENAI_API_KEY`
+//!   is sent when set.
+//! - `ollama`: a local Ollama server (`OLLAMA_HOST`, default
+//!   `http://localhost:11434`). No key.
+//!
+//! `TENOR_LLM_BASE_URL` and `TENOR_LLM_MODEL` (or `base_url` and `model`
+//! in `[llm]`) point a provider at another endpoint or model, so
+//! air-gapped installations can use a model on their own network.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tenor_core::project::LlmSection;
+
+/// Anthropic API version header value.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Maximum tokens requested per completion.
+const MAX_TOKENS: u32 = 4096;
+
+/// An LLM API flavour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Provider {
+    Anthropic,
+    OpenAi,
+    Ollama,
+}
+
+impl Provider {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "anthropic" => Ok(Provider::Anthropic),
+            "openai" => Ok(Provider::OpenAi),
+            "ollama" => Ok(Provider::Ollama),
+            other => Err(format!(
+                "unknown LLM provider '{}': expected anthropic, openai or ollama",
+                other
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "anthropic",
+            Provider::OpenAi => "openai",
+            Provider::Ollama => "ollama",
+        }
+    }
+}
+
+/// A configured LLM backend.
+#[derive(Debug, Clone)]
+pub(crate) struct LlmBackend {
+    provider: Provider,
+    base_url: String,
+    api_key: Option<String>,
+    /// Configured model, if any; commands fall back to their own default.
+    model: Option<String>,
+    /// Sampling seed sent to providers that accept one (OpenAI, Ollama).
+    seed: Option<u64>,
+}
+
+/// A model reply and the tokens it cost, when the provider reports them.
+#[derive(Debug, Clone)]
+pub(crate) struct Completion {
+    pub(crate) text: String,
+    pub(crate) usage: Option<TokenUsage>,
+}
+
+/// Prompt and completion token counts for one or more calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TokenUsage {
+    pub(crate) input_tokens: u64,
+    pub(crate) output_tokens: u64,
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
+}
+
+impl LlmBackend {
+    /// The backend configured by the environment and the project's `[llm]`
+    /// section, or `None` when no provider is set and there is no
+    /// `ANTHROPIC_API_KEY`.
+    pub(crate) fn from_env() -> Result<Option<Self>, String> {
+        Self::configure(|name| std::env::var(name).ok(), &crate::project().llm)
+    }
+
+    /// [`LlmBackend::from_env`] with an explicit variable lookup.
+    fn configure(
+        var: impl Fn(&str) -> Option<String>,
+        config: &LlmSection,
+    ) -> Result<Option<Self>, String> {
+        let var = |name: &str| var(name).filter(|v| !v.is_empty());
+        let provider = match var("TENOR_LLM_PROVIDER").or_else(|| config.provider.clone()) {
+            Some(name) => Provider::parse(&name)?,
+            None if var("ANTHROPIC_API_KEY").is_some() => Provider::Anthropic,
+            None => return Ok(None),
+        };
+
+        let api_key = match provider {
+            Provider::Anthropic => Some(var("ANTHROPIC_API_KEY").ok_or(
+                "ANTHROPIC_API_KEY environment variable is not set; \
+                 it is required by the anthropic LLM provider",
+            )?),
+            Provider::OpenAi => var("OPENAI_API_KEY"),
+            Provider::Ollama => None,
+        };
+        let base_url = var("TENOR_LLM_BASE_URL")
+            .or_else(|| config.base_url.clone())
+            .or_else(|| match provider {
+                Provider::Ollama => var("OLLAMA_HOST"),
+                _ => None,
+            })
+            .unwrap_or_else(|| {
+                match provider {
+                    Provider::Anthropic => "https://api.anthropic.com",
+                    Provider::OpenAi => "https://api.openai.com/v1",
+                    Provider::Ollama => "http://localhost:11434",
+                }
+                .to_string()
+            });
+
+        Ok(Some(LlmBackend {
+            provider,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model: var("TENOR_LLM_MODEL").or_else(|| config.model.clone()),
+            seed: None,
+        }))
+    }
+
+    /// Ask for deterministic sampling with `seed` where the provider
+    /// supports it. Anthropic has no seed parameter and ignores it.
+    pub(crate) fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The model to call: `flag` if given, else the configured model, else
+    /// the provider's default (`anthropic_default` for Anthropic).
+    pub(crate) fn model<'a>(
+        &'a self,
+        flag: Option<&'a str>,
+        anthropic_default: &'a str,
+    ) -> &'a str {
+        flag.or(self.model.as_deref())
+            .unwrap_or(match self.provider {
+                Provider::Anthropic => anthropic_default,
+                Provider::OpenAi => "gpt-4o-mini",
+                Provider::Ollama => "llama3.1",
+            })
+    }
+
+    /// `provider at base_url`, for progress messages.
+    pub(crate) fn describe(&self) -> String {
+        format!("{} at {}", self.provider.name(), self.base_url)
+    }
+
+    /// Send a system and a user prompt; return the model's reply.
+    pub(crate) fn complete(
+        &self,
+        model: &str,
+        system: &str,
+        user: &str,
+    ) -> Result<Completion, String> {
+        let (url, body) = self.request(model, system, user);
+        let agent = ureq::Agent::new_with_defaults();
+        let mut request = agent.post(&url).header("content-type", "application/json");
+        match (self.provider, &self.api_key) {
+            (Provider::Anthropic, Some(key)) => {
+                request = request
+                    .header("x-api-key", key)
+                    .header("anthropic-version", ANTHROPIC_VERSION);
+            }
+            (Provider::OpenAi, Some(key)) => {
+                request = request.header("authorization", &format!("Bearer {}", key));
+            }
+            _ => {}
+        }
+
+        let response: Value = request
+            .send_json(&body)
+            .map_err(|e| format!("API request failed: {}", e))?
+            .into_body()
+            .read_json()
+            .map_err(|e| format!("Failed to parse API response: {}", e))?;
+        let text = self
+            .response_text(&response)
+            .ok_or_else(|| "API response contained no text content".to_string())?;
+        Ok(Completion {
+            text,
+            usage: self.response_usage(&response),
+        })
+    }
+
+    /// The endpoint and JSON body of a completion request.
+    fn request(&self, model: &str, system: &str, user: &str) -> (String, Value) {
+        let (url, mut body) = match self.provider {
+            Provider::Anthropic => (
+                format!("{}/v1/messages", self.base_url),
+                json!({
+                    "model": model,
+                    "max_tokens": MAX_TOKENS,
+                    "system": system,
+                    "messages": [{ "role": "user", "content": user }],
+                }),
+            ),
+            Provider::OpenAi => (
+                format!("{}/chat/completions", self.base_url),
+                json!({
+                    "model": model,
+                    "max_tokens": MAX_TOKENS,
+                    "messages": [
+                        { "role": "system", "content": system },
+                        { "role": "user", "content": user },
+                    ],
+                }),
+            ),
+            Provider::Ollama => (
+                format!("{}/api/chat", self.base_url),
+                json!({
+                    "model": model,
+                    "stream": false,
+                    "messages": [
+                        { "role": "system", "content": system },
+                        { "role": "user", "content": user },
+                    ],
+                }),
+            ),
+        };
+        match (self.provider, self.seed) {
+            (Provider::OpenAi, Some(seed)) => body["seed"] = json!(seed),
+            (Provider::Ollama, Some(seed)) => body["options"] = json!({ "seed": seed }),
+            _ => {}
+        }
+        (url, body)
+    }
+
+    /// The reply text in a provider's response.
+    fn response_text(&self, response: &Value) -> Option<String> {
+        let text = match self.provider {
+            Provider::Anthropic => &response["content"][0]["text"],
+            Provider::OpenAi => &response["choices"][0]["message"]["content"],
+            Provider::Ollama => &response["message"]["content"],
+        };
+        text.as_str().map(str::to_string)
+    }
+
+    /// The token counts in a provider's response.
+    fn response_usage(&self, response: &Value) -> Option<TokenUsage> {
+        let (input, output) = match self.provider {
+            Provider::Anthropic => (
+                &response["usage"]["input_tokens"],
+                &response["usage"]["output_tokens"],
+            ),
+            Provider::OpenAi => (
+                &response["usage"]["prompt_tokens"],
+                &response["usage"]["completion_tokens"],
+            ),
+            Provider::Ollama => (&response["prompt_eval_count"], &response["eval_count"]),
+        };
+        Some(TokenUsage {
+            input_tokens: input.as_u64()?,
+            output_tokens: output.as_u64()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    fn configure(vars: &[(&str, &str)], config: &LlmSection) -> Result<Option<LlmBackend>, String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        LlmBackend::configure(|name| vars.get(name).cloned(), config)
+    }
+
+    #[test]
+    fn no_provider_and_no_key_is_unconfigured() {
+        assert!(configure(&[], &LlmSection::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn anthropic_key_alone_selects_anthropic() {
+        let backend = configure(&[("ANTHROPIC_API_KEY", "k")], &LlmSection::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(backend.provider, Provider::Anthropic);
+        assert_eq!(backend.base_url, "https://api.anthropic.com");
+        assert_eq!(backend.model(None, "claude-x"), "claude-x");
+    }
+
+    #[test]
+    fn environment_overrides_project_config() {
+        let config = LlmSection {
+            provider: Some("openai".to_string()),
+            base_url: Some("http://llm.internal/v1/".to_string()),
+            model: Some("qwen2.5".to_string()),
+        };
+        let backend = configure(&[], &config).unwrap().unwrap();
+        assert_eq!(backend.provider, Provider::OpenAi);
+        assert_eq!(backend.base_url, "http://llm.internal/v1");
+        assert_eq!(backend.api_key, None);
+        assert_eq!(backend.model(None, "claude-x"), "qwen2.5");
+        assert_eq!(backend.model(Some("flag"), "claude-x"), "flag");
+
+        let backend = configure(
+            &[
+                ("TENOR_LLM_PROVIDER", "ollama"),
+                ("OLLAMA_HOST", "http://gpu:11434"),
+                ("TENOR_LLM_MODEL", "mistral"),
+            ],
+            &LlmSection::default(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(backend.provider, Provider::Ollama);
+        assert_eq!(backend.base_url, "http://gpu:11434");
+        assert_eq!(backend.model(None, "claude-x"), "mistral");
+    }
+
+    #[test]
+    fn configuration_errors() {
+        let err = configure(&[("TENOR_LLM_PROVIDER", "bard")], &LlmSection::default()).unwrap_err();
+        assert!(err.contains("unknown LLM provider 'bard'"), "{}", err);
+
+        let err = configure(
+            &[("TENOR_LLM_PROVIDER", "anthropic")],
+            &LlmSection::default(),
+        )
+        .unwrap_err();
+        assert!(err.contains("ANTHROPIC_API_KEY"), "{}", err);
+    }
+
+    #[test]
+    fn requests_and_responses_per_provider() {
+        let backend = |provider| LlmBackend {
+            provider,
+            base_url: "http://h".to_string(),
+            api_key: None,
+            model: None,
+            seed: None,
+        };
+
+        let (url, body) = backend(Provider::Anthropic).request("m", "sys", "hi");
+        assert_eq!(url, "http://h/v1/messages");
+        assert_eq!(body["system"], "sys");
+        let text = backend(Provider::Anthropic)
+            .response_text(&json!({ "content": [{ "type": "text", "text": "ok" }] }));
+        assert_eq!(text.as_deref(), Some("ok"));
+
+        let (url, body) = backend(Provider::OpenAi).request("m", "sys", "hi");
+        assert_eq!(url, "http://h/chat/completions");
+        assert_eq!(body["messages"][0]["role"], "system");
+        let text = backend(Provider::OpenAi)
+            .response_text(&json!({ "choices": [{ "message": { "content": "ok" } }] }));
+        assert_eq!(text.as_deref(), Some("ok"));
+
+        let (url, body) = backend(Provider::Ollama).request("m", "sys", "hi");
+        assert_eq!(url, "http://h/api/chat");
+        assert_eq!(body["stream"], false);
+        let text =
+            backend(Provider::Ollama).response_text(&json!({ "message": { "content": "ok" } }));
+        assert_eq!(text.as_deref(), Some("ok"));
+    }
+
+    #[test]
+    fn seed_and_token_usage_per_provider() {
+        let backend = |provider| LlmBackend {
+            provider,
+            base_url: "http://h".to_string(),
+            api_key: None,
+            model: None,
+            seed: Some(7),
+        };
+
+        assert_eq!(
+            backend(Provider::OpenAi).request("m", "s", "u").1["seed"],
+            7
+        );
+        assert_eq!(
+            backend(Provider::Ollama).request("m", "s", "u").1["options"]["seed"],
+            7
+        );
+        assert!(backend(Provider::Anthropic).request("m", "s", "u").1["seed"].is_null());
+
+        let usage = |input, output| {
+            Some(TokenUsage {
+                input_tokens: input,
+                output_tokens: output,
+            })
+        };
+        assert_eq!(
+            backend(Provider::Anthropic)
+                .response_usage(&json!({ "usage": { "input_tokens": 10, "output_tokens": 2 } })),
+            usage(10, 2)
+        );
+        assert_eq!(
+            backend(Provider::OpenAi).response_usage(
+                &json!({ "usage": { "prompt_tokens": 11, "completion_tokens": 3 } })
+            ),
+            usage(11, 3)
+        );
+        assert_eq!(
+            backend(Provider::Ollama)
+                .response_usage(&json!({ "prompt_eval_count": 12, "eval_count": 4 })),
+            usage(12, 4)
+        );
+        assert_eq!(backend(Provider::Ollama).response_usage(&json!({})), None);
+    }
+
+    #[test]
+    fn completes_against_a_local_server() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            let mut authorization = None;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                let (name, value) = line.split_once(':').unwrap();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => content_length = value.trim().parse().unwrap(),
+                    "authorization" => authorization = Some(value.trim().to_string()),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let reply = r#"{"choices":[{"message":{"role":"assistant","content":"pong"}}]}"#;
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                reply.len(),
+                reply
+            )
+            .unwrap();
+            (request_line, authorization, body)
+        });
+
+        let backend = LlmBackend {
+            provider: Provider::OpenAi,
+            base_url: format!("http://{}/v1", addr),
+            api_key: Some("secret".to_string()),
+            model: None,
+            seed: None,
+        };
+        let completion = backend.complete("local-model", "sys", "ping").unwrap();
+        assert_eq!(completion.text, "pong");
+        assert_eq!(completion.usage, None);
+
+        let (request_line, authorization, body) = server.join().unwrap();
+        assert!(request_line.starts_with("POST /v1/chat/completions "));
+        assert_eq!(authorization.as_deref(), Some("Bearer secret"));
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["model"], "local-model");
+        assert_eq!(body["messages"][1]["content"], "ping");
+    }
+}
@@ -3,19 +3,31 @@ mod ambiguity;
 mod builder;
 mod commands;
 mod connect;
+mod decompile;
 mod diff;
+mod docs;
 mod explain;
+mod lint;
+mod llm;
 mod manifest;
+mod mcp;
 mod migrate;
+mod optimize;
+mod otlp;
+mod random_walk;
+mod repl;
 mod runner;
+mod scaffold;
 mod serve;
 mod tap;
 mod template;
 mod trust;
 mod ui;
+mod unit_test;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::OnceLock;
 
 use clap::{Parser, Subcommand, ValueEnum};
 
@@ -33,6 +45,21 @@ pub(crate) enum ExplainOutputFormat {
     Markdown,
 }
 
+/// Output format for the conformance subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ConformanceOutputFormat {
+    Tap,
+    Json,
+    Junit,
+}
+
+/// Format of the `check --admissibility-matrix` export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum MatrixFormat {
+    Csv,
+    Json,
+}
+
 /// Tenor contract language toolchain.
 #[derive(Parser)]
 #[command(name = "tenor", version, about = "Tenor contract language toolchain")]
@@ -58,6 +85,40 @@ enum Commands {
         /// Produce a TenorManifest instead of a bare interchange bundle
         #[arg(long)]
         manifest: bool,
+        /// Comma-separated features enabling `when feature("...")` constructs
+        #[arg(long, value_delimiter = ',')]
+        features: Vec<String>,
+        /// Write a source map from the bundle's constructs and predicates
+        /// back to their source byte ranges to this file
+        #[arg(long)]
+        source_map: Option<PathBuf>,
+    },
+
+    /// Scaffold a starter contract project, prompting for construct names
+    New {
+        /// Directory to create the project in
+        dir: PathBuf,
+        /// Contract name (default: the directory name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Entity moved through the starter flow
+        #[arg(long)]
+        entity: Option<String>,
+        /// Persona that submits the entity
+        #[arg(long)]
+        requester: Option<String>,
+        /// Persona that approves the entity
+        #[arg(long)]
+        approver: Option<String>,
+        /// Use defaults for names not given instead of prompting
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Print a shell completion script (bash, zsh, fish, elvish, powershell)
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
     },
 
     /// Validate interchange JSON against the formal JSON Schema
@@ -70,22 +131,145 @@ enum Commands {
     Eval {
         /// Path to the interchange JSON bundle file
         bundle: PathBuf,
-        /// Path to the facts JSON file
-        #[arg(long)]
-        facts: PathBuf,
+        /// Facts JSON file, or - for stdin; repeat to merge (later files win)
+        #[arg(long, value_name = "FILE", required_unless_present_any = ["compare_facts", "fact"])]
+        facts: Vec<PathBuf>,
+        /// Set one fact, coerced to its declared type (repeatable; applied after --facts)
+        #[arg(long, value_name = "ID=VALUE")]
+        fact: Vec<String>,
+        /// Evaluate against two facts files and report how the verdicts differ
+        #[arg(
+            long,
+            num_args = 2,
+            value_names = ["OLD", "NEW"],
+            conflicts_with_all = ["facts", "fact", "validate_facts", "flow", "partial", "as_of", "trace"]
+        )]
+        compare_facts: Option<Vec<PathBuf>>,
+        /// Only check the facts against the contract, reporting every error
+        #[arg(long, conflicts_with_all = ["flow", "partial", "as_of", "trace"])]
+        validate_facts: bool,
         /// Flow ID to execute (enables flow evaluation mode)
         #[arg(long)]
         flow: Option<String>,
         /// Persona executing the flow (required when --flow is specified)
         #[arg(long)]
         persona: Option<String>,
+        /// Write an OTLP/JSON trace of the flow execution to this file
+        #[arg(long, requires = "flow")]
+        export_trace: Option<PathBuf>,
+        /// Print per-rule and per-step timings (hot-spot table) to stderr
+        #[arg(long)]
+        profile: bool,
+        /// Trace each rule's condition: the value of every predicate subexpression
+        #[arg(long, conflicts_with_all = ["flow", "partial", "as_of"])]
+        trace: bool,
+        /// Treat missing facts as unknown and report which verdicts they block
+        #[arg(long, conflicts_with = "flow")]
+        partial: bool,
+        /// Evaluate against entity states as of this RFC 3339 timestamp (requires --history)
+        #[arg(long, requires = "history", conflicts_with = "partial")]
+        as_of: Option<String>,
+        /// Entity history JSON exported from storage: {"entity_states": [...], "transitions": [...]}
+        #[arg(long, requires = "as_of")]
+        history: Option<PathBuf>,
+        /// Evaluate as if the current time were this RFC 3339 timestamp (step deadlines)
+        #[arg(long, value_name = "TIMESTAMP", value_parser = tenor_eval::FixedClock::parse)]
+        now: Option<tenor_eval::FixedClock>,
     },
 
-    /// Run the conformance test suite
+    /// Simulate a flow run without persisting it, or sample paths with --random-walk
+    Simulate {
+        /// Path to the interchange JSON bundle file
+        bundle: PathBuf,
+        /// Sample paths by random walk, synthesizing facts for each walk
+        #[arg(long, required_unless_present = "facts", conflicts_with = "facts")]
+        random_walk: bool,
+        /// Seed for the random walk; the same seed yields the same walks
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Number of walks per flow
+        #[arg(long, default_value_t = 100)]
+        count: usize,
+        /// Flow to simulate (with --random-walk: only sample this flow)
+        #[arg(long)]
+        flow: Option<String>,
+        /// Persona executing the simulated flow
+        #[arg(long, requires = "facts")]
+        persona: Option<String>,
+        /// Path to the facts JSON file; simulates a single run of --flow
+        #[arg(long, requires_all = ["flow", "persona"])]
+        facts: Option<PathBuf>,
+        /// Entity states JSON: {"Entity": "state"} or {"Entity": {"instance": "state"}}
+        #[arg(long, requires = "facts")]
+        entity_states: Option<PathBuf>,
+        /// Instance bindings JSON: {"Entity": "instance"}
+        #[arg(long, requires = "facts")]
+        bindings: Option<PathBuf>,
+        /// Simulate as if the current time were this RFC 3339 timestamp (step deadlines)
+        #[arg(long, value_name = "TIMESTAMP", value_parser = tenor_eval::FixedClock::parse, requires = "facts")]
+        now: Option<tenor_eval::FixedClock>,
+    },
+
+    /// Show the flows a persona can start, and why others are blocked
+    Actions {
+        /// Path to .tenor source file or interchange JSON bundle
+        bundle: PathBuf,
+        /// Path to the facts JSON file
+        #[arg(long)]
+        facts: PathBuf,
+        /// Entity states JSON: {"Entity": "state"} or {"Entity": {"instance": "state"}}
+        #[arg(long)]
+        entity_states: Option<PathBuf>,
+        /// Persona whose action space to compute
+        #[arg(long)]
+        persona: String,
+    },
+
+    /// Search for facts that drive a flow to a given terminal outcome
+    Solve {
+        /// Path to the interchange JSON bundle file
+        bundle: PathBuf,
+        /// Flow to solve for
+        #[arg(long)]
+        flow: String,
+        /// Target terminal outcome, e.g. success
+        #[arg(long)]
+        outcome: String,
+        /// Persona initiating the flow (default: the entry step's persona)
+        #[arg(long)]
+        persona: Option<String>,
+        /// Entity states JSON ({"Entity": "state"}) overriding the initial states
+        #[arg(long)]
+        entity_states: Option<PathBuf>,
+        /// Maximum number of fact assignments to execute
+        #[arg(long, default_value_t = 100_000)]
+        max_assignments: usize,
+    },
+
+    /// Run the conformance test suite, or a contract's `test` blocks with --unit
     Test {
-        /// Path to the conformance suite directory
+        /// Path to the conformance suite directory (with --unit: a .tenor file or bundle)
         #[arg(default_value = "conformance")]
         suite_dir: PathBuf,
+        /// Run the rule-level `test` constructs of a contract instead of the suite
+        #[arg(long)]
+        unit: bool,
+        /// Run only the suite cases whose name (e.g. `negative/pass3/cycle`) matches this glob
+        #[arg(long, conflicts_with = "unit")]
+        filter: Option<String>,
+    },
+
+    /// Run the executor conformance suite (E1-E20) against a deployed executor
+    Conformance {
+        /// Base URL of the executor's HTTP API, e.g. http://localhost:8080
+        #[arg(long)]
+        url: String,
+        /// API key sent as a bearer token (default: TENOR_API_KEY)
+        #[arg(long)]
+        api_key: Option<String>,
+        /// Report format (tap, json or junit)
+        #[arg(long, default_value = "tap")]
+        format: ConformanceOutputFormat,
     },
 
     /// Diff two interchange JSON bundles for structural changes
@@ -105,18 +289,53 @@ enum Commands {
         v1: PathBuf,
         /// Path to the v2 contract (.tenor or .json)
         v2: PathBuf,
-        /// Skip confirmation prompt
+        /// Skip confirmation prompts, accepting suggested state mappings
         #[arg(long)]
         yes: bool,
+        /// TOML file mapping old entity states to new ones ([Entity] old = "new")
+        #[arg(long)]
+        mapping: Option<PathBuf>,
+        /// Write the executable migration artifact (JSON) to this path
+        #[arg(long)]
+        out: Option<PathBuf>,
     },
 
     /// Run static analysis checks on a .tenor file
     Check {
         /// Path to the .tenor source file
-        file: PathBuf,
+        #[arg(required_unless_present = "system")]
+        file: Option<PathBuf>,
         /// Comma-separated list of analyses to run (s1,s2,s3a,s4,s5,s6,s7,s8). Default: all.
-        #[arg(long)]
+        #[arg(long, conflicts_with = "system")]
         analysis: Option<String>,
+        /// Combined entity state count above which S1 warns of a product state space explosion (default: 1000000)
+        #[arg(long, conflicts_with = "system")]
+        state_space_limit: Option<u64>,
+        /// JSON file of temporal properties to check over flow paths (S12)
+        #[arg(long, conflicts_with = "system")]
+        properties: Option<PathBuf>,
+        /// Report only findings about constructs annotated with @tag(TAG)
+        #[arg(long, value_name = "TAG", conflicts_with = "system")]
+        only_tag: Option<String>,
+        /// Print the persona x operation x entity-state admissibility matrix instead of the report
+        #[arg(long, value_enum, conflicts_with_all = ["system", "analysis"])]
+        admissibility_matrix: Option<MatrixFormat>,
+        /// Analyze a System file together with all of its member contracts
+        #[arg(long, conflicts_with = "file")]
+        system: Option<PathBuf>,
+    },
+
+    /// Check a contract against style and best-practice lint rules
+    Lint {
+        /// Path to the .tenor source file or interchange JSON bundle
+        #[arg(required_unless_present = "list_rules")]
+        file: Option<PathBuf>,
+        /// Project file to read [lint] settings from (default: the discovered tenor.toml)
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// List the available lint rules and exit
+        #[arg(long)]
+        list_rules: bool,
     },
 
     /// Explain a contract bundle in natural language
@@ -131,6 +350,54 @@ enum Commands {
         verbose: bool,
     },
 
+    /// Summarize the facts, entities, rules, personas, operations and flows of a contract
+    Inspect {
+        /// Path to .tenor source file or interchange JSON bundle
+        file: PathBuf,
+    },
+
+    /// Describe a TEN-E error code and its common fixes
+    ExplainError {
+        /// Error code, e.g. TEN-E-0050 (lists all codes when omitted)
+        code: Option<String>,
+    },
+
+    /// Reconstruct .tenor source from an interchange JSON bundle
+    Decompile {
+        /// Path to the interchange JSON bundle (or manifest)
+        bundle: PathBuf,
+        /// Write the source to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Optimize an interchange bundle for fast loading in deployment
+    Optimize {
+        /// Path to the interchange JSON bundle
+        bundle: PathBuf,
+        /// Write the optimized bundle to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Remove construct provenance (the result no longer validates against the schema)
+        #[arg(long)]
+        strip_provenance: bool,
+        /// Print a size and structure report to stderr
+        #[arg(long)]
+        report: bool,
+    },
+
+    /// Upgrade an interchange bundle to a newer interchange format version
+    UpgradeBundle {
+        /// Path to the interchange JSON bundle
+        bundle: PathBuf,
+        /// Target interchange version (defaults to the newest supported)
+        #[arg(long)]
+        to: Option<String>,
+        /// Write the upgraded bundle to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
     /// Generate code from a contract bundle
     Generate {
         #[command(subcommand)]
@@ -147,6 +414,15 @@ enum Commands {
         /// LLM model name to use
         #[arg(long)]
         model: Option<String>,
+        /// Sampling seed, for providers that accept one (OpenAI-compatible, Ollama)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Where to write the run artifact (default: ambiguity-run.json; on replay, not written unless given)
+        #[arg(long)]
+        artifact: Option<PathBuf>,
+        /// Re-score the responses recorded in a run artifact instead of calling the LLM
+        #[arg(long, conflicts_with_all = ["model", "seed"])]
+        replay: Option<PathBuf>,
     },
 
     /// Start the Tenor HTTP API server
@@ -160,22 +436,68 @@ enum Commands {
         /// Path to TLS private key PEM file (requires --tls-cert)
         #[arg(long)]
         tls_key: Option<PathBuf>,
-        /// .tenor contract files to pre-load
+        /// .tenor contract files to pre-load (default: [serve] contracts in tenor.toml)
         #[arg()]
         contracts: Vec<PathBuf>,
+        /// Origin allowed by CORS; repeat for several (default: any origin)
+        #[arg(long = "cors-origin")]
+        cors_origins: Vec<String>,
+        /// Requests per minute per client, 0 to disable (default: TENOR_RATE_LIMIT, or 60)
+        #[arg(long)]
+        rate_limit: Option<u64>,
+        /// What identifies a client for rate limiting
+        #[arg(long, value_enum)]
+        rate_limit_key: Option<serve::RateLimitKey>,
+        /// Maximum request body size in bytes (default: 10485760)
+        #[arg(long)]
+        max_body_size: Option<usize>,
+        /// Seconds to let in-flight requests drain after SIGTERM (default: 30)
+        #[arg(long, value_name = "SECS")]
+        shutdown_timeout: Option<u64>,
+        /// Entity history exported from storage, served by /entities: {"entity_states": [...], "transitions": [...]}
+        #[arg(long, value_name = "FILE")]
+        history: Option<PathBuf>,
+        /// Let webhooks, schedule sinks and adapter fetches reach loopback, private and link-local addresses
+        #[arg(long)]
+        webhook_allow_private: bool,
     },
 
     /// Start an interactive agent shell for a contract
     Agent {
         /// Path to the .tenor source file
         file: PathBuf,
+        /// Session file: loaded if it exists, saved on exit
+        #[arg(long)]
+        session: Option<PathBuf>,
+        /// Replay commands from a script file instead of reading stdin
+        #[arg(long)]
+        script: Option<PathBuf>,
+    },
+
+    /// Evaluate DSL predicates interactively against a contract and facts
+    Repl {
+        /// Path to .tenor source file or interchange JSON bundle
+        bundle: PathBuf,
+        /// Path to the facts JSON file
+        #[arg(long)]
+        facts: PathBuf,
+        /// Evaluate predicates from a script file instead of reading stdin
+        #[arg(long)]
+        script: Option<PathBuf>,
+    },
+
+    /// Serve contracts as Model Context Protocol tools over stdio
+    Mcp {
+        /// .tenor contract files to serve
+        #[arg(required = true)]
+        contracts: Vec<PathBuf>,
     },
 
     /// Introspect sources and generate adapter scaffolding
     Connect {
         /// Path to the .tenor source file or interchange JSON
         contract: PathBuf,
-        /// Path to external schema document (OpenAPI, GraphQL SDL, SQL DDL — auto-detected)
+        /// Path to external schema document (OpenAPI, GraphQL SDL, SQL DDL, protobuf, Avro — auto-detected)
         #[arg(long)]
         environment: Option<PathBuf>,
         /// Output directory for generated adapter scaffolding
@@ -190,15 +512,21 @@ enum Commands {
         /// Generate adapters from a reviewed mapping file
         #[arg(long)]
         apply: Option<PathBuf>,
-        /// LLM model to use (default: claude-sonnet-4-20250514)
+        /// LLM model to use (default: the configured model, or the provider's default)
         #[arg(long)]
         model: Option<String>,
-        /// Force heuristic matching (skip LLM even if API key is set)
+        /// Force heuristic matching (skip LLM even if a provider is configured)
         #[arg(long)]
         heuristic: bool,
+        /// Synonym dictionary (TOML) for heuristic matching, e.g. `amount = ["total"]`
+        #[arg(long)]
+        synonyms: Option<PathBuf>,
         /// Show detailed matching reasoning
         #[arg(long)]
         verbose: bool,
+        /// Also generate TypeScript adapters
+        #[arg(long)]
+        typescript: bool,
     },
 
     /// Generate a React application from a contract
@@ -383,12 +711,23 @@ pub(crate) enum GenerateCommands {
     Typescript {
         /// Path to .tenor source file or interchange JSON bundle
         input: PathBuf,
-        /// Output directory for generated files
-        #[arg(long, default_value = "./generated")]
-        out: PathBuf,
+        /// Output directory for generated files (default: ./generated)
+        #[arg(long)]
+        out: Option<PathBuf>,
         /// SDK import path (default: @tenor/sdk)
-        #[arg(long, default_value = "@tenor/sdk")]
-        sdk_import: String,
+        #[arg(long)]
+        sdk_import: Option<String>,
+    },
+    /// Generate Markdown or HTML contract documentation
+    Docs {
+        /// Path to .tenor source file or interchange JSON bundle
+        input: PathBuf,
+        /// Output directory for generated documentation (default: ./docs)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Documentation format (markdown or html; default: markdown)
+        #[arg(long)]
+        format: Option<docs::DocsFormat>,
     },
 }
 
@@ -402,49 +741,340 @@ enum BuilderCommands {
     },
 }
 
+/// Project configuration for this invocation; see [`load_project`].
+static PROJECT: OnceLock<tenor_core::ProjectConfig> = OnceLock::new();
+
+/// Discover `tenor.toml` from the working directory. A malformed project
+/// file is fatal for every command.
+fn load_project(output: OutputFormat, quiet: bool) {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    match tenor_core::ProjectConfig::discover(&cwd) {
+        Ok(config) => {
+            let _ = PROJECT.set(config.unwrap_or_default());
+        }
+        Err(e) => {
+            report_error(&e, output, quiet);
+            process::exit(1);
+        }
+    }
+}
+
+/// The project configuration, or the defaults when there is no `tenor.toml`.
+pub(crate) fn project() -> &'static tenor_core::ProjectConfig {
+    PROJECT.get_or_init(tenor_core::ProjectConfig::default)
+}
+
+/// Elaborate a contract, resolving imports through the project's import paths.
+pub(crate) fn elaborate(path: &Path) -> Result<serde_json::Value, Box<tenor_core::ElabError>> {
+    tenor_core::elaborate_with_provider(path, &project().source_provider()).map_err(Box::new)
+}
+
+/// Resolve a contract path given on the command line against the project's
+/// source roots.
+fn contract(path: PathBuf) -> PathBuf {
+    project().find_contract(&path)
+}
+
+/// Adapter settings for `tenor serve` schedules: the file named by
+/// `[connect] adapter_config`, when it exists, overlaid with
+/// `TENOR_ADAPTER_*` variables.
+fn serve_adapter_config() -> tenor_eval::AdapterConfig {
+    serve::read_adapter_config(serve_adapter_config_path().as_deref()).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    })
+}
+
+/// The file named by `[connect] adapter_config`, resolved against the
+/// project root.
+fn serve_adapter_config_path() -> Option<PathBuf> {
+    let project = project();
+    project
+        .connect
+        .adapter_config
+        .as_ref()
+        .map(|p| project.resolve(p))
+}
+
+/// Run `f` with the evaluator's clock fixed at `now`, when given.
+fn at_time(now: Option<tenor_eval::FixedClock>, f: impl FnOnce()) {
+    match now {
+        Some(clock) => tenor_eval::with_clock(std::sync::Arc::new(clock), f),
+        None => f(),
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
+    load_project(cli.output, cli.quiet);
 
     match cli.command {
-        Commands::Elaborate { file, manifest } => {
-            commands::elaborate::cmd_elaborate(&file, manifest, cli.output, cli.quiet);
+        Commands::Elaborate {
+            file,
+            manifest,
+            features,
+            source_map,
+        } => {
+            commands::elaborate::cmd_elaborate(
+                &contract(file),
+                manifest,
+                &features.into_iter().collect(),
+                source_map.as_deref(),
+                cli.output,
+                cli.quiet,
+            );
         }
+        Commands::New {
+            dir,
+            name,
+            entity,
+            requester,
+            approver,
+            yes,
+        } => {
+            commands::new::cmd_new(
+                &dir,
+                commands::new::NewArgs {
+                    name: name.as_deref(),
+                    entity: entity.as_deref(),
+                    requester: requester.as_deref(),
+                    approver: approver.as_deref(),
+                    yes,
+                },
+                cli.output,
+                cli.quiet,
+            );
+        }
+        Commands::Completions { shell } => commands::completions::cmd_completions(shell),
         Commands::Validate { bundle } => {
             commands::validate::cmd_validate(&bundle, cli.output, cli.quiet);
         }
         Commands::Eval {
             bundle,
             facts,
+            fact,
+            compare_facts,
+            validate_facts,
             flow,
             persona,
-        } => {
-            commands::eval::cmd_eval(
+            export_trace,
+            profile,
+            trace,
+            partial,
+            as_of,
+            history,
+            now,
+        } => at_time(now, || match compare_facts.as_deref() {
+            Some([old, new]) => {
+                commands::eval::cmd_eval_compare(&bundle, old, new, cli.output, cli.quiet)
+            }
+            _ if validate_facts => {
+                commands::eval::cmd_validate_facts(&bundle, &facts, &fact, cli.output, cli.quiet)
+            }
+            _ => commands::eval::cmd_eval(
                 &bundle,
                 &facts,
+                &fact,
                 flow.as_deref(),
                 persona.as_deref(),
+                export_trace.as_deref(),
+                profile,
+                trace,
+                partial,
+                as_of.as_deref().zip(history.as_deref()),
+                cli.output,
+                cli.quiet,
+            ),
+        }),
+        Commands::Simulate {
+            bundle,
+            random_walk: _,
+            seed,
+            count,
+            flow,
+            persona,
+            facts,
+            entity_states,
+            bindings,
+            now,
+        } => match (facts, flow, persona) {
+            (Some(facts), Some(flow), Some(persona)) => at_time(now, || {
+                commands::simulate::cmd_simulate_flow(
+                    &bundle,
+                    &flow,
+                    &persona,
+                    &facts,
+                    entity_states.as_deref(),
+                    bindings.as_deref(),
+                    cli.output,
+                    cli.quiet,
+                )
+            }),
+            (_, flow, _) => {
+                commands::simulate::cmd_simulate(
+                    &bundle,
+                    seed,
+                    count,
+                    flow.as_deref(),
+                    cli.output,
+                    cli.quiet,
+                );
+            }
+        },
+        Commands::Actions {
+            bundle,
+            facts,
+            entity_states,
+            persona,
+        } => {
+            commands::actions::cmd_actions(
+                &contract(bundle),
+                &facts,
+                entity_states.as_deref(),
+                &persona,
                 cli.output,
                 cli.quiet,
             );
         }
-        Commands::Test { suite_dir } => {
-            commands::test::cmd_test(&suite_dir, cli.quiet);
+        Commands::Solve {
+            bundle,
+            flow,
+            outcome,
+            persona,
+            entity_states,
+            max_assignments,
+        } => {
+            commands::solve::cmd_solve(
+                &bundle,
+                &flow,
+                &outcome,
+                persona.as_deref(),
+                entity_states.as_deref(),
+                max_assignments,
+                cli.output,
+                cli.quiet,
+            );
+        }
+        Commands::Test {
+            suite_dir,
+            unit,
+            filter,
+        } => {
+            if unit {
+                commands::test::cmd_test_unit(&contract(suite_dir), cli.output, cli.quiet);
+            } else {
+                commands::test::cmd_test(&suite_dir, filter.as_deref(), cli.output, cli.quiet);
+            }
+        }
+        Commands::Conformance {
+            url,
+            api_key,
+            format,
+        } => {
+            commands::conformance::cmd_conformance(&url, api_key, format);
         }
         Commands::Diff { t1, t2, breaking } => {
             commands::diff::cmd_diff(&t1, &t2, breaking, cli.output, cli.quiet);
         }
-        Commands::Migrate { v1, v2, yes } => {
-            migrate::cmd_migrate(&v1, &v2, yes, cli.output, cli.quiet);
-        }
-        Commands::Check { file, analysis } => {
-            commands::check::cmd_check(&file, analysis.as_deref(), cli.output, cli.quiet);
+        Commands::Migrate {
+            v1,
+            v2,
+            yes,
+            mapping,
+            out,
+        } => {
+            migrate::cmd_migrate(
+                &v1,
+                &v2,
+                yes,
+                mapping.as_deref(),
+                out.as_deref(),
+                cli.output,
+                cli.quiet,
+            );
         }
+        Commands::Check {
+            file,
+            analysis,
+            state_space_limit,
+            properties,
+            only_tag,
+            admissibility_matrix,
+            system,
+        } => match (file, system, admissibility_matrix) {
+            (_, Some(system), _) => {
+                commands::check::cmd_check_system(&system, cli.output, cli.quiet);
+            }
+            (Some(file), None, Some(format)) => {
+                commands::check::cmd_admissibility_matrix(
+                    &contract(file),
+                    format,
+                    cli.output,
+                    cli.quiet,
+                );
+            }
+            (Some(file), None, None) => {
+                commands::check::cmd_check(
+                    &contract(file),
+                    analysis.as_deref(),
+                    state_space_limit,
+                    properties.as_deref(),
+                    only_tag.as_deref(),
+                    cli.output,
+                    cli.quiet,
+                );
+            }
+            (None, None, _) => unreachable!("clap requires FILE unless --system is given"),
+        },
+        Commands::Lint {
+            file,
+            config,
+            list_rules,
+        } => match file {
+            Some(file) if !list_rules => {
+                commands::lint::cmd_lint(&contract(file), config.as_deref(), cli.output, cli.quiet);
+            }
+            _ => commands::lint::cmd_lint_list_rules(cli.output),
+        },
         Commands::Explain {
             file,
             format,
             verbose,
         } => {
-            commands::explain::cmd_explain(&file, format, verbose, cli.output, cli.quiet);
+            commands::explain::cmd_explain(&contract(file), format, verbose, cli.output, cli.quiet);
+        }
+        Commands::Inspect { file } => {
+            commands::inspect::cmd_inspect(&contract(file), cli.output, cli.quiet);
+        }
+        Commands::ExplainError { code } => {
+            commands::explain_error::cmd_explain_error(code.as_deref(), cli.output, cli.quiet);
+        }
+        Commands::Decompile { bundle, out } => {
+            commands::decompile::cmd_decompile(&bundle, out.as_deref(), cli.output, cli.quiet);
+        }
+        Commands::Optimize {
+            bundle,
+            out,
+            strip_provenance,
+            report,
+        } => {
+            commands::optimize::cmd_optimize(
+                &bundle,
+                out.as_deref(),
+                strip_provenance,
+                report,
+                cli.output,
+                cli.quiet,
+            );
+        }
+        Commands::UpgradeBundle { bundle, to, out } => {
+            commands::upgrade_bundle::cmd_upgrade_bundle(
+                &bundle,
+                to.as_deref(),
+                out.as_deref(),
+                cli.output,
+                cli.quiet,
+            );
         }
         Commands::Generate { command } => {
             commands::generate::cmd_generate(command, cli.output, cli.quiet);
@@ -453,31 +1083,157 @@ fn main() {
             suite_dir,
             spec,
             model,
+            seed,
+            artifact,
+            replay,
         } => {
-            commands::ambiguity::cmd_ambiguity(&suite_dir, spec.as_deref(), model.as_deref());
+            commands::ambiguity::cmd_ambiguity(
+                &suite_dir,
+                spec.as_deref(),
+                model.as_deref(),
+                seed,
+                artifact.as_deref(),
+                replay.as_deref(),
+            );
         }
         Commands::Serve {
             port,
             contracts,
             tls_cert,
             tls_key,
+            cors_origins,
+            rate_limit,
+            rate_limit_key,
+            max_body_size,
+            shutdown_timeout,
+            history,
+            webhook_allow_private,
         } => {
             // Validate TLS flags: both must be provided or neither
             if tls_cert.is_some() != tls_key.is_some() {
                 eprintln!("error: --tls-cert and --tls-key must both be provided");
                 process::exit(1);
             }
+            // Flags override TENOR_RATE_LIMIT, which overrides [serve] in tenor.toml
+            let settings = &project().serve;
+            let defaults = serve::ServeOptions::default();
+            let rate_limit_key = match (rate_limit_key, &settings.rate_limit_key) {
+                (Some(key), _) => key,
+                (None, Some(name)) => match serve::RateLimitKey::from_str(name, true) {
+                    Ok(key) => key,
+                    Err(_) => {
+                        eprintln!(
+                            "error: invalid [serve] rate_limit_key '{}' in tenor.toml: expected ip or token",
+                            name
+                        );
+                        process::exit(1);
+                    }
+                },
+                (None, None) => defaults.rate_limit_key,
+            };
+            let options = serve::ServeOptions {
+                cors_origins: if cors_origins.is_empty() {
+                    settings.cors_origins.clone().unwrap_or_default()
+                } else {
+                    cors_origins
+                },
+                rate_limit: rate_limit
+                    .or_else(|| {
+                        std::env::var("TENOR_RATE_LIMIT")
+                            .ok()
+                            .and_then(|v| v.parse().ok())
+                    })
+                    .or(settings.rate_limit)
+                    .unwrap_or(defaults.rate_limit),
+                rate_limit_key,
+                max_body_size: max_body_size
+                    .or(settings.max_body_size)
+                    .unwrap_or(defaults.max_body_size),
+                shutdown_timeout: shutdown_timeout
+                    .or(settings.shutdown_timeout)
+                    .map(std::time::Duration::from_secs)
+                    .unwrap_or(defaults.shutdown_timeout),
+                tenants: settings
+                    .tenants
+                    .iter()
+                    .map(|(id, tenant)| {
+                        let token = std::env::var(&tenant.token_env)
+                            .ok()
+                            .filter(|t| !t.is_empty())
+                            .unwrap_or_else(|| {
+                                eprintln!(
+                                    "error: tenant '{}' in tenor.toml: environment variable {} is not set",
+                                    id, tenant.token_env
+                                );
+                                process::exit(1);
+                            });
+                        serve::TenantConfig {
+                            id: id.clone(),
+                            token,
+                            contracts: tenant
+                                .contracts
+                                .iter()
+                                .map(|p| project().resolve(p))
+                                .collect(),
+                            max_contracts: tenant.max_contracts,
+                            history: tenant.history.as_ref().map(|p| project().resolve(p)),
+                            adapter_config: tenant
+                                .adapter_config
+                                .as_ref()
+                                .map(|p| project().resolve(p)),
+                        }
+                    })
+                    .collect(),
+                adapter_config: serve_adapter_config(),
+                adapter_config_path: serve_adapter_config_path(),
+                history: history.or_else(|| settings.history.as_ref().map(|p| project().resolve(p))),
+                webhook_allow_private: webhook_allow_private
+                    || settings.webhook_allow_private.unwrap_or(false),
+            };
+            let contracts: Vec<PathBuf> = if contracts.is_empty() {
+                let project = project();
+                project
+                    .serve
+                    .contracts
+                    .iter()
+                    .map(|p| project.resolve(p))
+                    .collect()
+            } else {
+                contracts.into_iter().map(contract).collect()
+            };
             let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime");
-            if let Err(e) = rt.block_on(serve::start_server(port, contracts, tls_cert, tls_key)) {
+            if let Err(e) = rt.block_on(serve::start_server(
+                port, contracts, tls_cert, tls_key, options,
+            )) {
                 eprintln!("Server error: {}", e);
                 process::exit(1);
             }
         }
-        Commands::Agent { file } => {
-            agent::run_agent(&file);
+        Commands::Agent {
+            file,
+            session,
+            script,
+        } => {
+            agent::run_agent(&contract(file), session.as_deref(), script.as_deref());
+        }
+        Commands::Repl {
+            bundle,
+            facts,
+            script,
+        } => {
+            commands::repl::cmd_repl(
+                &contract(bundle),
+                &facts,
+                script.as_deref(),
+                cli.output,
+                cli.quiet,
+            );
+        }
+        Commands::Mcp { contracts } => {
+            mcp::run_mcp(&contracts);
         }
         Commands::Connect {
-            contract,
+            contract: contract_path,
             environment,
             out,
             dry_run,
@@ -485,10 +1241,12 @@ fn main() {
             apply,
             model,
             heuristic,
+            synonyms,
             verbose,
+            typescript,
         } => {
             connect::cmd_connect(connect::ConnectOptions {
-                contract: &contract,
+                contract: &contract(contract_path),
                 environment: environment.as_deref(),
                 output_dir: &out,
                 dry_run,
@@ -496,7 +1254,9 @@ fn main() {
                 apply: apply.as_deref(),
                 model: model.as_deref(),
                 heuristic,
+                synonyms: synonyms.as_deref(),
                 verbose,
+                typescript,
                 output: cli.output,
                 quiet: cli.quiet,
             });
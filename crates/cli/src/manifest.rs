@@ -1,15 +1,13 @@
 use serde_json::{Map, Value};
-use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use tenor_lsp::agent_capabilities::agent_capabilities_from_bundle;
 
 /// Manifest envelope version (e.g., "1.0").
 const MANIFEST_TENOR_VERSION: &str = "1.0";
 
 /// Compute SHA-256 etag from compact JSON representation.
 pub fn compute_etag(bundle: &Value) -> String {
-    let canonical = serde_json::to_string(bundle)
-        .unwrap_or_else(|e| panic!("serialization error computing etag: {}", e));
-    let hash = Sha256::digest(canonical.as_bytes());
-    format!("{:x}", hash)
+    tenor_eval::bundle_etag(bundle)
 }
 
 /// Wrap an interchange bundle in a TenorManifest envelope.
@@ -22,10 +20,17 @@ pub fn build_manifest(bundle: Value) -> Value {
     let etag = compute_etag(&bundle);
 
     let capabilities = serde_json::json!({
+        "evaluator_features": tenor_eval::SUPPORTED_FEATURES,
         "migration_analysis_mode": "conservative"
     });
 
+    // The agent's view of the contract, identical to what the LSP panel and
+    // MCP `inspect_contract` report.
+    let agent_capabilities = serde_json::to_value(agent_capabilities_from_bundle(&bundle))
+        .unwrap_or_else(|e| panic!("serialization error computing agent capabilities: {}", e));
+
     let mut map = Map::new();
+    map.insert("agent_capabilities".to_string(), agent_capabilities);
     map.insert("bundle".to_string(), bundle);
     map.insert("capabilities".to_string(), capabilities);
     map.insert("etag".to_string(), Value::String(etag));
@@ -35,3 +40,14 @@ pub fn build_manifest(bundle: Value) -> Value {
     );
     Value::Object(map)
 }
+
+/// Wrap a bundle elaborated with `features` in a TenorManifest envelope,
+/// recording the feature set so a deployed variant can be traced back to
+/// the flags that produced it. An empty set is omitted.
+pub fn build_manifest_with_features(bundle: Value, features: &BTreeSet<String>) -> Value {
+    let mut manifest = build_manifest(bundle);
+    if !features.is_empty() {
+        manifest["features"] = serde_json::json!(features);
+    }
+    manifest
+}
@@ -0,0 +1,613 @@
+//! `tenor mcp` -- Model Context Protocol server over stdio.
+//!
+//! Exposes contract inspection, evaluation, action-space computation, and
+//! flow simulation as MCP tools so LLM agents can ask "what can persona X
+//! do right now?" without bespoke glue. Messages are newline-delimited
+//! JSON-RPC 2.0 on stdin/stdout; diagnostics go to stderr.
+//!
+//! Every tool is read-only: facts and entity states come from the tool
+//! arguments and nothing is persisted between calls.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+/// Protocol revisions this server speaks, newest first.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+// JSON-RPC 2.0 error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// A contract loaded at startup.
+struct LoadedContract {
+    path: PathBuf,
+    bundle: Value,
+}
+
+/// The set of contracts served, keyed by contract id.
+pub(crate) struct McpServer {
+    contracts: BTreeMap<String, LoadedContract>,
+}
+
+impl McpServer {
+    /// Elaborate each `.tenor` file and index it by contract id.
+    pub(crate) fn load(files: &[PathBuf]) -> Result<Self, String> {
+        let mut contracts = BTreeMap::new();
+        for path in files {
+            let bundle = crate::elaborate(path)
+                .map_err(|e| format!("failed to elaborate '{}': {:?}", path.display(), e))?;
+            let id = bundle
+                .get("id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+            if contracts.contains_key(&id) {
+                return Err(format!(
+                    "duplicate contract id '{}' in '{}'",
+                    id,
+                    path.display()
+                ));
+            }
+            contracts.insert(
+                id,
+                LoadedContract {
+                    path: path.clone(),
+                    bundle,
+                },
+            );
+        }
+        Ok(McpServer { contracts })
+    }
+
+    /// Handle one JSON-RPC message. Returns the response to send, or
+    /// `None` for notifications.
+    pub(crate) fn handle_message(&self, message: &Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let method = match message.get("method").and_then(|m| m.as_str()) {
+            Some(m) => m,
+            None => {
+                return Some(error_response(
+                    id.unwrap_or(Value::Null),
+                    INVALID_REQUEST,
+                    "missing 'method'",
+                ))
+            }
+        };
+        // Notifications (no id) never get a response.
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or(json!({}));
+
+        let result = match method {
+            "initialize" => Ok(self.initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params),
+            _ => Err((METHOD_NOT_FOUND, format!("method '{}' not found", method))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params.get("protocolVersion").and_then(|v| v.as_str());
+        let version = requested
+            .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+            .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0]);
+        let ids: Vec<&str> = self.contracts.keys().map(|k| k.as_str()).collect();
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": { "name": "tenor", "version": env!("CARGO_PKG_VERSION") },
+            "instructions": format!(
+                "Tenor contracts loaded: {}. Use inspect_contract to discover personas, \
+                 facts, and flows, then action_space to see what a persona may do.",
+                ids.join(", ")
+            ),
+        })
+    }
+
+    /// Dispatch `tools/call`. Tool failures are reported in the result
+    /// with `isError: true`; only malformed calls are JSON-RPC errors.
+    fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(|n| n.as_str())
+            .ok_or((INVALID_PARAMS, "missing tool 'name'".to_string()))?;
+        let args = params.get("arguments").cloned().unwrap_or(json!({}));
+        let outcome = match name {
+            "list_contracts" => Ok(self.list_contracts()),
+            "inspect_contract" => self.inspect_contract(&args),
+            "evaluate" => self.evaluate(&args),
+            "action_space" => self.action_space(&args),
+            "simulate_flow" => self.simulate_flow(&args),
+            _ => return Err((INVALID_PARAMS, format!("unknown tool '{}'", name))),
+        };
+        Ok(match outcome {
+            Ok(value) => json!({
+                "content": [{ "type": "text", "text": value.to_string() }],
+                "structuredContent": value,
+                "isError": false,
+            }),
+            Err(message) => json!({
+                "content": [{ "type": "text", "text": message }],
+                "isError": true,
+            }),
+        })
+    }
+
+    /// Resolve the `contract_id` argument; it may be omitted when exactly
+    /// one contract is loaded.
+    fn contract(&self, args: &Value) -> Result<&LoadedContract, String> {
+        match args.get("contract_id").and_then(|v| v.as_str()) {
+            Some(id) => self
+                .contracts
+                .get(id)
+                .ok_or_else(|| format!("contract '{}' is not loaded", id)),
+            None if self.contracts.len() == 1 => Ok(self.contracts.values().next().unwrap()),
+            None => Err(format!(
+                "'contract_id' is required when several contracts are loaded ({})",
+                self.contracts
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    fn list_contracts(&self) -> Value {
+        let contracts: Vec<Value> = self
+            .contracts
+            .iter()
+            .map(|(id, c)| json!({ "contract_id": id, "source": c.path.display().to_string() }))
+            .collect();
+        json!({ "contracts": contracts })
+    }
+
+    fn inspect_contract(&self, args: &Value) -> Result<Value, String> {
+        let contract = self.contract(args)?;
+        let capabilities =
+            tenor_lsp::agent_capabilities::agent_capabilities_from_bundle(&contract.bundle);
+        let facts: Vec<Value> = constructs_of(&contract.bundle, "Fact")
+            .map(|f| {
+                json!({
+                    "id": f.get("id"),
+                    "type": f.get("type"),
+                    "has_default": f.get("default").is_some_and(|d| !d.is_null()),
+                })
+            })
+            .collect();
+        let mut value = serde_json::to_value(&capabilities).map_err(|e| e.to_string())?;
+        value["facts"] = Value::Array(facts);
+        value["etag"] = Value::String(crate::manifest::compute_etag(&contract.bundle));
+        Ok(value)
+    }
+
+    fn evaluate(&self, args: &Value) -> Result<Value, String> {
+        let contract = self.contract(args)?;
+        let facts = object_arg(args, "facts")?;
+        let result = tenor_eval::evaluate(&contract.bundle, &facts).map_err(|e| e.to_string())?;
+        Ok(result.verdicts.to_json())
+    }
+
+    fn action_space(&self, args: &Value) -> Result<Value, String> {
+        let contract = self.contract(args)?;
+        let persona_id = string_arg(args, "persona_id")?;
+        let facts = object_arg(args, "facts")?;
+        let entity_states: BTreeMap<String, String> =
+            serde_json::from_value(object_arg(args, "entity_states")?)
+                .map_err(|e| format!("invalid entity_states: {}", e))?;
+
+        let typed = tenor_eval::Contract::from_interchange(&contract.bundle)
+            .map_err(|e| format!("invalid contract: {}", e))?;
+        let mut states = tenor_eval::operation::init_entity_states(&typed);
+        states.extend(tenor_eval::single_instance(entity_states));
+        let space = tenor_eval::compute_action_space(&typed, &facts, &states, persona_id)
+            .map_err(|e| e.to_string())?;
+        serde_json::to_value(&space).map_err(|e| e.to_string())
+    }
+
+    fn simulate_flow(&self, args: &Value) -> Result<Value, String> {
+        use crate::serve::simulate::{simulate_flow_inner, SimulateError};
+
+        let contract = self.contract(args)?;
+        let flow_id = string_arg(args, "flow_id")?;
+        let persona_id = string_arg(args, "persona_id")?;
+        let facts = object_arg(args, "facts")?;
+        // Accept the same flat entity_id -> state map as action_space.
+        let entity_states: serde_json::Map<String, Value> = object_arg(args, "entity_states")?
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(entity, state)| (entity.clone(), json!({ "state": state })))
+            .collect();
+
+        let typed = tenor_eval::Contract::from_interchange(&contract.bundle)
+            .map_err(|e| format!("invalid contract: {}", e))?;
+        simulate_flow_inner(
+            &typed,
+            &facts,
+            flow_id,
+            persona_id,
+            Some(&Value::Object(entity_states)),
+        )
+        .map_err(|e| match e {
+            SimulateError::PersonaNotFound(p) => format!("persona '{}' not found in contract", p),
+            SimulateError::Eval(e) => e.to_string(),
+        })
+    }
+}
+
+fn constructs_of<'a>(bundle: &'a Value, kind: &'a str) -> impl Iterator<Item = &'a Value> {
+    bundle
+        .get("constructs")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter(move |c| c.get("kind").and_then(|k| k.as_str()) == Some(kind))
+}
+
+fn string_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    args.get(key)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| format!("missing '{}' argument", key))
+}
+
+/// An optional object argument, defaulting to `{}`.
+fn object_arg(args: &Value, key: &str) -> Result<Value, String> {
+    match args.get(key) {
+        None | Some(Value::Null) => Ok(json!({})),
+        Some(v @ Value::Object(_)) => Ok(v.clone()),
+        Some(_) => Err(format!("'{}' must be an object", key)),
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// The tool catalogue returned by `tools/list`.
+fn tool_definitions() -> Value {
+    let contract_id = json!({
+        "type": "string",
+        "description": "Contract to use; optional when only one contract is loaded",
+    });
+    let facts = json!({
+        "type": "object",
+        "description": "Fact values keyed by fact id, in interchange JSON form",
+    });
+    let entity_states = json!({
+        "type": "object",
+        "description": "Current entity states keyed by entity id; omitted entities are in their initial state",
+        "additionalProperties": { "type": "string" },
+    });
+    json!([
+        {
+            "name": "list_contracts",
+            "description": "List the loaded Tenor contracts.",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+        {
+            "name": "inspect_contract",
+            "description": "Describe a contract: personas and the operations they may invoke, facts, entity state machines, flows, and static analysis findings.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "contract_id": contract_id },
+            },
+        },
+        {
+            "name": "evaluate",
+            "description": "Evaluate the contract's rules against facts and return the verdicts produced.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "contract_id": contract_id, "facts": facts },
+                "required": ["facts"],
+            },
+        },
+        {
+            "name": "action_space",
+            "description": "List the flows a persona may start given facts and entity states, and why the others are blocked.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "contract_id": contract_id,
+                    "persona_id": { "type": "string" },
+                    "facts": facts,
+                    "entity_states": entity_states,
+                },
+                "required": ["persona_id", "facts"],
+            },
+        },
+        {
+            "name": "simulate_flow",
+            "description": "Simulate a flow without persisting anything: the path taken, its outcome, and the entity transitions it would make.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "contract_id": contract_id,
+                    "flow_id": { "type": "string" },
+                    "persona_id": { "type": "string" },
+                    "facts": facts,
+                    "entity_states": entity_states,
+                },
+                "required": ["flow_id", "persona_id", "facts"],
+            },
+        },
+    ])
+}
+
+/// Serve MCP over stdin/stdout until stdin closes.
+pub fn run_mcp(files: &[PathBuf]) {
+    if files.is_empty() {
+        eprintln!("error: at least one .tenor contract is required");
+        std::process::exit(1);
+    }
+    let server = match McpServer::load(files) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    eprintln!(
+        "tenor mcp: serving {} contract(s) on stdio",
+        server.contracts.len()
+    );
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("error reading input: {}", e);
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle_message(&message),
+            Err(e) => Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("parse error: {}", e),
+            )),
+        };
+        if let Some(response) = response {
+            if writeln!(stdout, "{}", response)
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = r#"
+persona admin
+
+fact is_active {
+  type:   Bool
+  source: "account.active"
+}
+
+entity Order {
+  states:      [pending, approved]
+  initial:     pending
+  transitions: [(pending, approved)]
+}
+
+rule check_active {
+  stratum: 0
+  when:    is_active = true
+  produce: verdict account_active { payload: Bool = true }
+}
+
+operation approve_order {
+  allowed_personas: [admin]
+  precondition:     verdict_present(account_active)
+  effects:          [(Order, pending, approved)]
+  error_contract:   [precondition_failed]
+}
+
+flow approval_flow {
+  snapshot: at_initiation
+  entry:    step_approve
+
+  steps: {
+    step_approve: OperationStep {
+      op:      approve_order
+      persona: admin
+      outcomes: {
+        success: Terminal(order_approved)
+      }
+      on_failure: Terminate(outcome: approval_failed)
+    }
+  }
+}
+"#;
+
+    /// The server plus the directory holding its contract, which must
+    /// outlive the server since `inspect_contract` re-reads the source.
+    fn server() -> (tempfile::TempDir, McpServer) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("approval.tenor");
+        std::fs::write(&path, CONTRACT).unwrap();
+        let server = McpServer::load(&[path]).expect("fixture elaborates");
+        (dir, server)
+    }
+
+    fn call(server: &McpServer, tool: &str, arguments: Value) -> Value {
+        let response = server
+            .handle_message(&json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "method": "tools/call",
+                "params": { "name": tool, "arguments": arguments },
+            }))
+            .expect("request gets a response");
+        assert_eq!(response["id"], 7);
+        response["result"].clone()
+    }
+
+    #[test]
+    fn initialize_negotiates_protocol_version() {
+        let (_dir, server) = server();
+        let response = server
+            .handle_message(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": "2024-11-05" },
+            }))
+            .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(response["result"]["serverInfo"]["name"], "tenor");
+
+        let response = server
+            .handle_message(&json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "initialize",
+                "params": { "protocolVersion": "1999-01-01" },
+            }))
+            .unwrap();
+        assert_eq!(
+            response["result"]["protocolVersion"],
+            SUPPORTED_PROTOCOL_VERSIONS[0]
+        );
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        let (_dir, server) = server();
+        assert!(server
+            .handle_message(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .is_none());
+    }
+
+    #[test]
+    fn unknown_method_is_a_jsonrpc_error() {
+        let response = server()
+            .1
+            .handle_message(&json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" }))
+            .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn tools_list_names_every_tool() {
+        let response = server()
+            .1
+            .handle_message(&json!({ "jsonrpc": "2.0", "id": 4, "method": "tools/list" }))
+            .unwrap();
+        let names: Vec<&str> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "list_contracts",
+                "inspect_contract",
+                "evaluate",
+                "action_space",
+                "simulate_flow"
+            ]
+        );
+    }
+
+    #[test]
+    fn action_space_reflects_facts_and_entity_states() {
+        let (_dir, server) = server();
+        let result = call(
+            &server,
+            "action_space",
+            json!({ "persona_id": "admin", "facts": { "is_active": true } }),
+        );
+        assert_eq!(result["isError"], false);
+        let actions = result["structuredContent"]["actions"].as_array().unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0]["flow_id"], "approval_flow");
+
+        let result = call(
+            &server,
+            "action_space",
+            json!({
+                "persona_id": "admin",
+                "facts": { "is_active": true },
+                "entity_states": { "Order": "approved" },
+            }),
+        );
+        assert!(result["structuredContent"]["actions"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn simulate_flow_reports_transitions() {
+        let result = call(
+            &server().1,
+            "simulate_flow",
+            json!({
+                "flow_id": "approval_flow",
+                "persona_id": "admin",
+                "facts": { "is_active": true },
+            }),
+        );
+        let sim = &result["structuredContent"];
+        assert_eq!(sim["outcome"], "order_approved");
+        assert_eq!(sim["would_transition"][0]["to"], "approved");
+    }
+
+    #[test]
+    fn tool_failures_are_reported_in_the_result() {
+        let result = call(
+            &server().1,
+            "simulate_flow",
+            json!({
+                "flow_id": "approval_flow",
+                "persona_id": "nobody",
+                "facts": { "is_active": true },
+            }),
+        );
+        assert_eq!(result["isError"], true);
+        assert!(result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("persona 'nobody' not found"));
+
+        let result = call(&server().1, "evaluate", json!({ "contract_id": "other" }));
+        assert_eq!(result["isError"], true);
+    }
+
+    #[test]
+    fn inspect_contract_lists_personas_and_facts() {
+        let result = call(&server().1, "inspect_contract", json!({}));
+        let info = &result["structuredContent"];
+        assert_eq!(info["personas"][0]["id"], "admin");
+        assert_eq!(info["facts"][0]["id"], "is_active");
+        assert!(info["etag"].as_str().is_some());
+    }
+}
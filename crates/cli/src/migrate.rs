@@ -1,24 +1,37 @@
 //! CLI migrate subcommand.
 //!
 //! Orchestrates the full migration analysis pipeline:
-//! diff -> classify -> analyze -> compatibility -> plan -> confirm.
+//! diff -> classify -> analyze -> compatibility -> plan -> map states -> confirm.
 //!
 //! Actual execution requires a TenorStorage backend (database) and is
-//! performed via the API server or SDK. The CLI demonstrates the
-//! analysis + planning pipeline.
+//! performed via the API server or SDK. The CLI produces the plan and,
+//! with `--out`, a `MigrationArtifact` carrying the confirmed entity state
+//! remaps that the storage layer applies to live entity records.
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
 use std::path::Path;
 use std::process;
 
 use tenor_eval::migration::{
-    analyze_migration, build_migration_plan, check_flow_compatibility_static, MigrationSeverity,
+    analyze_migration, build_migration_plan, check_flow_compatibility_static, default_state_remaps,
+    validate_state_remaps, EntityStateMapping, EntityStateRemap, MigrationArtifact,
+    MigrationSeverity,
 };
 use tenor_eval::types::Contract;
 
 use crate::{report_error, OutputFormat};
 
 /// Run the migration analysis and planning pipeline.
-pub fn cmd_migrate(v1_path: &Path, v2_path: &Path, yes: bool, output: OutputFormat, quiet: bool) {
+pub fn cmd_migrate(
+    v1_path: &Path,
+    v2_path: &Path,
+    yes: bool,
+    mapping_path: Option<&Path>,
+    out_path: Option<&Path>,
+    output: OutputFormat,
+    quiet: bool,
+) {
     // 1. Load both bundles
     let v1_json = load_interchange_bundle(v1_path, output, quiet);
     let v2_json = load_interchange_bundle(v2_path, output, quiet);
@@ -58,7 +71,45 @@ pub fn cmd_migrate(v1_path: &Path, v2_path: &Path, yes: bool, output: OutputForm
     // Populate flow compatibility into the plan
     plan.flow_compatibility = flow_compat_results;
 
-    // 5. Display or serialize
+    // 5. Resolve entity state remaps: suggested defaults, overridden by the
+    //    mapping file, then confirmed interactively unless --yes.
+    let mut remaps = default_state_remaps(&v1_json, &v2_json);
+    let mut supplied = BTreeSet::new();
+    if let Some(path) = mapping_path {
+        let file = match load_mapping_file(path) {
+            Ok(m) => m,
+            Err(e) => {
+                report_error(&e, output, quiet);
+                process::exit(1);
+            }
+        };
+        for (entity_id, states) in file {
+            let remap = match remaps.iter_mut().position(|r| r.entity_id == entity_id) {
+                Some(i) => &mut remaps[i],
+                None => {
+                    remaps.push(EntityStateRemap {
+                        entity_id: entity_id.clone(),
+                        states: BTreeMap::new(),
+                    });
+                    remaps.last_mut().expect("just pushed")
+                }
+            };
+            for (from, to) in states {
+                supplied.insert((entity_id.clone(), from.clone()));
+                remap.states.insert(from, to);
+            }
+        }
+    }
+    if !yes {
+        confirm_removed_state_targets(&mut remaps, &supplied, &v2_json);
+    }
+    if let Err(e) = validate_state_remaps(&v1_json, &v2_json, &remaps) {
+        report_error(&e.to_string(), output, quiet);
+        process::exit(1);
+    }
+    plan.entity_state_mappings = placeholder_mappings(&remaps);
+
+    // 6. Display or serialize
     match output {
         OutputFormat::Json => {
             let json = serde_json::to_string_pretty(&plan)
@@ -72,7 +123,7 @@ pub fn cmd_migrate(v1_path: &Path, v2_path: &Path, yes: bool, output: OutputForm
         }
     }
 
-    // 6. Confirmation logic
+    // 7. Confirmation logic
     if (severity == MigrationSeverity::Breaking || severity == MigrationSeverity::Cautious) && !yes
     {
         eprintln!();
@@ -86,17 +137,128 @@ pub fn cmd_migrate(v1_path: &Path, v2_path: &Path, yes: bool, output: OutputForm
         }
     }
 
-    // 7. Guidance (no live execution in CLI -- requires storage backend)
+    // 8. Write the executable artifact
+    if let Some(path) = out_path {
+        let artifact = MigrationArtifact::new(&plan.v1_id, &plan.v2_id, remaps);
+        let json = serde_json::to_string_pretty(&artifact).expect("artifact serializes");
+        if let Err(e) = std::fs::write(path, format!("{}\n", json)) {
+            let msg = format!("error writing '{}': {}", path.display(), e);
+            report_error(&msg, output, quiet);
+            process::exit(1);
+        }
+        if !quiet && output == OutputFormat::Text {
+            eprintln!();
+            eprintln!("Migration artifact written to {}", path.display());
+        }
+    }
+
+    // 9. Guidance (no live execution in CLI -- requires storage backend)
     if !quiet && output == OutputFormat::Text {
         eprintln!();
         eprintln!("Migration plan complete. Execution requires a storage backend.");
         eprintln!(
             "To execute, use the Tenor API server with `tenor serve` and call the migration endpoint,"
         );
-        eprintln!("or use the SDK's `execute_migration` function with a storage backend.");
+        eprintln!("or use the SDK's `execute_migration_artifact` function with a storage backend.");
     }
 }
 
+/// Load a state mapping file: one table per entity, `old = "new"` entries.
+///
+/// ```toml
+/// [Order]
+/// approved = "submitted"
+/// ```
+fn load_mapping_file(path: &Path) -> Result<BTreeMap<String, BTreeMap<String, String>>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("error reading '{}': {}", path.display(), e))?;
+    toml::from_str(&content).map_err(|e| format!("could not parse '{}': {}", path.display(), e))
+}
+
+/// Prompt for the target of every removed state the mapping file did not
+/// cover. An empty answer accepts the suggested target.
+fn confirm_removed_state_targets(
+    remaps: &mut [EntityStateRemap],
+    supplied: &BTreeSet<(String, String)>,
+    v2_bundle: &serde_json::Value,
+) {
+    let v2_states = entity_state_sets(v2_bundle);
+    let mut header_shown = false;
+    for remap in remaps.iter_mut() {
+        let Some(targets) = v2_states.get(&remap.entity_id) else {
+            continue;
+        };
+        for (from, to) in remap.states.iter_mut() {
+            if targets.contains(from) || supplied.contains(&(remap.entity_id.clone(), from.clone()))
+            {
+                continue;
+            }
+            if !header_shown {
+                eprintln!();
+                eprintln!("Map removed entity states to v2 states (Enter accepts the suggestion):");
+                header_shown = true;
+            }
+            eprint!(
+                "  {}.{} -> [{}] (one of: {}): ",
+                remap.entity_id,
+                from,
+                to,
+                targets.iter().cloned().collect::<Vec<_>>().join(", ")
+            );
+            let _ = std::io::stderr().flush();
+            let mut input = String::new();
+            if std::io::stdin().read_line(&mut input).is_err() {
+                eprintln!("Migration aborted.");
+                process::exit(1);
+            }
+            let answer = input.trim();
+            if !answer.is_empty() {
+                *to = answer.to_string();
+            }
+        }
+    }
+}
+
+/// Entity id -> declared states for every entity in a bundle.
+fn entity_state_sets(bundle: &serde_json::Value) -> BTreeMap<String, BTreeSet<String>> {
+    bundle
+        .get("constructs")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|c| c.get("kind").and_then(|k| k.as_str()) == Some("Entity"))
+        .filter_map(|c| {
+            let id = c.get("id")?.as_str()?.to_string();
+            let states = c
+                .get("states")?
+                .as_array()?
+                .iter()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect();
+            Some((id, states))
+        })
+        .collect()
+}
+
+/// Plan-level state mappings for every state that moves. Instances are
+/// resolved against live records at execution time.
+fn placeholder_mappings(remaps: &[EntityStateRemap]) -> Vec<EntityStateMapping> {
+    remaps
+        .iter()
+        .flat_map(|r| {
+            r.states
+                .iter()
+                .filter(|(from, to)| from != to)
+                .map(|(from, to)| EntityStateMapping {
+                    entity_id: r.entity_id.clone(),
+                    instance_id: String::new(),
+                    from_state: from.clone(),
+                    to_state: to.clone(),
+                })
+        })
+        .collect()
+}
+
 /// Load a bundle from a .tenor or .json file.
 fn load_interchange_bundle(path: &Path, output: OutputFormat, quiet: bool) -> serde_json::Value {
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
@@ -104,7 +266,7 @@ fn load_interchange_bundle(path: &Path, output: OutputFormat, quiet: bool) -> se
     match ext {
         "tenor" => {
             // Elaborate .tenor -> interchange JSON
-            match tenor_core::elaborate::elaborate(path) {
+            match crate::elaborate(path) {
                 Ok(bundle) => bundle,
                 Err(e) => {
                     let msg = format!("elaboration error for '{}': {:?}", path.display(), e);
@@ -208,8 +370,8 @@ fn display_plan_text(plan: &tenor_eval::migration::MigrationPlan) {
         );
         for mapping in &plan.entity_state_mappings {
             println!(
-                "  - {}/{}: {} -> {}",
-                mapping.entity_id, mapping.instance_id, mapping.from_state, mapping.to_state
+                "  - {}: {} -> {}",
+                mapping.entity_id, mapping.from_state, mapping.to_state
             );
         }
         println!();
@@ -0,0 +1,204 @@
+//! Bundle optimization for deployment (`tenor optimize`).
+//!
+//! Produces a smaller bundle that loads faster, at the cost of no longer
+//! being canonical Pass 6 output:
+//! - repeated parameterized TypeSpecs are hoisted into a type table
+//!   (see `tenor_interchange::type_table`);
+//! - constructs are put back into canonical order, so a hand-edited or
+//!   merged bundle parses in a single ordered pass;
+//! - optionally, construct provenance is stripped.
+//!
+//! The optimized bundle is written compact (no indentation).
+
+use std::collections::BTreeMap;
+
+/// What an optimization run changed.
+#[derive(Debug, Default)]
+pub(crate) struct OptimizeReport {
+    /// Compact JSON size before optimization, in bytes.
+    pub(crate) input_bytes: usize,
+    /// Compact JSON size after optimization, in bytes.
+    pub(crate) output_bytes: usize,
+    /// Construct count per kind.
+    pub(crate) constructs: BTreeMap<String, usize>,
+    /// Distinct TypeSpecs hoisted into the type table.
+    pub(crate) type_table_entries: usize,
+    /// TypeSpec occurrences replaced by a `type_ref`.
+    pub(crate) type_refs: usize,
+    /// Constructs whose provenance was removed.
+    pub(crate) provenance_stripped: usize,
+    /// Whether the constructs were out of canonical order.
+    pub(crate) reordered: bool,
+}
+
+impl OptimizeReport {
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "input_bytes": self.input_bytes,
+            "output_bytes": self.output_bytes,
+            "constructs": self.constructs,
+            "type_table_entries": self.type_table_entries,
+            "type_refs": self.type_refs,
+            "provenance_stripped": self.provenance_stripped,
+            "reordered": self.reordered,
+        })
+    }
+
+    pub(crate) fn to_text(&self) -> String {
+        let saved = self.input_bytes.saturating_sub(self.output_bytes);
+        let percent = if self.input_bytes == 0 {
+            0.0
+        } else {
+            saved as f64 * 100.0 / self.input_bytes as f64
+        };
+        let mut out = format!(
+            "size: {} -> {} bytes ({:.1}% smaller)\n",
+            self.input_bytes, self.output_bytes, percent
+        );
+        let total: usize = self.constructs.values().sum();
+        out.push_str(&format!("constructs: {}\n", total));
+        for (kind, count) in &self.constructs {
+            out.push_str(&format!("  {:<10} {}\n", kind, count));
+        }
+        out.push_str(&format!(
+            "type table: {} entries replacing {} occurrences\n",
+            self.type_table_entries, self.type_refs
+        ));
+        out.push_str(&format!(
+            "provenance stripped: {} constructs\n",
+            self.provenance_stripped
+        ));
+        out.push_str(&format!(
+            "reordered: {}\n",
+            if self.reordered { "yes" } else { "no" }
+        ));
+        out
+    }
+}
+
+/// Optimize `bundle` in place and report what changed.
+pub(crate) fn optimize(
+    bundle: &mut serde_json::Value,
+    strip_provenance: bool,
+) -> Result<OptimizeReport, String> {
+    let mut report = OptimizeReport {
+        input_bytes: compact_len(bundle),
+        ..OptimizeReport::default()
+    };
+    if bundle
+        .get(tenor_interchange::type_table::TYPE_TABLE_KEY)
+        .is_some()
+    {
+        return Err("bundle is already optimized (it has a type table)".to_string());
+    }
+    let constructs = bundle
+        .get_mut("constructs")
+        .and_then(|c| c.as_array_mut())
+        .ok_or("not an interchange bundle: missing 'constructs' array")?;
+
+    for c in constructs.iter() {
+        let kind = c.get("kind").and_then(|k| k.as_str()).unwrap_or("unknown");
+        *report.constructs.entry(kind.to_string()).or_default() += 1;
+    }
+
+    let before: Vec<(u8, i64, String)> = constructs.iter().map(canonical_key).collect();
+    constructs.sort_by_cached_key(canonical_key);
+    report.reordered = constructs.iter().map(canonical_key).ne(before);
+
+    if strip_provenance {
+        for c in constructs.iter_mut() {
+            if let Some(obj) = c.as_object_mut() {
+                if obj.remove("provenance").is_some() {
+                    report.provenance_stripped += 1;
+                }
+            }
+        }
+    }
+
+    report.type_refs = tenor_interchange::extract_type_table(bundle);
+    report.type_table_entries = bundle
+        .get(tenor_interchange::type_table::TYPE_TABLE_KEY)
+        .and_then(|t| t.as_array())
+        .map_or(0, |t| t.len());
+    report.output_bytes = compact_len(bundle);
+    Ok(report)
+}
+
+/// Sort key reproducing Pass 6 construct order: kind, then stratum for
+/// rules, then id.
+fn canonical_key(c: &serde_json::Value) -> (u8, i64, String) {
+    let rank = match c.get("kind").and_then(|k| k.as_str()) {
+        Some("Persona") => 0,
+        Some("Source") => 1,
+        Some("Fact") => 2,
+        Some("Entity") => 3,
+        Some("Rule") => 4,
+        Some("Operation") => 5,
+        Some("Flow") => 6,
+        Some("System") => 7,
+        _ => 8,
+    };
+    (
+        rank,
+        c.get("stratum").and_then(|s| s.as_i64()).unwrap_or(0),
+        c.get("id")
+            .and_then(|i| i.as_str())
+            .unwrap_or("")
+            .to_string(),
+    )
+}
+
+fn compact_len(v: &serde_json::Value) -> usize {
+    serde_json::to_vec(v).map_or(0, |b| b.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle() -> serde_json::Value {
+        let money = json!({"base": "Money", "currency": "USD"});
+        json!({
+            "id": "b",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": [
+                {"id": "r1", "kind": "Rule", "stratum": 1, "provenance": {"file": "b.tenor", "line": 9}},
+                {"id": "r0", "kind": "Rule", "stratum": 0, "provenance": {"file": "b.tenor", "line": 7}},
+                {"id": "total", "kind": "Fact", "type": money.clone(), "provenance": {"file": "b.tenor", "line": 4}},
+                {"id": "limit", "kind": "Fact", "type": money, "provenance": {"file": "b.tenor", "line": 1}}
+            ]
+        })
+    }
+
+    #[test]
+    fn optimize_sorts_strips_and_hoists() {
+        let mut b = bundle();
+        let report = optimize(&mut b, true).unwrap();
+        let ids: Vec<&str> = b["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["limit", "total", "r0", "r1"]);
+        assert!(report.reordered);
+        assert_eq!(report.provenance_stripped, 4);
+        assert_eq!(report.type_refs, 2);
+        assert_eq!(report.type_table_entries, 1);
+        assert_eq!(report.constructs["Rule"], 2);
+        assert!(report.output_bytes < report.input_bytes);
+        assert_eq!(b["requires"], json!(["type-table"]));
+    }
+
+    #[test]
+    fn optimize_keeps_provenance_unless_asked() {
+        let mut b = bundle();
+        let report = optimize(&mut b, false).unwrap();
+        assert_eq!(report.provenance_stripped, 0);
+        assert!(b["constructs"][0].get("provenance").is_some());
+        assert!(optimize(&mut b, false).is_err(), "second run is rejected");
+    }
+}
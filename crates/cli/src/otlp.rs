@@ -0,0 +1,483 @@
+//! OTLP/JSON trace export for flow executions.
+//!
+//! Converts a completed flow evaluation into an OpenTelemetry trace file
+//! (OTLP/JSON encoding) that can be loaded into Jaeger, Tempo, or any other
+//! OTLP-compatible viewer without a live collector.
+//!
+//! Span tree:
+//!
+//! ```text
+//! flow <flow_id>
+//! ├── fetch facts            (fact loading)
+//! ├── step <step_id>         (one per executed step)
+//! ├── step <parallel_id>
+//! │   ├── branch <branch_id>
+//! │   │   └── step <step_id>
+//! │   └── branch <branch_id>
+//! └── ...
+//! ```
+//!
+//! The evaluator does not time individual steps, so step spans are laid out
+//! sequentially across the measured evaluation window. Parallel branch
+//! membership is recovered from the contract's flow definition.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tenor_eval::types::{Flow, FlowStep};
+use tenor_eval::{Contract, FlowEvalResult, StepRecord};
+
+/// OTLP span kind: SPAN_KIND_INTERNAL.
+const SPAN_KIND_INTERNAL: u64 = 1;
+/// OTLP status code: STATUS_CODE_UNSET.
+const STATUS_UNSET: u64 = 0;
+/// OTLP status code: STATUS_CODE_ERROR.
+const STATUS_ERROR: u64 = 2;
+
+/// A measured wall-clock interval in Unix nanoseconds.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Window {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Window {
+    /// Start a window at the current time. Call [`Window::finish`] to close it.
+    pub(crate) fn begin() -> Self {
+        let now = now_nanos();
+        Window {
+            start: now,
+            end: now,
+        }
+    }
+
+    /// Close the window at the current time.
+    pub(crate) fn finish(mut self) -> Self {
+        self.end = now_nanos().max(self.start);
+        self
+    }
+}
+
+/// Everything the exporter needs to describe one flow execution.
+pub(crate) struct FlowTrace<'a> {
+    pub flow_id: &'a str,
+    pub persona: &'a str,
+    pub contract: &'a Contract,
+    pub result: &'a FlowEvalResult,
+    /// Where facts were loaded from (file path or adapter name).
+    pub facts_source: &'a str,
+    pub fact_count: usize,
+    /// Time spent loading and parsing facts.
+    pub fetch: Window,
+    /// Time spent in rule evaluation and flow execution.
+    pub eval: Window,
+}
+
+/// Intermediate span representation before OTLP encoding.
+struct Span {
+    span_id: String,
+    parent_span_id: Option<String>,
+    name: String,
+    start: u64,
+    end: u64,
+    attributes: Vec<(String, Value)>,
+    events: Vec<Value>,
+    error: Option<String>,
+}
+
+/// Build the OTLP/JSON `ExportTraceServiceRequest` document for a flow trace.
+pub(crate) fn export_flow_trace(trace: &FlowTrace<'_>) -> Value {
+    let trace_id = derive_id(
+        &format!("{}:{}:{}", trace.flow_id, trace.persona, trace.eval.start),
+        16,
+    );
+    let mut next_index = 0usize;
+    let mut new_span_id = || {
+        next_index += 1;
+        derive_id(&format!("{}:{}", trace_id, next_index), 8)
+    };
+
+    let flow_result = &trace.result.flow_result;
+    let root_id = new_span_id();
+    let mut spans = Vec::new();
+
+    // Root span covers fact loading through the end of flow execution.
+    let mut root = Span {
+        span_id: root_id.clone(),
+        parent_span_id: None,
+        name: format!("flow {}", trace.flow_id),
+        start: trace.fetch.start.min(trace.eval.start),
+        end: trace.eval.end.max(trace.fetch.end),
+        attributes: vec![
+            attr("tenor.flow.id", trace.flow_id),
+            attr("tenor.persona", trace.persona),
+            attr("tenor.flow.outcome", &flow_result.outcome),
+            attr_int("tenor.verdict.count", trace.result.verdicts.0.len()),
+            attr_int("tenor.step.count", flow_result.steps_executed.len()),
+        ],
+        events: Vec::new(),
+        error: None,
+    };
+    for change in &flow_result.entity_state_changes {
+        root.events.push(json!({
+            "timeUnixNano": trace.eval.end.to_string(),
+            "name": "entity_state_change",
+            "attributes": encode_attributes(&[
+                attr("tenor.entity.id", &change.entity_id),
+                attr("tenor.entity.instance_id", &change.instance_id),
+                attr("tenor.entity.from", &change.from_state),
+                attr("tenor.entity.to", &change.to_state),
+            ]),
+        }));
+    }
+    spans.push(root);
+
+    spans.push(Span {
+        span_id: new_span_id(),
+        parent_span_id: Some(root_id.clone()),
+        name: "fetch facts".to_string(),
+        start: trace.fetch.start,
+        end: trace.fetch.end,
+        attributes: vec![
+            attr("tenor.facts.source", trace.facts_source),
+            attr_int("tenor.facts.count", trace.fact_count),
+        ],
+        events: Vec::new(),
+        error: None,
+    });
+
+    // Lay steps out sequentially across the evaluation window.
+    let records = &flow_result.steps_executed;
+    let slot = if records.is_empty() {
+        0
+    } else {
+        (trace.eval.end - trace.eval.start) / records.len() as u64
+    };
+
+    let flow = trace.contract.get_flow(trace.flow_id);
+    let branch_of = flow.map(branch_membership).unwrap_or_default();
+
+    // Branch spans opened by parallel steps, keyed by (parallel_step_id, branch_id).
+    let mut branch_spans: HashMap<(String, String), usize> = HashMap::new();
+    let mut parallel_spans: HashMap<String, usize> = HashMap::new();
+
+    for (i, record) in records.iter().enumerate() {
+        let start = trace.eval.start + slot * i as u64;
+        let end = start + slot;
+
+        let parent = match branch_of.get(record.step_id.as_str()) {
+            Some(key) => match branch_spans.get(key) {
+                Some(&idx) => {
+                    // Stretch the branch and its parallel step to cover this step.
+                    spans[idx].end = spans[idx].end.max(end);
+                    if let Some(&pidx) = parallel_spans.get(&key.0) {
+                        spans[pidx].end = spans[pidx].end.max(end);
+                    }
+                    spans[idx].span_id.clone()
+                }
+                None => root_id.clone(),
+            },
+            None => root_id.clone(),
+        };
+
+        let span_id = new_span_id();
+        let mut span = step_span(record, span_id.clone(), parent, start, end);
+        if record.step_type == "sub_flow" {
+            if let Some(sub_flow_id) = flow.and_then(|f| sub_flow_target(f, &record.step_id)) {
+                span.attributes
+                    .push(attr("tenor.sub_flow.id", &sub_flow_id));
+            }
+        }
+        spans.push(span);
+
+        if record.step_type == "parallel" {
+            parallel_spans.insert(record.step_id.to_string(), spans.len() - 1);
+            for (branch_id, outcome) in parse_branch_summaries(&record.result) {
+                let error = outcome.strip_prefix("error:").map(|e| e.to_string());
+                spans.push(Span {
+                    span_id: new_span_id(),
+                    parent_span_id: Some(span_id.clone()),
+                    name: format!("branch {}", branch_id),
+                    start: end,
+                    end,
+                    attributes: vec![
+                        attr("tenor.branch.id", &branch_id),
+                        attr("tenor.branch.outcome", &outcome),
+                    ],
+                    events: Vec::new(),
+                    error,
+                });
+                branch_spans.insert((record.step_id.to_string(), branch_id), spans.len() - 1);
+            }
+        }
+    }
+
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": encode_attributes(&[
+                    attr("service.name", "tenor"),
+                    attr("service.version", env!("CARGO_PKG_VERSION")),
+                ]),
+            },
+            "scopeSpans": [{
+                "scope": {
+                    "name": "tenor-eval",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "spans": spans
+                    .iter()
+                    .map(|s| encode_span(s, &trace_id))
+                    .collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+/// Build the span for one executed step record.
+fn step_span(record: &StepRecord, span_id: String, parent: String, start: u64, end: u64) -> Span {
+    let mut attributes = vec![
+        attr("tenor.step.id", &record.step_id),
+        attr("tenor.step.type", &record.step_type),
+        attr("tenor.step.result", &record.result),
+    ];
+    for (entity_id, instance_id) in &record.instance_bindings {
+        attributes.push(attr(&format!("tenor.instance.{}", entity_id), instance_id));
+    }
+    let error = if record.result == "error" || record.result.starts_with("error:") {
+        Some(record.result.clone())
+    } else {
+        None
+    };
+    Span {
+        span_id,
+        parent_span_id: Some(parent),
+        name: format!("step {}", record.step_id),
+        start,
+        end,
+        attributes,
+        events: Vec::new(),
+        error,
+    }
+}
+
+/// Map every step inside a parallel branch to its (parallel_step_id, branch_id).
+fn branch_membership(flow: &Flow) -> HashMap<String, (String, String)> {
+    let mut out = HashMap::new();
+    collect_branch_membership(&flow.steps, &mut out);
+    out
+}
+
+fn collect_branch_membership(steps: &[FlowStep], out: &mut HashMap<String, (String, String)>) {
+    for step in steps {
+        if let FlowStep::ParallelStep { id, branches, .. } = step {
+            for branch in branches {
+                for inner in &branch.steps {
+                    out.insert(
+                        flow_step_id(inner).to_string(),
+                        (id.clone(), branch.id.clone()),
+                    );
+                }
+                collect_branch_membership(&branch.steps, out);
+            }
+        }
+    }
+}
+
+/// Find the sub-flow targeted by a SubFlowStep, searching nested branches.
+fn sub_flow_target(flow: &Flow, step_id: &str) -> Option<String> {
+    fn search(steps: &[FlowStep], step_id: &str) -> Option<String> {
+        steps.iter().find_map(|step| match step {
+            FlowStep::SubFlowStep { id, flow, .. } if id == step_id => Some(flow.clone()),
+            FlowStep::ParallelStep { branches, .. } => {
+                branches.iter().find_map(|b| search(&b.steps, step_id))
+            }
+            _ => None,
+        })
+    }
+    search(&flow.steps, step_id)
+}
+
+fn flow_step_id(step: &FlowStep) -> &str {
+    match step {
+        FlowStep::OperationStep { id, .. }
+        | FlowStep::BranchStep { id, .. }
+        | FlowStep::HandoffStep { id, .. }
+        | FlowStep::SubFlowStep { id, .. }
+        | FlowStep::ParallelStep { id, .. } => id,
+    }
+}
+
+/// Parse a parallel step result of the form `b1:outcome, b2:error:msg`.
+fn parse_branch_summaries(result: &str) -> Vec<(String, String)> {
+    result
+        .split(", ")
+        .filter_map(|part| {
+            part.split_once(':')
+                .map(|(b, o)| (b.to_string(), o.to_string()))
+        })
+        .collect()
+}
+
+fn encode_span(span: &Span, trace_id: &str) -> Value {
+    let status = match &span.error {
+        Some(message) => json!({ "code": STATUS_ERROR, "message": message }),
+        None => json!({ "code": STATUS_UNSET }),
+    };
+    json!({
+        "traceId": trace_id,
+        "spanId": span.span_id,
+        "parentSpanId": span.parent_span_id.clone().unwrap_or_default(),
+        "name": span.name,
+        "kind": SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": span.start.to_string(),
+        "endTimeUnixNano": span.end.to_string(),
+        "attributes": encode_attributes(&span.attributes),
+        "events": span.events,
+        "status": status,
+    })
+}
+
+fn encode_attributes(attrs: &[(String, Value)]) -> Value {
+    // BTreeMap keeps attribute order stable across runs.
+    let sorted: BTreeMap<&str, &Value> = attrs.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    Value::Array(
+        sorted
+            .into_iter()
+            .map(|(k, v)| json!({ "key": k, "value": v }))
+            .collect(),
+    )
+}
+
+fn attr(key: &str, value: &str) -> (String, Value) {
+    (key.to_string(), json!({ "stringValue": value }))
+}
+
+fn attr_int(key: &str, value: usize) -> (String, Value) {
+    // OTLP/JSON encodes 64-bit integers as strings.
+    (key.to_string(), json!({ "intValue": value.to_string() }))
+}
+
+/// Derive a hex ID of `bytes` length from a seed string.
+fn derive_id(seed: &str, bytes: usize) -> String {
+    Sha256::digest(seed.as_bytes())
+        .iter()
+        .take(bytes)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn now_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn run_parallel_fixture() -> (Contract, FlowEvalResult) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../conformance/eval/positive/parallel_step.tenor");
+        let bundle = tenor_core::elaborate::elaborate(&path).expect("elaborate fixture");
+        let contract = Contract::from_interchange(&bundle).expect("contract");
+        let facts = json!({ "is_ready": true });
+        let result = tenor_eval::evaluate_flow(
+            &bundle,
+            &facts,
+            "parallel_process",
+            "system",
+            None,
+            &tenor_eval::InstanceBindingMap::new(),
+        )
+        .expect("flow evaluation");
+        (contract, result)
+    }
+
+    fn trace_for(contract: &Contract, result: &FlowEvalResult) -> Value {
+        export_flow_trace(&FlowTrace {
+            flow_id: "parallel_process",
+            persona: "system",
+            contract,
+            result,
+            facts_source: "facts.json",
+            fact_count: 1,
+            fetch: Window {
+                start: 1_000,
+                end: 2_000,
+            },
+            eval: Window {
+                start: 2_000,
+                end: 10_000,
+            },
+        })
+    }
+
+    fn spans(doc: &Value) -> &Vec<Value> {
+        doc["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap()
+    }
+
+    fn span_named<'a>(doc: &'a Value, name: &str) -> &'a Value {
+        spans(doc)
+            .iter()
+            .find(|s| s["name"] == name)
+            .unwrap_or_else(|| panic!("span '{}' not found", name))
+    }
+
+    #[test]
+    fn root_span_carries_flow_attributes() {
+        let (contract, result) = run_parallel_fixture();
+        let doc = trace_for(&contract, &result);
+        let root = span_named(&doc, "flow parallel_process");
+        assert_eq!(root["parentSpanId"], "");
+        assert_eq!(root["startTimeUnixNano"], "1000");
+        assert_eq!(root["endTimeUnixNano"], "10000");
+        let attrs = root["attributes"].as_array().unwrap();
+        assert!(attrs
+            .iter()
+            .any(|a| a["key"] == "tenor.flow.outcome" && a["value"]["stringValue"] == "completed"));
+        assert_eq!(root["events"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn parallel_branch_steps_nest_under_branch_spans() {
+        let (contract, result) = run_parallel_fixture();
+        let doc = trace_for(&contract, &result);
+        let par = span_named(&doc, "step par_step");
+        let branch = span_named(&doc, "branch branch_fulfill");
+        let step = span_named(&doc, "step step_fulfill");
+        assert_eq!(branch["parentSpanId"], par["spanId"]);
+        assert_eq!(step["parentSpanId"], branch["spanId"]);
+        assert_eq!(branch["endTimeUnixNano"], step["endTimeUnixNano"]);
+    }
+
+    #[test]
+    fn all_spans_share_trace_id_and_unique_span_ids() {
+        let (contract, result) = run_parallel_fixture();
+        let doc = trace_for(&contract, &result);
+        let all = spans(&doc);
+        let trace_id = all[0]["traceId"].as_str().unwrap();
+        assert_eq!(trace_id.len(), 32);
+        let mut ids = std::collections::HashSet::new();
+        for s in all {
+            assert_eq!(s["traceId"], trace_id);
+            assert_eq!(s["spanId"].as_str().unwrap().len(), 16);
+            assert!(ids.insert(s["spanId"].as_str().unwrap().to_string()));
+        }
+        assert!(all.iter().any(|s| s["name"] == "fetch facts"));
+    }
+
+    #[test]
+    fn parse_branch_summaries_keeps_error_detail() {
+        let parsed = parse_branch_summaries("a:done, b:error:boom");
+        assert_eq!(parsed[0], ("a".to_string(), "done".to_string()));
+        assert_eq!(parsed[1], ("b".to_string(), "error:boom".to_string()));
+    }
+}
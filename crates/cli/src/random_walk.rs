@@ -0,0 +1,376 @@
+//! Deterministic random sampling of flow execution paths
+//! (`tenor simulate --random-walk`).
+//!
+//! Each walk synthesizes a fact set, checks that the flow is admissible
+//! for its entry persona (entry operation authorized, precondition met,
+//! entities in a source state), then executes it from the initial entity
+//! states. Fact values are drawn from the literals each fact is compared
+//! against in rules, preconditions and branch conditions (and their
+//! neighbours), mixed with values spread over the declared type, so both
+//! sides of most conditions get exercised. The same seed always yields the
+//! same walks.
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tenor_eval::types::{FlowStep, Predicate, TypeSpec};
+use tenor_eval::Contract;
+
+/// Fresh fact sets tried per walk before it counts as inadmissible.
+const MAX_ATTEMPTS: usize = 32;
+
+/// Outcome distribution of the sampled walks of one flow.
+#[derive(Debug, Serialize)]
+pub struct FlowSample {
+    pub flow_id: String,
+    pub persona: String,
+    pub walks: usize,
+    /// Walks for which no admissible fact set was found.
+    pub inadmissible: usize,
+    /// Walks whose flow evaluation failed.
+    pub errors: usize,
+    /// Flow outcome → number of walks.
+    pub outcomes: BTreeMap<String, usize>,
+    /// Executed steps (`step_id:result`, in order) → number of walks.
+    pub paths: BTreeMap<String, usize>,
+}
+
+/// Sample `count` walks of each flow (or only `flow_id`) from `seed`.
+pub fn random_walk(
+    contract: &Contract,
+    seed: u64,
+    count: usize,
+    flow_id: Option<&str>,
+) -> Result<Vec<FlowSample>, String> {
+    if let Some(id) = flow_id {
+        if contract.get_flow(id).is_none() {
+            return Err(format!("flow '{}' not found in contract", id));
+        }
+    }
+
+    let candidates = literal_candidates(contract);
+    let initial_states = tenor_eval::operation::init_entity_states(contract);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut samples = Vec::new();
+
+    for flow in &contract.flows {
+        if flow_id.is_some_and(|id| id != flow.id) {
+            continue;
+        }
+        let persona = entry_persona(contract, &flow.id);
+        let mut sample = FlowSample {
+            flow_id: flow.id.clone(),
+            persona: persona.clone(),
+            walks: count,
+            inadmissible: 0,
+            errors: 0,
+            outcomes: BTreeMap::new(),
+            paths: BTreeMap::new(),
+        };
+
+        for _ in 0..count {
+            let admissible = (0..MAX_ATTEMPTS).find_map(|_| {
+                let facts = synthesize_facts(contract, &candidates, &mut rng);
+                let space =
+                    tenor_eval::compute_action_space(contract, &facts, &initial_states, &persona)
+                        .ok()?;
+                space
+                    .actions
+                    .iter()
+                    .any(|a| a.flow_id == flow.id)
+                    .then_some(facts)
+            });
+            let Some(facts) = admissible else {
+                sample.inadmissible += 1;
+                continue;
+            };
+
+            match tenor_eval::evaluate_contract_flow(
+                contract,
+                &facts,
+                &flow.id,
+                &persona,
+                None,
+                &tenor_eval::InstanceBindingMap::new(),
+            ) {
+                Ok(result) => {
+                    let path: Vec<String> = result
+                        .flow_result
+                        .steps_executed
+                        .iter()
+                        .map(|s| format!("{}:{}", s.step_id, s.result))
+                        .collect();
+                    *sample
+                        .outcomes
+                        .entry(result.flow_result.outcome)
+                        .or_default() += 1;
+                    *sample.paths.entry(path.join(" -> ")).or_default() += 1;
+                }
+                Err(_) => sample.errors += 1,
+            }
+        }
+        samples.push(sample);
+    }
+    Ok(samples)
+}
+
+/// The persona of the flow's entry step, or the first declared persona.
+pub fn entry_persona(contract: &Contract, flow_id: &str) -> String {
+    let entry_step = contract.get_flow(flow_id).and_then(|flow| {
+        flow.steps.iter().find_map(|step| match step {
+            FlowStep::OperationStep { id, persona, .. } if *id == flow.entry => {
+                Some(persona.clone())
+            }
+            FlowStep::BranchStep { id, persona, .. } if *id == flow.entry => Some(persona.clone()),
+            _ => None,
+        })
+    });
+    entry_step
+        .or_else(|| contract.personas.first().cloned())
+        .unwrap_or_default()
+}
+
+// ──────────────────────────────────────────────
+// Fact synthesis
+// ──────────────────────────────────────────────
+
+/// Literals each fact is compared against, per fact id.
+type Candidates = BTreeMap<String, Vec<tenor_eval::Value>>;
+
+fn literal_candidates(contract: &Contract) -> Candidates {
+    let mut candidates = Candidates::new();
+    for rule in &contract.rules {
+        collect_literals(&rule.condition, &mut candidates);
+    }
+    for op in &contract.operations {
+        collect_literals(&op.precondition, &mut candidates);
+    }
+    for flow in &contract.flows {
+        collect_step_literals(&flow.steps, &mut candidates);
+    }
+    candidates
+}
+
+fn collect_step_literals(steps: &[FlowStep], candidates: &mut Candidates) {
+    for step in steps {
+        match step {
+            FlowStep::BranchStep { condition, .. } => collect_literals(condition, candidates),
+            FlowStep::ParallelStep { branches, .. } => {
+                for branch in branches {
+                    collect_step_literals(&branch.steps, candidates);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_literals(pred: &Predicate, candidates: &mut Candidates) {
+    match pred {
+        Predicate::Compare { left, right, .. } => {
+            match (left.as_ref(), right.as_ref()) {
+                (Predicate::FactRef(id), Predicate::Literal { value, .. })
+                | (Predicate::Literal { value, .. }, Predicate::FactRef(id)) => {
+                    candidates
+                        .entry(id.clone())
+                        .or_default()
+                        .push(value.clone());
+                }
+                _ => {}
+            }
+            collect_literals(left, candidates);
+            collect_literals(right, candidates);
+        }
+        Predicate::And { left, right } | Predicate::Or { left, right } => {
+            collect_literals(left, candidates);
+            collect_literals(right, candidates);
+        }
+        Predicate::Not { operand } => collect_literals(operand, candidates),
+        Predicate::Forall { domain, body, .. } | Predicate::Exists { domain, body, .. } => {
+            collect_literals(domain, candidates);
+            collect_literals(body, candidates);
+        }
+        _ => {}
+    }
+}
+
+/// A facts-file object with a value for every declared fact. Facts with a
+/// default are left out half the time.
+fn synthesize_facts(contract: &Contract, candidates: &Candidates, rng: &mut StdRng) -> Value {
+    let mut facts = Map::new();
+    for decl in &contract.facts {
+        if decl.default.is_some() && rng.gen_bool(0.5) {
+            continue;
+        }
+        let literals = candidates
+            .get(&decl.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        facts.insert(decl.id.clone(), synthesize(&decl.fact_type, literals, rng));
+    }
+    Value::Object(facts)
+}
+
+fn synthesize(spec: &TypeSpec, literals: &[tenor_eval::Value], rng: &mut StdRng) -> Value {
+    // Numeric literals and their neighbours probe both sides of a comparison.
+    let numbers: Vec<f64> = literals
+        .iter()
+        .filter_map(|v| match v {
+            tenor_eval::Value::Int(i) => Some(*i as f64),
+            tenor_eval::Value::Decimal(d) => d.to_string().parse().ok(),
+            tenor_eval::Value::Money { amount, .. } => amount.to_string().parse().ok(),
+            _ => None,
+        })
+        .flat_map(|n| [n - 1.0, n, n + 1.0])
+        .collect();
+    let number = |rng: &mut StdRng, lo: f64, hi: f64| -> f64 {
+        match numbers.choose(rng) {
+            Some(n) if rng.gen_bool(0.75) => n.clamp(lo, hi),
+            _ => rng.gen_range(lo..=hi).round(),
+        }
+    };
+    let text_literal = |rng: &mut StdRng| {
+        let texts: Vec<&str> = literals
+            .iter()
+            .filter_map(|v| match v {
+                tenor_eval::Value::Text(s)
+                | tenor_eval::Value::Date(s)
+                | tenor_eval::Value::DateTime(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect();
+        texts.choose(rng).map(|s| s.to_string())
+    };
+
+    match spec.base.as_str() {
+        "Bool" => json!(rng.gen_bool(0.5)),
+        "Int" => {
+            let lo = spec.min.map(|m| m as f64).unwrap_or(-100.0);
+            let hi = spec.max.map(|m| m as f64).unwrap_or(1000.0);
+            json!(number(rng, lo, hi) as i64)
+        }
+        "Decimal" => {
+            let scale = spec.scale.unwrap_or(2) as usize;
+            json!(format!("{:.*}", scale, number(rng, -100.0, 1000.0)))
+        }
+        "Money" => json!({
+            "amount": format!("{:.2}", number(rng, 0.0, 100000.0)),
+            "currency": spec.currency.as_deref().unwrap_or("USD"),
+        }),
+        "Text" => {
+            let text =
+                text_literal(rng).unwrap_or_else(|| format!("sample-{}", rng.gen_range(0..100)));
+            let max = spec.max_length.map(|m| m as usize).unwrap_or(text.len());
+            json!(text.chars().take(max).collect::<String>())
+        }
+        "Date" => match text_literal(rng) {
+            Some(date) if rng.gen_bool(0.5) => json!(date),
+            _ => json!(format!(
+                "{}-{:02}-{:02}",
+                rng.gen_range(2020..=2030),
+                rng.gen_range(1..=12),
+                rng.gen_range(1..=28)
+            )),
+        },
+        "DateTime" => match text_literal(rng) {
+            Some(dt) if rng.gen_bool(0.5) => json!(dt),
+            _ => json!(format!(
+                "{}-{:02}-{:02}T{:02}:00:00Z",
+                rng.gen_range(2020..=2030),
+                rng.gen_range(1..=12),
+                rng.gen_range(1..=28),
+                rng.gen_range(0..24)
+            )),
+        },
+        "Duration" => json!({
+            "value": rng.gen_range(0..=100),
+            "unit": spec.unit.as_deref().unwrap_or("seconds"),
+        }),
+        "Enum" => {
+            let values = spec.values.as_deref().unwrap_or_default();
+            json!(values.choose(rng).cloned().unwrap_or_default())
+        }
+        "Record" => {
+            let fields = spec.fields.iter().flatten();
+            Value::Object(
+                fields
+                    .map(|(name, ft)| (name.clone(), synthesize(ft, &[], rng)))
+                    .collect(),
+            )
+        }
+        "List" => {
+            let len = rng.gen_range(0..=3);
+            match &spec.element_type {
+                Some(et) => Value::Array((0..len).map(|_| synthesize(et, &[], rng)).collect()),
+                None => json!([]),
+            }
+        }
+        "TaggedUnion" => {
+            let variants: Vec<(&String, &TypeSpec)> = spec.variants.iter().flatten().collect();
+            match variants.choose(rng) {
+                Some((tag, payload)) => json!({
+                    "tag": tag,
+                    "payload": synthesize(payload, &[], rng),
+                }),
+                None => Value::Null,
+            }
+        }
+        _ => Value::Null,
+    }
+}
+
+// ──────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tenor_eval::test_support::flow_basic;
+
+    #[test]
+    fn same_seed_same_walks() {
+        let contract = flow_basic();
+        let a = random_walk(&contract, 7, 20, None).unwrap();
+        let b = random_walk(&contract, 7, 20, None).unwrap();
+        assert_eq!(
+            serde_json::to_value(&a).unwrap(),
+            serde_json::to_value(&b).unwrap()
+        );
+    }
+
+    #[test]
+    fn walks_reach_both_branch_outcomes() {
+        let contract = flow_basic();
+        let samples = random_walk(&contract, 1, 50, Some("order_approval")).unwrap();
+        assert_eq!(samples.len(), 1);
+        let sample = &samples[0];
+        assert_eq!(sample.persona, "buyer");
+        assert_eq!(
+            sample.outcomes.values().sum::<usize>() + sample.inadmissible + sample.errors,
+            50
+        );
+        // requires_review picks the branch, so more than one path appears
+        assert!(sample.paths.len() > 1, "paths: {:?}", sample.paths);
+    }
+
+    #[test]
+    fn synthesized_facts_assemble() {
+        let contract = flow_basic();
+        let candidates = literal_candidates(&contract);
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..10 {
+            let facts = synthesize_facts(&contract, &candidates, &mut rng);
+            tenor_eval::assemble::assemble_facts(&contract, &facts).unwrap();
+        }
+    }
+
+    #[test]
+    fn unknown_flow_is_an_error() {
+        assert!(random_walk(&flow_basic(), 0, 1, Some("nope")).is_err());
+    }
+}
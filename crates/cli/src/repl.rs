@@ -0,0 +1,285 @@
+//! `tenor repl` -- evaluate predicates interactively against a contract.
+//!
+//! Each input line is a predicate in DSL syntax, exactly as it would appear
+//! after `when:` in a rule. The REPL compiles it in the context of the
+//! loaded contract -- so fact types, verdict references, and type errors
+//! behave as in a real rule -- evaluates it against the facts file and the
+//! verdicts those facts produce, and prints the value together with the
+//! facts and verdicts it read.
+//!
+//! Compilation reconstructs the contract source from the bundle, appends a
+//! probe rule whose `when:` is the input, and elaborates the result in
+//! memory. The probe sits one stratum above every existing rule so any
+//! verdict may be referenced.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use tenor_eval::predicate::{eval_pred, EvalContext};
+use tenor_eval::provenance::ProvenanceCollector;
+use tenor_eval::types::Predicate;
+use tenor_eval::{Contract, FactSet, Value, VerdictSet};
+
+/// Rule and verdict id of the probe rule wrapping each input predicate.
+const PROBE_ID: &str = "repl_probe";
+
+/// File name the probe contract is elaborated under.
+const PROBE_FILE: &str = "repl.tenor";
+
+/// A contract loaded for predicate evaluation.
+pub(crate) struct Repl {
+    /// Contract source reconstructed from the bundle.
+    source: String,
+    /// Stratum of the probe rule: above every rule in the contract.
+    probe_stratum: u32,
+    facts: FactSet,
+    verdicts: VerdictSet,
+}
+
+/// The value of an input predicate and what it read.
+#[derive(Debug)]
+pub(crate) struct Evaluation {
+    pub value: Value,
+    /// Facts read, with their values, in first-use order.
+    pub facts_used: Vec<(String, Value)>,
+    pub verdicts_used: Vec<String>,
+}
+
+impl Repl {
+    /// Load `bundle` and evaluate its rules against `facts`.
+    pub(crate) fn new(
+        bundle: &serde_json::Value,
+        facts: &serde_json::Value,
+    ) -> Result<Self, String> {
+        let contract = Contract::from_interchange(bundle)
+            .map_err(|e| format!("error loading contract: {}", e))?;
+        let facts = tenor_eval::assemble::assemble_facts(&contract, facts)
+            .map_err(|e| format!("fact assembly error: {}", e))?;
+        let verdicts = tenor_eval::rules::eval_strata(&contract, &facts)
+            .map_err(|e| format!("evaluation error: {}", e))?;
+        let source = crate::decompile::decompile(bundle)?;
+        let probe_stratum = contract
+            .rules
+            .iter()
+            .map(|r| r.stratum + 1)
+            .max()
+            .unwrap_or(0);
+        Ok(Repl {
+            source,
+            probe_stratum,
+            facts,
+            verdicts,
+        })
+    }
+
+    /// Compile a DSL predicate in the context of the contract.
+    pub(crate) fn compile(&self, expr: &str) -> Result<Predicate, String> {
+        let source = format!(
+            "{}\nrule {id} {{\n  stratum: {stratum}\n  when:    {expr}\n  produce: verdict {id} {{ payload: Bool = true }}\n}}\n",
+            self.source,
+            id = PROBE_ID,
+            stratum = self.probe_stratum,
+            expr = expr,
+        );
+        let provider =
+            tenor_core::InMemoryProvider::new(HashMap::from([(PathBuf::from(PROBE_FILE), source)]));
+        let bundle = tenor_core::elaborate_with_provider(Path::new(PROBE_FILE), &provider)
+            .map_err(|e| e.message)?;
+        let contract = Contract::from_interchange(&bundle).map_err(|e| e.to_string())?;
+        contract
+            .rules
+            .into_iter()
+            .find(|r| r.id == PROBE_ID)
+            .map(|r| r.condition)
+            .ok_or_else(|| "predicate did not compile to a rule condition".to_string())
+    }
+
+    /// Compile and evaluate a DSL predicate.
+    pub(crate) fn evaluate(&self, expr: &str) -> Result<Evaluation, String> {
+        let predicate = self.compile(expr)?;
+        let mut collector = ProvenanceCollector::new();
+        let value = eval_pred(
+            &predicate,
+            &self.facts,
+            &self.verdicts,
+            &EvalContext::new(),
+            &mut collector,
+        )
+        .map_err(|e| format!("evaluation error: {}", e))?;
+        let facts_used = collector
+            .facts_used
+            .into_iter()
+            .filter_map(|id| self.facts.get(&id).cloned().map(|v| (id, v)))
+            .collect();
+        Ok(Evaluation {
+            value,
+            facts_used,
+            verdicts_used: collector.verdicts_used,
+        })
+    }
+}
+
+/// Run the REPL over stdin, or over `script` when given. In script mode
+/// the first failing line exits with status 1.
+pub(crate) fn run_repl(
+    bundle: &serde_json::Value,
+    facts: &serde_json::Value,
+    script: Option<&Path>,
+) -> Result<(), String> {
+    let repl = Repl::new(bundle, facts)?;
+
+    let script_source;
+    let mut reader: Box<dyn BufRead + '_> = match script {
+        Some(path) => {
+            script_source = std::fs::read_to_string(path)
+                .map_err(|e| format!("error reading script '{}': {}", path.display(), e))?;
+            Box::new(script_source.as_bytes())
+        }
+        None => {
+            println!("Tenor REPL: type a predicate, :help for commands, :quit to exit");
+            Box::new(io::stdin().lock())
+        }
+    };
+
+    let mut line = String::new();
+    loop {
+        if script.is_none() {
+            print!("tenor> ");
+            if io::stdout().flush().is_err() {
+                break;
+            }
+        }
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => return Err(format!("error reading input: {}", e)),
+        }
+
+        let input = line.trim();
+        if input.is_empty() || input.starts_with("//") {
+            continue;
+        }
+        if script.is_some() {
+            // Echo scripted input so the output reads like a session.
+            println!("tenor> {}", input);
+        }
+        match input {
+            ":quit" | ":q" => break,
+            ":help" => print_help(),
+            ":facts" => print_facts(&repl.facts),
+            ":verdicts" => print_verdicts(&repl.verdicts),
+            _ => match repl.evaluate(input) {
+                Ok(evaluation) => print_evaluation(&evaluation),
+                Err(e) if script.is_some() => return Err(e),
+                Err(e) => eprintln!("  {}", e),
+            },
+        }
+    }
+    Ok(())
+}
+
+fn print_help() {
+    println!(
+        "  <predicate>   Evaluate a predicate, e.g. amount > 100 and verdict_present(approved)"
+    );
+    println!("  :facts        List fact values");
+    println!("  :verdicts     List the verdicts the facts produce");
+    println!("  :help         Show this help");
+    println!("  :quit         Exit");
+}
+
+fn print_facts(facts: &FactSet) {
+    for (id, value) in &facts.0 {
+        println!("  {} = {}", id, format_value(value));
+    }
+}
+
+fn print_verdicts(verdicts: &VerdictSet) {
+    if verdicts.0.is_empty() {
+        println!("  no verdicts");
+    }
+    for v in &verdicts.0 {
+        println!(
+            "  {} = {} (rule: {})",
+            v.verdict_type,
+            format_value(&v.payload),
+            v.provenance.rule_id
+        );
+    }
+}
+
+fn print_evaluation(evaluation: &Evaluation) {
+    println!("  = {}", format_value(&evaluation.value));
+    for (id, value) in &evaluation.facts_used {
+        println!("    fact {} = {}", id, format_value(value));
+    }
+    for verdict in &evaluation.verdicts_used {
+        println!("    verdict {}", verdict);
+    }
+}
+
+fn format_value(v: &Value) -> String {
+    match v {
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::Decimal(d) => d.to_string(),
+        Value::Text(t) => format!("\"{}\"", t),
+        Value::Money { amount, currency } => format!("{} {}", amount, currency),
+        Value::Enum(e) => e.clone(),
+        _ => v.to_json().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repl() -> Repl {
+        let source = r#"
+fact amount {
+  type:   Int
+  source: "orders.amount"
+}
+
+fact vip {
+  type:   Bool
+  source: "crm.vip"
+}
+
+rule large {
+  stratum: 0
+  when:    amount > 100
+  produce: verdict large { payload: Bool = true }
+}
+"#;
+        let provider = tenor_core::InMemoryProvider::new(HashMap::from([(
+            PathBuf::from("c.tenor"),
+            source.to_string(),
+        )]));
+        let bundle = tenor_core::elaborate_with_provider(Path::new("c.tenor"), &provider).unwrap();
+        Repl::new(&bundle, &serde_json::json!({ "amount": 250, "vip": false })).unwrap()
+    }
+
+    #[test]
+    fn evaluates_predicates_with_provenance() {
+        let repl = repl();
+        let e = repl.evaluate("amount >= 200 and vip = false").unwrap();
+        assert_eq!(e.value, Value::Bool(true));
+        let facts: Vec<&str> = e.facts_used.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(facts, ["amount", "vip"]);
+
+        let e = repl.evaluate("verdict_present(large)").unwrap();
+        assert_eq!(e.value, Value::Bool(true));
+        assert_eq!(e.verdicts_used, ["large"]);
+    }
+
+    #[test]
+    fn reports_compile_errors() {
+        let repl = repl();
+        let err = repl.evaluate("missing_fact > 1").unwrap_err();
+        assert!(err.contains("missing_fact"), "{}", err);
+        assert!(repl.evaluate("amount > ").is_err());
+    }
+}
@@ -1,7 +1,9 @@
+use crate::commands::read_json;
 use crate::manifest;
-use crate::tap::Tap;
+use serde::Serialize;
 use serde_json::Value;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 /// Conformance suite runner.
 ///
 /// Convention:
@@ -12,13 +14,123 @@ use std::path::{Path, PathBuf};
 ///   manifest/               -- *.tenor + *.expected-manifest.json (manifest envelope test)
 use tenor_core::elaborate;
 
-pub struct RunResult {
+/// The outcome of a conformance suite run.
+#[derive(Debug, Clone, Serialize)]
+pub struct SuiteReport {
+    /// The suite directory.
+    pub suite: String,
+    /// The `--filter` glob, if the run was restricted to matching cases.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    pub passed: usize,
     pub failed: usize,
+    pub duration_ms: f64,
+    pub tests: Vec<CaseReport>,
 }
 
-pub fn run_suite(suite_dir: &Path) -> RunResult {
-    let mut tap = Tap::new();
+/// The outcome of one conformance case.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseReport {
+    /// Case name, `<category>/<stem>`, e.g. `negative/pass3/cycle`.
+    pub name: String,
+    /// Suite directory of the case, e.g. `negative/pass3`.
+    pub category: String,
+    pub passed: bool,
+    /// Failure message; `None` when the case passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Expected output, on a mismatch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    /// Actual output, on a mismatch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+    pub duration_ms: f64,
+}
+
+impl CaseReport {
+    /// The failure message, followed by the expected and actual output on a
+    /// mismatch; `None` when the case passed.
+    pub fn diagnostics(&self) -> Option<String> {
+        let message = self.message.as_ref()?;
+        Some(match (&self.expected, &self.actual) {
+            (Some(expected), Some(actual)) => {
+                format!("{}:\n{}", message, json_diff(expected, actual))
+            }
+            _ => message.clone(),
+        })
+    }
+}
+
+/// Why a case failed.
+struct Failure {
+    message: String,
+    /// Expected and actual output, when both were produced but differ.
+    mismatch: Option<(Value, Value)>,
+}
+
+impl Failure {
+    fn new(message: impl Into<String>) -> Self {
+        Failure {
+            message: message.into(),
+            mismatch: None,
+        }
+    }
+
+    fn mismatch(message: impl Into<String>, expected: Value, actual: Value) -> Self {
+        Failure {
+            message: message.into(),
+            mismatch: Some((expected, actual)),
+        }
+    }
+}
+
+/// Collects case outcomes, skipping cases outside the filter.
+struct Cases<'a> {
+    filter: Option<&'a str>,
+    tests: Vec<CaseReport>,
+}
+
+impl Cases<'_> {
+    fn run(&mut self, name: String, case: impl FnOnce() -> Result<(), Failure>) {
+        if self.filter.is_some_and(|f| !glob_match(f, &name)) {
+            return;
+        }
+        let start = Instant::now();
+        let result = case();
+        let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+        let category = name.rsplit_once('/').map_or("", |(c, _)| c).to_string();
+        let (message, expected, actual) = match result {
+            Ok(()) => (None, None, None),
+            Err(Failure {
+                message,
+                mismatch: Some((expected, actual)),
+            }) => (Some(message), Some(expected), Some(actual)),
+            Err(Failure {
+                message,
+                mismatch: None,
+            }) => (Some(message), None, None),
+        };
+        self.tests.push(CaseReport {
+            name,
+            category,
+            passed: message.is_none(),
+            message,
+            expected,
+            actual,
+            duration_ms,
+        });
+    }
+}
 
+/// Run the conformance suite in `suite_dir`, restricted to the cases whose
+/// name matches `filter` (a glob where `*` matches any run of characters,
+/// including `/`, and `?` any single character).
+pub fn run_suite(suite_dir: &Path, filter: Option<&str>) -> SuiteReport {
+    let mut cases = Cases {
+        filter,
+        tests: Vec::new(),
+    };
     // Known conformance gaps (cross-contract System constraints):
     //
     // The following constraints require cross-contract elaboration (validating
@@ -37,38 +149,43 @@ pub fn run_suite(suite_dir: &Path) -> RunResult {
     // regression tests, not in the conformance suite.
 
     // Positive tests
-    run_positive_dir(suite_dir, "positive", &mut tap);
+    run_positive_dir(suite_dir, "positive", &mut cases);
 
     // Negative tests by pass
     for pass in 0..=6 {
-        run_negative_tests(suite_dir, pass, &mut tap);
+        run_negative_tests(suite_dir, pass, &mut cases);
     }
 
     // Cross-file tests
-    run_cross_file_tests(suite_dir, &mut tap);
+    run_cross_file_tests(suite_dir, &mut cases);
 
     // Parallel entity conflict tests
-    run_parallel_tests(suite_dir, &mut tap);
+    run_parallel_tests(suite_dir, &mut cases);
 
     // Numeric precision tests
-    run_positive_dir(suite_dir, "numeric", &mut tap);
+    run_positive_dir(suite_dir, "numeric", &mut cases);
 
     // Type promotion tests
-    run_positive_dir(suite_dir, "promotion", &mut tap);
+    run_positive_dir(suite_dir, "promotion", &mut cases);
 
     // DSL shorthand expansion tests
-    run_positive_dir(suite_dir, "shorthand", &mut tap);
+    run_positive_dir(suite_dir, "shorthand", &mut cases);
 
     // Manifest envelope tests
-    run_manifest_tests(suite_dir, &mut tap);
-
-    let failed = tap.failure_count();
-    tap.finish();
-
-    RunResult { failed }
+    run_manifest_tests(suite_dir, &mut cases);
+
+    let passed = cases.tests.iter().filter(|t| t.passed).count();
+    SuiteReport {
+        suite: suite_dir.display().to_string(),
+        filter: filter.map(str::to_string),
+        passed,
+        failed: cases.tests.len() - passed,
+        duration_ms: cases.tests.iter().map(|t| t.duration_ms).sum(),
+        tests: cases.tests,
+    }
 }
 
-fn run_positive_dir(suite_dir: &Path, subdir: &str, tap: &mut Tap) {
+fn run_positive_dir(suite_dir: &Path, subdir: &str, cases: &mut Cases) {
     let dir = suite_dir.join(subdir);
     if !dir.exists() {
         return;
@@ -78,18 +195,19 @@ fn run_positive_dir(suite_dir: &Path, subdir: &str, tap: &mut Tap) {
     for tenor_path in &entries {
         let stem = stem(tenor_path);
         let expected_path = dir.join(format!("{}.expected.json", stem));
-        if !expected_path.exists() {
-            tap.not_ok(
-                format!("{}/{}", subdir, stem),
-                format!("missing expected file: {}", expected_path.display()),
-            );
-            continue;
-        }
-        run_positive_test(tenor_path, &expected_path, &stem, subdir, tap);
+        cases.run(format!("{}/{}", subdir, stem), || {
+            if !expected_path.exists() {
+                return Err(Failure::new(format!(
+                    "missing expected file: {}",
+                    expected_path.display()
+                )));
+            }
+            run_positive_test(tenor_path, &expected_path)
+        });
     }
 }
 
-fn run_negative_tests(suite_dir: &Path, pass: u8, tap: &mut Tap) {
+fn run_negative_tests(suite_dir: &Path, pass: u8, cases: &mut Cases) {
     let dir = suite_dir.join(format!("negative/pass{}", pass));
     if !dir.exists() {
         return;
@@ -113,18 +231,19 @@ fn run_negative_tests(suite_dir: &Path, pass: u8, tap: &mut Tap) {
     for tenor_path in &roots {
         let stem = stem(tenor_path);
         let expected_path = dir.join(format!("{}.expected-error.json", stem));
-        if !expected_path.exists() {
-            tap.not_ok(
-                format!("negative/pass{}/{}", pass, stem),
-                format!("missing expected-error file: {}", expected_path.display()),
-            );
-            continue;
-        }
-        run_negative_test(tenor_path, &expected_path, &stem, pass, tap);
+        cases.run(format!("negative/pass{}/{}", pass, stem), || {
+            if !expected_path.exists() {
+                return Err(Failure::new(format!(
+                    "missing expected-error file: {}",
+                    expected_path.display()
+                )));
+            }
+            run_negative_test(tenor_path, &expected_path, pass)
+        });
     }
 }
 
-fn run_cross_file_tests(suite_dir: &Path, tap: &mut Tap) {
+fn run_cross_file_tests(suite_dir: &Path, cases: &mut Cases) {
     let dir = suite_dir.join("cross_file");
     if !dir.exists() {
         return;
@@ -132,11 +251,13 @@ fn run_cross_file_tests(suite_dir: &Path, tap: &mut Tap) {
     let root = dir.join("rules.tenor");
     let expected = dir.join("bundle.expected.json");
     if root.exists() && expected.exists() {
-        run_positive_test(&root, &expected, "bundle", "cross_file", tap);
+        cases.run("cross_file/bundle".to_string(), || {
+            run_positive_test(&root, &expected)
+        });
     }
 }
 
-fn run_parallel_tests(suite_dir: &Path, tap: &mut Tap) {
+fn run_parallel_tests(suite_dir: &Path, cases: &mut Cases) {
     let dir = suite_dir.join("parallel");
     if !dir.exists() {
         return;
@@ -149,91 +270,53 @@ fn run_parallel_tests(suite_dir: &Path, tap: &mut Tap) {
         if !expected_path.exists() {
             continue;
         }
-        run_negative_test(tenor_path, &expected_path, &stem, 5, tap);
+        cases.run(format!("negative/pass5/{}", stem), || {
+            run_negative_test(tenor_path, &expected_path, 5)
+        });
     }
 }
 
-fn run_positive_test(
-    tenor_path: &Path,
-    expected_path: &Path,
-    name: &str,
-    category: &str,
-    tap: &mut Tap,
-) {
-    let test_name = format!("{}/{}", category, name);
-
-    let expected_json = match read_json(expected_path) {
-        Ok(v) => v,
-        Err(e) => {
-            tap.not_ok(&test_name, format!("failed to read expected file: {}", e));
-            return;
-        }
-    };
+fn run_positive_test(tenor_path: &Path, expected_path: &Path) -> Result<(), Failure> {
+    let expected_json = read_json(expected_path, "expected").map_err(Failure::new)?;
 
     match elaborate::elaborate(tenor_path) {
-        Ok(got) => {
-            if json_equal(&got, &expected_json) {
-                tap.ok(&test_name);
-            } else {
-                let diff = json_diff(&expected_json, &got);
-                tap.not_ok(&test_name, format!("output mismatch:\n{}", diff));
-            }
-        }
-        Err(e) => {
-            tap.not_ok(
-                &test_name,
-                format!(
-                    "unexpected elaboration error (pass {}): {}",
-                    e.pass, e.message
-                ),
-            );
-        }
+        Ok(got) if json_equal(&got, &expected_json) => Ok(()),
+        Ok(got) => Err(Failure::mismatch("output mismatch", expected_json, got)),
+        Err(e) => Err(Failure::new(format!(
+            "unexpected elaboration error (pass {}): {}",
+            e.pass, e.message
+        ))),
     }
 }
 
 fn run_negative_test(
     tenor_path: &Path,
     expected_error_path: &Path,
-    name: &str,
     pass: u8,
-    tap: &mut Tap,
-) {
-    let test_name = format!("negative/pass{}/{}", pass, name);
-
-    let expected_error = match read_json(expected_error_path) {
-        Ok(v) => v,
-        Err(e) => {
-            tap.not_ok(
-                &test_name,
-                format!("failed to read expected-error file: {}", e),
-            );
-            return;
-        }
-    };
+) -> Result<(), Failure> {
+    let expected_error = read_json(expected_error_path, "expected-error").map_err(Failure::new)?;
 
     match elaborate::elaborate(tenor_path) {
         Err(got_error) => {
             let got_json = got_error.to_json_value();
             if json_equal(&got_json, &expected_error) {
-                tap.ok(&test_name);
+                Ok(())
             } else {
-                let diff = json_diff(&expected_error, &got_json);
-                tap.not_ok(&test_name, format!("error mismatch:\n{}", diff));
+                Err(Failure::mismatch(
+                    "error mismatch",
+                    expected_error,
+                    got_json,
+                ))
             }
         }
-        Ok(_) => {
-            tap.not_ok(
-                &test_name,
-                format!(
-                    "expected pass {} elaboration error but elaboration succeeded",
-                    pass
-                ),
-            );
-        }
+        Ok(_) => Err(Failure::new(format!(
+            "expected pass {} elaboration error but elaboration succeeded",
+            pass
+        ))),
     }
 }
 
-fn run_manifest_tests(suite_dir: &Path, tap: &mut Tap) {
+fn run_manifest_tests(suite_dir: &Path, cases: &mut Cases) {
     let dir = suite_dir.join("manifest");
     if !dir.exists() {
         return;
@@ -243,51 +326,38 @@ fn run_manifest_tests(suite_dir: &Path, tap: &mut Tap) {
     for tenor_path in &entries {
         let stem_name = stem(tenor_path);
         let expected_path = dir.join(format!("{}.expected-manifest.json", stem_name));
-        if !expected_path.exists() {
-            tap.not_ok(
-                format!("manifest/{}", stem_name),
-                format!(
+        cases.run(format!("manifest/{}", stem_name), || {
+            if !expected_path.exists() {
+                return Err(Failure::new(format!(
                     "missing expected-manifest file: {}",
                     expected_path.display()
-                ),
-            );
-            continue;
-        }
-        run_manifest_test(tenor_path, &expected_path, &stem_name, tap);
+                )));
+            }
+            run_manifest_test(tenor_path, &expected_path)
+        });
     }
 }
 
-fn run_manifest_test(tenor_path: &Path, expected_path: &Path, name: &str, tap: &mut Tap) {
-    let test_name = format!("manifest/{}", name);
-
-    let expected_json = match read_json(expected_path) {
-        Ok(v) => v,
-        Err(e) => {
-            tap.not_ok(&test_name, format!("failed to read expected file: {}", e));
-            return;
-        }
-    };
+fn run_manifest_test(tenor_path: &Path, expected_path: &Path) -> Result<(), Failure> {
+    let expected_json = read_json(expected_path, "expected").map_err(Failure::new)?;
 
     match elaborate::elaborate(tenor_path) {
         Ok(bundle) => {
             let manifest = manifest::build_manifest(bundle);
-
             if json_equal(&manifest, &expected_json) {
-                tap.ok(&test_name);
+                Ok(())
             } else {
-                let diff = json_diff(&expected_json, &manifest);
-                tap.not_ok(&test_name, format!("output mismatch:\n{}", diff));
+                Err(Failure::mismatch(
+                    "output mismatch",
+                    expected_json,
+                    manifest,
+                ))
             }
         }
-        Err(e) => {
-            tap.not_ok(
-                &test_name,
-                format!(
-                    "unexpected elaboration error (pass {}): {}",
-                    e.pass, e.message
-                ),
-            );
-        }
+        Err(e) => Err(Failure::new(format!(
+            "unexpected elaboration error (pass {}): {}",
+            e.pass, e.message
+        ))),
     }
 }
 
@@ -313,12 +383,6 @@ fn stem(path: &Path) -> String {
         .to_string()
 }
 
-fn read_json(path: &Path) -> Result<Value, String> {
-    let src = std::fs::read_to_string(path)
-        .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
-    serde_json::from_str(&src).map_err(|e| format!("invalid JSON in {}: {}", path.display(), e))
-}
-
 /// Deep equality of two JSON values, normalizing number types.
 fn json_equal(a: &Value, b: &Value) -> bool {
     match (a, b) {
@@ -338,8 +402,56 @@ fn json_equal(a: &Value, b: &Value) -> bool {
     }
 }
 
+/// Whether `name` matches `pattern`, where `*` matches any run of
+/// characters and `?` any single character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` seen, and the name position it matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bn)) => {
+                    p = bp;
+                    n = bn + 1;
+                    backtrack = Some((bp, bn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 fn json_diff(expected: &Value, got: &Value) -> String {
     let exp_str = serde_json::to_string_pretty(expected).unwrap_or_default();
     let got_str = serde_json::to_string_pretty(got).unwrap_or_default();
     format!("--- expected\n{}\n+++ got\n{}", exp_str, got_str)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_matches_wildcards() {
+        assert!(glob_match("negative/pass3/*", "negative/pass3/cycle"));
+        assert!(glob_match("*/fact_*", "positive/fact_basic"));
+        assert!(glob_match("negative/pass?/*", "negative/pass5/conflict"));
+        assert!(glob_match("*cycle*", "negative/pass3/typedecl_cycle"));
+        assert!(glob_match("positive/fact_basic", "positive/fact_basic"));
+        assert!(!glob_match("positive/fact_*", "numeric/fact_basic"));
+        assert!(!glob_match("negative/pass?/x", "negative/pass10/x"));
+        assert!(!glob_match("positive/fact", "positive/fact_basic"));
+    }
+}
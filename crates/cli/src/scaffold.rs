@@ -0,0 +1,310 @@
+//! `tenor new` — starter project scaffolding.
+//!
+//! Writes a small but complete project a new author can run immediately:
+//! - `tenor.toml`           — project file
+//! - `<name>.tenor`         — personas, facts, an entity, a rule, two
+//!   operations, a flow, and `test` blocks
+//! - `facts.json`           — sample facts for `tenor eval`
+//! - `tests/<case>.facts.json` and `tests/<case>.verdicts.json` — eval
+//!   test cases; the expected verdicts are produced by evaluating the
+//!   generated contract, so they always agree with it
+//!
+//! Construct names are chosen by the author; operation and flow names are
+//! derived from the entity name.
+
+use std::path::{Path, PathBuf};
+
+/// Names chosen for a starter project.
+#[derive(Debug, Clone)]
+pub(crate) struct Starter {
+    /// Contract name; also the `.tenor` file stem.
+    pub name: String,
+    /// Entity moved through the flow, e.g. `Request`.
+    pub entity: String,
+    /// Persona that submits the entity.
+    pub requester: String,
+    /// Persona that approves it.
+    pub approver: String,
+}
+
+/// Sample facts for each generated test case: (case, amount).
+const TEST_CASES: &[(&str, &str)] = &[("within_limit", "250.00"), ("over_limit", "5000.00")];
+
+impl Starter {
+    /// Check that every name is a valid Tenor identifier and the two
+    /// personas differ.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        for (what, value) in [
+            ("contract name", &self.name),
+            ("entity", &self.entity),
+            ("requester persona", &self.requester),
+            ("approver persona", &self.approver),
+        ] {
+            if !is_identifier(value) {
+                return Err(format!(
+                    "invalid {} '{}': use letters, digits and underscores, starting with a letter",
+                    what, value
+                ));
+            }
+        }
+        if self.requester == self.approver {
+            return Err(format!(
+                "requester and approver personas must differ (both '{}')",
+                self.requester
+            ));
+        }
+        Ok(())
+    }
+
+    fn contract_file(&self) -> String {
+        format!("{}.tenor", self.name)
+    }
+
+    fn contract_source(&self) -> String {
+        let entity = &self.entity;
+        let snake = snake_case(entity);
+        let (requester, approver) = (&self.requester, &self.approver);
+        format!(
+            r#"// {name}: a starting point generated by `tenor new`.
+//
+// Run tests:  tenor test --unit {name}.tenor
+// Elaborate:  tenor elaborate {name}.tenor > {name}.json
+// Evaluate:   tenor eval {name}.json --facts facts.json
+// Simulate:   tenor simulate {name}.json --flow {snake}_approval --persona {requester} --facts facts.json
+
+persona {requester}
+persona {approver}
+
+fact amount {{
+  type:   Money(currency: "USD")
+  source: "{snake}_service.amount"
+}}
+
+fact approval_limit {{
+  type:    Money(currency: "USD")
+  source:  "policy_service.approval_limit"
+  default: Money {{ amount: "1000.00", currency: "USD" }}
+}}
+
+entity {entity} {{
+  states:  [draft, submitted, approved]
+  initial: draft
+  transitions: [
+    (draft, submitted),
+    (submitted, approved)
+  ]
+}}
+
+rule within_limit {{
+  stratum: 0
+  when:    amount <= approval_limit
+  produce: verdict within_limit {{ payload: Bool = true }}
+}}
+
+operation submit_{snake} {{
+  allowed_personas: [{requester}]
+  precondition:     verdict_present(within_limit)
+  effects:          [({entity}, draft, submitted)]
+  error_contract:   [precondition_failed, persona_rejected]
+}}
+
+operation approve_{snake} {{
+  allowed_personas: [{approver}]
+  precondition:     verdict_present(within_limit)
+  effects:          [({entity}, submitted, approved)]
+  error_contract:   [precondition_failed, persona_rejected]
+}}
+
+flow {snake}_approval {{
+  snapshot: at_initiation
+  entry:    step_submit
+
+  steps: {{
+    step_submit: OperationStep {{
+      op:      submit_{snake}
+      persona: {requester}
+      outcomes: {{
+        success: step_approve
+      }}
+      on_failure: Terminate(outcome: rejected)
+    }}
+
+    step_approve: OperationStep {{
+      op:      approve_{snake}
+      persona: {approver}
+      outcomes: {{
+        success: Terminal(approved)
+      }}
+      on_failure: Terminate(outcome: rejected)
+    }}
+  }}
+}}
+
+test within_limit_is_approved {{
+  given facts {{
+    amount: Money {{ amount: "250.00", currency: "USD" }}
+  }}
+  expect verdict within_limit present
+}}
+
+test over_limit_is_blocked {{
+  given facts {{
+    amount: Money {{ amount: "5000.00", currency: "USD" }}
+  }}
+  expect verdict within_limit absent
+}}
+"#,
+            name = self.name,
+        )
+    }
+}
+
+/// The facts for a test case with the given amount.
+fn facts_for(amount: &str) -> serde_json::Value {
+    serde_json::json!({ "amount": { "amount": amount, "currency": "USD" } })
+}
+
+/// Write the starter project into `dir`, creating it if needed. Existing
+/// files are never overwritten. Returns the written paths, relative to
+/// `dir`.
+pub(crate) fn write_starter(dir: &Path, starter: &Starter) -> Result<Vec<PathBuf>, String> {
+    starter.validate()?;
+
+    let contract_file = starter.contract_file();
+    let mut files: Vec<(PathBuf, String)> = vec![
+        (
+            PathBuf::from(tenor_core::project::PROJECT_FILE),
+            "[project]\nsource_roots = [\".\"]\n".to_string(),
+        ),
+        (PathBuf::from(&contract_file), starter.contract_source()),
+        (PathBuf::from("facts.json"), pretty(&facts_for("250.00"))),
+    ];
+    for (case, amount) in TEST_CASES {
+        files.push((
+            Path::new("tests").join(format!("{}.facts.json", case)),
+            pretty(&facts_for(amount)),
+        ));
+    }
+
+    if let Some((path, _)) = files.iter().find(|(path, _)| dir.join(path).exists()) {
+        return Err(format!(
+            "'{}' already exists; choose an empty directory",
+            dir.join(path).display()
+        ));
+    }
+
+    std::fs::create_dir_all(dir.join("tests"))
+        .map_err(|e| format!("error creating '{}': {}", dir.display(), e))?;
+    for (path, content) in &files {
+        write(&dir.join(path), content)?;
+    }
+
+    // Expected verdicts come from evaluating the contract just written.
+    let bundle = tenor_core::elaborate::elaborate(&dir.join(&contract_file))
+        .map_err(|e| format!("generated contract does not elaborate: {:?}", e))?;
+    let mut written: Vec<PathBuf> = files.into_iter().map(|(path, _)| path).collect();
+    for (case, amount) in TEST_CASES {
+        let result = tenor_eval::evaluate(&bundle, &facts_for(amount))
+            .map_err(|e| format!("generated contract does not evaluate: {}", e))?;
+        let path = Path::new("tests").join(format!("{}.verdicts.json", case));
+        write(&dir.join(&path), &pretty(&result.verdicts.to_json()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content).map_err(|e| format!("error writing '{}': {}", path.display(), e))
+}
+
+fn pretty(value: &serde_json::Value) -> String {
+    format!(
+        "{}\n",
+        serde_json::to_string_pretty(value).expect("JSON serializes")
+    )
+}
+
+/// Whether `s` is a Tenor identifier: a letter followed by letters, digits
+/// or underscores.
+pub(crate) fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `PurchaseOrder` -> `purchase_order`.
+pub(crate) fn snake_case(s: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starter() -> Starter {
+        Starter {
+            name: "purchasing".to_string(),
+            entity: "PurchaseOrder".to_string(),
+            requester: "buyer".to_string(),
+            approver: "manager".to_string(),
+        }
+    }
+
+    #[test]
+    fn derives_snake_case_names() {
+        assert_eq!(snake_case("PurchaseOrder"), "purchase_order");
+        assert_eq!(snake_case("Request"), "request");
+        assert_eq!(snake_case("order"), "order");
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        let mut bad = starter();
+        bad.entity = "Purchase Order".to_string();
+        assert!(bad.validate().unwrap_err().contains("invalid entity"));
+
+        let mut same = starter();
+        same.approver = "buyer".to_string();
+        assert!(same.validate().unwrap_err().contains("must differ"));
+    }
+
+    #[test]
+    fn starter_project_elaborates_and_records_expected_verdicts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let written = write_starter(tmp.path(), &starter()).unwrap();
+        assert!(written.contains(&PathBuf::from("purchasing.tenor")));
+
+        let bundle =
+            tenor_core::elaborate::elaborate(&tmp.path().join("purchasing.tenor")).unwrap();
+        let ids: Vec<&str> = bundle["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|c| c["id"].as_str())
+            .collect();
+        for id in ["submit_purchase_order", "purchase_order_approval", "buyer"] {
+            assert!(ids.contains(&id), "missing {}", id);
+        }
+
+        let within: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(tmp.path().join("tests/within_limit.verdicts.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(within["verdicts"][0]["type"], "within_limit");
+
+        // A second run refuses to overwrite the project.
+        let err = write_starter(tmp.path(), &starter()).unwrap_err();
+        assert!(err.contains("already exists"), "{}", err);
+    }
+}
@@ -0,0 +1,429 @@
+//! Adapter settings, their live reload, and the adapters they configure.
+//!
+//! Adapter settings belong to a tenant. The default tenant's come from the
+//! file named by `[connect] adapter_config`, overlaid with
+//! `TENOR_ADAPTER_*` variables; another tenant's come only from the file
+//! named by `adapter_config` in its `[serve.tenants.<id>]`, and a tenant
+//! without one has none. So one tenant's contracts never reach another's
+//! sources or credentials, nor the operator's.
+//!
+//! Scheduled evaluations build an `AdapterRegistry` from the tenant's
+//! current settings at every run. `http` sources are fetched by
+//! [`TenantHttpAdapter`], which vets each URL like a webhook target
+//! (`Webhooks::check_target`) and connects only to the vetted addresses.
+//! A contract of a tenant other than the default one may not choose where
+//! the server fetches from or which credentials it sends: its `http`
+//! sources take `base_url` and `auth_token` from the tenant's settings,
+//! and a run fails if the contract's Source sets either to anything else.
+//!
+//! `POST /admin/adapter-config/reload` re-reads the requesting tenant's
+//! settings, and SIGHUP every tenant's: the file is read again (with the
+//! `TENOR_ADAPTER_*` overlay for the default tenant), `env://` secrets are
+//! resolved, and the result is swapped in whole, so rotated credentials
+//! apply from the next run without restarting the server or dropping its
+//! contracts. A reload that fails keeps the previous settings.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use tenor_eval::adapter::{AdapterError, FactAdapter, StructuredSourceRef};
+
+use super::json_error;
+use super::state::AppState;
+use super::tenant::{Authenticated, Tenant, DEFAULT_TENANT};
+use super::webhooks;
+
+/// Prefix of the environment variables overlaid on the adapter file.
+const ENV_PREFIX: &str = "TENOR_ADAPTER";
+
+/// Source fields a tenant's contract may not set: what an `http` source
+/// sends its request to, and with which credentials.
+const TENANT_SET_FIELDS: &[&str] = &["base_url", "auth_token"];
+
+/// A tenant's adapter settings in use, swapped whole by a reload.
+pub(crate) struct AdapterSettings {
+    current: RwLock<Arc<tenor_eval::AdapterConfig>>,
+    /// The file the settings are re-read from.
+    path: Option<PathBuf>,
+    /// Whether `TENOR_ADAPTER_*` variables overlay the file, as they do for
+    /// the default tenant only.
+    from_env: bool,
+}
+
+impl AdapterSettings {
+    pub(crate) fn new(
+        config: tenor_eval::AdapterConfig,
+        path: Option<PathBuf>,
+        from_env: bool,
+    ) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(config)),
+            path,
+            from_env,
+        }
+    }
+
+    /// The settings for a run that starts now.
+    pub(crate) fn current(&self) -> Arc<tenor_eval::AdapterConfig> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Re-read the settings and swap them in, returning the ids of the
+    /// sources they configure.
+    pub(crate) async fn reload(&self) -> Result<Vec<String>, String> {
+        let mut config = if self.from_env {
+            read_adapter_config(self.path.as_deref())?
+        } else {
+            read_tenant_adapter_config(self.path.as_deref())?
+        };
+        config
+            .resolve_secrets(&[&tenor_eval::adapter::secrets::EnvSecretResolver])
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut sources: Vec<String> = config.source_configs.keys().cloned().collect();
+        sources.sort();
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(config);
+        Ok(sources)
+    }
+}
+
+/// Adapter settings from `path`, when it exists, overlaid with
+/// `TENOR_ADAPTER_*` variables. Secret references are left unresolved.
+pub fn read_adapter_config(path: Option<&Path>) -> Result<tenor_eval::AdapterConfig, String> {
+    let from_file = match path {
+        Some(path) if path.exists() => {
+            tenor_eval::AdapterConfig::from_toml(path).map_err(|e| e.to_string())?
+        }
+        _ => tenor_eval::AdapterConfig::default(),
+    };
+    Ok(from_file.merge(tenor_eval::AdapterConfig::from_env(ENV_PREFIX)))
+}
+
+/// Adapter settings of a tenant other than the default one: `path` alone,
+/// without the operator's `TENOR_ADAPTER_*` variables. Secret references
+/// are left unresolved.
+pub(crate) fn read_tenant_adapter_config(
+    path: Option<&Path>,
+) -> Result<tenor_eval::AdapterConfig, String> {
+    match path {
+        Some(path) => tenor_eval::AdapterConfig::from_toml(path).map_err(|e| e.to_string()),
+        None => Ok(tenor_eval::AdapterConfig::default()),
+    }
+}
+
+/// The adapters for a run of one of `tenant`'s contracts, whose Source
+/// constructs are `sources`, under the tenant's `config`.
+pub(crate) fn registry(
+    tenant: &Arc<Tenant>,
+    sources: &[tenor_interchange::SourceConstruct],
+    config: &tenor_eval::AdapterConfig,
+) -> Result<tenor_eval::AdapterRegistry, String> {
+    let mut registry = tenor_eval::AdapterRegistry::from_sources(sources, config);
+    for source in sources.iter().filter(|s| s.protocol == "http") {
+        let adapter = TenantHttpAdapter::new(tenant, source, config)?;
+        registry.register(source.id.clone(), source.fields.clone(), Box::new(adapter));
+    }
+    Ok(registry)
+}
+
+/// Fetches the facts of an `http` source like `tenor_eval`'s `HttpAdapter`
+/// (a GET of `{base_url}/{path segments}` with an optional bearer token),
+/// but only from addresses vetted for the tenant and without following
+/// redirects.
+pub(crate) struct TenantHttpAdapter {
+    tenant: Arc<Tenant>,
+    source_id: String,
+    base_url: String,
+    auth_token: Option<String>,
+}
+
+impl TenantHttpAdapter {
+    /// The adapter for `source`. The default tenant's sources name their
+    /// own `base_url` and take the token from the settings or
+    /// `TENOR_SOURCE_<ID>_AUTH_TOKEN`; another tenant's take both from its
+    /// settings.
+    fn new(
+        tenant: &Arc<Tenant>,
+        source: &tenor_interchange::SourceConstruct,
+        config: &tenor_eval::AdapterConfig,
+    ) -> Result<Self, String> {
+        let configured = |key: &str| config.get(&source.id, key).map(|s| s.to_string());
+        let (base_url, auth_token) = if tenant.id == DEFAULT_TENANT {
+            let auth_token = configured("auth_token").or_else(|| {
+                let key = format!("TENOR_SOURCE_{}_AUTH_TOKEN", source.id.to_uppercase());
+                std::env::var(key).ok()
+            });
+            (
+                source
+                    .fields
+                    .get("base_url")
+                    .cloned()
+                    .or_else(|| configured("base_url")),
+                auth_token,
+            )
+        } else {
+            for field in TENANT_SET_FIELDS {
+                if let Some(value) = source.fields.get(*field) {
+                    if configured(field).as_deref() != Some(value.as_str()) {
+                        return Err(format!(
+                            "source '{}' sets '{}', which only tenant '{}''s adapter configuration may set",
+                            source.id, field, tenant.id
+                        ));
+                    }
+                }
+            }
+            (configured("base_url"), configured("auth_token"))
+        };
+        let base_url = base_url.ok_or_else(|| {
+            format!(
+                "source '{}' has no base_url in tenant '{}''s adapter configuration",
+                source.id, tenant.id
+            )
+        })?;
+        Ok(Self {
+            tenant: Arc::clone(tenant),
+            source_id: source.id.clone(),
+            base_url,
+            auth_token,
+        })
+    }
+
+    fn failed(&self, message: String) -> AdapterError {
+        AdapterError::FetchFailed {
+            source_id: self.source_id.clone(),
+            message,
+        }
+    }
+}
+
+#[async_trait]
+impl FactAdapter for TenantHttpAdapter {
+    async fn fetch(
+        &self,
+        _fact_id: &str,
+        source: &StructuredSourceRef,
+        _source_fields: &BTreeMap<String, String>,
+    ) -> Result<serde_json::Value, AdapterError> {
+        let url = format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            source.path.replace('.', "/")
+        );
+        let addresses = self
+            .tenant
+            .webhooks
+            .check_target(&url)
+            .await
+            .map_err(|e| self.failed(e))?;
+        let auth_token = self.auth_token.clone();
+        let fetched = tokio::task::spawn_blocking(move || {
+            let mut request = webhooks::pinned_agent(addresses).get(&url);
+            if let Some(token) = &auth_token {
+                request = request.header("Authorization", &format!("Bearer {}", token));
+            }
+            let response = request.call().map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("answered {}", response.status()));
+            }
+            response
+                .into_body()
+                .read_json::<serde_json::Value>()
+                .map_err(|e| format!("failed to parse response as JSON: {}", e))
+        })
+        .await;
+        match fetched {
+            Ok(result) => result.map_err(|e| self.failed(e)),
+            Err(e) => Err(self.failed(format!("task join error: {}", e))),
+        }
+    }
+
+    fn adapter_id(&self) -> &str {
+        "http"
+    }
+}
+
+/// POST /admin/adapter-config/reload
+///
+/// Output: { "reloaded": true, "sources": ["<source_id>", ...] }
+///
+/// Reloads the requesting tenant's settings; requires authentication.
+/// Values are never echoed back.
+pub(crate) async fn handle_reload_adapter_config(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "reloading adapter settings requires authentication: set TENOR_API_KEY",
+        )
+        .into_response();
+    }
+    let Some(settings) = tenant.adapter_settings.get() else {
+        return json_error(
+            StatusCode::NOT_FOUND,
+            &format!(
+                "tenant '{}' has no adapter configuration: set adapter_config in its [serve.tenants.{}]",
+                tenant.id, tenant.id
+            ),
+        )
+        .into_response();
+    };
+    match settings.reload().await {
+        Ok(sources) => {
+            eprintln!("Reloaded adapter configuration: {} sources", sources.len());
+            Json(serde_json::json!({ "reloaded": true, "sources": sources })).into_response()
+        }
+        Err(e) => json_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            &format!(
+                "adapter configuration not reloaded, keeping the previous one: {}",
+                e
+            ),
+        )
+        .into_response(),
+    }
+}
+
+/// Reload every tenant's adapter settings on every SIGHUP until the server
+/// exits.
+#[cfg(unix)]
+pub(crate) fn reload_on_sighup(state: Arc<AppState>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("warning: SIGHUP will not reload adapters: {}", e);
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            for tenant in state.tenants.all() {
+                let Some(settings) = tenant.adapter_settings.get() else {
+                    continue;
+                };
+                match settings.reload().await {
+                    Ok(sources) if tenant.id == DEFAULT_TENANT => {
+                        eprintln!("Reloaded adapter configuration: {} sources", sources.len())
+                    }
+                    Ok(sources) => eprintln!(
+                        "Reloaded adapter configuration for tenant {}: {} sources",
+                        tenant.id,
+                        sources.len()
+                    ),
+                    Err(e) => eprintln!(
+                        "adapter configuration of tenant {} not reloaded, keeping the previous one: {}",
+                        tenant.id, e
+                    ),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub(crate) fn reload_on_sighup(_state: Arc<AppState>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serve::webhooks::tests::serve_once;
+
+    fn http_source(fields: &[(&str, &str)]) -> tenor_interchange::SourceConstruct {
+        tenor_interchange::SourceConstruct {
+            id: "crm".to_string(),
+            protocol: "http".to_string(),
+            fields: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            description: None,
+            provenance: None,
+            annotations: Default::default(),
+            tenor: None,
+        }
+    }
+
+    fn settings(pairs: &[(&str, &str)]) -> tenor_eval::AdapterConfig {
+        let mut config = tenor_eval::AdapterConfig::default();
+        config.source_configs.insert(
+            "crm".to_string(),
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        config
+    }
+
+    #[test]
+    fn tenant_contracts_cannot_choose_fetch_targets_or_credentials() {
+        let acme = Arc::new(Tenant::new("acme", None));
+        let configured = settings(&[
+            ("base_url", "https://crm.acme.example"),
+            ("auth_token", "acme-token"),
+        ]);
+        let build = |fields: &[(&str, &str)], config: &tenor_eval::AdapterConfig| {
+            registry(&acme, &[http_source(fields)], config).map(|_| ())
+        };
+
+        let metadata = build(&[("base_url", "http://169.254.169.254")], &configured);
+        assert!(metadata.unwrap_err().contains("'base_url'"));
+        let token = build(&[("auth_token", "stolen")], &configured);
+        assert!(token.unwrap_err().contains("'auth_token'"));
+        let unconfigured = build(&[], &tenor_eval::AdapterConfig::default());
+        assert!(unconfigured.unwrap_err().contains("no base_url"));
+        assert!(build(&[], &configured).is_ok());
+        assert!(build(&[("base_url", "https://crm.acme.example")], &configured).is_ok());
+
+        // The default tenant is the operator, whose contracts name their URLs
+        let default = Arc::new(Tenant::new(DEFAULT_TENANT, None));
+        let source = http_source(&[("base_url", "https://crm.example")]);
+        assert!(registry(&default, &[source], &tenor_eval::AdapterConfig::default()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn tenant_fetches_are_vetted_and_use_the_tenant_token() {
+        let (address, request) = serve_once(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 4\r\n\r\ntrue"
+                .to_string(),
+        );
+        let acme = Arc::new(Tenant::new("acme", None));
+        let config = settings(&[
+            ("base_url", &format!("http://{}", address)),
+            ("auth_token", "acme-token"),
+        ]);
+        let adapter = TenantHttpAdapter::new(&acme, &http_source(&[]), &config).unwrap();
+        let source = StructuredSourceRef {
+            source_id: "crm".to_string(),
+            path: "account.active".to_string(),
+        };
+
+        let refused = adapter.fetch("is_active", &source, &BTreeMap::new()).await;
+        assert!(refused
+            .unwrap_err()
+            .to_string()
+            .contains("internal address"));
+
+        acme.webhooks.allow_private_targets();
+        let fetched = adapter.fetch("is_active", &source, &BTreeMap::new()).await;
+        assert_eq!(fetched.unwrap(), serde_json::json!(true));
+        let request = request.recv().unwrap().to_lowercase();
+        assert!(request.starts_with("get /account/active "), "{}", request);
+        assert!(
+            request.contains("authorization: bearer acme-token"),
+            "{}",
+            request
+        );
+    }
+}
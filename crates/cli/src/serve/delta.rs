@@ -0,0 +1,332 @@
+//! Incremental evaluation: baseline fact documents and JSON Patch deltas.
+//!
+//! A client registers a full facts document once with
+//! `POST /contracts/{id}/baselines` and then sends only RFC 6902 patches
+//! against it to `POST /contracts/{id}/evaluate-delta`. Each delta response
+//! reports how the verdicts differ from the baseline's and carries the
+//! baseline id of the patched document, so a client can chain patches
+//! without resending facts. A delta that changes the verdicts is sent to
+//! `verdicts.changed` webhooks. Baselines live in memory, are identified by a
+//! hash of the contract id and facts, and the oldest are evicted once
+//! `MAX_BASELINES` are held.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use sha2::{Digest, Sha256};
+
+use super::json_patch::{apply_patch, PatchError};
+use super::negotiate::Payload;
+use super::tenant::Tenant;
+use super::webhooks::notify_verdict_diff;
+use super::{json_error, json_error_with_code};
+
+/// Maximum number of baselines held before the oldest are evicted.
+const MAX_BASELINES: usize = 1024;
+
+/// A facts document and the verdicts it produced.
+#[derive(Clone)]
+pub(crate) struct Baseline {
+    contract_id: String,
+    facts: serde_json::Value,
+    /// The `verdicts` array of the evaluation result.
+    verdicts: serde_json::Value,
+}
+
+/// Baselines registered by one tenant, keyed by baseline id.
+#[derive(Default)]
+pub(crate) struct FactBaselines {
+    inner: Mutex<BaselineStore>,
+}
+
+#[derive(Default)]
+struct BaselineStore {
+    baselines: HashMap<String, Baseline>,
+    /// Insertion order, oldest first, for eviction.
+    order: VecDeque<String>,
+}
+
+impl FactBaselines {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a baseline and return its id. Storing the same facts for the
+    /// same contract again returns the existing id.
+    fn insert(&self, baseline: Baseline) -> String {
+        let id = baseline_id(&baseline.contract_id, &baseline.facts);
+        let mut store = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if store.baselines.insert(id.clone(), baseline).is_none() {
+            store.order.push_back(id.clone());
+            while store.order.len() > MAX_BASELINES {
+                if let Some(oldest) = store.order.pop_front() {
+                    store.baselines.remove(&oldest);
+                }
+            }
+        }
+        id
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        let store = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        store.baselines.len()
+    }
+
+    fn get(&self, id: &str) -> Option<Baseline> {
+        let store = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        store.baselines.get(id).cloned()
+    }
+}
+
+/// Content-derived baseline id: a hex SHA-256 prefix of the contract id
+/// and the facts.
+fn baseline_id(contract_id: &str, facts: &serde_json::Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(contract_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(facts.to_string().as_bytes());
+    hasher.finalize()[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// POST /contracts/{id}/baselines
+///
+/// Evaluate a full facts document and store it as a baseline for later
+/// deltas.
+pub(crate) async fn handle_create_baseline(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Path(contract_id): Path<String>,
+    Payload(parsed): Payload,
+) -> Response {
+    let facts = match parsed.get("facts") {
+        Some(f) => f.clone(),
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'facts' field").into_response()
+        }
+    };
+    let baseline = match evaluate(&tenant, &contract_id, facts).await {
+        Ok(baseline) => baseline,
+        Err(response) => return response,
+    };
+    let verdicts = baseline.verdicts.clone();
+    let baseline_id = tenant.fact_baselines.insert(baseline);
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "baseline_id": baseline_id,
+            "verdicts": verdicts,
+        })),
+    )
+        .into_response()
+}
+
+/// POST /contracts/{id}/evaluate-delta
+///
+/// Apply a JSON Patch to a baseline's facts, evaluate the result, and
+/// report the verdict changes against the baseline.
+pub(crate) async fn handle_evaluate_delta(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Path(contract_id): Path<String>,
+    Payload(parsed): Payload,
+) -> Response {
+    let baseline_id = match parsed.get("baseline_id").and_then(|v| v.as_str()) {
+        Some(id) => id,
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'baseline_id' field")
+                .into_response()
+        }
+    };
+    let patch = match parsed.get("patch") {
+        Some(p) => p,
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'patch' field").into_response()
+        }
+    };
+    let baseline = match tenant.fact_baselines.get(baseline_id) {
+        Some(b) if b.contract_id == contract_id => b,
+        _ => {
+            return json_error(
+                StatusCode::NOT_FOUND,
+                &format!(
+                    "baseline '{}' not found for contract '{}'",
+                    baseline_id, contract_id
+                ),
+            )
+            .into_response()
+        }
+    };
+
+    let mut facts = baseline.facts.clone();
+    if let Err(e) = apply_patch(&mut facts, patch) {
+        let status = match e {
+            PatchError::Invalid(_) => StatusCode::BAD_REQUEST,
+            PatchError::Conflict(_) => StatusCode::CONFLICT,
+        };
+        return json_error(status, &format!("invalid patch: {}", e)).into_response();
+    }
+
+    let patched = match evaluate(&tenant, &contract_id, facts).await {
+        Ok(patched) => patched,
+        Err(response) => return response,
+    };
+    let diff = verdict_diff(&baseline.verdicts, &patched.verdicts);
+    let verdicts = patched.verdicts.clone();
+    let patched_id = tenant.fact_baselines.insert(patched);
+    notify_verdict_diff(
+        &tenant,
+        &contract_id,
+        serde_json::json!({
+            "source": "evaluate-delta",
+            "baseline_id": baseline_id,
+            "patched_baseline_id": patched_id,
+        }),
+        diff.clone(),
+    );
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "baseline_id": patched_id,
+            "verdicts": verdicts,
+            "diff": diff,
+        })),
+    )
+        .into_response()
+}
+
+/// Evaluate `facts` against a loaded contract.
+async fn evaluate(
+    tenant: &Arc<Tenant>,
+    contract_id: &str,
+    facts: serde_json::Value,
+) -> Result<Baseline, Response> {
+    let handle = tenant.contracts.get(contract_id).ok_or_else(|| {
+        json_error(
+            StatusCode::NOT_FOUND,
+            &format!("contract '{}' not found", contract_id),
+        )
+        .into_response()
+    })?;
+    let result = tokio::task::spawn_blocking(move || {
+        let result = handle.evaluate(&facts)?;
+        Ok::<_, tenor_eval::EvalError>((facts, result))
+    })
+    .await;
+    match result {
+        Ok(Ok((facts, result))) => Ok(Baseline {
+            contract_id: contract_id.to_string(),
+            facts,
+            verdicts: result.verdicts.to_json()["verdicts"].clone(),
+        }),
+        Ok(Err(e)) => Err(json_error_with_code(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            e.code(),
+            &format!("{}", e),
+        )
+        .into_response()),
+        Err(e) => Err(json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("task join error: {}", e),
+        )
+        .into_response()),
+    }
+}
+
+/// Verdicts added, removed, or with a changed payload, keyed by verdict
+/// type.
+pub(crate) fn verdict_diff(
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+) -> serde_json::Value {
+    let payloads = |verdicts: &serde_json::Value| -> BTreeMap<String, serde_json::Value> {
+        verdicts
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|v| {
+                let verdict_type = v.get("type")?.as_str()?.to_string();
+                Some((verdict_type, v.get("payload").cloned().unwrap_or_default()))
+            })
+            .collect()
+    };
+    let (before, after) = (payloads(before), payloads(after));
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (verdict_type, payload) in &after {
+        match before.get(verdict_type) {
+            None => added.push(serde_json::json!({ "type": verdict_type, "payload": payload })),
+            Some(old) if old != payload => changed.push(serde_json::json!({
+                "type": verdict_type,
+                "from": old,
+                "to": payload,
+            })),
+            Some(_) => {}
+        }
+    }
+    let removed: Vec<serde_json::Value> = before
+        .iter()
+        .filter(|(verdict_type, _)| !after.contains_key(*verdict_type))
+        .map(|(verdict_type, payload)| {
+            serde_json::json!({ "type": verdict_type, "payload": payload })
+        })
+        .collect();
+
+    serde_json::json!({
+        "added": added,
+        "removed": removed,
+        "changed": changed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_verdicts() {
+        let before = json!([
+            { "type": "approved", "payload": true },
+            { "type": "tier", "payload": "gold" }
+        ]);
+        let after = json!([
+            { "type": "tier", "payload": "silver" },
+            { "type": "flagged", "payload": true }
+        ]);
+        assert_eq!(
+            verdict_diff(&before, &after),
+            json!({
+                "added": [{ "type": "flagged", "payload": true }],
+                "removed": [{ "type": "approved", "payload": true }],
+                "changed": [{ "type": "tier", "from": "gold", "to": "silver" }],
+            })
+        );
+    }
+
+    #[test]
+    fn baselines_are_content_addressed_and_bounded() {
+        let baselines = FactBaselines::new();
+        let baseline = |n: usize| Baseline {
+            contract_id: "c".to_string(),
+            facts: json!({ "n": n }),
+            verdicts: json!([]),
+        };
+        let first = baselines.insert(baseline(0));
+        assert_eq!(baselines.insert(baseline(0)), first);
+        assert_ne!(baseline_id("other", &json!({ "n": 0 })), first);
+
+        for n in 1..=MAX_BASELINES {
+            baselines.insert(baseline(n));
+        }
+        assert!(baselines.get(&first).is_none(), "oldest baseline evicted");
+        assert!(baselines
+            .get(&baseline_id("c", &json!({ "n": 1 })))
+            .is_some());
+    }
+}
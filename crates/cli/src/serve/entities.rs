@@ -0,0 +1,411 @@
+//! Entity state projections.
+//!
+//! The entity endpoints query a tenant's [`EntityStore`] on every request.
+//! Every `TenorStorage` backend is an `EntityStore`. `tenor serve`
+//! evaluates without a storage backend of its own, so its tenants' stores
+//! are [`HistoryFile`]s over a history exported from one: the `EntityStateRecord`s,
+//! `EntityTransitionHistoryRecord`s and `ProvenanceRecord`s that
+//! `tenor_eval::EntityHistory::load` reads from a `TenorStorage` (the
+//! format `tenor eval --history` reads). The default tenant's history comes
+//! from `--history` or `[serve] history`, another tenant's from `history`
+//! in its `[serve.tenants.<id>]`. The file is re-read when its modification
+//! time or size changes, so a fresh export is served without a restart while
+//! the queries of one request share a single read.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use axum::extract::{Path as UrlPath, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use tenor_storage::{
+    EntityStateRecord, EntityTransitionHistoryRecord, ProvenanceRecord, StorageError, TenorStorage,
+};
+
+use super::json_error;
+use super::tenant::Tenant;
+
+/// The storage queries behind the entity endpoints, with the semantics of
+/// the `TenorStorage` methods of the same names.
+#[async_trait]
+pub(crate) trait EntityStore: Send + Sync {
+    async fn list_entity_states(
+        &self,
+        entity_id: &str,
+        state_filter: Option<&str>,
+    ) -> Result<Vec<EntityStateRecord>, StorageError>;
+
+    async fn get_entity_state(
+        &self,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Result<EntityStateRecord, StorageError>;
+
+    /// Every transition of the entity's instances, oldest first (by
+    /// `executed_at`, then `to_version`).
+    async fn list_entity_transitions(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityTransitionHistoryRecord>, StorageError>;
+
+    async fn get_provenance(
+        &self,
+        operation_execution_id: &str,
+    ) -> Result<Vec<ProvenanceRecord>, StorageError>;
+}
+
+#[async_trait]
+impl<S: TenorStorage> EntityStore for S {
+    async fn list_entity_states(
+        &self,
+        entity_id: &str,
+        state_filter: Option<&str>,
+    ) -> Result<Vec<EntityStateRecord>, StorageError> {
+        TenorStorage::list_entity_states(self, entity_id, state_filter).await
+    }
+
+    async fn get_entity_state(
+        &self,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Result<EntityStateRecord, StorageError> {
+        TenorStorage::get_entity_state(self, entity_id, instance_id).await
+    }
+
+    async fn list_entity_transitions(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityTransitionHistoryRecord>, StorageError> {
+        TenorStorage::list_entity_transitions(self, entity_id, None).await
+    }
+
+    async fn get_provenance(
+        &self,
+        operation_execution_id: &str,
+    ) -> Result<Vec<ProvenanceRecord>, StorageError> {
+        TenorStorage::get_provenance(self, operation_execution_id).await
+    }
+}
+
+/// Read an exported entity history.
+pub(crate) fn load_history(path: &Path) -> Result<tenor_eval::EntityHistory, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read history {}: {}", path.display(), e))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("invalid JSON in {}: {}", path.display(), e))?;
+    tenor_eval::EntityHistory::from_json(&value).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Modification time and size of a history file, as of one read.
+type Stamp = (SystemTime, u64);
+
+/// An entity store over an exported history file, cached until the file
+/// changes.
+pub(crate) struct HistoryFile {
+    path: PathBuf,
+    cached: Mutex<Option<(Stamp, Arc<tenor_eval::EntityHistory>)>>,
+}
+
+impl HistoryFile {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// The history in the file, re-read only if the file's stamp changed
+    /// since the last read. The stamp is taken before reading, so a write
+    /// racing the read is picked up by the next query.
+    async fn read(&self) -> Result<Arc<tenor_eval::EntityHistory>, StorageError> {
+        let path = self.path.clone();
+        let cached = self
+            .cached
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let (stamp, history) = tokio::task::spawn_blocking(move || {
+            let meta = std::fs::metadata(&path)
+                .map_err(|e| format!("could not read history {}: {}", path.display(), e))?;
+            let stamp = (
+                meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                meta.len(),
+            );
+            match cached {
+                Some((cached_stamp, history)) if cached_stamp == stamp => Ok((stamp, history)),
+                _ => load_history(&path).map(|history| (stamp, Arc::new(history))),
+            }
+        })
+        .await
+        .map_err(|e| StorageError::Backend(format!("task join error: {}", e)))?
+        .map_err(StorageError::Backend)?;
+        *self.cached.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((stamp, history.clone()));
+        Ok(history)
+    }
+}
+
+#[async_trait]
+impl EntityStore for HistoryFile {
+    async fn list_entity_states(
+        &self,
+        entity_id: &str,
+        state_filter: Option<&str>,
+    ) -> Result<Vec<EntityStateRecord>, StorageError> {
+        let history = self.read().await?;
+        Ok(history
+            .instances(entity_id, state_filter)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    async fn get_entity_state(
+        &self,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Result<EntityStateRecord, StorageError> {
+        let history = self.read().await?;
+        history
+            .instance(entity_id, instance_id)
+            .cloned()
+            .ok_or_else(|| StorageError::EntityNotFound {
+                entity_id: entity_id.to_string(),
+                instance_id: instance_id.to_string(),
+            })
+    }
+
+    async fn list_entity_transitions(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityTransitionHistoryRecord>, StorageError> {
+        let history = self.read().await?;
+        let mut transitions: Vec<EntityTransitionHistoryRecord> = history
+            .transitions
+            .iter()
+            .filter(|r| r.transition.entity_id == entity_id)
+            .cloned()
+            .collect();
+        transitions.sort_by(|a, b| {
+            (&a.executed_at, a.transition.to_version)
+                .cmp(&(&b.executed_at, b.transition.to_version))
+        });
+        Ok(transitions)
+    }
+
+    async fn get_provenance(
+        &self,
+        operation_execution_id: &str,
+    ) -> Result<Vec<ProvenanceRecord>, StorageError> {
+        let history = self.read().await?;
+        Ok(history
+            .provenance_of(operation_execution_id)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+}
+
+/// The tenant's entity store, or a 404 naming how to provide one.
+fn store(tenant: &Tenant) -> Result<&Arc<dyn EntityStore>, Box<Response>> {
+    tenant.entity_store.get().ok_or_else(|| {
+        Box::new(json_error(
+            StatusCode::NOT_FOUND,
+            "no entity history loaded for this tenant: start tenor serve with --history or set history in tenor.toml",
+        )
+        .into_response())
+    })
+}
+
+fn instance_not_found(entity_id: &str, instance_id: &str) -> Response {
+    json_error(
+        StatusCode::NOT_FOUND,
+        &format!(
+            "instance '{}' of entity '{}' not found",
+            instance_id, entity_id
+        ),
+    )
+    .into_response()
+}
+
+fn storage_error(e: StorageError) -> Response {
+    json_error(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        &format!("could not read entity storage: {}", e),
+    )
+    .into_response()
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct InstancesQuery {
+    state: Option<String>,
+}
+
+/// GET /entities/{entity_id}/instances
+///
+/// Current state records of the entity's instances; `?state=` keeps only
+/// those in one state.
+pub(crate) async fn handle_list_instances(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    UrlPath(entity_id): UrlPath<String>,
+    Query(query): Query<InstancesQuery>,
+) -> Response {
+    let store = match store(&tenant) {
+        Ok(s) => s,
+        Err(response) => return *response,
+    };
+    let instances = match store
+        .list_entity_states(&entity_id, query.state.as_deref())
+        .await
+    {
+        Ok(instances) => instances,
+        Err(e) => return storage_error(e),
+    };
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "entity_id": entity_id,
+            "instances": instances,
+        })),
+    )
+        .into_response()
+}
+
+/// GET /entities/{entity_id}/instances/{instance_id}
+pub(crate) async fn handle_get_instance(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    UrlPath((entity_id, instance_id)): UrlPath<(String, String)>,
+) -> Response {
+    let store = match store(&tenant) {
+        Ok(s) => s,
+        Err(response) => return *response,
+    };
+    match store.get_entity_state(&entity_id, &instance_id).await {
+        Ok(record) => (StatusCode::OK, Json(serde_json::json!(record))).into_response(),
+        Err(StorageError::EntityNotFound { .. }) => instance_not_found(&entity_id, &instance_id),
+        Err(e) => storage_error(e),
+    }
+}
+
+/// GET /entities/{entity_id}/instances/{instance_id}/history
+///
+/// The instance's current state and its transitions, oldest first, each
+/// with the provenance records (facts and verdicts used) of the operation
+/// execution that made it. `current` is null for a destroyed instance.
+pub(crate) async fn handle_instance_history(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    UrlPath((entity_id, instance_id)): UrlPath<(String, String)>,
+) -> Response {
+    let store = match store(&tenant) {
+        Ok(s) => s,
+        Err(response) => return *response,
+    };
+    let current = match store.get_entity_state(&entity_id, &instance_id).await {
+        Ok(record) => Some(record),
+        Err(StorageError::EntityNotFound { .. }) => None,
+        Err(e) => return storage_error(e),
+    };
+    let timeline: Vec<EntityTransitionHistoryRecord> =
+        match store.list_entity_transitions(&entity_id).await {
+            Ok(transitions) => transitions
+                .into_iter()
+                .filter(|r| r.transition.instance_id == instance_id)
+                .collect(),
+            Err(e) => return storage_error(e),
+        };
+    if current.is_none() && timeline.is_empty() {
+        return instance_not_found(&entity_id, &instance_id);
+    }
+    let executions: BTreeSet<&str> = timeline
+        .iter()
+        .map(|r| r.transition.operation_execution_id.as_str())
+        .collect();
+    let mut provenance = BTreeMap::new();
+    for execution_id in executions {
+        match store.get_provenance(execution_id).await {
+            Ok(records) => provenance.insert(execution_id, records),
+            Err(e) => return storage_error(e),
+        };
+    }
+    let transitions: Vec<serde_json::Value> = timeline
+        .iter()
+        .map(|record| {
+            let mut entry = serde_json::json!(record);
+            entry["provenance"] =
+                serde_json::json!(provenance[record.transition.operation_execution_id.as_str()]);
+            entry
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "entity_id": entity_id,
+            "instance_id": instance_id,
+            "current": current,
+            "transitions": transitions,
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(state: &str) -> String {
+        serde_json::json!({
+            "entity_states": [{
+                "entity_id": "Order", "instance_id": "o1", "state": state, "version": 1,
+                "updated_at": "2025-03-01T09:00:00Z", "last_flow_id": null,
+                "last_operation_id": null
+            }],
+            "transitions": []
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn unchanged_history_file_is_read_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        std::fs::write(&path, export("draft")).unwrap();
+        let file = HistoryFile::new(path.clone());
+
+        let first = file.read().await.unwrap();
+        let second = file.read().await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        std::fs::write(&path, export("approved")).unwrap();
+        let third = file.read().await.unwrap();
+        assert!(!Arc::ptr_eq(&first, &third));
+        assert_eq!(third.instance("Order", "o1").unwrap().state, "approved");
+    }
+
+    #[tokio::test]
+    async fn history_file_changes_are_served_without_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        std::fs::write(&path, export("draft")).unwrap();
+        let store: Arc<dyn EntityStore> = Arc::new(HistoryFile::new(path.clone()));
+
+        let before = store.get_entity_state("Order", "o1").await.unwrap();
+        std::fs::write(&path, export("approved")).unwrap();
+        let after = store.get_entity_state("Order", "o1").await.unwrap();
+
+        assert_eq!(before.state, "draft");
+        assert_eq!(after.state, "approved");
+        assert!(matches!(
+            store.get_entity_state("Order", "o9").await,
+            Err(StorageError::EntityNotFound { .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            store.list_entity_states("Order", None).await,
+            Err(StorageError::Backend(_))
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+//! Flow execution history.
+//!
+//! Every flow run the server makes -- started by `/evaluate`, resumed by
+//! `/flows/resume`, or expired by a timer -- is recorded as a
+//! `FlowExecutionRecord` carrying the run's correlation id. Runs started
+//! without one get a generated id, returned in the response, and a
+//! resumption inherits the id from its continuation, so a business
+//! transaction spanning several flow runs can be read back with
+//! `GET /flows/executions?correlation_id=`. Records live in memory; the
+//! oldest are evicted once `MAX_EXECUTIONS` are held.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use axum::extract::Query;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use tenor_storage::FlowExecutionRecord;
+
+use super::tenant::Tenant;
+
+/// Maximum number of executions held before the oldest are evicted.
+const MAX_EXECUTIONS: usize = 1024;
+
+/// One tenant's flow executions, oldest first.
+#[derive(Default)]
+pub(crate) struct FlowExecutions {
+    next_id: AtomicU64,
+    records: Mutex<VecDeque<FlowExecutionRecord>>,
+}
+
+impl FlowExecutions {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn next_id(&self) -> String {
+        format!("exec-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    pub(crate) fn record(&self, record: FlowExecutionRecord) {
+        let mut records = self.lock();
+        records.push_back(record);
+        while records.len() > MAX_EXECUTIONS {
+            records.pop_front();
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Executions matching every given filter, oldest first.
+    pub(crate) fn list(
+        &self,
+        correlation_id: Option<&str>,
+        flow_id: Option<&str>,
+    ) -> Vec<FlowExecutionRecord> {
+        self.lock()
+            .iter()
+            .filter(|r| correlation_id.is_none_or(|c| r.correlation_id.as_deref() == Some(c)))
+            .filter(|r| flow_id.is_none_or(|f| r.flow_id == f))
+            .cloned()
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<FlowExecutionRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A random 128-bit correlation id, hex encoded.
+pub(crate) fn generate_correlation_id() -> String {
+    let mut bytes = [0u8; 16];
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct ExecutionsQuery {
+    correlation_id: Option<String>,
+    flow_id: Option<String>,
+}
+
+/// GET /flows/executions
+///
+/// The tenant's recorded flow executions, oldest first, filtered by
+/// `?correlation_id=` and `?flow_id=`.
+pub(crate) async fn handle_list_executions(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Query(query): Query<ExecutionsQuery>,
+) -> Response {
+    let executions = tenant
+        .flow_executions
+        .list(query.correlation_id.as_deref(), query.flow_id.as_deref());
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "executions": executions })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(id: &str, flow_id: &str, correlation_id: Option<&str>) -> FlowExecutionRecord {
+        FlowExecutionRecord {
+            id: id.to_string(),
+            flow_id: flow_id.to_string(),
+            contract_id: "c".to_string(),
+            persona_id: "clerk".to_string(),
+            started_at: "2025-03-01T09:00:00Z".to_string(),
+            completed_at: None,
+            outcome: "suspended".to_string(),
+            snapshot_facts: serde_json::json!({}),
+            snapshot_verdicts: serde_json::json!([]),
+            correlation_id: correlation_id.map(String::from),
+        }
+    }
+
+    #[test]
+    fn lists_by_correlation_and_flow_oldest_first() {
+        let executions = FlowExecutions::new();
+        executions.record(execution("exec-1", "intake", Some("order-42")));
+        executions.record(execution("exec-2", "intake", Some("order-7")));
+        executions.record(execution("exec-3", "approval", Some("order-42")));
+
+        let ids = |records: Vec<FlowExecutionRecord>| -> Vec<String> {
+            records.into_iter().map(|r| r.id).collect()
+        };
+        assert_eq!(
+            ids(executions.list(Some("order-42"), None)),
+            ["exec-1", "exec-3"]
+        );
+        assert_eq!(
+            ids(executions.list(Some("order-42"), Some("approval"))),
+            ["exec-3"]
+        );
+        assert_eq!(executions.list(None, None).len(), 3);
+    }
+
+    #[test]
+    fn evicts_the_oldest_beyond_the_limit() {
+        let executions = FlowExecutions::new();
+        for i in 0..=MAX_EXECUTIONS {
+            executions.record(execution(&format!("exec-{}", i), "f", None));
+        }
+        assert_eq!(executions.len(), MAX_EXECUTIONS);
+        assert_eq!(executions.list(None, None)[0].id, "exec-1");
+    }
+}
@@ -1,14 +1,19 @@
-//! Core HTTP route handlers: health, contracts, elaborate, evaluate, explain.
+//! Core HTTP route handlers: health, contracts, elaborate, evaluate,
+//! validate-facts, explain.
 
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use time::OffsetDateTime;
+
+use axum::extract::Path;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
-use axum::Json;
+use axum::{Extension, Json};
 
-use super::state::AppState;
-use super::{json_error, MAX_SOURCE_SIZE};
+use super::negotiate::{negotiated, Payload};
+use super::pinning::check_contract_pin;
+use super::tenant::Tenant;
+use super::{json_error, json_error_with_code, MAX_SOURCE_SIZE};
 
 /// Fallback handler for unmatched routes.
 pub(crate) async fn handle_not_found() -> impl IntoResponse {
@@ -25,12 +30,16 @@ pub(crate) async fn handle_health() -> impl IntoResponse {
 }
 
 /// GET /contracts
-pub(crate) async fn handle_list_contracts(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let contracts = state.contracts.read().await;
-
-    let contract_list: Vec<serde_json::Value> = contracts
+pub(crate) async fn handle_list_contracts(
+    Extension(tenant): Extension<Arc<Tenant>>,
+) -> impl IntoResponse {
+    let contract_list: Vec<serde_json::Value> = tenant
+        .contracts
+        .handles()
         .iter()
-        .map(|(id, bundle)| {
+        .map(|handle| {
+            let id = handle.id();
+            let bundle = handle.bundle();
             let constructs = bundle
                 .get("constructs")
                 .and_then(|c| c.as_array())
@@ -56,6 +65,7 @@ pub(crate) async fn handle_list_contracts(State(state): State<Arc<AppState>>) ->
 
             serde_json::json!({
                 "id": id,
+                "etag": handle.etag(),
                 "construct_count": construct_count,
                 "facts": facts,
                 "operations": operations,
@@ -70,13 +80,11 @@ pub(crate) async fn handle_list_contracts(State(state): State<Arc<AppState>>) ->
 
 /// GET /contracts/{id}/operations
 pub(crate) async fn handle_get_operations(
-    State(state): State<Arc<AppState>>,
+    Extension(tenant): Extension<Arc<Tenant>>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    let contracts = state.contracts.read().await;
-
-    let bundle = match contracts.get(&id) {
-        Some(b) => b,
+    let handle = match tenant.contracts.get(&id) {
+        Some(h) => h,
         None => {
             return json_error(
                 StatusCode::NOT_FOUND,
@@ -86,7 +94,8 @@ pub(crate) async fn handle_get_operations(
         }
     };
 
-    let constructs = bundle
+    let constructs = handle
+        .bundle()
         .get("constructs")
         .and_then(|c| c.as_array())
         .cloned()
@@ -184,7 +193,10 @@ fn has_escaping_imports(source: &str) -> bool {
 }
 
 /// POST /elaborate
-pub(crate) async fn handle_elaborate(Json(parsed): Json<serde_json::Value>) -> impl IntoResponse {
+pub(crate) async fn handle_elaborate(
+    headers: HeaderMap,
+    Payload(parsed): Payload,
+) -> impl IntoResponse {
     let source = match parsed.get("source").and_then(|v| v.as_str()) {
         Some(s) => s.to_string(),
         None => {
@@ -228,17 +240,18 @@ pub(crate) async fn handle_elaborate(Json(parsed): Json<serde_json::Value>) -> i
         let tmp_dir = tempfile::tempdir()?;
         let tmp_path = tmp_dir.path().join(&filename);
         std::fs::write(&tmp_path, &source)?;
-        let elab_result = tenor_core::elaborate::elaborate(&tmp_path);
+        let elab_result = crate::elaborate(&tmp_path);
         Ok::<_, Box<dyn std::error::Error + Send + Sync>>(elab_result)
     })
     .await;
 
     match result {
-        Ok(Ok(Ok(bundle))) => (StatusCode::OK, Json(bundle)).into_response(),
+        Ok(Ok(Ok(bundle))) => negotiated(&headers, StatusCode::OK, bundle),
         Ok(Ok(Err(e))) => {
             let err_response = serde_json::json!({
                 "error": format!("{:?}", e),
-                "details": e.to_json_value(),
+                "code": e.code(),
+                "details": e.to_json_value_with_code(),
             });
             (StatusCode::BAD_REQUEST, Json(err_response)).into_response()
         }
@@ -257,8 +270,9 @@ pub(crate) async fn handle_elaborate(Json(parsed): Json<serde_json::Value>) -> i
 
 /// POST /evaluate
 pub(crate) async fn handle_evaluate(
-    State(state): State<Arc<AppState>>,
-    Json(parsed): Json<serde_json::Value>,
+    Extension(tenant): Extension<Arc<Tenant>>,
+    headers: HeaderMap,
+    Payload(parsed): Payload,
 ) -> impl IntoResponse {
     let bundle_id = match parsed.get("bundle_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
@@ -282,10 +296,20 @@ pub(crate) async fn handle_evaluate(
         .get("persona")
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
+    let correlation_id = match parsed.get("correlation_id") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(serde_json::Value::String(id)) if !id.is_empty() => Some(id.clone()),
+        Some(_) => {
+            return json_error(
+                StatusCode::BAD_REQUEST,
+                "'correlation_id' must be a non-empty string",
+            )
+            .into_response()
+        }
+    };
 
-    let contracts = state.contracts.read().await;
-    let bundle = match contracts.get(&bundle_id) {
-        Some(b) => b.clone(),
+    let handle = match tenant.contracts.get(&bundle_id) {
+        Some(h) => h,
         None => {
             return json_error(
                 StatusCode::NOT_FOUND,
@@ -294,7 +318,9 @@ pub(crate) async fn handle_evaluate(
             .into_response()
         }
     };
-    drop(contracts);
+    if let Err(response) = check_contract_pin(&headers, &parsed, &handle) {
+        return *response;
+    }
 
     if let Some(fid) = flow_id {
         let p = match persona {
@@ -309,90 +335,383 @@ pub(crate) async fn handle_evaluate(
         };
 
         let fid_for_response = fid.clone();
+        let handle_for_response = handle.clone();
+        let facts_for_record = facts.clone();
+        let correlation_id =
+            correlation_id.unwrap_or_else(super::executions::generate_correlation_id);
+        let started_at = OffsetDateTime::now_utc();
 
         let result = tokio::task::spawn_blocking(move || {
-            tenor_eval::evaluate_flow(
-                &bundle,
+            handle.start_flow(
                 &facts,
                 &fid,
                 &p,
                 None,
                 &tenor_eval::InstanceBindingMap::new(),
+                tenor_eval::FlowOptions {
+                    correlation_id: Some(&correlation_id),
+                    ..tenor_eval::FlowOptions::default()
+                },
             )
         })
         .await;
 
+        let run = FlowRunRecord {
+            flow_id: &fid_for_response,
+            facts: facts_for_record,
+            started_at,
+        };
+        flow_run_response(&tenant, &handle_for_response, run, result)
+    } else {
+        let result = tokio::task::spawn_blocking(move || handle.evaluate(&facts)).await;
+
         match result {
-            Ok(Ok(result)) => {
-                let mut json_output = serde_json::Map::new();
-                json_output.insert("flow_id".to_string(), serde_json::json!(fid_for_response));
-                json_output.insert(
-                    "outcome".to_string(),
-                    serde_json::json!(result.flow_result.outcome),
-                );
-                json_output.insert(
-                    "initiating_persona".to_string(),
-                    serde_json::json!(result.flow_result.initiating_persona),
-                );
-                let entity_changes: serde_json::Value = result
-                    .flow_result
-                    .entity_state_changes
-                    .iter()
-                    .map(|e| {
-                        serde_json::json!({
-                            "entity_id": e.entity_id,
-                            "from": e.from_state,
-                            "to": e.to_state
-                        })
-                    })
-                    .collect();
-                json_output.insert("entity_state_changes".to_string(), entity_changes);
-                let steps: serde_json::Value = result
-                    .flow_result
-                    .steps_executed
-                    .iter()
-                    .map(|s| {
-                        serde_json::json!({
-                            "step_id": s.step_id,
-                            "result": s.result
-                        })
-                    })
-                    .collect();
-                json_output.insert("steps_executed".to_string(), steps);
-                json_output.insert("verdicts".to_string(), result.verdicts.to_json());
-                (StatusCode::OK, Json(serde_json::Value::Object(json_output))).into_response()
-            }
-            Ok(Err(e)) => {
-                json_error(StatusCode::INTERNAL_SERVER_ERROR, &format!("{}", e)).into_response()
-            }
+            Ok(Ok(result)) => (StatusCode::OK, Json(result.verdicts.to_json())).into_response(),
+            Ok(Err(e)) => json_error_with_code(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.code(),
+                &format!("{}", e),
+            )
+            .into_response(),
             Err(e) => json_error(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 &format!("task join error: {}", e),
             )
             .into_response(),
         }
-    } else {
-        let result =
-            tokio::task::spawn_blocking(move || tenor_eval::evaluate(&bundle, &facts)).await;
+    }
+}
 
-        match result {
-            Ok(Ok(result)) => (StatusCode::OK, Json(result.verdicts.to_json())).into_response(),
-            Ok(Err(e)) => {
-                json_error(StatusCode::INTERNAL_SERVER_ERROR, &format!("{}", e)).into_response()
+/// POST /flows/resume
+///
+/// Resume a flow suspended at a pausing HandoffStep. The body carries the
+/// `continuation` returned by `/evaluate` (or a previous resume) and the
+/// `persona` resuming it, which must be the handoff's `to_persona`.
+pub(crate) async fn handle_resume_flow(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Payload(parsed): Payload,
+) -> impl IntoResponse {
+    let bundle_id = match parsed.get("bundle_id").and_then(|v| v.as_str()) {
+        Some(id) => id.to_string(),
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'bundle_id' field").into_response()
+        }
+    };
+    let persona = match parsed.get("persona").and_then(|v| v.as_str()) {
+        Some(p) => p.to_string(),
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'persona' field").into_response()
+        }
+    };
+    let continuation = match parsed.get("continuation") {
+        Some(c) => match tenor_eval::FlowContinuation::from_json(c) {
+            Ok(c) => c,
+            Err(e) => {
+                return json_error(
+                    StatusCode::BAD_REQUEST,
+                    &format!("invalid continuation: {}", e),
+                )
+                .into_response()
             }
-            Err(e) => json_error(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                &format!("task join error: {}", e),
+        },
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'continuation' field")
+                .into_response()
+        }
+    };
+
+    let handle = match tenant.contracts.get(&bundle_id) {
+        Some(h) => h,
+        None => {
+            return json_error(
+                StatusCode::NOT_FOUND,
+                &format!("contract '{}' not found", bundle_id),
+            )
+            .into_response()
+        }
+    };
+
+    // Disarm the flow's expiry timer, if the caller holds one. The persona
+    // is checked first so a rejected resume leaves the timer armed.
+    if let Some(timer_id) = parsed.get("timer_id").and_then(|v| v.as_str()) {
+        if persona != continuation.to_persona {
+            return json_error(
+                StatusCode::FORBIDDEN,
+                &format!(
+                    "flow is paused at '{}' awaiting persona '{}', not '{}'",
+                    continuation.handoff_step, continuation.to_persona, persona
+                ),
             )
+            .into_response();
+        }
+        match tenant.flow_timers.disarm(timer_id) {
+            Ok(()) => {}
+            Err(Some(status)) => {
+                return json_error(
+                    StatusCode::CONFLICT,
+                    &format!("timer '{}' is already {}", timer_id, status),
+                )
+                .into_response()
+            }
+            Err(None) => {
+                return json_error(
+                    StatusCode::NOT_FOUND,
+                    &format!("timer '{}' not found", timer_id),
+                )
+                .into_response()
+            }
+        }
+    }
+
+    let flow_id = continuation.flow_id.clone();
+    let facts = continuation.snapshot.snapshot.facts.to_json();
+    let started_at = OffsetDateTime::now_utc();
+    let handle_for_response = handle.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        handle.resume_flow(
+            continuation,
+            &persona,
+            &tenor_eval::EntityVersions::default(),
+        )
+    })
+    .await;
+
+    let run = FlowRunRecord {
+        flow_id: &flow_id,
+        facts,
+        started_at,
+    };
+    flow_run_response(&tenant, &handle_for_response, run, result)
+}
+
+/// GET /flows/timers/{id}
+pub(crate) async fn handle_get_flow_timer(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match tenant.flow_timers.describe(&id) {
+        Some(timer) => (StatusCode::OK, Json(timer)).into_response(),
+        None => {
+            json_error(StatusCode::NOT_FOUND, &format!("timer '{}' not found", id)).into_response()
+        }
+    }
+}
+
+fn flow_run_response(
+    tenant: &Arc<Tenant>,
+    handle: &tenor_eval::ContractHandle,
+    record: FlowRunRecord<'_>,
+    result: Result<Result<tenor_eval::FlowRun, tenor_eval::EvalError>, tokio::task::JoinError>,
+) -> axum::response::Response {
+    match result {
+        Ok(Ok(run)) => (
+            StatusCode::OK,
+            Json(flow_run_json(tenant, handle, record, run)),
+        )
             .into_response(),
+        Ok(Err(e)) => json_error_with_code(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            e.code(),
+            &format!("{}", e),
+        )
+        .into_response(),
+        Err(e) => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("task join error: {}", e),
+        )
+        .into_response(),
+    }
+}
+
+/// What the server knows about a flow run beyond its result, for its
+/// `FlowExecutionRecord`.
+pub(crate) struct FlowRunRecord<'a> {
+    pub(crate) flow_id: &'a str,
+    /// The snapshot's facts.
+    pub(crate) facts: serde_json::Value,
+    pub(crate) started_at: OffsetDateTime,
+}
+
+/// Render a started, resumed or expired flow run. Completed runs keep the
+/// shape `/evaluate` has always returned; suspended runs carry the
+/// continuation to pass to `/flows/resume`, and arm an expiry timer when
+/// the handoff has a deadline. Both report the run's `correlation_id` and
+/// the `execution_id` it is recorded under for `/flows/executions`. Entity
+/// transitions are sent to `entity_states.changed` webhooks.
+pub(crate) fn flow_run_json(
+    tenant: &Arc<Tenant>,
+    handle: &tenor_eval::ContractHandle,
+    record: FlowRunRecord<'_>,
+    run: tenor_eval::FlowRun,
+) -> serde_json::Value {
+    let flow_id = record.flow_id;
+    let steps_json = |steps: &[tenor_eval::StepRecord]| -> serde_json::Value {
+        steps
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "step_id": s.step_id,
+                    "result": s.result
+                })
+            })
+            .collect()
+    };
+    let changes_json = |changes: &[tenor_eval::EffectRecord]| -> serde_json::Value {
+        changes
+            .iter()
+            .map(|e| {
+                let mut change = serde_json::json!({
+                    "entity_id": e.entity_id,
+                    "from": e.from_state,
+                    "to": e.to_state
+                });
+                if e.kind != tenor_eval::types::EffectKind::Transition {
+                    change["kind"] = serde_json::json!(e.kind.as_str());
+                }
+                if !e.attributes.is_empty() {
+                    change["attributes"] = e
+                        .attributes
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.to_json()))
+                        .collect::<serde_json::Map<_, _>>()
+                        .into();
+                }
+                change
+            })
+            .collect()
+    };
+
+    let completed = matches!(run, tenor_eval::FlowRun::Completed(_));
+    let mut response = match run {
+        tenor_eval::FlowRun::Completed(result) => serde_json::json!({
+            "flow_id": flow_id,
+            "status": "completed",
+            "outcome": result.flow_result.outcome,
+            "initiating_persona": result.flow_result.initiating_persona,
+            "entity_state_changes": changes_json(&result.flow_result.entity_state_changes),
+            "steps_executed": steps_json(&result.flow_result.steps_executed),
+            "verdicts": result.verdicts.to_json(),
+            "correlation_id": result.flow_result.correlation_id,
+        }),
+        tenor_eval::FlowRun::Suspended(suspended) => {
+            let continuation = &suspended.continuation;
+            let mut response = serde_json::json!({
+                "flow_id": flow_id,
+                "status": "suspended",
+                "paused_at": continuation.handoff_step,
+                "awaiting_persona": continuation.to_persona,
+                "initiating_persona": continuation.initiating_persona,
+                "entity_state_changes": changes_json(&suspended.entity_state_changes),
+                "steps_executed": steps_json(&suspended.steps_executed),
+                "verdicts": continuation.snapshot.snapshot.verdicts.to_json(),
+                "correlation_id": continuation.correlation_id,
+                "continuation": continuation.to_json(),
+            });
+            if let Some(deadline) = &continuation.deadline {
+                response["deadline"] = serde_json::json!(deadline);
+            }
+            if let Some(timer_id) = super::timers::arm(tenant, handle, continuation) {
+                response["timer_id"] = serde_json::json!(timer_id);
+            }
+            response
+        }
+    };
+
+    let execution_id = tenant.flow_executions.next_id();
+    tenant
+        .flow_executions
+        .record(tenor_storage::FlowExecutionRecord {
+            id: execution_id.clone(),
+            flow_id: flow_id.to_string(),
+            contract_id: handle.id().to_string(),
+            persona_id: response["initiating_persona"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            started_at: super::schedules::rfc3339(record.started_at),
+            completed_at: completed.then(|| super::schedules::rfc3339(OffsetDateTime::now_utc())),
+            outcome: match response["outcome"].as_str() {
+                Some(outcome) => outcome.to_string(),
+                None => "suspended".to_string(),
+            },
+            snapshot_facts: record.facts,
+            snapshot_verdicts: response["verdicts"].clone(),
+            correlation_id: response["correlation_id"].as_str().map(String::from),
+        });
+    response["execution_id"] = serde_json::json!(execution_id);
+
+    let changes = &response["entity_state_changes"];
+    if changes.as_array().is_some_and(|c| !c.is_empty()) {
+        super::webhooks::notify(
+            tenant,
+            super::webhooks::ENTITY_STATES_CHANGED,
+            handle.id(),
+            serde_json::json!({
+                "flow_id": flow_id,
+                "status": response["status"],
+                "changes": changes,
+            }),
+        );
+    }
+    response
+}
+
+/// POST /validate-facts
+///
+/// Checks `facts` against the contract without evaluating and reports every
+/// missing or ill-typed fact at once, each with a JSON pointer into `facts`.
+/// Invalid facts are a 200 with `"valid": false`, not an error status.
+pub(crate) async fn handle_validate_facts(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    headers: HeaderMap,
+    Payload(parsed): Payload,
+) -> impl IntoResponse {
+    let bundle_id = match parsed.get("bundle_id").and_then(|v| v.as_str()) {
+        Some(id) => id.to_string(),
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'bundle_id' field").into_response()
+        }
+    };
+
+    let facts = match parsed.get("facts") {
+        Some(f) => f,
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'facts' field").into_response()
+        }
+    };
+
+    let handle = match tenant.contracts.get(&bundle_id) {
+        Some(h) => h,
+        None => {
+            return json_error(
+                StatusCode::NOT_FOUND,
+                &format!("contract '{}' not found", bundle_id),
+            )
+            .into_response()
         }
+    };
+    if let Err(response) = check_contract_pin(&headers, &parsed, &handle) {
+        return *response;
     }
+
+    let errors: Vec<serde_json::Value> = tenor_eval::validate_facts(handle.contract(), facts)
+        .iter()
+        .map(tenor_eval::FactError::to_json)
+        .collect();
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "valid": errors.is_empty(),
+            "errors": errors,
+        })),
+    )
+        .into_response()
 }
 
 /// POST /explain
 pub(crate) async fn handle_explain(
-    State(state): State<Arc<AppState>>,
-    Json(parsed): Json<serde_json::Value>,
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Payload(parsed): Payload,
 ) -> impl IntoResponse {
     let bundle_id = match parsed.get("bundle_id").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
@@ -401,9 +720,8 @@ pub(crate) async fn handle_explain(
         }
     };
 
-    let contracts = state.contracts.read().await;
-    let bundle = match contracts.get(&bundle_id) {
-        Some(b) => b.clone(),
+    let handle = match tenant.contracts.get(&bundle_id) {
+        Some(h) => h,
         None => {
             return json_error(
                 StatusCode::NOT_FOUND,
@@ -412,10 +730,10 @@ pub(crate) async fn handle_explain(
             .into_response()
         }
     };
-    drop(contracts);
 
     let result =
-        tokio::task::spawn_blocking(move || super::super::explain::explain_bundle(&bundle)).await;
+        tokio::task::spawn_blocking(move || super::super::explain::explain_bundle(handle.bundle()))
+            .await;
 
     match result {
         Ok(Ok(explanation)) => (StatusCode::OK, Json(explanation)).into_response(),
@@ -0,0 +1,83 @@
+//! Liveness and readiness probes.
+//!
+//! `GET /healthz` answers 200 whenever the process is serving requests.
+//! `GET /readyz` answers 200 only once contract preloading has finished and
+//! the server is not draining for shutdown, and 503 otherwise, so a load
+//! balancer or Kubernetes sends traffic only to instances that can answer
+//! it. `tenor serve` keeps all state in memory, so there is no storage
+//! backend to check.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use super::state::AppState;
+
+/// Paths that probes hit; exempt from authentication and rate limiting.
+pub(crate) const PROBE_PATHS: &[&str] = &["/health", "/healthz", "/readyz"];
+
+/// Readiness of the server.
+#[derive(Default)]
+pub(crate) struct Readiness {
+    /// Contract preloading has finished.
+    preloaded: AtomicBool,
+    /// Contracts that failed to load during preloading.
+    failed_contracts: AtomicUsize,
+    /// A shutdown signal was received; in-flight requests are draining.
+    draining: AtomicBool,
+}
+
+impl Readiness {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn mark_preloaded(&self, failed_contracts: usize) {
+        self.failed_contracts
+            .store(failed_contracts, Ordering::SeqCst);
+        self.preloaded.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn mark_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+}
+
+/// GET /healthz
+pub(crate) async fn handle_healthz() -> impl IntoResponse {
+    (StatusCode::OK, Json(serde_json::json!({ "status": "ok" })))
+}
+
+/// GET /readyz
+pub(crate) async fn handle_readyz(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let readiness = &state.readiness;
+    let preloaded = readiness.preloaded.load(Ordering::SeqCst);
+    let draining = readiness.draining.load(Ordering::SeqCst);
+    let status = if draining {
+        "draining"
+    } else if preloaded {
+        "ready"
+    } else {
+        "loading"
+    };
+    let body = serde_json::json!({
+        "status": status,
+        "checks": {
+            "contracts": {
+                "preloaded": preloaded,
+                "loaded": state.tenants.contract_count(),
+                "failed": readiness.failed_contracts.load(Ordering::SeqCst),
+            },
+        },
+    });
+    let code = if status == "ready" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(body))
+}
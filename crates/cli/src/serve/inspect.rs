@@ -2,13 +2,12 @@
 
 use std::sync::Arc;
 
-use axum::extract::State;
 use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
-use axum::Json;
+use axum::{Extension, Json};
 
 use super::json_error;
-use super::state::AppState;
+use super::tenant::Tenant;
 
 /// GET /.well-known/tenor
 ///
@@ -17,16 +16,13 @@ use super::state::AppState;
 /// supports If-None-Match for conditional requests (304 Not Modified).
 /// Satisfies executor obligations E10, E11, E12.
 pub(crate) async fn handle_well_known_tenor(
-    State(state): State<Arc<AppState>>,
+    Extension(tenant): Extension<Arc<Tenant>>,
     headers: HeaderMap,
 ) -> Response {
-    let contracts = state.contracts.read().await;
-
-    let bundle = match contracts.values().next() {
-        Some(b) => b.clone(),
+    let bundle = match tenant.contracts.handles().first() {
+        Some(h) => h.bundle().clone(),
         None => return json_error(StatusCode::NOT_FOUND, "no contracts loaded").into_response(),
     };
-    drop(contracts);
 
     let manifest_value = super::super::manifest::build_manifest(bundle);
     let etag = manifest_value
@@ -57,8 +53,8 @@ pub(crate) async fn handle_well_known_tenor(
 /// Structured summary of the loaded contract. Returns all declared constructs
 /// with their key properties -- enough for an agent to cold-start or a UI to
 /// render a contract explorer.
-pub(crate) async fn handle_inspect(State(state): State<Arc<AppState>>) -> Response {
-    let contracts = state.contracts.read().await;
+pub(crate) async fn handle_inspect(Extension(tenant): Extension<Arc<Tenant>>) -> Response {
+    let contracts = tenant.contracts.handles();
 
     let mut all_facts = Vec::new();
     let mut all_entities = Vec::new();
@@ -68,7 +64,8 @@ pub(crate) async fn handle_inspect(State(state): State<Arc<AppState>>) -> Respon
     let mut all_flows = Vec::new();
     let mut etag_bundle = None;
 
-    for bundle in contracts.values() {
+    for handle in &contracts {
+        let bundle = handle.bundle();
         if etag_bundle.is_none() {
             etag_bundle = Some(bundle.clone());
         }
@@ -180,6 +177,8 @@ pub(crate) async fn handle_inspect(State(state): State<Arc<AppState>>) -> Respon
                 }
                 tenor_interchange::InterchangeConstruct::Source(_)
                 | tenor_interchange::InterchangeConstruct::System(_)
+                | tenor_interchange::InterchangeConstruct::Test(_)
+                | tenor_interchange::InterchangeConstruct::Assert(_)
                 | tenor_interchange::InterchangeConstruct::TypeDecl(_) => {}
             }
         }
@@ -0,0 +1,235 @@
+//! RFC 6902 JSON Patch application, with RFC 6901 JSON Pointer paths.
+//!
+//! A patch is applied atomically: operations run in order against a copy
+//! of the document, and the document is only replaced once every
+//! operation (including `test`) has succeeded.
+
+use serde_json::Value;
+
+/// Why a patch could not be applied.
+#[derive(Debug, PartialEq)]
+pub(crate) enum PatchError {
+    /// The patch document itself is malformed.
+    Invalid(String),
+    /// The patch is well formed but does not apply to the document: a
+    /// path does not exist or a `test` failed.
+    Conflict(String),
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchError::Invalid(msg) | PatchError::Conflict(msg) => f.write_str(msg),
+        }
+    }
+}
+
+/// Apply `patch` (a JSON array of operations) to `doc`.
+pub(crate) fn apply_patch(doc: &mut Value, patch: &Value) -> Result<(), PatchError> {
+    let ops = patch
+        .as_array()
+        .ok_or_else(|| PatchError::Invalid("patch must be a JSON array".to_string()))?;
+    let mut patched = doc.clone();
+    for (i, op) in ops.iter().enumerate() {
+        apply_op(&mut patched, op).map_err(|e| match e {
+            PatchError::Invalid(msg) => PatchError::Invalid(format!("operation {}: {}", i, msg)),
+            PatchError::Conflict(msg) => PatchError::Conflict(format!("operation {}: {}", i, msg)),
+        })?;
+    }
+    *doc = patched;
+    Ok(())
+}
+
+fn apply_op(doc: &mut Value, op: &Value) -> Result<(), PatchError> {
+    let str_member = |name: &str| {
+        op.get(name)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| PatchError::Invalid(format!("missing '{}'", name)))
+    };
+    let value_member = || {
+        op.get("value")
+            .cloned()
+            .ok_or_else(|| PatchError::Invalid("missing 'value'".to_string()))
+    };
+    let path = parse_pointer(str_member("path")?)?;
+    match str_member("op")? {
+        "add" => add(doc, &path, value_member()?),
+        "remove" => remove(doc, &path).map(drop),
+        "replace" => {
+            let target = resolve_mut(doc, &path)?;
+            *target = value_member()?;
+            Ok(())
+        }
+        "move" => {
+            let from = parse_pointer(str_member("from")?)?;
+            if path.len() > from.len() && path[..from.len()] == from[..] {
+                return Err(PatchError::Invalid(
+                    "cannot move a value into one of its children".to_string(),
+                ));
+            }
+            let value = remove(doc, &from)?;
+            add(doc, &path, value)
+        }
+        "copy" => {
+            let from = parse_pointer(str_member("from")?)?;
+            let value = resolve_mut(doc, &from)?.clone();
+            add(doc, &path, value)
+        }
+        "test" => {
+            let expected = value_member()?;
+            if *resolve_mut(doc, &path)? == expected {
+                Ok(())
+            } else {
+                Err(PatchError::Conflict(format!(
+                    "test failed at '{}'",
+                    str_member("path")?
+                )))
+            }
+        }
+        other => Err(PatchError::Invalid(format!("unknown op '{}'", other))),
+    }
+}
+
+/// Split a JSON Pointer into unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, PatchError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rest = pointer.strip_prefix('/').ok_or_else(|| {
+        PatchError::Invalid(format!(
+            "path '{}' must be empty or start with '/'",
+            pointer
+        ))
+    })?;
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn resolve_mut<'a>(doc: &'a mut Value, path: &[String]) -> Result<&'a mut Value, PatchError> {
+    let mut current = doc;
+    for token in path {
+        current = match current {
+            Value::Object(map) => map.get_mut(token),
+            Value::Array(items) => array_index(token, items.len())?.and_then(|i| items.get_mut(i)),
+            _ => None,
+        }
+        .ok_or_else(|| missing(path))?;
+    }
+    Ok(current)
+}
+
+fn add(doc: &mut Value, path: &[String], value: Value) -> Result<(), PatchError> {
+    let Some((last, parent)) = path.split_last() else {
+        *doc = value;
+        return Ok(());
+    };
+    match resolve_mut(doc, parent)? {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            let index = if last == "-" {
+                items.len()
+            } else {
+                array_index(last, items.len() + 1)?.ok_or_else(|| missing(path))?
+            };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(missing(path)),
+    }
+}
+
+fn remove(doc: &mut Value, path: &[String]) -> Result<Value, PatchError> {
+    let (last, parent) = path
+        .split_last()
+        .ok_or_else(|| PatchError::Invalid("cannot remove the whole document".to_string()))?;
+    match resolve_mut(doc, parent)? {
+        Value::Object(map) => map.remove(last).ok_or_else(|| missing(path)),
+        Value::Array(items) => {
+            let index = array_index(last, items.len())?.ok_or_else(|| missing(path))?;
+            Ok(items.remove(index))
+        }
+        _ => Err(missing(path)),
+    }
+}
+
+/// Parse an array index token. `Ok(None)` when it is out of range
+/// (`index >= len`); `-` and malformed indices are errors.
+fn array_index(token: &str, len: usize) -> Result<Option<usize>, PatchError> {
+    let well_formed =
+        token == "0" || (!token.starts_with('0') && token.chars().all(|c| c.is_ascii_digit()));
+    if token.is_empty() || !well_formed {
+        return Err(PatchError::Conflict(format!(
+            "'{}' is not a valid array index",
+            token
+        )));
+    }
+    Ok(token.parse::<usize>().ok().filter(|&i| i < len))
+}
+
+fn missing(path: &[String]) -> PatchError {
+    let pointer: String = path
+        .iter()
+        .map(|t| format!("/{}", t.replace('~', "~0").replace('/', "~1")))
+        .collect();
+    PatchError::Conflict(format!("path '{}' does not exist", pointer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn applies_rfc_6902_operations() {
+        let mut doc = json!({ "amount": 10, "tags": ["a", "c"], "a/b": { "x": 1 } });
+        apply_patch(
+            &mut doc,
+            &json!([
+                { "op": "test", "path": "/amount", "value": 10 },
+                { "op": "replace", "path": "/amount", "value": 25 },
+                { "op": "add", "path": "/tags/1", "value": "b" },
+                { "op": "add", "path": "/tags/-", "value": "d" },
+                { "op": "copy", "from": "/a~1b/x", "path": "/copied" },
+                { "op": "move", "from": "/a~1b", "path": "/moved" },
+                { "op": "remove", "path": "/moved/x" }
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            doc,
+            json!({ "amount": 25, "tags": ["a", "b", "c", "d"], "copied": 1, "moved": {} })
+        );
+    }
+
+    #[test]
+    fn failed_patch_leaves_document_unchanged() {
+        let mut doc = json!({ "amount": 10 });
+        let err = apply_patch(
+            &mut doc,
+            &json!([
+                { "op": "replace", "path": "/amount", "value": 25 },
+                { "op": "test", "path": "/amount", "value": 10 }
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            PatchError::Conflict("operation 1: test failed at '/amount'".to_string())
+        );
+        assert_eq!(doc, json!({ "amount": 10 }));
+
+        let err = apply_patch(&mut doc, &json!([{ "op": "remove", "path": "/missing" }]));
+        assert!(matches!(err, Err(PatchError::Conflict(_))));
+        let err = apply_patch(&mut doc, &json!([{ "op": "jump", "path": "/amount" }]));
+        assert!(matches!(err, Err(PatchError::Invalid(_))));
+        assert!(matches!(
+            apply_patch(&mut doc, &json!({ "op": "add" })),
+            Err(PatchError::Invalid(_))
+        ));
+    }
+}
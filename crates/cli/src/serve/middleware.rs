@@ -3,26 +3,57 @@
 use std::sync::Arc;
 
 use axum::extract::{ConnectInfo, State};
-use axum::http::{Request, StatusCode};
+use axum::http::{HeaderMap, Request, StatusCode};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 
+use super::health::PROBE_PATHS;
 use super::state::AppState;
+use super::tenant::{AuthError, Authenticated};
+use super::RateLimitKey;
 
-/// Rate limiting middleware. Checks per-IP request rate before routing.
+/// The API token a request carries, from `Authorization: Bearer` or
+/// `X-API-Key`.
+fn request_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| headers.get("x-api-key").and_then(|v| v.to_str().ok()))
+}
+
+/// Rate limiting middleware. Checks the client's request rate before
+/// routing; the client is the peer IP or, per `RateLimitKey::Token`, the
+/// tenant the request's token authenticates as. Requests whose token does
+/// not authenticate count against their IP, so made-up tokens cannot
+/// escape the limit. Probe endpoints are exempt.
 pub(crate) async fn rate_limit_middleware(
     State(state): State<Arc<AppState>>,
     ConnectInfo(addr): ConnectInfo<std::net::SocketAddr>,
     request: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
-    let ip = addr.ip();
-    match state.rate_limiter.check(ip).await {
+    if PROBE_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+    let tenant = match state.rate_limiter.key {
+        RateLimitKey::Token if state.tenants.requires_auth() => state
+            .tenants
+            .authenticate(request_token(request.headers()))
+            .ok(),
+        _ => None,
+    };
+    let client = match tenant {
+        Some(tenant) => format!("tenant:{}", tenant.id),
+        None => format!("ip:{}", addr.ip()),
+    };
+    match state.rate_limiter.check(client).await {
         Ok(()) => next.run(request).await,
         Err(retry_after) => {
             let body = serde_json::json!({
                 "error": "rate limit exceeded",
+                "code": super::status_error_code(StatusCode::TOO_MANY_REQUESTS),
                 "retry_after": retry_after,
             });
             (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response()
@@ -30,52 +61,36 @@ pub(crate) async fn rate_limit_middleware(
     }
 }
 
-/// API key authentication middleware.
+/// Authentication middleware: assigns each request to a tenant.
 ///
-/// If `TENOR_API_KEY` is set, all requests (except /health) must include
-/// either `Authorization: Bearer <key>` or `X-API-Key: <key>`.
+/// If `TENOR_API_KEY` is set or tenants are configured, all requests
+/// (except the probe endpoints) must include either
+/// `Authorization: Bearer <token>` or `X-API-Key: <token>`, and the token
+/// selects the tenant. Otherwise every request belongs to the default
+/// tenant. The tenant is passed to handlers as a request extension.
 pub(crate) async fn auth_middleware(
     State(state): State<Arc<AppState>>,
-    request: Request<axum::body::Body>,
+    mut request: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
-    let expected_key = match &state.api_key {
-        Some(k) => k,
-        None => return next.run(request).await, // No auth configured
-    };
-
-    // /health is exempt from auth (for load balancer health checks)
-    if request.uri().path() == "/health" {
+    // Probes are exempt from auth (for load balancer health checks)
+    if PROBE_PATHS.contains(&request.uri().path()) {
         return next.run(request).await;
     }
 
-    // Check Authorization: Bearer <key>
-    let auth_header = request
-        .headers()
-        .get("authorization")
-        .and_then(|v| v.to_str().ok());
-
-    if let Some(auth) = auth_header {
-        if let Some(token) = auth.strip_prefix("Bearer ") {
-            if token == expected_key {
-                return next.run(request).await;
-            }
-            return super::json_error(StatusCode::FORBIDDEN, "invalid API key").into_response();
+    match state.tenants.authenticate(request_token(request.headers())) {
+        Ok(tenant) => {
+            tenant.record_request();
+            let authenticated = Authenticated(state.tenants.requires_auth());
+            request.extensions_mut().insert(tenant);
+            request.extensions_mut().insert(authenticated);
+            next.run(request).await
         }
-    }
-
-    // Check X-API-Key header
-    let api_key_header = request
-        .headers()
-        .get("x-api-key")
-        .and_then(|v| v.to_str().ok());
-
-    if let Some(key) = api_key_header {
-        if key == expected_key {
-            return next.run(request).await;
+        Err(AuthError::Missing) => {
+            super::json_error(StatusCode::UNAUTHORIZED, "authentication required").into_response()
+        }
+        Err(AuthError::Invalid) => {
+            super::json_error(StatusCode::FORBIDDEN, "invalid API key").into_response()
         }
-        return super::json_error(StatusCode::FORBIDDEN, "invalid API key").into_response();
     }
-
-    super::json_error(StatusCode::UNAUTHORIZED, "authentication required").into_response()
 }
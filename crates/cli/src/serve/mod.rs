@@ -5,52 +5,126 @@
 //!
 //! Security features:
 //! - Input validation on elaborate endpoint (size, encoding, imports, filename)
-//! - CORS headers on all responses (permissive for local dev)
-//! - Per-IP rate limiting (default: 60 req/min, configurable)
+//! - CORS headers on all responses: any origin by default, or an allow-list
+//! - Per-IP or per-token rate limiting (default: 60 req/min per IP)
+//! - Maximum request body size (default: 10 MB)
 //! - Optional API key authentication via TENOR_API_KEY env var
+//! - Tenant isolation: with `[serve.tenants]`, each API token selects a
+//!   tenant with its own contracts, baselines, timers, schedules, webhooks
+//!   and contract limit (see `tenant`)
+//!
+//! CORS, rate limiting and body size come from `ServeOptions`, filled from
+//! `tenor serve` flags, `TENOR_RATE_LIMIT`, and `[serve]` in tenor.toml.
+//!
+//! The server listens before contracts finish preloading; `/readyz`
+//! reports when it can take traffic. SIGHUP reloads the adapter settings
+//! (see `adapters`). On SIGTERM or Ctrl+C it stops
+//! accepting connections, fails `/readyz`, and drains in-flight requests
+//! for up to `ServeOptions::shutdown_timeout`.
 //!
 //! Endpoints:
 //! - GET  /health                      - Server status (exempt from auth)
+//! - GET  /healthz                     - Liveness probe (exempt from auth)
+//! - GET  /readyz                      - Readiness probe (exempt from auth)
 //! - GET  /contracts                   - List loaded contract bundles
+//! - POST /contracts                   - Load a contract bundle for the tenant
+//! - DELETE /contracts/{id}            - Unload a contract
 //! - GET  /contracts/{id}/operations   - Operations for a specific contract
+//! - POST /contracts/{id}/operations/{op_id}/preview - Dry-run an operation
+//! - POST /contracts/{id}/baselines    - Store a facts document for deltas
+//! - POST /contracts/{id}/evaluate-delta - Evaluate a JSON Patch to a baseline
+//! - GET  /contracts/{id}/schedules    - Scheduled evaluations of a contract
+//! - POST /contracts/{id}/schedules    - Evaluate a contract on a cron schedule
+//! - GET  /contracts/{id}/schedules/{schedule_id} - Last-run status and stored results
+//! - DELETE /contracts/{id}/schedules/{schedule_id} - Cancel a schedule
 //! - GET  /.well-known/tenor           - Contract manifest with ETag (spec §19)
 //! - GET  /inspect                     - Structured contract summary
 //! - POST /elaborate                   - Elaborate .tenor source text
 //! - POST /evaluate                    - Evaluate a contract against facts
+//! - POST /validate-facts              - Report every fact error in one pass
 //! - POST /explain                     - Explain a contract bundle
 //! - POST /flows/{flow_id}/simulate    - Stateless flow simulation
+//! - POST /flows/resume                - Resume a flow paused at a handoff
+//! - GET  /flows/timers/{id}           - Status of a suspended flow's expiry timer
+//! - GET  /flows/executions            - Recorded flow runs, by correlation id
 //! - POST /actions                     - Action space for a persona
+//! - POST /actions/matrix              - Action space for every persona at once
+//! - GET  /entities/{entity_id}/instances - Current states from the entity history
+//! - GET  /entities/{entity_id}/instances/{instance_id} - One instance's current state
+//! - GET  /entities/{entity_id}/instances/{instance_id}/history - Transitions with provenance
+//! - GET  /tenant                      - The requesting tenant and its usage
+//! - GET  /webhooks                    - Registered webhooks and their deliveries
+//! - POST /webhooks                    - Notify a URL of verdict and entity state changes
+//! - DELETE /webhooks/{id}             - Remove a webhook
+//! - POST /admin/adapter-config/reload - Re-read the tenant's adapter settings
+//!
+//! Everything except the probes is scoped to the requesting tenant.
+//!
+//! Evaluate, simulate, preview and action requests can pin the contract
+//! version with `If-Match` or `bundle_digest` (see `pinning`); a mismatch
+//! fails with 412.
 //!
-//! All responses use Content-Type: application/json.
+//! Request bodies may be JSON or CBOR (`Content-Type: application/cbor`).
+//! Responses use Content-Type: application/json, except that /elaborate
+//! returns a CBOR bundle when the client sends `Accept: application/cbor`.
 
+mod adapters;
+mod delta;
+mod entities;
+mod executions;
 mod handlers;
+mod health;
 mod inspect;
+mod json_patch;
 mod middleware;
-mod simulate;
+mod negotiate;
+mod pinning;
+mod schedules;
+pub(crate) mod simulate;
 mod state;
+mod tenant;
+mod timers;
+mod webhooks;
 
-use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::extract::DefaultBodyLimit;
-use axum::http::{Method, StatusCode};
+use axum::http::{HeaderValue, Method, StatusCode};
 use axum::response::IntoResponse;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::{middleware as axum_middleware, Json, Router};
-use tower_http::cors::{Any, CorsLayer};
+use clap::ValueEnum;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
+use self::adapters::{handle_reload_adapter_config, read_tenant_adapter_config, AdapterSettings};
+use self::delta::{handle_create_baseline, handle_evaluate_delta};
+use self::entities::{handle_get_instance, handle_instance_history, handle_list_instances};
+use self::executions::handle_list_executions;
 use self::handlers::{
-    handle_elaborate, handle_evaluate, handle_explain, handle_get_operations, handle_health,
-    handle_list_contracts, handle_not_found,
+    handle_elaborate, handle_evaluate, handle_explain, handle_get_flow_timer,
+    handle_get_operations, handle_health, handle_list_contracts, handle_not_found,
+    handle_resume_flow, handle_validate_facts,
 };
+use self::health::{handle_healthz, handle_readyz, Readiness};
 use self::inspect::{handle_inspect, handle_well_known_tenor};
 use self::middleware::{auth_middleware, rate_limit_middleware};
-use self::simulate::{handle_actions, handle_simulate_flow};
+use self::schedules::{
+    handle_create_schedule, handle_delete_schedule, handle_get_schedule, handle_list_schedules,
+};
+use self::simulate::{
+    handle_actions, handle_actions_matrix, handle_preview_operation, handle_simulate_flow,
+};
 use self::state::{AppState, RateLimiter};
+use self::tenant::{handle_load_contract, handle_tenant, handle_unload_contract, Tenants};
+use self::webhooks::{handle_create_webhook, handle_delete_webhook, handle_list_webhooks};
+
+pub use self::adapters::read_adapter_config;
+pub use self::tenant::TenantConfig;
 
-/// Maximum request body size: 10 MB.
-const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+/// Default maximum request body size: 10 MB.
+const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
 
 /// Maximum source content size for the elaborate endpoint: 1 MB.
 const MAX_SOURCE_SIZE: usize = 1024 * 1024;
@@ -58,12 +132,119 @@ const MAX_SOURCE_SIZE: usize = 1024 * 1024;
 /// Default rate limit: 60 requests per minute per IP.
 const DEFAULT_RATE_LIMIT: u64 = 60;
 
+/// Default time allowed for in-flight requests to finish on shutdown.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Rate limit window duration in seconds (1 minute).
 const RATE_LIMIT_WINDOW_SECS: u64 = 60;
 
+/// What identifies a client for rate limiting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RateLimitKey {
+    /// The peer IP address.
+    Ip,
+    /// The tenant the API token (`Authorization: Bearer` or `X-API-Key`)
+    /// authenticates as, falling back to the peer IP for requests without
+    /// a valid token.
+    Token,
+}
+
+/// HTTP policy for `tenor serve`: CORS, rate limiting and body size.
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    /// Origins allowed by CORS. Empty or `*` allows any origin.
+    pub cors_origins: Vec<String>,
+    /// Requests per minute per client; 0 disables rate limiting.
+    pub rate_limit: u64,
+    pub rate_limit_key: RateLimitKey,
+    /// Maximum request body size in bytes.
+    pub max_body_size: usize,
+    /// How long in-flight requests may drain after a shutdown signal.
+    pub shutdown_timeout: Duration,
+    /// Tenants besides the default one, each reached with its own token.
+    pub tenants: Vec<TenantConfig>,
+    /// The default tenant's adapter settings for fetching the facts of
+    /// scheduled evaluations. `env://` secret references are resolved at
+    /// startup.
+    pub adapter_config: tenor_eval::AdapterConfig,
+    /// The file `adapter_config` was read from, re-read on reload.
+    pub adapter_config_path: Option<PathBuf>,
+    /// Entity history exported from storage, for the default tenant.
+    pub history: Option<PathBuf>,
+    /// Let webhooks, schedule sinks and adapter fetches reach loopback,
+    /// private and link-local addresses.
+    pub webhook_allow_private: bool,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            cors_origins: Vec::new(),
+            rate_limit: DEFAULT_RATE_LIMIT,
+            rate_limit_key: RateLimitKey::Ip,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            tenants: Vec::new(),
+            adapter_config: tenor_eval::AdapterConfig::default(),
+            adapter_config_path: None,
+            history: None,
+            webhook_allow_private: false,
+        }
+    }
+}
+
+impl ServeOptions {
+    /// The CORS layer for the configured origins.
+    fn cors_layer(&self) -> Result<CorsLayer, String> {
+        let allow_origin =
+            if self.cors_origins.is_empty() || self.cors_origins.iter().any(|o| o == "*") {
+                AllowOrigin::from(Any)
+            } else {
+                let origins = self
+                    .cors_origins
+                    .iter()
+                    .map(|o| {
+                        HeaderValue::from_str(o).map_err(|_| format!("invalid CORS origin '{}'", o))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                AllowOrigin::list(origins)
+            };
+        Ok(CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST, Method::DELETE])
+            .allow_headers(Any))
+    }
+}
+
 /// Construct a JSON error response with the given status code and message.
+/// The body carries the stable error code for the status.
 fn json_error(status: StatusCode, message: &str) -> impl IntoResponse {
-    (status, Json(serde_json::json!({"error": message})))
+    json_error_with_code(status, status_error_code(status), message)
+}
+
+/// Construct a JSON error response carrying a specific error code, e.g. an
+/// `EvalError`'s own code.
+fn json_error_with_code(status: StatusCode, code: &str, message: &str) -> impl IntoResponse {
+    (
+        status,
+        Json(serde_json::json!({"error": message, "code": code})),
+    )
+}
+
+/// The stable error code (see `tenor_core::error_codes`) for an HTTP error
+/// status.
+fn status_error_code(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::PAYLOAD_TOO_LARGE => "TEN-E-0300",
+        StatusCode::UNAUTHORIZED => "TEN-E-0301",
+        StatusCode::FORBIDDEN => "TEN-E-0302",
+        StatusCode::NOT_FOUND => "TEN-E-0303",
+        StatusCode::CONFLICT => "TEN-E-0304",
+        StatusCode::UNPROCESSABLE_ENTITY => "TEN-E-0305",
+        StatusCode::TOO_MANY_REQUESTS => "TEN-E-0306",
+        StatusCode::PRECONDITION_FAILED => "TEN-E-0308",
+        _ => "TEN-E-0307",
+    }
 }
 
 /// Start the HTTP server on the given port, optionally pre-loading contracts.
@@ -72,40 +253,17 @@ fn json_error(status: StatusCode, message: &str) -> impl IntoResponse {
 /// using `axum-server` with rustls. Otherwise it uses plain HTTP.
 ///
 /// Security:
-/// - CORS: Permissive (`Any` origin) for local dev; tighten for production.
-/// - Rate limit: Per-IP, configurable via `rate_limit` param (default 60 req/min).
-/// - API key: If `TENOR_API_KEY` env var is set, all endpoints except /health require auth.
+/// - CORS, rate limiting and body size: see `ServeOptions`.
+/// - API key: If `TENOR_API_KEY` env var is set or tenants are configured,
+///   all endpoints except the probes require a token, which selects the tenant.
 pub async fn start_server(
     port: u16,
     contract_paths: Vec<PathBuf>,
     _tls_cert: Option<PathBuf>,
     _tls_key: Option<PathBuf>,
+    options: ServeOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut contracts = HashMap::new();
-
-    // Pre-load contracts
-    for path in &contract_paths {
-        match tenor_core::elaborate::elaborate(path) {
-            Ok(bundle) => {
-                let bundle_id = bundle
-                    .get("id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-                eprintln!("Loaded contract: {} (from {})", bundle_id, path.display());
-                contracts.insert(bundle_id, bundle);
-            }
-            Err(e) => {
-                eprintln!("Warning: failed to load {}: {:?}", path.display(), e);
-            }
-        }
-    }
-
-    // Rate limit: from TENOR_RATE_LIMIT env var, or default
-    let rate_limit = std::env::var("TENOR_RATE_LIMIT")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .unwrap_or(DEFAULT_RATE_LIMIT);
+    let cors = options.cors_layer()?;
 
     // API key: from TENOR_API_KEY env var (None = no auth)
     let api_key = std::env::var("TENOR_API_KEY")
@@ -115,31 +273,145 @@ pub async fn start_server(
     if api_key.is_some() {
         eprintln!("API key authentication enabled");
     }
-    eprintln!("Rate limit: {} requests per minute per IP", rate_limit);
+    if !options.tenants.is_empty() {
+        let ids: Vec<&str> = options.tenants.iter().map(|t| t.id.as_str()).collect();
+        eprintln!("Tenants: {}", ids.join(", "));
+    }
+    let tenants = Tenants::new(api_key, &options.tenants)?;
+    let histories = std::iter::once((tenant::DEFAULT_TENANT, &options.history))
+        .chain(options.tenants.iter().map(|t| (t.id.as_str(), &t.history)));
+    for (tenant_id, path) in histories {
+        if let (Some(tenant), Some(path)) = (tenants.get(tenant_id), path) {
+            let history = entities::load_history(path)?;
+            eprintln!(
+                "Loaded entity history for tenant {}: {} instances, {} transitions",
+                tenant_id,
+                history.entity_states.len(),
+                history.transitions.len()
+            );
+            let _ = tenant
+                .entity_store
+                .set(Arc::new(entities::HistoryFile::new(path.clone())));
+        }
+    }
+    if options.webhook_allow_private {
+        eprintln!("warning: webhooks and adapter fetches may reach loopback, private and link-local addresses");
+        let ids = std::iter::once(tenant::DEFAULT_TENANT)
+            .chain(options.tenants.iter().map(|t| t.id.as_str()));
+        for tenant in ids.filter_map(|id| tenants.get(id)) {
+            tenant.webhooks.allow_private_targets();
+        }
+    }
+    match (options.rate_limit, options.rate_limit_key) {
+        (0, _) => eprintln!("Rate limiting disabled"),
+        (limit, RateLimitKey::Ip) => eprintln!("Rate limit: {} requests per minute per IP", limit),
+        (limit, RateLimitKey::Token) => {
+            eprintln!("Rate limit: {} requests per minute per tenant", limit)
+        }
+    }
+    if !options.cors_origins.is_empty() {
+        eprintln!("CORS origins: {}", options.cors_origins.join(", "));
+    }
+
+    let adapter_configs = std::iter::once((
+        tenant::DEFAULT_TENANT,
+        Some(options.adapter_config.clone()),
+        &options.adapter_config_path,
+    ))
+    .chain(
+        options
+            .tenants
+            .iter()
+            .map(|t| (t.id.as_str(), None, &t.adapter_config)),
+    );
+    for (tenant_id, config, path) in adapter_configs {
+        let from_env = tenant_id == tenant::DEFAULT_TENANT;
+        let Some(tenant) = tenants.get(tenant_id) else {
+            continue;
+        };
+        let mut config = match (config, path) {
+            (Some(config), _) => config,
+            (None, Some(path)) => read_tenant_adapter_config(Some(path))?,
+            (None, None) => continue,
+        };
+        config
+            .resolve_secrets(&[&tenor_eval::adapter::secrets::EnvSecretResolver])
+            .await?;
+        let _ = tenant.adapter_settings.set(Arc::new(AdapterSettings::new(
+            config,
+            path.clone(),
+            from_env,
+        )));
+    }
 
     let state = Arc::new(AppState {
-        contracts: tokio::sync::RwLock::new(contracts),
-        rate_limiter: RateLimiter::new(rate_limit),
-        api_key,
+        tenants,
+        rate_limiter: RateLimiter::new(options.rate_limit, options.rate_limit_key),
+        readiness: Readiness::new(),
     });
-
-    // CORS: permissive for local dev (Phase 22 will tighten for production)
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods([Method::GET, Method::POST])
-        .allow_headers(Any);
+    adapters::reload_on_sighup(state.clone());
 
     let app = Router::new()
         .route("/health", get(handle_health))
-        .route("/contracts", get(handle_list_contracts))
+        .route("/healthz", get(handle_healthz))
+        .route("/readyz", get(handle_readyz))
+        .route(
+            "/contracts",
+            get(handle_list_contracts).post(handle_load_contract),
+        )
+        .route("/contracts/{id}", delete(handle_unload_contract))
         .route("/contracts/{id}/operations", get(handle_get_operations))
+        .route(
+            "/contracts/{id}/operations/{op_id}/preview",
+            post(handle_preview_operation),
+        )
+        .route("/contracts/{id}/baselines", post(handle_create_baseline))
+        .route(
+            "/contracts/{id}/evaluate-delta",
+            post(handle_evaluate_delta),
+        )
+        .route(
+            "/contracts/{id}/schedules",
+            get(handle_list_schedules).post(handle_create_schedule),
+        )
+        .route(
+            "/contracts/{id}/schedules/{schedule_id}",
+            get(handle_get_schedule).delete(handle_delete_schedule),
+        )
         .route("/.well-known/tenor", get(handle_well_known_tenor))
         .route("/inspect", get(handle_inspect))
         .route("/elaborate", post(handle_elaborate))
         .route("/evaluate", post(handle_evaluate))
+        .route("/validate-facts", post(handle_validate_facts))
         .route("/explain", post(handle_explain))
         .route("/flows/{flow_id}/simulate", post(handle_simulate_flow))
+        .route("/flows/resume", post(handle_resume_flow))
+        .route("/flows/timers/{id}", get(handle_get_flow_timer))
+        .route("/flows/executions", get(handle_list_executions))
         .route("/actions", post(handle_actions))
+        .route("/actions/matrix", post(handle_actions_matrix))
+        .route(
+            "/entities/{entity_id}/instances",
+            get(handle_list_instances),
+        )
+        .route(
+            "/entities/{entity_id}/instances/{instance_id}",
+            get(handle_get_instance),
+        )
+        .route(
+            "/entities/{entity_id}/instances/{instance_id}/history",
+            get(handle_instance_history),
+        )
+        .route("/tenant", get(handle_tenant))
+        .route(
+            "/webhooks",
+            get(handle_list_webhooks).post(handle_create_webhook),
+        )
+        .route("/webhooks/{id}", delete(handle_delete_webhook))
+        .route(
+            "/admin/adapter-config/reload",
+            post(handle_reload_adapter_config),
+        )
         .fallback(handle_not_found)
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
@@ -150,10 +422,11 @@ pub async fn start_server(
             rate_limit_middleware,
         ))
         .layer(cors)
-        .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
-        .with_state(state);
+        .layer(DefaultBodyLimit::max(options.max_body_size))
+        .with_state(state.clone());
 
     let addr = format!("0.0.0.0:{}", port);
+    let shutdown_timeout = options.shutdown_timeout;
 
     // TLS support via axum-server + rustls (requires `tls` feature)
     #[cfg(feature = "tls")]
@@ -161,30 +434,121 @@ pub async fn start_server(
         let config =
             axum_server::tls_rustls::RustlsConfig::from_pem_file(cert_path, key_path).await?;
         let socket_addr: std::net::SocketAddr = addr.parse()?;
+        let handle = axum_server::Handle::new();
+        tokio::spawn({
+            let (state, handle) = (state.clone(), handle.clone());
+            async move {
+                shutdown_signal(&state).await;
+                handle.graceful_shutdown(Some(shutdown_timeout));
+            }
+        });
         eprintln!("Tenor evaluator listening on https://0.0.0.0:{}", port);
+        preload_contracts(&state, contract_paths, &options.tenants);
         axum_server::bind_rustls(socket_addr, config)
+            .handle(handle)
             .serve(app.into_make_service_with_connect_info::<std::net::SocketAddr>())
             .await?;
+        eprintln!("\nServer shut down.");
         return Ok(());
     }
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     eprintln!("Tenor evaluator listening on http://0.0.0.0:{}", port);
-    axum::serve(
+    preload_contracts(&state, contract_paths, &options.tenants);
+
+    // Once the signal arrives the server stops accepting connections and
+    // waits for in-flight requests; give up on them after the timeout.
+    let signalled = Arc::new(tokio::sync::Notify::new());
+    let server = axum::serve(
         listener,
         app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
     )
-    .with_graceful_shutdown(shutdown_signal())
-    .await?;
+    .with_graceful_shutdown({
+        let (state, signalled) = (state.clone(), signalled.clone());
+        async move {
+            shutdown_signal(&state).await;
+            signalled.notify_one();
+        }
+    });
+    tokio::select! {
+        result = server => result?,
+        _ = async {
+            signalled.notified().await;
+            tokio::time::sleep(shutdown_timeout).await;
+        } => eprintln!(
+            "Shutdown timeout ({}s) reached; abandoning in-flight requests",
+            shutdown_timeout.as_secs()
+        ),
+    }
 
     eprintln!("\nServer shut down.");
     Ok(())
 }
 
-/// Wait for a shutdown signal (Ctrl+C).
-async fn shutdown_signal() {
-    tokio::signal::ctrl_c()
-        .await
-        .expect("failed to install Ctrl+C handler");
-    eprintln!("\nReceived shutdown signal...");
+/// Elaborate and load the preloaded contracts in the background, the
+/// command-line contracts for the default tenant and each tenant's own,
+/// then mark the server ready.
+fn preload_contracts(
+    state: &Arc<AppState>,
+    contract_paths: Vec<PathBuf>,
+    tenants: &[TenantConfig],
+) {
+    let state = state.clone();
+    let mut preloads = vec![(tenant::DEFAULT_TENANT.to_string(), contract_paths)];
+    preloads.extend(tenants.iter().map(|t| (t.id.clone(), t.contracts.clone())));
+    tokio::task::spawn_blocking(move || {
+        let mut failed = 0;
+        for (tenant_id, paths) in &preloads {
+            let Some(tenant) = state.tenants.get(tenant_id) else {
+                continue;
+            };
+            for path in paths {
+                let loaded = crate::elaborate(path)
+                    .map_err(|e| format!("{:?}", e))
+                    .and_then(|bundle| tenant.load(bundle).map_err(|e| e.to_string()));
+                match loaded {
+                    Ok(handle) if tenant_id == tenant::DEFAULT_TENANT => {
+                        eprintln!("Loaded contract: {} (from {})", handle.id(), path.display());
+                    }
+                    Ok(handle) => eprintln!(
+                        "Loaded contract: {} for tenant {} (from {})",
+                        handle.id(),
+                        tenant_id,
+                        path.display()
+                    ),
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("Warning: failed to load {}: {}", path.display(), e);
+                    }
+                }
+            }
+        }
+        state.readiness.mark_preloaded(failed);
+    });
+}
+
+/// Wait for a shutdown signal (SIGTERM or Ctrl+C), then mark the server
+/// as draining so `/readyz` fails.
+async fn shutdown_signal(state: &AppState) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    state.readiness.mark_draining();
+    eprintln!("\nReceived shutdown signal, draining in-flight requests...");
 }
@@ -0,0 +1,78 @@
+//! Content-Type negotiation between JSON and CBOR.
+//!
+//! Request bodies are decoded as CBOR when sent with
+//! `Content-Type: application/cbor` and as JSON otherwise. Endpoints that
+//! return a bundle encode it as CBOR when the client lists
+//! `application/cbor` in its `Accept` header.
+
+use axum::body::Bytes;
+use axum::extract::{FromRequest, Request};
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use tenor_interchange::CBOR_MEDIA_TYPE;
+
+use super::json_error;
+
+/// A request body decoded from either JSON or CBOR into a JSON value.
+pub(crate) struct Payload(pub(crate) serde_json::Value);
+
+impl<S: Send + Sync> FromRequest<S> for Payload {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_cbor(req.headers().get(CONTENT_TYPE)) {
+            return Json::<serde_json::Value>::from_request(req, state)
+                .await
+                .map(|Json(value)| Payload(value))
+                .map_err(IntoResponse::into_response);
+        }
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        tenor_interchange::from_cbor(&bytes)
+            .map(Payload)
+            .map_err(|e| json_error(StatusCode::BAD_REQUEST, &e.to_string()).into_response())
+    }
+}
+
+/// Respond with `value` as CBOR if the client accepts it, JSON otherwise.
+pub(crate) fn negotiated(
+    headers: &HeaderMap,
+    status: StatusCode,
+    value: serde_json::Value,
+) -> Response {
+    if !accepts_cbor(headers) {
+        return (status, Json(value)).into_response();
+    }
+    match tenor_interchange::to_cbor(&value) {
+        Ok(bytes) => (
+            status,
+            [(CONTENT_TYPE, HeaderValue::from_static(CBOR_MEDIA_TYPE))],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()).into_response(),
+    }
+}
+
+fn is_cbor(content_type: Option<&HeaderValue>) -> bool {
+    content_type
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| media_type(v).eq_ignore_ascii_case(CBOR_MEDIA_TYPE))
+}
+
+fn accepts_cbor(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|entry| media_type(entry).eq_ignore_ascii_case(CBOR_MEDIA_TYPE))
+}
+
+/// The media type of a header entry, without parameters such as `q=`.
+fn media_type(entry: &str) -> &str {
+    entry.split(';').next().unwrap_or("").trim()
+}
@@ -0,0 +1,92 @@
+//! Contract version pinning.
+//!
+//! A client can pin an evaluation request to the contract version it
+//! integrated against, with `If-Match: "<etag>"` or a `bundle_digest` body
+//! field. The etag is the SHA-256 of the contract's interchange bundle, as
+//! listed by `GET /contracts` and served by `/.well-known/tenor`; the handle
+//! computes it once at load, so checking a pin does not rehash the bundle. If the
+//! loaded contract no longer matches, the request fails with 412 Precondition
+//! Failed, carrying the current etag in the `ETag` header, instead of being
+//! answered by a contract the client has never seen.
+
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use tenor_eval::ContractHandle;
+
+use super::json_error;
+
+/// Check the request's version pins against `handle`. Requests without
+/// pins always pass.
+pub(crate) fn check_contract_pin(
+    headers: &HeaderMap,
+    body: &serde_json::Value,
+    handle: &ContractHandle,
+) -> Result<(), Box<Response>> {
+    let digest = match body.get("bundle_digest") {
+        None => None,
+        Some(serde_json::Value::String(d)) => Some(d.as_str()),
+        Some(_) => {
+            return Err(Box::new(
+                json_error(StatusCode::BAD_REQUEST, "'bundle_digest' must be a string")
+                    .into_response(),
+            ))
+        }
+    };
+    let if_match = match headers.get(header::IF_MATCH).map(|v| v.to_str()) {
+        None => None,
+        Some(Ok(v)) => Some(v),
+        Some(Err(_)) => {
+            return Err(Box::new(
+                json_error(StatusCode::BAD_REQUEST, "invalid If-Match header").into_response(),
+            ))
+        }
+    };
+    if digest.is_none() && if_match.is_none() {
+        return Ok(());
+    }
+
+    let etag = handle.etag();
+    let pinned = if_match.map(|v| if_match_covers(v, etag)).unwrap_or(true)
+        && digest.map(|d| d == etag).unwrap_or(true);
+    if pinned {
+        return Ok(());
+    }
+    let mut response = json_error(
+        StatusCode::PRECONDITION_FAILED,
+        &format!(
+            "contract '{}' has changed: its current etag is \"{}\"",
+            handle.id(),
+            etag
+        ),
+    )
+    .into_response();
+    if let Ok(val) = format!("\"{}\"", etag).parse() {
+        response.headers_mut().insert(header::ETAG, val);
+    }
+    Err(Box::new(response))
+}
+
+/// Whether an `If-Match` value (`*` or a list of entity tags) matches
+/// `etag`. Tags are compared strongly, so weak tags never match; bare
+/// unquoted tags are accepted.
+fn if_match_covers(value: &str, etag: &str) -> bool {
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || (!tag.starts_with("W/") && tag.trim_matches('"') == etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_match_accepts_any_listed_strong_tag() {
+        assert!(if_match_covers("\"abc\"", "abc"));
+        assert!(if_match_covers("abc", "abc"));
+        assert!(if_match_covers("\"old\", \"abc\"", "abc"));
+        assert!(if_match_covers("*", "abc"));
+        assert!(!if_match_covers("W/\"abc\"", "abc"));
+        assert!(!if_match_covers("\"old\"", "abc"));
+    }
+}
@@ -0,0 +1,750 @@
+//! Scheduled evaluations.
+//!
+//! `POST /contracts/{id}/schedules` registers a job that evaluates a loaded
+//! contract on a cron schedule. Each run gathers facts through the adapters
+//! for the contract's Source constructs (configured by the tenant's adapter
+//! settings, see `adapters`), with any
+//! facts given in the schedule taking priority, and hands the verdicts to
+//! the schedule's sink: a webhook that receives them as a JSON POST, or the
+//! server's store, which keeps the most recent results for
+//! `GET /contracts/{id}/schedules/{schedule_id}`.
+//!
+//! Cron expressions have the five standard fields (minute, hour, day of
+//! month, month, day of week), numeric values only, evaluated in UTC. Runs
+//! of one schedule never overlap: the next run time is computed after the
+//! previous run finishes. When a run's verdicts differ from the previous
+//! run's, webhooks subscribed to `verdicts.changed` hear about it (see
+//! `webhooks`). Like timers, schedules live in memory and do not
+//! survive a restart; unloading a contract cancels its schedules.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use tenor_eval::FactProvider;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
+
+use super::adapters;
+use super::delta::verdict_diff;
+use super::json_error;
+use super::negotiate::Payload;
+use super::tenant::{Authenticated, Tenant};
+use super::webhooks;
+
+/// Results kept per schedule with the store sink.
+const STORED_RESULTS: usize = 10;
+
+/// Longest a single adapter fetch may take during a scheduled run.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A parsed five-field cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether day of month and day of week were given as `*`; when both
+    /// are restricted, a day matching either one fires (as in Vixie cron).
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    pub(crate) fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "cron expression '{}' must have 5 fields (minute hour day month weekday), found {}",
+                expr,
+                fields.len()
+            ));
+        };
+        let mut weekdays = parse_field(weekday, "weekday", 0, 7)?;
+        // Both 0 and 7 are Sunday.
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)?,
+            days: parse_field(day, "day of month", 1, 31)?,
+            months: parse_field(month, "month", 1, 12)?,
+            weekdays,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    /// The first minute strictly after `after` that the expression matches,
+    /// or `None` if it matches nothing in the next five years (`0 0 30 2 *`).
+    pub(crate) fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        let after = after.to_offset(time::UtcOffset::UTC);
+        let mut t = at(after.date(), after.hour(), after.minute())? + time::Duration::MINUTE;
+        let horizon = after.year() + 5;
+        while t.year() <= horizon {
+            let date = t.date();
+            if !has(self.months, u8::from(date.month())) {
+                let (year, month) = match date.month() {
+                    time::Month::December => (date.year() + 1, time::Month::January),
+                    month => (date.year(), month.next()),
+                };
+                t = at(Date::from_calendar_date(year, month, 1).ok()?, 0, 0)?;
+            } else if !self.matches_day(date) {
+                t = at(date.next_day()?, 0, 0)?;
+            } else if !has(self.hours, t.hour()) {
+                t = at(date, t.hour(), 0)? + time::Duration::HOUR;
+            } else if !has(self.minutes, t.minute()) {
+                t += time::Duration::MINUTE;
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: Date) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().number_days_from_sunday());
+        if self.any_day || self.any_weekday {
+            day && weekday
+        } else {
+            day || weekday
+        }
+    }
+}
+
+fn has(bits: u64, value: u8) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn at(date: Date, hour: u8, minute: u8) -> Option<OffsetDateTime> {
+    Some(date.with_hms(hour, minute, 0).ok()?.assume_utc())
+}
+
+/// Parse one cron field (`*`, `5`, `1-5`, `*/15`, `10-40/10`, or a comma
+/// list of those) into a bitset of the values it allows.
+fn parse_field(text: &str, name: &str, min: u8, max: u8) -> Result<u64, String> {
+    let invalid = |reason: &str| format!("invalid cron {} '{}': {}", name, text, reason);
+    let number = |s: &str| -> Result<u8, String> {
+        let n: u8 = s.parse().map_err(|_| invalid("expected a number"))?;
+        if n < min || n > max {
+            return Err(invalid(&format!("values must be {}-{}", min, max)));
+        }
+        Ok(n)
+    };
+    let mut bits = 0u64;
+    for part in text.split(',') {
+        let stepped = part.contains('/');
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u8 = step
+                    .parse()
+                    .map_err(|_| invalid("expected a step number"))?;
+                if step == 0 {
+                    return Err(invalid("step must be at least 1"));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (low, high) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((low, high)) => (number(low)?, number(high)?),
+                // `5/15` means every 15 from 5.
+                None if stepped => (number(range)?, max),
+                None => {
+                    let n = number(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if low > high {
+            return Err(invalid("range start is after its end"));
+        }
+        for value in (low..=high).step_by(usize::from(step)) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+/// Where a schedule's verdicts go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Sink {
+    /// POST the verdicts to a URL.
+    Webhook(String),
+    /// Keep the last `STORED_RESULTS` verdict sets on the server.
+    Store,
+}
+
+impl Sink {
+    /// `{"webhook": "<url>"}` or `"store"`.
+    fn from_json(value: &serde_json::Value) -> Result<Self, String> {
+        if value.as_str() == Some("store") {
+            return Ok(Sink::Store);
+        }
+        match value.get("webhook").and_then(|u| u.as_str()) {
+            Some(url) if url.starts_with("http://") || url.starts_with("https://") => {
+                Ok(Sink::Webhook(url.to_string()))
+            }
+            Some(url) => Err(format!("webhook URL '{}' must be http or https", url)),
+            None => Err("'sink' must be \"store\" or {\"webhook\": \"<url>\"}".to_string()),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Sink::Webhook(url) => serde_json::json!({ "webhook": url }),
+            Sink::Store => serde_json::json!("store"),
+        }
+    }
+}
+
+/// What a schedule runs.
+#[derive(Clone)]
+struct Job {
+    contract_id: String,
+    /// Facts given with the schedule; they win over adapter-fetched values.
+    facts: HashMap<String, serde_json::Value>,
+    sink: Sink,
+}
+
+struct Schedule {
+    cron: String,
+    job: Job,
+    next_run: Option<String>,
+    runs: u64,
+    last_run: Option<serde_json::Value>,
+    /// Verdicts of recent runs, newest last (store sink only).
+    results: VecDeque<serde_json::Value>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// All schedules of one tenant, keyed by schedule id.
+#[derive(Default)]
+pub(crate) struct Schedules {
+    next_id: AtomicU64,
+    schedules: Mutex<HashMap<String, Schedule>>,
+}
+
+impl Schedules {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Cancel a schedule of `contract_id`. Returns false if there is none.
+    fn cancel(&self, contract_id: &str, id: &str) -> bool {
+        let mut schedules = self.lock();
+        match schedules.get(id) {
+            Some(s) if s.job.contract_id == contract_id => {
+                if let Some(s) = schedules.remove(id) {
+                    s.task.abort();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cancel every schedule of a contract that is being unloaded.
+    pub(crate) fn cancel_for_contract(&self, contract_id: &str) {
+        self.lock().retain(|_, s| {
+            let keep = s.job.contract_id != contract_id;
+            if !keep {
+                s.task.abort();
+            }
+            keep
+        });
+    }
+
+    /// JSON view of a schedule of `contract_id`; `with_results` adds the
+    /// stored verdicts.
+    fn describe(
+        &self,
+        contract_id: &str,
+        id: &str,
+        with_results: bool,
+    ) -> Option<serde_json::Value> {
+        let schedules = self.lock();
+        let schedule = schedules
+            .get(id)
+            .filter(|s| s.job.contract_id == contract_id)?;
+        let mut out = serde_json::json!({
+            "schedule_id": id,
+            "contract_id": schedule.job.contract_id,
+            "cron": schedule.cron,
+            "sink": schedule.job.sink.to_json(),
+            "next_run": schedule.next_run,
+            "runs": schedule.runs,
+            "last_run": schedule.last_run,
+        });
+        if with_results && schedule.job.sink == Sink::Store {
+            out["results"] = serde_json::json!(schedule.results);
+        }
+        Some(out)
+    }
+
+    /// Ids of the schedules of `contract_id`, in creation order.
+    fn ids_for(&self, contract_id: &str) -> Vec<String> {
+        let mut ids: Vec<(u64, String)> = self
+            .lock()
+            .iter()
+            .filter(|(_, s)| s.job.contract_id == contract_id)
+            .map(|(id, _)| (id_number(id), id.clone()))
+            .collect();
+        ids.sort();
+        ids.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Record the next run time. Returns false if the schedule was cancelled.
+    fn set_next_run(&self, id: &str, next: OffsetDateTime) -> bool {
+        match self.lock().get_mut(id) {
+            Some(schedule) => {
+                schedule.next_run = Some(rfc3339(next));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record a finished run. Returns false if the schedule was cancelled.
+    fn record(&self, id: &str, run: Run) -> bool {
+        let mut schedules = self.lock();
+        let Some(schedule) = schedules.get_mut(id) else {
+            return false;
+        };
+        schedule.runs += 1;
+        let mut last_run = serde_json::json!({
+            "started_at": run.started_at,
+            "finished_at": run.finished_at,
+            "status": if run.outcome.is_ok() { "succeeded" } else { "failed" },
+        });
+        match run.outcome {
+            Ok(verdicts) => {
+                if schedule.job.sink == Sink::Store {
+                    schedule.results.push_back(serde_json::json!({
+                        "started_at": run.started_at,
+                        "verdicts": verdicts,
+                    }));
+                    if schedule.results.len() > STORED_RESULTS {
+                        schedule.results.pop_front();
+                    }
+                }
+            }
+            Err(error) => last_run["error"] = serde_json::json!(error),
+        }
+        schedule.next_run = None;
+        schedule.last_run = Some(last_run);
+        true
+    }
+
+    // Never held across an await.
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Schedule>> {
+        self.schedules.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn id_number(id: &str) -> u64 {
+    id.trim_start_matches("schedule-").parse().unwrap_or(0)
+}
+
+pub(crate) fn rfc3339(t: OffsetDateTime) -> String {
+    t.format(&Rfc3339).unwrap_or_else(|_| t.to_string())
+}
+
+/// Register a schedule and start its task, returning the schedule id.
+fn start(tenant: &Arc<Tenant>, cron_text: &str, cron: Cron, job: Job) -> String {
+    let schedules = &tenant.schedules;
+    let id = format!(
+        "schedule-{}",
+        schedules.next_id.fetch_add(1, Ordering::Relaxed) + 1
+    );
+
+    // Hold the lock while spawning so the task cannot look for its entry
+    // before it exists.
+    let mut entries = schedules.lock();
+    let task = tokio::spawn(run_schedule(
+        Arc::clone(tenant),
+        id.clone(),
+        cron,
+        job.clone(),
+    ));
+    entries.insert(
+        id.clone(),
+        Schedule {
+            cron: cron_text.to_string(),
+            job,
+            next_run: None,
+            runs: 0,
+            last_run: None,
+            results: VecDeque::new(),
+            task,
+        },
+    );
+    id
+}
+
+async fn run_schedule(tenant: Arc<Tenant>, id: String, cron: Cron, job: Job) {
+    // Verdicts of the previous successful run, for `verdicts.changed`.
+    let mut previous: Option<serde_json::Value> = None;
+    loop {
+        let now = OffsetDateTime::now_utc();
+        let Some(next) = cron.next_after(now) else {
+            return;
+        };
+        if !tenant.schedules.set_next_run(&id, next) {
+            return;
+        }
+        tokio::time::sleep((next - now).unsigned_abs()).await;
+        // Read the settings per run, so a reload applies from the next one.
+        let adapter_config = tenant.adapter_config();
+        let run = run_once(&tenant, &adapter_config, &id, &job).await;
+        if let Ok(verdicts) = &run.outcome {
+            let verdicts = verdicts["verdicts"].clone();
+            if let Some(before) = previous.replace(verdicts.clone()) {
+                webhooks::notify_verdict_diff(
+                    &tenant,
+                    &job.contract_id,
+                    serde_json::json!({ "source": "schedule", "schedule_id": id }),
+                    verdict_diff(&before, &verdicts),
+                );
+            }
+        }
+        if !tenant.schedules.record(&id, run) {
+            return;
+        }
+    }
+}
+
+/// One finished run of a schedule.
+struct Run {
+    started_at: String,
+    finished_at: String,
+    /// The verdicts, or why the run failed.
+    outcome: Result<serde_json::Value, String>,
+}
+
+async fn run_once(
+    tenant: &Arc<Tenant>,
+    adapter_config: &tenor_eval::AdapterConfig,
+    id: &str,
+    job: &Job,
+) -> Run {
+    let started_at = rfc3339(OffsetDateTime::now_utc());
+    let outcome = match evaluate(tenant, adapter_config, job).await {
+        Ok(verdicts) => match &job.sink {
+            Sink::Webhook(url) => {
+                let body = serde_json::json!({
+                    "schedule_id": id,
+                    "contract_id": job.contract_id,
+                    "started_at": started_at,
+                    "verdicts": verdicts,
+                });
+                match tenant.webhooks.check_target(url).await {
+                    Ok(addresses) => {
+                        webhooks::post(url.clone(), addresses, body.to_string(), Vec::new())
+                            .await
+                            .map(|()| verdicts)
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Sink::Store => Ok(verdicts),
+        },
+        Err(e) => Err(e),
+    };
+    Run {
+        started_at,
+        finished_at: rfc3339(OffsetDateTime::now_utc()),
+        outcome,
+    }
+}
+
+/// Gather facts through the contract's adapters and evaluate its rules.
+async fn evaluate(
+    tenant: &Arc<Tenant>,
+    adapter_config: &tenor_eval::AdapterConfig,
+    job: &Job,
+) -> Result<serde_json::Value, String> {
+    let handle = tenant
+        .contracts
+        .get(&job.contract_id)
+        .ok_or_else(|| format!("contract '{}' is not loaded", job.contract_id))?;
+    let bundle = tenor_interchange::from_interchange(handle.bundle())
+        .map_err(|e| format!("failed to read contract sources: {}", e))?;
+    let sources: Vec<tenor_interchange::SourceConstruct> = bundle
+        .constructs
+        .into_iter()
+        .filter_map(|c| match c {
+            tenor_interchange::InterchangeConstruct::Source(s) => Some(s),
+            _ => None,
+        })
+        .collect();
+    let registry = adapters::registry(tenant, &sources, adapter_config)?;
+    let provider = tenor_eval::AdapterFactProvider::new(registry, job.facts.clone())
+        .with_fetch_timeout(FETCH_TIMEOUT);
+    let facts = provider
+        .provide(handle.bundle())
+        .await
+        .map_err(|e| format!("failed to fetch facts: {}", e))?;
+
+    let facts = serde_json::Value::Object(facts.into_iter().collect());
+    match tokio::task::spawn_blocking(move || handle.evaluate(&facts)).await {
+        Ok(Ok(result)) => Ok(result.verdicts.to_json()),
+        Ok(Err(e)) => Err(format!("{}: {}", e.code(), e)),
+        Err(e) => Err(format!("task join error: {}", e)),
+    }
+}
+
+/// POST /contracts/{id}/schedules
+///
+/// Input: { "cron": "*/15 * * * *", "sink": "store" | {"webhook": "<url>"},
+///          "facts": {...} }
+///
+/// `facts` is optional. Requires authentication, since a schedule makes
+/// the server fetch facts and call webhooks on its own.
+pub(crate) async fn handle_create_schedule(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+    Path(contract_id): Path<String>,
+    Payload(parsed): Payload,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "schedules require authentication: set TENOR_API_KEY or configure tenants",
+        )
+        .into_response();
+    }
+    if tenant.contracts.get(&contract_id).is_none() {
+        return json_error(
+            StatusCode::NOT_FOUND,
+            &format!("contract '{}' not found", contract_id),
+        )
+        .into_response();
+    }
+    let cron_text = match parsed.get("cron").and_then(|v| v.as_str()) {
+        Some(c) => c.to_string(),
+        None => return json_error(StatusCode::BAD_REQUEST, "missing 'cron' field").into_response(),
+    };
+    let cron = match Cron::parse(&cron_text) {
+        Ok(c) => c,
+        Err(e) => return json_error(StatusCode::BAD_REQUEST, &e).into_response(),
+    };
+    if cron.next_after(OffsetDateTime::now_utc()).is_none() {
+        return json_error(
+            StatusCode::BAD_REQUEST,
+            &format!("cron expression '{}' never fires", cron_text),
+        )
+        .into_response();
+    }
+    let sink = match parsed.get("sink").map(Sink::from_json) {
+        Some(Ok(sink)) => sink,
+        Some(Err(e)) => return json_error(StatusCode::BAD_REQUEST, &e).into_response(),
+        None => return json_error(StatusCode::BAD_REQUEST, "missing 'sink' field").into_response(),
+    };
+    if let Sink::Webhook(url) = &sink {
+        if let Err(e) = tenant.webhooks.check_target(url).await {
+            return json_error(StatusCode::BAD_REQUEST, &e).into_response();
+        }
+    }
+    let facts = match parsed.get("facts") {
+        None => HashMap::new(),
+        Some(serde_json::Value::Object(facts)) => facts.clone().into_iter().collect(),
+        Some(_) => {
+            return json_error(StatusCode::BAD_REQUEST, "'facts' must be an object").into_response()
+        }
+    };
+
+    let job = Job {
+        contract_id: contract_id.clone(),
+        facts,
+        sink,
+    };
+    let id = start(&tenant, &cron_text, cron, job);
+    match tenant.schedules.describe(&contract_id, &id, false) {
+        Some(body) => (StatusCode::CREATED, Json(body)).into_response(),
+        None => json_error(StatusCode::INTERNAL_SERVER_ERROR, "schedule vanished").into_response(),
+    }
+}
+
+/// GET /contracts/{id}/schedules
+pub(crate) async fn handle_list_schedules(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Path(contract_id): Path<String>,
+) -> Response {
+    if tenant.contracts.get(&contract_id).is_none() {
+        return json_error(
+            StatusCode::NOT_FOUND,
+            &format!("contract '{}' not found", contract_id),
+        )
+        .into_response();
+    }
+    let schedules: Vec<serde_json::Value> = tenant
+        .schedules
+        .ids_for(&contract_id)
+        .iter()
+        .filter_map(|id| tenant.schedules.describe(&contract_id, id, false))
+        .collect();
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "schedules": schedules })),
+    )
+        .into_response()
+}
+
+/// GET /contracts/{id}/schedules/{schedule_id}
+///
+/// The schedule with its last-run status and, for the store sink, the
+/// verdicts of its recent runs.
+pub(crate) async fn handle_get_schedule(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Path((contract_id, id)): Path<(String, String)>,
+) -> Response {
+    match tenant.schedules.describe(&contract_id, &id, true) {
+        Some(body) => (StatusCode::OK, Json(body)).into_response(),
+        None => json_error(
+            StatusCode::NOT_FOUND,
+            &format!("schedule '{}' not found", id),
+        )
+        .into_response(),
+    }
+}
+
+/// DELETE /contracts/{id}/schedules/{schedule_id}
+pub(crate) async fn handle_delete_schedule(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+    Path((contract_id, id)): Path<(String, String)>,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "schedules require authentication: set TENOR_API_KEY or configure tenants",
+        )
+        .into_response();
+    }
+    if tenant.schedules.cancel(&contract_id, &id) {
+        (StatusCode::OK, Json(serde_json::json!({ "removed": id }))).into_response()
+    } else {
+        json_error(
+            StatusCode::NOT_FOUND,
+            &format!("schedule '{}' not found", id),
+        )
+        .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn next(expr: &str, after: OffsetDateTime) -> OffsetDateTime {
+        Cron::parse(expr).unwrap().next_after(after).unwrap()
+    }
+
+    #[test]
+    fn fields_accept_lists_ranges_and_steps() {
+        assert_eq!(
+            parse_field("*/15", "minute", 0, 59),
+            Ok(0x1 | 1 << 15 | 1 << 30 | 1 << 45)
+        );
+        assert_eq!(parse_field("1-3,7", "hour", 0, 23), Ok(0b1000_1110));
+        assert_eq!(
+            parse_field("10-20/5", "minute", 0, 59),
+            Ok(1 << 10 | 1 << 15 | 1 << 20)
+        );
+        assert_eq!(parse_field("50/5", "minute", 0, 59), Ok(1 << 50 | 1 << 55));
+        assert!(parse_field("60", "minute", 0, 59).is_err());
+        assert!(parse_field("5-1", "hour", 0, 23).is_err());
+        assert!(parse_field("*/0", "hour", 0, 23).is_err());
+        assert!(Cron::parse("* * * *").is_err());
+        assert_eq!(
+            Cron::parse("0 0 * * 7").unwrap(),
+            Cron::parse("0 0 * * 0").unwrap()
+        );
+    }
+
+    #[test]
+    fn next_run_skips_to_the_first_matching_minute() {
+        let now = datetime!(2026-01-31 23:59:30 UTC);
+        assert_eq!(next("* * * * *", now), datetime!(2026-02-01 00:00 UTC));
+        assert_eq!(next("*/15 * * * *", now), datetime!(2026-02-01 00:00 UTC));
+        assert_eq!(next("30 9 * * *", now), datetime!(2026-02-01 09:30 UTC));
+        assert_eq!(next("0 12 1 * *", now), datetime!(2026-02-01 12:00 UTC));
+        assert_eq!(next("0 0 1 1 *", now), datetime!(2027-01-01 00:00 UTC));
+        // 2026-02-02 is a Monday.
+        assert_eq!(next("0 8 * * 1-5", now), datetime!(2026-02-02 08:00 UTC));
+        assert_eq!(next("0 0 29 2 *", now), datetime!(2028-02-29 00:00 UTC));
+        assert!(Cron::parse("0 0 30 2 *").unwrap().next_after(now).is_none());
+    }
+
+    #[test]
+    fn restricted_day_and_weekday_match_either() {
+        // The 15th of the month or any Sunday: 2026-02-01 is a Sunday.
+        let now = datetime!(2026-01-20 00:00 UTC);
+        assert_eq!(next("0 0 15 * 0", now), datetime!(2026-01-25 00:00 UTC));
+        assert_eq!(
+            next("0 0 15 * 0", datetime!(2026-02-10 00:00 UTC)),
+            datetime!(2026-02-15 00:00 UTC)
+        );
+    }
+
+    #[tokio::test]
+    async fn store_sink_keeps_verdicts_and_status() {
+        let tenant = Tenant::new("acme", None);
+        let bundle = serde_json::json!({
+            "constructs": [],
+            "id": "empty",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": tenor_core::TENOR_BUNDLE_VERSION,
+        });
+        tenant.load(bundle).ok().unwrap();
+        let tenant = Arc::new(tenant);
+        let job = Job {
+            contract_id: "empty".to_string(),
+            facts: HashMap::new(),
+            sink: Sink::Store,
+        };
+        let config = tenant.adapter_config();
+        let id = start(
+            &tenant,
+            "0 0 1 1 *",
+            Cron::parse("0 0 1 1 *").unwrap(),
+            job.clone(),
+        );
+
+        let run = run_once(&tenant, &config, &id, &job).await;
+        assert!(tenant.schedules.record(&id, run));
+        let view = tenant.schedules.describe("empty", &id, true).unwrap();
+        assert_eq!(view["runs"], 1);
+        assert_eq!(view["last_run"]["status"], "succeeded");
+        assert_eq!(view["results"].as_array().unwrap().len(), 1);
+        assert!(tenant.schedules.describe("other", &id, true).is_none());
+
+        tenant.contracts.remove("empty");
+        let run = run_once(&tenant, &config, &id, &job).await;
+        assert!(tenant.schedules.record(&id, run));
+        let view = tenant.schedules.describe("empty", &id, true).unwrap();
+        assert_eq!(view["last_run"]["status"], "failed");
+        assert_eq!(view["last_run"]["error"], "contract 'empty' is not loaded");
+
+        tenant.schedules.cancel_for_contract("empty");
+        assert_eq!(tenant.schedules.len(), 0);
+    }
+}
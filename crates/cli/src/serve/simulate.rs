@@ -1,32 +1,34 @@
-//! Flow simulation and action space handlers.
+//! Flow simulation, operation preview, and action space handlers.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::extract::Path;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
-use axum::Json;
+use axum::{Extension, Json};
 
-use super::json_error;
-use super::state::AppState;
+use super::negotiate::Payload;
+use super::pinning::check_contract_pin;
+use super::tenant::Tenant;
+use super::{json_error, json_error_with_code};
 
 /// Internal error type for simulate_flow to distinguish persona errors from eval errors.
-enum SimulateError {
+pub(crate) enum SimulateError {
     PersonaNotFound(String),
     Eval(tenor_eval::EvalError),
 }
 
 /// Core simulation logic, runs synchronously in a blocking task.
-fn simulate_flow_inner(
-    bundle: &serde_json::Value,
+///
+/// Also used by `tenor mcp` for its `simulate_flow` tool.
+pub(crate) fn simulate_flow_inner(
+    contract: &tenor_eval::Contract,
     facts: &serde_json::Value,
     flow_id: &str,
     persona_id: &str,
     entity_states_input: Option<&serde_json::Value>,
 ) -> Result<serde_json::Value, SimulateError> {
-    let contract = tenor_eval::Contract::from_interchange(bundle).map_err(SimulateError::Eval)?;
-
     // Check persona exists in contract
     if !contract.personas.contains(&persona_id.to_string()) {
         return Err(SimulateError::PersonaNotFound(persona_id.to_string()));
@@ -34,11 +36,11 @@ fn simulate_flow_inner(
 
     // Assemble facts
     let fact_set =
-        tenor_eval::assemble::assemble_facts(&contract, facts).map_err(SimulateError::Eval)?;
+        tenor_eval::assemble::assemble_facts(contract, facts).map_err(SimulateError::Eval)?;
 
     // Evaluate rules to produce verdicts
     let verdict_set =
-        tenor_eval::rules::eval_strata(&contract, &fact_set).map_err(SimulateError::Eval)?;
+        tenor_eval::rules::eval_strata(contract, &fact_set).map_err(SimulateError::Eval)?;
 
     // Create frozen snapshot
     let snapshot = tenor_eval::Snapshot {
@@ -48,17 +50,14 @@ fn simulate_flow_inner(
 
     // Build entity states: start from contract defaults, override with request.
     // Overrides use DEFAULT_INSTANCE_ID since the API takes plain entity_id -> state.
-    let mut entity_states = tenor_eval::operation::init_entity_states(&contract);
+    let mut entity_states = tenor_eval::operation::init_entity_states(contract);
     if let Some(es_input) = entity_states_input {
         if let Some(obj) = es_input.as_object() {
             for (entity_id, state_info) in obj {
                 if let Some(state) = state_info.get("state").and_then(|v| v.as_str()) {
                     entity_states.insert(
-                        (
-                            entity_id.clone(),
-                            tenor_eval::DEFAULT_INSTANCE_ID.to_string(),
-                        ),
-                        state.to_string(),
+                        tenor_eval::instance_key(entity_id, tenor_eval::DEFAULT_INSTANCE_ID),
+                        state.into(),
                     );
                 }
             }
@@ -87,11 +86,12 @@ fn simulate_flow_inner(
     // Execute the flow
     let flow_result = tenor_eval::flow::execute_flow(
         target_flow,
-        &contract,
+        contract,
         &snapshot,
         &mut entity_states,
+        &mut tenor_eval::EntityAttributeMap::new(),
         &tenor_eval::InstanceBindingMap::new(),
-        None,
+        tenor_eval::FlowOptions::default(),
     )
     .map_err(SimulateError::Eval)?;
 
@@ -105,7 +105,7 @@ fn simulate_flow_inner(
                 "type": step.step_type,
                 "outcome": step.result,
             });
-            if let Some((op_name, step_persona)) = step_info.get(&step.step_id) {
+            if let Some((op_name, step_persona)) = step_info.get(step.step_id.as_str()) {
                 step_json["operation"] = serde_json::json!(op_name);
                 step_json["persona"] = serde_json::json!(step_persona);
             }
@@ -153,9 +153,10 @@ fn simulate_flow_inner(
 /// Dedicated flow simulation endpoint. Stateless -- entity states come from
 /// the request body, nothing is persisted. Returns simulation: true always.
 pub(crate) async fn handle_simulate_flow(
-    State(state): State<Arc<AppState>>,
+    Extension(tenant): Extension<Arc<Tenant>>,
     Path(flow_id): Path<String>,
-    Json(parsed): Json<serde_json::Value>,
+    headers: HeaderMap,
+    Payload(parsed): Payload,
 ) -> Response {
     let persona_id = match parsed.get("persona_id").and_then(|v| v.as_str()) {
         Some(p) => p.to_string(),
@@ -172,25 +173,13 @@ pub(crate) async fn handle_simulate_flow(
     let entity_states_input = parsed.get("entity_states").cloned();
 
     // Find the contract containing this flow
-    let contracts = state.contracts.read().await;
-    let mut found_bundle: Option<serde_json::Value> = None;
-
-    for bundle in contracts.values() {
-        if let Some(constructs) = bundle.get("constructs").and_then(|c| c.as_array()) {
-            let has_flow = constructs.iter().any(|c| {
-                c.get("kind").and_then(|k| k.as_str()) == Some("Flow")
-                    && c.get("id").and_then(|i| i.as_str()) == Some(&flow_id)
-            });
-            if has_flow {
-                found_bundle = Some(bundle.clone());
-                break;
-            }
-        }
-    }
-    drop(contracts);
-
-    let bundle = match found_bundle {
-        Some(b) => b,
+    let handle = match tenant
+        .contracts
+        .handles()
+        .into_iter()
+        .find(|h| h.get_flow(&flow_id).is_some())
+    {
+        Some(h) => h,
         None => {
             return json_error(
                 StatusCode::NOT_FOUND,
@@ -199,11 +188,14 @@ pub(crate) async fn handle_simulate_flow(
             .into_response()
         }
     };
+    if let Err(response) = check_contract_pin(&headers, &parsed, &handle) {
+        return *response;
+    }
 
     let fid = flow_id.clone();
     let result = tokio::task::spawn_blocking(move || {
         simulate_flow_inner(
-            &bundle,
+            handle.contract(),
             &facts,
             &fid,
             &persona_id,
@@ -230,6 +222,159 @@ pub(crate) async fn handle_simulate_flow(
     }
 }
 
+/// POST /contracts/{id}/operations/{op_id}/preview
+///
+/// Dry-run one operation. Stateless -- facts, entity states and instance
+/// bindings come from the request body, nothing is persisted. Runs the
+/// persona, precondition, guard and source-state checks and reports the
+/// outcome and effects the operation would apply, or why it would be
+/// rejected.
+///
+/// Input: { "persona_id": "...", "facts": {...}, "entity_states": {...},
+///          "instance_bindings": {...} }
+/// Output: { "preview": true, "would_succeed": bool, "outcome", "effects",
+///           "facts_used", "verdicts_used" } or { ..., "error": {...} }
+pub(crate) async fn handle_preview_operation(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Path((contract_id, op_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Payload(parsed): Payload,
+) -> Response {
+    let persona_id = match parsed.get("persona_id").and_then(|v| v.as_str()) {
+        Some(p) => p.to_string(),
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'persona_id' field")
+                .into_response()
+        }
+    };
+    let facts = parsed
+        .get("facts")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    let inputs = parsed
+        .get("entity_states")
+        .map(tenor_eval::simulate::parse_entity_states)
+        .unwrap_or_else(|| Ok(tenor_eval::EntityStateMap::new()))
+        .and_then(|states| {
+            let bindings = parsed
+                .get("instance_bindings")
+                .map(tenor_eval::simulate::parse_instance_bindings)
+                .unwrap_or_else(|| Ok(tenor_eval::InstanceBindingMap::new()))?;
+            Ok((states, bindings))
+        });
+    let (entity_states_input, instance_bindings) = match inputs {
+        Ok(inputs) => inputs,
+        Err(e) => return json_error(StatusCode::BAD_REQUEST, &e).into_response(),
+    };
+
+    let handle = match tenant.contracts.get(&contract_id) {
+        Some(h) => h,
+        None => {
+            return json_error(
+                StatusCode::NOT_FOUND,
+                &format!("contract '{}' not found", contract_id),
+            )
+            .into_response()
+        }
+    };
+    if handle.contract().get_operation(&op_id).is_none() {
+        return json_error(
+            StatusCode::NOT_FOUND,
+            &format!(
+                "operation '{}' not found in contract '{}'",
+                op_id, contract_id
+            ),
+        )
+        .into_response();
+    }
+    if let Err(response) = check_contract_pin(&headers, &parsed, &handle) {
+        return *response;
+    }
+
+    let result = tokio::task::spawn_blocking(move || {
+        let contract = handle.contract();
+        let op = contract.get_operation(&op_id).expect("checked above");
+        let fact_set = tenor_eval::assemble::assemble_facts(contract, &facts)?;
+        let verdicts = tenor_eval::rules::eval_strata(contract, &fact_set)?;
+        // Entities not given in the request are in their initial state.
+        let mut entity_states = tenor_eval::operation::init_entity_states(contract);
+        entity_states.extend(entity_states_input);
+        let preview = tenor_eval::operation::preview_operation(
+            op,
+            &persona_id,
+            &fact_set,
+            &verdicts,
+            &entity_states,
+            &tenor_eval::EntityAttributeMap::new(),
+            &instance_bindings,
+            &tenor_eval::EntityVersions::default(),
+        );
+        Ok::<_, tenor_eval::EvalError>(preview_json(&op_id, &persona_id, preview))
+    })
+    .await;
+
+    match result {
+        Ok(Ok(Ok(json))) => (StatusCode::OK, Json(json)).into_response(),
+        Ok(Ok(Err(e))) | Ok(Err(e)) => json_error_with_code(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            e.code(),
+            &format!("{}", e),
+        )
+        .into_response(),
+        Err(e) => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("task join error: {}", e),
+        )
+        .into_response(),
+    }
+}
+
+/// The preview response for an operation. An evaluation error is returned
+/// as `Err`; a rejection by the operation's own checks is a preview with
+/// `would_succeed: false`.
+fn preview_json(
+    op_id: &str,
+    persona_id: &str,
+    preview: Result<tenor_eval::OperationResult, tenor_eval::OperationError>,
+) -> Result<serde_json::Value, tenor_eval::EvalError> {
+    let mut json = serde_json::json!({
+        "preview": true,
+        "operation_id": op_id,
+        "persona": persona_id,
+    });
+    match preview {
+        Ok(result) => {
+            let effects: Vec<serde_json::Value> = result
+                .effects_applied
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "entity_id": e.entity_id,
+                        "instance_id": e.instance_id,
+                        "kind": e.kind.as_str(),
+                        "from_state": e.from_state,
+                        "to_state": e.to_state,
+                    })
+                })
+                .collect();
+            json["would_succeed"] = serde_json::json!(true);
+            json["outcome"] = serde_json::json!(result.outcome);
+            json["effects"] = serde_json::json!(effects);
+            json["facts_used"] = serde_json::json!(result.provenance.facts_used);
+            json["verdicts_used"] = serde_json::json!(result.provenance.verdicts_used);
+        }
+        Err(tenor_eval::OperationError::EvalError(e)) => return Err(e),
+        Err(e) => {
+            json["would_succeed"] = serde_json::json!(false);
+            json["error"] = serde_json::json!({
+                "kind": e.kind(),
+                "message": e.to_string(),
+            });
+        }
+    }
+    Ok(json)
+}
+
 /// POST /actions
 ///
 /// Compute the action space for a persona. Stateless -- facts and entity
@@ -239,8 +384,9 @@ pub(crate) async fn handle_simulate_flow(
 /// Input: { "persona_id": "...", "facts": {...}, "entity_states": {...} }
 /// Output: ActionSpace JSON
 pub(crate) async fn handle_actions(
-    State(state): State<Arc<AppState>>,
-    Json(parsed): Json<serde_json::Value>,
+    Extension(tenant): Extension<Arc<Tenant>>,
+    headers: HeaderMap,
+    Payload(parsed): Payload,
 ) -> Response {
     let persona_id = match parsed.get("persona_id").and_then(|v| v.as_str()) {
         Some(p) => p.to_string(),
@@ -254,39 +400,25 @@ pub(crate) async fn handle_actions(
         .get("facts")
         .cloned()
         .unwrap_or(serde_json::json!({}));
-    let entity_states_input: std::collections::BTreeMap<String, String> =
-        match parsed.get("entity_states") {
-            Some(v) => match serde_json::from_value(v.clone()) {
-                Ok(m) => m,
-                Err(e) => {
-                    return json_error(
-                        StatusCode::BAD_REQUEST,
-                        &format!("invalid entity_states: {}", e),
-                    )
-                    .into_response()
-                }
-            },
-            None => std::collections::BTreeMap::new(),
-        };
+    let entity_states_input = match entity_states_input(&parsed) {
+        Ok(m) => m,
+        Err(message) => return json_error(StatusCode::BAD_REQUEST, &message).into_response(),
+    };
 
     // Find first loaded contract (same pattern as /evaluate)
-    let contracts = state.contracts.read().await;
-    let bundle = match contracts.values().next() {
-        Some(b) => b.clone(),
+    let handle = match tenant.contracts.handles().into_iter().next() {
+        Some(h) => h,
         None => return json_error(StatusCode::NOT_FOUND, "no contracts loaded").into_response(),
     };
-    drop(contracts);
+    if let Err(response) = check_contract_pin(&headers, &parsed, &handle) {
+        return *response;
+    }
 
     let result = tokio::task::spawn_blocking(move || {
-        let contract = match tenor_eval::Contract::from_interchange(&bundle) {
-            Ok(c) => c,
-            Err(e) => return Err(format!("invalid contract: {}", e)),
-        };
-
         // Convert flat entity_id -> state map to composite (entity_id, instance_id) key format.
         let entity_states = tenor_eval::single_instance(entity_states_input);
         tenor_eval::action_space::compute_action_space(
-            &contract,
+            handle.contract(),
             &facts,
             &entity_states,
             &persona_id,
@@ -312,3 +444,75 @@ pub(crate) async fn handle_actions(
         .into_response(),
     }
 }
+
+/// POST /actions/matrix
+///
+/// Compute the action space of every persona in one pass: rules are
+/// evaluated once and shared. Returns, for each persona, the availability
+/// of every operation (with the blocking reason) and the persona's action
+/// space.
+///
+/// Input: { "facts": {...}, "entity_states": {...} }
+/// Output: ActionSpaceMatrix JSON
+pub(crate) async fn handle_actions_matrix(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    headers: HeaderMap,
+    Payload(parsed): Payload,
+) -> Response {
+    let facts = parsed
+        .get("facts")
+        .cloned()
+        .unwrap_or(serde_json::json!({}));
+    let entity_states_input = match entity_states_input(&parsed) {
+        Ok(m) => m,
+        Err(message) => return json_error(StatusCode::BAD_REQUEST, &message).into_response(),
+    };
+
+    let handle = match tenant.contracts.handles().into_iter().next() {
+        Some(h) => h,
+        None => return json_error(StatusCode::NOT_FOUND, "no contracts loaded").into_response(),
+    };
+    if let Err(response) = check_contract_pin(&headers, &parsed, &handle) {
+        return *response;
+    }
+
+    let result = tokio::task::spawn_blocking(move || {
+        let entity_states = tenor_eval::single_instance(entity_states_input);
+        tenor_eval::action_space::compute_action_space_matrix(
+            handle.contract(),
+            &facts,
+            &entity_states,
+        )
+        .map_err(|e| format!("{}", e))
+    })
+    .await;
+
+    match result {
+        Ok(Ok(matrix)) => match serde_json::to_value(&matrix) {
+            Ok(json) => (StatusCode::OK, Json(json)).into_response(),
+            Err(e) => json_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("serialization error: {}", e),
+            )
+            .into_response(),
+        },
+        Ok(Err(e)) => json_error(StatusCode::UNPROCESSABLE_ENTITY, &e).into_response(),
+        Err(e) => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("task join error: {}", e),
+        )
+        .into_response(),
+    }
+}
+
+/// The flat entity_id -> state map from a request's `entity_states` field.
+fn entity_states_input(
+    parsed: &serde_json::Value,
+) -> Result<std::collections::BTreeMap<String, String>, String> {
+    match parsed.get("entity_states") {
+        Some(v) => {
+            serde_json::from_value(v.clone()).map_err(|e| format!("invalid entity_states: {}", e))
+        }
+        None => Ok(std::collections::BTreeMap::new()),
+    }
+}
@@ -1,39 +1,62 @@
 //! Application state and rate limiting.
 
 use std::collections::HashMap;
-use std::net::IpAddr;
 use std::time::Instant;
 
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::Mutex;
 
-use super::RATE_LIMIT_WINDOW_SECS;
+use super::health::Readiness;
+use super::tenant::Tenants;
+use super::{RateLimitKey, RATE_LIMIT_WINDOW_SECS};
 
-/// Per-IP request tracker: (request count, window start time).
-type IpTracker = HashMap<IpAddr, (u64, Instant)>;
+/// Per-client request counts: (request count, window start time).
+struct ClientTracker {
+    clients: HashMap<String, (u64, Instant)>,
+    /// When windows that ran out were last dropped.
+    last_sweep: Instant,
+}
 
-/// In-memory per-IP rate limiter.
+/// In-memory per-client rate limiter. A client is an IP address or an
+/// authenticated tenant, per `RateLimitKey`.
 pub(crate) struct RateLimiter {
-    /// Request counts per IP per window.
-    tracker: Mutex<IpTracker>,
-    /// Maximum requests per window.
+    /// Request counts per client per window.
+    tracker: Mutex<ClientTracker>,
+    /// Maximum requests per window; 0 disables the limit.
     pub(crate) max_requests: u64,
+    pub(crate) key: RateLimitKey,
 }
 
 impl RateLimiter {
-    pub(crate) fn new(max_requests: u64) -> Self {
+    pub(crate) fn new(max_requests: u64, key: RateLimitKey) -> Self {
         Self {
-            tracker: Mutex::new(HashMap::new()),
+            tracker: Mutex::new(ClientTracker {
+                clients: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
             max_requests,
+            key,
         }
     }
 
-    /// Check if a request from the given IP is allowed.
+    /// Check if a request from the given client is allowed.
     /// Returns Ok(()) if allowed, Err(retry_after_secs) if rate limited.
-    pub(crate) async fn check(&self, ip: IpAddr) -> Result<(), u64> {
+    pub(crate) async fn check(&self, client: String) -> Result<(), u64> {
+        if self.max_requests == 0 {
+            return Ok(());
+        }
         let mut tracker = self.tracker.lock().await;
         let now = Instant::now();
 
-        let entry = tracker.entry(ip).or_insert((0, now));
+        // Once per window, forget clients whose window has run out, so
+        // clients that stop sending do not stay in memory.
+        if now.duration_since(tracker.last_sweep).as_secs() >= RATE_LIMIT_WINDOW_SECS {
+            tracker.clients.retain(|_, (_, start)| {
+                now.duration_since(*start).as_secs() < RATE_LIMIT_WINDOW_SECS
+            });
+            tracker.last_sweep = now;
+        }
+
+        let entry = tracker.clients.entry(client).or_insert((0, now));
 
         // Reset window if expired
         let elapsed = now.duration_since(entry.1).as_secs();
@@ -54,10 +77,35 @@ impl RateLimiter {
 
 /// Application state shared across request handlers.
 pub(crate) struct AppState {
-    /// Loaded contract bundles keyed by bundle ID.
-    pub(crate) contracts: RwLock<HashMap<String, serde_json::Value>>,
+    /// Tenants and their contracts, baselines and timers.
+    pub(crate) tenants: Tenants,
     /// Per-IP rate limiter.
     pub(crate) rate_limiter: RateLimiter,
-    /// Optional API key for authentication. None = no auth required.
-    pub(crate) api_key: Option<String>,
+    /// Preload and shutdown state reported by `/readyz`.
+    pub(crate) readiness: Readiness,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn expired_windows_are_dropped() {
+        let limiter = RateLimiter::new(1, RateLimitKey::Ip);
+        let past = Instant::now()
+            .checked_sub(Duration::from_secs(RATE_LIMIT_WINDOW_SECS + 1))
+            .unwrap();
+        {
+            let mut tracker = limiter.tracker.lock().await;
+            tracker.clients.insert("ip:10.0.0.1".to_string(), (5, past));
+            tracker.last_sweep = past;
+        }
+
+        assert_eq!(limiter.check("ip:10.0.0.2".to_string()).await, Ok(()));
+        assert!(limiter.check("ip:10.0.0.2".to_string()).await.is_err());
+        let tracker = limiter.tracker.lock().await;
+        assert!(!tracker.clients.contains_key("ip:10.0.0.1"));
+        assert!(tracker.clients.contains_key("ip:10.0.0.2"));
+    }
 }
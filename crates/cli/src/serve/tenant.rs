@@ -0,0 +1,425 @@
+//! Tenant scoping.
+//!
+//! Every authenticated request belongs to a tenant, identified by the API
+//! token it carries. A tenant has its own contract store, delta baselines,
+//! flow timers, flow executions, schedules, webhooks and request counter,
+//! so contract ids, baseline ids, timer ids, execution ids, schedule ids
+//! and webhook ids of one tenant are invisible to every other, and a
+//! tenant's webhooks only hear about its own contracts. Tenants are declared in `[serve.tenants.<id>]` in
+//! tenor.toml, each with the environment variable holding its token, the
+//! contracts to preload, an optional contract limit, an optional entity
+//! history exported from its storage and optional adapter settings.
+//!
+//! The contracts given on the command line (or `[serve] contracts`) belong
+//! to the `default` tenant, reached with `TENOR_API_KEY`, or by every
+//! request when the server has no authentication at all.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+
+use super::adapters::AdapterSettings;
+use super::delta::FactBaselines;
+use super::entities::EntityStore;
+use super::executions::FlowExecutions;
+use super::negotiate::Payload;
+use super::schedules::Schedules;
+use super::timers::FlowTimers;
+use super::webhooks::Webhooks;
+use super::{json_error, json_error_with_code};
+
+/// Id of the tenant holding the command-line contracts.
+pub(crate) const DEFAULT_TENANT: &str = "default";
+
+/// A tenant of a multi-tenant server, from `[serve.tenants.<id>]`.
+#[derive(Debug, Clone)]
+pub struct TenantConfig {
+    pub id: String,
+    /// The API token identifying the tenant's requests.
+    pub token: String,
+    /// Contracts to preload for the tenant.
+    pub contracts: Vec<PathBuf>,
+    /// Maximum number of contracts the tenant may have loaded at once.
+    pub max_contracts: Option<usize>,
+    /// Entity history exported from the tenant's storage.
+    pub history: Option<PathBuf>,
+    /// Adapter settings for the facts of the tenant's scheduled evaluations.
+    pub adapter_config: Option<PathBuf>,
+}
+
+/// One tenant's contracts and server-side records.
+pub(crate) struct Tenant {
+    pub(crate) id: String,
+    /// Loaded contracts keyed by bundle ID.
+    pub(crate) contracts: tenor_eval::ContractStore,
+    pub(crate) max_contracts: Option<usize>,
+    /// Expiry timers for suspended flows with handoff deadlines.
+    pub(crate) flow_timers: FlowTimers,
+    /// Flow runs made for the tenant, for `/flows/executions`.
+    pub(crate) flow_executions: FlowExecutions,
+    /// Baseline facts documents for `/contracts/{id}/evaluate-delta`.
+    pub(crate) fact_baselines: FactBaselines,
+    /// Scheduled evaluations of the tenant's contracts.
+    pub(crate) schedules: Schedules,
+    /// Webhooks notified of verdict and entity state changes.
+    pub(crate) webhooks: Webhooks,
+    /// Entity states and transitions queried by `/entities`.
+    pub(crate) entity_store: OnceLock<Arc<dyn EntityStore>>,
+    /// Adapter settings for the facts of scheduled evaluations.
+    pub(crate) adapter_settings: OnceLock<Arc<AdapterSettings>>,
+    /// Authenticated requests made by the tenant.
+    requests: AtomicU64,
+    /// Serializes loads so the contract limit check and insert are atomic.
+    load_lock: Mutex<()>,
+}
+
+/// Why a contract could not be loaded for a tenant.
+pub(crate) enum LoadError {
+    /// The tenant already has its maximum number of contracts.
+    Limit(usize),
+    Invalid(tenor_eval::EvalError),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Limit(max) => write!(f, "contract limit of {} reached", max),
+            LoadError::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Tenant {
+    pub(crate) fn new(id: &str, max_contracts: Option<usize>) -> Self {
+        Self {
+            id: id.to_string(),
+            contracts: tenor_eval::ContractStore::new(),
+            max_contracts,
+            flow_timers: FlowTimers::new(),
+            flow_executions: FlowExecutions::new(),
+            fact_baselines: FactBaselines::new(),
+            schedules: Schedules::new(),
+            webhooks: Webhooks::new(),
+            entity_store: OnceLock::new(),
+            adapter_settings: OnceLock::new(),
+            requests: AtomicU64::new(0),
+            load_lock: Mutex::new(()),
+        }
+    }
+
+    /// Load a contract bundle. Replacing a loaded contract with the same id
+    /// does not count against the contract limit.
+    pub(crate) fn load(
+        &self,
+        bundle: serde_json::Value,
+    ) -> Result<tenor_eval::ContractHandle, LoadError> {
+        let handle =
+            tenor_eval::ContractHandle::from_interchange(bundle).map_err(LoadError::Invalid)?;
+        let _guard = self.load_lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(max) = self.max_contracts {
+            if self.contracts.get(handle.id()).is_none() && self.contracts.len() >= max {
+                return Err(LoadError::Limit(max));
+            }
+        }
+        self.contracts.insert(handle.clone());
+        Ok(handle)
+    }
+
+    /// The adapter settings for a run that starts now: none configured
+    /// when the tenant has no adapter settings.
+    pub(crate) fn adapter_config(&self) -> Arc<tenor_eval::AdapterConfig> {
+        self.adapter_settings
+            .get()
+            .map(|settings| settings.current())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// All tenants of the server and the tokens that reach them.
+pub(crate) struct Tenants {
+    default: Arc<Tenant>,
+    by_token: HashMap<String, Arc<Tenant>>,
+}
+
+/// Why a request could not be assigned to a tenant.
+pub(crate) enum AuthError {
+    /// No token was given.
+    Missing,
+    /// The token matches no tenant.
+    Invalid,
+}
+
+/// Whether the request was authenticated with a token, as opposed to
+/// reaching the default tenant of a server without authentication.
+#[derive(Clone, Copy)]
+pub(crate) struct Authenticated(pub(crate) bool);
+
+impl Tenants {
+    /// Tenants for `TENOR_API_KEY` (the default tenant) and `configs`.
+    /// Fails if two tenants share an id or a token.
+    pub(crate) fn new(api_key: Option<String>, configs: &[TenantConfig]) -> Result<Self, String> {
+        let default = Arc::new(Tenant::new(DEFAULT_TENANT, None));
+        let mut by_token = HashMap::new();
+        if let Some(key) = api_key {
+            by_token.insert(key, default.clone());
+        }
+        let mut ids = vec![DEFAULT_TENANT];
+        for config in configs {
+            if ids.contains(&config.id.as_str()) {
+                return Err(format!("duplicate tenant '{}'", config.id));
+            }
+            ids.push(&config.id);
+            let tenant = Arc::new(Tenant::new(&config.id, config.max_contracts));
+            if by_token.insert(config.token.clone(), tenant).is_some() {
+                return Err(format!(
+                    "tenant '{}' shares its API token with another tenant",
+                    config.id
+                ));
+            }
+        }
+        Ok(Self { default, by_token })
+    }
+
+    /// Whether requests must carry a token.
+    pub(crate) fn requires_auth(&self) -> bool {
+        !self.by_token.is_empty()
+    }
+
+    /// The tenant a request with `token` belongs to.
+    pub(crate) fn authenticate(&self, token: Option<&str>) -> Result<Arc<Tenant>, AuthError> {
+        if !self.requires_auth() {
+            return Ok(self.default.clone());
+        }
+        let token = token.ok_or(AuthError::Missing)?;
+        self.by_token.get(token).cloned().ok_or(AuthError::Invalid)
+    }
+
+    /// The tenant with the given id.
+    pub(crate) fn get(&self, id: &str) -> Option<Arc<Tenant>> {
+        if id == DEFAULT_TENANT {
+            return Some(self.default.clone());
+        }
+        self.by_token.values().find(|t| t.id == id).cloned()
+    }
+
+    /// Every tenant, the default one first.
+    pub(crate) fn all(&self) -> Vec<Arc<Tenant>> {
+        let mut all = vec![self.default.clone()];
+        for tenant in self.by_token.values() {
+            if !all.iter().any(|t| Arc::ptr_eq(t, tenant)) {
+                all.push(tenant.clone());
+            }
+        }
+        all
+    }
+
+    /// Total contracts loaded across tenants.
+    pub(crate) fn contract_count(&self) -> usize {
+        let others: usize = self
+            .by_token
+            .values()
+            .filter(|t| !Arc::ptr_eq(t, &self.default))
+            .map(|t| t.contracts.len())
+            .sum();
+        self.default.contracts.len() + others
+    }
+}
+
+/// POST /contracts
+///
+/// Load an interchange bundle for the requesting tenant, replacing a loaded
+/// contract with the same id. Requires authentication, so an open server
+/// cannot be filled with contracts by anyone who can reach it.
+///
+/// Input: { "bundle": {...} }
+pub(crate) async fn handle_load_contract(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+    Payload(parsed): Payload,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "loading contracts requires authentication: set TENOR_API_KEY or configure tenants",
+        )
+        .into_response();
+    }
+    let bundle = match parsed.get("bundle") {
+        Some(b) => b.clone(),
+        None => {
+            return json_error(StatusCode::BAD_REQUEST, "missing 'bundle' field").into_response()
+        }
+    };
+    let loading = tenant.clone();
+    let result = tokio::task::spawn_blocking(move || loading.load(bundle)).await;
+    match result {
+        Ok(Ok(handle)) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "id": handle.id(),
+                "etag": handle.etag(),
+            })),
+        )
+            .into_response(),
+        Ok(Err(LoadError::Limit(max))) => json_error(
+            StatusCode::FORBIDDEN,
+            &format!(
+                "tenant '{}' has reached its limit of {} contracts",
+                tenant.id, max
+            ),
+        )
+        .into_response(),
+        Ok(Err(LoadError::Invalid(e))) => json_error_with_code(
+            StatusCode::BAD_REQUEST,
+            e.code(),
+            &format!("invalid bundle: {}", e),
+        )
+        .into_response(),
+        Err(e) => json_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("task join error: {}", e),
+        )
+        .into_response(),
+    }
+}
+
+/// DELETE /contracts/{id}
+pub(crate) async fn handle_unload_contract(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "unloading contracts requires authentication: set TENOR_API_KEY or configure tenants",
+        )
+        .into_response();
+    }
+    match tenant.contracts.remove(&id) {
+        Some(_) => {
+            tenant.schedules.cancel_for_contract(&id);
+            (StatusCode::OK, Json(serde_json::json!({ "removed": id }))).into_response()
+        }
+        None => json_error(
+            StatusCode::NOT_FOUND,
+            &format!("contract '{}' not found", id),
+        )
+        .into_response(),
+    }
+}
+
+/// GET /tenant
+///
+/// The requesting tenant's id and usage: loaded contracts against its
+/// limit, stored baselines, active schedules, webhooks and requests made.
+pub(crate) async fn handle_tenant(Extension(tenant): Extension<Arc<Tenant>>) -> Response {
+    let body = serde_json::json!({
+        "tenant": tenant.id,
+        "contracts": tenant.contracts.ids(),
+        "max_contracts": tenant.max_contracts,
+        "baselines": tenant.fact_baselines.len(),
+        "flow_executions": tenant.flow_executions.len(),
+        "schedules": tenant.schedules.len(),
+        "webhooks": tenant.webhooks.len(),
+        "requests": tenant.requests.load(Ordering::Relaxed),
+    });
+    (StatusCode::OK, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(id: &str, token: &str, max_contracts: Option<usize>) -> TenantConfig {
+        TenantConfig {
+            id: id.to_string(),
+            token: token.to_string(),
+            contracts: Vec::new(),
+            max_contracts,
+            history: None,
+            adapter_config: None,
+        }
+    }
+
+    fn bundle(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "constructs": [],
+            "id": id,
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": tenor_core::TENOR_BUNDLE_VERSION,
+        })
+    }
+
+    #[test]
+    fn tokens_resolve_to_isolated_tenants() {
+        let tenants = Tenants::new(
+            Some("admin".to_string()),
+            &[
+                config("acme", "acme-token", None),
+                config("globex", "globex-token", None),
+            ],
+        )
+        .unwrap();
+        assert!(matches!(
+            tenants.authenticate(None),
+            Err(AuthError::Missing)
+        ));
+        assert!(matches!(
+            tenants.authenticate(Some("nope")),
+            Err(AuthError::Invalid)
+        ));
+        let acme = tenants.authenticate(Some("acme-token")).ok().unwrap();
+        let globex = tenants.authenticate(Some("globex-token")).ok().unwrap();
+        let default = tenants.authenticate(Some("admin")).ok().unwrap();
+        assert_eq!(
+            [acme.id.as_str(), globex.id.as_str(), default.id.as_str()],
+            ["acme", "globex", DEFAULT_TENANT]
+        );
+
+        acme.load(bundle("shared_id")).ok().unwrap();
+        assert!(acme.contracts.get("shared_id").is_some());
+        assert!(globex.contracts.get("shared_id").is_none());
+        assert!(default.contracts.get("shared_id").is_none());
+        assert_eq!(tenants.contract_count(), 1);
+        assert!(Arc::ptr_eq(&tenants.get("acme").unwrap(), &acme));
+    }
+
+    #[test]
+    fn contract_limit_allows_replacing_loaded_contracts() {
+        let tenant = Tenant::new("acme", Some(1));
+        tenant.load(bundle("first")).ok().unwrap();
+        tenant.load(bundle("first")).ok().unwrap();
+        assert!(matches!(
+            tenant.load(bundle("second")),
+            Err(LoadError::Limit(1))
+        ));
+        assert_eq!(tenant.contracts.ids(), vec!["first".to_string()]);
+    }
+
+    #[test]
+    fn duplicate_tokens_and_ids_are_rejected() {
+        assert!(Tenants::new(None, &[config("a", "t", None), config("b", "t", None)]).is_err());
+        assert!(Tenants::new(None, &[config("a", "t", None), config("a", "u", None)]).is_err());
+        assert!(Tenants::new(Some("t".to_string()), &[config("a", "t", None)]).is_err());
+        assert!(Tenants::new(None, &[config(DEFAULT_TENANT, "t", None)]).is_err());
+    }
+
+    #[test]
+    fn server_without_tokens_serves_everyone_as_default() {
+        let tenants = Tenants::new(None, &[]).unwrap();
+        assert!(!tenants.requires_auth());
+        let tenant = tenants.authenticate(None).ok().unwrap();
+        assert_eq!(tenant.id, DEFAULT_TENANT);
+    }
+}
@@ -0,0 +1,177 @@
+//! Expiry timers for suspended flows.
+//!
+//! When a flow pauses at a handoff that declares a `timeout`, the server
+//! arms a timer for the continuation's deadline. If the flow has not been
+//! resumed when the timer fires, the handoff's `on_timeout` action runs
+//! (`ContractHandle::expire_flow`) and the result is kept for
+//! `GET /flows/timers/{id}`. Passing the timer id to `/flows/resume`
+//! disarms it. Timers live in memory and do not survive a restart.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::handlers::{flow_run_json, FlowRunRecord};
+use super::tenant::Tenant;
+
+/// Lifecycle of one timer.
+pub(crate) enum TimerState {
+    Armed(tokio::task::JoinHandle<()>),
+    /// Fired; the timeout action is running.
+    Expiring,
+    /// Fired; holds the response body of the expired run.
+    Expired(serde_json::Value),
+    /// Disarmed by a resume before the deadline.
+    Resumed,
+}
+
+impl TimerState {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            TimerState::Armed(_) => "armed",
+            TimerState::Expiring => "expiring",
+            TimerState::Expired(_) => "expired",
+            TimerState::Resumed => "resumed",
+        }
+    }
+}
+
+pub(crate) struct Timer {
+    pub(crate) flow_id: String,
+    pub(crate) deadline: String,
+    pub(crate) state: TimerState,
+}
+
+/// All timers armed for one tenant, keyed by timer id.
+#[derive(Default)]
+pub(crate) struct FlowTimers {
+    next_id: AtomicU64,
+    timers: Mutex<HashMap<String, Timer>>,
+}
+
+impl FlowTimers {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Disarm an armed timer. Returns the timer's state label if it can no
+    /// longer be disarmed, or `None` if there is no such timer.
+    pub(crate) fn disarm(&self, id: &str) -> Result<(), Option<&'static str>> {
+        let mut timers = self.lock();
+        let timer = timers.get_mut(id).ok_or(None)?;
+        match std::mem::replace(&mut timer.state, TimerState::Resumed) {
+            TimerState::Armed(task) => {
+                task.abort();
+                Ok(())
+            }
+            other => {
+                let label = other.label();
+                timer.state = other;
+                Err(Some(label))
+            }
+        }
+    }
+
+    /// JSON view of a timer, including the expired run once it has fired.
+    pub(crate) fn describe(&self, id: &str) -> Option<serde_json::Value> {
+        let timers = self.lock();
+        let timer = timers.get(id)?;
+        let mut out = serde_json::json!({
+            "timer_id": id,
+            "flow_id": timer.flow_id,
+            "deadline": timer.deadline,
+            "status": timer.state.label(),
+        });
+        if let TimerState::Expired(result) = &timer.state {
+            out["result"] = result.clone();
+        }
+        Some(out)
+    }
+
+    // Never held across an await, so poisoning cannot leave a timer
+    // half-updated.
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Timer>> {
+        self.timers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Arm a timer for a suspended flow, returning its id. Returns `None` if
+/// the paused handoff has no deadline.
+pub(crate) fn arm(
+    tenant: &Arc<Tenant>,
+    handle: &tenor_eval::ContractHandle,
+    continuation: &tenor_eval::FlowContinuation,
+) -> Option<String> {
+    let delay = continuation.time_until_deadline()?;
+    let timers = &tenant.flow_timers;
+    let id = format!(
+        "timer-{}",
+        timers.next_id.fetch_add(1, Ordering::Relaxed) + 1
+    );
+
+    // Hold the lock while spawning so the task cannot fire before its
+    // entry exists.
+    let mut entries = timers.lock();
+    let task = tokio::spawn(expire_after(
+        Arc::clone(tenant),
+        handle.clone(),
+        continuation.clone(),
+        id.clone(),
+        delay,
+    ));
+    entries.insert(
+        id.clone(),
+        Timer {
+            flow_id: continuation.flow_id.clone(),
+            deadline: continuation.deadline.clone().unwrap_or_default(),
+            state: TimerState::Armed(task),
+        },
+    );
+    Some(id)
+}
+
+async fn expire_after(
+    tenant: Arc<Tenant>,
+    handle: tenor_eval::ContractHandle,
+    continuation: tenor_eval::FlowContinuation,
+    id: String,
+    delay: std::time::Duration,
+) {
+    tokio::time::sleep(delay).await;
+    {
+        let mut timers = tenant.flow_timers.lock();
+        match timers.get_mut(&id) {
+            Some(timer) if matches!(timer.state, TimerState::Armed(_)) => {
+                timer.state = TimerState::Expiring;
+            }
+            _ => return,
+        }
+    }
+
+    let flow_id = continuation.flow_id.clone();
+    let facts = continuation.snapshot.snapshot.facts.to_json();
+    let started_at = time::OffsetDateTime::now_utc();
+    let expiring = handle.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        expiring.expire_flow(continuation, &tenor_eval::EntityVersions::default())
+    })
+    .await;
+    let body = match result {
+        Ok(Ok(run)) => {
+            let record = FlowRunRecord {
+                flow_id: &flow_id,
+                facts,
+                started_at,
+            };
+            flow_run_json(&tenant, &handle, record, run)
+        }
+        Ok(Err(e)) => serde_json::json!({ "error": format!("{}", e), "code": e.code() }),
+        Err(e) => serde_json::json!({
+            "error": format!("task join error: {}", e),
+            "code": super::status_error_code(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+        }),
+    };
+    if let Some(timer) = tenant.flow_timers.lock().get_mut(&id) {
+        timer.state = TimerState::Expired(body);
+    }
+}
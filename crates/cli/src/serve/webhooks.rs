@@ -0,0 +1,670 @@
+//! Webhook notifications.
+//!
+//! A tenant registers webhooks with `POST /webhooks` to hear about
+//! decisions without polling. Two events are sent:
+//!
+//! - `verdicts.changed`: verdicts appeared, disappeared or changed payload
+//!   between a baseline and its patched facts (`/evaluate-delta`) or
+//!   between consecutive runs of a schedule.
+//! - `entity_states.changed`: a flow run started by `/evaluate`, resumed by
+//!   `/flows/resume` or expired by a timer transitioned entities.
+//!
+//! Each delivery is a JSON POST signed with the webhook's secret: the
+//! `X-Tenor-Signature` header is `sha256=` followed by the hex HMAC-SHA256
+//! of the raw body. Failed deliveries are retried with exponential backoff
+//! (1s, 2s, 4s, 8s) before being counted as failed. Webhooks live in memory
+//! and do not survive a restart.
+//!
+//! Webhook and schedule sink URLs must not reach the server's own network:
+//! a URL whose host is or resolves to a loopback, private, link-local
+//! (including the 169.254.169.254 cloud metadata service), shared or
+//! unspecified address is refused when it is registered and again before
+//! each delivery, unless the operator starts the server with
+//! `--webhook-allow-private` or `[serve] webhook_allow_private = true`.
+//! A delivery connects only to the addresses vetted just before it, so the
+//! host cannot be re-pointed between the check and the request, and
+//! redirects are not followed. Adapter fetches of scheduled evaluations go
+//! through the same check (see `adapters`).
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use axum::extract::Path;
+use axum::http::{StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use time::OffsetDateTime;
+
+use super::json_error;
+use super::negotiate::Payload;
+use super::schedules::rfc3339;
+use super::tenant::{Authenticated, Tenant};
+
+pub(crate) const VERDICTS_CHANGED: &str = "verdicts.changed";
+pub(crate) const ENTITY_STATES_CHANGED: &str = "entity_states.changed";
+const EVENTS: &[&str] = &[VERDICTS_CHANGED, ENTITY_STATES_CHANGED];
+
+/// Attempts per delivery, including the first.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Wait before the first retry; doubled for each further one.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+struct Webhook {
+    url: String,
+    secret: String,
+    events: Vec<String>,
+    /// Only events about this contract, when set.
+    contract_id: Option<String>,
+    delivered: u64,
+    failed: u64,
+    last_delivery: Option<serde_json::Value>,
+}
+
+impl Webhook {
+    fn wants(&self, event: &str, contract_id: &str) -> bool {
+        self.events.iter().any(|e| e == event)
+            && self.contract_id.as_deref().is_none_or(|c| c == contract_id)
+    }
+
+    /// JSON view, without the secret.
+    fn describe(&self, id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "webhook_id": id,
+            "url": self.url,
+            "events": self.events,
+            "contract_id": self.contract_id,
+            "delivered": self.delivered,
+            "failed": self.failed,
+            "last_delivery": self.last_delivery,
+        })
+    }
+}
+
+/// All webhooks of one tenant, keyed by webhook id.
+#[derive(Default)]
+pub(crate) struct Webhooks {
+    next_id: AtomicU64,
+    next_delivery: AtomicU64,
+    webhooks: Mutex<HashMap<String, Webhook>>,
+    /// Deliver to loopback, private and link-local addresses too.
+    allow_private_targets: AtomicBool,
+}
+
+impl Webhooks {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let deliveries reach the server's own network.
+    pub(crate) fn allow_private_targets(&self) {
+        self.allow_private_targets.store(true, Ordering::Relaxed);
+    }
+
+    /// The addresses `url` reaches, for `post` to connect to. Refused when
+    /// any of them is internal and the operator has not allowed that.
+    pub(crate) async fn check_target(&self, url: &str) -> Result<Vec<SocketAddr>, String> {
+        let uri: Uri = url.parse().map_err(|_| format!("invalid URL '{}'", url))?;
+        let host = uri
+            .host()
+            .ok_or_else(|| format!("URL '{}' has no host", url))?
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+        let port = uri.port_u16().unwrap_or(match uri.scheme_str() {
+            Some("https") => 443,
+            _ => 80,
+        });
+        let addresses: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|e| format!("could not resolve host '{}': {}", host, e))?
+                .collect(),
+        };
+        if self.allow_private_targets.load(Ordering::Relaxed) {
+            return Ok(addresses);
+        }
+        match addresses.iter().find(|a| is_internal(a.ip())) {
+            Some(a) => Err(format!(
+                "URL '{}' reaches internal address {}: start tenor serve with --webhook-allow-private to allow it",
+                url,
+                a.ip()
+            )),
+            None => Ok(addresses),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    // Never held across an await.
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Webhook>> {
+        self.webhooks.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Send `event` about `contract_id` to every webhook of the tenant that
+/// subscribes to it. Deliveries run in the background.
+pub(crate) fn notify(
+    tenant: &Arc<Tenant>,
+    event: &'static str,
+    contract_id: &str,
+    data: serde_json::Value,
+) {
+    let targets: Vec<(String, String, String)> = tenant
+        .webhooks
+        .lock()
+        .iter()
+        .filter(|(_, w)| w.wants(event, contract_id))
+        .map(|(id, w)| (id.clone(), w.url.clone(), w.secret.clone()))
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+    let timestamp = rfc3339(OffsetDateTime::now_utc());
+    for (webhook_id, url, secret) in targets {
+        let delivery_id = format!(
+            "delivery-{}",
+            tenant
+                .webhooks
+                .next_delivery
+                .fetch_add(1, Ordering::Relaxed)
+                + 1
+        );
+        let body = serde_json::json!({
+            "delivery_id": delivery_id,
+            "event": event,
+            "tenant": tenant.id,
+            "contract_id": contract_id,
+            "timestamp": timestamp,
+            "data": data,
+        })
+        .to_string();
+        let headers = vec![
+            ("x-tenor-event", event.to_string()),
+            ("x-tenor-delivery", delivery_id.clone()),
+            (
+                "x-tenor-signature",
+                format!("sha256={}", hmac_sha256(secret.as_bytes(), body.as_bytes())),
+            ),
+        ];
+        tokio::spawn(deliver(
+            Arc::clone(tenant),
+            webhook_id,
+            delivery_id,
+            event,
+            url,
+            body,
+            headers,
+        ));
+    }
+}
+
+/// Send `verdicts.changed` unless `diff` (see `delta::verdict_diff`) is
+/// empty.
+pub(crate) fn notify_verdict_diff(
+    tenant: &Arc<Tenant>,
+    contract_id: &str,
+    mut data: serde_json::Value,
+    diff: serde_json::Value,
+) {
+    let unchanged = ["added", "removed", "changed"]
+        .iter()
+        .all(|key| diff[key].as_array().is_none_or(|a| a.is_empty()));
+    if unchanged {
+        return;
+    }
+    data["diff"] = diff;
+    notify(tenant, VERDICTS_CHANGED, contract_id, data);
+}
+
+async fn deliver(
+    tenant: Arc<Tenant>,
+    webhook_id: String,
+    delivery_id: String,
+    event: &'static str,
+    url: String,
+    body: String,
+    headers: Vec<(&'static str, String)>,
+) {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempts = 0;
+    // The host may have been re-pointed since the webhook was created.
+    let outcome = match tenant.webhooks.check_target(&url).await {
+        Err(e) => Err(e),
+        Ok(addresses) => loop {
+            attempts += 1;
+            match post(
+                url.clone(),
+                addresses.clone(),
+                body.clone(),
+                headers.clone(),
+            )
+            .await
+            {
+                Ok(()) => break Ok(()),
+                Err(e) if attempts >= MAX_ATTEMPTS => break Err(e),
+                Err(_) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        },
+    };
+
+    let mut webhooks = tenant.webhooks.lock();
+    let Some(webhook) = webhooks.get_mut(&webhook_id) else {
+        return;
+    };
+    let mut last_delivery = serde_json::json!({
+        "delivery_id": delivery_id,
+        "event": event,
+        "attempts": attempts,
+        "finished_at": rfc3339(OffsetDateTime::now_utc()),
+    });
+    match outcome {
+        Ok(()) => {
+            webhook.delivered += 1;
+            last_delivery["status"] = serde_json::json!("delivered");
+        }
+        Err(e) => {
+            webhook.failed += 1;
+            last_delivery["status"] = serde_json::json!("failed");
+            last_delivery["error"] = serde_json::json!(e);
+        }
+    }
+    webhook.last_delivery = Some(last_delivery);
+}
+
+/// POST a JSON `body` to `url` at one of `addresses` (from `check_target`);
+/// any non-2xx response, redirects included, is a failure.
+pub(crate) async fn post(
+    url: String,
+    addresses: Vec<SocketAddr>,
+    body: String,
+    headers: Vec<(&'static str, String)>,
+) -> Result<(), String> {
+    let sent = tokio::task::spawn_blocking(move || {
+        let mut request = pinned_agent(addresses)
+            .post(&url)
+            .header("content-type", "application/json");
+        for (name, value) in &headers {
+            request = request.header(*name, value);
+        }
+        match request.send(body) {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => Err(format!(
+                "webhook delivery failed: answered {}",
+                response.status()
+            )),
+            Err(e) => Err(format!("webhook delivery failed: {}", e)),
+        }
+    })
+    .await;
+    sent.unwrap_or_else(|e| Err(format!("task join error: {}", e)))
+}
+
+/// An HTTP agent that connects only to `addresses`, whatever the URL's host
+/// resolves to by then, and neither follows redirects nor uses a proxy.
+pub(crate) fn pinned_agent(addresses: Vec<SocketAddr>) -> ureq::Agent {
+    let config = ureq::Agent::config_builder()
+        .max_redirects(0)
+        .proxy(None)
+        .build();
+    ureq::Agent::with_parts(
+        config,
+        ureq::unversioned::transport::DefaultConnector::new(),
+        PinnedResolver(addresses),
+    )
+}
+
+/// Resolves every host to the addresses vetted for it.
+#[derive(Debug)]
+struct PinnedResolver(Vec<SocketAddr>);
+
+impl ureq::unversioned::resolver::Resolver for PinnedResolver {
+    fn resolve(
+        &self,
+        _uri: &Uri,
+        _config: &ureq::config::Config,
+        _timeout: ureq::unversioned::transport::NextTimeout,
+    ) -> Result<ureq::unversioned::resolver::ResolvedSocketAddrs, ureq::Error> {
+        // The resolver's result holds at most 16 addresses
+        let mut resolved = self.empty();
+        for address in self.0.iter().take(16) {
+            resolved.push(*address);
+        }
+        if resolved.is_empty() {
+            return Err(ureq::Error::HostNotFound);
+        }
+        Ok(resolved)
+    }
+}
+
+/// Hex HMAC-SHA256 (RFC 2104) of `message` under `key`.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// Whether `ip` is on the server's own network: loopback, private, link-local
+/// (which holds the cloud metadata service), shared (RFC 6598), unspecified or
+/// broadcast, or an IPv6 unique-local or IPv4-mapped form of one of those.
+fn is_internal(ip: IpAddr) -> bool {
+    fn v4(ip: Ipv4Addr) -> bool {
+        let [a, b, ..] = ip.octets();
+        ip.is_loopback()
+            || ip.is_private()
+            || ip.is_link_local()
+            || ip.is_unspecified()
+            || ip.is_broadcast()
+            || (a == 100 && (b & 0xc0) == 64)
+    }
+    match ip {
+        IpAddr::V4(ip) => v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => v4(mapped),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local()
+            }
+        },
+    }
+}
+
+/// A random 256-bit secret, hex encoded.
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// POST /webhooks
+///
+/// Input: { "url": "<url>", "events": [...], "contract_id": "...",
+///          "secret": "..." }
+///
+/// Only `url` is required: `events` defaults to every event, `contract_id`
+/// to all of the tenant's contracts, and `secret` to a generated one. The
+/// response is the only place the secret is returned. Requires
+/// authentication.
+pub(crate) async fn handle_create_webhook(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+    Payload(parsed): Payload,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "webhooks require authentication: set TENOR_API_KEY or configure tenants",
+        )
+        .into_response();
+    }
+    let url = match parsed.get("url").and_then(|v| v.as_str()) {
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => url.to_string(),
+        Some(url) => {
+            return json_error(
+                StatusCode::BAD_REQUEST,
+                &format!("webhook URL '{}' must be http or https", url),
+            )
+            .into_response()
+        }
+        None => return json_error(StatusCode::BAD_REQUEST, "missing 'url' field").into_response(),
+    };
+    if let Err(e) = tenant.webhooks.check_target(&url).await {
+        return json_error(StatusCode::BAD_REQUEST, &e).into_response();
+    }
+    let events: Vec<String> = match parsed.get("events") {
+        None => EVENTS.iter().map(|e| e.to_string()).collect(),
+        Some(serde_json::Value::Array(events)) if !events.is_empty() => {
+            let mut names = Vec::new();
+            for event in events {
+                match event.as_str() {
+                    Some(name) if EVENTS.contains(&name) => names.push(name.to_string()),
+                    _ => {
+                        return json_error(
+                            StatusCode::BAD_REQUEST,
+                            &format!(
+                                "unknown event {}: expected one of {}",
+                                event,
+                                EVENTS.join(", ")
+                            ),
+                        )
+                        .into_response()
+                    }
+                }
+            }
+            names
+        }
+        Some(_) => {
+            return json_error(
+                StatusCode::BAD_REQUEST,
+                "'events' must be a non-empty array of event names",
+            )
+            .into_response()
+        }
+    };
+    let contract_id = parsed
+        .get("contract_id")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    if let Some(id) = &contract_id {
+        if tenant.contracts.get(id).is_none() {
+            return json_error(
+                StatusCode::NOT_FOUND,
+                &format!("contract '{}' not found", id),
+            )
+            .into_response();
+        }
+    }
+    let secret = match parsed.get("secret").and_then(|v| v.as_str()) {
+        Some(secret) if !secret.is_empty() => secret.to_string(),
+        _ => generate_secret(),
+    };
+
+    let webhook = Webhook {
+        url,
+        secret: secret.clone(),
+        events,
+        contract_id,
+        delivered: 0,
+        failed: 0,
+        last_delivery: None,
+    };
+    let id = format!(
+        "webhook-{}",
+        tenant.webhooks.next_id.fetch_add(1, Ordering::Relaxed) + 1
+    );
+    let mut body = webhook.describe(&id);
+    body["secret"] = serde_json::json!(secret);
+    tenant.webhooks.lock().insert(id, webhook);
+    (StatusCode::CREATED, Json(body)).into_response()
+}
+
+/// GET /webhooks
+///
+/// The tenant's webhooks with their delivery counts and last delivery.
+/// Requires authentication.
+pub(crate) async fn handle_list_webhooks(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "webhooks require authentication: set TENOR_API_KEY or configure tenants",
+        )
+        .into_response();
+    }
+    let webhooks = tenant.webhooks.lock();
+    let mut ids: Vec<&String> = webhooks.keys().collect();
+    ids.sort_by_key(|id| {
+        id.trim_start_matches("webhook-")
+            .parse::<u64>()
+            .unwrap_or(0)
+    });
+    let list: Vec<serde_json::Value> = ids.iter().map(|id| webhooks[*id].describe(id)).collect();
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "webhooks": list })),
+    )
+        .into_response()
+}
+
+/// DELETE /webhooks/{id}
+pub(crate) async fn handle_delete_webhook(
+    Extension(tenant): Extension<Arc<Tenant>>,
+    Extension(authenticated): Extension<Authenticated>,
+    Path(id): Path<String>,
+) -> Response {
+    if !authenticated.0 {
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "webhooks require authentication: set TENOR_API_KEY or configure tenants",
+        )
+        .into_response();
+    }
+    match tenant.webhooks.lock().remove(&id) {
+        Some(_) => (StatusCode::OK, Json(serde_json::json!({ "removed": id }))).into_response(),
+        None => json_error(
+            StatusCode::NOT_FOUND,
+            &format!("webhook '{}' not found", id),
+        )
+        .into_response(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_matches_rfc_4231_vectors() {
+        // Test case 2.
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6: a key longer than the block size is hashed first.
+        assert_eq!(
+            hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[tokio::test]
+    async fn internal_targets_are_refused_unless_allowed() {
+        let webhooks = Webhooks::new();
+        for url in [
+            "http://127.0.0.1:9000/hook",
+            "http://localhost/hook",
+            "http://10.1.2.3/hook",
+            "http://192.168.0.10/hook",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://100.100.100.200/",
+            "http://[::1]/hook",
+            "http://[fd00:ec2::254]/",
+            "http://[::ffff:10.0.0.1]/hook",
+        ] {
+            let refused = webhooks.check_target(url).await;
+            assert!(refused.is_err(), "{} was allowed", url);
+        }
+        assert!(webhooks
+            .check_target("https://93.184.216.34/hook")
+            .await
+            .is_ok());
+
+        webhooks.allow_private_targets();
+        assert!(webhooks
+            .check_target("http://127.0.0.1:9000/hook")
+            .await
+            .is_ok());
+    }
+
+    /// Answer one HTTP request on a local port with `response`; the
+    /// receiver gets the request's headers once it has arrived.
+    pub(crate) fn serve_once(response: String) -> (SocketAddr, std::sync::mpsc::Receiver<String>) {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (reached, heard) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 1024];
+            // Read the headers and the body they announce
+            loop {
+                let n = stream.read(&mut chunk).unwrap();
+                request.extend_from_slice(&chunk[..n]);
+                let text = String::from_utf8_lossy(&request).to_lowercase();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text
+                        .lines()
+                        .find_map(|l| l.strip_prefix("content-length:"))
+                        .map_or(0, |v| v.trim().parse().unwrap());
+                    if n == 0 || request.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+            }
+            let text = String::from_utf8_lossy(&request);
+            let headers = text.split("\r\n\r\n").next().unwrap_or_default();
+            reached.send(headers.to_string()).ok();
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        (address, heard)
+    }
+
+    #[tokio::test]
+    async fn deliveries_go_to_vetted_addresses_and_never_follow_redirects() {
+        let (internal, internal_reached) =
+            serve_once("HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n".to_string());
+        let (hook, hook_reached) = serve_once(format!(
+            "HTTP/1.1 302 Found\r\nlocation: http://{}/\r\ncontent-length: 0\r\n\r\n",
+            internal
+        ));
+
+        // The host does not resolve: only the vetted address is used
+        let sent = post(
+            "http://hook.invalid/".to_string(),
+            vec![hook],
+            "{}".to_string(),
+            Vec::new(),
+        )
+        .await;
+
+        assert!(hook_reached.try_recv().is_ok());
+        assert!(sent.unwrap_err().contains("302"));
+        assert!(internal_reached.try_recv().is_err());
+    }
+
+    #[test]
+    fn webhooks_filter_by_event_and_contract() {
+        let webhook = Webhook {
+            url: "http://localhost/hook".to_string(),
+            secret: generate_secret(),
+            events: vec![VERDICTS_CHANGED.to_string()],
+            contract_id: Some("escrow".to_string()),
+            delivered: 0,
+            failed: 0,
+            last_delivery: None,
+        };
+        assert!(webhook.wants(VERDICTS_CHANGED, "escrow"));
+        assert!(!webhook.wants(VERDICTS_CHANGED, "loan"));
+        assert!(!webhook.wants(ENTITY_STATES_CHANGED, "escrow"));
+        assert_eq!(webhook.secret.len(), 64);
+        assert!(webhook.describe("webhook-1").get("secret").is_none());
+    }
+}
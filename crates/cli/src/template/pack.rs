@@ -143,8 +143,8 @@ fn find_main_tenor<'a>(tenor_files: &'a [PathBuf], tmpl: &TemplateManifest) -> &
 
 /// Elaborate a .tenor file and return its interchange JSON as a pretty-printed string.
 fn elaborate_contract(path: &Path) -> Result<String, String> {
-    let bundle = tenor_core::elaborate::elaborate(path)
-        .map_err(|e| format!("contract does not elaborate: {:?}", e))?;
+    let bundle =
+        crate::elaborate(path).map_err(|e| format!("contract does not elaborate: {:?}", e))?;
 
     serde_json::to_string_pretty(&bundle).map_err(|e| format!("could not serialize bundle: {}", e))
 }
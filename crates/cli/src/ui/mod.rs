@@ -58,7 +58,7 @@ pub(crate) fn cmd_ui(opts: UiOptions<'_>) {
             }
         }
     } else {
-        match tenor_core::elaborate::elaborate(opts.contract) {
+        match crate::elaborate(opts.contract) {
             Ok(b) => b,
             Err(e) => {
                 report_error(
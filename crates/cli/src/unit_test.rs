@@ -0,0 +1,124 @@
+//! Runner for contract-level `test` constructs (`tenor test --unit`).
+//!
+//! Each `Test` construct in a bundle carries its given facts in the plain
+//! facts-file shape, so running one is a single rules-only evaluation
+//! followed by a presence check per expected verdict.
+
+use serde_json::Value;
+use tenor_interchange::{InterchangeConstruct, TestConstruct};
+
+/// Outcome of one `Test` construct.
+pub struct UnitOutcome {
+    pub id: String,
+    /// `None` when every expectation held
+    pub failure: Option<String>,
+}
+
+/// Run every `Test` construct in `bundle`, in bundle order.
+///
+/// Fails only when the bundle itself cannot be loaded; evaluation errors
+/// inside a test are reported as that test's failure.
+pub fn run_unit_tests(bundle: &Value) -> Result<Vec<UnitOutcome>, String> {
+    let contract = tenor_eval::Contract::from_interchange(bundle).map_err(|e| e.to_string())?;
+    let parsed = tenor_interchange::from_interchange(bundle).map_err(|e| e.to_string())?;
+
+    let mut outcomes = Vec::new();
+    for c in &parsed.constructs {
+        let InterchangeConstruct::Test(test) = c else {
+            continue;
+        };
+        let facts = Value::Object(test.given.clone());
+        let failure = match tenor_eval::evaluate_contract(&contract, &facts) {
+            Ok(result) => check_expectations(test, &result.verdicts),
+            Err(e) => Some(format!("evaluation error: {}", e)),
+        };
+        outcomes.push(UnitOutcome {
+            id: test.id.clone(),
+            failure,
+        });
+    }
+    Ok(outcomes)
+}
+
+fn check_expectations(test: &TestConstruct, verdicts: &tenor_eval::VerdictSet) -> Option<String> {
+    let failures: Vec<String> = test
+        .expect
+        .iter()
+        .filter(|exp| verdicts.has_verdict(&exp.verdict) != exp.present)
+        .map(|exp| {
+            let (want, got) = if exp.present {
+                ("present", "absent")
+            } else {
+                ("absent", "present")
+            };
+            format!(
+                "expected verdict '{}' {}, but it was {}",
+                exp.verdict, want, got
+            )
+        })
+        .collect();
+    if failures.is_empty() {
+        None
+    } else {
+        Some(failures.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> Value {
+        tenor_core::elaborate::elaborate_with_provider(
+            std::path::Path::new("limits.tenor"),
+            &tenor_core::InMemoryProvider::new(
+                [(
+                    std::path::PathBuf::from("limits.tenor"),
+                    r#"
+fact balance {
+  type:    Int(min: 0, max: 10000)
+  source:  "ledger.balance"
+}
+
+fact limit {
+  type:    Int(min: 0, max: 10000)
+  source:  "ledger.limit"
+  default: 1000
+}
+
+rule within {
+  stratum: 0
+  when:    balance <= limit
+  produce: verdict within_limit { payload: Bool = true }
+}
+
+test small_balance_is_within_limit {
+  given facts { balance: 500 }
+  expect verdict within_limit present
+}
+
+test wrong_expectation {
+  given facts { balance: 5000 }
+  expect verdict within_limit present
+}
+"#
+                    .to_string(),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+        )
+        .expect("elaborates")
+    }
+
+    #[test]
+    fn runs_tests_and_reports_failed_expectations() {
+        let outcomes = run_unit_tests(&bundle()).unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].id, "small_balance_is_within_limit");
+        assert!(outcomes[0].failure.is_none());
+        assert_eq!(outcomes[1].id, "wrong_expectation");
+        let failure = outcomes[1].failure.as_deref().unwrap();
+        assert!(failure.contains("'within_limit' present"), "{}", failure);
+    }
+}
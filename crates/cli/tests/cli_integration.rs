@@ -12,7 +12,6 @@ use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use ed25519_dalek::SigningKey;
 use predicates::prelude::*;
 use rand::rngs::OsRng;
-use serde_json;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
@@ -90,6 +89,70 @@ fn elaborate_nonexistent_file_exits_1() {
         .code(1);
 }
 
+#[test]
+fn elaborate_features_select_constructs_and_are_recorded_in_manifest() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("regional.tenor");
+    fs::write(
+        &path,
+        "fact is_active {\n  type:   Bool\n  source: \"svc.active\"\n}\n\n\
+         when feature(\"eu_rules\") rule eu_active {\n  stratum: 0\n  \
+         when:    is_active = true\n  produce: verdict eu_active { payload: Bool = true }\n}\n",
+    )
+    .unwrap();
+    let manifest = |features: &[&str]| -> serde_json::Value {
+        let mut args = vec!["elaborate", "--manifest", path.to_str().unwrap()];
+        args.extend(features);
+        let output = tenor().args(&args).output().unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    let base = manifest(&[]);
+    assert_eq!(base["bundle"]["constructs"].as_array().unwrap().len(), 1);
+    assert!(base.get("features").is_none());
+
+    let eu = manifest(&["--features", "eu_rules,beta"]);
+    assert_eq!(eu["bundle"]["constructs"][1]["id"], "eu_active");
+    assert_eq!(eu["features"], serde_json::json!(["beta", "eu_rules"]));
+}
+
+#[test]
+fn elaborate_source_map_points_at_rule_text() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("active.tenor");
+    let source = "fact is_active {\n  type:   Bool\n  source: \"svc.active\"\n}\n\n\
+                  rule active {\n  stratum: 0\n  when:    is_active = true\n  \
+                  produce: verdict active { payload: Bool = true }\n}\n";
+    fs::write(&path, source).unwrap();
+    let map_path = tmp.path().join("active.map.json");
+
+    let output = tenor()
+        .args(["elaborate", path.to_str().unwrap(), "--source-map"])
+        .arg(&map_path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let bundle: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(bundle["constructs"][1]["id"], "active");
+
+    let map: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&map_path).unwrap()).unwrap();
+    let text = |pointer: &str| {
+        let m = map["mappings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|m| m["pointer"] == pointer)
+            .unwrap_or_else(|| panic!("no mapping for {}", pointer));
+        let (start, end) = (m["start"].as_u64().unwrap(), m["end"].as_u64().unwrap());
+        &source[start as usize..end as usize]
+    };
+    assert_eq!(map["bundle"], "active");
+    assert!(text("/constructs/1").starts_with("rule active {"));
+    assert_eq!(text("/constructs/1/body/when"), "is_active = true");
+}
+
 #[test]
 fn elaborate_negative_fixture_exits_1() {
     // A file that exists but has elaboration errors
@@ -151,6 +214,170 @@ fn test_nonexistent_dir_exits_1() {
         .stderr(predicate::str::contains("not found"));
 }
 
+#[test]
+fn test_conformance_filter_runs_matching_cases() {
+    tenor()
+        .args(["test", "conformance", "--filter", "negative/pass3/*"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ok 1 - negative/pass3/"))
+        .stdout(predicate::str::contains("positive/").not());
+}
+
+#[test]
+fn test_conformance_filter_without_matches_exits_1() {
+    tenor()
+        .args(["test", "conformance", "--filter", "no_such_case_*"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("no conformance cases match"));
+}
+
+#[test]
+fn test_conformance_json_report_includes_mismatch() {
+    let tmp = TempDir::new().unwrap();
+    let positive = tmp.path().join("positive");
+    fs::create_dir(&positive).unwrap();
+    fs::write(
+        positive.join("flag.tenor"),
+        "fact flag {\n  type:   Bool\n  source: \"svc.flag\"\n}\n",
+    )
+    .unwrap();
+    fs::write(positive.join("flag.expected.json"), r#"{"kind": "Bundle"}"#).unwrap();
+
+    let output = tenor()
+        .args(["--output", "json", "test", tmp.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["passed"], 0);
+    assert_eq!(report["failed"], 1);
+    let case = &report["tests"][0];
+    assert_eq!(case["name"], "positive/flag");
+    assert_eq!(case["category"], "positive");
+    assert_eq!(case["passed"], false);
+    assert_eq!(case["message"], "output mismatch");
+    assert_eq!(case["expected"], serde_json::json!({"kind": "Bundle"}));
+    assert_eq!(case["actual"]["constructs"][0]["id"], "flag");
+    assert!(case["duration_ms"].is_number());
+}
+
+#[test]
+fn test_unit_runs_contract_test_blocks() {
+    tenor()
+        .args([
+            "test",
+            "--unit",
+            "conformance/positive/test_construct.tenor",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ok 1 - inactive_account"))
+        .stdout(predicate::str::contains("ok 2 - over_limit_is_rejected"));
+}
+
+#[test]
+fn test_unit_reports_failed_expectation() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("limits.tenor");
+    fs::write(
+        &path,
+        r#"
+fact is_active {
+  type:   Bool
+  source: "account_service.active"
+}
+
+rule account_active {
+  stratum: 0
+  when:    is_active = true
+  produce: verdict account_active { payload: Bool = true }
+}
+
+test inactive_account_is_active {
+  given facts { is_active: false }
+  expect verdict account_active present
+}
+"#,
+    )
+    .unwrap();
+
+    tenor()
+        .args(["test", "--unit", path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "not ok 1 - inactive_account_is_active",
+        ))
+        .stdout(predicate::str::contains(
+            "expected verdict 'account_active' present, but it was absent",
+        ));
+}
+
+#[test]
+fn conformance_url_reports_unreachable_executor() {
+    tenor()
+        .args(["conformance", "--url", "http://127.0.0.1:9"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("1..20"))
+        .stdout(predicate::str::contains("not ok 1 - E1: fact source"))
+        .stdout(predicate::str::contains("not ok 20 - E20: trust domain"));
+}
+
+#[test]
+fn conformance_json_report_lists_every_obligation() {
+    let output = tenor()
+        .args([
+            "conformance",
+            "--url",
+            "http://127.0.0.1:9",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["executor"], "http://127.0.0.1:9");
+    assert_eq!(report["failed"], 20);
+    let tests = report["tests"].as_array().unwrap();
+    assert_eq!(tests.len(), 20);
+    assert_eq!(tests[0]["obligation"], "E1");
+    assert_eq!(tests[0]["section"], "§17.2");
+    assert_eq!(tests[9]["section"], "§19.7");
+    assert_eq!(tests[0]["passed"], false);
+    assert!(tests[0]["message"].as_str().unwrap().contains("E1"));
+}
+
+#[test]
+fn conformance_junit_report_has_a_testcase_per_obligation() {
+    tenor()
+        .args([
+            "conformance",
+            "--url",
+            "http://127.0.0.1:9",
+            "--format",
+            "junit",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::starts_with("<?xml"))
+        .stdout(predicate::str::contains(
+            "<testsuites name=\"tenor-executor-conformance\" tests=\"20\" failures=\"20\"",
+        ))
+        .stdout(predicate::str::contains(
+            "<testcase classname=\"§17.4\" name=\"E20: trust domain\"",
+        ))
+        .stdout(predicate::str::contains("<failure message="));
+}
+
 // ──────────────────────────────────────────────
 // 5. Eval subcommand
 // ──────────────────────────────────────────────
@@ -185,6 +412,268 @@ fn eval_json_output_contains_verdicts() {
         .stdout(predicate::str::contains("\"verdicts\""));
 }
 
+#[test]
+fn eval_profile_prints_hot_spot_table() {
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+            "--profile",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("verdict"))
+        .stderr(predicate::str::contains(
+            "Profile (hot spots by total time):",
+        ))
+        .stderr(predicate::str::contains("rule  check_active"))
+        .stderr(predicate::str::contains("wall time:"));
+}
+
+#[test]
+fn eval_trace_prints_predicate_values() {
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+            "--trace",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "rule check_active (stratum 0): fired [active_confirmed]",
+        ))
+        .stdout(predicate::str::contains("  is_active = true => true"))
+        .stdout(predicate::str::contains("    is_active => true"));
+
+    let output = tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+            "--trace",
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["traces"][0]["rule"], "check_active");
+    assert_eq!(
+        json["traces"][0]["condition"]["children"][0]["expression"],
+        "is_active"
+    );
+}
+
+#[test]
+fn eval_compare_facts_reports_verdict_changes() {
+    let tmp = TempDir::new().unwrap();
+    let inactive = tmp.path().join("inactive.facts.json");
+    std::fs::write(&inactive, r#"{ "is_active": false }"#).unwrap();
+
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--compare-facts",
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+            inactive.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is_active: true -> false"))
+        .stdout(predicate::str::contains(
+            "- [active_confirmed] true (rule: check_active, stratum: 0, reads is_active)",
+        ));
+
+    let output = tenor()
+        .args([
+            "--output",
+            "json",
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--compare-facts",
+            inactive.to_str().unwrap(),
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["changed_facts"][0]["fact"], "is_active");
+    assert_eq!(json["verdicts"]["added"][0]["type"], "active_confirmed");
+    assert_eq!(json["verdicts"]["added"][0]["rule"], "check_active");
+    assert_eq!(json["verdicts"]["removed"], serde_json::json!([]));
+}
+
+#[test]
+fn eval_reads_facts_from_stdin() {
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            "-",
+        ])
+        .write_stdin(r#"{ "is_active": true }"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("active_confirmed"));
+}
+
+#[test]
+fn eval_merges_facts_files_and_inline_overrides_in_order() {
+    let tmp = TempDir::new().unwrap();
+    let inactive = tmp.path().join("inactive.facts.json");
+    std::fs::write(&inactive, r#"{ "is_active": false }"#).unwrap();
+    let verdicts = |args: &[&str]| -> serde_json::Value {
+        let output = tenor()
+            .args([
+                "--output",
+                "json",
+                "eval",
+                "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            ])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()["verdicts"].clone()
+    };
+
+    // The later file wins.
+    let base = "crates/cli/tests/fixtures/eval_basic.facts.json";
+    let inactive = inactive.to_str().unwrap();
+    assert_eq!(
+        verdicts(&["--facts", base, "--facts", inactive]),
+        serde_json::json!([])
+    );
+    assert_eq!(
+        verdicts(&["--facts", inactive, "--facts", base])[0]["type"],
+        "active_confirmed"
+    );
+    // --fact applies after every file, coerced to the declared Bool.
+    assert_eq!(
+        verdicts(&["--facts", base, "--fact", "is_active=false"]),
+        serde_json::json!([])
+    );
+    assert_eq!(
+        verdicts(&["--fact", "is_active=true"])[0]["type"],
+        "active_confirmed"
+    );
+}
+
+#[test]
+fn eval_rejects_bad_inline_facts() {
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--fact",
+            "is_active=maybe",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "--fact is_active: 'maybe' is not a valid Bool",
+        ));
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--fact",
+            "unknown=1",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("no such fact"));
+}
+
+#[test]
+fn eval_checks_inline_decimals_against_declared_precision_and_scale() {
+    let tmp = TempDir::new().unwrap();
+    let read = |path: &str| fs::read_to_string(workspace_root().join(path)).unwrap();
+    let decimal = elaborate_to(
+        tmp.path(),
+        "decimal",
+        &read("conformance/eval/numeric/decimal_precision_loss.tenor"),
+    );
+    let money = elaborate_to(
+        tmp.path(),
+        "money",
+        &read("conformance/eval/numeric/money_comparison.tenor"),
+    );
+    let eval = |bundle: &Path, facts: &[&str]| {
+        let mut cmd = tenor();
+        cmd.args(["eval", bundle.to_str().unwrap()]);
+        for fact in facts {
+            cmd.args(["--fact", fact]);
+        }
+        cmd.assert()
+    };
+
+    eval(&decimal, &["amount=42.5"]).success();
+    // Decimal(10, 2) without a rounding mode: a third decimal place, a
+    // float exponent and an integer part beyond 8 digits are all refused.
+    for raw in ["42.505", "4.25e1", "123456789", "NaN"] {
+        eval(&decimal, &[&format!("amount={}", raw)])
+            .failure()
+            .code(1)
+            .stderr(predicate::str::contains(format!(
+                "--fact amount: '{}' is not a valid Decimal (a decimal number of precision 10 and scale 2)",
+                raw
+            )));
+    }
+    eval(&money, &["payment_amount=12.34 USD", "payment_limit=50"]).success();
+    eval(&money, &["payment_amount=12.345 USD", "payment_limit=50"])
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "the amount a decimal number of precision 10 and scale 2",
+        ));
+}
+
+#[test]
+fn eval_validate_facts_reports_every_error() {
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+            "--validate-facts",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("facts are valid"));
+
+    let output = tenor()
+        .args([
+            "--output",
+            "json",
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            "-",
+            "--validate-facts",
+        ])
+        .write_stdin(r#"{ "is_active": "yes" }"#)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["errors"][0]["pointer"], "/is_active");
+    assert_eq!(json["errors"][0]["code"], "TEN-E-0101");
+}
+
 #[test]
 fn eval_nonexistent_bundle_exits_1() {
     tenor()
@@ -245,85 +734,961 @@ fn eval_invalid_json_bundle_exits_1() {
         .stderr(predicate::str::contains("invalid JSON"));
 }
 
-// ──────────────────────────────────────────────
-// 6. Diff subcommand
-// ──────────────────────────────────────────────
+#[test]
+fn eval_flow_export_trace_writes_otlp_json() {
+    let tmp = TempDir::new().unwrap();
+    let bundle_path = tmp.path().join("bundle.json");
+    let facts_path = tmp.path().join("facts.json");
+    let trace_path = tmp.path().join("trace.json");
+
+    let output = tenor()
+        .args(["elaborate", "conformance/eval/positive/parallel_step.tenor"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    fs::write(&bundle_path, &output.stdout).unwrap();
+    fs::write(&facts_path, r#"{ "is_ready": true }"#).unwrap();
+
+    tenor()
+        .args([
+            "eval",
+            bundle_path.to_str().unwrap(),
+            "--facts",
+            facts_path.to_str().unwrap(),
+            "--flow",
+            "parallel_process",
+            "--persona",
+            "system",
+            "--export-trace",
+            trace_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let trace: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&trace_path).unwrap()).unwrap();
+    let spans = trace["resourceSpans"][0]["scopeSpans"][0]["spans"]
+        .as_array()
+        .unwrap();
+    let names: Vec<&str> = spans.iter().map(|s| s["name"].as_str().unwrap()).collect();
+    assert!(names.contains(&"flow parallel_process"));
+    assert!(names.contains(&"branch branch_payment"));
+    assert!(names.contains(&"step step_payment"));
+}
+
+#[test]
+fn eval_as_of_reports_historical_action_space() {
+    let tmp = TempDir::new().unwrap();
+    let facts = tmp.path().join("facts.json");
+    let history = tmp.path().join("history.json");
+    fs::write(&facts, r#"{ "is_active": true, "requires_review": false }"#).unwrap();
+    fs::write(
+        &history,
+        r#"{
+  "entity_states": [
+    { "entity_id": "Order", "instance_id": "_default", "state": "submitted", "version": 1,
+      "updated_at": "2025-03-05T09:00:00Z", "last_flow_id": "order_approval",
+      "last_operation_id": "submit_order" }
+  ],
+  "transitions": [
+    { "id": "t1", "operation_execution_id": "op1", "entity_id": "Order",
+      "instance_id": "_default", "from_state": "draft", "to_state": "submitted",
+      "from_version": 0, "to_version": 1, "executed_at": "2025-03-05T09:00:00Z" }
+  ]
+}"#,
+    )
+    .unwrap();
+
+    let action_space = |as_of: &str| -> serde_json::Value {
+        let output = tenor()
+            .args([
+                "eval",
+                "conformance/positive/flow_basic.expected.json",
+                "--facts",
+                facts.to_str().unwrap(),
+                "--persona",
+                "buyer",
+                "--as-of",
+                as_of,
+                "--history",
+                history.to_str().unwrap(),
+                "--output",
+                "json",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    // Before submission the Order was a draft, so the buyer could start the flow.
+    let before = action_space("2025-03-03T00:00:00Z");
+    assert_eq!(before["as_of"], "2025-03-03T00:00:00Z");
+    assert_eq!(before["actions"][0]["flow_id"], "order_approval");
+
+    let after = action_space("2025-03-06T00:00:00Z");
+    assert_eq!(after["actions"], serde_json::json!([]));
+    assert_eq!(after["blocked_actions"][0]["flow_id"], "order_approval");
+}
+
+#[test]
+fn eval_as_of_requires_history() {
+    tenor()
+        .args([
+            "eval",
+            "conformance/positive/flow_basic.expected.json",
+            "--facts",
+            "facts.json",
+            "--as-of",
+            "2025-03-03T00:00:00Z",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--history"));
+}
+
+#[test]
+fn eval_now_pins_step_deadlines() {
+    let tmp = TempDir::new().unwrap();
+    let source = tmp.path().join("timed.tenor");
+    fs::write(
+        &source,
+        r#"
+fact review_by {
+  type:   DateTime
+  source: "x.review_by"
+}
+
+fact ready {
+  type:   Bool
+  source: "x.ready"
+}
+
+rule is_ready {
+  stratum: 0
+  when:    ready = true
+  produce: verdict is_ready { payload: Bool = true }
+}
+
+entity Order {
+  states:  [draft, submitted]
+  initial: draft
+  transitions: [(draft, submitted)]
+}
+
+operation submit {
+  allowed_personas: [user]
+  precondition:     verdict_present(is_ready)
+  effects:          [(Order, draft, submitted)]
+  error_contract:   [precondition_failed, persona_rejected]
+}
+
+flow timed_flow {
+  snapshot: at_initiation
+  entry:    step_one
+
+  steps: {
+    step_one: OperationStep {
+      op:      submit
+      persona: user
+      outcomes: {
+        success: Terminal(success)
+      }
+      on_failure: Terminate(outcome: failure)
+      timeout: Timeout(
+        deadline:   review_by
+        on_timeout: Terminal(expired)
+      )
+    }
+  }
+}
+"#,
+    )
+    .unwrap();
+    let elaborated = tenor()
+        .args(["elaborate", source.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(elaborated.status.success());
+    let bundle = tmp.path().join("timed.json");
+    fs::write(&bundle, &elaborated.stdout).unwrap();
+    let facts = tmp.path().join("facts.json");
+    fs::write(
+        &facts,
+        r#"{ "ready": true, "review_by": "2030-06-01T00:00:00Z" }"#,
+    )
+    .unwrap();
+
+    let outcome = |now: &str| -> serde_json::Value {
+        let output = tenor()
+            .args([
+                "eval",
+                bundle.to_str().unwrap(),
+                "--facts",
+                facts.to_str().unwrap(),
+                "--flow",
+                "timed_flow",
+                "--persona",
+                "user",
+                "--now",
+                now,
+                "--output",
+                "json",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()["outcome"].clone()
+    };
+    assert_eq!(outcome("2030-05-31T23:59:59Z"), "success");
+    assert_eq!(outcome("2030-06-01T00:00:00Z"), "expired");
+
+    tenor()
+        .args(["eval", "x.json", "--facts", "f.json", "--now", "yesterday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected RFC 3339"));
+}
+
+#[test]
+fn simulate_random_walk_is_deterministic_per_seed() {
+    let run = |seed: &str| -> serde_json::Value {
+        let output = tenor()
+            .args([
+                "simulate",
+                "conformance/positive/flow_basic.expected.json",
+                "--random-walk",
+                "--seed",
+                seed,
+                "--count",
+                "40",
+                "--output",
+                "json",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    let report = run("42");
+    assert_eq!(report, run("42"));
+
+    let flow = &report["flows"][0];
+    assert_eq!(flow["flow_id"], "order_approval");
+    assert_eq!(flow["persona"], "buyer");
+    let sampled: u64 = flow["outcomes"]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|n| n.as_u64().unwrap())
+        .sum();
+    assert_eq!(
+        sampled + flow["inadmissible"].as_u64().unwrap() + flow["errors"].as_u64().unwrap(),
+        40
+    );
+}
+
+#[test]
+fn simulate_unknown_flow_fails() {
+    tenor()
+        .args([
+            "simulate",
+            "conformance/positive/flow_basic.expected.json",
+            "--random-walk",
+            "--flow",
+            "no_such_flow",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no_such_flow"));
+}
+
+#[test]
+fn simulate_flow_uses_entity_states_and_bindings() {
+    let tmp = TempDir::new().unwrap();
+    let facts = tmp.path().join("facts.json");
+    let states = tmp.path().join("states.json");
+    let bindings = tmp.path().join("bindings.json");
+    fs::write(&facts, r#"{"is_active": true, "requires_review": false}"#).unwrap();
+    fs::write(&states, r#"{"Order": {"ord-1": "draft"}}"#).unwrap();
+    fs::write(&bindings, r#"{"Order": "ord-1"}"#).unwrap();
+
+    let output = tenor()
+        .args(["--output", "json", "simulate"])
+        .arg("conformance/positive/flow_basic.expected.json")
+        .args(["--flow", "order_approval", "--persona", "buyer", "--facts"])
+        .arg(&facts)
+        .arg("--entity-states")
+        .arg(&states)
+        .arg("--bindings")
+        .arg(&bindings)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["simulation"], true);
+    assert_eq!(report["outcome"], "success");
+    assert_eq!(report["path"][0]["instance_bindings"]["Order"], "ord-1");
+    assert_eq!(
+        report["would_transition"][0],
+        serde_json::json!({
+            "entity_id": "Order",
+            "instance_id": "ord-1",
+            "from_state": "draft",
+            "to_state": "submitted",
+        })
+    );
+}
+
+#[test]
+fn simulate_flow_requires_persona() {
+    tenor()
+        .args([
+            "simulate",
+            "conformance/positive/flow_basic.expected.json",
+            "--flow",
+            "order_approval",
+            "--facts",
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--persona"));
+}
+
+#[test]
+fn actions_lists_available_and_blocked_flows() {
+    let tmp = TempDir::new().unwrap();
+    let facts = tmp.path().join("facts.json");
+    fs::write(&facts, r#"{"is_active": true, "requires_review": false}"#).unwrap();
+
+    let output = tenor()
+        .args(["--output", "json", "actions"])
+        .arg("conformance/positive/flow_basic.expected.json")
+        .args(["--persona", "buyer", "--facts"])
+        .arg(&facts)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let space: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(space["persona_id"], "buyer");
+    assert_eq!(space["actions"][0]["flow_id"], "order_approval");
+    assert_eq!(space["actions"][0]["entry_operation_id"], "submit_order");
+
+    let states = tmp.path().join("states.json");
+    fs::write(&states, r#"{"Order": "submitted"}"#).unwrap();
+    tenor()
+        .args(["actions", "conformance/positive/flow_basic.expected.json"])
+        .args(["--persona", "buyer", "--facts"])
+        .arg(&facts)
+        .arg("--entity-states")
+        .arg(&states)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no actions available"))
+        .stdout(predicate::str::contains(
+            "order_approval: Order is in 'submitted', requires 'draft'",
+        ));
+}
+
+#[test]
+fn repl_script_evaluates_predicates() {
+    let tmp = TempDir::new().unwrap();
+    let facts = tmp.path().join("facts.json");
+    fs::write(&facts, r#"{"is_active": true, "requires_review": false}"#).unwrap();
+    let script = tmp.path().join("session.txt");
+    fs::write(
+        &script,
+        "// scripted session\nis_active = true and requires_review = false\n:verdicts\n",
+    )
+    .unwrap();
+
+    tenor()
+        .args([
+            "repl",
+            "conformance/positive/flow_basic.expected.json",
+            "--facts",
+        ])
+        .arg(&facts)
+        .arg("--script")
+        .arg(&script)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("= true"))
+        .stdout(predicate::str::contains("fact is_active = true"))
+        .stdout(predicate::str::contains("fact requires_review = false"));
+
+    fs::write(&script, "no_such_fact = true\n").unwrap();
+    tenor()
+        .args([
+            "repl",
+            "conformance/positive/flow_basic.expected.json",
+            "--facts",
+        ])
+        .arg(&facts)
+        .arg("--script")
+        .arg(&script)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no_such_fact"));
+}
+
+#[test]
+fn solve_finds_facts_for_reachable_outcome() {
+    let output = tenor()
+        .args([
+            "solve",
+            "conformance/positive/flow_basic.expected.json",
+            "--flow",
+            "order_approval",
+            "--outcome",
+            "success",
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["status"], "satisfiable");
+    assert_eq!(result["persona"], "buyer");
+    assert_eq!(result["facts"]["is_active"], true);
+}
+
+#[test]
+fn solve_proves_outcome_unreachable_from_entity_states() {
+    let tmp = TempDir::new().unwrap();
+    let states = tmp.path().join("states.json");
+    std::fs::write(&states, r#"{"Order": "approved"}"#).unwrap();
+
+    tenor()
+        .args([
+            "solve",
+            "conformance/positive/flow_basic.expected.json",
+            "--flow",
+            "order_approval",
+            "--outcome",
+            "success",
+            "--entity-states",
+            states.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unsatisfiable"));
+}
+
+#[test]
+fn eval_partial_reports_verdicts_blocked_on_missing_facts() {
+    let tmp = TempDir::new().unwrap();
+    let facts = tmp.path().join("facts.json");
+    std::fs::write(&facts, "{}").unwrap();
+
+    let output = tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            facts.to_str().unwrap(),
+            "--partial",
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["verdicts"], serde_json::json!([]));
+    assert_eq!(json["missing_facts"], serde_json::json!(["is_active"]));
+    assert_eq!(json["blocked"][0]["verdict_type"], "active_confirmed");
+    assert_eq!(
+        json["blocked"][0]["missing_facts"],
+        serde_json::json!(["is_active"])
+    );
+
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            facts.to_str().unwrap(),
+            "--partial",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[active_confirmed] needs is_active (rule: check_active)",
+        ));
+}
+
+#[test]
+fn eval_export_trace_requires_flow() {
+    tenor()
+        .args([
+            "eval",
+            "crates/cli/tests/fixtures/eval_basic_bundle.json",
+            "--facts",
+            "crates/cli/tests/fixtures/eval_basic.facts.json",
+            "--export-trace",
+            "trace.json",
+        ])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+// ──────────────────────────────────────────────
+// 6. Diff subcommand
+// ──────────────────────────────────────────────
+
+#[test]
+fn diff_identical_files_exits_0() {
+    tenor()
+        .args([
+            "diff",
+            "conformance/positive/fact_basic.expected.json",
+            "conformance/positive/fact_basic.expected.json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no differences"));
+}
+
+#[test]
+fn diff_different_files_exits_1() {
+    tenor()
+        .args([
+            "diff",
+            "conformance/positive/fact_basic.expected.json",
+            "conformance/positive/rule_basic.expected.json",
+        ])
+        .assert()
+        .failure()
+        .code(1);
+}
+
+#[test]
+fn diff_fact_added_shows_addition() {
+    // Create two bundles: one minimal, one with an extra fact
+    let tmp = TempDir::new().unwrap();
+    let bundle1 = serde_json::json!({
+        "kind": "Bundle", "id": "test_contract", "tenor": "1.0",
+        "constructs": [
+            { "kind": "Fact", "id": "amount", "type": { "base": "Int" },
+              "source": { "system": "billing", "field": "amt" },
+              "provenance": { "file": "test.tenor", "line": 1 }, "tenor": "1.0" }
+        ]
+    });
+    let bundle2 = serde_json::json!({
+        "kind": "Bundle", "id": "test_contract", "tenor": "1.0",
+        "constructs": [
+            { "kind": "Fact", "id": "amount", "type": { "base": "Int" },
+              "source": { "system": "billing", "field": "amt" },
+              "provenance": { "file": "test.tenor", "line": 1 }, "tenor": "1.0" },
+            { "kind": "Fact", "id": "is_active", "type": { "base": "Bool" },
+              "source": { "system": "crm", "field": "active" },
+              "provenance": { "file": "test.tenor", "line": 5 }, "tenor": "1.0" }
+        ]
+    });
+
+    let path1 = tmp.path().join("bundle1.json");
+    let path2 = tmp.path().join("bundle2.json");
+    fs::write(&path1, serde_json::to_string_pretty(&bundle1).unwrap()).unwrap();
+    fs::write(&path2, serde_json::to_string_pretty(&bundle2).unwrap()).unwrap();
+
+    // Diff should detect the addition and exit 1
+    tenor()
+        .args(["diff", path1.to_str().unwrap(), path2.to_str().unwrap()])
+        .assert()
+        .failure()
+        .code(1);
+}
+
+#[test]
+fn diff_breaking_non_breaking_changes() {
+    // Identical bundles should exit 0 with --breaking
+    tenor()
+        .args([
+            "diff",
+            "--breaking",
+            "conformance/positive/fact_basic.expected.json",
+            "conformance/positive/fact_basic.expected.json",
+        ])
+        .assert()
+        .success();
+}
+
+/// Elaborate `source` (written to `dir/name`) and return the bundle path.
+fn elaborate_to(dir: &Path, name: &str, source: &str) -> PathBuf {
+    let tenor_path = dir.join(format!("{}.tenor", name));
+    fs::write(&tenor_path, source).unwrap();
+    let output = tenor()
+        .args(["elaborate", tenor_path.to_str().unwrap()])
+        .output()
+        .expect("elaborate failed");
+    assert!(output.status.success());
+    let json_path = dir.join(format!("{}.json", name));
+    fs::write(&json_path, &output.stdout).unwrap();
+    json_path
+}
+
+#[test]
+fn elaborate_emits_handoff_pause_only_when_set() {
+    let tmp = TempDir::new().unwrap();
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/escrow_compliance.tenor"),
+    )
+    .unwrap();
+    let handoff_step = |bundle_path: &Path| -> serde_json::Value {
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(bundle_path).unwrap()).unwrap();
+        bundle["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|c| c["kind"] == "Flow")
+            .flat_map(|f| f["steps"].as_array().unwrap().clone())
+            .find(|s| s["kind"] == "HandoffStep")
+            .expect("handoff step")
+    };
+
+    let plain = elaborate_to(tmp.path(), "plain", &source);
+    assert!(handoff_step(&plain).get("pause").is_none());
+
+    let paused = elaborate_to(
+        tmp.path(),
+        "paused",
+        &source.replace(
+            "next:         step_compliance_release",
+            "next:         step_compliance_release\n      pause:        true",
+        ),
+    );
+    assert_eq!(handoff_step(&paused)["pause"], true);
+}
+
+#[test]
+fn step_timeout_elaborates_and_survives_decompile() {
+    let tmp = TempDir::new().unwrap();
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/escrow_compliance.tenor"),
+    )
+    .unwrap()
+    .replace(
+        "fact buyer_requested_refund {",
+        "fact compliance_deadline {\n  type:   DateTime\n  source: \"compliance_service.deadline\"\n}\n\nfact buyer_requested_refund {",
+    )
+    .replace(
+        "next:         step_compliance_release",
+        "next:         step_compliance_release\n      timeout: Timeout(\n        deadline:   compliance_deadline\n        on_timeout: Terminal(expired)\n      )",
+    );
+    let handoff_timeout = |bundle_path: &Path| -> serde_json::Value {
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(bundle_path).unwrap()).unwrap();
+        bundle["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|c| c["kind"] == "Flow")
+            .flat_map(|f| f["steps"].as_array().unwrap().clone())
+            .find(|s| s["kind"] == "HandoffStep")
+            .expect("handoff step")["timeout"]
+            .clone()
+    };
+
+    let timed = elaborate_to(tmp.path(), "timed", &source);
+    let expected = serde_json::json!({
+        "deadline": "compliance_deadline",
+        "on_timeout": { "kind": "Terminal", "outcome": "expired" }
+    });
+    assert_eq!(handoff_timeout(&timed), expected);
+
+    let decompiled = tmp.path().join("decompiled.tenor");
+    tenor()
+        .args([
+            "decompile",
+            timed.to_str().unwrap(),
+            "--out",
+            decompiled.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let round_trip = elaborate_to(
+        tmp.path(),
+        "round_trip",
+        &fs::read_to_string(&decompiled).unwrap(),
+    );
+    assert_eq!(handoff_timeout(&round_trip), expected);
+}
+
+#[test]
+fn transition_guard_elaborates_and_survives_decompile() {
+    let tmp = TempDir::new().unwrap();
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/transition_guard.tenor"),
+    )
+    .unwrap();
+    let guarded_transition = |bundle_path: &Path| -> serde_json::Value {
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(bundle_path).unwrap()).unwrap();
+        assert_eq!(bundle["requires"], serde_json::json!(["transition-guards"]));
+        bundle["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["kind"] == "Entity")
+            .expect("entity")["transitions"][0]
+            .clone()
+    };
+
+    let guarded = elaborate_to(tmp.path(), "guarded", &source);
+    let expected = serde_json::json!({
+        "from": "draft",
+        "guard": { "verdict_present": "credit_approved" },
+        "to": "submitted"
+    });
+    assert_eq!(guarded_transition(&guarded), expected);
+
+    let decompiled = tmp.path().join("decompiled.tenor");
+    tenor()
+        .args([
+            "decompile",
+            guarded.to_str().unwrap(),
+            "--out",
+            decompiled.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let round_trip = elaborate_to(
+        tmp.path(),
+        "round_trip",
+        &fs::read_to_string(&decompiled).unwrap(),
+    );
+    assert_eq!(guarded_transition(&round_trip), expected);
+}
+
+#[test]
+fn entity_attributes_elaborate_and_survive_decompile() {
+    let tmp = TempDir::new().unwrap();
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/entity_attributes.tenor"),
+    )
+    .unwrap();
+    let attributes_and_set = |bundle_path: &Path| -> (serde_json::Value, serde_json::Value) {
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(bundle_path).unwrap()).unwrap();
+        assert_eq!(bundle["requires"], serde_json::json!(["entity-attributes"]));
+        let constructs = bundle["constructs"].as_array().unwrap();
+        let entity = constructs.iter().find(|c| c["kind"] == "Entity").unwrap();
+        let submit = constructs
+            .iter()
+            .find(|c| c["id"] == "submit_order")
+            .unwrap();
+        (
+            entity["attributes"].clone(),
+            submit["effects"][0]["set"].clone(),
+        )
+    };
+
+    let bundle = elaborate_to(tmp.path(), "attributes", &source);
+    let (attributes, set) = attributes_and_set(&bundle);
+    assert_eq!(attributes["total"]["base"], "Int");
+    assert_eq!(attributes["region"]["base"], "Enum");
+    assert_eq!(
+        set["total"],
+        serde_json::json!({ "fact_ref": "order_total" })
+    );
+    assert_eq!(set["region"]["literal"], "EU");
+
+    let decompiled = tmp.path().join("decompiled.tenor");
+    tenor()
+        .args([
+            "decompile",
+            bundle.to_str().unwrap(),
+            "--out",
+            decompiled.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let round_trip = elaborate_to(
+        tmp.path(),
+        "round_trip",
+        &fs::read_to_string(&decompiled).unwrap(),
+    );
+    assert_eq!(attributes_and_set(&round_trip), (attributes, set));
+}
+
+#[test]
+fn instance_lifecycle_effects_elaborate_and_survive_decompile() {
+    let tmp = TempDir::new().unwrap();
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/instance_lifecycle.tenor"),
+    )
+    .unwrap();
+    let lifecycle_effects = |bundle_path: &Path| -> Vec<serde_json::Value> {
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(bundle_path).unwrap()).unwrap();
+        assert_eq!(
+            bundle["requires"],
+            serde_json::json!(["entity-attributes", "instance-lifecycle"])
+        );
+        let constructs = bundle["constructs"].as_array().unwrap();
+        ["open_ticket", "archive_ticket"]
+            .iter()
+            .map(|id| constructs.iter().find(|c| c["id"] == *id).unwrap()["effects"][0].clone())
+            .collect()
+    };
+
+    let bundle = elaborate_to(tmp.path(), "lifecycle", &source);
+    let effects = lifecycle_effects(&bundle);
+    assert_eq!(effects[0]["kind"], "create_instance");
+    assert!(effects[0].get("from").is_none());
+    assert_eq!(
+        effects[0]["set"]["priority"],
+        serde_json::json!({ "fact_ref": "severity" })
+    );
+    assert_eq!(
+        effects[1],
+        serde_json::json!({
+            "entity_id": "Ticket",
+            "from": "closed",
+            "kind": "destroy_instance"
+        })
+    );
+
+    let decompiled = tmp.path().join("decompiled.tenor");
+    tenor()
+        .args([
+            "decompile",
+            bundle.to_str().unwrap(),
+            "--out",
+            decompiled.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let round_trip = elaborate_to(
+        tmp.path(),
+        "round_trip",
+        &fs::read_to_string(&decompiled).unwrap(),
+    );
+    assert_eq!(lifecycle_effects(&round_trip), effects);
+}
+
+#[test]
+fn instance_quantifiers_elaborate_and_survive_decompile() {
+    let tmp = TempDir::new().unwrap();
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/instance_quantifiers.tenor"),
+    )
+    .unwrap();
+    let quantified = |bundle_path: &Path| -> Vec<serde_json::Value> {
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(bundle_path).unwrap()).unwrap();
+        assert_eq!(
+            bundle["requires"],
+            serde_json::json!(["instance-lifecycle", "instance-quantifiers"])
+        );
+        let constructs = bundle["constructs"].as_array().unwrap();
+        let find = |id: &str| constructs.iter().find(|c| c["id"] == id).unwrap();
+        let branch = find("shipment_flow")["steps"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["id"] == "step_all_delivered")
+            .unwrap()["condition"]
+            .clone();
+        vec![find("close_partial")["precondition"].clone(), branch]
+    };
+
+    let bundle = elaborate_to(tmp.path(), "quantifiers", &source);
+    let predicates = quantified(&bundle);
+    assert_eq!(
+        predicates[0],
+        serde_json::json!({
+            "body": {
+                "left": { "instance_state": "d" },
+                "op": "=",
+                "right": { "literal": "delivered", "type": { "base": "Text" } }
+            },
+            "domain": { "instances_of": "Delivery" },
+            "quantifier": "exists",
+            "variable": "d"
+        })
+    );
+    assert_eq!(predicates[1]["quantifier"], "forall");
 
-#[test]
-fn diff_identical_files_exits_0() {
+    let decompiled = tmp.path().join("decompiled.tenor");
     tenor()
         .args([
-            "diff",
-            "conformance/positive/fact_basic.expected.json",
-            "conformance/positive/fact_basic.expected.json",
+            "decompile",
+            bundle.to_str().unwrap(),
+            "--out",
+            decompiled.to_str().unwrap(),
         ])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("no differences"));
+        .success();
+    let decompiled_source = fs::read_to_string(&decompiled).unwrap();
+    assert!(decompiled_source.contains("∃ d ∈ instances(Delivery) . state(d) = \"delivered\""));
+    let round_trip = elaborate_to(tmp.path(), "round_trip", &decompiled_source);
+    assert_eq!(quantified(&round_trip), predicates);
 }
 
 #[test]
-fn diff_different_files_exits_1() {
+fn diff_breaking_treats_step_renames_as_non_breaking() {
+    let tmp = TempDir::new().unwrap();
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/entity_operation_basic.tenor"),
+    )
+    .unwrap();
+    let v1 = elaborate_to(tmp.path(), "v1", &source);
+    let v2 = elaborate_to(
+        tmp.path(),
+        "v2",
+        &source.replace("step_approve", "approve_order_step"),
+    );
+
     tenor()
         .args([
             "diff",
-            "conformance/positive/fact_basic.expected.json",
-            "conformance/positive/rule_basic.expected.json",
+            "--breaking",
+            v1.to_str().unwrap(),
+            v2.to_str().unwrap(),
         ])
         .assert()
-        .failure()
-        .code(1);
+        .success()
+        .stdout(predicate::str::contains("Structural refactor"))
+        .stdout(predicate::str::contains(
+            "step_approve → approve_order_step",
+        ))
+        .stdout(predicate::str::contains("0 breaking"))
+        .stdout(predicate::str::contains("\nBREAKING:").not());
 }
 
 #[test]
-fn diff_fact_added_shows_addition() {
-    // Create two bundles: one minimal, one with an extra fact
+fn diff_breaking_flags_lost_terminal_outcome() {
     let tmp = TempDir::new().unwrap();
-    let bundle1 = serde_json::json!({
-        "kind": "Bundle", "id": "test_contract", "tenor": "1.0",
-        "constructs": [
-            { "kind": "Fact", "id": "amount", "type": { "base": "Int" },
-              "source": { "system": "billing", "field": "amt" },
-              "provenance": { "file": "test.tenor", "line": 1 }, "tenor": "1.0" }
-        ]
-    });
-    let bundle2 = serde_json::json!({
-        "kind": "Bundle", "id": "test_contract", "tenor": "1.0",
-        "constructs": [
-            { "kind": "Fact", "id": "amount", "type": { "base": "Int" },
-              "source": { "system": "billing", "field": "amt" },
-              "provenance": { "file": "test.tenor", "line": 1 }, "tenor": "1.0" },
-            { "kind": "Fact", "id": "is_active", "type": { "base": "Bool" },
-              "source": { "system": "crm", "field": "active" },
-              "provenance": { "file": "test.tenor", "line": 5 }, "tenor": "1.0" }
-        ]
-    });
-
-    let path1 = tmp.path().join("bundle1.json");
-    let path2 = tmp.path().join("bundle2.json");
-    fs::write(&path1, serde_json::to_string_pretty(&bundle1).unwrap()).unwrap();
-    fs::write(&path2, serde_json::to_string_pretty(&bundle2).unwrap()).unwrap();
-
-    // Diff should detect the addition and exit 1
-    tenor()
-        .args(["diff", path1.to_str().unwrap(), path2.to_str().unwrap()])
-        .assert()
-        .failure()
-        .code(1);
-}
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/entity_operation_basic.tenor"),
+    )
+    .unwrap();
+    let v1 = elaborate_to(tmp.path(), "v1", &source);
+    let v2 = elaborate_to(
+        tmp.path(),
+        "v2",
+        &source.replace("Terminal(order_approved)", "Terminal(order_accepted)"),
+    );
 
-#[test]
-fn diff_breaking_non_breaking_changes() {
-    // Identical bundles should exit 0 with --breaking
     tenor()
         .args([
             "diff",
             "--breaking",
-            "conformance/positive/fact_basic.expected.json",
-            "conformance/positive/fact_basic.expected.json",
+            v1.to_str().unwrap(),
+            v2.to_str().unwrap(),
         ])
         .assert()
-        .success();
+        .failure()
+        .stdout(predicate::str::contains(
+            "no longer reach terminal outcome(s): order_approved",
+        ));
 }
 
 #[test]
@@ -401,6 +1766,167 @@ fn check_selected_analysis() {
         .stdout(predicate::str::contains("Reachability:"));
 }
 
+#[test]
+fn check_state_space_limit_flags_product_explosion() {
+    tenor()
+        .args(["check", "conformance/positive/entity_basic.tenor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Product State Space:"))
+        .stdout(predicate::str::contains("within limit 1000000"));
+
+    tenor()
+        .args([
+            "check",
+            "conformance/positive/entity_basic.tenor",
+            "--state-space-limit",
+            "4",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("WARNING: exceeds limit 4"))
+        .stdout(predicate::str::contains("[s1/WARNING]"))
+        .stdout(predicate::str::contains("dominated by Order (6 states)"));
+}
+
+#[test]
+fn check_only_tag_filters_findings() {
+    let tmp = TempDir::new().unwrap();
+    let source = tmp.path().join("tagged.tenor");
+    fs::write(
+        &source,
+        r#"
+@tag("payments")
+entity Payment {
+  states:  [pending, captured, orphaned]
+  initial: pending
+  transitions: [(pending, captured)]
+}
+
+entity Ledger {
+  states:  [open, closed, lost]
+  initial: open
+  transitions: [(open, closed)]
+}
+"#,
+    )
+    .unwrap();
+    let file = source.to_str().unwrap();
+
+    tenor()
+        .args(["check", file])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Entity 'Payment'"))
+        .stdout(predicate::str::contains("Entity 'Ledger'"));
+
+    tenor()
+        .args(["check", file, "--only-tag", "payments"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Findings (tagged 'payments'):"))
+        .stdout(predicate::str::contains("Entity 'Payment'"))
+        .stdout(predicate::str::contains("Entity 'Ledger'").not());
+
+    tenor()
+        .args(["check", file, "--only-tag", "billing"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No findings."));
+}
+
+#[test]
+fn check_exports_admissibility_matrix() {
+    tenor()
+        .args([
+            "check",
+            "conformance/positive/flow_basic.tenor",
+            "--admissibility-matrix",
+            "csv",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "persona,operation,entity,state,authorized,admissible\n",
+        ))
+        .stdout(predicate::str::contains(
+            "buyer,submit_order,Order,draft,true,true",
+        ))
+        .stdout(predicate::str::contains(
+            "buyer,submit_order,Order,submitted,true,false",
+        ));
+
+    let output = tenor()
+        .args([
+            "check",
+            "conformance/positive/flow_basic.tenor",
+            "--admissibility-matrix",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let matrix: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(matrix["personas"].as_array().unwrap().len() > 1);
+    let cells = matrix["cells"].as_array().unwrap();
+    assert!(cells.iter().any(|c| c["persona_id"] == "buyer"
+        && c["operation_id"] == "submit_order"
+        && c["admissible"] == true));
+}
+
+#[test]
+fn check_temporal_properties_reports_counterexample() {
+    let tmp = TempDir::new().unwrap();
+    let properties = tmp.path().join("properties.json");
+    fs::write(
+        &properties,
+        r#"[
+            "operation approve_order never before verdict review_required",
+            "reaching success passes through operation submit_order"
+        ]"#,
+    )
+    .unwrap();
+    tenor()
+        .args([
+            "check",
+            "conformance/positive/flow_basic.tenor",
+            "--properties",
+        ])
+        .arg(&properties)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Temporal Properties: 2 checked, 0 violated",
+        ));
+
+    fs::write(
+        &properties,
+        r#"[{"name": "no_review", "property": "never step step_review"}]"#,
+    )
+    .unwrap();
+    tenor()
+        .args(["check", "conformance/positive/flow_basic.tenor", "--properties"])
+        .arg(&properties)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "[s12/WARNING]: Property 'no_review' violated in flow 'order_approval': step_submit -> step_check_review -> step_handoff -> step_review -> success",
+        ));
+
+    fs::write(&properties, r#"["approve_order before submit_order"]"#).unwrap();
+    tenor()
+        .args([
+            "check",
+            "conformance/positive/flow_basic.tenor",
+            "--properties",
+        ])
+        .arg(&properties)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unrecognized property"));
+}
+
 #[test]
 fn check_nonexistent_file_exits_1() {
     tenor()
@@ -425,6 +1951,194 @@ fn check_invalid_analysis_exits_1() {
         .stderr(predicate::str::contains("invalid analysis"));
 }
 
+#[test]
+fn check_system_resolves_triggers_across_members() {
+    tenor()
+        .args([
+            "check",
+            "--system",
+            "conformance/positive/system_flow_trigger.tenor",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "contract_a.application_flow --[success]--> contract_b.review_flow",
+        ))
+        .stdout(predicate::str::contains("No conflicts."));
+}
+
+#[test]
+fn check_system_reports_conflicting_transition_authority() {
+    tenor()
+        .args([
+            "check",
+            "--system",
+            "conformance/positive/system_shared_entity.tenor",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "Transition application: draft -> submitted is claimed by multiple contracts: \
+             contract_a (submit_application), contract_b (start_review)",
+        ));
+}
+
+#[test]
+fn check_system_json_output() {
+    let output = tenor()
+        .args([
+            "--output",
+            "json",
+            "check",
+            "--system",
+            "conformance/positive/system_shared_entity.tenor",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["system_id"], "shared_entity_system");
+    assert!(json["members"]["contract_a"]["s4_authority"].is_object());
+    assert!(json["conflicts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c["kind"] == "transition_authority_conflict"));
+}
+
+const LINT_CONTRACT: &str = r#"
+fact amount {
+  type:   Int(min: 0, max: 1000)
+  source: "ledger.amount"
+}
+
+rule small {
+  stratum: 0
+  when:    amount < 100
+  produce: verdict SmallClaim { payload: Bool = true }
+}
+"#;
+
+#[test]
+fn lint_reports_findings_as_json() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("claims.tenor");
+    fs::write(&path, LINT_CONTRACT).unwrap();
+
+    let output = tenor()
+        .args(["--output", "json", "lint", path.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["rule"], "verdict-type-naming");
+    assert_eq!(findings[0]["construct_id"], "small");
+    assert_eq!(findings[0]["line"], 7);
+}
+
+#[test]
+fn lint_respects_tenor_toml() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("claims.tenor");
+    fs::write(&path, LINT_CONTRACT).unwrap();
+    fs::write(
+        tmp.path().join("tenor.toml"),
+        "[lint.rules]\nverdict-type-naming = false\n",
+    )
+    .unwrap();
+
+    tenor()
+        .current_dir(tmp.path())
+        .args(["lint", "claims.tenor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No lint findings."));
+}
+
+#[test]
+fn lint_rejects_unknown_rule_in_config() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("claims.tenor");
+    fs::write(&path, LINT_CONTRACT).unwrap();
+    fs::write(
+        tmp.path().join("tenor.toml"),
+        "[lint.rules]\nno-such-rule = false\n",
+    )
+    .unwrap();
+
+    tenor()
+        .current_dir(tmp.path())
+        .args(["lint", "claims.tenor"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown lint rule 'no-such-rule'"));
+}
+
+#[test]
+fn project_file_supplies_source_roots_import_paths_and_analyses() {
+    let tmp = TempDir::new().unwrap();
+    fs::create_dir_all(tmp.path().join("contracts")).unwrap();
+    fs::create_dir_all(tmp.path().join("shared")).unwrap();
+    fs::write(
+        tmp.path().join("tenor.toml"),
+        r#"
+[project]
+source_roots = ["contracts"]
+import_paths = ["shared"]
+
+[check]
+analyses = ["s1"]
+"#,
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("shared/accounts.tenor"),
+        "fact is_active {\n  type:   Bool\n  source: \"accounts.active\"\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("contracts/orders.tenor"),
+        r#"
+import "accounts.tenor"
+
+rule account_active {
+  stratum: 0
+  when:    is_active = true
+  produce: verdict account_active { payload: Bool = true }
+}
+"#,
+    )
+    .unwrap();
+
+    let output = tenor()
+        .current_dir(tmp.path())
+        .args(["--output", "json", "check", "orders.tenor"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["analyses_run"], serde_json::json!(["s1"]));
+}
+
+#[test]
+fn malformed_project_file_is_reported() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(tmp.path().join("tenor.toml"), "[chek]\n").unwrap();
+
+    tenor()
+        .current_dir(tmp.path())
+        .args(["lint", "--list-rules"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("tenor.toml"));
+}
+
 // ──────────────────────────────────────────────
 // 8. Explain subcommand
 // ──────────────────────────────────────────────
@@ -504,13 +2218,63 @@ fn explain_missing_file_exits_1() {
 }
 
 #[test]
-fn explain_invalid_json_exits_1() {
+fn explain_invalid_json_exits_1() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("bad.json");
+    fs::write(&path, "not valid json at all").unwrap();
+
+    tenor()
+        .args(["explain", path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .code(1);
+}
+
+#[test]
+fn decompile_round_trips_through_elaborate() {
+    let elaborate_output = tenor()
+        .args(["elaborate", "domains/saas/saas_subscription.tenor"])
+        .output()
+        .expect("elaborate failed");
+    assert!(elaborate_output.status.success());
+
+    let tmp = TempDir::new().unwrap();
+    let json_path = tmp.path().join("saas_subscription.json");
+    let tenor_path = tmp.path().join("saas_subscription.tenor");
+    fs::write(&json_path, &elaborate_output.stdout).unwrap();
+
+    tenor()
+        .args([
+            "decompile",
+            json_path.to_str().unwrap(),
+            "--out",
+            tenor_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let source = fs::read_to_string(&tenor_path).unwrap();
+    assert!(source.contains("entity Subscription"));
+
+    let round_trip = tenor()
+        .args(["elaborate", tenor_path.to_str().unwrap()])
+        .output()
+        .expect("elaborate failed");
+    assert!(
+        round_trip.status.success(),
+        "decompiled source failed to elaborate:\n{}",
+        source
+    );
+}
+
+#[test]
+fn decompile_invalid_bundle_exits_1() {
     let tmp = TempDir::new().unwrap();
     let path = tmp.path().join("bad.json");
-    fs::write(&path, "not valid json at all").unwrap();
+    fs::write(&path, r#"{"kind": "Bundle"}"#).unwrap();
 
     tenor()
-        .args(["explain", path.to_str().unwrap()])
+        .args(["decompile", path.to_str().unwrap()])
         .assert()
         .failure()
         .code(1);
@@ -699,6 +2463,32 @@ fn generate_typescript_from_tenor_exits_0() {
         .stdout(predicate::str::contains("Generated TypeScript"));
 }
 
+#[test]
+fn generate_docs_markdown_writes_pages() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    tenor()
+        .args([
+            "generate",
+            "docs",
+            "conformance/positive/operation_basic.tenor",
+            "--out",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Generated documentation"));
+
+    let contract_dir = fs::read_dir(dir.path())
+        .unwrap()
+        .next()
+        .expect("contract directory")
+        .unwrap()
+        .path();
+    for page in ["index.md", "facts.md", "entities.md", "operations.md"] {
+        assert!(contract_dir.join(page).exists(), "missing {}", page);
+    }
+}
+
 #[test]
 fn generate_typescript_nonexistent_file_exits_1() {
     let dir = tempfile::tempdir().expect("temp dir");
@@ -732,6 +2522,118 @@ fn generate_typescript_unsupported_type_exits_1() {
         .stderr(predicate::str::contains("unsupported input file type"));
 }
 
+// ──────────────────────────────────────────────
+// Optimize subcommand
+// ──────────────────────────────────────────────
+
+#[test]
+fn optimize_hoists_types_and_strips_provenance() {
+    let tmp = TempDir::new().unwrap();
+    let elaborated = tenor()
+        .args(["elaborate", "domains/energy_procurement/rfp_workflow.tenor"])
+        .output()
+        .expect("elaborate failed");
+    assert!(elaborated.status.success());
+    let bundle_path = tmp.path().join("rfp.json");
+    fs::write(&bundle_path, &elaborated.stdout).unwrap();
+    let optimized_path = tmp.path().join("rfp.optimized.json");
+
+    tenor()
+        .args([
+            "optimize",
+            bundle_path.to_str().unwrap(),
+            "--out",
+            optimized_path.to_str().unwrap(),
+            "--strip-provenance",
+            "--report",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("type table:"))
+        .stderr(predicate::str::contains("reordered: no"));
+
+    let original: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&bundle_path).unwrap()).unwrap();
+    let optimized: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&optimized_path).unwrap()).unwrap();
+    assert!(!optimized["type_table"].as_array().unwrap().is_empty());
+    assert!(optimized["requires"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|f| f == "type-table"));
+
+    // Inlining the type table gives back the original, minus provenance
+    let restored = tenor_interchange::into_inlined(optimized).unwrap();
+    let restored_constructs = restored["constructs"].as_array().unwrap();
+    let original_constructs = original["constructs"].as_array().unwrap();
+    assert_eq!(restored_constructs.len(), original_constructs.len());
+    for (restored, original) in restored_constructs.iter().zip(original_constructs) {
+        let mut original = original.clone();
+        original.as_object_mut().unwrap().remove("provenance");
+        assert_eq!(restored, &original);
+    }
+}
+
+// ──────────────────────────────────────────────
+// Upgrade-bundle subcommand
+// ──────────────────────────────────────────────
+
+#[test]
+fn upgrade_bundle_rewrites_1_0_effects() {
+    let tmp = TempDir::new().unwrap();
+    let elaborated = tenor()
+        .args(["elaborate", "domains/energy_procurement/rfp_workflow.tenor"])
+        .output()
+        .expect("elaborate failed");
+    assert!(elaborated.status.success());
+    let current: serde_json::Value = serde_json::from_slice(&elaborated.stdout).unwrap();
+
+    // Reconstruct the 1.0.0 shape: transition effects without a kind
+    let mut legacy = current.clone();
+    legacy["tenor_version"] = serde_json::json!("1.0.0");
+    for construct in legacy["constructs"].as_array_mut().unwrap() {
+        if let Some(effects) = construct.get_mut("effects").and_then(|e| e.as_array_mut()) {
+            for effect in effects {
+                if effect["kind"] == "transition" {
+                    effect.as_object_mut().unwrap().remove("kind");
+                }
+            }
+        }
+    }
+    let legacy_path = tmp.path().join("rfp.1.0.json");
+    fs::write(&legacy_path, serde_json::to_string(&legacy).unwrap()).unwrap();
+    let upgraded_path = tmp.path().join("rfp.1.1.json");
+
+    tenor()
+        .args([
+            "upgrade-bundle",
+            legacy_path.to_str().unwrap(),
+            "--out",
+            upgraded_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("upgraded 1.0.0 -> 1.1.0"));
+
+    let upgraded: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&upgraded_path).unwrap()).unwrap();
+    assert_eq!(upgraded, current);
+
+    tenor()
+        .args([
+            "upgrade-bundle",
+            legacy_path.to_str().unwrap(),
+            "--to",
+            "2.0.0",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unsupported interchange version '2.0.0'",
+        ));
+}
+
 // ──────────────────────────────────────────────
 // 8. Global flags
 // ──────────────────────────────────────────────
@@ -765,6 +2667,134 @@ fn elaborate_json_output_format() {
         .stdout(predicate::str::contains("\"kind\": \"Bundle\""));
 }
 
+#[test]
+fn elaborate_json_error_carries_error_code() {
+    tenor()
+        .args([
+            "--output",
+            "json",
+            "elaborate",
+            "conformance/negative/pass4/unresolved_fact_ref.tenor",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("\"code\": \"TEN-E-0040\""));
+}
+
+#[test]
+fn new_scaffolds_a_project_whose_tests_pass() {
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("purchasing");
+    tenor()
+        .arg("new")
+        .arg(&dir)
+        .args(["--entity", "PurchaseOrder", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("purchasing.tenor"));
+    for file in [
+        "tenor.toml",
+        "facts.json",
+        "tests/within_limit.facts.json",
+        "tests/within_limit.verdicts.json",
+    ] {
+        assert!(dir.join(file).exists(), "missing {}", file);
+    }
+    let source = fs::read_to_string(dir.join("purchasing.tenor")).unwrap();
+    assert!(source.contains("flow purchase_order_approval"));
+
+    tenor()
+        .args(["test", "--unit"])
+        .arg(dir.join("purchasing.tenor"))
+        .assert()
+        .success();
+
+    // Never overwrites an existing project
+    tenor()
+        .arg("new")
+        .arg(&dir)
+        .arg("--yes")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+}
+
+#[test]
+fn new_prompts_for_names_on_stdin() {
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("project");
+    tenor()
+        .arg("new")
+        .arg(&dir)
+        .write_stdin("lending\nLoan\nborrower\n\n")
+        .assert()
+        .success();
+    let source = fs::read_to_string(dir.join("lending.tenor")).unwrap();
+    assert!(source.contains("persona borrower"));
+    assert!(source.contains("persona approver"));
+    assert!(source.contains("operation submit_loan"));
+}
+
+#[test]
+fn completions_prints_shell_script() {
+    tenor()
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("_tenor()"))
+        .stdout(predicate::str::contains("simulate"));
+}
+
+#[test]
+fn explain_error_prints_description_and_fixes() {
+    tenor()
+        .args(["explain-error", "ten-e-0100"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TEN-E-0100: missing fact"))
+        .stdout(predicate::str::contains("Common fixes:"));
+
+    tenor()
+        .args(["explain-error", "TEN-E-9999"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown error code 'TEN-E-9999'"));
+}
+
+#[test]
+fn inspect_summarizes_contract_constructs() {
+    tenor()
+        .args(["inspect", "conformance/positive/flow_basic.tenor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Order: draft, submitted, approved, rejected  initial: draft",
+        ))
+        .stdout(predicate::str::contains("effect: Order draft -> submitted"))
+        .stdout(predicate::str::contains(
+            "order_approval  entry: step_submit",
+        ));
+}
+
+#[test]
+fn inspect_json_output_lists_constructs() {
+    let output = tenor()
+        .args([
+            "--output",
+            "json",
+            "inspect",
+            "conformance/positive/fact_basic.expected.json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let balance = &summary["facts"][0];
+    assert_eq!(balance["id"], "balance");
+    assert_eq!(balance["type"], "Money");
+    assert_eq!(balance["source"]["system"], "account_service");
+}
+
 #[test]
 fn eval_quiet_suppresses_output_on_success() {
     tenor()
@@ -900,6 +2930,34 @@ fn e12_etag_determinism() {
     );
 }
 
+/// The manifest's agent_capabilities section is the same capability surface
+/// the language server computes for the contract.
+#[test]
+fn manifest_agent_capabilities_match_lsp() {
+    let contract = "domains/saas/saas_subscription.tenor";
+    let output = tenor()
+        .args(["elaborate", "--manifest", contract])
+        .output()
+        .expect("failed to execute");
+    assert!(output.status.success());
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("manifest is not valid JSON");
+
+    let lsp =
+        tenor_lsp::agent_capabilities::compute_agent_capabilities(&workspace_root().join(contract));
+    assert_eq!(
+        manifest["agent_capabilities"],
+        serde_json::to_value(&lsp).unwrap()
+    );
+
+    let flow = &manifest["agent_capabilities"]["flows"][0];
+    assert_eq!(flow["entry_point"], "step_activate");
+    assert_eq!(
+        flow["entry_conditions"],
+        serde_json::json!(["verdict 'seats_ok' is present"])
+    );
+}
+
 /// E12: Change Detection — Different contracts produce different etags.
 #[test]
 fn e12_etag_change_detection() {
@@ -1115,3 +3173,295 @@ fn verify_wasm_help_shows_options() {
         .stdout(predicate::str::contains("--sig"))
         .stdout(predicate::str::contains("--pubkey"));
 }
+
+// ──────────────────────────────────────────────
+// Agent shell: sessions, scripts, transcripts
+// ──────────────────────────────────────────────
+
+const AGENT_CONTRACT: &str = "conformance/eval/positive/entity_operation_basic.tenor";
+
+#[test]
+fn agent_script_persists_entity_states_across_invocations() {
+    let tmp = TempDir::new().unwrap();
+    let session = tmp.path().join("session.json");
+    let script = tmp.path().join("run.txt");
+    fs::write(
+        &script,
+        "# approve the order\nset is_active true\nflow approval_flow admin\nentities\n",
+    )
+    .unwrap();
+
+    tenor()
+        .args([
+            "agent",
+            AGENT_CONTRACT,
+            "--session",
+            session.to_str().unwrap(),
+            "--script",
+            script.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Order : pending -> approved"))
+        .stdout(predicate::str::contains("session saved to"));
+
+    let saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&session).unwrap()).unwrap();
+    assert_eq!(saved["entity_states"]["Order"]["_default"], "approved");
+    assert_eq!(saved["facts"]["is_active"], true);
+    assert_eq!(saved["transcript"].as_array().unwrap().len(), 3);
+
+    // A second invocation resumes from the saved state.
+    let transcript = tmp.path().join("transcript.json");
+    fs::write(
+        &script,
+        format!("entities\nexport-transcript {}\n", transcript.display()),
+    )
+    .unwrap();
+    tenor()
+        .args([
+            "agent",
+            AGENT_CONTRACT,
+            "--session",
+            session.to_str().unwrap(),
+            "--script",
+            script.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Resumed session"))
+        .stdout(predicate::str::contains("Order : approved"));
+
+    let exported: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&transcript).unwrap()).unwrap();
+    assert_eq!(exported["kind"], "AgentTranscript");
+    assert!(exported["contract_etag"].as_str().is_some());
+    let entries = exported["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[1]["command"], "flow approval_flow admin");
+    assert_eq!(entries[1]["result"]["outcome"], "order_approved");
+    assert_eq!(
+        exported["final_state"]["entity_states"]["Order"]["_default"],
+        "approved"
+    );
+}
+
+#[test]
+fn agent_script_stops_on_first_error() {
+    let tmp = TempDir::new().unwrap();
+    let script = tmp.path().join("bad.txt");
+    fs::write(&script, "bogus\neval\n").unwrap();
+
+    tenor()
+        .args([
+            "agent",
+            AGENT_CONTRACT,
+            "--script",
+            script.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("unknown command: bogus"))
+        .stdout(predicate::str::contains("tenor> eval").not());
+}
+
+#[test]
+fn agent_rejects_session_from_other_contract() {
+    let tmp = TempDir::new().unwrap();
+    let session = tmp.path().join("session.json");
+    fs::write(
+        &session,
+        r#"{"contract_id": "something_else", "facts": {}, "entity_states": {}, "transcript": []}"#,
+    )
+    .unwrap();
+    let script = tmp.path().join("empty.txt");
+    fs::write(&script, "").unwrap();
+
+    tenor()
+        .args([
+            "agent",
+            AGENT_CONTRACT,
+            "--session",
+            session.to_str().unwrap(),
+            "--script",
+            script.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "belongs to contract 'something_else'",
+        ));
+}
+
+#[test]
+fn agent_what_if_reports_verdict_and_action_changes() {
+    let tmp = TempDir::new().unwrap();
+    let script = tmp.path().join("what_if.txt");
+    fs::write(
+        &script,
+        "set fact is_active true\nset fact is_active false\nunset fact is_active\n",
+    )
+    .unwrap();
+
+    tenor()
+        .args([
+            "agent",
+            AGENT_CONTRACT,
+            "--script",
+            script.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+ verdict account_active = true"))
+        .stdout(predicate::str::contains(
+            "+ action admin can start approval_flow",
+        ))
+        .stdout(predicate::str::contains("- verdict account_active = true"))
+        .stdout(predicate::str::contains(
+            "- action admin can no longer start approval_flow",
+        ))
+        .stdout(predicate::str::contains("evaluation incomplete"));
+}
+
+// ──────────────────────────────────────────────
+// MCP server
+// ──────────────────────────────────────────────
+
+#[test]
+fn mcp_answers_initialize_and_action_space_over_stdio() {
+    let requests = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"test","version":"0"}}}"#,
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"action_space","arguments":{"persona_id":"admin","facts":{"is_active":true}}}}"#,
+    ]
+    .join("\n");
+
+    let output = tenor()
+        .args(["mcp", AGENT_CONTRACT])
+        .write_stdin(requests + "\n")
+        .output()
+        .expect("mcp failed");
+    assert!(output.status.success());
+
+    let responses: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(responses.len(), 2, "notifications must not be answered");
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+    let space = &responses[1]["result"]["structuredContent"];
+    assert_eq!(space["actions"][0]["flow_id"], "approval_flow");
+}
+
+#[test]
+fn mcp_without_contracts_exits_nonzero() {
+    tenor().args(["mcp"]).assert().failure();
+}
+
+// ──────────────────────────────────────────────
+// Migrate: entity state mapping artifacts
+// ──────────────────────────────────────────────
+
+/// v1/v2 of the basic entity contract where v2 renames `approved` to `accepted`.
+fn migrate_versions(dir: &Path) -> (PathBuf, PathBuf) {
+    let source = fs::read_to_string(
+        workspace_root().join("conformance/eval/positive/entity_operation_basic.tenor"),
+    )
+    .unwrap();
+    let v1 = elaborate_to(dir, "v1", &source);
+    let v2 = elaborate_to(
+        dir,
+        "v2",
+        &source.replace("pending, approved", "pending, accepted"),
+    );
+    (v1, v2)
+}
+
+fn read_artifact(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn migrate_writes_artifact_from_mapping_file() {
+    let tmp = TempDir::new().unwrap();
+    let (v1, v2) = migrate_versions(tmp.path());
+    let mapping = tmp.path().join("mapping.toml");
+    fs::write(&mapping, "[Order]\napproved = \"accepted\"\n").unwrap();
+    let out = tmp.path().join("migration.json");
+
+    tenor()
+        .args([
+            "migrate",
+            v1.to_str().unwrap(),
+            v2.to_str().unwrap(),
+            "--yes",
+            "--mapping",
+            mapping.to_str().unwrap(),
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Order: approved -> accepted"));
+
+    let artifact = read_artifact(&out);
+    assert_eq!(artifact["kind"], "MigrationArtifact");
+    let remaps = artifact["entity_state_remaps"].as_array().unwrap();
+    assert_eq!(remaps.len(), 1);
+    assert_eq!(remaps[0]["entity_id"], "Order");
+    assert_eq!(remaps[0]["states"]["approved"], "accepted");
+    assert_eq!(remaps[0]["states"]["pending"], "pending");
+}
+
+#[test]
+fn migrate_confirms_removed_states_interactively() {
+    let tmp = TempDir::new().unwrap();
+    let (v1, v2) = migrate_versions(tmp.path());
+    let out = tmp.path().join("migration.json");
+
+    tenor()
+        .args([
+            "migrate",
+            v1.to_str().unwrap(),
+            v2.to_str().unwrap(),
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .write_stdin("accepted\nyes\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Order.approved -> [pending]"));
+
+    assert_eq!(
+        read_artifact(&out)["entity_state_remaps"][0]["states"]["approved"],
+        "accepted"
+    );
+}
+
+#[test]
+fn migrate_rejects_mapping_to_unknown_state() {
+    let tmp = TempDir::new().unwrap();
+    let (v1, v2) = migrate_versions(tmp.path());
+    let mapping = tmp.path().join("mapping.toml");
+    fs::write(&mapping, "[Order]\napproved = \"shipped\"\n").unwrap();
+    let out = tmp.path().join("migration.json");
+
+    tenor()
+        .args([
+            "migrate",
+            v1.to_str().unwrap(),
+            v2.to_str().unwrap(),
+            "--yes",
+            "--mapping",
+            mapping.to_str().unwrap(),
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not a v2 state"));
+    assert!(!out.exists());
+}
@@ -26,8 +26,9 @@ fn workspace_root() -> PathBuf {
 fn tenor() -> Command {
     let mut cmd = cargo_bin_cmd!("tenor");
     cmd.current_dir(workspace_root());
-    // Ensure no API key leaks into heuristic tests
+    // Ensure no API key or LLM provider leaks into heuristic tests
     cmd.env_remove("ANTHROPIC_API_KEY");
+    cmd.env_remove("TENOR_LLM_PROVIDER");
     cmd
 }
 
@@ -534,3 +535,153 @@ fn connect_apply_all_rejected_no_output() {
         "all-rejected should not generate adapter config"
     );
 }
+
+// ──────────────────────────────────────────────
+// 9. Apply generates runnable adapters
+// ──────────────────────────────────────────────
+
+#[test]
+fn connect_apply_generates_adapters() {
+    let (tmp, contract_path, openapi_path) = setup_fixtures();
+    let batch_dir = tmp.path().join("adapters-batch");
+    let apply_dir = tmp.path().join("adapters-apply");
+
+    tenor()
+        .args([
+            "connect",
+            contract_path.to_str().unwrap(),
+            "--environment",
+            openapi_path.to_str().unwrap(),
+            "--batch",
+            "--heuristic",
+            "--out",
+            batch_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let review_path = batch_dir.join("tenor-connect-review.toml");
+    let content = fs::read_to_string(&review_path).expect("read review");
+    let modified = content.replace("status = \"proposed\"", "status = \"accepted\"");
+    fs::write(&review_path, &modified).expect("write modified review");
+
+    tenor()
+        .args([
+            "connect",
+            contract_path.to_str().unwrap(),
+            "--apply",
+            review_path.to_str().unwrap(),
+            "--typescript",
+            "--out",
+            apply_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let rust = fs::read_to_string(apply_dir.join("adapters.rs")).expect("read adapters.rs");
+    assert!(rust.contains("pub fn register_adapters("));
+    assert!(rust.contains("impl FactAdapter for HttpSourceAdapter"));
+    assert!(rust.contains("escrow_balance"));
+    assert!(rust.contains("order_status"));
+
+    let ts = fs::read_to_string(apply_dir.join("adapters.ts")).expect("read adapters.ts");
+    assert!(ts.contains("export async function fetchFacts("));
+    assert!(ts.contains("escrow_balance"));
+
+    // Path parameters become config placeholders
+    let config = fs::read_to_string(apply_dir.join("tenor-adapters.toml")).expect("read config");
+    let parsed: toml::Value = toml::from_str(&config).expect("valid TOML");
+    assert_eq!(
+        parsed["source"]["escrow_service"]["id"].as_str(),
+        Some("TODO")
+    );
+}
+
+// ──────────────────────────────────────────────
+// 10. Synonym dictionary
+// ──────────────────────────────────────────────
+
+#[test]
+fn connect_synonyms_file_extends_matching() {
+    let (tmp, contract_path, openapi_path) = setup_fixtures();
+    let synonyms_path = tmp.path().join("synonyms.toml");
+    fs::write(&synonyms_path, "status = [\"phase\"]\n").unwrap();
+
+    tenor()
+        .args([
+            "connect",
+            contract_path.to_str().unwrap(),
+            "--environment",
+            openapi_path.to_str().unwrap(),
+            "--dry-run",
+            "--heuristic",
+            "--synonyms",
+            synonyms_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("order_status"));
+
+    fs::write(&synonyms_path, "status = \"phase\"\n").unwrap();
+    tenor()
+        .args([
+            "connect",
+            contract_path.to_str().unwrap(),
+            "--environment",
+            openapi_path.to_str().unwrap(),
+            "--dry-run",
+            "--heuristic",
+            "--synonyms",
+            synonyms_path.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid synonyms file"));
+}
+
+// ──────────────────────────────────────────────
+// 11. LLM provider selection
+// ──────────────────────────────────────────────
+
+#[test]
+fn connect_llm_provider_from_environment() {
+    let (_tmp, contract_path, openapi_path) = setup_fixtures();
+
+    tenor()
+        .env("TENOR_LLM_PROVIDER", "bogus")
+        .args([
+            "connect",
+            contract_path.to_str().unwrap(),
+            "--environment",
+            openapi_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown LLM provider 'bogus'"));
+
+    // An unreachable local server falls back to heuristic matching
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    tenor()
+        .env("TENOR_LLM_PROVIDER", "ollama")
+        .env("TENOR_LLM_BASE_URL", format!("http://127.0.0.1:{}", port))
+        .args([
+            "connect",
+            contract_path.to_str().unwrap(),
+            "--environment",
+            openapi_path.to_str().unwrap(),
+            "--dry-run",
+            "--verbose",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "provider: ollama at http://127.0.0.1",
+        ))
+        .stderr(predicate::str::contains("Falling back to heuristic"))
+        .stdout(predicate::str::contains("order_status"));
+}
@@ -25,27 +25,52 @@ fn next_port() -> u16 {
 
 /// Helper: start the tenor serve process on the given port.
 fn start_server(port: u16, contracts: &[&str]) -> Child {
-    // The workspace root is two levels up from crates/cli
+    start_server_with_args(port, contracts)
+}
+
+/// The workspace root, two levels up from crates/cli.
+fn workspace_root() -> &'static std::path::Path {
     let manifest_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
-    let workspace_root = manifest_dir
+    manifest_dir
         .parent()
         .and_then(|p| p.parent())
-        .expect("workspace root");
+        .expect("workspace root")
+}
 
+/// Helper: start the tenor serve process with extra arguments (contracts
+/// or flags).
+fn start_server_with_args(port: u16, args: &[&str]) -> Child {
+    start_server_in(workspace_root(), port, args, &[])
+}
+
+/// Helper: start the tenor serve process in `dir` (picking up its
+/// tenor.toml) with extra arguments and environment variables.
+fn start_server_in(
+    dir: &std::path::Path,
+    port: u16,
+    args: &[&str],
+    envs: &[(&str, &str)],
+) -> Child {
     let mut cmd = Command::new(env!("CARGO_BIN_EXE_tenor"));
-    cmd.current_dir(workspace_root);
+    cmd.current_dir(dir);
     cmd.arg("serve").arg("--port").arg(port.to_string());
-    for c in contracts {
-        cmd.arg(c);
+    for arg in args {
+        cmd.arg(arg);
+    }
+    cmd.env_remove("TENOR_API_KEY");
+    for (key, value) in envs {
+        cmd.env(key, value);
     }
     // Redirect stdout/stderr to avoid blocking
     cmd.stdout(std::process::Stdio::piped());
     cmd.stderr(std::process::Stdio::piped());
 
     let child = cmd.spawn().expect("failed to start tenor serve");
-    // Wait for server to be ready by polling the port
-    for _ in 0..50 {
-        if TcpStream::connect(format!("127.0.0.1:{}", port)).is_ok() {
+    // Contracts preload after the port opens; wait until /readyz passes
+    for _ in 0..300 {
+        if TcpStream::connect(format!("127.0.0.1:{}", port)).is_ok()
+            && http_get(port, "/readyz").0 == 200
+        {
             return child;
         }
         std::thread::sleep(Duration::from_millis(100));
@@ -91,6 +116,52 @@ fn http_post(port: u16, path: &str, body: &str) -> (u16, String) {
     parse_http_response(&response)
 }
 
+/// Helper: make an HTTP POST request with a binary body and custom headers,
+/// returning (status, response_headers, raw_body).
+fn http_post_bytes(
+    port: u16,
+    path: &str,
+    extra_headers: &[(&str, &str)],
+    body: &[u8],
+) -> (u16, String, Vec<u8>) {
+    let mut stream = TcpStream::connect(format!("127.0.0.1:{}", port)).expect("failed to connect");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+
+    let mut header_lines = String::new();
+    for (name, value) in extra_headers {
+        header_lines.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: localhost:{}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+        path,
+        port,
+        header_lines,
+        body.len()
+    );
+    std::io::Write::write_all(&mut stream, request.as_bytes()).expect("failed to write");
+    std::io::Write::write_all(&mut stream, body).expect("failed to write");
+
+    let mut response = Vec::new();
+    let _ = stream.read_to_end(&mut response);
+
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .unwrap_or(response.len());
+    let headers = String::from_utf8_lossy(&response[..split]).to_string();
+    let body = response.get(split + 4..).unwrap_or(&[]).to_vec();
+    let status = headers
+        .lines()
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse::<u16>().ok())
+        .unwrap_or(0);
+    (status, headers, body)
+}
+
 /// Helper: make an HTTP GET request with custom headers and return (status, response_headers, body).
 fn http_get_with_headers(
     port: u16,
@@ -212,6 +283,45 @@ fn health_returns_200_with_version() {
     );
 }
 
+#[test]
+fn probes_report_readiness_without_rate_limit() {
+    let port = next_port();
+    let mut child = start_server_with_args(
+        port,
+        &["--rate-limit", "1", "domains/saas/saas_subscription.tenor"],
+    );
+
+    let live = http_get(port, "/healthz");
+    let probes: Vec<u16> = (0..3).map(|_| http_get(port, "/readyz").0).collect();
+    let (_, body) = http_get(port, "/readyz");
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(live.0, 200);
+    assert_eq!(probes, [200, 200, 200], "probes are not rate limited");
+    let json: serde_json::Value = serde_json::from_str(&body).expect("valid JSON");
+    assert_eq!(json["status"], "ready");
+    assert_eq!(json["checks"]["contracts"]["loaded"], 1);
+    assert_eq!(json["checks"]["contracts"]["failed"], 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn sigterm_shuts_down_gracefully() {
+    let port = next_port();
+    let mut child = start_server(port, &[]);
+    assert_eq!(http_get(port, "/readyz").0, 200);
+
+    let killed = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .expect("failed to run kill");
+    assert!(killed.success());
+    let status = child.wait().expect("server exits");
+
+    assert!(status.success(), "server exited with {}", status);
+}
+
 #[test]
 fn contracts_list_empty_when_no_preloads() {
     let port = next_port();
@@ -328,6 +438,71 @@ fn evaluate_with_preloaded_contract() {
     assert!(!verdicts.is_empty(), "should produce verdicts");
 }
 
+#[test]
+fn evaluate_accepts_cbor_request_body() {
+    let port = next_port();
+    let mut child = start_server(port, &["domains/saas/saas_subscription.tenor"]);
+
+    let request = serde_json::json!({
+        "bundle_id": "saas_subscription",
+        "facts": {
+            "current_seat_count": 15,
+            "subscription_plan": "professional",
+            "plan_features": {"max_seats": 50, "api_access": true, "sso_enabled": true, "custom_branding": false},
+            "payment_ok": true,
+            "account_age_days": 14
+        }
+    });
+    let body = tenor_interchange::to_cbor(&request).unwrap();
+    let (status, _, response) = http_post_bytes(
+        port,
+        "/evaluate",
+        &[("Content-Type", "application/cbor")],
+        &body,
+    );
+    let (bad_status, _, _) = http_post_bytes(
+        port,
+        "/evaluate",
+        &[("Content-Type", "application/cbor")],
+        &[0xff, 0x00],
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 200, "body: {}", String::from_utf8_lossy(&response));
+    let json: serde_json::Value = serde_json::from_slice(&response).expect("valid JSON");
+    assert!(!json["verdicts"].as_array().expect("verdicts").is_empty());
+    assert_eq!(bad_status, 400, "malformed CBOR must be rejected");
+}
+
+#[test]
+fn elaborate_returns_cbor_when_accepted() {
+    let port = next_port();
+    let mut child = start_server(port, &[]);
+
+    let request =
+        br#"{"source": "fact is_active {\n  type: Bool\n  source: \"system.active\"\n}"}"#;
+    let (status, headers, body) = http_post_bytes(
+        port,
+        "/elaborate",
+        &[
+            ("Content-Type", "application/json"),
+            ("Accept", "application/cbor, application/json;q=0.5"),
+        ],
+        request,
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 200, "body: {}", String::from_utf8_lossy(&body));
+    assert_eq!(
+        extract_header(&headers, "content-type"),
+        Some("application/cbor")
+    );
+    let bundle = tenor_interchange::from_cbor(&body).expect("valid CBOR");
+    assert!(bundle.get("constructs").is_some(), "should have constructs");
+}
+
 #[test]
 fn evaluate_unknown_bundle_returns_404() {
     let port = next_port();
@@ -342,6 +517,84 @@ fn evaluate_unknown_bundle_returns_404() {
     assert!(json.get("error").is_some());
 }
 
+#[test]
+fn evaluate_pinned_to_contract_etag() {
+    let port = next_port();
+    let mut child = start_server(port, &["domains/saas/saas_subscription.tenor"]);
+
+    let (_, list) = http_get(port, "/contracts");
+    let list: serde_json::Value = serde_json::from_str(&list).expect("valid JSON");
+    let etag = list["contracts"][0]["etag"]
+        .as_str()
+        .expect("contract etag")
+        .to_string();
+    let request = |digest: Option<&str>| {
+        let mut body = serde_json::json!({
+            "bundle_id": "saas_subscription",
+            "facts": {
+                "current_seat_count": 15,
+                "subscription_plan": "professional",
+                "plan_features": {
+                    "max_seats": 50,
+                    "api_access": true,
+                    "sso_enabled": true,
+                    "custom_branding": false
+                },
+                "payment_ok": true,
+                "account_age_days": 14
+            }
+        });
+        if let Some(d) = digest {
+            body["bundle_digest"] = d.into();
+        }
+        body.to_string()
+    };
+    let if_match = format!("\"{}\"", etag);
+    let (pinned_status, _, _) = http_post_bytes(
+        port,
+        "/evaluate",
+        &[
+            ("Content-Type", "application/json"),
+            ("If-Match", &if_match),
+        ],
+        request(None).as_bytes(),
+    );
+    let (digest_status, _) = http_post(port, "/evaluate", &request(Some(&etag)));
+    let (stale_status, stale_headers, stale_body) = http_post_bytes(
+        port,
+        "/evaluate",
+        &[
+            ("Content-Type", "application/json"),
+            ("If-Match", "\"0000\""),
+        ],
+        request(None).as_bytes(),
+    );
+    let (stale_digest_status, _) = http_post(port, "/evaluate", &request(Some("0000")));
+    let (simulate_status, _, _) = http_post_bytes(
+        port,
+        "/flows/subscription_lifecycle/simulate",
+        &[
+            ("Content-Type", "application/json"),
+            ("If-Match", "\"0000\""),
+        ],
+        br#"{"persona_id": "billing_system", "facts": {}}"#,
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(pinned_status, 200);
+    assert_eq!(digest_status, 200);
+    assert_eq!(stale_status, 412);
+    assert_eq!(
+        extract_header(&stale_headers, "etag"),
+        Some(if_match.as_str())
+    );
+    let json: serde_json::Value = serde_json::from_slice(&stale_body).expect("valid JSON");
+    assert_eq!(json["code"], "TEN-E-0308");
+    assert_eq!(stale_digest_status, 412);
+    assert_eq!(simulate_status, 412);
+}
+
 #[test]
 fn explain_preloaded_contract() {
     let port = next_port();
@@ -666,6 +919,66 @@ fn simulate_flow_valid_and_invalid() {
     );
 }
 
+#[test]
+fn preview_operation_reports_effects_without_executing() {
+    let port = next_port();
+    let mut child = start_server(port, &["domains/saas/saas_subscription.tenor"]);
+    let path = "/contracts/saas_subscription/operations/activate_subscription/preview";
+    let request = |persona: &str| {
+        serde_json::json!({
+            "persona_id": persona,
+            "facts": {
+                "current_seat_count": 15,
+                "subscription_plan": "professional",
+                "plan_features": {
+                    "max_seats": 50,
+                    "api_access": true,
+                    "sso_enabled": true,
+                    "custom_branding": false
+                },
+                "payment_ok": true,
+                "account_age_days": 14,
+                "cancellation_requested": false
+            },
+            "entity_states": { "Subscription": "trial" }
+        })
+        .to_string()
+    };
+
+    let (status, body) = http_post(port, path, &request("billing_system"));
+    let (rejected_status, rejected) = http_post(port, path, &request("account_admin"));
+    let (missing_status, _) = http_post(
+        port,
+        "/contracts/saas_subscription/operations/nope/preview",
+        &request("billing_system"),
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 200, "preview should succeed, body: {}", body);
+    let json: serde_json::Value = serde_json::from_str(&body).expect("valid JSON");
+    assert_eq!(json["preview"], true);
+    assert_eq!(json["would_succeed"], true);
+    assert_eq!(json["outcome"], "success");
+    assert_eq!(
+        json["effects"],
+        serde_json::json!([{
+            "entity_id": "Subscription",
+            "instance_id": "_default",
+            "kind": "transition",
+            "from_state": "trial",
+            "to_state": "active"
+        }])
+    );
+    assert_eq!(json["verdicts_used"], serde_json::json!(["seats_ok"]));
+
+    assert_eq!(rejected_status, 200, "body: {}", rejected);
+    let json: serde_json::Value = serde_json::from_str(&rejected).expect("valid JSON");
+    assert_eq!(json["would_succeed"], false);
+    assert_eq!(json["error"]["kind"], "persona_rejected");
+    assert_eq!(missing_status, 404);
+}
+
 #[test]
 fn actions_returns_action_space() {
     let port = next_port();
@@ -729,3 +1042,724 @@ fn actions_returns_action_space() {
         "action must have entry_operation_id"
     );
 }
+
+#[test]
+fn actions_matrix_covers_all_personas() {
+    let port = next_port();
+    let mut child = start_server(port, &["domains/saas/saas_subscription.tenor"]);
+
+    let body = serde_json::json!({
+        "facts": {
+            "current_seat_count": 15,
+            "subscription_plan": "professional",
+            "plan_features": {
+                "max_seats": 50,
+                "api_access": true,
+                "sso_enabled": true,
+                "custom_branding": false
+            },
+            "payment_ok": true,
+            "account_age_days": 14,
+            "cancellation_requested": false
+        },
+        "entity_states": {
+            "Subscription": "trial"
+        }
+    })
+    .to_string();
+
+    let (status, body) = http_post(port, "/actions/matrix", &body);
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 200, "matrix should return 200, body: {}", body);
+    let json: serde_json::Value = serde_json::from_str(&body).expect("valid JSON");
+    let personas = json["personas"].as_array().expect("personas array");
+    assert!(personas.contains(&serde_json::json!("billing_system")));
+    for persona in personas {
+        let persona = persona.as_str().unwrap();
+        let row = json["matrix"][persona]
+            .as_object()
+            .expect("one row per persona");
+        assert_eq!(row.len(), json["operations"].as_array().unwrap().len());
+        assert!(json["action_spaces"][persona]["actions"].is_array());
+    }
+    // billing_system can act in this state, as /actions reports
+    assert!(!json["action_spaces"]["billing_system"]["actions"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn evaluate_delta_patches_baseline_facts() {
+    let port = next_port();
+    let mut child = start_server(port, &["domains/saas/saas_subscription.tenor"]);
+
+    let facts = r#"{"facts": {"current_seat_count": 15, "subscription_plan": "professional", "plan_features": {"max_seats": 50, "api_access": true, "sso_enabled": true, "custom_branding": false}, "payment_ok": true, "account_age_days": 14}}"#;
+    let (status, body) = http_post(port, "/contracts/saas_subscription/baselines", facts);
+    assert_eq!(status, 200, "body: {}", body);
+    let baseline: serde_json::Value = serde_json::from_str(&body).expect("valid JSON");
+    let baseline_id = baseline["baseline_id"].as_str().expect("baseline_id");
+
+    let delta = serde_json::json!({
+        "baseline_id": baseline_id,
+        "patch": [{ "op": "replace", "path": "/payment_ok", "value": false }],
+    });
+    let (status, body) = http_post(
+        port,
+        "/contracts/saas_subscription/evaluate-delta",
+        &delta.to_string(),
+    );
+    let failed_test = serde_json::json!({
+        "baseline_id": baseline_id,
+        "patch": [{ "op": "test", "path": "/payment_ok", "value": false }],
+    });
+    let (conflict_status, _) = http_post(
+        port,
+        "/contracts/saas_subscription/evaluate-delta",
+        &failed_test.to_string(),
+    );
+    let (unknown_status, _) = http_post(
+        port,
+        "/contracts/saas_subscription/evaluate-delta",
+        r#"{"baseline_id": "nope", "patch": []}"#,
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 200, "body: {}", body);
+    let json: serde_json::Value = serde_json::from_str(&body).expect("valid JSON");
+    let types = |list: &serde_json::Value| -> Vec<String> {
+        list.as_array()
+            .expect("array")
+            .iter()
+            .map(|v| v["type"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert!(types(&json["diff"]["added"]).contains(&"payment_failed".to_string()));
+    assert!(types(&json["diff"]["removed"]).contains(&"payment_current".to_string()));
+    assert_ne!(json["baseline_id"], baseline["baseline_id"]);
+    assert_eq!(conflict_status, 409);
+    assert_eq!(unknown_status, 404);
+}
+
+#[test]
+fn rate_limit_without_tenants_ignores_tokens() {
+    let port = next_port();
+    let mut child =
+        start_server_with_args(port, &["--rate-limit", "2", "--rate-limit-key", "token"]);
+
+    let get = |token: &str| http_get_with_headers(port, "/contracts", &[("X-API-Key", token)]).0;
+    let first = [get("alpha"), get("alpha"), get("alpha")];
+    let other = get("beta");
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(first, [200, 200, 429]);
+    assert_eq!(
+        other, 429,
+        "tokens that authenticate nobody share the IP budget"
+    );
+}
+
+#[test]
+fn cors_allow_list_and_body_size_limit() {
+    let port = next_port();
+    let mut child = start_server_with_args(
+        port,
+        &[
+            "--cors-origin",
+            "https://app.example.com",
+            "--max-body-size",
+            "64",
+        ],
+    );
+
+    let (_, allowed, _) =
+        http_get_with_headers(port, "/health", &[("Origin", "https://app.example.com")]);
+    let (_, denied, _) =
+        http_get_with_headers(port, "/health", &[("Origin", "https://evil.example.com")]);
+    let facts = format!(
+        r#"{{"bundle_id": "x", "facts": {{"note": "{}"}}}}"#,
+        "a".repeat(200)
+    );
+    let (status, _) = http_post(port, "/evaluate", &facts);
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(
+        extract_header(&allowed, "access-control-allow-origin"),
+        Some("https://app.example.com")
+    );
+    assert_eq!(extract_header(&denied, "access-control-allow-origin"), None);
+    assert_eq!(status, 413);
+}
+
+#[test]
+fn tenants_see_only_their_own_contracts() {
+    let dir = tempfile::tempdir().unwrap();
+    let saas = workspace_root().join("domains/saas/saas_subscription.tenor");
+    std::fs::write(
+        dir.path().join("tenor.toml"),
+        format!(
+            r#"
+[serve.tenants.acme]
+token_env = "ACME_TOKEN"
+contracts = [{:?}]
+max_contracts = 1
+
+[serve.tenants.globex]
+token_env = "GLOBEX_TOKEN"
+"#,
+            saas.display().to_string()
+        ),
+    )
+    .unwrap();
+    let port = next_port();
+    let mut child = start_server_in(
+        dir.path(),
+        port,
+        &[],
+        &[
+            ("ACME_TOKEN", "acme-secret"),
+            ("GLOBEX_TOKEN", "globex-secret"),
+        ],
+    );
+
+    let acme = [("X-API-Key", "acme-secret")];
+    let globex = [("Authorization", "Bearer globex-secret")];
+    let get = |path: &str, headers: &[(&str, &str)]| {
+        let (status, _, body) = http_get_with_headers(port, path, headers);
+        (status, serde_json::from_str(&body).unwrap_or_default())
+    };
+    let post = |path: &str, headers: &[(&str, &str)], body: &serde_json::Value| {
+        let mut headers = headers.to_vec();
+        headers.push(("Content-Type", "application/json"));
+        let (status, _, body) = http_post_bytes(port, path, &headers, body.to_string().as_bytes());
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+        (status, json)
+    };
+
+    let source = "fact is_active {\n  type: Bool\n  source: \"system.active\"\n}";
+    let (_, bundle) = post(
+        "/elaborate",
+        &globex,
+        &serde_json::json!({ "source": source }),
+    );
+    let (loaded_status, loaded) = post(
+        "/contracts",
+        &globex,
+        &serde_json::json!({ "bundle": bundle }),
+    );
+    let (limit_status, _) = post(
+        "/contracts",
+        &acme,
+        &serde_json::json!({ "bundle": bundle }),
+    );
+    let (_, acme_contracts) = get("/contracts", &acme);
+    let (_, globex_contracts) = get("/contracts", &globex);
+    let (cross_status, _) = post(
+        "/evaluate",
+        &globex,
+        &serde_json::json!({ "bundle_id": "saas_subscription", "facts": {} }),
+    );
+    let (acme_usage_status, acme_usage) = get("/tenant", &acme);
+    let (anonymous_status, _) = get("/contracts", &[]);
+    let (wrong_status, _) = get("/contracts", &[("X-API-Key", "nope")]);
+    child.kill().ok();
+    child.wait().ok();
+
+    let ids = |json: &serde_json::Value| -> Vec<String> {
+        json["contracts"]
+            .as_array()
+            .expect("contracts array")
+            .iter()
+            .map(|c| c["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(loaded_status, 201, "load failed: {}", loaded);
+    let globex_id = loaded["id"].as_str().unwrap().to_string();
+    assert_eq!(ids(&acme_contracts), vec!["saas_subscription"]);
+    assert_eq!(ids(&globex_contracts), vec![globex_id]);
+    assert_eq!(limit_status, 403, "acme is at its contract limit");
+    assert_eq!(cross_status, 404, "globex cannot reach acme's contract");
+    assert_eq!(acme_usage_status, 200);
+    assert_eq!(acme_usage["tenant"], "acme");
+    assert_eq!(acme_usage["max_contracts"], 1);
+    assert_eq!(acme_usage["requests"], 3);
+    assert_eq!(anonymous_status, 401);
+    assert_eq!(wrong_status, 403);
+}
+
+/// Helper: accept one HTTP request on a local listener, answer 200, and
+/// send back its (headers, body).
+fn receive_one_request() -> (u16, std::sync::mpsc::Receiver<(String, String)>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind receiver");
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut raw = Vec::new();
+        let mut buf = [0u8; 4096];
+        let (headers, body_start) = loop {
+            let n = stream.read(&mut buf).unwrap_or(0);
+            raw.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&raw).to_string();
+            if let Some(end) = text.find("\r\n\r\n") {
+                break (text[..end].to_string(), end + 4);
+            }
+            if n == 0 {
+                break (text, raw.len());
+            }
+        };
+        let length: usize = extract_header(&headers, "content-length")
+            .and_then(|l| l.parse().ok())
+            .unwrap_or(0);
+        while raw.len() < body_start + length {
+            let n = stream.read(&mut buf).unwrap_or(0);
+            if n == 0 {
+                break;
+            }
+            raw.extend_from_slice(&buf[..n]);
+        }
+        let body = String::from_utf8_lossy(&raw[body_start..]).to_string();
+        let _ = std::io::Write::write_all(
+            &mut stream,
+            b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+        let _ = tx.send((headers, body));
+    });
+    (port, rx)
+}
+
+#[test]
+fn webhook_receives_signed_verdict_changes() {
+    let (receiver_port, received) = receive_one_request();
+    let port = next_port();
+    let mut child = start_server_in(
+        workspace_root(),
+        port,
+        &[
+            "domains/saas/saas_subscription.tenor",
+            "--webhook-allow-private",
+        ],
+        &[("TENOR_API_KEY", "admin")],
+    );
+    let post = |path: &str, body: &serde_json::Value| {
+        let headers = [("X-API-Key", "admin"), ("Content-Type", "application/json")];
+        let (status, _, body) = http_post_bytes(port, path, &headers, body.to_string().as_bytes());
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+        (status, json)
+    };
+
+    let (created_status, webhook) = post(
+        "/webhooks",
+        &serde_json::json!({
+            "url": format!("http://127.0.0.1:{}/hook", receiver_port),
+            "events": ["verdicts.changed"],
+            "contract_id": "saas_subscription",
+            "secret": "s3cret",
+        }),
+    );
+    let (bad_event_status, _) = post(
+        "/webhooks",
+        &serde_json::json!({ "url": "http://127.0.0.1:1/", "events": ["nope"] }),
+    );
+    let facts: serde_json::Value = serde_json::from_str(r#"{"facts": {"current_seat_count": 15, "subscription_plan": "professional", "plan_features": {"max_seats": 50, "api_access": true, "sso_enabled": true, "custom_branding": false}, "payment_ok": true, "account_age_days": 14}}"#).unwrap();
+    let (_, baseline) = post("/contracts/saas_subscription/baselines", &facts);
+    let (delta_status, _) = post(
+        "/contracts/saas_subscription/evaluate-delta",
+        &serde_json::json!({
+            "baseline_id": baseline["baseline_id"],
+            "patch": [{ "op": "replace", "path": "/payment_ok", "value": false }],
+        }),
+    );
+    let delivery = received.recv_timeout(Duration::from_secs(10));
+    // Delivery is recorded once the receiver has answered.
+    std::thread::sleep(Duration::from_millis(200));
+    let (_, _, listed) = http_get_with_headers(port, "/webhooks", &[("X-API-Key", "admin")]);
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(created_status, 201, "create failed: {}", webhook);
+    assert_eq!(webhook["secret"], "s3cret");
+    assert_eq!(bad_event_status, 400);
+    assert_eq!(delta_status, 200);
+    let (headers, body) = delivery.expect("webhook delivery");
+    assert_eq!(
+        extract_header(&headers, "x-tenor-event"),
+        Some("verdicts.changed")
+    );
+    let signature = extract_header(&headers, "x-tenor-signature").expect("signature header");
+    let hex = signature.strip_prefix("sha256=").expect("sha256= prefix");
+    assert!(hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+    let event: serde_json::Value = serde_json::from_str(&body).expect("JSON body");
+    assert_eq!(event["contract_id"], "saas_subscription");
+    assert_eq!(event["data"]["source"], "evaluate-delta");
+    let added: Vec<&str> = event["data"]["diff"]["added"]
+        .as_array()
+        .expect("added")
+        .iter()
+        .map(|v| v["type"].as_str().unwrap())
+        .collect();
+    assert!(added.contains(&"payment_failed"));
+    let listed: serde_json::Value = serde_json::from_str(&listed).expect("JSON list");
+    assert_eq!(listed["webhooks"][0]["delivered"], 1);
+    assert!(listed["webhooks"][0].get("secret").is_none());
+}
+
+#[test]
+fn webhooks_to_internal_addresses_are_refused() {
+    let port = next_port();
+    let mut child = start_server_in(
+        workspace_root(),
+        port,
+        &["domains/saas/saas_subscription.tenor"],
+        &[("TENOR_API_KEY", "admin")],
+    );
+    let post = |path: &str, body: &serde_json::Value| {
+        let headers = [("X-API-Key", "admin"), ("Content-Type", "application/json")];
+        let (status, _, body) = http_post_bytes(port, path, &headers, body.to_string().as_bytes());
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+        (status, json)
+    };
+
+    let (loopback_status, loopback) = post(
+        "/webhooks",
+        &serde_json::json!({ "url": "http://127.0.0.1:9/hook" }),
+    );
+    let (metadata_status, _) = post(
+        "/webhooks",
+        &serde_json::json!({ "url": "http://169.254.169.254/latest/meta-data/" }),
+    );
+    let (schedule_status, _) = post(
+        "/contracts/saas_subscription/schedules",
+        &serde_json::json!({ "cron": "0 * * * *", "sink": { "webhook": "http://10.0.0.7/hook" } }),
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(loopback_status, 400);
+    assert!(
+        loopback["error"]
+            .as_str()
+            .unwrap()
+            .contains("--webhook-allow-private"),
+        "{}",
+        loopback
+    );
+    assert_eq!(metadata_status, 400);
+    assert_eq!(schedule_status, 400);
+}
+
+#[test]
+fn webhook_list_requires_authentication() {
+    let port = next_port();
+    let mut child = start_server(port, &[]);
+    let (status, body) = http_get(port, "/webhooks");
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 403, "{}", body);
+}
+
+#[test]
+fn entity_instances_and_history_from_exported_storage() {
+    let dir = tempfile::tempdir().unwrap();
+    let history_path = dir.path().join("history.json");
+    let history = serde_json::json!({
+        "entity_states": [{
+            "entity_id": "Order", "instance_id": "o1", "state": "approved", "version": 2,
+            "updated_at": "2025-03-05T09:00:00Z", "last_flow_id": "approval",
+            "last_operation_id": "approve"
+        }, {
+            "entity_id": "Order", "instance_id": "o2", "state": "draft", "version": 0,
+            "updated_at": "2025-03-02T00:00:00Z", "last_flow_id": null,
+            "last_operation_id": null
+        }],
+        "transitions": [{
+            "id": "t2", "operation_execution_id": "op-2", "entity_id": "Order",
+            "instance_id": "o1", "from_state": "submitted", "to_state": "approved",
+            "from_version": 1, "to_version": 2, "executed_at": "2025-03-05T09:00:00Z"
+        }, {
+            "id": "t1", "operation_execution_id": "op-1", "entity_id": "Order",
+            "instance_id": "o1", "from_state": "draft", "to_state": "submitted",
+            "from_version": 0, "to_version": 1, "executed_at": "2025-03-01T09:00:00Z"
+        }],
+        "provenance": [{
+            "id": "p2", "operation_execution_id": "op-2", "facts_used": ["amount"],
+            "verdicts_used": ["approvable"], "verdict_set_snapshot": []
+        }]
+    });
+    std::fs::write(&history_path, history.to_string()).unwrap();
+
+    let port = next_port();
+    let mut child = start_server_with_args(port, &["--history", history_path.to_str().unwrap()]);
+    let (all_status, all) = http_get(port, "/entities/Order/instances");
+    let (_, drafts) = http_get(port, "/entities/Order/instances?state=draft");
+    let (instance_status, instance) = http_get(port, "/entities/Order/instances/o1");
+    let (history_status, timeline) = http_get(port, "/entities/Order/instances/o1/history");
+    let (missing_status, _) = http_get(port, "/entities/Order/instances/o9/history");
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(all_status, 200, "list failed: {}", all);
+    let all: serde_json::Value = serde_json::from_str(&all).unwrap();
+    assert_eq!(all["instances"].as_array().unwrap().len(), 2);
+    let drafts: serde_json::Value = serde_json::from_str(&drafts).unwrap();
+    assert_eq!(drafts["instances"][0]["instance_id"], "o2");
+    assert_eq!(instance_status, 200);
+    let instance: serde_json::Value = serde_json::from_str(&instance).unwrap();
+    assert_eq!(instance["state"], "approved");
+    assert_eq!(history_status, 200, "history failed: {}", timeline);
+    let timeline: serde_json::Value = serde_json::from_str(&timeline).unwrap();
+    assert_eq!(timeline["current"]["version"], 2);
+    let transitions = timeline["transitions"].as_array().unwrap();
+    assert_eq!(transitions[0]["to_state"], "submitted");
+    assert_eq!(transitions[0]["provenance"], serde_json::json!([]));
+    assert_eq!(transitions[1]["to_state"], "approved");
+    assert_eq!(
+        transitions[1]["provenance"][0]["verdicts_used"][0],
+        "approvable"
+    );
+    assert_eq!(missing_status, 404);
+}
+
+#[test]
+fn entity_instances_without_history_return_404() {
+    let port = next_port();
+    let mut child = start_server(port, &[]);
+    let (status, body) = http_get(port, "/entities/Order/instances");
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 404);
+    assert!(body.contains("--history"), "unexpected body: {}", body);
+}
+
+#[test]
+fn flow_executions_grouped_by_correlation_id() {
+    let port = next_port();
+    let mut child = start_server(port, &["domains/saas/saas_subscription.tenor"]);
+
+    let run = |correlation_id: Option<&str>| {
+        let mut body = serde_json::json!({
+            "bundle_id": "saas_subscription",
+            "flow_id": "subscription_lifecycle",
+            "persona": "billing_system",
+            "facts": {
+                "current_seat_count": 15,
+                "subscription_plan": "professional",
+                "plan_features": {
+                    "max_seats": 50,
+                    "api_access": true,
+                    "sso_enabled": true,
+                    "custom_branding": false
+                },
+                "payment_ok": true,
+                "account_age_days": 14
+            }
+        });
+        if let Some(id) = correlation_id {
+            body["correlation_id"] = serde_json::json!(id);
+        }
+        let (status, body) = http_post(port, "/evaluate", &body.to_string());
+        assert_eq!(status, 200, "evaluate failed: {}", body);
+        serde_json::from_str::<serde_json::Value>(&body).unwrap()
+    };
+    let first = run(Some("order-42"));
+    let second = run(Some("order-42"));
+    let other = run(None);
+    let (status, listed) = http_get(port, "/flows/executions?correlation_id=order-42");
+    let (bad_status, _) = http_post(
+        port,
+        "/evaluate",
+        r#"{"bundle_id": "saas_subscription", "flow_id": "subscription_lifecycle", "persona": "billing_system", "facts": {}, "correlation_id": 7}"#,
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(first["correlation_id"], "order-42");
+    assert_ne!(first["execution_id"], second["execution_id"]);
+    let generated = other["correlation_id"].as_str().expect("generated id");
+    assert_ne!(generated, "order-42");
+    assert_eq!(status, 200);
+    let listed: serde_json::Value = serde_json::from_str(&listed).unwrap();
+    let executions = listed["executions"].as_array().unwrap();
+    let ids: Vec<&serde_json::Value> = executions.iter().map(|e| &e["id"]).collect();
+    assert_eq!(ids, [&first["execution_id"], &second["execution_id"]]);
+    assert_eq!(executions[0]["flow_id"], "subscription_lifecycle");
+    assert_eq!(executions[0]["persona_id"], "billing_system");
+    assert_eq!(executions[0]["outcome"], first["outcome"]);
+    assert_eq!(executions[0]["snapshot_facts"]["payment_ok"], true);
+    assert_eq!(bad_status, 400);
+}
+
+#[test]
+fn validate_facts_reports_all_errors_with_pointers() {
+    let port = next_port();
+    let mut child = start_server(port, &["domains/saas/saas_subscription.tenor"]);
+    let (status, body) = http_post(
+        port,
+        "/validate-facts",
+        r#"{"bundle_id": "saas_subscription", "facts": {"current_seat_count": "fifteen", "plan_features": {"max_seats": 50, "api_access": "yes", "sso_enabled": true, "custom_branding": false}}}"#,
+    );
+    let (missing_status, _) = http_post(
+        port,
+        "/validate-facts",
+        r#"{"bundle_id": "no_such_contract", "facts": {}}"#,
+    );
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 200, "validate-facts failed: {}", body);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["valid"], false);
+    let pointers: Vec<&str> = json["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["pointer"].as_str().unwrap())
+        .collect();
+    assert!(pointers.contains(&"/current_seat_count"), "{:?}", pointers);
+    assert!(
+        pointers.contains(&"/plan_features/api_access"),
+        "{:?}",
+        pointers
+    );
+    assert!(pointers.contains(&"/account_age_days"), "{:?}", pointers);
+    assert_eq!(missing_status, 404);
+}
+
+#[test]
+fn adapter_config_reloads_without_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let adapters = dir.path().join("adapters.toml");
+    std::fs::write(
+        dir.path().join("tenor.toml"),
+        "[connect]\nadapter_config = \"adapters.toml\"\n",
+    )
+    .unwrap();
+    std::fs::write(&adapters, "[source.crm]\ntoken = \"old\"\n").unwrap();
+    let port = next_port();
+    let mut child = start_server_in(dir.path(), port, &[], &[("TENOR_API_KEY", "admin-key")]);
+    let reload = || {
+        let (status, _, body) = http_post_bytes(
+            port,
+            "/admin/adapter-config/reload",
+            &[("X-API-Key", "admin-key")],
+            b"",
+        );
+        (status, String::from_utf8_lossy(&body).to_string())
+    };
+
+    // Rotate the credentials and add a source.
+    std::fs::write(
+        &adapters,
+        "[source.crm]\ntoken = \"new\"\n\n[source.billing]\ntoken = \"b\"\n",
+    )
+    .unwrap();
+    let (status, body) = reload();
+    std::fs::write(&adapters, "[source.crm\n").unwrap();
+    let (broken_status, _) = reload();
+    #[cfg(unix)]
+    {
+        std::fs::write(&adapters, "[source.ledger]\ntoken = \"l\"\n").unwrap();
+        Command::new("kill")
+            .args(["-HUP", &child.id().to_string()])
+            .status()
+            .expect("send SIGHUP");
+        std::thread::sleep(Duration::from_millis(500));
+    }
+    let (contracts_status, _, _) =
+        http_get_with_headers(port, "/contracts", &[("X-API-Key", "admin-key")]);
+    child.kill().ok();
+    let output = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(status, 200, "reload failed: {}", body);
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["reloaded"], true);
+    assert_eq!(json["sources"], serde_json::json!(["billing", "crm"]));
+    assert!(!body.contains("new"), "values are not echoed: {}", body);
+    assert_eq!(broken_status, 422);
+    #[cfg(unix)]
+    assert!(
+        stderr.contains("Reloaded adapter configuration: 1 sources"),
+        "SIGHUP did not reload: {}",
+        stderr
+    );
+    assert_eq!(contracts_status, 200, "the server kept serving");
+}
+
+#[test]
+fn adapter_config_reload_requires_authentication() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("tenor.toml"),
+        "[connect]\nadapter_config = \"adapters.toml\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("adapters.toml"),
+        "[source.crm]\ntoken = \"t\"\n",
+    )
+    .unwrap();
+    let port = next_port();
+    let mut child = start_server_in(dir.path(), port, &[], &[]);
+
+    let (status, body) = http_post(port, "/admin/adapter-config/reload", "{}");
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(status, 403, "{}", body);
+    assert!(body.contains("requires authentication"), "{}", body);
+}
+
+#[test]
+fn token_rate_limit_counts_per_tenant_and_per_ip_for_bad_tokens() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("tenor.toml"),
+        r#"
+[serve.tenants.acme]
+token_env = "ACME_TOKEN"
+
+[serve.tenants.globex]
+token_env = "GLOBEX_TOKEN"
+"#,
+    )
+    .unwrap();
+    let port = next_port();
+    let mut child = start_server_in(
+        dir.path(),
+        port,
+        &["--rate-limit", "2", "--rate-limit-key", "token"],
+        &[
+            ("ACME_TOKEN", "acme-secret"),
+            ("GLOBEX_TOKEN", "globex-secret"),
+        ],
+    );
+
+    let status = |token: &str| http_get_with_headers(port, "/contracts", &[("X-API-Key", token)]).0;
+    let acme: Vec<u16> = (0..3).map(|_| status("acme-secret")).collect();
+    let globex = status("globex-secret");
+    let made_up: Vec<u16> = ["guess-1", "guess-2", "guess-3"]
+        .iter()
+        .map(|t| status(t))
+        .collect();
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(acme, [200, 200, 429]);
+    assert_eq!(globex, 200, "each tenant has its own budget");
+    assert_eq!(
+        made_up,
+        [403, 403, 429],
+        "invalid tokens share the IP budget"
+    );
+}
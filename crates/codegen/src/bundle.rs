@@ -196,8 +196,10 @@ impl CodegenBundle {
                 }
                 InterchangeConstruct::Source(_)
                 | InterchangeConstruct::System(_)
+                | InterchangeConstruct::Test(_)
+                | InterchangeConstruct::Assert(_)
                 | InterchangeConstruct::TypeDecl(_) => {
-                    // Source, System, and TypeDecl constructs are not relevant for TypeScript codegen
+                    // Source, System, Test, Assert, and TypeDecl constructs are not relevant for TypeScript codegen
                 }
             }
         }
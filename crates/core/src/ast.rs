@@ -4,6 +4,7 @@
 //! elaboration passes. They live here so that pass modules can import
 //! them without depending on the parser.
 
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 // ──────────────────────────────────────────────
@@ -14,6 +15,54 @@ use std::collections::BTreeMap;
 pub struct Provenance {
     pub file: String,
     pub line: u32,
+    /// The construct's header on `line`: its keyword through its name.
+    pub span: Span,
+    /// The construct's whole text, its keyword through its last token.
+    pub range: SourceRange,
+    /// Annotations written before the construct; empty elsewhere.
+    pub annotations: Annotations,
+}
+
+/// A run of characters on one source line. `col` is 1-based and counts
+/// chars; a zero `col` means the position is unknown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub col: u32,
+    pub len: u32,
+}
+
+/// A byte range `start..end` in a source file. Both zero when unknown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl SourceRange {
+    /// The range from the start of `self` to the end of `other`.
+    pub fn to(self, other: SourceRange) -> SourceRange {
+        SourceRange {
+            start: self.start,
+            end: other.end,
+        }
+    }
+}
+
+/// Ownership and triage metadata written before a construct:
+/// `@owner("...")`, `@description("...")` and `@tag("...", ...)`.
+/// Annotations never affect elaboration or evaluation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotations {
+    pub owner: Option<String>,
+    pub description: Option<String>,
+    /// Tags in declaration order, without duplicates.
+    pub tags: Vec<String>,
+}
+
+impl Annotations {
+    pub fn is_empty(&self) -> bool {
+        self.owner.is_none() && self.description.is_none() && self.tags.is_empty()
+    }
 }
 
 // ──────────────────────────────────────────────
@@ -21,24 +70,32 @@ pub struct Provenance {
 // ──────────────────────────────────────────────
 
 /// A raw BaseType as it appears in the DSL, before TypeRef resolution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RawType {
     Bool,
     Int {
         min: i64,
         max: i64,
     },
+    /// `unit` is the canonical unit of measure (see [`crate::units`]);
+    /// `None` for a dimensionless Decimal.
     Decimal {
         precision: u32,
         scale: u32,
+        rounding: Option<RoundingMode>,
+        unit: Option<String>,
     },
     Text {
         max_length: u32,
     },
     Date,
     DateTime,
+    /// `precision` and `scale` default to (10, 2) when not declared.
     Money {
         currency: String,
+        precision: Option<u32>,
+        scale: Option<u32>,
+        rounding: Option<RoundingMode>,
     },
     Duration {
         unit: String,
@@ -62,6 +119,39 @@ pub enum RawType {
     TypeRef(String),
 }
 
+/// Default precision of a Money type that declares none.
+pub const MONEY_DEFAULT_PRECISION: u32 = 10;
+/// Default scale of a Money type that declares none.
+pub const MONEY_DEFAULT_SCALE: u32 = 2;
+
+/// How a Decimal or Money type rounds values that carry more fractional
+/// digits than its declared scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round half to even (banker's rounding).
+    HalfEven,
+    /// Round half away from zero.
+    HalfUp,
+}
+
+impl RoundingMode {
+    /// Parse the DSL and interchange spelling of a rounding mode.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "half_even" => Some(RoundingMode::HalfEven),
+            "half_up" => Some(RoundingMode::HalfUp),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RoundingMode::HalfEven => "half_even",
+            RoundingMode::HalfUp => "half_up",
+        }
+    }
+}
+
 // ──────────────────────────────────────────────
 // Raw literals
 // ──────────────────────────────────────────────
@@ -100,31 +190,58 @@ pub enum RawExpr {
         left: RawTerm,
         right: RawTerm,
         line: u32,
+        range: SourceRange,
     },
     /// verdict_present(id) -- line is the line of the verdict_present token
-    VerdictPresent { id: String, line: u32 },
+    VerdictPresent {
+        id: String,
+        line: u32,
+        range: SourceRange,
+    },
     /// e1 and e2
     And(Box<RawExpr>, Box<RawExpr>),
     /// e1 or e2
     Or(Box<RawExpr>, Box<RawExpr>),
-    /// not e
-    Not(Box<RawExpr>),
-    /// forall var in list_ref . body -- line is the line of the forall token
+    /// not e -- the range starts at the `not` token
+    Not(Box<RawExpr>, SourceRange),
+    /// forall var in list_ref . body -- line is the line of the forall token.
+    /// With `over_instances`, `domain` is an entity id and `var` ranges over
+    /// that entity's instances: `forall d in instances(Delivery) . body`
     Forall {
         var: String,
         domain: String,
+        over_instances: bool,
         body: Box<RawExpr>,
         line: u32,
+        range: SourceRange,
     },
-    /// exists var in list_ref . body -- line is the line of the exists token
+    /// exists var in list_ref . body -- line is the line of the exists token.
+    /// `over_instances` as for Forall
     Exists {
         var: String,
         domain: String,
+        over_instances: bool,
         body: Box<RawExpr>,
         line: u32,
+        range: SourceRange,
     },
 }
 
+impl RawExpr {
+    /// The expression's source text. `and`/`or` span their operands, so
+    /// parentheses around the whole expression are not included.
+    pub fn range(&self) -> SourceRange {
+        match self {
+            RawExpr::Compare { range, .. }
+            | RawExpr::VerdictPresent { range, .. }
+            | RawExpr::Not(_, range)
+            | RawExpr::Forall { range, .. }
+            | RawExpr::Exists { range, .. } => *range,
+            RawExpr::And(a, b) | RawExpr::Or(a, b) => a.range().to(b.range()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum RawTerm {
     FactRef(String),
@@ -133,17 +250,103 @@ pub enum RawTerm {
         field: String,
     },
     Literal(RawLiteral),
+    /// state(var) -- current state of the instance an instance quantifier
+    /// binds to var
+    InstanceState(String),
     /// Arithmetic multiplication: left * right
     Mul {
         left: Box<RawTerm>,
         right: Box<RawTerm>,
     },
+    /// convert(amount, rate) -- a Money amount converted into another
+    /// currency by the exchange rate held in the Money-typed fact `rate`,
+    /// or a Decimal quantity converted by the conversion factor held in a
+    /// Decimal fact `rate` with a unit
+    Convert {
+        amount: Box<RawTerm>,
+        rate: String,
+    },
 }
 
 // ──────────────────────────────────────────────
 // Raw constructs
 // ──────────────────────────────────────────────
 
+/// An Entity transition, optionally guarded by a predicate that must hold
+/// for any effect taking it.
+#[derive(Debug, Clone)]
+pub struct RawTransition {
+    pub from: String,
+    pub to: String,
+    pub guard: Option<RawExpr>,
+    /// Line of the `(` opening the tuple
+    pub line: u32,
+}
+
+/// What an Operation effect does to the entity instance it targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawEffectKind {
+    /// `(Entity, from, to)`: move an existing instance between states
+    Transition,
+    /// `(create Entity)`: add a new instance in the entity's initial state
+    Create,
+    /// `(destroy Entity, from)`: remove an instance currently in `from`
+    Destroy,
+}
+
+/// An Operation effect: one entity state transition, instance creation or
+/// instance removal, optionally assigning entity attributes as part of the
+/// same step.
+#[derive(Debug, Clone)]
+pub struct RawEffect {
+    pub kind: RawEffectKind,
+    pub entity: String,
+    /// Source state; empty for `Create`
+    pub from: String,
+    /// Target state; empty for `Create` and `Destroy`
+    pub to: String,
+    /// Outcome label (multi-outcome operations only)
+    pub outcome: Option<String>,
+    /// Attribute assignments from `set { name: term, ... }`
+    pub set: BTreeMap<String, RawTerm>,
+    /// Line of the `(` opening the tuple
+    pub line: u32,
+}
+
+/// One `<fact>: <literal>` entry in a Test's `given facts` block.
+#[derive(Debug, Clone)]
+pub struct RawGiven {
+    pub fact: String,
+    pub value: RawLiteral,
+    /// Line of the fact identifier
+    pub line: u32,
+}
+
+/// One `expect verdict <type> present|absent` line in a Test.
+#[derive(Debug, Clone)]
+pub struct RawExpectation {
+    pub verdict_type: String,
+    /// `true` for `present`, `false` for `absent`
+    pub present: bool,
+    /// Line of the `expect` keyword
+    pub line: u32,
+}
+
+/// Property an Assert requires of every state of its entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawAssertProperty {
+    /// Reachable from the initial state via the declared transitions
+    Reachable,
+}
+
+impl RawAssertProperty {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RawAssertProperty::Reachable => "reachable",
+        }
+    }
+}
+
 /// Raw construct from the parser
 #[derive(Debug, Clone)]
 pub enum RawConstruct {
@@ -151,6 +354,29 @@ pub enum RawConstruct {
         path: String,
         prov: Provenance,
     },
+    /// `extends "<path>"`: the file derives from a base contract, inheriting
+    /// its constructs. Resolved like an import by Pass 1.
+    Extends {
+        path: String,
+        prov: Provenance,
+    },
+    /// `override rule ...` or `override operation ...` in a file that
+    /// extends a base contract. The parser leaves `base` empty; Pass 1 puts
+    /// `construct` in place of the base construct with the same kind and id
+    /// and keeps the replaced one in `base` for Pass 5's compatibility check.
+    Override {
+        construct: Box<RawConstruct>,
+        base: Option<Box<RawConstruct>>,
+        prov: Provenance,
+    },
+    /// `when feature("<name>") <construct>`: a construct included only when
+    /// `feature` is enabled for elaboration. Pass 1 unwraps or drops it, so
+    /// later passes never see it.
+    Gated {
+        feature: String,
+        construct: Box<RawConstruct>,
+        prov: Provenance,
+    },
     TypeDecl {
         id: String,
         fields: BTreeMap<String, RawType>,
@@ -169,8 +395,9 @@ pub enum RawConstruct {
         initial: String,
         /// Line of the `initial:` field keyword
         initial_line: u32,
-        /// (from, to, line_of_lparen) -- line is the line of the `(` opening each tuple
-        transitions: Vec<(String, String, u32)>,
+        transitions: Vec<RawTransition>,
+        /// Typed per-instance attributes; empty if not declared
+        attributes: BTreeMap<String, RawType>,
         parent: Option<String>,
         /// Line of the `parent:` field keyword, when present
         parent_line: Option<u32>,
@@ -196,8 +423,7 @@ pub enum RawConstruct {
         /// Line of the `allowed_personas:` field keyword
         allowed_personas_line: u32,
         precondition: RawExpr,
-        /// (entity, from, to, outcome_label, line_of_lparen) -- line is the line of the `(` opening each tuple
-        effects: Vec<(String, String, String, Option<String>, u32)>,
+        effects: Vec<RawEffect>,
         error_contract: Vec<String>,
         /// Operation-local outcome identifiers (v1.0); empty if not declared
         outcomes: Vec<String>,
@@ -235,6 +461,68 @@ pub enum RawConstruct {
         shared_entities: Vec<(String, Vec<String>)>,
         prov: Provenance,
     },
+    Test {
+        id: String,
+        /// Fact values from `given facts { ... }`; facts not listed fall back
+        /// to their declared defaults at evaluation time
+        given: Vec<RawGiven>,
+        expectations: Vec<RawExpectation>,
+        prov: Provenance,
+    },
+    /// `assert forall states of <Entity>: <property>`. The id is derived
+    /// from the entity and property (e.g. `Order.reachable`).
+    Assert {
+        id: String,
+        entity: String,
+        property: RawAssertProperty,
+        /// Line of the entity identifier
+        entity_line: u32,
+        prov: Provenance,
+    },
+}
+
+impl RawConstruct {
+    /// This construct's own provenance, not looking through overrides or
+    /// feature gates.
+    pub fn prov(&self) -> &Provenance {
+        match self {
+            RawConstruct::Import { prov, .. }
+            | RawConstruct::Extends { prov, .. }
+            | RawConstruct::Override { prov, .. }
+            | RawConstruct::Gated { prov, .. }
+            | RawConstruct::TypeDecl { prov, .. }
+            | RawConstruct::Fact { prov, .. }
+            | RawConstruct::Entity { prov, .. }
+            | RawConstruct::Rule { prov, .. }
+            | RawConstruct::Operation { prov, .. }
+            | RawConstruct::Persona { prov, .. }
+            | RawConstruct::Source { prov, .. }
+            | RawConstruct::Flow { prov, .. }
+            | RawConstruct::System { prov, .. }
+            | RawConstruct::Test { prov, .. }
+            | RawConstruct::Assert { prov, .. } => prov,
+        }
+    }
+
+    pub fn prov_mut(&mut self) -> &mut Provenance {
+        match self {
+            RawConstruct::Import { prov, .. }
+            | RawConstruct::Extends { prov, .. }
+            | RawConstruct::Override { prov, .. }
+            | RawConstruct::Gated { prov, .. }
+            | RawConstruct::TypeDecl { prov, .. }
+            | RawConstruct::Fact { prov, .. }
+            | RawConstruct::Entity { prov, .. }
+            | RawConstruct::Rule { prov, .. }
+            | RawConstruct::Operation { prov, .. }
+            | RawConstruct::Persona { prov, .. }
+            | RawConstruct::Source { prov, .. }
+            | RawConstruct::Flow { prov, .. }
+            | RawConstruct::System { prov, .. }
+            | RawConstruct::Test { prov, .. }
+            | RawConstruct::Assert { prov, .. } => prov,
+        }
+    }
 }
 
 // ──────────────────────────────────────────────
@@ -264,6 +552,10 @@ pub enum RawStep {
         outcomes: BTreeMap<String, RawStepTarget>,
         /// Optional at parse time; absence is a Pass 5 error (not a parse error)
         on_failure: Option<RawFailureHandler>,
+        timeout: Option<RawTimeout>,
+        /// Probability weight per outcome label, or `failure` for the
+        /// failure handler. Metadata for analysis; ignored by evaluation.
+        weights: BTreeMap<String, f64>,
         /// Line of the `step_id:` token in the steps map
         line: u32,
     },
@@ -272,6 +564,9 @@ pub enum RawStep {
         persona: String,
         if_true: RawStepTarget,
         if_false: RawStepTarget,
+        /// Probability weight of the `true` and `false` sides. Metadata for
+        /// analysis; ignored by evaluation.
+        weights: BTreeMap<String, f64>,
         /// Line of the `step_id:` token in the steps map
         line: u32,
     },
@@ -279,6 +574,9 @@ pub enum RawStep {
         from_persona: String,
         to_persona: String,
         next: String,
+        /// Suspend execution here until `to_persona` resumes the flow
+        pause: bool,
+        timeout: Option<RawTimeout>,
         /// Line of the `step_id:` token in the steps map
         line: u32,
     },
@@ -327,6 +625,25 @@ pub enum RawFailureHandler {
     },
 }
 
+/// A step deadline: once the `deadline` DateTime fact has passed, the step
+/// is skipped and `on_timeout` fires instead.
+#[derive(Debug, Clone)]
+pub struct RawTimeout {
+    /// Id of a DateTime Fact
+    pub deadline: String,
+    /// Line of the `deadline:` field keyword
+    pub deadline_line: u32,
+    pub on_timeout: RawTimeoutAction,
+}
+
+#[derive(Debug, Clone)]
+pub enum RawTimeoutAction {
+    /// Continue at another step or end the flow with a terminal outcome
+    Goto(RawStepTarget),
+    /// Run a failure handler as if the step had failed
+    Handle(RawFailureHandler),
+}
+
 #[derive(Debug, Clone)]
 pub struct RawCompStep {
     pub op: String,
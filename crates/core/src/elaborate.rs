@@ -3,7 +3,8 @@
 //! This is a thin orchestrator that calls each pass module in order.
 //! See CLAUDE.md for the pass overview.
 
-use crate::error::ElabError;
+use crate::ast::{RawConstruct, Span};
+use crate::error::{ElabError, ErrorSink};
 use crate::pass1_bundle;
 use crate::pass2_index;
 use crate::pass3_types;
@@ -11,7 +12,9 @@ use crate::pass4_typecheck;
 use crate::pass5_validate;
 use crate::pass6_serialize;
 use crate::source::SourceProvider;
+use crate::source_map::{self, SourceMap};
 use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 
 /// Elaborate the given root `.tenor` file and return the interchange bundle,
@@ -24,25 +27,7 @@ pub fn elaborate(root_path: &Path) -> Result<Value, ElabError> {
     // Passes 0+1: parse all files in the import graph
     let (constructs, bundle_id) = pass1_bundle::load_bundle(root_path)?;
 
-    // Pass 2: construct indexing
-    let index = pass2_index::build_index(&constructs)?;
-
-    // Pass 3: type environment
-    let type_env = pass3_types::build_type_env(&constructs, &index)?;
-
-    // Pass 4: resolve types in all constructs (TypeRef -> concrete BaseType)
-    let constructs = pass4_typecheck::resolve_types(constructs, &type_env)?;
-
-    // Pass 4 (continued): expression type-checking
-    pass4_typecheck::type_check_rules(&constructs)?;
-
-    // Pass 5: validation
-    pass5_validate::validate(&constructs, &index)?;
-    pass5_validate::validate_operation_transitions(&constructs, &index)?;
-
-    // Pass 6: serialization
-    let bundle = pass6_serialize::serialize(&constructs, &bundle_id);
-    Ok(bundle)
+    elaborate_constructs(constructs, &bundle_id, &mut ErrorSink::fail_fast())
 }
 
 /// Elaborate the given root `.tenor` file using the provided [`SourceProvider`]
@@ -53,29 +38,150 @@ pub fn elaborate(root_path: &Path) -> Result<Value, ElabError> {
 pub fn elaborate_with_provider(
     root_path: &Path,
     provider: &dyn SourceProvider,
+) -> Result<Value, ElabError> {
+    elaborate_with_features(root_path, provider, &BTreeSet::new())
+}
+
+/// Elaborate the given root `.tenor` file using the provided [`SourceProvider`],
+/// including constructs guarded by `when feature("...")` only when their
+/// feature is in `features`.
+///
+/// One source tree can produce several bundle variants this way; the
+/// feature set itself is not part of the bundle.
+pub fn elaborate_with_features(
+    root_path: &Path,
+    provider: &dyn SourceProvider,
+    features: &BTreeSet<String>,
 ) -> Result<Value, ElabError> {
     // Passes 0+1: parse all files in the import graph
-    let (constructs, bundle_id) = pass1_bundle::load_bundle_with_provider(root_path, provider)?;
+    let (constructs, bundle_id) =
+        pass1_bundle::load_bundle_with_features(root_path, provider, features)?;
 
+    elaborate_constructs(constructs, &bundle_id, &mut ErrorSink::fail_fast())
+}
+
+/// Elaborate like [`elaborate_with_features`], and also return the
+/// [`SourceMap`] from the bundle's constructs and predicates back to the
+/// source text they were written in.
+pub fn elaborate_with_source_map(
+    root_path: &Path,
+    provider: &dyn SourceProvider,
+    features: &BTreeSet<String>,
+) -> Result<(Value, SourceMap), ElabError> {
+    // Passes 0+1: parse all files in the import graph
+    let (constructs, bundle_id) =
+        pass1_bundle::load_bundle_with_features(root_path, provider, features)?;
+
+    let constructs = check_constructs(constructs, &mut ErrorSink::fail_fast())?;
+    let bundle = pass6_serialize::serialize(&constructs, &bundle_id);
+    let map = source_map::build(&constructs, &bundle);
+    Ok((bundle, map))
+}
+
+/// Elaborate like [`elaborate_with_provider`], but keep checking after an
+/// error in passes 3-5 and return up to `max_errors` errors.
+///
+/// Passes 0-2 still stop at their first error. Within passes 3-5 each
+/// construct is checked on its own, and elaboration ends after the first
+/// pass that reported anything, so errors caused by an earlier failure are
+/// not shown. Expression type-checking (Pass 4b) and Pass 5 count as one
+/// step since neither changes the constructs the other sees.
+pub fn elaborate_recovering(
+    root_path: &Path,
+    provider: &dyn SourceProvider,
+    max_errors: usize,
+) -> Result<Value, Vec<ElabError>> {
+    // Passes 0+1: parse all files in the import graph
+    let (constructs, bundle_id) =
+        pass1_bundle::load_bundle_with_features(root_path, provider, &BTreeSet::new())
+            .map_err(|e| vec![e])?;
+
+    let mut sink = ErrorSink::new(max_errors);
+    elaborate_constructs(constructs, &bundle_id, &mut sink).map_err(|e| sink.into_errors(e))
+}
+
+/// Passes 2-6 over the parsed constructs.
+fn elaborate_constructs(
+    constructs: Vec<RawConstruct>,
+    bundle_id: &str,
+    sink: &mut ErrorSink,
+) -> Result<Value, ElabError> {
+    let constructs = check_constructs(constructs, sink)?;
+    Ok(pass6_serialize::serialize(&constructs, bundle_id))
+}
+
+/// Passes 2-5 over the parsed constructs, returning them ready for
+/// serialization. Errors reported on a construct's declaration line are
+/// given that construct's header span.
+fn check_constructs(
+    constructs: Vec<RawConstruct>,
+    sink: &mut ErrorSink,
+) -> Result<Vec<RawConstruct>, ElabError> {
+    let mut headers = HashMap::new();
+    collect_headers(&constructs, &mut headers);
+    let locate = |e: &mut ElabError| {
+        if e.span.is_none() {
+            e.span = headers.get(&(e.file.clone(), e.line)).copied();
+        }
+    };
+
+    let result = run_passes(constructs, sink).map_err(|mut e| {
+        locate(&mut e);
+        e
+    });
+    sink.update(locate);
+    result
+}
+
+/// Map (file, line) to the header span of the construct declared there,
+/// including constructs inside overrides and feature gates.
+fn collect_headers(constructs: &[RawConstruct], headers: &mut HashMap<(String, u32), Span>) {
+    for c in constructs {
+        let prov = c.prov();
+        headers
+            .entry((prov.file.clone(), prov.line))
+            .or_insert(prov.span);
+        match c {
+            RawConstruct::Override {
+                construct, base, ..
+            } => {
+                collect_headers(std::slice::from_ref(construct.as_ref()), headers);
+                if let Some(base) = base {
+                    collect_headers(std::slice::from_ref(base.as_ref()), headers);
+                }
+            }
+            RawConstruct::Gated { construct, .. } => {
+                collect_headers(std::slice::from_ref(construct.as_ref()), headers);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn run_passes(
+    constructs: Vec<RawConstruct>,
+    sink: &mut ErrorSink,
+) -> Result<Vec<RawConstruct>, ElabError> {
     // Pass 2: construct indexing
     let index = pass2_index::build_index(&constructs)?;
 
     // Pass 3: type environment
-    let type_env = pass3_types::build_type_env(&constructs, &index)?;
+    let type_env = pass3_types::build_type_env_recovering(&constructs, &index, sink)?;
+    sink.checkpoint()?;
 
     // Pass 4: resolve types in all constructs (TypeRef -> concrete BaseType)
-    let constructs = pass4_typecheck::resolve_types(constructs, &type_env)?;
+    let constructs = pass4_typecheck::resolve_types_recovering(constructs, &type_env, sink)?;
+    sink.checkpoint()?;
 
     // Pass 4 (continued): expression type-checking
-    pass4_typecheck::type_check_rules(&constructs)?;
+    pass4_typecheck::type_check_rules(&constructs, sink)?;
 
-    // Pass 5: validation
-    pass5_validate::validate(&constructs, &index)?;
+    // Pass 5: validation. Transition checks assume the entities and
+    // operations they cover are otherwise valid.
+    pass5_validate::validate(&constructs, &index, sink)?;
+    sink.checkpoint()?;
     pass5_validate::validate_operation_transitions(&constructs, &index)?;
-
-    // Pass 6: serialization
-    let bundle = pass6_serialize::serialize(&constructs, &bundle_id);
-    Ok(bundle)
+    Ok(constructs)
 }
 
 #[cfg(test)]
@@ -136,4 +242,278 @@ mod tests {
         // Should have both facts: rate (from import) and score (from main)
         assert_eq!(constructs.len(), 2);
     }
+
+    const OVERRIDE_BASE: &str = r#"persona clerk
+persona auditor
+
+fact is_active {
+  type:   Bool
+  source: "svc.active"
+}
+
+entity Order {
+  states:  [draft, submitted]
+  initial: draft
+  transitions: [(draft, submitted), (submitted, draft)]
+}
+
+rule account_active {
+  stratum: 0
+  when:    is_active = true
+  produce: verdict account_active { payload: Bool = true }
+}
+
+operation submit_order {
+  allowed_personas: [clerk]
+  precondition:     verdict_present(account_active)
+  effects:          [(Order, draft, submitted)]
+  error_contract:   [precondition_failed]
+}
+"#;
+
+    fn elaborate_variant(variant: &str) -> Result<Value, ElabError> {
+        let mut files = HashMap::new();
+        files.insert(
+            PathBuf::from("/contract/base.tenor"),
+            OVERRIDE_BASE.to_string(),
+        );
+        files.insert(
+            PathBuf::from("/contract/variant.tenor"),
+            variant.to_string(),
+        );
+        let provider = InMemoryProvider::new(files);
+        elaborate_with_provider(std::path::Path::new("/contract/variant.tenor"), &provider)
+    }
+
+    fn construct<'a>(bundle: &'a Value, id: &str) -> &'a Value {
+        bundle["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["id"] == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn elaborate_in_memory_override_replaces_base_constructs() {
+        let bundle = elaborate_variant(
+            "extends \"base.tenor\"\n\n\
+             override rule account_active {\n  stratum: 0\n  when:    is_active = false\n  \
+             produce: verdict account_active { payload: Bool = true }\n}\n\n\
+             override operation submit_order {\n  allowed_personas: [auditor]\n  \
+             precondition:     verdict_present(account_active)\n  \
+             effects:          [(Order, draft, submitted)]\n  \
+             error_contract:   [precondition_failed]\n}\n",
+        )
+        .unwrap();
+        assert_eq!(bundle["id"], "variant");
+        let rules: Vec<_> = bundle["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|c| c["kind"] == "Rule")
+            .collect();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["provenance"]["file"], "variant.tenor");
+        let op = construct(&bundle, "submit_order");
+        assert_eq!(op["allowed_personas"], serde_json::json!(["auditor"]));
+        assert_eq!(op["provenance"]["file"], "variant.tenor");
+        // Inherited constructs keep the base contract's provenance
+        assert_eq!(
+            construct(&bundle, "is_active")["provenance"]["file"],
+            "base.tenor"
+        );
+    }
+
+    #[test]
+    fn elaborate_in_memory_override_must_keep_operation_effects() {
+        let err = elaborate_variant(
+            "extends \"base.tenor\"\n\n\
+             override operation submit_order {\n  allowed_personas: [clerk]\n  \
+             precondition:     verdict_present(account_active)\n  \
+             effects:          [(Order, submitted, draft)]\n  \
+             error_contract:   [precondition_failed]\n}\n",
+        )
+        .unwrap_err();
+        assert_eq!(err.pass, 5);
+        assert_eq!(err.field.as_deref(), Some("effects"));
+        assert!(err.message.contains("(Order, draft, submitted)"));
+    }
+
+    #[test]
+    fn elaborate_in_memory_override_requires_extends() {
+        let err = elaborate_variant(
+            "import \"base.tenor\"\n\n\
+             override rule account_active {\n  stratum: 0\n  when:    is_active = false\n  \
+             produce: verdict account_active { payload: Bool = true }\n}\n",
+        )
+        .unwrap_err();
+        assert_eq!(err.pass, 1);
+        assert!(err.message.contains("requires an extends declaration"));
+    }
+
+    #[test]
+    fn elaborate_in_memory_redeclaring_base_construct_without_override_fails() {
+        let err = elaborate_variant(
+            "extends \"base.tenor\"\n\n\
+             rule account_active {\n  stratum: 0\n  when:    is_active = false\n  \
+             produce: verdict account_active { payload: Bool = true }\n}\n",
+        )
+        .unwrap_err();
+        assert_eq!(err.pass, 1);
+        assert!(err.message.contains("duplicate Rule id 'account_active'"));
+    }
+
+    fn elaborate_gated(features: &[&str]) -> Result<Value, ElabError> {
+        let mut files = HashMap::new();
+        files.insert(
+            PathBuf::from("/contract/main.tenor"),
+            "fact is_active {\n  type:   Bool\n  source: \"svc.active\"\n}\n\n\
+             when feature(\"eu_rules\") rule eu_active {\n  stratum: 0\n  \
+             when:    is_active = true\n  produce: verdict eu_active { payload: Bool = true }\n}\n\n\
+             when feature(\"beta\") import \"beta.tenor\"\n"
+                .to_string(),
+        );
+        files.insert(
+            PathBuf::from("/contract/beta.tenor"),
+            "fact beta_score {\n  type:   Int(min: 0, max: 100)\n  source: \"svc.score\"\n}\n"
+                .to_string(),
+        );
+        let provider = InMemoryProvider::new(files);
+        let features = features.iter().map(|f| f.to_string()).collect();
+        elaborate_with_features(
+            std::path::Path::new("/contract/main.tenor"),
+            &provider,
+            &features,
+        )
+    }
+
+    fn ids(bundle: &Value) -> Vec<&str> {
+        bundle["constructs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn elaborate_in_memory_drops_constructs_of_disabled_features() {
+        let bundle = elaborate_gated(&[]).unwrap();
+        assert_eq!(ids(&bundle), vec!["is_active"]);
+    }
+
+    #[test]
+    fn elaborate_in_memory_includes_constructs_of_enabled_features() {
+        let bundle = elaborate_gated(&["eu_rules"]).unwrap();
+        assert_eq!(ids(&bundle), vec!["is_active", "eu_active"]);
+
+        let bundle = elaborate_gated(&["eu_rules", "beta"]).unwrap();
+        assert_eq!(ids(&bundle), vec!["beta_score", "is_active", "eu_active"]);
+    }
+
+    #[test]
+    fn elaborate_in_memory_rejects_guarded_extends() {
+        let err = elaborate_variant("when feature(\"eu\") extends \"base.tenor\"\n").unwrap_err();
+        assert_eq!(err.pass, 0);
+        assert!(err
+            .message
+            .contains("'extends' cannot be guarded by a feature"));
+    }
+
+    const MULTI_ERROR: &str = r#"fact is_active {
+  type:   Bool
+  source: "svc.active"
+}
+
+entity Order {
+  states:  [draft, submitted]
+  initial: archived
+  transitions: [(draft, submitted)]
+}
+
+entity Invoice {
+  states:  [open, paid]
+  initial: closed
+  transitions: [(open, paid)]
+}
+
+rule account_active {
+  stratum: 0
+  when:    is_enabled = true
+  produce: verdict account_active { payload: Bool = true }
+}
+"#;
+
+    fn elaborate_recovering_source(source: &str, max_errors: usize) -> Vec<ElabError> {
+        let mut files = HashMap::new();
+        files.insert(PathBuf::from("/contract/main.tenor"), source.to_string());
+        let provider = InMemoryProvider::new(files);
+        elaborate_recovering(Path::new("/contract/main.tenor"), &provider, max_errors)
+            .expect_err("elaboration should fail")
+    }
+
+    #[test]
+    fn elaborate_recovering_reports_every_failing_construct() {
+        let errors = elaborate_recovering_source(MULTI_ERROR, 10);
+        let lines: Vec<(u8, u32)> = errors.iter().map(|e| (e.pass, e.line)).collect();
+        assert_eq!(lines, vec![(4, 20), (5, 8), (5, 14)], "{:#?}", errors);
+    }
+
+    #[test]
+    fn elaborate_recovering_first_error_matches_fail_fast() {
+        let mut files = HashMap::new();
+        files.insert(
+            PathBuf::from("/contract/main.tenor"),
+            MULTI_ERROR.to_string(),
+        );
+        let provider = InMemoryProvider::new(files);
+        let first = elaborate_with_provider(Path::new("/contract/main.tenor"), &provider)
+            .expect_err("elaboration should fail");
+        assert_eq!(elaborate_recovering_source(MULTI_ERROR, 10)[0], first);
+    }
+
+    #[test]
+    fn elaborate_recovering_stops_at_the_error_limit() {
+        let errors = elaborate_recovering_source(MULTI_ERROR, 2);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[1].line, 8);
+    }
+
+    #[test]
+    fn elaborate_recovering_stops_after_a_failing_type_pass() {
+        // The unknown type fails Pass 4a; the bad initial state is a Pass 5
+        // error and is not reported until the type is fixed.
+        let source = MULTI_ERROR.replace("type:   Bool", "type:   Missing");
+        let errors = elaborate_recovering_source(&source, 10);
+        assert_eq!(errors.len(), 1, "{:#?}", errors);
+        assert_eq!(errors[0].pass, 4);
+        assert!(errors[0].message.contains("Missing"));
+    }
+
+    #[test]
+    fn elaborate_recovering_never_panics_on_truncated_sources() {
+        // Every line prefix of a valid contract either elaborates or yields
+        // at least one error.
+        let lines: Vec<&str> = OVERRIDE_BASE.lines().collect();
+        for n in 0..=lines.len() {
+            let mut files = HashMap::new();
+            files.insert(PathBuf::from("/contract/main.tenor"), lines[..n].join("\n"));
+            let provider = InMemoryProvider::new(files);
+            if let Err(errors) =
+                elaborate_recovering(Path::new("/contract/main.tenor"), &provider, 10)
+            {
+                assert!(!errors.is_empty(), "empty error list for {} lines", n);
+            }
+        }
+    }
+
+    #[test]
+    fn errors_on_a_declaration_line_carry_the_header_span() {
+        let source = "type Left {\n  right: Right\n}\n\n  type Right {\n  left: Left\n}\n";
+        let errors = elaborate_recovering_source(source, 10);
+        assert_eq!(errors.len(), 1, "{:#?}", errors);
+        assert_eq!(errors[0].line, 5);
+        assert_eq!(errors[0].span, Some(Span { col: 3, len: 10 }));
+    }
 }
@@ -1,3 +1,4 @@
+use crate::ast::Span;
 use serde::{Deserialize, Serialize};
 
 /// An elaboration error. Matches the expected-error.json format exactly.
@@ -12,6 +13,10 @@ pub struct ElabError {
     pub field: Option<String>,
     pub file: String,
     pub line: u32,
+    /// Where on `line` the error is, when known. Not part of the
+    /// expected-error.json format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
     pub message: String,
 }
 
@@ -32,10 +37,16 @@ impl ElabError {
             field: field.map(str::to_owned),
             file: file.to_owned(),
             line,
+            span: None,
             message: message.into(),
         }
     }
 
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
     pub fn lex(file: &str, line: u32, message: impl Into<String>) -> Self {
         ElabError::new(0, None, None, None, file, line, message)
     }
@@ -44,6 +55,35 @@ impl ElabError {
         ElabError::new(0, None, None, None, file, line, message)
     }
 
+    /// The stable error code (see [`crate::error_codes`]). Derived from
+    /// the pass, and for Pass 5 from the construct kind.
+    pub fn code(&self) -> &'static str {
+        match (self.pass, self.construct_kind.as_deref()) {
+            (0, _) => "TEN-E-0001",
+            (1, _) => "TEN-E-0010",
+            (2, _) => "TEN-E-0020",
+            (3, _) => "TEN-E-0030",
+            (4, _) => "TEN-E-0040",
+            (5, Some("Entity")) => "TEN-E-0050",
+            (5, Some("Rule")) => "TEN-E-0051",
+            (5, Some("Operation")) => "TEN-E-0052",
+            (5, Some("Flow")) => "TEN-E-0053",
+            (5, Some("Fact" | "Source")) => "TEN-E-0054",
+            (5, Some("System")) => "TEN-E-0055",
+            (5, Some("Test" | "Assert")) => "TEN-E-0056",
+            (6, _) => "TEN-E-0060",
+            _ => "TEN-E-0059",
+        }
+    }
+
+    /// [`to_json_value`](Self::to_json_value) plus the stable `code`. Used
+    /// for CLI and server output; conformance fixtures use the plain form.
+    pub fn to_json_value_with_code(&self) -> serde_json::Value {
+        let mut value = self.to_json_value();
+        value["code"] = serde_json::json!(self.code());
+        value
+    }
+
     /// Serialize to JSON matching the expected-error.json format.
     /// The format always includes all fields (null for missing), not skip_serializing_if.
     pub fn to_json_value(&self) -> serde_json::Value {
@@ -58,3 +98,71 @@ impl ElabError {
         })
     }
 }
+
+/// Collects elaboration errors so a pass can keep checking the remaining
+/// constructs after one fails.
+///
+/// [`report`](Self::report) hands the error back once `max_errors` is
+/// reached, so passes propagate it with `?` and stop. A limit of 1 is
+/// fail-fast: the first error is returned as-is and nothing is collected.
+#[derive(Debug)]
+pub struct ErrorSink {
+    errors: Vec<ElabError>,
+    max_errors: usize,
+}
+
+impl ErrorSink {
+    pub fn new(max_errors: usize) -> Self {
+        ErrorSink {
+            errors: Vec::new(),
+            max_errors: max_errors.max(1),
+        }
+    }
+
+    pub fn fail_fast() -> Self {
+        ErrorSink::new(1)
+    }
+
+    /// Record `e`, or return it if it is the last error the limit allows.
+    /// An error identical to one already recorded is dropped.
+    pub fn report(&mut self, e: ElabError) -> Result<(), ElabError> {
+        if self.errors.contains(&e) {
+            return Ok(());
+        }
+        if self.errors.len() + 1 >= self.max_errors {
+            return Err(e);
+        }
+        self.errors.push(e);
+        Ok(())
+    }
+
+    /// [`report`](Self::report) the error of `result`, if any. Returns the
+    /// value on success and `None` once the error has been recorded.
+    pub fn check<T>(&mut self, result: Result<T, ElabError>) -> Result<Option<T>, ElabError> {
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(e) => self.report(e).map(|()| None),
+        }
+    }
+
+    /// End of a pass: if anything was reported, return the most recent
+    /// error so elaboration stops before later passes see the failure.
+    pub fn checkpoint(&mut self) -> Result<(), ElabError> {
+        match self.errors.pop() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Apply `f` to every recorded error.
+    pub fn update(&mut self, f: impl FnMut(&mut ElabError)) {
+        self.errors.iter_mut().for_each(f);
+    }
+
+    /// Every recorded error in report order, followed by `last` (the error
+    /// that stopped elaboration).
+    pub fn into_errors(mut self, last: ElabError) -> Vec<ElabError> {
+        self.errors.push(last);
+        self.errors
+    }
+}
@@ -0,0 +1,414 @@
+//! Stable error codes shared by every Tenor crate.
+//!
+//! Each error type maps its variants onto a code of the form `TEN-E-NNNN`
+//! (`ElabError::code`, `EvalError::code`, `AnalysisError::code`, and the
+//! HTTP server's error bodies). Codes never change meaning once published;
+//! scripts should match on the code, not the message. [`CATALOG`] holds
+//! the description and common fixes for each code, as printed by
+//! `tenor explain-error`.
+//!
+//! Ranges:
+//!
+//! - `TEN-E-00xx` -- elaboration, by pass (and construct kind for Pass 5)
+//! - `TEN-E-01xx` -- evaluation
+//! - `TEN-E-02xx` -- static analysis
+//! - `TEN-E-03xx` -- HTTP server
+
+/// A catalog entry for one error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCodeInfo {
+    /// The code, e.g. `"TEN-E-0050"`.
+    pub code: &'static str,
+    /// Short title.
+    pub title: &'static str,
+    /// What the error means and when it is raised.
+    pub description: &'static str,
+    /// Common fixes, most likely first.
+    pub fixes: &'static [&'static str],
+}
+
+/// Look up a code in the catalog. Matching is case-insensitive.
+pub fn lookup(code: &str) -> Option<&'static ErrorCodeInfo> {
+    CATALOG.iter().find(|e| e.code.eq_ignore_ascii_case(code))
+}
+
+/// Every published error code, in code order.
+pub const CATALOG: &[ErrorCodeInfo] = &[
+    // ── Elaboration ─────────────────────────────────────────────────
+    ErrorCodeInfo {
+        code: "TEN-E-0001",
+        title: "syntax error",
+        description: "The source could not be lexed or parsed (Pass 0). The message names the unexpected token or character.",
+        fixes: &[
+            "Check the reported line for a missing brace, colon or comma.",
+            "Check that keywords and construct names are spelled correctly.",
+            "Quote string literals with double quotes.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0010",
+        title: "bundle assembly error",
+        description: "Imports could not be resolved or combined into one bundle (Pass 1): a missing or unreadable file, an import cycle, a construct id declared in two files, or an invalid `extends`/`override`/`when feature(...)`.",
+        fixes: &[
+            "Check that import paths are relative to the importing file or listed in tenor.toml.",
+            "Break the import cycle by moving shared constructs into a separate file.",
+            "Rename one of the duplicated constructs, or use `override` in an extending contract.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0020",
+        title: "duplicate construct id",
+        description: "Two constructs of the same kind share an id within one file (Pass 2).",
+        fixes: &["Rename one of the constructs; ids must be unique per construct kind."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0030",
+        title: "type declaration error",
+        description: "A named type could not be resolved (Pass 3), usually because TypeDecls refer to each other in a cycle.",
+        fixes: &[
+            "Break the TypeDecl cycle; record types cannot contain themselves.",
+            "Check that every referenced type name is declared.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0040",
+        title: "type error",
+        description: "An expression is ill-typed (Pass 4): an unknown type reference, incompatible comparison operands, or an arithmetic result outside the declared range.",
+        fixes: &[
+            "Compare values of the same type, or of Int and Decimal.",
+            "Widen the declared verdict payload range to contain the computed product.",
+            "Check that the referenced fact or type exists.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0050",
+        title: "invalid entity",
+        description: "An Entity is structurally invalid (Pass 5): an initial state or transition endpoint not in `states`, or a cycle in the entity hierarchy.",
+        fixes: &[
+            "Add the missing state to `states`, or fix the misspelled state name.",
+            "Remove the `parent` link that closes the hierarchy cycle.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0051",
+        title: "invalid rule",
+        description: "A Rule is invalid (Pass 5): a negative stratum, a reference to a verdict no rule produces, a stratum ordering violation, or a verdict produced by two rules.",
+        fixes: &[
+            "Place the rule at a higher stratum than every rule whose verdict it reads.",
+            "Check the verdict name against the producing rule.",
+            "Produce each verdict type from exactly one rule.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0052",
+        title: "invalid operation",
+        description: "An Operation is invalid (Pass 5): empty `allowed_personas`, duplicate outcome labels, effects on undeclared entities or transitions, or an override that changes effects or outcomes.",
+        fixes: &[
+            "List at least one persona in `allowed_personas`.",
+            "Declare each effect's transition on the target Entity.",
+            "Keep an overriding operation's effects and outcomes identical to the base.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0053",
+        title: "invalid flow",
+        description: "A Flow is invalid (Pass 5): an undeclared entry or target step, a missing failure handler, unhandled operation outcomes, a step cycle, or parallel branches that affect the same entity.",
+        fixes: &[
+            "Declare the referenced step, or fix the step name.",
+            "Give every OperationStep an `on_failure` handler.",
+            "Route every outcome of the operation to a step or terminal.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0054",
+        title: "invalid fact or source",
+        description: "A Fact or Source is invalid (Pass 5): a fact references an undeclared source, or a source uses an unknown protocol or lacks a required field.",
+        fixes: &[
+            "Declare the Source the fact refers to.",
+            "Use a known protocol tag or an `x_` extension tag.",
+            "Add the field the protocol requires (see the message).",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0055",
+        title: "invalid system",
+        description: "A System is invalid (Pass 5): no members, duplicate or nested members, shared personas or entities naming non-members, or a trigger cycle.",
+        fixes: &[
+            "Declare every contract referenced in shared_personas, shared_entities and triggers as a member.",
+            "Remove the trigger that closes the cycle.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0056",
+        title: "invalid test or assertion",
+        description: "A Test or Assert is invalid or does not hold (Pass 5): it gives an undeclared fact or an ill-typed value, expects a verdict no rule produces, or asserts a property the entity violates.",
+        fixes: &[
+            "Check fact and verdict names in the test against the contract.",
+            "Fix the value to match the fact's declared type and range.",
+            "Fix the entity's transitions so the asserted property holds.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0059",
+        title: "validation error",
+        description: "A construct failed Pass 5 validation in a way not covered by a more specific code.",
+        fixes: &["Read the message and the reported construct and field."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0060",
+        title: "serialization error",
+        description: "The validated contract could not be serialized to interchange JSON (Pass 6). This indicates an elaborator bug.",
+        fixes: &["Report the contract that triggers it."],
+    },
+    // ── Evaluation ──────────────────────────────────────────────────
+    ErrorCodeInfo {
+        code: "TEN-E-0100",
+        title: "missing fact",
+        description: "A fact the contract declares was not provided and has no default.",
+        fixes: &[
+            "Provide the fact in the facts JSON.",
+            "Declare a default for the fact in the contract.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0101",
+        title: "fact type mismatch",
+        description: "A provided fact value does not match the fact's declared type or range.",
+        fixes: &[
+            "Check the value's JSON type: Decimal and Money amounts are strings.",
+            "Keep Int values within the declared min and max.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0102",
+        title: "numeric overflow",
+        description: "Arithmetic overflowed during evaluation.",
+        fixes: &["Narrow the declared ranges, or check the fact values for outliers."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0103",
+        title: "invalid operator",
+        description: "The bundle uses an operator the evaluator does not define for its operands.",
+        fixes: &["Re-elaborate the contract; hand-edited bundles may carry invalid operators."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0104",
+        title: "unknown fact",
+        description: "An expression references a fact that is not in the fact set.",
+        fixes: &["Re-elaborate the contract so the bundle and its fact declarations agree."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0105",
+        title: "unknown verdict",
+        description: "An expression references a verdict that has not been produced.",
+        fixes: &["Re-elaborate the contract; the elaborator orders rules by stratum."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0106",
+        title: "invalid bundle",
+        description: "The interchange bundle could not be deserialized: a missing field, a malformed construct, or an unsupported interchange version.",
+        fixes: &[
+            "Validate the bundle with `tenor validate`.",
+            "Upgrade older bundles with `tenor upgrade-bundle`.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0107",
+        title: "strict mode violation",
+        description: "Strict deserialization found an unknown field or construct kind. The error carries the JSON pointer of the offending field.",
+        fixes: &[
+            "Fix the misspelled field at the reported pointer.",
+            "Load the bundle in the default (lenient) mode if it targets a newer interchange minor version.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0108",
+        title: "evaluation type error",
+        description: "A predicate combined values of incompatible types at evaluation time.",
+        fixes: &["Check that fact values match their declared types."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0109",
+        title: "list too long",
+        description: "A List fact has more elements than its declared max.",
+        fixes: &["Trim the list, or raise the declared max in the contract."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0110",
+        title: "invalid enum value",
+        description: "An Enum fact value is not one of the declared variants.",
+        fixes: &["Use one of the variants listed in the message; matching is case-sensitive."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0111",
+        title: "not a record",
+        description: "A field access was applied to a value that is not a Record.",
+        fixes: &["Provide the fact as a JSON object with the declared fields."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0112",
+        title: "unbound variable",
+        description: "An expression references a quantifier variable that is not in scope.",
+        fixes: &["Re-elaborate the contract; hand-edited bundles may reference unbound variables."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0113",
+        title: "flow execution error",
+        description: "A flow could not run to completion: the persona may not start it, an entity is in the wrong state, the step limit was exceeded, or a referenced step or operation is missing.",
+        fixes: &[
+            "Check the persona against the flow's entry step.",
+            "Check the entity states passed in against the operations' transitions.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0114",
+        title: "unsupported features",
+        description: "The bundle's `requires` list names evaluator features this evaluator does not implement.",
+        fixes: &["Upgrade the evaluator, or elaborate the contract without the listed features."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0115",
+        title: "snapshot mismatch",
+        description: "A persisted flow snapshot was taken against a different bundle than the one loaded.",
+        fixes: &["Resume the flow with the bundle it was started with, or migrate the snapshot."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0116",
+        title: "verdict payload mismatch",
+        description: "A rule produced a payload that does not match its declared payload type (payload validation only).",
+        fixes: &["Fix the rule's produce clause, or widen the declared payload type."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0117",
+        title: "precision loss",
+        description: "A Decimal or Money fact value has more integer or fractional digits than its declared precision and scale allow.",
+        fixes: &["Supply the value at the declared scale, widen the type's precision or scale, or declare a rounding mode (`rounding: half_even` or `rounding: half_up`) on the type."],
+    },
+    // ── Analysis ────────────────────────────────────────────────────
+    ErrorCodeInfo {
+        code: "TEN-E-0200",
+        title: "invalid bundle for analysis",
+        description: "The bundle could not be loaded for static analysis.",
+        fixes: &["Validate the bundle with `tenor validate`, or re-elaborate the contract."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0201",
+        title: "missing construct field",
+        description: "A construct in the bundle lacks a field the analyzer requires.",
+        fixes: &["Re-elaborate the contract; hand-edited bundles may drop required fields."],
+    },
+    // ── HTTP server ─────────────────────────────────────────────────
+    ErrorCodeInfo {
+        code: "TEN-E-0300",
+        title: "bad request",
+        description: "The request body is missing a field or is malformed.",
+        fixes: &["Check the request body against the endpoint's documented fields."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0301",
+        title: "authentication required",
+        description: "The server requires an API key and the request carried none.",
+        fixes: &["Send `Authorization: Bearer <key>` or `X-API-Key: <key>`."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0302",
+        title: "forbidden",
+        description: "The API key is invalid, or the request is not permitted.",
+        fixes: &["Check the key against TENOR_API_KEY on the server."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0303",
+        title: "not found",
+        description: "The route, contract, flow or timer does not exist.",
+        fixes: &["List loaded contracts with `GET /contracts`."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0304",
+        title: "conflict",
+        description: "The request conflicts with the current server state.",
+        fixes: &["Fetch the current state and retry."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0305",
+        title: "unprocessable request",
+        description: "The request was well-formed but could not be processed, e.g. a source that fails to elaborate or facts that fail to evaluate.",
+        fixes: &["Read the `error` message for the underlying cause."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0306",
+        title: "rate limit exceeded",
+        description: "The client exceeded the per-IP request rate.",
+        fixes: &["Wait `retry_after` seconds before retrying."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0307",
+        title: "internal server error",
+        description: "The server failed while handling the request.",
+        fixes: &["Check the server log; report the request if it persists."],
+    },
+    ErrorCodeInfo {
+        code: "TEN-E-0308",
+        title: "contract version mismatch",
+        description: "The request pinned a contract version with `If-Match` or `bundle_digest`, and the loaded contract has a different etag.",
+        fixes: &["Fetch the current etag from `GET /contracts`, check the contract change against your integration, and retry with the new etag."],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_and_ordered() {
+        for pair in CATALOG.windows(2) {
+            assert!(
+                pair[0].code < pair[1].code,
+                "{} >= {}",
+                pair[0].code,
+                pair[1].code
+            );
+        }
+    }
+
+    #[test]
+    fn codes_are_well_formed() {
+        for entry in CATALOG {
+            let digits = entry.code.strip_prefix("TEN-E-").unwrap();
+            assert_eq!(digits.len(), 4, "{}", entry.code);
+            assert!(digits.chars().all(|c| c.is_ascii_digit()), "{}", entry.code);
+            assert!(!entry.fixes.is_empty(), "{} has no fixes", entry.code);
+        }
+    }
+
+    #[test]
+    fn elab_error_codes_are_catalogued() {
+        let kinds = [
+            None,
+            Some("Entity"),
+            Some("Rule"),
+            Some("Operation"),
+            Some("Flow"),
+            Some("Fact"),
+            Some("Source"),
+            Some("System"),
+            Some("Test"),
+            Some("Assert"),
+            Some("Persona"),
+        ];
+        for pass in 0..=6 {
+            for kind in kinds {
+                let e = crate::ElabError::new(pass, kind, None, None, "t.tenor", 1, "m");
+                assert!(lookup(e.code()).is_some(), "{}", e.code());
+            }
+        }
+        let e = crate::ElabError::new(5, Some("Flow"), Some("f"), None, "t.tenor", 1, "m");
+        assert_eq!(e.to_json_value_with_code()["code"], "TEN-E-0053");
+        assert!(e.to_json_value().get("code").is_none());
+    }
+
+    #[test]
+    fn lookup_is_case_insensitive() {
+        assert_eq!(lookup("ten-e-0050").unwrap().title, "invalid entity");
+        assert!(lookup("TEN-E-9999").is_none());
+    }
+}
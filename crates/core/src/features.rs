@@ -0,0 +1,443 @@
+//! Evaluator feature requirements derived from serialized constructs.
+//!
+//! Pass 6 records which optional evaluator features a bundle actually uses
+//! in the top-level `"requires"` array, so an evaluator can refuse a
+//! contract it cannot execute faithfully instead of failing mid-flow.
+//! Contracts that use only the core feature set omit the field entirely.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// A flow step failure handler compensates before routing onward.
+pub const FEATURE_COMPENSATION: &str = "compensation";
+/// Predicates convert Money amounts between currencies by a rate fact.
+pub const FEATURE_CURRENCY_CONVERSION: &str = "currency-conversion";
+/// Entities declare typed attributes that effects set.
+pub const FEATURE_ENTITY_ATTRIBUTES: &str = "entity-attributes";
+/// Entities declare a parent entity.
+pub const FEATURE_ENTITY_HIERARCHY: &str = "entity-hierarchy";
+/// A flow step failure handler escalates to another persona.
+pub const FEATURE_ESCALATION: &str = "escalation";
+/// A handoff step pauses the flow until it is resumed.
+pub const FEATURE_HANDOFF_PAUSE: &str = "handoff-pause";
+/// Operation effects create or destroy entity instances.
+pub const FEATURE_INSTANCE_LIFECYCLE: &str = "instance-lifecycle";
+/// Predicates quantify over the live instances of an entity.
+pub const FEATURE_INSTANCE_QUANTIFIERS: &str = "instance-quantifiers";
+/// Money types declare their own precision and scale.
+pub const FEATURE_MONEY_PRECISION: &str = "money-precision";
+/// Operations declare named outcomes with outcome-specific effects.
+pub const FEATURE_MULTI_OUTCOME: &str = "multi-outcome-operations";
+/// Flows fork into parallel branches with a join policy.
+pub const FEATURE_PARALLEL_STEPS: &str = "parallel-steps";
+/// Decimal or Money types declare a rounding mode.
+pub const FEATURE_ROUNDING_MODES: &str = "rounding-modes";
+/// Flow steps expire at a deadline fact.
+pub const FEATURE_STEP_TIMEOUTS: &str = "step-timeouts";
+/// Flow steps weight their outcomes or branches with probabilities.
+pub const FEATURE_STEP_WEIGHTS: &str = "step-weights";
+/// Facts reference declared Source constructs.
+pub const FEATURE_STRUCTURED_SOURCES: &str = "structured-sources";
+/// Flows invoke other flows as steps.
+pub const FEATURE_SUB_FLOWS: &str = "sub-flows";
+/// The bundle composes multiple contracts via a System construct.
+pub const FEATURE_SYSTEMS: &str = "systems";
+/// Entity transitions declare guard predicates.
+pub const FEATURE_TRANSITION_GUARDS: &str = "transition-guards";
+/// Repeated types are hoisted into a top-level type table. Never emitted by
+/// the elaborator; added by `tenor optimize`.
+pub const FEATURE_TYPE_TABLE: &str = "type-table";
+/// Decimal types declare a unit of measure.
+pub const FEATURE_UNITS_OF_MEASURE: &str = "units-of-measure";
+
+/// Every feature name a bundle may require, in lexicographic order.
+pub const ALL_FEATURES: &[&str] = &[
+    FEATURE_COMPENSATION,
+    FEATURE_CURRENCY_CONVERSION,
+    FEATURE_ENTITY_ATTRIBUTES,
+    FEATURE_ENTITY_HIERARCHY,
+    FEATURE_ESCALATION,
+    FEATURE_HANDOFF_PAUSE,
+    FEATURE_INSTANCE_LIFECYCLE,
+    FEATURE_INSTANCE_QUANTIFIERS,
+    FEATURE_MONEY_PRECISION,
+    FEATURE_MULTI_OUTCOME,
+    FEATURE_PARALLEL_STEPS,
+    FEATURE_ROUNDING_MODES,
+    FEATURE_STEP_TIMEOUTS,
+    FEATURE_STEP_WEIGHTS,
+    FEATURE_STRUCTURED_SOURCES,
+    FEATURE_SUB_FLOWS,
+    FEATURE_SYSTEMS,
+    FEATURE_TRANSITION_GUARDS,
+    FEATURE_TYPE_TABLE,
+    FEATURE_UNITS_OF_MEASURE,
+];
+
+/// Compute the sorted set of optional features used by serialized constructs.
+pub fn required_features(constructs: &[Value]) -> Vec<&'static str> {
+    let mut used = BTreeSet::new();
+    for c in constructs {
+        if any_node(c, is_instance_quantifier) {
+            used.insert(FEATURE_INSTANCE_QUANTIFIERS);
+        }
+        if any_node(c, is_convert_term) {
+            used.insert(FEATURE_CURRENCY_CONVERSION);
+        }
+        if declares_unit(c) {
+            used.insert(FEATURE_UNITS_OF_MEASURE);
+        }
+        if declares_money_precision(c) {
+            used.insert(FEATURE_MONEY_PRECISION);
+        }
+        if declares_rounding(c) {
+            used.insert(FEATURE_ROUNDING_MODES);
+        }
+        match c.get("kind").and_then(|k| k.as_str()) {
+            Some("Entity") => {
+                if c.get("attributes").is_some() {
+                    used.insert(FEATURE_ENTITY_ATTRIBUTES);
+                }
+                if c.get("parent").is_some_and(|p| !p.is_null()) {
+                    used.insert(FEATURE_ENTITY_HIERARCHY);
+                }
+                if c.get("transitions")
+                    .and_then(|t| t.as_array())
+                    .is_some_and(|t| t.iter().any(|t| t.get("guard").is_some()))
+                {
+                    used.insert(FEATURE_TRANSITION_GUARDS);
+                }
+            }
+            Some("Operation") => {
+                if c.get("effects")
+                    .and_then(|e| e.as_array())
+                    .is_some_and(|e| e.iter().any(is_lifecycle_effect))
+                {
+                    used.insert(FEATURE_INSTANCE_LIFECYCLE);
+                }
+                if c.get("outcomes")
+                    .and_then(|o| o.as_array())
+                    .is_some_and(|o| !o.is_empty())
+                {
+                    used.insert(FEATURE_MULTI_OUTCOME);
+                }
+            }
+            Some("Flow") => {
+                if let Some(steps) = c.get("steps").and_then(|s| s.as_array()) {
+                    scan_steps(steps, &mut used);
+                }
+            }
+            Some("Source") => {
+                used.insert(FEATURE_STRUCTURED_SOURCES);
+            }
+            Some("System") => {
+                used.insert(FEATURE_SYSTEMS);
+            }
+            _ => {}
+        }
+    }
+    used.into_iter().collect()
+}
+
+/// Whether an Operation effect creates or destroys an instance. Effects
+/// without a `kind` (1.0.x bundles) are state transitions.
+fn is_lifecycle_effect(effect: &Value) -> bool {
+    effect
+        .get("kind")
+        .and_then(|k| k.as_str())
+        .is_some_and(|k| k != "transition")
+}
+
+/// Whether `v` or any object nested in it satisfies `matches`.
+fn any_node(v: &Value, matches: fn(&Map<String, Value>) -> bool) -> bool {
+    match v {
+        Value::Object(m) => matches(m) || m.values().any(|v| any_node(v, matches)),
+        Value::Array(a) => a.iter().any(|v| any_node(v, matches)),
+        _ => false,
+    }
+}
+
+/// A quantifier expression over the live instances of an entity:
+/// `{"quantifier": ..., "domain": {"instances_of": "<entity>"}, ...}`.
+/// Matching the whole shape keeps a Record field named `instances_of` from
+/// counting.
+fn is_instance_quantifier(m: &Map<String, Value>) -> bool {
+    m.get("quantifier").is_some_and(Value::is_string)
+        && m.get("domain")
+            .and_then(|d| d.get("instances_of"))
+            .is_some_and(Value::is_string)
+}
+
+/// A currency conversion term: `{"convert": <term>, "rate": {"fact_ref":
+/// "<fact>"}}`. Matching the whole shape keeps a Record field named
+/// `convert` from counting.
+fn is_convert_term(m: &Map<String, Value>) -> bool {
+    m.get("convert").is_some_and(Value::is_object)
+        && m.get("rate")
+            .and_then(|r| r.get("fact_ref"))
+            .is_some_and(Value::is_string)
+}
+
+/// Whether any Decimal type nested in `v` declares a unit of measure.
+/// (Duration types carry a `unit` too, but it is not a unit of measure.)
+fn declares_unit(v: &Value) -> bool {
+    match v {
+        Value::Object(m) => {
+            (m.get("base").and_then(|b| b.as_str()) == Some("Decimal") && m.contains_key("unit"))
+                || m.values().any(declares_unit)
+        }
+        Value::Array(a) => a.iter().any(declares_unit),
+        _ => false,
+    }
+}
+
+/// Whether any Money type nested in `v` declares a precision or scale.
+fn declares_money_precision(v: &Value) -> bool {
+    match v {
+        Value::Object(m) => {
+            (m.get("base").and_then(|b| b.as_str()) == Some("Money")
+                && (m.contains_key("precision") || m.contains_key("scale")))
+                || m.values().any(declares_money_precision)
+        }
+        Value::Array(a) => a.iter().any(declares_money_precision),
+        _ => false,
+    }
+}
+
+/// Whether any Decimal or Money type nested in `v` declares a rounding mode.
+fn declares_rounding(v: &Value) -> bool {
+    match v {
+        Value::Object(m) => {
+            (matches!(
+                m.get("base").and_then(|b| b.as_str()),
+                Some("Decimal" | "Money")
+            ) && m.contains_key("rounding"))
+                || m.values().any(declares_rounding)
+        }
+        Value::Array(a) => a.iter().any(declares_rounding),
+        _ => false,
+    }
+}
+
+fn scan_steps(steps: &[Value], used: &mut BTreeSet<&'static str>) {
+    for step in steps {
+        match step.get("kind").and_then(|k| k.as_str()) {
+            Some("HandoffStep") if step.get("pause").and_then(|p| p.as_bool()) == Some(true) => {
+                used.insert(FEATURE_HANDOFF_PAUSE);
+            }
+            Some("SubFlowStep") => {
+                used.insert(FEATURE_SUB_FLOWS);
+            }
+            Some("ParallelStep") => {
+                used.insert(FEATURE_PARALLEL_STEPS);
+                if let Some(branches) = step.get("branches").and_then(|b| b.as_array()) {
+                    for branch in branches {
+                        if let Some(inner) = branch.get("steps").and_then(|s| s.as_array()) {
+                            scan_steps(inner, used);
+                        }
+                    }
+                }
+                if let Some(handler) = step.get("join").and_then(|j| j.get("on_any_failure")) {
+                    scan_handler(handler, used);
+                }
+            }
+            _ => {}
+        }
+        if let Some(handler) = step.get("on_failure") {
+            scan_handler(handler, used);
+        }
+        if let Some(timeout) = step.get("timeout") {
+            used.insert(FEATURE_STEP_TIMEOUTS);
+            if let Some(handler) = timeout.get("on_timeout") {
+                scan_handler(handler, used);
+            }
+        }
+        if step.get("weights").is_some() {
+            used.insert(FEATURE_STEP_WEIGHTS);
+        }
+    }
+}
+
+fn scan_handler(handler: &Value, used: &mut BTreeSet<&'static str>) {
+    match handler.get("kind").and_then(|k| k.as_str()) {
+        Some("Compensate") => {
+            used.insert(FEATURE_COMPENSATION);
+        }
+        Some("Escalate") => {
+            used.insert(FEATURE_ESCALATION);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn core_constructs_require_nothing() {
+        let constructs = vec![
+            json!({ "kind": "Fact", "id": "f" }),
+            json!({ "kind": "Entity", "id": "e", "states": ["a"] }),
+            json!({ "kind": "Flow", "id": "fl", "steps": [
+                { "kind": "OperationStep", "id": "s", "on_failure": { "kind": "Terminate", "outcome": "x" } }
+            ]}),
+        ];
+        assert!(required_features(&constructs).is_empty());
+    }
+
+    #[test]
+    fn nested_parallel_branch_features_are_detected() {
+        let constructs = vec![json!({ "kind": "Flow", "id": "fl", "steps": [
+            { "kind": "ParallelStep", "id": "p", "branches": [
+                { "id": "b", "steps": [
+                    { "kind": "SubFlowStep", "id": "s", "on_failure": { "kind": "Escalate", "to_persona": "m", "next": "n" } }
+                ]}
+            ], "join": { "on_any_failure": { "kind": "Compensate", "steps": [], "then": "x" } } }
+        ]})];
+        assert_eq!(
+            required_features(&constructs),
+            vec![
+                FEATURE_COMPENSATION,
+                FEATURE_ESCALATION,
+                FEATURE_PARALLEL_STEPS,
+                FEATURE_SUB_FLOWS
+            ]
+        );
+    }
+
+    #[test]
+    fn guarded_transitions_are_detected() {
+        let constructs = vec![json!({ "kind": "Entity", "id": "e", "states": ["a", "b"],
+            "transitions": [{ "from": "a", "to": "b", "guard": { "verdict_present": "ok" } }] })];
+        assert_eq!(
+            required_features(&constructs),
+            vec![FEATURE_TRANSITION_GUARDS]
+        );
+    }
+
+    #[test]
+    fn entity_attributes_are_detected() {
+        let constructs = vec![json!({ "kind": "Entity", "id": "e", "states": ["a"],
+            "attributes": { "total": { "base": "Int", "min": 0, "max": 10 } } })];
+        assert_eq!(
+            required_features(&constructs),
+            vec![FEATURE_ENTITY_ATTRIBUTES]
+        );
+    }
+
+    #[test]
+    fn instance_lifecycle_effects_are_detected() {
+        let constructs = vec![json!({ "kind": "Operation", "id": "op",
+            "effects": [{ "entity_id": "e", "kind": "create_instance" }] })];
+        assert_eq!(
+            required_features(&constructs),
+            vec![FEATURE_INSTANCE_LIFECYCLE]
+        );
+    }
+
+    #[test]
+    fn instance_quantifiers_in_branch_conditions_are_detected() {
+        let constructs = vec![json!({ "kind": "Flow", "id": "fl", "steps": [
+            { "kind": "BranchStep", "id": "b", "condition": {
+                "quantifier": "forall", "variable": "d", "domain": { "instances_of": "Delivery" },
+                "body": { "left": { "instance_state": "d" }, "op": "=", "right": { "literal": "done" } }
+            } }
+        ]})];
+        assert_eq!(
+            required_features(&constructs),
+            vec![FEATURE_INSTANCE_QUANTIFIERS]
+        );
+    }
+
+    #[test]
+    fn currency_conversions_are_detected() {
+        let constructs = vec![json!({ "kind": "Rule", "id": "r", "body": { "when": {
+            "left": { "convert": { "fact_ref": "price" }, "rate": { "fact_ref": "eur_usd" } },
+            "op": "<=", "right": { "fact_ref": "budget" }
+        } } })];
+        assert_eq!(
+            required_features(&constructs),
+            vec![FEATURE_CURRENCY_CONVERSION]
+        );
+    }
+
+    #[test]
+    fn record_fields_named_like_expression_keys_require_nothing() {
+        let constructs = vec![
+            json!({ "kind": "TypeDecl", "id": "Quote", "type": { "base": "Record", "fields": {
+                "convert": { "base": "Bool" },
+                "rate": { "base": "Decimal", "precision": 10, "scale": 4 },
+                "instances_of": { "base": "Text" }
+            } } }),
+            json!({ "kind": "Fact", "id": "quote", "type": { "base": "Record", "fields": {
+                "convert": { "base": "Bool" }
+            } } }),
+        ];
+        assert!(required_features(&constructs).is_empty());
+    }
+
+    #[test]
+    fn handoff_pause_timeouts_and_weights_are_detected() {
+        let constructs = vec![json!({ "kind": "Flow", "id": "fl", "steps": [
+            { "kind": "HandoffStep", "id": "h", "from_persona": "a", "to_persona": "b",
+              "next": "o", "pause": true,
+              "timeout": { "deadline": "due", "on_timeout": { "kind": "Escalate", "to_persona": "m", "next": "o" } } },
+            { "kind": "BranchStep", "id": "b", "weights": { "true": 0.8, "false": 0.2 } }
+        ]})];
+        assert_eq!(
+            required_features(&constructs),
+            vec![
+                FEATURE_ESCALATION,
+                FEATURE_HANDOFF_PAUSE,
+                FEATURE_STEP_TIMEOUTS,
+                FEATURE_STEP_WEIGHTS
+            ]
+        );
+
+        let unpaused = vec![json!({ "kind": "Flow", "id": "fl", "steps": [
+            { "kind": "HandoffStep", "id": "h", "from_persona": "a", "to_persona": "b", "next": "o" }
+        ]})];
+        assert!(required_features(&unpaused).is_empty());
+    }
+
+    #[test]
+    fn money_precision_and_rounding_modes_are_detected() {
+        let constructs = vec![json!({ "kind": "Fact", "id": "rate",
+            "type": { "base": "Money", "currency": "USD", "precision": 12, "scale": 4 } })];
+        assert_eq!(
+            required_features(&constructs),
+            vec![FEATURE_MONEY_PRECISION]
+        );
+
+        let rounded = vec![json!({ "kind": "Fact", "id": "fee",
+            "type": { "base": "Decimal", "precision": 10, "scale": 2, "rounding": "half_up" } })];
+        assert_eq!(required_features(&rounded), vec![FEATURE_ROUNDING_MODES]);
+
+        let plain = vec![json!({ "kind": "Fact", "id": "price",
+            "type": { "base": "Money", "currency": "USD" } })];
+        assert!(required_features(&plain).is_empty());
+    }
+
+    #[test]
+    fn all_features_sorted() {
+        let mut sorted = ALL_FEATURES.to_vec();
+        sorted.sort();
+        assert_eq!(sorted, ALL_FEATURES);
+    }
+
+    #[test]
+    fn units_of_measure_are_detected() {
+        let constructs = vec![json!({ "kind": "Fact", "id": "weight",
+            "type": { "base": "Decimal", "precision": 10, "scale": 3, "unit": "kg" } })];
+        assert_eq!(
+            required_features(&constructs),
+            vec![FEATURE_UNITS_OF_MEASURE]
+        );
+
+        let durations = vec![json!({ "kind": "Fact", "id": "wait",
+            "type": { "base": "Duration", "unit": "days", "min": 0, "max": 30 } })];
+        assert!(required_features(&durations).is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! Canonical source formatting for `.tenor` files.
+//!
+//! The formatter is deliberately layout-only: it never re-tokenizes or
+//! reorders anything, so comments, string contents, and the author's
+//! choice of key alignment survive untouched. It normalizes
+//!
+//! - indentation to two spaces per open `{`, `[`, or `(`;
+//! - trailing whitespace (removed);
+//! - blank lines (runs collapsed to one, none at start or end of file);
+//! - the final newline (exactly one).
+//!
+//! Predicate continuation lines (starting with `∧`, `∨`, `and`, or `or`)
+//! keep their offset relative to the line they continue, so hanging
+//! alignment under a `when:` value is preserved. Lines inside a block
+//! comment are left as written.
+
+/// Indentation unit for one nesting level.
+const INDENT: usize = 2;
+
+/// Format a complete `.tenor` source file.
+pub fn format_source(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut pending_blank = false;
+    for line in reindent_lines(src) {
+        if line.is_empty() {
+            pending_blank = !out.is_empty();
+            continue;
+        }
+        if pending_blank {
+            out.push('\n');
+            pending_blank = false;
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Re-indent `src` line by line.
+///
+/// Returns exactly one output line per input line (blank lines become
+/// empty strings), which lets callers format a line range in place.
+pub fn reindent_lines(src: &str) -> Vec<String> {
+    let mut depth: usize = 0;
+    let mut in_block_comment = false;
+    // Column shift applied to the most recent non-continuation line;
+    // continuation lines move by the same amount.
+    let mut shift: isize = 0;
+    let mut out = Vec::new();
+
+    for raw in src.lines() {
+        let trimmed_end = raw.trim_end();
+        let text = trimmed_end.trim_start();
+
+        if in_block_comment {
+            out.push(trimmed_end.to_string());
+            scan_line(text, &mut depth, &mut in_block_comment);
+            continue;
+        }
+        if text.is_empty() {
+            out.push(String::new());
+            continue;
+        }
+
+        let closers = text
+            .chars()
+            .take_while(|c| matches!(c, '}' | ']' | ')'))
+            .count();
+        let base = depth.saturating_sub(closers) * INDENT;
+        let old = leading_width(trimmed_end);
+        let indent = if is_continuation(text) {
+            (old as isize + shift).max(base as isize) as usize
+        } else {
+            shift = base as isize - old as isize;
+            base
+        };
+        out.push(format!("{}{}", " ".repeat(indent), text));
+        scan_line(text, &mut depth, &mut in_block_comment);
+    }
+    out
+}
+
+/// Width of leading whitespace, counting a tab as one indent level.
+fn leading_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { INDENT } else { 1 })
+        .sum()
+}
+
+fn is_continuation(text: &str) -> bool {
+    text.starts_with('∧')
+        || text.starts_with('∨')
+        || text.starts_with("and ")
+        || text.starts_with("or ")
+}
+
+/// Update bracket depth and block-comment state across one line,
+/// ignoring brackets inside strings and comments.
+fn scan_line(text: &str, depth: &mut usize, in_block_comment: &mut bool) {
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if *in_block_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                *in_block_comment = false;
+            }
+            continue;
+        }
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '/' if chars.peek() == Some(&'/') => return,
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                *in_block_comment = true;
+            }
+            '{' | '[' | '(' => *depth += 1,
+            '}' | ']' | ')' => *depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reindents_nested_blocks() {
+        let src = "entity Order {\n      states: [a, b]\ninitial: a\n  transitions: [\n(a, b)\n     ]\n}\n";
+        assert_eq!(
+            format_source(src),
+            "entity Order {\n  states: [a, b]\n  initial: a\n  transitions: [\n    (a, b)\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn collapses_blank_lines_and_trailing_whitespace() {
+        let src = "\n\npersona a   \n\n\n\npersona b\n\n\n";
+        assert_eq!(format_source(src), "persona a\n\npersona b\n");
+    }
+
+    #[test]
+    fn preserves_continuation_alignment() {
+        let src = "rule r {\n    stratum: 0\n    when:    verdict_present(a)\n           ∧ verdict_present(b)\n    produce: verdict v { payload: Bool = true }\n}\n";
+        assert_eq!(
+            format_source(src),
+            "rule r {\n  stratum: 0\n  when:    verdict_present(a)\n         ∧ verdict_present(b)\n  produce: verdict v { payload: Bool = true }\n}\n"
+        );
+    }
+
+    #[test]
+    fn ignores_brackets_in_strings_and_comments() {
+        let src =
+            "fact f {\ntype: Text // {\nsource: \"a.{b\"\n/* [\n   keep me */\n}\npersona p\n";
+        assert_eq!(
+            format_source(src),
+            "fact f {\n  type: Text // {\n  source: \"a.{b\"\n  /* [\n   keep me */\n}\npersona p\n"
+        );
+    }
+
+    #[test]
+    fn reindent_lines_is_one_to_one() {
+        let src = "a {\n\n\nb\n}";
+        assert_eq!(reindent_lines(src), vec!["a {", "", "", "  b", "}"]);
+    }
+
+    #[test]
+    fn conformance_fixtures_format_idempotently_and_elaborate_unchanged() {
+        use std::collections::HashMap;
+        use std::path::PathBuf;
+
+        fn strip_provenance(v: &mut serde_json::Value) {
+            match v {
+                serde_json::Value::Object(map) => {
+                    map.remove("provenance");
+                    map.values_mut().for_each(strip_provenance);
+                }
+                serde_json::Value::Array(items) => items.iter_mut().for_each(strip_provenance),
+                _ => {}
+            }
+        }
+
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../conformance/positive");
+        let dir = dir.canonicalize().expect("conformance/positive");
+        let mut originals = HashMap::new();
+        let mut formatted = HashMap::new();
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|e| e == "tenor") {
+                let src = std::fs::read_to_string(&path).unwrap();
+                let once = format_source(&src);
+                assert_eq!(
+                    format_source(&once),
+                    once,
+                    "not idempotent: {}",
+                    path.display()
+                );
+                formatted.insert(path.clone(), once);
+                originals.insert(path, src);
+            }
+        }
+
+        let provider = crate::source::InMemoryProvider::new(formatted);
+        for path in originals.keys() {
+            let Ok(mut expected) = crate::elaborate::elaborate(path) else {
+                continue;
+            };
+            let mut actual = crate::elaborate::elaborate_with_provider(path, &provider)
+                .unwrap_or_else(|e| panic!("{} failed after formatting: {:?}", path.display(), e));
+            strip_provenance(&mut expected);
+            strip_provenance(&mut actual);
+            assert_eq!(expected, actual, "{} changed meaning", path.display());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ast::{SourceRange, Span};
 use crate::error::ElabError;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +21,8 @@ pub enum Token {
     Colon,
     Comma,
     Dot,
+    /// `@`, opening a construct annotation
+    At,
     // Comparison operators
     Eq,
     Neq,
@@ -44,15 +47,58 @@ pub enum Token {
 pub struct Spanned {
     pub token: Token,
     pub line: u32,
+    /// 1-based column of the token's first character, counted in chars.
+    pub col: u32,
+    /// Length of the token's source text in chars, quotes and escapes
+    /// included. 0 for `Eof`.
+    pub len: u32,
+    /// Byte offsets of the token's source text.
+    pub range: SourceRange,
+}
+
+impl Spanned {
+    fn new(token: Token, line: u32, col: u32) -> Self {
+        // The length is filled in once the lexer has moved past the token
+        Spanned {
+            token,
+            line,
+            col,
+            len: 0,
+            range: SourceRange::default(),
+        }
+    }
+}
+
+/// Set the length and byte range of the token pushed last, which starts at
+/// char `start` and ends at char `pos`. `offsets` maps char indices to byte
+/// offsets.
+fn close_span(tokens: &mut [Spanned], start: usize, pos: usize, offsets: &[usize]) {
+    if let Some(last) = tokens.last_mut() {
+        if last.len == 0 {
+            last.len = (pos - start) as u32;
+            last.range = SourceRange {
+                start: offsets[start] as u32,
+                end: offsets[pos] as u32,
+            };
+        }
+    }
 }
 
 pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
     let mut tokens = Vec::new();
     let chars: Vec<char> = src.chars().collect();
+    let offsets: Vec<usize> = src
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(src.len()))
+        .collect();
     let mut pos = 0usize;
     let mut line: u32 = 1;
+    let mut line_start = 0usize;
+    let mut tok_start = 0usize;
 
     while pos < chars.len() {
+        close_span(&mut tokens, tok_start, pos, &offsets);
         let c = chars[pos];
 
         // Line comment
@@ -72,6 +118,7 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
                 }
                 if chars[pos] == '\n' {
                     line += 1;
+                    line_start = pos + 1;
                 }
                 if chars[pos] == '*' && pos + 1 < chars.len() && chars[pos + 1] == '/' {
                     pos += 2;
@@ -86,12 +133,15 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
         if c.is_whitespace() {
             if c == '\n' {
                 line += 1;
+                line_start = pos + 1;
             }
             pos += 1;
             continue;
         }
 
         let tok_line = line;
+        let tok_col = (pos - line_start + 1) as u32;
+        tok_start = pos;
 
         // String literal
         if c == '"' {
@@ -142,10 +192,7 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
                 s.push(sc);
                 pos += 1;
             }
-            tokens.push(Spanned {
-                token: Token::Str(s),
-                line: tok_line,
-            });
+            tokens.push(Spanned::new(Token::Str(s), tok_line, tok_col));
             continue;
         }
 
@@ -170,19 +217,13 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
                     pos += 1;
                 }
                 let s: String = chars[start..pos].iter().collect();
-                tokens.push(Spanned {
-                    token: Token::Float(s),
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Float(s), tok_line, tok_col));
             } else {
                 let s: String = chars[start..pos].iter().collect();
                 let n: i64 = s.parse().map_err(|_| {
                     ElabError::lex(filename, tok_line, format!("invalid integer '{}'", s))
                 })?;
-                tokens.push(Spanned {
-                    token: Token::Int(n),
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Int(n), tok_line, tok_col));
             }
             continue;
         }
@@ -190,51 +231,33 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
         // Operators
         match c {
             '=' => {
-                tokens.push(Spanned {
-                    token: Token::Eq,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Eq, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '<' => {
                 if pos + 1 < chars.len() && chars[pos + 1] == '=' {
-                    tokens.push(Spanned {
-                        token: Token::Lte,
-                        line: tok_line,
-                    });
+                    tokens.push(Spanned::new(Token::Lte, tok_line, tok_col));
                     pos += 2;
                 } else {
-                    tokens.push(Spanned {
-                        token: Token::Lt,
-                        line: tok_line,
-                    });
+                    tokens.push(Spanned::new(Token::Lt, tok_line, tok_col));
                     pos += 1;
                 }
                 continue;
             }
             '>' => {
                 if pos + 1 < chars.len() && chars[pos + 1] == '=' {
-                    tokens.push(Spanned {
-                        token: Token::Gte,
-                        line: tok_line,
-                    });
+                    tokens.push(Spanned::new(Token::Gte, tok_line, tok_col));
                     pos += 2;
                 } else {
-                    tokens.push(Spanned {
-                        token: Token::Gt,
-                        line: tok_line,
-                    });
+                    tokens.push(Spanned::new(Token::Gt, tok_line, tok_col));
                     pos += 1;
                 }
                 continue;
             }
             '!' => {
                 if pos + 1 < chars.len() && chars[pos + 1] == '=' {
-                    tokens.push(Spanned {
-                        token: Token::Neq,
-                        line: tok_line,
-                    });
+                    tokens.push(Spanned::new(Token::Neq, tok_line, tok_col));
                     pos += 2;
                 } else {
                     return Err(ElabError::lex(
@@ -246,82 +269,57 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
                 continue;
             }
             '*' => {
-                tokens.push(Spanned {
-                    token: Token::Star,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Star, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '{' => {
-                tokens.push(Spanned {
-                    token: Token::LBrace,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::LBrace, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '}' => {
-                tokens.push(Spanned {
-                    token: Token::RBrace,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::RBrace, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '[' => {
-                tokens.push(Spanned {
-                    token: Token::LBracket,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::LBracket, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             ']' => {
-                tokens.push(Spanned {
-                    token: Token::RBracket,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::RBracket, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '(' => {
-                tokens.push(Spanned {
-                    token: Token::LParen,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::LParen, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             ')' => {
-                tokens.push(Spanned {
-                    token: Token::RParen,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::RParen, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             ':' => {
-                tokens.push(Spanned {
-                    token: Token::Colon,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Colon, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             ',' => {
-                tokens.push(Spanned {
-                    token: Token::Comma,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Comma, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '.' => {
-                tokens.push(Spanned {
-                    token: Token::Dot,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Dot, tok_line, tok_col));
+                pos += 1;
+                continue;
+            }
+            '@' => {
+                tokens.push(Spanned::new(Token::At, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
@@ -331,59 +329,38 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
         // Unicode operators (multi-byte chars)
         match c {
             '\u{2200}' => {
-                tokens.push(Spanned {
-                    token: Token::Forall,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Forall, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '\u{2203}' => {
-                tokens.push(Spanned {
-                    token: Token::Exists,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Exists, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '\u{2208}' => {
-                tokens.push(Spanned {
-                    token: Token::In,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::In, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '\u{2227}' => {
-                tokens.push(Spanned {
-                    token: Token::And,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::And, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '\u{2228}' => {
-                tokens.push(Spanned {
-                    token: Token::Or,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Or, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             '\u{00AC}' => {
-                tokens.push(Spanned {
-                    token: Token::Not,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Not, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
             // U+2192 RIGHTWARDS ARROW — same token as ASCII "->" (where '-' is silently skipped)
             '\u{2192}' => {
-                tokens.push(Spanned {
-                    token: Token::Gt,
-                    line: tok_line,
-                });
+                tokens.push(Spanned::new(Token::Gt, tok_line, tok_col));
                 pos += 1;
                 continue;
             }
@@ -397,23 +374,30 @@ pub fn lex(src: &str, filename: &str) -> Result<Vec<Spanned>, ElabError> {
                 pos += 1;
             }
             let word: String = chars[start..pos].iter().collect();
-            tokens.push(Spanned {
-                token: Token::Word(word),
-                line: tok_line,
-            });
+            tokens.push(Spanned::new(Token::Word(word), tok_line, tok_col));
             continue;
         }
 
-        return Err(ElabError::lex(
-            filename,
-            tok_line,
-            format!("unexpected character '{}'", c),
-        ));
+        return Err(
+            ElabError::lex(filename, tok_line, format!("unexpected character '{}'", c)).with_span(
+                Span {
+                    col: tok_col,
+                    len: 1,
+                },
+            ),
+        );
     }
 
+    close_span(&mut tokens, tok_start, pos, &offsets);
     tokens.push(Spanned {
         token: Token::Eof,
         line,
+        col: (pos - line_start + 1) as u32,
+        len: 0,
+        range: SourceRange {
+            start: src.len() as u32,
+            end: src.len() as u32,
+        },
     });
     Ok(tokens)
 }
@@ -9,23 +9,28 @@
 //! Key types are re-exported at the crate root for convenience:
 //!
 //! - [`elaborate()`] -- run the full 6-pass pipeline
+//! - [`elaborate_recovering()`] -- the same, collecting errors across Passes 3-5
 //! - [`Index`] -- construct lookup index (Pass 2 output)
 //! - [`TypeEnv`] -- name-to-concrete-type map (Pass 3 output)
 //! - [`ElabError`] -- elaboration error type
+//! - [`error_codes`] -- stable `TEN-E-NNNN` error code catalog
 //! - AST types: [`RawConstruct`], [`RawType`], [`RawExpr`], [`RawTerm`],
-//!   [`RawLiteral`], [`Provenance`]
+//!   [`RawLiteral`], [`Provenance`], [`Span`]
 //!
 //! Individual pass entry functions are also re-exported for selective
 //! pipeline execution.
 
 /// Tenor spec version used in per-construct `"tenor"` fields (e.g., "1.0").
 pub const TENOR_VERSION: &str = "1.0";
-/// Tenor interchange bundle version (semver, e.g., "1.0.0").
-pub const TENOR_BUNDLE_VERSION: &str = "1.0.0";
+/// Tenor interchange bundle version (semver, e.g., "1.1.0").
+pub const TENOR_BUNDLE_VERSION: &str = "1.1.0";
 
 pub mod ast;
 pub mod elaborate;
 pub mod error;
+pub mod error_codes;
+pub mod features;
+pub mod format;
 pub mod lexer;
 pub mod parser;
 pub mod pass1_bundle;
@@ -34,20 +39,30 @@ pub mod pass3_types;
 pub mod pass4_typecheck;
 pub mod pass5_validate;
 pub mod pass6_serialize;
+pub mod project;
 pub mod source;
+pub mod source_map;
+pub mod units;
 
 // -- Convenience re-exports: key types ------------------------------------
 
-pub use ast::{Provenance, RawConstruct, RawExpr, RawLiteral, RawTerm, RawType};
-pub use error::ElabError;
+pub use ast::{
+    Annotations, Provenance, RawConstruct, RawExpr, RawLiteral, RawTerm, RawType, SourceRange, Span,
+};
+pub use error::{ElabError, ErrorSink};
 pub use pass2_index::Index;
 pub use pass3_types::TypeEnv;
 
 // -- Convenience re-exports: pipeline entry points ------------------------
 
-pub use elaborate::{elaborate, elaborate_with_provider};
+pub use elaborate::{
+    elaborate, elaborate_recovering, elaborate_with_features, elaborate_with_provider,
+    elaborate_with_source_map,
+};
 pub use pass1_bundle::load_bundle;
 pub use pass2_index::build_index;
 pub use pass3_types::build_type_env;
 pub use pass4_typecheck::resolve_types;
-pub use source::{FileSystemProvider, InMemoryProvider, SourceProvider};
+pub use project::ProjectConfig;
+pub use source::{FileSystemProvider, InMemoryProvider, SearchPathProvider, SourceProvider};
+pub use source_map::{SourceMap, SourceMapping};
@@ -1,5 +1,8 @@
 use super::Parser;
-use crate::ast::{Provenance, RawConstruct, RawLiteral, RawSourceDecl, RawTerm, RawType};
+use crate::ast::{
+    RawAssertProperty, RawConstruct, RawEffect, RawEffectKind, RawExpectation, RawGiven,
+    RawLiteral, RawSourceDecl, RawTerm, RawTransition, RawType,
+};
 use crate::error::ElabError;
 use crate::lexer::Token;
 use std::collections::BTreeMap;
@@ -12,10 +15,7 @@ impl<'a> Parser<'a> {
         Ok(RawConstruct::TypeDecl {
             id,
             fields,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
@@ -64,10 +64,7 @@ impl<'a> Parser<'a> {
             type_: type_.ok_or_else(|| self.err("Fact missing 'type'"))?,
             source: source.ok_or_else(|| self.err("Fact missing 'source'"))?,
             default,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
@@ -122,10 +119,7 @@ impl<'a> Parser<'a> {
             protocol: protocol.ok_or_else(|| self.err("Source missing 'protocol'"))?,
             fields,
             description,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
@@ -149,6 +143,7 @@ impl<'a> Parser<'a> {
         let mut initial = String::new();
         let mut initial_line = line;
         let mut transitions = Vec::new();
+        let mut attributes = BTreeMap::new();
         let mut parent = None;
         let mut parent_line = None;
         while self.peek() != &Token::RBrace {
@@ -166,6 +161,9 @@ impl<'a> Parser<'a> {
                 "transitions" => {
                     transitions = self.parse_transitions()?;
                 }
+                "attributes" => {
+                    attributes = self.parse_typedecl_body()?;
+                }
                 "parent" => {
                     parent_line = Some(field_line);
                     parent = Some(self.take_word()?);
@@ -180,12 +178,10 @@ impl<'a> Parser<'a> {
             initial,
             initial_line,
             transitions,
+            attributes,
             parent,
             parent_line,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
@@ -202,17 +198,28 @@ impl<'a> Parser<'a> {
         Ok(items)
     }
 
-    fn parse_transitions(&mut self) -> Result<Vec<(String, String, u32)>, ElabError> {
+    fn parse_transitions(&mut self) -> Result<Vec<RawTransition>, ElabError> {
         self.expect_lbracket()?;
         let mut transitions = Vec::new();
         while self.peek() != &Token::RBracket {
-            let t_line = self.cur_line();
+            let line = self.cur_line();
             self.advance_lparen()?;
             let from = self.take_word()?;
             self.expect_transition_sep()?;
             let to = self.take_word()?;
+            let guard = if self.is_word("when") {
+                self.advance();
+                Some(self.parse_expr()?)
+            } else {
+                None
+            };
             self.expect_rparen()?;
-            transitions.push((from, to, t_line));
+            transitions.push(RawTransition {
+                from,
+                to,
+                guard,
+                line,
+            });
             if self.peek() == &Token::Comma {
                 self.advance();
             }
@@ -234,7 +241,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn expect_comma(&mut self) -> Result<(), ElabError> {
+    pub(super) fn expect_comma(&mut self) -> Result<(), ElabError> {
         if self.peek() == &Token::Comma {
             self.advance();
             Ok(())
@@ -286,10 +293,7 @@ impl<'a> Parser<'a> {
             payload_type,
             payload_value,
             produce_line,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
@@ -359,44 +363,73 @@ impl<'a> Parser<'a> {
             effects,
             error_contract,
             outcomes,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
-    #[allow(clippy::type_complexity)]
-    fn parse_effects(
-        &mut self,
-    ) -> Result<Vec<(String, String, String, Option<String>, u32)>, ElabError> {
+    fn parse_effects(&mut self) -> Result<Vec<RawEffect>, ElabError> {
         self.expect_lbracket()?;
         let mut effects = Vec::new();
         while self.peek() != &Token::RBracket {
             let e_line = self.cur_line();
             self.advance_lparen()?;
-            let entity = self.take_word()?;
-            self.expect_comma()?;
-            let from = self.take_word()?;
-            self.expect_comma()?;
-            let to = self.take_word()?;
-            // Optional outcome label: if next token is a comma followed by a word (not RParen)
-            let outcome = if self.peek() == &Token::Comma {
-                // Peek ahead: save position, check if after comma there's a word before RParen
+            // `create`/`destroy` followed directly by the entity name (no
+            // comma) mark a lifecycle effect; otherwise the word is the entity.
+            let first = self.take_word()?;
+            let kind = match first.as_str() {
+                "create" if matches!(self.peek(), Token::Word(_)) => RawEffectKind::Create,
+                "destroy" if matches!(self.peek(), Token::Word(_)) => RawEffectKind::Destroy,
+                _ => RawEffectKind::Transition,
+            };
+            let (entity, from, to) = match kind {
+                RawEffectKind::Transition => {
+                    self.expect_comma()?;
+                    let from = self.take_word()?;
+                    self.expect_comma()?;
+                    let to = self.take_word()?;
+                    (first, from, to)
+                }
+                RawEffectKind::Create => (self.take_word()?, String::new(), String::new()),
+                RawEffectKind::Destroy => {
+                    let entity = self.take_word()?;
+                    self.expect_comma()?;
+                    (entity, self.take_word()?, String::new())
+                }
+            };
+            // Optional outcome label and `set { ... }` block, each after a comma
+            let mut outcome = None;
+            let mut set = BTreeMap::new();
+            while self.peek() == &Token::Comma {
+                // Peek ahead: save position, check what follows the comma
                 let saved_pos = self.pos;
                 self.advance(); // consume comma
-                if let Token::Word(_) = self.peek() {
-                    Some(self.take_word()?)
+                if self.is_word("set") {
+                    self.advance();
+                    if self.peek() == &Token::LBrace {
+                        set = self.parse_effect_set()?;
+                        continue;
+                    }
+                    // `set` used as an outcome label
+                    self.pos -= 1;
+                }
+                if outcome.is_none() && matches!(self.peek(), Token::Word(_)) {
+                    outcome = Some(self.take_word()?);
                 } else {
-                    // Not a word after comma -- restore position (the comma was a trailing comma)
+                    // Not a label or set block -- restore position (the comma was a trailing comma)
                     self.pos = saved_pos;
-                    None
+                    break;
                 }
-            } else {
-                None
-            };
+            }
             self.expect_rparen()?;
-            effects.push((entity, from, to, outcome, e_line));
+            effects.push(RawEffect {
+                kind,
+                entity,
+                from,
+                to,
+                outcome,
+                set,
+                line: e_line,
+            });
             if self.peek() == &Token::Comma {
                 self.advance();
             }
@@ -405,15 +438,134 @@ impl<'a> Parser<'a> {
         Ok(effects)
     }
 
+    /// `{ attribute: term, ... }` following `set` in an effect tuple.
+    fn parse_effect_set(&mut self) -> Result<BTreeMap<String, RawTerm>, ElabError> {
+        let mut set = BTreeMap::new();
+        self.expect_lbrace()?;
+        while self.peek() != &Token::RBrace {
+            let name = self.take_word()?;
+            self.expect_colon()?;
+            let term = self.parse_term()?;
+            if set.insert(name.clone(), term).is_some() {
+                return Err(self.err(format!("attribute '{}' is set more than once", name)));
+            }
+            if self.peek() == &Token::Comma {
+                self.advance();
+            }
+        }
+        self.expect_rbrace()?;
+        Ok(set)
+    }
+
     pub(super) fn parse_persona(&mut self, line: u32) -> Result<RawConstruct, ElabError> {
         self.advance(); // consume 'persona'
         let id = self.take_word()?;
         Ok(RawConstruct::Persona {
             id,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
+        })
+    }
+
+    /// `test <id> { given facts { <fact>: <literal> ... } expect verdict <type> present ... }`,
+    /// where each `expect` line ends in `present` or `absent`.
+    pub(super) fn parse_test(&mut self, line: u32) -> Result<RawConstruct, ElabError> {
+        self.advance(); // consume 'test'
+        let id = self.take_word()?;
+        self.expect_lbrace()?;
+        let mut given: Vec<RawGiven> = Vec::new();
+        let mut has_given = false;
+        let mut expectations = Vec::new();
+        while self.peek() != &Token::RBrace {
+            let key_line = self.cur_line();
+            match self.take_word()?.as_str() {
+                "given" => {
+                    if has_given {
+                        return Err(self.err("Test has more than one 'given' block"));
+                    }
+                    has_given = true;
+                    self.expect_word("facts")?;
+                    self.expect_lbrace()?;
+                    while self.peek() != &Token::RBrace {
+                        let fact_line = self.cur_line();
+                        let fact = self.take_word()?;
+                        if given.iter().any(|g| g.fact == fact) {
+                            return Err(
+                                self.err(format!("fact '{}' is given more than once", fact))
+                            );
+                        }
+                        self.expect_colon()?;
+                        let value = self.parse_literal()?;
+                        given.push(RawGiven {
+                            fact,
+                            value,
+                            line: fact_line,
+                        });
+                        if self.peek() == &Token::Comma {
+                            self.advance();
+                        }
+                    }
+                    self.expect_rbrace()?;
+                }
+                "expect" => {
+                    self.expect_word("verdict")?;
+                    let verdict_type = self.take_word()?;
+                    let present = match self.take_word()?.as_str() {
+                        "present" => true,
+                        "absent" => false,
+                        other => {
+                            return Err(self
+                                .err(format!("expected 'present' or 'absent', got '{}'", other)))
+                        }
+                    };
+                    expectations.push(RawExpectation {
+                        verdict_type,
+                        present,
+                        line: key_line,
+                    });
+                }
+                other => return Err(self.err(format!("unknown Test field '{}'", other))),
+            }
+        }
+        self.expect_rbrace()?;
+        if expectations.is_empty() {
+            return Err(self.err("Test missing 'expect'"));
+        }
+        Ok(RawConstruct::Test {
+            id,
+            given,
+            expectations,
+            prov: self.prov(line),
+        })
+    }
+
+    /// `assert forall states of <Entity>: reachable`. `forall` may also be
+    /// written `∀`.
+    pub(super) fn parse_assert(&mut self, line: u32) -> Result<RawConstruct, ElabError> {
+        self.advance(); // consume 'assert'
+        match self.peek().clone() {
+            Token::Forall => {
+                self.advance();
+            }
+            Token::Word(w) if w == "forall" => {
+                self.advance();
+            }
+            other => return Err(self.err(format!("expected 'forall', got {:?}", other))),
+        }
+        self.expect_word("states")?;
+        self.expect_word("of")?;
+        let entity_line = self.cur_line();
+        let entity = self.take_word()?;
+        self.expect_colon()?;
+        let property = match self.take_word()?.as_str() {
+            "reachable" => RawAssertProperty::Reachable,
+            other => return Err(self.err(format!("unknown assert property '{}'", other))),
+        };
+        Ok(RawConstruct::Assert {
+            id: format!("{}.{}", entity, property.as_str()),
+            entity,
+            property,
+            entity_line,
+            prov: self.prov(line),
         })
     }
 }
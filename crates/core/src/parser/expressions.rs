@@ -86,15 +86,17 @@ impl<'a> Parser<'a> {
 
     fn parse_unary_expr(&mut self) -> Result<RawExpr, ElabError> {
         if self.peek() == &Token::Not || self.is_word("not") {
+            let start = self.pos;
             self.advance();
             let e = self.parse_atom_expr()?;
-            return Ok(RawExpr::Not(Box::new(e)));
+            return Ok(RawExpr::Not(Box::new(e), self.range_from(start)));
         }
         self.parse_atom_expr()
     }
 
     fn parse_atom_expr(&mut self) -> Result<RawExpr, ElabError> {
         if self.peek() == &Token::Forall {
+            let start = self.pos;
             let line = self.cur_line();
             self.advance();
             let var = self.take_word()?;
@@ -102,7 +104,7 @@ impl<'a> Parser<'a> {
                 return Err(self.err("expected \u{2208} after quantifier variable"));
             }
             self.advance();
-            let domain = self.take_word()?;
+            let (domain, over_instances) = self.parse_quantifier_domain()?;
             if self.peek() == &Token::Dot {
                 self.advance();
             } else {
@@ -112,12 +114,15 @@ impl<'a> Parser<'a> {
             return Ok(RawExpr::Forall {
                 var,
                 domain,
+                over_instances,
                 body: Box::new(body),
                 line,
+                range: self.range_from(start),
             });
         }
 
         if self.peek() == &Token::Exists {
+            let start = self.pos;
             let line = self.cur_line();
             self.advance();
             let var = self.take_word()?;
@@ -125,7 +130,7 @@ impl<'a> Parser<'a> {
                 return Err(self.err("expected \u{2208} after quantifier variable"));
             }
             self.advance();
-            let domain = self.take_word()?;
+            let (domain, over_instances) = self.parse_quantifier_domain()?;
             if self.peek() == &Token::Dot {
                 self.advance();
             } else {
@@ -135,18 +140,25 @@ impl<'a> Parser<'a> {
             return Ok(RawExpr::Exists {
                 var,
                 domain,
+                over_instances,
                 body: Box::new(body),
                 line,
+                range: self.range_from(start),
             });
         }
 
         if self.is_word("verdict_present") {
+            let start = self.pos;
             let line = self.cur_line();
             self.advance();
             self.advance_lparen()?;
             let id = self.take_word()?;
             self.expect_rparen()?;
-            return Ok(RawExpr::VerdictPresent { id, line });
+            return Ok(RawExpr::VerdictPresent {
+                id,
+                line,
+                range: self.range_from(start),
+            });
         }
 
         if self.peek() == &Token::LParen {
@@ -156,18 +168,43 @@ impl<'a> Parser<'a> {
             return Ok(e);
         }
 
+        let start = self.pos;
         let line = self.cur_line();
-        let left = self.parse_term()?;
+        let mut left = self.parse_term()?;
         let op = self.parse_compare_op()?;
-        let right = self.parse_term()?;
+        let mut right = self.parse_term()?;
+        // A bare word compared with state(var) names a state, not a fact
+        match (&left, &right) {
+            (RawTerm::InstanceState(_), RawTerm::FactRef(name)) => {
+                right = RawTerm::Literal(RawLiteral::Str(name.clone()));
+            }
+            (RawTerm::FactRef(name), RawTerm::InstanceState(_)) => {
+                left = RawTerm::Literal(RawLiteral::Str(name.clone()));
+            }
+            _ => {}
+        }
         Ok(RawExpr::Compare {
             op,
             left,
             right,
             line,
+            range: self.range_from(start),
         })
     }
 
+    /// A quantifier domain: a List fact, or `instances(Entity)`. Returns
+    /// the fact or entity id and whether it ranges over instances.
+    fn parse_quantifier_domain(&mut self) -> Result<(String, bool), ElabError> {
+        let domain = self.take_word()?;
+        if domain == "instances" && self.peek() == &Token::LParen {
+            self.advance();
+            let entity = self.take_word()?;
+            self.expect_rparen()?;
+            return Ok((entity, true));
+        }
+        Ok((domain, false))
+    }
+
     fn parse_compare_op(&mut self) -> Result<String, ElabError> {
         let op = match self.peek() {
             Token::Eq => "=",
@@ -218,6 +255,23 @@ impl<'a> Parser<'a> {
             Token::Word(ref w) => {
                 let name = w.clone();
                 self.advance();
+                if name == "state" && self.peek() == &Token::LParen {
+                    self.advance();
+                    let var = self.take_word()?;
+                    self.expect_rparen()?;
+                    return Ok(RawTerm::InstanceState(var));
+                }
+                if name == "convert" && self.peek() == &Token::LParen {
+                    self.advance();
+                    let amount = self.parse_term()?;
+                    self.expect_comma()?;
+                    let rate = self.take_word()?;
+                    self.expect_rparen()?;
+                    return Ok(RawTerm::Convert {
+                        amount: Box::new(amount),
+                        rate,
+                    });
+                }
                 if self.peek() == &Token::Dot {
                     self.advance();
                     let field = self.take_word()?;
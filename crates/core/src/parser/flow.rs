@@ -1,7 +1,7 @@
 use super::Parser;
 use crate::ast::{
-    Provenance, RawBranch, RawCompStep, RawConstruct, RawFailureHandler, RawJoinPolicy, RawStep,
-    RawStepTarget,
+    RawBranch, RawCompStep, RawConstruct, RawFailureHandler, RawJoinPolicy, RawStep, RawStepTarget,
+    RawTimeout, RawTimeoutAction,
 };
 use crate::error::ElabError;
 use crate::lexer::Token;
@@ -41,10 +41,7 @@ impl<'a> Parser<'a> {
             entry,
             entry_line,
             steps,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
@@ -71,10 +68,18 @@ impl<'a> Parser<'a> {
                 let mut persona = String::new();
                 let mut outcomes = BTreeMap::new();
                 let mut on_failure = None;
+                let mut timeout = None;
+                let mut weights = BTreeMap::new();
                 while self.peek() != &Token::RBrace {
                     let key = self.take_word()?;
                     self.expect_colon()?;
                     match key.as_str() {
+                        "timeout" => {
+                            timeout = Some(self.parse_timeout()?);
+                        }
+                        "weights" => {
+                            weights = self.parse_weights()?;
+                        }
                         "op" => {
                             op = self.take_word()?;
                         }
@@ -95,6 +100,8 @@ impl<'a> Parser<'a> {
                     persona,
                     outcomes,
                     on_failure,
+                    timeout,
+                    weights,
                     line: step_line,
                 }
             }
@@ -103,6 +110,7 @@ impl<'a> Parser<'a> {
                 let mut persona = String::new();
                 let mut if_true = None;
                 let mut if_false = None;
+                let mut weights = BTreeMap::new();
                 while self.peek() != &Token::RBrace {
                     let key = self.take_word()?;
                     self.expect_colon()?;
@@ -119,6 +127,9 @@ impl<'a> Parser<'a> {
                         "if_false" => {
                             if_false = Some(self.parse_step_target()?);
                         }
+                        "weights" => {
+                            weights = self.parse_weights()?;
+                        }
                         _ => return Err(self.err(format!("unknown BranchStep field '{}'", key))),
                     }
                 }
@@ -127,6 +138,7 @@ impl<'a> Parser<'a> {
                     persona,
                     if_true: if_true.ok_or_else(|| self.err("BranchStep missing if_true"))?,
                     if_false: if_false.ok_or_else(|| self.err("BranchStep missing if_false"))?,
+                    weights,
                     line: step_line,
                 }
             }
@@ -134,10 +146,27 @@ impl<'a> Parser<'a> {
                 let mut from_persona = String::new();
                 let mut to_persona = String::new();
                 let mut next = String::new();
+                let mut pause = false;
+                let mut timeout = None;
                 while self.peek() != &Token::RBrace {
                     let key = self.take_word()?;
                     self.expect_colon()?;
                     match key.as_str() {
+                        "timeout" => {
+                            timeout = Some(self.parse_timeout()?);
+                        }
+                        "pause" => {
+                            pause = match self.take_word()?.as_str() {
+                                "true" => true,
+                                "false" => false,
+                                other => {
+                                    return Err(self.err(format!(
+                                        "HandoffStep pause must be true or false, got '{}'",
+                                        other
+                                    )))
+                                }
+                            };
+                        }
                         "from_persona" => {
                             from_persona = self.take_word()?;
                         }
@@ -154,6 +183,8 @@ impl<'a> Parser<'a> {
                     from_persona,
                     to_persona,
                     next,
+                    pause,
+                    timeout,
                     line: step_line,
                 }
             }
@@ -240,6 +271,32 @@ impl<'a> Parser<'a> {
         Ok(outcomes)
     }
 
+    /// `{ label: weight, ... }`, commas optional.
+    fn parse_weights(&mut self) -> Result<BTreeMap<String, f64>, ElabError> {
+        let mut weights = BTreeMap::new();
+        self.expect_lbrace()?;
+        while self.peek() != &Token::RBrace {
+            let label = self.take_word()?;
+            self.expect_colon()?;
+            let weight = match self.peek().clone() {
+                Token::Int(n) => n as f64,
+                Token::Float(f) => f
+                    .parse::<f64>()
+                    .map_err(|_| self.err(format!("invalid weight '{}'", f)))?,
+                other => return Err(self.err(format!("expected a weight number, got {:?}", other))),
+            };
+            self.advance();
+            if weights.insert(label.clone(), weight).is_some() {
+                return Err(self.err(format!("duplicate weight for '{}'", label)));
+            }
+            if self.peek() == &Token::Comma {
+                self.advance();
+            }
+        }
+        self.expect_rbrace()?;
+        Ok(weights)
+    }
+
     fn parse_step_target(&mut self) -> Result<RawStepTarget, ElabError> {
         if self.is_word("Terminal") {
             self.advance();
@@ -253,6 +310,42 @@ impl<'a> Parser<'a> {
         Ok(RawStepTarget::StepRef(name, line))
     }
 
+    fn parse_timeout(&mut self) -> Result<RawTimeout, ElabError> {
+        self.expect_word("Timeout")?;
+        self.advance_lparen()?;
+        let mut deadline = None;
+        let mut on_timeout = None;
+        while self.peek() != &Token::RParen {
+            let field_line = self.cur_line();
+            let key = self.take_word()?;
+            self.expect_colon()?;
+            match key.as_str() {
+                "deadline" => {
+                    deadline = Some((self.take_word()?, field_line));
+                }
+                "on_timeout" => {
+                    let handler = self.is_word("Terminate")
+                        || self.is_word("Compensate")
+                        || self.is_word("Escalate");
+                    on_timeout = Some(if handler {
+                        RawTimeoutAction::Handle(self.parse_failure_handler()?)
+                    } else {
+                        RawTimeoutAction::Goto(self.parse_step_target()?)
+                    });
+                }
+                _ => return Err(self.err(format!("unknown Timeout field '{}'", key))),
+            }
+        }
+        self.expect_rparen()?;
+        let (deadline, deadline_line) =
+            deadline.ok_or_else(|| self.err("Timeout missing deadline"))?;
+        Ok(RawTimeout {
+            deadline,
+            deadline_line,
+            on_timeout: on_timeout.ok_or_else(|| self.err("Timeout missing on_timeout"))?,
+        })
+    }
+
     fn parse_failure_handler(&mut self) -> Result<RawFailureHandler, ElabError> {
         let kind = self.take_word()?;
         match kind.as_str() {
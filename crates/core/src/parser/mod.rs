@@ -12,8 +12,9 @@ mod types;
 
 // Re-export AST types so existing callers that use `parser::Foo` still work.
 pub use crate::ast::{
-    Provenance, RawBranch, RawCompStep, RawConstruct, RawExpr, RawFailureHandler, RawJoinPolicy,
-    RawLiteral, RawSourceDecl, RawStep, RawStepTarget, RawTerm, RawTrigger, RawType,
+    Annotations, Provenance, RawBranch, RawCompStep, RawConstruct, RawExpr, RawFailureHandler,
+    RawJoinPolicy, RawLiteral, RawSourceDecl, RawStep, RawStepTarget, RawTerm, RawTrigger, RawType,
+    SourceRange, Span,
 };
 
 // ──────────────────────────────────────────────
@@ -95,7 +96,52 @@ impl<'a> Parser<'a> {
     }
 
     fn err(&self, msg: impl Into<String>) -> ElabError {
-        ElabError::parse(&self.filename, self.cur_line(), msg)
+        let cur = self.cur();
+        ElabError::parse(&self.filename, cur.line, msg).with_span(Span {
+            col: cur.col,
+            len: cur.len.max(1),
+        })
+    }
+
+    fn prov(&self, line: u32) -> Provenance {
+        Provenance {
+            file: self.filename.clone(),
+            line,
+            span: Span::default(),
+            range: SourceRange::default(),
+            annotations: Annotations::default(),
+        }
+    }
+
+    /// The source text from token `start` through the last consumed token.
+    fn range_from(&self, start: usize) -> SourceRange {
+        let first = self.tokens[start].range;
+        match self.pos.checked_sub(1) {
+            Some(last) if last >= start => first.to(self.tokens[last].range),
+            _ => SourceRange {
+                start: first.start,
+                end: first.start,
+            },
+        }
+    }
+
+    /// The header of the construct whose keyword is at token `start`: the
+    /// keyword through the name after it, when both are on one line.
+    fn header_span(&self, start: usize) -> Span {
+        let keyword = &self.tokens[start];
+        let end = match self.tokens.get(start + 1) {
+            Some(name)
+                if name.line == keyword.line
+                    && matches!(name.token, Token::Word(_) | Token::Str(_)) =>
+            {
+                name.col + name.len
+            }
+            _ => keyword.col + keyword.len,
+        };
+        Span {
+            col: keyword.col,
+            len: end - keyword.col,
+        }
     }
 
     fn is_word(&self, w: &str) -> bool {
@@ -154,6 +200,22 @@ impl<'a> Parser<'a> {
                 _ => None,
             })
             .collect();
+        // A file derives from at most one base contract
+        if let Some(prov) = constructs
+            .iter()
+            .filter_map(|c| match c {
+                RawConstruct::Extends { prov, .. } => Some(prov),
+                _ => None,
+            })
+            .nth(1)
+        {
+            return Err(ElabError::parse(
+                &prov.file,
+                prov.line,
+                "multiple extends declarations in a single file",
+            ));
+        }
+
         if systems.len() > 1 {
             let (_, prov) = &systems[1];
             return Err(ElabError::parse(
@@ -177,6 +239,8 @@ impl<'a> Parser<'a> {
                             | RawConstruct::Flow { prov, .. }
                             | RawConstruct::Persona { prov, .. }
                             | RawConstruct::Source { prov, .. }
+                            | RawConstruct::Test { prov, .. }
+                            | RawConstruct::Assert { prov, .. }
                             | RawConstruct::TypeDecl { prov, .. } => prov,
                             _ => sys_prov,
                         };
@@ -194,10 +258,16 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_construct(&mut self) -> Result<RawConstruct, ElabError> {
+        let annotation_line = self.cur_line();
+        let annotations = self.parse_annotations()?;
         let line = self.cur_line();
-        match self.peek().clone() {
+        let start = self.pos;
+        let mut construct = match self.peek().clone() {
             Token::Word(w) => match w.as_str() {
                 "import" => self.parse_import(line),
+                "extends" => self.parse_extends(line),
+                "override" => self.parse_override(line),
+                "when" => self.parse_gated(line),
                 "type" => self.parse_typedecl(line),
                 "fact" => self.parse_fact(line),
                 "entity" => self.parse_entity(line),
@@ -207,10 +277,81 @@ impl<'a> Parser<'a> {
                 "persona" => self.parse_persona(line),
                 "system" => self.parse_system(line),
                 "source" => self.parse_source(line),
+                "test" => self.parse_test(line),
+                "assert" => self.parse_assert(line),
                 _ => Err(self.err(format!("unexpected token '{}'", w))),
             },
             other => Err(self.err(format!("expected construct keyword, got {:?}", other))),
+        }?;
+        let prov = construct.prov_mut();
+        prov.span = self.header_span(start);
+        prov.range = self.range_from(start);
+        if !annotations.is_empty() {
+            match annotated_prov(&mut construct) {
+                Some(prov) => prov.annotations = annotations,
+                None => {
+                    return Err(ElabError::parse(
+                        &self.filename,
+                        annotation_line,
+                        "annotations may only precede a declaration",
+                    ))
+                }
+            }
+        }
+        Ok(construct)
+    }
+
+    /// Parse the `@owner(..)`, `@description(..)` and `@tag(..)`
+    /// annotations before a construct.
+    fn parse_annotations(&mut self) -> Result<Annotations, ElabError> {
+        let mut annotations = Annotations::default();
+        while self.peek() == &Token::At {
+            let line = self.cur_line();
+            let file = self.filename.clone();
+            let err = |msg: String| ElabError::parse(&file, line, msg);
+            self.advance();
+            let name = self.take_word()?;
+            self.advance_lparen()?;
+            let mut args = vec![self.take_str()?];
+            while self.peek() == &Token::Comma {
+                self.advance();
+                args.push(self.take_str()?);
+            }
+            self.expect_rparen()?;
+            match name.as_str() {
+                "owner" | "description" => {
+                    let slot = if name == "owner" {
+                        &mut annotations.owner
+                    } else {
+                        &mut annotations.description
+                    };
+                    if slot.is_some() {
+                        return Err(err(format!("duplicate annotation '@{}'", name)));
+                    }
+                    if args.len() != 1 {
+                        return Err(err(format!("'@{}' takes one string", name)));
+                    }
+                    *slot = args.pop();
+                }
+                "tag" => {
+                    for tag in args {
+                        if tag.is_empty() {
+                            return Err(err("tag must not be empty".to_string()));
+                        }
+                        if !annotations.tags.contains(&tag) {
+                            annotations.tags.push(tag);
+                        }
+                    }
+                }
+                other => {
+                    return Err(err(format!(
+                        "unknown annotation '@{}': expected @owner, @description or @tag",
+                        other
+                    )))
+                }
+            }
         }
+        Ok(annotations)
     }
 
     fn parse_import(&mut self, line: u32) -> Result<RawConstruct, ElabError> {
@@ -218,12 +359,85 @@ impl<'a> Parser<'a> {
         let path = self.take_str()?;
         Ok(RawConstruct::Import {
             path,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
+        })
+    }
+
+    fn parse_extends(&mut self, line: u32) -> Result<RawConstruct, ElabError> {
+        self.advance();
+        let path = self.take_str()?;
+        Ok(RawConstruct::Extends {
+            path,
+            prov: self.prov(line),
         })
     }
+
+    fn parse_override(&mut self, line: u32) -> Result<RawConstruct, ElabError> {
+        self.advance();
+        let construct_line = self.cur_line();
+        let start = self.pos;
+        let mut construct = match self.peek().clone() {
+            Token::Word(w) if w == "rule" => self.parse_rule(construct_line)?,
+            Token::Word(w) if w == "operation" => self.parse_operation(construct_line)?,
+            other => {
+                return Err(self.err(format!(
+                    "expected 'rule' or 'operation' after 'override', got {:?}",
+                    other
+                )))
+            }
+        };
+        let prov = construct.prov_mut();
+        prov.span = self.header_span(start);
+        prov.range = self.range_from(start);
+        Ok(RawConstruct::Override {
+            construct: Box::new(construct),
+            base: None,
+            prov: self.prov(line),
+        })
+    }
+
+    fn parse_gated(&mut self, line: u32) -> Result<RawConstruct, ElabError> {
+        self.advance();
+        self.expect_word("feature")?;
+        self.advance_lparen()?;
+        let feature = self.take_str()?;
+        if feature.is_empty() {
+            return Err(self.err("feature name must not be empty"));
+        }
+        self.expect_rparen()?;
+        // A file's base contract does not vary between bundle variants
+        if self.is_word("extends") {
+            return Err(self.err("'extends' cannot be guarded by a feature"));
+        }
+        let construct = self.parse_construct()?;
+        Ok(RawConstruct::Gated {
+            feature,
+            construct: Box::new(construct),
+            prov: self.prov(line),
+        })
+    }
+}
+
+/// The provenance annotations attach to: the declared construct, looking
+/// through overrides and feature gates. `None` for imports and `extends`.
+fn annotated_prov(construct: &mut RawConstruct) -> Option<&mut Provenance> {
+    match construct {
+        RawConstruct::Override { construct, .. } | RawConstruct::Gated { construct, .. } => {
+            annotated_prov(construct)
+        }
+        RawConstruct::Import { .. } | RawConstruct::Extends { .. } => None,
+        RawConstruct::TypeDecl { prov, .. }
+        | RawConstruct::Fact { prov, .. }
+        | RawConstruct::Entity { prov, .. }
+        | RawConstruct::Rule { prov, .. }
+        | RawConstruct::Operation { prov, .. }
+        | RawConstruct::Persona { prov, .. }
+        | RawConstruct::Source { prov, .. }
+        | RawConstruct::Flow { prov, .. }
+        | RawConstruct::System { prov, .. }
+        | RawConstruct::Test { prov, .. }
+        | RawConstruct::Assert { prov, .. } => Some(prov),
+    }
 }
 
 /// Default maximum number of errors collected in multi-error mode before aborting.
@@ -253,16 +467,19 @@ pub fn parse_recovering(
 }
 
 impl<'a> Parser<'a> {
-    /// Check whether the current token is a top-level construct keyword.
+    /// Check whether the current token starts a top-level construct: a
+    /// construct keyword or an annotation.
     fn is_construct_keyword(&self) -> bool {
-        matches!(
-            self.peek(),
-            Token::Word(w) if matches!(
-                w.as_str(),
-                "fact" | "entity" | "rule" | "operation" | "flow"
-                    | "type" | "persona" | "system" | "import" | "source"
+        self.peek() == &Token::At
+            || matches!(
+                self.peek(),
+                Token::Word(w) if matches!(
+                    w.as_str(),
+                    "fact" | "entity" | "rule" | "operation" | "flow"
+                        | "type" | "persona" | "system" | "import" | "source" | "test"
+                        | "assert" | "extends" | "override" | "when"
+                )
             )
-        )
     }
 
     /// Skip tokens until we find a closing `}` at the original nesting level,
@@ -451,4 +668,67 @@ fact good_fact {
             errors.len()
         );
     }
+
+    #[test]
+    fn tokens_carry_char_columns_and_lengths() {
+        let src = "rule r {\n  when: a \u{2227} \"x\\\"y\" = 12\n}";
+        let tokens = lexer::lex(src, "span.tenor").expect("lex");
+        let spans: Vec<(u32, u32, u32)> = tokens.iter().map(|t| (t.line, t.col, t.len)).collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, 1, 4),  // rule
+                (1, 6, 1),  // r
+                (1, 8, 1),  // {
+                (2, 3, 4),  // when
+                (2, 7, 1),  // :
+                (2, 9, 1),  // a
+                (2, 11, 1), // ∧
+                (2, 13, 6), // "x\"y"
+                (2, 20, 1), // =
+                (2, 22, 2), // 12
+                (3, 1, 1),  // }
+                (3, 2, 0),  // Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn provenance_spans_the_construct_header() {
+        let src = "persona clerk\n\n  entity Order {\n    states: [a]\n    initial: a\n  }\n";
+        let tokens = lexer::lex(src, "span.tenor").expect("lex");
+        let constructs = parse(&tokens, "span.tenor").expect("parse");
+        let spans: Vec<Span> = constructs.iter().map(|c| c.prov().span).collect();
+        assert_eq!(
+            spans,
+            vec![Span { col: 1, len: 13 }, Span { col: 3, len: 12 }]
+        );
+    }
+
+    #[test]
+    fn ranges_count_bytes_through_the_last_token() {
+        let src = "rule r {\n  stratum: 0\n  when: ¬ a = 1 ∧ b = 2\n  produce: verdict v { payload: Bool = true }\n}\n";
+        let tokens = lexer::lex(src, "range.tenor").expect("lex");
+        let constructs = parse(&tokens, "range.tenor").expect("parse");
+        let text = |r: SourceRange| &src[r.start as usize..r.end as usize];
+        assert_eq!(text(constructs[0].prov().range), src.trim_end());
+        let RawConstruct::Rule { when, .. } = &constructs[0] else {
+            panic!("expected a rule");
+        };
+        assert_eq!(text(when.range()), "¬ a = 1 ∧ b = 2");
+        let RawExpr::And(left, right) = when else {
+            panic!("expected a conjunction");
+        };
+        assert_eq!(text(left.range()), "¬ a = 1");
+        assert_eq!(text(right.range()), "b = 2");
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_offending_token() {
+        let src = "fact f {\n  type: Bool\n  source \"svc.f\"\n}\n";
+        let tokens = lexer::lex(src, "span.tenor").expect("lex");
+        let err = parse(&tokens, "span.tenor").expect_err("missing colon");
+        assert_eq!(err.line, 3);
+        assert_eq!(err.span, Some(Span { col: 10, len: 7 }));
+    }
 }
@@ -1,5 +1,5 @@
 use super::Parser;
-use crate::ast::{Provenance, RawConstruct, RawTrigger};
+use crate::ast::{RawConstruct, RawTrigger};
 use crate::error::ElabError;
 use crate::lexer::Token;
 
@@ -41,10 +41,7 @@ impl<'a> Parser<'a> {
             shared_personas,
             triggers,
             shared_entities,
-            prov: Provenance {
-                file: self.filename.clone(),
-                line,
-            },
+            prov: self.prov(line),
         })
     }
 
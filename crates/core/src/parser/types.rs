@@ -1,7 +1,8 @@
 use super::Parser;
-use crate::ast::RawType;
+use crate::ast::{RawType, RoundingMode};
 use crate::error::ElabError;
 use crate::lexer::Token;
+use crate::units::Unit;
 use std::collections::BTreeMap;
 
 impl<'a> Parser<'a> {
@@ -33,8 +34,30 @@ impl<'a> Parser<'a> {
                     self.advance();
                 }
                 let scale = self.parse_named_or_positional_u32("scale")? as u32;
+                let mut rounding = None;
+                let mut unit = None;
+                while self.peek() == &Token::Comma {
+                    self.advance();
+                    if self.is_word("rounding") {
+                        rounding = Some(self.parse_rounding()?);
+                    } else if self.is_word("unit") {
+                        self.advance();
+                        self.expect_colon()?;
+                        let expr = self.take_str()?;
+                        let parsed = Unit::parse(&expr)
+                            .map_err(|e| self.err(format!("invalid unit '{}': {}", expr, e)))?;
+                        unit = parsed.annotation();
+                    } else {
+                        return Err(self.err("expected 'rounding' or 'unit' after Decimal scale"));
+                    }
+                }
                 self.expect_rparen()?;
-                Ok(RawType::Decimal { precision, scale })
+                Ok(RawType::Decimal {
+                    precision,
+                    scale,
+                    rounding,
+                    unit,
+                })
             }
             "Text" => {
                 if self.peek() == &Token::LParen {
@@ -53,8 +76,30 @@ impl<'a> Parser<'a> {
                     self.expect_colon()?;
                 }
                 let currency = self.take_str()?;
+                let mut precision = None;
+                let mut scale = None;
+                let mut rounding = None;
+                while self.peek() == &Token::Comma {
+                    self.advance();
+                    if self.is_word("rounding") {
+                        rounding = Some(self.parse_rounding()?);
+                        continue;
+                    }
+                    let key = self.take_word()?;
+                    self.expect_colon()?;
+                    match key.as_str() {
+                        "precision" => precision = Some(self.take_int()? as u32),
+                        "scale" => scale = Some(self.take_int()? as u32),
+                        _ => return Err(self.err(format!("unknown Money param '{}'", key))),
+                    }
+                }
                 self.expect_rparen()?;
-                Ok(RawType::Money { currency })
+                Ok(RawType::Money {
+                    currency,
+                    precision,
+                    scale,
+                    rounding,
+                })
             }
             "Duration" => {
                 self.advance_lparen()?;
@@ -164,6 +209,19 @@ impl<'a> Parser<'a> {
         self.take_int()
     }
 
+    /// Parse `rounding: half_even | half_up`.
+    fn parse_rounding(&mut self) -> Result<RoundingMode, ElabError> {
+        self.advance();
+        self.expect_colon()?;
+        let mode = self.take_word()?;
+        RoundingMode::parse(&mode).ok_or_else(|| {
+            self.err(format!(
+                "unknown rounding mode '{}'; expected half_even or half_up",
+                mode
+            ))
+        })
+    }
+
     fn parse_int_params(&mut self) -> Result<(i64, i64), ElabError> {
         let first_is_key = self.is_word("min");
         if first_is_key {
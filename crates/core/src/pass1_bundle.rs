@@ -5,7 +5,7 @@ use crate::error::ElabError;
 use crate::lexer;
 use crate::parser;
 use crate::source::{FileSystemProvider, SourceProvider};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 /// Parse the root `.tenor` file and all transitive imports, returning
@@ -22,6 +22,16 @@ pub fn load_bundle(root: &Path) -> Result<(Vec<RawConstruct>, String), ElabError
 pub fn load_bundle_with_provider(
     root: &Path,
     provider: &dyn SourceProvider,
+) -> Result<(Vec<RawConstruct>, String), ElabError> {
+    load_bundle_with_features(root, provider, &BTreeSet::new())
+}
+
+/// Like [`load_bundle_with_provider`], including constructs guarded by
+/// `when feature("...")` only when their feature is in `features`.
+pub fn load_bundle_with_features(
+    root: &Path,
+    provider: &dyn SourceProvider,
+    features: &BTreeSet<String>,
 ) -> Result<(Vec<RawConstruct>, String), ElabError> {
     let root = provider.canonicalize(root).map_err(|e| {
         ElabError::new(
@@ -55,6 +65,16 @@ pub fn load_bundle_with_provider(
         )
     })?;
 
+    // Configured import search paths are trusted like the root directory.
+    // A search path that does not exist cannot contain an import; skip it.
+    let mut sandbox_roots = vec![sandbox_root];
+    sandbox_roots.extend(
+        provider
+            .import_roots()
+            .iter()
+            .filter_map(|dir| provider.canonicalize(dir).ok()),
+    );
+
     let mut visited: HashSet<PathBuf> = HashSet::new();
     let mut stack: Vec<PathBuf> = Vec::new();
     // Parallel HashSet for O(1) cycle detection lookups.
@@ -65,8 +85,9 @@ pub fn load_bundle_with_provider(
     load_file(
         &root,
         &root_dir,
-        &sandbox_root,
+        &sandbox_roots,
         provider,
+        features,
         &mut visited,
         &mut stack,
         &mut stack_set,
@@ -82,17 +103,8 @@ pub fn load_bundle_with_provider(
 fn check_cross_file_dups(constructs: &[RawConstruct]) -> Result<(), ElabError> {
     let mut seen: HashMap<(&str, &str), &Provenance> = HashMap::new();
     for c in constructs.iter().rev() {
-        let (kind, id, prov): (&str, &str, &Provenance) = match c {
-            RawConstruct::Fact { id, prov, .. } => ("Fact", id, prov),
-            RawConstruct::Entity { id, prov, .. } => ("Entity", id, prov),
-            RawConstruct::Rule { id, prov, .. } => ("Rule", id, prov),
-            RawConstruct::Operation { id, prov, .. } => ("Operation", id, prov),
-            RawConstruct::Flow { id, prov, .. } => ("Flow", id, prov),
-            RawConstruct::TypeDecl { id, prov, .. } => ("TypeDecl", id, prov),
-            RawConstruct::Persona { id, prov, .. } => ("Persona", id, prov),
-            RawConstruct::System { id, prov, .. } => ("System", id, prov),
-            RawConstruct::Source { id, prov, .. } => ("Source", id, prov),
-            RawConstruct::Import { .. } => continue,
+        let Some((kind, id, prov)) = declaration(c) else {
+            continue;
         };
         if let Some(first) = seen.get(&(kind, id)) {
             if first.file != prov.file {
@@ -116,12 +128,35 @@ fn check_cross_file_dups(constructs: &[RawConstruct]) -> Result<(), ElabError> {
     Ok(())
 }
 
+/// The kind, id and provenance of a construct that declares something;
+/// `None` for imports, `extends`, overrides and feature gates.
+fn declaration(c: &RawConstruct) -> Option<(&'static str, &str, &Provenance)> {
+    Some(match c {
+        RawConstruct::Fact { id, prov, .. } => ("Fact", id, prov),
+        RawConstruct::Entity { id, prov, .. } => ("Entity", id, prov),
+        RawConstruct::Rule { id, prov, .. } => ("Rule", id, prov),
+        RawConstruct::Operation { id, prov, .. } => ("Operation", id, prov),
+        RawConstruct::Flow { id, prov, .. } => ("Flow", id, prov),
+        RawConstruct::TypeDecl { id, prov, .. } => ("TypeDecl", id, prov),
+        RawConstruct::Persona { id, prov, .. } => ("Persona", id, prov),
+        RawConstruct::System { id, prov, .. } => ("System", id, prov),
+        RawConstruct::Source { id, prov, .. } => ("Source", id, prov),
+        RawConstruct::Test { id, prov, .. } => ("Test", id, prov),
+        RawConstruct::Assert { id, prov, .. } => ("Assert", id, prov),
+        RawConstruct::Import { .. }
+        | RawConstruct::Extends { .. }
+        | RawConstruct::Override { .. }
+        | RawConstruct::Gated { .. } => return None,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn load_file(
     path: &Path,
     base_dir: &Path,
-    sandbox_root: &Path,
+    sandbox_roots: &[PathBuf],
     provider: &dyn SourceProvider,
+    features: &BTreeSet<String>,
     visited: &mut HashSet<PathBuf>,
     stack: &mut Vec<PathBuf>,
     stack_set: &mut HashSet<PathBuf>,
@@ -192,7 +227,10 @@ fn load_file(
         .to_string_lossy()
         .to_string();
     let tokens = lexer::lex(&src, &filename)?;
-    let constructs = parser::parse(&tokens, &filename)?;
+    let constructs: Vec<RawConstruct> = parser::parse(&tokens, &filename)?
+        .into_iter()
+        .filter_map(|c| select_feature(c, features))
+        .collect();
 
     // Check type library import constraint (§4.6):
     // A file containing only TypeDecl constructs is a type library.
@@ -227,6 +265,10 @@ fn load_file(
         ));
     }
 
+    let extends = constructs
+        .iter()
+        .any(|c| matches!(c, RawConstruct::Extends { .. }));
+
     stack_set.insert(canon.clone());
     stack.push(canon.clone());
 
@@ -236,6 +278,10 @@ fn load_file(
             RawConstruct::Import {
                 path: import_path,
                 prov,
+            }
+            | RawConstruct::Extends {
+                path: import_path,
+                prov,
             } => {
                 let resolved = provider
                     .resolve_import(base_dir, import_path)
@@ -271,7 +317,10 @@ fn load_file(
                     )
                 })?;
 
-                if !canon_import.starts_with(sandbox_root) {
+                if !sandbox_roots
+                    .iter()
+                    .any(|root| canon_import.starts_with(root))
+                {
                     return Err(ElabError::new(
                         1,
                         None,
@@ -321,8 +370,9 @@ fn load_file(
                 load_file(
                     &resolved,
                     &import_base,
-                    sandbox_root,
+                    sandbox_roots,
                     provider,
+                    features,
                     visited,
                     stack,
                     stack_set,
@@ -332,6 +382,7 @@ fn load_file(
             _ => local.push(c),
         }
     }
+    resolve_overrides(&mut local, out, extends)?;
     out.extend(local);
 
     stack.pop();
@@ -339,3 +390,73 @@ fn load_file(
     visited.insert(canon);
     Ok(())
 }
+
+/// Unwrap a feature-gated construct whose feature is enabled; drop it
+/// otherwise. Ungated constructs pass through unchanged.
+fn select_feature(c: RawConstruct, features: &BTreeSet<String>) -> Option<RawConstruct> {
+    match c {
+        RawConstruct::Gated {
+            feature, construct, ..
+        } => {
+            if features.contains(&feature) {
+                select_feature(*construct, features)
+            } else {
+                None
+            }
+        }
+        other => Some(other),
+    }
+}
+
+/// Put each `override` in `local` in place of the inherited construct it
+/// replaces in `out`, recording the replaced construct in the override.
+fn resolve_overrides(
+    local: &mut [RawConstruct],
+    out: &mut [RawConstruct],
+    extends: bool,
+) -> Result<(), ElabError> {
+    let mut overridden: HashSet<(&str, String)> = HashSet::new();
+    for c in local.iter_mut() {
+        let RawConstruct::Override {
+            construct, base, ..
+        } = c
+        else {
+            continue;
+        };
+        let Some((kind, id, prov)) = declaration(construct) else {
+            continue;
+        };
+        let err = |message: String| {
+            ElabError::new(
+                1,
+                Some(kind),
+                Some(id),
+                Some("override"),
+                &prov.file,
+                prov.line,
+                message,
+            )
+        };
+        if !extends {
+            return Err(err(format!(
+                "override of {} '{}' requires an extends declaration",
+                kind, id
+            )));
+        }
+        if !overridden.insert((kind, id.to_string())) {
+            return Err(err(format!("duplicate override of {} '{}'", kind, id)));
+        }
+        let Some(target) = out
+            .iter_mut()
+            .find(|o| declaration(o).is_some_and(|(k, i, _)| k == kind && i == id))
+        else {
+            return Err(err(format!(
+                "override of {} '{}': the base contract does not declare it",
+                kind, id
+            )));
+        };
+        let replaced = std::mem::replace(target, (**construct).clone());
+        *base = Some(Box::new(replaced));
+    }
+    Ok(())
+}
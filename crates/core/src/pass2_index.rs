@@ -16,6 +16,8 @@ pub struct Index {
     pub personas: HashMap<String, Provenance>,
     pub systems: HashMap<String, Provenance>,
     pub sources: HashMap<String, Provenance>,
+    pub tests: HashMap<String, Provenance>,
+    pub asserts: HashMap<String, Provenance>,
     /// Map from rule_id -> verdict_type name produced by that rule
     pub rule_verdicts: HashMap<String, String>,
     /// Map from verdict_type -> (rule_id, stratum) of the producing rule
@@ -37,6 +39,8 @@ pub fn build_index(constructs: &[RawConstruct]) -> Result<Index, ElabError> {
         personas: HashMap::new(),
         systems: HashMap::new(),
         sources: HashMap::new(),
+        tests: HashMap::new(),
+        asserts: HashMap::new(),
         rule_verdicts: HashMap::new(),
         verdict_strata: HashMap::new(),
         operation_outcomes: HashMap::new(),
@@ -216,7 +220,46 @@ pub fn build_index(constructs: &[RawConstruct]) -> Result<Index, ElabError> {
                 }
                 idx.sources.insert(id.clone(), prov.clone());
             }
-            RawConstruct::Import { .. } => {}
+            RawConstruct::Test { id, prov, .. } => {
+                if let Some(first) = idx.tests.get(id) {
+                    return Err(ElabError::new(
+                        2,
+                        Some("Test"),
+                        Some(id),
+                        Some("id"),
+                        &prov.file,
+                        prov.line,
+                        format!(
+                            "duplicate Test id '{}': first declared at line {}",
+                            id, first.line
+                        ),
+                    ));
+                }
+                idx.tests.insert(id.clone(), prov.clone());
+            }
+            RawConstruct::Assert { id, prov, .. } => {
+                if let Some(first) = idx.asserts.get(id) {
+                    return Err(ElabError::new(
+                        2,
+                        Some("Assert"),
+                        Some(id),
+                        Some("id"),
+                        &prov.file,
+                        prov.line,
+                        format!(
+                            "duplicate Assert '{}': first declared at line {}",
+                            id, first.line
+                        ),
+                    ));
+                }
+                idx.asserts.insert(id.clone(), prov.clone());
+            }
+            // Pass 1 has put overriding constructs in place of their bases
+            // and resolved feature gates
+            RawConstruct::Import { .. }
+            | RawConstruct::Extends { .. }
+            | RawConstruct::Override { .. }
+            | RawConstruct::Gated { .. } => {}
         }
     }
 
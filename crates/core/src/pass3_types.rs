@@ -2,13 +2,24 @@
 //! detect cycles, build the name-to-concrete-type map.
 
 use crate::ast::*;
-use crate::error::ElabError;
+use crate::error::{ElabError, ErrorSink};
 use crate::pass2_index::Index;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 pub type TypeEnv = HashMap<String, RawType>;
 
-pub fn build_type_env(constructs: &[RawConstruct], _index: &Index) -> Result<TypeEnv, ElabError> {
+pub fn build_type_env(constructs: &[RawConstruct], index: &Index) -> Result<TypeEnv, ElabError> {
+    build_type_env_recovering(constructs, index, &mut ErrorSink::fail_fast())
+}
+
+/// [`build_type_env`], reporting each TypeDecl that fails to resolve to
+/// `sink` and leaving it out of the environment. A cycle stops the pass at
+/// the first one found.
+pub fn build_type_env_recovering(
+    constructs: &[RawConstruct],
+    _index: &Index,
+    sink: &mut ErrorSink,
+) -> Result<TypeEnv, ElabError> {
     let mut decls: BTreeMap<String, (BTreeMap<String, RawType>, Provenance)> = BTreeMap::new();
     for c in constructs {
         if let RawConstruct::TypeDecl { id, fields, prov } = c {
@@ -26,8 +37,9 @@ pub fn build_type_env(constructs: &[RawConstruct], _index: &Index) -> Result<Typ
     let mut env: TypeEnv = HashMap::new();
     let names: Vec<String> = decls.keys().cloned().collect();
     for name in &names {
-        let t = resolve_typedecl(name, &decls, &env)?;
-        env.insert(name.clone(), t);
+        if let Some(t) = sink.check(resolve_typedecl(name, &decls, &env))? {
+            env.insert(name.clone(), t);
+        }
     }
 
     Ok(env)
@@ -273,6 +285,9 @@ mod tests {
                 Provenance {
                     file: "test.tenor".to_string(),
                     line: 1,
+                    span: Span::default(),
+                    range: SourceRange::default(),
+                    annotations: Annotations::default(),
                 },
             ),
         );
@@ -285,6 +300,9 @@ mod tests {
                 Provenance {
                     file: "test.tenor".to_string(),
                     line: 5,
+                    span: Span::default(),
+                    range: SourceRange::default(),
+                    annotations: Annotations::default(),
                 },
             ),
         );
@@ -309,6 +327,9 @@ mod tests {
                 Provenance {
                     file: "test.tenor".to_string(),
                     line: 1,
+                    span: Span::default(),
+                    range: SourceRange::default(),
+                    annotations: Annotations::default(),
                 },
             ),
         );
@@ -4,8 +4,9 @@
 //! predicate expressions and produce clauses.
 
 use crate::ast::*;
-use crate::error::ElabError;
+use crate::error::{ElabError, ErrorSink};
 use crate::pass3_types::TypeEnv;
+use crate::units::{unit_of, Unit};
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 // ──────────────────────────────────────────────────────────────────────────────
@@ -15,10 +16,22 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 pub fn resolve_types(
     constructs: Vec<RawConstruct>,
     type_env: &TypeEnv,
+) -> Result<Vec<RawConstruct>, ElabError> {
+    resolve_types_recovering(constructs, type_env, &mut ErrorSink::fail_fast())
+}
+
+/// [`resolve_types`], reporting each construct that fails to resolve to
+/// `sink` and dropping it from the result.
+pub fn resolve_types_recovering(
+    constructs: Vec<RawConstruct>,
+    type_env: &TypeEnv,
+    sink: &mut ErrorSink,
 ) -> Result<Vec<RawConstruct>, ElabError> {
     let mut out = Vec::new();
     for c in constructs {
-        out.push(resolve_construct(c, type_env)?);
+        if let Some(c) = sink.check(resolve_construct(c, type_env))? {
+            out.push(c);
+        }
     }
     Ok(out)
 }
@@ -65,6 +78,48 @@ fn resolve_construct(c: RawConstruct, env: &TypeEnv) -> Result<RawConstruct, Ela
                 prov,
             })
         }
+        RawConstruct::Entity {
+            id,
+            states,
+            initial,
+            initial_line,
+            transitions,
+            attributes,
+            parent,
+            parent_line,
+            prov,
+        } => {
+            let mut resolved = BTreeMap::new();
+            for (name, t) in &attributes {
+                resolved.insert(
+                    name.clone(),
+                    resolve_raw_type(t, env, &prov.file, prov.line)?,
+                );
+            }
+            Ok(RawConstruct::Entity {
+                id,
+                states,
+                initial,
+                initial_line,
+                transitions,
+                attributes: resolved,
+                parent,
+                parent_line,
+                prov,
+            })
+        }
+        RawConstruct::Override {
+            construct,
+            base,
+            prov,
+        } => Ok(RawConstruct::Override {
+            construct: Box::new(resolve_construct(*construct, env)?),
+            base: match base {
+                Some(b) => Some(Box::new(resolve_construct(*b, env)?)),
+                None => None,
+            },
+            prov,
+        }),
         other => Ok(other),
     }
 }
@@ -116,7 +171,11 @@ fn resolve_raw_type(
 // Pass 4b: expression type-checking (unresolved refs + type errors)
 // ──────────────────────────────────────────────────────────────────────────────
 
-pub fn type_check_rules(constructs: &[RawConstruct]) -> Result<(), ElabError> {
+/// Type-check every rule, reporting each failing rule to `sink`.
+pub fn type_check_rules(
+    constructs: &[RawConstruct],
+    sink: &mut ErrorSink,
+) -> Result<(), ElabError> {
     let mut fact_types: HashMap<&str, &RawType> = HashMap::new();
     for c in constructs {
         if let RawConstruct::Fact { id, type_, .. } = c {
@@ -134,15 +193,18 @@ pub fn type_check_rules(constructs: &[RawConstruct]) -> Result<(), ElabError> {
             ..
         } = c
         {
-            type_check_expr(id, when, &fact_types, &HashSet::new(), prov)?;
-            type_check_produce(
-                id,
-                payload_type,
-                payload_value,
-                *produce_line,
-                &fact_types,
-                prov,
-            )?;
+            let checked =
+                type_check_expr(id, when, &fact_types, &HashSet::new(), prov).and_then(|_| {
+                    type_check_produce(
+                        id,
+                        payload_type,
+                        payload_value,
+                        *produce_line,
+                        &fact_types,
+                        prov,
+                    )
+                });
+            sink.check(checked)?;
         }
     }
     Ok(())
@@ -220,6 +282,154 @@ fn type_of_fact_term<'a>(
     }
 }
 
+/// The currency of a Money-valued fact reference or conversion, if known
+/// statically.
+fn money_currency<'a>(
+    term: &RawTerm,
+    fact_types: &'a HashMap<&str, &RawType>,
+    bound_vars: &HashSet<&str>,
+) -> Option<&'a str> {
+    let fact = match term {
+        RawTerm::Convert { rate, .. } => rate.as_str(),
+        RawTerm::FactRef(name) if !bound_vars.contains(name.as_str()) => name.as_str(),
+        _ => return None,
+    };
+    match fact_types.get(fact).copied() {
+        Some(RawType::Money { currency, .. }) => Some(currency.as_str()),
+        _ => None,
+    }
+}
+
+/// The unit of measure of a numeric comparison operand. Int facts and
+/// Decimal facts without a unit are dimensionless; literals take the unit
+/// of the other operand and yield `None`, as do operands whose unit is not
+/// statically known.
+fn term_unit(
+    term: &RawTerm,
+    fact_types: &HashMap<&str, &RawType>,
+    bound_vars: &HashSet<&str>,
+) -> Option<Unit> {
+    match term {
+        RawTerm::FactRef(name) if !bound_vars.contains(name.as_str()) => {
+            match fact_types.get(name.as_str()).copied() {
+                Some(RawType::Decimal { unit, .. }) => Some(unit_of(unit.as_deref())),
+                Some(RawType::Int { .. }) => Some(Unit::dimensionless()),
+                _ => None,
+            }
+        }
+        RawTerm::Mul { left, right } => term_unit(left, fact_types, bound_vars)
+            .or_else(|| term_unit(right, fact_types, bound_vars)),
+        RawTerm::Convert { amount, rate } => match fact_types.get(rate.as_str()).copied() {
+            Some(RawType::Decimal { unit, .. }) => {
+                term_unit(amount, fact_types, bound_vars).map(|u| u.mul(&unit_of(unit.as_deref())))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// convert(amount, rate): `rate` must be a declared Money fact, and a
+/// statically typed `amount` must be Money in a different currency; or
+/// `rate` is a Decimal conversion factor with a unit, and `amount` a
+/// Decimal quantity.
+fn type_check_convert(
+    rule_id: &str,
+    amount: &RawTerm,
+    rate: &str,
+    fact_types: &HashMap<&str, &RawType>,
+    bound_vars: &HashSet<&str>,
+    prov: &Provenance,
+    line: u32,
+) -> Result<(), ElabError> {
+    let err = |message: String| {
+        ElabError::new(
+            4,
+            Some("Rule"),
+            Some(rule_id),
+            Some("body.when"),
+            &prov.file,
+            line,
+            message,
+        )
+    };
+    let to = match fact_types.get(rate) {
+        None => {
+            return Err(err(format!(
+                "unresolved fact reference: '{}' is not declared in this contract",
+                rate
+            )))
+        }
+        Some(RawType::Money { currency, .. }) => currency,
+        Some(RawType::Decimal { unit: Some(_), .. }) => {
+            // Unit conversion: the factor's unit multiplies the amount's.
+            return match amount {
+                RawTerm::FactRef(name) if !bound_vars.contains(name.as_str()) => {
+                    match fact_types.get(name.as_str()) {
+                        None => Err(err(format!(
+                            "unresolved fact reference: '{}' is not declared in this contract",
+                            name
+                        ))),
+                        Some(RawType::Decimal { .. }) => Ok(()),
+                        Some(t) => Err(err(format!(
+                            "type error: convert() amount '{}' has type {}; a unit conversion factor converts Decimal quantities",
+                            name,
+                            type_name(t)
+                        ))),
+                    }
+                }
+                RawTerm::FactRef(_) | RawTerm::FieldRef { .. } => Ok(()),
+                _ => Err(err(
+                    "type error: convert() amount must be a Decimal fact or bound variable"
+                        .to_string(),
+                )),
+            };
+        }
+        Some(t) => {
+            return Err(err(format!(
+                "type error: conversion rate '{}' has type {}; rate must be Money-typed or a Decimal with a unit",
+                rate,
+                type_name(t)
+            )))
+        }
+    };
+    let from = match amount {
+        RawTerm::Literal(RawLiteral::Money { currency, .. }) => currency,
+        RawTerm::FactRef(name) if !bound_vars.contains(name.as_str()) => {
+            match fact_types.get(name.as_str()) {
+                None => {
+                    return Err(err(format!(
+                        "unresolved fact reference: '{}' is not declared in this contract",
+                        name
+                    )))
+                }
+                Some(RawType::Money { currency, .. }) => currency,
+                Some(t) => {
+                    return Err(err(format!(
+                        "type error: convert() amount '{}' has type {}; amount must be Money-typed",
+                        name,
+                        type_name(t)
+                    )))
+                }
+            }
+        }
+        RawTerm::FactRef(_) | RawTerm::FieldRef { .. } => return Ok(()),
+        _ => {
+            return Err(err(
+                "type error: convert() amount must be a Money fact, bound variable or literal"
+                    .to_string(),
+            ))
+        }
+    };
+    if from == to {
+        return Err(err(format!(
+            "type error: convert() amount is already Money(currency: {}); rate '{}' converts into the same currency",
+            from, rate
+        )));
+    }
+    Ok(())
+}
+
 pub fn type_name(t: &RawType) -> String {
     match t {
         RawType::Bool => "Bool".to_owned(),
@@ -227,7 +437,7 @@ pub fn type_name(t: &RawType) -> String {
         RawType::Decimal { .. } => "Decimal".to_owned(),
         RawType::Text { .. } => "Text".to_owned(),
         RawType::Enum { .. } => "Enum".to_owned(),
-        RawType::Money { currency } => format!("Money(currency: {})", currency),
+        RawType::Money { currency, .. } => format!("Money(currency: {})", currency),
         RawType::Date => "Date".to_owned(),
         RawType::DateTime => "DateTime".to_owned(),
         RawType::Duration { .. } => "Duration".to_owned(),
@@ -238,6 +448,21 @@ pub fn type_name(t: &RawType) -> String {
     }
 }
 
+/// Rules are evaluated over facts alone; entity state is only visible to
+/// operation preconditions, transition guards and flow branch conditions.
+fn instance_predicate_in_rule(rule_id: &str, prov: &Provenance, line: u32) -> ElabError {
+    ElabError::new(
+        4,
+        Some("Rule"),
+        Some(rule_id),
+        Some("body.when"),
+        &prov.file,
+        line,
+        "entity instance predicates are not permitted in rule bodies; rules range over facts only"
+            .to_string(),
+    )
+}
+
 fn type_check_expr(
     rule_id: &str,
     expr: &RawExpr,
@@ -251,7 +476,14 @@ fn type_check_expr(
             left,
             right,
             line,
+            ..
         } => {
+            if [left, right]
+                .iter()
+                .any(|t| matches!(t, RawTerm::InstanceState(_)))
+            {
+                return Err(instance_predicate_in_rule(rule_id, prov, *line));
+            }
             for term in &[left, right] {
                 if let RawTerm::Mul {
                     left: ml,
@@ -269,6 +501,11 @@ fn type_check_expr(
                     }
                 }
             }
+            for term in &[left, right] {
+                if let RawTerm::Convert { amount, rate } = term {
+                    type_check_convert(rule_id, amount, rate, fact_types, bound_vars, prov, *line)?;
+                }
+            }
             for term in &[left, right] {
                 if let RawTerm::FactRef(name) = term {
                     if !bound_vars.contains(name.as_str())
@@ -312,29 +549,45 @@ fn type_check_expr(
                             format!("type error: operator '{}' not defined for Enum; Enum supports only = and !=", op),
                         ));
                     }
-                    RawType::Money { currency: lc } => {
-                        if let Some(RawType::Money { currency: rc }) =
-                            type_of_fact_term(right, fact_types, bound_vars)
-                        {
-                            if lc != rc {
-                                return Err(ElabError::new(
-                                    4, Some("Rule"), Some(rule_id), Some("body.when"),
-                                    &prov.file, *line,
-                                    format!("type error: cannot compare Money(currency: {}) with Money(currency: {}); Money comparisons require identical currency codes", lc, rc),
-                                ));
-                            }
-                        }
-                    }
                     _ => {}
                 }
             }
+            if let (Some(lu), Some(ru)) = (
+                term_unit(left, fact_types, bound_vars),
+                term_unit(right, fact_types, bound_vars),
+            ) {
+                if lu != ru {
+                    return Err(ElabError::new(
+                        4, Some("Rule"), Some(rule_id), Some("body.when"),
+                        &prov.file, *line,
+                        format!("type error: cannot compare a quantity in unit {} with a quantity in unit {}; convert one side with convert(amount, factor)", lu, ru),
+                    ));
+                }
+            }
+            if let (Some(lc), Some(rc)) = (
+                money_currency(left, fact_types, bound_vars),
+                money_currency(right, fact_types, bound_vars),
+            ) {
+                if lc != rc {
+                    return Err(ElabError::new(
+                        4, Some("Rule"), Some(rule_id), Some("body.when"),
+                        &prov.file, *line,
+                        format!("type error: cannot compare Money(currency: {}) with Money(currency: {}); Money comparisons require identical currency codes", lc, rc),
+                    ));
+                }
+            }
         }
         RawExpr::Forall {
             var,
             domain,
+            over_instances,
             body,
             line,
+            ..
         } => {
+            if *over_instances {
+                return Err(instance_predicate_in_rule(rule_id, prov, *line));
+            }
             if !fact_types.contains_key(domain.as_str()) {
                 return Err(ElabError::new(
                     4,
@@ -372,9 +625,14 @@ fn type_check_expr(
         RawExpr::Exists {
             var,
             domain,
+            over_instances,
             body,
             line,
+            ..
         } => {
+            if *over_instances {
+                return Err(instance_predicate_in_rule(rule_id, prov, *line));
+            }
             if !fact_types.contains_key(domain.as_str()) {
                 return Err(ElabError::new(
                     4,
@@ -413,7 +671,7 @@ fn type_check_expr(
             type_check_expr(rule_id, a, fact_types, bound_vars, prov)?;
             type_check_expr(rule_id, b, fact_types, bound_vars, prov)?;
         }
-        RawExpr::Not(e) => {
+        RawExpr::Not(e, _) => {
             type_check_expr(rule_id, e, fact_types, bound_vars, prov)?;
         }
         RawExpr::VerdictPresent { .. } => {}
@@ -0,0 +1,78 @@
+//! Assert construct validation functions.
+
+use crate::ast::*;
+use crate::error::ElabError;
+use std::collections::{BTreeSet, VecDeque};
+
+// ── Assert validation ─────────────────────────────────────────────────────────
+
+pub(super) fn validate_assert(
+    id: &str,
+    entity: &str,
+    property: RawAssertProperty,
+    entity_line: u32,
+    prov: &Provenance,
+    constructs: &[RawConstruct],
+) -> Result<(), ElabError> {
+    let err = |msg: String| {
+        ElabError::new(
+            5,
+            Some("Assert"),
+            Some(id),
+            Some("entity"),
+            &prov.file,
+            entity_line,
+            msg,
+        )
+    };
+
+    // C-ASR-02: the asserted entity must be declared
+    let Some((states, initial, transitions)) = constructs.iter().find_map(|c| match c {
+        RawConstruct::Entity {
+            id: entity_id,
+            states,
+            initial,
+            transitions,
+            ..
+        } if entity_id == entity => Some((states, initial, transitions)),
+        _ => None,
+    }) else {
+        return Err(err(format!(
+            "assert '{}' refers to undeclared entity '{}'",
+            id, entity
+        )));
+    };
+
+    match property {
+        // C-ASR-03: every declared state is reachable from the initial state
+        // through the declared transitions. Guards are not evaluated.
+        RawAssertProperty::Reachable => {
+            let mut reached: BTreeSet<&str> = BTreeSet::new();
+            let mut queue: VecDeque<&str> = VecDeque::new();
+            reached.insert(initial);
+            queue.push_back(initial);
+            while let Some(state) = queue.pop_front() {
+                for t in transitions.iter().filter(|t| t.from == state) {
+                    if reached.insert(&t.to) {
+                        queue.push_back(&t.to);
+                    }
+                }
+            }
+            let unreachable: Vec<&str> = states
+                .iter()
+                .map(String::as_str)
+                .filter(|s| !reached.contains(s))
+                .collect();
+            if !unreachable.is_empty() {
+                return Err(err(format!(
+                    "assertion 'forall states of {}: reachable' does not hold: {} unreachable from initial state '{}'",
+                    entity,
+                    unreachable.join(", "),
+                    initial
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -13,7 +13,7 @@ pub(super) fn validate_entity(
     states: &[String],
     initial: &str,
     initial_line: u32,
-    transitions: &[(String, String, u32)],
+    transitions: &[RawTransition],
     _parent: Option<&str>,
     _parent_line: Option<u32>,
     prov: &Provenance,
@@ -38,7 +38,13 @@ pub(super) fn validate_entity(
         ));
     }
 
-    for (from, to, t_line) in transitions {
+    for RawTransition {
+        from,
+        to,
+        line: t_line,
+        ..
+    } in transitions
+    {
         if !state_set.contains(from.as_str()) {
             return Err(ElabError::new(
                 5,
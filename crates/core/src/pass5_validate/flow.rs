@@ -125,6 +125,23 @@ pub(super) fn validate_flow(
             }
             RawStep::ParallelStep { .. } => {}
         }
+        if let Some(RawTimeout {
+            on_timeout: RawTimeoutAction::Goto(RawStepTarget::StepRef(r, ref_line)),
+            ..
+        }) = step_timeout(step)
+        {
+            if !steps.contains_key(r.as_str()) {
+                return Err(ElabError::new(
+                    5,
+                    Some("Flow"),
+                    Some(id),
+                    Some(&format!("steps.{}.timeout.on_timeout", step_id)),
+                    &prov.file,
+                    *ref_line,
+                    format!("step reference '{}' is not declared in steps", r),
+                ));
+            }
+        }
     }
 
     // KL-3: Validate outcome exhaustiveness for OperationSteps
@@ -262,6 +279,13 @@ pub(super) fn detect_step_cycle(
             }
             RawStep::ParallelStep { .. } => {}
         }
+        if let Some(RawTimeout {
+            on_timeout: RawTimeoutAction::Goto(RawStepTarget::StepRef(r, _)),
+            ..
+        }) = step_timeout(step)
+        {
+            neighbors.push(r.as_str());
+        }
         adj.insert(sid.as_str(), neighbors);
     }
 
@@ -341,6 +365,161 @@ pub(super) fn detect_step_cycle(
     Ok(())
 }
 
+fn step_timeout(step: &RawStep) -> Option<&RawTimeout> {
+    match step {
+        RawStep::OperationStep { timeout, .. } | RawStep::HandoffStep { timeout, .. } => {
+            timeout.as_ref()
+        }
+        _ => None,
+    }
+}
+
+// ── Step deadlines ────────────────────────────────────────────────────────────
+
+/// Every step `timeout` must name a declared DateTime fact as its deadline.
+pub(super) fn validate_step_deadlines(constructs: &[RawConstruct]) -> Result<(), ElabError> {
+    let fact_types: HashMap<&str, &RawType> = constructs
+        .iter()
+        .filter_map(|c| match c {
+            RawConstruct::Fact { id, type_, .. } => Some((id.as_str(), type_)),
+            _ => None,
+        })
+        .collect();
+    for c in constructs {
+        if let RawConstruct::Flow {
+            id, steps, prov, ..
+        } = c
+        {
+            check_deadlines(id, steps, prov, &fact_types)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_deadlines(
+    flow_id: &str,
+    steps: &BTreeMap<String, RawStep>,
+    prov: &Provenance,
+    fact_types: &HashMap<&str, &RawType>,
+) -> Result<(), ElabError> {
+    for (step_id, step) in steps {
+        if let RawStep::ParallelStep { branches, .. } = step {
+            for branch in branches {
+                check_deadlines(flow_id, &branch.steps, prov, fact_types)?;
+            }
+        }
+        let Some(timeout) = step_timeout(step) else {
+            continue;
+        };
+        let message = match fact_types.get(timeout.deadline.as_str()) {
+            None => format!(
+                "timeout deadline '{}' is not a declared fact",
+                timeout.deadline
+            ),
+            Some(RawType::DateTime) => continue,
+            Some(_) => format!(
+                "timeout deadline '{}' must be a DateTime fact",
+                timeout.deadline
+            ),
+        };
+        return Err(ElabError::new(
+            5,
+            Some("Flow"),
+            Some(flow_id),
+            Some(&format!("steps.{}.timeout.deadline", step_id)),
+            &prov.file,
+            timeout.deadline_line,
+            message,
+        ));
+    }
+    Ok(())
+}
+
+// ── Step weights ──────────────────────────────────────────────────────────────
+
+/// Step `weights` must name the step's outcomes (`true`/`false` for a
+/// BranchStep; outcome labels or `failure` for an OperationStep), each
+/// between 0 and 1, together at most 1.
+pub(super) fn validate_step_weights(constructs: &[RawConstruct]) -> Result<(), ElabError> {
+    for c in constructs {
+        if let RawConstruct::Flow {
+            id, steps, prov, ..
+        } = c
+        {
+            check_weights(id, steps, prov)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_weights(
+    flow_id: &str,
+    steps: &BTreeMap<String, RawStep>,
+    prov: &Provenance,
+) -> Result<(), ElabError> {
+    for (step_id, step) in steps {
+        let (weights, labels, line): (&BTreeMap<String, f64>, Vec<&str>, u32) = match step {
+            RawStep::OperationStep {
+                weights,
+                outcomes,
+                line,
+                ..
+            } => (
+                weights,
+                outcomes
+                    .keys()
+                    .map(String::as_str)
+                    .chain(["failure"])
+                    .collect(),
+                *line,
+            ),
+            RawStep::BranchStep { weights, line, .. } => (weights, vec!["true", "false"], *line),
+            RawStep::ParallelStep { branches, .. } => {
+                for branch in branches {
+                    check_weights(flow_id, &branch.steps, prov)?;
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let error = |message: String| {
+            ElabError::new(
+                5,
+                Some("Flow"),
+                Some(flow_id),
+                Some(&format!("steps.{}.weights", step_id)),
+                &prov.file,
+                line,
+                message,
+            )
+        };
+        for (label, weight) in weights {
+            if !labels.contains(&label.as_str()) {
+                return Err(error(format!(
+                    "weight '{}' does not name an outcome of step '{}' (expected one of: {})",
+                    label,
+                    step_id,
+                    labels.join(", ")
+                )));
+            }
+            if !(0.0..=1.0).contains(weight) {
+                return Err(error(format!(
+                    "weight for '{}' must be between 0 and 1, got {}",
+                    label, weight
+                )));
+            }
+        }
+        let total: f64 = weights.values().sum();
+        if total > 1.0 + 1e-9 {
+            return Err(error(format!(
+                "weights of step '{}' sum to {}, more than 1",
+                step_id, total
+            )));
+        }
+    }
+    Ok(())
+}
+
 // ── Flow reference graph cycle detection ──────────────────────────────────────
 
 pub(super) fn validate_flow_reference_graph(constructs: &[RawConstruct]) -> Result<(), ElabError> {
@@ -1,26 +1,44 @@
 //! Pass 5: Construct validation -- structural checks on Entity, Rule,
-//! Operation, Flow, and System constructs.
+//! Operation, Flow, System, Test, and Assert constructs, and override
+//! compatibility.
 
+mod assert;
 mod entity;
 mod flow;
 mod operation;
+mod overrides;
 mod parallel;
+mod quantifier;
 mod rule;
 mod source;
 mod system;
+mod test;
 
 use crate::ast::*;
-use crate::error::ElabError;
+use crate::error::{ElabError, ErrorSink};
 use crate::pass2_index::Index;
 use std::collections::{HashMap, HashSet};
 
-pub fn validate(constructs: &[RawConstruct], index: &Index) -> Result<(), ElabError> {
-    validate_verdict_uniqueness(constructs)?;
+/// Validate every construct, reporting each failing construct and each
+/// failing bundle-wide check to `sink`.
+pub fn validate(
+    constructs: &[RawConstruct],
+    index: &Index,
+    sink: &mut ErrorSink,
+) -> Result<(), ElabError> {
+    sink.check(validate_verdict_uniqueness(constructs))?;
 
     let produced_verdicts: HashSet<String> = index.rule_verdicts.values().cloned().collect();
+    let fact_types: HashMap<&str, &RawType> = constructs
+        .iter()
+        .filter_map(|c| match c {
+            RawConstruct::Fact { id, type_, .. } => Some((id.as_str(), type_)),
+            _ => None,
+        })
+        .collect();
 
     for c in constructs {
-        match c {
+        let result = match c {
             RawConstruct::Entity {
                 id,
                 states,
@@ -30,19 +48,18 @@ pub fn validate(constructs: &[RawConstruct], index: &Index) -> Result<(), ElabEr
                 parent,
                 parent_line,
                 prov,
-            } => {
-                entity::validate_entity(
-                    id,
-                    states,
-                    initial,
-                    *initial_line,
-                    transitions,
-                    parent.as_deref(),
-                    *parent_line,
-                    prov,
-                    index,
-                )?;
-            }
+                ..
+            } => entity::validate_entity(
+                id,
+                states,
+                initial,
+                *initial_line,
+                transitions,
+                parent.as_deref(),
+                *parent_line,
+                prov,
+                index,
+            ),
             RawConstruct::Rule {
                 id,
                 stratum,
@@ -50,17 +67,15 @@ pub fn validate(constructs: &[RawConstruct], index: &Index) -> Result<(), ElabEr
                 when,
                 prov,
                 ..
-            } => {
-                rule::validate_rule(
-                    id,
-                    *stratum,
-                    *stratum_line,
-                    when,
-                    prov,
-                    index,
-                    &produced_verdicts,
-                )?;
-            }
+            } => rule::validate_rule(
+                id,
+                *stratum,
+                *stratum_line,
+                when,
+                prov,
+                index,
+                &produced_verdicts,
+            ),
             RawConstruct::Operation {
                 id,
                 allowed_personas,
@@ -70,18 +85,16 @@ pub fn validate(constructs: &[RawConstruct], index: &Index) -> Result<(), ElabEr
                 error_contract,
                 prov,
                 ..
-            } => {
-                operation::validate_operation(
-                    id,
-                    allowed_personas,
-                    *allowed_personas_line,
-                    effects,
-                    outcomes,
-                    error_contract,
-                    prov,
-                    index,
-                )?;
-            }
+            } => operation::validate_operation(
+                id,
+                allowed_personas,
+                *allowed_personas_line,
+                effects,
+                outcomes,
+                error_contract,
+                prov,
+                index,
+            ),
             RawConstruct::Flow {
                 id,
                 entry,
@@ -89,9 +102,7 @@ pub fn validate(constructs: &[RawConstruct], index: &Index) -> Result<(), ElabEr
                 steps,
                 prov,
                 ..
-            } => {
-                flow::validate_flow(id, entry, *entry_line, steps, prov, index)?;
-            }
+            } => flow::validate_flow(id, entry, *entry_line, steps, prov, index),
             RawConstruct::System {
                 id,
                 members,
@@ -99,51 +110,74 @@ pub fn validate(constructs: &[RawConstruct], index: &Index) -> Result<(), ElabEr
                 triggers,
                 shared_entities,
                 prov,
-            } => {
-                system::validate_system(
-                    id,
-                    members,
-                    shared_personas,
-                    triggers,
-                    shared_entities,
-                    prov,
-                    constructs,
-                )?;
-            }
+            } => system::validate_system(
+                id,
+                members,
+                shared_personas,
+                triggers,
+                shared_entities,
+                prov,
+                constructs,
+            ),
             RawConstruct::Source {
                 id,
                 protocol,
                 fields,
                 prov,
                 ..
-            } => {
-                source::validate_source(id, protocol, fields, prov, index)?;
-            }
+            } => source::validate_source(id, protocol, fields, prov, index),
+            RawConstruct::Test {
+                id,
+                given,
+                expectations,
+                prov,
+            } => test::validate_test(
+                id,
+                given,
+                expectations,
+                prov,
+                index,
+                &fact_types,
+                &produced_verdicts,
+            ),
+            RawConstruct::Assert {
+                id,
+                entity,
+                property,
+                entity_line,
+                prov,
+            } => assert::validate_assert(id, entity, *property, *entity_line, prov, constructs),
+            RawConstruct::Override {
+                construct,
+                base: Some(base),
+                prov,
+            } => overrides::validate_override(construct, base, prov),
             RawConstruct::Fact {
                 id,
                 source: crate::ast::RawSourceDecl::Structured { source_id, .. },
                 prov,
                 ..
-            } => {
-                if !index.sources.contains_key(source_id) {
-                    return Err(ElabError::new(
-                        5,
-                        Some("Fact"),
-                        Some(id),
-                        Some("source"),
-                        &prov.file,
-                        prov.line,
-                        format!("fact '{}' references undeclared source '{}'", id, source_id),
-                    ));
-                }
-            }
-            _ => {}
-        }
+            } if !index.sources.contains_key(source_id) => Err(ElabError::new(
+                5,
+                Some("Fact"),
+                Some(id),
+                Some("source"),
+                &prov.file,
+                prov.line,
+                format!("fact '{}' references undeclared source '{}'", id, source_id),
+            )),
+            _ => Ok(()),
+        };
+        sink.check(result)?;
     }
 
-    entity::validate_entity_dag(constructs, index)?;
-    flow::validate_flow_reference_graph(constructs)?;
-    parallel::validate_parallel_conflicts(constructs)?;
+    sink.check(entity::validate_entity_dag(constructs, index))?;
+    sink.check(flow::validate_flow_reference_graph(constructs))?;
+    sink.check(flow::validate_step_deadlines(constructs))?;
+    sink.check(flow::validate_step_weights(constructs))?;
+    sink.check(operation::validate_entity_attributes(constructs))?;
+    sink.check(parallel::validate_parallel_conflicts(constructs))?;
+    sink.check(quantifier::validate_instance_quantifiers(constructs))?;
 
     Ok(())
 }
@@ -197,6 +231,9 @@ mod tests {
         Provenance {
             file: "test.tenor".to_string(),
             line: 1,
+            span: Span::default(),
+            range: SourceRange::default(),
+            annotations: Annotations::default(),
         }
     }
 
@@ -225,6 +262,8 @@ mod tests {
                 persona: "admin".to_string(),
                 outcomes: outcomes1,
                 on_failure: Some(test_failure_handler()),
+                timeout: None,
+                weights: BTreeMap::new(),
                 line: 2,
             },
         );
@@ -235,6 +274,8 @@ mod tests {
                 persona: "admin".to_string(),
                 outcomes: BTreeMap::new(),
                 on_failure: Some(test_failure_handler()),
+                timeout: None,
+                weights: BTreeMap::new(),
                 line: 3,
             },
         );
@@ -259,6 +300,8 @@ mod tests {
                 persona: "admin".to_string(),
                 outcomes: outcomes1,
                 on_failure: Some(test_failure_handler()),
+                timeout: None,
+                weights: BTreeMap::new(),
                 line: 2,
             },
         );
@@ -274,6 +317,8 @@ mod tests {
                 persona: "admin".to_string(),
                 outcomes: outcomes2,
                 on_failure: Some(test_failure_handler()),
+                timeout: None,
+                weights: BTreeMap::new(),
                 line: 3,
             },
         );
@@ -283,6 +328,59 @@ mod tests {
         assert!(err.message.contains("cycle detected"));
     }
 
+    #[test]
+    fn detect_step_cycle_follows_timeout_targets() {
+        // s1 -> s2 via its outcome, s2 -> s1 via its timeout => cycle
+        let mut steps = BTreeMap::new();
+        let mut outcomes1 = BTreeMap::new();
+        outcomes1.insert(
+            "ok".to_string(),
+            RawStepTarget::StepRef("s2".to_string(), 2),
+        );
+        steps.insert(
+            "s1".to_string(),
+            RawStep::OperationStep {
+                op: "op1".to_string(),
+                persona: "admin".to_string(),
+                outcomes: outcomes1,
+                on_failure: Some(test_failure_handler()),
+                timeout: None,
+                weights: BTreeMap::new(),
+                line: 2,
+            },
+        );
+        steps.insert(
+            "s2".to_string(),
+            RawStep::HandoffStep {
+                from_persona: "admin".to_string(),
+                to_persona: "reviewer".to_string(),
+                next: "s3".to_string(),
+                pause: false,
+                timeout: Some(RawTimeout {
+                    deadline: "review_by".to_string(),
+                    deadline_line: 4,
+                    on_timeout: RawTimeoutAction::Goto(RawStepTarget::StepRef("s1".to_string(), 5)),
+                }),
+                line: 3,
+            },
+        );
+        steps.insert(
+            "s3".to_string(),
+            RawStep::OperationStep {
+                op: "op2".to_string(),
+                persona: "reviewer".to_string(),
+                outcomes: BTreeMap::new(),
+                on_failure: Some(test_failure_handler()),
+                timeout: None,
+                weights: BTreeMap::new(),
+                line: 6,
+            },
+        );
+        let prov = test_prov();
+        let err = flow::detect_step_cycle("test_flow", "s1", &steps, &prov).unwrap_err();
+        assert!(err.message.contains("cycle detected"));
+    }
+
     #[test]
     fn trigger_acyclicity_detects_cycle() {
         // A -> B -> A triggers form a cycle
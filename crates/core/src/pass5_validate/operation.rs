@@ -3,7 +3,7 @@
 use crate::ast::*;
 use crate::error::ElabError;
 use crate::pass2_index::Index;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 // ── Operation validation ──────────────────────────────────────────────────────
 
@@ -12,7 +12,7 @@ pub(super) fn validate_operation(
     id: &str,
     allowed_personas: &[String],
     allowed_personas_line: u32,
-    effects: &[(String, String, String, Option<String>, u32)],
+    effects: &[RawEffect],
     outcomes: &[String],
     error_contract: &[String],
     prov: &Provenance,
@@ -65,7 +65,12 @@ pub(super) fn validate_operation(
         }
     }
 
-    for (entity_id, _from, _to, _outcome, e_line) in effects {
+    for RawEffect {
+        entity: entity_id,
+        line: e_line,
+        ..
+    } in effects
+    {
         if !index.entities.contains_key(entity_id.as_str()) {
             return Err(ElabError::new(
                 5,
@@ -82,7 +87,12 @@ pub(super) fn validate_operation(
     // Validate effect-to-outcome mapping for multi-outcome operations
     if outcomes.len() >= 2 {
         let outcome_set: HashSet<&str> = outcomes.iter().map(String::as_str).collect();
-        for (entity_id, from, to, outcome_label, e_line) in effects {
+        for effect in effects {
+            let RawEffect {
+                outcome: outcome_label,
+                line: e_line,
+                ..
+            } = effect;
             match outcome_label {
                 None => {
                     return Err(ElabError::new(
@@ -93,8 +103,8 @@ pub(super) fn validate_operation(
                         &prov.file,
                         *e_line,
                         format!(
-                            "effect ({}, {}, {}) is missing an outcome label; multi-outcome operations require every effect to specify which outcome it belongs to",
-                            entity_id, from, to
+                            "effect {} is missing an outcome label; multi-outcome operations require every effect to specify which outcome it belongs to",
+                            effect_tuple(effect)
                         ),
                     ));
                 }
@@ -108,8 +118,8 @@ pub(super) fn validate_operation(
                             &prov.file,
                             *e_line,
                             format!(
-                                "effect ({}, {}, {}) references undeclared outcome '{}'; declared outcomes are: [{}]",
-                                entity_id, from, to, label, outcomes.join(", ")
+                                "effect {} references undeclared outcome '{}'; declared outcomes are: [{}]",
+                                effect_tuple(effect), label, outcomes.join(", ")
                             ),
                         ));
                     }
@@ -141,14 +151,19 @@ pub(super) fn validate_operation_transitions(
     _index: &Index,
 ) -> Result<(), ElabError> {
     let mut entity_transitions: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    let mut entity_states: HashMap<&str, &[String]> = HashMap::new();
     for c in constructs {
         if let RawConstruct::Entity {
-            id, transitions, ..
+            id,
+            states,
+            transitions,
+            ..
         } = c
         {
+            entity_states.insert(id.as_str(), states);
             let list = entity_transitions.entry(id.as_str()).or_default();
-            for (f, t, _) in transitions {
-                list.push((f.as_str(), t.as_str()));
+            for t in transitions {
+                list.push((t.from.as_str(), t.to.as_str()));
             }
         }
     }
@@ -158,7 +173,49 @@ pub(super) fn validate_operation_transitions(
             id, effects, prov, ..
         } = c
         {
-            for (entity_id, from, to, _outcome, e_line) in effects {
+            for effect in effects {
+                let RawEffect {
+                    kind,
+                    entity: entity_id,
+                    from,
+                    to,
+                    line: e_line,
+                    ..
+                } = effect;
+                let lifecycle_error = |message: String| {
+                    ElabError::new(
+                        5,
+                        Some("Operation"),
+                        Some(id),
+                        Some("effects"),
+                        &prov.file,
+                        *e_line,
+                        message,
+                    )
+                };
+                match kind {
+                    RawEffectKind::Transition => {}
+                    RawEffectKind::Create => continue,
+                    RawEffectKind::Destroy => {
+                        if !effect.set.is_empty() {
+                            return Err(lifecycle_error(format!(
+                                "effect {} cannot set attributes of an instance it destroys",
+                                effect_tuple(effect)
+                            )));
+                        }
+                        if let Some(states) = entity_states.get(entity_id.as_str()) {
+                            if !states.contains(from) {
+                                return Err(lifecycle_error(format!(
+                                    "effect {} destroys from undeclared state '{}'; declared states are: [{}]",
+                                    effect_tuple(effect),
+                                    from,
+                                    states.join(", ")
+                                )));
+                            }
+                        }
+                        continue;
+                    }
+                }
                 if let Some(transitions) = entity_transitions.get(entity_id.as_str()) {
                     if !transitions.iter().any(|(f, t)| f == from && t == to) {
                         let declared: Vec<String> = transitions
@@ -182,3 +239,240 @@ pub(super) fn validate_operation_transitions(
 
     Ok(())
 }
+
+/// Render an effect the way it is written in source, for error messages.
+fn effect_tuple(effect: &RawEffect) -> String {
+    match effect.kind {
+        RawEffectKind::Transition => {
+            format!("({}, {}, {})", effect.entity, effect.from, effect.to)
+        }
+        RawEffectKind::Create => format!("(create {})", effect.entity),
+        RawEffectKind::Destroy => format!("(destroy {}, {})", effect.entity, effect.from),
+    }
+}
+
+/// Entity attributes: every attribute an effect sets must be declared on the
+/// effect's entity, and an `Entity.attribute` reference in an Operation
+/// precondition or a transition guard must name a declared attribute of an
+/// entity the construct can see -- one the Operation has an effect on, or
+/// the guarded entity itself.
+pub(super) fn validate_entity_attributes(constructs: &[RawConstruct]) -> Result<(), ElabError> {
+    let mut attributes: HashMap<&str, &BTreeMap<String, RawType>> = HashMap::new();
+    let mut fact_types: HashMap<&str, &RawType> = HashMap::new();
+    for c in constructs {
+        if let RawConstruct::Fact { id, type_, .. } = c {
+            fact_types.insert(id.as_str(), type_);
+        }
+        if let RawConstruct::Entity {
+            id, attributes: a, ..
+        } = c
+        {
+            attributes.insert(id.as_str(), a);
+        }
+    }
+
+    let check_ref = |construct: &str,
+                     id: &str,
+                     field: &str,
+                     prov: &Provenance,
+                     visible: &[&str],
+                     (var, attr, line): (&str, &str, u32)|
+     -> Result<(), ElabError> {
+        let Some(declared) = attributes.get(var) else {
+            // Not an entity: a quantifier variable or Record-typed fact
+            return Ok(());
+        };
+        let message = if !visible.contains(&var) {
+            format!(
+                "'{}.{}' reads entity '{}', which this {} does not affect",
+                var, attr, var, construct
+            )
+        } else if !declared.contains_key(attr) {
+            format!("entity '{}' declares no attribute '{}'", var, attr)
+        } else {
+            return Ok(());
+        };
+        Err(ElabError::new(
+            5,
+            Some(construct),
+            Some(id),
+            Some(field),
+            &prov.file,
+            line,
+            message,
+        ))
+    };
+
+    for c in constructs {
+        match c {
+            RawConstruct::Entity {
+                id,
+                transitions,
+                prov,
+                ..
+            } => {
+                for t in transitions {
+                    if let Some(guard) = &t.guard {
+                        let mut refs = Vec::new();
+                        collect_attribute_refs(guard, &mut Vec::new(), &mut refs);
+                        for r in refs {
+                            check_ref("Entity", id, "transitions", prov, &[id.as_str()], r)?;
+                        }
+                    }
+                }
+            }
+            RawConstruct::Operation {
+                id,
+                precondition,
+                effects,
+                prov,
+                ..
+            } => {
+                for effect in effects {
+                    let Some(declared) = attributes.get(effect.entity.as_str()) else {
+                        continue;
+                    };
+                    for (name, term) in &effect.set {
+                        let message = match declared.get(name) {
+                            None => format!(
+                                "effect sets undeclared attribute '{}' of entity '{}'",
+                                name, effect.entity
+                            ),
+                            Some(t) => match set_term_mismatch(term, t, &fact_types) {
+                                Some(got) => format!(
+                                    "effect sets attribute '{}' of entity '{}' ({}) to {}",
+                                    name,
+                                    effect.entity,
+                                    crate::pass4_typecheck::type_name(t),
+                                    got
+                                ),
+                                None => continue,
+                            },
+                        };
+                        return Err(ElabError::new(
+                            5,
+                            Some("Operation"),
+                            Some(id),
+                            Some("effects"),
+                            &prov.file,
+                            effect.line,
+                            message,
+                        ));
+                    }
+                }
+                // A created instance has no attributes until its effect applies
+                let visible: Vec<&str> = effects
+                    .iter()
+                    .filter(|e| e.kind != RawEffectKind::Create)
+                    .map(|e| e.entity.as_str())
+                    .collect();
+                let mut refs = Vec::new();
+                collect_attribute_refs(precondition, &mut Vec::new(), &mut refs);
+                for r in refs {
+                    check_ref("Operation", id, "precondition", prov, &visible, r)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Describe `term` if it cannot be assigned to an attribute of type `t`.
+/// Field references and products are checked at evaluation time.
+fn set_term_mismatch(
+    term: &RawTerm,
+    t: &RawType,
+    fact_types: &HashMap<&str, &RawType>,
+) -> Option<String> {
+    match term {
+        RawTerm::FactRef(fact) => {
+            let Some(ft) = fact_types.get(fact.as_str()) else {
+                return Some(format!("undeclared fact '{}'", fact));
+            };
+            let compatible = std::mem::discriminant(*ft) == std::mem::discriminant(t)
+                || matches!((ft, t), (RawType::Int { .. }, RawType::Decimal { .. }));
+            (!compatible).then(|| {
+                format!(
+                    "fact '{}' of type {}",
+                    fact,
+                    crate::pass4_typecheck::type_name(ft)
+                )
+            })
+        }
+        RawTerm::Literal(lit) => {
+            let compatible = match (lit, t) {
+                (RawLiteral::Bool(_), RawType::Bool) => true,
+                (RawLiteral::Int(n), RawType::Int { min, max }) => min <= n && n <= max,
+                (RawLiteral::Int(_) | RawLiteral::Float(_), RawType::Decimal { .. }) => true,
+                (RawLiteral::Str(v), RawType::Enum { values }) => values.contains(v),
+                (RawLiteral::Str(_), RawType::Text { .. } | RawType::Date | RawType::DateTime) => {
+                    true
+                }
+                (RawLiteral::Money { currency, .. }, RawType::Money { currency: c, .. }) => {
+                    currency == c
+                }
+                _ => false,
+            };
+            (!compatible).then(|| "an incompatible literal".to_string())
+        }
+        RawTerm::InstanceState(var) => Some(format!("the state of unbound instance '{}'", var)),
+        RawTerm::Convert { rate, .. } => match (fact_types.get(rate.as_str()), t) {
+            (Some(RawType::Money { currency: to, .. }), RawType::Money { currency: c, .. })
+                if to == c =>
+            {
+                None
+            }
+            _ => Some(format!("an amount converted by '{}'", rate)),
+        },
+        RawTerm::FieldRef { .. } | RawTerm::Mul { .. } => None,
+    }
+}
+
+/// Collect `(var, field, line)` for every field reference in `expr` whose
+/// variable is not bound by an enclosing quantifier.
+fn collect_attribute_refs<'a>(
+    expr: &'a RawExpr,
+    bound: &mut Vec<&'a str>,
+    out: &mut Vec<(&'a str, &'a str, u32)>,
+) {
+    match expr {
+        RawExpr::Compare {
+            left, right, line, ..
+        } => {
+            for term in [left, right] {
+                collect_term_attribute_refs(term, bound, *line, out);
+            }
+        }
+        RawExpr::And(a, b) | RawExpr::Or(a, b) => {
+            collect_attribute_refs(a, bound, out);
+            collect_attribute_refs(b, bound, out);
+        }
+        RawExpr::Not(e, _) => collect_attribute_refs(e, bound, out),
+        RawExpr::Forall { var, body, .. } | RawExpr::Exists { var, body, .. } => {
+            bound.push(var.as_str());
+            collect_attribute_refs(body, bound, out);
+            bound.pop();
+        }
+        RawExpr::VerdictPresent { .. } => {}
+    }
+}
+
+fn collect_term_attribute_refs<'a>(
+    term: &'a RawTerm,
+    bound: &[&'a str],
+    line: u32,
+    out: &mut Vec<(&'a str, &'a str, u32)>,
+) {
+    match term {
+        RawTerm::FieldRef { var, field } if !bound.contains(&var.as_str()) => {
+            out.push((var.as_str(), field.as_str(), line));
+        }
+        RawTerm::Mul { left, right } => {
+            collect_term_attribute_refs(left, bound, line, out);
+            collect_term_attribute_refs(right, bound, line, out);
+        }
+        RawTerm::Convert { amount, .. } => collect_term_attribute_refs(amount, bound, line, out),
+        _ => {}
+    }
+}
@@ -0,0 +1,143 @@
+//! Override compatibility: a construct overridden through `extends` must
+//! keep the interface other constructs rely on.
+
+use crate::ast::*;
+use crate::error::ElabError;
+use crate::pass4_typecheck::type_name;
+use std::collections::BTreeSet;
+
+// ── Override validation ───────────────────────────────────────────────────────
+
+/// A Rule override keeps its verdict type and payload type; an Operation
+/// override keeps its entity effects and outcomes. Preconditions,
+/// conditions, strata, personas and error contracts may change.
+pub(super) fn validate_override(
+    construct: &RawConstruct,
+    base: &RawConstruct,
+    prov: &Provenance,
+) -> Result<(), ElabError> {
+    match (construct, base) {
+        (
+            RawConstruct::Rule {
+                id,
+                verdict_type,
+                payload_type,
+                produce_line,
+                ..
+            },
+            RawConstruct::Rule {
+                verdict_type: base_verdict,
+                payload_type: base_payload,
+                prov: base_prov,
+                ..
+            },
+        ) => {
+            let err = |msg: String| {
+                ElabError::new(
+                    5,
+                    Some("Rule"),
+                    Some(id),
+                    Some("produce"),
+                    &prov.file,
+                    *produce_line,
+                    msg,
+                )
+            };
+            if verdict_type != base_verdict {
+                return Err(err(format!(
+                    "override of rule '{}' produces verdict '{}', but the base rule at {}:{} produces '{}'",
+                    id, verdict_type, base_prov.file, base_prov.line, base_verdict
+                )));
+            }
+            if payload_type != base_payload {
+                return Err(err(format!(
+                    "override of rule '{}' changes the payload type of verdict '{}' from {} to {}",
+                    id,
+                    verdict_type,
+                    type_name(base_payload),
+                    type_name(payload_type)
+                )));
+            }
+        }
+        (
+            RawConstruct::Operation {
+                id,
+                effects,
+                outcomes,
+                ..
+            },
+            RawConstruct::Operation {
+                effects: base_effects,
+                outcomes: base_outcomes,
+                prov: base_prov,
+                ..
+            },
+        ) => {
+            let err = |field: &str, msg: String| {
+                ElabError::new(
+                    5,
+                    Some("Operation"),
+                    Some(id),
+                    Some(field),
+                    &prov.file,
+                    prov.line,
+                    msg,
+                )
+            };
+            if effect_set(effects) != effect_set(base_effects) {
+                return Err(err(
+                    "effects",
+                    format!(
+                        "override of operation '{}' changes its entity effects; the base operation at {}:{} declares [{}]",
+                        id,
+                        base_prov.file,
+                        base_prov.line,
+                        describe_effects(base_effects)
+                    ),
+                ));
+            }
+            let set = |o: &[String]| o.iter().cloned().collect::<BTreeSet<_>>();
+            if set(outcomes) != set(base_outcomes) {
+                return Err(err(
+                    "outcomes",
+                    format!(
+                        "override of operation '{}' changes its outcomes from [{}] to [{}]",
+                        id,
+                        base_outcomes.join(", "),
+                        outcomes.join(", ")
+                    ),
+                ));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The entity interface of an operation: what each effect does to which
+/// entity, ignoring attribute assignments.
+fn effect_set(effects: &[RawEffect]) -> BTreeSet<(String, &str, &str, Option<&str>)> {
+    effects
+        .iter()
+        .map(|e| {
+            (
+                format!("{:?}:{}", e.kind, e.entity),
+                e.from.as_str(),
+                e.to.as_str(),
+                e.outcome.as_deref(),
+            )
+        })
+        .collect()
+}
+
+fn describe_effects(effects: &[RawEffect]) -> String {
+    effects
+        .iter()
+        .map(|e| match e.kind {
+            RawEffectKind::Transition => format!("({}, {}, {})", e.entity, e.from, e.to),
+            RawEffectKind::Create => format!("(create {})", e.entity),
+            RawEffectKind::Destroy => format!("(destroy {}, {})", e.entity, e.from),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -48,7 +48,7 @@ pub(super) fn validate_parallel_conflicts(constructs: &[RawConstruct]) -> Result
     let mut op_entities: HashMap<&str, Vec<&str>> = HashMap::new();
     for c in constructs {
         if let RawConstruct::Operation { id, effects, .. } = c {
-            let entities: Vec<&str> = effects.iter().map(|(e, _, _, _, _)| e.as_str()).collect();
+            let entities: Vec<&str> = effects.iter().map(|e| e.entity.as_str()).collect();
             op_entities.insert(id.as_str(), entities);
         }
     }
@@ -0,0 +1,263 @@
+//! Entity instance quantifier validation.
+//!
+//! `forall d in instances(E) . body` and `exists d in instances(E) . body`
+//! range over the live instances of entity `E`. They may appear in Operation
+//! preconditions, Entity transition guards and Flow branch conditions.
+
+use crate::ast::*;
+use crate::error::ElabError;
+use std::collections::{BTreeMap, HashMap};
+
+struct EntityShape<'a> {
+    states: &'a [String],
+    attributes: &'a BTreeMap<String, RawType>,
+}
+
+/// Where the predicate under validation is declared, for error reporting.
+struct Site<'a> {
+    construct: &'static str,
+    id: &'a str,
+    field: &'a str,
+    prov: &'a Provenance,
+}
+
+/// Validate every instance quantifier and `state(var)` term: the domain must
+/// be a declared entity, `state(var)` must name a variable bound by an
+/// instance quantifier and may only be compared with `=` / `!=` against one
+/// of that entity's states, and `var.attr` must be a declared attribute.
+pub(super) fn validate_instance_quantifiers(constructs: &[RawConstruct]) -> Result<(), ElabError> {
+    let mut entities: HashMap<&str, EntityShape> = HashMap::new();
+    for c in constructs {
+        if let RawConstruct::Entity {
+            id,
+            states,
+            attributes,
+            ..
+        } = c
+        {
+            entities.insert(id.as_str(), EntityShape { states, attributes });
+        }
+    }
+
+    for c in constructs {
+        match c {
+            RawConstruct::Entity {
+                id,
+                transitions,
+                prov,
+                ..
+            } => {
+                let site = Site {
+                    construct: "Entity",
+                    id,
+                    field: "transitions",
+                    prov,
+                };
+                for guard in transitions.iter().filter_map(|t| t.guard.as_ref()) {
+                    check_expr(guard, &mut Vec::new(), &entities, &site)?;
+                }
+            }
+            RawConstruct::Operation {
+                id,
+                precondition,
+                prov,
+                ..
+            } => {
+                let site = Site {
+                    construct: "Operation",
+                    id,
+                    field: "precondition",
+                    prov,
+                };
+                check_expr(precondition, &mut Vec::new(), &entities, &site)?;
+            }
+            RawConstruct::Flow {
+                id, steps, prov, ..
+            } => {
+                let site = Site {
+                    construct: "Flow",
+                    id,
+                    field: "steps",
+                    prov,
+                };
+                check_steps(steps, &entities, &site)?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_steps(
+    steps: &BTreeMap<String, RawStep>,
+    entities: &HashMap<&str, EntityShape>,
+    site: &Site,
+) -> Result<(), ElabError> {
+    for step in steps.values() {
+        match step {
+            RawStep::BranchStep { condition, .. } => {
+                check_expr(condition, &mut Vec::new(), entities, site)?;
+            }
+            RawStep::ParallelStep { branches, .. } => {
+                for branch in branches {
+                    check_steps(&branch.steps, entities, site)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// `bound` maps each enclosing quantifier variable to the entity whose
+/// instances it ranges over, or `None` for List quantifiers.
+fn check_expr<'a>(
+    expr: &'a RawExpr,
+    bound: &mut Vec<(&'a str, Option<&'a str>)>,
+    entities: &HashMap<&str, EntityShape>,
+    site: &Site,
+) -> Result<(), ElabError> {
+    match expr {
+        RawExpr::Forall {
+            var,
+            domain,
+            over_instances,
+            body,
+            line,
+            ..
+        }
+        | RawExpr::Exists {
+            var,
+            domain,
+            over_instances,
+            body,
+            line,
+            ..
+        } => {
+            let entity = if *over_instances {
+                if !entities.contains_key(domain.as_str()) {
+                    return Err(error(
+                        site,
+                        *line,
+                        format!(
+                            "quantifier ranges over instances of undeclared entity '{}'",
+                            domain
+                        ),
+                    ));
+                }
+                Some(domain.as_str())
+            } else {
+                None
+            };
+            bound.push((var.as_str(), entity));
+            let result = check_expr(body, bound, entities, site);
+            bound.pop();
+            result
+        }
+        RawExpr::Compare {
+            op,
+            left,
+            right,
+            line,
+            ..
+        } => {
+            for (term, other) in [(left, right), (right, left)] {
+                check_term(term, other, op, *line, bound, entities, site)?;
+            }
+            Ok(())
+        }
+        RawExpr::And(a, b) | RawExpr::Or(a, b) => {
+            check_expr(a, bound, entities, site)?;
+            check_expr(b, bound, entities, site)
+        }
+        RawExpr::Not(e, _) => check_expr(e, bound, entities, site),
+        RawExpr::VerdictPresent { .. } => Ok(()),
+    }
+}
+
+fn check_term(
+    term: &RawTerm,
+    other: &RawTerm,
+    op: &str,
+    line: u32,
+    bound: &[(&str, Option<&str>)],
+    entities: &HashMap<&str, EntityShape>,
+    site: &Site,
+) -> Result<(), ElabError> {
+    let instance_entity = |var: &str| {
+        bound
+            .iter()
+            .rev()
+            .find(|(v, _)| *v == var)
+            .and_then(|(_, entity)| *entity)
+    };
+    match term {
+        RawTerm::InstanceState(var) => {
+            let Some(entity) = instance_entity(var) else {
+                return Err(error(
+                    site,
+                    line,
+                    format!(
+                        "state({}) requires '{}' to be bound by an instance quantifier",
+                        var, var
+                    ),
+                ));
+            };
+            if op != "=" && op != "!=" {
+                return Err(error(
+                    site,
+                    line,
+                    format!(
+                        "operator '{}' not defined for instance state; state supports only = and !=",
+                        op
+                    ),
+                ));
+            }
+            match other {
+                RawTerm::Literal(RawLiteral::Str(state))
+                    if !entities[entity].states.contains(state) =>
+                {
+                    Err(error(
+                        site,
+                        line,
+                        format!("entity '{}' declares no state '{}'", entity, state),
+                    ))
+                }
+                RawTerm::Literal(RawLiteral::Str(_)) | RawTerm::InstanceState(_) => Ok(()),
+                _ => Err(error(
+                    site,
+                    line,
+                    format!("state({}) may only be compared with a state name", var),
+                )),
+            }
+        }
+        RawTerm::FieldRef { var, field } => match instance_entity(var) {
+            Some(entity) if !entities[entity].attributes.contains_key(field) => Err(error(
+                site,
+                line,
+                format!("entity '{}' declares no attribute '{}'", entity, field),
+            )),
+            _ => Ok(()),
+        },
+        RawTerm::Mul { left, right } => {
+            check_term(left, other, op, line, bound, entities, site)?;
+            check_term(right, other, op, line, bound, entities, site)
+        }
+        RawTerm::Convert { amount, .. } => {
+            check_term(amount, other, op, line, bound, entities, site)
+        }
+        RawTerm::FactRef(_) | RawTerm::Literal(_) => Ok(()),
+    }
+}
+
+fn error(site: &Site, line: u32, message: String) -> ElabError {
+    ElabError::new(
+        5,
+        Some(site.construct),
+        Some(site.id),
+        Some(site.field),
+        &site.prov.file,
+        line,
+        message,
+    )
+}
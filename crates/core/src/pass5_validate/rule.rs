@@ -42,7 +42,7 @@ fn validate_verdict_refs_in_expr(
     produced_verdicts: &HashSet<String>,
 ) -> Result<(), ElabError> {
     match expr {
-        RawExpr::VerdictPresent { id: vid, line } => {
+        RawExpr::VerdictPresent { id: vid, line, .. } => {
             if !produced_verdicts.contains(vid.as_str()) {
                 return Err(ElabError::new(
                     5, Some("Rule"), Some(rule_id),
@@ -83,7 +83,7 @@ fn validate_verdict_refs_in_expr(
                 produced_verdicts,
             )?;
         }
-        RawExpr::Not(e) => {
+        RawExpr::Not(e, _) => {
             validate_verdict_refs_in_expr(
                 e,
                 rule_id,
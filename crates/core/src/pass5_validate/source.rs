@@ -22,6 +22,7 @@ pub(super) fn validate_source(
         "database" => &["dialect"],
         "graphql" => &["endpoint"],
         "grpc" => &["endpoint"],
+        "file" => &["format"],
         "static" | "manual" => &[],
         tag if tag.starts_with("x_") => {
             // C-SRC-04: Extension protocol tag format
@@ -0,0 +1,137 @@
+//! Test construct validation functions.
+
+use crate::ast::*;
+use crate::error::ElabError;
+use crate::pass2_index::Index;
+use std::collections::{HashMap, HashSet};
+
+// ── Test validation ───────────────────────────────────────────────────────────
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn validate_test(
+    id: &str,
+    given: &[RawGiven],
+    expectations: &[RawExpectation],
+    prov: &Provenance,
+    index: &Index,
+    fact_types: &HashMap<&str, &RawType>,
+    produced_verdicts: &HashSet<String>,
+) -> Result<(), ElabError> {
+    for g in given {
+        let err = |msg: String| {
+            ElabError::new(
+                5,
+                Some("Test"),
+                Some(id),
+                Some("given"),
+                &prov.file,
+                g.line,
+                msg,
+            )
+        };
+        if !index.facts.contains_key(&g.fact) {
+            return Err(err(format!(
+                "test '{}' gives undeclared fact '{}'",
+                id, g.fact
+            )));
+        }
+        if let Some(type_) = fact_types.get(g.fact.as_str()) {
+            check_literal(type_, &g.value)
+                .map_err(|msg| err(format!("test '{}' fact '{}': {}", id, g.fact, msg)))?;
+        }
+    }
+
+    for exp in expectations {
+        if !produced_verdicts.contains(&exp.verdict_type) {
+            return Err(ElabError::new(
+                5,
+                Some("Test"),
+                Some(id),
+                Some("expect"),
+                &prov.file,
+                exp.line,
+                format!(
+                    "test '{}' expects verdict '{}', which no rule produces",
+                    id, exp.verdict_type
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a DSL literal is a valid value for a (resolved) fact type.
+fn check_literal(type_: &RawType, value: &RawLiteral) -> Result<(), String> {
+    match (type_, value) {
+        (RawType::Bool, RawLiteral::Bool(_)) => Ok(()),
+        (RawType::Int { min, max }, RawLiteral::Int(n)) => {
+            if n < min || n > max {
+                Err(format!(
+                    "value {} is outside Int range [{}, {}]",
+                    n, min, max
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        (
+            RawType::Decimal { .. },
+            RawLiteral::Int(_) | RawLiteral::Float(_) | RawLiteral::Str(_),
+        ) => Ok(()),
+        (RawType::Money { currency, .. }, RawLiteral::Money { currency: c, .. }) => {
+            if c == currency {
+                Ok(())
+            } else {
+                Err(format!("expected currency {}, got {}", currency, c))
+            }
+        }
+        (RawType::Text { max_length }, RawLiteral::Str(s)) => {
+            if *max_length > 0 && s.chars().count() > *max_length as usize {
+                Err(format!("value exceeds Text max_length {}", max_length))
+            } else {
+                Ok(())
+            }
+        }
+        (RawType::Date | RawType::DateTime, RawLiteral::Str(_)) => Ok(()),
+        (RawType::Enum { values }, RawLiteral::Str(s)) => {
+            if values.contains(s) {
+                Ok(())
+            } else {
+                Err(format!("'{}' is not one of [{}]", s, values.join(", ")))
+            }
+        }
+        (
+            RawType::Duration { .. }
+            | RawType::Record { .. }
+            | RawType::List { .. }
+            | RawType::TaggedUnion { .. },
+            _,
+        ) => Err(format!(
+            "{} facts cannot be given as a literal",
+            type_name(type_)
+        )),
+        _ => Err(format!(
+            "literal does not match declared type {}",
+            type_name(type_)
+        )),
+    }
+}
+
+fn type_name(type_: &RawType) -> &str {
+    match type_ {
+        RawType::Bool => "Bool",
+        RawType::Int { .. } => "Int",
+        RawType::Decimal { .. } => "Decimal",
+        RawType::Text { .. } => "Text",
+        RawType::Date => "Date",
+        RawType::DateTime => "DateTime",
+        RawType::Money { .. } => "Money",
+        RawType::Duration { .. } => "Duration",
+        RawType::Enum { .. } => "Enum",
+        RawType::Record { .. } => "Record",
+        RawType::List { .. } => "List",
+        RawType::TaggedUnion { .. } => "TaggedUnion",
+        RawType::TypeRef(name) => name,
+    }
+}
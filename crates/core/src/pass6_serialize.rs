@@ -2,6 +2,7 @@
 //! keys and structured numeric values.
 
 use crate::ast::*;
+use crate::units::unit_of;
 use serde_json::{json, Map, Value};
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
@@ -37,6 +38,8 @@ pub fn serialize(constructs: &[RawConstruct], bundle_id: &str) -> Value {
     let mut personas: Vec<&RawConstruct> = Vec::new();
     let mut systems: Vec<&RawConstruct> = Vec::new();
     let mut sources: Vec<&RawConstruct> = Vec::new();
+    let mut tests: Vec<&RawConstruct> = Vec::new();
+    let mut asserts: Vec<&RawConstruct> = Vec::new();
 
     for c in constructs {
         match c {
@@ -50,6 +53,8 @@ pub fn serialize(constructs: &[RawConstruct], bundle_id: &str) -> Value {
             RawConstruct::Persona { .. } => personas.push(c),
             RawConstruct::System { .. } => systems.push(c),
             RawConstruct::Source { .. } => sources.push(c),
+            RawConstruct::Test { .. } => tests.push(c),
+            RawConstruct::Assert { .. } => asserts.push(c),
             _ => {}
         }
     }
@@ -64,6 +69,8 @@ pub fn serialize(constructs: &[RawConstruct], bundle_id: &str) -> Value {
     personas.sort_by(|a, b| construct_id(a).cmp(construct_id(b)));
     systems.sort_by(|a, b| construct_id(a).cmp(construct_id(b)));
     sources.sort_by(|a, b| construct_id(a).cmp(construct_id(b)));
+    tests.sort_by(|a, b| construct_id(a).cmp(construct_id(b)));
+    asserts.sort_by(|a, b| construct_id(a).cmp(construct_id(b)));
 
     let mut result: Vec<Value> = Vec::new();
     for c in &personas {
@@ -92,11 +99,22 @@ pub fn serialize(constructs: &[RawConstruct], bundle_id: &str) -> Value {
     for c in &systems {
         result.push(serialize_construct(c, &fact_types));
     }
+    for c in &tests {
+        result.push(serialize_construct(c, &fact_types));
+    }
+    for c in &asserts {
+        result.push(serialize_construct(c, &fact_types));
+    }
+
+    let requires = crate::features::required_features(&result);
 
     let mut bundle = Map::new();
     ins(&mut bundle, "constructs", Value::Array(result));
     ins(&mut bundle, K_ID, Value::String(bundle_id.to_owned()));
     ins(&mut bundle, K_KIND, Value::String("Bundle".to_owned()));
+    if !requires.is_empty() {
+        ins(&mut bundle, "requires", json!(requires));
+    }
     ins(
         &mut bundle,
         K_TENOR,
@@ -121,11 +139,61 @@ fn construct_id(c: &RawConstruct) -> &str {
         RawConstruct::Persona { id, .. } => id,
         RawConstruct::System { id, .. } => id,
         RawConstruct::Source { id, .. } => id,
-        RawConstruct::Import { .. } => "",
+        RawConstruct::Test { id, .. } => id,
+        RawConstruct::Assert { id, .. } => id,
+        RawConstruct::Import { .. }
+        | RawConstruct::Extends { .. }
+        | RawConstruct::Override { .. }
+        | RawConstruct::Gated { .. } => "",
     }
 }
 
+fn construct_annotations(c: &RawConstruct) -> Option<&Annotations> {
+    match c {
+        RawConstruct::Fact { prov, .. }
+        | RawConstruct::Entity { prov, .. }
+        | RawConstruct::Rule { prov, .. }
+        | RawConstruct::Operation { prov, .. }
+        | RawConstruct::Flow { prov, .. }
+        | RawConstruct::TypeDecl { prov, .. }
+        | RawConstruct::Persona { prov, .. }
+        | RawConstruct::System { prov, .. }
+        | RawConstruct::Source { prov, .. }
+        | RawConstruct::Test { prov, .. }
+        | RawConstruct::Assert { prov, .. } => Some(&prov.annotations),
+        RawConstruct::Import { .. }
+        | RawConstruct::Extends { .. }
+        | RawConstruct::Override { .. }
+        | RawConstruct::Gated { .. } => None,
+    }
+}
+
+/// Serialize a construct, with its annotations when it has any.
 fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>) -> Value {
+    let mut value = serialize_construct_fields(c, fact_types);
+    if let (Some(annotations), Value::Object(m)) = (construct_annotations(c), &mut value) {
+        if !annotations.is_empty() {
+            ins(m, "annotations", serialize_annotations(annotations));
+        }
+    }
+    value
+}
+
+fn serialize_annotations(annotations: &Annotations) -> Value {
+    let mut m = Map::new();
+    if let Some(description) = &annotations.description {
+        ins(&mut m, "description", json!(description));
+    }
+    if let Some(owner) = &annotations.owner {
+        ins(&mut m, "owner", json!(owner));
+    }
+    if !annotations.tags.is_empty() {
+        ins(&mut m, "tags", json!(annotations.tags));
+    }
+    Value::Object(m)
+}
+
+fn serialize_construct_fields(c: &RawConstruct, fact_types: &HashMap<String, RawType>) -> Value {
     match c {
         RawConstruct::Fact {
             id,
@@ -137,8 +205,16 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
             let mut m = Map::new();
             if let Some(d) = default {
                 let default_val = match (type_, d) {
-                    (RawType::Decimal { precision, scale }, RawLiteral::Str(s)) => {
-                        let rounded = round_decimal_to_scale(s, *scale);
+                    (
+                        RawType::Decimal {
+                            precision,
+                            scale,
+                            rounding,
+                            ..
+                        },
+                        RawLiteral::Str(s),
+                    ) => {
+                        let rounded = round_decimal_to_scale(s, *scale, *rounding);
                         let mut dm = Map::new();
                         ins(&mut dm, K_KIND, json!("decimal_value"));
                         ins(&mut dm, "precision", json!(precision));
@@ -146,8 +222,16 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
                         ins(&mut dm, K_VALUE, json!(rounded));
                         Value::Object(dm)
                     }
-                    (RawType::Decimal { precision, scale }, RawLiteral::Float(s)) => {
-                        let rounded = round_decimal_to_scale(s, *scale);
+                    (
+                        RawType::Decimal {
+                            precision,
+                            scale,
+                            rounding,
+                            ..
+                        },
+                        RawLiteral::Float(s),
+                    ) => {
+                        let rounded = round_decimal_to_scale(s, *scale, *rounding);
                         let mut dm = Map::new();
                         ins(&mut dm, K_KIND, json!("decimal_value"));
                         ins(&mut dm, "precision", json!(precision));
@@ -155,9 +239,18 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
                         ins(&mut dm, K_VALUE, json!(rounded));
                         Value::Object(dm)
                     }
-                    (RawType::Money { .. }, RawLiteral::Money { amount, currency }) => {
-                        let (p, sc) = money_decimal_precision_scale(amount);
-                        let rounded = round_decimal_to_scale(amount, sc);
+                    (
+                        RawType::Money {
+                            precision,
+                            scale,
+                            rounding,
+                            ..
+                        },
+                        RawLiteral::Money { amount, currency },
+                    ) => {
+                        let p = precision.unwrap_or(MONEY_DEFAULT_PRECISION);
+                        let sc = scale.unwrap_or(MONEY_DEFAULT_SCALE);
+                        let rounded = round_decimal_to_scale(amount, sc, *rounding);
                         let mut amount_m = Map::new();
                         ins(&mut amount_m, K_KIND, json!("decimal_value"));
                         ins(&mut amount_m, "precision", json!(p));
@@ -186,11 +279,19 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
             states,
             initial,
             transitions,
+            attributes,
             parent,
             prov,
             ..
         } => {
             let mut m = Map::new();
+            if !attributes.is_empty() {
+                let attrs: Map<String, Value> = attributes
+                    .iter()
+                    .map(|(name, t)| (name.clone(), serialize_type(t)))
+                    .collect();
+                ins(&mut m, "attributes", Value::Object(attrs));
+            }
             ins(&mut m, K_ID, json!(id));
             ins(&mut m, "initial", json!(initial));
             ins(&mut m, K_KIND, json!("Entity"));
@@ -202,10 +303,13 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
             ins(&mut m, K_TENOR, json!(crate::TENOR_VERSION));
             let t_arr: Vec<Value> = transitions
                 .iter()
-                .map(|(f, to, _)| {
+                .map(|t| {
                     let mut tm = Map::new();
-                    ins(&mut tm, "from", json!(f));
-                    ins(&mut tm, "to", json!(to));
+                    ins(&mut tm, "from", json!(t.from));
+                    if let Some(guard) = &t.guard {
+                        ins(&mut tm, "guard", serialize_expr(guard, fact_types));
+                    }
+                    ins(&mut tm, "to", json!(t.to));
                     Value::Object(tm)
                 })
                 .collect();
@@ -255,14 +359,34 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
             ins(&mut m, "allowed_personas", json!(allowed_personas));
             let effects_arr: Vec<Value> = effects
                 .iter()
-                .map(|(eid, f, t, outcome, _)| {
+                .map(|e| {
                     let mut em = Map::new();
-                    ins(&mut em, "entity_id", json!(eid));
-                    ins(&mut em, "from", json!(f));
-                    if let Some(o) = outcome {
+                    ins(&mut em, "entity_id", json!(e.entity));
+                    let kind = match e.kind {
+                        RawEffectKind::Transition => "transition",
+                        RawEffectKind::Create => "create_instance",
+                        RawEffectKind::Destroy => "destroy_instance",
+                    };
+                    ins(&mut em, K_KIND, json!(kind));
+                    if !e.from.is_empty() {
+                        ins(&mut em, "from", json!(e.from));
+                    }
+                    if let Some(o) = &e.outcome {
                         ins(&mut em, "outcome", json!(o));
                     }
-                    ins(&mut em, "to", json!(t));
+                    if !e.set.is_empty() {
+                        let set: Map<String, Value> = e
+                            .set
+                            .iter()
+                            .map(|(name, term)| {
+                                (name.clone(), serialize_term_ctx(term, fact_types))
+                            })
+                            .collect();
+                        ins(&mut em, "set", Value::Object(set));
+                    }
+                    if !e.to.is_empty() {
+                        ins(&mut em, "to", json!(e.to));
+                    }
                     Value::Object(em)
                 })
                 .collect();
@@ -331,6 +455,48 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
             ins(&mut m, K_TENOR, json!(crate::TENOR_VERSION));
             Value::Object(m)
         }
+        RawConstruct::Test {
+            id,
+            given,
+            expectations,
+            prov,
+        } => {
+            let expect: Vec<Value> = expectations
+                .iter()
+                .map(|e| json!({"present": e.present, "verdict": e.verdict_type}))
+                .collect();
+            let mut gm = Map::new();
+            for g in given {
+                gm.insert(
+                    g.fact.clone(),
+                    serialize_fact_input(fact_types.get(&g.fact), &g.value),
+                );
+            }
+            let mut m = Map::new();
+            ins(&mut m, "expect", Value::Array(expect));
+            ins(&mut m, "given", Value::Object(gm));
+            ins(&mut m, K_ID, json!(id));
+            ins(&mut m, K_KIND, json!("Test"));
+            ins(&mut m, K_PROVENANCE, serialize_prov(prov));
+            ins(&mut m, K_TENOR, json!(crate::TENOR_VERSION));
+            Value::Object(m)
+        }
+        RawConstruct::Assert {
+            id,
+            entity,
+            property,
+            prov,
+            ..
+        } => {
+            let mut m = Map::new();
+            ins(&mut m, "entity", json!(entity));
+            ins(&mut m, K_ID, json!(id));
+            ins(&mut m, K_KIND, json!("Assert"));
+            ins(&mut m, "property", json!(property.as_str()));
+            ins(&mut m, K_PROVENANCE, serialize_prov(prov));
+            ins(&mut m, K_TENOR, json!(crate::TENOR_VERSION));
+            Value::Object(m)
+        }
         RawConstruct::System {
             id,
             members,
@@ -350,10 +516,29 @@ fn serialize_construct(c: &RawConstruct, fact_types: &HashMap<String, RawType>)
     }
 }
 
+/// A Test's given fact value, in the plain shape `tenor eval` accepts in a
+/// facts file (decimals as strings, money as `{amount, currency}`).
+fn serialize_fact_input(type_: Option<&RawType>, lit: &RawLiteral) -> Value {
+    match (type_, lit) {
+        (_, RawLiteral::Bool(b)) => json!(b),
+        (Some(RawType::Decimal { .. }), RawLiteral::Int(n)) => json!(n.to_string()),
+        (_, RawLiteral::Int(n)) => json!(n),
+        (_, RawLiteral::Float(f)) => json!(f),
+        (_, RawLiteral::Str(s)) => json!(s),
+        (_, RawLiteral::Money { amount, currency }) => {
+            json!({"amount": amount, "currency": currency})
+        }
+    }
+}
+
 fn serialize_prov(prov: &Provenance) -> Value {
     let mut m = Map::new();
     ins(&mut m, "file", json!(prov.file));
     ins(&mut m, "line", json!(prov.line));
+    if prov.span.col > 0 {
+        ins(&mut m, "col", json!(prov.span.col));
+        ins(&mut m, "len", json!(prov.span.len));
+    }
     Value::Object(m)
 }
 
@@ -393,11 +578,22 @@ fn serialize_type(t: &RawType) -> Value {
             ins(&mut m, "min", json!(min));
             Value::Object(m)
         }
-        RawType::Decimal { precision, scale } => {
+        RawType::Decimal {
+            precision,
+            scale,
+            rounding,
+            unit,
+        } => {
             let mut m = Map::new();
             ins(&mut m, K_BASE, json!("Decimal"));
             ins(&mut m, "precision", json!(precision));
+            if let Some(mode) = rounding {
+                ins(&mut m, "rounding", json!(mode.as_str()));
+            }
             ins(&mut m, "scale", json!(scale));
+            if let Some(u) = unit {
+                ins(&mut m, "unit", json!(u));
+            }
             Value::Object(m)
         }
         RawType::Text { max_length } => {
@@ -407,10 +603,24 @@ fn serialize_type(t: &RawType) -> Value {
             Value::Object(m)
         }
         RawType::Enum { values } => json!({"base": "Enum", "values": values}),
-        RawType::Money { currency } => {
+        RawType::Money {
+            currency,
+            precision,
+            scale,
+            rounding,
+        } => {
             let mut m = Map::new();
             ins(&mut m, K_BASE, json!("Money"));
             ins(&mut m, "currency", json!(currency));
+            if let Some(p) = precision {
+                ins(&mut m, "precision", json!(p));
+            }
+            if let Some(mode) = rounding {
+                ins(&mut m, "rounding", json!(mode.as_str()));
+            }
+            if let Some(sc) = scale {
+                ins(&mut m, "scale", json!(sc));
+            }
             Value::Object(m)
         }
         RawType::Duration { unit, min, max } => {
@@ -493,12 +703,13 @@ fn serialize_literal(lit: &RawLiteral) -> Value {
 }
 
 fn money_decimal_precision_scale(_amount: &str) -> (u32, u32) {
-    (10, 2)
+    (MONEY_DEFAULT_PRECISION, MONEY_DEFAULT_SCALE)
 }
 
-/// Round a decimal string to `target_scale` fractional digits using
-/// round-half-to-even (banker's rounding, per spec §13).
-fn round_decimal_to_scale(s: &str, target_scale: u32) -> String {
+/// Round a decimal string to `target_scale` fractional digits using the
+/// type's declared rounding mode, or round-half-to-even (banker's rounding,
+/// per spec §13) when it declares none.
+fn round_decimal_to_scale(s: &str, target_scale: u32, rounding: Option<RoundingMode>) -> String {
     let is_negative = s.starts_with('-');
     let abs_s = if is_negative { &s[1..] } else { s };
     let (integer_part, frac_part) = if let Some(dot) = abs_s.find('.') {
@@ -534,7 +745,7 @@ fn round_decimal_to_scale(s: &str, target_scale: u32) -> String {
     } else {
         // Exactly 5 — check for trailing non-zero digits
         let has_trailing = rest[1..].bytes().any(|b| b != b'0');
-        if has_trailing {
+        if has_trailing || rounding == Some(RoundingMode::HalfUp) {
             true
         } else {
             // Tie: round to even (the last kept digit)
@@ -716,6 +927,33 @@ fn term_numeric_type(term: &RawTerm, fact_types: &HashMap<String, RawType>) -> O
     match term {
         RawTerm::FactRef(name) => fact_types.get(name.as_str()).cloned(),
         RawTerm::Literal(RawLiteral::Int(n)) => Some(RawType::Int { min: *n, max: *n }),
+        RawTerm::Convert { amount, rate } => {
+            let rate_t = fact_types.get(rate.as_str())?;
+            match (term_numeric_type(amount, fact_types), rate_t) {
+                (
+                    Some(RawType::Decimal {
+                        precision: pa,
+                        scale: sa,
+                        unit: ua,
+                        ..
+                    }),
+                    RawType::Decimal {
+                        precision: pr,
+                        scale: sr,
+                        unit: ur,
+                        ..
+                    },
+                ) => Some(RawType::Decimal {
+                    precision: pa + pr,
+                    scale: sa + sr,
+                    rounding: None,
+                    unit: unit_of(ua.as_deref())
+                        .mul(&unit_of(ur.as_deref()))
+                        .annotation(),
+                }),
+                _ => Some(rate_t.clone()),
+            }
+        }
         RawTerm::Mul { left, right } => {
             let (fact_name, lit_n) = match (left.as_ref(), right.as_ref()) {
                 (RawTerm::FactRef(n), RawTerm::Literal(RawLiteral::Int(v))) => {
@@ -756,18 +994,38 @@ fn comparison_type_for_compare(
         (Some(t @ RawType::Money { .. }), _) | (_, Some(t @ RawType::Money { .. })) => {
             Some(t.clone())
         }
-        (Some(RawType::Int { min, max }), Some(RawType::Decimal { precision, scale })) => {
+        (
+            Some(RawType::Int { min, max }),
+            Some(RawType::Decimal {
+                precision,
+                scale,
+                rounding,
+                unit,
+            }),
+        ) => {
             let int_prec = int_to_decimal_precision(*min, *max);
             Some(RawType::Decimal {
                 precision: (*precision).max(int_prec) + 1,
                 scale: *scale,
+                rounding: *rounding,
+                unit: unit.clone(),
             })
         }
-        (Some(RawType::Decimal { precision, scale }), Some(RawType::Int { min, max })) => {
+        (
+            Some(RawType::Decimal {
+                precision,
+                scale,
+                rounding,
+                unit,
+            }),
+            Some(RawType::Int { min, max }),
+        ) => {
             let int_prec = int_to_decimal_precision(*min, *max);
             Some(RawType::Decimal {
                 precision: (*precision).max(int_prec) + 1,
                 scale: *scale,
+                rounding: *rounding,
+                unit: unit.clone(),
             })
         }
         (
@@ -807,12 +1065,25 @@ fn serialize_expr(expr: &RawExpr, fact_types: &HashMap<String, RawType>) -> Valu
             if let Some(ct) = comparison_type_for_compare(left, right, fact_types) {
                 ins(&mut m, "comparison_type", serialize_type(&ct));
             }
-            ins(&mut m, "left", serialize_term_ctx(left, fact_types));
+            // State names compared with state(var) are Text literals
+            let compares_state = [left, right]
+                .iter()
+                .any(|t| matches!(t, RawTerm::InstanceState(_)));
+            let left_val = match left {
+                RawTerm::Literal(RawLiteral::Str(s)) if compares_state => {
+                    json!({"literal": s, "type": {"base": "Text"}})
+                }
+                _ => serialize_term_ctx(left, fact_types),
+            };
+            ins(&mut m, "left", left_val);
             ins(&mut m, K_OP, json!(op));
             let right_val = match (right, &left_fact_type) {
                 (RawTerm::Literal(RawLiteral::Str(s)), Some(t @ RawType::Enum { .. })) => {
                     json!({"literal": s, "type": serialize_type(t)})
                 }
+                (RawTerm::Literal(RawLiteral::Str(s)), _) if compares_state => {
+                    json!({"literal": s, "type": {"base": "Text"}})
+                }
                 _ => serialize_term_ctx(right, fact_types),
             };
             ins(&mut m, "right", right_val);
@@ -833,19 +1104,29 @@ fn serialize_expr(expr: &RawExpr, fact_types: &HashMap<String, RawType>) -> Valu
                 "right": serialize_expr(b, fact_types)
             })
         }
-        RawExpr::Not(e) => {
+        RawExpr::Not(e, _) => {
             json!({"op": "not", "operand": serialize_expr(e, fact_types)})
         }
         RawExpr::Forall {
-            var, domain, body, ..
+            var,
+            domain,
+            over_instances,
+            body,
+            ..
         } => {
             let variable_type = match fact_types.get(domain.as_str()) {
-                Some(RawType::List { element_type, .. }) => Some(element_type.as_ref().clone()),
+                Some(RawType::List { element_type, .. }) if !over_instances => {
+                    Some(element_type.as_ref().clone())
+                }
                 _ => None,
             };
             let mut m = Map::new();
             ins(&mut m, "body", serialize_expr(body, fact_types));
-            ins(&mut m, "domain", json!({"fact_ref": domain}));
+            ins(
+                &mut m,
+                "domain",
+                serialize_quantifier_domain(domain, *over_instances),
+            );
             ins(&mut m, "quantifier", json!("forall"));
             ins(&mut m, "variable", json!(var));
             if let Some(vt) = variable_type {
@@ -854,15 +1135,25 @@ fn serialize_expr(expr: &RawExpr, fact_types: &HashMap<String, RawType>) -> Valu
             Value::Object(m)
         }
         RawExpr::Exists {
-            var, domain, body, ..
+            var,
+            domain,
+            over_instances,
+            body,
+            ..
         } => {
             let variable_type = match fact_types.get(domain.as_str()) {
-                Some(RawType::List { element_type, .. }) => Some(element_type.as_ref().clone()),
+                Some(RawType::List { element_type, .. }) if !over_instances => {
+                    Some(element_type.as_ref().clone())
+                }
                 _ => None,
             };
             let mut m = Map::new();
             ins(&mut m, "body", serialize_expr(body, fact_types));
-            ins(&mut m, "domain", json!({"fact_ref": domain}));
+            ins(
+                &mut m,
+                "domain",
+                serialize_quantifier_domain(domain, *over_instances),
+            );
             ins(&mut m, "quantifier", json!("exists"));
             ins(&mut m, "variable", json!(var));
             if let Some(vt) = variable_type {
@@ -873,6 +1164,14 @@ fn serialize_expr(expr: &RawExpr, fact_types: &HashMap<String, RawType>) -> Valu
     }
 }
 
+fn serialize_quantifier_domain(domain: &str, over_instances: bool) -> Value {
+    if over_instances {
+        json!({"instances_of": domain})
+    } else {
+        json!({"fact_ref": domain})
+    }
+}
+
 fn serialize_term(term: &RawTerm) -> Value {
     match term {
         RawTerm::FactRef(name) => json!({"fact_ref": name}),
@@ -900,6 +1199,10 @@ fn serialize_term(term: &RawTerm) -> Value {
                 })
             }
         },
+        RawTerm::InstanceState(var) => json!({"instance_state": var}),
+        RawTerm::Convert { amount, rate } => {
+            json!({"convert": serialize_term(amount), "rate": {"fact_ref": rate}})
+        }
         RawTerm::Mul { left, right } => {
             let mut m = Map::new();
             ins(&mut m, "left", serialize_term(left));
@@ -988,6 +1291,8 @@ fn serialize_step(id: &str, step: &RawStep, fact_types: &HashMap<String, RawType
             persona,
             outcomes,
             on_failure,
+            timeout,
+            weights,
             ..
         } => {
             let mut m = Map::new();
@@ -1003,6 +1308,12 @@ fn serialize_step(id: &str, step: &RawStep, fact_types: &HashMap<String, RawType
             }
             ins(&mut m, "outcomes", Value::Object(out_m));
             ins(&mut m, "persona", json!(persona));
+            if let Some(t) = timeout {
+                ins(&mut m, "timeout", serialize_timeout(t));
+            }
+            if !weights.is_empty() {
+                ins(&mut m, "weights", json!(weights));
+            }
             Value::Object(m)
         }
         RawStep::BranchStep {
@@ -1010,6 +1321,7 @@ fn serialize_step(id: &str, step: &RawStep, fact_types: &HashMap<String, RawType
             persona,
             if_true,
             if_false,
+            weights,
             ..
         } => {
             let mut m = Map::new();
@@ -1019,12 +1331,17 @@ fn serialize_step(id: &str, step: &RawStep, fact_types: &HashMap<String, RawType
             ins(&mut m, "if_true", serialize_step_target(if_true));
             ins(&mut m, K_KIND, json!("BranchStep"));
             ins(&mut m, "persona", json!(persona));
+            if !weights.is_empty() {
+                ins(&mut m, "weights", json!(weights));
+            }
             Value::Object(m)
         }
         RawStep::HandoffStep {
             from_persona,
             to_persona,
             next,
+            pause,
+            timeout,
             ..
         } => {
             let mut m = Map::new();
@@ -1032,6 +1349,12 @@ fn serialize_step(id: &str, step: &RawStep, fact_types: &HashMap<String, RawType
             ins(&mut m, K_ID, json!(id));
             ins(&mut m, K_KIND, json!("HandoffStep"));
             ins(&mut m, "next", json!(next));
+            if *pause {
+                ins(&mut m, "pause", json!(true));
+            }
+            if let Some(t) = timeout {
+                ins(&mut m, "timeout", serialize_timeout(t));
+            }
             ins(&mut m, "to_persona", json!(to_persona));
             Value::Object(m)
         }
@@ -1118,6 +1441,17 @@ fn serialize_failure_handler(handler: &RawFailureHandler) -> Value {
     }
 }
 
+fn serialize_timeout(timeout: &RawTimeout) -> Value {
+    let on_timeout = match &timeout.on_timeout {
+        RawTimeoutAction::Goto(target) => serialize_step_target(target),
+        RawTimeoutAction::Handle(handler) => serialize_failure_handler(handler),
+    };
+    json!({
+        "deadline": timeout.deadline,
+        "on_timeout": on_timeout
+    })
+}
+
 fn serialize_comp_step(step: &RawCompStep) -> Value {
     json!({
         "on_failure": {"kind": "Terminal", "outcome": step.on_failure},
@@ -0,0 +1,395 @@
+//! Project configuration: the `tenor.toml` file shared by the CLI and LSP.
+//!
+//! A project file sits at the root of a repository of contracts and holds
+//! the settings teams would otherwise repeat as command-line flags:
+//!
+//! ```toml
+//! [project]
+//! source_roots = ["contracts"]
+//! import_paths = ["vendor/tenor-types"]
+//!
+//! [check]
+//! analyses = ["s1", "s2", "s4", "s6"]
+//! state_space_limit = 100000
+//!
+//! [lint]
+//! max_predicate_depth = 6
+//! rules = { operation-error-contract = false }
+//!
+//! [connect]
+//! adapter_config = "deploy/tenor-adapters.toml"
+//!
+//! [llm]
+//! provider = "ollama"
+//! base_url = "http://gpu-box:11434"
+//! model = "llama3.1:70b"
+//!
+//! [generate.typescript]
+//! out = "web/src/generated"
+//! sdk_import = "@acme/tenor-sdk"
+//!
+//! [generate.docs]
+//! out = "site/contracts"
+//! format = "html"
+//!
+//! [serve]
+//! contracts = ["contracts/escrow.tenor"]
+//! cors_origins = ["https://app.example.com"]
+//! rate_limit = 120
+//! rate_limit_key = "token"
+//! max_body_size = 2097152
+//! shutdown_timeout = 10
+//! history = "exports/entity-history.json"
+//! webhook_allow_private = false
+//!
+//! [serve.tenants.acme]
+//! token_env = "ACME_TENOR_TOKEN"
+//! contracts = ["tenants/acme/loan.tenor"]
+//! max_contracts = 20
+//! history = "tenants/acme/entity-history.json"
+//! adapter_config = "tenants/acme/tenor-adapters.toml"
+//! ```
+//!
+//! Every section is optional. Relative paths are resolved against the
+//! directory containing `tenor.toml`, not the working directory.
+
+use crate::source::SearchPathProvider;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File name of the project configuration.
+pub const PROJECT_FILE: &str = "tenor.toml";
+
+/// A parsed `tenor.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Directory containing the project file; empty for the default config.
+    #[serde(skip)]
+    pub root: PathBuf,
+    #[serde(default)]
+    pub project: ProjectSection,
+    #[serde(default)]
+    pub check: CheckSection,
+    #[serde(default)]
+    pub lint: LintSection,
+    #[serde(default)]
+    pub connect: ConnectSection,
+    #[serde(default)]
+    pub llm: LlmSection,
+    #[serde(default)]
+    pub generate: GenerateSection,
+    #[serde(default)]
+    pub serve: ServeSection,
+}
+
+/// `[project]`: where contracts and shared imports live.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectSection {
+    /// Directories searched for contract files named on the command line,
+    /// and indexed by the language server.
+    #[serde(default)]
+    pub source_roots: Vec<PathBuf>,
+    /// Directories searched for imports not found next to the importing file.
+    #[serde(default)]
+    pub import_paths: Vec<PathBuf>,
+}
+
+/// `[check]`: defaults for `tenor check`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckSection {
+    /// Analyses to run when `--analysis` is not given; all when absent.
+    pub analyses: Option<Vec<String>>,
+    /// Combined entity state count above which S1 reports a product state
+    /// space explosion; the analyzer default when absent.
+    pub state_space_limit: Option<u64>,
+}
+
+/// `[lint]`: settings for `tenor lint`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintSection {
+    pub max_predicate_depth: Option<usize>,
+    /// Rule name -> enabled. Rules not listed are enabled.
+    #[serde(default)]
+    pub rules: BTreeMap<String, bool>,
+}
+
+/// `[connect]`: settings for `tenor connect`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectSection {
+    /// Where the generated adapter configuration is written.
+    pub adapter_config: Option<PathBuf>,
+}
+
+/// `[llm]`: the model backend used by `tenor connect` and `tenor ambiguity`.
+/// The `TENOR_LLM_*` environment variables override these.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LlmSection {
+    /// `anthropic`, `openai` (any OpenAI-compatible API) or `ollama`.
+    pub provider: Option<String>,
+    /// API base URL; the provider's public endpoint or local default when absent.
+    pub base_url: Option<String>,
+    /// Model identifier; each command's default when absent.
+    pub model: Option<String>,
+}
+
+/// `[generate.<target>]`: output settings per code generation target.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenerateSection {
+    pub typescript: Option<GenerateTarget>,
+    pub docs: Option<GenerateTarget>,
+}
+
+/// Settings for one code generation target. Fields a target does not use
+/// are ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenerateTarget {
+    pub out: Option<PathBuf>,
+    pub sdk_import: Option<String>,
+    pub format: Option<String>,
+}
+
+/// `[serve]`: settings for `tenor serve`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServeSection {
+    /// Contracts to pre-load when none are given on the command line.
+    #[serde(default)]
+    pub contracts: Vec<PathBuf>,
+    /// Origins allowed by CORS; any origin when absent.
+    pub cors_origins: Option<Vec<String>>,
+    /// Requests per minute per client; 0 disables rate limiting.
+    pub rate_limit: Option<u64>,
+    /// What identifies a client for rate limiting: `ip` or `token`.
+    pub rate_limit_key: Option<String>,
+    /// Maximum request body size in bytes.
+    pub max_body_size: Option<usize>,
+    /// Seconds to let in-flight requests drain after SIGTERM.
+    pub shutdown_timeout: Option<u64>,
+    /// Entity history exported from storage, served by `/entities`.
+    pub history: Option<PathBuf>,
+    /// Let webhooks, schedule sinks and adapter fetches reach loopback,
+    /// private and link-local addresses.
+    pub webhook_allow_private: Option<bool>,
+    /// Tenants keyed by id, each isolated from the others.
+    #[serde(default)]
+    pub tenants: BTreeMap<String, TenantSection>,
+}
+
+/// `[serve.tenants.<id>]`: one tenant of a multi-tenant `tenor serve`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantSection {
+    /// Environment variable holding the tenant's API token. Tokens are not
+    /// stored in the project file.
+    pub token_env: String,
+    /// Contracts to pre-load for the tenant.
+    #[serde(default)]
+    pub contracts: Vec<PathBuf>,
+    /// Maximum number of contracts the tenant may have loaded; unlimited
+    /// when absent.
+    pub max_contracts: Option<usize>,
+    /// Entity history exported from the tenant's storage.
+    pub history: Option<PathBuf>,
+    /// Adapter settings for the tenant's scheduled evaluations, in the shape
+    /// of `[connect] adapter_config`.
+    pub adapter_config: Option<PathBuf>,
+}
+
+impl ProjectConfig {
+    /// Parse a project file. Relative paths are resolved against `root`.
+    pub fn parse(content: &str, root: &Path) -> Result<Self, String> {
+        let mut config: ProjectConfig = toml::from_str(content).map_err(|e| e.to_string())?;
+        config.root = root.to_path_buf();
+        Ok(config)
+    }
+
+    /// Load a project file from `path`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("error reading '{}': {}", path.display(), e))?;
+        let root = path.parent().unwrap_or(Path::new("."));
+        Self::parse(&content, root)
+            .map_err(|e| format!("could not parse '{}': {}", path.display(), e))
+    }
+
+    /// Find `tenor.toml` in `start` or its nearest ancestor and load it.
+    /// Returns `Ok(None)` when there is no project file.
+    pub fn discover(start: &Path) -> Result<Option<Self>, String> {
+        let mut dir = Some(start);
+        while let Some(d) = dir {
+            let candidate = d.join(PROJECT_FILE);
+            if candidate.is_file() {
+                return Self::load(&candidate).map(Some);
+            }
+            dir = d.parent();
+        }
+        Ok(None)
+    }
+
+    /// Resolve a path from the project file against the project root.
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Source roots, resolved against the project root.
+    pub fn source_roots(&self) -> Vec<PathBuf> {
+        self.project
+            .source_roots
+            .iter()
+            .map(|p| self.resolve(p))
+            .collect()
+    }
+
+    /// Locate a contract named on the command line: the path itself if it
+    /// exists, otherwise the first source root containing it.
+    pub fn find_contract(&self, path: &Path) -> PathBuf {
+        if path.exists() || path.is_absolute() {
+            return path.to_path_buf();
+        }
+        self.source_roots()
+            .into_iter()
+            .map(|root| root.join(path))
+            .find(|candidate| candidate.exists())
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// A source provider that resolves imports through the configured
+    /// import paths.
+    pub fn source_provider(&self) -> SearchPathProvider {
+        SearchPathProvider::new(
+            self.project
+                .import_paths
+                .iter()
+                .map(|p| self.resolve(p))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_sections_and_resolves_paths() {
+        let config = ProjectConfig::parse(
+            r#"
+[project]
+source_roots = ["contracts"]
+import_paths = ["vendor/types"]
+
+[check]
+analyses = ["s1", "s2"]
+state_space_limit = 5000
+
+[lint]
+max_predicate_depth = 6
+rules = { operation-error-contract = false }
+
+[connect]
+adapter_config = "deploy/tenor-adapters.toml"
+
+[llm]
+provider = "openai"
+base_url = "http://llm.internal/v1"
+model = "qwen2.5"
+
+[generate.typescript]
+out = "web/generated"
+
+[serve]
+contracts = ["contracts/escrow.tenor"]
+cors_origins = ["https://app.example.com"]
+rate_limit = 120
+rate_limit_key = "token"
+max_body_size = 2048
+shutdown_timeout = 10
+history = "exports/history.json"
+
+[serve.tenants.acme]
+token_env = "ACME_TOKEN"
+contracts = ["tenants/acme.tenor"]
+max_contracts = 3
+history = "tenants/acme-history.json"
+adapter_config = "tenants/acme-adapters.toml"
+"#,
+            Path::new("/repo"),
+        )
+        .unwrap();
+        assert_eq!(
+            config.source_roots(),
+            vec![PathBuf::from("/repo/contracts")]
+        );
+        assert_eq!(
+            config.check.analyses,
+            Some(vec!["s1".to_string(), "s2".to_string()])
+        );
+        assert_eq!(config.check.state_space_limit, Some(5000));
+        assert_eq!(config.lint.max_predicate_depth, Some(6));
+        assert_eq!(
+            config.lint.rules.get("operation-error-contract"),
+            Some(&false)
+        );
+        assert_eq!(
+            config.resolve(config.connect.adapter_config.as_ref().unwrap()),
+            PathBuf::from("/repo/deploy/tenor-adapters.toml")
+        );
+        assert_eq!(config.llm.provider.as_deref(), Some("openai"));
+        assert_eq!(
+            config.llm.base_url.as_deref(),
+            Some("http://llm.internal/v1")
+        );
+        assert_eq!(config.llm.model.as_deref(), Some("qwen2.5"));
+        assert_eq!(
+            config.generate.typescript.unwrap().out,
+            Some(PathBuf::from("web/generated"))
+        );
+        assert_eq!(config.serve.contracts.len(), 1);
+        assert_eq!(
+            config.serve.cors_origins,
+            Some(vec!["https://app.example.com".to_string()])
+        );
+        assert_eq!(config.serve.rate_limit, Some(120));
+        assert_eq!(config.serve.rate_limit_key.as_deref(), Some("token"));
+        assert_eq!(config.serve.max_body_size, Some(2048));
+        assert_eq!(config.serve.shutdown_timeout, Some(10));
+        assert_eq!(
+            config.serve.history,
+            Some(PathBuf::from("exports/history.json"))
+        );
+        let acme = &config.serve.tenants["acme"];
+        assert_eq!(acme.token_env, "ACME_TOKEN");
+        assert_eq!(acme.contracts, vec![PathBuf::from("tenants/acme.tenor")]);
+        assert_eq!(acme.max_contracts, Some(3));
+        assert_eq!(
+            acme.history,
+            Some(PathBuf::from("tenants/acme-history.json"))
+        );
+        assert_eq!(
+            acme.adapter_config,
+            Some(PathBuf::from("tenants/acme-adapters.toml"))
+        );
+    }
+
+    #[test]
+    fn empty_file_is_default() {
+        let config = ProjectConfig::parse("", Path::new("/repo")).unwrap();
+        assert!(config.project.source_roots.is_empty());
+        assert!(config.check.analyses.is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let err = ProjectConfig::parse("[chek]\nanalyses = []\n", Path::new("/repo")).unwrap_err();
+        assert!(err.contains("chek"), "{}", err);
+    }
+}
@@ -20,6 +20,12 @@ pub trait SourceProvider {
 
     /// Canonicalize a path for cycle detection and sandbox checks.
     fn canonicalize(&self, path: &Path) -> Result<PathBuf, std::io::Error>;
+
+    /// Directories outside the contract root that imports may resolve into.
+    /// Empty by default: imports stay within the root file's directory.
+    fn import_roots(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
 
 /// Default filesystem-backed source provider.
@@ -43,6 +49,50 @@ impl SourceProvider for FileSystemProvider {
     }
 }
 
+/// Filesystem provider that falls back to a list of import search paths.
+///
+/// An import is first resolved relative to the importing file, as with
+/// [`FileSystemProvider`]; if no such file exists, each search path is tried
+/// in order. Search paths are also accepted by the import sandbox check.
+pub struct SearchPathProvider {
+    import_paths: Vec<PathBuf>,
+}
+
+impl SearchPathProvider {
+    /// Create a provider that searches `import_paths` after the importing
+    /// file's directory.
+    pub fn new(import_paths: Vec<PathBuf>) -> Self {
+        Self { import_paths }
+    }
+}
+
+impl SourceProvider for SearchPathProvider {
+    fn read_source(&self, path: &Path) -> Result<String, std::io::Error> {
+        std::fs::read_to_string(path)
+    }
+
+    fn resolve_import(&self, base: &Path, import: &str) -> Result<PathBuf, std::io::Error> {
+        let local = base.join(import);
+        if local.exists() {
+            return Ok(local);
+        }
+        Ok(self
+            .import_paths
+            .iter()
+            .map(|dir| dir.join(import))
+            .find(|candidate| candidate.exists())
+            .unwrap_or(local))
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, std::io::Error> {
+        path.canonicalize()
+    }
+
+    fn import_roots(&self) -> Vec<PathBuf> {
+        self.import_paths.clone()
+    }
+}
+
 /// In-memory source provider for WASM and testing.
 ///
 /// Maps paths to source text strings. Canonicalization normalizes the path
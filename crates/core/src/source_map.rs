@@ -0,0 +1,272 @@
+//! Source maps from an interchange bundle back to its DSL text.
+//!
+//! A [`SourceMap`] records, for every construct in a bundle and every
+//! predicate node inside one, the byte range of the source text it was
+//! elaborated from. Nodes are addressed by JSON Pointer (RFC 6901) into the
+//! bundle, e.g. `/constructs/3/body/when/left`, so a tool holding a verdict's
+//! rule can highlight the exact condition that produced it.
+
+use crate::ast::{RawConstruct, RawExpr, RawStep, SourceRange};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Where each construct and predicate node of a bundle came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceMap {
+    /// Id of the bundle the pointers refer into
+    pub bundle: String,
+    /// One entry per construct and predicate node, in bundle order
+    pub mappings: Vec<SourceMapping>,
+}
+
+/// The source text of one bundle node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceMapping {
+    /// JSON Pointer to the node in the bundle
+    pub pointer: String,
+    /// Source file, as recorded in the construct's provenance
+    pub file: String,
+    /// Byte offset of the node's first character in `file`
+    pub start: u32,
+    /// Byte offset just past the node's last character in `file`
+    pub end: u32,
+}
+
+impl SourceMap {
+    /// The mapping for the node at `pointer`, if the map has one.
+    pub fn get(&self, pointer: &str) -> Option<&SourceMapping> {
+        self.mappings.iter().find(|m| m.pointer == pointer)
+    }
+}
+
+/// Build the source map of `bundle`, the Pass 6 serialization of
+/// `constructs`.
+pub fn build(constructs: &[RawConstruct], bundle: &Value) -> SourceMap {
+    let by_key: HashMap<(&str, &str), &RawConstruct> = constructs
+        .iter()
+        .filter_map(|c| construct_key(c).map(|key| (key, c)))
+        .collect();
+
+    let mut map = SourceMap {
+        bundle: bundle["id"].as_str().unwrap_or_default().to_owned(),
+        mappings: Vec::new(),
+    };
+    let serialized = bundle["constructs"].as_array().map(Vec::as_slice);
+    for (i, value) in serialized.unwrap_or_default().iter().enumerate() {
+        let key = (
+            value["kind"].as_str().unwrap_or_default(),
+            value["id"].as_str().unwrap_or_default(),
+        );
+        let Some(c) = by_key.get(&key) else {
+            continue;
+        };
+        let prov = c.prov();
+        let mut out = Mappings {
+            file: &prov.file,
+            mappings: &mut map.mappings,
+        };
+        let pointer = format!("/constructs/{}", i);
+        out.push(&pointer, prov.range);
+        match c {
+            RawConstruct::Entity { transitions, .. } => {
+                for (j, t) in transitions.iter().enumerate() {
+                    if let Some(guard) = &t.guard {
+                        out.expr(&format!("{}/transitions/{}/guard", pointer, j), guard);
+                    }
+                }
+            }
+            RawConstruct::Rule { when, .. } => {
+                out.expr(&format!("{}/body/when", pointer), when);
+            }
+            RawConstruct::Operation { precondition, .. } => {
+                out.expr(&format!("{}/precondition", pointer), precondition);
+            }
+            RawConstruct::Flow { steps, .. } => {
+                out.steps(&format!("{}/steps", pointer), &value["steps"], steps);
+            }
+            _ => {}
+        }
+    }
+    map
+}
+
+/// The serialized `kind` and id of a construct that appears in bundles.
+fn construct_key(c: &RawConstruct) -> Option<(&'static str, &str)> {
+    match c {
+        RawConstruct::Fact { id, .. } => Some(("Fact", id)),
+        RawConstruct::Entity { id, .. } => Some(("Entity", id)),
+        RawConstruct::Rule { id, .. } => Some(("Rule", id)),
+        RawConstruct::Operation { id, .. } => Some(("Operation", id)),
+        RawConstruct::Flow { id, .. } => Some(("Flow", id)),
+        RawConstruct::Persona { id, .. } => Some(("Persona", id)),
+        RawConstruct::Source { id, .. } => Some(("Source", id)),
+        RawConstruct::System { id, .. } => Some(("System", id)),
+        RawConstruct::Test { id, .. } => Some(("Test", id)),
+        RawConstruct::Assert { id, .. } => Some(("Assert", id)),
+        RawConstruct::TypeDecl { .. }
+        | RawConstruct::Import { .. }
+        | RawConstruct::Extends { .. }
+        | RawConstruct::Override { .. }
+        | RawConstruct::Gated { .. } => None,
+    }
+}
+
+/// Mappings being collected for one construct's file.
+struct Mappings<'a> {
+    file: &'a str,
+    mappings: &'a mut Vec<SourceMapping>,
+}
+
+impl Mappings<'_> {
+    fn push(&mut self, pointer: &str, range: SourceRange) {
+        self.mappings.push(SourceMapping {
+            pointer: pointer.to_owned(),
+            file: self.file.to_owned(),
+            start: range.start,
+            end: range.end,
+        });
+    }
+
+    /// Map a predicate and its sub-expressions, following the key names
+    /// Pass 6 gives them.
+    fn expr(&mut self, pointer: &str, expr: &RawExpr) {
+        self.push(pointer, expr.range());
+        match expr {
+            RawExpr::And(a, b) | RawExpr::Or(a, b) => {
+                self.expr(&format!("{}/left", pointer), a);
+                self.expr(&format!("{}/right", pointer), b);
+            }
+            RawExpr::Not(e, _) => self.expr(&format!("{}/operand", pointer), e),
+            RawExpr::Forall { body, .. } | RawExpr::Exists { body, .. } => {
+                self.expr(&format!("{}/body", pointer), body);
+            }
+            RawExpr::Compare { .. } | RawExpr::VerdictPresent { .. } => {}
+        }
+    }
+
+    /// Map branch conditions in a serialized step list. Pass 6 orders steps
+    /// from the entry, so they are matched to `steps` by id.
+    fn steps(&mut self, pointer: &str, serialized: &Value, steps: &BTreeMap<String, RawStep>) {
+        let Some(serialized) = serialized.as_array() else {
+            return;
+        };
+        for (k, value) in serialized.iter().enumerate() {
+            let Some(step) = value["id"].as_str().and_then(|id| steps.get(id)) else {
+                continue;
+            };
+            match step {
+                RawStep::BranchStep { condition, .. } => {
+                    self.expr(&format!("{}/{}/condition", pointer, k), condition);
+                }
+                RawStep::ParallelStep { branches, .. } => {
+                    let serialized_branches = value["branches"].as_array();
+                    for (b, branch) in serialized_branches.into_iter().flatten().enumerate() {
+                        let id = branch["id"].as_str().unwrap_or_default();
+                        if let Some(raw) = branches.iter().find(|r| r.id == id) {
+                            self.steps(
+                                &format!("{}/{}/branches/{}/steps", pointer, k, b),
+                                &branch["steps"],
+                                &raw.steps,
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::elaborate::elaborate_with_source_map;
+    use crate::source::InMemoryProvider;
+    use std::collections::{BTreeSet, HashMap};
+    use std::path::{Path, PathBuf};
+
+    const SOURCE: &str = r#"fact amount { type: Int(min: 0, max: 1000) source: "s.amount" }
+fact urgent { type: Bool source: "s.urgent" }
+
+rule big_order {
+  stratum: 0
+  when: amount > 100 and not urgent = true
+  produce: verdict big { payload: Bool = true }
+}
+
+flow review {
+  snapshot: at_initiation
+  entry: check
+  steps: {
+    check: BranchStep {
+      condition: verdict_present(big)
+      persona: clerk
+      if_true: Terminal(success)
+      if_false: Terminal(failure)
+    }
+  }
+}
+
+persona clerk
+"#;
+
+    fn text(map: &super::SourceMap, pointer: &str) -> &'static str {
+        let m = map
+            .get(pointer)
+            .unwrap_or_else(|| panic!("no mapping for {}", pointer));
+        assert!(m.file.ends_with("main.tenor"), "{}", m.file);
+        &SOURCE[m.start as usize..m.end as usize]
+    }
+
+    fn source_map() -> (serde_json::Value, super::SourceMap) {
+        let mut files = HashMap::new();
+        files.insert(PathBuf::from("/contract/main.tenor"), SOURCE.to_string());
+        let provider = InMemoryProvider::new(files);
+        elaborate_with_source_map(
+            Path::new("/contract/main.tenor"),
+            &provider,
+            &BTreeSet::new(),
+        )
+        .expect("elaborates")
+    }
+
+    fn pointer_of(bundle: &serde_json::Value, id: &str) -> String {
+        let constructs = bundle["constructs"].as_array().unwrap();
+        let i = constructs.iter().position(|c| c["id"] == id).unwrap();
+        format!("/constructs/{}", i)
+    }
+
+    #[test]
+    fn constructs_map_to_their_whole_declaration() {
+        let (bundle, map) = source_map();
+        assert_eq!(map.bundle, "main");
+        let rule = pointer_of(&bundle, "big_order");
+        assert!(text(&map, &rule).starts_with("rule big_order {"));
+        assert!(text(&map, &rule).ends_with("true }\n}"));
+        assert_eq!(text(&map, &pointer_of(&bundle, "clerk")), "persona clerk");
+    }
+
+    #[test]
+    fn predicate_nodes_map_to_their_expression_text() {
+        let (bundle, map) = source_map();
+        let when = format!("{}/body/when", pointer_of(&bundle, "big_order"));
+        assert_eq!(text(&map, &when), "amount > 100 and not urgent = true");
+        assert_eq!(text(&map, &format!("{}/left", when)), "amount > 100");
+        assert_eq!(text(&map, &format!("{}/right", when)), "not urgent = true");
+        assert_eq!(
+            text(&map, &format!("{}/right/operand", when)),
+            "urgent = true"
+        );
+        // Every pointer resolves in the bundle
+        for m in &map.mappings {
+            assert!(bundle.pointer(&m.pointer).is_some(), "{}", m.pointer);
+        }
+    }
+
+    #[test]
+    fn branch_conditions_are_mapped() {
+        let (bundle, map) = source_map();
+        let condition = format!("{}/steps/0/condition", pointer_of(&bundle, "review"));
+        assert_eq!(text(&map, &condition), "verdict_present(big)");
+    }
+}
@@ -0,0 +1,177 @@
+//! Units of measure for dimensioned Decimal types.
+//!
+//! A unit expression is a product of symbols raised to integer powers, e.g.
+//! `kg`, `m/s`, `kg*m/s^2` or `1/s`. Units are compared in canonical form:
+//! symbols sorted, exponents combined, and zero exponents dropped, so
+//! `m*s/s` and `m` are the same unit. A Decimal without a unit is
+//! dimensionless, written `1`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A unit of measure in canonical form: symbol -> non-zero exponent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Unit(BTreeMap<String, i32>);
+
+impl Unit {
+    /// The unit of a dimensionless quantity.
+    pub fn dimensionless() -> Unit {
+        Unit::default()
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Parse a unit expression: factors separated by `*` or `/`, each a
+    /// symbol with an optional `^exponent`, or the literal `1`. A `/`
+    /// divides by the single factor that follows it.
+    pub fn parse(expr: &str) -> Result<Unit, String> {
+        let mut unit = Unit::default();
+        let mut sign = 1;
+        let mut rest = expr.trim();
+        if rest.is_empty() {
+            return Err("unit expression is empty".to_string());
+        }
+        loop {
+            let end = rest.find(['*', '/']).unwrap_or(rest.len());
+            let factor = rest[..end].trim();
+            if factor != "1" {
+                let (symbol, exponent) = parse_factor(factor)?;
+                unit.add(symbol, sign * exponent);
+            }
+            if end == rest.len() {
+                break;
+            }
+            sign = if rest.as_bytes()[end] == b'/' { -1 } else { 1 };
+            rest = &rest[end + 1..];
+        }
+        Ok(unit)
+    }
+
+    /// The `unit` annotation of a Decimal type in this unit; `None` when
+    /// dimensionless.
+    pub fn annotation(&self) -> Option<String> {
+        (!self.is_dimensionless()).then(|| self.to_string())
+    }
+
+    /// The unit of a product of quantities in `self` and `other`.
+    pub fn mul(&self, other: &Unit) -> Unit {
+        let mut product = self.clone();
+        for (symbol, exponent) in &other.0 {
+            product.add(symbol, *exponent);
+        }
+        product
+    }
+
+    fn add(&mut self, symbol: &str, exponent: i32) {
+        let e = self.0.entry(symbol.to_string()).or_insert(0);
+        *e += exponent;
+        if *e == 0 {
+            self.0.remove(symbol);
+        }
+    }
+}
+
+fn parse_factor(factor: &str) -> Result<(&str, i32), String> {
+    let (symbol, exponent) = match factor.split_once('^') {
+        Some((s, e)) => {
+            let e = e
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| format!("invalid exponent in unit factor '{}'", factor))?;
+            (s.trim(), e)
+        }
+        None => (factor, 1),
+    };
+    let mut chars = symbol.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("invalid unit symbol '{}'", symbol));
+    }
+    Ok((symbol, exponent))
+}
+
+/// Canonical form: positive powers joined by `*`, then `/` and each
+/// negative power; `1` when dimensionless.
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let render = |symbol: &str, exponent: i32| {
+            if exponent == 1 {
+                symbol.to_string()
+            } else {
+                format!("{}^{}", symbol, exponent)
+            }
+        };
+        let numerator: Vec<String> = self
+            .0
+            .iter()
+            .filter(|(_, e)| **e > 0)
+            .map(|(s, e)| render(s, *e))
+            .collect();
+        let denominator: Vec<String> = self
+            .0
+            .iter()
+            .filter(|(_, e)| **e < 0)
+            .map(|(s, e)| render(s, -*e))
+            .collect();
+        if numerator.is_empty() {
+            write!(f, "1")?;
+        } else {
+            write!(f, "{}", numerator.join("*"))?;
+        }
+        for d in denominator {
+            write!(f, "/{}", d)?;
+        }
+        Ok(())
+    }
+}
+
+/// The unit of a Decimal type's optional `unit` annotation.
+pub fn unit_of(annotation: Option<&str>) -> Unit {
+    annotation
+        .and_then(|u| Unit::parse(u).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(expr: &str) -> String {
+        Unit::parse(expr).unwrap().to_string()
+    }
+
+    #[test]
+    fn parses_and_canonicalizes_units() {
+        assert_eq!(canonical("kg"), "kg");
+        assert_eq!(canonical("s^-2 * m * kg"), "kg*m/s^2");
+        assert_eq!(canonical("m/s/s"), "m/s^2");
+        assert_eq!(canonical("m*s/s"), "m");
+        assert_eq!(canonical("1/s"), "1/s");
+        assert_eq!(canonical("1"), "1");
+    }
+
+    #[test]
+    fn rejects_malformed_units() {
+        assert!(Unit::parse("").is_err());
+        assert!(Unit::parse("kg*").is_err());
+        assert!(Unit::parse("2kg").is_err());
+        assert!(Unit::parse("m^x").is_err());
+    }
+
+    #[test]
+    fn multiplication_derives_units() {
+        let lb = Unit::parse("lb").unwrap();
+        let kg_per_lb = Unit::parse("kg/lb").unwrap();
+        assert_eq!(lb.mul(&kg_per_lb).to_string(), "kg");
+
+        let m = Unit::parse("m").unwrap();
+        assert_eq!(m.mul(&m).to_string(), "m^2");
+        assert!(Unit::parse("s")
+            .unwrap()
+            .mul(&Unit::parse("1/s").unwrap())
+            .is_dimensionless());
+    }
+}
@@ -26,7 +26,7 @@ fn collect_expected_json_files(dir: &Path) -> Vec<std::path::PathBuf> {
         .filter_map(|e| e.ok())
         .map(|e| e.path())
         .filter(|p| {
-            p.extension().map_or(false, |e| e == "json")
+            p.extension().is_some_and(|e| e == "json")
                 && p.to_string_lossy().contains("expected.json")
                 && !p.to_string_lossy().contains("expected-error.json")
         })
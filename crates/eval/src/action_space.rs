@@ -3,9 +3,13 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use crate::assemble;
-use crate::operation::{EntityStateMap, DEFAULT_INSTANCE_ID};
+use crate::operation::{get_instance_state, EntityStateMap, DEFAULT_INSTANCE_ID};
+use crate::predicate::{eval_pred, EvalContext};
+use crate::provenance::ProvenanceCollector;
 use crate::rules;
-use crate::types::{Contract, EvalError, FlowStep, Predicate, VerdictSet};
+use crate::types::{
+    Contract, EffectKind, EvalError, FactSet, FlowStep, Operation, Predicate, Value, VerdictSet,
+};
 use serde::{Deserialize, Serialize};
 
 /// A single executable action available to a persona.
@@ -49,6 +53,31 @@ pub struct ActionSpace {
     pub blocked_actions: Vec<BlockedAction>,
 }
 
+/// Availability of every operation to every persona, from one evaluation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionSpaceMatrix {
+    /// Declared personas, in declaration order.
+    pub personas: Vec<String>,
+    /// Declared operations, in declaration order.
+    pub operations: Vec<String>,
+    pub current_verdicts: Vec<VerdictSummary>,
+    /// persona_id → operation_id → availability.
+    pub matrix: BTreeMap<String, BTreeMap<String, OperationAvailability>>,
+    /// persona_id → that persona's flow-level action space.
+    pub action_spaces: BTreeMap<String, ActionSpace>,
+}
+
+/// Whether a persona can perform an operation right now.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationAvailability {
+    pub available: bool,
+    /// Why the operation is unavailable; `None` when available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<BlockedReason>,
+    /// entity_id → valid instance_ids when available, blocking ones otherwise.
+    pub instance_bindings: BTreeMap<String, BTreeSet<String>>,
+}
+
 /// An action that exists but is NOT currently executable.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockedAction {
@@ -63,20 +92,45 @@ pub struct BlockedAction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum BlockedReason {
-    PersonaNotAuthorized,
+    /// The persona is not allowed to perform the flow's entry operation.
+    PersonaNotAuthorized {
+        /// Personas that may perform the entry operation.
+        #[serde(default)]
+        allowed_personas: Vec<String>,
+    },
+    /// The entry operation's precondition or a transition guard does not hold.
     PreconditionNotMet {
         missing_verdicts: Vec<String>,
+        /// Top-level clauses of the precondition and guards that are false.
+        #[serde(default)]
+        failed_clauses: Vec<FailedClause>,
     },
     EntityNotInSourceState {
         entity_id: String,
         current_state: String,
         required_state: String,
+        /// Every source state the entry operation's effects require of
+        /// this entity.
+        #[serde(default)]
+        expected_states: Vec<String>,
     },
     MissingFacts {
         fact_ids: Vec<String>,
     },
 }
 
+/// One top-level clause of a precondition (or transition guard) that
+/// evaluated to false.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedClause {
+    /// The clause in Tenor predicate syntax, e.g. `amount <= limit`.
+    pub clause: String,
+    /// Facts the clause read, with their current values as plain JSON.
+    pub facts: BTreeMap<String, serde_json::Value>,
+    /// Verdicts the clause requires that are absent.
+    pub missing_verdicts: Vec<String>,
+}
+
 /// Compute the complete action space for a persona given the current state of the world.
 ///
 /// Pure function. No IO. No side effects. No state mutation.
@@ -98,10 +152,83 @@ pub fn compute_action_space(
     // 2. Evaluate rules to get verdicts
     let verdict_set = rules::eval_strata(contract, &fact_set)?;
 
-    // 3. Build current_verdicts
-    let current_verdicts = verdict_set_to_summaries(&verdict_set);
+    // 3. Analyze each flow
+    Ok(action_space_for(
+        contract,
+        &fact_set,
+        &verdict_set,
+        entity_states,
+        persona_id,
+    ))
+}
+
+/// Compute the action space of every persona, and the availability of
+/// every operation to every persona, evaluating the rules once.
+///
+/// Equivalent to calling [`compute_action_space`] per persona, without
+/// re-assembling facts and re-evaluating rules each time.
+pub fn compute_action_space_matrix(
+    contract: &Contract,
+    facts: &serde_json::Value,
+    entity_states: &EntityStateMap,
+) -> Result<ActionSpaceMatrix, EvalError> {
+    let fact_set = assemble::assemble_facts(contract, facts)?;
+    let verdict_set = rules::eval_strata(contract, &fact_set)?;
+
+    let mut matrix = BTreeMap::new();
+    let mut action_spaces = BTreeMap::new();
+    for persona_id in &contract.personas {
+        let row = contract
+            .operations
+            .iter()
+            .map(|op| {
+                let availability =
+                    match check_operation(op, persona_id, &fact_set, &verdict_set, entity_states) {
+                        OperationCheck::Enabled {
+                            instance_bindings, ..
+                        } => OperationAvailability {
+                            available: true,
+                            reason: None,
+                            instance_bindings,
+                        },
+                        OperationCheck::Blocked {
+                            reason,
+                            instance_bindings,
+                        } => OperationAvailability {
+                            available: false,
+                            reason: Some(reason),
+                            instance_bindings,
+                        },
+                    };
+                (op.id.clone(), availability)
+            })
+            .collect();
+        matrix.insert(persona_id.clone(), row);
+        action_spaces.insert(
+            persona_id.clone(),
+            action_space_for(contract, &fact_set, &verdict_set, entity_states, persona_id),
+        );
+    }
+
+    Ok(ActionSpaceMatrix {
+        personas: contract.personas.clone(),
+        operations: contract.operations.iter().map(|op| op.id.clone()).collect(),
+        current_verdicts: verdict_set_to_summaries(&verdict_set),
+        matrix,
+        action_spaces,
+    })
+}
+
+/// The action space of one persona over already-evaluated facts and verdicts.
+fn action_space_for(
+    contract: &Contract,
+    fact_set: &FactSet,
+    verdict_set: &VerdictSet,
+    entity_states: &EntityStateMap,
+    persona_id: &str,
+) -> ActionSpace {
+    let current_verdicts = verdict_set_to_summaries(verdict_set);
 
-    // 4. Analyze each flow
     let mut actions = Vec::new();
     let mut blocked_actions = Vec::new();
 
@@ -124,133 +251,31 @@ pub fn compute_action_space(
             None => continue,
         };
 
-        // Check 1: Persona authorization
-        if !operation.allowed_personas.contains(&persona_id.to_string()) {
-            blocked_actions.push(BlockedAction {
-                flow_id: flow.id.clone(),
-                reason: BlockedReason::PersonaNotAuthorized,
-                instance_bindings: BTreeMap::new(),
-            });
-            continue;
-        }
-
-        // Check 2: Precondition verdicts
-        let required_verdicts = extract_verdict_refs(&operation.precondition);
-        let missing: Vec<String> = required_verdicts
-            .iter()
-            .filter(|v| !verdict_set.has_verdict(v))
-            .cloned()
-            .collect();
-
-        if !missing.is_empty() {
-            blocked_actions.push(BlockedAction {
-                flow_id: flow.id.clone(),
-                reason: BlockedReason::PreconditionNotMet {
-                    missing_verdicts: missing,
-                },
-                instance_bindings: BTreeMap::new(),
-            });
-            continue;
-        }
-
-        // Check 3: Entity states — per §15.6, check per-instance availability.
-        //
-        // For each entity referenced by the entry operation's effects, collect
-        // all instance_ids that are in the required source state. An action is
-        // available if every required entity has at least one instance in the
-        // correct source state. The instance_bindings map captures which instances
-        // are valid.
-        //
-        // For single-instance contracts (only `_default` per entity), this behaves
-        // identically to the pre-multi-instance check.
-        let mut entity_blocked = false;
-        let mut blocking_instance_bindings: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
-        let mut valid_instance_bindings: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
-
-        for effect in &operation.effects {
-            let entity_id = &effect.entity_id;
-
-            // Collect all instances of this entity and their states
-            let mut valid_instances: BTreeSet<String> = BTreeSet::new();
-            let mut blocking_instances: BTreeSet<String> = BTreeSet::new();
-            let mut found_any = false;
-
-            for ((eid, iid), state) in entity_states {
-                if eid != entity_id {
-                    continue;
-                }
-                found_any = true;
-                if state == &effect.from {
-                    valid_instances.insert(iid.clone());
-                } else {
-                    blocking_instances.insert(iid.clone());
-                }
-            }
-
-            if !found_any {
-                // No instances found for this entity — treat as blocked
-                blocking_instance_bindings
-                    .entry(entity_id.clone())
-                    .or_default()
-                    .insert(DEFAULT_INSTANCE_ID.to_string());
-                blocked_actions.push(BlockedAction {
-                    flow_id: flow.id.clone(),
-                    reason: BlockedReason::EntityNotInSourceState {
-                        entity_id: entity_id.clone(),
-                        current_state: "(unknown)".to_string(),
-                        required_state: effect.from.clone(),
-                    },
-                    instance_bindings: blocking_instance_bindings.clone(),
-                });
-                entity_blocked = true;
-                break;
-            }
-
-            if valid_instances.is_empty() {
-                // All instances of this entity are in wrong states
-                if let Some(((_, iid), state)) =
-                    entity_states.iter().find(|((eid, _), _)| eid == entity_id)
-                {
-                    blocking_instance_bindings
-                        .entry(entity_id.clone())
-                        .or_default()
-                        .insert(iid.clone());
+        let (required_verdicts, valid_instance_bindings) =
+            match check_operation(operation, persona_id, fact_set, verdict_set, entity_states) {
+                OperationCheck::Enabled {
+                    required_verdicts,
+                    instance_bindings,
+                } => (required_verdicts, instance_bindings),
+                OperationCheck::Blocked {
+                    reason,
+                    instance_bindings,
+                } => {
                     blocked_actions.push(BlockedAction {
                         flow_id: flow.id.clone(),
-                        reason: BlockedReason::EntityNotInSourceState {
-                            entity_id: entity_id.clone(),
-                            current_state: state.clone(),
-                            required_state: effect.from.clone(),
-                        },
-                        instance_bindings: blocking_instance_bindings.clone(),
+                        reason,
+                        instance_bindings,
                     });
+                    continue;
                 }
-                entity_blocked = true;
-                break;
-            }
-
-            // Some or all instances are valid — record them
-            *valid_instance_bindings
-                .entry(entity_id.clone())
-                .or_default() = valid_instances;
-
-            // Also record blocking instances for information
-            if !blocking_instances.is_empty() {
-                *blocking_instance_bindings
-                    .entry(entity_id.clone())
-                    .or_default() = blocking_instances;
-            }
-        }
-        if entity_blocked {
-            continue;
-        }
+            };
 
         // All checks pass — build Action
         let enabling_verdicts: Vec<VerdictSummary> = required_verdicts
             .iter()
             .filter_map(|vt| {
                 verdict_set.get_verdict(vt).map(|vi| VerdictSummary {
-                    verdict_type: vi.verdict_type.clone(),
+                    verdict_type: vi.verdict_type.to_string(),
                     payload: vi.payload.to_json(),
                     producing_rule: vi.provenance.rule_id.clone(),
                     stratum: vi.provenance.stratum,
@@ -262,6 +287,13 @@ pub fn compute_action_space(
             .effects
             .iter()
             .map(|effect| {
+                if effect.kind == EffectKind::CreateInstance {
+                    return EntitySummary {
+                        entity_id: effect.entity_id.clone(),
+                        current_state: "(new)".to_string(),
+                        possible_transitions: vec![effect.to.clone()],
+                    };
+                }
                 // For affected_entities summary, pick any valid instance's state.
                 // We use DEFAULT_INSTANCE_ID for single-instance backward compat,
                 // but for multi-instance we pick the first valid instance.
@@ -270,11 +302,10 @@ pub fn compute_action_space(
                     .and_then(|s| s.iter().next())
                     .map(|s| s.as_str())
                     .unwrap_or(DEFAULT_INSTANCE_ID);
-                let key = (effect.entity_id.clone(), lookup_instance.to_string());
-                let current_state = entity_states
-                    .get(&key)
-                    .cloned()
-                    .unwrap_or_else(|| "(unknown)".to_string());
+                let current_state =
+                    get_instance_state(entity_states, &effect.entity_id, lookup_instance)
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| "(unknown)".to_string());
                 let possible_transitions = contract
                     .get_entity(&effect.entity_id)
                     .map(|e| {
@@ -307,12 +338,167 @@ pub fn compute_action_space(
         });
     }
 
-    Ok(ActionSpace {
+    ActionSpace {
         persona_id: persona_id.to_string(),
         actions,
         current_verdicts,
         blocked_actions,
-    })
+    }
+}
+
+/// Outcome of checking whether a persona can perform an operation now.
+enum OperationCheck {
+    Enabled {
+        /// Verdicts the precondition and guards reference, all present.
+        required_verdicts: Vec<String>,
+        /// entity_id → instances in a valid source state.
+        instance_bindings: BTreeMap<String, BTreeSet<String>>,
+    },
+    Blocked {
+        reason: BlockedReason,
+        /// entity_id → instances blocking the operation.
+        instance_bindings: BTreeMap<String, BTreeSet<String>>,
+    },
+}
+
+/// Check persona authorization, then the precondition and transition
+/// guards, then entity source states.
+fn check_operation(
+    operation: &Operation,
+    persona_id: &str,
+    fact_set: &FactSet,
+    verdict_set: &VerdictSet,
+    entity_states: &EntityStateMap,
+) -> OperationCheck {
+    // Check 1: Persona authorization
+    if !operation.allowed_personas.contains(&persona_id.to_string()) {
+        return OperationCheck::Blocked {
+            reason: BlockedReason::PersonaNotAuthorized {
+                allowed_personas: operation.allowed_personas.clone(),
+            },
+            instance_bindings: BTreeMap::new(),
+        };
+    }
+
+    // Check 2: Precondition and transition guard verdicts
+    let mut required_verdicts = extract_verdict_refs(&operation.precondition);
+    for guard in operation.effects.iter().filter_map(|e| e.guard.as_ref()) {
+        for v in extract_verdict_refs(guard) {
+            if !required_verdicts.contains(&v) {
+                required_verdicts.push(v);
+            }
+        }
+    }
+    let missing: Vec<String> = required_verdicts
+        .iter()
+        .filter(|v| !verdict_set.has_verdict(v))
+        .cloned()
+        .collect();
+
+    let failed_clauses = failed_clauses(operation, fact_set, verdict_set);
+
+    if !missing.is_empty() || !failed_clauses.is_empty() {
+        return OperationCheck::Blocked {
+            reason: BlockedReason::PreconditionNotMet {
+                missing_verdicts: missing,
+                failed_clauses,
+            },
+            instance_bindings: BTreeMap::new(),
+        };
+    }
+
+    // Check 3: Entity states — per §15.6, check per-instance availability.
+    //
+    // For each entity referenced by the operation's effects, collect all
+    // instance_ids that are in the required source state. An operation is
+    // available if every required entity has at least one instance in the
+    // correct source state. The instance_bindings map captures which instances
+    // are valid.
+    //
+    // For single-instance contracts (only `_default` per entity), this behaves
+    // identically to the pre-multi-instance check. Create effects need no
+    // existing instance, so they never block and bind no instances.
+    let mut blocking_instance_bindings: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut valid_instance_bindings: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for effect in &operation.effects {
+        if effect.kind == EffectKind::CreateInstance {
+            continue;
+        }
+        let entity_id = &effect.entity_id;
+
+        // Collect all instances of this entity and their states
+        let mut valid_instances: BTreeSet<String> = BTreeSet::new();
+        let mut blocking_instances: BTreeSet<String> = BTreeSet::new();
+        let mut found_any = false;
+
+        for ((eid, iid), state) in entity_states {
+            if eid != entity_id {
+                continue;
+            }
+            found_any = true;
+            if state == &effect.from {
+                valid_instances.insert(iid.to_string());
+            } else {
+                blocking_instances.insert(iid.to_string());
+            }
+        }
+
+        if !found_any {
+            // No instances found for this entity — treat as blocked
+            blocking_instance_bindings
+                .entry(entity_id.clone())
+                .or_default()
+                .insert(DEFAULT_INSTANCE_ID.to_string());
+            return OperationCheck::Blocked {
+                reason: BlockedReason::EntityNotInSourceState {
+                    entity_id: entity_id.clone(),
+                    current_state: "(unknown)".to_string(),
+                    required_state: effect.from.clone(),
+                    expected_states: expected_states(operation, entity_id),
+                },
+                instance_bindings: blocking_instance_bindings,
+            };
+        }
+
+        if valid_instances.is_empty() {
+            // All instances of this entity are in wrong states
+            if let Some(((_, iid), state)) =
+                entity_states.iter().find(|((eid, _), _)| eid == entity_id)
+            {
+                blocking_instance_bindings
+                    .entry(entity_id.clone())
+                    .or_default()
+                    .insert(iid.to_string());
+                return OperationCheck::Blocked {
+                    reason: BlockedReason::EntityNotInSourceState {
+                        entity_id: entity_id.clone(),
+                        current_state: state.to_string(),
+                        required_state: effect.from.clone(),
+                        expected_states: expected_states(operation, entity_id),
+                    },
+                    instance_bindings: blocking_instance_bindings,
+                };
+            }
+        }
+
+        // Some or all instances are valid — record them
+        *valid_instance_bindings
+            .entry(entity_id.clone())
+            .or_default() = valid_instances;
+
+        // Also record blocking instances for information
+        if !blocking_instances.is_empty() {
+            *blocking_instance_bindings
+                .entry(entity_id.clone())
+                .or_default() = blocking_instances;
+        }
+    }
+
+    OperationCheck::Enabled {
+        required_verdicts,
+        instance_bindings: valid_instance_bindings,
+    }
 }
 
 /// Get the id of a flow step.
@@ -330,7 +516,7 @@ fn step_id(step: &FlowStep) -> &str {
 fn verdict_set_to_summaries(vs: &VerdictSet) -> Vec<VerdictSummary> {
     vs.0.iter()
         .map(|vi| VerdictSummary {
-            verdict_type: vi.verdict_type.clone(),
+            verdict_type: vi.verdict_type.to_string(),
             payload: vi.payload.to_json(),
             producing_rule: vi.provenance.rule_id.clone(),
             stratum: vi.provenance.stratum,
@@ -345,7 +531,11 @@ fn build_description(flow_id: &str, op_id: &str, effects: &[crate::types::Effect
     }
     let transitions: Vec<String> = effects
         .iter()
-        .map(|e| format!("{} from {} to {}", e.entity_id, e.from, e.to))
+        .map(|e| match e.kind {
+            EffectKind::Transition => format!("{} from {} to {}", e.entity_id, e.from, e.to),
+            EffectKind::CreateInstance => format!("new {} in {}", e.entity_id, e.to),
+            EffectKind::DestroyInstance => format!("{} from {} to destroyed", e.entity_id, e.from),
+        })
         .collect();
     format!(
         "Execute {}: {} transitions {}",
@@ -355,6 +545,145 @@ fn build_description(flow_id: &str, op_id: &str, effects: &[crate::types::Effect
     )
 }
 
+/// Source states the operation's effects require of `entity_id`, sorted.
+fn expected_states(operation: &Operation, entity_id: &str) -> Vec<String> {
+    operation
+        .effects
+        .iter()
+        .filter(|e| e.entity_id == entity_id && e.kind != EffectKind::CreateInstance)
+        .map(|e| e.from.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Evaluate each top-level conjunct of the operation's precondition and
+/// transition guards, returning those that are false.
+///
+/// Clauses that cannot be evaluated without entity instance data (field
+/// references through an entity binding, instance quantifiers) are
+/// skipped; the operation itself decides them at execution time.
+fn failed_clauses(
+    operation: &Operation,
+    facts: &FactSet,
+    verdicts: &VerdictSet,
+) -> Vec<FailedClause> {
+    let mut clauses = Vec::new();
+    conjuncts(&operation.precondition, &mut clauses);
+    for guard in operation.effects.iter().filter_map(|e| e.guard.as_ref()) {
+        conjuncts(guard, &mut clauses);
+    }
+
+    let ctx = EvalContext::new();
+    clauses
+        .into_iter()
+        .filter_map(|clause| {
+            let mut collector = ProvenanceCollector::new();
+            match eval_pred(clause, facts, verdicts, &ctx, &mut collector) {
+                Ok(Value::Bool(false)) => Some(FailedClause {
+                    clause: render_clause(clause),
+                    facts: collector
+                        .facts_used
+                        .iter()
+                        .filter_map(|id| facts.get(id).map(|v| (id.clone(), v.to_plain_json())))
+                        .collect(),
+                    missing_verdicts: collector
+                        .verdicts_used
+                        .into_iter()
+                        .filter(|v| !verdicts.has_verdict(v))
+                        .collect(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Flatten a chain of `∧` into its operands.
+fn conjuncts<'a>(pred: &'a Predicate, out: &mut Vec<&'a Predicate>) {
+    match pred {
+        Predicate::And { left, right } => {
+            conjuncts(left, out);
+            conjuncts(right, out);
+        }
+        other => out.push(other),
+    }
+}
+
+/// Render a predicate in Tenor predicate syntax for display.
+fn render_clause(pred: &Predicate) -> String {
+    match pred {
+        Predicate::FactRef(id) => id.clone(),
+        Predicate::FieldRef { var, field } => format!("{}.{}", var, field),
+        Predicate::Literal { value, .. } => match value {
+            Value::Bool(b) => b.to_string(),
+            Value::Int(n) => n.to_string(),
+            Value::Decimal(d) => d.to_string(),
+            Value::Text(s) | Value::Date(s) | Value::DateTime(s) => {
+                serde_json::Value::String(s.clone()).to_string()
+            }
+            Value::Enum(s) => s.clone(),
+            other => other.to_json().to_string(),
+        },
+        Predicate::VerdictPresent(id) => format!("verdict_present({})", id),
+        Predicate::InstancesOf(entity) => format!("instances({})", entity),
+        Predicate::InstanceState(var) => format!("state({})", var),
+        Predicate::Compare {
+            left, op, right, ..
+        } => format!("{} {} {}", render_clause(left), op, render_clause(right)),
+        Predicate::Mul { left, literal, .. } => format!("{} * {}", render_clause(left), literal),
+        Predicate::Convert { amount, rate_fact } => {
+            format!("convert({}, {})", render_clause(amount), rate_fact)
+        }
+        Predicate::And { left, right } => {
+            format!("{} ∧ {}", render_operand(left), render_operand(right))
+        }
+        Predicate::Or { left, right } => {
+            format!("{} ∨ {}", render_operand(left), render_operand(right))
+        }
+        Predicate::Not { operand } => match operand.as_ref() {
+            Predicate::VerdictPresent(_) | Predicate::FactRef(_) => {
+                format!("¬{}", render_clause(operand))
+            }
+            _ => format!("¬({})", render_clause(operand)),
+        },
+        Predicate::Forall {
+            variable,
+            domain,
+            body,
+            ..
+        } => format!(
+            "∀ {} ∈ {} . {}",
+            variable,
+            render_clause(domain),
+            render_clause(body)
+        ),
+        Predicate::Exists {
+            variable,
+            domain,
+            body,
+            ..
+        } => format!(
+            "∃ {} ∈ {} . {}",
+            variable,
+            render_clause(domain),
+            render_clause(body)
+        ),
+    }
+}
+
+/// Render an operand of `∧` or `∨`, parenthesizing nested connectives and
+/// quantifiers.
+fn render_operand(pred: &Predicate) -> String {
+    match pred {
+        Predicate::And { .. }
+        | Predicate::Or { .. }
+        | Predicate::Forall { .. }
+        | Predicate::Exists { .. } => format!("({})", render_clause(pred)),
+        _ => render_clause(pred),
+    }
+}
+
 /// Walk a predicate AST and collect all VerdictPresent node identifiers.
 pub(crate) fn extract_verdict_refs(pred: &Predicate) -> Vec<String> {
     let mut refs = Vec::new();
@@ -387,14 +716,21 @@ fn collect_verdict_refs(pred: &Predicate, refs: &mut Vec<String>) {
         Predicate::Mul { left, .. } => {
             collect_verdict_refs(left, refs);
         }
-        Predicate::FactRef(_) | Predicate::FieldRef { .. } | Predicate::Literal { .. } => {}
+        Predicate::Convert { amount, .. } => {
+            collect_verdict_refs(amount, refs);
+        }
+        Predicate::FactRef(_)
+        | Predicate::FieldRef { .. }
+        | Predicate::Literal { .. }
+        | Predicate::InstancesOf(_)
+        | Predicate::InstanceState(_) => {}
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::operation::EntityStateMap;
+    use crate::operation::{instance_key, EntityStateMap};
     use crate::types::{Contract, TypeSpec, Value};
 
     fn bool_type() -> TypeSpec {
@@ -402,6 +738,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -622,10 +959,12 @@ mod tests {
         assert_eq!(result.actions.len(), 0);
         assert_eq!(result.blocked_actions.len(), 1);
         assert_eq!(result.blocked_actions[0].flow_id, "approval_flow");
-        assert!(matches!(
-            &result.blocked_actions[0].reason,
-            BlockedReason::PersonaNotAuthorized
-        ));
+        match &result.blocked_actions[0].reason {
+            BlockedReason::PersonaNotAuthorized { allowed_personas } => {
+                assert_eq!(allowed_personas, &vec!["admin".to_string()]);
+            }
+            other => panic!("expected PersonaNotAuthorized, got {:?}", other),
+        }
         // No instance bindings for persona-blocked actions
         assert!(result.blocked_actions[0].instance_bindings.is_empty());
     }
@@ -647,8 +986,54 @@ mod tests {
         assert_eq!(result.blocked_actions.len(), 1);
         assert_eq!(result.blocked_actions[0].flow_id, "approval_flow");
         match &result.blocked_actions[0].reason {
-            BlockedReason::PreconditionNotMet { missing_verdicts } => {
+            BlockedReason::PreconditionNotMet {
+                missing_verdicts,
+                failed_clauses,
+            } => {
                 assert_eq!(missing_verdicts, &vec!["account_active".to_string()]);
+                assert_eq!(failed_clauses.len(), 1);
+                assert_eq!(failed_clauses[0].clause, "verdict_present(account_active)");
+                assert_eq!(failed_clauses[0].missing_verdicts, ["account_active"]);
+            }
+            other => panic!("expected PreconditionNotMet, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn failed_fact_clause_blocks_with_values() {
+        let mut bundle = test_bundle();
+        bundle["constructs"][3]["precondition"] = serde_json::json!({
+            "left": { "verdict_present": "account_active" },
+            "op": "and",
+            "right": {
+                "left": { "fact_ref": "is_active" },
+                "op": "=",
+                "right": { "literal": false, "type": { "base": "Bool" } }
+            }
+        });
+        let contract = make_contract(&bundle);
+        let facts = serde_json::json!({ "is_active": true });
+        let entity_states = crate::operation::single_instance(
+            [("Order".to_string(), "pending".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        let result = compute_action_space(&contract, &facts, &entity_states, "admin").unwrap();
+
+        assert_eq!(result.actions.len(), 0);
+        match &result.blocked_actions[0].reason {
+            BlockedReason::PreconditionNotMet {
+                missing_verdicts,
+                failed_clauses,
+            } => {
+                assert!(missing_verdicts.is_empty());
+                assert_eq!(failed_clauses.len(), 1);
+                assert_eq!(failed_clauses[0].clause, "is_active = false");
+                assert_eq!(
+                    failed_clauses[0].facts["is_active"],
+                    serde_json::json!(true)
+                );
             }
             other => panic!("expected PreconditionNotMet, got {:?}", other),
         }
@@ -674,10 +1059,12 @@ mod tests {
                 entity_id,
                 current_state,
                 required_state,
+                expected_states,
             } => {
                 assert_eq!(entity_id, "Order");
                 assert_eq!(current_state, "approved");
                 assert_eq!(required_state, "pending");
+                assert_eq!(expected_states, &vec!["pending".to_string()]);
             }
             other => panic!("expected EntityNotInSourceState, got {:?}", other),
         }
@@ -783,14 +1170,8 @@ mod tests {
         let facts = serde_json::json!({ "is_active": true });
 
         let mut entity_states = EntityStateMap::new();
-        entity_states.insert(
-            ("Order".to_string(), "order-1".to_string()),
-            "pending".to_string(),
-        );
-        entity_states.insert(
-            ("Order".to_string(), "order-2".to_string()),
-            "approved".to_string(),
-        );
+        entity_states.insert(instance_key("Order", "order-1"), "pending".into());
+        entity_states.insert(instance_key("Order", "order-2"), "approved".into());
 
         let result = compute_action_space(&contract, &facts, &entity_states, "admin").unwrap();
 
@@ -819,14 +1200,8 @@ mod tests {
         let facts = serde_json::json!({ "is_active": true });
 
         let mut entity_states = EntityStateMap::new();
-        entity_states.insert(
-            ("Order".to_string(), "order-1".to_string()),
-            "approved".to_string(),
-        );
-        entity_states.insert(
-            ("Order".to_string(), "order-2".to_string()),
-            "approved".to_string(),
-        );
+        entity_states.insert(instance_key("Order", "order-1"), "approved".into());
+        entity_states.insert(instance_key("Order", "order-2"), "approved".into());
 
         let result = compute_action_space(&contract, &facts, &entity_states, "admin").unwrap();
 
@@ -857,14 +1232,8 @@ mod tests {
         let facts = serde_json::json!({ "is_active": true });
 
         let mut entity_states = EntityStateMap::new();
-        entity_states.insert(
-            ("Order".to_string(), "order-1".to_string()),
-            "pending".to_string(),
-        );
-        entity_states.insert(
-            ("Order".to_string(), "order-2".to_string()),
-            "pending".to_string(),
-        );
+        entity_states.insert(instance_key("Order", "order-1"), "pending".into());
+        entity_states.insert(instance_key("Order", "order-2"), "pending".into());
 
         let result = compute_action_space(&contract, &facts, &entity_states, "admin").unwrap();
 
@@ -874,4 +1243,68 @@ mod tests {
         assert!(bindings["Order"].contains("order-1"));
         assert!(bindings["Order"].contains("order-2"));
     }
+
+    #[test]
+    fn create_effect_available_without_instances() {
+        // An operation that creates an Order needs no existing Order.
+        let mut bundle = test_bundle();
+        bundle["constructs"][3]["effects"] =
+            serde_json::json!([{ "entity_id": "Order", "kind": "create_instance" }]);
+        let contract = make_contract(&bundle);
+        let facts = serde_json::json!({ "is_active": true });
+
+        let result =
+            compute_action_space(&contract, &facts, &EntityStateMap::new(), "admin").unwrap();
+
+        assert_eq!(result.actions.len(), 1, "action available");
+        assert!(result.actions[0].instance_bindings.is_empty());
+        let summary = &result.actions[0].affected_entities[0];
+        assert_eq!(summary.current_state, "(new)");
+        assert_eq!(summary.possible_transitions, vec!["pending"]);
+    }
+
+    #[test]
+    fn matrix_covers_every_persona_and_operation() {
+        let mut bundle = test_bundle();
+        let constructs = bundle["constructs"].as_array_mut().unwrap();
+        for (line, id) in [(20, "admin"), (21, "guest")] {
+            constructs.push(serde_json::json!({
+                "id": id,
+                "kind": "Persona",
+                "provenance": { "file": "test.tenor", "line": line },
+                "tenor": "1.0"
+            }));
+        }
+        let contract = make_contract(&bundle);
+        let facts = serde_json::json!({ "is_active": true });
+        let entity_states = crate::operation::single_instance(
+            [("Order".to_string(), "pending".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        let matrix = compute_action_space_matrix(&contract, &facts, &entity_states).unwrap();
+
+        assert_eq!(matrix.personas, ["admin", "guest"]);
+        assert_eq!(matrix.operations, ["approve_order"]);
+        let admin = &matrix.matrix["admin"]["approve_order"];
+        assert!(admin.available);
+        assert!(admin.reason.is_none());
+        assert!(admin.instance_bindings["Order"].contains(DEFAULT_INSTANCE_ID));
+        let guest = &matrix.matrix["guest"]["approve_order"];
+        assert!(!guest.available);
+        assert!(matches!(
+            guest.reason,
+            Some(BlockedReason::PersonaNotAuthorized { .. })
+        ));
+
+        // Each persona's action space matches the per-persona API
+        for persona in ["admin", "guest"] {
+            let single = compute_action_space(&contract, &facts, &entity_states, persona).unwrap();
+            assert_eq!(
+                serde_json::to_value(&matrix.action_spaces[persona]).unwrap(),
+                serde_json::to_value(&single).unwrap()
+            );
+        }
+    }
 }
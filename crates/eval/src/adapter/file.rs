@@ -0,0 +1,373 @@
+//! File fact adapter — reads facts from local data extracts.
+//!
+//! Supports CSV and JSON Lines, and Parquet behind the `parquet` feature.
+//! The file is read once, on the first fetch, and every fact from the
+//! source is taken from the same row.
+
+use super::{AdapterConfig, AdapterError, FactAdapter, StructuredSourceRef};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// One row of a data file: column name → value.
+type Row = serde_json::Map<String, serde_json::Value>;
+
+/// Adapter that reads facts from a local CSV, JSONL, or Parquet file.
+///
+/// - `format` from source fields (`csv`, `jsonl`, or `parquet`), falling back
+///   to config, then to the file extension
+/// - `path` from config (required): the data file
+/// - `key_column` and `key` from config: the row whose `key_column` equals
+///   `key` is selected; both may be omitted when the file has a single row
+/// - The last path segment names the column: `accounts.balance` → `balance`
+///
+/// CSV cells are typed by content: `true`/`false` become booleans, integers
+/// become numbers, cells holding a JSON object or array are parsed, and
+/// everything else (including decimals) stays a string.
+pub struct FileAdapter {
+    source_id: String,
+    path: Option<PathBuf>,
+    format: Option<String>,
+    key_column: Option<String>,
+    key: Option<String>,
+    row: tokio::sync::OnceCell<Result<Arc<Row>, AdapterError>>,
+}
+
+impl FileAdapter {
+    /// Create a file adapter for the given source from config.
+    pub fn new(source_id: &str, config: &AdapterConfig) -> Self {
+        let get = |key: &str| config.get(source_id, key).map(|s| s.to_string());
+        FileAdapter {
+            source_id: source_id.to_string(),
+            path: get("path").map(PathBuf::from),
+            format: get("format"),
+            key_column: get("key_column"),
+            key: get("key"),
+            row: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// The column a source path reads: its last dot-separated segment.
+    pub fn path_to_column(path: &str) -> &str {
+        path.rsplit('.').next().unwrap_or(path)
+    }
+
+    fn config_error(&self, message: String) -> AdapterError {
+        AdapterError::ConfigError {
+            message: format!("file source '{}': {}", self.source_id, message),
+        }
+    }
+
+    /// Read the file and select the configured row.
+    async fn load_row(
+        &self,
+        source_fields: &BTreeMap<String, String>,
+    ) -> Result<Arc<Row>, AdapterError> {
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| self.config_error("missing config key 'path'".to_string()))?;
+        let format = source_fields
+            .get("format")
+            .cloned()
+            .or_else(|| self.format.clone())
+            .or_else(|| {
+                path.extension()
+                    .and_then(|e| e.to_str())
+                    .map(|e| e.to_lowercase())
+            })
+            .ok_or_else(|| {
+                self.config_error(format!(
+                    "cannot infer format of '{}'; set 'format'",
+                    path.display()
+                ))
+            })?;
+
+        let source_id = self.source_id.clone();
+        let rows = tokio::task::spawn_blocking(move || read_rows(&path, &format))
+            .await
+            .map_err(|e| AdapterError::FetchFailed {
+                source_id: source_id.clone(),
+                message: format!("task join error: {}", e),
+            })?
+            .map_err(|message| AdapterError::FetchFailed { source_id, message })?;
+
+        self.select_row(rows).map(Arc::new)
+    }
+
+    fn select_row(&self, rows: Vec<Row>) -> Result<Row, AdapterError> {
+        let fetch_failed = |message: String| AdapterError::FetchFailed {
+            source_id: self.source_id.clone(),
+            message,
+        };
+        match (&self.key_column, &self.key) {
+            (Some(column), Some(key)) => rows
+                .into_iter()
+                .find(|row| row.get(column).map(cell_text).as_deref() == Some(key.as_str()))
+                .ok_or_else(|| fetch_failed(format!("no row with {} = '{}'", column, key))),
+            (None, None) => {
+                let count = rows.len();
+                let mut rows = rows.into_iter();
+                match (rows.next(), rows.next()) {
+                    (Some(row), None) => Ok(row),
+                    _ => Err(self.config_error(format!(
+                        "file has {} rows; set 'key_column' and 'key' to select one",
+                        count
+                    ))),
+                }
+            }
+            _ => Err(self.config_error("set both 'key_column' and 'key'".to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl FactAdapter for FileAdapter {
+    async fn fetch(
+        &self,
+        fact_id: &str,
+        source: &StructuredSourceRef,
+        source_fields: &BTreeMap<String, String>,
+    ) -> Result<serde_json::Value, AdapterError> {
+        let row = self
+            .row
+            .get_or_init(|| self.load_row(source_fields))
+            .await
+            .clone()?;
+
+        let column = Self::path_to_column(&source.path);
+        row.get(column)
+            .cloned()
+            .ok_or_else(|| AdapterError::FetchFailed {
+                source_id: source.source_id.clone(),
+                message: format!("no column '{}' for fact '{}'", column, fact_id),
+            })
+    }
+
+    fn adapter_id(&self) -> &str {
+        "file"
+    }
+}
+
+/// Read every row of a data file in the given format.
+fn read_rows(path: &Path, format: &str) -> Result<Vec<Row>, String> {
+    match format {
+        "csv" => read_csv(path),
+        "jsonl" | "ndjson" => read_jsonl(path),
+        #[cfg(feature = "parquet")]
+        "parquet" => read_parquet(path),
+        #[cfg(not(feature = "parquet"))]
+        "parquet" => Err("Parquet support requires the 'parquet' feature".to_string()),
+        other => Err(format!(
+            "unsupported file format '{}' (expected csv, jsonl, or parquet)",
+            other
+        )),
+    }
+}
+
+fn read_csv(path: &Path) -> Result<Vec<Row>, String> {
+    let mut reader = csv::Reader::from_path(path)
+        .map_err(|e| format!("could not open '{}': {}", path.display(), e))?;
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("could not read CSV header: {}", e))?
+        .clone();
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("could not read CSV record: {}", e))?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(column, cell)| (column.to_string(), csv_cell(cell)))
+                .collect())
+        })
+        .collect()
+}
+
+fn read_jsonl(path: &Path) -> Result<Vec<Row>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read '{}': {}", path.display(), e))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| match serde_json::from_str(line) {
+            Ok(serde_json::Value::Object(row)) => Ok(row),
+            Ok(_) => Err(format!("line {}: expected a JSON object", i + 1)),
+            Err(e) => Err(format!("line {}: {}", i + 1, e)),
+        })
+        .collect()
+}
+
+#[cfg(feature = "parquet")]
+fn read_parquet(path: &Path) -> Result<Vec<Row>, String> {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("could not open '{}': {}", path.display(), e))?;
+    let reader = SerializedFileReader::new(file).map_err(|e| e.to_string())?;
+    let rows = reader.get_row_iter(None).map_err(|e| e.to_string())?;
+    rows.map(|row| {
+        let row = row.map_err(|e| e.to_string())?;
+        Ok(row
+            .get_column_iter()
+            .map(|(column, field)| (column.clone(), field.to_json_value()))
+            .collect())
+    })
+    .collect()
+}
+
+/// Type a CSV cell by its content.
+fn csv_cell(cell: &str) -> serde_json::Value {
+    match cell {
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        _ => {
+            if let Ok(n) = cell.parse::<i64>() {
+                return serde_json::Value::from(n);
+            }
+            if cell.starts_with('{') || cell.starts_with('[') {
+                if let Ok(value) = serde_json::from_str(cell) {
+                    return value;
+                }
+            }
+            serde_json::Value::String(cell.to_string())
+        }
+    }
+}
+
+/// A cell's text, for comparison against the configured key.
+fn cell_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// ──────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(source_id: &str, entries: &[(&str, &str)]) -> FileAdapter {
+        let mut config = AdapterConfig::default();
+        config.source_configs.insert(
+            source_id.to_string(),
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        FileAdapter::new(source_id, &config)
+    }
+
+    fn source(path: &str) -> StructuredSourceRef {
+        StructuredSourceRef {
+            source_id: "extract".to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    fn write_temp(name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tenor-file-adapter-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn csv_selects_row_by_key_column() {
+        let path = write_temp(
+            "accounts.csv",
+            "account_id,balance,active,rate\nA1,100,true,0.25\nA2,250,false,0.5\n",
+        );
+        let adapter = adapter(
+            "extract",
+            &[
+                ("path", path.to_str().unwrap()),
+                ("key_column", "account_id"),
+                ("key", "A2"),
+            ],
+        );
+        let fields = BTreeMap::new();
+        let balance = adapter
+            .fetch("balance", &source("accounts.balance"), &fields)
+            .await;
+        assert_eq!(balance.unwrap(), serde_json::json!(250));
+        let active = adapter
+            .fetch("active", &source("accounts.active"), &fields)
+            .await;
+        assert_eq!(active.unwrap(), serde_json::json!(false));
+        let rate = adapter.fetch("rate", &source("rate"), &fields).await;
+        assert_eq!(rate.unwrap(), serde_json::json!("0.5"));
+    }
+
+    #[tokio::test]
+    async fn jsonl_single_row_needs_no_key() {
+        let path = write_temp(
+            "single.jsonl",
+            "{\"limit\": {\"amount\": \"500.00\", \"currency\": \"USD\"}}\n",
+        );
+        let adapter = adapter("extract", &[("path", path.to_str().unwrap())]);
+        let result = adapter
+            .fetch("limit", &source("limits.limit"), &BTreeMap::new())
+            .await;
+        assert_eq!(
+            result.unwrap(),
+            serde_json::json!({"amount": "500.00", "currency": "USD"})
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_row_and_column_are_fetch_errors() {
+        let path = write_temp("rows.csv", "id,value\n1,10\n2,20\n");
+        let fields = BTreeMap::new();
+
+        let unknown_key = adapter(
+            "extract",
+            &[
+                ("path", path.to_str().unwrap()),
+                ("key_column", "id"),
+                ("key", "3"),
+            ],
+        );
+        let result = unknown_key.fetch("value", &source("value"), &fields).await;
+        assert!(matches!(result, Err(AdapterError::FetchFailed { .. })));
+
+        let known_key = adapter(
+            "extract",
+            &[
+                ("path", path.to_str().unwrap()),
+                ("key_column", "id"),
+                ("key", "1"),
+            ],
+        );
+        let result = known_key.fetch("other", &source("other"), &fields).await;
+        assert!(matches!(result, Err(AdapterError::FetchFailed { .. })));
+    }
+
+    #[tokio::test]
+    async fn several_rows_without_key_is_config_error() {
+        let path = write_temp("many.csv", "id,value\n1,10\n2,20\n");
+        let adapter = adapter("extract", &[("path", path.to_str().unwrap())]);
+        let result = adapter
+            .fetch("value", &source("value"), &BTreeMap::new())
+            .await;
+        assert!(matches!(result, Err(AdapterError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn csv_cells_are_typed_by_content() {
+        assert_eq!(csv_cell("true"), serde_json::json!(true));
+        assert_eq!(csv_cell("-7"), serde_json::json!(-7));
+        assert_eq!(csv_cell("12.50"), serde_json::json!("12.50"));
+        assert_eq!(csv_cell("[1, 2]"), serde_json::json!([1, 2]));
+        assert_eq!(csv_cell("plain"), serde_json::json!("plain"));
+    }
+}
@@ -9,13 +9,18 @@
 //! fact to the appropriate adapter, falling back to directly-provided facts.
 
 pub mod database;
+pub mod file;
 pub mod http;
 pub mod manual;
+pub mod secrets;
 pub mod static_adapter;
 
 use async_trait::async_trait;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::sync::Arc;
+
+use crate::clock::{Clock, SystemClock};
 
 // ──────────────────────────────────────────────
 // StructuredSourceRef
@@ -108,6 +113,8 @@ pub struct EnrichedFactProvenance {
     pub assertion_source: String,
     pub adapter_id: String,
     pub fetch_timestamp: String,
+    /// Wall-clock time the adapter took to return the value.
+    pub fetch_latency_ms: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_response: Option<serde_json::Value>,
 }
@@ -122,6 +129,11 @@ pub struct EnrichedFactProvenance {
 /// Two levels:
 /// - `source_configs`: per-source config keyed by source_id, then by key
 /// - `global`: global config (fallback for any source)
+///
+/// Load from a `tenor-adapters.toml` with [`from_toml`](Self::from_toml),
+/// overlay environment variables with [`from_env`](Self::from_env) and
+/// [`merge`](Self::merge), then replace secret references with
+/// [`resolve_secrets`](Self::resolve_secrets).
 #[derive(Debug, Clone, Default)]
 pub struct AdapterConfig {
     pub source_configs: HashMap<String, HashMap<String, String>>,
@@ -137,6 +149,137 @@ impl AdapterConfig {
             .or_else(|| self.global.get(key))
             .map(|s| s.as_str())
     }
+
+    /// Load config from a TOML file in the shape `tenor connect` generates:
+    /// one `[source.<source_id>]` table per source, with top-level keys as
+    /// global config. Non-string scalars are stored as their TOML text.
+    pub fn from_toml(path: &std::path::Path) -> Result<Self, AdapterError> {
+        let content = std::fs::read_to_string(path).map_err(|e| AdapterError::ConfigError {
+            message: format!("could not read '{}': {}", path.display(), e),
+        })?;
+        Self::parse_toml(&content).map_err(|message| AdapterError::ConfigError {
+            message: format!("{}: {}", path.display(), message),
+        })
+    }
+
+    /// Parse config from TOML text. See [`from_toml`](Self::from_toml).
+    pub fn parse_toml(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content
+            .parse()
+            .map_err(|e: toml::de::Error| e.to_string())?;
+        let mut config = AdapterConfig::default();
+        for (key, value) in table {
+            if key != "source" {
+                let scalar = toml_scalar(&value).ok_or_else(|| {
+                    format!("global key '{}' must be a string, number, or boolean", key)
+                })?;
+                config.global.insert(key, scalar);
+                continue;
+            }
+            let toml::Value::Table(sources) = value else {
+                return Err("'source' must be a table of [source.<id>] tables".to_string());
+            };
+            for (source_id, entries) in sources {
+                let toml::Value::Table(entries) = entries else {
+                    return Err(format!("[source.{}] must be a table", source_id));
+                };
+                let mut values = HashMap::new();
+                for (k, v) in entries {
+                    let scalar = toml_scalar(&v).ok_or_else(|| {
+                        format!(
+                            "source.{}.{} must be a string, number, or boolean",
+                            source_id, k
+                        )
+                    })?;
+                    values.insert(k, scalar);
+                }
+                config.source_configs.insert(source_id, values);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Load config from environment variables starting with `{prefix}_`.
+    ///
+    /// `{prefix}_{SOURCE_ID}__{KEY}` sets a per-source key and
+    /// `{prefix}_{KEY}` a global one; names are lowercased. With prefix
+    /// `TENOR_ADAPTER`, `TENOR_ADAPTER_ORDER_SERVICE__AUTH_TOKEN` sets
+    /// `auth_token` for source `order_service`.
+    pub fn from_env(prefix: &str) -> Self {
+        Self::from_vars(prefix, std::env::vars())
+    }
+
+    fn from_vars(prefix: &str, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let prefix = format!("{}_", prefix);
+        let mut config = AdapterConfig::default();
+        for (name, value) in vars {
+            let Some(rest) = name.strip_prefix(&prefix) else {
+                continue;
+            };
+            match rest.split_once("__") {
+                Some((source_id, key)) if !source_id.is_empty() && !key.is_empty() => {
+                    config
+                        .source_configs
+                        .entry(source_id.to_lowercase())
+                        .or_default()
+                        .insert(key.to_lowercase(), value);
+                }
+                Some(_) => continue,
+                None if !rest.is_empty() => {
+                    config.global.insert(rest.to_lowercase(), value);
+                }
+                None => continue,
+            }
+        }
+        config
+    }
+
+    /// Overlay `other` on this config; its values win.
+    pub fn merge(mut self, other: AdapterConfig) -> Self {
+        for (source_id, values) in other.source_configs {
+            self.source_configs
+                .entry(source_id)
+                .or_default()
+                .extend(values);
+        }
+        self.global.extend(other.global);
+        self
+    }
+
+    /// Replace every value of the form `<scheme>://<reference>` whose scheme
+    /// has a resolver with the secret it resolves to. Values with other
+    /// schemes (such as `https://` base URLs) are left alone.
+    pub async fn resolve_secrets(
+        &mut self,
+        resolvers: &[&dyn secrets::SecretResolver],
+    ) -> Result<(), AdapterError> {
+        let values = self
+            .source_configs
+            .values_mut()
+            .flat_map(|m| m.values_mut())
+            .chain(self.global.values_mut());
+        for value in values {
+            let Some((scheme, reference)) = value.split_once("://") else {
+                continue;
+            };
+            if let Some(resolver) = resolvers.iter().find(|r| r.scheme() == scheme) {
+                *value = resolver.resolve(reference).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A TOML scalar as config text; `None` for tables and arrays.
+fn toml_scalar(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(_)
+        | toml::Value::Float(_)
+        | toml::Value::Boolean(_)
+        | toml::Value::Datetime(_) => Some(value.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
 }
 
 // ──────────────────────────────────────────────
@@ -175,6 +318,8 @@ pub trait FactAdapter: Send + Sync {
 pub struct AdapterRegistry {
     adapters: HashMap<String, Box<dyn FactAdapter>>,
     source_fields: HashMap<String, BTreeMap<String, String>>,
+    /// Source of `fetch_timestamp` in provenance.
+    clock: Arc<dyn Clock>,
 }
 
 impl AdapterRegistry {
@@ -184,6 +329,7 @@ impl AdapterRegistry {
     /// - `"http"` → [`http::HttpAdapter`]
     /// - `"database"` → [`database::DatabaseAdapter`] (stub)
     /// - `"static"` → [`static_adapter::StaticAdapter`]
+    /// - `"file"` → [`file::FileAdapter`]
     /// - `"manual"` → [`manual::ManualAdapter`]
     /// - other → skipped (not configured)
     pub fn from_sources(
@@ -200,6 +346,7 @@ impl AdapterRegistry {
                 "http" => Box::new(http::HttpAdapter::new(&source.id, config)),
                 "database" => Box::new(database::DatabaseAdapter::new_stub(&source.id)),
                 "static" => Box::new(static_adapter::StaticAdapter::new(&source.id, config)),
+                "file" => Box::new(file::FileAdapter::new(&source.id, config)),
                 "manual" => Box::new(manual::ManualAdapter::new()),
                 _ => continue,
             };
@@ -209,6 +356,7 @@ impl AdapterRegistry {
         AdapterRegistry {
             adapters,
             source_fields: source_fields_map,
+            clock: Arc::new(SystemClock),
         }
     }
 
@@ -217,9 +365,16 @@ impl AdapterRegistry {
         AdapterRegistry {
             adapters: HashMap::new(),
             source_fields: HashMap::new(),
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Stamp fetches with `clock` instead of the system time.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Register a custom adapter for a source ID.
     pub fn register(
         &mut self,
@@ -250,10 +405,13 @@ impl AdapterRegistry {
             .cloned()
             .unwrap_or_default();
 
+        let started = std::time::Instant::now();
         let value = adapter.fetch(fact_id, source, &fields).await?;
+        let fetch_latency_ms = started.elapsed().as_millis() as u64;
 
-        let now = time::OffsetDateTime::now_utc();
-        let timestamp = now
+        let timestamp = self
+            .clock
+            .now()
             .format(&time::format_description::well_known::Rfc3339)
             .unwrap_or_else(|_| "unknown".to_string());
 
@@ -265,6 +423,7 @@ impl AdapterRegistry {
             assertion_source: "external".to_string(),
             adapter_id: adapter.adapter_id().to_string(),
             fetch_timestamp: timestamp,
+            fetch_latency_ms,
             source_response: None,
         };
 
@@ -276,16 +435,23 @@ impl AdapterRegistry {
 // AdapterFactProvider
 // ──────────────────────────────────────────────
 
+/// Default cap on concurrent adapter fetches in [`AdapterFactProvider`].
+pub const DEFAULT_MAX_CONCURRENT_FETCHES: usize = 8;
+
 /// Bridges [`AdapterRegistry`] to [`FactProvider`](crate::FactProvider).
 ///
 /// Direct-provided facts (in `direct_facts`) take priority.
-/// Structured-source facts are fetched via adapters in the registry.
+/// Structured-source facts are fetched via adapters in the registry,
+/// at most `max_concurrent_fetches` at a time, each bounded by
+/// `fetch_timeout` when set. Provenance is recorded in declaration order.
 /// Facts with no structured source and no direct value will be missing
 /// (the evaluator handles defaults).
 pub struct AdapterFactProvider {
     pub registry: AdapterRegistry,
     pub direct_facts: HashMap<String, serde_json::Value>,
     pub provenance_log: std::sync::Arc<std::sync::Mutex<Vec<EnrichedFactProvenance>>>,
+    pub max_concurrent_fetches: usize,
+    pub fetch_timeout: Option<std::time::Duration>,
 }
 
 impl AdapterFactProvider {
@@ -297,9 +463,23 @@ impl AdapterFactProvider {
             registry,
             direct_facts,
             provenance_log: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+            max_concurrent_fetches: DEFAULT_MAX_CONCURRENT_FETCHES,
+            fetch_timeout: None,
         }
     }
 
+    /// Cap the number of adapter fetches in flight at once (minimum 1).
+    pub fn with_max_concurrent_fetches(mut self, max: usize) -> Self {
+        self.max_concurrent_fetches = max.max(1);
+        self
+    }
+
+    /// Fail any single fact fetch that takes longer than `timeout`.
+    pub fn with_fetch_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.fetch_timeout = Some(timeout);
+        self
+    }
+
     /// Returns collected provenance records from all adapter fetches.
     pub fn provenance(&self) -> Vec<EnrichedFactProvenance> {
         // Recover data even if mutex was poisoned by a panic in another thread
@@ -308,6 +488,30 @@ impl AdapterFactProvider {
             .unwrap_or_else(|e| e.into_inner())
             .clone()
     }
+
+    /// Fetch one fact, applying the per-fact timeout.
+    async fn fetch_one(
+        &self,
+        fact_id: &str,
+        source: &StructuredSourceRef,
+    ) -> Result<(serde_json::Value, EnrichedFactProvenance), AdapterError> {
+        let fetch = self.registry.fetch_fact(fact_id, source);
+        match self.fetch_timeout {
+            None => fetch.await,
+            Some(timeout) => tokio::time::timeout(timeout, fetch)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(AdapterError::FetchFailed {
+                        source_id: source.source_id.clone(),
+                        message: format!(
+                            "fact '{}' timed out after {}ms",
+                            fact_id,
+                            timeout.as_millis()
+                        ),
+                    })
+                }),
+        }
+    }
 }
 
 #[async_trait]
@@ -316,6 +520,8 @@ impl crate::FactProvider for AdapterFactProvider {
         &self,
         contract: &serde_json::Value,
     ) -> Result<HashMap<String, serde_json::Value>, crate::FactProviderError> {
+        use futures::stream::StreamExt;
+
         let mut result = self.direct_facts.clone();
 
         // Parse constructs from bundle to find facts with structured sources
@@ -325,6 +531,7 @@ impl crate::FactProvider for AdapterFactProvider {
             .cloned()
             .unwrap_or_default();
 
+        let mut pending: Vec<(&str, StructuredSourceRef)> = Vec::new();
         for construct in &constructs {
             let kind = construct.get("kind").and_then(|k| k.as_str());
             if kind != Some("Fact") {
@@ -342,28 +549,37 @@ impl crate::FactProvider for AdapterFactProvider {
             }
 
             // Check for structured source reference
-            let source_ref = construct
+            if let Some(sref) = construct
                 .get("source")
-                .and_then(StructuredSourceRef::from_json);
-
-            if let Some(ref sref) = source_ref {
-                match self.registry.fetch_fact(fact_id, sref).await {
-                    Ok((value, provenance)) => {
-                        result.insert(fact_id.to_string(), value);
-                        self.provenance_log
-                            .lock()
-                            .map_err(|e| {
-                                crate::FactProviderError::Provider(format!("lock poisoned: {e}"))
-                            })?
-                            .push(provenance);
-                    }
-                    Err(e) => {
-                        return Err(crate::FactProviderError::Provider(e.to_string()));
-                    }
-                }
+                .and_then(StructuredSourceRef::from_json)
+            {
+                pending.push((fact_id, sref));
             }
         }
 
+        // Facts are independent, so fetch them concurrently; `buffered`
+        // yields results in declaration order. The futures are built up front
+        // so the stream holds no closure borrowing `pending` across awaits.
+        let fetches: Vec<_> = pending
+            .iter()
+            .map(|(fact_id, sref)| self.fetch_one(fact_id, sref))
+            .collect();
+        let fetched: Vec<_> = futures::stream::iter(fetches)
+            .buffered(self.max_concurrent_fetches.max(1))
+            .collect()
+            .await;
+
+        let mut log = self
+            .provenance_log
+            .lock()
+            .map_err(|e| crate::FactProviderError::Provider(format!("lock poisoned: {e}")))?;
+        for ((fact_id, _), outcome) in pending.iter().zip(fetched) {
+            let (value, provenance) =
+                outcome.map_err(|e| crate::FactProviderError::Provider(e.to_string()))?;
+            result.insert(fact_id.to_string(), value);
+            log.push(provenance);
+        }
+
         Ok(result)
     }
 }
@@ -421,6 +637,83 @@ mod tests {
         assert_eq!(config.get("src1", "missing"), None);
     }
 
+    #[test]
+    fn adapter_config_from_toml() {
+        let config = AdapterConfig::parse_toml(
+            r#"
+timeout = 30
+
+[source.order_service]
+protocol = "http"
+base_url = "https://orders.example.com"
+verify_tls = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.get("order_service", "protocol"), Some("http"));
+        assert_eq!(config.get("order_service", "verify_tls"), Some("true"));
+        assert_eq!(config.get("order_service", "timeout"), Some("30"));
+
+        let err = AdapterConfig::parse_toml("[source.x]\nports = [1, 2]\n").unwrap_err();
+        assert!(err.contains("source.x.ports"), "{}", err);
+    }
+
+    #[test]
+    fn adapter_config_from_env_vars_and_merge() {
+        let vars = [
+            ("TENOR_ADAPTER_ORDER_SERVICE__AUTH_TOKEN", "tok"),
+            ("TENOR_ADAPTER_TIMEOUT", "10"),
+            ("OTHER_ORDER_SERVICE__AUTH_TOKEN", "ignored"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+        let env = AdapterConfig::from_vars("TENOR_ADAPTER", vars);
+        assert_eq!(env.get("order_service", "auth_token"), Some("tok"));
+        assert_eq!(env.get("anything", "timeout"), Some("10"));
+
+        let file = AdapterConfig::parse_toml(
+            "timeout = 30\n[source.order_service]\nbase_url = \"https://x\"\n",
+        )
+        .unwrap();
+        let merged = file.merge(env);
+        assert_eq!(merged.get("order_service", "base_url"), Some("https://x"));
+        assert_eq!(merged.get("order_service", "auth_token"), Some("tok"));
+        assert_eq!(merged.get("order_service", "timeout"), Some("10"));
+    }
+
+    struct FixedResolver;
+
+    #[async_trait]
+    impl secrets::SecretResolver for FixedResolver {
+        fn scheme(&self) -> &str {
+            "fixed"
+        }
+
+        async fn resolve(&self, reference: &str) -> Result<String, AdapterError> {
+            Ok(format!("resolved:{}", reference))
+        }
+    }
+
+    #[tokio::test]
+    async fn adapter_config_resolves_secret_references() {
+        let mut config = AdapterConfig::parse_toml(
+            r#"
+[source.order_service]
+base_url = "https://orders.example.com"
+auth_token = "fixed://orders/token"
+"#,
+        )
+        .unwrap();
+        config.resolve_secrets(&[&FixedResolver]).await.unwrap();
+        assert_eq!(
+            config.get("order_service", "auth_token"),
+            Some("resolved:orders/token")
+        );
+        assert_eq!(
+            config.get("order_service", "base_url"),
+            Some("https://orders.example.com")
+        );
+    }
+
     #[test]
     fn adapter_error_display() {
         let err = AdapterError::NotConfigured {
@@ -457,7 +750,8 @@ mod tests {
 
     #[tokio::test]
     async fn registry_with_static_adapter() {
-        let mut registry = AdapterRegistry::empty();
+        let clock = crate::clock::FixedClock::parse("2030-01-01T00:00:00Z").unwrap();
+        let mut registry = AdapterRegistry::empty().with_clock(Arc::new(clock));
         let adapter = static_adapter::StaticAdapter::with_values(
             "test_src",
             [("items.count".to_string(), serde_json::json!(42))]
@@ -476,6 +770,7 @@ mod tests {
         assert_eq!(provenance.source_id, "test_src");
         assert_eq!(provenance.assertion_source, "external");
         assert_eq!(provenance.adapter_id, "static");
+        assert_eq!(provenance.fetch_timestamp, "2030-01-01T00:00:00Z");
     }
 
     #[tokio::test]
@@ -544,4 +839,110 @@ mod tests {
         assert_eq!(provider.provenance().len(), 1);
         assert_eq!(provider.provenance()[0].adapter_id, "static");
     }
+
+    /// Adapter that sleeps before answering and records how many fetches
+    /// were in flight at once.
+    struct SlowAdapter {
+        delay: std::time::Duration,
+        in_flight: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        max_in_flight: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl FactAdapter for SlowAdapter {
+        async fn fetch(
+            &self,
+            _fact_id: &str,
+            source: &StructuredSourceRef,
+            _source_fields: &BTreeMap<String, String>,
+        ) -> Result<serde_json::Value, AdapterError> {
+            use std::sync::atomic::Ordering;
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(serde_json::json!(source.path.len() as i64))
+        }
+
+        fn adapter_id(&self) -> &str {
+            "slow"
+        }
+    }
+
+    fn slow_provider(
+        delay_ms: u64,
+    ) -> (
+        AdapterFactProvider,
+        std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    ) {
+        let max_in_flight = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut registry = AdapterRegistry::empty();
+        registry.register(
+            "svc".to_string(),
+            BTreeMap::new(),
+            Box::new(SlowAdapter {
+                delay: std::time::Duration::from_millis(delay_ms),
+                in_flight: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+                max_in_flight: max_in_flight.clone(),
+            }),
+        );
+        (
+            AdapterFactProvider::new(registry, HashMap::new()),
+            max_in_flight,
+        )
+    }
+
+    fn bundle_with_facts(count: usize) -> serde_json::Value {
+        let constructs: Vec<serde_json::Value> = (0..count)
+            .map(|i| {
+                serde_json::json!({
+                    "id": format!("f{}", i),
+                    "kind": "Fact",
+                    "tenor": "1.0",
+                    "provenance": {"file": "test.tenor", "line": i + 1},
+                    "source": {"source_id": "svc", "path": format!("p.{}", "x".repeat(i))},
+                    "type": {"base": "Int"}
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "id": "test",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": constructs
+        })
+    }
+
+    #[tokio::test]
+    async fn adapter_fact_provider_fetches_concurrently_up_to_cap() {
+        let (provider, max_in_flight) = slow_provider(20);
+        let provider = provider.with_max_concurrent_fetches(3);
+
+        let result = crate::FactProvider::provide(&provider, &bundle_with_facts(6))
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 6);
+        assert_eq!(result["f4"], serde_json::json!(6));
+
+        let max = max_in_flight.load(std::sync::atomic::Ordering::SeqCst);
+        assert!((2..=3).contains(&max), "max in flight: {}", max);
+
+        // Provenance stays in declaration order, with latency recorded
+        let provenance = provider.provenance();
+        let ids: Vec<&str> = provenance.iter().map(|p| p.fact_id.as_str()).collect();
+        assert_eq!(ids, ["f0", "f1", "f2", "f3", "f4", "f5"]);
+        assert!(provenance.iter().all(|p| p.fetch_latency_ms >= 20));
+    }
+
+    #[tokio::test]
+    async fn adapter_fact_provider_times_out_slow_fetch() {
+        let (provider, _) = slow_provider(1_000);
+        let provider = provider.with_fetch_timeout(std::time::Duration::from_millis(10));
+
+        let err = crate::FactProvider::provide(&provider, &bundle_with_facts(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out after 10ms"), "{}", err);
+    }
 }
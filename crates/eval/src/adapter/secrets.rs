@@ -0,0 +1,262 @@
+//! Secret resolution for [`AdapterConfig`](super::AdapterConfig) values.
+//!
+//! A config value of the form `<scheme>://<reference>` is a reference to a
+//! secret held elsewhere; [`AdapterConfig::resolve_secrets`](super::AdapterConfig::resolve_secrets)
+//! replaces it with the value returned by the [`SecretResolver`] for that
+//! scheme. Credentials stay out of `tenor-adapters.toml`:
+//!
+//! ```toml
+//! [source.order_service]
+//! protocol = "http"
+//! auth_token = "env://ORDER_SERVICE_TOKEN"
+//!
+//! [source.compliance_db]
+//! protocol = "database"
+//! connection_string = "vault://secret/data/tenor/compliance#dsn"
+//! ```
+//!
+//! Resolvers:
+//! - [`EnvSecretResolver`] (`env://VAR`) — always available
+//! - [`VaultSecretResolver`] (`vault://path#field`) — `vault` feature
+//! - [`AwsSecretsManagerResolver`] (`aws-sm://secret-id#field`) — `aws-secrets` feature
+
+use super::AdapterError;
+use async_trait::async_trait;
+
+/// Resolves secret references of one scheme to their values.
+#[async_trait]
+pub trait SecretResolver: Send + Sync {
+    /// The scheme this resolver handles, without `://` (e.g. `"vault"`).
+    fn scheme(&self) -> &str;
+
+    /// Resolve the part of a reference after `<scheme>://`.
+    async fn resolve(&self, reference: &str) -> Result<String, AdapterError>;
+}
+
+fn resolve_error(scheme: &str, reference: &str, message: impl std::fmt::Display) -> AdapterError {
+    AdapterError::ConfigError {
+        message: format!(
+            "could not resolve secret '{}://{}': {}",
+            scheme, reference, message
+        ),
+    }
+}
+
+/// Split `path#field` into the secret location and an optional JSON field.
+#[cfg_attr(not(any(feature = "vault", feature = "aws-secrets")), allow(dead_code))]
+fn split_field(reference: &str) -> (&str, Option<&str>) {
+    match reference.split_once('#') {
+        Some((path, field)) => (path, Some(field)),
+        None => (reference, None),
+    }
+}
+
+// ──────────────────────────────────────────────
+// Environment variables
+// ──────────────────────────────────────────────
+
+/// Resolves `env://VAR` to the value of environment variable `VAR`.
+pub struct EnvSecretResolver;
+
+#[async_trait]
+impl SecretResolver for EnvSecretResolver {
+    fn scheme(&self) -> &str {
+        "env"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, AdapterError> {
+        std::env::var(reference).map_err(|e| resolve_error("env", reference, e))
+    }
+}
+
+// ──────────────────────────────────────────────
+// HashiCorp Vault
+// ──────────────────────────────────────────────
+
+/// Resolves `vault://<path>#<field>` by reading a KV version 2 secret over
+/// Vault's HTTP API (`GET {addr}/v1/<path>`) and taking `<field>` from its
+/// data. Without `#<field>`, the secret must hold exactly one field.
+#[cfg(feature = "vault")]
+pub struct VaultSecretResolver {
+    addr: String,
+    token: String,
+}
+
+#[cfg(feature = "vault")]
+impl VaultSecretResolver {
+    /// Create a resolver for the Vault server at `addr`.
+    pub fn new(addr: &str, token: &str) -> Self {
+        VaultSecretResolver {
+            addr: addr.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    /// Create a resolver from the standard `VAULT_ADDR` and `VAULT_TOKEN`
+    /// environment variables.
+    pub fn from_env() -> Result<Self, AdapterError> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| AdapterError::ConfigError {
+                message: format!("{} is not set", name),
+            })
+        };
+        Ok(Self::new(&var("VAULT_ADDR")?, &var("VAULT_TOKEN")?))
+    }
+}
+
+#[cfg(feature = "vault")]
+#[async_trait]
+impl SecretResolver for VaultSecretResolver {
+    fn scheme(&self) -> &str {
+        "vault"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, AdapterError> {
+        let (path, field) = split_field(reference);
+        let url = format!("{}/v1/{}", self.addr, path.trim_start_matches('/'));
+        let token = self.token.clone();
+
+        let body = tokio::task::spawn_blocking(move || {
+            let agent = ureq::Agent::new_with_defaults();
+            let response = agent
+                .get(&url)
+                .header("X-Vault-Token", &token)
+                .call()
+                .map_err(|e| e.to_string())?;
+            response
+                .into_body()
+                .read_json::<serde_json::Value>()
+                .map_err(|e| format!("failed to parse response as JSON: {}", e))
+        })
+        .await
+        .map_err(|e| resolve_error("vault", reference, format!("task join error: {}", e)))?
+        .map_err(|e| resolve_error("vault", reference, e))?;
+
+        let data = body
+            .pointer("/data/data")
+            .and_then(|d| d.as_object())
+            .ok_or_else(|| resolve_error("vault", reference, "response has no data.data"))?;
+        select_field(data, field).map_err(|e| resolve_error("vault", reference, e))
+    }
+}
+
+// ──────────────────────────────────────────────
+// AWS Secrets Manager
+// ──────────────────────────────────────────────
+
+/// Resolves `aws-sm://<secret-id>#<field>` with AWS Secrets Manager.
+///
+/// Without `#<field>` the whole secret string is returned; with it, the
+/// secret string is parsed as a JSON object and `<field>` is taken.
+#[cfg(feature = "aws-secrets")]
+pub struct AwsSecretsManagerResolver {
+    client: aws_sdk_secretsmanager::Client,
+}
+
+#[cfg(feature = "aws-secrets")]
+impl AwsSecretsManagerResolver {
+    /// Create a resolver using an existing client.
+    pub fn new(client: aws_sdk_secretsmanager::Client) -> Self {
+        AwsSecretsManagerResolver { client }
+    }
+
+    /// Create a resolver from the default AWS credential and region chain.
+    pub async fn from_env() -> Self {
+        let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+        Self::new(aws_sdk_secretsmanager::Client::new(&config))
+    }
+}
+
+#[cfg(feature = "aws-secrets")]
+#[async_trait]
+impl SecretResolver for AwsSecretsManagerResolver {
+    fn scheme(&self) -> &str {
+        "aws-sm"
+    }
+
+    async fn resolve(&self, reference: &str) -> Result<String, AdapterError> {
+        let (secret_id, field) = split_field(reference);
+        let output = self
+            .client
+            .get_secret_value()
+            .secret_id(secret_id)
+            .send()
+            .await
+            .map_err(|e| resolve_error("aws-sm", reference, e))?;
+        let secret = output
+            .secret_string()
+            .ok_or_else(|| resolve_error("aws-sm", reference, "secret has no string value"))?;
+        let Some(field) = field else {
+            return Ok(secret.to_string());
+        };
+        let data: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(secret).map_err(|e| resolve_error("aws-sm", reference, e))?;
+        select_field(&data, Some(field)).map_err(|e| resolve_error("aws-sm", reference, e))
+    }
+}
+
+/// Take `field` from a secret's key/value data. Without a field, the data
+/// must hold exactly one entry.
+#[cfg_attr(not(any(feature = "vault", feature = "aws-secrets")), allow(dead_code))]
+fn select_field(
+    data: &serde_json::Map<String, serde_json::Value>,
+    field: Option<&str>,
+) -> Result<String, String> {
+    let value = match field {
+        Some(field) => data
+            .get(field)
+            .ok_or_else(|| format!("secret has no field '{}'", field))?,
+        None if data.len() == 1 => data.values().next().expect("one entry"),
+        None => {
+            return Err(format!(
+                "secret has {} fields; name one with '#<field>'",
+                data.len()
+            ))
+        }
+    };
+    Ok(match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+// ──────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_field_separates_json_field() {
+        assert_eq!(
+            split_field("secret/data/tenor#dsn"),
+            ("secret/data/tenor", Some("dsn"))
+        );
+        assert_eq!(split_field("prod/token"), ("prod/token", None));
+    }
+
+    #[test]
+    fn select_field_requires_field_for_multi_entry_secrets() {
+        let data: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(r#"{"user": "svc", "port": 5432}"#).unwrap();
+        assert_eq!(select_field(&data, Some("port")).unwrap(), "5432");
+        assert!(select_field(&data, None).is_err());
+        assert!(select_field(&data, Some("password")).is_err());
+    }
+
+    #[tokio::test]
+    async fn env_resolver_reads_variable() {
+        std::env::set_var("TENOR_TEST_SECRET_RESOLVER_TOKEN", "s3cret");
+        let value = EnvSecretResolver
+            .resolve("TENOR_TEST_SECRET_RESOLVER_TOKEN")
+            .await
+            .unwrap();
+        assert_eq!(value, "s3cret");
+        assert!(EnvSecretResolver
+            .resolve("TENOR_TEST_SECRET_RESOLVER_UNSET")
+            .await
+            .is_err());
+    }
+}
@@ -4,10 +4,23 @@
 //! For each declared fact:
 //! - If present in facts_json: parse and type-check against declared type
 //! - If missing with default: use default value
-//! - If missing without default: return MissingFact error
+//! - If missing without default: return MissingFact error (or, for
+//!   [`assemble_facts_partial`], report it as missing)
+//!
+//! [`validate_facts`] checks the same things without stopping at the first
+//! error, locating each one with a JSON pointer into the facts document.
+
+use rust_decimal::Decimal;
+use tenor_core::units::{unit_of, Unit};
 
+use crate::numeric;
 use crate::types::{parse_plain_value, Contract, EvalError, FactSet, TypeSpec, Value};
 
+/// Precision of a Money type that declares none.
+const MONEY_DEFAULT_PRECISION: u32 = 10;
+/// Scale of a Money type that declares none.
+const MONEY_DEFAULT_SCALE: u32 = 2;
+
 /// Assemble a FactSet from a contract and a facts JSON object.
 ///
 /// The facts_json is expected to be a JSON object mapping fact IDs to values.
@@ -16,13 +29,159 @@ pub fn assemble_facts(
     contract: &Contract,
     facts_json: &serde_json::Value,
 ) -> Result<FactSet, EvalError> {
+    let mut fact_set = FactSet::new();
+    if let Some(fact_id) = assemble_into(contract, facts_json, &mut fact_set, false)?
+        .into_iter()
+        .next()
+    {
+        return Err(EvalError::MissingFact { fact_id });
+    }
+    Ok(fact_set)
+}
+
+/// Assemble a FactSet, collecting missing facts instead of failing on them.
+///
+/// Returns the facts that were provided or defaulted, and the ids of the
+/// declared facts that were neither, in declaration order. Provided facts
+/// are still type-checked. Used by partial evaluation.
+pub fn assemble_facts_partial(
+    contract: &Contract,
+    facts_json: &serde_json::Value,
+) -> Result<(FactSet, Vec<String>), EvalError> {
+    let mut fact_set = FactSet::new();
+    let missing = assemble_into(contract, facts_json, &mut fact_set, true)?;
+    Ok((fact_set, missing))
+}
+
+/// One error found by [`validate_facts`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactError {
+    /// RFC 6901 JSON pointer to the offending value in the facts document
+    /// (`/fact_id`, `/items/2`, `/address/zip`); for a missing fact, where
+    /// it should have been.
+    pub pointer: String,
+    /// The declared fact the error belongs to; empty when the document
+    /// itself is not an object.
+    pub fact_id: String,
+    pub error: EvalError,
+}
+
+impl FactError {
+    /// `{"pointer", "fact_id", "code", "message"}`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "pointer": self.pointer,
+            "fact_id": self.fact_id,
+            "code": self.error.code(),
+            "message": self.error.to_string(),
+        })
+    }
+}
+
+/// Check a facts document against the contract, reporting every error.
+///
+/// Unlike [`assemble_facts`], which stops at the first missing or
+/// ill-typed fact, every declared fact is checked. A List or Record value
+/// that does not fit its type is reported at each failing element or field
+/// where one can be singled out. An empty result means `assemble_facts`
+/// would succeed.
+pub fn validate_facts(contract: &Contract, facts_json: &serde_json::Value) -> Vec<FactError> {
+    let Some(facts_obj) = facts_json.as_object() else {
+        return vec![FactError {
+            pointer: String::new(),
+            fact_id: String::new(),
+            error: EvalError::DeserializeError {
+                message: "facts must be a JSON object".to_string(),
+            },
+        }];
+    };
+
+    let mut errors = Vec::new();
+    for decl in &contract.facts {
+        let pointer = format!("/{}", escape_pointer_token(&decl.id));
+        match facts_obj.get(&decl.id) {
+            Some(fact_val) => {
+                locate_errors(&decl.id, fact_val, &decl.fact_type, pointer, &mut errors)
+            }
+            None if decl.default.is_none() => errors.push(FactError {
+                pointer,
+                fact_id: decl.id.clone(),
+                error: EvalError::MissingFact {
+                    fact_id: decl.id.clone(),
+                },
+            }),
+            None => {}
+        }
+    }
+    errors
+}
+
+/// Push the errors of `value` against `type_spec`, descending into List
+/// elements and Record fields so each failing one gets its own pointer.
+/// A value whose parts all check out on their own (a Record missing a
+/// field, a List over its bounds) is reported at `pointer` itself.
+fn locate_errors(
+    fact_id: &str,
+    value: &serde_json::Value,
+    type_spec: &TypeSpec,
+    pointer: String,
+    errors: &mut Vec<FactError>,
+) {
+    let Err(error) = parse_and_typecheck(fact_id, value, type_spec) else {
+        return;
+    };
+    let before = errors.len();
+    match (type_spec.base.as_str(), value) {
+        ("List", serde_json::Value::Array(items)) => {
+            if let Some(element_type) = &type_spec.element_type {
+                for (i, item) in items.iter().enumerate() {
+                    let item_pointer = format!("{}/{}", pointer, i);
+                    locate_errors(fact_id, item, element_type, item_pointer, errors);
+                }
+            }
+        }
+        ("Record", serde_json::Value::Object(obj)) => {
+            if let Some(fields) = &type_spec.fields {
+                for (name, field_type) in fields {
+                    if let Some(field_val) = obj.get(name) {
+                        let field_pointer = format!("{}/{}", pointer, escape_pointer_token(name));
+                        locate_errors(fact_id, field_val, field_type, field_pointer, errors);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    if errors.len() == before {
+        errors.push(FactError {
+            pointer,
+            fact_id: fact_id.to_string(),
+            error,
+        });
+    }
+}
+
+/// Escape a JSON pointer reference token (RFC 6901: `~` as `~0`, `/` as
+/// `~1`).
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Fill `fact_set` from `facts_json`, returning the missing facts. Unless
+/// `collect_missing` is set, stops at the first missing fact.
+fn assemble_into(
+    contract: &Contract,
+    facts_json: &serde_json::Value,
+    fact_set: &mut FactSet,
+    collect_missing: bool,
+) -> Result<Vec<String>, EvalError> {
     let facts_obj = facts_json
         .as_object()
         .ok_or_else(|| EvalError::DeserializeError {
             message: "facts must be a JSON object".to_string(),
         })?;
 
-    let mut fact_set = FactSet::new();
+    let mut missing = Vec::new();
 
     for decl in &contract.facts {
         if let Some(fact_val) = facts_obj.get(&decl.id) {
@@ -33,14 +192,15 @@ pub fn assemble_facts(
             // Fact not provided but has default
             fact_set.insert(decl.id.clone(), default.clone());
         } else {
-            // Fact not provided and no default -- error
-            return Err(EvalError::MissingFact {
-                fact_id: decl.id.clone(),
-            });
+            // Fact not provided and no default
+            missing.push(decl.id.clone());
+            if !collect_missing {
+                break;
+            }
         }
     }
 
-    Ok(fact_set)
+    Ok(missing)
 }
 
 /// Parse a JSON value and type-check it against the declared type.
@@ -57,8 +217,74 @@ fn parse_and_typecheck(
 
     // Additional type-specific validation
     validate_value(fact_id, &parsed, type_spec)?;
+    check_declared_unit(fact_id, value, type_spec)?;
 
-    Ok(parsed)
+    fit_to_declared_scale(fact_id, parsed, type_spec)
+}
+
+/// A Decimal fact may be supplied as `{"value": "...", "unit": "..."}`;
+/// the unit must then be the declared unit of measure.
+fn check_declared_unit(
+    fact_id: &str,
+    value: &serde_json::Value,
+    type_spec: &TypeSpec,
+) -> Result<(), EvalError> {
+    let Some(given) = value.get("unit").and_then(|u| u.as_str()) else {
+        return Ok(());
+    };
+    if type_spec.base != "Decimal" {
+        return Ok(());
+    }
+    let declared = unit_of(type_spec.unit.as_deref());
+    if Unit::parse(given).ok().as_ref() != Some(&declared) {
+        return Err(EvalError::TypeMismatch {
+            fact_id: fact_id.to_string(),
+            expected: format!("Decimal(unit: {})", declared),
+            got: format!("Decimal(unit: {})", given),
+        });
+    }
+    Ok(())
+}
+
+/// Fit Decimal and Money values to their declared precision and scale.
+///
+/// Money types that declare no precision or scale default to (10, 2).
+/// Values that cannot be fitted without losing digits are rejected; see
+/// [`numeric::fit_to_scale`].
+fn fit_to_declared_scale(
+    fact_id: &str,
+    value: Value,
+    type_spec: &TypeSpec,
+) -> Result<Value, EvalError> {
+    let (precision, scale) = match type_spec.base.as_str() {
+        "Decimal" => match (type_spec.precision, type_spec.scale) {
+            (Some(p), Some(s)) => (p, s),
+            _ => return Ok(value),
+        },
+        "Money" => (
+            type_spec.precision.unwrap_or(MONEY_DEFAULT_PRECISION),
+            type_spec.scale.unwrap_or(MONEY_DEFAULT_SCALE),
+        ),
+        _ => return Ok(value),
+    };
+    let fit = |d: Decimal| {
+        numeric::fit_to_scale(d, precision, scale, type_spec.rounding.as_deref()).ok_or_else(|| {
+            EvalError::PrecisionLoss {
+                fact_id: fact_id.to_string(),
+                value: d.to_string(),
+                precision,
+                scale,
+            }
+        })
+    };
+    match value {
+        Value::Decimal(d) => Ok(Value::Decimal(fit(d)?)),
+        Value::Money { amount, currency } => Ok(Value::Money {
+            amount: fit(amount)?,
+            currency,
+        }),
+        other => Ok(other),
+    }
 }
 
 /// Valid Duration unit values per the spec DurationUnit enum.
@@ -135,37 +361,31 @@ fn validate_value(fact_id: &str, value: &Value, type_spec: &TypeSpec) -> Result<
                 }
             }
         }
-        Value::Date(s) => {
-            if !validate_date_format(s) {
-                return Err(EvalError::TypeError {
-                    message: format!(
-                        "fact '{}': invalid Date format '{}', expected ISO 8601 (YYYY-MM-DD)",
-                        fact_id, s
-                    ),
-                });
-            }
+        Value::Date(s) if !validate_date_format(s) => {
+            return Err(EvalError::TypeError {
+                message: format!(
+                    "fact '{}': invalid Date format '{}', expected ISO 8601 (YYYY-MM-DD)",
+                    fact_id, s
+                ),
+            });
         }
-        Value::DateTime(s) => {
-            if !validate_datetime_format(s) {
-                return Err(EvalError::TypeError {
-                    message: format!(
-                        "fact '{}': invalid DateTime format '{}', expected ISO 8601 (YYYY-MM-DDT...)",
-                        fact_id, s
-                    ),
-                });
-            }
+        Value::DateTime(s) if !validate_datetime_format(s) => {
+            return Err(EvalError::TypeError {
+                message: format!(
+                    "fact '{}': invalid DateTime format '{}', expected ISO 8601 (YYYY-MM-DDT...)",
+                    fact_id, s
+                ),
+            });
         }
-        Value::Duration { unit, .. } => {
-            if !VALID_DURATION_UNITS.contains(&unit.as_str()) {
-                return Err(EvalError::TypeError {
-                    message: format!(
-                        "fact '{}': invalid Duration unit '{}', expected one of: {}",
-                        fact_id,
-                        unit,
-                        VALID_DURATION_UNITS.join(", ")
-                    ),
-                });
-            }
+        Value::Duration { unit, .. } if !VALID_DURATION_UNITS.contains(&unit.as_str()) => {
+            return Err(EvalError::TypeError {
+                message: format!(
+                    "fact '{}': invalid Duration unit '{}', expected one of: {}",
+                    fact_id,
+                    unit,
+                    VALID_DURATION_UNITS.join(", ")
+                ),
+            });
         }
         _ => {}
     }
@@ -203,6 +423,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -220,6 +441,7 @@ mod tests {
             base: "Int".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: Some(min),
             max: Some(max),
@@ -237,6 +459,7 @@ mod tests {
             base: "Money".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: Some(currency.to_string()),
             min: None,
             max: None,
@@ -254,6 +477,7 @@ mod tests {
             base: "Enum".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -271,6 +495,7 @@ mod tests {
             base: "Text".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -324,6 +549,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn assemble_partial_collects_missing_facts() {
+        let decl = |id: &str, default: Option<Value>| FactDecl {
+            id: id.to_string(),
+            fact_type: bool_type(),
+            default,
+        };
+        let contract = make_contract(vec![
+            decl("a", None),
+            decl("b", Some(Value::Bool(true))),
+            decl("c", None),
+            decl("d", None),
+        ]);
+        let facts = serde_json::json!({ "c": false });
+        let (fs, missing) = assemble_facts_partial(&contract, &facts).unwrap();
+        assert_eq!(missing, ["a", "d"]);
+        assert_eq!(fs.get("b"), Some(&Value::Bool(true)));
+        assert_eq!(fs.get("c"), Some(&Value::Bool(false)));
+    }
+
     #[test]
     fn assemble_type_mismatch_error() {
         let contract = make_contract(vec![FactDecl {
@@ -386,6 +631,7 @@ mod tests {
             base: "Decimal".to_string(),
             precision: Some(precision),
             scale: Some(scale),
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -457,6 +703,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn assemble_decimal_excess_scale_rejected() {
+        let contract = make_contract(vec![FactDecl {
+            id: "amount".to_string(),
+            fact_type: decimal_type(10, 2),
+            default: None,
+        }]);
+        let facts = serde_json::json!({ "amount": "200.755" });
+        assert_eq!(
+            assemble_facts(&contract, &facts).unwrap_err(),
+            EvalError::PrecisionLoss {
+                fact_id: "amount".to_string(),
+                value: "200.755".to_string(),
+                precision: 10,
+                scale: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn assemble_decimal_excess_scale_rounded_with_declared_mode() {
+        let mut fact_type = decimal_type(10, 2);
+        fact_type.rounding = Some("half_up".to_string());
+        let contract = make_contract(vec![FactDecl {
+            id: "amount".to_string(),
+            fact_type,
+            default: None,
+        }]);
+        let facts = serde_json::json!({ "amount": "200.745" });
+        let fs = assemble_facts(&contract, &facts).unwrap();
+        assert_eq!(
+            fs.get("amount"),
+            Some(&Value::Decimal(
+                rust_decimal::Decimal::from_str("200.75").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn assemble_decimal_checks_supplied_unit() {
+        let mut fact_type = decimal_type(10, 3);
+        fact_type.unit = Some("kg".to_string());
+        let contract = make_contract(vec![FactDecl {
+            id: "weight".to_string(),
+            fact_type,
+            default: None,
+        }]);
+        let facts = serde_json::json!({ "weight": { "value": "12.500", "unit": "kg" } });
+        assert!(assemble_facts(&contract, &facts).is_ok());
+
+        let facts = serde_json::json!({ "weight": { "value": "12.500", "unit": "lb" } });
+        assert_eq!(
+            assemble_facts(&contract, &facts).unwrap_err(),
+            EvalError::TypeMismatch {
+                fact_id: "weight".to_string(),
+                expected: "Decimal(unit: kg)".to_string(),
+                got: "Decimal(unit: lb)".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn assemble_money_held_to_default_precision_and_scale() {
+        let contract = make_contract(vec![FactDecl {
+            id: "amount".to_string(),
+            fact_type: money_type("USD"),
+            default: None,
+        }]);
+        for amount in ["5000.001", "123456789.00"] {
+            let facts = serde_json::json!({ "amount": { "amount": amount, "currency": "USD" } });
+            assert!(matches!(
+                assemble_facts(&contract, &facts),
+                Err(EvalError::PrecisionLoss {
+                    precision: 10,
+                    scale: 2,
+                    ..
+                })
+            ));
+        }
+
+        let mut wide = money_type("USD");
+        wide.scale = Some(4);
+        let contract = make_contract(vec![FactDecl {
+            id: "rate".to_string(),
+            fact_type: wide,
+            default: None,
+        }]);
+        let facts = serde_json::json!({ "rate": { "amount": "1.0850", "currency": "USD" } });
+        assert!(assemble_facts(&contract, &facts).is_ok());
+    }
+
     #[test]
     fn assemble_text_too_long() {
         let contract = make_contract(vec![FactDecl {
@@ -489,6 +826,7 @@ mod tests {
             base: "Record".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -507,6 +845,7 @@ mod tests {
             base: "List".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: Some(10),
@@ -606,6 +945,7 @@ mod tests {
             base: "Date".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -635,6 +975,7 @@ mod tests {
             base: "Date".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -664,6 +1005,7 @@ mod tests {
             base: "Duration".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -715,4 +1057,103 @@ mod tests {
             other => panic!("expected TypeError, got {:?}", other),
         }
     }
+
+    #[test]
+    fn validate_facts_reports_every_error_with_pointers() {
+        let contract = make_contract(vec![
+            FactDecl {
+                id: "flag".to_string(),
+                fact_type: bool_type(),
+                default: None,
+            },
+            FactDecl {
+                id: "count".to_string(),
+                fact_type: int_type(0, 10),
+                default: None,
+            },
+            FactDecl {
+                id: "a/b".to_string(),
+                fact_type: bool_type(),
+                default: None,
+            },
+            FactDecl {
+                id: "optional".to_string(),
+                fact_type: bool_type(),
+                default: Some(Value::Bool(false)),
+            },
+        ]);
+        let facts = serde_json::json!({ "flag": "yes", "count": 11 });
+        let errors = validate_facts(&contract, &facts);
+        let found: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|e| (e.pointer.as_str(), e.error.code()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("/flag", "TEN-E-0101"),
+                ("/count", "TEN-E-0101"),
+                ("/a~1b", "TEN-E-0100"),
+            ]
+        );
+        assert_eq!(errors[2].fact_id, "a/b");
+        assert_eq!(errors[0].to_json()["pointer"], "/flag");
+    }
+
+    #[test]
+    fn validate_facts_points_into_lists_and_records() {
+        let list = TypeSpec {
+            base: "List".to_string(),
+            element_type: Some(Box::new(int_type(0, 100))),
+            ..bool_type()
+        };
+        let record = TypeSpec {
+            base: "Record".to_string(),
+            fields: Some(
+                [
+                    ("flag".to_string(), bool_type()),
+                    ("count".to_string(), int_type(0, 5)),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            ..bool_type()
+        };
+        let contract = make_contract(vec![
+            FactDecl {
+                id: "items".to_string(),
+                fact_type: list,
+                default: None,
+            },
+            FactDecl {
+                id: "rec".to_string(),
+                fact_type: record,
+                default: None,
+            },
+        ]);
+        let facts = serde_json::json!({
+            "items": [1, "two", 3, 400],
+            "rec": { "flag": true, "count": "many" },
+        });
+        let pointers: Vec<String> = validate_facts(&contract, &facts)
+            .into_iter()
+            .map(|e| e.pointer)
+            .collect();
+        assert_eq!(pointers, vec!["/items/1", "/items/3", "/rec/count"]);
+
+        let valid = serde_json::json!({
+            "items": [1, 2],
+            "rec": { "flag": false, "count": 1 },
+        });
+        assert!(validate_facts(&contract, &valid).is_empty());
+        assert!(assemble_facts(&contract, &valid).is_ok());
+    }
+
+    #[test]
+    fn validate_facts_rejects_non_object_document() {
+        let contract = make_contract(vec![]);
+        let errors = validate_facts(&contract, &serde_json::json!([1]));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer, "");
+    }
 }
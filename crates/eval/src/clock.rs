@@ -0,0 +1,88 @@
+//! Time source for time-dependent evaluation.
+//!
+//! Step deadlines, handoff timeouts, and adapter fetch timestamps read the
+//! current instant through a [`Clock`] rather than the system time, so a
+//! simulation can be pinned to a fixed instant and replayed exactly.
+//! [`with_clock`] installs a clock on the current thread for the duration
+//! of a closure; outside of it evaluation uses [`SystemClock`].
+//! `AdapterRegistry`, whose fetches run on async tasks, holds its own clock.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use time::OffsetDateTime;
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// A source of the current instant.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// The system's UTC wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// A clock stopped at one instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub OffsetDateTime);
+
+impl FixedClock {
+    /// Parse an RFC 3339 timestamp. Timestamps without an offset are taken
+    /// to be UTC, as DateTime facts are.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        crate::flow::timeout::parse_datetime(s)
+            .map(FixedClock)
+            .ok_or_else(|| format!("invalid timestamp '{}': expected RFC 3339", s))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        self.0
+    }
+}
+
+/// Run `f` with `clock` as the time source on this thread.
+pub fn with_clock<T>(clock: Arc<dyn Clock>, f: impl FnOnce() -> T) -> T {
+    let previous = ACTIVE.with(|a| a.borrow_mut().replace(clock));
+    let value = f();
+    ACTIVE.with(|a| *a.borrow_mut() = previous);
+    value
+}
+
+/// The current instant according to this thread's clock.
+pub fn now() -> OffsetDateTime {
+    ACTIVE
+        .with(|a| a.borrow().as_ref().map(|clock| clock.now()))
+        .unwrap_or_else(OffsetDateTime::now_utc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_clock_pins_now_and_restores() {
+        let fixed = FixedClock::parse("2030-01-01T00:00:00Z").unwrap();
+        let pinned = with_clock(Arc::new(fixed), || {
+            let inner = with_clock(
+                Arc::new(FixedClock::parse("2031-06-01T12:00:00").unwrap()),
+                now,
+            );
+            assert_eq!(inner.year(), 2031);
+            now()
+        });
+        assert_eq!(pinned, fixed.0);
+        assert_ne!(now(), fixed.0, "system clock restored");
+        assert!(FixedClock::parse("tomorrow").is_err());
+    }
+}
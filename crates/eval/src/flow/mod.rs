@@ -12,14 +12,24 @@
 use std::collections::{BTreeMap, HashMap};
 
 use crate::operation::{
-    execute_operation, resolve_instance_id, EffectRecord, EntityStateMap, InstanceBindingMap,
+    execute_operation, resolve_instance_id, EffectRecord, EntityAttributeMap, EntityStateMap,
+    EntityVersions, InstanceBindingMap,
 };
 use crate::predicate::{eval_pred, EvalContext};
 use crate::provenance::ProvenanceCollector;
+use crate::symbol::Symbol;
 use crate::types::{
-    Contract, EvalError, FactSet, FailureHandler, Flow, FlowStep, Operation, StepTarget, VerdictSet,
+    Contract, EffectKind, EvalError, FactSet, FailureHandler, Flow, FlowStep, Operation,
+    StepTarget, VerdictSet,
 };
 
+mod resume;
+mod snapshot;
+pub(crate) mod timeout;
+
+pub use resume::{expire_flow, resume_flow, start_flow, FlowContinuation, FlowRun, FlowSuspended};
+pub use snapshot::{SnapshotEnvelope, SNAPSHOT_FORMAT, SNAPSHOT_FORMAT_VERSION};
+
 // ──────────────────────────────────────────────
 // Frozen verdict snapshot
 // ──────────────────────────────────────────────
@@ -29,11 +39,26 @@ use crate::types::{
 /// Per spec Section 11.4, this snapshot is NEVER recomputed during flow
 /// execution. Entity state changes during the flow do NOT trigger verdict
 /// re-evaluation. Sub-flows inherit the parent flow's snapshot (spec E5).
+#[derive(Debug, Clone)]
 pub struct Snapshot {
     pub facts: FactSet,
     pub verdicts: VerdictSet,
 }
 
+/// Optional inputs to a flow run. `FlowOptions::default()` runs without
+/// version checks, with the default step limit and no correlation id.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlowOptions<'a> {
+    /// Versions the caller read entity instances at; every operation the
+    /// run executes checks them.
+    pub entity_versions: Option<&'a EntityVersions>,
+    /// Step limit for the run; 1000 when unset.
+    pub max_steps: Option<usize>,
+    /// Ties this run to the other flow runs of one business transaction;
+    /// recorded on the result and every step record.
+    pub correlation_id: Option<&'a str>,
+}
+
 // ──────────────────────────────────────────────
 // Flow execution result types
 // ──────────────────────────────────────────────
@@ -44,12 +69,15 @@ pub struct Snapshot {
 /// which specific entity instances were targeted at each step.
 #[derive(Debug, Clone)]
 pub struct StepRecord {
-    pub step_id: String,
+    pub step_id: Symbol,
     pub step_type: String,
     pub result: String,
     /// Maps entity_id -> instance_id for the instances targeted at this step.
     /// Empty for non-operation steps (branch, handoff, parallel).
     pub instance_bindings: std::collections::BTreeMap<String, String>,
+    /// The business transaction the flow run belongs to, if the caller
+    /// supplied one.
+    pub correlation_id: Option<String>,
 }
 
 /// Result of a successful flow execution.
@@ -62,6 +90,23 @@ pub struct FlowResult {
     /// Flow-level persona authorization is delegated to step-level Operation
     /// persona checks.
     pub initiating_persona: Option<String>,
+    /// Groups the flow runs of one business transaction. Also stamped on
+    /// every step record.
+    pub correlation_id: Option<String>,
+}
+
+impl FlowResult {
+    /// Stamp `correlation_id` on the result and its step records.
+    pub(crate) fn correlate(&mut self, correlation_id: Option<&str>) {
+        self.correlation_id = correlation_id.map(String::from);
+        correlate_steps(&mut self.steps_executed, correlation_id);
+    }
+}
+
+pub(crate) fn correlate_steps(steps: &mut [StepRecord], correlation_id: Option<&str>) {
+    for step in steps {
+        step.correlation_id = correlation_id.map(String::from);
+    }
 }
 
 /// Result of a full evaluation including flow execution.
@@ -120,7 +165,9 @@ fn handle_failure(
     op_index: &HashMap<&str, &Operation>,
     snapshot: &Snapshot,
     entity_states: &mut EntityStateMap,
+    entity_attributes: &mut EntityAttributeMap,
     instance_bindings: &InstanceBindingMap,
+    entity_versions: &EntityVersions,
     steps_executed: &mut Vec<StepRecord>,
     entity_changes_all: &mut Vec<EffectRecord>,
 ) -> Result<Option<FlowResult>, EvalError> {
@@ -134,6 +181,7 @@ fn handle_failure(
                 steps_executed: std::mem::take(steps_executed),
                 entity_state_changes: std::mem::take(entity_changes_all),
                 initiating_persona: None, // Caller sets this
+                correlation_id: None,
             }))
         }
         FailureHandler::Compensate { steps, then } => {
@@ -155,24 +203,28 @@ fn handle_failure(
                     &snapshot.facts,
                     &snapshot.verdicts,
                     entity_states,
+                    entity_attributes,
                     &comp_bindings,
+                    entity_versions,
                 ) {
                     Ok(comp_result) => {
                         entity_changes_all.extend(comp_result.effects_applied.clone());
                         steps_executed.push(StepRecord {
-                            step_id: format!("comp:{}", comp_step.op),
+                            step_id: format!("comp:{}", comp_step.op).into(),
                             step_type: "compensation".to_string(),
                             result: comp_result.outcome.clone(),
                             instance_bindings: comp_result.provenance.instance_binding.clone(),
+                            correlation_id: None,
                         });
                     }
                     Err(comp_err) => {
                         // Compensation step failed -- route per comp_step.on_failure
                         steps_executed.push(StepRecord {
-                            step_id: format!("comp:{}", comp_step.op),
+                            step_id: format!("comp:{}", comp_step.op).into(),
                             step_type: "compensation".to_string(),
                             result: format!("error: {}", comp_err),
                             instance_bindings: std::collections::BTreeMap::new(),
+                            correlation_id: None,
                         });
                         match &comp_step.on_failure {
                             StepTarget::Terminal { outcome } => {
@@ -182,6 +234,7 @@ fn handle_failure(
                                     steps_executed: std::mem::take(steps_executed),
                                     entity_state_changes: std::mem::take(entity_changes_all),
                                     initiating_persona: None,
+                                    correlation_id: None,
                                 }));
                             }
                             StepTarget::StepRef(_step_ref) => {
@@ -210,6 +263,7 @@ fn handle_failure(
                         steps_executed: std::mem::take(steps_executed),
                         entity_state_changes: std::mem::take(entity_changes_all),
                         initiating_persona: None,
+                        correlation_id: None,
                     }))
                 }
                 StepTarget::StepRef(_next_id) => {
@@ -225,10 +279,11 @@ fn handle_failure(
             // Escalation is a persona transfer on failure -- record handoff
             // and continue from the next step
             steps_executed.push(StepRecord {
-                step_id: step_id.to_string(),
+                step_id: Symbol::new(step_id),
                 step_type: "escalation".to_string(),
                 result: format!("escalated to {}", to_persona),
                 instance_bindings: std::collections::BTreeMap::new(),
+                correlation_id: None,
             });
             // Caller will set current_step_id = next
             Ok(None)
@@ -253,11 +308,76 @@ pub fn execute_flow(
     contract: &Contract,
     snapshot: &Snapshot,
     entity_states: &mut EntityStateMap,
+    entity_attributes: &mut EntityAttributeMap,
     instance_bindings: &InstanceBindingMap,
-    max_steps: Option<usize>,
+    options: FlowOptions<'_>,
 ) -> Result<FlowResult, EvalError> {
+    match walk_flow(
+        flow,
+        contract,
+        snapshot,
+        entity_states,
+        entity_attributes,
+        instance_bindings,
+        options
+            .entity_versions
+            .unwrap_or(&EntityVersions::default()),
+        options.max_steps,
+        &flow.entry,
+        false,
+    )? {
+        Walk::Completed(mut result) => {
+            result.correlate(options.correlation_id);
+            Ok(result)
+        }
+        Walk::Paused(_) => unreachable!("execute_flow does not honor handoff pauses"),
+    }
+}
+
+/// How a walk over a flow's steps ended.
+pub(crate) enum Walk {
+    Completed(FlowResult),
+    /// Stopped at a HandoffStep with `pause: true`. Only produced when the
+    /// walk honors pauses.
+    Paused(PausedWalk),
+}
+
+pub(crate) struct PausedWalk {
+    pub handoff_step: String,
+    pub from_persona: String,
+    pub to_persona: String,
+    pub next: String,
+    /// The handoff's resolved timeout deadline, if it declares one
+    pub deadline: Option<time::OffsetDateTime>,
+    pub steps_executed: Vec<StepRecord>,
+    pub entity_state_changes: Vec<EffectRecord>,
+    /// The walk's bindings at the pause, including created instances
+    pub instance_bindings: InstanceBindingMap,
+}
+
+/// Walk `flow` starting at `start_at`.
+///
+/// With `honor_pauses`, a pausing HandoffStep ends the walk with
+/// [`Walk::Paused`]. Sub-flows and parallel branches always run through
+/// `execute_flow`, so pauses only take effect at the top level of the flow
+/// being walked.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn walk_flow(
+    flow: &Flow,
+    contract: &Contract,
+    snapshot: &Snapshot,
+    entity_states: &mut EntityStateMap,
+    entity_attributes: &mut EntityAttributeMap,
+    instance_bindings: &InstanceBindingMap,
+    entity_versions: &EntityVersions,
+    max_steps: Option<usize>,
+    start_at: &str,
+    honor_pauses: bool,
+) -> Result<Walk, EvalError> {
     let mut steps_executed = Vec::new();
     let mut entity_changes_all = Vec::new();
+    // Instances created by a step are targeted by the steps after it.
+    let mut bindings = instance_bindings.clone();
 
     // Build step index by id for fast lookup
     let step_index: BTreeMap<String, &FlowStep> = flow
@@ -273,8 +393,10 @@ pub fn execute_flow(
         .map(|o| (o.id.as_str(), o))
         .collect();
 
-    // Start at entry step
-    let mut current_step_id = flow.entry.clone();
+    let mut current_step_id = start_at.to_string();
+
+    // Deadlines are compared against one instant for the whole walk
+    let now = crate::clock::now();
 
     // Max steps to prevent infinite loops
     let max_steps = max_steps.unwrap_or(1000);
@@ -297,6 +419,34 @@ pub fn execute_flow(
                     current_step_id, flow.id
                 ),
             })?;
+        let _timer = crate::profile::step_timer(&flow.id, &current_step_id);
+
+        let deadline = match timeout::step_timeout(step) {
+            Some(t) => Some((t, timeout::resolve_deadline(t, snapshot, &flow.id)?)),
+            None => None,
+        };
+        if let Some((t, at)) = deadline {
+            if at <= now {
+                match timeout::fire_timeout(
+                    t,
+                    &current_step_id,
+                    &op_index,
+                    snapshot,
+                    entity_states,
+                    entity_attributes,
+                    &bindings,
+                    entity_versions,
+                    &mut steps_executed,
+                    &mut entity_changes_all,
+                )? {
+                    timeout::TimeoutRoute::Finish(result) => return Ok(Walk::Completed(result)),
+                    timeout::TimeoutRoute::Continue(next_id) => {
+                        current_step_id = next_id;
+                        continue;
+                    }
+                }
+            }
+        }
 
         match step {
             FlowStep::OperationStep {
@@ -305,6 +455,7 @@ pub fn execute_flow(
                 persona,
                 outcomes,
                 on_failure,
+                ..
             } => {
                 // Find the operation in the contract (O(1) via index)
                 let operation =
@@ -316,7 +467,7 @@ pub fn execute_flow(
 
                 // Resolve instance bindings for this operation's effects.
                 // Per §11.4: maps entity_id -> instance_id for each entity this op targets.
-                let op_bindings = resolve_bindings(operation, instance_bindings);
+                let op_bindings = resolve_bindings(operation, &bindings);
 
                 // Execute the operation against the FROZEN snapshot
                 match execute_operation(
@@ -325,15 +476,26 @@ pub fn execute_flow(
                     &snapshot.facts,
                     &snapshot.verdicts,
                     entity_states,
+                    entity_attributes,
                     &op_bindings,
+                    entity_versions,
                 ) {
                     Ok(op_result) => {
+                        for record in &op_result.effects_applied {
+                            if record.kind == EffectKind::CreateInstance {
+                                bindings.insert(
+                                    record.entity_id.to_string(),
+                                    record.instance_id.to_string(),
+                                );
+                            }
+                        }
                         entity_changes_all.extend(op_result.effects_applied.clone());
                         steps_executed.push(StepRecord {
-                            step_id: id.clone(),
+                            step_id: Symbol::from(id),
                             step_type: "operation".to_string(),
                             result: op_result.outcome.clone(),
                             instance_bindings: op_result.provenance.instance_binding.clone(),
+                            correlation_id: None,
                         });
 
                         // Route based on outcome
@@ -351,22 +513,24 @@ pub fn execute_flow(
                                 current_step_id = next_id.clone();
                             }
                             StepTarget::Terminal { outcome } => {
-                                return Ok(FlowResult {
+                                return Ok(Walk::Completed(FlowResult {
                                     outcome: outcome.clone(),
                                     steps_executed,
                                     entity_state_changes: entity_changes_all,
                                     initiating_persona: None,
-                                });
+                                    correlation_id: None,
+                                }));
                             }
                         }
                     }
                     Err(op_err) => {
                         // Handle operation failure
                         steps_executed.push(StepRecord {
-                            step_id: id.clone(),
+                            step_id: Symbol::from(id),
                             step_type: "operation".to_string(),
                             result: format!("error: {}", op_err),
                             instance_bindings: op_bindings.clone(),
+                            correlation_id: None,
                         });
 
                         match handle_failure(
@@ -375,11 +539,13 @@ pub fn execute_flow(
                             &op_index,
                             snapshot,
                             entity_states,
-                            instance_bindings,
+                            entity_attributes,
+                            &bindings,
+                            entity_versions,
                             &mut steps_executed,
                             &mut entity_changes_all,
                         )? {
-                            Some(result) => return Ok(result),
+                            Some(result) => return Ok(Walk::Completed(result)),
                             None => {
                                 // Continue flow from the handler's next step
                                 match on_failure {
@@ -409,7 +575,11 @@ pub fn execute_flow(
             } => {
                 // Evaluate condition against FROZEN snapshot
                 let mut collector = ProvenanceCollector::new();
-                let ctx = EvalContext::new();
+                let ctx = if condition.reads_instances() {
+                    EvalContext::with_instances(entity_states, entity_attributes)
+                } else {
+                    EvalContext::new()
+                };
                 let cond_result = eval_pred(
                     condition,
                     &snapshot.facts,
@@ -421,10 +591,11 @@ pub fn execute_flow(
 
                 let branch_label = if branch_taken { "true" } else { "false" };
                 steps_executed.push(StepRecord {
-                    step_id: id.clone(),
+                    step_id: Symbol::from(id),
                     step_type: "branch".to_string(),
                     result: branch_label.to_string(),
                     instance_bindings: std::collections::BTreeMap::new(),
+                    correlation_id: None,
                 });
 
                 let target = if branch_taken { if_true } else { if_false };
@@ -433,12 +604,13 @@ pub fn execute_flow(
                         current_step_id = next_id.clone();
                     }
                     StepTarget::Terminal { outcome } => {
-                        return Ok(FlowResult {
+                        return Ok(Walk::Completed(FlowResult {
                             outcome: outcome.clone(),
                             steps_executed,
                             entity_state_changes: entity_changes_all,
                             initiating_persona: None,
-                        });
+                            correlation_id: None,
+                        }));
                     }
                 }
             }
@@ -465,18 +637,23 @@ pub fn execute_flow(
                     contract,
                     snapshot,
                     entity_states,
-                    instance_bindings,
-                    None,
+                    entity_attributes,
+                    &bindings,
+                    FlowOptions {
+                        entity_versions: Some(entity_versions),
+                        ..FlowOptions::default()
+                    },
                 ) {
                     Ok(sub_result) => {
                         entity_changes_all.extend(sub_result.entity_state_changes);
                         steps_executed.push(StepRecord {
-                            step_id: id.clone(),
+                            step_id: Symbol::from(id),
                             step_type: "sub_flow".to_string(),
                             result: sub_result.outcome.clone(),
                             // Sub-flows inherit the parent instance_bindings per §11.4/§11.5.
                             // We record the parent's bindings for this sub-flow step.
-                            instance_bindings: instance_bindings.clone(),
+                            instance_bindings: bindings.clone(),
+                            correlation_id: None,
                         });
 
                         match on_success {
@@ -484,21 +661,23 @@ pub fn execute_flow(
                                 current_step_id = next_id.clone();
                             }
                             StepTarget::Terminal { outcome } => {
-                                return Ok(FlowResult {
+                                return Ok(Walk::Completed(FlowResult {
                                     outcome: outcome.clone(),
                                     steps_executed,
                                     entity_state_changes: entity_changes_all,
                                     initiating_persona: None,
-                                });
+                                    correlation_id: None,
+                                }));
                             }
                         }
                     }
                     Err(_sub_err) => {
                         steps_executed.push(StepRecord {
-                            step_id: id.clone(),
+                            step_id: Symbol::from(id),
                             step_type: "sub_flow".to_string(),
                             result: "error".to_string(),
-                            instance_bindings: instance_bindings.clone(),
+                            instance_bindings: bindings.clone(),
+                            correlation_id: None,
                         });
 
                         match handle_failure(
@@ -507,11 +686,13 @@ pub fn execute_flow(
                             &op_index,
                             snapshot,
                             entity_states,
-                            instance_bindings,
+                            entity_attributes,
+                            &bindings,
+                            entity_versions,
                             &mut steps_executed,
                             &mut entity_changes_all,
                         )? {
-                            Some(result) => return Ok(result),
+                            Some(result) => return Ok(Walk::Completed(result)),
                             None => {
                                 // Continue flow from the handler's next step
                                 match on_failure {
@@ -534,17 +715,34 @@ pub fn execute_flow(
 
             FlowStep::HandoffStep {
                 id,
-                from_persona: _,
-                to_persona: _,
+                from_persona,
+                to_persona,
                 next,
+                pause,
+                ..
             } => {
-                // Handoff is a persona transfer -- record and continue
+                // Handoff is a persona transfer -- record and continue,
+                // or stop here if the step pauses for `to_persona`
+                let paused = *pause && honor_pauses;
                 steps_executed.push(StepRecord {
-                    step_id: id.clone(),
+                    step_id: Symbol::from(id),
                     step_type: "handoff".to_string(),
-                    result: "handoff".to_string(),
+                    result: if paused { "paused" } else { "handoff" }.to_string(),
                     instance_bindings: std::collections::BTreeMap::new(),
+                    correlation_id: None,
                 });
+                if paused {
+                    return Ok(Walk::Paused(PausedWalk {
+                        handoff_step: id.clone(),
+                        from_persona: from_persona.clone(),
+                        to_persona: to_persona.clone(),
+                        next: next.clone(),
+                        deadline: deadline.map(|(_, at)| at),
+                        steps_executed,
+                        entity_state_changes: entity_changes_all,
+                        instance_bindings: bindings,
+                    }));
+                }
                 current_step_id = next.clone();
             }
 
@@ -557,6 +755,7 @@ pub fn execute_flow(
                 for branch in branches {
                     // Each branch gets its own clone of entity states
                     let mut branch_entity_states = entity_states.clone();
+                    let mut branch_entity_attributes = entity_attributes.clone();
 
                     // Build a branch-local flow to execute
                     let branch_flow = Flow {
@@ -571,8 +770,12 @@ pub fn execute_flow(
                         contract,
                         snapshot,
                         &mut branch_entity_states,
-                        instance_bindings,
-                        None,
+                        &mut branch_entity_attributes,
+                        &bindings,
+                        FlowOptions {
+                            entity_versions: Some(entity_versions),
+                            ..FlowOptions::default()
+                        },
                     ) {
                         Ok(branch_result) => {
                             branch_outcomes.push(BranchOutcome::Success {
@@ -607,11 +810,12 @@ pub fn execute_flow(
                     .collect();
 
                 steps_executed.push(StepRecord {
-                    step_id: id.clone(),
+                    step_id: Symbol::from(id),
                     step_type: "parallel".to_string(),
                     result: branch_summaries.join(", "),
                     // Parallel steps use the parent's instance_bindings
-                    instance_bindings: instance_bindings.clone(),
+                    instance_bindings: bindings.clone(),
+                    correlation_id: None,
                 });
 
                 // Collect branch step records
@@ -640,10 +844,19 @@ pub fn execute_flow(
                         // EffectRecord now carries instance_id per Plan 04-02, so we use it
                         // directly to form the composite (entity_id, instance_id) key.
                         for change in entity_changes {
-                            entity_states.insert(
-                                (change.entity_id.clone(), change.instance_id.clone()),
-                                change.to_state.clone(),
-                            );
+                            let key = (change.entity_id.clone(), change.instance_id.clone());
+                            if change.kind == EffectKind::DestroyInstance {
+                                entity_states.remove(&key);
+                                entity_attributes.remove(&key);
+                                continue;
+                            }
+                            entity_states.insert(key.clone(), change.to_state.clone());
+                            if !change.attributes.is_empty() {
+                                entity_attributes
+                                    .entry(key)
+                                    .or_default()
+                                    .extend(change.attributes.clone());
+                            }
                         }
                         // Also apply any entity states that were modified but not
                         // captured as EffectRecords (safety: merge all branch state)
@@ -667,12 +880,13 @@ pub fn execute_flow(
                                 continue;
                             }
                             StepTarget::Terminal { outcome } => {
-                                return Ok(FlowResult {
+                                return Ok(Walk::Completed(FlowResult {
                                     outcome: outcome.clone(),
                                     steps_executed,
                                     entity_state_changes: entity_changes_all,
                                     initiating_persona: None,
-                                });
+                                    correlation_id: None,
+                                }));
                             }
                         }
                     }
@@ -686,11 +900,13 @@ pub fn execute_flow(
                             &op_index,
                             snapshot,
                             entity_states,
-                            instance_bindings,
+                            entity_attributes,
+                            &bindings,
+                            entity_versions,
                             &mut steps_executed,
                             &mut entity_changes_all,
                         )? {
-                            Some(result) => return Ok(result),
+                            Some(result) => return Ok(Walk::Completed(result)),
                             None => match handler {
                                 FailureHandler::Compensate {
                                     then: StepTarget::StepRef(next_id),
@@ -716,12 +932,13 @@ pub fn execute_flow(
                             current_step_id = next_id.clone();
                         }
                         StepTarget::Terminal { outcome } => {
-                            return Ok(FlowResult {
+                            return Ok(Walk::Completed(FlowResult {
                                 outcome: outcome.clone(),
                                 steps_executed,
                                 entity_state_changes: entity_changes_all,
                                 initiating_persona: None,
-                            });
+                                correlation_id: None,
+                            }));
                         }
                     }
                 } else {
@@ -0,0 +1,476 @@
+//! Suspending and resuming flows at handoff steps.
+//!
+//! A HandoffStep declared with `pause: true` hands the flow to another
+//! persona and stops. [`start_flow`] returns [`FlowRun::Suspended`] with a
+//! [`FlowContinuation`] -- everything needed to carry on: where to resume,
+//! who may resume, entity states and attributes, instance bindings, and the frozen
+//! snapshot (wrapped in a [`SnapshotEnvelope`] so it cannot be replayed
+//! against a different bundle). The continuation serializes to JSON, so the
+//! flow can be resumed with [`resume_flow`] in another process, days later.
+//!
+//! A pausing handoff with a `timeout` records its deadline in the
+//! continuation. Once the deadline has passed the flow can no longer be
+//! resumed; [`expire_flow`] runs the handoff's `on_timeout` action instead.
+//! Embedders are expected to schedule that call (the HTTP server keeps a
+//! timer per suspended flow).
+//!
+//! [`execute_flow`](super::execute_flow) ignores pauses; handoffs inside
+//! sub-flows and parallel branches never pause.
+
+use std::collections::BTreeMap;
+
+use super::timeout::{self, TimeoutRoute};
+use super::{
+    correlate_steps, walk_flow, FlowEvalResult, FlowOptions, Snapshot, SnapshotEnvelope,
+    StepRecord, Walk,
+};
+use crate::operation::{
+    instance_key, EffectRecord, EntityAttributeMap, EntityStateMap, EntityVersions,
+    InstanceBindingMap,
+};
+use crate::symbol::Symbol;
+use crate::types::{Contract, EvalError, FlowStep, Operation, Value};
+
+/// Result of starting or resuming a flow that may pause.
+#[derive(Debug)]
+pub enum FlowRun {
+    Completed(FlowEvalResult),
+    Suspended(Box<FlowSuspended>),
+}
+
+/// A flow stopped at a pausing HandoffStep.
+#[derive(Debug, Clone)]
+pub struct FlowSuspended {
+    /// Steps executed in this segment, ending with the paused handoff.
+    pub steps_executed: Vec<StepRecord>,
+    /// Entity state changes made in this segment.
+    pub entity_state_changes: Vec<EffectRecord>,
+    pub continuation: FlowContinuation,
+}
+
+/// Everything needed to resume a suspended flow.
+#[derive(Debug, Clone)]
+pub struct FlowContinuation {
+    pub flow_id: String,
+    /// The HandoffStep the flow paused at.
+    pub handoff_step: String,
+    pub from_persona: String,
+    /// The only persona allowed to resume the flow.
+    pub to_persona: String,
+    /// The step execution continues from.
+    pub resume_at: String,
+    pub initiating_persona: String,
+    /// Carried over to the resumed run.
+    pub correlation_id: Option<String>,
+    /// RFC 3339 instant after which the flow can no longer be resumed, from
+    /// the handoff's `timeout`.
+    pub deadline: Option<String>,
+    pub entity_states: EntityStateMap,
+    pub entity_attributes: EntityAttributeMap,
+    pub instance_bindings: InstanceBindingMap,
+    pub snapshot: SnapshotEnvelope,
+}
+
+impl FlowContinuation {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut entity_states: BTreeMap<&str, BTreeMap<&str, &str>> = BTreeMap::new();
+        for ((entity_id, instance_id), state) in &self.entity_states {
+            entity_states
+                .entry(entity_id.as_str())
+                .or_default()
+                .insert(instance_id.as_str(), state.as_str());
+        }
+        let mut entity_attributes: BTreeMap<&str, BTreeMap<&str, serde_json::Value>> =
+            BTreeMap::new();
+        for ((entity_id, instance_id), attrs) in &self.entity_attributes {
+            let attrs: serde_json::Map<String, serde_json::Value> = attrs
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_json()))
+                .collect();
+            entity_attributes
+                .entry(entity_id.as_str())
+                .or_default()
+                .insert(instance_id.as_str(), serde_json::Value::Object(attrs));
+        }
+        serde_json::json!({
+            "flow_id": self.flow_id,
+            "handoff_step": self.handoff_step,
+            "from_persona": self.from_persona,
+            "to_persona": self.to_persona,
+            "resume_at": self.resume_at,
+            "initiating_persona": self.initiating_persona,
+            "correlation_id": self.correlation_id,
+            "deadline": self.deadline,
+            "entity_states": entity_states,
+            "entity_attributes": entity_attributes,
+            "instance_bindings": self.instance_bindings,
+            "snapshot": self.snapshot.to_json(),
+        })
+    }
+
+    /// Inverse of [`FlowContinuation::to_json`].
+    pub fn from_json(v: &serde_json::Value) -> Result<FlowContinuation, EvalError> {
+        let err = |message: String| EvalError::DeserializeError { message };
+        let text = |field: &str| {
+            v.get(field)
+                .and_then(|s| s.as_str())
+                .map(String::from)
+                .ok_or_else(|| err(format!("continuation missing '{}'", field)))
+        };
+
+        let mut entity_states = EntityStateMap::new();
+        let states = v
+            .get("entity_states")
+            .and_then(|s| s.as_object())
+            .ok_or_else(|| err("continuation missing 'entity_states'".to_string()))?;
+        for (entity_id, instances) in states {
+            let instances = instances.as_object().ok_or_else(|| {
+                err(format!(
+                    "continuation entity_states[{}] must be an object",
+                    entity_id
+                ))
+            })?;
+            for (instance_id, state) in instances {
+                let state = state.as_str().ok_or_else(|| {
+                    err(format!(
+                        "continuation entity_states[{}][{}] must be a string",
+                        entity_id, instance_id
+                    ))
+                })?;
+                entity_states.insert(instance_key(entity_id, instance_id), Symbol::from(state));
+            }
+        }
+
+        // Absent in continuations written before entity attributes existed.
+        let mut entity_attributes = EntityAttributeMap::new();
+        if let Some(attributes) = v.get("entity_attributes").and_then(|a| a.as_object()) {
+            for (entity_id, instances) in attributes {
+                let Some(instances) = instances.as_object() else {
+                    return Err(err(format!(
+                        "continuation entity_attributes[{}] must be an object",
+                        entity_id
+                    )));
+                };
+                for (instance_id, attrs) in instances {
+                    let Some(attrs) = attrs.as_object() else {
+                        return Err(err(format!(
+                            "continuation entity_attributes[{}][{}] must be an object",
+                            entity_id, instance_id
+                        )));
+                    };
+                    let attrs = attrs
+                        .iter()
+                        .map(|(name, value)| Ok((name.clone(), Value::from_json(value)?)))
+                        .collect::<Result<BTreeMap<_, _>, EvalError>>()?;
+                    entity_attributes.insert(instance_key(entity_id, instance_id), attrs);
+                }
+            }
+        }
+
+        let instance_bindings: InstanceBindingMap = match v.get("instance_bindings") {
+            Some(b) => serde_json::from_value(b.clone())
+                .map_err(|e| err(format!("invalid continuation instance_bindings: {}", e)))?,
+            None => InstanceBindingMap::new(),
+        };
+        let snapshot = v
+            .get("snapshot")
+            .ok_or_else(|| err("continuation missing 'snapshot'".to_string()))?;
+
+        Ok(FlowContinuation {
+            flow_id: text("flow_id")?,
+            handoff_step: text("handoff_step")?,
+            from_persona: text("from_persona")?,
+            to_persona: text("to_persona")?,
+            resume_at: text("resume_at")?,
+            initiating_persona: text("initiating_persona")?,
+            correlation_id: v
+                .get("correlation_id")
+                .and_then(|c| c.as_str())
+                .map(String::from),
+            deadline: v.get("deadline").and_then(|d| d.as_str()).map(String::from),
+            entity_states,
+            entity_attributes,
+            instance_bindings,
+            snapshot: SnapshotEnvelope::from_json(snapshot)?,
+        })
+    }
+
+    /// Whether the handoff's deadline has passed.
+    pub fn deadline_passed(&self) -> bool {
+        self.time_until_deadline() == Some(std::time::Duration::ZERO)
+    }
+
+    /// Time left before the deadline (zero once it has passed), or `None`
+    /// if the handoff has no timeout.
+    pub fn time_until_deadline(&self) -> Option<std::time::Duration> {
+        let deadline = timeout::parse_datetime(self.deadline.as_deref()?)?;
+        let left = deadline - crate::clock::now();
+        Some(left.try_into().unwrap_or(std::time::Duration::ZERO))
+    }
+}
+
+/// Execute `flow_id` from its entry step, pausing at handoffs declared with
+/// `pause: true`.
+///
+/// `bundle` is the interchange bundle `contract` was parsed from; its
+/// identity is recorded in the continuation's snapshot envelope.
+/// `options.entity_versions` is checked by every operation the run
+/// executes; `options.correlation_id` is recorded on the run and carried
+/// through its continuation to every resumed segment.
+#[allow(clippy::too_many_arguments)]
+pub fn start_flow(
+    contract: &Contract,
+    bundle: &serde_json::Value,
+    snapshot: Snapshot,
+    flow_id: &str,
+    persona: &str,
+    entity_states: EntityStateMap,
+    instance_bindings: InstanceBindingMap,
+    options: FlowOptions<'_>,
+) -> Result<FlowRun, EvalError> {
+    let flow = contract
+        .get_flow(flow_id)
+        .ok_or_else(|| EvalError::DeserializeError {
+            message: format!("flow '{}' not found in contract", flow_id),
+        })?;
+    let entry = flow.entry.clone();
+    drive(
+        contract,
+        bundle,
+        snapshot,
+        flow_id,
+        &entry,
+        persona,
+        entity_states,
+        EntityAttributeMap::new(),
+        instance_bindings,
+        options
+            .entity_versions
+            .unwrap_or(&EntityVersions::default()),
+        options.max_steps,
+        options.correlation_id.map(String::from),
+        Vec::new(),
+        Vec::new(),
+    )
+}
+
+/// Resume a suspended flow as `persona`, which must be the handoff's
+/// `to_persona`.
+///
+/// The continuation's snapshot must have been taken against `bundle`. The
+/// returned steps start with a `resume` record for the handoff step and
+/// cover only the resumed segment. `entity_versions` holds the versions the
+/// caller read the suspended instances at; days may pass between segments,
+/// so a concurrent writer is detected here rather than overwritten.
+pub fn resume_flow(
+    contract: &Contract,
+    bundle: &serde_json::Value,
+    continuation: FlowContinuation,
+    persona: &str,
+    entity_versions: &EntityVersions,
+) -> Result<FlowRun, EvalError> {
+    if persona != continuation.to_persona {
+        return Err(EvalError::FlowError {
+            flow_id: continuation.flow_id,
+            message: format!(
+                "flow is paused at '{}' awaiting persona '{}', not '{}'",
+                continuation.handoff_step, continuation.to_persona, persona
+            ),
+        });
+    }
+    if continuation.deadline_passed() {
+        return Err(EvalError::FlowError {
+            flow_id: continuation.flow_id,
+            message: format!(
+                "handoff '{}' timed out at {}",
+                continuation.handoff_step,
+                continuation.deadline.unwrap_or_default()
+            ),
+        });
+    }
+    let snapshot = continuation.snapshot.open(bundle)?;
+    let resumed = StepRecord {
+        step_id: Symbol::new(&continuation.handoff_step),
+        step_type: "resume".to_string(),
+        result: format!("resumed by {}", persona),
+        instance_bindings: BTreeMap::new(),
+        correlation_id: None,
+    };
+    drive(
+        contract,
+        bundle,
+        snapshot,
+        &continuation.flow_id,
+        &continuation.resume_at,
+        &continuation.initiating_persona,
+        continuation.entity_states,
+        continuation.entity_attributes,
+        continuation.instance_bindings,
+        entity_versions,
+        None,
+        continuation.correlation_id,
+        vec![resumed],
+        Vec::new(),
+    )
+}
+
+/// Run the `on_timeout` action of the handoff a suspended flow is paused
+/// at, then carry on from wherever it leads.
+///
+/// Callers normally invoke this once [`FlowContinuation::deadline_passed`]
+/// holds, but it does not check the deadline itself. Fails if the handoff
+/// declares no timeout. `entity_versions` is checked as in [`resume_flow`].
+pub fn expire_flow(
+    contract: &Contract,
+    bundle: &serde_json::Value,
+    continuation: FlowContinuation,
+    entity_versions: &EntityVersions,
+) -> Result<FlowRun, EvalError> {
+    let flow_err = |message: String| EvalError::FlowError {
+        flow_id: continuation.flow_id.clone(),
+        message,
+    };
+    let flow =
+        contract
+            .get_flow(&continuation.flow_id)
+            .ok_or_else(|| EvalError::DeserializeError {
+                message: format!("flow '{}' not found in contract", continuation.flow_id),
+            })?;
+    let timeout = flow
+        .steps
+        .iter()
+        .find_map(|step| match step {
+            FlowStep::HandoffStep { id, timeout, .. } if *id == continuation.handoff_step => {
+                timeout.as_ref()
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            flow_err(format!(
+                "handoff '{}' declares no timeout",
+                continuation.handoff_step
+            ))
+        })?;
+
+    let snapshot = continuation.snapshot.open(bundle)?;
+    let mut entity_states = continuation.entity_states;
+    let mut entity_attributes = continuation.entity_attributes;
+    let mut steps_executed = Vec::new();
+    let mut entity_changes = Vec::new();
+    let op_index: std::collections::HashMap<&str, &Operation> = contract
+        .operations
+        .iter()
+        .map(|o| (o.id.as_str(), o))
+        .collect();
+    let route = timeout::fire_timeout(
+        timeout,
+        &continuation.handoff_step,
+        &op_index,
+        &snapshot,
+        &mut entity_states,
+        &mut entity_attributes,
+        &continuation.instance_bindings,
+        entity_versions,
+        &mut steps_executed,
+        &mut entity_changes,
+    )?;
+    match route {
+        TimeoutRoute::Finish(mut flow_result) => {
+            flow_result.initiating_persona = Some(continuation.initiating_persona);
+            flow_result.correlate(continuation.correlation_id.as_deref());
+            Ok(FlowRun::Completed(FlowEvalResult {
+                verdicts: snapshot.verdicts,
+                flow_result,
+            }))
+        }
+        TimeoutRoute::Continue(next) => drive(
+            contract,
+            bundle,
+            snapshot,
+            &continuation.flow_id,
+            &next,
+            &continuation.initiating_persona,
+            entity_states,
+            entity_attributes,
+            continuation.instance_bindings,
+            entity_versions,
+            None,
+            continuation.correlation_id,
+            steps_executed,
+            entity_changes,
+        ),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn drive(
+    contract: &Contract,
+    bundle: &serde_json::Value,
+    snapshot: Snapshot,
+    flow_id: &str,
+    start_at: &str,
+    initiating_persona: &str,
+    mut entity_states: EntityStateMap,
+    mut entity_attributes: EntityAttributeMap,
+    instance_bindings: InstanceBindingMap,
+    entity_versions: &EntityVersions,
+    max_steps: Option<usize>,
+    correlation_id: Option<String>,
+    mut steps_executed: Vec<StepRecord>,
+    mut entity_changes: Vec<EffectRecord>,
+) -> Result<FlowRun, EvalError> {
+    let flow = contract
+        .get_flow(flow_id)
+        .ok_or_else(|| EvalError::DeserializeError {
+            message: format!("flow '{}' not found in contract", flow_id),
+        })?;
+    let walk = walk_flow(
+        flow,
+        contract,
+        &snapshot,
+        &mut entity_states,
+        &mut entity_attributes,
+        &instance_bindings,
+        entity_versions,
+        max_steps,
+        start_at,
+        true,
+    )?;
+    match walk {
+        Walk::Completed(mut flow_result) => {
+            steps_executed.append(&mut flow_result.steps_executed);
+            flow_result.steps_executed = steps_executed;
+            entity_changes.append(&mut flow_result.entity_state_changes);
+            flow_result.entity_state_changes = entity_changes;
+            flow_result.initiating_persona = Some(initiating_persona.to_string());
+            flow_result.correlate(correlation_id.as_deref());
+            Ok(FlowRun::Completed(FlowEvalResult {
+                verdicts: snapshot.verdicts,
+                flow_result,
+            }))
+        }
+        Walk::Paused(paused) => {
+            steps_executed.extend(paused.steps_executed);
+            entity_changes.extend(paused.entity_state_changes);
+            correlate_steps(&mut steps_executed, correlation_id.as_deref());
+            Ok(FlowRun::Suspended(Box::new(FlowSuspended {
+                steps_executed,
+                entity_state_changes: entity_changes,
+                continuation: FlowContinuation {
+                    flow_id: flow_id.to_string(),
+                    handoff_step: paused.handoff_step,
+                    from_persona: paused.from_persona,
+                    to_persona: paused.to_persona,
+                    resume_at: paused.next,
+                    initiating_persona: initiating_persona.to_string(),
+                    correlation_id,
+                    deadline: paused.deadline.map(timeout::format_datetime),
+                    entity_states,
+                    entity_attributes,
+                    instance_bindings: paused.instance_bindings,
+                    snapshot: SnapshotEnvelope::new(bundle, snapshot),
+                },
+            })))
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Snapshot persistence.
+//!
+//! A [`Snapshot`] is frozen at flow initiation, so it can be written out
+//! and read back verbatim: a flow paused for human approval can resume
+//! against the exact facts and verdicts it started with, in this process
+//! or another one. [`SnapshotEnvelope`] records which bundle the snapshot
+//! was taken against so it is never replayed against a different contract.
+
+use super::Snapshot;
+use crate::types::{EvalError, FactSet, VerdictSet};
+
+/// Envelope format identifier.
+pub const SNAPSHOT_FORMAT: &str = "tenor-snapshot";
+
+/// Current envelope format version. Bumped on incompatible layout changes.
+pub const SNAPSHOT_FORMAT_VERSION: u64 = 1;
+
+impl Snapshot {
+    /// Serialize to `{ "facts": {...}, "verdicts": [...] }` using the tagged
+    /// value encoding of [`crate::Value::to_json`].
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut out = self.verdicts.to_json();
+        out["facts"] = self.facts.to_json();
+        out
+    }
+
+    /// Inverse of [`Snapshot::to_json`].
+    pub fn from_json(v: &serde_json::Value) -> Result<Snapshot, EvalError> {
+        let facts = v.get("facts").ok_or_else(|| EvalError::DeserializeError {
+            message: "snapshot missing 'facts'".to_string(),
+        })?;
+        Ok(Snapshot {
+            facts: FactSet::from_json(facts)?,
+            verdicts: VerdictSet::from_json(v)?,
+        })
+    }
+}
+
+/// A snapshot tagged with the id and `tenor_version` of the bundle it was
+/// taken against.
+#[derive(Debug, Clone)]
+pub struct SnapshotEnvelope {
+    pub bundle_id: String,
+    pub bundle_tenor_version: String,
+    pub snapshot: Snapshot,
+}
+
+impl SnapshotEnvelope {
+    /// Wrap `snapshot`, recording `bundle`'s identity.
+    pub fn new(bundle: &serde_json::Value, snapshot: Snapshot) -> SnapshotEnvelope {
+        let (bundle_id, bundle_tenor_version) = bundle_identity(bundle);
+        SnapshotEnvelope {
+            bundle_id,
+            bundle_tenor_version,
+            snapshot,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "format": SNAPSHOT_FORMAT,
+            "format_version": SNAPSHOT_FORMAT_VERSION,
+            "bundle": {
+                "id": self.bundle_id,
+                "tenor_version": self.bundle_tenor_version,
+            },
+            "snapshot": self.snapshot.to_json(),
+        })
+    }
+
+    /// Parse an envelope, rejecting unknown formats and newer format
+    /// versions.
+    pub fn from_json(v: &serde_json::Value) -> Result<SnapshotEnvelope, EvalError> {
+        let err = |message: String| EvalError::DeserializeError { message };
+        if v.get("format").and_then(|f| f.as_str()) != Some(SNAPSHOT_FORMAT) {
+            return Err(err(format!(
+                "not a snapshot envelope (expected format '{}')",
+                SNAPSHOT_FORMAT
+            )));
+        }
+        let version = v
+            .get("format_version")
+            .and_then(|f| f.as_u64())
+            .ok_or_else(|| err("snapshot envelope missing 'format_version'".to_string()))?;
+        if version > SNAPSHOT_FORMAT_VERSION {
+            return Err(err(format!(
+                "snapshot format version {} is newer than supported version {}",
+                version, SNAPSHOT_FORMAT_VERSION
+            )));
+        }
+        let bundle = v
+            .get("bundle")
+            .ok_or_else(|| err("snapshot envelope missing 'bundle'".to_string()))?;
+        let snapshot = v
+            .get("snapshot")
+            .ok_or_else(|| err("snapshot envelope missing 'snapshot'".to_string()))?;
+        let (bundle_id, bundle_tenor_version) = bundle_identity(bundle);
+        Ok(SnapshotEnvelope {
+            bundle_id,
+            bundle_tenor_version,
+            snapshot: Snapshot::from_json(snapshot)?,
+        })
+    }
+
+    /// Unwrap the snapshot if it was taken against `bundle`.
+    pub fn open(self, bundle: &serde_json::Value) -> Result<Snapshot, EvalError> {
+        let (id, tenor_version) = bundle_identity(bundle);
+        if id != self.bundle_id || tenor_version != self.bundle_tenor_version {
+            return Err(EvalError::SnapshotMismatch {
+                expected: format!("{}@{}", id, tenor_version),
+                found: format!("{}@{}", self.bundle_id, self.bundle_tenor_version),
+            });
+        }
+        Ok(self.snapshot)
+    }
+}
+
+fn bundle_identity(bundle: &serde_json::Value) -> (String, String) {
+    let field = |name: &str| {
+        bundle
+            .get(name)
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string()
+    };
+    (field("id"), field("tenor_version"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance::{Conversion, VerdictProvenance};
+    use crate::types::{Value, VerdictInstance};
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn sample() -> Snapshot {
+        let mut facts = FactSet::new();
+        facts.insert("approved", Value::Bool(true));
+        facts.insert("count", Value::Int(3));
+        facts.insert("rate", Value::Decimal(Decimal::new(1050, 3)));
+        facts.insert(
+            "limit",
+            Value::Money {
+                amount: Decimal::new(1000000, 2),
+                currency: "USD".to_string(),
+            },
+        );
+        facts.insert(
+            "window",
+            Value::Duration {
+                value: 30,
+                unit: "days".to_string(),
+            },
+        );
+        facts.insert("opened", Value::Date("2024-01-15".to_string()));
+        facts.insert("tier", Value::Enum("gold".to_string()));
+        facts.insert(
+            "items",
+            Value::List(vec![Value::Record(BTreeMap::from([
+                ("sku".to_string(), Value::Text("A-1".to_string())),
+                ("qty".to_string(), Value::Int(2)),
+            ]))]),
+        );
+        facts.insert(
+            "outcome",
+            Value::TaggedUnion {
+                tag: "ok".to_string(),
+                payload: Box::new(Value::DateTime("2024-01-15T10:00:00Z".to_string())),
+            },
+        );
+        let mut verdicts = VerdictSet::new();
+        verdicts.push(VerdictInstance {
+            verdict_type: "within_limit".into(),
+            payload: Value::Bool(true),
+            provenance: VerdictProvenance {
+                rule_id: "check_limit".to_string(),
+                stratum: 1,
+                facts_used: vec!["limit".to_string()],
+                verdicts_used: vec![],
+                conversions: vec![Conversion {
+                    rate_fact: "rate".to_string(),
+                    from_currency: "EUR".to_string(),
+                    to_currency: "USD".to_string(),
+                    rate: Decimal::new(1050, 3),
+                }],
+            },
+        });
+        Snapshot { facts, verdicts }
+    }
+
+    fn bundle(id: &str) -> serde_json::Value {
+        json!({ "id": id, "kind": "Bundle", "tenor_version": "1.0.0", "constructs": [] })
+    }
+
+    #[test]
+    fn snapshot_json_round_trips() {
+        let snapshot = sample();
+        let restored = Snapshot::from_json(&snapshot.to_json()).unwrap();
+        assert_eq!(restored.facts.0, snapshot.facts.0);
+        assert_eq!(restored.verdicts.to_json(), snapshot.verdicts.to_json());
+        let rate = restored.facts.get("rate").unwrap();
+        assert_eq!(rate, &Value::Decimal(Decimal::new(1050, 3)));
+        let verdict = restored.verdicts.get_verdict("within_limit").unwrap();
+        assert_eq!(
+            verdict.provenance.conversions[0].rate,
+            Decimal::new(1050, 3)
+        );
+    }
+
+    #[test]
+    fn envelope_round_trips_through_text() {
+        let envelope = SnapshotEnvelope::new(&bundle("loans"), sample());
+        let text = envelope.to_json().to_string();
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        let restored = SnapshotEnvelope::from_json(&parsed).unwrap();
+        assert_eq!(restored.bundle_id, "loans");
+        assert_eq!(restored.bundle_tenor_version, "1.0.0");
+        let snapshot = restored.open(&bundle("loans")).unwrap();
+        assert!(snapshot.verdicts.has_verdict("within_limit"));
+    }
+
+    #[test]
+    fn envelope_rejects_other_bundle_and_newer_format() {
+        let envelope = SnapshotEnvelope::new(&bundle("loans"), sample());
+        let err = envelope.clone().open(&bundle("leases")).unwrap_err();
+        assert!(matches!(err, EvalError::SnapshotMismatch { .. }));
+
+        let mut newer = envelope.to_json();
+        newer["format_version"] = json!(SNAPSHOT_FORMAT_VERSION + 1);
+        assert!(SnapshotEnvelope::from_json(&newer).is_err());
+        assert!(SnapshotEnvelope::from_json(&json!({ "facts": {} })).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::operation::EntityStateMap;
+use crate::operation::{EntityAttributeMap, EntityStateMap, EntityVersions};
 use crate::types::*;
 
 fn bool_type() -> TypeSpec {
@@ -7,6 +7,7 @@ fn bool_type() -> TypeSpec {
         base: "Bool".to_string(),
         precision: None,
         scale: None,
+        rounding: None,
         currency: None,
         min: None,
         max: None,
@@ -49,9 +50,12 @@ fn simple_flow_entry_operation_terminal() {
         },
         effects: vec![Effect {
             entity_id: "order".to_string(),
+            kind: EffectKind::Transition,
             from: "pending".to_string(),
             to: "approved".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["approved".to_string()],
@@ -78,6 +82,7 @@ fn simple_flow_entry_operation_terminal() {
             on_failure: FailureHandler::Terminate {
                 outcome: "approval_failed".to_string(),
             },
+            timeout: None,
         }],
     };
 
@@ -87,6 +92,7 @@ fn simple_flow_entry_operation_terminal() {
             states: vec!["pending".to_string(), "approved".to_string()],
             initial: "pending".to_string(),
             transitions: vec![],
+            attributes: BTreeMap::new(),
         }],
         vec![operation],
         vec![flow.clone()],
@@ -108,8 +114,9 @@ fn simple_flow_entry_operation_terminal() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
     assert_eq!(result.outcome, "order_approved");
@@ -156,13 +163,14 @@ fn branch_flow_true_path() {
     // Verdict is present -> should take true path
     let mut verdicts = VerdictSet::new();
     verdicts.push(VerdictInstance {
-        verdict_type: "is_valid".to_string(),
+        verdict_type: "is_valid".into(),
         payload: Value::Bool(true),
         provenance: crate::provenance::VerdictProvenance {
             rule_id: "check".to_string(),
             stratum: 0,
             facts_used: vec![],
             verdicts_used: vec![],
+            conversions: vec![],
         },
     });
 
@@ -177,8 +185,9 @@ fn branch_flow_true_path() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
     assert_eq!(result.outcome, "valid_path");
@@ -218,8 +227,9 @@ fn branch_flow_false_path() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
     assert_eq!(result.outcome, "invalid_path");
@@ -258,9 +268,12 @@ fn frozen_verdict_semantics_entity_change_does_not_affect_verdicts() {
         },
         effects: vec![Effect {
             entity_id: "order".to_string(),
+            kind: EffectKind::Transition,
             from: "pending".to_string(),
             to: "rejected".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["rejected".to_string()],
@@ -287,6 +300,7 @@ fn frozen_verdict_semantics_entity_change_does_not_affect_verdicts() {
                 on_failure: FailureHandler::Terminate {
                     outcome: "flow_error".to_string(),
                 },
+                timeout: None,
             },
             // Step 2: Check verdict that was set at initiation
             // Even though entity state changed, verdict MUST still be present
@@ -310,6 +324,7 @@ fn frozen_verdict_semantics_entity_change_does_not_affect_verdicts() {
             states: vec!["pending".to_string(), "rejected".to_string()],
             initial: "pending".to_string(),
             transitions: vec![],
+            attributes: BTreeMap::new(),
         }],
         vec![operation],
         vec![flow.clone()],
@@ -319,13 +334,14 @@ fn frozen_verdict_semantics_entity_change_does_not_affect_verdicts() {
     // (In a real system, this would have been computed from rules)
     let mut verdicts = VerdictSet::new();
     verdicts.push(VerdictInstance {
-        verdict_type: "order_eligible".to_string(),
+        verdict_type: "order_eligible".into(),
         payload: Value::Bool(true),
         provenance: crate::provenance::VerdictProvenance {
             rule_id: "eligibility_check".to_string(),
             stratum: 0,
             facts_used: vec!["order_status".to_string()],
             verdicts_used: vec![],
+            conversions: vec![],
         },
     });
 
@@ -345,8 +361,9 @@ fn frozen_verdict_semantics_entity_change_does_not_affect_verdicts() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
 
@@ -395,9 +412,12 @@ fn sub_flow_inherits_parent_snapshot() {
         },
         effects: vec![Effect {
             entity_id: "order".to_string(),
+            kind: EffectKind::Transition,
             from: "approved".to_string(),
             to: "finalized".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["done".to_string()],
@@ -442,6 +462,7 @@ fn sub_flow_inherits_parent_snapshot() {
                 on_failure: FailureHandler::Terminate {
                     outcome: "parent_error".to_string(),
                 },
+                timeout: None,
             },
             FlowStep::SubFlowStep {
                 id: "step_sub".to_string(),
@@ -463,6 +484,7 @@ fn sub_flow_inherits_parent_snapshot() {
             states: vec!["approved".to_string(), "finalized".to_string()],
             initial: "approved".to_string(),
             transitions: vec![],
+            attributes: BTreeMap::new(),
         }],
         vec![operation],
         vec![parent_flow.clone(), sub_flow],
@@ -471,13 +493,14 @@ fn sub_flow_inherits_parent_snapshot() {
     // Snapshot with parent_verdict present
     let mut verdicts = VerdictSet::new();
     verdicts.push(VerdictInstance {
-        verdict_type: "parent_verdict".to_string(),
+        verdict_type: "parent_verdict".into(),
         payload: Value::Bool(true),
         provenance: crate::provenance::VerdictProvenance {
             rule_id: "parent_rule".to_string(),
             stratum: 0,
             facts_used: vec![],
             verdicts_used: vec![],
+            conversions: vec![],
         },
     });
 
@@ -497,8 +520,9 @@ fn sub_flow_inherits_parent_snapshot() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
 
@@ -525,9 +549,12 @@ fn operation_step_on_failure_terminates() {
         },
         effects: vec![Effect {
             entity_id: "order".to_string(),
+            kind: EffectKind::Transition,
             from: "pending".to_string(),
             to: "approved".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["approved".to_string()],
@@ -554,6 +581,7 @@ fn operation_step_on_failure_terminates() {
             on_failure: FailureHandler::Terminate {
                 outcome: "failure_handled".to_string(),
             },
+            timeout: None,
         }],
     };
 
@@ -567,6 +595,7 @@ fn operation_step_on_failure_terminates() {
             ],
             initial: "draft".to_string(),
             transitions: vec![],
+            attributes: BTreeMap::new(),
         }],
         vec![operation],
         vec![flow.clone()],
@@ -589,13 +618,109 @@ fn operation_step_on_failure_terminates() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
     assert_eq!(result.outcome, "failure_handled");
 }
 
+#[test]
+fn concurrent_conflict_routes_to_on_failure() {
+    let operation = Operation {
+        id: "approve".to_string(),
+        allowed_personas: vec!["admin".to_string()],
+        precondition: Predicate::Literal {
+            value: Value::Bool(true),
+            type_spec: bool_type(),
+        },
+        effects: vec![Effect {
+            entity_id: "order".to_string(),
+            kind: EffectKind::Transition,
+            from: "pending".to_string(),
+            to: "approved".to_string(),
+            outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
+        }],
+        error_contract: vec![],
+        outcomes: vec!["approved".to_string()],
+    };
+
+    let flow = Flow {
+        id: "approve_flow".to_string(),
+        snapshot: "at_initiation".to_string(),
+        entry: "step_approve".to_string(),
+        steps: vec![FlowStep::OperationStep {
+            id: "step_approve".to_string(),
+            op: "approve".to_string(),
+            persona: "admin".to_string(),
+            outcomes: [(
+                "approved".to_string(),
+                StepTarget::Terminal {
+                    outcome: "success".to_string(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+            on_failure: FailureHandler::Terminate {
+                outcome: "retry_later".to_string(),
+            },
+            timeout: None,
+        }],
+    };
+
+    let contract = make_contract_with(
+        vec![Entity {
+            id: "order".to_string(),
+            states: vec!["pending".to_string(), "approved".to_string()],
+            initial: "pending".to_string(),
+            transitions: vec![],
+            attributes: BTreeMap::new(),
+        }],
+        vec![operation],
+        vec![flow.clone()],
+    );
+
+    let snapshot = Snapshot {
+        facts: FactSet::new(),
+        verdicts: VerdictSet::new(),
+    };
+
+    // The order is still "pending", but another executor has moved it
+    // through a round trip since the caller read it at version 1.
+    let mut entity_states = crate::operation::single_instance(
+        [("order".to_string(), "pending".to_string())]
+            .into_iter()
+            .collect(),
+    );
+    let key = crate::operation::instance_key("order", crate::operation::DEFAULT_INSTANCE_ID);
+    let mut versions = EntityVersions::default();
+    versions.current.insert(key.clone(), 3);
+    versions.expected.insert(key.clone(), 1);
+
+    let result = execute_flow(
+        &flow,
+        &contract,
+        &snapshot,
+        &mut entity_states,
+        &mut EntityAttributeMap::new(),
+        &InstanceBindingMap::new(),
+        FlowOptions {
+            entity_versions: Some(&versions),
+            ..FlowOptions::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(result.outcome, "retry_later");
+    assert!(result.steps_executed[0]
+        .result
+        .contains("modified concurrently"));
+    assert!(result.entity_state_changes.is_empty());
+    assert_eq!(entity_states[&key], "pending");
+}
+
 // ──────────────────────────────────────
 // Multi-step flow: operation -> branch -> terminal
 // ──────────────────────────────────────
@@ -611,9 +736,12 @@ fn multi_step_flow_operation_then_branch() {
         },
         effects: vec![Effect {
             entity_id: "order".to_string(),
+            kind: EffectKind::Transition,
             from: "draft".to_string(),
             to: "submitted".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["submitted".to_string()],
@@ -639,6 +767,7 @@ fn multi_step_flow_operation_then_branch() {
                 on_failure: FailureHandler::Terminate {
                     outcome: "submit_failed".to_string(),
                 },
+                timeout: None,
             },
             FlowStep::BranchStep {
                 id: "step_check".to_string(),
@@ -660,6 +789,7 @@ fn multi_step_flow_operation_then_branch() {
             states: vec!["draft".to_string(), "submitted".to_string()],
             initial: "draft".to_string(),
             transitions: vec![],
+            attributes: BTreeMap::new(),
         }],
         vec![operation],
         vec![flow.clone()],
@@ -668,13 +798,14 @@ fn multi_step_flow_operation_then_branch() {
     // Snapshot with "order_valid" verdict
     let mut verdicts = VerdictSet::new();
     verdicts.push(VerdictInstance {
-        verdict_type: "order_valid".to_string(),
+        verdict_type: "order_valid".into(),
         payload: Value::Bool(true),
         provenance: crate::provenance::VerdictProvenance {
             rule_id: "validate".to_string(),
             stratum: 0,
             facts_used: vec![],
             verdicts_used: vec![],
+            conversions: vec![],
         },
     });
 
@@ -694,8 +825,9 @@ fn multi_step_flow_operation_then_branch() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
     assert_eq!(result.outcome, "submitted_valid");
@@ -763,6 +895,7 @@ fn flow_step_limit_returns_flow_error() {
                 on_failure: FailureHandler::Terminate {
                     outcome: "error".to_string(),
                 },
+                timeout: None,
             },
             FlowStep::OperationStep {
                 id: "step_b".to_string(),
@@ -779,6 +912,7 @@ fn flow_step_limit_returns_flow_error() {
                 on_failure: FailureHandler::Terminate {
                     outcome: "error".to_string(),
                 },
+                timeout: None,
             },
         ],
     };
@@ -797,8 +931,9 @@ fn flow_step_limit_returns_flow_error() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     );
     assert!(result.is_err());
     match result.unwrap_err() {
@@ -858,6 +993,7 @@ fn parallel_branch_entity_conflict_last_writer_wins() {
         ],
         initial: "initial".to_string(),
         transitions: vec![],
+        attributes: BTreeMap::new(),
     };
 
     // op_to_a: transitions Order from initial -> state_a
@@ -870,9 +1006,12 @@ fn parallel_branch_entity_conflict_last_writer_wins() {
         },
         effects: vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "initial".to_string(),
             to: "state_a".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["success".to_string()],
@@ -888,9 +1027,12 @@ fn parallel_branch_entity_conflict_last_writer_wins() {
         },
         effects: vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "initial".to_string(),
             to: "state_b".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["success".to_string()],
@@ -924,6 +1066,7 @@ fn parallel_branch_entity_conflict_last_writer_wins() {
                         on_failure: FailureHandler::Terminate {
                             outcome: "branch_a_failed".to_string(),
                         },
+                        timeout: None,
                     }],
                 },
                 ParallelBranch {
@@ -946,6 +1089,7 @@ fn parallel_branch_entity_conflict_last_writer_wins() {
                         on_failure: FailureHandler::Terminate {
                             outcome: "branch_b_failed".to_string(),
                         },
+                        timeout: None,
                     }],
                 },
             ],
@@ -979,8 +1123,9 @@ fn parallel_branch_entity_conflict_last_writer_wins() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     );
 
     // ── Characterize actual behavior ──
@@ -1110,6 +1255,7 @@ fn configurable_max_steps_triggers_at_custom_limit() {
                 on_failure: FailureHandler::Terminate {
                     outcome: "error".to_string(),
                 },
+                timeout: None,
             },
             FlowStep::OperationStep {
                 id: "step_b".to_string(),
@@ -1126,6 +1272,7 @@ fn configurable_max_steps_triggers_at_custom_limit() {
                 on_failure: FailureHandler::Terminate {
                     outcome: "error".to_string(),
                 },
+                timeout: None,
             },
         ],
     };
@@ -1144,8 +1291,12 @@ fn configurable_max_steps_triggers_at_custom_limit() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        Some(5),
+        FlowOptions {
+            max_steps: Some(5),
+            ..FlowOptions::default()
+        },
     );
     assert!(result.is_err());
     match result.unwrap_err() {
@@ -1168,8 +1319,9 @@ fn configurable_max_steps_triggers_at_custom_limit() {
         &contract,
         &snapshot,
         &mut entity_states2,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     );
     assert!(result2.is_err());
     match result2.unwrap_err() {
@@ -1183,3 +1335,500 @@ fn configurable_max_steps_triggers_at_custom_limit() {
         other => panic!("expected FlowError with default limit, got {:?}", other),
     }
 }
+
+// ──────────────────────────────────────
+// Pausing handoffs: start_flow / resume_flow
+// ──────────────────────────────────────
+
+fn transition_op(id: &str, persona: &str, from: &str, to: &str) -> Operation {
+    Operation {
+        id: id.to_string(),
+        allowed_personas: vec![persona.to_string()],
+        precondition: Predicate::Literal {
+            value: Value::Bool(true),
+            type_spec: bool_type(),
+        },
+        effects: vec![Effect {
+            entity_id: "order".to_string(),
+            kind: EffectKind::Transition,
+            from: from.to_string(),
+            to: to.to_string(),
+            outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
+        }],
+        error_contract: vec![],
+        outcomes: vec!["done".to_string()],
+    }
+}
+
+fn op_step(id: &str, op: &str, persona: &str, next: StepTarget) -> FlowStep {
+    FlowStep::OperationStep {
+        id: id.to_string(),
+        op: op.to_string(),
+        persona: persona.to_string(),
+        outcomes: BTreeMap::from([("done".to_string(), next)]),
+        on_failure: FailureHandler::Terminate {
+            outcome: "failed".to_string(),
+        },
+        timeout: None,
+    }
+}
+
+fn pausing_contract() -> Contract {
+    pausing_contract_with(None)
+}
+
+fn pausing_contract_with(handoff_timeout: Option<Timeout>) -> Contract {
+    let flow = Flow {
+        id: "approval_flow".to_string(),
+        snapshot: "at_initiation".to_string(),
+        entry: "step_submit".to_string(),
+        steps: vec![
+            op_step(
+                "step_submit",
+                "submit",
+                "admin",
+                StepTarget::StepRef("step_handoff".to_string()),
+            ),
+            FlowStep::HandoffStep {
+                id: "step_handoff".to_string(),
+                from_persona: "admin".to_string(),
+                to_persona: "system".to_string(),
+                next: "step_approve".to_string(),
+                pause: true,
+                timeout: handoff_timeout,
+            },
+            op_step(
+                "step_approve",
+                "approve",
+                "system",
+                StepTarget::Terminal {
+                    outcome: "approved".to_string(),
+                },
+            ),
+        ],
+    };
+    make_contract_with(
+        vec![Entity {
+            id: "order".to_string(),
+            states: vec![
+                "pending".to_string(),
+                "submitted".to_string(),
+                "approved".to_string(),
+            ],
+            initial: "pending".to_string(),
+            transitions: vec![],
+            attributes: BTreeMap::new(),
+        }],
+        vec![
+            transition_op("submit", "admin", "pending", "submitted"),
+            transition_op("approve", "system", "submitted", "approved"),
+        ],
+        vec![flow],
+    )
+}
+
+fn empty_snapshot() -> Snapshot {
+    Snapshot {
+        facts: FactSet::new(),
+        verdicts: VerdictSet::new(),
+    }
+}
+
+#[test]
+fn execute_flow_ignores_handoff_pause() {
+    let contract = pausing_contract();
+    let mut entity_states = crate::operation::init_entity_states(&contract);
+    let result = execute_flow(
+        contract.get_flow("approval_flow").unwrap(),
+        &contract,
+        &empty_snapshot(),
+        &mut entity_states,
+        &mut EntityAttributeMap::new(),
+        &InstanceBindingMap::new(),
+        FlowOptions {
+            correlation_id: Some("order-42"),
+            ..FlowOptions::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(result.outcome, "approved");
+    assert_eq!(result.steps_executed[1].result, "handoff");
+    assert_eq!(result.correlation_id.as_deref(), Some("order-42"));
+    assert!(result
+        .steps_executed
+        .iter()
+        .all(|s| s.correlation_id.as_deref() == Some("order-42")));
+}
+
+#[test]
+fn paused_flow_resumes_from_serialized_continuation() {
+    let contract = pausing_contract();
+    let bundle = serde_json::json!({ "id": "orders", "tenor_version": "1.0.0" });
+
+    let run = start_flow(
+        &contract,
+        &bundle,
+        empty_snapshot(),
+        "approval_flow",
+        "admin",
+        crate::operation::init_entity_states(&contract),
+        InstanceBindingMap::new(),
+        FlowOptions {
+            correlation_id: Some("order-42"),
+            ..FlowOptions::default()
+        },
+    )
+    .unwrap();
+    let FlowRun::Suspended(suspended) = run else {
+        panic!("expected the flow to pause at the handoff");
+    };
+    let results: Vec<&str> = suspended
+        .steps_executed
+        .iter()
+        .map(|s| s.result.as_str())
+        .collect();
+    assert_eq!(results, vec!["done", "paused"]);
+    assert_eq!(suspended.continuation.to_persona, "system");
+    assert_eq!(suspended.continuation.resume_at, "step_approve");
+
+    // Round-trip through text, as a caller persisting the continuation would.
+    let text = suspended.continuation.to_json().to_string();
+    let continuation = FlowContinuation::from_json(&serde_json::from_str(&text).unwrap()).unwrap();
+    assert_eq!(
+        crate::operation::get_instance_state(
+            &continuation.entity_states,
+            "order",
+            crate::operation::DEFAULT_INSTANCE_ID
+        )
+        .unwrap(),
+        "submitted"
+    );
+    assert_eq!(continuation.correlation_id.as_deref(), Some("order-42"));
+
+    let err = resume_flow(
+        &contract,
+        &bundle,
+        continuation.clone(),
+        "admin",
+        &EntityVersions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EvalError::FlowError { .. }));
+
+    let other_bundle = serde_json::json!({ "id": "invoices", "tenor_version": "1.0.0" });
+    let err = resume_flow(
+        &contract,
+        &other_bundle,
+        continuation.clone(),
+        "system",
+        &EntityVersions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EvalError::SnapshotMismatch { .. }));
+
+    let FlowRun::Completed(done) = resume_flow(
+        &contract,
+        &bundle,
+        continuation,
+        "system",
+        &EntityVersions::default(),
+    )
+    .unwrap() else {
+        panic!("expected the resumed flow to complete");
+    };
+    assert_eq!(done.flow_result.outcome, "approved");
+    assert_eq!(
+        done.flow_result.initiating_persona.as_deref(),
+        Some("admin")
+    );
+    assert_eq!(done.flow_result.steps_executed[0].step_type, "resume");
+    assert_eq!(done.flow_result.entity_state_changes.len(), 1);
+    // The resumed segment belongs to the same business transaction.
+    assert_eq!(done.flow_result.correlation_id.as_deref(), Some("order-42"));
+    assert!(done
+        .flow_result
+        .steps_executed
+        .iter()
+        .all(|s| s.correlation_id.as_deref() == Some("order-42")));
+}
+
+#[test]
+fn resumed_flow_checks_entity_versions() {
+    let contract = pausing_contract();
+    let bundle = serde_json::json!({ "id": "orders", "tenor_version": "1.0.0" });
+
+    let run = start_flow(
+        &contract,
+        &bundle,
+        empty_snapshot(),
+        "approval_flow",
+        "admin",
+        crate::operation::init_entity_states(&contract),
+        InstanceBindingMap::new(),
+        FlowOptions::default(),
+    )
+    .unwrap();
+    let FlowRun::Suspended(suspended) = run else {
+        panic!("expected the flow to pause at the handoff");
+    };
+
+    // Another executor moved the order while the flow was suspended.
+    let key = crate::operation::instance_key("order", crate::operation::DEFAULT_INSTANCE_ID);
+    let mut versions = EntityVersions::default();
+    versions.current.insert(key.clone(), 3);
+    versions.expected.insert(key, 2);
+
+    let FlowRun::Completed(done) = resume_flow(
+        &contract,
+        &bundle,
+        suspended.continuation,
+        "system",
+        &versions,
+    )
+    .unwrap() else {
+        panic!("expected the resumed flow to complete");
+    };
+    assert_eq!(done.flow_result.outcome, "failed");
+    assert!(done.flow_result.steps_executed[1]
+        .result
+        .contains("concurrent"));
+    assert!(done.flow_result.entity_state_changes.is_empty());
+}
+
+// ──────────────────────────────────────
+// Step timeouts
+// ──────────────────────────────────────
+
+const PAST: &str = "2000-01-01T00:00:00Z";
+const FUTURE: &str = "2999-01-01T00:00:00Z";
+
+fn deadline_snapshot(deadline: &str) -> Snapshot {
+    let mut facts = FactSet::new();
+    facts.insert("review_by", Value::DateTime(deadline.to_string()));
+    Snapshot {
+        facts,
+        verdicts: VerdictSet::new(),
+    }
+}
+
+fn review_timeout(on_timeout: TimeoutAction) -> Option<Timeout> {
+    Some(Timeout {
+        deadline: "review_by".to_string(),
+        on_timeout,
+    })
+}
+
+fn timed_review_contract(on_timeout: TimeoutAction) -> Contract {
+    let flow = Flow {
+        id: "review_flow".to_string(),
+        snapshot: "at_initiation".to_string(),
+        entry: "step_submit".to_string(),
+        steps: vec![
+            FlowStep::OperationStep {
+                id: "step_submit".to_string(),
+                op: "submit".to_string(),
+                persona: "admin".to_string(),
+                outcomes: BTreeMap::from([(
+                    "done".to_string(),
+                    StepTarget::Terminal {
+                        outcome: "submitted".to_string(),
+                    },
+                )]),
+                on_failure: FailureHandler::Terminate {
+                    outcome: "failed".to_string(),
+                },
+                timeout: review_timeout(on_timeout),
+            },
+            op_step(
+                "step_escalated",
+                "approve",
+                "system",
+                StepTarget::Terminal {
+                    outcome: "escalated".to_string(),
+                },
+            ),
+        ],
+    };
+    make_contract_with(
+        vec![Entity {
+            id: "order".to_string(),
+            states: vec![
+                "pending".to_string(),
+                "submitted".to_string(),
+                "approved".to_string(),
+            ],
+            initial: "pending".to_string(),
+            transitions: vec![],
+            attributes: BTreeMap::new(),
+        }],
+        vec![
+            transition_op("submit", "admin", "pending", "submitted"),
+            transition_op("approve", "system", "pending", "approved"),
+        ],
+        vec![flow],
+    )
+}
+
+fn run_review(contract: &Contract, deadline: &str) -> FlowResult {
+    let mut entity_states = crate::operation::init_entity_states(contract);
+    execute_flow(
+        contract.get_flow("review_flow").unwrap(),
+        contract,
+        &deadline_snapshot(deadline),
+        &mut entity_states,
+        &mut EntityAttributeMap::new(),
+        &InstanceBindingMap::new(),
+        FlowOptions::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn step_runs_normally_before_its_deadline() {
+    let contract = timed_review_contract(TimeoutAction::Goto(StepTarget::Terminal {
+        outcome: "expired".to_string(),
+    }));
+    let result = run_review(&contract, FUTURE);
+    assert_eq!(result.outcome, "submitted");
+    assert_eq!(result.steps_executed[0].step_type, "operation");
+}
+
+#[test]
+fn passed_deadline_routes_to_on_timeout_target() {
+    let contract = timed_review_contract(TimeoutAction::Goto(StepTarget::Terminal {
+        outcome: "expired".to_string(),
+    }));
+    let result = run_review(&contract, PAST);
+    assert_eq!(result.outcome, "expired");
+    assert_eq!(result.steps_executed.len(), 1);
+    assert_eq!(result.steps_executed[0].step_type, "timeout");
+    assert!(result.entity_state_changes.is_empty());
+}
+
+#[test]
+fn fixed_clock_decides_whether_a_deadline_passed() {
+    use crate::clock::{with_clock, FixedClock};
+    use std::sync::Arc;
+
+    let contract = timed_review_contract(TimeoutAction::Goto(StepTarget::Terminal {
+        outcome: "expired".to_string(),
+    }));
+    let at = |now: &str| {
+        let clock = Arc::new(FixedClock::parse(now).unwrap());
+        with_clock(clock, || {
+            run_review(&contract, "2030-06-01T00:00:00Z").outcome
+        })
+    };
+    assert_eq!(at("2030-05-31T23:59:59Z"), "submitted");
+    assert_eq!(at("2030-06-01T00:00:00Z"), "expired");
+}
+
+#[test]
+fn passed_deadline_runs_on_timeout_handler() {
+    let contract = timed_review_contract(TimeoutAction::Handle(FailureHandler::Escalate {
+        to_persona: "system".to_string(),
+        next: "step_escalated".to_string(),
+    }));
+    let result = run_review(&contract, PAST);
+    assert_eq!(result.outcome, "escalated");
+    let types: Vec<&str> = result
+        .steps_executed
+        .iter()
+        .map(|s| s.step_type.as_str())
+        .collect();
+    assert_eq!(types, vec!["timeout", "escalation", "operation"]);
+}
+
+#[test]
+fn timeout_requires_a_datetime_deadline_fact() {
+    let contract = timed_review_contract(TimeoutAction::Goto(StepTarget::Terminal {
+        outcome: "expired".to_string(),
+    }));
+    let mut entity_states = crate::operation::init_entity_states(&contract);
+    let err = execute_flow(
+        contract.get_flow("review_flow").unwrap(),
+        &contract,
+        &empty_snapshot(),
+        &mut entity_states,
+        &mut EntityAttributeMap::new(),
+        &InstanceBindingMap::new(),
+        FlowOptions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EvalError::FlowError { .. }));
+}
+
+#[test]
+fn suspended_flow_expires_through_handoff_timeout() {
+    let contract =
+        pausing_contract_with(review_timeout(TimeoutAction::Goto(StepTarget::Terminal {
+            outcome: "abandoned".to_string(),
+        })));
+    let bundle = serde_json::json!({ "id": "orders", "tenor_version": "1.0.0" });
+
+    let run = start_flow(
+        &contract,
+        &bundle,
+        deadline_snapshot(FUTURE),
+        "approval_flow",
+        "admin",
+        crate::operation::init_entity_states(&contract),
+        InstanceBindingMap::new(),
+        FlowOptions::default(),
+    )
+    .unwrap();
+    let FlowRun::Suspended(suspended) = run else {
+        panic!("expected the flow to pause at the handoff");
+    };
+    let continuation = suspended.continuation;
+    assert_eq!(continuation.deadline.as_deref(), Some(FUTURE));
+    assert!(!continuation.deadline_passed());
+    assert!(continuation.time_until_deadline().unwrap() > std::time::Duration::ZERO);
+
+    let text = continuation.to_json().to_string();
+    let mut expired = FlowContinuation::from_json(&serde_json::from_str(&text).unwrap()).unwrap();
+    assert_eq!(expired.deadline.as_deref(), Some(FUTURE));
+    expired.deadline = Some(PAST.to_string());
+    assert!(expired.deadline_passed());
+
+    let err = resume_flow(
+        &contract,
+        &bundle,
+        expired.clone(),
+        "system",
+        &EntityVersions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EvalError::FlowError { .. }));
+
+    let FlowRun::Completed(done) =
+        expire_flow(&contract, &bundle, expired, &EntityVersions::default()).unwrap()
+    else {
+        panic!("expected the expired flow to complete");
+    };
+    assert_eq!(done.flow_result.outcome, "abandoned");
+    assert_eq!(done.flow_result.steps_executed[0].step_type, "timeout");
+    assert_eq!(
+        done.flow_result.initiating_persona.as_deref(),
+        Some("admin")
+    );
+
+    // A handoff whose deadline has already passed never pauses.
+    let run = start_flow(
+        &contract,
+        &bundle,
+        deadline_snapshot(PAST),
+        "approval_flow",
+        "admin",
+        crate::operation::init_entity_states(&contract),
+        InstanceBindingMap::new(),
+        FlowOptions::default(),
+    )
+    .unwrap();
+    let FlowRun::Completed(done) = run else {
+        panic!("expected the passed deadline to fire instead of pausing");
+    };
+    assert_eq!(done.flow_result.outcome, "abandoned");
+}
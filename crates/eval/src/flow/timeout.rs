@@ -0,0 +1,148 @@
+//! Step deadlines.
+//!
+//! An OperationStep or HandoffStep may declare a `timeout` naming a
+//! DateTime fact. The deadline is read from the frozen snapshot like any
+//! other fact, so a simulation run with a past deadline takes the timeout
+//! path deterministically. When a step is reached after its deadline, the
+//! step does not run; its `on_timeout` action fires instead, either routing
+//! to a step target or running a failure handler. Deadlines are compared
+//! against `clock::now()`, so a fixed clock makes them reproducible.
+
+use std::collections::HashMap;
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use super::{handle_failure, FlowResult, Snapshot, StepRecord};
+use crate::operation::{
+    EffectRecord, EntityAttributeMap, EntityStateMap, EntityVersions, InstanceBindingMap,
+};
+use crate::symbol::Symbol;
+use crate::types::{
+    EvalError, FailureHandler, FlowStep, Operation, StepTarget, Timeout, TimeoutAction, Value,
+};
+
+/// The step's timeout, if it declares one.
+pub(crate) fn step_timeout(step: &FlowStep) -> Option<&Timeout> {
+    match step {
+        FlowStep::OperationStep { timeout, .. } | FlowStep::HandoffStep { timeout, .. } => {
+            timeout.as_ref()
+        }
+        _ => None,
+    }
+}
+
+/// Resolve `timeout.deadline` against the snapshot's facts.
+pub(crate) fn resolve_deadline(
+    timeout: &Timeout,
+    snapshot: &Snapshot,
+    flow_id: &str,
+) -> Result<OffsetDateTime, EvalError> {
+    let err = |message: String| EvalError::FlowError {
+        flow_id: flow_id.to_string(),
+        message,
+    };
+    match snapshot.facts.get(&timeout.deadline) {
+        Some(Value::DateTime(s)) => parse_datetime(s).ok_or_else(|| {
+            err(format!(
+                "deadline fact '{}' is not a valid DateTime: '{}'",
+                timeout.deadline, s
+            ))
+        }),
+        Some(other) => Err(err(format!(
+            "deadline fact '{}' must be a DateTime, got {}",
+            timeout.deadline,
+            other.type_name()
+        ))),
+        None => Err(err(format!(
+            "deadline fact '{}' has no value",
+            timeout.deadline
+        ))),
+    }
+}
+
+/// Parse an RFC 3339 timestamp. DateTime facts without an offset are
+/// taken to be UTC.
+pub(crate) fn parse_datetime(s: &str) -> Option<OffsetDateTime> {
+    if let Ok(dt) = OffsetDateTime::parse(s, &Rfc3339) {
+        return Some(dt);
+    }
+    let format = time::macros::format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+    time::PrimitiveDateTime::parse(s.get(..19)?, &format)
+        .ok()
+        .map(|dt| dt.assume_utc())
+}
+
+pub(crate) fn format_datetime(dt: OffsetDateTime) -> String {
+    dt.format(&Rfc3339)
+        .unwrap_or_else(|_| dt.unix_timestamp().to_string())
+}
+
+/// Where a flow goes after a timeout fires.
+pub(crate) enum TimeoutRoute {
+    Finish(FlowResult),
+    Continue(String),
+}
+
+/// Record that `step_id` timed out and run its `on_timeout` action.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn fire_timeout(
+    timeout: &Timeout,
+    step_id: &str,
+    op_index: &HashMap<&str, &Operation>,
+    snapshot: &Snapshot,
+    entity_states: &mut EntityStateMap,
+    entity_attributes: &mut EntityAttributeMap,
+    instance_bindings: &InstanceBindingMap,
+    entity_versions: &EntityVersions,
+    steps_executed: &mut Vec<StepRecord>,
+    entity_changes_all: &mut Vec<EffectRecord>,
+) -> Result<TimeoutRoute, EvalError> {
+    steps_executed.push(StepRecord {
+        step_id: Symbol::new(step_id),
+        step_type: "timeout".to_string(),
+        result: format!("deadline '{}' passed", timeout.deadline),
+        instance_bindings: std::collections::BTreeMap::new(),
+        correlation_id: None,
+    });
+    let target = match &timeout.on_timeout {
+        TimeoutAction::Goto(target) => target,
+        TimeoutAction::Handle(handler) => {
+            if let Some(result) = handle_failure(
+                handler,
+                step_id,
+                op_index,
+                snapshot,
+                entity_states,
+                entity_attributes,
+                instance_bindings,
+                entity_versions,
+                steps_executed,
+                entity_changes_all,
+            )? {
+                return Ok(TimeoutRoute::Finish(result));
+            }
+            return match handler {
+                FailureHandler::Compensate {
+                    then: StepTarget::StepRef(next_id),
+                    ..
+                } => Ok(TimeoutRoute::Continue(next_id.clone())),
+                FailureHandler::Escalate { next, .. } => Ok(TimeoutRoute::Continue(next.clone())),
+                _ => Err(EvalError::FlowError {
+                    flow_id: step_id.to_string(),
+                    message: "timeout handler did not produce a next step".to_string(),
+                }),
+            };
+        }
+    };
+    match target {
+        StepTarget::StepRef(next_id) => Ok(TimeoutRoute::Continue(next_id.clone())),
+        StepTarget::Terminal { outcome } => Ok(TimeoutRoute::Finish(FlowResult {
+            outcome: outcome.clone(),
+            steps_executed: std::mem::take(steps_executed),
+            entity_state_changes: std::mem::take(entity_changes_all),
+            initiating_persona: None,
+            correlation_id: None,
+        })),
+    }
+}
@@ -0,0 +1,358 @@
+//! Time travel: entity states as of a past instant.
+//!
+//! Reconstructs an `EntityStateMap` from the entity transitions a
+//! `TenorStorage` backend records, so flows and action spaces can be
+//! evaluated against the states that held at that time ("what could the
+//! clerk do on March 3rd?"), and projects the records of one entity
+//! instance into its transition timeline with the provenance behind each
+//! transition.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tenor_storage::{
+    EntityStateRecord, EntityTransitionHistoryRecord, ProvenanceRecord, StorageError, TenorStorage,
+};
+use time::OffsetDateTime;
+
+use crate::flow::timeout::parse_datetime;
+use crate::operation::{instance_key, EntityStateMap};
+use crate::types::Contract;
+
+/// Errors from reconstructing historical entity states.
+#[derive(Debug)]
+pub enum HistoryError {
+    /// The storage backend failed.
+    Storage(StorageError),
+    /// A timestamp that is not RFC 3339.
+    InvalidTimestamp(String),
+    /// A history document that does not deserialize.
+    Deserialize(String),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Storage(e) => write!(f, "storage error: {}", e),
+            HistoryError::InvalidTimestamp(ts) => {
+                write!(f, "invalid timestamp '{}': expected RFC 3339", ts)
+            }
+            HistoryError::Deserialize(msg) => write!(f, "invalid entity history: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+impl From<StorageError> for HistoryError {
+    fn from(e: StorageError) -> Self {
+        HistoryError::Storage(e)
+    }
+}
+
+/// The current entity states and full transition history of a contract's
+/// entities, as recorded by a storage backend.
+///
+/// Serializes as `{"entity_states": [...], "transitions": [...]}`, the
+/// format `tenor eval --history` and `tenor serve --history` read, plus
+/// `"provenance": [...]` when provenance records were loaded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntityHistory {
+    pub entity_states: Vec<EntityStateRecord>,
+    pub transitions: Vec<EntityTransitionHistoryRecord>,
+    /// Provenance records of the operation executions behind `transitions`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provenance: Vec<ProvenanceRecord>,
+}
+
+impl EntityHistory {
+    /// Read the states and transitions of every entity `contract` declares,
+    /// and the provenance records of the operations behind the transitions.
+    pub async fn load<S: TenorStorage>(
+        storage: &S,
+        contract: &Contract,
+    ) -> Result<Self, HistoryError> {
+        let mut history = EntityHistory::default();
+        for entity in &contract.entities {
+            history
+                .entity_states
+                .extend(storage.list_entity_states(&entity.id, None).await?);
+            history
+                .transitions
+                .extend(storage.list_entity_transitions(&entity.id, None).await?);
+        }
+        let executions: BTreeSet<&str> = history
+            .transitions
+            .iter()
+            .map(|t| t.transition.operation_execution_id.as_str())
+            .collect();
+        let mut provenance = Vec::new();
+        for execution_id in executions {
+            provenance.extend(storage.get_provenance(execution_id).await?);
+        }
+        history.provenance = provenance;
+        Ok(history)
+    }
+
+    /// The current states of an entity's instances, optionally only those
+    /// in `state`.
+    pub fn instances(&self, entity_id: &str, state: Option<&str>) -> Vec<&EntityStateRecord> {
+        self.entity_states
+            .iter()
+            .filter(|r| r.entity_id == entity_id && state.is_none_or(|s| r.state == s))
+            .collect()
+    }
+
+    /// The current state of one instance, if it exists.
+    pub fn instance(&self, entity_id: &str, instance_id: &str) -> Option<&EntityStateRecord> {
+        self.entity_states
+            .iter()
+            .find(|r| r.entity_id == entity_id && r.instance_id == instance_id)
+    }
+
+    /// The transitions of one instance, oldest first.
+    pub fn timeline(
+        &self,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Vec<&EntityTransitionHistoryRecord> {
+        let mut timeline: Vec<&EntityTransitionHistoryRecord> = self
+            .transitions
+            .iter()
+            .filter(|r| {
+                r.transition.entity_id == entity_id && r.transition.instance_id == instance_id
+            })
+            .collect();
+        timeline.sort_by_key(|r| r.transition.to_version);
+        timeline
+    }
+
+    /// The provenance records of an operation execution.
+    pub fn provenance_of(&self, operation_execution_id: &str) -> Vec<&ProvenanceRecord> {
+        self.provenance
+            .iter()
+            .filter(|p| p.operation_execution_id == operation_execution_id)
+            .collect()
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, HistoryError> {
+        serde_json::from_value(value.clone()).map_err(|e| HistoryError::Deserialize(e.to_string()))
+    }
+
+    /// The state of every instance that existed at `as_of` (RFC 3339).
+    ///
+    /// An instance is in the target state of its last transition at or
+    /// before `as_of`, or else the source state of its first later one. An
+    /// instance that never transitioned is in its stored state if it was
+    /// initialized by `as_of`. Instances created later, or destroyed by
+    /// then, are absent.
+    pub fn entity_states_as_of(&self, as_of: &str) -> Result<EntityStateMap, HistoryError> {
+        let as_of = timestamp(as_of)?;
+
+        // (executed_at, to_version, index into `transitions`) per instance
+        type Timeline = Vec<(OffsetDateTime, i64, usize)>;
+        let mut timelines: BTreeMap<(&str, &str), Timeline> = BTreeMap::new();
+        for (i, record) in self.transitions.iter().enumerate() {
+            let t = &record.transition;
+            timelines
+                .entry((t.entity_id.as_str(), t.instance_id.as_str()))
+                .or_default()
+                .push((timestamp(&record.executed_at)?, t.to_version, i));
+        }
+
+        let mut states = EntityStateMap::new();
+        for ((entity_id, instance_id), timeline) in &mut timelines {
+            timeline.sort();
+            let state = match timeline.iter().rev().find(|(at, _, _)| *at <= as_of) {
+                Some((_, _, i)) => &self.transitions[*i].transition.to_state,
+                None => &self.transitions[timeline[0].2].transition.from_state,
+            };
+            // Empty states mark instances not yet created or already destroyed.
+            if !state.is_empty() {
+                states.insert(instance_key(entity_id, instance_id), state.as_str().into());
+            }
+        }
+
+        for record in &self.entity_states {
+            let id = (record.entity_id.as_str(), record.instance_id.as_str());
+            if !timelines.contains_key(&id) && timestamp(&record.updated_at)? <= as_of {
+                states.insert(
+                    instance_key(&record.entity_id, &record.instance_id),
+                    record.state.as_str().into(),
+                );
+            }
+        }
+        Ok(states)
+    }
+}
+
+/// Reconstruct the entity states of `contract`'s entities as of `as_of`
+/// (RFC 3339) from `storage`. See [`EntityHistory::entity_states_as_of`].
+pub async fn entity_states_as_of<S: TenorStorage>(
+    storage: &S,
+    contract: &Contract,
+    as_of: &str,
+) -> Result<EntityStateMap, HistoryError> {
+    EntityHistory::load(storage, contract)
+        .await?
+        .entity_states_as_of(as_of)
+}
+
+fn timestamp(s: &str) -> Result<OffsetDateTime, HistoryError> {
+    parse_datetime(s).ok_or_else(|| HistoryError::InvalidTimestamp(s.to_string()))
+}
+
+// ──────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tenor_storage::EntityTransitionRecord;
+
+    fn state(instance_id: &str, state: &str, updated_at: &str) -> EntityStateRecord {
+        EntityStateRecord {
+            entity_id: "Order".to_string(),
+            instance_id: instance_id.to_string(),
+            state: state.to_string(),
+            version: 0,
+            updated_at: updated_at.to_string(),
+            last_flow_id: None,
+            last_operation_id: None,
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    fn transition(
+        instance_id: &str,
+        from: &str,
+        to: &str,
+        to_version: i64,
+        executed_at: &str,
+    ) -> EntityTransitionHistoryRecord {
+        EntityTransitionHistoryRecord {
+            transition: EntityTransitionRecord {
+                id: format!("{}-{}", instance_id, to_version),
+                operation_execution_id: format!("op-{}-{}", instance_id, to_version),
+                entity_id: "Order".to_string(),
+                instance_id: instance_id.to_string(),
+                from_state: from.to_string(),
+                to_state: to.to_string(),
+                from_version: to_version - 1,
+                to_version,
+            },
+            executed_at: executed_at.to_string(),
+        }
+    }
+
+    fn history() -> EntityHistory {
+        EntityHistory {
+            entity_states: vec![
+                state("o1", "approved", "2025-03-05T09:00:00Z"),
+                state("o2", "draft", "2025-03-02T00:00:00Z"),
+                state("o3", "draft", "2025-03-10T00:00:00Z"),
+            ],
+            transitions: vec![
+                transition("o1", "submitted", "approved", 2, "2025-03-05T09:00:00Z"),
+                transition("o1", "draft", "submitted", 1, "2025-03-01T09:00:00Z"),
+            ],
+            provenance: vec![ProvenanceRecord {
+                id: "prov-o1-2".to_string(),
+                operation_execution_id: "op-o1-2".to_string(),
+                facts_used: serde_json::json!(["amount"]),
+                verdicts_used: serde_json::json!(["approvable"]),
+                verdict_set_snapshot: serde_json::json!([]),
+            }],
+        }
+    }
+
+    fn state_of(states: &EntityStateMap, instance_id: &str) -> Option<String> {
+        states
+            .get(&instance_key("Order", instance_id))
+            .map(|s| s.to_string())
+    }
+
+    #[test]
+    fn replays_transitions_up_to_the_instant() {
+        let states = history()
+            .entity_states_as_of("2025-03-03T00:00:00Z")
+            .unwrap();
+        assert_eq!(state_of(&states, "o1").as_deref(), Some("submitted"));
+        assert_eq!(state_of(&states, "o2").as_deref(), Some("draft"));
+        // o3 was initialized after the instant
+        assert_eq!(state_of(&states, "o3"), None);
+    }
+
+    #[test]
+    fn before_first_transition_uses_its_source_state() {
+        let states = history()
+            .entity_states_as_of("2025-02-28T00:00:00Z")
+            .unwrap();
+        assert_eq!(state_of(&states, "o1").as_deref(), Some("draft"));
+        assert_eq!(state_of(&states, "o2"), None);
+    }
+
+    #[test]
+    fn destroyed_instances_are_absent() {
+        let mut history = history();
+        history
+            .transitions
+            .push(transition("o1", "approved", "", 3, "2025-03-07T00:00:00Z"));
+        let states = history.entity_states_as_of("2025-03-08T00:00:00Z").unwrap();
+        assert_eq!(state_of(&states, "o1"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_timestamp() {
+        assert!(matches!(
+            history().entity_states_as_of("March 3rd"),
+            Err(HistoryError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn projects_an_instance_timeline_with_provenance() {
+        let history = history();
+        let drafts: Vec<&str> = history
+            .instances("Order", Some("draft"))
+            .iter()
+            .map(|r| r.instance_id.as_str())
+            .collect();
+        assert_eq!(drafts, ["o2", "o3"]);
+        assert_eq!(history.instances("Invoice", None).len(), 0);
+        assert_eq!(history.instance("Order", "o1").unwrap().state, "approved");
+
+        let timeline = history.timeline("Order", "o1");
+        let states: Vec<&str> = timeline
+            .iter()
+            .map(|r| r.transition.to_state.as_str())
+            .collect();
+        assert_eq!(states, ["submitted", "approved"]);
+        assert!(history.timeline("Order", "o2").is_empty());
+        assert_eq!(history.provenance_of("op-o1-2")[0].id, "prov-o1-2");
+        assert!(history.provenance_of("op-o1-1").is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let json = serde_json::to_value(history()).unwrap();
+        assert_eq!(
+            json["transitions"][0]["executed_at"],
+            "2025-03-05T09:00:00Z"
+        );
+        assert_eq!(json["transitions"][0]["to_state"], "approved");
+        let parsed = EntityHistory::from_json(&json).unwrap();
+        assert_eq!(parsed.transitions.len(), 2);
+        assert_eq!(parsed.provenance.len(), 1);
+
+        // Histories exported without provenance still read.
+        let mut json = json;
+        json.as_object_mut().unwrap().remove("provenance");
+        assert!(EntityHistory::from_json(&json)
+            .unwrap()
+            .provenance
+            .is_empty());
+    }
+}
@@ -11,34 +11,60 @@ pub mod action_space;
 #[cfg(feature = "adapter")]
 pub mod adapter;
 pub mod assemble;
+pub mod clock;
 pub mod fact_provider;
 pub mod flow;
+pub mod history;
 pub mod migration;
 pub mod numeric;
 pub mod operation;
+pub mod partial;
 pub mod policy;
 pub mod predicate;
+pub mod profile;
 pub mod provenance;
 pub mod rules;
+pub mod simulate;
+pub mod solve;
+pub mod store;
+pub mod symbol;
+pub mod system;
+#[cfg(any(test, feature = "test-support"))]
+#[doc(hidden)]
+pub mod test_support;
+pub mod trace;
 pub mod types;
+pub mod verdict_diff;
 
 pub use action_space::{
-    compute_action_space, Action, ActionSpace, BlockedAction, BlockedReason, EntitySummary,
+    compute_action_space, compute_action_space_matrix, Action, ActionSpace, ActionSpaceMatrix,
+    BlockedAction, BlockedReason, EntitySummary, FailedClause, OperationAvailability,
     VerdictSummary,
 };
 #[cfg(feature = "adapter")]
 pub use adapter::database::DatabaseQuery;
 #[cfg(feature = "adapter")]
+pub use adapter::secrets::SecretResolver;
+#[cfg(feature = "adapter")]
 pub use adapter::{
     AdapterConfig, AdapterError, AdapterFactProvider, AdapterRegistry, EnrichedFactProvenance,
     FactAdapter, StructuredSourceRef,
 };
+pub use assemble::{validate_facts, FactError};
+pub use clock::{with_clock, Clock, FixedClock, SystemClock};
 pub use fact_provider::{FactProvider, FactProviderError, StaticFactProvider};
-pub use flow::{FlowEvalResult, FlowResult, Snapshot, StepRecord};
+pub use flow::{
+    FlowContinuation, FlowEvalResult, FlowOptions, FlowResult, FlowRun, FlowSuspended, Snapshot,
+    SnapshotEnvelope, StepRecord,
+};
+pub use history::{entity_states_as_of, EntityHistory, HistoryError};
 pub use operation::{
-    get_instance_state, resolve_instance_id, single_instance, EffectRecord, EntityStateMap,
-    InstanceBindingMap, OperationError, OperationProvenance, OperationResult, DEFAULT_INSTANCE_ID,
+    get_instance_attributes, get_instance_state, instance_key, resolve_instance_id,
+    single_instance, EffectRecord, EntityAttributeMap, EntityStateMap, EntityVersionMap,
+    EntityVersions, InstanceBindingMap, OperationError, OperationProvenance, OperationResult,
+    DEFAULT_INSTANCE_ID,
 };
+pub use partial::{BlockedVerdict, PartialEvalResult};
 #[cfg(feature = "anthropic")]
 pub use policy::AnthropicClient;
 pub use policy::{
@@ -50,7 +76,23 @@ pub use policy::{
 pub use policy::{
     CallbackApprovalChannel, HumanInTheLoopPolicy, RandomPolicy, StdinApprovalChannel,
 };
+pub use profile::{profile, ProfileReport, ProfileStat};
+pub use solve::{solve_flow_outcome, SolveResult, SolveStatus};
+pub use store::{bundle_etag, ContractHandle, ContractStore};
+pub use symbol::Symbol;
+pub use system::{
+    SystemEntityStates, SystemEvaluator, SystemFlowExecution, SystemFlowResult, TriggerProvenance,
+};
+pub use trace::{PredicateTrace, RuleTrace};
 pub use types::{Contract, EvalError, FactSet, Value, VerdictInstance, VerdictSet};
+pub use verdict_diff::{VerdictChange, VerdictDiff};
+
+/// Optional evaluator features this evaluator implements.
+///
+/// Bundles list the features they use in their top-level `"requires"`
+/// array; [`Contract::from_interchange`] rejects any bundle requiring a
+/// feature not listed here.
+pub const SUPPORTED_FEATURES: &[&str] = tenor_core::features::ALL_FEATURES;
 
 /// Result of evaluating a contract against facts (rules only).
 pub struct EvalResult {
@@ -73,13 +115,62 @@ pub fn evaluate(
     facts: &serde_json::Value,
 ) -> Result<EvalResult, EvalError> {
     let contract = Contract::from_interchange(bundle)?;
-    let fact_set = assemble::assemble_facts(&contract, facts)?;
-    let verdict_set = rules::eval_strata(&contract, &fact_set)?;
+    evaluate_contract(&contract, facts)
+}
+
+/// Evaluate an already-parsed contract against provided facts (rules only).
+///
+/// Same as [`evaluate`] without re-parsing the bundle; see
+/// [`ContractHandle`] for sharing one parsed contract across threads.
+pub fn evaluate_contract(
+    contract: &Contract,
+    facts: &serde_json::Value,
+) -> Result<EvalResult, EvalError> {
+    let fact_set = assemble::assemble_facts(contract, facts)?;
+    let verdict_set = rules::eval_strata(contract, &fact_set)?;
     Ok(EvalResult {
         verdicts: verdict_set,
     })
 }
 
+/// [`evaluate`], also tracing every rule's condition: the value of each
+/// predicate subexpression is available from [`VerdictSet::traces`].
+pub fn evaluate_traced(
+    bundle: &serde_json::Value,
+    facts: &serde_json::Value,
+) -> Result<EvalResult, EvalError> {
+    let contract = Contract::from_interchange(bundle)?;
+    let fact_set = assemble::assemble_facts(&contract, facts)?;
+    Ok(EvalResult {
+        verdicts: rules::eval_strata_traced(&contract, &fact_set)?,
+    })
+}
+
+/// Evaluate a contract against incomplete facts (rules only).
+///
+/// Declared facts that are neither provided nor defaulted are treated as
+/// unknown rather than raising [`EvalError::MissingFact`]. Verdicts that do
+/// not depend on them are produced as usual; rules whose outcome does are
+/// reported as [`BlockedVerdict`]s naming the facts they need.
+pub fn evaluate_partial(
+    bundle: &serde_json::Value,
+    facts: &serde_json::Value,
+) -> Result<PartialEvalResult, EvalError> {
+    let contract = Contract::from_interchange(bundle)?;
+    evaluate_contract_partial(&contract, facts)
+}
+
+/// Partially evaluate an already-parsed contract.
+///
+/// Same as [`evaluate_partial`] without re-parsing the bundle.
+pub fn evaluate_contract_partial(
+    contract: &Contract,
+    facts: &serde_json::Value,
+) -> Result<PartialEvalResult, EvalError> {
+    let (fact_set, missing) = assemble::assemble_facts_partial(contract, facts)?;
+    partial::eval_strata_partial(contract, &fact_set, &missing.into_iter().collect())
+}
+
 /// Evaluate a contract and execute a named flow.
 ///
 /// Runs the full evaluation pipeline (spec Section 14):
@@ -113,8 +204,29 @@ pub fn evaluate_flow(
     instance_bindings: &InstanceBindingMap,
 ) -> Result<FlowEvalResult, EvalError> {
     let contract = Contract::from_interchange(bundle)?;
-    let fact_set = assemble::assemble_facts(&contract, facts)?;
-    let verdict_set = rules::eval_strata(&contract, &fact_set)?;
+    evaluate_contract_flow(
+        &contract,
+        facts,
+        flow_id,
+        persona,
+        override_entity_states,
+        instance_bindings,
+    )
+}
+
+/// Evaluate an already-parsed contract and execute a named flow.
+///
+/// Same as [`evaluate_flow`] without re-parsing the bundle.
+pub fn evaluate_contract_flow(
+    contract: &Contract,
+    facts: &serde_json::Value,
+    flow_id: &str,
+    persona: &str,
+    override_entity_states: Option<&EntityStateMap>,
+    instance_bindings: &InstanceBindingMap,
+) -> Result<FlowEvalResult, EvalError> {
+    let fact_set = assemble::assemble_facts(contract, facts)?;
+    let verdict_set = rules::eval_strata(contract, &fact_set)?;
 
     // Create frozen snapshot
     let snapshot = Snapshot {
@@ -125,7 +237,7 @@ pub fn evaluate_flow(
     // Use provided entity states or initialize from contract defaults
     let mut entity_states = match override_entity_states {
         Some(provided) => provided.clone(),
-        None => operation::init_entity_states(&contract),
+        None => operation::init_entity_states(contract),
     };
 
     // Find the flow (O(1) via HashMap index)
@@ -138,11 +250,12 @@ pub fn evaluate_flow(
     // Execute the flow with instance bindings per §11.1
     let mut flow_result = flow::execute_flow(
         target_flow,
-        &contract,
+        contract,
         &snapshot,
         &mut entity_states,
+        &mut operation::EntityAttributeMap::new(),
         instance_bindings,
-        None,
+        FlowOptions::default(),
     )?;
 
     // Per spec Section 11.4: initiating_persona is recorded for provenance.
@@ -381,6 +494,164 @@ mod integration_tests {
         }
     }
 
+    /// Bundles requiring unknown evaluator features are rejected up front.
+    #[test]
+    fn evaluate_rejects_unsupported_features() {
+        let bundle = serde_json::json!({
+            "id": "test_requires",
+            "kind": "Bundle",
+            "requires": ["parallel-steps", "time-travel"],
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": []
+        });
+
+        let result = evaluate(&bundle, &serde_json::json!({}));
+        match result {
+            Err(EvalError::UnsupportedFeatures { features }) => {
+                assert_eq!(features, vec!["time-travel".to_string()]);
+            }
+            _ => panic!("expected UnsupportedFeatures error"),
+        }
+    }
+
+    /// An evaluator lacking a feature rejects every elaborated bundle that
+    /// uses it, naming just that feature.
+    #[test]
+    fn evaluator_lacking_a_feature_rejects_bundles_using_it() {
+        use tenor_core::features::*;
+
+        let escrow = include_str!("../../../conformance/eval/positive/escrow_compliance.tenor");
+        let paused = escrow.replace(
+            "next:         step_compliance_release",
+            "next:         step_compliance_release\n      pause:        true",
+        );
+        let timed = escrow
+            .replace(
+                "fact buyer_requested_refund {",
+                "fact compliance_deadline {\n  type:   DateTime\n  source: \"compliance_service.deadline\"\n}\n\nfact buyer_requested_refund {",
+            )
+            .replace(
+                "next:         step_compliance_release",
+                "next:         step_compliance_release\n      timeout: Timeout(\n        deadline:   compliance_deadline\n        on_timeout: Terminal(expired)\n      )",
+            );
+        let weighted =
+            include_str!("../../../conformance/positive/flow_step_weights.tenor").to_string();
+        let money = include_str!("../../../conformance/numeric/money_declared_scale.tenor");
+        let cases = [
+            (FEATURE_HANDOFF_PAUSE, paused),
+            (FEATURE_STEP_TIMEOUTS, timed),
+            (FEATURE_STEP_WEIGHTS, weighted),
+            (FEATURE_MONEY_PRECISION, money.to_string()),
+            (FEATURE_ROUNDING_MODES, money.to_string()),
+        ];
+
+        for (feature, source) in cases {
+            let path = std::path::PathBuf::from("contract.tenor");
+            let provider = tenor_core::source::InMemoryProvider::new(
+                [(path.clone(), source)].into_iter().collect(),
+            );
+            let bundle = tenor_core::elaborate::elaborate_with_provider(&path, &provider)
+                .unwrap_or_else(|e| panic!("{}: {:?}", feature, e));
+            let requires: Vec<String> = serde_json::from_value(bundle["requires"].clone()).unwrap();
+            assert!(requires.iter().any(|f| f == feature), "{}", feature);
+            Contract::from_interchange(&bundle).unwrap();
+
+            let lacking: Vec<&str> = SUPPORTED_FEATURES
+                .iter()
+                .copied()
+                .filter(|f| *f != feature)
+                .collect();
+            match types::contract::check_requires(&requires, &lacking) {
+                Err(EvalError::UnsupportedFeatures { features }) => {
+                    assert_eq!(features, vec![feature.to_string()]);
+                }
+                other => panic!("{}: expected UnsupportedFeatures, got {:?}", feature, other),
+            }
+        }
+    }
+
+    /// Hand-written bundles comparing quantities of different units are
+    /// rejected when the contract is loaded.
+    #[test]
+    fn evaluate_rejects_unit_mismatch() {
+        let weight = |id: &str, unit: &str| {
+            serde_json::json!({
+                "id": id, "kind": "Fact", "tenor": "1.0",
+                "provenance": { "file": "test.tenor", "line": 1 },
+                "source": { "system": "test", "field": id },
+                "type": { "base": "Decimal", "precision": 10, "scale": 3, "unit": unit }
+            })
+        };
+        let bundle = serde_json::json!({
+            "id": "test_units",
+            "kind": "Bundle",
+            "requires": ["units-of-measure"],
+            "tenor": "1.0",
+            "tenor_version": "1.1.0",
+            "constructs": [
+                weight("parcel_weight", "lb"),
+                weight("weight_limit", "kg"),
+                {
+                    "id": "within_limit", "kind": "Rule", "stratum": 0, "tenor": "1.0",
+                    "provenance": { "file": "test.tenor", "line": 1 },
+                    "body": {
+                        "when": {
+                            "left": { "fact_ref": "parcel_weight" },
+                            "op": "<=",
+                            "right": { "fact_ref": "weight_limit" }
+                        },
+                        "produce": {
+                            "verdict_type": "ok",
+                            "payload": { "type": { "base": "Bool" }, "value": true }
+                        }
+                    }
+                }
+            ]
+        });
+
+        match evaluate(&bundle, &serde_json::json!({})) {
+            Err(EvalError::TypeError { message }) => {
+                assert!(message.contains("rule 'within_limit'"), "{}", message);
+                assert!(
+                    message.contains("unit lb with a quantity in unit kg"),
+                    "{}",
+                    message
+                );
+            }
+            other => panic!("expected TypeError, got {:?}", other.err()),
+        }
+    }
+
+    /// Strict mode reports unknown fields by JSON pointer; the default
+    /// mode ignores them.
+    #[test]
+    fn strict_contract_rejects_unknown_fields() {
+        let bundle = serde_json::json!({
+            "id": "test_strict",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.1.0",
+            "constructs": [{
+                "id": "ready",
+                "kind": "Fact",
+                "provenance": { "file": "t.tenor", "line": 1 },
+                "source": { "field": "ready", "system": "svc" },
+                "tenor": "1.0",
+                "type": { "base": "Bool" },
+                "defualt": true
+            }]
+        });
+
+        assert!(Contract::from_interchange(&bundle).is_ok());
+        match Contract::from_interchange_strict(&bundle) {
+            Err(EvalError::StrictViolation { pointer, .. }) => {
+                assert_eq!(pointer, "/constructs/0/defualt");
+            }
+            _ => panic!("expected StrictViolation error"),
+        }
+    }
+
     /// Test with no rules -- should produce empty verdict set.
     #[test]
     fn evaluate_no_rules() {
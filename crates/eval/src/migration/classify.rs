@@ -68,6 +68,17 @@ pub struct ClassificationSummary {
     pub total_changes: usize,
 }
 
+impl ClassificationSummary {
+    fn count_mut(&mut self, severity: &ChangeSeverity) -> &mut usize {
+        match severity {
+            ChangeSeverity::Breaking => &mut self.breaking_count,
+            ChangeSeverity::NonBreaking => &mut self.non_breaking_count,
+            ChangeSeverity::RequiresAnalysis => &mut self.requires_analysis_count,
+            ChangeSeverity::Infrastructure => &mut self.infrastructure_count,
+        }
+    }
+}
+
 /// A fully classified diff.
 #[derive(Debug, Clone, Serialize)]
 pub struct ClassifiedDiff {
@@ -214,6 +225,35 @@ impl ClassifiedDiff {
     pub fn has_breaking(&self) -> bool {
         self.summary.breaking_count > 0
     }
+
+    /// Replace the classification of one field change, keeping the
+    /// summary counts consistent. Returns false if no such field change
+    /// exists.
+    ///
+    /// Used by callers that refine the structural taxonomy with deeper
+    /// analysis (e.g. S6 flow-path comparison).
+    pub fn reclassify_field(
+        &mut self,
+        kind: &str,
+        id: &str,
+        field: &str,
+        classification: ChangeClassification,
+    ) -> bool {
+        let Some(target) = self
+            .changed
+            .iter_mut()
+            .filter(|c| c.kind == kind && c.id == id)
+            .flat_map(|c| c.fields.iter_mut())
+            .find(|f| f.field == field)
+        else {
+            return false;
+        };
+        let old = std::mem::replace(&mut target.classification, classification);
+        let new = target.classification.severity.clone();
+        *self.summary.count_mut(&old.severity) -= 1;
+        *self.summary.count_mut(&new) += 1;
+        true
+    }
 }
 
 /// Classify a structural diff using the breaking change taxonomy (Section 18.2).
@@ -1224,6 +1264,56 @@ mod tests {
             ChangeSeverity::NonBreaking | ChangeSeverity::Breaking
         ));
     }
+
+    #[test]
+    fn reclassify_field_updates_summary() {
+        let t1 = make_bundle(vec![make_fact("amount", "Int", 4)]);
+        let mut fact = make_fact("amount", "Int", 4);
+        fact["type"] = json!({ "base": "Decimal", "precision": 10, "scale": 2 });
+        let t2 = make_bundle(vec![fact]);
+        let mut classified = classify_diff(&diff_bundles(&t1, &t2).unwrap());
+        let before = classified.summary.clone();
+        let field = classified.changed[0].fields[0].field.clone();
+        let old = classified.changed[0].fields[0]
+            .classification
+            .severity
+            .clone();
+
+        assert!(classified.reclassify_field(
+            "Fact",
+            "amount",
+            &field,
+            ChangeClassification {
+                severity: ChangeSeverity::Infrastructure,
+                reason: "test".to_string(),
+                migration_action: None,
+            },
+        ));
+        assert_eq!(
+            classified.summary.infrastructure_count,
+            before.infrastructure_count + 1
+        );
+        assert_eq!(classified.summary.total_changes, before.total_changes);
+        if old != ChangeSeverity::Infrastructure {
+            let count = |s: &ClassificationSummary| match old {
+                ChangeSeverity::Breaking => s.breaking_count,
+                ChangeSeverity::NonBreaking => s.non_breaking_count,
+                ChangeSeverity::RequiresAnalysis => s.requires_analysis_count,
+                ChangeSeverity::Infrastructure => s.infrastructure_count,
+            };
+            assert_eq!(count(&classified.summary), count(&before) - 1);
+        }
+        assert!(!classified.reclassify_field(
+            "Fact",
+            "missing",
+            &field,
+            ChangeClassification {
+                severity: ChangeSeverity::NonBreaking,
+                reason: "test".to_string(),
+                migration_action: None,
+            },
+        ));
+    }
 }
 
 // ──────────────────────────────────────────────
@@ -114,11 +114,8 @@ pub fn check_flow_compatibility_static(
     let mut entity_states = EntityStateMap::new();
     for entity in &v1.entities {
         entity_states.insert(
-            (
-                entity.id.clone(),
-                crate::operation::DEFAULT_INSTANCE_ID.to_string(),
-            ),
-            entity.initial.clone(),
+            crate::operation::instance_key(&entity.id, crate::operation::DEFAULT_INSTANCE_ID),
+            entity.initial.as_str().into(),
         );
     }
 
@@ -234,10 +231,10 @@ fn check_layer2_entity_states(
         match v2_entity {
             Some(v2_ent) => {
                 // Check current state exists in v2
-                if !v2_ent.states.contains(current_state) {
+                if !v2_ent.states.iter().any(|s| s == current_state) {
                     reasons.push(IncompatibilityReason::EntityStateNotInV2 {
-                        entity_id: entity_id.clone(),
-                        state: current_state.clone(),
+                        entity_id: entity_id.to_string(),
+                        state: current_state.to_string(),
                     });
                 }
 
@@ -260,7 +257,7 @@ fn check_layer2_entity_states(
                                         if !v2_ent.states.contains(&effect.to) {
                                             reasons.push(
                                                 IncompatibilityReason::TransitionNotInV2 {
-                                                    entity_id: entity_id.clone(),
+                                                    entity_id: entity_id.to_string(),
                                                     from: effect.from.clone(),
                                                     to: effect.to.clone(),
                                                 },
@@ -276,8 +273,8 @@ fn check_layer2_entity_states(
             None => {
                 // Entity doesn't exist in v2 at all
                 reasons.push(IncompatibilityReason::EntityStateNotInV2 {
-                    entity_id: entity_id.clone(),
-                    state: current_state.clone(),
+                    entity_id: entity_id.to_string(),
+                    state: current_state.to_string(),
                 });
             }
         }
@@ -368,8 +365,8 @@ fn check_layer3_structure(
 mod tests {
     use super::*;
     use crate::types::{
-        Contract, Effect, Entity, FactDecl, Flow, FlowStep, Operation, Predicate, StepTarget,
-        Transition, TypeSpec, Value,
+        Contract, Effect, EffectKind, Entity, FactDecl, Flow, FlowStep, Operation, Predicate,
+        StepTarget, Transition, TypeSpec, Value,
     };
     use std::collections::BTreeMap;
 
@@ -391,6 +388,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -426,8 +424,10 @@ mod tests {
                 .map(|(from, to)| Transition {
                     from: from.to_string(),
                     to: to.to_string(),
+                    guard: None,
                 })
                 .collect(),
+            attributes: BTreeMap::new(),
         }
     }
 
@@ -453,9 +453,12 @@ mod tests {
     fn make_effect(entity_id: &str, from: &str, to: &str) -> Effect {
         Effect {
             entity_id: entity_id.to_string(),
+            kind: EffectKind::Transition,
             from: from.to_string(),
             to: to.to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }
     }
 
@@ -477,6 +480,7 @@ mod tests {
             on_failure: crate::types::FailureHandler::Terminate {
                 outcome: "failed".to_string(),
             },
+            timeout: None,
         }
     }
 
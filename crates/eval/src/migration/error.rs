@@ -20,6 +20,8 @@ pub enum MigrationError {
     Storage(String),
     /// Incompatible migration.
     Incompatible(String),
+    /// State mapping that does not fit the two contract versions.
+    InvalidStateMapping(String),
 }
 
 impl fmt::Display for MigrationError {
@@ -40,6 +42,7 @@ impl fmt::Display for MigrationError {
             ),
             MigrationError::Storage(msg) => write!(f, "storage error: {}", msg),
             MigrationError::Incompatible(msg) => write!(f, "incompatible migration: {}", msg),
+            MigrationError::InvalidStateMapping(msg) => write!(f, "invalid state mapping: {}", msg),
         }
     }
 }
@@ -14,7 +14,7 @@ use tenor_storage::{
 };
 
 use super::error::MigrationError;
-use super::plan::MigrationPlan;
+use super::plan::{EntityStateMapping, MigrationArtifact, MigrationPlan};
 
 /// Result of executing a migration plan.
 #[derive(Debug, Clone, Serialize)]
@@ -48,9 +48,46 @@ pub struct EntityMigrationRecord {
 pub async fn execute_migration<S: TenorStorage>(
     storage: &S,
     plan: &MigrationPlan,
+) -> Result<MigrationResult, MigrationError> {
+    apply_state_mappings(
+        storage,
+        &plan.entity_state_mappings,
+        &plan.v1_id,
+        &plan.v2_id,
+    )
+    .await
+}
+
+/// Execute a `MigrationArtifact` atomically via a TenorStorage backend.
+///
+/// Lists the live instances of every remapped entity, expands the
+/// artifact's per-entity remaps into per-instance mappings, and applies
+/// them with the same snapshot semantics as [`execute_migration`].
+pub async fn execute_migration_artifact<S: TenorStorage>(
+    storage: &S,
+    artifact: &MigrationArtifact,
+) -> Result<MigrationResult, MigrationError> {
+    let mut records = Vec::new();
+    for remap in &artifact.entity_state_remaps {
+        records.extend(
+            storage
+                .list_entity_states(&remap.entity_id, None)
+                .await
+                .map_err(storage_err)?,
+        );
+    }
+    let mappings = artifact.instance_mappings(&records);
+    apply_state_mappings(storage, &mappings, &artifact.v1_id, &artifact.v2_id).await
+}
+
+async fn apply_state_mappings<S: TenorStorage>(
+    storage: &S,
+    mappings: &[EntityStateMapping],
+    v1_id: &str,
+    v2_id: &str,
 ) -> Result<MigrationResult, MigrationError> {
     // Empty plan -> nothing to do
-    if plan.entity_state_mappings.is_empty() {
+    if mappings.is_empty() {
         return Ok(MigrationResult {
             entities_migrated: Vec::new(),
             provenance_records_created: 0,
@@ -63,7 +100,7 @@ pub async fn execute_migration<S: TenorStorage>(
     let mut migrated = Vec::new();
     let mut provenance_count = 0;
 
-    for mapping in &plan.entity_state_mappings {
+    for mapping in mappings {
         // 1. Read current state with lock
         let current = match storage
             .get_entity_state_for_update(&mut snapshot, &mapping.entity_id, &mapping.instance_id)
@@ -117,6 +154,8 @@ pub async fn execute_migration<S: TenorStorage>(
             outcome: "success".to_string(),
             executed_at: now_iso8601(),
             step_id: "migration".to_string(),
+            idempotency_key: None,
+            response: None,
         };
         if let Err(e) = storage
             .insert_operation_execution(&mut snapshot, op_record)
@@ -155,8 +194,8 @@ pub async fn execute_migration<S: TenorStorage>(
             verdicts_used: serde_json::json!({}),
             verdict_set_snapshot: serde_json::json!({
                 "migration": true,
-                "from_contract": plan.v1_id,
-                "to_contract": plan.v2_id
+                "from_contract": v1_id,
+                "to_contract": v2_id
             }),
         };
         if let Err(e) = storage
@@ -216,13 +255,15 @@ fn storage_err(e: StorageError) -> MigrationError {
 #[cfg(test)]
 mod tests {
     use super::super::analysis::{MigrationAnalysis, MigrationSeverity};
-    use super::super::plan::{EntityStateMapping, MigrationPlan, MigrationPolicy};
+    use super::super::plan::{EntityStateRemap, MigrationPlan, MigrationPolicy};
     use super::*;
     use async_trait::async_trait;
+    use std::collections::BTreeMap;
     use std::sync::{Arc, Mutex};
     use tenor_storage::{
-        EntityStateRecord, EntityTransitionRecord, FlowExecutionRecord, OperationExecutionRecord,
-        ProvenanceRecord, StorageError, TenorStorage,
+        EntityStateRecord, EntityTransitionHistoryRecord, EntityTransitionRecord,
+        FlowExecutionRecord, OperationExecutionRecord, ProvenanceRecord, StorageError,
+        TenorStorage,
     };
 
     // ── Mock storage ──────────────────────────────────────────────────
@@ -338,6 +379,7 @@ mod tests {
                 updated_at: "2026-01-01T00:00:00Z".to_string(),
                 last_flow_id: None,
                 last_operation_id: None,
+                attributes: BTreeMap::new(),
             });
             Ok(())
         }
@@ -393,6 +435,46 @@ mod tests {
             Ok(entity.version)
         }
 
+        async fn update_entity_attributes(
+            &self,
+            _snapshot: &mut MockSnapshot,
+            entity_id: &str,
+            instance_id: &str,
+            attributes: &BTreeMap<String, serde_json::Value>,
+        ) -> Result<(), StorageError> {
+            let mut inner = self.inner.lock().unwrap();
+            let entity = inner
+                .entities
+                .iter_mut()
+                .find(|e| e.entity_id == entity_id && e.instance_id == instance_id)
+                .ok_or(StorageError::EntityNotFound {
+                    entity_id: entity_id.to_string(),
+                    instance_id: instance_id.to_string(),
+                })?;
+            entity.attributes.extend(attributes.clone());
+            Ok(())
+        }
+
+        async fn delete_entity(
+            &self,
+            _snapshot: &mut MockSnapshot,
+            entity_id: &str,
+            instance_id: &str,
+        ) -> Result<(), StorageError> {
+            let mut inner = self.inner.lock().unwrap();
+            let before = inner.entities.len();
+            inner
+                .entities
+                .retain(|e| !(e.entity_id == entity_id && e.instance_id == instance_id));
+            if inner.entities.len() == before {
+                return Err(StorageError::EntityNotFound {
+                    entity_id: entity_id.to_string(),
+                    instance_id: instance_id.to_string(),
+                });
+            }
+            Ok(())
+        }
+
         async fn insert_flow_execution(
             &self,
             _snapshot: &mut MockSnapshot,
@@ -406,7 +488,19 @@ mod tests {
             _snapshot: &mut MockSnapshot,
             record: OperationExecutionRecord,
         ) -> Result<(), StorageError> {
-            self.inner.lock().unwrap().op_executions.push(record);
+            let mut inner = self.inner.lock().unwrap();
+            if let Some(key) = &record.idempotency_key {
+                if inner
+                    .op_executions
+                    .iter()
+                    .any(|op| op.idempotency_key.as_ref() == Some(key))
+                {
+                    return Err(StorageError::DuplicateIdempotencyKey {
+                        idempotency_key: key.clone(),
+                    });
+                }
+            }
+            inner.op_executions.push(record);
             Ok(())
         }
 
@@ -454,7 +548,7 @@ mod tests {
             Ok(inner
                 .entities
                 .iter()
-                .filter(|e| e.entity_id == entity_id && state_filter.map_or(true, |s| e.state == s))
+                .filter(|e| e.entity_id == entity_id && state_filter.is_none_or(|s| e.state == s))
                 .cloned()
                 .collect())
         }
@@ -489,6 +583,47 @@ mod tests {
         ) -> Result<Vec<FlowExecutionRecord>, StorageError> {
             Ok(Vec::new())
         }
+
+        async fn list_correlated_flow_executions(
+            &self,
+            _correlation_id: &str,
+        ) -> Result<Vec<FlowExecutionRecord>, StorageError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_operation_execution_by_idempotency_key(
+            &self,
+            idempotency_key: &str,
+        ) -> Result<Option<OperationExecutionRecord>, StorageError> {
+            let inner = self.inner.lock().unwrap();
+            Ok(inner
+                .op_executions
+                .iter()
+                .find(|op| op.idempotency_key.as_deref() == Some(idempotency_key))
+                .cloned())
+        }
+
+        async fn list_entity_transitions(
+            &self,
+            entity_id: &str,
+            _until: Option<&str>,
+        ) -> Result<Vec<EntityTransitionHistoryRecord>, StorageError> {
+            let inner = self.inner.lock().unwrap();
+            Ok(inner
+                .transitions
+                .iter()
+                .filter(|t| t.entity_id == entity_id)
+                .map(|t| EntityTransitionHistoryRecord {
+                    transition: t.clone(),
+                    executed_at: inner
+                        .op_executions
+                        .iter()
+                        .find(|op| op.id == t.operation_execution_id)
+                        .map(|op| op.executed_at.clone())
+                        .unwrap_or_default(),
+                })
+                .collect())
+        }
     }
 
     // ── Test helpers ──────────────────────────────────────────────────
@@ -502,6 +637,7 @@ mod tests {
             updated_at: "2026-01-01T00:00:00Z".to_string(),
             last_flow_id: None,
             last_operation_id: None,
+            attributes: BTreeMap::new(),
         }
     }
 
@@ -666,4 +802,47 @@ mod tests {
         assert!(result.entities_migrated.is_empty());
         assert_eq!(result.provenance_records_created, 0);
     }
+
+    #[tokio::test]
+    async fn artifact_migrates_only_remapped_instances() {
+        let storage = MockStorage::with_entities(vec![
+            make_entity_record("Order", "order-1", "approved"),
+            make_entity_record("Order", "order-2", "draft"),
+            make_entity_record("Invoice", "inv-1", "approved"),
+        ]);
+
+        let artifact = MigrationArtifact::new(
+            "v1",
+            "v2",
+            vec![EntityStateRemap {
+                entity_id: "Order".to_string(),
+                states: [
+                    ("approved".to_string(), "accepted".to_string()),
+                    ("draft".to_string(), "draft".to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            }],
+        );
+
+        let result = execute_migration_artifact(&storage, &artifact)
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.entities_migrated.len(), 1);
+        assert_eq!(result.entities_migrated[0].instance_id, "order-1");
+        assert_eq!(
+            storage.entity_state("Order", "order-1"),
+            Some("accepted".to_string())
+        );
+        assert_eq!(
+            storage.entity_state("Order", "order-2"),
+            Some("draft".to_string())
+        );
+        assert_eq!(
+            storage.entity_state("Invoice", "inv-1"),
+            Some("approved".to_string())
+        );
+    }
 }
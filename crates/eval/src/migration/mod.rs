@@ -22,8 +22,11 @@ pub use classify::{
 pub use compatibility::{check_flow_compatibility, check_flow_compatibility_static};
 pub use diff::{diff_bundles, BundleDiff, ConstructChange, ConstructSummary, DiffError, FieldDiff};
 pub use error::MigrationError;
-pub use executor::{execute_migration, EntityMigrationRecord, MigrationResult};
+pub use executor::{
+    execute_migration, execute_migration_artifact, EntityMigrationRecord, MigrationResult,
+};
 pub use plan::{
-    build_migration_plan, EntityStateMapping, FlowCompatibilityResult, IncompatibilityReason,
-    LayerResults, MigrationPlan, MigrationPolicy,
+    build_migration_plan, default_state_remaps, validate_state_remaps, EntityStateMapping,
+    EntityStateRemap, FlowCompatibilityResult, IncompatibilityReason, LayerResults,
+    MigrationArtifact, MigrationPlan, MigrationPolicy, MIGRATION_ARTIFACT_KIND,
 };
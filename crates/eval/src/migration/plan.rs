@@ -3,8 +3,11 @@
 //! Aggregates migration analysis, flow compatibility results, and
 //! entity state mappings into a complete migration plan.
 
-use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tenor_storage::EntityStateRecord;
 
 use super::analysis::{EntityAction, MigrationAnalysis, MigrationSeverity};
 use super::error::MigrationError;
@@ -161,6 +164,194 @@ fn get_v2_initial(bundle: &Value, entity_id: &str) -> Option<String> {
         .and_then(|c| c.get("initial")?.as_str().map(|s| s.to_string()))
 }
 
+/// Kind tag of a serialized [`MigrationArtifact`].
+pub const MIGRATION_ARTIFACT_KIND: &str = "MigrationArtifact";
+
+/// Old-state -> new-state mapping for one entity whose state set changed.
+///
+/// Unlike [`EntityStateMapping`] this is not tied to an instance; it is
+/// expanded against live entity records when the migration is applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntityStateRemap {
+    pub entity_id: String,
+    /// v1 state -> v2 state. States absent from the map are left as-is.
+    pub states: BTreeMap<String, String>,
+}
+
+/// Executable migration artifact written by `tenor migrate --out`.
+///
+/// Carries the confirmed state remaps between two contract versions so a
+/// storage backend can apply them to live `EntityStateRecord`s.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationArtifact {
+    pub kind: String,
+    pub v1_id: String,
+    pub v2_id: String,
+    pub entity_state_remaps: Vec<EntityStateRemap>,
+}
+
+impl MigrationArtifact {
+    pub fn new(v1_id: &str, v2_id: &str, entity_state_remaps: Vec<EntityStateRemap>) -> Self {
+        MigrationArtifact {
+            kind: MIGRATION_ARTIFACT_KIND.to_string(),
+            v1_id: v1_id.to_string(),
+            v2_id: v2_id.to_string(),
+            entity_state_remaps,
+        }
+    }
+
+    /// Expand the remaps against live entity records.
+    ///
+    /// Yields one mapping per instance whose state actually changes.
+    pub fn instance_mappings(&self, records: &[EntityStateRecord]) -> Vec<EntityStateMapping> {
+        let remaps: BTreeMap<&str, &BTreeMap<String, String>> = self
+            .entity_state_remaps
+            .iter()
+            .map(|r| (r.entity_id.as_str(), &r.states))
+            .collect();
+
+        records
+            .iter()
+            .filter_map(|rec| {
+                let to_state = remaps.get(rec.entity_id.as_str())?.get(&rec.state)?;
+                (*to_state != rec.state).then(|| EntityStateMapping {
+                    entity_id: rec.entity_id.clone(),
+                    instance_id: rec.instance_id.clone(),
+                    from_state: rec.state.clone(),
+                    to_state: to_state.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Default remaps for every entity present in both versions whose state
+/// set changed.
+///
+/// Retained states map to themselves; removed states map to the v2
+/// initial state, matching the suggestion in [`build_migration_plan`].
+pub fn default_state_remaps(v1_bundle: &Value, v2_bundle: &Value) -> Vec<EntityStateRemap> {
+    let v1_entities = entity_states(v1_bundle);
+    let v2_entities = entity_states(v2_bundle);
+
+    let mut remaps = Vec::new();
+    for (entity_id, (v1_states, _)) in &v1_entities {
+        let Some((v2_states, v2_initial)) = v2_entities.get(entity_id) else {
+            continue;
+        };
+        if v1_states == v2_states {
+            continue;
+        }
+        let states = v1_states
+            .iter()
+            .map(|s| {
+                let target = if v2_states.contains(s) {
+                    s.clone()
+                } else {
+                    v2_initial
+                        .clone()
+                        .unwrap_or_else(|| "(unknown)".to_string())
+                };
+                (s.clone(), target)
+            })
+            .collect();
+        remaps.push(EntityStateRemap {
+            entity_id: entity_id.clone(),
+            states,
+        });
+    }
+    remaps
+}
+
+/// Check state remaps against both contract versions.
+///
+/// Every remapped entity must exist in both versions, every source must be
+/// a v1 state, every target a v2 state, and every state removed in v2 must
+/// be mapped somewhere.
+pub fn validate_state_remaps(
+    v1_bundle: &Value,
+    v2_bundle: &Value,
+    remaps: &[EntityStateRemap],
+) -> Result<(), MigrationError> {
+    let v1_entities = entity_states(v1_bundle);
+    let v2_entities = entity_states(v2_bundle);
+    let invalid = |msg: String| Err(MigrationError::InvalidStateMapping(msg));
+
+    let by_entity: BTreeMap<&str, &EntityStateRemap> =
+        remaps.iter().map(|r| (r.entity_id.as_str(), r)).collect();
+
+    for remap in remaps {
+        let (Some((v1_states, _)), Some((v2_states, _))) = (
+            v1_entities.get(&remap.entity_id),
+            v2_entities.get(&remap.entity_id),
+        ) else {
+            return invalid(format!(
+                "entity '{}' does not exist in both versions",
+                remap.entity_id
+            ));
+        };
+        for (from, to) in &remap.states {
+            if !v1_states.contains(from) {
+                return invalid(format!(
+                    "'{}' is not a v1 state of entity '{}'",
+                    from, remap.entity_id
+                ));
+            }
+            if !v2_states.contains(to) {
+                return invalid(format!(
+                    "cannot map {}.{} to '{}': not a v2 state",
+                    remap.entity_id, from, to
+                ));
+            }
+        }
+    }
+
+    for (entity_id, (v1_states, _)) in &v1_entities {
+        let Some((v2_states, _)) = v2_entities.get(entity_id) else {
+            continue;
+        };
+        for removed in v1_states.difference(v2_states) {
+            let mapped = by_entity
+                .get(entity_id.as_str())
+                .is_some_and(|r| r.states.contains_key(removed));
+            if !mapped {
+                return invalid(format!(
+                    "state '{}' of entity '{}' is removed in v2 and has no mapping",
+                    removed, entity_id
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Entity id -> (declared states, initial state) for every entity in a bundle.
+fn entity_states(bundle: &Value) -> BTreeMap<String, (BTreeSet<String>, Option<String>)> {
+    bundle
+        .get("constructs")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|c| c.get("kind").and_then(|k| k.as_str()) == Some("Entity"))
+        .filter_map(|c| {
+            let id = c.get("id")?.as_str()?.to_string();
+            let states = c
+                .get("states")
+                .and_then(|s| s.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect();
+            let initial = c
+                .get("initial")
+                .and_then(|i| i.as_str())
+                .map(str::to_string);
+            Some((id, (states, initial)))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,4 +493,94 @@ mod tests {
         assert_eq!(json["severity"], "Safe");
         assert_eq!(json["recommended_policy"], "Abort");
     }
+
+    fn order_versions() -> (Value, Value) {
+        let v1 = make_bundle_with_id(
+            "contract_v1",
+            vec![make_entity(
+                "Order",
+                vec!["draft", "submitted", "approved"],
+                "draft",
+                vec![],
+            )],
+        );
+        let v2 = make_bundle_with_id(
+            "contract_v2",
+            vec![make_entity(
+                "Order",
+                vec!["draft", "submitted", "accepted"],
+                "draft",
+                vec![],
+            )],
+        );
+        (v1, v2)
+    }
+
+    fn record(entity_id: &str, instance_id: &str, state: &str) -> EntityStateRecord {
+        EntityStateRecord {
+            entity_id: entity_id.to_string(),
+            instance_id: instance_id.to_string(),
+            state: state.to_string(),
+            version: 0,
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            last_flow_id: None,
+            last_operation_id: None,
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn default_remaps_keep_retained_states() {
+        let (v1, v2) = order_versions();
+        let remaps = default_state_remaps(&v1, &v2);
+        assert_eq!(remaps.len(), 1);
+        assert_eq!(remaps[0].entity_id, "Order");
+        assert_eq!(remaps[0].states["draft"], "draft");
+        assert_eq!(remaps[0].states["submitted"], "submitted");
+        assert_eq!(remaps[0].states["approved"], "draft");
+        assert!(validate_state_remaps(&v1, &v2, &remaps).is_ok());
+
+        assert!(default_state_remaps(&v1, &v1).is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_target_and_unmapped_removal() {
+        let (v1, v2) = order_versions();
+
+        let mut remaps = default_state_remaps(&v1, &v2);
+        remaps[0]
+            .states
+            .insert("approved".to_string(), "approved".to_string());
+        let err = validate_state_remaps(&v1, &v2, &remaps).unwrap_err();
+        assert!(err.to_string().contains("not a v2 state"), "{}", err);
+
+        remaps[0].states.remove("approved");
+        let err = validate_state_remaps(&v1, &v2, &remaps).unwrap_err();
+        assert!(err.to_string().contains("has no mapping"), "{}", err);
+    }
+
+    #[test]
+    fn artifact_expands_against_live_records() {
+        let (v1, v2) = order_versions();
+        let mut remaps = default_state_remaps(&v1, &v2);
+        remaps[0]
+            .states
+            .insert("approved".to_string(), "accepted".to_string());
+        let artifact = MigrationArtifact::new("contract_v1", "contract_v2", remaps);
+
+        let mappings = artifact.instance_mappings(&[
+            record("Order", "o-1", "approved"),
+            record("Order", "o-2", "draft"),
+            record("Invoice", "i-1", "approved"),
+        ]);
+        assert_eq!(mappings.len(), 1);
+        assert_eq!(mappings[0].instance_id, "o-1");
+        assert_eq!(mappings[0].from_state, "approved");
+        assert_eq!(mappings[0].to_state, "accepted");
+
+        let json = serde_json::to_value(&artifact).unwrap();
+        assert_eq!(json["kind"], MIGRATION_ARTIFACT_KIND);
+        let back: MigrationArtifact = serde_json::from_value(json).unwrap();
+        assert_eq!(back.entity_state_remaps, artifact.entity_state_remaps);
+    }
 }
@@ -1,14 +1,47 @@
 //! NumericModel implementation using `rust_decimal`.
 //!
 //! Implements spec Section 12: all arithmetic uses `rust_decimal::Decimal`
-//! with `RoundingStrategy::MidpointNearestEven`. No `f64` anywhere in
-//! the evaluation path.
+//! and rounds with the type's declared rounding mode, defaulting to
+//! `RoundingStrategy::MidpointNearestEven`. No `f64` anywhere in the
+//! evaluation path.
 
 use rust_decimal::Decimal;
 use rust_decimal::RoundingStrategy;
 
 use crate::types::{EvalError, TypeSpec, Value};
 
+/// The rounding strategy for a type's declared rounding mode.
+///
+/// `half_up` rounds half away from zero; an undeclared mode rounds half
+/// to even (banker's rounding).
+pub fn rounding_strategy(rounding: Option<&str>) -> RoundingStrategy {
+    match rounding {
+        Some("half_up") => RoundingStrategy::MidpointAwayFromZero,
+        _ => RoundingStrategy::MidpointNearestEven,
+    }
+}
+
+/// Fit a fact value to its declared precision and scale.
+///
+/// Excess fractional digits are rounded with the declared `rounding` mode;
+/// a type that declares none rejects them rather than rounding silently.
+/// Returns `None` when the value cannot be fitted.
+pub fn fit_to_scale(
+    val: Decimal,
+    precision: u32,
+    scale: u32,
+    rounding: Option<&str>,
+) -> Option<Decimal> {
+    let fitted = if val.normalize().scale() <= scale {
+        val
+    } else {
+        let mode = rounding?;
+        val.round_dp_with_strategy(scale, rounding_strategy(Some(mode)))
+    };
+    check_precision(fitted, precision, scale).ok()?;
+    Some(fitted)
+}
+
 /// Promote an integer to Decimal with the given target type's precision/scale.
 ///
 /// Per spec Section 12.2, Int-to-Decimal promotion converts the integer
@@ -16,24 +49,24 @@ use crate::types::{EvalError, TypeSpec, Value};
 pub fn promote_int_to_decimal(val: i64, target: &TypeSpec) -> Result<Decimal, EvalError> {
     let d = Decimal::from(val);
     let scale = target.scale.unwrap_or(0);
-    Ok(d.round_dp_with_strategy(scale, RoundingStrategy::MidpointNearestEven))
+    Ok(d.round_dp_with_strategy(scale, rounding_strategy(target.rounding.as_deref())))
 }
 
 /// Evaluate multiplication with overflow checking and rounding.
 ///
 /// Both operands are Decimal values. The result is rounded to the specified
-/// precision/scale and checked for overflow.
+/// precision/scale with the `rounding` mode and checked for overflow.
 pub fn eval_mul(
     left: Decimal,
     right: Decimal,
     result_precision: u32,
     result_scale: u32,
+    rounding: Option<&str>,
 ) -> Result<Decimal, EvalError> {
     let product = left.checked_mul(right).ok_or_else(|| EvalError::Overflow {
         message: "multiplication overflow".to_string(),
     })?;
-    let rounded =
-        product.round_dp_with_strategy(result_scale, RoundingStrategy::MidpointNearestEven);
+    let rounded = product.round_dp_with_strategy(result_scale, rounding_strategy(rounding));
     // Check that result fits in declared precision
     check_precision(rounded, result_precision, result_scale)?;
     Ok(rounded)
@@ -62,6 +95,39 @@ pub fn eval_int_mul(left: i64, literal: i64, result_type: &TypeSpec) -> Result<V
     Ok(Value::Int(product))
 }
 
+/// Convert a Money amount into the currency of `rate`, the value of one
+/// unit of the amount's currency.
+///
+/// The product is exact (no rounding); a conversion into the amount's own
+/// currency is a type error. A Decimal quantity is converted by a Decimal
+/// conversion factor; units are checked when the contract is loaded.
+pub fn eval_convert(amount: &Value, rate: &Value) -> Result<Value, EvalError> {
+    if let (Value::Decimal(quantity), Value::Decimal(factor)) = (amount, rate) {
+        return quantity
+            .checked_mul(*factor)
+            .map(Value::Decimal)
+            .ok_or_else(|| EvalError::Overflow {
+                message: "unit conversion overflow".to_string(),
+            });
+    }
+    let (amount, from) = coerce_to_money(amount)?;
+    let (rate, to) = coerce_to_money(rate)?;
+    if from == to {
+        return Err(EvalError::TypeError {
+            message: format!("cannot convert Money from {} into the same currency", from),
+        });
+    }
+    let converted = amount
+        .checked_mul(rate)
+        .ok_or_else(|| EvalError::Overflow {
+            message: format!("currency conversion overflow converting {} to {}", from, to),
+        })?;
+    Ok(Value::Money {
+        amount: converted,
+        currency: to.to_string(),
+    })
+}
+
 /// Check that a decimal value fits within the declared precision.
 ///
 /// Uses checked Decimal arithmetic to avoid panics when precision > 18.
@@ -253,7 +319,7 @@ fn coerce_to_decimal(val: &Value, target: &TypeSpec) -> Result<Decimal, EvalErro
     match val {
         Value::Decimal(d) => {
             let scale = target.scale.unwrap_or(0);
-            Ok(d.round_dp_with_strategy(scale, RoundingStrategy::MidpointNearestEven))
+            Ok(d.round_dp_with_strategy(scale, rounding_strategy(target.rounding.as_deref())))
         }
         Value::Int(i) => promote_int_to_decimal(*i, target),
         _ => Err(EvalError::TypeError {
@@ -348,6 +414,7 @@ mod tests {
             base: "Decimal".to_string(),
             precision: Some(10),
             scale: Some(2),
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -368,6 +435,7 @@ mod tests {
             base: "Decimal".to_string(),
             precision: Some(5),
             scale: Some(0),
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -384,14 +452,14 @@ mod tests {
 
     #[test]
     fn eval_mul_basic() {
-        let result = eval_mul(dec("10.50"), dec("3"), 10, 2).unwrap();
+        let result = eval_mul(dec("10.50"), dec("3"), 10, 2, None).unwrap();
         assert_eq!(result, dec("31.50"));
     }
 
     #[test]
     fn eval_mul_overflow() {
         // precision=4, scale=2 means max integer part is 99
-        let result = eval_mul(dec("50.00"), dec("3"), 4, 2);
+        let result = eval_mul(dec("50.00"), dec("3"), 4, 2, None);
         assert!(result.is_err());
         if let Err(EvalError::Overflow { .. }) = result {
             // expected
@@ -404,19 +472,56 @@ mod tests {
     fn eval_mul_rounding_midpoint_nearest_even() {
         // Test MidpointNearestEven: 2.5 rounds to 2, 3.5 rounds to 4
         // (banker's rounding)
-        let result = eval_mul(dec("2.5"), dec("1"), 10, 0).unwrap();
+        let result = eval_mul(dec("2.5"), dec("1"), 10, 0, None).unwrap();
         assert_eq!(result, dec("2")); // 2.5 -> 2 (nearest even)
 
-        let result = eval_mul(dec("3.5"), dec("1"), 10, 0).unwrap();
+        let result = eval_mul(dec("3.5"), dec("1"), 10, 0, None).unwrap();
         assert_eq!(result, dec("4")); // 3.5 -> 4 (nearest even)
     }
 
+    #[test]
+    fn eval_mul_rounding_half_up() {
+        let result = eval_mul(dec("2.5"), dec("1"), 10, 0, Some("half_up")).unwrap();
+        assert_eq!(result, dec("3"));
+
+        let result = eval_mul(dec("-2.5"), dec("1"), 10, 0, Some("half_up")).unwrap();
+        assert_eq!(result, dec("-3")); // away from zero
+    }
+
+    #[test]
+    fn fit_to_scale_rounds_only_with_declared_mode() {
+        assert_eq!(fit_to_scale(dec("42.50"), 10, 2, None), Some(dec("42.50")));
+        // Trailing zeros beyond the scale lose nothing
+        assert_eq!(
+            fit_to_scale(dec("42.5000"), 10, 2, None),
+            Some(dec("42.5000"))
+        );
+        assert_eq!(fit_to_scale(dec("42.505"), 10, 2, None), None);
+        assert_eq!(
+            fit_to_scale(dec("42.505"), 10, 2, Some("half_even")),
+            Some(dec("42.50"))
+        );
+        assert_eq!(
+            fit_to_scale(dec("42.505"), 10, 2, Some("half_up")),
+            Some(dec("42.51"))
+        );
+    }
+
+    #[test]
+    fn fit_to_scale_rejects_excess_integer_digits() {
+        assert_eq!(fit_to_scale(dec("999.99"), 5, 2, None), Some(dec("999.99")));
+        assert_eq!(fit_to_scale(dec("1000.00"), 5, 2, Some("half_up")), None);
+        // Rounding up may carry into an extra integer digit
+        assert_eq!(fit_to_scale(dec("999.995"), 5, 2, Some("half_up")), None);
+    }
+
     #[test]
     fn eval_int_mul_basic() {
         let result_type = TypeSpec {
             base: "Int".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: Some(0),
             max: Some(100),
@@ -437,6 +542,7 @@ mod tests {
             base: "Int".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: Some(0),
             max: Some(100),
@@ -489,6 +595,7 @@ mod tests {
             base: "Money".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: Some("USD".to_string()),
             min: None,
             max: None,
@@ -536,6 +643,7 @@ mod tests {
             base: "Decimal".to_string(),
             precision: Some(9),
             scale: Some(2),
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -562,6 +670,7 @@ mod tests {
             base: "Decimal".to_string(),
             precision: Some(9),
             scale: Some(2),
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -661,6 +770,7 @@ mod tests {
             base: "Money".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: Some("USD".to_string()),
             min: None,
             max: None,
@@ -699,6 +809,7 @@ mod tests {
             base: "Money".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: Some("USD".to_string()),
             min: None,
             max: None,
@@ -8,12 +8,21 @@
 //! 2. Precondition evaluation
 //! 3. Effect execution (entity state transitions)
 //! 4. Outcome determination (single or multi-outcome routing)
+//!
+//! [`preview_operation`] runs the same checks as a dry run, reporting the
+//! effects and outcome without applying them.
+//!
+//! [`EntityVersions`] adds optimistic concurrency: an effect on an instance
+//! that changed since the caller last read it fails with
+//! [`OperationError::ConcurrentConflict`], which a flow routes through the
+//! step's `on_failure` like any other operation failure.
 
 use std::collections::{BTreeMap, BTreeSet};
 
 use crate::predicate::{eval_pred, EvalContext};
 use crate::provenance::ProvenanceCollector;
-use crate::types::{EvalError, FactSet, Operation, VerdictSet};
+use crate::symbol::Symbol;
+use crate::types::{EffectKind, EvalError, FactSet, Operation, Predicate, Value, VerdictSet};
 
 // ──────────────────────────────────────────────
 // Operation execution types
@@ -22,11 +31,18 @@ use crate::types::{EvalError, FactSet, Operation, VerdictSet};
 /// Default instance ID for single-instance entities per §6.5 degenerate case.
 pub const DEFAULT_INSTANCE_ID: &str = "_default";
 
+/// Map of (entity_id, instance_id) -> attribute values set on that instance.
+///
+/// Instances start with no attribute values; effects add them. An instance
+/// missing from the map has no attribute values.
+pub type EntityAttributeMap = BTreeMap<(Symbol, Symbol), BTreeMap<String, Value>>;
+
 /// Map of (entity_id, instance_id) -> current state name.
 ///
 /// Per §6.5: every entity instance is identified by a composite key.
-/// Single-instance contracts use `_default` as the instance_id.
-pub type EntityStateMap = BTreeMap<(String, String), String>;
+/// Single-instance contracts use `_default` as the instance_id. Keys and
+/// states are shared [`Symbol`]s, so copying entries never allocates.
+pub type EntityStateMap = BTreeMap<(Symbol, Symbol), Symbol>;
 
 /// Maps entity_id → instance_id for instance targeting in operations and flows.
 ///
@@ -35,22 +51,101 @@ pub type EntityStateMap = BTreeMap<(String, String), String>;
 /// for backward compatibility with single-instance contracts.
 pub type InstanceBindingMap = BTreeMap<String, String>;
 
+/// Map of (entity_id, instance_id) -> version of the instance's state.
+///
+/// Versions count as `EntityStateRecord::version` does in storage: 0 at
+/// initialization, plus one for every transition.
+pub type EntityVersionMap = BTreeMap<(Symbol, Symbol), i64>;
+
+/// Versions of entity instances, for compare-and-swap on transitions.
+///
+/// `current` holds the versions read along with the [`EntityStateMap`];
+/// `expected` holds the versions the caller last observed each instance at.
+/// An instance is only checked when both maps hold a version for it, so the
+/// default checks nothing. When an executor then writes the transitions,
+/// `TenorStorage::update_entity_state` repeats the check against the
+/// current version, which catches a writer racing in after the read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntityVersions {
+    pub current: EntityVersionMap,
+    pub expected: EntityVersionMap,
+}
+
+impl EntityVersions {
+    /// The expected and actual versions of `key`, if they differ.
+    fn conflict(&self, key: &(Symbol, Symbol)) -> Option<(i64, i64)> {
+        let expected = *self.expected.get(key)?;
+        let actual = *self.current.get(key)?;
+        (expected != actual).then_some((expected, actual))
+    }
+}
+
 /// Create a single-instance state map from entity_id -> state (backward compat).
 /// Each entity gets the `_default` instance ID per §6.5 degenerate case.
 pub fn single_instance(states: BTreeMap<String, String>) -> EntityStateMap {
     states
         .into_iter()
-        .map(|(entity_id, state)| ((entity_id, DEFAULT_INSTANCE_ID.to_string()), state))
+        .map(|(entity_id, state)| {
+            (
+                (Symbol::from(entity_id), Symbol::new(DEFAULT_INSTANCE_ID)),
+                Symbol::from(state),
+            )
+        })
         .collect()
 }
 
+/// Build the [`EntityStateMap`] key for an (entity_id, instance_id) pair.
+pub fn instance_key(entity_id: &str, instance_id: &str) -> (Symbol, Symbol) {
+    (Symbol::new(entity_id), Symbol::new(instance_id))
+}
+
 /// Get state for a specific (entity_id, instance_id) pair.
 pub fn get_instance_state<'a>(
     states: &'a EntityStateMap,
     entity_id: &str,
     instance_id: &str,
-) -> Option<&'a String> {
-    states.get(&(entity_id.to_string(), instance_id.to_string()))
+) -> Option<&'a Symbol> {
+    states.get(&instance_key(entity_id, instance_id))
+}
+
+/// Get the attribute values set on a specific (entity_id, instance_id) pair.
+pub fn get_instance_attributes<'a>(
+    attributes: &'a EntityAttributeMap,
+    entity_id: &str,
+    instance_id: &str,
+) -> Option<&'a BTreeMap<String, Value>> {
+    attributes.get(&instance_key(entity_id, instance_id))
+}
+
+/// Generate the instance_id for an instance created by an effect.
+///
+/// Ids are `{entity_id}-{n}`, counting up from one past the number of
+/// existing instances of the entity until an unused id is found, so replaying
+/// the same operations against the same state yields the same ids.
+pub fn next_instance_id(states: &EntityStateMap, entity_id: &str) -> String {
+    allocate_instance_id(states, &EntityStateMap::new(), entity_id)
+}
+
+/// [`next_instance_id`] over the union of `states` and instances created
+/// earlier in the same operation.
+fn allocate_instance_id(
+    states: &EntityStateMap,
+    created: &EntityStateMap,
+    entity_id: &str,
+) -> String {
+    let entity = Symbol::new(entity_id);
+    let existing = states
+        .keys()
+        .chain(created.keys())
+        .filter(|(e, _)| *e == entity)
+        .count();
+    (existing + 1..)
+        .map(|n| format!("{}-{}", entity_id, n))
+        .find(|id| {
+            let key = (entity.clone(), Symbol::new(id));
+            !states.contains_key(&key) && !created.contains_key(&key)
+        })
+        .expect("unbounded range always yields an unused id")
 }
 
 /// Resolve the target instance_id for a given entity from the binding map.
@@ -67,12 +162,18 @@ pub fn resolve_instance_id<'a>(bindings: &'a InstanceBindingMap, entity_id: &str
 /// Record of a single entity state transition applied by an operation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EffectRecord {
-    pub entity_id: String,
+    pub entity_id: Symbol,
     /// The specific instance that was targeted by this effect.
     /// Per §9.5: provenance records instance_binding.
-    pub instance_id: String,
-    pub from_state: String,
-    pub to_state: String,
+    pub instance_id: Symbol,
+    /// Whether the effect moved, created, or destroyed the instance.
+    pub kind: EffectKind,
+    /// Empty for a created instance.
+    pub from_state: Symbol,
+    /// Empty for a destroyed instance.
+    pub to_state: Symbol,
+    /// Attribute values set by this effect. Empty if it sets none.
+    pub attributes: BTreeMap<String, Value>,
 }
 
 /// Provenance record for an operation execution.
@@ -97,10 +198,10 @@ pub struct OperationProvenance {
     pub instance_binding: BTreeMap<String, String>,
     /// Per-instance entity state BEFORE effects were applied.
     /// Key: (entity_id, instance_id). Only includes instances targeted by effects.
-    pub state_before: BTreeMap<(String, String), String>,
+    pub state_before: EntityStateMap,
     /// Per-instance entity state AFTER effects were applied.
     /// Key: (entity_id, instance_id). Only includes instances targeted by effects.
-    pub state_after: BTreeMap<(String, String), String>,
+    pub state_after: EntityStateMap,
 }
 
 /// Result of a successful operation execution.
@@ -124,6 +225,13 @@ pub enum OperationError {
         operation_id: String,
         condition_desc: String,
     },
+    /// The guard on an entity transition taken by an effect was not met.
+    TransitionGuardFailed {
+        operation_id: String,
+        entity_id: String,
+        from: String,
+        to: String,
+    },
     /// Entity is not in the expected state for the effect.
     InvalidEntityState {
         entity_id: String,
@@ -136,6 +244,14 @@ pub enum OperationError {
         entity_id: String,
         instance_id: String,
     },
+    /// The instance is no longer at the version the caller expected:
+    /// another executor transitioned it since the caller read it.
+    ConcurrentConflict {
+        entity_id: String,
+        instance_id: String,
+        expected_version: i64,
+        actual_version: i64,
+    },
     /// Evaluation error during precondition check.
     EvalError(EvalError),
 }
@@ -163,6 +279,18 @@ impl std::fmt::Display for OperationError {
                     operation_id, condition_desc
                 )
             }
+            OperationError::TransitionGuardFailed {
+                operation_id,
+                entity_id,
+                from,
+                to,
+            } => {
+                write!(
+                    f,
+                    "guard on transition ({}, {}) of entity '{}' not met for operation '{}'",
+                    from, to, entity_id, operation_id
+                )
+            }
             OperationError::InvalidEntityState {
                 entity_id,
                 instance_id,
@@ -185,6 +313,18 @@ impl std::fmt::Display for OperationError {
                     entity_id, instance_id
                 )
             }
+            OperationError::ConcurrentConflict {
+                entity_id,
+                instance_id,
+                expected_version,
+                actual_version,
+            } => {
+                write!(
+                    f,
+                    "entity '{}' instance '{}' is at version {}, expected {}: modified concurrently",
+                    entity_id, instance_id, actual_version, expected_version
+                )
+            }
             OperationError::EvalError(e) => write!(f, "evaluation error: {}", e),
         }
     }
@@ -192,6 +332,22 @@ impl std::fmt::Display for OperationError {
 
 impl std::error::Error for OperationError {}
 
+impl OperationError {
+    /// A stable snake_case name for the error variant, as in
+    /// `"precondition_failed"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OperationError::PersonaRejected { .. } => "persona_rejected",
+            OperationError::PreconditionFailed { .. } => "precondition_failed",
+            OperationError::TransitionGuardFailed { .. } => "transition_guard_failed",
+            OperationError::InvalidEntityState { .. } => "invalid_entity_state",
+            OperationError::EntityNotFound { .. } => "entity_not_found",
+            OperationError::ConcurrentConflict { .. } => "concurrent_conflict",
+            OperationError::EvalError(_) => "eval_error",
+        }
+    }
+}
+
 impl From<EvalError> for OperationError {
     fn from(e: EvalError) -> Self {
         OperationError::EvalError(e)
@@ -206,8 +362,8 @@ pub fn init_entity_states(contract: &crate::types::Contract) -> EntityStateMap {
     let mut map = EntityStateMap::new();
     for entity in &contract.entities {
         map.insert(
-            (entity.id.clone(), DEFAULT_INSTANCE_ID.to_string()),
-            entity.initial.clone(),
+            instance_key(&entity.id, DEFAULT_INSTANCE_ID),
+            Symbol::new(&entity.initial),
         );
     }
     map
@@ -217,7 +373,7 @@ pub fn init_entity_states(contract: &crate::types::Contract) -> EntityStateMap {
 ///
 /// Follows spec §9.3:
 /// 1. Persona check
-/// 2. Precondition evaluation
+/// 2. Precondition and transition guard evaluation
 /// 3. Outcome determination
 /// 4. Transition source validation
 /// 5. Atomic effect application
@@ -226,13 +382,29 @@ pub fn init_entity_states(contract: &crate::types::Contract) -> EntityStateMap {
 /// which specific instance each entity effect targets. An empty map falls back
 /// to DEFAULT_INSTANCE_ID for each entity (backward compat with single-instance
 /// contracts per §6.5 degenerate case).
+///
+/// The precondition, transition guards, and `set` terms can read the
+/// attributes of each targeted instance as `Entity.attribute`; they see the
+/// values from before any effect is applied.
+///
+/// A `CreateInstance` effect ignores the binding for its entity: it inserts a
+/// new instance under [`next_instance_id`] in the entity's initial state, and
+/// provenance binds the entity to that new id. A `DestroyInstance` effect
+/// removes the bound instance and its attributes.
+///
+/// Before any mutation, each instance an effect moves or destroys is checked
+/// against `entity_versions`; a version mismatch fails the operation with
+/// [`OperationError::ConcurrentConflict`] and leaves everything untouched.
+#[allow(clippy::too_many_arguments)]
 pub fn execute_operation(
     op: &Operation,
     persona: &str,
     facts: &FactSet,
     verdicts: &VerdictSet,
     entity_states: &mut EntityStateMap,
+    entity_attributes: &mut EntityAttributeMap,
     instance_bindings: &InstanceBindingMap,
+    entity_versions: &EntityVersions,
 ) -> Result<OperationResult, OperationError> {
     // Step 1: Persona check
     if !op.allowed_personas.contains(&persona.to_string()) {
@@ -244,7 +416,29 @@ pub fn execute_operation(
 
     // Step 2: Precondition check
     let mut collector = ProvenanceCollector::new();
-    let ctx = EvalContext::new();
+    let reads_instances = op.precondition.reads_instances()
+        || op
+            .effects
+            .iter()
+            .filter_map(|e| e.guard.as_ref())
+            .any(Predicate::reads_instances);
+    let mut ctx = if reads_instances {
+        EvalContext::with_instances(entity_states, entity_attributes)
+    } else {
+        EvalContext::new()
+    };
+    for effect in &op.effects {
+        let instance_id = resolve_instance_id(instance_bindings, &effect.entity_id);
+        let attributes = match effect.kind {
+            EffectKind::CreateInstance => BTreeMap::new(),
+            _ => get_instance_attributes(entity_attributes, &effect.entity_id, instance_id)
+                .cloned()
+                .unwrap_or_default(),
+        };
+        ctx.bindings
+            .entry(effect.entity_id.clone())
+            .or_insert(Value::Record(attributes));
+    }
     let cond_result = eval_pred(&op.precondition, facts, verdicts, &ctx, &mut collector)?;
     let precondition_met = cond_result.as_bool()?;
     if !precondition_met {
@@ -253,8 +447,36 @@ pub fn execute_operation(
             condition_desc: "precondition evaluated to false".to_string(),
         });
     }
+    for effect in &op.effects {
+        let Some(guard) = &effect.guard else {
+            continue;
+        };
+        if !eval_pred(guard, facts, verdicts, &ctx, &mut collector)?.as_bool()? {
+            return Err(OperationError::TransitionGuardFailed {
+                operation_id: op.id.clone(),
+                entity_id: effect.entity_id.clone(),
+                from: effect.from.clone(),
+                to: effect.to.clone(),
+            });
+        }
+    }
 
-    // Capture facts_used and verdicts_used from precondition evaluation.
+    // Evaluate attribute assignments up front, so a failing term leaves
+    // nothing applied.
+    let mut attribute_sets = Vec::with_capacity(op.effects.len());
+    for effect in &op.effects {
+        let mut set = BTreeMap::new();
+        for (name, term) in &effect.set {
+            set.insert(
+                name.clone(),
+                eval_pred(term, facts, verdicts, &ctx, &mut collector)?,
+            );
+        }
+        attribute_sets.push(set);
+    }
+
+    // Capture facts_used and verdicts_used from precondition, guard, and
+    // attribute assignment evaluation.
     let facts_used: BTreeSet<String> = collector.facts_used.into_iter().collect();
     let verdicts_used: BTreeSet<String> = collector.verdicts_used.into_iter().collect();
 
@@ -264,52 +486,86 @@ pub fn execute_operation(
     // Step 4: Transition source validation — resolve all target instances
     // and verify from-state before any mutation.
     let mut instance_binding_map: BTreeMap<String, String> = BTreeMap::new();
-    let mut state_before: BTreeMap<(String, String), String> = BTreeMap::new();
+    let mut state_before = EntityStateMap::new();
+    let mut keys = Vec::with_capacity(op.effects.len());
+    let mut created = EntityStateMap::new();
 
     for effect in &op.effects {
-        let instance_id = resolve_instance_id(instance_bindings, &effect.entity_id).to_string();
+        if effect.kind == EffectKind::CreateInstance {
+            let instance_id = allocate_instance_id(entity_states, &created, &effect.entity_id);
+            let key = instance_key(&effect.entity_id, &instance_id);
+            created.insert(key.clone(), Symbol::new(&effect.to));
+            instance_binding_map.insert(effect.entity_id.clone(), instance_id);
+            keys.push(key);
+            continue;
+        }
+
+        let instance_id = resolve_instance_id(instance_bindings, &effect.entity_id);
         instance_binding_map
             .entry(effect.entity_id.clone())
-            .or_insert_with(|| instance_id.clone());
-
-        let key = (effect.entity_id.clone(), instance_id.clone());
-        let current_state = entity_states
-            .get(&key)
-            .ok_or_else(|| OperationError::EntityNotFound {
+            .or_insert_with(|| instance_id.to_string());
+
+        let key = instance_key(&effect.entity_id, instance_id);
+        let current_state =
+            entity_states
+                .get(&key)
+                .cloned()
+                .ok_or_else(|| OperationError::EntityNotFound {
+                    entity_id: effect.entity_id.clone(),
+                    instance_id: instance_id.to_string(),
+                })?;
+
+        if let Some((expected_version, actual_version)) = entity_versions.conflict(&key) {
+            return Err(OperationError::ConcurrentConflict {
                 entity_id: effect.entity_id.clone(),
-                instance_id: instance_id.clone(),
-            })?
-            .clone();
+                instance_id: instance_id.to_string(),
+                expected_version,
+                actual_version,
+            });
+        }
 
         if current_state != effect.from {
             return Err(OperationError::InvalidEntityState {
                 entity_id: effect.entity_id.clone(),
-                instance_id: instance_id.clone(),
+                instance_id: instance_id.to_string(),
                 expected: effect.from.clone(),
-                actual: current_state,
+                actual: current_state.to_string(),
             });
         }
 
-        state_before.entry(key).or_insert_with(|| current_state);
+        state_before.entry(key.clone()).or_insert(current_state);
+        keys.push(key);
     }
 
     // Step 5: Atomic effect application
     let mut effects_applied = Vec::new();
-    for effect in &op.effects {
-        let instance_id = resolve_instance_id(instance_bindings, &effect.entity_id).to_string();
-        let key = (effect.entity_id.clone(), instance_id.clone());
-
-        entity_states.insert(key, effect.to.clone());
-        effects_applied.push(EffectRecord {
-            entity_id: effect.entity_id.clone(),
-            instance_id,
-            from_state: effect.from.clone(),
-            to_state: effect.to.clone(),
-        });
+    for ((effect, set), key) in op.effects.iter().zip(attribute_sets).zip(keys) {
+        if !set.is_empty() {
+            entity_attributes
+                .entry(key.clone())
+                .or_default()
+                .extend(set.clone());
+        }
+        let record = EffectRecord {
+            entity_id: key.0.clone(),
+            instance_id: key.1.clone(),
+            kind: effect.kind,
+            from_state: Symbol::new(&effect.from),
+            to_state: Symbol::new(&effect.to),
+            attributes: set,
+        };
+
+        if record.kind == EffectKind::DestroyInstance {
+            entity_states.remove(&key);
+            entity_attributes.remove(&key);
+        } else {
+            entity_states.insert(key, record.to_state.clone());
+        }
+        effects_applied.push(record);
     }
 
     // Capture state_after for all targeted instances after effects applied.
-    let mut state_after: BTreeMap<(String, String), String> = BTreeMap::new();
+    let mut state_after = EntityStateMap::new();
     for record in &effects_applied {
         let key = (record.entity_id.clone(), record.instance_id.clone());
         if let Some(s) = entity_states.get(&key) {
@@ -336,6 +592,36 @@ pub fn execute_operation(
     })
 }
 
+/// Dry-run an operation.
+///
+/// Runs every check of [`execute_operation`] (persona, precondition,
+/// transition guards, and the source state of each targeted instance) and
+/// returns the outcome and effects it would produce, without touching
+/// `entity_states` or `entity_attributes`. The result's provenance carries
+/// the would-be `state_after`.
+#[allow(clippy::too_many_arguments)]
+pub fn preview_operation(
+    op: &Operation,
+    persona: &str,
+    facts: &FactSet,
+    verdicts: &VerdictSet,
+    entity_states: &EntityStateMap,
+    entity_attributes: &EntityAttributeMap,
+    instance_bindings: &InstanceBindingMap,
+    entity_versions: &EntityVersions,
+) -> Result<OperationResult, OperationError> {
+    execute_operation(
+        op,
+        persona,
+        facts,
+        verdicts,
+        &mut entity_states.clone(),
+        &mut entity_attributes.clone(),
+        instance_bindings,
+        entity_versions,
+    )
+}
+
 /// Determine the outcome label for an operation.
 ///
 /// Per §9.3: outcome is determined before effects are applied.
@@ -375,6 +661,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -429,6 +716,108 @@ mod tests {
     // Persona authorization tests
     // ──────────────────────────────────────
 
+    #[test]
+    fn preview_reports_effects_without_mutating_state() {
+        let op = make_operation(
+            "submit_order",
+            vec!["buyer"],
+            vec![Effect {
+                entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
+                from: "draft".to_string(),
+                to: "submitted".to_string(),
+                outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
+            }],
+            vec!["submitted"],
+        );
+        let entity_states = single_instance(
+            [("order".to_string(), "draft".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        let before = entity_states.clone();
+        let preview = |persona: &str| {
+            preview_operation(
+                &op,
+                persona,
+                &FactSet::new(),
+                &VerdictSet::new(),
+                &entity_states,
+                &EntityAttributeMap::new(),
+                &InstanceBindingMap::new(),
+                &EntityVersions::default(),
+            )
+        };
+
+        let result = preview("buyer").unwrap();
+        assert_eq!(result.outcome, "submitted");
+        assert_eq!(result.effects_applied[0].to_state, "submitted");
+        assert_eq!(
+            result.provenance.state_after[&instance_key("order", DEFAULT_INSTANCE_ID)],
+            "submitted"
+        );
+        assert_eq!(entity_states, before);
+
+        let err = preview("seller").unwrap_err();
+        assert_eq!(err.kind(), "persona_rejected");
+    }
+
+    #[test]
+    fn stale_expected_version_conflicts_without_mutating() {
+        let op = make_operation(
+            "submit_order",
+            vec!["buyer"],
+            vec![Effect {
+                entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
+                from: "draft".to_string(),
+                to: "submitted".to_string(),
+                outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
+            }],
+            vec!["submitted"],
+        );
+        let key = instance_key("order", DEFAULT_INSTANCE_ID);
+        let mut entity_states = single_instance(
+            [("order".to_string(), "draft".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        let mut versions = EntityVersions::default();
+        versions.current.insert(key.clone(), 4);
+        versions.expected.insert(key.clone(), 3);
+        let mut execute = |versions: &EntityVersions| {
+            execute_operation(
+                &op,
+                "buyer",
+                &FactSet::new(),
+                &VerdictSet::new(),
+                &mut entity_states,
+                &mut EntityAttributeMap::new(),
+                &InstanceBindingMap::new(),
+                versions,
+            )
+        };
+
+        let err = execute(&versions).unwrap_err();
+        assert_eq!(
+            err,
+            OperationError::ConcurrentConflict {
+                entity_id: "order".to_string(),
+                instance_id: DEFAULT_INSTANCE_ID.to_string(),
+                expected_version: 3,
+                actual_version: 4,
+            }
+        );
+        assert_eq!(err.kind(), "concurrent_conflict");
+
+        versions.expected.insert(key, 4);
+        assert_eq!(execute(&versions).unwrap().outcome, "submitted");
+    }
+
     #[test]
     fn authorized_persona_succeeds() {
         let op = make_operation(
@@ -436,9 +825,12 @@ mod tests {
             vec!["buyer"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "draft".to_string(),
                 to: "submitted".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["submitted"],
         );
@@ -457,7 +849,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         let res = result.unwrap();
@@ -480,7 +874,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -502,9 +898,12 @@ mod tests {
             vec!["buyer", "admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "submitted".to_string(),
                 to: "reviewed".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["reviewed"],
         );
@@ -525,7 +924,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut states_clone,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
     }
@@ -553,9 +954,12 @@ mod tests {
             },
             vec![Effect {
                 entity_id: "account".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "active".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["activated"],
         );
@@ -573,7 +977,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         assert_eq!(
@@ -612,7 +1018,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -623,6 +1031,341 @@ mod tests {
         }
     }
 
+    fn guarded_submit() -> Operation {
+        make_operation(
+            "submit",
+            vec!["buyer"],
+            vec![Effect {
+                entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
+                from: "draft".to_string(),
+                to: "submitted".to_string(),
+                outcome: None,
+                guard: Some(Predicate::VerdictPresent("order_processable".to_string())),
+                set: BTreeMap::new(),
+            }],
+            vec!["submitted"],
+        )
+    }
+
+    #[test]
+    fn unmet_transition_guard_fails_without_mutation() {
+        let op = guarded_submit();
+        let mut entity_states = single_instance(
+            [("order".to_string(), "draft".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        let result = execute_operation(
+            &op,
+            "buyer",
+            &FactSet::new(),
+            &VerdictSet::new(),
+            &mut entity_states,
+            &mut EntityAttributeMap::new(),
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        );
+        match result.unwrap_err() {
+            OperationError::TransitionGuardFailed {
+                operation_id,
+                entity_id,
+                from,
+                to,
+            } => {
+                assert_eq!(operation_id, "submit");
+                assert_eq!(entity_id, "order");
+                assert_eq!(from, "draft");
+                assert_eq!(to, "submitted");
+            }
+            other => panic!("expected TransitionGuardFailed, got {:?}", other),
+        }
+        assert_eq!(
+            get_instance_state(&entity_states, "order", DEFAULT_INSTANCE_ID).unwrap(),
+            "draft"
+        );
+    }
+
+    #[test]
+    fn met_transition_guard_proceeds_and_is_recorded() {
+        let op = guarded_submit();
+        let mut verdicts = VerdictSet::new();
+        verdicts.push(VerdictInstance {
+            verdict_type: "order_processable".into(),
+            payload: Value::Bool(true),
+            provenance: crate::provenance::VerdictProvenance {
+                rule_id: "check_order".to_string(),
+                stratum: 0,
+                facts_used: vec![],
+                verdicts_used: vec![],
+                conversions: vec![],
+            },
+        });
+        let mut entity_states = single_instance(
+            [("order".to_string(), "draft".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        let result = execute_operation(
+            &op,
+            "buyer",
+            &FactSet::new(),
+            &verdicts,
+            &mut entity_states,
+            &mut EntityAttributeMap::new(),
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .unwrap();
+        assert!(result
+            .provenance
+            .verdicts_used
+            .contains("order_processable"));
+        assert_eq!(
+            get_instance_state(&entity_states, "order", DEFAULT_INSTANCE_ID).unwrap(),
+            "submitted"
+        );
+    }
+
+    #[test]
+    fn effect_sets_attributes_read_by_later_precondition() {
+        let submit = make_operation(
+            "submit",
+            vec!["buyer"],
+            vec![Effect {
+                entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
+                from: "draft".to_string(),
+                to: "submitted".to_string(),
+                outcome: None,
+                guard: None,
+                set: BTreeMap::from([(
+                    "total".to_string(),
+                    Predicate::FactRef("amount".to_string()),
+                )]),
+            }],
+            vec!["submitted"],
+        );
+        let approve = make_operation_with_precondition(
+            "approve",
+            vec!["buyer"],
+            Predicate::Compare {
+                left: Box::new(Predicate::FieldRef {
+                    var: "order".to_string(),
+                    field: "total".to_string(),
+                }),
+                op: ">=".to_string(),
+                right: Box::new(Predicate::Literal {
+                    value: Value::Int(100),
+                    type_spec: TypeSpec {
+                        base: "Int".to_string(),
+                        ..bool_type()
+                    },
+                }),
+                comparison_type: None,
+            },
+            vec![Effect {
+                entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
+                from: "submitted".to_string(),
+                to: "approved".to_string(),
+                outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
+            }],
+            vec!["approved"],
+        );
+        let mut facts = FactSet::new();
+        facts.insert("amount", Value::Int(250));
+        let mut entity_states = single_instance(
+            [("order".to_string(), "draft".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        let mut entity_attributes = EntityAttributeMap::new();
+
+        // Unset attributes cannot be read.
+        assert!(execute_operation(
+            &approve,
+            "buyer",
+            &facts,
+            &VerdictSet::new(),
+            &mut entity_states.clone(),
+            &mut entity_attributes,
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .is_err());
+
+        let result = execute_operation(
+            &submit,
+            "buyer",
+            &facts,
+            &VerdictSet::new(),
+            &mut entity_states,
+            &mut entity_attributes,
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            result.effects_applied[0].attributes.get("total"),
+            Some(&Value::Int(250))
+        );
+        assert!(result.provenance.facts_used.contains("amount"));
+        assert_eq!(
+            get_instance_attributes(&entity_attributes, "order", DEFAULT_INSTANCE_ID)
+                .and_then(|attrs| attrs.get("total")),
+            Some(&Value::Int(250))
+        );
+
+        execute_operation(
+            &approve,
+            "buyer",
+            &facts,
+            &VerdictSet::new(),
+            &mut entity_states,
+            &mut entity_attributes,
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            get_instance_state(&entity_states, "order", DEFAULT_INSTANCE_ID).unwrap(),
+            "approved"
+        );
+    }
+
+    #[test]
+    fn create_effect_adds_instance_with_generated_id() {
+        let op = make_operation(
+            "open_order",
+            vec!["buyer"],
+            vec![Effect {
+                entity_id: "order".to_string(),
+                kind: EffectKind::CreateInstance,
+                from: String::new(),
+                to: "draft".to_string(),
+                outcome: None,
+                guard: None,
+                set: BTreeMap::from([(
+                    "total".to_string(),
+                    Predicate::FactRef("amount".to_string()),
+                )]),
+            }],
+            vec!["opened"],
+        );
+        let mut facts = FactSet::new();
+        facts.insert("amount", Value::Int(250));
+        // One existing instance, so the first generated id is "order-2".
+        let mut entity_states = EntityStateMap::new();
+        entity_states.insert(instance_key("order", "order-1"), "approved".into());
+        let mut entity_attributes = EntityAttributeMap::new();
+
+        let result = execute_operation(
+            &op,
+            "buyer",
+            &facts,
+            &VerdictSet::new(),
+            &mut entity_states,
+            &mut entity_attributes,
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .unwrap();
+        let record = &result.effects_applied[0];
+        assert_eq!(record.kind, EffectKind::CreateInstance);
+        assert_eq!(record.instance_id, "order-2");
+        assert_eq!(result.provenance.instance_binding["order"], "order-2");
+        assert!(result.provenance.state_before.is_empty());
+        assert_eq!(
+            get_instance_state(&entity_states, "order", "order-2").unwrap(),
+            "draft"
+        );
+        assert_eq!(
+            get_instance_attributes(&entity_attributes, "order", "order-2")
+                .and_then(|attrs| attrs.get("total")),
+            Some(&Value::Int(250))
+        );
+
+        // Replaying against the same state generates the next id.
+        let again = execute_operation(
+            &op,
+            "buyer",
+            &facts,
+            &VerdictSet::new(),
+            &mut entity_states,
+            &mut entity_attributes,
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .unwrap();
+        assert_eq!(again.effects_applied[0].instance_id, "order-3");
+    }
+
+    #[test]
+    fn destroy_effect_removes_instance_and_attributes() {
+        let op = make_operation(
+            "archive_order",
+            vec!["buyer"],
+            vec![Effect {
+                entity_id: "order".to_string(),
+                kind: EffectKind::DestroyInstance,
+                from: "closed".to_string(),
+                to: String::new(),
+                outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
+            }],
+            vec!["archived"],
+        );
+        let mut entity_states = single_instance(
+            [("order".to_string(), "draft".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        let key = instance_key("order", DEFAULT_INSTANCE_ID);
+        let mut entity_attributes = EntityAttributeMap::from([(
+            key.clone(),
+            BTreeMap::from([("total".to_string(), Value::Int(1))]),
+        )]);
+
+        // Not in the destroy's source state: nothing is removed.
+        let err = execute_operation(
+            &op,
+            "buyer",
+            &FactSet::new(),
+            &VerdictSet::new(),
+            &mut entity_states,
+            &mut entity_attributes,
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OperationError::InvalidEntityState { .. }));
+        assert!(entity_states.contains_key(&key));
+
+        entity_states.insert(key.clone(), "closed".into());
+        let result = execute_operation(
+            &op,
+            "buyer",
+            &FactSet::new(),
+            &VerdictSet::new(),
+            &mut entity_states,
+            &mut entity_attributes,
+            &InstanceBindingMap::new(),
+            &EntityVersions::default(),
+        )
+        .unwrap();
+        assert_eq!(result.effects_applied[0].kind, EffectKind::DestroyInstance);
+        assert_eq!(result.provenance.state_before[&key], "closed");
+        assert!(result.provenance.state_after.is_empty());
+        assert!(entity_states.is_empty());
+        assert!(entity_attributes.is_empty());
+    }
+
     // ──────────────────────────────────────
     // Entity state transition tests
     // ──────────────────────────────────────
@@ -634,9 +1377,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         );
@@ -655,17 +1401,21 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         let res = result.unwrap();
         assert_eq!(
             res.effects_applied[0],
             EffectRecord {
-                entity_id: "order".to_string(),
-                instance_id: DEFAULT_INSTANCE_ID.to_string(),
-                from_state: "pending".to_string(),
-                to_state: "approved".to_string(),
+                entity_id: "order".into(),
+                instance_id: DEFAULT_INSTANCE_ID.into(),
+                kind: EffectKind::Transition,
+                from_state: "pending".into(),
+                to_state: "approved".into(),
+                attributes: BTreeMap::new(),
             }
         );
         assert_eq!(
@@ -681,9 +1431,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         );
@@ -702,7 +1455,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -728,9 +1483,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         );
@@ -745,7 +1503,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -768,15 +1528,21 @@ mod tests {
             vec![
                 Effect {
                     entity_id: "order".to_string(),
+                    kind: EffectKind::Transition,
                     from: "approved".to_string(),
                     to: "fulfilled".to_string(),
                     outcome: None,
+                    guard: None,
+                    set: BTreeMap::new(),
                 },
                 Effect {
                     entity_id: "payment".to_string(),
+                    kind: EffectKind::Transition,
                     from: "authorized".to_string(),
                     to: "captured".to_string(),
                     outcome: None,
+                    guard: None,
+                    set: BTreeMap::new(),
                 },
             ],
             vec!["completed"],
@@ -799,7 +1565,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         let res = result.unwrap();
@@ -829,9 +1597,12 @@ mod tests {
             },
             effects: vec![Effect {
                 entity_id: "payment".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "captured".to_string(),
                 outcome: Some("payment_success".to_string()),
+                guard: None,
+                set: BTreeMap::new(),
             }],
             error_contract: vec![],
             outcomes: vec!["payment_success".to_string(), "payment_failed".to_string()],
@@ -851,7 +1622,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         let res = result.unwrap();
@@ -869,9 +1642,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         );
@@ -890,7 +1666,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         )
         .unwrap();
         assert_eq!(result.provenance.operation_id, "approve");
@@ -900,10 +1678,12 @@ mod tests {
         assert_eq!(
             result.provenance.effects[0],
             EffectRecord {
-                entity_id: "order".to_string(),
-                instance_id: DEFAULT_INSTANCE_ID.to_string(),
-                from_state: "pending".to_string(),
-                to_state: "approved".to_string(),
+                entity_id: "order".into(),
+                instance_id: DEFAULT_INSTANCE_ID.into(),
+                kind: EffectKind::Transition,
+                from_state: "pending".into(),
+                to_state: "approved".into(),
+                attributes: BTreeMap::new(),
             }
         );
         // No facts or verdicts in precondition (literal true) — both empty
@@ -920,7 +1700,7 @@ mod tests {
             Some(DEFAULT_INSTANCE_ID),
             "instance_binding should map 'order' -> '_default'"
         );
-        let before_key = ("order".to_string(), DEFAULT_INSTANCE_ID.to_string());
+        let before_key = instance_key("order", DEFAULT_INSTANCE_ID);
         assert_eq!(
             result
                 .provenance
@@ -930,7 +1710,7 @@ mod tests {
             Some("pending"),
             "state_before should be 'pending'"
         );
-        let after_key = ("order".to_string(), DEFAULT_INSTANCE_ID.to_string());
+        let after_key = instance_key("order", DEFAULT_INSTANCE_ID);
         assert_eq!(
             result
                 .provenance
@@ -950,9 +1730,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         );
@@ -960,10 +1743,7 @@ mod tests {
         let facts = FactSet::new();
         let verdicts = VerdictSet::new();
         let mut entity_states: EntityStateMap = BTreeMap::new();
-        entity_states.insert(
-            ("order".to_string(), "order-99".to_string()),
-            "pending".to_string(),
-        );
+        entity_states.insert(instance_key("order", "order-99"), "pending".into());
 
         let mut bindings = InstanceBindingMap::new();
         bindings.insert("order".to_string(), "order-99".to_string());
@@ -974,7 +1754,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &bindings,
+            &EntityVersions::default(),
         )
         .unwrap();
 
@@ -990,7 +1772,7 @@ mod tests {
         );
 
         // state_before: order/order-99 was "pending"
-        let before_key = ("order".to_string(), "order-99".to_string());
+        let before_key = instance_key("order", "order-99");
         assert_eq!(
             result
                 .provenance
@@ -1002,7 +1784,7 @@ mod tests {
         );
 
         // state_after: order/order-99 is now "approved"
-        let after_key = ("order".to_string(), "order-99".to_string());
+        let after_key = instance_key("order", "order-99");
         assert_eq!(
             result
                 .provenance
@@ -1023,15 +1805,21 @@ mod tests {
             vec![
                 Effect {
                     entity_id: "order".to_string(),
+                    kind: EffectKind::Transition,
                     from: "approved".to_string(),
                     to: "fulfilled".to_string(),
                     outcome: None,
+                    guard: None,
+                    set: BTreeMap::new(),
                 },
                 Effect {
                     entity_id: "payment".to_string(),
+                    kind: EffectKind::Transition,
                     from: "authorized".to_string(),
                     to: "captured".to_string(),
                     outcome: None,
+                    guard: None,
+                    set: BTreeMap::new(),
                 },
             ],
             vec!["completed"],
@@ -1040,14 +1828,8 @@ mod tests {
         let facts = FactSet::new();
         let verdicts = VerdictSet::new();
         let mut entity_states: EntityStateMap = BTreeMap::new();
-        entity_states.insert(
-            ("order".to_string(), "ord-1".to_string()),
-            "approved".to_string(),
-        );
-        entity_states.insert(
-            ("payment".to_string(), "pay-1".to_string()),
-            "authorized".to_string(),
-        );
+        entity_states.insert(instance_key("order", "ord-1"), "approved".into());
+        entity_states.insert(instance_key("payment", "pay-1"), "authorized".into());
 
         let mut bindings = InstanceBindingMap::new();
         bindings.insert("order".to_string(), "ord-1".to_string());
@@ -1059,7 +1841,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &bindings,
+            &EntityVersions::default(),
         )
         .unwrap();
 
@@ -1086,7 +1870,7 @@ mod tests {
             result
                 .provenance
                 .state_before
-                .get(&("order".to_string(), "ord-1".to_string()))
+                .get(&instance_key("order", "ord-1"))
                 .map(|s| s.as_str()),
             Some("approved")
         );
@@ -1094,7 +1878,7 @@ mod tests {
             result
                 .provenance
                 .state_before
-                .get(&("payment".to_string(), "pay-1".to_string()))
+                .get(&instance_key("payment", "pay-1"))
                 .map(|s| s.as_str()),
             Some("authorized")
         );
@@ -1104,7 +1888,7 @@ mod tests {
             result
                 .provenance
                 .state_after
-                .get(&("order".to_string(), "ord-1".to_string()))
+                .get(&instance_key("order", "ord-1"))
                 .map(|s| s.as_str()),
             Some("fulfilled")
         );
@@ -1112,7 +1896,7 @@ mod tests {
             result
                 .provenance
                 .state_after
-                .get(&("payment".to_string(), "pay-1".to_string()))
+                .get(&instance_key("payment", "pay-1"))
                 .map(|s| s.as_str()),
             Some("captured")
         );
@@ -1126,13 +1910,14 @@ mod tests {
 
         let mut verdicts = VerdictSet::new();
         verdicts.push(VerdictInstance {
-            verdict_type: "eligible".to_string(),
+            verdict_type: "eligible".into(),
             payload: Value::Bool(true),
             provenance: crate::provenance::VerdictProvenance {
                 rule_id: "check_eligible".to_string(),
                 stratum: 0,
                 facts_used: vec![],
                 verdicts_used: vec![],
+                conversions: vec![],
             },
         });
 
@@ -1147,6 +1932,7 @@ mod tests {
                         base: "Int".to_string(),
                         precision: None,
                         scale: None,
+                        rounding: None,
                         currency: None,
                         min: None,
                         max: None,
@@ -1169,9 +1955,12 @@ mod tests {
             precondition,
             vec![Effect {
                 entity_id: "account".to_string(),
+                kind: EffectKind::Transition,
                 from: "active".to_string(),
                 to: "withdrawn".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["withdrawn"],
         );
@@ -1188,7 +1977,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         )
         .unwrap();
 
@@ -1214,9 +2005,12 @@ mod tests {
             },
             effects: vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "completed".to_string(),
                 outcome: Some("success".to_string()),
+                guard: None,
+                set: BTreeMap::new(),
             }],
             error_contract: vec![],
             outcomes: vec!["success".to_string(), "failure".to_string()],
@@ -1236,7 +2030,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         )
         .unwrap();
 
@@ -1261,12 +2057,14 @@ mod tests {
                     states: vec!["draft".to_string(), "submitted".to_string()],
                     initial: "draft".to_string(),
                     transitions: vec![],
+                    attributes: BTreeMap::new(),
                 },
                 Entity {
                     id: "payment".to_string(),
                     states: vec!["pending".to_string(), "captured".to_string()],
                     initial: "pending".to_string(),
                     transitions: vec![],
+                    attributes: BTreeMap::new(),
                 },
             ],
             vec![],
@@ -1298,9 +2096,12 @@ mod tests {
             Predicate::VerdictPresent("order_valid".to_string()),
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "processing".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["processing"],
         );
@@ -1309,13 +2110,14 @@ mod tests {
         let facts = FactSet::new();
         let mut verdicts = VerdictSet::new();
         verdicts.push(crate::types::VerdictInstance {
-            verdict_type: "order_valid".to_string(),
+            verdict_type: "order_valid".into(),
             payload: Value::Bool(true),
             provenance: crate::provenance::VerdictProvenance {
                 rule_id: "check".to_string(),
                 stratum: 0,
                 facts_used: vec![],
                 verdicts_used: vec![],
+                conversions: vec![],
             },
         });
         let mut entity_states = single_instance(
@@ -1330,7 +2132,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
 
@@ -1347,7 +2151,9 @@ mod tests {
             &facts,
             &empty_verdicts,
             &mut entity_states2,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result2.is_err());
         match result2.unwrap_err() {
@@ -1369,9 +2175,12 @@ mod tests {
             vec!["system"],
             vec![Effect {
                 entity_id: "payment".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "captured".to_string(),
                 outcome: None, // No outcome mapping!
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["payment_success", "payment_failed"], // 2 outcomes
         );
@@ -1390,7 +2199,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -1418,9 +2229,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "draft".to_string(),
                 to: "submitted".to_string(),
                 outcome: None, // No mapping, but only 1 outcome
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["submitted"], // Single outcome
         );
@@ -1439,7 +2253,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         assert_eq!(result.unwrap().outcome, "submitted");
@@ -1458,9 +2274,12 @@ mod tests {
             vec!["buyer"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "draft".to_string(),
                 to: "submitted".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["submitted"],
         );
@@ -1470,14 +2289,8 @@ mod tests {
 
         // Two instances of the same entity type
         let mut entity_states: EntityStateMap = BTreeMap::new();
-        entity_states.insert(
-            ("order".to_string(), "order-1".to_string()),
-            "draft".to_string(),
-        );
-        entity_states.insert(
-            ("order".to_string(), "order-2".to_string()),
-            "submitted".to_string(),
-        );
+        entity_states.insert(instance_key("order", "order-1"), "draft".into());
+        entity_states.insert(instance_key("order", "order-2"), "submitted".into());
 
         let mut bindings = InstanceBindingMap::new();
         bindings.insert("order".to_string(), "order-1".to_string());
@@ -1488,7 +2301,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &bindings,
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         let res = result.unwrap();
@@ -1516,9 +2331,12 @@ mod tests {
             vec!["buyer"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "draft".to_string(),
                 to: "submitted".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["submitted"],
         );
@@ -1527,14 +2345,8 @@ mod tests {
         let verdicts = VerdictSet::new();
 
         let mut entity_states: EntityStateMap = BTreeMap::new();
-        entity_states.insert(
-            ("order".to_string(), "order-1".to_string()),
-            "draft".to_string(),
-        );
-        entity_states.insert(
-            ("order".to_string(), "order-2".to_string()),
-            "submitted".to_string(),
-        );
+        entity_states.insert(instance_key("order", "order-1"), "draft".into());
+        entity_states.insert(instance_key("order", "order-2"), "submitted".into());
 
         // Target order-2 which is in "submitted", not "draft" -> InvalidEntityState
         let mut bindings = InstanceBindingMap::new();
@@ -1546,7 +2358,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &bindings,
+            &EntityVersions::default(),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -1573,9 +2387,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         );
@@ -1595,7 +2412,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &InstanceBindingMap::new(),
+            &EntityVersions::default(),
         );
         assert!(result.is_ok());
         assert_eq!(
@@ -1612,9 +2431,12 @@ mod tests {
             vec!["admin"],
             vec![Effect {
                 entity_id: "order".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         );
@@ -1633,7 +2455,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &bindings,
+            &EntityVersions::default(),
         );
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -1656,9 +2480,12 @@ mod tests {
             vec!["system"],
             vec![Effect {
                 entity_id: "payment".to_string(),
+                kind: EffectKind::Transition,
                 from: "pending".to_string(),
                 to: "captured".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["done"],
         );
@@ -1666,10 +2493,7 @@ mod tests {
         let facts = FactSet::new();
         let verdicts = VerdictSet::new();
         let mut entity_states: EntityStateMap = BTreeMap::new();
-        entity_states.insert(
-            ("payment".to_string(), "pay-42".to_string()),
-            "pending".to_string(),
-        );
+        entity_states.insert(instance_key("payment", "pay-42"), "pending".into());
 
         let mut bindings = InstanceBindingMap::new();
         bindings.insert("payment".to_string(), "pay-42".to_string());
@@ -1680,7 +2504,9 @@ mod tests {
             &facts,
             &verdicts,
             &mut entity_states,
+            &mut EntityAttributeMap::new(),
             &bindings,
+            &EntityVersions::default(),
         )
         .unwrap();
         assert_eq!(result.effects_applied[0].entity_id, "payment");
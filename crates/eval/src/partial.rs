@@ -0,0 +1,547 @@
+//! Partial (three-valued) rule evaluation.
+//!
+//! Ordinary evaluation fails when a fact without a default is missing.
+//! Partial evaluation instead treats a missing fact as *unknown*: any
+//! predicate that depends on it evaluates to unknown, carrying the set of
+//! missing facts it is blocked on. Logical operators follow Kleene logic,
+//! so `false and unknown` is still `false` and `true or unknown` is still
+//! `true` -- a verdict is only blocked when the missing facts could
+//! actually change the outcome.
+//!
+//! The result separates definite verdicts from verdicts that depend on
+//! missing facts, so a caller can say "approve is possible if you provide
+//! credit_score".
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::numeric;
+use crate::predicate::{eval_pred, EvalContext};
+use crate::provenance::ProvenanceCollector;
+use crate::types::{
+    Contract, EvalError, FactSet, PayloadValue, Predicate, Rule, Value, VerdictInstance, VerdictSet,
+};
+
+/// A rule whose verdict depends on facts that were not provided.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedVerdict {
+    pub rule_id: String,
+    pub verdict_type: String,
+    /// Missing facts that could decide the outcome, sorted.
+    pub missing_facts: Vec<String>,
+    /// The rule's condition holds; only its payload depends on the
+    /// missing facts, so the verdict is known to be present.
+    pub condition_holds: bool,
+}
+
+/// Result of partial evaluation.
+#[derive(Debug, Clone)]
+pub struct PartialEvalResult {
+    /// Verdicts produced regardless of the missing facts.
+    pub verdicts: VerdictSet,
+    /// Rules whose verdict depends on the missing facts, in evaluation order.
+    pub blocked: Vec<BlockedVerdict>,
+    /// Declared facts that were neither provided nor defaulted, sorted.
+    pub missing_facts: Vec<String>,
+}
+
+impl PartialEvalResult {
+    /// Whether every verdict was decided.
+    pub fn is_complete(&self) -> bool {
+        self.blocked.is_empty()
+    }
+
+    /// Serialize to JSON: the [`VerdictSet::to_json`] shape plus `blocked`
+    /// and `missing_facts`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = self.verdicts.to_json();
+        json["blocked"] = self
+            .blocked
+            .iter()
+            .map(|b| {
+                serde_json::json!({
+                    "rule": b.rule_id,
+                    "verdict_type": b.verdict_type,
+                    "missing_facts": b.missing_facts,
+                    "condition_holds": b.condition_holds,
+                })
+            })
+            .collect();
+        json["missing_facts"] = serde_json::json!(self.missing_facts);
+        json
+    }
+}
+
+/// A predicate value under partial evaluation.
+#[derive(Debug, Clone, PartialEq)]
+enum Partial {
+    Known(Value),
+    /// Unknown; depends on these missing facts.
+    Unknown(BTreeSet<String>),
+}
+
+/// What lower strata established about each verdict type.
+struct VerdictState<'a> {
+    /// Definite verdicts.
+    verdicts: &'a VerdictSet,
+    /// Verdict types known to be present whose payload is unknown.
+    present: &'a BTreeSet<String>,
+    /// Verdict types whose presence depends on missing facts.
+    blocked: &'a BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Evaluate all rules in stratum order, treating `missing` facts as unknown.
+///
+/// `facts` holds every fact that is known; each fact in `missing` must be
+/// absent from it.
+pub fn eval_strata_partial(
+    contract: &Contract,
+    facts: &FactSet,
+    missing: &BTreeSet<String>,
+) -> Result<PartialEvalResult, EvalError> {
+    let mut verdicts = VerdictSet::new();
+    let mut present: BTreeSet<String> = BTreeSet::new();
+    let mut blocked_types: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut blocked = Vec::new();
+    let ctx = EvalContext::new();
+
+    for &index in &contract.compiled_rules.order {
+        let rule = &contract.rules[index];
+        let mut collector = ProvenanceCollector::new();
+        let state = VerdictState {
+            verdicts: &verdicts,
+            present: &present,
+            blocked: &blocked_types,
+        };
+        let condition = eval_partial(
+            &rule.condition,
+            facts,
+            missing,
+            &state,
+            &ctx,
+            &mut collector,
+        )?;
+        let verdict_type = rule.produce.verdict_type.clone();
+
+        match condition {
+            Partial::Known(v) if !v.as_bool()? => {}
+            Partial::Known(_) => match rule_payload(rule, facts, missing, &mut collector)? {
+                Partial::Known(payload) => {
+                    if contract.validate_payloads {
+                        crate::rules::check_payload(rule, &payload)?;
+                    }
+                    verdicts.push(VerdictInstance {
+                        verdict_type: verdict_type.as_str().into(),
+                        payload,
+                        provenance: collector.into_provenance(rule.id.clone(), rule.stratum),
+                    });
+                    present.insert(verdict_type);
+                }
+                Partial::Unknown(needed) => {
+                    blocked.push(BlockedVerdict {
+                        rule_id: rule.id.clone(),
+                        verdict_type: verdict_type.clone(),
+                        missing_facts: needed.into_iter().collect(),
+                        condition_holds: true,
+                    });
+                    present.insert(verdict_type);
+                }
+            },
+            Partial::Unknown(needed) => {
+                blocked_types
+                    .entry(verdict_type.clone())
+                    .or_default()
+                    .extend(needed.iter().cloned());
+                blocked.push(BlockedVerdict {
+                    rule_id: rule.id.clone(),
+                    verdict_type,
+                    missing_facts: needed.into_iter().collect(),
+                    condition_holds: false,
+                });
+            }
+        }
+    }
+
+    Ok(PartialEvalResult {
+        verdicts,
+        blocked,
+        missing_facts: missing.iter().cloned().collect(),
+    })
+}
+
+/// A rule's payload, unknown when it multiplies a missing fact.
+fn rule_payload(
+    rule: &Rule,
+    facts: &FactSet,
+    missing: &BTreeSet<String>,
+    collector: &mut ProvenanceCollector,
+) -> Result<Partial, EvalError> {
+    match &rule.produce.payload_value {
+        PayloadValue::Literal(v) => Ok(Partial::Known(v.clone())),
+        PayloadValue::Mul(mul) if missing.contains(&mul.fact_ref) => {
+            Ok(Partial::Unknown(BTreeSet::from([mul.fact_ref.clone()])))
+        }
+        PayloadValue::Mul(mul) => {
+            let pred = Predicate::Mul {
+                left: Box::new(Predicate::FactRef(mul.fact_ref.clone())),
+                literal: mul.literal,
+                result_type: mul.result_type.clone(),
+            };
+            eval_pred(
+                &pred,
+                facts,
+                &VerdictSet::new(),
+                &EvalContext::new(),
+                collector,
+            )
+            .map(Partial::Known)
+        }
+    }
+}
+
+/// Three-valued predicate evaluation. Subtrees that cannot reach a missing
+/// fact or an undecided verdict are handed to [`eval_pred`].
+fn eval_partial(
+    pred: &Predicate,
+    facts: &FactSet,
+    missing: &BTreeSet<String>,
+    state: &VerdictState,
+    ctx: &EvalContext,
+    collector: &mut ProvenanceCollector,
+) -> Result<Partial, EvalError> {
+    let recurse = |p: &Predicate, ctx: &EvalContext, collector: &mut ProvenanceCollector| {
+        eval_partial(p, facts, missing, state, ctx, collector)
+    };
+
+    match pred {
+        Predicate::FactRef(id) if missing.contains(id) => {
+            collector.record_fact(id);
+            Ok(Partial::Unknown(BTreeSet::from([id.clone()])))
+        }
+
+        Predicate::FieldRef { var, .. }
+            if !ctx.bindings.contains_key(var) && missing.contains(var) =>
+        {
+            collector.record_fact(var);
+            Ok(Partial::Unknown(BTreeSet::from([var.clone()])))
+        }
+
+        Predicate::VerdictPresent(id) => {
+            collector.record_verdict(id);
+            if state.verdicts.has_verdict(id) || state.present.contains(id) {
+                Ok(Partial::Known(Value::Bool(true)))
+            } else if let Some(needed) = state.blocked.get(id) {
+                Ok(Partial::Unknown(needed.clone()))
+            } else {
+                Ok(Partial::Known(Value::Bool(false)))
+            }
+        }
+
+        Predicate::Compare {
+            left,
+            op,
+            right,
+            comparison_type,
+        } => {
+            let left_val = recurse(left, ctx, collector)?;
+            let right_val = recurse(right, ctx, collector)?;
+            match (left_val, right_val) {
+                (Partial::Known(l), Partial::Known(r)) => {
+                    let result = numeric::compare_values(&l, &r, op, comparison_type.as_ref())?;
+                    Ok(Partial::Known(Value::Bool(result)))
+                }
+                (l, r) => Ok(Partial::Unknown(unknowns([&l, &r]))),
+            }
+        }
+
+        Predicate::Mul { left, .. } => match recurse(left, ctx, collector)? {
+            Partial::Known(_) => eval_known(pred, facts, state, ctx, collector),
+            unknown => Ok(unknown),
+        },
+
+        Predicate::Convert { amount, rate_fact } => {
+            let amount_val = recurse(amount, ctx, collector)?;
+            if missing.contains(rate_fact) {
+                collector.record_fact(rate_fact);
+                let mut needed = unknowns([&amount_val]);
+                needed.insert(rate_fact.clone());
+                return Ok(Partial::Unknown(needed));
+            }
+            match amount_val {
+                Partial::Known(_) => eval_known(pred, facts, state, ctx, collector),
+                unknown => Ok(unknown),
+            }
+        }
+
+        Predicate::And { left, right } => {
+            let l = recurse(left, ctx, collector)?;
+            if as_bool(&l)? == Some(false) {
+                return Ok(Partial::Known(Value::Bool(false)));
+            }
+            let r = recurse(right, ctx, collector)?;
+            kleene(&[l, r], false)
+        }
+
+        Predicate::Or { left, right } => {
+            let l = recurse(left, ctx, collector)?;
+            if as_bool(&l)? == Some(true) {
+                return Ok(Partial::Known(Value::Bool(true)));
+            }
+            let r = recurse(right, ctx, collector)?;
+            kleene(&[l, r], true)
+        }
+
+        Predicate::Not { operand } => match recurse(operand, ctx, collector)? {
+            Partial::Known(v) => Ok(Partial::Known(Value::Bool(!v.as_bool()?))),
+            unknown => Ok(unknown),
+        },
+
+        Predicate::Forall {
+            variable,
+            domain,
+            body,
+            ..
+        }
+        | Predicate::Exists {
+            variable,
+            domain,
+            body,
+            ..
+        } if !matches!(domain.as_ref(), Predicate::InstancesOf(_)) => {
+            let is_exists = matches!(pred, Predicate::Exists { .. });
+            let elements = match recurse(domain, ctx, collector)? {
+                Partial::Known(Value::List(items)) => items,
+                Partial::Known(other) => {
+                    return Err(EvalError::TypeError {
+                        message: format!(
+                            "{} domain must be a List, got {}",
+                            if is_exists { "exists" } else { "forall" },
+                            other.type_name()
+                        ),
+                    });
+                }
+                unknown => return Ok(unknown),
+            };
+            let mut results = Vec::new();
+            for elem in &elements {
+                let mut inner_ctx = ctx.clone();
+                inner_ctx.bindings.insert(variable.clone(), elem.clone());
+                let result = recurse(body, &inner_ctx, collector)?;
+                // A decisive element settles the quantifier
+                if as_bool(&result)? == Some(is_exists) {
+                    return Ok(Partial::Known(Value::Bool(is_exists)));
+                }
+                results.push(result);
+            }
+            kleene(&results, is_exists)
+        }
+
+        _ => eval_known(pred, facts, state, ctx, collector),
+    }
+}
+
+/// Evaluate a subtree that depends on no missing fact or undecided verdict.
+fn eval_known(
+    pred: &Predicate,
+    facts: &FactSet,
+    state: &VerdictState,
+    ctx: &EvalContext,
+    collector: &mut ProvenanceCollector,
+) -> Result<Partial, EvalError> {
+    eval_pred(pred, facts, state.verdicts, ctx, collector).map(Partial::Known)
+}
+
+/// `Some(b)` for a known boolean, `None` for unknown.
+fn as_bool(value: &Partial) -> Result<Option<bool>, EvalError> {
+    match value {
+        Partial::Known(v) => v.as_bool().map(Some),
+        Partial::Unknown(_) => Ok(None),
+    }
+}
+
+/// Combine operands of an `and` (`decisive = false`) or `or`
+/// (`decisive = true`) under Kleene logic.
+fn kleene(operands: &[Partial], decisive: bool) -> Result<Partial, EvalError> {
+    for operand in operands {
+        if as_bool(operand)? == Some(decisive) {
+            return Ok(Partial::Known(Value::Bool(decisive)));
+        }
+    }
+    let needed = unknowns(operands);
+    if needed.is_empty() {
+        Ok(Partial::Known(Value::Bool(!decisive)))
+    } else {
+        Ok(Partial::Unknown(needed))
+    }
+}
+
+/// The missing facts behind every unknown operand.
+fn unknowns<'a>(operands: impl IntoIterator<Item = &'a Partial>) -> BTreeSet<String> {
+    operands
+        .into_iter()
+        .filter_map(|p| match p {
+            Partial::Unknown(needed) => Some(needed.iter().cloned()),
+            Partial::Known(_) => None,
+        })
+        .flatten()
+        .collect()
+}
+
+// ──────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    fn int_type() -> TypeSpec {
+        TypeSpec {
+            base: "Int".to_string(),
+            precision: None,
+            scale: None,
+            rounding: None,
+            currency: None,
+            min: None,
+            max: None,
+            max_length: None,
+            values: None,
+            fields: None,
+            element_type: None,
+            unit: None,
+            variants: None,
+        }
+    }
+
+    fn fact(id: &str) -> Box<Predicate> {
+        Box::new(Predicate::FactRef(id.to_string()))
+    }
+
+    fn int(n: i64) -> Box<Predicate> {
+        Box::new(Predicate::Literal {
+            value: Value::Int(n),
+            type_spec: int_type(),
+        })
+    }
+
+    fn compare(id: &str, op: &str, n: i64) -> Predicate {
+        Predicate::Compare {
+            left: fact(id),
+            op: op.to_string(),
+            right: int(n),
+            comparison_type: None,
+        }
+    }
+
+    fn rule(id: &str, stratum: u32, condition: Predicate, verdict_type: &str) -> Rule {
+        Rule {
+            id: id.to_string(),
+            stratum,
+            condition,
+            produce: ProduceClause {
+                verdict_type: verdict_type.to_string(),
+                payload_type: int_type(),
+                payload_value: PayloadValue::Literal(Value::Int(1)),
+            },
+        }
+    }
+
+    /// `eligible` needs income >= 100 and credit_score >= 600;
+    /// `approve` needs `eligible`; `flagged` needs income < 10.
+    fn contract() -> Contract {
+        Contract::new(
+            vec![],
+            vec![],
+            vec![
+                rule(
+                    "eligible",
+                    0,
+                    Predicate::And {
+                        left: Box::new(compare("income", ">=", 100)),
+                        right: Box::new(compare("credit_score", ">=", 600)),
+                    },
+                    "eligible",
+                ),
+                rule("flagged", 0, compare("income", "<", 10), "flagged"),
+                rule(
+                    "approve",
+                    1,
+                    Predicate::VerdictPresent("eligible".to_string()),
+                    "approve",
+                ),
+            ],
+            vec![],
+            vec![],
+            vec![],
+        )
+    }
+
+    fn run(income: i64) -> PartialEvalResult {
+        let mut facts = FactSet::new();
+        facts.insert("income".to_string(), Value::Int(income));
+        let missing = BTreeSet::from(["credit_score".to_string()]);
+        eval_strata_partial(&contract(), &facts, &missing).unwrap()
+    }
+
+    #[test]
+    fn missing_fact_blocks_dependent_verdicts() {
+        let result = run(500);
+        assert!(result.verdicts.0.is_empty());
+        let blocked: Vec<(&str, &[String])> = result
+            .blocked
+            .iter()
+            .map(|b| (b.rule_id.as_str(), b.missing_facts.as_slice()))
+            .collect();
+        assert_eq!(
+            blocked,
+            [
+                ("eligible", &["credit_score".to_string()][..]),
+                ("approve", &["credit_score".to_string()][..]),
+            ]
+        );
+        assert!(!result.is_complete());
+    }
+
+    #[test]
+    fn known_false_operand_decides_and() {
+        // income < 100 makes `eligible` false whatever the credit score
+        let result = run(50);
+        assert!(result.is_complete());
+        assert!(result.verdicts.0.is_empty());
+    }
+
+    #[test]
+    fn unrelated_rules_are_definite() {
+        let result = run(5);
+        assert!(result.verdicts.has_verdict("flagged"));
+        assert!(result.is_complete());
+        assert_eq!(result.missing_facts, ["credit_score"]);
+    }
+
+    #[test]
+    fn kleene_or_with_known_true_is_true() {
+        let pred = Predicate::Or {
+            left: Box::new(compare("credit_score", ">=", 600)),
+            right: Box::new(compare("income", ">", 0)),
+        };
+        let mut facts = FactSet::new();
+        facts.insert("income".to_string(), Value::Int(1));
+        let missing = BTreeSet::from(["credit_score".to_string()]);
+        let verdicts = VerdictSet::new();
+        let state = VerdictState {
+            verdicts: &verdicts,
+            present: &BTreeSet::new(),
+            blocked: &BTreeMap::new(),
+        };
+        let result = eval_partial(
+            &pred,
+            &facts,
+            &missing,
+            &state,
+            &EvalContext::new(),
+            &mut ProvenanceCollector::new(),
+        )
+        .unwrap();
+        assert_eq!(result, Partial::Known(Value::Bool(true)));
+    }
+}
@@ -546,7 +546,9 @@ mod tests {
             current_verdicts: vec![],
             blocked_actions: vec![crate::action_space::BlockedAction {
                 flow_id: "flow_blocked".to_string(),
-                reason: crate::action_space::BlockedReason::PersonaNotAuthorized,
+                reason: crate::action_space::BlockedReason::PersonaNotAuthorized {
+                    allowed_personas: vec!["admin".to_string()],
+                },
                 instance_bindings: std::collections::BTreeMap::new(),
             }],
         };
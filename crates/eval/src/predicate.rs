@@ -7,19 +7,39 @@
 //! to a `Value` (usually Bool for logical nodes, but can be other types
 //! for fact refs, field refs, and literals).
 
+use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use crate::numeric;
-use crate::provenance::ProvenanceCollector;
+use crate::operation::{EntityAttributeMap, EntityStateMap};
+use crate::provenance::{Conversion, ProvenanceCollector};
+use crate::symbol::Symbol;
 #[cfg(test)]
 use crate::types::TypeSpec;
-use crate::types::{EvalError, FactSet, Predicate, Value, VerdictSet};
+use crate::types::{
+    CompiledPredicate, CompiledRules, EvalError, FactSet, FactSlots, Predicate, Value, VerdictSet,
+};
 
 /// Evaluation context for bound variables (forall quantification).
 #[derive(Debug, Clone)]
 pub struct EvalContext {
     /// Bound variables from enclosing forall quantifiers.
     pub bindings: BTreeMap<String, Value>,
+    /// Live entity instances by entity id, the domains of `instances_of`
+    /// quantifiers. Empty unless built with [`EvalContext::with_instances`].
+    pub instances: Arc<BTreeMap<Symbol, Vec<InstanceView>>>,
+    /// State of each variable bound by an enclosing instance quantifier.
+    pub instance_states: BTreeMap<String, Symbol>,
+}
+
+/// One entity instance as an instance quantifier sees it.
+#[derive(Debug, Clone)]
+pub struct InstanceView {
+    pub instance_id: Symbol,
+    pub state: Symbol,
+    /// Attribute values as a Record, bound to the quantifier variable.
+    pub attributes: Value,
 }
 
 impl Default for EvalContext {
@@ -32,6 +52,29 @@ impl EvalContext {
     pub fn new() -> Self {
         EvalContext {
             bindings: BTreeMap::new(),
+            instances: Arc::default(),
+            instance_states: BTreeMap::new(),
+        }
+    }
+
+    /// A context whose instance quantifiers range over `entity_states`,
+    /// reading each instance's attributes from `entity_attributes`.
+    pub fn with_instances(
+        entity_states: &EntityStateMap,
+        entity_attributes: &EntityAttributeMap,
+    ) -> Self {
+        let mut instances: BTreeMap<Symbol, Vec<InstanceView>> = BTreeMap::new();
+        for (key, state) in entity_states {
+            let (entity_id, instance_id) = key.clone();
+            instances.entry(entity_id).or_default().push(InstanceView {
+                instance_id,
+                state: state.clone(),
+                attributes: Value::Record(entity_attributes.get(key).cloned().unwrap_or_default()),
+            });
+        }
+        EvalContext {
+            instances: Arc::new(instances),
+            ..Self::new()
         }
     }
 }
@@ -151,6 +194,11 @@ pub fn eval_pred(
             domain,
             body,
         } => {
+            if let Predicate::InstancesOf(entity_id) = domain.as_ref() {
+                return eval_instance_quantifier(
+                    false, variable, entity_id, body, facts, verdicts, ctx, collector,
+                );
+            }
             // Evaluate domain (must be a List-typed fact)
             let domain_val = eval_pred(domain, facts, verdicts, ctx, collector)?;
             let elements = match domain_val {
@@ -184,6 +232,11 @@ pub fn eval_pred(
             domain,
             body,
         } => {
+            if let Predicate::InstancesOf(entity_id) = domain.as_ref() {
+                return eval_instance_quantifier(
+                    true, variable, entity_id, body, facts, verdicts, ctx, collector,
+                );
+            }
             // Evaluate domain (must be a List-typed fact)
             let domain_val = eval_pred(domain, facts, verdicts, ctx, collector)?;
             let elements = match domain_val {
@@ -211,6 +264,19 @@ pub fn eval_pred(
             Ok(Value::Bool(false))
         }
 
+        Predicate::InstancesOf(entity_id) => Err(EvalError::TypeError {
+            message: format!(
+                "instances_of '{}' is only valid as a quantifier domain",
+                entity_id
+            ),
+        }),
+
+        Predicate::InstanceState(var) => ctx
+            .instance_states
+            .get(var)
+            .map(|state| Value::Text(state.to_string()))
+            .ok_or_else(|| EvalError::UnboundVariable { name: var.clone() }),
+
         Predicate::Mul {
             left,
             literal,
@@ -226,6 +292,7 @@ pub fn eval_pred(
                         lit_decimal,
                         result_type.precision.unwrap_or(28),
                         result_type.scale.unwrap_or(0),
+                        result_type.rounding.as_deref(),
                     )?;
                     Ok(Value::Decimal(result))
                 }
@@ -237,6 +304,266 @@ pub fn eval_pred(
                 }),
             }
         }
+
+        Predicate::Convert { amount, rate_fact } => {
+            let amount_val = eval_pred(amount, facts, verdicts, ctx, collector)?;
+            collector.record_fact(rate_fact);
+            let rate = facts.get(rate_fact).ok_or_else(|| EvalError::UnknownFact {
+                fact_id: rate_fact.clone(),
+            })?;
+            eval_convert(&amount_val, rate_fact, rate, collector)
+        }
+    }
+}
+
+/// Convert `amount` by the rate held in `rate_fact`, recording the rate
+/// used in the provenance.
+fn eval_convert(
+    amount: &Value,
+    rate_fact: &str,
+    rate: &Value,
+    collector: &mut ProvenanceCollector,
+) -> Result<Value, EvalError> {
+    let converted = numeric::eval_convert(amount, rate)?;
+    if let (
+        Value::Money { currency: from, .. },
+        Value::Money {
+            amount: r,
+            currency: to,
+        },
+    ) = (amount, rate)
+    {
+        collector.record_conversion(Conversion {
+            rate_fact: rate_fact.to_string(),
+            from_currency: from.clone(),
+            to_currency: to.clone(),
+            rate: *r,
+        });
+    }
+    Ok(converted)
+}
+
+/// Forall/exists over the instances of `entity_id`: binds `variable` to
+/// each instance's attribute Record and its state, and stops at the first
+/// instance whose body evaluates to `short_circuit_on`.
+#[allow(clippy::too_many_arguments)]
+fn eval_instance_quantifier(
+    short_circuit_on: bool,
+    variable: &str,
+    entity_id: &str,
+    body: &Predicate,
+    facts: &FactSet,
+    verdicts: &VerdictSet,
+    ctx: &EvalContext,
+    collector: &mut ProvenanceCollector,
+) -> Result<Value, EvalError> {
+    let instances = ctx.instances.get(entity_id).map(Vec::as_slice);
+    for instance in instances.unwrap_or_default() {
+        let mut inner_ctx = ctx.clone();
+        inner_ctx
+            .bindings
+            .insert(variable.to_string(), instance.attributes.clone());
+        inner_ctx
+            .instance_states
+            .insert(variable.to_string(), instance.state.clone());
+        if eval_pred(body, facts, verdicts, &inner_ctx, collector)?.as_bool()? == short_circuit_on {
+            return Ok(Value::Bool(short_circuit_on));
+        }
+    }
+    Ok(Value::Bool(!short_circuit_on))
+}
+
+// ──────────────────────────────────────────────
+// Compiled predicate evaluation
+// ──────────────────────────────────────────────
+
+/// Slot-resolved inputs for [`eval_compiled`].
+#[derive(Debug, Clone, Copy)]
+pub struct SlotEnv<'a, 'e> {
+    pub compiled: &'a CompiledRules,
+    pub facts: &'e FactSlots<'a>,
+    /// Verdict presence, indexed by verdict slot.
+    pub verdicts: &'e [bool],
+}
+
+/// Evaluate a compiled predicate.
+///
+/// Semantics (including error variants and provenance recording) match
+/// [`eval_pred`]. Values are borrowed from the fact slots where possible;
+/// `bindings` is the quantifier binding stack that `BoundField` levels
+/// index into.
+pub fn eval_compiled<'a>(
+    pred: &'a CompiledPredicate,
+    env: SlotEnv<'a, '_>,
+    bindings: &mut Vec<Cow<'a, Value>>,
+    collector: &mut ProvenanceCollector,
+) -> Result<Cow<'a, Value>, EvalError> {
+    match pred {
+        CompiledPredicate::Fact(slot) => {
+            let id = &env.compiled.fact_names[*slot];
+            collector.record_fact(id);
+            env.facts
+                .get(*slot)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| EvalError::UnknownFact {
+                    fact_id: id.clone(),
+                })
+        }
+
+        CompiledPredicate::FactField { slot, field } => {
+            let var = &env.compiled.fact_names[*slot];
+            collector.record_fact(var);
+            let val = env
+                .facts
+                .get(*slot)
+                .ok_or_else(|| EvalError::UnboundVariable { name: var.clone() })?;
+            record_field(val, var, field).map(Cow::Borrowed)
+        }
+
+        CompiledPredicate::BoundField { level, var, field } => match &bindings[*level] {
+            Cow::Borrowed(val) => record_field(val, var, field).map(Cow::Borrowed),
+            Cow::Owned(val) => record_field(val, var, field).map(|v| Cow::Owned(v.clone())),
+        },
+
+        CompiledPredicate::Literal(value) => Ok(Cow::Borrowed(value)),
+
+        CompiledPredicate::VerdictPresent(slot) => {
+            collector.record_verdict(&env.compiled.verdict_names[*slot]);
+            let present = env.verdicts.get(*slot).copied().unwrap_or(false);
+            Ok(Cow::Owned(Value::Bool(present)))
+        }
+
+        CompiledPredicate::Compare {
+            left,
+            op,
+            right,
+            comparison_type,
+        } => {
+            let left_val = eval_compiled(left, env, bindings, collector)?;
+            let right_val = eval_compiled(right, env, bindings, collector)?;
+            let result =
+                numeric::compare_values(&left_val, &right_val, op, comparison_type.as_ref())?;
+            Ok(Cow::Owned(Value::Bool(result)))
+        }
+
+        CompiledPredicate::And { left, right } => {
+            if !eval_compiled(left, env, bindings, collector)?.as_bool()? {
+                return Ok(Cow::Owned(Value::Bool(false)));
+            }
+            let right_bool = eval_compiled(right, env, bindings, collector)?.as_bool()?;
+            Ok(Cow::Owned(Value::Bool(right_bool)))
+        }
+
+        CompiledPredicate::Or { left, right } => {
+            if eval_compiled(left, env, bindings, collector)?.as_bool()? {
+                return Ok(Cow::Owned(Value::Bool(true)));
+            }
+            let right_bool = eval_compiled(right, env, bindings, collector)?.as_bool()?;
+            Ok(Cow::Owned(Value::Bool(right_bool)))
+        }
+
+        CompiledPredicate::Not { operand } => {
+            let b = eval_compiled(operand, env, bindings, collector)?.as_bool()?;
+            Ok(Cow::Owned(Value::Bool(!b)))
+        }
+
+        CompiledPredicate::EntityState(what) => Err(EvalError::TypeError {
+            message: format!("{} reads entity state, which rules cannot see", what),
+        }),
+
+        CompiledPredicate::Forall { domain, body } => {
+            eval_quantifier("forall", false, domain, body, env, bindings, collector)
+        }
+
+        CompiledPredicate::Exists { domain, body } => {
+            eval_quantifier("exists", true, domain, body, env, bindings, collector)
+        }
+
+        CompiledPredicate::Mul {
+            left,
+            literal,
+            result_type,
+        } => {
+            let left_val = eval_compiled(left, env, bindings, collector)?;
+            match *left_val {
+                Value::Int(i) => numeric::eval_int_mul(i, *literal, result_type).map(Cow::Owned),
+                Value::Decimal(d) => {
+                    let lit_decimal = rust_decimal::Decimal::from(*literal);
+                    let result = numeric::eval_mul(
+                        d,
+                        lit_decimal,
+                        result_type.precision.unwrap_or(28),
+                        result_type.scale.unwrap_or(0),
+                        result_type.rounding.as_deref(),
+                    )?;
+                    Ok(Cow::Owned(Value::Decimal(result)))
+                }
+                _ => Err(EvalError::TypeError {
+                    message: format!(
+                        "multiplication requires numeric operand, got {}",
+                        left_val.type_name()
+                    ),
+                }),
+            }
+        }
+
+        CompiledPredicate::Convert { amount, rate } => {
+            let amount_val = eval_compiled(amount, env, bindings, collector)?;
+            let rate_fact = &env.compiled.fact_names[*rate];
+            collector.record_fact(rate_fact);
+            let rate_val = env.facts.get(*rate).ok_or_else(|| EvalError::UnknownFact {
+                fact_id: rate_fact.clone(),
+            })?;
+            eval_convert(&amount_val, rate_fact, rate_val, collector).map(Cow::Owned)
+        }
+    }
+}
+
+/// Shared forall/exists loop: stops at the first element whose body
+/// evaluates to `short_circuit_on`.
+fn eval_quantifier<'a>(
+    name: &str,
+    short_circuit_on: bool,
+    domain: &'a CompiledPredicate,
+    body: &'a CompiledPredicate,
+    env: SlotEnv<'a, '_>,
+    bindings: &mut Vec<Cow<'a, Value>>,
+    collector: &mut ProvenanceCollector,
+) -> Result<Cow<'a, Value>, EvalError> {
+    let elements: Vec<Cow<'a, Value>> = match eval_compiled(domain, env, bindings, collector)? {
+        Cow::Borrowed(Value::List(items)) => items.iter().map(Cow::Borrowed).collect(),
+        Cow::Owned(Value::List(items)) => items.into_iter().map(Cow::Owned).collect(),
+        other => {
+            return Err(EvalError::TypeError {
+                message: format!("{} domain must be a List, got {}", name, other.type_name()),
+            });
+        }
+    };
+
+    for elem in elements {
+        bindings.push(elem);
+        let result = eval_compiled(body, env, bindings, collector).and_then(|v| v.as_bool());
+        bindings.pop();
+        if result? == short_circuit_on {
+            return Ok(Cow::Owned(Value::Bool(short_circuit_on)));
+        }
+    }
+    Ok(Cow::Owned(Value::Bool(!short_circuit_on)))
+}
+
+/// Field of a Record value, with the interpreter's error messages.
+fn record_field<'v>(val: &'v Value, var: &str, field: &str) -> Result<&'v Value, EvalError> {
+    match val {
+        Value::Record(fields) => fields.get(field).ok_or_else(|| EvalError::NotARecord {
+            message: format!("field '{}' not found in record variable '{}'", field, var),
+        }),
+        _ => Err(EvalError::NotARecord {
+            message: format!(
+                "variable '{}' is not a Record, got {}",
+                var,
+                val.type_name()
+            ),
+        }),
     }
 }
 
@@ -257,13 +584,14 @@ mod tests {
         let mut vs = VerdictSet::new();
         for t in types {
             vs.push(crate::types::VerdictInstance {
-                verdict_type: t.to_string(),
+                verdict_type: crate::symbol::Symbol::new(t),
                 payload: Value::Bool(true),
                 provenance: crate::provenance::VerdictProvenance {
                     rule_id: "test".to_string(),
                     stratum: 0,
                     facts_used: vec![],
                     verdicts_used: vec![],
+                    conversions: vec![],
                 },
             });
         }
@@ -312,6 +640,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -378,6 +707,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -502,6 +832,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -516,6 +847,7 @@ mod tests {
             base: "Record".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -574,6 +906,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -588,6 +921,7 @@ mod tests {
             base: "Record".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -645,6 +979,7 @@ mod tests {
             base: "Money".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: Some("USD".to_string()),
             min: None,
             max: None,
@@ -678,6 +1013,7 @@ mod tests {
             base: "Enum".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -718,6 +1054,7 @@ mod tests {
             base: "Int".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: Some(0),
             max: Some(100),
@@ -764,6 +1101,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -778,6 +1116,7 @@ mod tests {
             base: "Record".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -836,6 +1175,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -850,6 +1190,7 @@ mod tests {
             base: "Record".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -906,6 +1247,7 @@ mod failure_tests {
             base: base.to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -993,6 +1335,7 @@ mod failure_tests {
                 base: "Decimal".to_string(),
                 precision: Some(5),
                 scale: Some(2),
+                rounding: None,
                 currency: None,
                 min: None,
                 max: None,
@@ -1035,6 +1378,7 @@ mod failure_tests {
                 base: "Int".to_string(),
                 precision: None,
                 scale: None,
+                rounding: None,
                 currency: None,
                 min: None,
                 max: None,
@@ -1277,6 +1621,76 @@ mod failure_tests {
         }
     }
 
+    // ── 5f. Convert multiplies by the rate fact and records it ──
+    fn money(amount: &str, currency: &str) -> Value {
+        Value::Money {
+            amount: amount.parse().unwrap(),
+            currency: currency.to_string(),
+        }
+    }
+
+    fn convert_by(rate_fact: &str) -> Predicate {
+        Predicate::Convert {
+            amount: Box::new(Predicate::FactRef("price".to_string())),
+            rate_fact: rate_fact.to_string(),
+        }
+    }
+
+    #[test]
+    fn convert_applies_rate_and_records_conversion() {
+        let mut facts = FactSet::new();
+        facts.insert("price".to_string(), money("200.00", "EUR"));
+        facts.insert("eur_usd".to_string(), money("1.0850", "USD"));
+
+        let mut collector = ProvenanceCollector::new();
+        let ctx = EvalContext::new();
+        let result = eval_pred(
+            &convert_by("eur_usd"),
+            &facts,
+            &empty_verdicts(),
+            &ctx,
+            &mut collector,
+        )
+        .unwrap();
+
+        assert_eq!(result, money("217.00", "USD"));
+        assert_eq!(collector.facts_used, vec!["price", "eur_usd"]);
+        assert_eq!(
+            collector.conversions,
+            vec![Conversion {
+                rate_fact: "eur_usd".to_string(),
+                from_currency: "EUR".to_string(),
+                to_currency: "USD".to_string(),
+                rate: "1.0850".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn convert_into_same_currency_returns_type_error() {
+        let mut facts = FactSet::new();
+        facts.insert("price".to_string(), money("200.00", "EUR"));
+        facts.insert("eur_eur".to_string(), money("1", "EUR"));
+
+        let mut collector = ProvenanceCollector::new();
+        let ctx = EvalContext::new();
+        let result = eval_pred(
+            &convert_by("eur_eur"),
+            &facts,
+            &empty_verdicts(),
+            &ctx,
+            &mut collector,
+        );
+
+        match result.unwrap_err() {
+            EvalError::TypeError { message } => {
+                assert!(message.contains("same currency"), "got: {}", message);
+            }
+            other => panic!("expected TypeError, got: {:?}", other),
+        }
+        assert!(collector.conversions.is_empty());
+    }
+
     // ── Forall on non-list domain produces TypeError ──
     #[test]
     fn forall_on_non_list_domain_returns_type_error() {
@@ -1336,4 +1750,121 @@ mod failure_tests {
             other => panic!("expected TypeError, got: {:?}", other),
         }
     }
+
+    // ── Instance quantifiers range over the EntityStateMap ──
+    fn state_is(var: &str, state: &str) -> Predicate {
+        Predicate::Compare {
+            left: Box::new(Predicate::InstanceState(var.to_string())),
+            op: "=".to_string(),
+            right: Box::new(Predicate::Literal {
+                value: Value::Text(state.to_string()),
+                type_spec: make_type_spec("Text"),
+            }),
+            comparison_type: None,
+        }
+    }
+
+    fn over_deliveries(forall: bool, body: Predicate) -> Predicate {
+        let variable = "d".to_string();
+        let variable_type = make_type_spec("Record");
+        let domain = Box::new(Predicate::InstancesOf("Delivery".to_string()));
+        let body = Box::new(body);
+        if forall {
+            Predicate::Forall {
+                variable,
+                variable_type,
+                domain,
+                body,
+            }
+        } else {
+            Predicate::Exists {
+                variable,
+                variable_type,
+                domain,
+                body,
+            }
+        }
+    }
+
+    #[test]
+    fn instance_quantifiers_read_each_instance_state() {
+        let mut states = EntityStateMap::new();
+        states.insert(("Delivery".into(), "d1".into()), "delivered".into());
+        states.insert(("Delivery".into(), "d2".into()), "pending".into());
+        states.insert(("Order".into(), "o1".into()), "delivered".into());
+        let ctx = EvalContext::with_instances(&states, &EntityAttributeMap::new());
+        let facts = FactSet::new();
+        let mut collector = ProvenanceCollector::new();
+        let mut eval = |pred: Predicate| {
+            eval_pred(&pred, &facts, &empty_verdicts(), &ctx, &mut collector).unwrap()
+        };
+
+        assert_eq!(
+            eval(over_deliveries(true, state_is("d", "delivered"))),
+            Value::Bool(false)
+        );
+        assert_eq!(
+            eval(over_deliveries(false, state_is("d", "pending"))),
+            Value::Bool(true)
+        );
+
+        states.remove(&("Delivery".into(), "d2".into()));
+        let ctx = EvalContext::with_instances(&states, &EntityAttributeMap::new());
+        let pred = over_deliveries(true, state_is("d", "delivered"));
+        let result = eval_pred(&pred, &facts, &empty_verdicts(), &ctx, &mut collector);
+        assert_eq!(result.unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn instance_quantifiers_bind_attributes_and_handle_no_instances() {
+        let mut states = EntityStateMap::new();
+        states.insert(("Delivery".into(), "d1".into()), "failed".into());
+        let mut attributes = EntityAttributeMap::new();
+        attributes.insert(
+            ("Delivery".into(), "d1".into()),
+            BTreeMap::from([("attempts".to_string(), Value::Int(3))]),
+        );
+        let ctx = EvalContext::with_instances(&states, &attributes);
+        let facts = FactSet::new();
+        let mut collector = ProvenanceCollector::new();
+
+        let many_attempts = Predicate::Compare {
+            left: Box::new(Predicate::FieldRef {
+                var: "d".to_string(),
+                field: "attempts".to_string(),
+            }),
+            op: ">".to_string(),
+            right: Box::new(Predicate::Literal {
+                value: Value::Int(2),
+                type_spec: make_type_spec("Int"),
+            }),
+            comparison_type: None,
+        };
+        let pred = over_deliveries(false, many_attempts);
+        let result = eval_pred(&pred, &facts, &empty_verdicts(), &ctx, &mut collector);
+        assert_eq!(result.unwrap(), Value::Bool(true));
+
+        // Without an instance view the domain is empty
+        let ctx = EvalContext::new();
+        let forall = over_deliveries(true, state_is("d", "delivered"));
+        let exists = over_deliveries(false, state_is("d", "delivered"));
+        assert!(forall.reads_instances());
+        let result = eval_pred(&forall, &facts, &empty_verdicts(), &ctx, &mut collector);
+        assert_eq!(result.unwrap(), Value::Bool(true));
+        let result = eval_pred(&exists, &facts, &empty_verdicts(), &ctx, &mut collector);
+        assert_eq!(result.unwrap(), Value::Bool(false));
+    }
+
+    #[test]
+    fn instance_state_outside_quantifier_is_unbound() {
+        let mut collector = ProvenanceCollector::new();
+        let result = eval_pred(
+            &state_is("d", "delivered"),
+            &FactSet::new(),
+            &empty_verdicts(),
+            &EvalContext::new(),
+            &mut collector,
+        );
+        assert!(matches!(result, Err(EvalError::UnboundVariable { name }) if name == "d"));
+    }
 }
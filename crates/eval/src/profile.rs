@@ -0,0 +1,293 @@
+//! Opt-in evaluation profiling.
+//!
+//! [`profile`] runs a closure with profiling enabled on the current thread
+//! and returns a [`ProfileReport`] of wall time and invocation counts per
+//! rule and per flow step. Outside of [`profile`] the hooks cost one
+//! thread-local check and never read the clock, so regular evaluation
+//! (including wasm builds, which have no monotonic clock) is unaffected.
+//!
+//! Step timings are inclusive: a SubFlowStep or ParallelStep includes the
+//! time of the steps it runs.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+thread_local! {
+    static ACTIVE: RefCell<Option<ProfileReport>> = const { RefCell::new(None) };
+}
+
+/// Wall time and invocation count for one rule or flow step.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProfileStat {
+    pub calls: u64,
+    pub total_nanos: u64,
+    pub max_nanos: u64,
+}
+
+impl ProfileStat {
+    pub fn total(&self) -> Duration {
+        Duration::from_nanos(self.total_nanos)
+    }
+
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos(self.total_nanos / self.calls.max(1))
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_nanos)
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.calls += 1;
+        self.total_nanos = self.total_nanos.saturating_add(nanos);
+        self.max_nanos = self.max_nanos.max(nanos);
+    }
+}
+
+/// Per-rule and per-step timings collected by [`profile`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProfileReport {
+    /// rule id -> stat
+    pub rules: BTreeMap<String, ProfileStat>,
+    /// "flow_id.step_id" -> stat
+    pub steps: BTreeMap<String, ProfileStat>,
+    /// Wall time of the whole profiled closure.
+    pub wall_nanos: u64,
+}
+
+/// One row of the hot-spot table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotSpot<'a> {
+    /// "rule" or "step".
+    pub kind: &'static str,
+    pub id: &'a str,
+    pub stat: &'a ProfileStat,
+}
+
+impl ProfileReport {
+    /// All rules and steps, slowest (by total time) first.
+    pub fn hot_spots(&self) -> Vec<HotSpot<'_>> {
+        let rules = self.rules.iter().map(|(id, stat)| HotSpot {
+            kind: "rule",
+            id,
+            stat,
+        });
+        let steps = self.steps.iter().map(|(id, stat)| HotSpot {
+            kind: "step",
+            id,
+            stat,
+        });
+        let mut spots: Vec<HotSpot<'_>> = rules.chain(steps).collect();
+        spots.sort_by(|a, b| {
+            b.stat
+                .total_nanos
+                .cmp(&a.stat.total_nanos)
+                .then_with(|| a.kind.cmp(b.kind))
+                .then_with(|| a.id.cmp(b.id))
+        });
+        spots
+    }
+
+    /// Render the hot-spot table, showing at most `limit` rows.
+    pub fn render_table(&self, limit: usize) -> String {
+        let spots = self.hot_spots();
+        let id_width = spots
+            .iter()
+            .take(limit)
+            .map(|s| s.id.len())
+            .max()
+            .unwrap_or(0)
+            .max("id".len());
+
+        let mut out = format!(
+            "{:<4}  {:<id_width$}  {:>7}  {:>10}  {:>10}  {:>10}\n",
+            "kind",
+            "id",
+            "calls",
+            "total",
+            "mean",
+            "max",
+            id_width = id_width
+        );
+        for spot in spots.iter().take(limit) {
+            out.push_str(&format!(
+                "{:<4}  {:<id_width$}  {:>7}  {:>10}  {:>10}  {:>10}\n",
+                spot.kind,
+                spot.id,
+                spot.stat.calls,
+                format_duration(spot.stat.total()),
+                format_duration(spot.stat.mean()),
+                format_duration(spot.stat.max()),
+                id_width = id_width
+            ));
+        }
+        if spots.len() > limit {
+            out.push_str(&format!("... {} more\n", spots.len() - limit));
+        }
+        out.push_str(&format!(
+            "wall time: {}\n",
+            format_duration(Duration::from_nanos(self.wall_nanos))
+        ));
+        out
+    }
+}
+
+/// Run `f` with profiling enabled on this thread.
+///
+/// Nested calls are not supported: the inner call's timings are reported
+/// by the inner call only.
+pub fn profile<T>(f: impl FnOnce() -> T) -> (T, ProfileReport) {
+    let previous = ACTIVE.with(|a| a.borrow_mut().replace(ProfileReport::default()));
+    let started = Instant::now();
+    let value = f();
+    let elapsed = started.elapsed();
+    let mut report = ACTIVE
+        .with(|a| std::mem::replace(&mut *a.borrow_mut(), previous))
+        .unwrap_or_default();
+    report.wall_nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    (value, report)
+}
+
+/// Records its lifetime against a rule or step when dropped.
+pub(crate) struct Timer {
+    step: bool,
+    id: String,
+    started: Instant,
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed();
+        ACTIVE.with(|a| {
+            if let Some(report) = a.borrow_mut().as_mut() {
+                let map = if self.step {
+                    &mut report.steps
+                } else {
+                    &mut report.rules
+                };
+                map.entry(std::mem::take(&mut self.id))
+                    .or_default()
+                    .record(elapsed);
+            }
+        });
+    }
+}
+
+fn enabled() -> bool {
+    ACTIVE.with(|a| a.borrow().is_some())
+}
+
+/// Time a rule evaluation; `None` unless profiling is enabled.
+pub(crate) fn rule_timer(rule_id: &str) -> Option<Timer> {
+    enabled().then(|| Timer {
+        step: false,
+        id: rule_id.to_string(),
+        started: Instant::now(),
+    })
+}
+
+/// Time a flow step; `None` unless profiling is enabled.
+pub(crate) fn step_timer(flow_id: &str, step_id: &str) -> Option<Timer> {
+    enabled().then(|| Timer {
+        step: true,
+        id: format!("{}.{}", flow_id, step_id),
+        started: Instant::now(),
+    })
+}
+
+/// Human-scale duration: ns, µs, ms, or s with three significant digits.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> serde_json::Value {
+        serde_json::json!({
+            "id": "p",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": [
+                {"id": "is_active", "kind": "Fact", "tenor": "1.0",
+                 "type": {"base": "Bool"},
+                 "source": {"system": "test", "field": "active"},
+                 "provenance": {"file": "p.tenor", "line": 1}},
+                {"id": "check_active", "kind": "Rule", "tenor": "1.0", "stratum": 0,
+                 "body": {
+                     "when": {"left": {"fact_ref": "is_active"}, "op": "=",
+                              "right": {"literal": true, "type": {"base": "Bool"}}},
+                     "produce": {"verdict_type": "account_active",
+                                 "payload": {"type": {"base": "Bool"}, "value": true}}},
+                 "provenance": {"file": "p.tenor", "line": 2}},
+            ],
+        })
+    }
+
+    #[test]
+    fn profile_counts_rule_invocations() {
+        let facts = serde_json::json!({"is_active": true});
+        let (result, report) = profile(|| {
+            crate::evaluate(&bundle(), &facts).unwrap();
+            crate::evaluate(&bundle(), &facts)
+        });
+        assert!(result.is_ok());
+        assert_eq!(report.rules["check_active"].calls, 2);
+        assert!(report.steps.is_empty());
+        assert!(report.wall_nanos >= report.rules["check_active"].total_nanos);
+
+        let table = report.render_table(10);
+        assert!(table.contains("check_active"), "{}", table);
+        assert!(table.contains("wall time:"), "{}", table);
+    }
+
+    #[test]
+    fn hooks_are_inert_outside_profile() {
+        assert!(rule_timer("r").is_none());
+        assert!(step_timer("f", "s").is_none());
+        let (_, report) = profile(|| drop(rule_timer("r")));
+        assert_eq!(report.rules["r"].calls, 1);
+        assert!(rule_timer("r").is_none());
+    }
+
+    #[test]
+    fn hot_spots_sort_by_total_time() {
+        let mut report = ProfileReport::default();
+        report.rules.insert(
+            "fast".to_string(),
+            ProfileStat {
+                calls: 10,
+                total_nanos: 100,
+                max_nanos: 20,
+            },
+        );
+        report.steps.insert(
+            "flow.slow".to_string(),
+            ProfileStat {
+                calls: 1,
+                total_nanos: 5_000,
+                max_nanos: 5_000,
+            },
+        );
+        let spots = report.hot_spots();
+        assert_eq!(spots[0].id, "flow.slow");
+        assert_eq!(spots[0].kind, "step");
+        assert_eq!(spots[1].id, "fast");
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "1.5µs");
+    }
+}
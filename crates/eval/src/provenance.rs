@@ -1,8 +1,10 @@
 //! Provenance chain construction types for verdict tracing.
 //!
 //! Each verdict carries provenance recording which rule produced it,
-//! at what stratum, and which facts and verdicts were accessed during
-//! evaluation.
+//! at what stratum, which facts and verdicts were accessed during
+//! evaluation, and the exchange rate behind every currency conversion.
+
+use rust_decimal::Decimal;
 
 /// Provenance record for a single verdict instance.
 #[derive(Debug, Clone)]
@@ -15,6 +17,30 @@ pub struct VerdictProvenance {
     pub facts_used: Vec<String>,
     /// Verdict types that were accessed during predicate evaluation.
     pub verdicts_used: Vec<String>,
+    /// Currency conversions applied during predicate evaluation.
+    pub conversions: Vec<Conversion>,
+}
+
+/// A `convert(amount, rate)` applied during evaluation: the rate fact
+/// read and the rate it held.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub rate_fact: String,
+    pub from_currency: String,
+    pub to_currency: String,
+    /// Value of one unit of `from_currency` in `to_currency`.
+    pub rate: Decimal,
+}
+
+impl Conversion {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "rate_fact": self.rate_fact,
+            "from_currency": self.from_currency,
+            "to_currency": self.to_currency,
+            "rate": self.rate.to_string(),
+        })
+    }
 }
 
 /// Collector that tracks fact and verdict references during
@@ -23,6 +49,7 @@ pub struct VerdictProvenance {
 pub struct ProvenanceCollector {
     pub facts_used: Vec<String>,
     pub verdicts_used: Vec<String>,
+    pub conversions: Vec<Conversion>,
 }
 
 impl Default for ProvenanceCollector {
@@ -36,6 +63,7 @@ impl ProvenanceCollector {
         ProvenanceCollector {
             facts_used: Vec::new(),
             verdicts_used: Vec::new(),
+            conversions: Vec::new(),
         }
     }
 
@@ -53,6 +81,13 @@ impl ProvenanceCollector {
         }
     }
 
+    /// Record a currency conversion.
+    pub fn record_conversion(&mut self, conversion: Conversion) {
+        if !self.conversions.contains(&conversion) {
+            self.conversions.push(conversion);
+        }
+    }
+
     /// Finalize into a VerdictProvenance.
     pub fn into_provenance(self, rule_id: String, stratum: u32) -> VerdictProvenance {
         VerdictProvenance {
@@ -60,6 +95,7 @@ impl ProvenanceCollector {
             stratum,
             facts_used: self.facts_used,
             verdicts_used: self.verdicts_used,
+            conversions: self.conversions,
         }
     }
 }
@@ -109,5 +145,29 @@ mod tests {
         assert_eq!(p.stratum, 0);
         assert_eq!(p.facts_used, vec!["f1"]);
         assert_eq!(p.verdicts_used, vec!["v1"]);
+        assert!(p.conversions.is_empty());
+    }
+
+    #[test]
+    fn collector_deduplicates_conversions() {
+        let mut c = ProvenanceCollector::new();
+        let conversion = Conversion {
+            rate_fact: "eur_usd".to_string(),
+            from_currency: "EUR".to_string(),
+            to_currency: "USD".to_string(),
+            rate: Decimal::new(108, 2),
+        };
+        c.record_conversion(conversion.clone());
+        c.record_conversion(conversion.clone());
+        assert_eq!(c.conversions, vec![conversion]);
+        assert_eq!(
+            c.conversions[0].to_json(),
+            serde_json::json!({
+                "rate_fact": "eur_usd",
+                "from_currency": "EUR",
+                "to_currency": "USD",
+                "rate": "1.08"
+            })
+        );
     }
 }
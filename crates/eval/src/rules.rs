@@ -7,38 +7,62 @@
 //! a stratum does not matter. Higher stratum rules CAN reference verdicts
 //! produced by lower strata.
 
-use std::collections::BTreeMap;
-
-use crate::predicate::{eval_pred, EvalContext};
+use crate::predicate::{eval_compiled, SlotEnv};
 use crate::provenance::ProvenanceCollector;
 use crate::types::{
-    Contract, EvalError, FactSet, PayloadValue, Predicate, Value, VerdictInstance, VerdictSet,
+    CompiledPayload, CompiledRule, Contract, EvalError, FactSet, Rule, Value, VerdictInstance,
+    VerdictSet,
 };
 
 /// Evaluate all rules in stratum order, producing a VerdictSet.
 ///
-/// Uses a BTreeMap stratum index for O(n) build + O(n) evaluate, replacing
-/// the previous O(k*n) double-scan (find max_stratum, then filter per stratum).
-/// BTreeMap iteration is in key order, ensuring correct stratum sequencing.
+/// Runs over the contract's [`CompiledRules`](crate::types::CompiledRules):
+/// facts are laid out into dense slots once, verdict presence is tracked
+/// by slot, and rules are visited in the precomputed stratum order.
 pub fn eval_strata(contract: &Contract, facts: &FactSet) -> Result<VerdictSet, EvalError> {
+    eval_strata_with(contract, facts, false)
+}
+
+/// [`eval_strata`], also recording a [`RuleTrace`](crate::trace::RuleTrace)
+/// for every rule, available from [`VerdictSet::traces`].
+pub fn eval_strata_traced(contract: &Contract, facts: &FactSet) -> Result<VerdictSet, EvalError> {
+    eval_strata_with(contract, facts, true)
+}
+
+fn eval_strata_with(
+    contract: &Contract,
+    facts: &FactSet,
+    trace: bool,
+) -> Result<VerdictSet, EvalError> {
     let mut verdicts = VerdictSet::new();
 
-    if contract.rules.is_empty() {
+    let compiled = &contract.compiled_rules;
+    if compiled.rules.is_empty() {
         return Ok(verdicts);
     }
 
-    // Build stratum index once: O(n) where n = number of rules
-    let mut stratum_index: BTreeMap<u32, Vec<&crate::types::Rule>> = BTreeMap::new();
-    for rule in &contract.rules {
-        stratum_index.entry(rule.stratum).or_default().push(rule);
-    }
-
-    // Evaluate in stratum order (BTreeMap iterates in key order): O(n) total
-    for (stratum, rules) in &stratum_index {
-        for rule in rules {
-            if let Some(verdict) = eval_rule(rule, facts, &verdicts, *stratum)? {
-                verdicts.push(verdict);
+    let fact_slots = compiled.fact_slots(facts);
+    let mut present = vec![false; compiled.verdict_names.len()];
+
+    for &index in &compiled.order {
+        let rule = &contract.rules[index];
+        let compiled_rule = &compiled.rules[index];
+        let _timer = crate::profile::rule_timer(&rule.id);
+        let env = SlotEnv {
+            compiled,
+            facts: &fact_slots,
+            verdicts: &present,
+        };
+        let produced = eval_rule(rule, compiled_rule, env)?;
+        if trace {
+            verdicts.push_trace(crate::trace::trace_rule(rule, facts, &verdicts)?);
+        }
+        if let Some(verdict) = produced {
+            if contract.validate_payloads {
+                check_payload(rule, &verdict.payload)?;
             }
+            present[compiled_rule.verdict] = true;
+            verdicts.push(verdict);
         }
     }
 
@@ -47,17 +71,16 @@ pub fn eval_strata(contract: &Contract, facts: &FactSet) -> Result<VerdictSet, E
 
 /// Evaluate a single rule. Returns Some(VerdictInstance) if the condition
 /// is true, None if the condition is false.
-fn eval_rule(
-    rule: &crate::types::Rule,
-    facts: &FactSet,
-    verdicts: &VerdictSet,
-    stratum: u32,
+fn eval_rule<'a>(
+    rule: &Rule,
+    compiled: &'a CompiledRule,
+    env: SlotEnv<'a, '_>,
 ) -> Result<Option<VerdictInstance>, EvalError> {
     let mut collector = ProvenanceCollector::new();
-    let ctx = EvalContext::new();
+    let mut bindings = Vec::new();
 
     // Evaluate the rule's condition
-    let condition_result = eval_pred(&rule.condition, facts, verdicts, &ctx, &mut collector)?;
+    let condition_result = eval_compiled(&compiled.condition, env, &mut bindings, &mut collector)?;
     let is_true = condition_result.as_bool()?;
 
     if !is_true {
@@ -65,43 +88,32 @@ fn eval_rule(
     }
 
     // Condition is true -- compute payload
-    let payload = eval_payload(
-        &rule.produce.payload_value,
-        facts,
-        verdicts,
-        &ctx,
-        &mut collector,
-    )?;
+    let payload = match &compiled.payload {
+        CompiledPayload::Literal(v) => v.clone(),
+        CompiledPayload::Computed(pred) => {
+            eval_compiled(pred, env, &mut bindings, &mut collector)?.into_owned()
+        }
+    };
 
-    let provenance = collector.into_provenance(rule.id.clone(), stratum);
+    let provenance = collector.into_provenance(rule.id.clone(), rule.stratum);
 
     Ok(Some(VerdictInstance {
-        verdict_type: rule.produce.verdict_type.clone(),
+        verdict_type: env.compiled.verdict_names[compiled.verdict].clone(),
         payload,
         provenance,
     }))
 }
 
-/// Evaluate a payload value expression.
-fn eval_payload(
-    payload_value: &PayloadValue,
-    facts: &FactSet,
-    verdicts: &VerdictSet,
-    ctx: &EvalContext,
-    collector: &mut ProvenanceCollector,
-) -> Result<Value, EvalError> {
-    match payload_value {
-        PayloadValue::Literal(v) => Ok(v.clone()),
-        PayloadValue::Mul(mul_expr) => {
-            // Build a Predicate::Mul from the MulExpr and evaluate it
-            let pred = Predicate::Mul {
-                left: Box::new(Predicate::FactRef(mul_expr.fact_ref.clone())),
-                literal: mul_expr.literal,
-                result_type: mul_expr.result_type.clone(),
-            };
-            eval_pred(&pred, facts, verdicts, ctx, collector)
-        }
-    }
+/// Check a produced payload against the rule's declared payload type.
+pub(crate) fn check_payload(rule: &Rule, payload: &Value) -> Result<(), EvalError> {
+    rule.produce
+        .payload_type
+        .check_value(payload)
+        .map_err(|message| EvalError::PayloadMismatch {
+            rule_id: rule.id.clone(),
+            verdict_type: rule.produce.verdict_type.clone(),
+            message,
+        })
 }
 
 // ──────────────────────────────────────────────
@@ -118,6 +130,7 @@ mod tests {
             base: "Bool".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: None,
             max: None,
@@ -320,6 +333,7 @@ mod tests {
             base: "Money".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: Some("USD".to_string()),
             min: None,
             max: None,
@@ -486,6 +500,7 @@ mod tests {
             base: "Int".to_string(),
             precision: None,
             scale: None,
+            rounding: None,
             currency: None,
             min: Some(0),
             max: Some(100),
@@ -509,6 +524,7 @@ mod tests {
                         base: "Int".to_string(),
                         precision: None,
                         scale: None,
+                        rounding: None,
                         currency: None,
                         min: Some(0),
                         max: Some(0),
@@ -539,4 +555,185 @@ mod tests {
         assert_eq!(v.payload, Value::Int(50));
         assert!(v.provenance.facts_used.contains(&"x".to_string()));
     }
+
+    #[test]
+    fn eval_quantifier_over_record_list_fact() {
+        // exists item in items . item.ok = true
+        let record = |ok: bool| {
+            let mut fields = std::collections::BTreeMap::new();
+            fields.insert("ok".to_string(), Value::Bool(ok));
+            Value::Record(fields)
+        };
+        let mut facts = FactSet::new();
+        facts.insert(
+            "items".to_string(),
+            Value::List(vec![record(false), record(true)]),
+        );
+
+        let contract = make_contract(vec![make_rule(
+            "any_ok",
+            0,
+            Predicate::Exists {
+                variable: "item".to_string(),
+                variable_type: bool_type(),
+                domain: Box::new(Predicate::FactRef("items".to_string())),
+                body: Box::new(Predicate::Compare {
+                    left: Box::new(Predicate::FieldRef {
+                        var: "item".to_string(),
+                        field: "ok".to_string(),
+                    }),
+                    op: "=".to_string(),
+                    right: Box::new(Predicate::Literal {
+                        value: Value::Bool(true),
+                        type_spec: bool_type(),
+                    }),
+                    comparison_type: None,
+                }),
+            },
+            "some_ok",
+            Value::Bool(true),
+        )]);
+
+        let verdicts = eval_strata(&contract, &facts).unwrap();
+        let v = verdicts.get_verdict("some_ok").unwrap();
+        // Bound variables are not facts and do not appear in provenance.
+        assert_eq!(v.provenance.facts_used, vec!["items"]);
+    }
+
+    #[test]
+    fn payload_validation_rejects_mismatched_payload() {
+        let always = Predicate::Literal {
+            value: Value::Bool(true),
+            type_spec: bool_type(),
+        };
+        // Declared Bool, produces Int
+        let contract = make_contract(vec![make_rule(
+            "flag_rule",
+            0,
+            always,
+            "flag",
+            Value::Int(1),
+        )]);
+        let facts = FactSet::new();
+
+        // Off by default: the mismatched payload is emitted as-is
+        assert!(eval_strata(&contract, &facts).is_ok());
+
+        let contract = contract.with_payload_validation(true);
+        match eval_strata(&contract, &facts) {
+            Err(EvalError::PayloadMismatch {
+                rule_id,
+                verdict_type,
+                message,
+            }) => {
+                assert_eq!(rule_id, "flag_rule");
+                assert_eq!(verdict_type, "flag");
+                assert_eq!(message, "expected Bool, got Int");
+            }
+            other => panic!("expected PayloadMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn verdict_schema_maps_verdict_types_to_payload_types() {
+        let always = Predicate::Literal {
+            value: Value::Bool(true),
+            type_spec: bool_type(),
+        };
+        let contract = make_contract(vec![
+            make_rule("b", 1, always.clone(), "approved", Value::Bool(true)),
+            make_rule("a", 0, always, "eligible", Value::Bool(true)),
+        ]);
+        let schema = contract.verdict_schema();
+        assert_eq!(
+            schema.keys().copied().collect::<Vec<_>>(),
+            vec!["approved", "eligible"]
+        );
+        assert_eq!(
+            schema["eligible"].to_json(),
+            serde_json::json!({"base": "Bool"})
+        );
+    }
+
+    #[test]
+    fn eval_strata_traced_records_subexpression_values() {
+        let mut facts = FactSet::new();
+        facts.insert("amount".to_string(), Value::Int(500));
+        facts.insert("is_active".to_string(), Value::Bool(true));
+
+        let int_type = TypeSpec {
+            base: "Int".to_string(),
+            ..bool_type()
+        };
+        let large = Predicate::Compare {
+            left: Box::new(Predicate::FactRef("amount".to_string())),
+            op: ">".to_string(),
+            right: Box::new(Predicate::Literal {
+                value: Value::Int(1000),
+                type_spec: int_type,
+            }),
+            comparison_type: None,
+        };
+        let contract = make_contract(vec![
+            make_rule(
+                "large_active",
+                0,
+                Predicate::And {
+                    left: Box::new(large),
+                    right: Box::new(Predicate::FactRef("is_active".to_string())),
+                },
+                "large",
+                Value::Bool(true),
+            ),
+            make_rule(
+                "small",
+                1,
+                Predicate::Not {
+                    operand: Box::new(Predicate::VerdictPresent("large".to_string())),
+                },
+                "small",
+                Value::Bool(true),
+            ),
+        ]);
+
+        // Untraced evaluation records nothing
+        assert!(eval_strata(&contract, &facts).unwrap().traces().is_empty());
+
+        let verdicts = eval_strata_traced(&contract, &facts).unwrap();
+        assert!(verdicts.has_verdict("small"));
+        let traces = verdicts.traces();
+        assert_eq!(traces.len(), 2);
+
+        let large = &traces[0];
+        assert_eq!(large.rule_id, "large_active");
+        assert!(!large.fired);
+        assert_eq!(large.condition.expression, "amount > 1000 ∧ is_active");
+        let [compare, skipped] = large.condition.children.as_slice() else {
+            panic!("expected two operands, got {:?}", large.condition.children);
+        };
+        assert_eq!(compare.value, Some(Value::Bool(false)));
+        assert_eq!(compare.children[0].expression, "amount");
+        assert_eq!(compare.children[0].value, Some(Value::Int(500)));
+        assert_eq!(compare.children.len(), 1, "literal operands are omitted");
+        assert_eq!(skipped.expression, "is_active");
+        assert_eq!(skipped.value, None, "short-circuited operand");
+
+        let small = &traces[1];
+        assert!(small.fired);
+        assert_eq!(small.condition.expression, "¬verdict_present(large)");
+        assert_eq!(small.condition.children[0].value, Some(Value::Bool(false)));
+        assert_eq!(
+            small.render(),
+            "rule small (stratum 1): fired [small]\n  \
+             ¬verdict_present(large) => true\n    \
+             verdict_present(large) => false\n"
+        );
+
+        let json = verdicts.to_json();
+        assert_eq!(
+            json["traces"][0]["condition"]["children"][1]["value"],
+            serde_json::Value::Null
+        );
+        assert_eq!(json["traces"][1]["fired"], true);
+    }
 }
@@ -0,0 +1,171 @@
+//! Flow simulation: execute a flow against caller-supplied entity states
+//! and instance bindings without persisting anything.
+//!
+//! `tenor simulate --flow` and the WASM `simulate_flow_with_bindings`
+//! binding share the input parsing and the result shape defined here, so
+//! every SDK reports a simulation the same way.
+
+use crate::flow::FlowEvalResult;
+use crate::operation::{instance_key, EntityStateMap, InstanceBindingMap, DEFAULT_INSTANCE_ID};
+
+/// Parse entity states in either format:
+///
+/// - flat: `{ "Order": "draft" }`, targeting the `_default` instance
+/// - nested: `{ "Order": { "ord-001": "draft" } }`
+///
+/// The format is chosen per entity, so the two may be mixed.
+pub fn parse_entity_states(json: &serde_json::Value) -> Result<EntityStateMap, String> {
+    let obj = match json.as_object() {
+        Some(o) => o,
+        None => return Err("entity_states must be a JSON object".to_string()),
+    };
+
+    let mut entity_states = EntityStateMap::new();
+
+    for (entity_id, value) in obj {
+        if let Some(state_str) = value.as_str() {
+            // Old format: entity_id -> state string; convert to single instance
+            entity_states.insert(
+                instance_key(entity_id, DEFAULT_INSTANCE_ID),
+                state_str.into(),
+            );
+        } else if let Some(instance_map) = value.as_object() {
+            // New format: entity_id -> { instance_id -> state }
+            for (instance_id, state_val) in instance_map {
+                let state_str = match state_val.as_str() {
+                    Some(s) => s,
+                    None => {
+                        return Err(format!(
+                            "entity_states[{}][{}] must be a string state",
+                            entity_id, instance_id
+                        ))
+                    }
+                };
+                entity_states.insert(instance_key(entity_id, instance_id), state_str.into());
+            }
+        } else {
+            return Err(format!(
+                "entity_states[{}] must be a string (old format) or object (new format)",
+                entity_id
+            ));
+        }
+    }
+
+    Ok(entity_states)
+}
+
+/// Parse instance bindings: entity_id -> instance_id.
+///
+/// Format: `{ "Order": "ord-001", "DeliveryRecord": "del-001" }`. `null`
+/// means no bindings.
+pub fn parse_instance_bindings(json: &serde_json::Value) -> Result<InstanceBindingMap, String> {
+    match json {
+        serde_json::Value::Null => Ok(InstanceBindingMap::new()),
+        serde_json::Value::Object(map) => {
+            let mut result = InstanceBindingMap::new();
+            for (entity_id, instance_val) in map {
+                let instance_id = match instance_val.as_str() {
+                    Some(s) => s.to_string(),
+                    None => {
+                        return Err(format!(
+                            "instance_bindings[{}] must be a string instance_id",
+                            entity_id
+                        ))
+                    }
+                };
+                result.insert(entity_id.clone(), instance_id);
+            }
+            Ok(result)
+        }
+        _ => Err("instance_bindings must be a JSON object or null".to_string()),
+    }
+}
+
+/// The simulation report for a flow run: outcome, step path, the entity
+/// transitions the run would make, and the verdicts it was evaluated
+/// against.
+pub fn simulation_json(
+    flow_id: &str,
+    persona_id: &str,
+    result: &FlowEvalResult,
+    instance_bindings: &InstanceBindingMap,
+) -> serde_json::Value {
+    let flow_result = &result.flow_result;
+    let path: Vec<serde_json::Value> = flow_result
+        .steps_executed
+        .iter()
+        .map(|s| {
+            let mut step_json = serde_json::json!({
+                "step_id": s.step_id,
+                "step_type": s.step_type,
+                "result": s.result,
+            });
+            // Include instance_bindings on each step if non-empty
+            if !s.instance_bindings.is_empty() {
+                step_json["instance_bindings"] =
+                    serde_json::to_value(&s.instance_bindings).unwrap_or(serde_json::Value::Null);
+            }
+            step_json
+        })
+        .collect();
+
+    let would_transition: Vec<serde_json::Value> = flow_result
+        .entity_state_changes
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "entity_id": e.entity_id,
+                "instance_id": e.instance_id,
+                "from_state": e.from_state,
+                "to_state": e.to_state,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "simulation": true,
+        "flow_id": flow_id,
+        "persona": persona_id,
+        "outcome": flow_result.outcome,
+        "path": path,
+        "would_transition": would_transition,
+        "verdicts": result.verdicts.to_json()["verdicts"],
+        "instance_bindings": instance_bindings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn entity_states_accept_flat_and_nested_formats() {
+        let states = parse_entity_states(&json!({
+            "Order": "submitted",
+            "Delivery": { "del-1": "pending", "del-2": "shipped" }
+        }))
+        .unwrap();
+        assert_eq!(states.len(), 3);
+        assert_eq!(
+            states[&instance_key("Order", DEFAULT_INSTANCE_ID)].as_str(),
+            "submitted"
+        );
+        assert_eq!(
+            states[&instance_key("Delivery", "del-2")].as_str(),
+            "shipped"
+        );
+
+        let err = parse_entity_states(&json!({ "Order": 1 })).unwrap_err();
+        assert!(err.contains("entity_states[Order]"), "{}", err);
+    }
+
+    #[test]
+    fn instance_bindings_map_entities_to_instances() {
+        let bindings = parse_instance_bindings(&json!({ "Order": "ord-1" })).unwrap();
+        assert_eq!(bindings["Order"], "ord-1");
+        assert!(parse_instance_bindings(&json!(null)).unwrap().is_empty());
+        assert!(parse_instance_bindings(&json!({ "Order": 1 })).is_err());
+        assert!(parse_instance_bindings(&json!(["Order"])).is_err());
+    }
+}
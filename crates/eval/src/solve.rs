@@ -0,0 +1,507 @@
+//! Fact synthesis: search for facts that drive a flow to a target outcome.
+//!
+//! Each fact the flow depends on (see
+//! [`Contract::required_facts_for_flow`]) gets a finite domain: every value
+//! of a Bool or Enum, and for Int, Decimal, Money and Text facts the
+//! literals they are compared against, their neighbours one step away and
+//! the declared bounds. Comparisons of a fact with a literal only
+//! distinguish values by which side of the literal they fall on, so these
+//! domains cover every distinguishable case. The search executes the flow
+//! for each combination of domain values until one ends in the target
+//! outcome.
+//!
+//! When every required fact is only ever compared with literals and the
+//! whole product fits in the budget, a failed search is exhaustive: no
+//! facts within the declared types reach the outcome from the given entity
+//! states. Facts compared with other facts, read through record fields,
+//! used as deadlines or multiplied, and Date/Duration/structured facts,
+//! make the search a best effort.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::operation::{EntityStateMap, InstanceBindingMap};
+use crate::types::{
+    Contract, EvalError, FactDecl, FlowStep, PayloadValue, Predicate, TypeSpec, Value,
+};
+
+/// How a solve ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolveStatus {
+    /// `facts` drive the flow to the target outcome.
+    Satisfiable,
+    /// The search was exhaustive and no facts reach the target outcome.
+    Unsatisfiable,
+    /// No facts found, but the search was not exhaustive.
+    Unknown,
+}
+
+/// The result of [`solve_flow_outcome`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolveResult {
+    pub flow_id: String,
+    pub outcome: String,
+    pub persona: String,
+    pub status: SolveStatus,
+    /// A facts document (the `--facts` format) reaching `outcome`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facts: Option<serde_json::Value>,
+    /// Steps executed under `facts`, as `step_id:result`.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub steps: Vec<String>,
+    /// Number of fact assignments executed.
+    pub assignments_tried: usize,
+    /// Required facts whose domain is not known to cover every case.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub approximate_facts: Vec<String>,
+}
+
+/// Search for facts under which `flow_id`, started by `persona` from
+/// `entity_states`, ends in `outcome`. At most `max_assignments` fact
+/// assignments are executed.
+pub fn solve_flow_outcome(
+    contract: &Contract,
+    flow_id: &str,
+    persona: &str,
+    outcome: &str,
+    entity_states: &EntityStateMap,
+    max_assignments: usize,
+) -> Result<SolveResult, EvalError> {
+    let required =
+        contract
+            .required_facts_for_flow(flow_id)
+            .ok_or_else(|| EvalError::DeserializeError {
+                message: format!("flow '{}' not found in contract", flow_id),
+            })?;
+    let usage = FactUsage::scan(contract);
+
+    let mut base = serde_json::Map::new();
+    for decl in &contract.facts {
+        if decl.default.is_none() && !required.iter().any(|r| r.id == decl.id) {
+            // Irrelevant to the flow but still assembled.
+            let (values, _) = domain(decl, &usage);
+            base.insert(decl.id.clone(), values[0].clone());
+        }
+    }
+
+    let mut approximate_facts = Vec::new();
+    let mut domains = Vec::new();
+    for decl in &required {
+        let (values, complete) = domain(decl, &usage);
+        if !complete {
+            approximate_facts.push(decl.id.clone());
+        }
+        domains.push((decl.id.as_str(), values));
+    }
+    let total = domains
+        .iter()
+        .try_fold(1usize, |n, (_, values)| n.checked_mul(values.len()));
+
+    let mut result = SolveResult {
+        flow_id: flow_id.to_string(),
+        outcome: outcome.to_string(),
+        persona: persona.to_string(),
+        status: SolveStatus::Unknown,
+        facts: None,
+        steps: Vec::new(),
+        assignments_tried: 0,
+        approximate_facts,
+    };
+
+    let budget = total.map_or(max_assignments, |t| t.min(max_assignments));
+    for index in 0..budget {
+        // Decode `index` in mixed radix over the domains.
+        let mut facts = base.clone();
+        let mut rest = index;
+        for (id, values) in &domains {
+            facts.insert(id.to_string(), values[rest % values.len()].clone());
+            rest /= values.len();
+        }
+        let facts = serde_json::Value::Object(facts);
+        result.assignments_tried += 1;
+
+        let Ok(eval) = crate::evaluate_contract_flow(
+            contract,
+            &facts,
+            flow_id,
+            persona,
+            Some(entity_states),
+            &InstanceBindingMap::new(),
+        ) else {
+            continue;
+        };
+        if eval.flow_result.outcome == outcome {
+            result.status = SolveStatus::Satisfiable;
+            result.steps = eval
+                .flow_result
+                .steps_executed
+                .iter()
+                .map(|s| format!("{}:{}", s.step_id, s.result))
+                .collect();
+            result.facts = Some(facts);
+            return Ok(result);
+        }
+    }
+
+    if result.approximate_facts.is_empty() && total.is_some_and(|t| t <= max_assignments) {
+        result.status = SolveStatus::Unsatisfiable;
+    }
+    Ok(result)
+}
+
+// ──────────────────────────────────────────────
+// Fact domains
+// ──────────────────────────────────────────────
+
+/// How predicates across the contract use each fact.
+#[derive(Default)]
+struct FactUsage {
+    /// Literals each fact is compared with.
+    literals: BTreeMap<String, Vec<Value>>,
+    /// Facts read other than by comparison with a literal.
+    opaque: BTreeSet<String>,
+}
+
+impl FactUsage {
+    fn scan(contract: &Contract) -> Self {
+        let mut usage = FactUsage::default();
+        for rule in &contract.rules {
+            usage.predicate(&rule.condition);
+            if let PayloadValue::Mul(mul) = &rule.produce.payload_value {
+                usage.opaque.insert(mul.fact_ref.clone());
+            }
+        }
+        for op in &contract.operations {
+            usage.predicate(&op.precondition);
+            for effect in &op.effects {
+                if let Some(guard) = &effect.guard {
+                    usage.predicate(guard);
+                }
+                for value in effect.set.values() {
+                    usage.predicate(value);
+                }
+            }
+        }
+        for flow in &contract.flows {
+            usage.steps(&flow.steps);
+        }
+        usage
+    }
+
+    fn steps(&mut self, steps: &[FlowStep]) {
+        for step in steps {
+            match step {
+                FlowStep::BranchStep { condition, .. } => self.predicate(condition),
+                FlowStep::OperationStep { timeout, .. } | FlowStep::HandoffStep { timeout, .. } => {
+                    if let Some(timeout) = timeout {
+                        self.opaque.insert(timeout.deadline.clone());
+                    }
+                }
+                FlowStep::ParallelStep { branches, .. } => {
+                    for branch in branches {
+                        self.steps(&branch.steps);
+                    }
+                }
+                FlowStep::SubFlowStep { .. } => {}
+            }
+        }
+    }
+
+    fn predicate(&mut self, pred: &Predicate) {
+        match pred {
+            Predicate::Compare { left, right, .. } => match (left.as_ref(), right.as_ref()) {
+                (Predicate::FactRef(id), Predicate::Literal { value, .. })
+                | (Predicate::Literal { value, .. }, Predicate::FactRef(id)) => {
+                    self.literals
+                        .entry(id.clone())
+                        .or_default()
+                        .push(value.clone());
+                }
+                _ => {
+                    self.predicate(left);
+                    self.predicate(right);
+                }
+            },
+            Predicate::FactRef(id) => {
+                self.opaque.insert(id.clone());
+            }
+            // Reads a Record fact, or the element bound by a quantifier.
+            Predicate::FieldRef { var, .. } => {
+                self.opaque.insert(var.clone());
+            }
+            Predicate::And { left, right } | Predicate::Or { left, right } => {
+                self.predicate(left);
+                self.predicate(right);
+            }
+            Predicate::Not { operand } => self.predicate(operand),
+            Predicate::Mul { left, .. } => self.predicate(left),
+            Predicate::Convert { amount, rate_fact } => {
+                self.predicate(amount);
+                self.opaque.insert(rate_fact.clone());
+            }
+            Predicate::Forall { domain, body, .. } | Predicate::Exists { domain, body, .. } => {
+                self.predicate(domain);
+                self.predicate(body);
+            }
+            Predicate::Literal { .. }
+            | Predicate::VerdictPresent(_)
+            | Predicate::InstancesOf(_)
+            | Predicate::InstanceState(_) => {}
+        }
+    }
+}
+
+/// The candidate values of a fact in the facts-file format, never empty,
+/// and whether they cover every case the contract can distinguish.
+fn domain(decl: &FactDecl, usage: &FactUsage) -> (Vec<serde_json::Value>, bool) {
+    let spec = &decl.fact_type;
+    let literals = usage
+        .literals
+        .get(&decl.id)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let compared_only = !usage.opaque.contains(&decl.id);
+
+    match spec.base.as_str() {
+        "Bool" => (vec![json!(false), json!(true)], true),
+        "Enum" => {
+            let values: Vec<_> = spec.values.iter().flatten().map(|v| json!(v)).collect();
+            if values.is_empty() {
+                (vec![json!("")], false)
+            } else {
+                (values, true)
+            }
+        }
+        "Int" => {
+            let min = spec.min.unwrap_or(i64::MIN);
+            let max = spec.max.unwrap_or(i64::MAX);
+            let mut points = BTreeSet::new();
+            for literal in literals {
+                let (lo, hi) = match literal {
+                    Value::Int(n) => (*n, *n),
+                    Value::Decimal(d) => (
+                        d.floor().try_into().unwrap_or(0),
+                        d.ceil().try_into().unwrap_or(0),
+                    ),
+                    _ => continue,
+                };
+                points.extend([lo.saturating_sub(1), lo, hi, hi.saturating_add(1)]);
+            }
+            points.insert(0i64.clamp(min, max));
+            if spec.min.is_some() {
+                points.insert(min);
+            }
+            if spec.max.is_some() {
+                points.insert(max);
+            }
+            let values = points
+                .into_iter()
+                .filter(|n| (min..=max).contains(n))
+                .map(|n| json!(n))
+                .collect();
+            (values, compared_only)
+        }
+        "Decimal" => {
+            let scale = spec.scale.unwrap_or(0);
+            let values = decimal_points(literals, scale)
+                .into_iter()
+                .map(|d| json!(d.to_string()))
+                .collect();
+            (values, compared_only && spec.scale.is_some())
+        }
+        "Money" => {
+            let currency = spec.currency.clone().unwrap_or_else(|| "USD".to_string());
+            let values = decimal_points(literals, spec.scale.unwrap_or(2))
+                .into_iter()
+                .map(|d| json!({ "amount": d.to_string(), "currency": currency }))
+                .collect();
+            (values, compared_only)
+        }
+        "Text" => {
+            let max_length = spec.max_length.map_or(usize::MAX, |m| m as usize);
+            let mut texts: BTreeSet<String> = literals
+                .iter()
+                .filter_map(|v| match v {
+                    Value::Text(t) if t.chars().count() <= max_length => Some(t.clone()),
+                    _ => None,
+                })
+                .collect();
+            // One value distinct from every literal.
+            let other = (0..)
+                .map(|i| format!("x{}", i))
+                .find(|t| !texts.contains(t))
+                .unwrap_or_default();
+            texts.insert(other.chars().take(max_length).collect());
+            (texts.into_iter().map(|t| json!(t)).collect(), compared_only)
+        }
+        _ => {
+            let mut values: Vec<_> = literals
+                .iter()
+                .filter_map(|v| match v {
+                    Value::Date(s) | Value::DateTime(s) => Some(json!(s)),
+                    _ => None,
+                })
+                .collect();
+            values.push(placeholder(spec));
+            (values, false)
+        }
+    }
+}
+
+/// Each literal amount and its neighbours one unit of `scale` away, plus
+/// zero.
+fn decimal_points(literals: &[Value], scale: u32) -> BTreeSet<Decimal> {
+    let step = Decimal::new(1, scale);
+    let mut points = BTreeSet::new();
+    for literal in literals {
+        let amount = match literal {
+            Value::Int(n) => Decimal::from(*n),
+            Value::Decimal(d) => *d,
+            Value::Money { amount, .. } => *amount,
+            _ => continue,
+        };
+        for d in [amount - step, amount, amount + step] {
+            points.insert(d.round_dp(scale));
+        }
+    }
+    points.insert(Decimal::new(0, scale));
+    points
+}
+
+/// A valid value of `spec` for facts whose value does not matter.
+fn placeholder(spec: &TypeSpec) -> serde_json::Value {
+    match spec.base.as_str() {
+        "Bool" => json!(false),
+        "Int" => json!(0i64.clamp(spec.min.unwrap_or(i64::MIN), spec.max.unwrap_or(i64::MAX))),
+        "Decimal" => json!(Decimal::new(0, spec.scale.unwrap_or(0)).to_string()),
+        "Money" => json!({
+            "amount": Decimal::new(0, spec.scale.unwrap_or(2)).to_string(),
+            "currency": spec.currency.as_deref().unwrap_or("USD"),
+        }),
+        "Text" => json!(""),
+        "Date" => json!("2025-01-01"),
+        "DateTime" => json!("2025-01-01T00:00:00Z"),
+        "Duration" => json!({ "value": 0, "unit": spec.unit.as_deref().unwrap_or("seconds") }),
+        "Enum" => json!(spec
+            .values
+            .iter()
+            .flatten()
+            .next()
+            .cloned()
+            .unwrap_or_default()),
+        "Record" => serde_json::Value::Object(
+            spec.fields
+                .iter()
+                .flatten()
+                .map(|(name, field)| (name.clone(), placeholder(field)))
+                .collect(),
+        ),
+        "List" => json!([]),
+        "TaggedUnion" => match spec.variants.iter().flatten().next() {
+            Some((tag, payload)) => json!({ "tag": tag, "payload": placeholder(payload) }),
+            None => serde_json::Value::Null,
+        },
+        _ => serde_json::Value::Null,
+    }
+}
+
+// ──────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation::{init_entity_states, instance_key, DEFAULT_INSTANCE_ID};
+    use crate::test_support::flow_basic;
+
+    fn solve(contract: &Contract, outcome: &str, states: &EntityStateMap) -> SolveResult {
+        solve_flow_outcome(contract, "order_approval", "buyer", outcome, states, 1000).unwrap()
+    }
+
+    #[test]
+    fn finds_facts_reaching_the_outcome() {
+        let contract = flow_basic();
+        let result = solve(&contract, "success", &init_entity_states(&contract));
+        assert_eq!(result.status, SolveStatus::Satisfiable);
+        let facts = result.facts.unwrap();
+        assert_eq!(facts["is_active"], true);
+        assert_eq!(result.steps[0], "step_submit:success");
+    }
+
+    #[test]
+    fn proves_unreachable_outcome() {
+        let contract = flow_basic();
+        let result = solve(&contract, "escalated", &init_entity_states(&contract));
+        assert_eq!(result.status, SolveStatus::Unsatisfiable);
+        assert!(result.facts.is_none());
+        // is_active x requires_review
+        assert_eq!(result.assignments_tried, 4);
+    }
+
+    #[test]
+    fn entity_states_constrain_the_search() {
+        let contract = flow_basic();
+        // An approved Order cannot be submitted, so the flow never succeeds.
+        let mut states = EntityStateMap::new();
+        states.insert(
+            instance_key("Order", DEFAULT_INSTANCE_ID),
+            "approved".into(),
+        );
+        assert_eq!(
+            solve(&contract, "success", &states).status,
+            SolveStatus::Unsatisfiable
+        );
+    }
+
+    #[test]
+    fn int_domain_straddles_literals_within_bounds() {
+        let decl = FactDecl {
+            id: "amount".to_string(),
+            fact_type: TypeSpec {
+                base: "Int".to_string(),
+                precision: None,
+                scale: None,
+                rounding: None,
+                currency: None,
+                min: Some(0),
+                max: Some(100),
+                max_length: None,
+                values: None,
+                fields: None,
+                element_type: None,
+                unit: None,
+                variants: None,
+            },
+            default: None,
+        };
+        let mut usage = FactUsage::default();
+        usage
+            .literals
+            .insert("amount".to_string(), vec![Value::Int(50), Value::Int(100)]);
+        let (values, complete) = domain(&decl, &usage);
+        assert_eq!(values, [0, 49, 50, 51, 99, 100].map(|n| json!(n)));
+        assert!(complete);
+
+        usage.opaque.insert("amount".to_string());
+        assert!(!domain(&decl, &usage).1);
+    }
+
+    #[test]
+    fn unknown_flow_is_an_error() {
+        let contract = flow_basic();
+        assert!(solve_flow_outcome(
+            &contract,
+            "nope",
+            "buyer",
+            "success",
+            &init_entity_states(&contract),
+            10
+        )
+        .is_err());
+    }
+}
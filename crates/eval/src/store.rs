@@ -0,0 +1,353 @@
+//! Shared, thread-safe access to parsed contracts.
+//!
+//! [`Contract::from_interchange`] does real work: it deserializes every
+//! construct, builds lookup indexes, and compiles rules. A [`ContractHandle`]
+//! parses a bundle once and shares the result behind an `Arc`, so any number
+//! of threads can evaluate against it without re-parsing or copying.
+//! [`ContractStore`] is a keyed collection of handles for embedders that
+//! serve several contracts (the HTTP server, for example).
+
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use std::sync::{Arc, RwLock};
+
+use sha2::{Digest, Sha256};
+
+use crate::flow::{FlowContinuation, FlowOptions, FlowRun, Snapshot};
+use crate::operation::{EntityStateMap, EntityVersions, InstanceBindingMap};
+use crate::types::{Contract, EvalError};
+use crate::{EvalResult, FlowEvalResult};
+
+struct Loaded {
+    id: String,
+    bundle: serde_json::Value,
+    etag: String,
+    contract: Contract,
+}
+
+/// SHA-256 of the compact JSON form of `bundle`, hex-encoded: the etag a
+/// contract is published and pinned under.
+pub fn bundle_etag(bundle: &serde_json::Value) -> String {
+    let canonical = serde_json::to_string(bundle)
+        .unwrap_or_else(|e| panic!("serialization error computing etag: {}", e));
+    format!("{:x}", Sha256::digest(canonical.as_bytes()))
+}
+
+/// A parsed contract together with the interchange bundle it came from.
+///
+/// Cloning a handle is a reference-count increment. Handles are `Send` and
+/// `Sync`; evaluation only borrows the contract, so concurrent evaluations
+/// against one handle never contend.
+#[derive(Clone)]
+pub struct ContractHandle(Arc<Loaded>);
+
+impl ContractHandle {
+    /// Parse an interchange bundle. A type table is inlined, so
+    /// [`ContractHandle::bundle`] never contains `type_ref`s.
+    pub fn from_interchange(bundle: serde_json::Value) -> Result<ContractHandle, EvalError> {
+        let bundle =
+            tenor_interchange::into_inlined(bundle).map_err(|e| EvalError::DeserializeError {
+                message: e.to_string(),
+            })?;
+        let contract = Contract::from_interchange(&bundle)?;
+        let id = bundle
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let etag = bundle_etag(&bundle);
+        Ok(ContractHandle(Arc::new(Loaded {
+            id,
+            bundle,
+            etag,
+            contract,
+        })))
+    }
+
+    /// The bundle's `id`, or `"unknown"` if it has none.
+    pub fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    /// The interchange bundle the contract was parsed from.
+    pub fn bundle(&self) -> &serde_json::Value {
+        &self.0.bundle
+    }
+
+    /// The [`bundle_etag`] of [`ContractHandle::bundle`], computed at load.
+    pub fn etag(&self) -> &str {
+        &self.0.etag
+    }
+
+    pub fn contract(&self) -> &Contract {
+        &self.0.contract
+    }
+
+    /// Evaluate rules against `facts`; see [`crate::evaluate`].
+    pub fn evaluate(&self, facts: &serde_json::Value) -> Result<EvalResult, EvalError> {
+        crate::evaluate_contract(&self.0.contract, facts)
+    }
+
+    /// Evaluate rules and execute a flow; see [`crate::evaluate_flow`].
+    pub fn evaluate_flow(
+        &self,
+        facts: &serde_json::Value,
+        flow_id: &str,
+        persona: &str,
+        override_entity_states: Option<&EntityStateMap>,
+        instance_bindings: &InstanceBindingMap,
+    ) -> Result<FlowEvalResult, EvalError> {
+        crate::evaluate_contract_flow(
+            &self.0.contract,
+            facts,
+            flow_id,
+            persona,
+            override_entity_states,
+            instance_bindings,
+        )
+    }
+
+    /// Evaluate rules and start a flow that may pause at handoff steps; see
+    /// [`crate::flow::start_flow`].
+    pub fn start_flow(
+        &self,
+        facts: &serde_json::Value,
+        flow_id: &str,
+        persona: &str,
+        override_entity_states: Option<&EntityStateMap>,
+        instance_bindings: &InstanceBindingMap,
+        options: FlowOptions<'_>,
+    ) -> Result<FlowRun, EvalError> {
+        let contract = &self.0.contract;
+        let fact_set = crate::assemble::assemble_facts(contract, facts)?;
+        let verdicts = crate::rules::eval_strata(contract, &fact_set)?;
+        let entity_states = match override_entity_states {
+            Some(provided) => provided.clone(),
+            None => crate::operation::init_entity_states(contract),
+        };
+        crate::flow::start_flow(
+            contract,
+            &self.0.bundle,
+            Snapshot {
+                facts: fact_set,
+                verdicts,
+            },
+            flow_id,
+            persona,
+            entity_states,
+            instance_bindings.clone(),
+            options,
+        )
+    }
+
+    /// Resume a suspended flow; see [`crate::flow::resume_flow`].
+    pub fn resume_flow(
+        &self,
+        continuation: FlowContinuation,
+        persona: &str,
+        entity_versions: &EntityVersions,
+    ) -> Result<FlowRun, EvalError> {
+        crate::flow::resume_flow(
+            &self.0.contract,
+            &self.0.bundle,
+            continuation,
+            persona,
+            entity_versions,
+        )
+    }
+
+    /// Fire the timeout of the handoff a suspended flow is paused at; see
+    /// [`crate::flow::expire_flow`].
+    pub fn expire_flow(
+        &self,
+        continuation: FlowContinuation,
+        entity_versions: &EntityVersions,
+    ) -> Result<FlowRun, EvalError> {
+        crate::flow::expire_flow(
+            &self.0.contract,
+            &self.0.bundle,
+            continuation,
+            entity_versions,
+        )
+    }
+
+    /// Whether two handles share the same parsed contract.
+    pub fn ptr_eq(&self, other: &ContractHandle) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for ContractHandle {
+    type Target = Contract;
+
+    fn deref(&self) -> &Contract {
+        &self.0.contract
+    }
+}
+
+impl std::fmt::Debug for ContractHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ContractHandle").field(&self.0.id).finish()
+    }
+}
+
+/// Contract handles keyed by bundle id.
+///
+/// All methods take `&self`; wrap the store in an `Arc` (or put it in a
+/// `static`) to share it. Lookups return cloned handles, so the internal
+/// lock is held only for the duration of the map operation, never across
+/// an evaluation.
+#[derive(Default)]
+pub struct ContractStore {
+    contracts: RwLock<BTreeMap<String, ContractHandle>>,
+}
+
+impl ContractStore {
+    pub fn new() -> ContractStore {
+        ContractStore::default()
+    }
+
+    /// Parse `bundle` and store it under its id, replacing any contract
+    /// already stored under that id.
+    pub fn load(&self, bundle: serde_json::Value) -> Result<ContractHandle, EvalError> {
+        let handle = ContractHandle::from_interchange(bundle)?;
+        self.insert(handle.clone());
+        Ok(handle)
+    }
+
+    /// Store `handle` under its id, returning the handle it replaced.
+    pub fn insert(&self, handle: ContractHandle) -> Option<ContractHandle> {
+        self.write().insert(handle.id().to_string(), handle)
+    }
+
+    pub fn get(&self, id: &str) -> Option<ContractHandle> {
+        self.read().get(id).cloned()
+    }
+
+    pub fn remove(&self, id: &str) -> Option<ContractHandle> {
+        self.write().remove(id)
+    }
+
+    /// Stored contract ids, sorted.
+    pub fn ids(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    /// All stored handles, sorted by id.
+    pub fn handles(&self) -> Vec<ContractHandle> {
+        self.read().values().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    // A panic while holding the lock cannot leave the map half-updated
+    // (every write is a single insert or remove), so poisoning is ignored.
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<String, ContractHandle>> {
+        self.contracts.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, BTreeMap<String, ContractHandle>> {
+        self.contracts.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle(id: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": [
+                {
+                    "id": "is_active",
+                    "kind": "Fact",
+                    "tenor": "1.0",
+                    "provenance": { "file": "test.tenor", "line": 1 },
+                    "source": { "system": "test", "field": "active" },
+                    "type": { "base": "Bool" }
+                },
+                {
+                    "id": "check_active",
+                    "kind": "Rule",
+                    "tenor": "1.0",
+                    "provenance": { "file": "test.tenor", "line": 5 },
+                    "stratum": 0,
+                    "body": {
+                        "when": {
+                            "left": { "fact_ref": "is_active" },
+                            "op": "=",
+                            "right": { "literal": true, "type": { "base": "Bool" } }
+                        },
+                        "produce": {
+                            "verdict_type": "account_active",
+                            "payload": { "type": { "base": "Bool" }, "value": true }
+                        }
+                    }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn handles_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ContractHandle>();
+        assert_send_sync::<ContractStore>();
+    }
+
+    #[test]
+    fn etag_is_the_bundle_etag_of_the_loaded_bundle() {
+        let handle = ContractHandle::from_interchange(bundle("accounts")).unwrap();
+        assert_eq!(handle.etag(), bundle_etag(handle.bundle()));
+        assert_eq!(handle.etag().len(), 64);
+        let other = ContractHandle::from_interchange(bundle("payments")).unwrap();
+        assert_ne!(handle.etag(), other.etag());
+    }
+
+    #[test]
+    fn store_shares_one_parsed_contract() {
+        let store = ContractStore::new();
+        let loaded = store.load(bundle("accounts")).unwrap();
+        assert_eq!(loaded.id(), "accounts");
+        assert_eq!(store.ids(), vec!["accounts".to_string()]);
+        assert!(store.get("accounts").unwrap().ptr_eq(&loaded));
+        assert!(store.get("missing").is_none());
+
+        let store = Arc::new(store);
+        let threads: Vec<_> = [true, false]
+            .into_iter()
+            .map(|active| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    let handle = store.get("accounts").unwrap();
+                    let result = handle.evaluate(&json!({ "is_active": active })).unwrap();
+                    result.verdicts.has_verdict("account_active")
+                })
+            })
+            .collect();
+        let produced: Vec<bool> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+        assert_eq!(produced, vec![true, false]);
+
+        assert!(store.remove("accounts").is_some());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn load_rejects_invalid_bundle_without_storing() {
+        let store = ContractStore::new();
+        let err = store.load(json!({ "id": "broken", "constructs": "nope" }));
+        assert!(err.is_err());
+        assert!(store.is_empty());
+    }
+}
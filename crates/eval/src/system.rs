@@ -0,0 +1,535 @@
+//! System-level evaluation -- executes flows across the member contracts
+//! of a System.
+//!
+//! A [`SystemEvaluator`] holds every member contract of one System and
+//! applies the System declaration at run time:
+//!
+//! - **Facts** are routed per member: a flat facts object is shared by all
+//!   members, and a `"<member>.<fact>"` key overrides `<fact>` for that
+//!   member only.
+//! - **Shared entities** live in one state store visible to every member
+//!   that shares them; all other entities are private to their member.
+//! - **Flow triggers** fire when a flow reaches the trigger's outcome: the
+//!   target flow runs in the target contract under the trigger persona,
+//!   against the entity states the source flow left behind, and records
+//!   which execution triggered it.
+//!
+//! Spec reference: Section 12 (System).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use tenor_interchange::{InterchangeConstruct, SystemConstruct};
+
+use crate::flow::{self, FlowOptions, FlowResult, Snapshot};
+use crate::operation::{
+    self, EffectRecord, EntityAttributeMap, EntityStateMap, InstanceBindingMap,
+};
+use crate::types::{Contract, EffectKind, EvalError, VerdictSet};
+use crate::{assemble, rules};
+
+/// Upper bound on chained trigger executions from a single flow run.
+///
+/// Pass 5 rejects trigger cycles, so this only guards hand-built bundles.
+pub const MAX_TRIGGER_DEPTH: usize = 32;
+
+/// Entity states across all members of a System.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SystemEntityStates {
+    /// States of shared entities, visible to every member sharing them.
+    pub shared: EntityStateMap,
+    /// member id -> states of entities private to that member.
+    pub members: BTreeMap<String, EntityStateMap>,
+}
+
+/// The trigger that started a flow execution in another contract.
+#[derive(Debug, Clone)]
+pub struct TriggerProvenance {
+    pub source_contract: String,
+    pub source_flow: String,
+    pub on: String,
+    /// Shared-entity effects of the source flow that the target flow
+    /// observed when it started.
+    pub shared_effects: Vec<EffectRecord>,
+}
+
+/// One flow execution within a System run.
+#[derive(Debug, Clone)]
+pub struct SystemFlowExecution {
+    pub contract_id: String,
+    pub flow_id: String,
+    pub persona: String,
+    /// `None` for the flow the caller started.
+    pub triggered_by: Option<TriggerProvenance>,
+    pub verdicts: VerdictSet,
+    pub flow_result: FlowResult,
+}
+
+/// Result of running a flow and every flow it triggered, in execution order.
+#[derive(Debug, Clone)]
+pub struct SystemFlowResult {
+    pub executions: Vec<SystemFlowExecution>,
+}
+
+/// A member contract loaded into a System.
+struct Member {
+    contract: Contract,
+    /// Entities of this member that the System declares shared with it.
+    shared_entities: BTreeSet<String>,
+}
+
+/// Evaluator for all member contracts of a System.
+pub struct SystemEvaluator {
+    system: SystemConstruct,
+    members: BTreeMap<String, Member>,
+}
+
+impl SystemEvaluator {
+    /// Load a System from its interchange bundle and the bundles of its
+    /// members, keyed by member id.
+    pub fn new(
+        system_bundle: &serde_json::Value,
+        member_bundles: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<Self, EvalError> {
+        let bundle = tenor_interchange::from_interchange(system_bundle).map_err(|e| {
+            EvalError::DeserializeError {
+                message: e.to_string(),
+            }
+        })?;
+        let system = bundle
+            .constructs
+            .into_iter()
+            .find_map(|c| match c {
+                InterchangeConstruct::System(s) => Some(s),
+                _ => None,
+            })
+            .ok_or_else(|| EvalError::DeserializeError {
+                message: format!("bundle '{}' does not declare a system", bundle.id),
+            })?;
+        Self::from_system(system, member_bundles)
+    }
+
+    /// Load a System from an already-deserialized System construct.
+    pub fn from_system(
+        system: SystemConstruct,
+        member_bundles: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<Self, EvalError> {
+        let mut members = BTreeMap::new();
+        for member in &system.members {
+            let bundle =
+                member_bundles
+                    .get(&member.id)
+                    .ok_or_else(|| EvalError::DeserializeError {
+                        message: format!("no bundle provided for member contract '{}'", member.id),
+                    })?;
+            let shared_entities = system
+                .shared_entities
+                .iter()
+                .filter(|s| s.contracts.contains(&member.id))
+                .map(|s| s.entity.clone())
+                .collect();
+            members.insert(
+                member.id.clone(),
+                Member {
+                    contract: Contract::from_interchange(bundle)?,
+                    shared_entities,
+                },
+            );
+        }
+
+        // Shared entities must agree on their state machine to share states.
+        for shared in &system.shared_entities {
+            let mut declared: Option<(&str, &crate::types::Entity)> = None;
+            for contract_id in &shared.contracts {
+                let entity = members
+                    .get(contract_id)
+                    .and_then(|m| m.contract.get_entity(&shared.entity))
+                    .ok_or_else(|| EvalError::DeserializeError {
+                        message: format!(
+                            "shared entity '{}' is not declared in contract '{}'",
+                            shared.entity, contract_id
+                        ),
+                    })?;
+                if let Some((first_id, first)) = declared {
+                    let same_states = first.states.iter().collect::<BTreeSet<_>>()
+                        == entity.states.iter().collect::<BTreeSet<_>>();
+                    if !same_states || first.initial != entity.initial {
+                        return Err(EvalError::DeserializeError {
+                            message: format!(
+                                "shared entity '{}' is declared differently in '{}' and '{}'",
+                                shared.entity, first_id, contract_id
+                            ),
+                        });
+                    }
+                } else {
+                    declared = Some((contract_id.as_str(), entity));
+                }
+            }
+        }
+
+        Ok(SystemEvaluator { system, members })
+    }
+
+    pub fn system_id(&self) -> &str {
+        &self.system.id
+    }
+
+    /// Member contract by id.
+    pub fn contract(&self, member_id: &str) -> Option<&Contract> {
+        self.members.get(member_id).map(|m| &m.contract)
+    }
+
+    /// Initial states of every entity in every member, with each shared
+    /// entity stored once.
+    pub fn initial_entity_states(&self) -> SystemEntityStates {
+        let mut states = SystemEntityStates::default();
+        for (member_id, member) in &self.members {
+            let (shared, private): (EntityStateMap, EntityStateMap) =
+                operation::init_entity_states(&member.contract)
+                    .into_iter()
+                    .partition(|((entity_id, _), _)| {
+                        member.shared_entities.contains(entity_id.as_str())
+                    });
+            for (key, state) in shared {
+                states.shared.entry(key).or_insert(state);
+            }
+            states.members.insert(member_id.clone(), private);
+        }
+        states
+    }
+
+    /// Execute a flow in one member contract, then every flow its outcome
+    /// triggers, transitively.
+    ///
+    /// `entity_states` is updated in place with the effects of every
+    /// execution. Evaluation errors in a triggered flow abort the run.
+    pub fn execute_flow(
+        &self,
+        contract_id: &str,
+        flow_id: &str,
+        persona: &str,
+        facts: &serde_json::Value,
+        entity_states: &mut SystemEntityStates,
+    ) -> Result<SystemFlowResult, EvalError> {
+        let mut executions = Vec::new();
+        let mut pending = vec![(
+            contract_id.to_string(),
+            flow_id.to_string(),
+            persona.to_string(),
+            None,
+        )];
+
+        while let Some((contract_id, flow_id, persona, triggered_by)) = pending.pop() {
+            if executions.len() > MAX_TRIGGER_DEPTH {
+                return Err(EvalError::FlowError {
+                    flow_id,
+                    message: format!(
+                        "system '{}' exceeded {} chained trigger executions",
+                        self.system.id, MAX_TRIGGER_DEPTH
+                    ),
+                });
+            }
+
+            let execution = self.execute_member_flow(
+                &contract_id,
+                &flow_id,
+                &persona,
+                facts,
+                entity_states,
+                triggered_by,
+            )?;
+
+            let member = &self.members[&contract_id];
+            let shared_effects: Vec<EffectRecord> = execution
+                .flow_result
+                .entity_state_changes
+                .iter()
+                .filter(|e| member.shared_entities.contains(e.entity_id.as_str()))
+                .cloned()
+                .collect();
+
+            // Push in reverse so triggers run in declaration order.
+            for trigger in self.system.flow_triggers.iter().rev().filter(|t| {
+                t.source_contract == contract_id
+                    && t.source_flow == flow_id
+                    && t.on == execution.flow_result.outcome
+            }) {
+                pending.push((
+                    trigger.target_contract.clone(),
+                    trigger.target_flow.clone(),
+                    trigger.persona.clone(),
+                    Some(TriggerProvenance {
+                        source_contract: contract_id.clone(),
+                        source_flow: flow_id.clone(),
+                        on: trigger.on.clone(),
+                        shared_effects: shared_effects.clone(),
+                    }),
+                ));
+            }
+
+            executions.push(execution);
+        }
+
+        Ok(SystemFlowResult { executions })
+    }
+
+    fn execute_member_flow(
+        &self,
+        contract_id: &str,
+        flow_id: &str,
+        persona: &str,
+        facts: &serde_json::Value,
+        entity_states: &mut SystemEntityStates,
+        triggered_by: Option<TriggerProvenance>,
+    ) -> Result<SystemFlowExecution, EvalError> {
+        let member = self
+            .members
+            .get(contract_id)
+            .ok_or_else(|| EvalError::DeserializeError {
+                message: format!(
+                    "contract '{}' is not a member of system '{}'",
+                    contract_id, self.system.id
+                ),
+            })?;
+        let contract = &member.contract;
+        let target_flow =
+            contract
+                .get_flow(flow_id)
+                .ok_or_else(|| EvalError::DeserializeError {
+                    message: format!("flow '{}' not found in contract '{}'", flow_id, contract_id),
+                })?;
+
+        let fact_set = assemble::assemble_facts(contract, &route_facts(contract_id, facts)?)?;
+        let verdict_set = rules::eval_strata(contract, &fact_set)?;
+        let snapshot = Snapshot {
+            facts: fact_set,
+            verdicts: verdict_set.clone(),
+        };
+
+        // Assemble this member's view: its private entities plus the
+        // shared entities it participates in.
+        let mut view: EntityStateMap = entity_states
+            .members
+            .get(contract_id)
+            .cloned()
+            .unwrap_or_default();
+        view.extend(
+            entity_states
+                .shared
+                .iter()
+                .filter(|((entity_id, _), _)| member.shared_entities.contains(entity_id.as_str()))
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+
+        let mut flow_result = flow::execute_flow(
+            target_flow,
+            contract,
+            &snapshot,
+            &mut view,
+            &mut EntityAttributeMap::new(),
+            &InstanceBindingMap::new(),
+            FlowOptions::default(),
+        )?;
+        flow_result.initiating_persona = Some(persona.to_string());
+
+        // Write the view back, splitting shared from private states.
+        let private = entity_states
+            .members
+            .entry(contract_id.to_string())
+            .or_default();
+        // The view no longer holds destroyed instances; drop them before
+        // copying the view over, which may hold an instance re-created under
+        // the same id.
+        for change in &flow_result.entity_state_changes {
+            if change.kind == EffectKind::DestroyInstance {
+                let key = (change.entity_id.clone(), change.instance_id.clone());
+                entity_states.shared.remove(&key);
+                private.remove(&key);
+            }
+        }
+        for (key, state) in view {
+            if member.shared_entities.contains(key.0.as_str()) {
+                entity_states.shared.insert(key, state);
+            } else {
+                private.insert(key, state);
+            }
+        }
+
+        Ok(SystemFlowExecution {
+            contract_id: contract_id.to_string(),
+            flow_id: flow_id.to_string(),
+            persona: persona.to_string(),
+            triggered_by,
+            verdicts: verdict_set,
+            flow_result,
+        })
+    }
+}
+
+/// Facts for one member: unqualified keys apply to every member, and
+/// `"<member>.<fact>"` keys override them for that member.
+fn route_facts(member_id: &str, facts: &serde_json::Value) -> Result<serde_json::Value, EvalError> {
+    let facts_obj = facts
+        .as_object()
+        .ok_or_else(|| EvalError::DeserializeError {
+            message: "facts must be a JSON object".to_string(),
+        })?;
+    let prefix = format!("{}.", member_id);
+
+    let mut routed: serde_json::Map<String, serde_json::Value> = facts_obj
+        .iter()
+        .filter(|(k, _)| !k.contains('.'))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    for (key, value) in facts_obj {
+        if let Some(fact_id) = key.strip_prefix(&prefix) {
+            routed.insert(fact_id.to_string(), value.clone());
+        }
+    }
+    Ok(serde_json::Value::Object(routed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation::DEFAULT_INSTANCE_ID;
+    use crate::symbol::Symbol;
+    use serde_json::json;
+
+    /// A member whose single flow moves the shared `Order` entity one step
+    /// forward when its `ready` fact is true.
+    fn member(op: &str, from: &str, to: &str) -> serde_json::Value {
+        json!({
+            "id": "member",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": [
+                {"id": "clerk", "kind": "Persona", "tenor": "1.0",
+                 "provenance": {"file": "m.tenor", "line": 1}},
+                {"id": "ready", "kind": "Fact", "tenor": "1.0",
+                 "type": {"base": "Bool"},
+                 "source": {"system": "test", "field": "ready"},
+                 "provenance": {"file": "m.tenor", "line": 2}},
+                {"id": "Order", "kind": "Entity", "tenor": "1.0",
+                 "states": ["draft", "submitted", "approved"], "initial": "draft",
+                 "transitions": [{"from": "draft", "to": "submitted"},
+                                 {"from": "submitted", "to": "approved"}],
+                 "provenance": {"file": "m.tenor", "line": 3}},
+                {"id": op, "kind": "Operation", "tenor": "1.0",
+                 "allowed_personas": ["clerk"],
+                 "precondition": {"left": {"fact_ref": "ready"}, "op": "=",
+                                  "right": {"literal": true, "type": {"base": "Bool"}}},
+                 "effects": [{"entity_id": "Order", "from": from, "to": to}],
+                 "error_contract": [],
+                 "provenance": {"file": "m.tenor", "line": 4}},
+                {"id": "main", "kind": "Flow", "tenor": "1.0",
+                 "entry": "s", "snapshot": "at_initiation",
+                 "steps": [{"id": "s", "kind": "OperationStep", "op": op, "persona": "clerk",
+                            "outcomes": {"success": {"kind": "Terminal", "outcome": "done"}},
+                            "on_failure": {"kind": "Terminate", "outcome": "failed"}}],
+                 "provenance": {"file": "m.tenor", "line": 5}},
+            ],
+        })
+    }
+
+    fn system_bundle(shared: bool) -> serde_json::Value {
+        let shared_entities = if shared {
+            json!([{"entity": "Order", "contracts": ["a", "b"]}])
+        } else {
+            json!([])
+        };
+        json!({
+            "id": "sys",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": [{
+                "id": "sys",
+                "kind": "System",
+                "tenor": "1.0",
+                "members": [{"id": "a", "path": "a.tenor"}, {"id": "b", "path": "b.tenor"}],
+                "shared_personas": [{"persona": "clerk", "contracts": ["a", "b"]}],
+                "triggers": [{"source_contract": "a", "source_flow": "main", "on": "done",
+                              "target_contract": "b", "target_flow": "main", "persona": "clerk"}],
+                "shared_entities": shared_entities,
+                "provenance": {"file": "sys.tenor", "line": 1},
+            }],
+        })
+    }
+
+    fn evaluator(shared: bool) -> SystemEvaluator {
+        let members = BTreeMap::from([
+            ("a".to_string(), member("submit", "draft", "submitted")),
+            ("b".to_string(), member("approve", "submitted", "approved")),
+        ]);
+        SystemEvaluator::new(&system_bundle(shared), &members).unwrap()
+    }
+
+    fn order() -> (Symbol, Symbol) {
+        operation::instance_key("Order", DEFAULT_INSTANCE_ID)
+    }
+
+    #[test]
+    fn trigger_runs_target_flow_on_shared_entity_state() {
+        let system = evaluator(true);
+        let mut states = system.initial_entity_states();
+        assert_eq!(states.shared[&order()], "draft");
+
+        let result = system
+            .execute_flow("a", "main", "clerk", &json!({"ready": true}), &mut states)
+            .unwrap();
+
+        assert_eq!(result.executions.len(), 2);
+        let triggered = &result.executions[1];
+        assert_eq!(triggered.contract_id, "b");
+        assert_eq!(triggered.flow_result.outcome, "done");
+        let provenance = triggered.triggered_by.as_ref().unwrap();
+        assert_eq!(provenance.source_contract, "a");
+        assert_eq!(provenance.on, "done");
+        assert_eq!(provenance.shared_effects.len(), 1);
+        assert_eq!(provenance.shared_effects[0].to_state, "submitted");
+        assert_eq!(states.shared[&order()], "approved");
+    }
+
+    #[test]
+    fn unshared_entities_stay_private_to_each_member() {
+        let system = evaluator(false);
+        let mut states = system.initial_entity_states();
+
+        let result = system
+            .execute_flow("a", "main", "clerk", &json!({"ready": true}), &mut states)
+            .unwrap();
+
+        // b's Order is still in draft, so its approve operation fails.
+        assert_eq!(result.executions.len(), 2);
+        assert_eq!(result.executions[1].flow_result.outcome, "failed");
+        assert_eq!(states.members["a"][&order()], "submitted");
+        assert_eq!(states.members["b"][&order()], "draft");
+        assert!(states.shared.is_empty());
+    }
+
+    #[test]
+    fn qualified_facts_override_per_member() {
+        let system = evaluator(true);
+        let mut states = system.initial_entity_states();
+
+        let result = system
+            .execute_flow(
+                "a",
+                "main",
+                "clerk",
+                &json!({"ready": true, "b.ready": false}),
+                &mut states,
+            )
+            .unwrap();
+
+        assert_eq!(result.executions[1].flow_result.outcome, "failed");
+        assert_eq!(states.shared[&order()], "submitted");
+    }
+
+    #[test]
+    fn missing_member_bundle_is_rejected() {
+        let members = BTreeMap::from([("a".to_string(), member("submit", "draft", "submitted"))]);
+        assert!(SystemEvaluator::new(&system_bundle(true), &members).is_err());
+    }
+}
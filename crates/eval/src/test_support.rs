@@ -0,0 +1,15 @@
+//! Shared fixtures for unit tests in this crate and its dependents.
+//!
+//! Compiled for this crate's own tests and, in other crates, behind the
+//! `test-support` feature (enabled from their `[dev-dependencies]`).
+
+use crate::types::Contract;
+
+/// The `flow_basic` conformance contract, parsed for evaluation.
+pub fn flow_basic() -> Contract {
+    let bundle: serde_json::Value = serde_json::from_str(include_str!(
+        "../../../conformance/positive/flow_basic.expected.json"
+    ))
+    .expect("flow_basic.expected.json is valid JSON");
+    Contract::from_interchange(&bundle).expect("flow_basic.expected.json is a valid bundle")
+}
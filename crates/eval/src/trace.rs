@@ -0,0 +1,280 @@
+//! Opt-in rule evaluation traces.
+//!
+//! [`eval_strata_traced`](crate::rules::eval_strata_traced) records, for
+//! every rule, the value each subexpression of its condition evaluated to:
+//! fact references with the fact's value, comparisons with their result,
+//! and so on up to the condition itself. Provenance only lists the facts a
+//! verdict used; a trace also shows the intermediate results, including
+//! for rules that did not fire.
+//!
+//! Tracing re-evaluates each condition over the rule's predicate tree after
+//! the regular evaluation, so it costs nothing unless requested.
+
+use crate::predicate::{eval_pred, EvalContext};
+use crate::provenance::ProvenanceCollector;
+use crate::types::{EvalError, FactSet, Predicate, Rule, Value, VerdictSet};
+
+/// The trace of one rule's condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTrace {
+    pub rule_id: String,
+    pub stratum: u32,
+    pub verdict_type: String,
+    /// Whether the condition held and the verdict was produced.
+    pub fired: bool,
+    pub condition: PredicateTrace,
+}
+
+/// A predicate subexpression and the value it evaluated to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredicateTrace {
+    /// The subexpression in contract syntax.
+    pub expression: String,
+    /// `None` when short-circuiting skipped the subexpression.
+    pub value: Option<Value>,
+    /// Traces of the operands that were evaluated. Literal operands are
+    /// omitted; their value is in the expression.
+    pub children: Vec<PredicateTrace>,
+}
+
+impl RuleTrace {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "rule": self.rule_id,
+            "stratum": self.stratum,
+            "verdict": self.verdict_type,
+            "fired": self.fired,
+            "condition": self.condition.to_json(),
+        })
+    }
+
+    /// Render the trace as an indented tree, one subexpression per line.
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "rule {} (stratum {}): {} [{}]\n",
+            self.rule_id,
+            self.stratum,
+            if self.fired { "fired" } else { "not fired" },
+            self.verdict_type
+        );
+        self.condition.render_into(1, &mut out);
+        out
+    }
+}
+
+impl PredicateTrace {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "expression": self.expression,
+            "value": self.value.as_ref().map(Value::to_json),
+        });
+        if !self.children.is_empty() {
+            json["children"] = self.children.iter().map(|c| c.to_json()).collect();
+        }
+        json
+    }
+
+    fn render_into(&self, depth: usize, out: &mut String) {
+        let value = match &self.value {
+            Some(value) => display_value(value),
+            None => "(not evaluated)".to_string(),
+        };
+        out.push_str(&format!(
+            "{}{} => {}\n",
+            "  ".repeat(depth),
+            self.expression,
+            value
+        ));
+        for child in &self.children {
+            child.render_into(depth + 1, out);
+        }
+    }
+}
+
+/// Trace `rule`'s condition against `facts` and the verdicts of lower strata.
+pub(crate) fn trace_rule(
+    rule: &Rule,
+    facts: &FactSet,
+    verdicts: &VerdictSet,
+) -> Result<RuleTrace, EvalError> {
+    let condition = trace_pred(&rule.condition, facts, verdicts, &EvalContext::new())?;
+    Ok(RuleTrace {
+        rule_id: rule.id.clone(),
+        stratum: rule.stratum,
+        verdict_type: rule.produce.verdict_type.clone(),
+        fired: condition.value == Some(Value::Bool(true)),
+        condition,
+    })
+}
+
+fn trace_pred(
+    pred: &Predicate,
+    facts: &FactSet,
+    verdicts: &VerdictSet,
+    ctx: &EvalContext,
+) -> Result<PredicateTrace, EvalError> {
+    let value = eval_pred(pred, facts, verdicts, ctx, &mut ProvenanceCollector::new())?;
+    let trace = |p: &Predicate| trace_pred(p, facts, verdicts, ctx);
+    let mut children = Vec::new();
+
+    match pred {
+        Predicate::Compare { left, right, .. } => {
+            for operand in [left, right] {
+                if !matches!(operand.as_ref(), Predicate::Literal { .. }) {
+                    children.push(trace(operand)?);
+                }
+            }
+        }
+        Predicate::And { left, right } | Predicate::Or { left, right } => {
+            // Mirror eval_pred's short-circuit: `∧` stops on false, `∨` on true.
+            let stop = matches!(pred, Predicate::Or { .. });
+            let left = trace(left)?;
+            let short_circuits = left.value == Some(Value::Bool(stop));
+            children.push(left);
+            children.push(if short_circuits {
+                skipped(right)
+            } else {
+                trace(right)?
+            });
+        }
+        Predicate::Not { operand } => children.push(trace(operand)?),
+        Predicate::Mul { left, .. } => children.push(trace(left)?),
+        Predicate::Convert { amount, rate_fact } => {
+            children.push(trace(amount)?);
+            children.push(PredicateTrace {
+                expression: rate_fact.clone(),
+                value: facts.get(rate_fact).cloned(),
+                children: Vec::new(),
+            });
+        }
+        Predicate::Forall {
+            variable,
+            domain,
+            body,
+            ..
+        }
+        | Predicate::Exists {
+            variable,
+            domain,
+            body,
+            ..
+        } if !matches!(domain.as_ref(), Predicate::InstancesOf(_)) => {
+            // Forall stops at the first false element, exists at the first true.
+            let stop = matches!(pred, Predicate::Exists { .. });
+            let domain = trace(domain)?;
+            let elements = match &domain.value {
+                Some(Value::List(items)) => items.clone(),
+                _ => Vec::new(),
+            };
+            children.push(domain);
+            for element in elements {
+                let mut inner = ctx.clone();
+                inner.bindings.insert(variable.clone(), element.clone());
+                let mut body = trace_pred(body, facts, verdicts, &inner)?;
+                body.expression = format!(
+                    "{} = {}: {}",
+                    variable,
+                    display_value(&element),
+                    body.expression
+                );
+                let done = body.value == Some(Value::Bool(stop));
+                children.push(body);
+                if done {
+                    break;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(PredicateTrace {
+        expression: render(pred),
+        value: Some(value),
+        children,
+    })
+}
+
+fn skipped(pred: &Predicate) -> PredicateTrace {
+    PredicateTrace {
+        expression: render(pred),
+        value: None,
+        children: Vec::new(),
+    }
+}
+
+/// Render a predicate in contract syntax.
+fn render(pred: &Predicate) -> String {
+    match pred {
+        Predicate::FactRef(id) => id.clone(),
+        Predicate::FieldRef { var, field } => format!("{}.{}", var, field),
+        Predicate::Literal { value, .. } => display_value(value),
+        Predicate::VerdictPresent(id) => format!("verdict_present({})", id),
+        Predicate::Compare {
+            left, op, right, ..
+        } => format!("{} {} {}", render(left), op, render(right)),
+        Predicate::And { left, right } => format!(
+            "{} ∧ {}",
+            render_operand(left, pred, false),
+            render_operand(right, pred, true)
+        ),
+        Predicate::Or { left, right } => format!(
+            "{} ∨ {}",
+            render_operand(left, pred, false),
+            render_operand(right, pred, true)
+        ),
+        Predicate::Not { operand } => format!("¬{}", render_operand(operand, pred, false)),
+        Predicate::Forall {
+            variable,
+            domain,
+            body,
+            ..
+        } => format!("∀ {} ∈ {} . {}", variable, render(domain), render(body)),
+        Predicate::Exists {
+            variable,
+            domain,
+            body,
+            ..
+        } => format!("∃ {} ∈ {} . {}", variable, render(domain), render(body)),
+        Predicate::InstancesOf(entity) => format!("instances({})", entity),
+        Predicate::InstanceState(var) => format!("state({})", var),
+        Predicate::Mul { left, literal, .. } => format!("{} * {}", render(left), literal),
+        Predicate::Convert { amount, rate_fact } => {
+            format!("convert({}, {})", render(amount), rate_fact)
+        }
+    }
+}
+
+/// Render `pred` as an operand of `parent`, parenthesized where precedence
+/// (`¬` > `∧` > `∨`, left-associative) requires it.
+fn render_operand(pred: &Predicate, parent: &Predicate, is_right: bool) -> String {
+    let needs_parens = match (pred, parent) {
+        (Predicate::Forall { .. } | Predicate::Exists { .. }, _) => true,
+        (
+            Predicate::And { .. } | Predicate::Or { .. } | Predicate::Compare { .. },
+            Predicate::Not { .. },
+        ) => true,
+        (Predicate::Or { .. }, Predicate::And { .. }) => true,
+        (Predicate::And { .. }, Predicate::And { .. })
+        | (Predicate::Or { .. }, Predicate::Or { .. }) => is_right,
+        _ => false,
+    };
+    if needs_parens {
+        format!("({})", render(pred))
+    } else {
+        render(pred)
+    }
+}
+
+/// A value as it would be written in a contract or fact file.
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::Decimal(d) => d.to_string(),
+        Value::Text(t) => format!("\"{}\"", t),
+        Value::Date(d) | Value::DateTime(d) | Value::Enum(d) => d.clone(),
+        Value::Money { amount, currency } => format!("{} {}", amount, currency),
+        Value::Duration { value, unit } => format!("{} {}", value, unit),
+        other => other.to_json().to_string(),
+    }
+}
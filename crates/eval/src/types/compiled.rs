@@ -0,0 +1,457 @@
+//! Compiled rule representation.
+//!
+//! [`CompiledRules`] is built once per [`Contract`](super::Contract). It
+//! resolves every fact and verdict reference in rule conditions and payloads
+//! to an integer slot, and every quantifier-bound variable to a position on
+//! the binding stack. Rule evaluation then reads facts from a dense
+//! [`FactSlots`] array and tracks verdict presence in a `Vec<bool>` instead
+//! of looking up string keys on every reference.
+//!
+//! Operation preconditions and flow branch conditions are evaluated once per
+//! step against an externally supplied FactSet, so they keep using the
+//! [`Predicate`] tree interpreter.
+
+use std::collections::HashMap;
+
+use super::contract::{PayloadValue, Rule};
+use super::fact::{FactDecl, FactSet};
+use super::values::Value;
+use super::{Predicate, TypeSpec};
+use crate::symbol::Symbol;
+
+/// Index into [`CompiledRules::fact_names`] and [`FactSlots`].
+pub type FactSlot = usize;
+
+/// Index into [`CompiledRules::verdict_names`].
+pub type VerdictSlot = usize;
+
+/// A predicate with all references resolved to slots.
+#[derive(Debug, Clone)]
+pub enum CompiledPredicate {
+    Compare {
+        left: Box<CompiledPredicate>,
+        op: String,
+        right: Box<CompiledPredicate>,
+        comparison_type: Option<TypeSpec>,
+    },
+    And {
+        left: Box<CompiledPredicate>,
+        right: Box<CompiledPredicate>,
+    },
+    Or {
+        left: Box<CompiledPredicate>,
+        right: Box<CompiledPredicate>,
+    },
+    Not {
+        operand: Box<CompiledPredicate>,
+    },
+    /// Value of a fact.
+    Fact(FactSlot),
+    /// Field of a Record-typed fact (a field_ref whose var is not bound by
+    /// an enclosing quantifier).
+    FactField {
+        slot: FactSlot,
+        field: String,
+    },
+    /// Field of a quantifier-bound variable. `level` is the variable's
+    /// position on the binding stack (0 = outermost quantifier).
+    BoundField {
+        level: usize,
+        var: String,
+        field: String,
+    },
+    Literal(Value),
+    VerdictPresent(VerdictSlot),
+    Forall {
+        domain: Box<CompiledPredicate>,
+        body: Box<CompiledPredicate>,
+    },
+    Exists {
+        domain: Box<CompiledPredicate>,
+        body: Box<CompiledPredicate>,
+    },
+    Mul {
+        left: Box<CompiledPredicate>,
+        literal: i64,
+        result_type: TypeSpec,
+    },
+    Convert {
+        amount: Box<CompiledPredicate>,
+        rate: FactSlot,
+    },
+    /// An `instances_of` domain or `instance_state` term. Rules are
+    /// evaluated without entity state, so evaluating this is a type error.
+    EntityState(String),
+}
+
+/// A compiled verdict payload.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum CompiledPayload {
+    Literal(Value),
+    Computed(CompiledPredicate),
+}
+
+/// A rule with its condition and payload compiled.
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    pub condition: CompiledPredicate,
+    pub payload: CompiledPayload,
+    /// Slot of the verdict type this rule produces.
+    pub verdict: VerdictSlot,
+}
+
+/// Compiled rules of a contract plus the slot tables they index into.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledRules {
+    /// Fact id per slot: declared facts first (in declaration order), then
+    /// any undeclared fact ids referenced by rules.
+    pub fact_names: Vec<String>,
+    /// Verdict type per slot, in first-reference order.
+    pub verdict_names: Vec<Symbol>,
+    /// One entry per `Contract::rules`, at the same index.
+    pub rules: Vec<CompiledRule>,
+    /// Indices into `rules` in evaluation order: ascending stratum,
+    /// declaration order within a stratum.
+    pub order: Vec<usize>,
+}
+
+/// Fact values laid out by [`FactSlot`]; `None` where the FactSet has no
+/// value for the slot's fact.
+#[derive(Debug, Clone)]
+pub struct FactSlots<'a>(pub Vec<Option<&'a Value>>);
+
+impl<'a> FactSlots<'a> {
+    pub fn get(&self, slot: FactSlot) -> Option<&'a Value> {
+        self.0.get(slot).copied().flatten()
+    }
+}
+
+impl CompiledRules {
+    /// Compile `rules`, allocating fact slots for `facts` first.
+    pub fn compile(facts: &[FactDecl], rules: &[Rule]) -> CompiledRules {
+        let mut compiler = Compiler::default();
+        for fact in facts {
+            compiler.fact(&fact.id);
+        }
+
+        let compiled = rules
+            .iter()
+            .map(|rule| {
+                let condition = compiler.predicate(&rule.condition);
+                let payload = match &rule.produce.payload_value {
+                    PayloadValue::Literal(v) => CompiledPayload::Literal(v.clone()),
+                    PayloadValue::Mul(mul) => CompiledPayload::Computed(CompiledPredicate::Mul {
+                        left: Box::new(CompiledPredicate::Fact(compiler.fact(&mul.fact_ref))),
+                        literal: mul.literal,
+                        result_type: mul.result_type.clone(),
+                    }),
+                };
+                let verdict = compiler.verdict(&rule.produce.verdict_type);
+                CompiledRule {
+                    condition,
+                    payload,
+                    verdict,
+                }
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..rules.len()).collect();
+        order.sort_by_key(|&i| rules[i].stratum);
+
+        CompiledRules {
+            fact_names: compiler.fact_names,
+            verdict_names: compiler.verdict_names.iter().map(Symbol::from).collect(),
+            rules: compiled,
+            order,
+        }
+    }
+
+    /// Lay out `facts` by slot. Each fact id is looked up once here rather
+    /// than once per reference during evaluation.
+    pub fn fact_slots<'a>(&self, facts: &'a FactSet) -> FactSlots<'a> {
+        FactSlots(self.fact_names.iter().map(|id| facts.get(id)).collect())
+    }
+}
+
+#[derive(Default)]
+struct Compiler<'r> {
+    fact_index: HashMap<String, FactSlot>,
+    fact_names: Vec<String>,
+    verdict_index: HashMap<String, VerdictSlot>,
+    verdict_names: Vec<String>,
+    /// Variables bound by enclosing quantifiers, outermost first.
+    scope: Vec<&'r str>,
+}
+
+impl<'r> Compiler<'r> {
+    fn fact(&mut self, id: &str) -> FactSlot {
+        intern(&mut self.fact_index, &mut self.fact_names, id)
+    }
+
+    fn verdict(&mut self, verdict_type: &str) -> VerdictSlot {
+        intern(
+            &mut self.verdict_index,
+            &mut self.verdict_names,
+            verdict_type,
+        )
+    }
+
+    fn boxed(&mut self, pred: &'r Predicate) -> Box<CompiledPredicate> {
+        Box::new(self.predicate(pred))
+    }
+
+    fn predicate(&mut self, pred: &'r Predicate) -> CompiledPredicate {
+        match pred {
+            Predicate::Compare {
+                left,
+                op,
+                right,
+                comparison_type,
+            } => CompiledPredicate::Compare {
+                left: self.boxed(left),
+                op: op.clone(),
+                right: self.boxed(right),
+                comparison_type: comparison_type.clone(),
+            },
+            Predicate::And { left, right } => CompiledPredicate::And {
+                left: self.boxed(left),
+                right: self.boxed(right),
+            },
+            Predicate::Or { left, right } => CompiledPredicate::Or {
+                left: self.boxed(left),
+                right: self.boxed(right),
+            },
+            Predicate::Not { operand } => CompiledPredicate::Not {
+                operand: self.boxed(operand),
+            },
+            Predicate::FactRef(id) => CompiledPredicate::Fact(self.fact(id)),
+            Predicate::FieldRef { var, field } => {
+                // Innermost binding wins, matching the interpreter's scoping.
+                match self.scope.iter().rposition(|bound| *bound == var) {
+                    Some(level) => CompiledPredicate::BoundField {
+                        level,
+                        var: var.clone(),
+                        field: field.clone(),
+                    },
+                    None => CompiledPredicate::FactField {
+                        slot: self.fact(var),
+                        field: field.clone(),
+                    },
+                }
+            }
+            Predicate::Literal { value, .. } => CompiledPredicate::Literal(value.clone()),
+            Predicate::VerdictPresent(id) => CompiledPredicate::VerdictPresent(self.verdict(id)),
+            Predicate::Forall {
+                variable,
+                domain,
+                body,
+                ..
+            } => {
+                let (domain, body) = self.quantifier(variable, domain, body);
+                CompiledPredicate::Forall { domain, body }
+            }
+            Predicate::Exists {
+                variable,
+                domain,
+                body,
+                ..
+            } => {
+                let (domain, body) = self.quantifier(variable, domain, body);
+                CompiledPredicate::Exists { domain, body }
+            }
+            Predicate::Mul {
+                left,
+                literal,
+                result_type,
+            } => CompiledPredicate::Mul {
+                left: self.boxed(left),
+                literal: *literal,
+                result_type: result_type.clone(),
+            },
+            Predicate::Convert { amount, rate_fact } => CompiledPredicate::Convert {
+                amount: self.boxed(amount),
+                rate: self.fact(rate_fact),
+            },
+            Predicate::InstancesOf(entity) => {
+                CompiledPredicate::EntityState(format!("instances_of '{}'", entity))
+            }
+            Predicate::InstanceState(var) => {
+                CompiledPredicate::EntityState(format!("instance_state '{}'", var))
+            }
+        }
+    }
+
+    fn quantifier(
+        &mut self,
+        variable: &'r str,
+        domain: &'r Predicate,
+        body: &'r Predicate,
+    ) -> (Box<CompiledPredicate>, Box<CompiledPredicate>) {
+        // The domain is evaluated outside the variable's scope.
+        let domain = self.boxed(domain);
+        self.scope.push(variable);
+        let body = self.boxed(body);
+        self.scope.pop();
+        (domain, body)
+    }
+}
+
+fn intern(index: &mut HashMap<String, usize>, names: &mut Vec<String>, id: &str) -> usize {
+    if let Some(&slot) = index.get(id) {
+        return slot;
+    }
+    let slot = names.len();
+    names.push(id.to_string());
+    index.insert(id.to_string(), slot);
+    slot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ProduceClause, TypeSpec};
+
+    fn bool_type() -> TypeSpec {
+        TypeSpec {
+            base: "Bool".to_string(),
+            precision: None,
+            scale: None,
+            rounding: None,
+            currency: None,
+            min: None,
+            max: None,
+            max_length: None,
+            values: None,
+            fields: None,
+            element_type: None,
+            unit: None,
+            variants: None,
+        }
+    }
+
+    fn rule(id: &str, stratum: u32, condition: Predicate, verdict_type: &str) -> Rule {
+        Rule {
+            id: id.to_string(),
+            stratum,
+            condition,
+            produce: ProduceClause {
+                verdict_type: verdict_type.to_string(),
+                payload_type: bool_type(),
+                payload_value: PayloadValue::Literal(Value::Bool(true)),
+            },
+        }
+    }
+
+    #[test]
+    fn declared_facts_take_the_first_slots() {
+        let decl = FactDecl {
+            id: "declared".to_string(),
+            fact_type: bool_type(),
+            default: None,
+        };
+        let rules = vec![rule(
+            "r",
+            0,
+            Predicate::And {
+                left: Box::new(Predicate::FactRef("undeclared".to_string())),
+                right: Box::new(Predicate::FactRef("declared".to_string())),
+            },
+            "v",
+        )];
+        let compiled = CompiledRules::compile(&[decl], &rules);
+        assert_eq!(compiled.fact_names, vec!["declared", "undeclared"]);
+        match &compiled.rules[0].condition {
+            CompiledPredicate::And { left, right } => {
+                assert!(matches!(**left, CompiledPredicate::Fact(1)));
+                assert!(matches!(**right, CompiledPredicate::Fact(0)));
+            }
+            other => panic!("expected And, got {:?}", other),
+        }
+
+        let mut facts = FactSet::new();
+        facts.insert("undeclared".to_string(), Value::Bool(true));
+        let slots = compiled.fact_slots(&facts);
+        assert_eq!(slots.get(0), None);
+        assert_eq!(slots.get(1), Some(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn quantified_field_refs_resolve_to_binding_levels() {
+        // forall a in outer . exists b in a_list . b.x = a.x, plus a free
+        // field_ref on a record fact named like neither variable.
+        let field = |var: &str| Predicate::FieldRef {
+            var: var.to_string(),
+            field: "x".to_string(),
+        };
+        let inner = Predicate::Exists {
+            variable: "b".to_string(),
+            variable_type: bool_type(),
+            domain: Box::new(Predicate::FactRef("b_list".to_string())),
+            body: Box::new(Predicate::Compare {
+                left: Box::new(field("b")),
+                op: "=".to_string(),
+                right: Box::new(field("a")),
+                comparison_type: None,
+            }),
+        };
+        let pred = Predicate::And {
+            left: Box::new(Predicate::Forall {
+                variable: "a".to_string(),
+                variable_type: bool_type(),
+                domain: Box::new(Predicate::FactRef("a_list".to_string())),
+                body: Box::new(inner),
+            }),
+            right: Box::new(field("a")),
+        };
+        let compiled = CompiledRules::compile(&[], &[rule("r", 0, pred, "v")]);
+
+        let CompiledPredicate::And { left, right } = &compiled.rules[0].condition else {
+            panic!("expected And");
+        };
+        // Outside the quantifier, `a` is a fact.
+        assert!(matches!(
+            **right,
+            CompiledPredicate::FactField { slot, .. } if compiled.fact_names[slot] == "a"
+        ));
+        let CompiledPredicate::Forall { body, .. } = &**left else {
+            panic!("expected Forall");
+        };
+        let CompiledPredicate::Exists { body, .. } = &**body else {
+            panic!("expected Exists");
+        };
+        let CompiledPredicate::Compare { left, right, .. } = &**body else {
+            panic!("expected Compare");
+        };
+        assert!(matches!(
+            **left,
+            CompiledPredicate::BoundField { level: 1, .. }
+        ));
+        assert!(matches!(
+            **right,
+            CompiledPredicate::BoundField { level: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn rules_are_ordered_by_stratum_then_declaration() {
+        let t = || Predicate::Literal {
+            value: Value::Bool(true),
+            type_spec: bool_type(),
+        };
+        let rules = vec![
+            rule(
+                "late",
+                1,
+                Predicate::VerdictPresent("early_v".to_string()),
+                "late_v",
+            ),
+            rule("early", 0, t(), "early_v"),
+            rule("also_early", 0, t(), "early_v"),
+        ];
+        let compiled = CompiledRules::compile(&[], &rules);
+        assert_eq!(compiled.order, vec![1, 2, 0]);
+        assert_eq!(compiled.verdict_names, vec!["early_v", "late_v"]);
+        assert_eq!(compiled.rules[1].verdict, compiled.rules[2].verdict);
+    }
+}
@@ -2,8 +2,13 @@
 
 use std::collections::{BTreeMap, HashMap};
 
+use tenor_core::units::{unit_of, Unit};
+
+use super::compiled::CompiledRules;
 use super::fact::FactDecl;
-use super::values::{get_str, infer_literal, parse_default_value, parse_literal_value, Value};
+use super::values::{
+    get_str, infer_literal, parse_default_value, parse_literal_value, parse_plain_value, Value,
+};
 use super::{EvalError, Predicate, TypeSpec};
 
 // ──────────────────────────────────────────────
@@ -24,10 +29,33 @@ pub struct Contract {
     pub flow_index: HashMap<String, usize>,
     pub entity_index: HashMap<String, usize>,
     pub fact_index: HashMap<String, usize>,
+    /// Rules with fact/verdict references resolved to slots; used by
+    /// stratified evaluation.
+    pub compiled_rules: CompiledRules,
+    /// Check every produced payload against its rule's declared payload
+    /// type during stratified evaluation. Off by default.
+    pub validate_payloads: bool,
+}
+
+/// Reject a bundle whose `requires` list names features outside `supported`.
+pub(crate) fn check_requires(requires: &[String], supported: &[&str]) -> Result<(), EvalError> {
+    let unsupported: Vec<String> = requires
+        .iter()
+        .filter(|f| !supported.contains(&f.as_str()))
+        .cloned()
+        .collect();
+    if unsupported.is_empty() {
+        Ok(())
+    } else {
+        Err(EvalError::UnsupportedFeatures {
+            features: unsupported,
+        })
+    }
 }
 
 impl Contract {
-    /// Construct a Contract from its component Vecs, automatically building indexes.
+    /// Construct a Contract from its component Vecs, automatically building
+    /// indexes and compiling rules.
     pub fn new(
         facts: Vec<FactDecl>,
         entities: Vec<Entity>,
@@ -56,6 +84,7 @@ impl Contract {
             .enumerate()
             .map(|(i, f)| (f.id.clone(), i))
             .collect();
+        let compiled_rules = CompiledRules::compile(&facts, &rules);
         Contract {
             facts,
             entities,
@@ -67,9 +96,33 @@ impl Contract {
             flow_index,
             entity_index,
             fact_index,
+            compiled_rules,
+            validate_payloads: false,
         }
     }
 
+    /// Enable or disable payload validation; see
+    /// [`Contract::validate_payloads`]. A mismatch fails evaluation with
+    /// [`EvalError::PayloadMismatch`].
+    pub fn with_payload_validation(mut self, enabled: bool) -> Self {
+        self.validate_payloads = enabled;
+        self
+    }
+
+    /// The declared payload type of each verdict type, keyed by verdict
+    /// type. When several rules produce the same verdict type, the
+    /// declaration of the first rule in stratum order is reported.
+    pub fn verdict_schema(&self) -> BTreeMap<&str, &TypeSpec> {
+        let mut schema = BTreeMap::new();
+        for &index in &self.compiled_rules.order {
+            let produce = &self.rules[index].produce;
+            schema
+                .entry(produce.verdict_type.as_str())
+                .or_insert(&produce.payload_type);
+        }
+        schema
+    }
+
     /// Look up an operation by ID in O(1) via the index.
     pub fn get_operation(&self, id: &str) -> Option<&Operation> {
         self.operation_index.get(id).map(|&i| &self.operations[i])
@@ -98,13 +151,40 @@ impl Contract {
     /// and kind dispatch, then converts shared types to eval-specific domain
     /// types using deep parsers for predicates, flow steps, etc.
     pub fn from_interchange(bundle: &serde_json::Value) -> Result<Contract, EvalError> {
-        use tenor_interchange::InterchangeConstruct;
-
         let parsed = tenor_interchange::from_interchange(bundle).map_err(|e| {
             EvalError::DeserializeError {
                 message: e.to_string(),
             }
         })?;
+        Contract::from_parsed(parsed)
+    }
+
+    /// Deserialize a Contract in strict mode: unknown construct kinds and
+    /// unknown fields are rejected instead of ignored.
+    ///
+    /// Violations are reported as [`EvalError::StrictViolation`] carrying
+    /// the JSON pointer of the offending field.
+    pub fn from_interchange_strict(bundle: &serde_json::Value) -> Result<Contract, EvalError> {
+        use tenor_interchange::InterchangeError;
+
+        let parsed = tenor_interchange::from_interchange_strict(bundle).map_err(|e| match e {
+            InterchangeError::UnknownField { ref pointer }
+            | InterchangeError::UnknownKind { ref pointer, .. } => EvalError::StrictViolation {
+                pointer: pointer.clone(),
+                message: e.to_string(),
+            },
+            _ => EvalError::DeserializeError {
+                message: e.to_string(),
+            },
+        })?;
+        Contract::from_parsed(parsed)
+    }
+
+    fn from_parsed(parsed: tenor_interchange::InterchangeBundle) -> Result<Contract, EvalError> {
+        use tenor_interchange::InterchangeConstruct;
+
+        // Refuse bundles that rely on features this evaluator cannot execute.
+        check_requires(&parsed.requires, crate::SUPPORTED_FEATURES)?;
 
         let mut facts = Vec::new();
         let mut entities = Vec::new();
@@ -133,14 +213,22 @@ impl Contract {
                         id: e.id.clone(),
                         states: e.states.clone(),
                         initial: e.initial.clone(),
+                        attributes: e
+                            .attributes
+                            .iter()
+                            .map(|(name, t)| Ok((name.clone(), TypeSpec::from_json(t)?)))
+                            .collect::<Result<BTreeMap<_, _>, EvalError>>()?,
                         transitions: e
                             .transitions
                             .iter()
-                            .map(|t| Transition {
-                                from: t.from.clone(),
-                                to: t.to.clone(),
+                            .map(|t| {
+                                Ok(Transition {
+                                    from: t.from.clone(),
+                                    to: t.to.clone(),
+                                    guard: t.guard.as_ref().map(parse_predicate).transpose()?,
+                                })
                             })
-                            .collect(),
+                            .collect::<Result<Vec<_>, EvalError>>()?,
                     });
                 }
                 InterchangeConstruct::Rule(r) => {
@@ -170,6 +258,7 @@ impl Contract {
                                 base: "Bool".to_string(),
                                 precision: None,
                                 scale: None,
+                                rounding: None,
                                 currency: None,
                                 min: None,
                                 max: None,
@@ -185,13 +274,35 @@ impl Contract {
                     let effects: Vec<Effect> = op
                         .effects
                         .iter()
-                        .map(|e| Effect {
-                            entity_id: e.entity_id.clone(),
-                            from: e.from.clone(),
-                            to: e.to.clone(),
-                            outcome: e.outcome.clone(),
+                        .map(|e| {
+                            let kind = match e.kind.as_deref() {
+                                None | Some("transition") => EffectKind::Transition,
+                                Some("create_instance") => EffectKind::CreateInstance,
+                                Some("destroy_instance") => EffectKind::DestroyInstance,
+                                Some(other) => {
+                                    return Err(EvalError::DeserializeError {
+                                        message: format!(
+                                            "Operation '{}' has unknown effect kind '{}'",
+                                            op.id, other
+                                        ),
+                                    })
+                                }
+                            };
+                            Ok(Effect {
+                                entity_id: e.entity_id.clone(),
+                                kind,
+                                from: e.from.clone(),
+                                to: e.to.clone(),
+                                outcome: e.outcome.clone(),
+                                guard: None,
+                                set: e
+                                    .set
+                                    .iter()
+                                    .map(|(name, term)| Ok((name.clone(), parse_predicate(term)?)))
+                                    .collect::<Result<BTreeMap<_, _>, EvalError>>()?,
+                            })
                         })
-                        .collect();
+                        .collect::<Result<Vec<_>, EvalError>>()?;
                     let error_contract: Vec<String> = op
                         .error_contract
                         .as_ref()
@@ -229,12 +340,18 @@ impl Contract {
                 }
                 InterchangeConstruct::Source(_)
                 | InterchangeConstruct::System(_)
+                | InterchangeConstruct::Test(_)
+                | InterchangeConstruct::Assert(_)
                 | InterchangeConstruct::TypeDecl(_) => {
-                    // Source, System, and TypeDecl constructs are not used in evaluation
+                    // Source, System, Test, Assert, and TypeDecl constructs are not used in evaluation
                 }
             }
         }
 
+        attach_transition_guards(&entities, &mut operations);
+        type_attribute_literals(&entities, &mut operations)?;
+        check_units(&facts, &rules, &operations)?;
+
         Ok(Contract::new(
             facts, entities, rules, operations, flows, personas,
         ))
@@ -252,12 +369,16 @@ pub struct Entity {
     pub states: Vec<String>,
     pub initial: String,
     pub transitions: Vec<Transition>,
+    /// Declared attribute types. Each instance carries its own values.
+    pub attributes: BTreeMap<String, TypeSpec>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Transition {
     pub from: String,
     pub to: String,
+    /// Must hold for an effect to take this transition.
+    pub guard: Option<Predicate>,
 }
 
 /// A verdict-producing rule with stratification.
@@ -304,12 +425,43 @@ pub struct Operation {
     pub outcomes: Vec<String>,
 }
 
+/// What an effect does to its entity instance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EffectKind {
+    /// Move an existing instance from `from` to `to`.
+    #[default]
+    Transition,
+    /// Spawn a new instance in the entity's initial state (`to`).
+    CreateInstance,
+    /// Retire an existing instance that is in state `from`.
+    DestroyInstance,
+}
+
+impl EffectKind {
+    /// The interchange `kind` value, as in `"create_instance"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EffectKind::Transition => "transition",
+            EffectKind::CreateInstance => "create_instance",
+            EffectKind::DestroyInstance => "destroy_instance",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Effect {
     pub entity_id: String,
+    pub kind: EffectKind,
+    /// Empty for `CreateInstance`.
     pub from: String,
+    /// Empty for `DestroyInstance`; the initial state for `CreateInstance`.
     pub to: String,
     pub outcome: Option<String>,
+    /// Guard of the entity transition this effect takes, copied from the
+    /// Entity when the contract is loaded.
+    pub guard: Option<Predicate>,
+    /// Attribute assignments, evaluated against the pre-effect state.
+    pub set: BTreeMap<String, Predicate>,
 }
 
 /// A flow (DAG of steps).
@@ -331,6 +483,7 @@ pub enum FlowStep {
         persona: String,
         outcomes: BTreeMap<String, StepTarget>,
         on_failure: FailureHandler,
+        timeout: Option<Timeout>,
     },
     BranchStep {
         id: String,
@@ -344,6 +497,9 @@ pub enum FlowStep {
         from_persona: String,
         to_persona: String,
         next: String,
+        /// Suspend here until `to_persona` resumes (see `flow::start_flow`).
+        pause: bool,
+        timeout: Option<Timeout>,
     },
     SubFlowStep {
         id: String,
@@ -380,6 +536,20 @@ pub enum FailureHandler {
     },
 }
 
+/// A step deadline. Once the DateTime fact `deadline` has passed, the step
+/// does not run and `on_timeout` fires instead.
+#[derive(Debug, Clone)]
+pub struct Timeout {
+    pub deadline: String,
+    pub on_timeout: TimeoutAction,
+}
+
+#[derive(Debug, Clone)]
+pub enum TimeoutAction {
+    Goto(StepTarget),
+    Handle(FailureHandler),
+}
+
 #[derive(Debug, Clone)]
 pub struct CompStep {
     pub op: String,
@@ -405,6 +575,149 @@ pub struct JoinPolicy {
 // Interchange JSON parsing helpers
 // ──────────────────────────────────────────────
 
+/// Copy each guarded transition's predicate onto the effects that take it,
+/// so operation execution can check guards without the Entity in hand.
+/// Create effects get the entity's initial state as their target.
+fn attach_transition_guards(entities: &[Entity], operations: &mut [Operation]) {
+    for op in operations.iter_mut() {
+        for effect in &mut op.effects {
+            if effect.kind == EffectKind::CreateInstance {
+                if let Some(entity) = entities.iter().find(|e| e.id == effect.entity_id) {
+                    effect.to = entity.initial.clone();
+                }
+                continue;
+            }
+            effect.guard = entities
+                .iter()
+                .find(|e| e.id == effect.entity_id)
+                .and_then(|e| {
+                    e.transitions
+                        .iter()
+                        .find(|t| t.from == effect.from && t.to == effect.to)
+                })
+                .and_then(|t| t.guard.clone());
+        }
+    }
+}
+
+/// Reject comparisons between quantities of different units of measure.
+///
+/// The elaborator already rejects these in Pass 4; the check is repeated
+/// here so hand-written or transformed bundles cannot compare kilograms
+/// with pounds.
+fn check_units(
+    facts: &[FactDecl],
+    rules: &[Rule],
+    operations: &[Operation],
+) -> Result<(), EvalError> {
+    let fact_types: HashMap<&str, &TypeSpec> = facts
+        .iter()
+        .map(|f| (f.id.as_str(), &f.fact_type))
+        .collect();
+    for rule in rules {
+        check_predicate_units(&rule.condition, &fact_types).map_err(|message| {
+            EvalError::TypeError {
+                message: format!("rule '{}': {}", rule.id, message),
+            }
+        })?;
+    }
+    for op in operations {
+        check_predicate_units(&op.precondition, &fact_types).map_err(|message| {
+            EvalError::TypeError {
+                message: format!("operation '{}': {}", op.id, message),
+            }
+        })?;
+    }
+    Ok(())
+}
+
+fn check_predicate_units(
+    pred: &Predicate,
+    fact_types: &HashMap<&str, &TypeSpec>,
+) -> Result<(), String> {
+    match pred {
+        Predicate::Compare { left, right, .. } => {
+            if let (Some(l), Some(r)) = (
+                predicate_unit(left, fact_types),
+                predicate_unit(right, fact_types),
+            ) {
+                if l != r {
+                    return Err(format!(
+                        "cannot compare a quantity in unit {} with a quantity in unit {}",
+                        l, r
+                    ));
+                }
+            }
+            Ok(())
+        }
+        Predicate::And { left, right } | Predicate::Or { left, right } => {
+            check_predicate_units(left, fact_types)?;
+            check_predicate_units(right, fact_types)
+        }
+        Predicate::Not { operand } => check_predicate_units(operand, fact_types),
+        Predicate::Forall { body, .. } | Predicate::Exists { body, .. } => {
+            check_predicate_units(body, fact_types)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The unit of a comparison operand, or `None` when it takes the unit of
+/// the other side (literals) or its unit is not known statically.
+fn predicate_unit(pred: &Predicate, fact_types: &HashMap<&str, &TypeSpec>) -> Option<Unit> {
+    match pred {
+        Predicate::FactRef(id) => match fact_types.get(id.as_str()) {
+            Some(t) if t.base == "Decimal" => Some(unit_of(t.unit.as_deref())),
+            Some(t) if t.base == "Int" => Some(Unit::dimensionless()),
+            _ => None,
+        },
+        Predicate::Mul { left, .. } => predicate_unit(left, fact_types),
+        Predicate::Convert { amount, rate_fact } => match fact_types.get(rate_fact.as_str()) {
+            Some(t) if t.base == "Decimal" => {
+                predicate_unit(amount, fact_types).map(|u| u.mul(&unit_of(t.unit.as_deref())))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Untyped string literals in `set` clauses parse as Text; give them the
+/// declared attribute type so Enum, Date and DateTime attributes hold the
+/// same values facts of those types would.
+fn type_attribute_literals(
+    entities: &[Entity],
+    operations: &mut [Operation],
+) -> Result<(), EvalError> {
+    for op in operations.iter_mut() {
+        for effect in &mut op.effects {
+            let Some(entity) = entities.iter().find(|e| e.id == effect.entity_id) else {
+                continue;
+            };
+            for (name, term) in effect.set.iter_mut() {
+                let Some(attr_type) = entity.attributes.get(name) else {
+                    continue;
+                };
+                if !matches!(attr_type.base.as_str(), "Enum" | "Date" | "DateTime") {
+                    continue;
+                }
+                if let Predicate::Literal {
+                    value: Value::Text(text),
+                    ..
+                } = term
+                {
+                    let typed = parse_plain_value(&serde_json::json!(text), attr_type)?;
+                    *term = Predicate::Literal {
+                        value: typed,
+                        type_spec: attr_type.clone(),
+                    };
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn parse_produce(v: &serde_json::Value) -> Result<ProduceClause, EvalError> {
     let verdict_type = get_str(v, "verdict_type")?;
     let payload = v
@@ -481,6 +794,33 @@ pub fn parse_predicate(v: &serde_json::Value) -> Result<Predicate, EvalError> {
         return Ok(Predicate::FactRef(id.to_string()));
     }
 
+    // Check for instances_of (instance quantifier domain)
+    if let Some(io) = v.get("instances_of") {
+        let id = io.as_str().ok_or_else(|| EvalError::DeserializeError {
+            message: "instances_of must be a string".to_string(),
+        })?;
+        return Ok(Predicate::InstancesOf(id.to_string()));
+    }
+
+    // Check for instance_state
+    if let Some(is) = v.get("instance_state") {
+        let var = is.as_str().ok_or_else(|| EvalError::DeserializeError {
+            message: "instance_state must be a string".to_string(),
+        })?;
+        return Ok(Predicate::InstanceState(var.to_string()));
+    }
+
+    // Check for convert
+    if let Some(amount) = v.get("convert") {
+        let rate = v.get("rate").ok_or_else(|| EvalError::DeserializeError {
+            message: "convert missing 'rate'".to_string(),
+        })?;
+        return Ok(Predicate::Convert {
+            amount: Box::new(parse_predicate(amount)?),
+            rate_fact: get_str(rate, "fact_ref")?,
+        });
+    }
+
     // Check for field_ref
     if let Some(fr) = v.get("field_ref") {
         let var = get_str(fr, "var")?;
@@ -606,16 +946,11 @@ pub fn parse_predicate(v: &serde_json::Value) -> Result<Predicate, EvalError> {
     // Check for forall (quantifier)
     if v.get("quantifier").and_then(|q| q.as_str()) == Some("forall") {
         let variable = get_str(v, "variable")?;
-        let vt = v
-            .get("variable_type")
-            .ok_or_else(|| EvalError::DeserializeError {
-                message: "forall missing 'variable_type'".to_string(),
-            })?;
-        let variable_type = TypeSpec::from_json(vt)?;
         let domain_val = v.get("domain").ok_or_else(|| EvalError::DeserializeError {
             message: "forall missing 'domain'".to_string(),
         })?;
         let domain = parse_predicate(domain_val)?;
+        let variable_type = parse_variable_type(v, &domain, "forall")?;
         let body_val = v.get("body").ok_or_else(|| EvalError::DeserializeError {
             message: "forall missing 'body'".to_string(),
         })?;
@@ -631,16 +966,11 @@ pub fn parse_predicate(v: &serde_json::Value) -> Result<Predicate, EvalError> {
     // Check for exists (quantifier)
     if v.get("quantifier").and_then(|q| q.as_str()) == Some("exists") {
         let variable = get_str(v, "variable")?;
-        let vt = v
-            .get("variable_type")
-            .ok_or_else(|| EvalError::DeserializeError {
-                message: "exists missing 'variable_type'".to_string(),
-            })?;
-        let variable_type = TypeSpec::from_json(vt)?;
         let domain_val = v.get("domain").ok_or_else(|| EvalError::DeserializeError {
             message: "exists missing 'domain'".to_string(),
         })?;
         let domain = parse_predicate(domain_val)?;
+        let variable_type = parse_variable_type(v, &domain, "exists")?;
         let body_val = v.get("body").ok_or_else(|| EvalError::DeserializeError {
             message: "exists missing 'body'".to_string(),
         })?;
@@ -658,6 +988,24 @@ pub fn parse_predicate(v: &serde_json::Value) -> Result<Predicate, EvalError> {
     })
 }
 
+/// A quantifier's `variable_type`. Instance quantifiers bind a Record of
+/// the instance's attributes and may omit it.
+fn parse_variable_type(
+    v: &serde_json::Value,
+    domain: &Predicate,
+    quantifier: &str,
+) -> Result<TypeSpec, EvalError> {
+    match (v.get("variable_type"), domain) {
+        (Some(vt), _) => TypeSpec::from_json(vt),
+        (None, Predicate::InstancesOf(_)) => {
+            TypeSpec::from_json(&serde_json::json!({"base": "Record"}))
+        }
+        (None, _) => Err(EvalError::DeserializeError {
+            message: format!("{} missing 'variable_type'", quantifier),
+        }),
+    }
+}
+
 fn parse_flow_step(v: &serde_json::Value) -> Result<FlowStep, EvalError> {
     let kind = get_str(v, "kind")?;
     match kind.as_str() {
@@ -686,6 +1034,7 @@ fn parse_flow_step(v: &serde_json::Value) -> Result<FlowStep, EvalError> {
                 persona,
                 outcomes,
                 on_failure,
+                timeout: parse_timeout(v)?,
             })
         }
         "BranchStep" => {
@@ -719,11 +1068,14 @@ fn parse_flow_step(v: &serde_json::Value) -> Result<FlowStep, EvalError> {
             let from_persona = get_str(v, "from_persona")?;
             let to_persona = get_str(v, "to_persona")?;
             let next = get_str(v, "next")?;
+            let pause = v.get("pause").and_then(|p| p.as_bool()).unwrap_or(false);
             Ok(FlowStep::HandoffStep {
                 id,
                 from_persona,
                 to_persona,
                 next,
+                pause,
+                timeout: parse_timeout(v)?,
             })
         }
         "SubFlowStep" => {
@@ -820,6 +1172,30 @@ fn parse_step_target(v: &serde_json::Value) -> Result<StepTarget, EvalError> {
     }
 }
 
+/// Parse a step's optional `timeout`. `on_timeout` is a failure handler
+/// when it carries a handler `kind`, otherwise a step target.
+fn parse_timeout(step: &serde_json::Value) -> Result<Option<Timeout>, EvalError> {
+    let Some(v) = step.get("timeout") else {
+        return Ok(None);
+    };
+    let deadline = get_str(v, "deadline")?;
+    let action = v
+        .get("on_timeout")
+        .ok_or_else(|| EvalError::DeserializeError {
+            message: "timeout missing 'on_timeout'".to_string(),
+        })?;
+    let on_timeout = match action.get("kind").and_then(|k| k.as_str()) {
+        Some("Terminate" | "Compensate" | "Escalate") => {
+            TimeoutAction::Handle(parse_failure_handler(action)?)
+        }
+        _ => TimeoutAction::Goto(parse_step_target(action)?),
+    };
+    Ok(Some(Timeout {
+        deadline,
+        on_timeout,
+    }))
+}
+
 fn parse_failure_handler(v: &serde_json::Value) -> Result<FailureHandler, EvalError> {
     let kind = get_str(v, "kind")?;
     match kind.as_str() {
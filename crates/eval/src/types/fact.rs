@@ -2,8 +2,11 @@
 
 use std::collections::BTreeMap;
 
-use super::values::Value;
-use super::TypeSpec;
+use super::values::{get_str, Value};
+use super::{EvalError, TypeSpec};
+use crate::provenance::Conversion;
+use crate::symbol::Symbol;
+use crate::trace::RuleTrace;
 
 /// A declared fact with type and optional default.
 #[derive(Debug, Clone)]
@@ -15,7 +18,7 @@ pub struct FactDecl {
 
 /// A set of fact values keyed by fact id.
 #[derive(Debug, Clone)]
-pub struct FactSet(pub BTreeMap<String, Value>);
+pub struct FactSet(pub BTreeMap<Symbol, Value>);
 
 impl Default for FactSet {
     fn default() -> Self {
@@ -32,14 +35,38 @@ impl FactSet {
         self.0.get(id)
     }
 
-    pub fn insert(&mut self, id: String, value: Value) {
-        self.0.insert(id, value);
+    pub fn insert(&mut self, id: impl Into<Symbol>, value: Value) {
+        self.0.insert(id.into(), value);
+    }
+
+    /// Serialize to a JSON object of fact id to tagged value
+    /// (see [`Value::to_json`]).
+    pub fn to_json(&self) -> serde_json::Value {
+        let facts: serde_json::Map<String, serde_json::Value> = self
+            .0
+            .iter()
+            .map(|(id, v)| (id.to_string(), v.to_json()))
+            .collect();
+        serde_json::Value::Object(facts)
+    }
+
+    /// Inverse of [`FactSet::to_json`].
+    pub fn from_json(v: &serde_json::Value) -> Result<FactSet, EvalError> {
+        let obj = v.as_object().ok_or_else(|| EvalError::DeserializeError {
+            message: "facts must be a JSON object".to_string(),
+        })?;
+        let mut facts = FactSet::new();
+        for (id, value) in obj {
+            facts.insert(id, Value::from_json(value)?);
+        }
+        Ok(facts)
     }
 }
 
-/// A set of produced verdicts.
+/// A set of produced verdicts, with the rule traces recorded by
+/// [`eval_strata_traced`](crate::rules::eval_strata_traced).
 #[derive(Debug, Clone)]
-pub struct VerdictSet(pub Vec<VerdictInstance>);
+pub struct VerdictSet(pub Vec<VerdictInstance>, Vec<RuleTrace>);
 
 impl Default for VerdictSet {
     fn default() -> Self {
@@ -49,13 +76,23 @@ impl Default for VerdictSet {
 
 impl VerdictSet {
     pub fn new() -> Self {
-        VerdictSet(Vec::new())
+        VerdictSet(Vec::new(), Vec::new())
     }
 
     pub fn push(&mut self, verdict: VerdictInstance) {
         self.0.push(verdict);
     }
 
+    /// Per-rule traces, in evaluation order. Empty unless the set was
+    /// produced by [`eval_strata_traced`](crate::rules::eval_strata_traced).
+    pub fn traces(&self) -> &[RuleTrace] {
+        &self.1
+    }
+
+    pub(crate) fn push_trace(&mut self, trace: RuleTrace) {
+        self.1.push(trace);
+    }
+
     /// Check if a verdict of the given type has been produced.
     pub fn has_verdict(&self, verdict_type: &str) -> bool {
         self.0.iter().any(|v| v.verdict_type == verdict_type)
@@ -72,26 +109,103 @@ impl VerdictSet {
             .0
             .iter()
             .map(|v| {
+                let mut provenance = serde_json::json!({
+                    "rule": v.provenance.rule_id,
+                    "stratum": v.provenance.stratum,
+                    "facts_used": v.provenance.facts_used,
+                    "verdicts_used": v.provenance.verdicts_used,
+                });
+                if !v.provenance.conversions.is_empty() {
+                    provenance["conversions"] = v
+                        .provenance
+                        .conversions
+                        .iter()
+                        .map(|c| c.to_json())
+                        .collect();
+                }
                 serde_json::json!({
                     "type": v.verdict_type,
                     "payload": v.payload.to_json(),
-                    "provenance": {
-                        "rule": v.provenance.rule_id,
-                        "stratum": v.provenance.stratum,
-                        "facts_used": v.provenance.facts_used,
-                        "verdicts_used": v.provenance.verdicts_used,
-                    }
+                    "provenance": provenance,
                 })
             })
             .collect();
-        serde_json::json!({ "verdicts": verdicts })
+        let mut json = serde_json::json!({ "verdicts": verdicts });
+        if !self.1.is_empty() {
+            json["traces"] = self.1.iter().map(|t| t.to_json()).collect();
+        }
+        json
+    }
+
+    /// Inverse of [`VerdictSet::to_json`]. Traces are not read back.
+    pub fn from_json(v: &serde_json::Value) -> Result<VerdictSet, EvalError> {
+        let err = |message: String| EvalError::DeserializeError { message };
+        let strings = |p: &serde_json::Value, field: &str| -> Result<Vec<String>, EvalError> {
+            p.get(field)
+                .and_then(|a| a.as_array())
+                .and_then(|a| a.iter().map(|s| s.as_str().map(String::from)).collect())
+                .ok_or_else(|| err(format!("verdict provenance missing '{}'", field)))
+        };
+        let conversions = |p: &serde_json::Value| -> Result<Vec<Conversion>, EvalError> {
+            let Some(items) = p.get("conversions") else {
+                return Ok(Vec::new());
+            };
+            let items = items
+                .as_array()
+                .ok_or_else(|| err("verdict provenance 'conversions' must be an array".into()))?;
+            items
+                .iter()
+                .map(|c| {
+                    let rate = get_str(c, "rate")?;
+                    Ok(Conversion {
+                        rate_fact: get_str(c, "rate_fact")?,
+                        from_currency: get_str(c, "from_currency")?,
+                        to_currency: get_str(c, "to_currency")?,
+                        rate: rate
+                            .parse()
+                            .map_err(|_| err(format!("invalid conversion rate '{}'", rate)))?,
+                    })
+                })
+                .collect()
+        };
+        let items = v
+            .get("verdicts")
+            .and_then(|a| a.as_array())
+            .ok_or_else(|| err("missing 'verdicts' array".to_string()))?;
+        let mut verdicts = VerdictSet::new();
+        for item in items {
+            let verdict_type = get_str(item, "type")?;
+            let payload = item
+                .get("payload")
+                .ok_or_else(|| err(format!("verdict '{}' missing 'payload'", verdict_type)))?;
+            let prov = item
+                .get("provenance")
+                .ok_or_else(|| err(format!("verdict '{}' missing 'provenance'", verdict_type)))?;
+            let stratum = prov
+                .get("stratum")
+                .and_then(|s| s.as_u64())
+                .and_then(|s| u32::try_from(s).ok())
+                .ok_or_else(|| err("verdict provenance missing 'stratum'".to_string()))?;
+            verdicts.push(VerdictInstance {
+                verdict_type: verdict_type.into(),
+                payload: Value::from_json(payload)?,
+                provenance: crate::provenance::VerdictProvenance {
+                    rule_id: get_str(prov, "rule")?,
+                    stratum,
+                    facts_used: strings(prov, "facts_used")?,
+                    verdicts_used: strings(prov, "verdicts_used")?,
+                    conversions: conversions(prov)?,
+                },
+            });
+        }
+        Ok(verdicts)
     }
 }
 
 /// A single verdict instance with provenance.
 #[derive(Debug, Clone)]
 pub struct VerdictInstance {
-    pub verdict_type: String,
+    pub verdict_type: Symbol,
     pub payload: Value,
     pub provenance: crate::provenance::VerdictProvenance,
 }
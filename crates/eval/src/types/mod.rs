@@ -4,18 +4,24 @@
 //! interchange JSON, not raw DSL. All types here are deserialized from the
 //! canonical interchange format.
 
+pub mod compiled;
 pub mod contract;
 pub mod fact;
+mod requirements;
 pub mod values;
 
 use std::collections::BTreeMap;
 use std::fmt;
 
 // Re-export everything at the types:: level for backward compatibility.
+pub use compiled::{
+    CompiledPayload, CompiledPredicate, CompiledRule, CompiledRules, FactSlot, FactSlots,
+    VerdictSlot,
+};
 pub use contract::{
-    parse_predicate, CompStep, Contract, Effect, Entity, FailureHandler, Flow, FlowStep,
-    JoinPolicy, MulExpr, Operation, ParallelBranch, PayloadValue, ProduceClause, Rule, StepTarget,
-    Transition,
+    parse_predicate, CompStep, Contract, Effect, EffectKind, Entity, FailureHandler, Flow,
+    FlowStep, JoinPolicy, MulExpr, Operation, ParallelBranch, PayloadValue, ProduceClause, Rule,
+    StepTarget, Timeout, TimeoutAction, Transition,
 };
 pub use fact::{FactDecl, FactSet, VerdictInstance, VerdictSet};
 pub use values::{parse_default_value, parse_plain_value, value_to_json, Value};
@@ -45,6 +51,9 @@ pub enum EvalError {
     UnknownVerdict { verdict_type: String },
     /// Error deserializing interchange JSON.
     DeserializeError { message: String },
+    /// Strict deserialization found an unknown field or construct kind at
+    /// `pointer` (a JSON pointer into the bundle).
+    StrictViolation { pointer: String, message: String },
     /// Type error during predicate evaluation.
     TypeError { message: String },
     /// List exceeds declared max length.
@@ -65,6 +74,51 @@ pub enum EvalError {
     UnboundVariable { name: String },
     /// Error during flow execution (step limit, structural issues).
     FlowError { flow_id: String, message: String },
+    /// The bundle requires evaluator features this evaluator does not implement.
+    UnsupportedFeatures { features: Vec<String> },
+    /// A persisted snapshot was taken against a different bundle.
+    SnapshotMismatch { expected: String, found: String },
+    /// A rule produced a payload that does not match its declared payload
+    /// type. Only raised when payload validation is enabled.
+    PayloadMismatch {
+        rule_id: String,
+        verdict_type: String,
+        message: String,
+    },
+    /// A Decimal or Money fact value does not fit its declared precision and
+    /// scale without losing digits.
+    PrecisionLoss {
+        fact_id: String,
+        value: String,
+        precision: u32,
+        scale: u32,
+    },
+}
+
+impl EvalError {
+    /// The stable error code (see `tenor_core::error_codes`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            EvalError::MissingFact { .. } => "TEN-E-0100",
+            EvalError::TypeMismatch { .. } => "TEN-E-0101",
+            EvalError::Overflow { .. } => "TEN-E-0102",
+            EvalError::InvalidOperator { .. } => "TEN-E-0103",
+            EvalError::UnknownFact { .. } => "TEN-E-0104",
+            EvalError::UnknownVerdict { .. } => "TEN-E-0105",
+            EvalError::DeserializeError { .. } => "TEN-E-0106",
+            EvalError::StrictViolation { .. } => "TEN-E-0107",
+            EvalError::TypeError { .. } => "TEN-E-0108",
+            EvalError::ListOverflow { .. } => "TEN-E-0109",
+            EvalError::InvalidEnum { .. } => "TEN-E-0110",
+            EvalError::NotARecord { .. } => "TEN-E-0111",
+            EvalError::UnboundVariable { .. } => "TEN-E-0112",
+            EvalError::FlowError { .. } => "TEN-E-0113",
+            EvalError::UnsupportedFeatures { .. } => "TEN-E-0114",
+            EvalError::SnapshotMismatch { .. } => "TEN-E-0115",
+            EvalError::PayloadMismatch { .. } => "TEN-E-0116",
+            EvalError::PrecisionLoss { .. } => "TEN-E-0117",
+        }
+    }
 }
 
 impl fmt::Display for EvalError {
@@ -99,6 +153,9 @@ impl fmt::Display for EvalError {
             EvalError::DeserializeError { message } => {
                 write!(f, "deserialization error: {}", message)
             }
+            EvalError::StrictViolation { message, .. } => {
+                write!(f, "strict deserialization error: {}", message)
+            }
             EvalError::TypeError { message } => {
                 write!(f, "type error: {}", message)
             }
@@ -133,6 +190,44 @@ impl fmt::Display for EvalError {
             EvalError::FlowError { flow_id, message } => {
                 write!(f, "flow error in '{}': {}", flow_id, message)
             }
+            EvalError::UnsupportedFeatures { features } => {
+                write!(
+                    f,
+                    "contract requires evaluator features not supported by this evaluator: {} (supported: {})",
+                    features.join(", "),
+                    crate::SUPPORTED_FEATURES.join(", ")
+                )
+            }
+            EvalError::SnapshotMismatch { expected, found } => {
+                write!(
+                    f,
+                    "snapshot was taken against bundle {}, not {}",
+                    found, expected
+                )
+            }
+            EvalError::PayloadMismatch {
+                rule_id,
+                verdict_type,
+                message,
+            } => {
+                write!(
+                    f,
+                    "rule '{}' produced a '{}' payload that does not match its declared type: {}",
+                    rule_id, verdict_type, message
+                )
+            }
+            EvalError::PrecisionLoss {
+                fact_id,
+                value,
+                precision,
+                scale,
+            } => {
+                write!(
+                    f,
+                    "fact '{}' value {} does not fit precision {} and scale {} without loss of precision",
+                    fact_id, value, precision, scale
+                )
+            }
         }
     }
 }
@@ -149,6 +244,9 @@ pub struct TypeSpec {
     pub base: String,
     pub precision: Option<u32>,
     pub scale: Option<u32>,
+    /// Declared rounding mode of a Decimal or Money type: `"half_even"` or
+    /// `"half_up"`.
+    pub rounding: Option<String>,
     pub currency: Option<String>,
     pub min: Option<i64>,
     pub max: Option<i64>,
@@ -179,6 +277,10 @@ impl TypeSpec {
             .and_then(|v| v.as_u64())
             .map(|v| v as u32);
         let scale = obj.get("scale").and_then(|v| v.as_u64()).map(|v| v as u32);
+        let rounding = obj
+            .get("rounding")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
         let currency = obj
             .get("currency")
             .and_then(|v| v.as_str())
@@ -238,6 +340,7 @@ impl TypeSpec {
             base,
             precision,
             scale,
+            rounding,
             currency,
             min,
             max,
@@ -249,6 +352,130 @@ impl TypeSpec {
             variants,
         })
     }
+
+    /// Serialize back to an interchange JSON BaseType object; the inverse
+    /// of [`TypeSpec::from_json`].
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut obj = serde_json::Map::new();
+        obj.insert("base".to_string(), self.base.clone().into());
+        if let Some(p) = self.precision {
+            obj.insert("precision".to_string(), p.into());
+        }
+        if let Some(s) = self.scale {
+            obj.insert("scale".to_string(), s.into());
+        }
+        if let Some(ref c) = self.currency {
+            obj.insert("currency".to_string(), c.clone().into());
+        }
+        if let Some(min) = self.min {
+            obj.insert("min".to_string(), min.into());
+        }
+        if let Some(max) = self.max {
+            obj.insert("max".to_string(), max.into());
+        }
+        if let Some(len) = self.max_length {
+            obj.insert("max_length".to_string(), len.into());
+        }
+        if let Some(ref values) = self.values {
+            obj.insert("values".to_string(), values.clone().into());
+        }
+        if let Some(ref fields) = self.fields {
+            let fields = fields.iter().map(|(k, t)| (k.clone(), t.to_json()));
+            obj.insert("fields".to_string(), fields.collect());
+        }
+        if let Some(ref et) = self.element_type {
+            obj.insert("element_type".to_string(), et.to_json());
+        }
+        if let Some(ref unit) = self.unit {
+            obj.insert("unit".to_string(), unit.clone().into());
+        }
+        if let Some(ref variants) = self.variants {
+            let variants = variants.iter().map(|(k, t)| (k.clone(), t.to_json()));
+            obj.insert("variants".to_string(), variants.collect());
+        }
+        serde_json::Value::Object(obj)
+    }
+
+    /// Check that a runtime value inhabits this type, describing the first
+    /// mismatch found.
+    pub fn check_value(&self, value: &Value) -> Result<(), String> {
+        let mismatch = || Err(format!("expected {}, got {}", self.base, value.type_name()));
+        match (self.base.as_str(), value) {
+            ("Bool", Value::Bool(_))
+            | ("Decimal", Value::Decimal(_))
+            | ("Date", Value::Date(_))
+            | ("DateTime", Value::DateTime(_)) => Ok(()),
+            ("Int", Value::Int(i)) => match (self.min, self.max) {
+                (Some(min), _) if *i < min => Err(format!("{} is below the minimum {}", i, min)),
+                (_, Some(max)) if *i > max => Err(format!("{} is above the maximum {}", i, max)),
+                _ => Ok(()),
+            },
+            ("Text", Value::Text(t)) => match self.max_length {
+                Some(len) if t.len() > len as usize => Err(format!(
+                    "text of length {} exceeds max_length {}",
+                    t.len(),
+                    len
+                )),
+                _ => Ok(()),
+            },
+            ("Enum", Value::Enum(v)) => match self.values {
+                Some(ref values) if !values.contains(v) => {
+                    Err(format!("'{}' is not one of: {}", v, values.join(", ")))
+                }
+                _ => Ok(()),
+            },
+            ("Money", Value::Money { currency, .. }) => match self.currency {
+                Some(ref c) if c != currency => {
+                    Err(format!("expected currency {}, got {}", c, currency))
+                }
+                _ => Ok(()),
+            },
+            ("Duration", Value::Duration { unit, .. }) => match self.unit {
+                Some(ref u) if u != unit => Err(format!("expected unit {}, got {}", u, unit)),
+                _ => Ok(()),
+            },
+            ("Record", Value::Record(fields)) => {
+                let Some(ref declared) = self.fields else {
+                    return Ok(());
+                };
+                if let Some(extra) = fields.keys().find(|k| !declared.contains_key(*k)) {
+                    return Err(format!("undeclared field '{}'", extra));
+                }
+                for (name, ty) in declared {
+                    let field = fields
+                        .get(name)
+                        .ok_or_else(|| format!("missing field '{}'", name))?;
+                    ty.check_value(field)
+                        .map_err(|e| format!("field '{}': {}", name, e))?;
+                }
+                Ok(())
+            }
+            ("List", Value::List(items)) => {
+                if let Some(max) = self.max {
+                    if items.len() as i64 > max {
+                        return Err(format!("list of {} items exceeds max {}", items.len(), max));
+                    }
+                }
+                if let Some(ref et) = self.element_type {
+                    for (i, item) in items.iter().enumerate() {
+                        et.check_value(item)
+                            .map_err(|e| format!("element {}: {}", i, e))?;
+                    }
+                }
+                Ok(())
+            }
+            ("TaggedUnion", Value::TaggedUnion { tag, payload }) => {
+                match self.variants.as_ref().map(|v| v.get(tag)) {
+                    Some(None) => Err(format!("undeclared variant '{}'", tag)),
+                    Some(Some(ty)) => ty
+                        .check_value(payload)
+                        .map_err(|e| format!("variant '{}': {}", tag, e)),
+                    None => Ok(()),
+                }
+            }
+            _ => mismatch(),
+        }
+    }
 }
 
 // ──────────────────────────────────────────────
@@ -305,6 +532,41 @@ pub enum Predicate {
         literal: i64,
         result_type: TypeSpec,
     },
+    /// Money amount converted into the currency of the Money-typed
+    /// `rate_fact`, or a Decimal quantity multiplied by the Decimal
+    /// conversion factor `rate_fact`.
+    Convert {
+        amount: Box<Predicate>,
+        rate_fact: String,
+    },
+    /// Quantifier domain ranging over the live instances of an entity.
+    InstancesOf(String),
+    /// Current state of the instance bound to an instance quantifier variable.
+    InstanceState(String),
+}
+
+impl Predicate {
+    /// Whether this predicate quantifies over entity instances, and so must
+    /// be evaluated in a context built with
+    /// [`EvalContext::with_instances`](crate::predicate::EvalContext::with_instances).
+    pub fn reads_instances(&self) -> bool {
+        match self {
+            Predicate::InstancesOf(_) | Predicate::InstanceState(_) => true,
+            Predicate::Compare { left, right, .. }
+            | Predicate::And { left, right }
+            | Predicate::Or { left, right } => left.reads_instances() || right.reads_instances(),
+            Predicate::Not { operand } => operand.reads_instances(),
+            Predicate::Forall { domain, body, .. } | Predicate::Exists { domain, body, .. } => {
+                domain.reads_instances() || body.reads_instances()
+            }
+            Predicate::Mul { left, .. } => left.reads_instances(),
+            Predicate::Convert { amount, .. } => amount.reads_instances(),
+            Predicate::FactRef(_)
+            | Predicate::FieldRef { .. }
+            | Predicate::Literal { .. }
+            | Predicate::VerdictPresent(_) => false,
+        }
+    }
 }
 
 // ──────────────────────────────────────────────
@@ -321,6 +583,67 @@ mod tests {
         Decimal::from_str(s).unwrap()
     }
 
+    #[test]
+    fn eval_error_codes_are_catalogued() {
+        let s = String::new;
+        let errors = [
+            EvalError::MissingFact { fact_id: s() },
+            EvalError::TypeMismatch {
+                fact_id: s(),
+                expected: s(),
+                got: s(),
+            },
+            EvalError::Overflow { message: s() },
+            EvalError::InvalidOperator { op: s() },
+            EvalError::UnknownFact { fact_id: s() },
+            EvalError::UnknownVerdict { verdict_type: s() },
+            EvalError::DeserializeError { message: s() },
+            EvalError::StrictViolation {
+                pointer: s(),
+                message: s(),
+            },
+            EvalError::TypeError { message: s() },
+            EvalError::ListOverflow {
+                fact_id: s(),
+                max: 0,
+                actual: 0,
+            },
+            EvalError::InvalidEnum {
+                fact_id: s(),
+                value: s(),
+                variants: vec![],
+            },
+            EvalError::NotARecord { message: s() },
+            EvalError::UnboundVariable { name: s() },
+            EvalError::FlowError {
+                flow_id: s(),
+                message: s(),
+            },
+            EvalError::UnsupportedFeatures { features: vec![] },
+            EvalError::SnapshotMismatch {
+                expected: s(),
+                found: s(),
+            },
+            EvalError::PayloadMismatch {
+                rule_id: s(),
+                verdict_type: s(),
+                message: s(),
+            },
+            EvalError::PrecisionLoss {
+                fact_id: s(),
+                value: s(),
+                precision: 0,
+                scale: 0,
+            },
+        ];
+        let mut codes: Vec<&str> = errors.iter().map(EvalError::code).collect();
+        for code in &codes {
+            assert!(tenor_core::error_codes::lookup(code).is_some(), "{}", code);
+        }
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+    }
+
     #[test]
     fn value_bool_equality() {
         assert_eq!(Value::Bool(true), Value::Bool(true));
@@ -405,7 +728,7 @@ mod tests {
 
     #[test]
     fn value_as_bool() {
-        assert_eq!(Value::Bool(true).as_bool().unwrap(), true);
+        assert!(Value::Bool(true).as_bool().unwrap());
         assert!(Value::Int(1).as_bool().is_err());
     }
 
@@ -488,13 +811,14 @@ mod tests {
         let mut vs = VerdictSet::new();
         assert!(!vs.has_verdict("test"));
         vs.push(VerdictInstance {
-            verdict_type: "test".to_string(),
+            verdict_type: "test".into(),
             payload: Value::Bool(true),
             provenance: crate::provenance::VerdictProvenance {
                 rule_id: "r1".to_string(),
                 stratum: 0,
                 facts_used: vec!["f1".to_string()],
                 verdicts_used: vec![],
+                conversions: vec![],
             },
         });
         assert!(vs.has_verdict("test"));
@@ -654,4 +978,39 @@ mod tests {
             "Source declarations must not affect evaluation results"
         );
     }
+
+    #[test]
+    fn type_spec_json_round_trips_and_checks_records() {
+        let json = serde_json::json!({
+            "base": "Record",
+            "fields": {
+                "amount": {"base": "Money", "currency": "USD"},
+                "count": {"base": "Int", "min": 0, "max": 10}
+            }
+        });
+        let ts = TypeSpec::from_json(&json).unwrap();
+        assert_eq!(ts.to_json(), json);
+
+        let record = |currency: &str, count: i64| {
+            Value::Record(BTreeMap::from([
+                (
+                    "amount".to_string(),
+                    Value::Money {
+                        amount: dec("1.00"),
+                        currency: currency.to_string(),
+                    },
+                ),
+                ("count".to_string(), Value::Int(count)),
+            ]))
+        };
+        assert!(ts.check_value(&record("USD", 3)).is_ok());
+        assert_eq!(
+            ts.check_value(&record("EUR", 3)).unwrap_err(),
+            "field 'amount': expected currency USD, got EUR"
+        );
+        assert_eq!(
+            ts.check_value(&record("USD", 11)).unwrap_err(),
+            "field 'count': 11 is above the maximum 10"
+        );
+    }
 }
@@ -0,0 +1,275 @@
+//! Fact requirement introspection: which facts can affect a flow or
+//! operation.
+//!
+//! Walks operation preconditions, effect guards and attribute assignments,
+//! flow branch conditions, step deadlines, compensation and sub-flows, and
+//! -- through `verdict_present` -- the conditions and payloads of every
+//! rule producing a referenced verdict, transitively.
+
+use std::collections::{BTreeSet, HashSet};
+
+use super::contract::{
+    Contract, FailureHandler, FlowStep, Operation, PayloadValue, Timeout, TimeoutAction,
+};
+use super::fact::FactDecl;
+use super::Predicate;
+
+impl Contract {
+    /// The declared facts that can affect the outcome of flow `flow_id`, in
+    /// declaration order, or `None` if there is no such flow.
+    ///
+    /// Facts outside this set cannot change which path the flow takes or
+    /// which operations succeed. Evaluation still assembles every declared
+    /// fact, so facts without a default must be supplied regardless.
+    pub fn required_facts_for_flow(&self, flow_id: &str) -> Option<Vec<&FactDecl>> {
+        let mut walk = RequirementWalk::new(self);
+        walk.flow(flow_id)?;
+        Some(walk.into_facts())
+    }
+
+    /// The declared facts that can affect whether operation `op_id`
+    /// succeeds and what its effects do, in declaration order, or `None`
+    /// if there is no such operation.
+    pub fn required_facts_for_operation(&self, op_id: &str) -> Option<Vec<&FactDecl>> {
+        let mut walk = RequirementWalk::new(self);
+        walk.operation(self.get_operation(op_id)?);
+        Some(walk.into_facts())
+    }
+}
+
+/// Accumulates referenced facts, visiting each flow, operation and verdict
+/// type once.
+struct RequirementWalk<'c> {
+    contract: &'c Contract,
+    facts: BTreeSet<&'c str>,
+    flows: HashSet<&'c str>,
+    operations: HashSet<&'c str>,
+    verdicts: HashSet<&'c str>,
+}
+
+impl<'c> RequirementWalk<'c> {
+    fn new(contract: &'c Contract) -> Self {
+        RequirementWalk {
+            contract,
+            facts: BTreeSet::new(),
+            flows: HashSet::new(),
+            operations: HashSet::new(),
+            verdicts: HashSet::new(),
+        }
+    }
+
+    /// The collected facts that the contract declares, in declaration order.
+    fn into_facts(self) -> Vec<&'c FactDecl> {
+        self.contract
+            .facts
+            .iter()
+            .filter(|decl| self.facts.contains(decl.id.as_str()))
+            .collect()
+    }
+
+    fn flow(&mut self, flow_id: &str) -> Option<()> {
+        let contract = self.contract;
+        let flow = contract.get_flow(flow_id)?;
+        if self.flows.insert(flow.id.as_str()) {
+            self.steps(&flow.steps);
+        }
+        Some(())
+    }
+
+    fn operation(&mut self, op: &'c Operation) {
+        if !self.operations.insert(op.id.as_str()) {
+            return;
+        }
+        self.predicate(&op.precondition, &mut Vec::new());
+        for effect in &op.effects {
+            if let Some(guard) = &effect.guard {
+                self.predicate(guard, &mut Vec::new());
+            }
+            for value in effect.set.values() {
+                self.predicate(value, &mut Vec::new());
+            }
+        }
+    }
+
+    fn operation_by_id(&mut self, op_id: &str) {
+        let contract = self.contract;
+        if let Some(op) = contract.get_operation(op_id) {
+            self.operation(op);
+        }
+    }
+
+    fn steps(&mut self, steps: &'c [FlowStep]) {
+        for step in steps {
+            match step {
+                FlowStep::OperationStep {
+                    op,
+                    on_failure,
+                    timeout,
+                    ..
+                } => {
+                    self.operation_by_id(op);
+                    self.failure_handler(on_failure);
+                    self.timeout(timeout.as_ref());
+                }
+                FlowStep::BranchStep { condition, .. } => {
+                    self.predicate(condition, &mut Vec::new());
+                }
+                FlowStep::HandoffStep { timeout, .. } => self.timeout(timeout.as_ref()),
+                FlowStep::SubFlowStep {
+                    flow, on_failure, ..
+                } => {
+                    self.flow(flow);
+                    self.failure_handler(on_failure);
+                }
+                FlowStep::ParallelStep { branches, join, .. } => {
+                    for branch in branches {
+                        self.steps(&branch.steps);
+                    }
+                    if let Some(handler) = &join.on_any_failure {
+                        self.failure_handler(handler);
+                    }
+                }
+            }
+        }
+    }
+
+    fn failure_handler(&mut self, handler: &'c FailureHandler) {
+        if let FailureHandler::Compensate { steps, .. } = handler {
+            for step in steps {
+                self.operation_by_id(&step.op);
+            }
+        }
+    }
+
+    fn timeout(&mut self, timeout: Option<&'c Timeout>) {
+        let Some(timeout) = timeout else {
+            return;
+        };
+        self.facts.insert(timeout.deadline.as_str());
+        if let TimeoutAction::Handle(handler) = &timeout.on_timeout {
+            self.failure_handler(handler);
+        }
+    }
+
+    /// Every rule producing `verdict_type` decides whether it is present.
+    fn verdict(&mut self, verdict_type: &'c str) {
+        if !self.verdicts.insert(verdict_type) {
+            return;
+        }
+        let contract = self.contract;
+        for rule in &contract.rules {
+            if rule.produce.verdict_type != verdict_type {
+                continue;
+            }
+            self.predicate(&rule.condition, &mut Vec::new());
+            if let PayloadValue::Mul(mul) = &rule.produce.payload_value {
+                self.facts.insert(mul.fact_ref.as_str());
+            }
+        }
+    }
+
+    /// `bound` holds the variables of enclosing quantifiers; a field
+    /// reference through any other variable reads a Record fact.
+    fn predicate(&mut self, pred: &'c Predicate, bound: &mut Vec<&'c str>) {
+        match pred {
+            Predicate::FactRef(id) => {
+                self.facts.insert(id.as_str());
+            }
+            Predicate::FieldRef { var, .. } => {
+                if !bound.contains(&var.as_str()) {
+                    self.facts.insert(var.as_str());
+                }
+            }
+            Predicate::VerdictPresent(id) => self.verdict(id.as_str()),
+            Predicate::Compare { left, right, .. }
+            | Predicate::And { left, right }
+            | Predicate::Or { left, right } => {
+                self.predicate(left, bound);
+                self.predicate(right, bound);
+            }
+            Predicate::Not { operand } => self.predicate(operand, bound),
+            Predicate::Mul { left, .. } => self.predicate(left, bound),
+            Predicate::Convert { amount, rate_fact } => {
+                self.predicate(amount, bound);
+                self.facts.insert(rate_fact.as_str());
+            }
+            Predicate::Forall {
+                variable,
+                domain,
+                body,
+                ..
+            }
+            | Predicate::Exists {
+                variable,
+                domain,
+                body,
+                ..
+            } => {
+                self.predicate(domain, bound);
+                bound.push(variable.as_str());
+                self.predicate(body, bound);
+                bound.pop();
+            }
+            Predicate::Literal { .. } | Predicate::InstancesOf(_) | Predicate::InstanceState(_) => {
+            }
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::flow_basic;
+    use crate::types::TypeSpec;
+
+    fn ids(facts: Option<Vec<&FactDecl>>) -> Vec<&str> {
+        facts.unwrap().iter().map(|f| f.id.as_str()).collect()
+    }
+
+    #[test]
+    fn operation_needs_facts_behind_its_precondition_verdicts() {
+        let contract = flow_basic();
+        // submit_order requires verdict account_active, produced from is_active
+        assert_eq!(
+            ids(contract.required_facts_for_operation("submit_order")),
+            ["is_active"]
+        );
+    }
+
+    #[test]
+    fn flow_adds_branch_conditions_and_compensation() {
+        let contract = flow_basic();
+        assert_eq!(
+            ids(contract.required_facts_for_flow("order_approval")),
+            ["is_active", "requires_review"]
+        );
+    }
+
+    #[test]
+    fn unknown_ids_return_none() {
+        let contract = flow_basic();
+        assert!(contract.required_facts_for_flow("nope").is_none());
+        assert!(contract.required_facts_for_operation("nope").is_none());
+    }
+
+    #[test]
+    fn quantifier_variables_are_not_facts() {
+        let contract = Contract::new(vec![], vec![], vec![], vec![], vec![], vec![]);
+        let mut walk = RequirementWalk::new(&contract);
+        let pred = Predicate::Forall {
+            variable: "item".to_string(),
+            variable_type: TypeSpec::from_json(&serde_json::json!({"base": "Record"})).unwrap(),
+            domain: Box::new(Predicate::FactRef("items".to_string())),
+            body: Box::new(Predicate::FieldRef {
+                var: "item".to_string(),
+                field: "ok".to_string(),
+            }),
+        };
+        walk.predicate(&pred, &mut Vec::new());
+        assert_eq!(walk.facts, BTreeSet::from(["items"]));
+    }
+}
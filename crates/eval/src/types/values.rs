@@ -99,6 +99,136 @@ impl Value {
             }),
         }
     }
+
+    /// Convert this Value to the plain JSON a caller supplies facts in.
+    ///
+    /// Inverse of [`parse_plain_value`]: no "kind" wrappers, decimals as
+    /// strings.
+    pub fn to_plain_json(&self) -> serde_json::Value {
+        match self {
+            Value::Bool(b) => serde_json::json!(b),
+            Value::Int(i) => serde_json::json!(i),
+            Value::Decimal(d) => serde_json::json!(d.to_string()),
+            Value::Text(s) | Value::Date(s) | Value::DateTime(s) | Value::Enum(s) => {
+                serde_json::json!(s)
+            }
+            Value::Money { amount, currency } => serde_json::json!({
+                "amount": amount.to_string(),
+                "currency": currency,
+            }),
+            Value::Duration { value, unit } => serde_json::json!({
+                "value": value,
+                "unit": unit,
+            }),
+            Value::Record(fields) => serde_json::Value::Object(
+                fields
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.to_plain_json()))
+                    .collect(),
+            ),
+            Value::List(items) => {
+                serde_json::Value::Array(items.iter().map(Value::to_plain_json).collect())
+            }
+            Value::TaggedUnion { tag, payload } => serde_json::json!({
+                "tag": tag,
+                "payload": payload.to_plain_json(),
+            }),
+        }
+    }
+
+    /// Parse the tagged representation produced by [`Value::to_json`].
+    ///
+    /// The encoding is self-describing, so no type spec is needed; this is
+    /// what lets persisted snapshots be restored without the contract.
+    pub fn from_json(v: &serde_json::Value) -> Result<Value, EvalError> {
+        let kind = get_str(v, "kind")?;
+        let value = || {
+            v.get("value").ok_or_else(|| EvalError::DeserializeError {
+                message: format!("{} missing 'value'", kind),
+            })
+        };
+        let text = |field: &str| -> Result<String, EvalError> {
+            get_str(v, field).map_err(|_| EvalError::DeserializeError {
+                message: format!("{} missing string '{}'", kind, field),
+            })
+        };
+        let decimal = |s: &str| {
+            s.parse::<Decimal>()
+                .map_err(|e| EvalError::DeserializeError {
+                    message: format!("invalid decimal in {}: {}", kind, e),
+                })
+        };
+        let integer = |field: &str| {
+            v.get(field)
+                .and_then(|i| i.as_i64())
+                .ok_or_else(|| EvalError::DeserializeError {
+                    message: format!("{} missing integer '{}'", kind, field),
+                })
+        };
+        match kind.as_str() {
+            "bool_value" => {
+                value()?
+                    .as_bool()
+                    .map(Value::Bool)
+                    .ok_or_else(|| EvalError::DeserializeError {
+                        message: "bool_value 'value' must be a boolean".to_string(),
+                    })
+            }
+            "int_value" => integer("value").map(Value::Int),
+            "decimal_value" => decimal(&text("value")?).map(Value::Decimal),
+            "text_value" => text("value").map(Value::Text),
+            "date_value" => text("value").map(Value::Date),
+            "datetime_value" => text("value").map(Value::DateTime),
+            "money_value" => Ok(Value::Money {
+                amount: decimal(&text("amount")?)?,
+                currency: text("currency")?,
+            }),
+            "duration_value" => Ok(Value::Duration {
+                value: integer("value")?,
+                unit: text("unit")?,
+            }),
+            "enum_value" => text("value").map(Value::Enum),
+            "record_value" => {
+                let fields = v.get("fields").and_then(|f| f.as_object()).ok_or_else(|| {
+                    EvalError::DeserializeError {
+                        message: "record_value missing 'fields'".to_string(),
+                    }
+                })?;
+                fields
+                    .iter()
+                    .map(|(k, fv)| Ok((k.clone(), Value::from_json(fv)?)))
+                    .collect::<Result<_, EvalError>>()
+                    .map(Value::Record)
+            }
+            "list_value" => {
+                let elements = v
+                    .get("elements")
+                    .and_then(|e| e.as_array())
+                    .ok_or_else(|| EvalError::DeserializeError {
+                        message: "list_value missing 'elements'".to_string(),
+                    })?;
+                elements
+                    .iter()
+                    .map(Value::from_json)
+                    .collect::<Result<_, EvalError>>()
+                    .map(Value::List)
+            }
+            "tagged_union_value" => {
+                let payload = v
+                    .get("payload")
+                    .ok_or_else(|| EvalError::DeserializeError {
+                        message: "tagged_union_value missing 'payload'".to_string(),
+                    })?;
+                Ok(Value::TaggedUnion {
+                    tag: text("tag")?,
+                    payload: Box::new(Value::from_json(payload)?),
+                })
+            }
+            other => Err(EvalError::DeserializeError {
+                message: format!("unknown value kind '{}'", other),
+            }),
+        }
+    }
 }
 
 // ──────────────────────────────────────────────
@@ -398,6 +528,7 @@ pub(crate) fn infer_literal(v: &serde_json::Value) -> Result<(Value, TypeSpec),
         base: base.to_string(),
         precision: None,
         scale: None,
+        rounding: None,
         currency: None,
         min: None,
         max: None,
@@ -0,0 +1,192 @@
+//! Differences between the verdicts of two evaluations.
+//!
+//! [`VerdictSet::diff`] compares a baseline verdict set with a new one,
+//! typically the same contract evaluated before and after a fact changed,
+//! and reports which verdicts appeared, disappeared, or changed payload,
+//! each with the rule that produced it.
+
+use crate::types::{Value, VerdictInstance, VerdictSet};
+
+/// Verdicts added, removed, and changed between two evaluations.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerdictDiff {
+    /// Produced only by the new evaluation.
+    pub added: Vec<VerdictChange>,
+    /// Produced only by the baseline evaluation.
+    pub removed: Vec<VerdictChange>,
+    /// Produced by the same rule in both, with different payloads.
+    pub changed: Vec<VerdictChange>,
+}
+
+/// One verdict that differs between two evaluations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerdictChange {
+    pub verdict_type: String,
+    /// The rule responsible for the verdict.
+    pub rule_id: String,
+    pub stratum: u32,
+    /// Payload in the baseline evaluation; `None` for added verdicts.
+    pub old_payload: Option<Value>,
+    /// Payload in the new evaluation; `None` for removed verdicts.
+    pub new_payload: Option<Value>,
+    /// Facts the rule read, from the evaluation that produced the verdict
+    /// (the new one unless the verdict was removed).
+    pub facts_used: Vec<String>,
+    /// Verdicts the rule read, likewise.
+    pub verdicts_used: Vec<String>,
+}
+
+impl VerdictSet {
+    /// Compare this (baseline) verdict set with `other`.
+    ///
+    /// Verdicts are matched by verdict type and producing rule, so a verdict
+    /// now produced by a different rule shows as removed and added.
+    pub fn diff(&self, other: &VerdictSet) -> VerdictDiff {
+        let mut diff = VerdictDiff::default();
+        for old in &self.0 {
+            match find(other, old) {
+                None => diff.removed.push(change(old, Some(old), None)),
+                Some(new) if new.payload != old.payload => {
+                    diff.changed.push(change(new, Some(old), Some(new)))
+                }
+                Some(_) => {}
+            }
+        }
+        for new in &other.0 {
+            if find(self, new).is_none() {
+                diff.added.push(change(new, None, Some(new)));
+            }
+        }
+        diff
+    }
+}
+
+/// The verdict in `set` of the same type and rule as `v`.
+fn find<'a>(set: &'a VerdictSet, v: &VerdictInstance) -> Option<&'a VerdictInstance> {
+    set.0
+        .iter()
+        .find(|o| o.verdict_type == v.verdict_type && o.provenance.rule_id == v.provenance.rule_id)
+}
+
+/// A change attributed to `source`'s rule and provenance.
+fn change(
+    source: &VerdictInstance,
+    old: Option<&VerdictInstance>,
+    new: Option<&VerdictInstance>,
+) -> VerdictChange {
+    VerdictChange {
+        verdict_type: source.verdict_type.to_string(),
+        rule_id: source.provenance.rule_id.clone(),
+        stratum: source.provenance.stratum,
+        old_payload: old.map(|v| v.payload.clone()),
+        new_payload: new.map(|v| v.payload.clone()),
+        facts_used: source.provenance.facts_used.clone(),
+        verdicts_used: source.provenance.verdicts_used.clone(),
+    }
+}
+
+impl VerdictDiff {
+    /// True when both evaluations produced the same verdicts.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let changes = |changes: &[VerdictChange]| -> serde_json::Value {
+            changes.iter().map(VerdictChange::to_json).collect()
+        };
+        serde_json::json!({
+            "added": changes(&self.added),
+            "removed": changes(&self.removed),
+            "changed": changes(&self.changed),
+        })
+    }
+}
+
+impl VerdictChange {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "type": self.verdict_type,
+            "rule": self.rule_id,
+            "stratum": self.stratum,
+            "facts_used": self.facts_used,
+            "verdicts_used": self.verdicts_used,
+        });
+        if let Some(payload) = &self.old_payload {
+            json["old_payload"] = payload.to_json();
+        }
+        if let Some(payload) = &self.new_payload {
+            json["new_payload"] = payload.to_json();
+        }
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance::VerdictProvenance;
+
+    fn verdict(verdict_type: &str, rule_id: &str, payload: Value) -> VerdictInstance {
+        VerdictInstance {
+            verdict_type: verdict_type.into(),
+            payload,
+            provenance: VerdictProvenance {
+                rule_id: rule_id.to_string(),
+                stratum: 0,
+                facts_used: vec!["amount".to_string()],
+                verdicts_used: Vec::new(),
+                conversions: Vec::new(),
+            },
+        }
+    }
+
+    fn set(verdicts: Vec<VerdictInstance>) -> VerdictSet {
+        let mut set = VerdictSet::new();
+        for v in verdicts {
+            set.push(v);
+        }
+        set
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_verdicts() {
+        let old = set(vec![
+            verdict("approved", "approve", Value::Bool(true)),
+            verdict("limit", "set_limit", Value::Int(100)),
+            verdict("stable", "keep", Value::Bool(true)),
+            verdict("risk", "low_risk", Value::Bool(true)),
+        ]);
+        let new = set(vec![
+            verdict("limit", "set_limit", Value::Int(250)),
+            verdict("stable", "keep", Value::Bool(true)),
+            verdict("flagged", "flag", Value::Bool(true)),
+            verdict("risk", "high_risk", Value::Bool(true)),
+        ]);
+
+        let diff = old.diff(&new);
+        let types = |changes: &[VerdictChange]| {
+            changes
+                .iter()
+                .map(|c| format!("{}/{}", c.verdict_type, c.rule_id))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(types(&diff.added), vec!["flagged/flag", "risk/high_risk"]);
+        assert_eq!(
+            types(&diff.removed),
+            vec!["approved/approve", "risk/low_risk"]
+        );
+        assert_eq!(types(&diff.changed), vec!["limit/set_limit"]);
+        assert_eq!(diff.changed[0].old_payload, Some(Value::Int(100)));
+        assert_eq!(diff.changed[0].new_payload, Some(Value::Int(250)));
+        assert_eq!(diff.removed[0].new_payload, None);
+        assert_eq!(diff.added[0].old_payload, None);
+
+        let json = diff.to_json();
+        assert_eq!(json["changed"][0]["rule"], "set_limit");
+        assert_eq!(json["changed"][0]["facts_used"][0], "amount");
+        assert!(json["added"][0].get("old_payload").is_none());
+
+        assert!(new.diff(&new).is_empty());
+    }
+}
@@ -25,6 +25,10 @@ fn run_eval_fixture(fixture_dir: &Path, name: &str) {
     let bundle = tenor_core::elaborate::elaborate(&tenor_path)
         .unwrap_or_else(|e| panic!("Failed to elaborate {}: {:?}", name, e));
 
+    // Elaborator output never trips strict mode
+    tenor_eval::Contract::from_interchange_strict(&bundle)
+        .unwrap_or_else(|e| panic!("Strict load failed for {}: {}", name, e));
+
     // Step 2: Load facts.json
     let facts_str = std::fs::read_to_string(&facts_path)
         .unwrap_or_else(|e| panic!("Failed to read facts for {}: {}", name, e));
@@ -98,7 +102,7 @@ fn run_domain_flow_fixture(
     let expected_path = fixture_dir.join(format!("{}.verdicts.json", name));
 
     // Step 1: Elaborate
-    let bundle = tenor_core::elaborate::elaborate(&tenor_path)
+    let bundle = tenor_core::elaborate::elaborate(tenor_path)
         .unwrap_or_else(|e| panic!("Failed to elaborate {}: {:?}", name, e));
 
     // Step 2: Load facts
@@ -275,6 +279,36 @@ fn operation_precondition() {
     );
 }
 
+#[test]
+fn transition_guard() {
+    run_eval_flow_fixture(&positive_dir(), "transition_guard", "submit_flow", "buyer");
+}
+
+#[test]
+fn entity_attributes() {
+    run_eval_flow_fixture(&positive_dir(), "entity_attributes", "submit_flow", "buyer");
+}
+
+#[test]
+fn instance_lifecycle() {
+    run_eval_flow_fixture(
+        &positive_dir(),
+        "instance_lifecycle",
+        "ticket_flow",
+        "agent",
+    );
+}
+
+#[test]
+fn instance_quantifiers() {
+    run_eval_flow_fixture(
+        &positive_dir(),
+        "instance_quantifiers",
+        "shipment_flow",
+        "courier",
+    );
+}
+
 #[test]
 fn flow_linear_basic() {
     run_eval_flow_fixture(&positive_dir(), "flow_linear_basic", "submit_flow", "buyer");
@@ -420,11 +454,31 @@ fn numeric_money_comparison() {
     run_eval_fixture(&numeric_dir(), "money_comparison");
 }
 
+#[test]
+fn numeric_money_conversion() {
+    run_eval_fixture(&numeric_dir(), "money_conversion");
+}
+
+#[test]
+fn numeric_unit_conversion() {
+    run_eval_fixture(&numeric_dir(), "unit_conversion");
+}
+
 #[test]
 fn numeric_decimal_overflow() {
     run_eval_fixture_error(&numeric_dir(), "decimal_overflow");
 }
 
+#[test]
+fn numeric_decimal_rounding_half_up() {
+    run_eval_fixture(&numeric_dir(), "decimal_rounding_half_up");
+}
+
+#[test]
+fn numeric_decimal_precision_loss() {
+    run_eval_fixture_error(&numeric_dir(), "decimal_precision_loss");
+}
+
 // ──────────────────────────────────────────────
 // Domain validation — SaaS Subscription (Phase 5)
 // ──────────────────────────────────────────────
@@ -15,14 +15,14 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use tenor_eval::{
     action_space::{compute_action_space, BlockedReason},
-    flow::{execute_flow, Snapshot},
+    flow::{execute_flow, FlowOptions, Snapshot},
     operation::{
-        execute_operation, get_instance_state, single_instance, EntityStateMap, InstanceBindingMap,
-        OperationError, DEFAULT_INSTANCE_ID,
+        execute_operation, get_instance_state, instance_key, single_instance, EntityAttributeMap,
+        EntityStateMap, EntityVersions, InstanceBindingMap, OperationError, DEFAULT_INSTANCE_ID,
     },
     types::{
-        Contract, Effect, Entity, FactSet, FailureHandler, Flow, FlowStep, Operation, Predicate,
-        StepTarget, TypeSpec, Value, VerdictSet,
+        Contract, Effect, EffectKind, Entity, FactSet, FailureHandler, Flow, FlowStep, Operation,
+        Predicate, StepTarget, TypeSpec, Value, VerdictSet,
     },
 };
 
@@ -36,6 +36,7 @@ fn bool_type() -> TypeSpec {
         base: "Bool".to_string(),
         precision: None,
         scale: None,
+        rounding: None,
         currency: None,
         min: None,
         max: None,
@@ -82,6 +83,7 @@ fn order_entity() -> Entity {
         ],
         initial: "draft".to_string(),
         transitions: vec![],
+        attributes: BTreeMap::new(),
     }
 }
 
@@ -97,9 +99,12 @@ fn order_operations() -> Vec<Operation> {
             vec!["buyer"],
             vec![Effect {
                 entity_id: "Order".to_string(),
+                kind: EffectKind::Transition,
                 from: "draft".to_string(),
                 to: "submitted".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["submitted"],
         ),
@@ -108,9 +113,12 @@ fn order_operations() -> Vec<Operation> {
             vec!["admin"],
             vec![Effect {
                 entity_id: "Order".to_string(),
+                kind: EffectKind::Transition,
                 from: "submitted".to_string(),
                 to: "approved".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["approved"],
         ),
@@ -119,9 +127,12 @@ fn order_operations() -> Vec<Operation> {
             vec!["admin"],
             vec![Effect {
                 entity_id: "Order".to_string(),
+                kind: EffectKind::Transition,
                 from: "submitted".to_string(),
                 to: "rejected".to_string(),
                 outcome: None,
+                guard: None,
+                set: BTreeMap::new(),
             }],
             vec!["rejected"],
         ),
@@ -152,6 +163,7 @@ fn simple_flow(flow_id: &str, op_id: &str, persona: &str, op_outcome: &str) -> F
             on_failure: FailureHandler::Terminate {
                 outcome: format!("{}_failed", op_id),
             },
+            timeout: None,
         }],
     }
 }
@@ -301,18 +313,9 @@ fn multiple_instances_same_entity_type() {
     let facts = serde_json::json!({});
 
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
-    entity_states.insert(
-        ("Order".to_string(), "ord-002".to_string()),
-        "submitted".to_string(),
-    );
-    entity_states.insert(
-        ("Order".to_string(), "ord-003".to_string()),
-        "approved".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
+    entity_states.insert(instance_key("Order", "ord-002"), "submitted".into());
+    entity_states.insert(instance_key("Order", "ord-003"), "approved".into());
 
     let result = compute_action_space(&contract, &facts, &entity_states, "buyer").unwrap();
 
@@ -371,14 +374,8 @@ fn action_space_per_instance() {
     let facts = serde_json::json!({});
 
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
-    entity_states.insert(
-        ("Order".to_string(), "ord-002".to_string()),
-        "submitted".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
+    entity_states.insert(instance_key("Order", "ord-002"), "submitted".into());
 
     // Buyer: submit_flow should list ord-001 as valid (draft→submitted)
     let buyer_space = compute_action_space(&contract, &facts, &entity_states, "buyer").unwrap();
@@ -457,9 +454,12 @@ fn execute_with_instance_targeting() {
         vec!["buyer"],
         vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "draft".to_string(),
             to: "submitted".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         vec!["submitted"],
     );
@@ -468,18 +468,9 @@ fn execute_with_instance_targeting() {
     let verdicts = VerdictSet::new();
 
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
-    entity_states.insert(
-        ("Order".to_string(), "ord-002".to_string()),
-        "submitted".to_string(),
-    );
-    entity_states.insert(
-        ("Order".to_string(), "ord-003".to_string()),
-        "approved".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
+    entity_states.insert(instance_key("Order", "ord-002"), "submitted".into());
+    entity_states.insert(instance_key("Order", "ord-003"), "approved".into());
 
     // Target only ord-001
     let mut bindings = InstanceBindingMap::new();
@@ -491,7 +482,9 @@ fn execute_with_instance_targeting() {
         &facts,
         &verdicts,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
+        &EntityVersions::default(),
     )
     .unwrap();
 
@@ -534,7 +527,7 @@ fn execute_with_instance_targeting() {
     );
 
     // state_before: ord-001 was draft
-    let before_key = ("Order".to_string(), "ord-001".to_string());
+    let before_key = instance_key("Order", "ord-001");
     assert_eq!(
         result
             .provenance
@@ -546,7 +539,7 @@ fn execute_with_instance_targeting() {
     );
 
     // state_after: ord-001 is now submitted
-    let after_key = ("Order".to_string(), "ord-001".to_string());
+    let after_key = instance_key("Order", "ord-001");
     assert_eq!(
         result
             .provenance
@@ -580,14 +573,8 @@ fn flow_with_instance_bindings() {
     };
 
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
-    entity_states.insert(
-        ("Order".to_string(), "ord-002".to_string()),
-        "submitted".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
+    entity_states.insert(instance_key("Order", "ord-002"), "submitted".into());
 
     // Explicitly target ord-001 for the submit flow
     let mut bindings = InstanceBindingMap::new();
@@ -598,8 +585,9 @@ fn flow_with_instance_bindings() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
 
@@ -666,10 +654,7 @@ fn missing_instance_binding_error() {
 
     // Only ord-001 exists, NO _default instance
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
 
     // Empty bindings — falls back to DEFAULT_INSTANCE_ID "_default"
     // But "_default" is not in the map → the operation step fails
@@ -680,8 +665,9 @@ fn missing_instance_binding_error() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
-        None,
+        FlowOptions::default(),
     );
 
     // The flow itself does not panic — it handles the error gracefully.
@@ -714,9 +700,12 @@ fn explicit_missing_binding_returns_entity_not_found() {
         vec!["buyer"],
         vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "draft".to_string(),
             to: "submitted".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         vec!["submitted"],
     );
@@ -726,10 +715,7 @@ fn explicit_missing_binding_returns_entity_not_found() {
 
     // Only ord-001 in the map
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
 
     // Bind to ord-999 which does not exist
     let mut bindings = InstanceBindingMap::new();
@@ -741,7 +727,9 @@ fn explicit_missing_binding_returns_entity_not_found() {
         &facts,
         &verdicts,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
+        &EntityVersions::default(),
     );
 
     assert!(result.is_err());
@@ -774,9 +762,12 @@ fn single_instance_degenerate_case() {
         vec!["buyer"],
         vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "draft".to_string(),
             to: "submitted".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         vec!["submitted"],
     );
@@ -798,7 +789,9 @@ fn single_instance_degenerate_case() {
         &facts,
         &verdicts,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
+        &EntityVersions::default(),
     )
     .unwrap();
 
@@ -890,8 +883,9 @@ fn single_instance_flow_backward_compat() {
         &contract,
         &snapshot,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &InstanceBindingMap::new(),
-        None,
+        FlowOptions::default(),
     )
     .unwrap();
 
@@ -926,9 +920,12 @@ fn instance_absence() {
         vec!["buyer"],
         vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "draft".to_string(),
             to: "submitted".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         vec!["submitted"],
     );
@@ -938,10 +935,7 @@ fn instance_absence() {
 
     // Only ord-001 exists in EntityStateMap
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
 
     // Attempt to target ord-999 — not in EntityStateMap
     let mut bindings = InstanceBindingMap::new();
@@ -953,7 +947,9 @@ fn instance_absence() {
         &facts,
         &verdicts,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
+        &EntityVersions::default(),
     );
 
     // Must fail with EntityNotFound — ord-999 does not exist per §6.5
@@ -1000,10 +996,7 @@ fn absent_instance_not_considered_for_action_space() {
 
     // EntityStateMap only has ord-002 (submitted) — no draft instances
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-002".to_string()),
-        "submitted".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-002"), "submitted".into());
 
     // For buyer: submit_flow requires Order in "draft" — no draft instance exists
     let result = compute_action_space(&contract, &facts, &entity_states, "buyer").unwrap();
@@ -1055,9 +1048,12 @@ fn operation_provenance_scoped_to_targeted_instance() {
         vec!["admin"],
         vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "submitted".to_string(),
             to: "approved".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         vec!["approved"],
     );
@@ -1066,14 +1062,8 @@ fn operation_provenance_scoped_to_targeted_instance() {
     let verdicts = VerdictSet::new();
 
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-A".to_string()),
-        "submitted".to_string(),
-    );
-    entity_states.insert(
-        ("Order".to_string(), "ord-B".to_string()),
-        "submitted".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-A"), "submitted".into());
+    entity_states.insert(instance_key("Order", "ord-B"), "submitted".into());
 
     // Target ord-A
     let mut bindings = InstanceBindingMap::new();
@@ -1085,7 +1075,9 @@ fn operation_provenance_scoped_to_targeted_instance() {
         &facts,
         &verdicts,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
+        &EntityVersions::default(),
     )
     .unwrap();
 
@@ -1104,7 +1096,7 @@ fn operation_provenance_scoped_to_targeted_instance() {
         result
             .provenance
             .state_before
-            .get(&("Order".to_string(), "ord-A".to_string()))
+            .get(&instance_key("Order", "ord-A"))
             .map(|s| s.as_str()),
         Some("submitted"),
         "state_before should capture ord-A pre-transition state"
@@ -1115,7 +1107,7 @@ fn operation_provenance_scoped_to_targeted_instance() {
         result
             .provenance
             .state_after
-            .get(&("Order".to_string(), "ord-A".to_string()))
+            .get(&instance_key("Order", "ord-A"))
             .map(|s| s.as_str()),
         Some("approved"),
         "state_after should capture ord-A post-transition state"
@@ -1126,7 +1118,7 @@ fn operation_provenance_scoped_to_targeted_instance() {
         !result
             .provenance
             .state_before
-            .contains_key(&("Order".to_string(), "ord-B".to_string())),
+            .contains_key(&instance_key("Order", "ord-B")),
         "state_before should NOT include untouched ord-B"
     );
 
@@ -1164,9 +1156,12 @@ fn precondition_failure_on_targeted_instance() {
         },
         effects: vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "draft".to_string(),
             to: "submitted".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         error_contract: vec![],
         outcomes: vec!["submitted".to_string()],
@@ -1176,10 +1171,7 @@ fn precondition_failure_on_targeted_instance() {
     let verdicts = VerdictSet::new();
 
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
 
     // Target ord-001 (correct state, authorized persona, but precondition is false)
     let mut bindings = InstanceBindingMap::new();
@@ -1191,7 +1183,9 @@ fn precondition_failure_on_targeted_instance() {
         &facts,
         &verdicts,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
+        &EntityVersions::default(),
     );
 
     assert!(
@@ -1242,9 +1236,12 @@ fn unauthorized_persona_on_instance_operation() {
         vec!["buyer"],
         vec![Effect {
             entity_id: "Order".to_string(),
+            kind: EffectKind::Transition,
             from: "draft".to_string(),
             to: "submitted".to_string(),
             outcome: None,
+            guard: None,
+            set: BTreeMap::new(),
         }],
         vec!["submitted"],
     );
@@ -1253,10 +1250,7 @@ fn unauthorized_persona_on_instance_operation() {
     let verdicts = VerdictSet::new();
 
     let mut entity_states = EntityStateMap::new();
-    entity_states.insert(
-        ("Order".to_string(), "ord-001".to_string()),
-        "draft".to_string(),
-    );
+    entity_states.insert(instance_key("Order", "ord-001"), "draft".into());
 
     // Target ord-001 (correct state, true precondition)
     let mut bindings = InstanceBindingMap::new();
@@ -1269,7 +1263,9 @@ fn unauthorized_persona_on_instance_operation() {
         &facts,
         &verdicts,
         &mut entity_states,
+        &mut EntityAttributeMap::new(),
         &bindings,
+        &EntityVersions::default(),
     );
 
     assert!(
@@ -0,0 +1,423 @@
+//! `HttpExecutor`: a `TestableExecutor` that drives a running executor over
+//! the standard Tenor HTTP API.
+//!
+//! Flow execution, simulation, action spaces and the manifest use the
+//! endpoints `tenor serve` implements:
+//!
+//! - `POST /evaluate`                  - `{bundle_id, flow_id, persona, facts, entity_states}`
+//! - `POST /flows/{flow_id}/simulate`  - `{persona_id, facts, entity_states}`
+//! - `POST /actions`                   - `{persona_id, facts, entity_states}`
+//! - `GET  /.well-known/tenor`         - TenorManifest, `ETag` header
+//!
+//! Obligations that need a stateful executor also use:
+//!
+//! - `POST /contracts`                              - body is the interchange bundle
+//! - `POST /entities/{entity_id}/instances`         - `{instance_id}`
+//! - `GET  /entities/{entity_id}/instances`         - `{instances: [instance_id | {instance_id}]}`
+//! - `GET  /entities/{entity_id}/instances/{id}`    - `{state}`, 404 if absent
+//!
+//! `tenor serve` is a stateless evaluator and does not implement these, so
+//! it fails the obligations that load contracts or manage instances.
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::traits::*;
+
+/// Drives an executor deployment at `base_url` over HTTP.
+pub struct HttpExecutor {
+    base_url: String,
+    api_key: Option<String>,
+    agent: ureq::Agent,
+    /// Bundle id of the last contract passed to `load_contract`, sent as
+    /// `bundle_id` to `/evaluate`.
+    bundle_id: Mutex<Option<String>>,
+}
+
+impl HttpExecutor {
+    /// Target the executor at `base_url`, e.g. `http://localhost:8080`.
+    pub fn new(base_url: &str) -> Self {
+        HttpExecutor {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: None,
+            agent: ureq::Agent::new_with_defaults(),
+            bundle_id: Mutex::new(None),
+        }
+    }
+
+    /// Authenticate every request with `Authorization: Bearer <key>`.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn bearer(&self) -> Option<String> {
+        self.api_key.as_ref().map(|key| format!("Bearer {}", key))
+    }
+
+    /// GET `path`, returning the status and body. Error statuses are
+    /// returned, not raised, so callers can tell 404 apart.
+    async fn get(&self, path: &str) -> ConformanceResult<(u16, Option<String>, Value)> {
+        let agent = self.agent.clone();
+        let url = self.url(path);
+        let bearer = self.bearer();
+        blocking(move || {
+            let mut request = agent.get(&url).config().http_status_as_error(false).build();
+            if let Some(bearer) = &bearer {
+                request = request.header("authorization", bearer);
+            }
+            let response = request.call().map_err(|e| request_error(&url, e))?;
+            let etag = response
+                .headers()
+                .get("etag")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim_matches('"').to_string());
+            let status = response.status().as_u16();
+            Ok((status, etag, read_body(&url, response)?))
+        })
+        .await
+    }
+
+    /// POST `body` as JSON to `path`, returning the response body. Error
+    /// statuses become errors carrying the executor's `error` message.
+    async fn post(&self, path: &str, body: Value) -> ConformanceResult<Value> {
+        let agent = self.agent.clone();
+        let url = self.url(path);
+        let bearer = self.bearer();
+        blocking(move || {
+            let mut request = agent
+                .post(&url)
+                .config()
+                .http_status_as_error(false)
+                .build();
+            if let Some(bearer) = &bearer {
+                request = request.header("authorization", bearer);
+            }
+            let response = request
+                .send_json(&body)
+                .map_err(|e| request_error(&url, e))?;
+            let status = response.status().as_u16();
+            let body = read_body(&url, response)?;
+            check_status(&url, status, body)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl TestableExecutor for HttpExecutor {
+    async fn load_contract(&self, bundle: &Value) -> ConformanceResult<()> {
+        let id = bundle
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| error("bundle has no 'id'".to_string()))?;
+        self.post("/contracts", bundle.clone()).await?;
+        *self.bundle_id.lock().expect("bundle_id lock poisoned") = Some(id.to_string());
+        Ok(())
+    }
+
+    async fn execute_flow(
+        &self,
+        flow_id: &str,
+        persona: &str,
+        facts: &Value,
+        entity_states: &Value,
+    ) -> ConformanceResult<FlowResult> {
+        let bundle_id = self
+            .bundle_id
+            .lock()
+            .expect("bundle_id lock poisoned")
+            .clone()
+            .ok_or_else(|| error("no contract loaded".to_string()))?;
+        let response = self
+            .post(
+                "/evaluate",
+                json!({
+                    "bundle_id": bundle_id,
+                    "flow_id": flow_id,
+                    "persona": persona,
+                    "facts": facts,
+                    "entity_states": entity_states,
+                }),
+            )
+            .await?;
+        Ok(evaluate_flow_result(&response))
+    }
+
+    async fn simulate_flow(
+        &self,
+        flow_id: &str,
+        persona: &str,
+        facts: &Value,
+        entity_states: &Value,
+    ) -> ConformanceResult<FlowResult> {
+        let response = self
+            .post(
+                &format!("/flows/{}/simulate", flow_id),
+                json!({
+                    "persona_id": persona,
+                    "facts": facts,
+                    "entity_states": simulate_entity_states(entity_states),
+                }),
+            )
+            .await?;
+        Ok(simulate_flow_result(&response))
+    }
+
+    async fn get_entity_state(
+        &self,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> ConformanceResult<Option<String>> {
+        let path = format!("/entities/{}/instances/{}", entity_id, instance_id);
+        let (status, _, body) = self.get(&path).await?;
+        if status == 404 {
+            return Ok(None);
+        }
+        let body = check_status(&self.url(&path), status, body)?;
+        Ok(body.get("state").and_then(|v| v.as_str()).map(String::from))
+    }
+
+    async fn get_action_space(
+        &self,
+        persona: &str,
+        facts: &Value,
+        entity_states: &Value,
+    ) -> ConformanceResult<ActionSpace> {
+        let response = self
+            .post(
+                "/actions",
+                json!({
+                    "persona_id": persona,
+                    "facts": facts,
+                    "entity_states": entity_states,
+                }),
+            )
+            .await?;
+        Ok(action_space(&response))
+    }
+
+    async fn get_manifest(&self) -> ConformanceResult<TenorManifest> {
+        let path = "/.well-known/tenor";
+        let (status, etag_header, body) = self.get(path).await?;
+        let body = check_status(&self.url(path), status, body)?;
+        let str_field = |name: &str| body.get(name).and_then(|v| v.as_str()).map(String::from);
+        Ok(TenorManifest {
+            bundle: body.get("bundle").cloned().unwrap_or(Value::Null),
+            etag: str_field("etag").or(etag_header).unwrap_or_default(),
+            tenor: str_field("tenor").unwrap_or_default(),
+            capabilities: body.get("capabilities").cloned().unwrap_or(Value::Null),
+            trust: body.get("trust").filter(|v| !v.is_null()).cloned(),
+        })
+    }
+
+    async fn is_trust_configured(&self) -> bool {
+        matches!(self.get_manifest().await, Ok(m) if m.trust.is_some())
+    }
+
+    async fn create_instance(&self, entity_id: &str, instance_id: &str) -> ConformanceResult<()> {
+        self.post(
+            &format!("/entities/{}/instances", entity_id),
+            json!({ "instance_id": instance_id }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn list_instances(&self, entity_id: &str) -> ConformanceResult<Vec<String>> {
+        let path = format!("/entities/{}/instances", entity_id);
+        let (status, _, body) = self.get(&path).await?;
+        let body = check_status(&self.url(&path), status, body)?;
+        Ok(body
+            .get("instances")
+            .and_then(|v| v.as_array())
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| {
+                        id.as_str()
+                            .or_else(|| id.get("instance_id").and_then(|v| v.as_str()))
+                            .map(String::from)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+// ──────────────────────────────────────────────
+// Response mapping
+// ──────────────────────────────────────────────
+
+/// `/evaluate` flow response: `entity_state_changes` use `from`/`to`.
+fn evaluate_flow_result(response: &Value) -> FlowResult {
+    FlowResult {
+        outcome: str_at(response, "outcome"),
+        entity_state_changes: state_changes(response.get("entity_state_changes")),
+        steps_executed: steps(response.get("steps_executed"), "step_id", "result"),
+        provenance: response
+            .get("provenance")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
+
+/// `/flows/{id}/simulate` response: the path and the transitions that
+/// would have happened.
+fn simulate_flow_result(response: &Value) -> FlowResult {
+    FlowResult {
+        outcome: str_at(response, "outcome"),
+        entity_state_changes: state_changes(response.get("would_transition")),
+        steps_executed: steps(response.get("path"), "step", "outcome"),
+        provenance: Vec::new(),
+    }
+}
+
+/// One available operation per entity instance the action's entry
+/// operation can bind to, or one unbound entry if it binds none.
+fn action_space(response: &Value) -> ActionSpace {
+    let mut available_operations = Vec::new();
+    for action in response
+        .get("actions")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let operation_id = str_at(action, "entry_operation_id");
+        let persona = str_at(action, "persona_id");
+        let mut bound = false;
+        for (entity_id, instances) in action
+            .get("instance_bindings")
+            .and_then(|v| v.as_object())
+            .into_iter()
+            .flatten()
+        {
+            for instance_id in instances.as_array().into_iter().flatten() {
+                bound = true;
+                available_operations.push(AvailableOperation {
+                    operation_id: operation_id.clone(),
+                    entity_id: entity_id.clone(),
+                    instance_id: instance_id.as_str().unwrap_or_default().to_string(),
+                    persona: persona.clone(),
+                });
+            }
+        }
+        if !bound {
+            available_operations.push(AvailableOperation {
+                operation_id,
+                entity_id: String::new(),
+                instance_id: String::new(),
+                persona,
+            });
+        }
+    }
+    ActionSpace {
+        available_operations,
+    }
+}
+
+/// The simulate endpoint takes `{"Order": {"state": "draft"}}` where the
+/// suite uses `{"Order": "draft"}`.
+fn simulate_entity_states(entity_states: &Value) -> Value {
+    match entity_states.as_object() {
+        Some(obj) => obj
+            .iter()
+            .map(|(entity_id, state)| match state {
+                Value::String(_) => (entity_id.clone(), json!({ "state": state })),
+                _ => (entity_id.clone(), state.clone()),
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        None => json!({}),
+    }
+}
+
+fn state_changes(changes: Option<&Value>) -> Vec<EntityStateChange> {
+    changes
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(|c| EntityStateChange {
+            entity_id: str_at(c, "entity_id"),
+            instance_id: c
+                .get("instance_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("_default")
+                .to_string(),
+            from_state: str_at(c, "from"),
+            to_state: str_at(c, "to"),
+        })
+        .collect()
+}
+
+fn steps(steps: Option<&Value>, id_key: &str, result_key: &str) -> Vec<StepResult> {
+    steps
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(|s| StepResult {
+            step_id: str_at(s, id_key),
+            result: str_at(s, result_key),
+        })
+        .collect()
+}
+
+fn str_at(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+// ──────────────────────────────────────────────
+// Transport
+// ──────────────────────────────────────────────
+
+/// ureq is synchronous; run requests off the async runtime.
+async fn blocking<T, F>(f: F) -> ConformanceResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> ConformanceResult<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| error(format!("task join error: {}", e)))?
+}
+
+fn read_body(url: &str, response: ureq::http::Response<ureq::Body>) -> ConformanceResult<Value> {
+    let text = response
+        .into_body()
+        .read_to_string()
+        .map_err(|e| error(format!("{}: could not read response: {}", url, e)))?;
+    if text.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(&text).map_err(|e| error(format!("{}: invalid JSON response: {}", url, e)))
+}
+
+fn check_status(url: &str, status: u16, body: Value) -> ConformanceResult<Value> {
+    if (200..300).contains(&status) {
+        return Ok(body);
+    }
+    let detail = body
+        .get("error")
+        .and_then(|v| v.as_str())
+        .map(|msg| format!(": {}", msg))
+        .unwrap_or_default();
+    Err(error(format!("{} returned HTTP {}{}", url, status, detail)))
+}
+
+fn request_error(url: &str, e: ureq::Error) -> ConformanceError {
+    error(format!("{}: request failed: {}", url, e))
+}
+
+fn error(message: String) -> ConformanceError {
+    ConformanceError { message }
+}
@@ -3,10 +3,18 @@
 //! Provides a `TestableExecutor` trait and `executor_conformance_tests!`
 //! macro for validating any executor implementation against the full
 //! set of executor obligations from the Tenor specification (Section 17).
+//! `HttpExecutor` runs the suite against a deployed executor over the
+//! standard HTTP API; `tenor conformance --url` wraps it. `ConformanceReport`
+//! renders the results as JSON or JUnit XML.
 
 pub mod fixtures;
+pub mod http;
+pub mod report;
 pub mod suite;
 pub mod tests;
 pub mod traits;
 
+pub use http::HttpExecutor;
+pub use report::{ConformanceReport, TestReport};
+pub use suite::{run_suite, ObligationResult};
 pub use traits::*;
@@ -0,0 +1,129 @@
+//! Machine-readable conformance reports.
+//!
+//! A `ConformanceReport` collects the outcome of every obligation from
+//! [`run_suite`](crate::suite::run_suite) and serializes it as JSON or as
+//! JUnit XML for CI systems.
+
+use serde::Serialize;
+
+use crate::suite::ObligationResult;
+
+/// The outcome of a conformance run against one executor.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConformanceReport {
+    /// Identifies the executor under test, e.g. its base URL.
+    pub executor: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub duration_ms: u64,
+    pub tests: Vec<TestReport>,
+}
+
+/// The outcome of one obligation.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestReport {
+    /// Obligation identifier, `E1` through `E20`.
+    pub obligation: String,
+    pub name: String,
+    /// Specification section defining the obligation, e.g. `§17.2`.
+    pub section: String,
+    pub passed: bool,
+    /// Failure message; `None` when the obligation passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl ConformanceReport {
+    pub fn new(executor: impl Into<String>, results: Vec<ObligationResult>) -> Self {
+        let tests: Vec<TestReport> = results
+            .into_iter()
+            .map(|r| TestReport {
+                obligation: r.id.to_string(),
+                name: r.name.to_string(),
+                section: r.section.to_string(),
+                passed: r.result.is_ok(),
+                message: r.result.err(),
+                duration_ms: r.duration.as_millis() as u64,
+            })
+            .collect();
+        let passed = tests.iter().filter(|t| t.passed).count();
+        ConformanceReport {
+            executor: executor.into(),
+            passed,
+            failed: tests.len() - passed,
+            duration_ms: tests.iter().map(|t| t.duration_ms).sum(),
+            tests,
+        }
+    }
+
+    /// Whether every obligation passed.
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("ConformanceReport serializes to JSON")
+    }
+
+    /// Render as a JUnit XML document with one `<testcase>` per obligation.
+    pub fn to_junit_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"tenor-executor-conformance\" tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+            self.tests.len(),
+            self.failed,
+            seconds(self.duration_ms)
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+            escape(&self.executor),
+            self.tests.len(),
+            self.failed,
+            seconds(self.duration_ms)
+        ));
+        for test in &self.tests {
+            let open = format!(
+                "    <testcase classname=\"{}\" name=\"{}: {}\" time=\"{}\"",
+                escape(&test.section),
+                escape(&test.obligation),
+                escape(&test.name),
+                seconds(test.duration_ms)
+            );
+            match &test.message {
+                None => xml.push_str(&format!("{}/>\n", open)),
+                Some(message) => {
+                    xml.push_str(&format!("{}>\n", open));
+                    xml.push_str(&format!(
+                        "      <failure message=\"{}\"/>\n",
+                        escape(message)
+                    ));
+                    xml.push_str("    </testcase>\n");
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+/// Escape text for use in an XML attribute value.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
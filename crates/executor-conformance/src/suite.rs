@@ -193,3 +193,155 @@ macro_rules! executor_conformance_tests {
         }
     };
 }
+
+/// The outcome of one executor obligation from [`run_suite`].
+#[derive(Debug, Clone)]
+pub struct ObligationResult {
+    /// Obligation identifier, `E1` through `E20`.
+    pub id: &'static str,
+    /// Short description of the obligation.
+    pub name: &'static str,
+    /// Specification section defining the obligation, e.g. `§17.2`.
+    pub section: &'static str,
+    /// Wall-clock time the obligation's test took.
+    pub duration: std::time::Duration,
+    /// `Err` carries the failure message reported by the test.
+    pub result: Result<(), String>,
+}
+
+impl ObligationResult {
+    pub fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// The specification section that defines obligation `id`: E10-E14 are
+/// the manifest obligations of §19.7, E18-E20 the trust obligations of
+/// §17.4, and the rest the core executor obligations of §17.2.
+pub fn obligation_section(id: &str) -> &'static str {
+    match id {
+        "E10" | "E11" | "E12" | "E13" | "E14" => "§19.7",
+        "E18" | "E19" | "E20" => "§17.4",
+        _ => "§17.2",
+    }
+}
+
+/// Run E1-E20 in order against a single executor and collect every
+/// outcome, for harnesses that report results instead of panicking
+/// (e.g. `tenor conformance --url`).
+///
+/// Unlike `executor_conformance_tests!`, every obligation runs against the
+/// same executor, so a deployment must tolerate contracts and instances
+/// left behind by earlier obligations.
+pub async fn run_suite<E: crate::traits::TestableExecutor>(executor: &E) -> Vec<ObligationResult> {
+    use crate::tests::*;
+
+    let mut results = Vec::with_capacity(20);
+    macro_rules! run {
+        ($id:literal, $name:literal, $test:path) => {
+            let started = std::time::Instant::now();
+            let result = $test(executor).await;
+            results.push(ObligationResult {
+                id: $id,
+                name: $name,
+                section: obligation_section($id),
+                duration: started.elapsed(),
+                result,
+            });
+        };
+    }
+
+    run!(
+        "E1",
+        "fact source",
+        e01_fact_source::test_e01_fact_source_only
+    );
+    run!(
+        "E2",
+        "transition validation",
+        e02_transition_validation::test_e02_transition_validation
+    );
+    run!("E3", "atomicity", e03_atomicity::test_e03_atomicity);
+    run!(
+        "E4",
+        "snapshot isolation",
+        e04_snapshot_isolation::test_e04_snapshot_isolation
+    );
+    run!(
+        "E5",
+        "sub-flow snapshot",
+        e05_subflow_snapshot::test_e05_subflow_snapshot
+    );
+    run!(
+        "E6",
+        "DateTime UTC normalization",
+        e06_datetime_utc::test_e06_datetime_utc
+    );
+    run!(
+        "E7",
+        "numeric model",
+        e07_numeric_model::test_e07_numeric_model
+    );
+    run!(
+        "E8",
+        "branch isolation",
+        e08_branch_isolation::test_e08_branch_isolation
+    );
+    run!(
+        "E9",
+        "join completion",
+        e09_join_completion::test_e09_join_completion
+    );
+    run!(
+        "E10",
+        "manifest endpoint",
+        e10_manifest_endpoint::test_e10_manifest_endpoint
+    );
+    run!(
+        "E11",
+        "manifest bundle completeness",
+        e11_manifest_bundle::test_e11_manifest_bundle_complete
+    );
+    run!(
+        "E12",
+        "ETag stability",
+        e12_etag_stability::test_e12_etag_stability
+    );
+    run!("E13", "dry-run", e13_dry_run::test_e13_dry_run);
+    run!(
+        "E14",
+        "capability advertisement",
+        e14_capability_advertisement::test_e14_capability_advertisement
+    );
+    run!(
+        "E15",
+        "instance creation",
+        e15_instance_creation::test_e15_instance_creation
+    );
+    run!(
+        "E16",
+        "instance identity",
+        e16_instance_identity::test_e16_instance_identity
+    );
+    run!(
+        "E17",
+        "instance enumeration",
+        e17_instance_enumeration::test_e17_instance_enumeration
+    );
+    run!(
+        "E18",
+        "artifact integrity",
+        e18_artifact_integrity::test_e18_artifact_integrity
+    );
+    run!(
+        "E19",
+        "provenance authenticity",
+        e19_provenance_authenticity::test_e19_provenance_authenticity
+    );
+    run!(
+        "E20",
+        "trust domain",
+        e20_trust_domain::test_e20_trust_domain
+    );
+    results
+}
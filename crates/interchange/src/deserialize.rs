@@ -19,6 +19,16 @@ pub enum InterchangeError {
     },
     /// The bundle structure is invalid.
     InvalidBundle(String),
+    /// The bundle could not be decoded from (or encoded to) a binary encoding.
+    Encoding(String),
+    /// The bundle's `tenor_version` cannot be read or upgraded by this crate.
+    UnsupportedVersion { version: String, supported: String },
+    /// Strict mode: a field not defined for its object. `pointer` is the
+    /// JSON pointer (RFC 6901) of the field.
+    UnknownField { pointer: String },
+    /// Strict mode: an unrecognized construct, step, handler or type kind.
+    /// `pointer` is the JSON pointer of the discriminant field.
+    UnknownKind { pointer: String, kind: String },
 }
 
 impl fmt::Display for InterchangeError {
@@ -33,6 +43,18 @@ impl fmt::Display for InterchangeError {
             InterchangeError::InvalidBundle(msg) => {
                 write!(f, "invalid bundle: {}", msg)
             }
+            InterchangeError::Encoding(msg) => write!(f, "{}", msg),
+            InterchangeError::UnsupportedVersion { version, supported } => write!(
+                f,
+                "unsupported interchange version '{}' (this build reads up to {})",
+                version, supported
+            ),
+            InterchangeError::UnknownField { pointer } => {
+                write!(f, "unknown field at {}", pointer)
+            }
+            InterchangeError::UnknownKind { pointer, kind } => {
+                write!(f, "unknown kind '{}' at {}", kind, pointer)
+            }
         }
     }
 }
@@ -42,8 +64,16 @@ impl std::error::Error for InterchangeError {}
 /// Deserialize an interchange JSON bundle into typed structs.
 ///
 /// Walks the `constructs` array and dispatches on the `kind` field.
-/// Unknown construct kinds are silently skipped for forward compatibility.
+/// Unknown construct kinds are silently skipped and unknown fields ignored
+/// for forward compatibility; see [`crate::from_interchange_strict`] to
+/// reject them instead.
+/// Bundles of an older interchange minor version are upgraded first (see
+/// [`crate::migrate`]), then a type table, if present, is inlined.
 pub fn from_interchange(bundle: &serde_json::Value) -> Result<InterchangeBundle, InterchangeError> {
+    let bundle = crate::migrate::upgrade_bundle(bundle)?;
+    let bundle = crate::type_table::inline_type_table(bundle.as_ref())?;
+    let bundle = bundle.as_ref();
+
     let id = bundle
         .get("id")
         .and_then(|v| v.as_str())
@@ -85,6 +115,8 @@ pub fn from_interchange(bundle: &serde_json::Value) -> Result<InterchangeBundle,
             "Persona" => Some(InterchangeConstruct::Persona(parse_persona(obj)?)),
             "Source" => Some(InterchangeConstruct::Source(parse_source(obj)?)),
             "System" => Some(InterchangeConstruct::System(parse_system(obj)?)),
+            "Test" => Some(InterchangeConstruct::Test(parse_test(obj)?)),
+            "Assert" => Some(InterchangeConstruct::Assert(parse_assert(obj)?)),
             "TypeDecl" => Some(InterchangeConstruct::TypeDecl(parse_type_decl(obj)?)),
             _ => None, // Skip unknown kinds for forward compatibility
         };
@@ -96,12 +128,23 @@ pub fn from_interchange(bundle: &serde_json::Value) -> Result<InterchangeBundle,
 
     let trust = parse_trust_metadata(bundle);
 
+    let requires = bundle
+        .get("requires")
+        .and_then(|r| r.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|f| f.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
     Ok(InterchangeBundle {
         id,
         tenor,
         tenor_version,
         constructs,
         trust,
+        requires,
     })
 }
 
@@ -118,7 +161,20 @@ fn parse_provenance(obj: &serde_json::Value) -> Option<Provenance> {
     let prov = obj.get("provenance")?;
     let file = prov.get("file")?.as_str()?.to_string();
     let line = prov.get("line")?.as_u64()?;
-    Some(Provenance { file, line })
+    let col = prov.get("col").and_then(|v| v.as_u64());
+    let len = prov.get("len").and_then(|v| v.as_u64());
+    Some(Provenance {
+        file,
+        line,
+        col,
+        len,
+    })
+}
+
+fn parse_annotations(obj: &serde_json::Value) -> Annotations {
+    obj.get("annotations")
+        .and_then(|a| serde_json::from_value(a.clone()).ok())
+        .unwrap_or_default()
 }
 
 fn parse_tenor(obj: &serde_json::Value) -> Option<String> {
@@ -127,6 +183,17 @@ fn parse_tenor(obj: &serde_json::Value) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// An optional JSON object field as a name-to-value map.
+fn parse_value_map(
+    obj: &serde_json::Value,
+    field: &str,
+) -> std::collections::BTreeMap<String, serde_json::Value> {
+    obj.get(field)
+        .and_then(|m| m.as_object())
+        .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default()
+}
+
 fn parse_trust_metadata(obj: &serde_json::Value) -> Option<TrustMetadata> {
     let trust = obj.get("trust")?;
     if trust.is_null() {
@@ -167,6 +234,7 @@ fn parse_fact(obj: &serde_json::Value) -> Result<FactConstruct, InterchangeError
         source,
         default,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -206,12 +274,15 @@ fn parse_entity(obj: &serde_json::Value) -> Result<EntityConstruct, InterchangeE
                 .filter_map(|t| {
                     let from = t.get("from")?.as_str()?.to_string();
                     let to = t.get("to")?.as_str()?.to_string();
-                    Some(Transition { from, to })
+                    let guard = t.get("guard").filter(|g| !g.is_null()).cloned();
+                    Some(Transition { from, to, guard })
                 })
                 .collect::<Vec<_>>()
         })
         .unwrap_or_default();
 
+    let attributes = parse_value_map(obj, "attributes");
+
     let parent = obj
         .get("parent")
         .and_then(|p| p.as_str())
@@ -225,8 +296,10 @@ fn parse_entity(obj: &serde_json::Value) -> Result<EntityConstruct, InterchangeE
         states,
         initial,
         transitions,
+        attributes,
         parent,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -259,6 +332,7 @@ fn parse_rule(obj: &serde_json::Value) -> Result<RuleConstruct, InterchangeError
         stratum,
         body,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -287,17 +361,25 @@ fn parse_operation(obj: &serde_json::Value) -> Result<OperationConstruct, Interc
             arr.iter()
                 .filter_map(|e| {
                     let entity_id = e.get("entity_id")?.as_str()?.to_string();
-                    let from = e.get("from")?.as_str()?.to_string();
-                    let to = e.get("to")?.as_str()?.to_string();
+                    let kind = e.get("kind").and_then(|k| k.as_str()).map(String::from);
+                    let state = |field: &str| e.get(field).and_then(|s| s.as_str());
+                    let (from, to) = match kind.as_deref() {
+                        Some("create_instance") => (String::new(), String::new()),
+                        Some("destroy_instance") => (state("from")?.to_string(), String::new()),
+                        _ => (state("from")?.to_string(), state("to")?.to_string()),
+                    };
                     let outcome = e
                         .get("outcome")
                         .and_then(|o| o.as_str())
                         .map(|s| s.to_string());
+                    let set = parse_value_map(e, "set");
                     Some(Effect {
                         entity_id,
+                        kind,
                         from,
                         to,
                         outcome,
+                        set,
                     })
                 })
                 .collect::<Vec<_>>()
@@ -329,6 +411,7 @@ fn parse_operation(obj: &serde_json::Value) -> Result<OperationConstruct, Interc
         outcomes,
         error_contract,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -367,6 +450,7 @@ fn parse_flow(obj: &serde_json::Value) -> Result<FlowConstruct, InterchangeError
         steps,
         snapshot,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -379,6 +463,7 @@ fn parse_persona(obj: &serde_json::Value) -> Result<PersonaConstruct, Interchang
     Ok(PersonaConstruct {
         id,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -411,6 +496,58 @@ fn parse_source(obj: &serde_json::Value) -> Result<SourceConstruct, InterchangeE
         fields,
         description,
         provenance,
+        annotations: parse_annotations(obj),
+        tenor,
+    })
+}
+
+fn parse_test(obj: &serde_json::Value) -> Result<TestConstruct, InterchangeError> {
+    let id = required_str(obj, "id")?;
+
+    let given = obj
+        .get("given")
+        .and_then(|g| g.as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    let expect = obj
+        .get("expect")
+        .cloned()
+        .map(serde_json::from_value::<Vec<TestExpectation>>)
+        .transpose()
+        .map_err(|e| InterchangeError::ConstructError {
+            kind: "Test".to_string(),
+            id: id.clone(),
+            message: format!("invalid 'expect': {}", e),
+        })?
+        .unwrap_or_default();
+
+    let provenance = parse_provenance(obj);
+    let tenor = parse_tenor(obj);
+
+    Ok(TestConstruct {
+        id,
+        given,
+        expect,
+        provenance,
+        annotations: parse_annotations(obj),
+        tenor,
+    })
+}
+
+fn parse_assert(obj: &serde_json::Value) -> Result<AssertConstruct, InterchangeError> {
+    let id = required_str(obj, "id")?;
+    let entity = required_str(obj, "entity")?;
+    let property = required_str(obj, "property")?;
+    let provenance = parse_provenance(obj);
+    let tenor = parse_tenor(obj);
+
+    Ok(AssertConstruct {
+        id,
+        entity,
+        property,
+        provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -505,6 +642,7 @@ fn parse_system(obj: &serde_json::Value) -> Result<SystemConstruct, InterchangeE
         flow_triggers,
         shared_entities,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -519,6 +657,7 @@ fn parse_type_decl(obj: &serde_json::Value) -> Result<TypeDeclConstruct, Interch
         id,
         type_def,
         provenance,
+        annotations: parse_annotations(obj),
         tenor,
     })
 }
@@ -544,7 +683,8 @@ mod tests {
         let result = from_interchange(&bundle).unwrap();
         assert_eq!(result.id, "test-bundle");
         assert_eq!(result.tenor, "1.0");
-        assert_eq!(result.tenor_version, "1.0.0");
+        // 1.0.0 bundles are upgraded on load
+        assert_eq!(result.tenor_version, "1.1.0");
         assert!(result.constructs.is_empty());
     }
 
@@ -606,7 +746,7 @@ mod tests {
             "states": ["draft", "submitted", "approved"],
             "transitions": [
                 {"from": "draft", "to": "submitted"},
-                {"from": "submitted", "to": "approved"}
+                {"from": "submitted", "to": "approved", "guard": {"verdict_present": "reviewed"}}
             ],
             "provenance": {"file": "test.tenor", "line": 1},
             "tenor": "1.0"
@@ -622,6 +762,12 @@ mod tests {
                 assert_eq!(e.transitions.len(), 2);
                 assert_eq!(e.transitions[0].from, "draft");
                 assert_eq!(e.transitions[0].to, "submitted");
+                assert!(e.transitions[0].guard.is_none());
+                assert_eq!(
+                    e.transitions[1].guard,
+                    Some(json!({"verdict_present": "reviewed"}))
+                );
+                assert!(e.attributes.is_empty());
                 assert!(e.parent.is_none());
             }
             other => panic!("expected Entity, got {:?}", other),
@@ -698,6 +844,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_entity_attributes_and_effect_set() {
+        let bundle = make_bundle(vec![
+            json!({
+                "id": "Order",
+                "kind": "Entity",
+                "initial": "draft",
+                "states": ["draft", "submitted"],
+                "transitions": [{"from": "draft", "to": "submitted"}],
+                "attributes": {"total": {"base": "Int", "min": 0, "max": 1000}},
+                "provenance": {"file": "test.tenor", "line": 1},
+                "tenor": "1.0"
+            }),
+            json!({
+                "id": "submit",
+                "kind": "Operation",
+                "allowed_personas": ["buyer"],
+                "precondition": null,
+                "effects": [
+                    {"entity_id": "Order", "from": "draft", "to": "submitted",
+                     "set": {"total": {"fact_ref": "order_total"}}}
+                ],
+                "error_contract": [],
+                "provenance": {"file": "test.tenor", "line": 8},
+                "tenor": "1.0"
+            }),
+        ]);
+
+        let result = from_interchange(&bundle).unwrap();
+        match &result.constructs[0] {
+            InterchangeConstruct::Entity(e) => {
+                assert_eq!(e.attributes["total"]["base"], "Int");
+            }
+            other => panic!("expected Entity, got {:?}", other),
+        }
+        match &result.constructs[1] {
+            InterchangeConstruct::Operation(op) => {
+                assert_eq!(
+                    op.effects[0].set.get("total"),
+                    Some(&json!({"fact_ref": "order_total"}))
+                );
+            }
+            other => panic!("expected Operation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_instance_lifecycle_effects() {
+        let bundle = make_bundle(vec![json!({
+            "id": "open_and_close",
+            "kind": "Operation",
+            "allowed_personas": ["clerk"],
+            "precondition": null,
+            "effects": [
+                {"entity_id": "Order", "kind": "create_instance"},
+                {"entity_id": "Ticket", "kind": "destroy_instance", "from": "closed"}
+            ],
+            "error_contract": [],
+            "provenance": {"file": "test.tenor", "line": 1},
+            "tenor": "1.0"
+        })]);
+
+        let result = from_interchange(&bundle).unwrap();
+        match &result.constructs[0] {
+            InterchangeConstruct::Operation(op) => {
+                assert_eq!(op.effects.len(), 2);
+                assert_eq!(op.effects[0].kind.as_deref(), Some("create_instance"));
+                assert_eq!(op.effects[0].from, "");
+                assert_eq!(op.effects[1].kind.as_deref(), Some("destroy_instance"));
+                assert_eq!(op.effects[1].from, "closed");
+                assert_eq!(op.effects[1].to, "");
+            }
+            other => panic!("expected Operation, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_parse_operation_null_precondition() {
         let bundle = make_bundle(vec![json!({
@@ -768,6 +990,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_annotations() {
+        let bundle = make_bundle(vec![
+            json!({
+                "annotations": {"owner": "payments-team", "tags": ["payments", "pci"]},
+                "id": "admin",
+                "kind": "Persona",
+                "provenance": {"file": "test.tenor", "line": 2},
+                "tenor": "1.0"
+            }),
+            json!({
+                "id": "clerk",
+                "kind": "Persona",
+                "provenance": {"file": "test.tenor", "line": 3},
+                "tenor": "1.0"
+            }),
+        ]);
+
+        let result = from_interchange(&bundle).unwrap();
+        let admin = result.constructs[0].annotations();
+        assert_eq!(admin.owner.as_deref(), Some("payments-team"));
+        assert_eq!(admin.description, None);
+        assert!(admin.has_tag("pci") && !admin.has_tag("pc"));
+        assert!(result.constructs[1].annotations().is_empty());
+
+        let summary = crate::build_inspect(&bundle).unwrap();
+        assert_eq!(
+            summary["personas"][0]["annotations"],
+            json!({"owner": "payments-team", "tags": ["payments", "pci"]})
+        );
+        assert!(summary["personas"][1].get("annotations").is_none());
+    }
+
     #[test]
     fn test_parse_system() {
         let bundle = make_bundle(vec![json!({
@@ -904,6 +1159,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_test() {
+        let bundle = make_bundle(vec![json!({
+            "expect": [
+                {"present": false, "verdict": "within_limit"},
+                {"present": true, "verdict": "over_limit"}
+            ],
+            "given": {"balance": {"amount": "1200.00", "currency": "USD"}},
+            "id": "rejects_over_limit",
+            "kind": "Test",
+            "provenance": {"file": "limits.tenor", "line": 24},
+            "tenor": "1.0"
+        })]);
+
+        let result = from_interchange(&bundle).unwrap();
+        match &result.constructs[0] {
+            InterchangeConstruct::Test(t) => {
+                assert_eq!(t.id, "rejects_over_limit");
+                assert_eq!(t.given["balance"]["currency"], "USD");
+                assert_eq!(t.expect.len(), 2);
+                assert_eq!(t.expect[0].verdict, "within_limit");
+                assert!(!t.expect[0].present);
+                assert!(t.expect[1].present);
+            }
+            other => panic!("expected Test, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_assert() {
+        let bundle = make_bundle(vec![json!({
+            "entity": "Order",
+            "id": "Order.reachable",
+            "kind": "Assert",
+            "property": "reachable",
+            "provenance": {"file": "orders.tenor", "line": 30},
+            "tenor": "1.0"
+        })]);
+
+        let result = from_interchange(&bundle).unwrap();
+        match &result.constructs[0] {
+            InterchangeConstruct::Assert(a) => {
+                assert_eq!(a.id, "Order.reachable");
+                assert_eq!(a.entity, "Order");
+                assert_eq!(a.property, "reachable");
+            }
+            other => panic!("expected Assert, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_parse_source_minimal() {
         let bundle = make_bundle(vec![json!({
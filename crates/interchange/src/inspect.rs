@@ -1,7 +1,19 @@
-use tenor_interchange::InterchangeConstruct;
+//! Construct summary of an interchange bundle.
+//!
+//! [`build_inspect`] condenses a bundle into the facts, entities, rules,
+//! personas, operations and flows it declares, with their annotations but
+//! without the full predicate and step trees. It backs `tenor inspect` and the WASM
+//! `inspect_contract` binding.
 
-pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, String> {
-    let parsed = tenor_interchange::from_interchange(bundle).map_err(|e| e.to_string())?;
+use crate::{
+    from_interchange, inline_type_table, Annotations, InterchangeConstruct, InterchangeError,
+};
+
+/// Summarize the constructs of `bundle`. Shared type references are
+/// inlined first, so fact types are always reported in full.
+pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, InterchangeError> {
+    let bundle = inline_type_table(bundle)?;
+    let parsed = from_interchange(&bundle)?;
 
     let mut facts = Vec::new();
     let mut entities = Vec::new();
@@ -29,7 +41,7 @@ pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, St
                     fact_json["has_default"] = serde_json::json!(true);
                 }
                 fact_json["type_spec"] = f.fact_type.clone();
-                facts.push(fact_json);
+                facts.push(annotate(fact_json, &f.annotations));
             }
             InterchangeConstruct::Entity(e) => {
                 let transitions: Vec<serde_json::Value> = e
@@ -37,12 +49,15 @@ pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, St
                     .iter()
                     .map(|t| serde_json::json!({ "from": t.from, "to": t.to }))
                     .collect();
-                entities.push(serde_json::json!({
-                    "id": e.id,
-                    "states": e.states,
-                    "initial": e.initial,
-                    "transitions": transitions,
-                }));
+                entities.push(annotate(
+                    serde_json::json!({
+                        "id": e.id,
+                        "states": e.states,
+                        "initial": e.initial,
+                        "transitions": transitions,
+                    }),
+                    &e.annotations,
+                ));
             }
             InterchangeConstruct::Rule(r) => {
                 let condition_summary = r.when().map(summarize_condition).unwrap_or_default();
@@ -51,26 +66,33 @@ pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, St
                     .and_then(|p| p.get("verdict_type"))
                     .and_then(|v| v.as_str())
                     .unwrap_or("?");
-                rules.push(serde_json::json!({
-                    "id": r.id,
-                    "stratum": r.stratum,
-                    "produces": verdict_type,
-                    "condition_summary": condition_summary,
-                }));
+                rules.push(annotate(
+                    serde_json::json!({
+                        "id": r.id,
+                        "stratum": r.stratum,
+                        "produces": verdict_type,
+                        "condition_summary": condition_summary,
+                    }),
+                    &r.annotations,
+                ));
             }
             InterchangeConstruct::Persona(p) => {
-                personas.push(serde_json::json!({ "id": p.id }));
+                personas.push(annotate(serde_json::json!({ "id": p.id }), &p.annotations));
             }
             InterchangeConstruct::Operation(op) => {
                 let effects: Vec<serde_json::Value> = op
                     .effects
                     .iter()
                     .map(|e| {
-                        serde_json::json!({
+                        let mut effect = serde_json::json!({
                             "entity_id": e.entity_id,
                             "from": e.from,
                             "to": e.to,
-                        })
+                        });
+                        if let Some(kind) = &e.kind {
+                            effect["kind"] = serde_json::json!(kind);
+                        }
+                        effect
                     })
                     .collect();
                 let precondition_summary = op
@@ -78,13 +100,16 @@ pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, St
                     .as_ref()
                     .map(summarize_condition)
                     .unwrap_or_else(|| "none".to_string());
-                operations.push(serde_json::json!({
-                    "id": op.id,
-                    "allowed_personas": op.allowed_personas,
-                    "effects": effects,
-                    "precondition_summary": precondition_summary,
-                    "outcomes": op.outcomes,
-                }));
+                operations.push(annotate(
+                    serde_json::json!({
+                        "id": op.id,
+                        "allowed_personas": op.allowed_personas,
+                        "effects": effects,
+                        "precondition_summary": precondition_summary,
+                        "outcomes": op.outcomes,
+                    }),
+                    &op.annotations,
+                ));
             }
             InterchangeConstruct::Flow(f) => {
                 let step_ids: Vec<&str> = f
@@ -92,14 +117,19 @@ pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, St
                     .iter()
                     .filter_map(|s| s.get("id").and_then(|v| v.as_str()))
                     .collect();
-                flows.push(serde_json::json!({
-                    "id": f.id,
-                    "entry": f.entry,
-                    "steps": step_ids,
-                }));
+                flows.push(annotate(
+                    serde_json::json!({
+                        "id": f.id,
+                        "entry": f.entry,
+                        "steps": step_ids,
+                    }),
+                    &f.annotations,
+                ));
             }
             InterchangeConstruct::Source(_)
             | InterchangeConstruct::System(_)
+            | InterchangeConstruct::Test(_)
+            | InterchangeConstruct::Assert(_)
             | InterchangeConstruct::TypeDecl(_) => {}
         }
     }
@@ -111,9 +141,18 @@ pub fn build_inspect(bundle: &serde_json::Value) -> Result<serde_json::Value, St
         "personas": personas,
         "operations": operations,
         "flows": flows,
+        "requires": parsed.requires,
     }))
 }
 
+/// Attach a construct's annotations to its summary, when it has any.
+fn annotate(mut summary: serde_json::Value, annotations: &Annotations) -> serde_json::Value {
+    if !annotations.is_empty() {
+        summary["annotations"] = serde_json::to_value(annotations).unwrap_or_default();
+    }
+    summary
+}
+
 fn summarize_condition(cond: &serde_json::Value) -> String {
     if cond.is_null() {
         return "always".to_string();
@@ -1,7 +1,8 @@
 //! tenor-interchange: Shared interchange JSON types and deserialization.
 //!
 //! Provides typed structs for all Tenor interchange construct kinds
-//! (Fact, Entity, Rule, Operation, Flow, Persona, System, TypeDecl)
+//! (Fact, Entity, Rule, Operation, Flow, Persona, Source, System, Test,
+//! Assert, TypeDecl)
 //! and a single `from_interchange()` entry point that deserializes
 //! a `serde_json::Value` bundle into an `InterchangeBundle`.
 //!
@@ -9,9 +10,30 @@
 //! tenor-eval, tenor-analyze, and tenor-codegen. Each consumer
 //! depends on this crate for initial JSON parsing, then converts
 //! shared types to its own domain-specific representations.
+//!
+//! With the `cbor` feature, bundles can also be exchanged in a binary
+//! encoding via [`from_cbor`] and [`to_cbor`].
+//!
+//! Bundles written for an older interchange minor version are upgraded on
+//! load; [`migrate_bundle`] exposes the upgrade directly.
+//! [`from_interchange_strict`] rejects unknown construct kinds and fields
+//! instead of ignoring them. [`build_inspect`] summarizes the constructs
+//! a bundle declares.
 
 pub mod deserialize;
+#[cfg(feature = "cbor")]
+pub mod encoding;
+pub mod inspect;
+pub mod migrate;
+pub mod strict;
+pub mod type_table;
 pub mod types;
 
 pub use deserialize::{from_interchange, InterchangeError};
+#[cfg(feature = "cbor")]
+pub use encoding::{from_cbor, to_cbor, CBOR_MEDIA_TYPE};
+pub use inspect::build_inspect;
+pub use migrate::{migrate_bundle, upgrade_bundle, INTERCHANGE_VERSION};
+pub use strict::{check_strict, from_interchange_strict};
+pub use type_table::{extract_type_table, inline_type_table, into_inlined};
 pub use types::*;
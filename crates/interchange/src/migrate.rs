@@ -0,0 +1,257 @@
+//! Interchange format version negotiation and bundle upgrades.
+//!
+//! Bundles record the interchange format version they were produced for in
+//! `tenor_version`. [`migrate_bundle`] rewrites a bundle from an older minor
+//! version to a newer one, one minor version at a time, so a bundle file
+//! elaborated before a format bump keeps loading in current executors.
+//! [`upgrade_bundle`] applies it on load: [`crate::from_interchange`] calls
+//! it before parsing, so consumers always see the current shapes.
+//!
+//! Format changes by version:
+//!
+//! - **1.1.0** — every Operation effect carries an explicit `kind`
+//!   (`"transition"`, `"create_instance"` or `"destroy_instance"`). In 1.0.x
+//!   a state transition omitted `kind`.
+
+use crate::deserialize::InterchangeError;
+use std::borrow::Cow;
+
+/// The newest interchange format version this crate reads and writes;
+/// mirrors `tenor_core::TENOR_BUNDLE_VERSION`.
+pub const INTERCHANGE_VERSION: &str = "1.1.0";
+
+/// One upgrade step: the minor version it reads, and the rewrite that
+/// brings a bundle to the next minor version.
+struct Step {
+    from: (u64, u64),
+    rewrite: fn(&mut serde_json::Value),
+}
+
+/// Upgrade steps in version order.
+const STEPS: &[Step] = &[Step {
+    from: (1, 0),
+    rewrite: explicit_effect_kinds,
+}];
+
+/// Rewrite `bundle` from interchange format `from_version` to `to_version`.
+///
+/// Only upgrades within major version 1 are supported; patch versions are
+/// interchangeable. The bundle's own `tenor_version`, if present, must
+/// agree with `from_version` up to the patch version. The result records
+/// `to_version` as its `tenor_version`.
+pub fn migrate_bundle(
+    bundle: &serde_json::Value,
+    from_version: &str,
+    to_version: &str,
+) -> Result<serde_json::Value, InterchangeError> {
+    let from = parse_version(from_version)?;
+    let to = parse_version(to_version)?;
+    let current = parse_version(INTERCHANGE_VERSION)?;
+
+    if let Some(declared) = bundle.get("tenor_version").and_then(|v| v.as_str()) {
+        let declared_version = parse_version(declared)?;
+        if (declared_version.0, declared_version.1) != (from.0, from.1) {
+            return Err(InterchangeError::InvalidBundle(format!(
+                "bundle declares tenor_version '{}', not '{}'",
+                declared, from_version
+            )));
+        }
+    }
+    for version in [from, to] {
+        if version.0 != current.0 || version.1 > current.1 {
+            return Err(unsupported(&format_version(version)));
+        }
+    }
+    if (to.0, to.1) < (from.0, from.1) {
+        return Err(InterchangeError::InvalidBundle(format!(
+            "cannot downgrade bundle from {} to {}",
+            from_version, to_version
+        )));
+    }
+
+    let mut upgraded = bundle.clone();
+    for step in STEPS {
+        if step.from >= (from.0, from.1) && step.from < (to.0, to.1) {
+            (step.rewrite)(&mut upgraded);
+        }
+    }
+    if let Some(obj) = upgraded.as_object_mut() {
+        obj.insert(
+            "tenor_version".to_owned(),
+            serde_json::Value::String(format_version(to)),
+        );
+    }
+    Ok(upgraded)
+}
+
+/// Negotiate the interchange version of `bundle` for this crate.
+///
+/// Bundles of an older minor version are upgraded to
+/// [`INTERCHANGE_VERSION`]. Bundles of the current or a newer minor version
+/// of the same major version, and bundles without a `tenor_version`, are
+/// borrowed as-is. A bundle of another major version is rejected.
+pub fn upgrade_bundle(
+    bundle: &serde_json::Value,
+) -> Result<Cow<'_, serde_json::Value>, InterchangeError> {
+    let declared = match bundle.get("tenor_version").and_then(|v| v.as_str()) {
+        Some(v) if !v.is_empty() => v,
+        _ => return Ok(Cow::Borrowed(bundle)),
+    };
+    let version = parse_version(declared)?;
+    let current = parse_version(INTERCHANGE_VERSION)?;
+    if version.0 != current.0 {
+        return Err(unsupported(declared));
+    }
+    if version.1 >= current.1 {
+        return Ok(Cow::Borrowed(bundle));
+    }
+    migrate_bundle(bundle, declared, INTERCHANGE_VERSION).map(Cow::Owned)
+}
+
+fn parse_version(version: &str) -> Result<(u64, u64, u64), InterchangeError> {
+    let parts: Vec<u64> = version
+        .split('.')
+        .map(|p| p.parse::<u64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid_version(version))?;
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid_version(version)),
+    }
+}
+
+fn format_version((major, minor, patch): (u64, u64, u64)) -> String {
+    format!("{}.{}.{}", major, minor, patch)
+}
+
+fn invalid_version(version: &str) -> InterchangeError {
+    InterchangeError::InvalidBundle(format!(
+        "invalid tenor_version '{}': expected MAJOR.MINOR.PATCH",
+        version
+    ))
+}
+
+fn unsupported(version: &str) -> InterchangeError {
+    InterchangeError::UnsupportedVersion {
+        version: version.to_owned(),
+        supported: INTERCHANGE_VERSION.to_owned(),
+    }
+}
+
+// ── 1.0 → 1.1 ───────────────────────────────────────────────────────
+
+/// Give every Operation effect without a `kind` the explicit
+/// `"transition"` kind.
+fn explicit_effect_kinds(bundle: &mut serde_json::Value) {
+    let Some(constructs) = bundle.get_mut("constructs").and_then(|c| c.as_array_mut()) else {
+        return;
+    };
+    for construct in constructs {
+        if construct.get("kind").and_then(|k| k.as_str()) != Some("Operation") {
+            continue;
+        }
+        let Some(effects) = construct.get_mut("effects").and_then(|e| e.as_array_mut()) else {
+            continue;
+        };
+        for effect in effects.iter_mut().filter_map(|e| e.as_object_mut()) {
+            effect
+                .entry("kind")
+                .or_insert_with(|| serde_json::Value::String("transition".to_owned()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle_1_0() -> serde_json::Value {
+        json!({
+            "constructs": [{
+                "allowed_personas": ["clerk"],
+                "effects": [
+                    { "entity_id": "Order", "from": "draft", "to": "submitted" },
+                    { "entity_id": "Order", "kind": "create_instance" }
+                ],
+                "error_contract": [],
+                "id": "submit",
+                "kind": "Operation",
+                "precondition": null,
+                "tenor": "1.0"
+            }],
+            "id": "orders",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0"
+        })
+    }
+
+    #[test]
+    fn migrate_1_0_adds_transition_kinds() {
+        let upgraded = migrate_bundle(&bundle_1_0(), "1.0.0", "1.1.0").unwrap();
+        assert_eq!(upgraded["tenor_version"], "1.1.0");
+        let effects = &upgraded["constructs"][0]["effects"];
+        assert_eq!(effects[0]["kind"], "transition");
+        assert_eq!(effects[1]["kind"], "create_instance");
+    }
+
+    #[test]
+    fn migrate_to_same_minor_only_restamps_version() {
+        let upgraded = migrate_bundle(&bundle_1_0(), "1.0.0", "1.0.2").unwrap();
+        assert_eq!(upgraded["tenor_version"], "1.0.2");
+        assert!(upgraded["constructs"][0]["effects"][0]
+            .get("kind")
+            .is_none());
+    }
+
+    #[test]
+    fn migrate_rejects_mismatched_declared_version() {
+        let err = migrate_bundle(&bundle_1_0(), "1.1.0", "1.1.0").unwrap_err();
+        assert!(err.to_string().contains("declares tenor_version '1.0.0'"));
+    }
+
+    #[test]
+    fn migrate_rejects_downgrade_and_unknown_versions() {
+        let mut bundle = bundle_1_0();
+        bundle["tenor_version"] = json!("1.1.0");
+        let err = migrate_bundle(&bundle, "1.1.0", "1.0.0").unwrap_err();
+        assert!(err.to_string().contains("cannot downgrade"));
+
+        let err = migrate_bundle(&bundle_1_0(), "1.0.0", "1.2.0").unwrap_err();
+        assert!(matches!(err, InterchangeError::UnsupportedVersion { .. }));
+
+        let err = migrate_bundle(&bundle_1_0(), "1.0", "1.1.0").unwrap_err();
+        assert!(err.to_string().contains("expected MAJOR.MINOR.PATCH"));
+    }
+
+    #[test]
+    fn upgrade_bundle_negotiates_by_major_and_minor() {
+        let bundle = bundle_1_0();
+        assert!(matches!(upgrade_bundle(&bundle).unwrap(), Cow::Owned(_)));
+
+        let mut current = bundle_1_0();
+        current["tenor_version"] = json!("1.1.3");
+        assert!(matches!(
+            upgrade_bundle(&current).unwrap(),
+            Cow::Borrowed(_)
+        ));
+
+        let mut newer_minor = bundle_1_0();
+        newer_minor["tenor_version"] = json!("1.4.0");
+        assert!(matches!(
+            upgrade_bundle(&newer_minor).unwrap(),
+            Cow::Borrowed(_)
+        ));
+
+        let mut next_major = bundle_1_0();
+        next_major["tenor_version"] = json!("2.0.0");
+        assert_eq!(
+            upgrade_bundle(&next_major).unwrap_err(),
+            InterchangeError::UnsupportedVersion {
+                version: "2.0.0".to_string(),
+                supported: "1.1.0".to_string(),
+            }
+        );
+    }
+}
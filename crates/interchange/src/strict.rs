@@ -0,0 +1,697 @@
+//! Strict interchange deserialization.
+//!
+//! [`from_interchange`](crate::from_interchange) skips unknown construct
+//! kinds and ignores unknown fields so that older consumers can read newer
+//! minor versions. That tolerance also hides typos: a misspelled optional
+//! field is simply dropped. [`from_interchange_strict`] rejects both,
+//! reporting the offending location as a JSON pointer (RFC 6901) into the
+//! bundle.
+//!
+//! Field sets follow `schema/interchange-schema.json`. Checking covers the
+//! bundle, every construct, and the records nested in them: provenance,
+//! annotations, types, predicate expressions, effects, transitions, flow
+//! steps and their handlers, and System members. Literal values and source `fields`
+//! are free-form and not checked.
+
+use crate::deserialize::InterchangeError;
+use crate::types::InterchangeBundle;
+use serde_json::{Map, Value};
+
+const BUNDLE_FIELDS: &[&str] = &[
+    "constructs",
+    "id",
+    "kind",
+    "requires",
+    "tenor",
+    "tenor_version",
+    "trust",
+    "type_table",
+];
+const TRUST_FIELDS: &[&str] = &[
+    "attestation_format",
+    "bundle_attestation",
+    "signer_public_key",
+    "trust_domain",
+];
+const PROVENANCE_FIELDS: &[&str] = &["col", "file", "len", "line"];
+const ANNOTATION_FIELDS: &[&str] = &["description", "owner", "tags"];
+
+const FACT_FIELDS: &[&str] = &[
+    "annotations",
+    "default",
+    "id",
+    "kind",
+    "provenance",
+    "source",
+    "tenor",
+    "type",
+];
+const ENTITY_FIELDS: &[&str] = &[
+    "annotations",
+    "attributes",
+    "id",
+    "initial",
+    "kind",
+    "parent",
+    "provenance",
+    "states",
+    "tenor",
+    "transitions",
+];
+const PERSONA_FIELDS: &[&str] = &["annotations", "id", "kind", "provenance", "tenor"];
+const RULE_FIELDS: &[&str] = &[
+    "annotations",
+    "body",
+    "id",
+    "kind",
+    "provenance",
+    "stratum",
+    "tenor",
+];
+const OPERATION_FIELDS: &[&str] = &[
+    "annotations",
+    "allowed_personas",
+    "effects",
+    "error_contract",
+    "id",
+    "kind",
+    "outcomes",
+    "precondition",
+    "provenance",
+    "tenor",
+];
+const FLOW_FIELDS: &[&str] = &[
+    "annotations",
+    "entry",
+    "id",
+    "kind",
+    "provenance",
+    "snapshot",
+    "steps",
+    "tenor",
+];
+const SOURCE_FIELDS: &[&str] = &[
+    "annotations",
+    "description",
+    "fields",
+    "id",
+    "kind",
+    "protocol",
+    "provenance",
+    "tenor",
+];
+const SYSTEM_FIELDS: &[&str] = &[
+    "annotations",
+    "id",
+    "kind",
+    "members",
+    "provenance",
+    "shared_entities",
+    "shared_personas",
+    "tenor",
+    "triggers",
+];
+const TEST_FIELDS: &[&str] = &[
+    "annotations",
+    "expect",
+    "given",
+    "id",
+    "kind",
+    "provenance",
+    "tenor",
+];
+const ASSERT_FIELDS: &[&str] = &[
+    "annotations",
+    "entity",
+    "id",
+    "kind",
+    "property",
+    "provenance",
+    "tenor",
+];
+const TYPE_DECL_FIELDS: &[&str] = &["annotations", "id", "kind", "provenance", "tenor", "type"];
+
+const TRANSITION_FIELDS: &[&str] = &["from", "guard", "to"];
+const RULE_BODY_FIELDS: &[&str] = &["produce", "when"];
+const PRODUCE_FIELDS: &[&str] = &["payload", "verdict_type"];
+const PAYLOAD_FIELDS: &[&str] = &["type", "value"];
+const EFFECT_FIELDS: &[&str] = &["entity_id", "from", "kind", "outcome", "set", "to"];
+const FACT_SOURCE_FIELDS: &[&str] = &["field", "path", "source_id", "system"];
+const TEST_EXPECTATION_FIELDS: &[&str] = &["present", "verdict"];
+
+/// Every key a predicate expression or operand may carry. Expressions are
+/// an untagged union, so keys are checked against the union of all shapes.
+const EXPRESSION_FIELDS: &[&str] = &[
+    "body",
+    "comparison_type",
+    "convert",
+    "domain",
+    "fact_ref",
+    "field_ref",
+    "instance_state",
+    "instances_of",
+    "left",
+    "literal",
+    "op",
+    "operand",
+    "quantifier",
+    "rate",
+    "result_type",
+    "right",
+    "type",
+    "variable",
+    "variable_type",
+    "verdict_present",
+];
+
+const OPERATION_STEP_FIELDS: &[&str] = &[
+    "id",
+    "kind",
+    "on_failure",
+    "op",
+    "outcomes",
+    "persona",
+    "timeout",
+    "weights",
+];
+const BRANCH_STEP_FIELDS: &[&str] = &[
+    "condition",
+    "id",
+    "if_false",
+    "if_true",
+    "kind",
+    "persona",
+    "weights",
+];
+const HANDOFF_STEP_FIELDS: &[&str] = &[
+    "from_persona",
+    "id",
+    "kind",
+    "next",
+    "pause",
+    "timeout",
+    "to_persona",
+];
+const SUB_FLOW_STEP_FIELDS: &[&str] =
+    &["flow", "id", "kind", "on_failure", "on_success", "persona"];
+const PARALLEL_STEP_FIELDS: &[&str] = &["branches", "id", "join", "kind"];
+const PARALLEL_BRANCH_FIELDS: &[&str] = &["entry", "id", "steps"];
+const JOIN_FIELDS: &[&str] = &["on_all_complete", "on_all_success", "on_any_failure"];
+const TIMEOUT_FIELDS: &[&str] = &["deadline", "on_timeout"];
+const TERMINAL_FIELDS: &[&str] = &["kind", "outcome"];
+const COMPENSATE_FIELDS: &[&str] = &["kind", "steps", "then"];
+const COMPENSATION_STEP_FIELDS: &[&str] = &["on_failure", "op", "persona"];
+const ESCALATE_FIELDS: &[&str] = &["kind", "next", "to_persona"];
+
+const SYSTEM_MEMBER_FIELDS: &[&str] = &["id", "path"];
+const SHARED_PERSONA_FIELDS: &[&str] = &["contracts", "persona"];
+const SHARED_ENTITY_FIELDS: &[&str] = &["contracts", "entity"];
+const TRIGGER_FIELDS: &[&str] = &[
+    "on",
+    "persona",
+    "source_contract",
+    "source_flow",
+    "target_contract",
+    "target_flow",
+];
+
+/// Deserialize an interchange JSON bundle, rejecting unknown construct
+/// kinds and unknown fields.
+///
+/// Older minor versions are upgraded and a type table is inlined before
+/// checking, exactly as in [`from_interchange`](crate::from_interchange),
+/// so pointers refer to the upgraded, inlined bundle. Errors are
+/// [`InterchangeError::UnknownField`] or [`InterchangeError::UnknownKind`].
+pub fn from_interchange_strict(bundle: &Value) -> Result<InterchangeBundle, InterchangeError> {
+    let upgraded = crate::migrate::upgrade_bundle(bundle)?;
+    let inlined = crate::type_table::inline_type_table(upgraded.as_ref())?;
+    check_strict(inlined.as_ref())?;
+    crate::from_interchange(inlined.as_ref())
+}
+
+/// Check `bundle` for unknown construct kinds and unknown fields without
+/// deserializing it. Returns the first violation found.
+pub fn check_strict(bundle: &Value) -> Result<(), InterchangeError> {
+    let Some(obj) = bundle.as_object() else {
+        return Ok(());
+    };
+    fields(obj, "", BUNDLE_FIELDS)?;
+    if let Some(trust) = obj.get("trust").and_then(|t| t.as_object()) {
+        fields(trust, "/trust", TRUST_FIELDS)?;
+    }
+    if let Some(table) = obj.get("type_table").and_then(|t| t.as_array()) {
+        for (i, ty) in table.iter().enumerate() {
+            type_spec(ty, &format!("/type_table/{}", i))?;
+        }
+    }
+    for (i, construct) in array(obj, "constructs") {
+        self::construct(construct, &format!("/constructs/{}", i))?;
+    }
+    Ok(())
+}
+
+fn construct(value: &Value, ptr: &str) -> Result<(), InterchangeError> {
+    let Some(obj) = value.as_object() else {
+        return Ok(());
+    };
+    let kind = obj.get("kind").and_then(|k| k.as_str()).unwrap_or("");
+    let allowed = match kind {
+        "Fact" => FACT_FIELDS,
+        "Entity" => ENTITY_FIELDS,
+        "Persona" => PERSONA_FIELDS,
+        "Rule" => RULE_FIELDS,
+        "Operation" => OPERATION_FIELDS,
+        "Flow" => FLOW_FIELDS,
+        "Source" => SOURCE_FIELDS,
+        "System" => SYSTEM_FIELDS,
+        "Test" => TEST_FIELDS,
+        "Assert" => ASSERT_FIELDS,
+        "TypeDecl" => TYPE_DECL_FIELDS,
+        _ => return Err(unknown_kind(&format!("{}/kind", ptr), kind)),
+    };
+    fields(obj, ptr, allowed)?;
+    if let Some(prov) = obj.get("provenance").and_then(|p| p.as_object()) {
+        fields(prov, &format!("{}/provenance", ptr), PROVENANCE_FIELDS)?;
+    }
+    if let Some(annotations) = obj.get("annotations").and_then(|a| a.as_object()) {
+        fields(
+            annotations,
+            &format!("{}/annotations", ptr),
+            ANNOTATION_FIELDS,
+        )?;
+    }
+    if let Some(ty) = obj.get("type") {
+        type_spec(ty, &format!("{}/type", ptr))?;
+    }
+
+    match kind {
+        "Fact" => {
+            if let Some(source) = obj.get("source").and_then(|s| s.as_object()) {
+                fields(source, &format!("{}/source", ptr), FACT_SOURCE_FIELDS)?;
+            }
+        }
+        "Entity" => {
+            if let Some(attrs) = obj.get("attributes").and_then(|a| a.as_object()) {
+                for (name, ty) in attrs {
+                    type_spec(ty, &format!("{}/attributes/{}", ptr, escape(name)))?;
+                }
+            }
+            for (i, t) in array(obj, "transitions") {
+                let tp = format!("{}/transitions/{}", ptr, i);
+                object(t, &tp, TRANSITION_FIELDS)?;
+                if let Some(guard) = t.get("guard") {
+                    expression(guard, &format!("{}/guard", tp))?;
+                }
+            }
+        }
+        "Rule" => {
+            if let Some(body) = obj.get("body").and_then(|b| b.as_object()) {
+                let bp = format!("{}/body", ptr);
+                fields(body, &bp, RULE_BODY_FIELDS)?;
+                if let Some(when) = body.get("when") {
+                    expression(when, &format!("{}/when", bp))?;
+                }
+                if let Some(produce) = body.get("produce").and_then(|p| p.as_object()) {
+                    let pp = format!("{}/produce", bp);
+                    fields(produce, &pp, PRODUCE_FIELDS)?;
+                    if let Some(payload) = produce.get("payload").and_then(|p| p.as_object()) {
+                        let pl = format!("{}/payload", pp);
+                        fields(payload, &pl, PAYLOAD_FIELDS)?;
+                        if let Some(ty) = payload.get("type") {
+                            type_spec(ty, &format!("{}/type", pl))?;
+                        }
+                    }
+                }
+            }
+        }
+        "Operation" => {
+            if let Some(pre) = obj.get("precondition") {
+                expression(pre, &format!("{}/precondition", ptr))?;
+            }
+            for (i, effect) in array(obj, "effects") {
+                let ep = format!("{}/effects/{}", ptr, i);
+                object(effect, &ep, EFFECT_FIELDS)?;
+                if let Some(set) = effect.get("set").and_then(|s| s.as_object()) {
+                    for (attr, term) in set {
+                        expression(term, &format!("{}/set/{}", ep, escape(attr)))?;
+                    }
+                }
+            }
+        }
+        "Flow" => {
+            for (i, step) in array(obj, "steps") {
+                flow_step(step, &format!("{}/steps/{}", ptr, i))?;
+            }
+        }
+        "System" => {
+            for (i, m) in array(obj, "members") {
+                object(m, &format!("{}/members/{}", ptr, i), SYSTEM_MEMBER_FIELDS)?;
+            }
+            for (i, p) in array(obj, "shared_personas") {
+                object(
+                    p,
+                    &format!("{}/shared_personas/{}", ptr, i),
+                    SHARED_PERSONA_FIELDS,
+                )?;
+            }
+            for (i, e) in array(obj, "shared_entities") {
+                object(
+                    e,
+                    &format!("{}/shared_entities/{}", ptr, i),
+                    SHARED_ENTITY_FIELDS,
+                )?;
+            }
+            for (i, t) in array(obj, "triggers") {
+                object(t, &format!("{}/triggers/{}", ptr, i), TRIGGER_FIELDS)?;
+            }
+        }
+        "Test" => {
+            for (i, e) in array(obj, "expect") {
+                object(e, &format!("{}/expect/{}", ptr, i), TEST_EXPECTATION_FIELDS)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn flow_step(value: &Value, ptr: &str) -> Result<(), InterchangeError> {
+    let Some(obj) = value.as_object() else {
+        return Ok(());
+    };
+    let kind = obj.get("kind").and_then(|k| k.as_str()).unwrap_or("");
+    let allowed = match kind {
+        "OperationStep" => OPERATION_STEP_FIELDS,
+        "BranchStep" => BRANCH_STEP_FIELDS,
+        "HandoffStep" => HANDOFF_STEP_FIELDS,
+        "SubFlowStep" => SUB_FLOW_STEP_FIELDS,
+        "ParallelStep" => PARALLEL_STEP_FIELDS,
+        _ => return Err(unknown_kind(&format!("{}/kind", ptr), kind)),
+    };
+    fields(obj, ptr, allowed)?;
+
+    if let Some(handler) = obj.get("on_failure") {
+        failure_handler(handler, &format!("{}/on_failure", ptr))?;
+    }
+    if let Some(timeout) = obj.get("timeout").and_then(|t| t.as_object()) {
+        let tp = format!("{}/timeout", ptr);
+        fields(timeout, &tp, TIMEOUT_FIELDS)?;
+        if let Some(on_timeout) = timeout.get("on_timeout") {
+            failure_handler(on_timeout, &format!("{}/on_timeout", tp))?;
+        }
+    }
+    if let Some(outcomes) = obj.get("outcomes").and_then(|o| o.as_object()) {
+        for (label, target) in outcomes {
+            step_target(target, &format!("{}/outcomes/{}", ptr, escape(label)))?;
+        }
+    }
+    for key in ["if_true", "if_false", "on_success"] {
+        if let Some(target) = obj.get(key) {
+            step_target(target, &format!("{}/{}", ptr, key))?;
+        }
+    }
+    if let Some(condition) = obj.get("condition") {
+        expression(condition, &format!("{}/condition", ptr))?;
+    }
+    for (i, branch) in array(obj, "branches") {
+        let bp = format!("{}/branches/{}", ptr, i);
+        object(branch, &bp, PARALLEL_BRANCH_FIELDS)?;
+        if let Some(steps) = branch.get("steps").and_then(|s| s.as_array()) {
+            for (j, step) in steps.iter().enumerate() {
+                flow_step(step, &format!("{}/steps/{}", bp, j))?;
+            }
+        }
+    }
+    if let Some(join) = obj.get("join").and_then(|j| j.as_object()) {
+        let jp = format!("{}/join", ptr);
+        fields(join, &jp, JOIN_FIELDS)?;
+        for key in ["on_all_complete", "on_all_success"] {
+            if let Some(target) = join.get(key) {
+                step_target(target, &format!("{}/{}", jp, key))?;
+            }
+        }
+        if let Some(handler) = join.get("on_any_failure") {
+            failure_handler(handler, &format!("{}/on_any_failure", jp))?;
+        }
+    }
+    Ok(())
+}
+
+/// A step target is a step id or a `Terminal` object.
+fn step_target(value: &Value, ptr: &str) -> Result<(), InterchangeError> {
+    if value.is_object() {
+        failure_handler(value, ptr)?;
+    }
+    Ok(())
+}
+
+/// Failure handlers and terminal targets share the `kind` discriminant.
+fn failure_handler(value: &Value, ptr: &str) -> Result<(), InterchangeError> {
+    let Some(obj) = value.as_object() else {
+        return Ok(());
+    };
+    let kind = obj.get("kind").and_then(|k| k.as_str()).unwrap_or("");
+    let allowed = match kind {
+        "Terminal" | "Terminate" => TERMINAL_FIELDS,
+        "Compensate" => COMPENSATE_FIELDS,
+        "Escalate" => ESCALATE_FIELDS,
+        _ => return Err(unknown_kind(&format!("{}/kind", ptr), kind)),
+    };
+    fields(obj, ptr, allowed)?;
+    for (i, step) in array(obj, "steps") {
+        let sp = format!("{}/steps/{}", ptr, i);
+        object(step, &sp, COMPENSATION_STEP_FIELDS)?;
+        if let Some(handler) = step.get("on_failure") {
+            failure_handler(handler, &format!("{}/on_failure", sp))?;
+        }
+    }
+    if let Some(then) = obj.get("then") {
+        failure_handler(then, &format!("{}/then", ptr))?;
+    }
+    Ok(())
+}
+
+fn expression(value: &Value, ptr: &str) -> Result<(), InterchangeError> {
+    let Some(obj) = value.as_object() else {
+        return Ok(());
+    };
+    fields(obj, ptr, EXPRESSION_FIELDS)?;
+    for key in [
+        "left", "right", "operand", "body", "domain", "convert", "rate",
+    ] {
+        if let Some(sub) = obj.get(key) {
+            expression(sub, &format!("{}/{}", ptr, key))?;
+        }
+    }
+    for key in ["type", "comparison_type", "result_type", "variable_type"] {
+        if let Some(ty) = obj.get(key) {
+            type_spec(ty, &format!("{}/{}", ptr, key))?;
+        }
+    }
+    Ok(())
+}
+
+fn type_spec(value: &Value, ptr: &str) -> Result<(), InterchangeError> {
+    let Some(obj) = value.as_object() else {
+        return Ok(());
+    };
+    let base = obj.get("base").and_then(|b| b.as_str()).unwrap_or("");
+    let allowed: &[&str] = match base {
+        "Bool" | "Date" | "DateTime" => &["base"],
+        "Int" => &["base", "max", "min"],
+        "Decimal" => &["base", "precision", "rounding", "scale", "unit"],
+        "Money" => &["base", "currency", "precision", "rounding", "scale"],
+        "Text" => &["base", "max_length"],
+        "Duration" => &["base", "max", "min", "unit"],
+        "Enum" => &["base", "values"],
+        "List" => &["base", "element_type", "max"],
+        "Record" => &["base", "fields"],
+        "TaggedUnion" => &["base", "variants"],
+        _ => return Err(unknown_kind(&format!("{}/base", ptr), base)),
+    };
+    fields(obj, ptr, allowed)?;
+    if let Some(element) = obj.get("element_type") {
+        type_spec(element, &format!("{}/element_type", ptr))?;
+    }
+    for key in ["fields", "variants"] {
+        if let Some(map) = obj.get(key).and_then(|m| m.as_object()) {
+            for (name, ty) in map {
+                type_spec(ty, &format!("{}/{}/{}", ptr, key, escape(name)))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// ── Helpers ─────────────────────────────────────────────────────────
+
+fn object(value: &Value, ptr: &str, allowed: &[&str]) -> Result<(), InterchangeError> {
+    match value.as_object() {
+        Some(obj) => fields(obj, ptr, allowed),
+        None => Ok(()),
+    }
+}
+
+fn fields(obj: &Map<String, Value>, ptr: &str, allowed: &[&str]) -> Result<(), InterchangeError> {
+    match obj.keys().find(|k| !allowed.contains(&k.as_str())) {
+        Some(key) => Err(InterchangeError::UnknownField {
+            pointer: format!("{}/{}", ptr, escape(key)),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn array<'a>(
+    obj: &'a Map<String, Value>,
+    key: &str,
+) -> impl Iterator<Item = (usize, &'a Value)> + 'a {
+    obj.get(key)
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate()
+}
+
+fn unknown_kind(ptr: &str, kind: &str) -> InterchangeError {
+    InterchangeError::UnknownKind {
+        pointer: ptr.to_owned(),
+        kind: kind.to_owned(),
+    }
+}
+
+/// Escape a key as a JSON pointer reference token (RFC 6901).
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle(constructs: Vec<Value>) -> Value {
+        json!({
+            "constructs": constructs,
+            "id": "orders",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.1.0"
+        })
+    }
+
+    fn operation() -> Value {
+        json!({
+            "allowed_personas": ["clerk"],
+            "effects": [
+                { "entity_id": "Order", "from": "draft", "kind": "transition", "to": "submitted" }
+            ],
+            "error_contract": [],
+            "id": "submit",
+            "kind": "Operation",
+            "precondition": {
+                "left": { "fact_ref": "ready" },
+                "op": "=",
+                "right": { "literal": true, "type": { "base": "Bool" } }
+            },
+            "provenance": { "file": "orders.tenor", "line": 3 },
+            "tenor": "1.0"
+        })
+    }
+
+    #[test]
+    fn accepts_well_formed_bundle() {
+        let b = bundle(vec![operation()]);
+        assert!(check_strict(&b).is_ok());
+        assert_eq!(from_interchange_strict(&b).unwrap().constructs.len(), 1);
+    }
+
+    #[test]
+    fn rejects_unknown_top_level_field() {
+        let mut b = bundle(vec![]);
+        b["tenor_verison"] = json!("1.1.0");
+        assert_eq!(
+            check_strict(&b).unwrap_err(),
+            InterchangeError::UnknownField {
+                pointer: "/tenor_verison".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_construct_kind() {
+        let b = bundle(vec![json!({ "id": "x", "kind": "Oparation" })]);
+        assert_eq!(
+            check_strict(&b).unwrap_err(),
+            InterchangeError::UnknownKind {
+                pointer: "/constructs/0/kind".to_string(),
+                kind: "Oparation".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn reports_pointer_to_nested_field() {
+        let mut op = operation();
+        op["effects"][0]["outcom"] = json!("ok");
+        op["precondition"]["right"]["type"]["mni"] = json!(0);
+        let b = bundle(vec![op.clone()]);
+        let err = check_strict(&b).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown field at /constructs/0/precondition/right/type/mni"
+        );
+
+        op["precondition"] = json!(null);
+        let err = check_strict(&bundle(vec![op])).unwrap_err();
+        assert_eq!(
+            err,
+            InterchangeError::UnknownField {
+                pointer: "/constructs/0/effects/0/outcom".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn checks_annotation_fields() {
+        let mut op = operation();
+        op["annotations"] = json!({ "owner": "payments-team", "tags": ["payments"] });
+        assert!(check_strict(&bundle(vec![op.clone()])).is_ok());
+
+        op["annotations"]["ower"] = json!("payments-team");
+        assert_eq!(
+            check_strict(&bundle(vec![op])).unwrap_err(),
+            InterchangeError::UnknownField {
+                pointer: "/constructs/0/annotations/ower".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn escapes_pointer_tokens() {
+        let entity = json!({
+            "attributes": { "a/b": { "base": "Int", "mn": 0 } },
+            "id": "Order",
+            "initial": "draft",
+            "kind": "Entity",
+            "states": ["draft"],
+            "tenor": "1.0",
+            "transitions": []
+        });
+        let err = check_strict(&bundle(vec![entity])).unwrap_err();
+        assert_eq!(
+            err,
+            InterchangeError::UnknownField {
+                pointer: "/constructs/0/attributes/a~1b/mn".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn lenient_mode_still_ignores_unknowns() {
+        let mut op = operation();
+        op["effects"][0]["outcom"] = json!("ok");
+        let b = bundle(vec![op, json!({ "id": "x", "kind": "Oparation" })]);
+        assert!(from_interchange_strict(&b).is_err());
+        assert_eq!(crate::from_interchange(&b).unwrap().constructs.len(), 1);
+    }
+}
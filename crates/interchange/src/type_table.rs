@@ -0,0 +1,242 @@
+//! Type tables: deduplicated TypeSpec subtrees.
+//!
+//! An optimized bundle (`tenor optimize`) may hoist TypeSpecs that occur more
+//! than once into a top-level `"type_table"` array and replace each occurrence
+//! with `{"type_ref": N}`, the index of the entry. Such bundles list the
+//! `type-table` feature in `requires`. [`inline_type_table`] restores the
+//! plain form; [`crate::from_interchange`] applies it before parsing, so
+//! consumers never see a `type_ref`.
+
+use crate::deserialize::InterchangeError;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Top-level bundle key holding the type table.
+pub const TYPE_TABLE_KEY: &str = "type_table";
+/// Key of a reference into the type table.
+pub const TYPE_REF_KEY: &str = "type_ref";
+/// Feature name an optimized bundle with a type table requires; mirrors
+/// `tenor_core::features::FEATURE_TYPE_TABLE`.
+const FEATURE_TYPE_TABLE: &str = "type-table";
+
+/// Hoist TypeSpecs that occur more than once in `constructs` into a type
+/// table, replacing each occurrence with a `type_ref`.
+///
+/// Only parameterized types (those with fields besides `base`) are hoisted:
+/// a reference to `{"base": "Bool"}` would be no smaller than the type.
+/// Returns the number of occurrences replaced; a bundle with nothing worth
+/// hoisting is left untouched.
+pub fn extract_type_table(bundle: &mut serde_json::Value) -> usize {
+    let Some(constructs) = bundle.get_mut("constructs") else {
+        return 0;
+    };
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    count_types(constructs, &mut counts);
+    counts.retain(|_, n| *n > 1);
+    if counts.is_empty() {
+        return 0;
+    }
+
+    let mut table: Vec<serde_json::Value> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let replaced = replace_types(constructs, &counts, &mut table, &mut index);
+
+    let obj = bundle
+        .as_object_mut()
+        .expect("bundle with constructs is an object");
+    obj.insert(TYPE_TABLE_KEY.to_owned(), serde_json::Value::Array(table));
+    let requires = obj
+        .entry("requires")
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+    if let Some(features) = requires.as_array_mut() {
+        if !features.iter().any(|f| f == FEATURE_TYPE_TABLE) {
+            features.push(FEATURE_TYPE_TABLE.into());
+            features.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+        }
+    }
+    replaced
+}
+
+/// Return `bundle` with every `type_ref` replaced by its type table entry
+/// and the table removed. Bundles without a type table are borrowed as-is.
+pub fn inline_type_table(
+    bundle: &serde_json::Value,
+) -> Result<Cow<'_, serde_json::Value>, InterchangeError> {
+    let Some(table) = bundle.get(TYPE_TABLE_KEY) else {
+        return Ok(Cow::Borrowed(bundle));
+    };
+    let table = table.as_array().ok_or_else(|| {
+        InterchangeError::InvalidBundle(format!("'{}' must be an array", TYPE_TABLE_KEY))
+    })?;
+
+    let mut inlined = bundle.clone();
+    resolve_bundle(&mut inlined, table)?;
+    Ok(Cow::Owned(inlined))
+}
+
+/// Owned form of [`inline_type_table`], which inlines in place instead of
+/// copying the bundle.
+pub fn into_inlined(mut bundle: serde_json::Value) -> Result<serde_json::Value, InterchangeError> {
+    let Some(table) = bundle
+        .as_object_mut()
+        .and_then(|obj| obj.remove(TYPE_TABLE_KEY))
+    else {
+        return Ok(bundle);
+    };
+    let serde_json::Value::Array(table) = table else {
+        return Err(InterchangeError::InvalidBundle(format!(
+            "'{}' must be an array",
+            TYPE_TABLE_KEY
+        )));
+    };
+    resolve_bundle(&mut bundle, &table)?;
+    Ok(bundle)
+}
+
+fn resolve_bundle(
+    bundle: &mut serde_json::Value,
+    table: &[serde_json::Value],
+) -> Result<(), InterchangeError> {
+    let obj = bundle
+        .as_object_mut()
+        .expect("bundle with a type table is an object");
+    obj.remove(TYPE_TABLE_KEY);
+    match obj.get_mut("constructs") {
+        Some(constructs) => resolve_refs(constructs, table),
+        None => Ok(()),
+    }
+}
+
+/// Whether `v` is a TypeSpec worth hoisting into the table.
+fn is_hoistable_type(v: &serde_json::Value) -> bool {
+    v.as_object()
+        .is_some_and(|m| m.get("base").is_some_and(|b| b.is_string()) && m.len() > 1)
+}
+
+fn count_types(v: &serde_json::Value, counts: &mut HashMap<String, usize>) {
+    if is_hoistable_type(v) {
+        *counts.entry(v.to_string()).or_default() += 1;
+    }
+    match v {
+        serde_json::Value::Object(m) => m.values().for_each(|c| count_types(c, counts)),
+        serde_json::Value::Array(a) => a.iter().for_each(|c| count_types(c, counts)),
+        _ => {}
+    }
+}
+
+fn replace_types(
+    v: &mut serde_json::Value,
+    repeated: &HashMap<String, usize>,
+    table: &mut Vec<serde_json::Value>,
+    index: &mut HashMap<String, usize>,
+) -> usize {
+    if is_hoistable_type(v) {
+        let key = v.to_string();
+        if repeated.contains_key(&key) {
+            let i = *index.entry(key).or_insert_with(|| {
+                table.push(v.clone());
+                table.len() - 1
+            });
+            *v = serde_json::json!({ TYPE_REF_KEY: i });
+            return 1;
+        }
+    }
+    match v {
+        serde_json::Value::Object(m) => m
+            .values_mut()
+            .map(|c| replace_types(c, repeated, table, index))
+            .sum(),
+        serde_json::Value::Array(a) => a
+            .iter_mut()
+            .map(|c| replace_types(c, repeated, table, index))
+            .sum(),
+        _ => 0,
+    }
+}
+
+fn resolve_refs(
+    v: &mut serde_json::Value,
+    table: &[serde_json::Value],
+) -> Result<(), InterchangeError> {
+    match v {
+        serde_json::Value::Object(m) if m.len() == 1 && m.contains_key(TYPE_REF_KEY) => {
+            let entry = m[TYPE_REF_KEY]
+                .as_u64()
+                .and_then(|i| table.get(i as usize))
+                .ok_or_else(|| {
+                    InterchangeError::InvalidBundle(format!(
+                        "{} {} does not index the type table ({} entries)",
+                        TYPE_REF_KEY,
+                        m[TYPE_REF_KEY],
+                        table.len()
+                    ))
+                })?;
+            *v = entry.clone();
+            Ok(())
+        }
+        serde_json::Value::Object(m) => m.values_mut().try_for_each(|c| resolve_refs(c, table)),
+        serde_json::Value::Array(a) => a.iter_mut().try_for_each(|c| resolve_refs(c, table)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle() -> serde_json::Value {
+        let money = json!({"base": "Money", "currency": "USD"});
+        json!({
+            "id": "t",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0",
+            "constructs": [
+                {"id": "limit", "kind": "Fact", "type": money.clone()},
+                {"id": "total", "kind": "Fact", "type": money.clone()},
+                {"id": "flag", "kind": "Fact", "type": {"base": "Bool"}},
+                {"id": "items", "kind": "Fact", "type": {"base": "List", "element_type": money, "max": 5}}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_repeated_types_are_hoisted_and_inline_back() {
+        let original = bundle();
+        let mut optimized = original.clone();
+        assert_eq!(extract_type_table(&mut optimized), 3);
+        assert_eq!(
+            optimized["type_table"],
+            json!([{"base": "Money", "currency": "USD"}])
+        );
+        assert_eq!(optimized["constructs"][0]["type"], json!({"type_ref": 0}));
+        assert_eq!(optimized["constructs"][2]["type"], json!({"base": "Bool"}));
+        assert_eq!(optimized["requires"], json!(["type-table"]));
+
+        let mut restored = inline_type_table(&optimized).unwrap().into_owned();
+        assert_eq!(into_inlined(optimized).unwrap(), restored);
+        restored.as_object_mut().unwrap().remove("requires");
+        assert_eq!(restored, original);
+    }
+
+    #[test]
+    fn test_unique_types_leave_bundle_untouched() {
+        let mut b =
+            json!({"id": "t", "constructs": [{"id": "f", "type": {"base": "Int", "min": 0}}]});
+        let before = b.clone();
+        assert_eq!(extract_type_table(&mut b), 0);
+        assert_eq!(b, before);
+        assert!(matches!(inline_type_table(&b).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_dangling_type_ref_is_rejected() {
+        let b = json!({"id": "t", "type_table": [], "constructs": [{"type": {"type_ref": 3}}]});
+        match inline_type_table(&b) {
+            Err(InterchangeError::InvalidBundle(msg)) => assert!(msg.contains("type_ref 3")),
+            other => panic!("expected InvalidBundle, got {:?}", other),
+        }
+    }
+}
@@ -6,12 +6,42 @@
 //! to parse deeply nested expression trees.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Source location provenance recorded by the elaborator.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Provenance {
     pub file: String,
     pub line: u64,
+    /// Column of the construct header on `line` (1-based). Absent in
+    /// bundles from elaborators that record lines only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub col: Option<u64>,
+    /// Length of the construct header, in chars.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+}
+
+/// Ownership and triage metadata from `@owner`, `@description` and `@tag`
+/// annotations on a construct. Empty when the construct has none.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Annotations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Annotations {
+    pub fn is_empty(&self) -> bool {
+        self.owner.is_none() && self.description.is_none() && self.tags.is_empty()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
 }
 
 /// Trust metadata for signed bundles and manifest trust sections (Section 19.1).
@@ -58,6 +88,8 @@ pub struct InterchangeBundle {
     pub constructs: Vec<InterchangeConstruct>,
     /// Optional trust metadata for signed bundles (Section 19.1).
     pub trust: Option<TrustMetadata>,
+    /// Optional evaluator features the bundle requires (empty when absent).
+    pub requires: Vec<String>,
 }
 
 /// A single construct from the interchange bundle, dispatched by kind.
@@ -71,9 +103,62 @@ pub enum InterchangeConstruct {
     Persona(PersonaConstruct),
     Source(SourceConstruct),
     System(SystemConstruct),
+    Test(TestConstruct),
+    Assert(AssertConstruct),
     TypeDecl(TypeDeclConstruct),
 }
 
+impl InterchangeConstruct {
+    pub fn id(&self) -> &str {
+        match self {
+            InterchangeConstruct::Fact(c) => &c.id,
+            InterchangeConstruct::Entity(c) => &c.id,
+            InterchangeConstruct::Rule(c) => &c.id,
+            InterchangeConstruct::Operation(c) => &c.id,
+            InterchangeConstruct::Flow(c) => &c.id,
+            InterchangeConstruct::Persona(c) => &c.id,
+            InterchangeConstruct::Source(c) => &c.id,
+            InterchangeConstruct::System(c) => &c.id,
+            InterchangeConstruct::Test(c) => &c.id,
+            InterchangeConstruct::Assert(c) => &c.id,
+            InterchangeConstruct::TypeDecl(c) => &c.id,
+        }
+    }
+
+    /// The construct's `kind` in interchange JSON.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            InterchangeConstruct::Fact(_) => "Fact",
+            InterchangeConstruct::Entity(_) => "Entity",
+            InterchangeConstruct::Rule(_) => "Rule",
+            InterchangeConstruct::Operation(_) => "Operation",
+            InterchangeConstruct::Flow(_) => "Flow",
+            InterchangeConstruct::Persona(_) => "Persona",
+            InterchangeConstruct::Source(_) => "Source",
+            InterchangeConstruct::System(_) => "System",
+            InterchangeConstruct::Test(_) => "Test",
+            InterchangeConstruct::Assert(_) => "Assert",
+            InterchangeConstruct::TypeDecl(_) => "TypeDecl",
+        }
+    }
+
+    pub fn annotations(&self) -> &Annotations {
+        match self {
+            InterchangeConstruct::Fact(c) => &c.annotations,
+            InterchangeConstruct::Entity(c) => &c.annotations,
+            InterchangeConstruct::Rule(c) => &c.annotations,
+            InterchangeConstruct::Operation(c) => &c.annotations,
+            InterchangeConstruct::Flow(c) => &c.annotations,
+            InterchangeConstruct::Persona(c) => &c.annotations,
+            InterchangeConstruct::Source(c) => &c.annotations,
+            InterchangeConstruct::System(c) => &c.annotations,
+            InterchangeConstruct::Test(c) => &c.annotations,
+            InterchangeConstruct::Assert(c) => &c.annotations,
+            InterchangeConstruct::TypeDecl(c) => &c.annotations,
+        }
+    }
+}
+
 // ── Fact ────────────────────────────────────────────────────────────
 
 /// A Fact construct from interchange JSON.
@@ -89,6 +174,7 @@ pub struct FactConstruct {
     /// Default value, if declared.
     pub default: Option<serde_json::Value>,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -99,6 +185,9 @@ pub struct FactConstruct {
 pub struct Transition {
     pub from: String,
     pub to: String,
+    /// Guard predicate expression. None if the transition is unguarded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guard: Option<serde_json::Value>,
 }
 
 /// An Entity construct from interchange JSON.
@@ -108,9 +197,12 @@ pub struct EntityConstruct {
     pub states: Vec<String>,
     pub initial: String,
     pub transitions: Vec<Transition>,
+    /// Declared attribute types, keyed by attribute name. Empty if none.
+    pub attributes: BTreeMap<String, serde_json::Value>,
     /// Parent entity id for inheritance.
     pub parent: Option<String>,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -126,6 +218,7 @@ pub struct RuleConstruct {
     /// are deeply nested and interpreted differently by each consumer.
     pub body: serde_json::Value,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -160,9 +253,20 @@ impl RuleConstruct {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Effect {
     pub entity_id: String,
+    /// `"transition"`, `"create_instance"` or `"destroy_instance"`. Bundles
+    /// before interchange 1.1.0 omit it for a state transition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Source state. Empty for `create_instance`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub from: String,
+    /// Target state. Empty for `create_instance` and `destroy_instance`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub to: String,
     pub outcome: Option<String>,
+    /// Attribute assignments (attribute name to value term). Empty if none.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set: BTreeMap<String, serde_json::Value>,
 }
 
 /// An Operation construct from interchange JSON.
@@ -178,6 +282,7 @@ pub struct OperationConstruct {
     /// Error contract references.
     pub error_contract: Option<serde_json::Value>,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -194,6 +299,7 @@ pub struct FlowConstruct {
     pub steps: Vec<serde_json::Value>,
     pub snapshot: String,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -204,6 +310,7 @@ pub struct FlowConstruct {
 pub struct PersonaConstruct {
     pub id: String,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -219,6 +326,7 @@ pub struct SourceConstruct {
     pub fields: std::collections::BTreeMap<String, String>,
     pub description: Option<String>,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -265,6 +373,48 @@ pub struct SystemConstruct {
     pub flow_triggers: Vec<FlowTrigger>,
     pub shared_entities: Vec<SharedEntity>,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
+    pub tenor: Option<String>,
+}
+
+// ── Test ────────────────────────────────────────────────────────────
+
+/// One expected verdict in a Test construct.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TestExpectation {
+    pub verdict: String,
+    /// `true` if the verdict must be produced, `false` if it must not be.
+    pub present: bool,
+}
+
+/// A rule-level unit test from interchange JSON.
+///
+/// Tests do not affect evaluation; `tenor test --unit` runs them.
+#[derive(Debug, Clone)]
+pub struct TestConstruct {
+    pub id: String,
+    /// Fact values in the plain facts-file shape, keyed by fact id.
+    pub given: serde_json::Map<String, serde_json::Value>,
+    pub expect: Vec<TestExpectation>,
+    pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
+    pub tenor: Option<String>,
+}
+
+// ── Assert ──────────────────────────────────────────────────────────
+
+/// A statically checked invariant from interchange JSON
+/// (`assert forall states of <entity>: <property>`).
+///
+/// Asserts are verified at elaboration time and do not affect evaluation.
+#[derive(Debug, Clone)]
+pub struct AssertConstruct {
+    pub id: String,
+    pub entity: String,
+    /// The property every state of `entity` must have, e.g. `"reachable"`.
+    pub property: String,
+    pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
 
@@ -277,5 +427,6 @@ pub struct TypeDeclConstruct {
     /// The type definition JSON.
     pub type_def: serde_json::Value,
     pub provenance: Option<Provenance>,
+    pub annotations: Annotations,
     pub tenor: Option<String>,
 }
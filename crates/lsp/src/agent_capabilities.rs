@@ -19,6 +19,7 @@ pub struct AgentCapabilities {
     pub operations: Vec<OperationView>,
     pub flows: Vec<FlowView>,
     pub analysis_findings: Vec<Finding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
 
@@ -69,6 +70,10 @@ pub struct EffectInfo {
 pub struct FlowView {
     pub id: String,
     pub entry_point: String,
+    /// Preconditions of the entry step's operation, in the same form as
+    /// [`OperationView::preconditions`]; empty when the flow starts with a
+    /// non-operation step or the operation has no precondition.
+    pub entry_conditions: Vec<String>,
     pub step_count: usize,
     pub step_summary: Vec<String>,
 }
@@ -89,7 +94,7 @@ pub struct Finding {
 /// returning a structured view suitable for the webview panel.
 pub fn compute_agent_capabilities(file_path: &Path) -> AgentCapabilities {
     // Step 1: Elaborate
-    let bundle = match tenor_core::elaborate::elaborate(file_path) {
+    let bundle = match crate::diagnostics::elaborate_in_project(file_path) {
         Ok(b) => b,
         Err(e) => {
             return AgentCapabilities {
@@ -104,7 +109,15 @@ pub fn compute_agent_capabilities(file_path: &Path) -> AgentCapabilities {
         }
     };
 
-    // Step 2: Extract constructs from interchange JSON
+    // Step 2: Extract the agent's view of the elaborated bundle
+    agent_capabilities_from_bundle(&bundle)
+}
+
+/// Compute agent capabilities from an elaborated interchange bundle.
+///
+/// This is the same view the webview panel shows, for consumers that
+/// already hold a bundle (the contract manifest, `tenor serve`, MCP).
+pub fn agent_capabilities_from_bundle(bundle: &serde_json::Value) -> AgentCapabilities {
     let contract_id = bundle
         .get("id")
         .and_then(|v| v.as_str())
@@ -187,6 +200,15 @@ pub fn compute_agent_capabilities(file_path: &Path) -> AgentCapabilities {
         operations = operations_updated;
     }
 
+    // A flow's entry conditions are its entry operation's preconditions
+    for flow in &mut flows {
+        if let Some(op) = entry_operation(&constructs, &flow.id)
+            .and_then(|op_id| operations.iter().find(|o| o.id == op_id))
+        {
+            flow.entry_conditions = op.preconditions.clone();
+        }
+    }
+
     // Build persona views
     let personas: Vec<PersonaView> = persona_ops
         .into_iter()
@@ -196,8 +218,8 @@ pub fn compute_agent_capabilities(file_path: &Path) -> AgentCapabilities {
         })
         .collect();
 
-    // Step 3: Run analysis (non-fatal)
-    let analysis_findings = match tenor_analyze::analyze(&bundle) {
+    // Run analysis (non-fatal)
+    let analysis_findings = match tenor_analyze::analyze(bundle) {
         Ok(report) => extract_findings(&report),
         Err(_) => Vec::new(),
     };
@@ -303,12 +325,13 @@ fn extract_operation(
             arr.iter()
                 .filter_map(|e| {
                     let entity = e.get("entity_id")?.as_str()?.to_string();
-                    let from = e.get("from")?.as_str()?;
-                    let to = e.get("to")?.as_str()?;
-                    Some(EffectInfo {
-                        entity,
-                        transition: format!("{} -> {}", from, to),
-                    })
+                    let state = |key: &str| e.get(key).and_then(|v| v.as_str());
+                    let transition = match e.get("kind").and_then(|k| k.as_str()) {
+                        Some("create_instance") => "create".to_string(),
+                        Some("destroy_instance") => format!("{} -> destroyed", state("from")?),
+                        _ => format!("{} -> {}", state("from")?, state("to")?),
+                    };
+                    Some(EffectInfo { entity, transition })
                 })
                 .collect()
         })
@@ -386,11 +409,30 @@ fn extract_flow(construct: &serde_json::Value) -> Option<FlowView> {
     Some(FlowView {
         id,
         entry_point,
+        entry_conditions: Vec::new(),
         step_count,
         step_summary,
     })
 }
 
+/// The operation performed by `flow_id`'s entry step, if it is an operation step.
+fn entry_operation<'a>(constructs: &'a [serde_json::Value], flow_id: &str) -> Option<&'a str> {
+    let flow = constructs.iter().find(|c| {
+        c.get("kind").and_then(|v| v.as_str()) == Some("Flow")
+            && c.get("id").and_then(|v| v.as_str()) == Some(flow_id)
+    })?;
+    let entry = flow.get("entry")?.as_str()?;
+    let step = flow
+        .get("steps")?
+        .as_array()?
+        .iter()
+        .find(|s| s.get("id").and_then(|v| v.as_str()) == Some(entry))?;
+    if step.get("kind")?.as_str()? != "OperationStep" {
+        return None;
+    }
+    step.get("op")?.as_str()
+}
+
 /// Collect all fact_ref values from a JSON expression tree.
 fn collect_fact_refs(expr: &serde_json::Value) -> Vec<String> {
     let mut refs = Vec::new();
@@ -414,6 +456,12 @@ fn collect_fact_refs_inner(expr: &serde_json::Value, refs: &mut Vec<String>) {
     if let Some(operand) = expr.get("operand") {
         collect_fact_refs_inner(operand, refs);
     }
+    if let Some(amount) = expr.get("convert") {
+        collect_fact_refs_inner(amount, refs);
+    }
+    if let Some(rate) = expr.get("rate") {
+        collect_fact_refs_inner(rate, refs);
+    }
 }
 
 /// Describe a type JSON value as a human-readable string.
@@ -503,6 +551,13 @@ fn describe_expr(expr: Option<&serde_json::Value>) -> String {
             if let Some(fact_ref) = v.get("fact_ref").and_then(|v| v.as_str()) {
                 return fact_ref.to_string();
             }
+            if let Some(amount) = v.get("convert") {
+                return format!(
+                    "convert({}, {})",
+                    describe_expr(Some(amount)),
+                    describe_expr(v.get("rate"))
+                );
+            }
             if let Some(lit) = v.get("literal") {
                 if let Some(b) = lit.as_bool() {
                     return format!("{}", b);
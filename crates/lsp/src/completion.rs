@@ -27,6 +27,8 @@ static CONSTRUCT_KEYWORDS: &[&str] = &[
     "type",
     "system",
     "import",
+    "extends",
+    "override",
 ];
 
 /// Compute completions for the given position in the document.
@@ -54,7 +56,7 @@ pub fn compute_completions(
         }
         // Inside entity body: offer entity field keywords
         (Some("entity"), None) => {
-            for kw in &["states", "initial", "transitions", "parent"] {
+            for kw in &["states", "initial", "transitions", "attributes", "parent"] {
                 items.push(CompletionItem {
                     label: kw.to_string(),
                     kind: Some(CompletionItemKind::PROPERTY),
@@ -1,31 +1,327 @@
-//! Elaboration-to-diagnostic conversion with first-failing-pass stop.
+//! Elaboration and analysis diagnostics.
 //!
-//! Calls `tenor_core::elaborate()` and converts `ElabError` into
-//! `lsp_types::Diagnostic`. Because `elaborate()` returns on the first
-//! error, diagnostics naturally stop at the first failing pass --
+//! Elaborates through the enclosing project's `tenor.toml` (so configured
+//! import paths resolve) and converts `ElabError` into
+//! `lsp_types::Diagnostic`. Errors in Passes 3-5 are collected per
+//! construct (up to `DEFAULT_MAX_ERRORS`), so every broken construct is
+//! shown at once; elaboration still stops after the first failing pass, so
 //! no cascading downstream errors are shown.
+//!
+//! Once a file elaborates cleanly, the S1-S8 findings from
+//! `tenor_analyze::analyze()` are surfaced as warnings and information
+//! diagnostics on the declaration line of the construct they concern.
+//! The server runs analysis through [`AnalysisScheduler`] so it only
+//! fires once edits have settled.
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range, Uri};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tenor_core::parser::DEFAULT_MAX_ERRORS;
+use tenor_core::{ElabError, ProjectConfig};
 
-use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
-use std::path::Path;
+/// Quiet period after a successful elaboration before analysis runs.
+pub const ANALYSIS_DEBOUNCE: Duration = Duration::from_millis(400);
 
 /// Elaborate the file at `file_path` and return any diagnostics.
 ///
-/// On success: empty vec (no diagnostics).
-/// On `ElabError`: a single diagnostic at the error's line.
+/// On success: analysis findings located in this file (empty if none).
+/// On elaboration errors: one diagnostic at each error's line.
 /// On other errors (file not found, etc.): a diagnostic at line 0.
 pub fn compute_diagnostics(file_path: &Path) -> Vec<Diagnostic> {
-    match tenor_core::elaborate::elaborate(file_path) {
-        Ok(_) => Vec::new(),
-        Err(e) => {
-            // ElabError line is 1-indexed; LSP positions are 0-indexed.
-            let line = if e.line > 0 { e.line - 1 } else { 0 };
-            vec![Diagnostic {
-                range: Range::new(Position::new(line, 0), Position::new(line, u32::MAX)),
-                severity: Some(DiagnosticSeverity::ERROR),
-                source: Some("tenor".to_string()),
-                message: e.message,
-                ..Default::default()
-            }]
+    match elaborate_file(file_path) {
+        Ok(bundle) => analysis_diagnostics(file_path, &bundle),
+        Err(diags) => diags,
+    }
+}
+
+/// Elaborate the file at `file_path`, returning the interchange bundle or
+/// one diagnostic per elaboration error.
+pub fn elaborate_file(file_path: &Path) -> Result<serde_json::Value, Vec<Diagnostic>> {
+    elaborate_all_in_project(file_path).map_err(|errors| {
+        let source = std::fs::read_to_string(file_path).ok();
+        let file_name = file_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        errors
+            .iter()
+            .map(|e| {
+                let source = source.as_deref().filter(|_| e.file == file_name);
+                error_diagnostic(e, source)
+            })
+            .collect()
+    })
+}
+
+fn error_diagnostic(e: &ElabError, source: Option<&str>) -> Diagnostic {
+    Diagnostic {
+        range: error_range(e, source),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(e.code().to_string())),
+        source: Some("tenor".to_string()),
+        message: e.message.clone(),
+        ..Default::default()
+    }
+}
+
+/// The range to underline for `e`: its span when the elaborator recorded
+/// one, else the first name quoted in the message that appears on the
+/// error's line of `source`, else the whole line.
+fn error_range(e: &ElabError, source: Option<&str>) -> Range {
+    // ElabError line is 1-indexed; LSP positions are 0-indexed.
+    let line = e.line.saturating_sub(1);
+    if let Some(span) = e.span.filter(|s| s.col > 0) {
+        return span_range(line, span.col, span.len);
+    }
+    let text = match source.and_then(|s| s.lines().nth(line as usize)) {
+        Some(text) => text,
+        None => return line_range(line),
+    };
+    let chars: Vec<char> = text.chars().collect();
+    for name in e.message.split('\'').skip(1).step_by(2) {
+        if let Some(col) = find_word(&chars, name) {
+            return span_range(line, col as u32 + 1, name.chars().count() as u32);
         }
     }
+    let indent = chars.iter().take_while(|c| c.is_whitespace()).count() as u32;
+    Range::new(
+        Position::new(line, indent),
+        Position::new(line, chars.len() as u32),
+    )
+}
+
+/// Char index of `word` in `chars` where it is not part of a longer
+/// identifier.
+fn find_word(chars: &[char], word: &str) -> Option<usize> {
+    let word: Vec<char> = word.chars().collect();
+    if word.is_empty() || word.len() > chars.len() {
+        return None;
+    }
+    let is_ident = |c: &char| c.is_alphanumeric() || *c == '_';
+    (0..=chars.len() - word.len()).find(|&i| {
+        chars[i..i + word.len()] == word[..]
+            && (i == 0 || !is_ident(&chars[i - 1]))
+            && chars.get(i + word.len()).is_none_or(|c| !is_ident(c))
+    })
+}
+
+/// Elaborate `file_path` with the import paths of the nearest `tenor.toml`
+/// above it. A malformed project file is reported as an error on line 1.
+pub fn elaborate_in_project(file_path: &Path) -> Result<serde_json::Value, Box<ElabError>> {
+    let project = discover_project(file_path)?;
+    tenor_core::elaborate::elaborate_with_provider(file_path, &project.source_provider())
+        .map_err(Box::new)
+}
+
+/// [`elaborate_in_project`], collecting up to [`DEFAULT_MAX_ERRORS`]
+/// errors across Passes 3-5 instead of stopping at the first.
+pub fn elaborate_all_in_project(file_path: &Path) -> Result<serde_json::Value, Vec<ElabError>> {
+    let project = discover_project(file_path).map_err(|e| vec![*e])?;
+    tenor_core::elaborate_recovering(file_path, &project.source_provider(), DEFAULT_MAX_ERRORS)
+}
+
+fn discover_project(file_path: &Path) -> Result<ProjectConfig, Box<ElabError>> {
+    let dir = file_path.parent().unwrap_or(Path::new("."));
+    Ok(ProjectConfig::discover(dir)
+        .map_err(|msg| {
+            Box::new(ElabError::new(
+                0,
+                None,
+                None,
+                None,
+                &file_path.to_string_lossy(),
+                1,
+                msg,
+            ))
+        })?
+        .unwrap_or_default())
+}
+
+/// Run the S1-S8 analyses on an elaborated bundle and return the findings
+/// that belong to `file_path`.
+///
+/// Findings are anchored to the declaration of the entity, flow, persona,
+/// or system they name. Bundle-wide findings with no such construct are
+/// anchored to the first line of the root file.
+pub fn analysis_diagnostics(file_path: &Path, bundle: &serde_json::Value) -> Vec<Diagnostic> {
+    let report = match tenor_analyze::analyze(bundle) {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let declarations = construct_declarations(bundle);
+
+    let mut diags = Vec::new();
+    for finding in &report.findings {
+        let range = match finding_anchor(finding) {
+            Some(key) => match declarations.get(&key) {
+                Some(decl) if decl.file == file_name => decl.range(),
+                Some(_) => continue,
+                None => line_range(0),
+            },
+            None => line_range(0),
+        };
+        let severity = match finding.severity {
+            tenor_analyze::FindingSeverity::Warning => DiagnosticSeverity::WARNING,
+            tenor_analyze::FindingSeverity::Info => DiagnosticSeverity::INFORMATION,
+        };
+        diags.push(Diagnostic {
+            range,
+            severity: Some(severity),
+            code: Some(NumberOrString::String(finding.analysis.clone())),
+            source: Some("tenor".to_string()),
+            message: finding.message.clone(),
+            ..Default::default()
+        });
+    }
+    diags
+}
+
+fn line_range(line: u32) -> Range {
+    Range::new(Position::new(line, 0), Position::new(line, u32::MAX))
+}
+
+/// A range on 0-indexed `line` from 1-indexed char column `col`.
+fn span_range(line: u32, col: u32, len: u32) -> Range {
+    let start = col.saturating_sub(1);
+    Range::new(Position::new(line, start), Position::new(line, start + len))
+}
+
+/// The (kind, id) of the construct a finding is about, if any.
+fn finding_anchor(finding: &tenor_analyze::Finding) -> Option<(&'static str, String)> {
+    if let Some(entity_id) = &finding.entity_id {
+        return Some(("Entity", entity_id.clone()));
+    }
+    let details = finding.details.as_ref()?;
+    let detail = |key: &str| details.get(key).and_then(|v| v.as_str()).map(String::from);
+    if let Some(flow_id) = detail("flow_id") {
+        return Some(("Flow", flow_id));
+    }
+    if let Some(system_id) = detail("system_id") {
+        return Some(("System", system_id));
+    }
+    detail("persona_id").map(|p| ("Persona", p))
+}
+
+/// Where a construct is declared, from its interchange provenance.
+struct Declaration {
+    file: String,
+    /// 1-indexed
+    line: u32,
+    /// Header span, absent in bundles that record lines only.
+    span: Option<(u32, u32)>,
+}
+
+impl Declaration {
+    fn range(&self) -> Range {
+        let line = self.line.saturating_sub(1);
+        match self.span {
+            Some((col, len)) => span_range(line, col, len),
+            None => line_range(line),
+        }
+    }
+}
+
+/// Map (kind, id) to the construct's declaration from provenance.
+fn construct_declarations(
+    bundle: &serde_json::Value,
+) -> HashMap<(&'static str, String), Declaration> {
+    let mut out = HashMap::new();
+    let constructs = match bundle.get("constructs").and_then(|c| c.as_array()) {
+        Some(c) => c,
+        None => return out,
+    };
+    for c in constructs {
+        let kind = match c.get("kind").and_then(|k| k.as_str()) {
+            Some("Entity") => "Entity",
+            Some("Flow") => "Flow",
+            Some("System") => "System",
+            Some("Persona") => "Persona",
+            _ => continue,
+        };
+        let id = c.get("id").and_then(|v| v.as_str());
+        let prov = c.get("provenance");
+        let field = |name: &str| prov.and_then(|p| p.get(name)).and_then(|v| v.as_u64());
+        let file = prov.and_then(|p| p.get("file")).and_then(|f| f.as_str());
+        if let (Some(id), Some(file), Some(line)) = (id, file, field("line")) {
+            let span = field("col")
+                .zip(field("len"))
+                .map(|(col, len)| (col as u32, len as u32));
+            out.insert(
+                (kind, id.to_string()),
+                Declaration {
+                    file: file.to_string(),
+                    line: line as u32,
+                    span,
+                },
+            );
+        }
+    }
+    out
+}
+
+/// An analysis run waiting for its quiet period to elapse.
+pub struct PendingAnalysis {
+    pub uri: Uri,
+    pub path: PathBuf,
+    pub bundle: serde_json::Value,
+    due: Instant,
+}
+
+/// Debounces analysis runs per document.
+///
+/// Each successful elaboration (re)schedules analysis for that document
+/// [`ANALYSIS_DEBOUNCE`] in the future; a failed elaboration cancels it.
+/// Rapid successive saves therefore trigger a single analysis run over
+/// the latest bundle.
+#[derive(Default)]
+pub struct AnalysisScheduler {
+    pending: HashMap<String, PendingAnalysis>,
+}
+
+impl AnalysisScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule (or reschedule) analysis of `bundle` for `uri`.
+    pub fn schedule(&mut self, uri: Uri, path: PathBuf, bundle: serde_json::Value, now: Instant) {
+        self.pending.insert(
+            uri.as_str().to_string(),
+            PendingAnalysis {
+                uri,
+                path,
+                bundle,
+                due: now + ANALYSIS_DEBOUNCE,
+            },
+        );
+    }
+
+    /// Drop any pending analysis for `uri`.
+    pub fn cancel(&mut self, uri: &Uri) {
+        self.pending.remove(uri.as_str());
+    }
+
+    /// The earliest time at which a pending analysis becomes due.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().map(|p| p.due).min()
+    }
+
+    /// Remove and return every analysis whose quiet period has elapsed.
+    pub fn take_due(&mut self, now: Instant) -> Vec<PendingAnalysis> {
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| p.due <= now)
+            .map(|(uri, _)| uri.clone())
+            .collect();
+        due.iter()
+            .filter_map(|uri| self.pending.remove(uri))
+            .collect()
+    }
 }
@@ -0,0 +1,66 @@
+//! Document and range formatting via the canonical `.tenor` formatter.
+//!
+//! Both requests answer with a single whole-line replacement edit (or no
+//! edits when the text is already formatted). Range formatting re-indents
+//! the selected lines in the context of the full document but leaves blank
+//! line runs alone, so line numbers outside the range never shift.
+
+use lsp_types::{Position, Range, TextEdit};
+use tenor_core::format::{format_source, reindent_lines};
+
+/// Format the whole document.
+pub fn format_document(content: &str) -> Vec<TextEdit> {
+    let formatted = format_source(content);
+    if formatted == content {
+        return Vec::new();
+    }
+    vec![TextEdit {
+        range: Range::new(Position::new(0, 0), end_of_document(content)),
+        new_text: formatted,
+    }]
+}
+
+/// Format the lines touched by `range`.
+pub fn format_range(content: &str, range: Range) -> Vec<TextEdit> {
+    let original: Vec<&str> = content.lines().collect();
+    if original.is_empty() {
+        return Vec::new();
+    }
+    let reindented = reindent_lines(content);
+    let last = original.len() - 1;
+    let start = (range.start.line as usize).min(last);
+    // A selection ending at column 0 does not include that line.
+    let mut end = (range.end.line as usize).min(last);
+    if range.end.character == 0 && end > start {
+        end -= 1;
+    }
+
+    if original[start..=end]
+        .iter()
+        .zip(&reindented[start..=end])
+        .all(|(a, b)| a == b)
+    {
+        return Vec::new();
+    }
+    let mut new_text = reindented[start..=end].join("\n");
+    new_text.push('\n');
+    let end_pos = if end == last {
+        end_of_document(content)
+    } else {
+        Position::new(end as u32 + 1, 0)
+    };
+    if end == last && !content.ends_with('\n') {
+        new_text.pop();
+    }
+    vec![TextEdit {
+        range: Range::new(Position::new(start as u32, 0), end_pos),
+        new_text,
+    }]
+}
+
+/// Position just past the last character, in UTF-16 code units.
+fn end_of_document(content: &str) -> Position {
+    let line = content.split('\n').count() - 1;
+    let last = content.rsplit('\n').next().unwrap_or("");
+    Position::new(line as u32, last.encode_utf16().count() as u32)
+}
@@ -1,6 +1,7 @@
 //! Tenor Language Server Protocol implementation for IDE integration.
 //!
-//! Provides diagnostics on save, semantic token highlighting,
+//! Provides diagnostics on save (elaboration errors plus debounced S1-S8
+//! analysis findings), semantic token highlighting, rename, formatting,
 //! agent capabilities extraction, and document state management
 //! for open files. Connects to editors via the `tenor lsp` CLI
 //! subcommand over stdio.
@@ -9,8 +10,10 @@ pub mod agent_capabilities;
 pub mod completion;
 pub mod diagnostics;
 pub mod document;
+pub mod formatting;
 pub mod hover;
 pub mod navigation;
+pub mod rename;
 pub mod semantic_tokens;
 pub mod server;
 
@@ -1,15 +1,36 @@
 //! Navigation features: go-to-definition, find-all-references, document symbols.
 //!
-//! Builds a `ProjectIndex` from all `.tenor` files under the workspace root,
-//! mapping construct declarations and references to file:line locations.
+//! Builds a `ProjectIndex` from all `.tenor` files under the workspace root
+//! (plus any files they import from outside it), mapping construct
+//! declarations and references to file:line locations. Each file's
+//! contribution is kept separately so single files can be re-indexed when
+//! they are saved or changed on disk.
+//!
+//! Lookups are scoped by the import graph: a name resolves first to a
+//! declaration in the same bundle (the files connected to the current one
+//! through `import`), and only falls back to the rest of the workspace
+//! when the bundle has no match.
 
 use lsp_types::{DocumentSymbol, Location, Position, Range, SymbolKind, Uri};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
-use tenor_core::ast::{RawConstruct, RawExpr, RawStep, RawTerm};
+use tenor_core::ast::{RawConstruct, RawEffectKind, RawExpr, RawStep, RawTerm};
 use tenor_core::lexer;
 use tenor_core::parser;
 
+/// Construct kinds that navigation resolves, in lookup priority order.
+const NAVIGABLE_KINDS: &[&str] = &[
+    "Fact",
+    "Entity",
+    "Operation",
+    "Flow",
+    "Persona",
+    "TypeDecl",
+    "Rule",
+    "System",
+    "Source",
+];
+
 /// Cached construct locations across the workspace.
 #[derive(Default)]
 pub struct ProjectIndex {
@@ -21,6 +42,26 @@ pub struct ProjectIndex {
     pub symbols: HashMap<String, Vec<DocumentSymbol>>,
     /// Maps (construct_kind, id) to a brief summary for hover.
     pub summaries: HashMap<(String, String), ConstructSummary>,
+    /// Per-file contributions keyed by file URI; the maps above are
+    /// merged from these.
+    files: HashMap<String, IndexedFile>,
+}
+
+/// What a single file contributes to the project index.
+struct IndexedFile {
+    path: PathBuf,
+    /// Resolved `import` statements in this file.
+    imports: Vec<ImportEdge>,
+    /// Declarations, references, symbols, and summaries from this file only.
+    contribution: ProjectIndex,
+}
+
+/// An `import` statement resolved to the imported file.
+struct ImportEdge {
+    /// 1-indexed line of the `import` statement.
+    line: u32,
+    path: PathBuf,
+    uri: String,
 }
 
 /// Summary information about a construct, used for hover tooltips.
@@ -35,69 +76,216 @@ impl ProjectIndex {
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-/// Build a project index from all `.tenor` files under `root_path`.
-pub fn build_project_index(root_path: &Path) -> ProjectIndex {
-    let mut index = ProjectIndex::new();
-    let files = find_tenor_files(root_path);
+    /// Re-index one file from disk, along with any newly imported files.
+    ///
+    /// A file that no longer exists is removed from the index.
+    pub fn update_file(&mut self, path: &Path) {
+        if !path.is_file() {
+            self.remove_file(path);
+            return;
+        }
+        self.index_file_and_imports(path);
+        self.rebuild();
+    }
 
-    for file_path in &files {
-        let content = match std::fs::read_to_string(file_path) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
-        let filename = file_path.to_string_lossy().to_string();
-        let tokens = match lexer::lex(&content, &filename) {
-            Ok(t) => t,
-            Err(_) => continue,
-        };
-        let constructs = match parser::parse(&tokens, &filename) {
-            Ok(c) => c,
-            Err(_) => continue,
+    /// Drop a file's declarations, references, and symbols from the index.
+    pub fn remove_file(&mut self, path: &Path) {
+        // The file may already be gone, so canonicalize via its directory.
+        let path = match (path.parent().map(Path::canonicalize), path.file_name()) {
+            (Some(Ok(dir)), Some(name)) => dir.join(name),
+            _ => path.to_path_buf(),
         };
+        self.files.retain(|_, f| f.path != path);
+        self.rebuild();
+    }
 
-        let uri = path_to_uri(file_path);
-        let mut file_symbols = Vec::new();
+    /// Index `path` (replacing any previous entry) and follow its imports
+    /// to files not yet in the index.
+    fn index_file_and_imports(&mut self, path: &Path) {
+        let mut queue = vec![path.to_path_buf()];
+        let mut first = true;
+        while let Some(next) = queue.pop() {
+            let Some(uri) = path_to_uri(&next) else {
+                continue;
+            };
+            if !first && self.files.contains_key(uri.as_str()) {
+                continue;
+            }
+            first = false;
+            if let Some(file) = index_file(&next) {
+                queue.extend(file.imports.iter().map(|i| i.path.clone()));
+                self.files.insert(uri.as_str().to_string(), file);
+            }
+        }
+    }
+
+    /// Merge per-file contributions into the lookup maps. Files are
+    /// visited in URI order so duplicate declarations resolve
+    /// deterministically.
+    fn rebuild(&mut self) {
+        self.declarations.clear();
+        self.references.clear();
+        self.symbols.clear();
+        self.summaries.clear();
 
-        for construct in &constructs {
-            index_construct(construct, &uri, &content, &mut index, &mut file_symbols);
+        let mut uris: Vec<&String> = self.files.keys().collect();
+        uris.sort();
+        for uri in uris {
+            let c = &self.files[uri].contribution;
+            for (key, loc) in &c.declarations {
+                self.declarations
+                    .entry(key.clone())
+                    .or_insert_with(|| loc.clone());
+            }
+            for (key, refs) in &c.references {
+                self.references
+                    .entry(key.clone())
+                    .or_default()
+                    .extend(refs.iter().cloned());
+            }
+            for (key, summary) in &c.summaries {
+                self.summaries
+                    .entry(key.clone())
+                    .or_insert_with(|| summary.clone());
+            }
+            if let Some(syms) = c.symbols.get(uri) {
+                self.symbols.insert(uri.clone(), syms.clone());
+            }
         }
+    }
 
-        index.symbols.insert(uri.as_str().to_string(), file_symbols);
+    /// URIs of every file connected to `uri` through imports, in either
+    /// direction -- i.e. the files that elaborate together with it.
+    fn import_scope(&self, uri: &str) -> BTreeSet<String> {
+        let mut scope = BTreeSet::new();
+        if !self.files.contains_key(uri) {
+            return scope;
+        }
+        let mut stack = vec![uri.to_string()];
+        while let Some(current) = stack.pop() {
+            if !scope.insert(current.clone()) {
+                continue;
+            }
+            if let Some(file) = self.files.get(&current) {
+                stack.extend(file.imports.iter().map(|i| i.uri.clone()));
+            }
+            for (other_uri, other) in &self.files {
+                if other.imports.iter().any(|i| i.uri == current) {
+                    stack.push(other_uri.clone());
+                }
+            }
+        }
+        scope
     }
 
+    /// The declaration of `key` within `scope`, falling back to any
+    /// declaration in the workspace.
+    fn resolve(&self, key: &(String, String), scope: &BTreeSet<String>) -> Option<Location> {
+        scope
+            .iter()
+            .filter_map(|uri| self.files.get(uri))
+            .find_map(|f| f.contribution.declarations.get(key).cloned())
+            .or_else(|| self.declarations.get(key).cloned())
+    }
+}
+
+/// Build a project index from all `.tenor` files under `root_path`, or
+/// under the source roots of its `tenor.toml` when it declares any.
+pub fn build_project_index(root_path: &Path) -> ProjectIndex {
+    // A tenor.toml at the root narrows indexing to its source roots
+    let roots = match tenor_core::ProjectConfig::discover(root_path) {
+        Ok(Some(project)) if !project.project.source_roots.is_empty() => project.source_roots(),
+        _ => vec![root_path.to_path_buf()],
+    };
+    let mut index = ProjectIndex::new();
+    for file_path in roots.iter().flat_map(|root| find_tenor_files(root)) {
+        index.index_file_and_imports(&file_path);
+    }
+    index.rebuild();
     index
 }
 
+/// Parse and index a single file. Returns `None` if it cannot be read,
+/// lexed, or parsed.
+fn index_file(file_path: &Path) -> Option<IndexedFile> {
+    let content = std::fs::read_to_string(file_path).ok()?;
+    let filename = file_path.to_string_lossy().to_string();
+    let tokens = lexer::lex(&content, &filename).ok()?;
+    let constructs = parser::parse(&tokens, &filename).ok()?;
+
+    let path = file_path
+        .canonicalize()
+        .unwrap_or_else(|_| file_path.to_path_buf());
+    let uri = path_to_uri(&path)?;
+    let base = path.parent().unwrap_or(Path::new("."));
+    let mut contribution = ProjectIndex::new();
+    let mut file_symbols = Vec::new();
+    let mut imports = Vec::new();
+
+    for construct in &constructs {
+        if let RawConstruct::Import { path: import, prov }
+        | RawConstruct::Extends { path: import, prov } = construct
+        {
+            let target = base.join(import);
+            let target = target.canonicalize().unwrap_or(target);
+            if let Some(import_uri) = path_to_uri(&target) {
+                imports.push(ImportEdge {
+                    line: prov.line,
+                    uri: import_uri.as_str().to_string(),
+                    path: target,
+                });
+            }
+        }
+        index_construct(
+            construct,
+            &uri,
+            &content,
+            &mut contribution,
+            &mut file_symbols,
+        );
+    }
+    contribution
+        .symbols
+        .insert(uri.as_str().to_string(), file_symbols);
+
+    Some(IndexedFile {
+        path,
+        imports,
+        contribution,
+    })
+}
+
 /// Go-to-definition: find the declaration location for the word at position.
+///
+/// On an `import` line, jumps to the imported file.
 pub fn goto_definition(
     index: &ProjectIndex,
-    _uri: &Uri,
+    uri: &Uri,
     position: Position,
     content: &str,
 ) -> Option<Location> {
-    let word = word_at_position(content, position)?;
-    // Try each construct kind
-    for kind in &[
-        "Fact",
-        "Entity",
-        "Operation",
-        "Flow",
-        "Persona",
-        "TypeDecl",
-        "Rule",
-        "System",
-    ] {
-        let key = (kind.to_string(), word.clone());
-        if let Some(loc) = index.declarations.get(&key) {
-            return Some(loc.clone());
+    if let Some(file) = index.files.get(uri.as_str()) {
+        if let Some(edge) = file.imports.iter().find(|i| i.line == position.line + 1) {
+            let target: Uri = edge.uri.parse().ok()?;
+            return Some(Location::new(
+                target,
+                Range::new(Position::new(0, 0), Position::new(0, 0)),
+            ));
         }
     }
-    None
+
+    let word = word_at_position(content, position)?;
+    let scope = index.import_scope(uri.as_str());
+    NAVIGABLE_KINDS
+        .iter()
+        .find_map(|kind| index.resolve(&(kind.to_string(), word.clone()), &scope))
 }
 
 /// Find all references to the construct at position (including the declaration).
+///
+/// When the current file belongs to an import graph that declares the
+/// construct, only references from that graph are returned.
 pub fn find_references(
     index: &ProjectIndex,
     uri: &Uri,
@@ -108,35 +296,25 @@ pub fn find_references(
         Some(w) => w,
         None => return Vec::new(),
     };
+    let scope = index.import_scope(uri.as_str());
 
-    // Determine which construct kind this word refers to
-    for kind in &[
-        "Fact",
-        "Entity",
-        "Operation",
-        "Flow",
-        "Persona",
-        "TypeDecl",
-        "Rule",
-        "System",
-    ] {
+    for kind in NAVIGABLE_KINDS {
         let key = (kind.to_string(), word.clone());
-        if index.declarations.contains_key(&key) {
-            let mut result = Vec::new();
-            // Include the declaration itself
-            if let Some(decl) = index.declarations.get(&key) {
-                result.push(decl.clone());
-            }
-            // Include all references
-            if let Some(refs) = index.references.get(&key) {
-                result.extend(refs.iter().cloned());
-            }
-            return result;
+        let decl = match index.resolve(&key, &scope) {
+            Some(d) => d,
+            None => continue,
+        };
+        let scoped = scope.contains(decl.uri.as_str());
+        let mut result = vec![decl];
+        if let Some(refs) = index.references.get(&key) {
+            result.extend(
+                refs.iter()
+                    .filter(|r| !scoped || scope.contains(r.uri.as_str()))
+                    .cloned(),
+            );
         }
+        return result;
     }
-
-    // Also check if word matches any reference even without declaration in scope
-    let _ = uri; // used for context if needed in future
     Vec::new()
 }
 
@@ -169,29 +347,30 @@ fn find_tenor_files(root: &Path) -> Vec<PathBuf> {
     files
 }
 
-/// Convert a file path to a `file://` URI.
-fn path_to_uri(path: &Path) -> Uri {
+/// Convert a file path to a `file://` URI, percent-encoding every byte
+/// outside the unreserved set, `/` and `:`. `None` if the result still
+/// does not parse.
+pub(crate) fn path_to_uri(path: &Path) -> Option<Uri> {
     let abs = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-    let path_str = abs.to_string_lossy().to_string();
+    let path_str = abs.to_string_lossy();
     // On Windows: strip UNC prefix (\\?\), use forward slashes, and prepend /
     #[cfg(windows)]
-    let uri_path = {
-        let p = path_str.strip_prefix(r"\\?\").unwrap_or(&path_str);
-        format!("file:///{}", p.replace('\\', "/"))
-    };
-    #[cfg(not(windows))]
-    let uri_path = format!("file://{}", path_str);
-    uri_path.parse().unwrap_or_else(|e| {
-        // Last resort: use the original path as-is
-        let fallback = format!("file:///{}", path.display().to_string().replace('\\', "/"));
-        fallback.parse().unwrap_or_else(|_| {
-            panic!(
-                "cannot convert path to URI: {} (error: {:?})",
-                path.display(),
-                e
-            )
-        })
-    })
+    let path_str = format!(
+        "/{}",
+        path_str
+            .strip_prefix(r"\\?\")
+            .unwrap_or(&path_str)
+            .replace('\\', "/")
+    );
+    let mut uri = String::from("file://");
+    for b in path_str.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/:".contains(&b) {
+            uri.push(b as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", b));
+        }
+    }
+    uri.parse().ok()
 }
 
 /// Extract the word (identifier) at the given LSP position.
@@ -299,7 +478,11 @@ fn index_construct(
             });
         }
         RawConstruct::Entity {
-            id, prov, states, ..
+            id,
+            prov,
+            states,
+            transitions,
+            ..
         } => {
             let loc = make_location(uri, content, prov.line);
             index
@@ -325,6 +508,10 @@ fn index_construct(
                 selection_range: make_range(content, prov.line),
                 children: None,
             });
+
+            for guard in transitions.iter().filter_map(|t| t.guard.as_ref()) {
+                index_expr_refs(guard, uri, content, index);
+            }
         }
         RawConstruct::Rule {
             id,
@@ -378,7 +565,11 @@ fn index_construct(
             let personas_str = allowed_personas.join(", ");
             let effects_summary: Vec<String> = effects
                 .iter()
-                .map(|(entity, from, to, _, _)| format!("{}: {} -> {}", entity, from, to))
+                .map(|e| match e.kind {
+                    RawEffectKind::Transition => format!("{}: {} -> {}", e.entity, e.from, e.to),
+                    RawEffectKind::Create => format!("create {}", e.entity),
+                    RawEffectKind::Destroy => format!("destroy {} from {}", e.entity, e.from),
+                })
                 .collect();
             let outcomes_str = if outcomes.is_empty() {
                 String::new()
@@ -406,8 +597,8 @@ fn index_construct(
             }
 
             // Index entity references in effects
-            for (entity, _, _, _, effect_line) in effects {
-                add_reference(index, "Entity", entity, uri, content, *effect_line);
+            for effect in effects {
+                add_reference(index, "Entity", &effect.entity, uri, content, effect.line);
             }
 
             // Index fact references in precondition
@@ -561,7 +752,61 @@ fn index_construct(
                 children: None,
             });
         }
-        RawConstruct::Import { .. } => {}
+        RawConstruct::Test {
+            id,
+            given,
+            expectations,
+            prov,
+        } => {
+            let loc = make_location(uri, content, prov.line);
+            index
+                .declarations
+                .insert(("Test".to_string(), id.clone()), loc);
+
+            for g in given {
+                add_reference(index, "Fact", &g.fact, uri, content, g.line);
+            }
+
+            symbols.push(DocumentSymbol {
+                name: id.clone(),
+                detail: Some(format!("test ({} expectations)", expectations.len())),
+                kind: SymbolKind::METHOD,
+                tags: None,
+                deprecated: None,
+                range: make_range(content, prov.line),
+                selection_range: make_range(content, prov.line),
+                children: None,
+            });
+        }
+        RawConstruct::Assert {
+            id,
+            entity,
+            property,
+            entity_line,
+            prov,
+        } => {
+            let loc = make_location(uri, content, prov.line);
+            index
+                .declarations
+                .insert(("Assert".to_string(), id.clone()), loc);
+
+            add_reference(index, "Entity", entity, uri, content, *entity_line);
+
+            symbols.push(DocumentSymbol {
+                name: id.clone(),
+                detail: Some(format!("assert (forall states: {})", property.as_str())),
+                kind: SymbolKind::PROPERTY,
+                tags: None,
+                deprecated: None,
+                range: make_range(content, prov.line),
+                selection_range: make_range(content, prov.line),
+                children: None,
+            });
+        }
+        RawConstruct::Override { construct, .. } | RawConstruct::Gated { construct, .. } => {
+            index_construct(construct, uri, content, index, symbols);
+        }
+        RawConstruct::Import { .. } | RawConstruct::Extends { .. } => {}
     }
 }
 
@@ -593,19 +838,29 @@ fn index_expr_refs(expr: &RawExpr, uri: &Uri, content: &str, index: &mut Project
             index_expr_refs(a, uri, content, index);
             index_expr_refs(b, uri, content, index);
         }
-        RawExpr::Not(e) => {
+        RawExpr::Not(e, _) => {
             index_expr_refs(e, uri, content, index);
         }
         RawExpr::Forall {
-            domain, body, line, ..
+            domain,
+            over_instances,
+            body,
+            line,
+            ..
         } => {
-            add_reference(index, "Fact", domain, uri, content, *line);
+            let kind = if *over_instances { "Entity" } else { "Fact" };
+            add_reference(index, kind, domain, uri, content, *line);
             index_expr_refs(body, uri, content, index);
         }
         RawExpr::Exists {
-            domain, body, line, ..
+            domain,
+            over_instances,
+            body,
+            line,
+            ..
         } => {
-            add_reference(index, "Fact", domain, uri, content, *line);
+            let kind = if *over_instances { "Entity" } else { "Fact" };
+            add_reference(index, kind, domain, uri, content, *line);
             index_expr_refs(body, uri, content, index);
         }
     }
@@ -618,11 +873,16 @@ fn index_term_refs(term: &RawTerm, uri: &Uri, content: &str, index: &mut Project
             add_reference(index, "Fact", name, uri, content, line);
         }
         RawTerm::FieldRef { .. } => {}
+        RawTerm::InstanceState(_) => {}
         RawTerm::Literal(_) => {}
         RawTerm::Mul { left, right } => {
             index_term_refs(left, uri, content, index, line);
             index_term_refs(right, uri, content, index, line);
         }
+        RawTerm::Convert { amount, rate } => {
+            index_term_refs(amount, uri, content, index, line);
+            add_reference(index, "Fact", rate, uri, content, line);
+        }
     }
 }
 
@@ -630,10 +890,17 @@ fn index_term_refs(term: &RawTerm, uri: &Uri, content: &str, index: &mut Project
 fn index_step_refs(step: &RawStep, uri: &Uri, content: &str, index: &mut ProjectIndex) {
     match step {
         RawStep::OperationStep {
-            op, persona, line, ..
+            op,
+            persona,
+            timeout,
+            line,
+            ..
         } => {
             add_reference(index, "Operation", op, uri, content, *line);
             add_reference(index, "Persona", persona, uri, content, *line);
+            if let Some(t) = timeout {
+                add_reference(index, "Fact", &t.deadline, uri, content, t.deadline_line);
+            }
         }
         RawStep::BranchStep {
             persona,
@@ -647,11 +914,15 @@ fn index_step_refs(step: &RawStep, uri: &Uri, content: &str, index: &mut Project
         RawStep::HandoffStep {
             from_persona,
             to_persona,
+            timeout,
             line,
             ..
         } => {
             add_reference(index, "Persona", from_persona, uri, content, *line);
             add_reference(index, "Persona", to_persona, uri, content, *line);
+            if let Some(t) = timeout {
+                add_reference(index, "Fact", &t.deadline, uri, content, t.deadline_line);
+            }
         }
         RawStep::SubFlowStep {
             flow,
@@ -684,7 +955,9 @@ fn format_raw_type(ty: &tenor_core::ast::RawType) -> String {
                 format!("Int({}, {})", min, max)
             }
         }
-        RawType::Decimal { precision, scale } => format!("Decimal({}, {})", precision, scale),
+        RawType::Decimal {
+            precision, scale, ..
+        } => format!("Decimal({}, {})", precision, scale),
         RawType::Text { max_length } => {
             if *max_length == 0 {
                 "Text".to_string()
@@ -694,7 +967,7 @@ fn format_raw_type(ty: &tenor_core::ast::RawType) -> String {
         }
         RawType::Date => "Date".to_string(),
         RawType::DateTime => "DateTime".to_string(),
-        RawType::Money { currency } => format!("Money(\"{}\")", currency),
+        RawType::Money { currency, .. } => format!("Money(\"{}\")", currency),
         RawType::Duration { unit, .. } => format!("Duration(\"{}\")", unit),
         RawType::Enum { values } => {
             let vals: Vec<String> = values.iter().map(|v| format!("\"{}\"", v)).collect();
@@ -0,0 +1,556 @@
+//! Rename-symbol refactoring for facts, entities, personas, and verdict types.
+//!
+//! The symbol under the cursor is resolved against the Pass 2 index of the
+//! document's bundle (the document plus everything it transitively imports).
+//! Every identifier occurrence of the symbol in those files is then rewritten.
+//! Renames that cannot be resolved unambiguously are refused rather than
+//! guessed at.
+
+use lsp_types::{Position, Range, TextEdit, Uri, WorkspaceEdit};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tenor_core::ast::{
+    RawConstruct, RawExpr, RawFailureHandler, RawStep, RawStepTarget, RawTerm, RawTimeout,
+    RawTimeoutAction, RawType,
+};
+use tenor_core::pass1_bundle;
+use tenor_core::pass2_index::{self, Index};
+use tenor_core::source::{FileSystemProvider, SourceProvider};
+
+use crate::navigation::{get_word_at_position, path_to_uri};
+
+/// Compute the workspace edit that renames the symbol at `position`.
+///
+/// `path` and `content` describe the document the request was made in;
+/// `content` takes precedence over what is on disk so unsaved edits are
+/// honoured. Returns `Ok(None)` when the cursor is not on a renamable symbol
+/// and `Err` with a user-facing message when the rename is not safe.
+pub fn compute_rename(
+    path: &Path,
+    content: &str,
+    position: Position,
+    new_name: &str,
+) -> Result<Option<WorkspaceEdit>, String> {
+    let old_name = match get_word_at_position(content, position) {
+        Some(w) => w,
+        None => return Ok(None),
+    };
+    if !is_identifier(new_name) {
+        return Err(format!("'{}' is not a valid identifier", new_name));
+    }
+    if new_name == old_name {
+        return Ok(None);
+    }
+
+    let provider = OverlayProvider::new(path, content);
+    let (constructs, _) = pass1_bundle::load_bundle_with_provider(path, &provider)
+        .map_err(|e| format!("cannot rename while the contract has errors: {}", e.message))?;
+    let index = pass2_index::build_index(&constructs)
+        .map_err(|e| format!("cannot rename while the contract has errors: {}", e.message))?;
+
+    let kinds = symbol_kinds(&index, &old_name);
+    let kind = match kinds.as_slice() {
+        [] => return Ok(None),
+        [kind] => *kind,
+        _ => {
+            return Err(format!(
+                "'{}' names more than one construct ({}); rename is ambiguous",
+                old_name,
+                kinds.join(", ")
+            ))
+        }
+    };
+
+    if is_declared_elsewhere(&index, &old_name) {
+        return Err(format!(
+            "'{}' also names another construct; rename is ambiguous",
+            old_name
+        ));
+    }
+    let local = local_names(&constructs);
+    if let Some(role) = local.get(old_name.as_str()) {
+        return Err(format!(
+            "'{}' is also used as {}; rename would be ambiguous",
+            old_name, role
+        ));
+    }
+    if !symbol_kinds(&index, new_name).is_empty() || is_declared_elsewhere(&index, new_name) {
+        return Err(format!("'{}' is already declared", new_name));
+    }
+    if let Some(role) = local.get(new_name) {
+        return Err(format!("'{}' is already used as {}", new_name, role));
+    }
+
+    // `Uri` caches its parsed parts in cells, so collect pairs and build
+    // the protocol's `changes` map only at the end.
+    let mut changes: Vec<(Uri, Vec<TextEdit>)> = Vec::new();
+    for (file, source) in provider.files() {
+        let edits: Vec<TextEdit> = identifier_ranges(&source, &old_name)
+            .into_iter()
+            .map(|range| TextEdit::new(range, new_name.to_string()))
+            .collect();
+        if edits.is_empty() {
+            continue;
+        }
+        match path_to_uri(&file) {
+            Some(uri) => changes.push((uri, edits)),
+            None => return Err(format!("cannot express {} as a URI", file.display())),
+        }
+    }
+
+    if changes.is_empty() {
+        return Err(format!("no occurrences of {} '{}' found", kind, old_name));
+    }
+
+    Ok(Some(WorkspaceEdit {
+        changes: Some(changes.into_iter().collect()),
+        ..Default::default()
+    }))
+}
+
+// ── Symbol resolution ────────────────────────────────────────────────
+
+/// The renamable construct kinds that `name` refers to in the index.
+fn symbol_kinds(index: &Index, name: &str) -> Vec<&'static str> {
+    let mut kinds = Vec::new();
+    if index.facts.contains_key(name) {
+        kinds.push("fact");
+    }
+    if index.entities.contains_key(name) {
+        kinds.push("entity");
+    }
+    if index.personas.contains_key(name) {
+        kinds.push("persona");
+    }
+    if index.verdict_strata.contains_key(name) {
+        kinds.push("verdict type");
+    }
+    kinds
+}
+
+/// Whether `name` is declared as any non-renamable construct.
+fn is_declared_elsewhere(index: &Index, name: &str) -> bool {
+    index.rules.contains_key(name)
+        || index.operations.contains_key(name)
+        || index.flows.contains_key(name)
+        || index.type_decls.contains_key(name)
+        || index.systems.contains_key(name)
+        || index.sources.contains_key(name)
+        || index.tests.contains_key(name)
+}
+
+/// Names used for something other than a construct id (entity states,
+/// flow step ids, outcomes, record fields, quantifier variables), keyed
+/// to a description of the first role found.
+///
+/// A symbol that shares its spelling with one of these cannot be renamed
+/// by identifier substitution without also rewriting the unrelated use.
+fn local_names(constructs: &[RawConstruct]) -> HashMap<&str, &'static str> {
+    let mut names: HashMap<&str, &'static str> = HashMap::new();
+    for c in constructs {
+        match c {
+            RawConstruct::Entity { states, .. } => {
+                for s in states {
+                    names.entry(s).or_insert("an entity state");
+                }
+            }
+            RawConstruct::TypeDecl { fields, .. } => {
+                for (name, ty) in fields {
+                    names.entry(name).or_insert("a record field");
+                    collect_type_fields(ty, &mut names);
+                }
+            }
+            RawConstruct::Fact { type_, .. } => collect_type_fields(type_, &mut names),
+            RawConstruct::Rule { when, .. } => collect_expr_locals(when, &mut names),
+            RawConstruct::Operation {
+                precondition,
+                outcomes,
+                error_contract,
+                ..
+            } => {
+                for o in outcomes {
+                    names.entry(o).or_insert("an operation outcome");
+                }
+                for e in error_contract {
+                    names.entry(e).or_insert("an error contract label");
+                }
+                collect_expr_locals(precondition, &mut names);
+            }
+            RawConstruct::Flow { steps, .. } => collect_step_locals(steps, &mut names),
+            RawConstruct::System { members, .. } => {
+                for (member, _) in members {
+                    names.entry(member).or_insert("a system member");
+                }
+            }
+            // Pass 1 has put overriding constructs in place of their bases
+            // and resolved feature gates
+            RawConstruct::Import { .. }
+            | RawConstruct::Extends { .. }
+            | RawConstruct::Override { .. }
+            | RawConstruct::Gated { .. }
+            | RawConstruct::Persona { .. }
+            | RawConstruct::Source { .. }
+            | RawConstruct::Test { .. }
+            | RawConstruct::Assert { .. } => {}
+        }
+    }
+    names
+}
+
+fn collect_type_fields<'a>(ty: &'a RawType, names: &mut HashMap<&'a str, &'static str>) {
+    match ty {
+        RawType::Record { fields } => {
+            for (name, field_ty) in fields {
+                names.entry(name).or_insert("a record field");
+                collect_type_fields(field_ty, names);
+            }
+        }
+        RawType::TaggedUnion { variants } => {
+            for (name, variant_ty) in variants {
+                names.entry(name).or_insert("a tagged union variant");
+                collect_type_fields(variant_ty, names);
+            }
+        }
+        RawType::List { element_type, .. } => collect_type_fields(element_type, names),
+        _ => {}
+    }
+}
+
+fn collect_expr_locals<'a>(expr: &'a RawExpr, names: &mut HashMap<&'a str, &'static str>) {
+    match expr {
+        RawExpr::Compare { left, right, .. } => {
+            collect_term_locals(left, names);
+            collect_term_locals(right, names);
+        }
+        RawExpr::VerdictPresent { .. } => {}
+        RawExpr::And(a, b) | RawExpr::Or(a, b) => {
+            collect_expr_locals(a, names);
+            collect_expr_locals(b, names);
+        }
+        RawExpr::Not(e, _) => collect_expr_locals(e, names),
+        RawExpr::Forall { var, body, .. } | RawExpr::Exists { var, body, .. } => {
+            names.entry(var).or_insert("a quantifier variable");
+            collect_expr_locals(body, names);
+        }
+    }
+}
+
+fn collect_term_locals<'a>(term: &'a RawTerm, names: &mut HashMap<&'a str, &'static str>) {
+    match term {
+        RawTerm::FieldRef { var, field } => {
+            names.entry(var).or_insert("a quantifier variable");
+            names.entry(field).or_insert("a record field");
+        }
+        RawTerm::Mul { left, right } => {
+            collect_term_locals(left, names);
+            collect_term_locals(right, names);
+        }
+        RawTerm::Convert { amount, .. } => collect_term_locals(amount, names),
+        RawTerm::InstanceState(var) => {
+            names.entry(var).or_insert("a quantifier variable");
+        }
+        RawTerm::FactRef(_) | RawTerm::Literal(_) => {}
+    }
+}
+
+fn collect_step_locals<'a>(
+    steps: &'a BTreeMap<String, RawStep>,
+    names: &mut HashMap<&'a str, &'static str>,
+) {
+    for (step_id, step) in steps {
+        names.entry(step_id).or_insert("a flow step id");
+        match step {
+            RawStep::OperationStep {
+                outcomes,
+                on_failure,
+                timeout,
+                ..
+            } => {
+                for (label, target) in outcomes {
+                    names.entry(label).or_insert("an operation outcome");
+                    collect_target_locals(target, names);
+                }
+                if let Some(handler) = on_failure {
+                    collect_handler_locals(handler, names);
+                }
+                if let Some(timeout) = timeout {
+                    collect_timeout_locals(timeout, names);
+                }
+            }
+            RawStep::BranchStep {
+                condition,
+                if_true,
+                if_false,
+                ..
+            } => {
+                collect_expr_locals(condition, names);
+                collect_target_locals(if_true, names);
+                collect_target_locals(if_false, names);
+            }
+            RawStep::HandoffStep { timeout, .. } => {
+                if let Some(timeout) = timeout {
+                    collect_timeout_locals(timeout, names);
+                }
+            }
+            RawStep::SubFlowStep {
+                on_success,
+                on_failure,
+                ..
+            } => {
+                collect_target_locals(on_success, names);
+                collect_handler_locals(on_failure, names);
+            }
+            RawStep::ParallelStep { branches, join, .. } => {
+                for branch in branches {
+                    names.entry(&branch.id).or_insert("a parallel branch id");
+                    collect_step_locals(&branch.steps, names);
+                }
+                if let Some(handler) = &join.on_any_failure {
+                    collect_handler_locals(handler, names);
+                }
+                for target in [&join.on_all_success, &join.on_all_complete]
+                    .into_iter()
+                    .flatten()
+                {
+                    collect_target_locals(target, names);
+                }
+            }
+        }
+    }
+}
+
+fn collect_target_locals<'a>(
+    target: &'a RawStepTarget,
+    names: &mut HashMap<&'a str, &'static str>,
+) {
+    if let RawStepTarget::Terminal { outcome } = target {
+        names.entry(outcome).or_insert("a flow outcome");
+    }
+}
+
+fn collect_timeout_locals<'a>(timeout: &'a RawTimeout, names: &mut HashMap<&'a str, &'static str>) {
+    match &timeout.on_timeout {
+        RawTimeoutAction::Goto(target) => collect_target_locals(target, names),
+        RawTimeoutAction::Handle(handler) => collect_handler_locals(handler, names),
+    }
+}
+
+fn collect_handler_locals<'a>(
+    handler: &'a RawFailureHandler,
+    names: &mut HashMap<&'a str, &'static str>,
+) {
+    match handler {
+        RawFailureHandler::Terminate { outcome } => {
+            names.entry(outcome).or_insert("a flow outcome");
+        }
+        RawFailureHandler::Compensate { steps, then } => {
+            names.entry(then).or_insert("a flow outcome");
+            for step in steps {
+                names.entry(&step.on_failure).or_insert("a flow outcome");
+            }
+        }
+        RawFailureHandler::Escalate { .. } => {}
+    }
+}
+
+// ── Source scanning ──────────────────────────────────────────────────
+
+/// Whether `name` is a lexically valid Tenor identifier.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Ranges of every identifier token equal to `name`, skipping comments
+/// and string literals. Columns are counted in characters, matching the
+/// rest of the server.
+fn identifier_ranges(src: &str, name: &str) -> Vec<Range> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut ranges = Vec::new();
+    let mut pos = 0usize;
+    let mut line = 0u32;
+    let mut col = 0u32;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        if c == '\n' {
+            pos += 1;
+            line += 1;
+            col = 0;
+        } else if c == '/' && chars.get(pos + 1) == Some(&'/') {
+            while pos < chars.len() && chars[pos] != '\n' {
+                pos += 1;
+            }
+        } else if c == '/' && chars.get(pos + 1) == Some(&'*') {
+            pos += 2;
+            col += 2;
+            while pos < chars.len() && !(chars[pos] == '*' && chars.get(pos + 1) == Some(&'/')) {
+                if chars[pos] == '\n' {
+                    line += 1;
+                    col = 0;
+                } else {
+                    col += utf16_len(chars[pos]);
+                }
+                pos += 1;
+            }
+            pos += 2;
+            col += 2;
+        } else if c == '"' {
+            pos += 1;
+            col += 1;
+            while pos < chars.len() && chars[pos] != '"' {
+                if chars[pos] == '\\' {
+                    pos += 1;
+                    col += 1;
+                }
+                match chars.get(pos) {
+                    Some('\n') => {
+                        line += 1;
+                        col = 0;
+                    }
+                    Some(&c) => col += utf16_len(c),
+                    None => {}
+                }
+                pos += 1;
+            }
+            pos += 1;
+            col += 1;
+        } else if c.is_alphabetic() || c == '_' {
+            let start = col;
+            let mut word = String::new();
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                word.push(chars[pos]);
+                col += utf16_len(chars[pos]);
+                pos += 1;
+            }
+            if word == name {
+                ranges.push(Range::new(
+                    Position::new(line, start),
+                    Position::new(line, col),
+                ));
+            }
+        } else {
+            pos += 1;
+            col += utf16_len(c);
+        }
+    }
+    ranges
+}
+
+/// Width of `c` in UTF-16 code units, the unit LSP positions count in.
+fn utf16_len(c: char) -> u32 {
+    c.len_utf16() as u32
+}
+
+// ── Source provider ──────────────────────────────────────────────────
+
+/// Filesystem provider that serves the edited document from memory and
+/// records every file the elaborator reads, so edits can be computed
+/// against exactly the sources the index was built from.
+struct OverlayProvider {
+    overlay_path: PathBuf,
+    overlay_content: String,
+    sources: RefCell<BTreeMap<PathBuf, String>>,
+}
+
+impl OverlayProvider {
+    fn new(path: &Path, content: &str) -> Self {
+        Self {
+            overlay_path: path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+            overlay_content: content.to_string(),
+            sources: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Every file read while loading the bundle, with its source text.
+    fn files(&self) -> Vec<(PathBuf, String)> {
+        self.sources
+            .borrow()
+            .iter()
+            .map(|(p, s)| (p.clone(), s.clone()))
+            .collect()
+    }
+}
+
+impl SourceProvider for OverlayProvider {
+    fn read_source(&self, path: &Path) -> Result<String, std::io::Error> {
+        let canon = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let src = if canon == self.overlay_path {
+            self.overlay_content.clone()
+        } else {
+            FileSystemProvider.read_source(path)?
+        };
+        self.sources.borrow_mut().insert(canon, src.clone());
+        Ok(src)
+    }
+
+    fn resolve_import(&self, base: &Path, import: &str) -> Result<PathBuf, std::io::Error> {
+        FileSystemProvider.resolve_import(base, import)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, std::io::Error> {
+        FileSystemProvider.canonicalize(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifier_ranges_skip_comments_and_strings() {
+        let src = "fact paid {\n  // paid here\n  source: \"billing.paid\"\n}\nrule r { when: paid = true }\n";
+        let ranges = identifier_ranges(src, "paid");
+        assert_eq!(
+            ranges,
+            vec![
+                Range::new(Position::new(0, 5), Position::new(0, 9)),
+                Range::new(Position::new(4, 15), Position::new(4, 19)),
+            ]
+        );
+    }
+
+    #[test]
+    fn identifier_ranges_count_columns_in_utf16() {
+        let src = "/* \u{e9} */ paid\nrule \"\u{1F600}\" paid\n\u{1F600} paid";
+        let ranges = identifier_ranges(src, "paid");
+        assert_eq!(
+            ranges,
+            vec![
+                Range::new(Position::new(0, 8), Position::new(0, 12)),
+                Range::new(Position::new(1, 10), Position::new(1, 14)),
+                Range::new(Position::new(2, 3), Position::new(2, 7)),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn path_to_uri_percent_encodes_instead_of_panicking() {
+        let uri = path_to_uri(Path::new("/no such dir/caf\u{e9} [1].tenor")).unwrap();
+        assert_eq!(
+            uri.as_str(),
+            "file:///no%20such%20dir/caf%C3%A9%20%5B1%5D.tenor"
+        );
+    }
+
+    #[test]
+    fn identifier_ranges_match_whole_words_only() {
+        let ranges = identifier_ranges("paid paid_out unpaid paid", "paid");
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[1].start, Position::new(0, 21));
+    }
+
+    #[test]
+    fn is_identifier_rejects_non_identifiers() {
+        assert!(is_identifier("new_name2"));
+        assert!(!is_identifier("2fast"));
+        assert!(!is_identifier("with space"));
+        assert!(!is_identifier(""));
+    }
+}
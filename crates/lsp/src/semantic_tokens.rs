@@ -59,7 +59,7 @@ struct RawSemanticToken {
 
 /// Compute semantic tokens for the given file content.
 ///
-/// Uses the lexer for token positions and construct-level knowledge
+/// Uses the lexer's token spans for positions and construct-level knowledge
 /// from parsing to assign semantic types. Best-effort: returns None
 /// only if lexing fails entirely.
 pub fn compute_semantic_tokens(file_path: &Path, content: &str) -> Option<Vec<SemanticToken>> {
@@ -68,23 +68,13 @@ pub fn compute_semantic_tokens(file_path: &Path, content: &str) -> Option<Vec<Se
     // Lex the content -- if this fails, we can't provide tokens
     let spanned = lexer::lex(content, &filename).ok()?;
 
-    // Build line offset table for column calculation
-    let line_offsets = build_line_offsets(content);
-
     // Collect known names from constructs for better tagging
     let names = collect_construct_names(file_path);
 
     // Walk the token stream and assign semantic types
     let mut raw_tokens = Vec::new();
-    let chars: Vec<char> = content.chars().collect();
-    let mut char_pos: usize = 0;
-
     for (i, sp) in spanned.iter().enumerate() {
-        // Advance char_pos to match the token's line
-        // We need the column, so we track character positions
-        let (col, token_len) = find_token_position(&chars, &mut char_pos, sp, &line_offsets);
-
-        if token_len == 0 {
+        if sp.len == 0 {
             continue;
         }
 
@@ -93,8 +83,8 @@ pub fn compute_semantic_tokens(file_path: &Path, content: &str) -> Option<Vec<Se
         if let Some(tt) = token_type {
             raw_tokens.push(RawSemanticToken {
                 line: sp.line.saturating_sub(1), // LSP is 0-indexed
-                col,
-                length: token_len as u32,
+                col: sp.col.saturating_sub(1),
+                length: sp.len,
                 token_type: tt,
                 modifiers,
             });
@@ -106,107 +96,6 @@ pub fn compute_semantic_tokens(file_path: &Path, content: &str) -> Option<Vec<Se
     Some(delta_encode(&raw_tokens))
 }
 
-/// Build a table of byte offsets for each line start.
-fn build_line_offsets(content: &str) -> Vec<usize> {
-    let mut offsets = vec![0];
-    for (i, ch) in content.chars().enumerate() {
-        if ch == '\n' {
-            offsets.push(i + 1);
-        }
-    }
-    offsets
-}
-
-/// Find the column and length of a token in the source.
-fn find_token_position(
-    chars: &[char],
-    char_pos: &mut usize,
-    sp: &Spanned,
-    line_offsets: &[usize],
-) -> (u32, usize) {
-    // Use line offset to determine column
-    let line_idx = (sp.line as usize).saturating_sub(1);
-    let line_start = if line_idx < line_offsets.len() {
-        line_offsets[line_idx]
-    } else {
-        0
-    };
-
-    let token_text = token_text(&sp.token);
-    let token_len = token_text.len();
-
-    if token_len == 0 {
-        return (0, 0);
-    }
-
-    // Search from line_start for the token text, starting at or after char_pos
-    let search_start = (*char_pos).max(line_start);
-    if let Some(offset) = find_substr(chars, search_start, &token_text) {
-        *char_pos = offset + token_len;
-        let col = (offset - line_start) as u32;
-        (col, token_len)
-    } else if let Some(offset) = find_substr(chars, line_start, &token_text) {
-        // Fallback: search from line start
-        *char_pos = offset + token_len;
-        let col = (offset - line_start) as u32;
-        (col, token_len)
-    } else {
-        (0, token_len)
-    }
-}
-
-/// Find a substring in a char slice starting at `from`.
-fn find_substr(chars: &[char], from: usize, needle: &[char]) -> Option<usize> {
-    if needle.is_empty() || from + needle.len() > chars.len() {
-        return None;
-    }
-    for i in from..=(chars.len() - needle.len()) {
-        if chars[i..i + needle.len()] == *needle {
-            return Some(i);
-        }
-    }
-    None
-}
-
-/// Get the text representation of a token for position finding.
-fn token_text(tok: &Token) -> Vec<char> {
-    match tok {
-        Token::Word(w) => w.chars().collect(),
-        Token::Str(s) => {
-            // Include quotes for position calculation
-            let mut v = vec!['"'];
-            v.extend(s.chars());
-            v.push('"');
-            v
-        }
-        Token::Int(n) => n.to_string().chars().collect(),
-        Token::Float(f) => f.chars().collect(),
-        Token::LBrace => vec!['{'],
-        Token::RBrace => vec!['}'],
-        Token::LBracket => vec!['['],
-        Token::RBracket => vec![']'],
-        Token::LParen => vec!['('],
-        Token::RParen => vec![')'],
-        Token::Colon => vec![':'],
-        Token::Comma => vec![','],
-        Token::Dot => vec!['.'],
-        Token::Eq => vec!['='],
-        Token::Neq => vec!['!', '='],
-        Token::Lt => vec!['<'],
-        Token::Lte => vec!['<', '='],
-        Token::Gt => vec!['>'],
-        Token::Gte => vec!['>', '='],
-        Token::Star => vec!['*'],
-        Token::And => vec!['\u{2227}'],
-        Token::Or => vec!['\u{2228}'],
-        Token::Not => vec!['\u{00AC}'],
-        Token::Forall => vec!['\u{2200}'],
-        Token::Exists => vec!['\u{2203}'],
-        Token::In => vec!['\u{2208}'],
-        Token::Eof => vec![],
-    }
-}
-
 /// Known construct names gathered from parsing.
 struct ConstructNames {
     entities: HashSet<String>,
@@ -285,6 +174,8 @@ static CONSTRUCT_KEYWORDS: &[&str] = &[
     "persona",
     "system",
     "import",
+    "extends",
+    "override",
 ];
 
 /// Field-level keywords within construct bodies.
@@ -292,6 +183,7 @@ static FIELD_KEYWORDS: &[&str] = &[
     "states",
     "initial",
     "transitions",
+    "attributes",
     "parent",
     "source",
     "default",
@@ -316,6 +208,11 @@ static FIELD_KEYWORDS: &[&str] = &[
     "next",
     "from_persona",
     "to_persona",
+    "pause",
+    "timeout",
+    "deadline",
+    "on_timeout",
+    "weights",
     "branches",
     "join",
     "on_all_success",
@@ -346,6 +243,7 @@ static STEP_TYPES: &[&str] = &[
     "Terminate",
     "Compensate",
     "Escalate",
+    "Timeout",
 ];
 
 /// Classify a Word token based on context.
@@ -355,6 +253,11 @@ fn classify_word(
     tokens: &[Spanned],
     names: &ConstructNames,
 ) -> (Option<u32>, u32) {
+    // Annotation names: `@owner`, `@tag`, ...
+    if idx > 0 && tokens[idx - 1].token == Token::At {
+        return (Some(TK_KEYWORD), 0);
+    }
+
     // Construct declaration keywords
     if CONSTRUCT_KEYWORDS.contains(&word) {
         // Check if this is a declaration (followed by an identifier)
@@ -5,28 +5,32 @@
 
 use lsp_server::{Connection, Message, Notification, Response};
 use lsp_types::notification::{
-    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
-    Notification as _, PublishDiagnostics,
+    DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+    DidSaveTextDocument, Notification as _, PublishDiagnostics,
 };
 use lsp_types::request::{
-    Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, References,
-    SemanticTokensFullRequest,
+    Completion, DocumentSymbolRequest, Formatting, GotoDefinition, HoverRequest, RangeFormatting,
+    References, Rename, SemanticTokensFullRequest,
 };
 use lsp_types::{
-    CompletionOptions, CompletionResponse, DocumentSymbolResponse, GotoDefinitionResponse,
-    HoverProviderCapability, OneOf, PublishDiagnosticsParams, SaveOptions, SemanticTokens,
-    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, SemanticTokensResult,
-    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
-    TextDocumentSyncSaveOptions, Uri,
+    CompletionOptions, CompletionResponse, DidChangeWatchedFilesRegistrationOptions,
+    DocumentSymbolResponse, FileChangeType, FileSystemWatcher, GlobPattern, GotoDefinitionResponse,
+    HoverProviderCapability, OneOf, PublishDiagnosticsParams, Registration, RegistrationParams,
+    SaveOptions, SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, SemanticTokensResult, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Uri,
 };
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use crate::agent_capabilities;
 use crate::completion;
-use crate::diagnostics;
+use crate::diagnostics::{self, AnalysisScheduler};
 use crate::document::DocumentState;
+use crate::formatting;
 use crate::hover;
 use crate::navigation::{self, ProjectIndex};
+use crate::rename;
 use crate::semantic_tokens;
 
 /// Run the LSP server over stdio until shutdown.
@@ -39,6 +43,10 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     let init_params: lsp_types::InitializeParams =
         serde_json::from_value(connection.initialize(init_json)?)?;
 
+    if supports_watched_files_registration(&init_params) {
+        register_file_watcher(&connection)?;
+    }
+
     // ── Build initial project index from workspace root ──────────────
     let mut workspace_root = extract_workspace_root(&init_params);
     let mut project_index = if let Some(root) = &workspace_root {
@@ -49,8 +57,27 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     // ── Main loop ─────────────────────────────────────────────────────
     let mut doc_state = DocumentState::new();
+    let mut scheduler = AnalysisScheduler::new();
 
-    for msg in &connection.receiver {
+    loop {
+        // Wake up for the next pending analysis even if no message arrives.
+        let msg = match scheduler.next_deadline() {
+            Some(deadline) => {
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                match connection.receiver.recv_timeout(timeout) {
+                    Ok(msg) => msg,
+                    Err(e) if e.is_timeout() => {
+                        run_due_analyses(&connection, &mut scheduler)?;
+                        continue;
+                    }
+                    Err(_) => break,
+                }
+            }
+            None => match connection.receiver.recv() {
+                Ok(msg) => msg,
+                Err(_) => break,
+            },
+        };
         match msg {
             Message::Request(req) => {
                 if connection.handle_shutdown(&req)? {
@@ -64,6 +91,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                     &mut doc_state,
                     &mut project_index,
                     &mut workspace_root,
+                    &mut scheduler,
                     not,
                 )?;
             }
@@ -99,6 +127,42 @@ fn extract_workspace_root(params: &lsp_types::InitializeParams) -> Option<PathBu
     None
 }
 
+/// Whether the client lets us register file watchers dynamically.
+fn supports_watched_files_registration(params: &lsp_types::InitializeParams) -> bool {
+    params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|w| w.did_change_watched_files.as_ref())
+        .and_then(|c| c.dynamic_registration)
+        .unwrap_or(false)
+}
+
+/// Ask the client to send `workspace/didChangeWatchedFiles` for `.tenor`
+/// files, so the project index tracks edits made outside the editor.
+fn register_file_watcher(connection: &Connection) -> Result<(), Box<dyn std::error::Error>> {
+    let options = DidChangeWatchedFilesRegistrationOptions {
+        watchers: vec![FileSystemWatcher {
+            glob_pattern: GlobPattern::String("**/*.tenor".to_string()),
+            kind: None,
+        }],
+    };
+    let params = RegistrationParams {
+        registrations: vec![Registration {
+            id: "tenor-watch-files".to_string(),
+            method: DidChangeWatchedFiles::METHOD.to_string(),
+            register_options: Some(serde_json::to_value(options)?),
+        }],
+    };
+    let req = lsp_server::Request::new(
+        "tenor/registerWatchedFiles".to_string().into(),
+        "client/registerCapability".to_string(),
+        params,
+    );
+    connection.sender.send(Message::Request(req))?;
+    Ok(())
+}
+
 /// Convert a URI string to a file system path.
 fn uri_to_path_from_str(uri_str: &str) -> PathBuf {
     if let Some(path) = uri_str.strip_prefix("file://") {
@@ -143,6 +207,9 @@ fn build_capabilities() -> ServerCapabilities {
         ),
         definition_provider: Some(OneOf::Left(true)),
         references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
         document_symbol_provider: Some(OneOf::Left(true)),
         hover_provider: Some(HoverProviderCapability::Simple(true)),
         completion_provider: Some(CompletionOptions {
@@ -220,6 +287,31 @@ fn handle_request(
             if refs.is_empty() { None } else { Some(refs) };
         let resp = Response::new_ok(req.id, serde_json::to_value(result)?);
         connection.sender.send(Message::Response(resp))?;
+    } else if req.method == Rename::METHOD {
+        let params: lsp_types::RenameParams = serde_json::from_value(req.params.clone())?;
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let content = get_document_content(doc_state, uri);
+        let path = uri_to_path(uri);
+        let resp = match rename::compute_rename(&path, &content, position, &params.new_name) {
+            Ok(edit) => Response::new_ok(req.id, serde_json::to_value(edit)?),
+            Err(msg) => Response::new_err(req.id, lsp_server::ErrorCode::RequestFailed as i32, msg),
+        };
+        connection.sender.send(Message::Response(resp))?;
+    } else if req.method == Formatting::METHOD {
+        let params: lsp_types::DocumentFormattingParams =
+            serde_json::from_value(req.params.clone())?;
+        let content = get_document_content(doc_state, &params.text_document.uri);
+        let edits = formatting::format_document(&content);
+        let resp = Response::new_ok(req.id, serde_json::to_value(Some(edits))?);
+        connection.sender.send(Message::Response(resp))?;
+    } else if req.method == RangeFormatting::METHOD {
+        let params: lsp_types::DocumentRangeFormattingParams =
+            serde_json::from_value(req.params.clone())?;
+        let content = get_document_content(doc_state, &params.text_document.uri);
+        let edits = formatting::format_range(&content, params.range);
+        let resp = Response::new_ok(req.id, serde_json::to_value(Some(edits))?);
+        connection.sender.send(Message::Response(resp))?;
     } else if req.method == DocumentSymbolRequest::METHOD {
         let params: lsp_types::DocumentSymbolParams = serde_json::from_value(req.params.clone())?;
         let uri = &params.text_document.uri;
@@ -283,6 +375,7 @@ fn handle_notification(
     doc_state: &mut DocumentState,
     project_index: &mut ProjectIndex,
     workspace_root: &mut Option<PathBuf>,
+    scheduler: &mut AnalysisScheduler,
     not: Notification,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match not.method.as_str() {
@@ -306,8 +399,7 @@ fn handle_notification(
                 }
             }
 
-            let diags = diagnostics::compute_diagnostics(&path);
-            publish_diagnostics(connection, params.text_document.uri, diags)?;
+            check_document(connection, scheduler, params.text_document.uri, path)?;
         }
         m if m == DidChangeTextDocument::METHOD => {
             let params: lsp_types::DidChangeTextDocumentParams =
@@ -321,8 +413,12 @@ fn handle_notification(
         m if m == DidSaveTextDocument::METHOD => {
             let params: lsp_types::DidSaveTextDocumentParams = serde_json::from_value(not.params)?;
             let path = uri_to_path(&params.text_document.uri);
-            let diags = diagnostics::compute_diagnostics(&path);
-            publish_diagnostics(connection, params.text_document.uri.clone(), diags)?;
+            check_document(
+                connection,
+                scheduler,
+                params.text_document.uri.clone(),
+                path.clone(),
+            )?;
 
             // Send updated agent capabilities after save
             let caps = agent_capabilities::compute_agent_capabilities(&path);
@@ -337,15 +433,29 @@ fn handle_notification(
                 .sender
                 .send(Message::Notification(caps_notification))?;
 
-            // Rebuild project index on save to pick up changes
-            if let Some(root) = workspace_root {
-                *project_index = navigation::build_project_index(root);
+            // Re-index the saved file (and anything it now imports)
+            project_index.update_file(&path);
+        }
+        m if m == DidChangeWatchedFiles::METHOD => {
+            let params: lsp_types::DidChangeWatchedFilesParams =
+                serde_json::from_value(not.params)?;
+            for change in params.changes {
+                let path = uri_to_path(&change.uri);
+                if path.extension().is_none_or(|e| e != "tenor") {
+                    continue;
+                }
+                if change.typ == FileChangeType::DELETED {
+                    project_index.remove_file(&path);
+                } else {
+                    project_index.update_file(&path);
+                }
             }
         }
         m if m == DidCloseTextDocument::METHOD => {
             let params: lsp_types::DidCloseTextDocumentParams = serde_json::from_value(not.params)?;
             let uri_str = params.text_document.uri.as_str().to_string();
             doc_state.close(&uri_str);
+            scheduler.cancel(&params.text_document.uri);
             // Clear diagnostics for closed file
             publish_diagnostics(connection, params.text_document.uri, Vec::new())?;
         }
@@ -356,6 +466,39 @@ fn handle_notification(
     Ok(())
 }
 
+/// Elaborate a document and publish any error immediately. On success,
+/// clear stale errors and schedule the (debounced) analysis run.
+fn check_document(
+    connection: &Connection,
+    scheduler: &mut AnalysisScheduler,
+    uri: Uri,
+    path: PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match diagnostics::elaborate_file(&path) {
+        Ok(bundle) => {
+            publish_diagnostics(connection, uri.clone(), Vec::new())?;
+            scheduler.schedule(uri, path, bundle, Instant::now());
+        }
+        Err(diags) => {
+            scheduler.cancel(&uri);
+            publish_diagnostics(connection, uri, diags)?;
+        }
+    }
+    Ok(())
+}
+
+/// Run analyses whose debounce period has elapsed and publish the findings.
+fn run_due_analyses(
+    connection: &Connection,
+    scheduler: &mut AnalysisScheduler,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for pending in scheduler.take_due(Instant::now()) {
+        let diags = diagnostics::analysis_diagnostics(&pending.path, &pending.bundle);
+        publish_diagnostics(connection, pending.uri, diags)?;
+    }
+    Ok(())
+}
+
 /// Send `textDocument/publishDiagnostics` notification to the client.
 fn publish_diagnostics(
     connection: &Connection,
@@ -381,7 +524,8 @@ fn infer_workspace_root(file_path: &Path) -> Option<PathBuf> {
     };
     loop {
         // Look for common project root markers
-        if dir.join("Cargo.toml").exists()
+        if dir.join(tenor_core::project::PROJECT_FILE).exists()
+            || dir.join("Cargo.toml").exists()
             || dir.join(".git").exists()
             || dir.join("package.json").exists()
         {
@@ -341,3 +341,494 @@ fn hover_on_construct_name_returns_summary() {
 
     assert!(hover.is_some(), "should provide hover for fact name");
 }
+
+// ──────────────────────────────────────────────
+// Rename
+// ──────────────────────────────────────────────
+
+/// Helper: write `files` into a temp dir and return (dir path, path of the first file).
+fn write_fixture_files(files: &[(&str, &str)]) -> (std::path::PathBuf, std::path::PathBuf) {
+    let dir = TempDir::new().expect("temp dir");
+    for (name, source) in files {
+        std::fs::write(dir.path().join(name), source).expect("write file");
+    }
+    let root = dir.keep();
+    let first = root.join(files[0].0);
+    (root, first)
+}
+
+/// Apply all edits for the file whose URI ends with `file_name`.
+fn apply_edits(edit: &lsp_types::WorkspaceEdit, file_name: &str, source: &str) -> String {
+    let (_, edits) = edit
+        .changes
+        .as_ref()
+        .expect("changes")
+        .iter()
+        .find(|(uri, _)| uri.as_str().ends_with(file_name))
+        .unwrap_or_else(|| panic!("no edits for {}", file_name));
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let mut edits = edits.clone();
+    edits.sort_by_key(|e| std::cmp::Reverse((e.range.start.line, e.range.start.character)));
+    for e in edits {
+        let line = &mut lines[e.range.start.line as usize];
+        let chars: Vec<char> = line.chars().collect();
+        let before: String = chars[..e.range.start.character as usize].iter().collect();
+        let after: String = chars[e.range.end.character as usize..].iter().collect();
+        *line = format!("{}{}{}", before, e.new_text, after);
+    }
+    lines.join("\n") + "\n"
+}
+
+#[test]
+fn rename_fact_updates_declaration_and_references() {
+    let (_, path) = write_fixture_files(&[("test.tenor", SAMPLE_CONTRACT)]);
+
+    let edit = tenor_lsp::rename::compute_rename(
+        &path,
+        SAMPLE_CONTRACT,
+        Position::new(0, 5), // "payment_ok" at declaration
+        "payment_cleared",
+    )
+    .expect("rename succeeds")
+    .expect("edit produced");
+
+    let renamed = apply_edits(&edit, "test.tenor", SAMPLE_CONTRACT);
+    assert!(renamed.starts_with("fact payment_cleared {"));
+    assert!(renamed.contains("when: payment_cleared = true"));
+    assert!(renamed.contains("precondition: payment_cleared = true"));
+    // String literals are left untouched
+    assert!(renamed.contains("source: \"billing.payment_ok\""));
+}
+
+#[test]
+fn rename_persona_and_verdict_type() {
+    let (_, path) = write_fixture_files(&[("test.tenor", SAMPLE_CONTRACT)]);
+
+    let edit = tenor_lsp::rename::compute_rename(
+        &path,
+        SAMPLE_CONTRACT,
+        Position::new(14, 8), // "admin" at persona declaration
+        "operator",
+    )
+    .unwrap()
+    .unwrap();
+    let renamed = apply_edits(&edit, "test.tenor", SAMPLE_CONTRACT);
+    assert!(renamed.contains("persona operator"));
+    assert!(renamed.contains("allowed_personas: [operator]"));
+
+    let edit = tenor_lsp::rename::compute_rename(
+        &path,
+        SAMPLE_CONTRACT,
+        Position::new(19, 22), // "payment_valid" in produce clause
+        "payment_verified",
+    )
+    .unwrap()
+    .unwrap();
+    let renamed = apply_edits(&edit, "test.tenor", SAMPLE_CONTRACT);
+    assert!(renamed.contains("produce: verdict payment_verified {"));
+}
+
+#[test]
+fn rename_entity_updates_imported_file() {
+    let types = "entity Order {\n  states: [draft, submitted]\n  initial: draft\n  transitions: [(draft, submitted)]\n}\n\npersona admin\n\nfact ready {\n  type: Bool\n  source: \"s.ready\"\n}\n";
+    let main = "import \"types.tenor\"\n\noperation submit_order {\n  allowed_personas: [admin]\n  precondition: ready = true\n  effects: [(Order, draft, submitted)]\n  error_contract: [precondition_failed]\n}\n";
+    let (root, path) = write_fixture_files(&[("main.tenor", main), ("types.tenor", types)]);
+    assert!(root.join("types.tenor").exists());
+
+    let edit = tenor_lsp::rename::compute_rename(
+        &path,
+        main,
+        Position::new(5, 14), // "Order" in effects
+        "PurchaseOrder",
+    )
+    .unwrap()
+    .unwrap();
+
+    assert!(apply_edits(&edit, "main.tenor", main).contains("effects: [(PurchaseOrder, draft"));
+    assert!(apply_edits(&edit, "types.tenor", types).starts_with("entity PurchaseOrder {"));
+}
+
+#[test]
+fn rename_rejects_conflicts_and_invalid_names() {
+    let (_, path) = write_fixture_files(&[("test.tenor", SAMPLE_CONTRACT)]);
+
+    // New name collides with an existing construct
+    let err =
+        tenor_lsp::rename::compute_rename(&path, SAMPLE_CONTRACT, Position::new(0, 5), "admin")
+            .unwrap_err();
+    assert!(err.contains("already declared"), "got: {}", err);
+
+    // New name collides with an entity state
+    let err =
+        tenor_lsp::rename::compute_rename(&path, SAMPLE_CONTRACT, Position::new(0, 5), "draft")
+            .unwrap_err();
+    assert!(err.contains("entity state"), "got: {}", err);
+
+    let err =
+        tenor_lsp::rename::compute_rename(&path, SAMPLE_CONTRACT, Position::new(0, 5), "not valid")
+            .unwrap_err();
+    assert!(err.contains("not a valid identifier"), "got: {}", err);
+}
+
+#[test]
+fn rename_on_non_symbol_returns_none() {
+    let (_, path) = write_fixture_files(&[("test.tenor", SAMPLE_CONTRACT)]);
+
+    // "stratum" keyword inside the rule body
+    let result =
+        tenor_lsp::rename::compute_rename(&path, SAMPLE_CONTRACT, Position::new(17, 3), "level")
+            .unwrap();
+    assert!(result.is_none());
+}
+
+// ──────────────────────────────────────────────
+// Diagnostics: analysis findings
+// ──────────────────────────────────────────────
+
+const DEAD_STATE_CONTRACT: &str = r#"persona clerk
+
+fact ready {
+  type: Bool
+  source: "desk.ready"
+}
+
+entity Ticket {
+  states: [open, closed, archived]
+  initial: open
+  transitions: [(open, closed)]
+}
+
+operation close_ticket {
+  allowed_personas: [clerk]
+  precondition: ready = true
+  effects: [(Ticket, open, closed)]
+  error_contract: [precondition_failed]
+}
+"#;
+
+#[test]
+fn diagnostics_report_dead_states_as_warnings() {
+    let (_, path) = write_fixture_files(&[("tickets.tenor", DEAD_STATE_CONTRACT)]);
+    let diags = tenor_lsp::diagnostics::compute_diagnostics(&path);
+
+    let dead = diags
+        .iter()
+        .find(|d| d.message.contains("unreachable state"))
+        .unwrap_or_else(|| panic!("expected a dead-state finding, got {:?}", diags));
+    assert_eq!(dead.severity, Some(lsp_types::DiagnosticSeverity::WARNING));
+    assert_eq!(
+        dead.code,
+        Some(lsp_types::NumberOrString::String("s2".to_string()))
+    );
+    assert!(dead.message.contains("archived"));
+    // Anchored on the `entity Ticket` declaration header.
+    assert_eq!(
+        dead.range,
+        lsp_types::Range::new(Position::new(7, 0), Position::new(7, 13))
+    );
+}
+
+#[test]
+fn diagnostics_skip_analysis_when_elaboration_fails() {
+    let broken = DEAD_STATE_CONTRACT.replace("initial: open", "initial: missing");
+    let (_, path) = write_fixture_files(&[("tickets.tenor", &broken)]);
+    let diags = tenor_lsp::diagnostics::compute_diagnostics(&path);
+
+    assert_eq!(diags.len(), 1);
+    assert_eq!(
+        diags[0].severity,
+        Some(lsp_types::DiagnosticSeverity::ERROR)
+    );
+}
+
+#[test]
+fn diagnostics_report_every_failing_construct() {
+    let broken = DEAD_STATE_CONTRACT
+        .replace("initial: open", "initial: missing")
+        .replace("allowed_personas: [clerk]", "allowed_personas: [ghost]");
+    let (_, path) = write_fixture_files(&[("tickets.tenor", &broken)]);
+    let diags = tenor_lsp::diagnostics::compute_diagnostics(&path);
+
+    assert_eq!(diags.len(), 2, "{:?}", diags);
+    assert!(diags[0].message.contains("missing"));
+    // Underlines the undeclared state, not the whole line.
+    assert_eq!(
+        diags[0].range,
+        lsp_types::Range::new(Position::new(9, 11), Position::new(9, 18))
+    );
+    assert!(diags[1].message.contains("ghost"));
+}
+
+#[test]
+fn diagnostics_underline_the_token_a_parse_error_names() {
+    let broken = DEAD_STATE_CONTRACT.replace("  type: Bool", "  type Bool");
+    let (_, path) = write_fixture_files(&[("tickets.tenor", &broken)]);
+    let diags = tenor_lsp::diagnostics::compute_diagnostics(&path);
+
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert_eq!(
+        diags[0].range,
+        lsp_types::Range::new(Position::new(3, 7), Position::new(3, 11))
+    );
+}
+
+#[test]
+fn analysis_findings_from_imported_files_stay_in_their_file() {
+    let (_, path) = write_fixture_files(&[
+        ("main.tenor", "import \"tickets.tenor\"\n"),
+        ("tickets.tenor", DEAD_STATE_CONTRACT),
+    ]);
+    let diags = tenor_lsp::diagnostics::compute_diagnostics(&path);
+    assert!(
+        diags
+            .iter()
+            .all(|d| !d.message.contains("unreachable state")),
+        "finding for imported entity leaked into main.tenor: {:?}",
+        diags
+    );
+}
+
+#[test]
+fn analysis_scheduler_debounces_per_document() {
+    use tenor_lsp::diagnostics::{AnalysisScheduler, ANALYSIS_DEBOUNCE};
+
+    let uri: Uri = "file:///tmp/a.tenor".parse().unwrap();
+    let path = std::path::PathBuf::from("/tmp/a.tenor");
+    let bundle = serde_json::json!({"constructs": []});
+    let start = std::time::Instant::now();
+
+    let mut scheduler = AnalysisScheduler::new();
+    assert!(scheduler.next_deadline().is_none());
+
+    scheduler.schedule(uri.clone(), path.clone(), bundle.clone(), start);
+    // A second save before the quiet period ends pushes the deadline out.
+    let later = start + ANALYSIS_DEBOUNCE / 2;
+    scheduler.schedule(uri.clone(), path, bundle, later);
+    assert_eq!(scheduler.next_deadline(), Some(later + ANALYSIS_DEBOUNCE));
+
+    assert!(scheduler.take_due(start + ANALYSIS_DEBOUNCE).is_empty());
+    let due = scheduler.take_due(later + ANALYSIS_DEBOUNCE);
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].uri, uri);
+    assert!(scheduler.next_deadline().is_none());
+
+    scheduler.schedule(
+        uri.clone(),
+        std::path::PathBuf::from("/tmp/a.tenor"),
+        serde_json::json!({}),
+        start,
+    );
+    scheduler.cancel(&uri);
+    assert!(scheduler.take_due(start + ANALYSIS_DEBOUNCE * 2).is_empty());
+}
+
+// ──────────────────────────────────────────────
+// Formatting
+// ──────────────────────────────────────────────
+
+const UNFORMATTED_CONTRACT: &str = "persona admin   \n\n\n\nentity Order {\n      states: [draft, done]\ninitial: draft\n  transitions: [\n(draft, done)\n     ]\n}\n";
+
+#[test]
+fn semantic_tokens_use_lexer_positions() {
+    // The escaped quote would throw off a search for the token text.
+    let source = "fact f {\n  type: Text\n  source: \"a\\\"b.c\"\n}\n";
+    let tokens = tenor_lsp::semantic_tokens::compute_semantic_tokens(
+        std::path::Path::new("t.tenor"),
+        source,
+    )
+    .expect("tokens");
+    let mut line = 0;
+    let mut col = 0;
+    let absolute: Vec<(u32, u32, u32)> = tokens
+        .iter()
+        .map(|t| {
+            if t.delta_line > 0 {
+                col = 0;
+            }
+            line += t.delta_line;
+            col += t.delta_start;
+            (line, col, t.length)
+        })
+        .collect();
+    assert!(absolute.contains(&(2, 10, 8)), "{:?}", absolute);
+}
+
+#[test]
+fn format_document_returns_single_full_edit() {
+    let edits = tenor_lsp::formatting::format_document(UNFORMATTED_CONTRACT);
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range.start, Position::new(0, 0));
+    assert_eq!(edits[0].range.end, Position::new(11, 0));
+    assert_eq!(
+        edits[0].new_text,
+        "persona admin\n\nentity Order {\n  states: [draft, done]\n  initial: draft\n  transitions: [\n    (draft, done)\n  ]\n}\n"
+    );
+
+    // Already-formatted input produces no edits.
+    assert!(tenor_lsp::formatting::format_document(&edits[0].new_text).is_empty());
+}
+
+#[test]
+fn format_range_only_touches_selected_lines() {
+    let range = lsp_types::Range::new(Position::new(5, 0), Position::new(6, 3));
+    let edits = tenor_lsp::formatting::format_range(UNFORMATTED_CONTRACT, range);
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range.start, Position::new(5, 0));
+    assert_eq!(edits[0].range.end, Position::new(7, 0));
+    assert_eq!(
+        edits[0].new_text,
+        "  states: [draft, done]\n  initial: draft\n"
+    );
+}
+
+// ──────────────────────────────────────────────
+// Navigation: cross-file definitions and references
+// ──────────────────────────────────────────────
+
+/// Helper: `file://` URI for a path, matching the index's canonical form.
+fn file_uri(path: &std::path::Path) -> Uri {
+    let abs = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let path_str = abs.to_string_lossy().to_string();
+    #[cfg(windows)]
+    let uri_str = {
+        let p = path_str.strip_prefix(r"\\?\").unwrap_or(&path_str);
+        format!("file:///{}", p.replace('\\', "/"))
+    };
+    #[cfg(not(windows))]
+    let uri_str = format!("file://{}", path_str);
+    uri_str.parse::<Uri>().expect("URI parse")
+}
+
+const IMPORTING_CONTRACT: &str = r#"import "facts.tenor"
+
+persona admin
+
+rule check_payment {
+  stratum: 0
+  when: payment_ok = true
+  produce: verdict payment_valid { payload: Bool = true }
+}
+"#;
+
+const IMPORTED_FACTS: &str = r#"fact payment_ok {
+  type: Bool
+  source: "billing.payment_ok"
+}
+"#;
+
+#[test]
+fn goto_definition_resolves_fact_in_imported_file() {
+    let (root, main) = write_fixture_files(&[
+        ("main.tenor", IMPORTING_CONTRACT),
+        ("facts.tenor", IMPORTED_FACTS),
+    ]);
+    let index = tenor_lsp::navigation::build_project_index(&root);
+    let uri = file_uri(&main);
+
+    let loc = tenor_lsp::navigation::goto_definition(
+        &index,
+        &uri,
+        Position::new(6, 10),
+        IMPORTING_CONTRACT,
+    )
+    .expect("definition");
+    assert_eq!(loc.uri, file_uri(&root.join("facts.tenor")));
+    assert_eq!(loc.range.start.line, 0);
+}
+
+#[test]
+fn goto_definition_on_import_line_opens_imported_file() {
+    let (root, main) = write_fixture_files(&[
+        ("main.tenor", IMPORTING_CONTRACT),
+        ("facts.tenor", IMPORTED_FACTS),
+    ]);
+    let index = tenor_lsp::navigation::build_project_index(&root);
+
+    let loc = tenor_lsp::navigation::goto_definition(
+        &index,
+        &file_uri(&main),
+        Position::new(0, 9),
+        IMPORTING_CONTRACT,
+    )
+    .expect("import target");
+    assert_eq!(loc.uri, file_uri(&root.join("facts.tenor")));
+}
+
+#[test]
+fn navigation_prefers_declarations_from_the_same_import_graph() {
+    // Two unrelated bundles in one workspace both declare `payment_ok`.
+    let (root, main) = write_fixture_files(&[
+        ("main.tenor", IMPORTING_CONTRACT),
+        ("facts.tenor", IMPORTED_FACTS),
+        ("a_other.tenor", IMPORTED_FACTS),
+    ]);
+    let index = tenor_lsp::navigation::build_project_index(&root);
+    let uri = file_uri(&main);
+
+    let loc = tenor_lsp::navigation::goto_definition(
+        &index,
+        &uri,
+        Position::new(6, 10),
+        IMPORTING_CONTRACT,
+    )
+    .expect("definition");
+    assert_eq!(loc.uri, file_uri(&root.join("facts.tenor")));
+
+    let refs = tenor_lsp::navigation::find_references(
+        &index,
+        &uri,
+        Position::new(6, 10),
+        IMPORTING_CONTRACT,
+    );
+    assert!(!refs.is_empty());
+    assert!(
+        refs.iter()
+            .all(|r| r.uri != file_uri(&root.join("a_other.tenor"))),
+        "references leaked from an unrelated bundle: {:?}",
+        refs
+    );
+}
+
+#[test]
+fn index_follows_imports_outside_workspace_root() {
+    let (root, _) = write_fixture_files(&[("facts.tenor", IMPORTED_FACTS)]);
+    let project = root.join("project");
+    std::fs::create_dir(&project).unwrap();
+    let main_src = IMPORTING_CONTRACT.replace("\"facts.tenor\"", "\"../facts.tenor\"");
+    std::fs::write(project.join("main.tenor"), &main_src).unwrap();
+
+    let index = tenor_lsp::navigation::build_project_index(&project);
+    let loc = tenor_lsp::navigation::goto_definition(
+        &index,
+        &file_uri(&project.join("main.tenor")),
+        Position::new(6, 10),
+        &main_src,
+    )
+    .expect("definition outside root");
+    assert_eq!(loc.uri, file_uri(&root.join("facts.tenor")));
+}
+
+#[test]
+fn update_and_remove_file_keep_index_current() {
+    let (root, main) = write_fixture_files(&[("main.tenor", IMPORTING_CONTRACT)]);
+    let mut index = tenor_lsp::navigation::build_project_index(&root);
+    let uri = file_uri(&main);
+    let find = |index: &tenor_lsp::navigation::ProjectIndex| {
+        tenor_lsp::navigation::goto_definition(
+            index,
+            &uri,
+            Position::new(6, 10),
+            IMPORTING_CONTRACT,
+        )
+    };
+    assert!(find(&index).is_none());
+
+    let facts = root.join("facts.tenor");
+    std::fs::write(&facts, IMPORTED_FACTS).unwrap();
+    index.update_file(&facts);
+    assert_eq!(find(&index).expect("added").uri, file_uri(&facts));
+
+    std::fs::remove_file(&facts).unwrap();
+    index.remove_file(&facts);
+    assert!(find(&index).is_none());
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::future::Future;
 
 use super::{
@@ -127,6 +128,11 @@ where
         "commit_then_read_entity_state",
         commit_then_read_entity_state(factory).await,
     ));
+    results.push(TestResult::from_result(
+        "commit",
+        "attribute_updates_merged_after_commit",
+        attribute_updates_merged_after_commit(factory).await,
+    ));
     results.push(TestResult::from_result(
         "commit",
         "commit_then_read_flow_execution",
@@ -1234,6 +1240,49 @@ where
     Ok(())
 }
 
+/// Attribute updates merge into the stored attributes without bumping the
+/// version.
+async fn attribute_updates_merged_after_commit<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.initialize_entity(&mut snap, "Order", "order-1", "draft")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.commit_snapshot(snap).await.map_err(|e| e.to_string())?;
+
+    for (name, value) in [
+        ("total", serde_json::json!(120)),
+        ("region", serde_json::json!("EU")),
+    ] {
+        let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+        let attributes = BTreeMap::from([(name.to_string(), value)]);
+        s.update_entity_attributes(&mut snap, "Order", "order-1", &attributes)
+            .await
+            .map_err(|e| e.to_string())?;
+        s.commit_snapshot(snap).await.map_err(|e| e.to_string())?;
+    }
+
+    let rec = s
+        .get_entity_state("Order", "order-1")
+        .await
+        .map_err(|e| e.to_string())?;
+    if rec.attributes.get("total") != Some(&serde_json::json!(120))
+        || rec.attributes.get("region") != Some(&serde_json::json!("EU"))
+    {
+        return Err(format!("unexpected attributes: {:?}", rec.attributes));
+    }
+    if rec.version != 0 {
+        return Err(format!("expected version 0, got {}", rec.version));
+    }
+    Ok(())
+}
+
 /// Standard read-after-commit for flow execution.
 async fn commit_then_read_flow_execution<S, F, Fut>(factory: &F) -> Result<(), String>
 where
@@ -0,0 +1,339 @@
+use std::future::Future;
+
+use super::{
+    make_entity_transition, make_flow_execution, make_operation_execution, make_provenance_record,
+    TestResult,
+};
+use crate::TenorStorage;
+
+pub(super) async fn run_history_tests<S, F, Fut>(factory: &F) -> Vec<TestResult>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let mut results = Vec::new();
+
+    results.push(TestResult::from_result(
+        "history",
+        "list_entity_transitions_oldest_first",
+        list_entity_transitions_oldest_first(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "history",
+        "list_entity_transitions_until_omits_later",
+        list_entity_transitions_until_omits_later(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "history",
+        "list_entity_transitions_excludes_aborted",
+        list_entity_transitions_excludes_aborted(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "history",
+        "list_entity_transitions_scoped_to_entity",
+        list_entity_transitions_scoped_to_entity(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "history",
+        "list_correlated_flow_executions_oldest_first",
+        list_correlated_flow_executions_oldest_first(factory).await,
+    ));
+
+    results
+}
+
+/// Initialize `entity_id`/`instance_id` in state `initial` and commit.
+async fn init<S: TenorStorage>(s: &S, entity_id: &str, instance_id: &str) -> Result<(), String> {
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.initialize_entity(&mut snap, entity_id, instance_id, "initial")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.commit_snapshot(snap).await.map_err(|e| e.to_string())
+}
+
+/// Move an instance from `from` (at `from_version`) to `to` through an
+/// operation executed at `executed_at`, committing or aborting the snapshot.
+#[allow(clippy::too_many_arguments)]
+async fn transition<S: TenorStorage>(
+    s: &S,
+    exec: &str,
+    entity_id: &str,
+    instance_id: &str,
+    from: &str,
+    to: &str,
+    from_version: i64,
+    executed_at: &str,
+    commit: bool,
+) -> Result<(), String> {
+    let flow_exec = format!("flow-{}", exec);
+    let op_exec = format!("op-{}", exec);
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.insert_flow_execution(&mut snap, make_flow_execution(&flow_exec, "flow-1"))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut op = make_operation_execution(&op_exec, &flow_exec, "op-1");
+    op.executed_at = executed_at.to_string();
+    s.insert_operation_execution(&mut snap, op)
+        .await
+        .map_err(|e| e.to_string())?;
+    s.update_entity_state(
+        &mut snap,
+        entity_id,
+        instance_id,
+        from_version,
+        to,
+        "flow-1",
+        "op-1",
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    s.insert_entity_transition(
+        &mut snap,
+        make_entity_transition(
+            &format!("trans-{}", exec),
+            &op_exec,
+            entity_id,
+            instance_id,
+            from,
+            to,
+            from_version,
+            from_version + 1,
+        ),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    s.insert_provenance_record(
+        &mut snap,
+        make_provenance_record(&format!("prov-{}", exec), &op_exec),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    if commit {
+        s.commit_snapshot(snap).await.map_err(|e| e.to_string())
+    } else {
+        s.abort_snapshot(snap).await.map_err(|e| e.to_string())
+    }
+}
+
+/// Two committed transitions come back in execution order with the
+/// operation's `executed_at`.
+async fn list_entity_transitions_oldest_first<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    init(&s, "Order", "order-1").await?;
+    transition(
+        &s,
+        "1",
+        "Order",
+        "order-1",
+        "initial",
+        "submitted",
+        0,
+        "2025-03-01T09:00:00Z",
+        true,
+    )
+    .await?;
+    transition(
+        &s,
+        "2",
+        "Order",
+        "order-1",
+        "submitted",
+        "approved",
+        1,
+        "2025-03-05T09:00:00Z",
+        true,
+    )
+    .await?;
+
+    let history = s
+        .list_entity_transitions("Order", None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let seen: Vec<(&str, &str)> = history
+        .iter()
+        .map(|h| (h.transition.to_state.as_str(), h.executed_at.as_str()))
+        .collect();
+    let expected = [
+        ("submitted", "2025-03-01T09:00:00Z"),
+        ("approved", "2025-03-05T09:00:00Z"),
+    ];
+    if seen != expected {
+        return Err(format!("expected {:?}, got {:?}", expected, seen));
+    }
+    Ok(())
+}
+
+/// `until` omits transitions executed after it.
+async fn list_entity_transitions_until_omits_later<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    init(&s, "Order", "order-1").await?;
+    transition(
+        &s,
+        "1",
+        "Order",
+        "order-1",
+        "initial",
+        "submitted",
+        0,
+        "2025-03-01T09:00:00Z",
+        true,
+    )
+    .await?;
+    transition(
+        &s,
+        "2",
+        "Order",
+        "order-1",
+        "submitted",
+        "approved",
+        1,
+        "2025-03-05T09:00:00Z",
+        true,
+    )
+    .await?;
+
+    let history = s
+        .list_entity_transitions("Order", Some("2025-03-03T00:00:00Z"))
+        .await
+        .map_err(|e| e.to_string())?;
+    if history.len() != 1 || history[0].transition.to_state != "submitted" {
+        return Err(format!(
+            "expected only the 'submitted' transition, got {:?}",
+            history
+        ));
+    }
+    Ok(())
+}
+
+/// Transitions from an aborted snapshot are not listed.
+async fn list_entity_transitions_excludes_aborted<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    init(&s, "Order", "order-1").await?;
+    transition(
+        &s,
+        "1",
+        "Order",
+        "order-1",
+        "initial",
+        "submitted",
+        0,
+        "2025-03-01T09:00:00Z",
+        false,
+    )
+    .await?;
+
+    let history = s
+        .list_entity_transitions("Order", None)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !history.is_empty() {
+        return Err(format!("expected no transitions, got {:?}", history));
+    }
+    Ok(())
+}
+
+/// Only transitions of the requested entity are listed.
+async fn list_entity_transitions_scoped_to_entity<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    init(&s, "Order", "order-1").await?;
+    init(&s, "Invoice", "inv-1").await?;
+    transition(
+        &s,
+        "1",
+        "Order",
+        "order-1",
+        "initial",
+        "submitted",
+        0,
+        "2025-03-01T09:00:00Z",
+        true,
+    )
+    .await?;
+    transition(
+        &s,
+        "2",
+        "Invoice",
+        "inv-1",
+        "initial",
+        "issued",
+        0,
+        "2025-03-02T09:00:00Z",
+        true,
+    )
+    .await?;
+
+    let history = s
+        .list_entity_transitions("Invoice", None)
+        .await
+        .map_err(|e| e.to_string())?;
+    if history.len() != 1 || history[0].transition.entity_id != "Invoice" {
+        return Err(format!(
+            "expected only the Invoice transition, got {:?}",
+            history
+        ));
+    }
+    Ok(())
+}
+
+/// Flow executions sharing a correlation id come back oldest first;
+/// uncorrelated and aborted ones are left out.
+async fn list_correlated_flow_executions_oldest_first<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    let runs = [
+        ("flow-b", Some("order-42"), "2025-03-05T09:00:00Z", true),
+        ("flow-a", Some("order-42"), "2025-03-01T09:00:00Z", true),
+        ("flow-c", Some("order-7"), "2025-03-02T09:00:00Z", true),
+        ("flow-d", None, "2025-03-03T09:00:00Z", true),
+        ("flow-e", Some("order-42"), "2025-03-04T09:00:00Z", false),
+    ];
+    for (id, correlation_id, started_at, commit) in runs {
+        let mut record = make_flow_execution(id, id);
+        record.correlation_id = correlation_id.map(String::from);
+        record.started_at = started_at.to_string();
+        let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+        s.insert_flow_execution(&mut snap, record)
+            .await
+            .map_err(|e| e.to_string())?;
+        if commit {
+            s.commit_snapshot(snap).await.map_err(|e| e.to_string())?;
+        } else {
+            s.abort_snapshot(snap).await.map_err(|e| e.to_string())?;
+        }
+    }
+
+    let executions = s
+        .list_correlated_flow_executions("order-42")
+        .await
+        .map_err(|e| e.to_string())?;
+    let seen: Vec<&str> = executions.iter().map(|e| e.id.as_str()).collect();
+    if seen != ["flow-a", "flow-b"] {
+        return Err(format!("expected [flow-a, flow-b], got {:?}", seen));
+    }
+    Ok(())
+}
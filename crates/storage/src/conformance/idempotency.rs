@@ -0,0 +1,241 @@
+use std::future::Future;
+
+use super::{make_flow_execution, make_operation_execution, TestResult};
+use crate::{OperationExecutionRecord, StorageError, TenorStorage};
+
+pub(super) async fn run_idempotency_tests<S, F, Fut>(factory: &F) -> Vec<TestResult>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let mut results = Vec::new();
+
+    results.push(TestResult::from_result(
+        "idempotency",
+        "keyed_execution_found_after_commit",
+        keyed_execution_found_after_commit(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "idempotency",
+        "unknown_key_returns_none",
+        unknown_key_returns_none(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "idempotency",
+        "duplicate_key_rejected_after_commit",
+        duplicate_key_rejected_after_commit(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "idempotency",
+        "duplicate_key_rejected_in_same_snapshot",
+        duplicate_key_rejected_in_same_snapshot(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "idempotency",
+        "aborted_key_not_found_and_reusable",
+        aborted_key_not_found_and_reusable(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "idempotency",
+        "unkeyed_executions_do_not_conflict",
+        unkeyed_executions_do_not_conflict(factory).await,
+    ));
+
+    results
+}
+
+/// An operation execution for `exec` carrying `key` and a response naming it.
+fn keyed(exec: &str, key: Option<&str>) -> OperationExecutionRecord {
+    let mut op = make_operation_execution(&format!("op-{}", exec), "flow-exec-1", "op-1");
+    op.idempotency_key = key.map(str::to_string);
+    op.response = key.map(|_| serde_json::json!({ "outcome": "approved", "exec": exec }));
+    op
+}
+
+/// Insert the flow execution the keyed records reference, and commit.
+async fn setup<S: TenorStorage>(s: &S) -> Result<(), String> {
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.insert_flow_execution(&mut snap, make_flow_execution("flow-exec-1", "flow-1"))
+        .await
+        .map_err(|e| e.to_string())?;
+    s.commit_snapshot(snap).await.map_err(|e| e.to_string())
+}
+
+/// Insert `record` in its own snapshot, then commit or abort. An insert
+/// error aborts the snapshot and is returned as is.
+async fn execute<S: TenorStorage>(
+    s: &S,
+    record: OperationExecutionRecord,
+    commit: bool,
+) -> Result<(), StorageError> {
+    let mut snap = s.begin_snapshot().await?;
+    if let Err(e) = s.insert_operation_execution(&mut snap, record).await {
+        let _ = s.abort_snapshot(snap).await;
+        return Err(e);
+    }
+    if commit {
+        s.commit_snapshot(snap).await
+    } else {
+        s.abort_snapshot(snap).await
+    }
+}
+
+/// A committed execution is found by its key, with its stored response.
+async fn keyed_execution_found_after_commit<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    setup(&s).await?;
+    execute(&s, keyed("1", Some("key-1")), true)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let found = s
+        .get_operation_execution_by_idempotency_key("key-1")
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("expected an execution for key-1")?;
+    if found.id != "op-1" {
+        return Err(format!("expected execution 'op-1', got '{}'", found.id));
+    }
+    let expected = serde_json::json!({ "outcome": "approved", "exec": "1" });
+    if found.response.as_ref() != Some(&expected) {
+        return Err(format!(
+            "expected response {}, got {:?}",
+            expected, found.response
+        ));
+    }
+    Ok(())
+}
+
+/// Looking up a key no execution carries returns `None`.
+async fn unknown_key_returns_none<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    match s
+        .get_operation_execution_by_idempotency_key("missing")
+        .await
+    {
+        Ok(None) => Ok(()),
+        Ok(Some(r)) => Err(format!("expected None, got {:?}", r)),
+        Err(e) => Err(format!("expected Ok(None), got error: {}", e)),
+    }
+}
+
+/// A second execution with a committed key fails with
+/// `DuplicateIdempotencyKey`, at insert or at commit, and leaves the
+/// original response in place.
+async fn duplicate_key_rejected_after_commit<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    setup(&s).await?;
+    execute(&s, keyed("1", Some("key-1")), true)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match execute(&s, keyed("2", Some("key-1")), true).await {
+        Err(StorageError::DuplicateIdempotencyKey { idempotency_key })
+            if idempotency_key == "key-1" => {}
+        other => {
+            return Err(format!(
+                "expected DuplicateIdempotencyKey for key-1, got {:?}",
+                other
+            ))
+        }
+    }
+    let found = s
+        .get_operation_execution_by_idempotency_key("key-1")
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("expected an execution for key-1")?;
+    if found.id != "op-1" {
+        return Err(format!(
+            "expected the original execution 'op-1', got '{}'",
+            found.id
+        ));
+    }
+    Ok(())
+}
+
+/// Two executions with the same key cannot be inserted in one snapshot.
+async fn duplicate_key_rejected_in_same_snapshot<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    setup(&s).await?;
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.insert_operation_execution(&mut snap, keyed("1", Some("key-1")))
+        .await
+        .map_err(|e| e.to_string())?;
+    let result = match s
+        .insert_operation_execution(&mut snap, keyed("2", Some("key-1")))
+        .await
+    {
+        Ok(()) => s.commit_snapshot(snap).await,
+        Err(e) => {
+            let _ = s.abort_snapshot(snap).await;
+            Err(e)
+        }
+    };
+    match result {
+        Err(StorageError::DuplicateIdempotencyKey { .. }) => Ok(()),
+        other => Err(format!("expected DuplicateIdempotencyKey, got {:?}", other)),
+    }
+}
+
+/// The key of an aborted execution is not found and can be used again.
+async fn aborted_key_not_found_and_reusable<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    setup(&s).await?;
+    execute(&s, keyed("1", Some("key-1")), false)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let found = s
+        .get_operation_execution_by_idempotency_key("key-1")
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(r) = found {
+        return Err(format!("expected no execution after abort, got {:?}", r));
+    }
+    execute(&s, keyed("2", Some("key-1")), true)
+        .await
+        .map_err(|e| format!("reusing an aborted key failed: {}", e))
+}
+
+/// Executions without a key never conflict with each other.
+async fn unkeyed_executions_do_not_conflict<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    setup(&s).await?;
+    execute(&s, keyed("1", None), true)
+        .await
+        .map_err(|e| e.to_string())?;
+    execute(&s, keyed("2", None), true)
+        .await
+        .map_err(|e| format!("second unkeyed execution failed: {}", e))
+}
@@ -86,6 +86,21 @@ where
         "initialize_sets_null_flow_and_operation_ids",
         initialize_sets_null_flow_and_operation_ids(factory).await,
     ));
+    results.push(TestResult::from_result(
+        "init",
+        "deleted_entity_not_found_after_commit",
+        deleted_entity_not_found_after_commit(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "init",
+        "delete_missing_entity_returns_not_found",
+        delete_missing_entity_returns_not_found(factory).await,
+    ));
+    results.push(TestResult::from_result(
+        "init",
+        "deleted_entity_can_be_reinitialized",
+        deleted_entity_can_be_reinitialized(factory).await,
+    ));
 
     results
 }
@@ -539,3 +554,85 @@ where
     }
     Ok(())
 }
+
+/// After delete + commit, the entity must no longer exist.
+async fn deleted_entity_not_found_after_commit<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.initialize_entity(&mut snap, "Order", "order-1", "initial")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.commit_snapshot(snap).await.map_err(|e| e.to_string())?;
+
+    let mut snap2 = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.delete_entity(&mut snap2, "Order", "order-1")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.commit_snapshot(snap2).await.map_err(|e| e.to_string())?;
+
+    match s.get_entity_state("Order", "order-1").await {
+        Err(StorageError::EntityNotFound { .. }) => Ok(()),
+        Err(e) => Err(format!("expected EntityNotFound, got: {e}")),
+        Ok(_) => Err("entity should not exist after delete".to_string()),
+    }
+}
+
+/// Deleting an entity that does not exist returns EntityNotFound.
+async fn delete_missing_entity_returns_not_found<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    let result = s.delete_entity(&mut snap, "Order", "order-1").await;
+    s.abort_snapshot(snap).await.map_err(|e| e.to_string())?;
+
+    match result {
+        Err(StorageError::EntityNotFound { .. }) => Ok(()),
+        Err(e) => Err(format!("expected EntityNotFound, got: {e}")),
+        Ok(()) => Err("delete of a missing entity should fail".to_string()),
+    }
+}
+
+/// A deleted instance id can be initialized again, starting over at version 0.
+async fn deleted_entity_can_be_reinitialized<S, F, Fut>(factory: &F) -> Result<(), String>
+where
+    S: TenorStorage,
+    F: Fn() -> Fut,
+    Fut: Future<Output = S>,
+{
+    let s = factory().await;
+    let mut snap = s.begin_snapshot().await.map_err(|e| e.to_string())?;
+    s.initialize_entity(&mut snap, "Order", "order-1", "initial")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.update_entity_state(&mut snap, "Order", "order-1", 0, "done", "flow-1", "op-1")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.delete_entity(&mut snap, "Order", "order-1")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.initialize_entity(&mut snap, "Order", "order-1", "initial")
+        .await
+        .map_err(|e| e.to_string())?;
+    s.commit_snapshot(snap).await.map_err(|e| e.to_string())?;
+
+    let rec = s
+        .get_entity_state("Order", "order-1")
+        .await
+        .map_err(|e| e.to_string())?;
+    if rec.state != "initial" || rec.version != 0 {
+        return Err(format!(
+            "expected state \"initial\" at version 0, got \"{}\" at version {}",
+            rec.state, rec.version
+        ));
+    }
+    Ok(())
+}
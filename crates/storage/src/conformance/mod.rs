@@ -8,6 +8,8 @@
 //! - **Atomic commit**: all-or-nothing semantics for multi-record snapshots
 //! - **Version validation / OCC**: optimistic concurrency conflict detection
 //! - **Provenance coupling**: provenance records tied to operation executions
+//! - **Transition history**: transitions listed in order, as of a time
+//! - **Idempotency keys**: unique keys, stored responses, reuse after abort
 //! - **Error handling**: correct error variants for invalid operations
 //!
 //! # Usage
@@ -30,6 +32,8 @@
 mod commit;
 mod concurrent;
 mod error;
+mod history;
+mod idempotency;
 mod init;
 mod provenance;
 mod snapshot;
@@ -133,6 +137,8 @@ where
     results.extend(version::run_version_tests(&factory).await);
     results.extend(provenance::run_provenance_tests(&factory).await);
     results.extend(concurrent::run_concurrent_tests(&factory).await);
+    results.extend(history::run_history_tests(&factory).await);
+    results.extend(idempotency::run_idempotency_tests(&factory).await);
 
     let passed = results.iter().filter(|r| r.passed).count();
     let total = results.len();
@@ -158,6 +164,7 @@ fn make_flow_execution(id: &str, flow_id: &str) -> FlowExecutionRecord {
         outcome: "success".to_string(),
         snapshot_facts: serde_json::json!({"test": true}),
         snapshot_verdicts: serde_json::json!({"approved": true}),
+        correlation_id: None,
     }
 }
 
@@ -174,6 +181,8 @@ fn make_operation_execution(
         outcome: "success".to_string(),
         executed_at: "2025-01-01T00:00:30Z".to_string(),
         step_id: "step-1".to_string(),
+        idempotency_key: None,
+        response: None,
     }
 }
 
@@ -30,6 +30,12 @@ pub enum StorageError {
     #[error("flow execution not found: {execution_id}")]
     ExecutionNotFound { execution_id: String },
 
+    /// An operation execution with this idempotency key already exists.
+    /// The request is a retry: abort the snapshot and replay the stored
+    /// response instead of applying the operation again.
+    #[error("duplicate idempotency key: {idempotency_key}")]
+    DuplicateIdempotencyKey { idempotency_key: String },
+
     /// A backend-specific storage error (DB connection, serialization, etc.).
     #[error("storage backend error: {0}")]
     Backend(String),
@@ -6,7 +6,7 @@ pub mod conformance;
 
 pub use error::StorageError;
 pub use record::{
-    EntityStateRecord, EntityTransitionRecord, FlowExecutionRecord, OperationExecutionRecord,
-    ProvenanceRecord,
+    EntityStateRecord, EntityTransitionHistoryRecord, EntityTransitionRecord, FlowExecutionRecord,
+    OperationExecutionRecord, ProvenanceRecord,
 };
 pub use traits::TenorStorage;
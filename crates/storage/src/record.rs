@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// A snapshot of an entity's current state as stored in the backend.
@@ -11,6 +13,9 @@ pub struct EntityStateRecord {
     pub updated_at: String,
     pub last_flow_id: Option<String>,
     pub last_operation_id: Option<String>,
+    /// Entity attribute values by name. Empty until an effect sets one.
+    #[serde(default)]
+    pub attributes: BTreeMap<String, serde_json::Value>,
 }
 
 /// A record of a completed flow execution.
@@ -27,6 +32,10 @@ pub struct FlowExecutionRecord {
     pub outcome: String,
     pub snapshot_facts: serde_json::Value,
     pub snapshot_verdicts: serde_json::Value,
+    /// Caller-supplied id shared by the flow executions of one business
+    /// transaction (a flow and its resumptions, or a chain of flows).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
 }
 
 /// A record of a single operation execution within a flow.
@@ -40,6 +49,15 @@ pub struct OperationExecutionRecord {
     /// ISO 8601 / RFC 3339 timestamp string.
     pub executed_at: String,
     pub step_id: String,
+    /// Client-supplied key identifying a logical request. Unique among
+    /// committed operation executions; a retry carrying the same key is
+    /// answered from `response` instead of being applied again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// The response returned for the original request, replayed verbatim
+    /// on retries with the same `idempotency_key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<serde_json::Value>,
 }
 
 /// A record of a single entity state transition.
@@ -55,6 +73,18 @@ pub struct EntityTransitionRecord {
     pub to_version: i64,
 }
 
+/// An entity transition together with the time its operation executed.
+///
+/// Returned by `TenorStorage::list_entity_transitions`. `executed_at` is the
+/// `executed_at` of the referenced operation execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityTransitionHistoryRecord {
+    #[serde(flatten)]
+    pub transition: EntityTransitionRecord,
+    /// ISO 8601 / RFC 3339 timestamp string.
+    pub executed_at: String,
+}
+
 /// A provenance record coupling operation execution to facts + verdicts.
 ///
 /// Per spec C7: every state transition must have an atomically coupled
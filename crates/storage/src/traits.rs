@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 
 use crate::error::StorageError;
 use crate::record::{
-    EntityStateRecord, EntityTransitionRecord, FlowExecutionRecord, OperationExecutionRecord,
-    ProvenanceRecord,
+    EntityStateRecord, EntityTransitionHistoryRecord, EntityTransitionRecord, FlowExecutionRecord,
+    OperationExecutionRecord, ProvenanceRecord,
 };
 
 /// The storage trait for Tenor execution backends.
@@ -86,6 +88,10 @@ pub trait TenorStorage: Send + Sync + 'static {
     ///
     /// The UPDATE is conditional on `version = expected_version`.
     /// If zero rows are affected, returns `Err(StorageError::ConcurrentConflict)`.
+    /// Executors pass the version they read the instance at (the
+    /// `EntityVersions` given to `tenor_eval::execute_operation`), plus one
+    /// per transition already written in the snapshot, and report a conflict
+    /// as the operation's `ConcurrentConflict` failure.
     ///
     /// Returns the new version number on success.
     #[allow(clippy::too_many_arguments)]
@@ -100,6 +106,34 @@ pub trait TenorStorage: Send + Sync + 'static {
         operation_id: &str,
     ) -> Result<i64, StorageError>;
 
+    /// Merge attribute values set by an effect into an entity instance.
+    ///
+    /// Called in the same snapshot as the `update_entity_state` for the
+    /// effect that set them. Attributes not named in `attributes` keep their
+    /// stored values. Does not change the version.
+    ///
+    /// Returns `Err(StorageError::EntityNotFound)` if the entity does not exist.
+    async fn update_entity_attributes(
+        &self,
+        snapshot: &mut Self::Snapshot,
+        entity_id: &str,
+        instance_id: &str,
+        attributes: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<(), StorageError>;
+
+    /// Remove an entity instance, along with its attributes.
+    ///
+    /// Used when an operation applies a `destroy_instance` effect. Transition
+    /// and provenance records that mention the instance are kept.
+    ///
+    /// Returns `Err(StorageError::EntityNotFound)` if the entity does not exist.
+    async fn delete_entity(
+        &self,
+        snapshot: &mut Self::Snapshot,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Result<(), StorageError>;
+
     // ── Recording operations (within snapshot) ────────────────────────────────
 
     /// Insert a flow execution record.
@@ -113,6 +147,11 @@ pub trait TenorStorage: Send + Sync + 'static {
     ///
     /// Must be inserted BEFORE any `entity_transitions` that reference it,
     /// due to the FK constraint `entity_transitions.operation_execution_id`.
+    ///
+    /// `idempotency_key`, when set, is UNIQUE: returns
+    /// `Err(StorageError::DuplicateIdempotencyKey)` if a committed execution,
+    /// or one inserted earlier in the same snapshot, carries the same key.
+    /// The key of an aborted execution may be reused.
     async fn insert_operation_execution(
         &self,
         snapshot: &mut Self::Snapshot,
@@ -175,6 +214,27 @@ pub trait TenorStorage: Send + Sync + 'static {
         operation_execution_id: &str,
     ) -> Result<Vec<ProvenanceRecord>, StorageError>;
 
+    /// Read the committed operation execution carrying `idempotency_key`.
+    ///
+    /// Executors call this before applying a request that carries a key:
+    /// `Some` means the request is a retry, and the record's `response` is
+    /// returned instead of executing again. Otherwise the operation runs and
+    /// its record is inserted with the key and response in the same snapshot
+    /// as its entity transitions. If a concurrent request committed the key
+    /// first, that insert or the commit fails with
+    /// `StorageError::DuplicateIdempotencyKey`; abort and look the key up again.
+    ///
+    /// Returns `Ok(None)` if no committed execution carries the key. The
+    /// default finds nothing, for backends that do not store keys yet; a
+    /// backend storing them must override it to pass the conformance suite.
+    async fn get_operation_execution_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> Result<Option<OperationExecutionRecord>, StorageError> {
+        let _ = idempotency_key;
+        Ok(None)
+    }
+
     /// List flow executions with optional filters.
     ///
     /// - `flow_id`: filter to a specific flow
@@ -186,4 +246,23 @@ pub trait TenorStorage: Send + Sync + 'static {
         outcome: Option<&str>,
         limit: usize,
     ) -> Result<Vec<FlowExecutionRecord>, StorageError>;
+
+    /// List the committed flow executions carrying `correlation_id`, oldest
+    /// first (by `started_at`), so a business transaction spanning several
+    /// flow runs can be read back end to end.
+    async fn list_correlated_flow_executions(
+        &self,
+        correlation_id: &str,
+    ) -> Result<Vec<FlowExecutionRecord>, StorageError>;
+
+    /// List the committed transitions of every instance of an entity,
+    /// oldest first (by `executed_at`, then `to_version`), each with the
+    /// `executed_at` of its operation execution.
+    ///
+    /// - `until`: RFC 3339 timestamp; transitions executed after it are omitted
+    async fn list_entity_transitions(
+        &self,
+        entity_id: &str,
+        until: Option<&str>,
+    ) -> Result<Vec<EntityTransitionHistoryRecord>, StorageError>;
 }
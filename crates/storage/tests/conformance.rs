@@ -0,0 +1,471 @@
+//! Runs the `TenorStorage` conformance suite against an in-memory reference
+//! backend, so every conformance case executes on each `cargo test`.
+//!
+//! The backend keeps committed rows behind one mutex. A snapshot buffers its
+//! writes and takes a row lock on each entity instance it writes; a second
+//! snapshot writing a locked instance fails as if it had lost the OCC race.
+//! Reads for update do not lock: two snapshots may read the same version,
+//! and the version check on write decides which one wins.
+//! Commit applies the buffered writes, re-checking idempotency keys against
+//! executions committed since the insert.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tenor_storage::conformance::run_conformance_suite;
+use tenor_storage::{
+    EntityStateRecord, EntityTransitionHistoryRecord, EntityTransitionRecord, FlowExecutionRecord,
+    OperationExecutionRecord, ProvenanceRecord, StorageError, TenorStorage,
+};
+
+type Key = (String, String);
+
+fn key(entity_id: &str, instance_id: &str) -> Key {
+    (entity_id.to_string(), instance_id.to_string())
+}
+
+fn not_found(entity_id: &str, instance_id: &str) -> StorageError {
+    StorageError::EntityNotFound {
+        entity_id: entity_id.to_string(),
+        instance_id: instance_id.to_string(),
+    }
+}
+
+fn locked(entity_id: &str, instance_id: &str) -> StorageError {
+    StorageError::Backend(format!(
+        "{entity_id}/{instance_id} is locked by another snapshot"
+    ))
+}
+
+#[derive(Default)]
+struct Tables {
+    entities: BTreeMap<Key, EntityStateRecord>,
+    flows: Vec<FlowExecutionRecord>,
+    operations: Vec<OperationExecutionRecord>,
+    transitions: Vec<EntityTransitionRecord>,
+    provenance: Vec<ProvenanceRecord>,
+    /// Entity instances written by an open snapshot, by snapshot id.
+    locks: HashMap<Key, u64>,
+    /// Advances on every commit; stands in for the clock in `updated_at`.
+    clock: u64,
+}
+
+impl Tables {
+    fn key_committed(&self, idempotency_key: &str) -> bool {
+        self.operations
+            .iter()
+            .any(|op| op.idempotency_key.as_deref() == Some(idempotency_key))
+    }
+
+    fn release(&mut self, snapshot: u64) {
+        self.locks.retain(|_, owner| *owner != snapshot);
+    }
+}
+
+#[derive(Default)]
+struct MemoryStorage {
+    tables: Arc<Mutex<Tables>>,
+    next_snapshot: AtomicU64,
+}
+
+/// Writes buffered until commit. `entities` holds the new row for each
+/// instance written, or `None` once deleted.
+struct MemorySnapshot {
+    id: u64,
+    tables: Arc<Mutex<Tables>>,
+    entities: BTreeMap<Key, Option<EntityStateRecord>>,
+    flows: Vec<FlowExecutionRecord>,
+    operations: Vec<OperationExecutionRecord>,
+    transitions: Vec<EntityTransitionRecord>,
+    provenance: Vec<ProvenanceRecord>,
+}
+
+impl Drop for MemorySnapshot {
+    fn drop(&mut self) {
+        if let Ok(mut tables) = self.tables.lock() {
+            tables.release(self.id);
+        }
+    }
+}
+
+impl MemoryStorage {
+    /// The row `snapshot` sees for an instance, after locking it for the
+    /// snapshot. Fails with `locked` if another open snapshot holds it.
+    fn lock_row(
+        &self,
+        snapshot: &MemorySnapshot,
+        entity_id: &str,
+        instance_id: &str,
+        locked: impl FnOnce() -> StorageError,
+    ) -> Result<Option<EntityStateRecord>, StorageError> {
+        let k = key(entity_id, instance_id);
+        let mut tables = self.tables.lock().unwrap();
+        match tables.locks.get(&k) {
+            Some(owner) if *owner != snapshot.id => return Err(locked()),
+            _ => {}
+        }
+        tables.locks.insert(k.clone(), snapshot.id);
+        Ok(match snapshot.entities.get(&k) {
+            Some(row) => row.clone(),
+            None => tables.entities.get(&k).cloned(),
+        })
+    }
+}
+
+#[async_trait]
+impl TenorStorage for MemoryStorage {
+    type Snapshot = MemorySnapshot;
+
+    async fn begin_snapshot(&self) -> Result<MemorySnapshot, StorageError> {
+        Ok(MemorySnapshot {
+            id: self.next_snapshot.fetch_add(1, Ordering::SeqCst),
+            tables: self.tables.clone(),
+            entities: BTreeMap::new(),
+            flows: Vec::new(),
+            operations: Vec::new(),
+            transitions: Vec::new(),
+            provenance: Vec::new(),
+        })
+    }
+
+    async fn commit_snapshot(&self, mut snapshot: MemorySnapshot) -> Result<(), StorageError> {
+        let mut tables = self.tables.lock().unwrap();
+        for op in &snapshot.operations {
+            if let Some(k) = &op.idempotency_key {
+                if tables.key_committed(k) {
+                    return Err(StorageError::DuplicateIdempotencyKey {
+                        idempotency_key: k.clone(),
+                    });
+                }
+            }
+        }
+        tables.clock += 1;
+        let now = format!("2026-01-01T00:00:00.{:06}Z", tables.clock);
+        for (k, row) in std::mem::take(&mut snapshot.entities) {
+            match row {
+                Some(mut row) => {
+                    row.updated_at = now.clone();
+                    tables.entities.insert(k, row);
+                }
+                None => {
+                    tables.entities.remove(&k);
+                }
+            }
+        }
+        tables.flows.append(&mut snapshot.flows);
+        tables.operations.append(&mut snapshot.operations);
+        tables.transitions.append(&mut snapshot.transitions);
+        tables.provenance.append(&mut snapshot.provenance);
+        Ok(())
+    }
+
+    async fn abort_snapshot(&self, _snapshot: MemorySnapshot) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    async fn initialize_entity(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        entity_id: &str,
+        instance_id: &str,
+        initial_state: &str,
+    ) -> Result<(), StorageError> {
+        let already = || StorageError::AlreadyInitialized {
+            entity_id: entity_id.to_string(),
+            instance_id: instance_id.to_string(),
+        };
+        if self
+            .lock_row(snapshot, entity_id, instance_id, already)?
+            .is_some()
+        {
+            return Err(already());
+        }
+        snapshot.entities.insert(
+            key(entity_id, instance_id),
+            Some(EntityStateRecord {
+                entity_id: entity_id.to_string(),
+                instance_id: instance_id.to_string(),
+                state: initial_state.to_string(),
+                version: 0,
+                updated_at: String::new(),
+                last_flow_id: None,
+                last_operation_id: None,
+                attributes: BTreeMap::new(),
+            }),
+        );
+        Ok(())
+    }
+
+    async fn get_entity_state_for_update(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Result<EntityStateRecord, StorageError> {
+        let k = key(entity_id, instance_id);
+        let row = match snapshot.entities.get(&k) {
+            Some(row) => row.clone(),
+            None => self.tables.lock().unwrap().entities.get(&k).cloned(),
+        };
+        row.ok_or_else(|| not_found(entity_id, instance_id))
+    }
+
+    async fn update_entity_state(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        entity_id: &str,
+        instance_id: &str,
+        expected_version: i64,
+        new_state: &str,
+        flow_id: &str,
+        operation_id: &str,
+    ) -> Result<i64, StorageError> {
+        let conflict = || StorageError::ConcurrentConflict {
+            entity_id: entity_id.to_string(),
+            instance_id: instance_id.to_string(),
+            expected_version,
+        };
+        let mut row = self
+            .lock_row(snapshot, entity_id, instance_id, conflict)?
+            .ok_or_else(|| not_found(entity_id, instance_id))?;
+        if row.version != expected_version {
+            return Err(conflict());
+        }
+        row.state = new_state.to_string();
+        row.version += 1;
+        row.last_flow_id = Some(flow_id.to_string());
+        row.last_operation_id = Some(operation_id.to_string());
+        let version = row.version;
+        snapshot
+            .entities
+            .insert(key(entity_id, instance_id), Some(row));
+        Ok(version)
+    }
+
+    async fn update_entity_attributes(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        entity_id: &str,
+        instance_id: &str,
+        attributes: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<(), StorageError> {
+        let mut row = self
+            .lock_row(snapshot, entity_id, instance_id, || {
+                locked(entity_id, instance_id)
+            })?
+            .ok_or_else(|| not_found(entity_id, instance_id))?;
+        row.attributes.extend(attributes.clone());
+        snapshot
+            .entities
+            .insert(key(entity_id, instance_id), Some(row));
+        Ok(())
+    }
+
+    async fn delete_entity(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Result<(), StorageError> {
+        self.lock_row(snapshot, entity_id, instance_id, || {
+            locked(entity_id, instance_id)
+        })?
+        .ok_or_else(|| not_found(entity_id, instance_id))?;
+        snapshot.entities.insert(key(entity_id, instance_id), None);
+        Ok(())
+    }
+
+    async fn insert_flow_execution(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        record: FlowExecutionRecord,
+    ) -> Result<(), StorageError> {
+        snapshot.flows.push(record);
+        Ok(())
+    }
+
+    async fn insert_operation_execution(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        record: OperationExecutionRecord,
+    ) -> Result<(), StorageError> {
+        if let Some(k) = &record.idempotency_key {
+            let in_snapshot = snapshot
+                .operations
+                .iter()
+                .any(|op| op.idempotency_key.as_ref() == Some(k));
+            if in_snapshot || self.tables.lock().unwrap().key_committed(k) {
+                return Err(StorageError::DuplicateIdempotencyKey {
+                    idempotency_key: k.clone(),
+                });
+            }
+        }
+        snapshot.operations.push(record);
+        Ok(())
+    }
+
+    async fn insert_entity_transition(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        record: EntityTransitionRecord,
+    ) -> Result<(), StorageError> {
+        snapshot.transitions.push(record);
+        Ok(())
+    }
+
+    async fn insert_provenance_record(
+        &self,
+        snapshot: &mut MemorySnapshot,
+        record: ProvenanceRecord,
+    ) -> Result<(), StorageError> {
+        snapshot.provenance.push(record);
+        Ok(())
+    }
+
+    async fn get_entity_state(
+        &self,
+        entity_id: &str,
+        instance_id: &str,
+    ) -> Result<EntityStateRecord, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        tables
+            .entities
+            .get(&key(entity_id, instance_id))
+            .cloned()
+            .ok_or_else(|| not_found(entity_id, instance_id))
+    }
+
+    async fn list_entity_states(
+        &self,
+        entity_id: &str,
+        state_filter: Option<&str>,
+    ) -> Result<Vec<EntityStateRecord>, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables
+            .entities
+            .values()
+            .filter(|e| e.entity_id == entity_id)
+            .filter(|e| state_filter.is_none_or(|s| e.state == s))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_flow_execution(
+        &self,
+        execution_id: &str,
+    ) -> Result<FlowExecutionRecord, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        tables
+            .flows
+            .iter()
+            .find(|f| f.id == execution_id)
+            .cloned()
+            .ok_or_else(|| StorageError::ExecutionNotFound {
+                execution_id: execution_id.to_string(),
+            })
+    }
+
+    async fn get_provenance(
+        &self,
+        operation_execution_id: &str,
+    ) -> Result<Vec<ProvenanceRecord>, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables
+            .provenance
+            .iter()
+            .filter(|p| p.operation_execution_id == operation_execution_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_operation_execution_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> Result<Option<OperationExecutionRecord>, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        Ok(tables
+            .operations
+            .iter()
+            .find(|op| op.idempotency_key.as_deref() == Some(idempotency_key))
+            .cloned())
+    }
+
+    async fn list_flow_executions(
+        &self,
+        flow_id: Option<&str>,
+        outcome: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FlowExecutionRecord>, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        let matching = tables
+            .flows
+            .iter()
+            .filter(|f| flow_id.is_none_or(|id| f.flow_id == id))
+            .filter(|f| outcome.is_none_or(|o| f.outcome == o))
+            .cloned();
+        Ok(if limit == 0 {
+            matching.collect()
+        } else {
+            matching.take(limit).collect()
+        })
+    }
+
+    async fn list_correlated_flow_executions(
+        &self,
+        correlation_id: &str,
+    ) -> Result<Vec<FlowExecutionRecord>, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        let mut flows: Vec<_> = tables
+            .flows
+            .iter()
+            .filter(|f| f.correlation_id.as_deref() == Some(correlation_id))
+            .cloned()
+            .collect();
+        flows.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        Ok(flows)
+    }
+
+    async fn list_entity_transitions(
+        &self,
+        entity_id: &str,
+        until: Option<&str>,
+    ) -> Result<Vec<EntityTransitionHistoryRecord>, StorageError> {
+        let tables = self.tables.lock().unwrap();
+        let mut history: Vec<_> = tables
+            .transitions
+            .iter()
+            .filter(|t| t.entity_id == entity_id)
+            .filter_map(|t| {
+                let op = tables
+                    .operations
+                    .iter()
+                    .find(|op| op.id == t.operation_execution_id)?;
+                Some(EntityTransitionHistoryRecord {
+                    transition: t.clone(),
+                    executed_at: op.executed_at.clone(),
+                })
+            })
+            .filter(|h| until.is_none_or(|u| h.executed_at.as_str() <= u))
+            .collect();
+        history.sort_by(|a, b| {
+            (&a.executed_at, a.transition.to_version)
+                .cmp(&(&b.executed_at, b.transition.to_version))
+        });
+        Ok(history)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn in_memory_backend_passes_conformance_suite() {
+    let report = run_conformance_suite(|| async { MemoryStorage::default() }).await;
+    assert!(report.failed == 0, "{report}");
+    assert!(
+        report
+            .results
+            .iter()
+            .any(|r| r.category == "idempotency" && r.passed),
+        "idempotency cases did not run"
+    );
+}
@@ -0,0 +1,6 @@
+//! Print the TypeScript declarations of the result shapes:
+//! `cargo run --example typings > src/types.d.ts`.
+
+fn main() {
+    print!("{}", tenor_eval_wasm::results::typescript());
+}
@@ -4,7 +4,13 @@ use std::collections::BTreeMap;
 use tenor_eval::Contract;
 use wasm_bindgen::prelude::*;
 
-mod inspect;
+pub mod results;
+
+// Result shapes for the generated `.d.ts`, generated from `results`. Every
+// function below returns a JSON string; `unchecked_return_type` tags it with
+// the shape it carries.
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = include_str!("types.d.ts");
 
 struct StoredContract {
     contract: Contract,
@@ -32,113 +38,41 @@ where
     })
 }
 
-/// Parse entity_states JSON with auto-detection of old and new formats.
-///
-/// Old format (single-instance, flat):
-/// ```json
-/// { "Order": "draft", "DeliveryRecord": "pending" }
-/// ```
-///
-/// New format (multi-instance, nested):
-/// ```json
-/// {
-///   "Order": { "ord-001": "draft", "ord-002": "submitted" },
-///   "DeliveryRecord": { "del-001": "pending" }
-/// }
-/// ```
-///
-/// Mixed format is supported: per entity_id, if the value is a string it is
-/// treated as old format (converted to _default instance), if it is an object
-/// it is treated as new format (parsed directly).
-///
-/// Per plan-4.md §A9: detect by checking whether values are strings (old) or
-/// objects (new). Convert old format by assigning DEFAULT_INSTANCE_ID.
-fn parse_entity_states(json: &serde_json::Value) -> Result<tenor_eval::EntityStateMap, String> {
-    let obj = match json.as_object() {
-        Some(o) => o,
-        None => return Err("entity_states must be a JSON object".to_string()),
-    };
-
-    let mut entity_states = tenor_eval::EntityStateMap::new();
-
-    for (entity_id, value) in obj {
-        if let Some(state_str) = value.as_str() {
-            // Old format: entity_id -> state string; convert to single instance
-            entity_states.insert(
-                (
-                    entity_id.clone(),
-                    tenor_eval::DEFAULT_INSTANCE_ID.to_string(),
-                ),
-                state_str.to_string(),
-            );
-        } else if let Some(instance_map) = value.as_object() {
-            // New format: entity_id -> { instance_id -> state }
-            for (instance_id, state_val) in instance_map {
-                let state_str = match state_val.as_str() {
-                    Some(s) => s,
-                    None => {
-                        return Err(format!(
-                            "entity_states[{}][{}] must be a string state",
-                            entity_id, instance_id
-                        ))
-                    }
-                };
-                entity_states.insert(
-                    (entity_id.clone(), instance_id.clone()),
-                    state_str.to_string(),
-                );
-            }
-        } else {
-            return Err(format!(
-                "entity_states[{}] must be a string (old format) or object (new format)",
-                entity_id
-            ));
-        }
-    }
-
-    Ok(entity_states)
-}
-
-/// Parse instance_bindings JSON: entity_id -> instance_id.
-///
-/// Format: `{ "Order": "ord-001", "DeliveryRecord": "del-001" }`
-///
-/// Returns an empty map if the JSON is null, empty object, or empty string.
+/// Parse instance_bindings JSON: entity_id -> instance_id. An empty
+/// string means no bindings.
 fn parse_instance_bindings(json_str: &str) -> Result<tenor_eval::InstanceBindingMap, String> {
-    if json_str.trim().is_empty() || json_str.trim() == "null" || json_str.trim() == "{}" {
+    if json_str.trim().is_empty() {
         return Ok(BTreeMap::new());
     }
 
     let val: serde_json::Value = serde_json::from_str(json_str)
         .map_err(|e| format!("invalid instance_bindings JSON: {}", e))?;
+    tenor_eval::simulate::parse_instance_bindings(&val)
+}
 
-    match val {
-        serde_json::Value::Null => Ok(BTreeMap::new()),
-        serde_json::Value::Object(map) => {
-            let mut result = BTreeMap::new();
-            for (entity_id, instance_val) in map {
-                let instance_id = match instance_val.as_str() {
-                    Some(s) => s.to_string(),
-                    None => {
-                        return Err(format!(
-                            "instance_bindings[{}] must be a string instance_id",
-                            entity_id
-                        ))
-                    }
-                };
-                result.insert(entity_id, instance_id);
-            }
-            Ok(result)
-        }
-        _ => Err("instance_bindings must be a JSON object or null".to_string()),
+#[wasm_bindgen(unchecked_return_type = "Json<ContractHandle | TenorError>")]
+pub fn load_contract(interchange_json: &str) -> String {
+    match serde_json::from_str(interchange_json) {
+        Ok(bundle) => store_contract(bundle),
+        Err(e) => error_json(&format!("invalid JSON: {}", e)),
     }
 }
 
-#[wasm_bindgen]
-pub fn load_contract(interchange_json: &str) -> String {
-    let bundle: serde_json::Value = match serde_json::from_str(interchange_json) {
-        Ok(v) => v,
-        Err(e) => return error_json(&format!("invalid JSON: {}", e)),
+/// Load a CBOR-encoded interchange bundle. Decoding CBOR avoids the cost
+/// of parsing large JSON bundles on every cold start.
+#[wasm_bindgen(unchecked_return_type = "Json<ContractHandle | TenorError>")]
+pub fn load_contract_cbor(interchange_cbor: &[u8]) -> String {
+    match tenor_interchange::from_cbor(interchange_cbor) {
+        Ok(bundle) => store_contract(bundle),
+        Err(e) => error_json(&e.to_string()),
+    }
+}
+
+fn store_contract(bundle: serde_json::Value) -> String {
+    // Store the plain form so inspection never sees type_refs
+    let bundle = match tenor_interchange::into_inlined(bundle) {
+        Ok(b) => b,
+        Err(e) => return error_json(&format!("invalid contract: {}", e)),
     };
 
     let contract = match Contract::from_interchange(&bundle) {
@@ -165,29 +99,52 @@ pub fn free_contract(handle: u32) {
     });
 }
 
-#[wasm_bindgen]
+/// Evaluate rules against one fact document: the VerdictSet JSON, or an
+/// `{"error": ...}` object.
+fn evaluate_facts(stored: &StoredContract, facts: &serde_json::Value) -> serde_json::Value {
+    let fact_set = match tenor_eval::assemble::assemble_facts(&stored.contract, facts) {
+        Ok(fs) => fs,
+        Err(e) => return serde_json::json!({ "error": format!("fact assembly error: {}", e) }),
+    };
+
+    match tenor_eval::rules::eval_strata(&stored.contract, &fact_set) {
+        Ok(vs) => vs.to_json(),
+        Err(e) => serde_json::json!({ "error": format!("evaluation error: {}", e) }),
+    }
+}
+
+#[wasm_bindgen(unchecked_return_type = "Json<VerdictSet | TenorError>")]
 pub fn evaluate(handle: u32, facts_json: &str) -> String {
     let facts: serde_json::Value = match serde_json::from_str(facts_json) {
         Ok(v) => v,
         Err(e) => return error_json(&format!("invalid facts JSON: {}", e)),
     };
 
-    with_contract(handle, |stored| {
-        let fact_set = match tenor_eval::assemble::assemble_facts(&stored.contract, &facts) {
-            Ok(fs) => fs,
-            Err(e) => return error_json(&format!("fact assembly error: {}", e)),
-        };
+    with_contract(handle, |stored| evaluate_facts(stored, &facts).to_string())
+}
 
-        let verdict_set = match tenor_eval::rules::eval_strata(&stored.contract, &fact_set) {
-            Ok(vs) => vs,
-            Err(e) => return error_json(&format!("evaluation error: {}", e)),
-        };
+/// Evaluate rules against each fact document in a JSON array, in one call.
+///
+/// Returns an array of the same length whose entries are the VerdictSet for
+/// the matching fact document, or `{"error": ...}` if that document failed.
+/// A malformed batch or an invalid handle is a single top-level error.
+#[wasm_bindgen(unchecked_return_type = "Json<Array<VerdictSet | TenorError> | TenorError>")]
+pub fn evaluate_batch(handle: u32, facts_batch_json: &str) -> String {
+    let batch: Vec<serde_json::Value> = match serde_json::from_str(facts_batch_json) {
+        Ok(v) => v,
+        Err(e) => return error_json(&format!("invalid facts batch JSON: {}", e)),
+    };
 
-        verdict_set.to_json().to_string()
+    with_contract(handle, |stored| {
+        let results: Vec<serde_json::Value> = batch
+            .iter()
+            .map(|facts| evaluate_facts(stored, facts))
+            .collect();
+        serde_json::Value::Array(results).to_string()
     })
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Json<FlowResult | TenorError>")]
 pub fn simulate_flow(
     handle: u32,
     flow_id: &str,
@@ -209,7 +166,7 @@ pub fn simulate_flow(
 ///
 /// `entity_states_json` accepts both old flat format and new nested format.
 /// `instance_bindings_json` maps entity_id → instance_id; if empty/null, uses _default.
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Json<FlowResult | TenorError>")]
 pub fn simulate_flow_with_bindings(
     handle: u32,
     flow_id: &str,
@@ -234,7 +191,7 @@ pub fn simulate_flow_with_bindings(
     };
 
     with_contract(handle, |stored| {
-        let entity_states = match parse_entity_states(&entity_states_val) {
+        let entity_states = match tenor_eval::simulate::parse_entity_states(&entity_states_val) {
             Ok(s) => s,
             Err(e) => return error_json(&format!("invalid entity states: {}", e)),
         };
@@ -270,69 +227,43 @@ pub fn simulate_flow_with_bindings(
             &stored.contract,
             &snapshot,
             &mut merged_entity_states,
+            &mut tenor_eval::EntityAttributeMap::new(),
             &instance_bindings,
-            None,
+            tenor_eval::FlowOptions::default(),
         ) {
             Ok(r) => r,
             Err(e) => return error_json(&format!("flow execution error: {}", e)),
         };
 
-        let path: Vec<serde_json::Value> = flow_result
-            .steps_executed
-            .iter()
-            .map(|s| {
-                let mut step_json = serde_json::json!({
-                    "step_id": s.step_id,
-                    "step_type": s.step_type,
-                    "result": s.result,
-                });
-                // Include instance_bindings on each step if non-empty
-                if !s.instance_bindings.is_empty() {
-                    step_json["instance_bindings"] = serde_json::to_value(&s.instance_bindings)
-                        .unwrap_or(serde_json::Value::Null);
-                }
-                step_json
-            })
-            .collect();
-
-        let would_transition: Vec<serde_json::Value> = flow_result
-            .entity_state_changes
-            .iter()
-            .map(|e| {
-                serde_json::json!({
-                    "entity_id": e.entity_id,
-                    "instance_id": e.instance_id,
-                    "from_state": e.from_state,
-                    "to_state": e.to_state,
-                })
-            })
-            .collect();
-
-        serde_json::json!({
-            "simulation": true,
-            "flow_id": flow_id,
-            "persona": persona_id,
-            "outcome": flow_result.outcome,
-            "path": path,
-            "would_transition": would_transition,
-            "verdicts": verdict_set.to_json()["verdicts"],
-            "instance_bindings": instance_bindings,
-        })
-        .to_string()
+        let result = tenor_eval::FlowEvalResult {
+            verdicts: verdict_set,
+            flow_result,
+        };
+        tenor_eval::simulate::simulation_json(flow_id, persona_id, &result, &instance_bindings)
+            .to_string()
     })
 }
 
-#[wasm_bindgen]
+/// List the evaluator features this build supports, as a JSON array.
+///
+/// Contracts whose `requires` list names a feature outside this set are
+/// rejected by `load_contract`.
+#[wasm_bindgen(unchecked_return_type = "Json<string[]>")]
+pub fn supported_features() -> String {
+    serde_json::json!(tenor_eval::SUPPORTED_FEATURES).to_string()
+}
+
+#[wasm_bindgen(unchecked_return_type = "Json<InspectResult | TenorError>")]
 pub fn inspect_contract(handle: u32) -> String {
     with_contract(handle, |stored| {
-        match inspect::build_inspect(&stored.bundle) {
+        match tenor_interchange::build_inspect(&stored.bundle) {
             Ok(json) => json.to_string(),
             Err(e) => error_json(&format!("inspect error: {}", e)),
         }
     })
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Json<ActionSpace | TenorError>")]
 pub fn compute_action_space(
     handle: u32,
     facts_json: &str,
@@ -350,7 +281,7 @@ pub fn compute_action_space(
     };
 
     with_contract(handle, |stored| {
-        let entity_states = match parse_entity_states(&entity_states_val) {
+        let entity_states = match tenor_eval::simulate::parse_entity_states(&entity_states_val) {
             Ok(s) => s,
             Err(e) => return error_json(&format!("invalid entity states: {}", e)),
         };
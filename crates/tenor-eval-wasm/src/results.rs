@@ -0,0 +1,471 @@
+//! Shapes of the JSON documents the exported functions return.
+//!
+//! `types.d.ts` is generated from these types by [`typescript`]
+//! (`cargo run --example typings > src/types.d.ts`), and the WASM tests
+//! parse every function's output into them with unknown fields denied, so
+//! the typings cannot drift from what the functions return.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Returned in place of a result when a call fails.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TenorError {
+    pub error: String,
+}
+
+/// Result of `load_contract` and `load_contract_cbor`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContractHandle {
+    pub handle: u32,
+}
+
+/// A currency conversion applied while a verdict was produced.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Conversion {
+    pub rate_fact: String,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub rate: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VerdictProvenance {
+    pub rule: String,
+    pub stratum: u32,
+    pub facts_used: Vec<String>,
+    pub verdicts_used: Vec<String>,
+    #[serde(default)]
+    pub conversions: Option<Vec<Conversion>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Verdict {
+    #[serde(rename = "type")]
+    pub verdict_type: String,
+    pub payload: Value,
+    pub provenance: VerdictProvenance,
+}
+
+/// Result of `evaluate`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VerdictSet {
+    pub verdicts: Vec<Verdict>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VerdictSummary {
+    pub verdict_type: String,
+    pub payload: Value,
+    pub producing_rule: String,
+    pub stratum: u32,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EntitySummary {
+    pub entity_id: String,
+    pub current_state: String,
+    pub possible_transitions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Action {
+    pub flow_id: String,
+    pub persona_id: String,
+    pub entry_operation_id: String,
+    pub enabling_verdicts: Vec<VerdictSummary>,
+    pub affected_entities: Vec<EntitySummary>,
+    pub description: String,
+    /// entity_id -> instance_ids in the required source state.
+    pub instance_bindings: BTreeMap<String, Vec<String>>,
+}
+
+/// A top-level precondition or guard clause that evaluated to false.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FailedClause {
+    pub clause: String,
+    pub facts: BTreeMap<String, Value>,
+    pub missing_verdicts: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub enum BlockedReason {
+    PersonaNotAuthorized {
+        allowed_personas: Vec<String>,
+    },
+    PreconditionNotMet {
+        missing_verdicts: Vec<String>,
+        failed_clauses: Vec<FailedClause>,
+    },
+    EntityNotInSourceState {
+        entity_id: String,
+        current_state: String,
+        required_state: String,
+        expected_states: Vec<String>,
+    },
+    MissingFacts {
+        fact_ids: Vec<String>,
+    },
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BlockedAction {
+    pub flow_id: String,
+    pub reason: BlockedReason,
+    /// entity_id -> blocking instance_ids (for EntityNotInSourceState).
+    pub instance_bindings: BTreeMap<String, Vec<String>>,
+}
+
+/// Result of `compute_action_space`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ActionSpace {
+    pub persona_id: String,
+    pub actions: Vec<Action>,
+    pub current_verdicts: Vec<VerdictSummary>,
+    pub blocked_actions: Vec<BlockedAction>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StepResult {
+    pub step_id: String,
+    pub step_type: String,
+    pub result: String,
+    #[serde(default)]
+    pub instance_bindings: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EntityStateChange {
+    pub entity_id: String,
+    pub instance_id: String,
+    pub from_state: String,
+    pub to_state: String,
+}
+
+/// Result of `simulate_flow` and `simulate_flow_with_bindings`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FlowResult {
+    pub simulation: bool,
+    pub flow_id: String,
+    pub persona: String,
+    pub outcome: String,
+    pub path: Vec<StepResult>,
+    pub would_transition: Vec<EntityStateChange>,
+    pub verdicts: Vec<Verdict>,
+    pub instance_bindings: BTreeMap<String, String>,
+}
+
+/// `@owner`, `@description` and `@tag` annotations of a construct.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Annotations {
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectFact {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub fact_type: String,
+    #[serde(default)]
+    pub source: Option<Value>,
+    #[serde(default)]
+    pub has_default: Option<bool>,
+    pub type_spec: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub annotations: Option<Annotations>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectTransition {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectEntity {
+    pub id: String,
+    pub states: Vec<String>,
+    pub initial: String,
+    pub transitions: Vec<InspectTransition>,
+    #[serde(default)]
+    pub annotations: Option<Annotations>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectRule {
+    pub id: String,
+    pub stratum: u32,
+    pub produces: String,
+    pub condition_summary: String,
+    #[serde(default)]
+    pub annotations: Option<Annotations>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectPersona {
+    pub id: String,
+    #[serde(default)]
+    pub annotations: Option<Annotations>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectEffect {
+    pub entity_id: String,
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectOperation {
+    pub id: String,
+    pub allowed_personas: Vec<String>,
+    pub effects: Vec<InspectEffect>,
+    pub precondition_summary: String,
+    pub outcomes: Vec<String>,
+    #[serde(default)]
+    pub annotations: Option<Annotations>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectFlow {
+    pub id: String,
+    pub entry: String,
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub annotations: Option<Annotations>,
+}
+
+/// Result of `inspect_contract`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InspectResult {
+    pub facts: Vec<InspectFact>,
+    pub entities: Vec<InspectEntity>,
+    pub rules: Vec<InspectRule>,
+    pub personas: Vec<InspectPersona>,
+    pub operations: Vec<InspectOperation>,
+    pub flows: Vec<InspectFlow>,
+    pub requires: Vec<String>,
+}
+
+const PREAMBLE: &str = "\
+// Generated from crates/tenor-eval-wasm/src/results.rs; do not edit.
+// Regenerate with `cargo run --example typings > src/types.d.ts`.
+
+/**
+ * A JSON document of shape `T`, as returned by the evaluator functions.
+ * Results are plain strings at runtime; pass them to `JSON.parse` and
+ * narrow on `\"error\" in result`.
+ */
+export type Json<T> = string & { readonly __json?: T };
+";
+
+/// The TypeScript declarations of every result shape, as checked in at
+/// `src/types.d.ts`.
+pub fn typescript() -> String {
+    let mut generator = schemars::generate::SchemaSettings::draft2020_12().into_generator();
+    generator.subschema_for::<TenorError>();
+    generator.subschema_for::<ContractHandle>();
+    generator.subschema_for::<VerdictSet>();
+    generator.subschema_for::<ActionSpace>();
+    generator.subschema_for::<FlowResult>();
+    generator.subschema_for::<InspectResult>();
+    let definitions = generator.take_definitions(true);
+
+    let mut out = PREAMBLE.to_string();
+    let mut emitted = Vec::new();
+    for name in [
+        "TenorError",
+        "ContractHandle",
+        "VerdictSet",
+        "ActionSpace",
+        "FlowResult",
+        "InspectResult",
+    ] {
+        emit(name, &definitions, &mut emitted, &mut out);
+    }
+    out
+}
+
+/// Append the declaration of `name` to `out`, after those of the types it
+/// uses, unless it was already emitted.
+fn emit(name: &str, definitions: &Map<String, Value>, emitted: &mut Vec<String>, out: &mut String) {
+    if emitted.iter().any(|e| e == name) {
+        return;
+    }
+    emitted.push(name.to_string());
+    let mut refs = Vec::new();
+    let declaration = declaration(name, &definitions[name], &mut refs);
+    for r in &refs {
+        emit(r, definitions, emitted, out);
+    }
+    out.push('\n');
+    out.push_str(&declaration);
+}
+
+/// `export interface` for an object schema, `export type` for a union.
+fn declaration(name: &str, schema: &Value, refs: &mut Vec<String>) -> String {
+    let mut out = doc_comment(schema, "");
+    match schema.get("oneOf").and_then(Value::as_array) {
+        Some(variants) => {
+            out.push_str(&format!("export type {} =\n", name));
+            for variant in variants {
+                let members = members(variant, refs);
+                let line = format!("  | {{ {} }}", members.join("; "));
+                if line.len() <= 80 {
+                    out.push_str(&line);
+                } else {
+                    out.push_str("  | {\n");
+                    for member in members {
+                        out.push_str(&format!("      {};\n", member));
+                    }
+                    out.push_str("    }");
+                }
+                out.push('\n');
+            }
+            out.pop();
+            out.push_str(";\n");
+        }
+        None => {
+            out.push_str(&format!("export interface {} {{\n", name));
+            for (field, optional, ty) in fields(schema) {
+                out.push_str(&doc_comment(ty, "  "));
+                let mark = if optional { "?" } else { "" };
+                out.push_str(&format!("  {}{}: {};\n", field, mark, ts_type(ty, refs)));
+            }
+            out.push_str("}\n");
+        }
+    }
+    out
+}
+
+/// The members of an object type literal, discriminator first.
+fn members(schema: &Value, refs: &mut Vec<String>) -> Vec<String> {
+    let mut members: Vec<String> = fields(schema)
+        .into_iter()
+        .map(|(field, optional, ty)| {
+            let mark = if optional { "?" } else { "" };
+            format!("{}{}: {}", field, mark, ts_type(ty, refs))
+        })
+        .collect();
+    members.sort_by_key(|m| !m.contains(": \""));
+    members
+}
+
+/// The properties of an object schema: name, whether optional, schema.
+fn fields(schema: &Value) -> Vec<(&str, bool, &Value)> {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    properties
+        .iter()
+        .map(|(field, ty)| (field.as_str(), !required.contains(&field.as_str()), ty))
+        .collect()
+}
+
+fn ts_type(schema: &Value, refs: &mut Vec<String>) -> String {
+    let Some(obj) = schema.as_object() else {
+        return "unknown".to_string();
+    };
+    if let Some(r) = obj.get("$ref").and_then(Value::as_str) {
+        let name = r.rsplit('/').next().unwrap_or(r).to_string();
+        refs.push(name.clone());
+        return name;
+    }
+    if let Some(c) = obj.get("const") {
+        return c.to_string();
+    }
+    // Optional fields are `T | null` in the schema; `?` already covers absence
+    if let Some(any_of) = obj.get("anyOf").and_then(Value::as_array) {
+        let types: Vec<String> = any_of
+            .iter()
+            .filter(|s| s.get("type") != Some(&Value::from("null")))
+            .map(|s| ts_type(s, refs))
+            .collect();
+        return types.join(" | ");
+    }
+    let ty = match obj.get("type") {
+        Some(Value::Array(types)) => types.iter().find(|t| *t != "null").and_then(Value::as_str),
+        Some(t) => t.as_str(),
+        None => None,
+    };
+    match ty {
+        Some("string") => "string".to_string(),
+        Some("integer") | Some("number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("array") => {
+            let item = ts_type(obj.get("items").unwrap_or(&Value::Bool(true)), refs);
+            if item.contains(' ') {
+                format!("Array<{}>", item)
+            } else {
+                format!("{}[]", item)
+            }
+        }
+        Some("object") if obj.contains_key("properties") => {
+            format!("{{ {} }}", members(schema, refs).join("; "))
+        }
+        Some("object") => {
+            let values = obj
+                .get("additionalProperties")
+                .unwrap_or(&Value::Bool(true));
+            format!("Record<string, {}>", ts_type(values, refs))
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+/// A JSDoc comment carrying the schema's description, if it has one.
+fn doc_comment(schema: &Value, indent: &str) -> String {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return String::new();
+    };
+    let lines: Vec<&str> = description.lines().collect();
+    if let [line] = lines.as_slice() {
+        return format!("{}/** {} */\n", indent, line);
+    }
+    let mut out = format!("{}/**\n", indent);
+    for line in lines {
+        out.push_str(&format!("{} * {}\n", indent, line).replace(" * \n", " *\n"));
+    }
+    out.push_str(&format!("{} */\n", indent));
+    out
+}
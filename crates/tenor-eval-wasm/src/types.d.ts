@@ -0,0 +1,210 @@
+// Generated from crates/tenor-eval-wasm/src/results.rs; do not edit.
+// Regenerate with `cargo run --example typings > src/types.d.ts`.
+
+/**
+ * A JSON document of shape `T`, as returned by the evaluator functions.
+ * Results are plain strings at runtime; pass them to `JSON.parse` and
+ * narrow on `"error" in result`.
+ */
+export type Json<T> = string & { readonly __json?: T };
+
+/** Returned in place of a result when a call fails. */
+export interface TenorError {
+  error: string;
+}
+
+/** Result of `load_contract` and `load_contract_cbor`. */
+export interface ContractHandle {
+  handle: number;
+}
+
+/** A currency conversion applied while a verdict was produced. */
+export interface Conversion {
+  from_currency: string;
+  rate: string;
+  rate_fact: string;
+  to_currency: string;
+}
+
+export interface VerdictProvenance {
+  conversions?: Conversion[];
+  facts_used: string[];
+  rule: string;
+  stratum: number;
+  verdicts_used: string[];
+}
+
+export interface Verdict {
+  payload: unknown;
+  provenance: VerdictProvenance;
+  type: string;
+}
+
+/** Result of `evaluate`. */
+export interface VerdictSet {
+  verdicts: Verdict[];
+}
+
+export interface EntitySummary {
+  current_state: string;
+  entity_id: string;
+  possible_transitions: string[];
+}
+
+export interface VerdictSummary {
+  payload: unknown;
+  producing_rule: string;
+  stratum: number;
+  verdict_type: string;
+}
+
+export interface Action {
+  affected_entities: EntitySummary[];
+  description: string;
+  enabling_verdicts: VerdictSummary[];
+  entry_operation_id: string;
+  flow_id: string;
+  /** entity_id -> instance_ids in the required source state. */
+  instance_bindings: Record<string, string[]>;
+  persona_id: string;
+}
+
+/** A top-level precondition or guard clause that evaluated to false. */
+export interface FailedClause {
+  clause: string;
+  facts: Record<string, unknown>;
+  missing_verdicts: string[];
+}
+
+export type BlockedReason =
+  | { type: "PersonaNotAuthorized"; allowed_personas: string[] }
+  | {
+      type: "PreconditionNotMet";
+      failed_clauses: FailedClause[];
+      missing_verdicts: string[];
+    }
+  | {
+      type: "EntityNotInSourceState";
+      current_state: string;
+      entity_id: string;
+      expected_states: string[];
+      required_state: string;
+    }
+  | { type: "MissingFacts"; fact_ids: string[] };
+
+export interface BlockedAction {
+  flow_id: string;
+  /** entity_id -> blocking instance_ids (for EntityNotInSourceState). */
+  instance_bindings: Record<string, string[]>;
+  reason: BlockedReason;
+}
+
+/** Result of `compute_action_space`. */
+export interface ActionSpace {
+  actions: Action[];
+  blocked_actions: BlockedAction[];
+  current_verdicts: VerdictSummary[];
+  persona_id: string;
+}
+
+export interface StepResult {
+  instance_bindings?: Record<string, string>;
+  result: string;
+  step_id: string;
+  step_type: string;
+}
+
+export interface EntityStateChange {
+  entity_id: string;
+  from_state: string;
+  instance_id: string;
+  to_state: string;
+}
+
+/** Result of `simulate_flow` and `simulate_flow_with_bindings`. */
+export interface FlowResult {
+  flow_id: string;
+  instance_bindings: Record<string, string>;
+  outcome: string;
+  path: StepResult[];
+  persona: string;
+  simulation: boolean;
+  verdicts: Verdict[];
+  would_transition: EntityStateChange[];
+}
+
+/** `@owner`, `@description` and `@tag` annotations of a construct. */
+export interface Annotations {
+  description?: string;
+  owner?: string;
+  tags?: string[];
+}
+
+export interface InspectTransition {
+  from: string;
+  to: string;
+}
+
+export interface InspectEntity {
+  annotations?: Annotations;
+  id: string;
+  initial: string;
+  states: string[];
+  transitions: InspectTransition[];
+}
+
+export interface InspectFact {
+  annotations?: Annotations;
+  has_default?: boolean;
+  id: string;
+  source?: unknown;
+  type: string;
+  type_spec: Record<string, unknown>;
+}
+
+export interface InspectFlow {
+  annotations?: Annotations;
+  entry: string;
+  id: string;
+  steps: string[];
+}
+
+export interface InspectEffect {
+  entity_id: string;
+  from: string;
+  kind?: string;
+  to: string;
+}
+
+export interface InspectOperation {
+  allowed_personas: string[];
+  annotations?: Annotations;
+  effects: InspectEffect[];
+  id: string;
+  outcomes: string[];
+  precondition_summary: string;
+}
+
+export interface InspectPersona {
+  annotations?: Annotations;
+  id: string;
+}
+
+export interface InspectRule {
+  annotations?: Annotations;
+  condition_summary: string;
+  id: string;
+  produces: string;
+  stratum: number;
+}
+
+/** Result of `inspect_contract`. */
+export interface InspectResult {
+  entities: InspectEntity[];
+  facts: InspectFact[];
+  flows: InspectFlow[];
+  operations: InspectOperation[];
+  personas: InspectPersona[];
+  requires: string[];
+  rules: InspectRule[];
+}
@@ -114,6 +114,31 @@ fn test_load_contract_invalid_bundle() {
     );
 }
 
+#[wasm_bindgen_test(unsupported = test)]
+fn test_load_contract_cbor_matches_json() {
+    let bundle: serde_json::Value = serde_json::from_str(BASIC_BUNDLE).unwrap();
+    let cbor = tenor_interchange::to_cbor(&bundle).unwrap();
+    let load_result = tenor_eval_wasm::load_contract_cbor(&cbor);
+    let handle = serde_json::from_str::<serde_json::Value>(&load_result).unwrap()["handle"]
+        .as_u64()
+        .unwrap_or_else(|| panic!("expected handle in: {}", load_result)) as u32;
+
+    let result = tenor_eval_wasm::evaluate(handle, r#"{"is_active": true}"#);
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(parsed["verdicts"][0]["type"], "account_active");
+
+    let bad = tenor_eval_wasm::load_contract_cbor(&[0xff, 0x00]);
+    let parsed: serde_json::Value = serde_json::from_str(&bad).unwrap();
+    assert!(
+        parsed["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid CBOR"),
+        "expected CBOR error in: {}",
+        bad
+    );
+}
+
 #[wasm_bindgen_test(unsupported = test)]
 fn test_evaluate_produces_verdicts() {
     let load_result = tenor_eval_wasm::load_contract(BASIC_BUNDLE);
@@ -183,6 +208,55 @@ fn test_evaluate_invalid_handle() {
     );
 }
 
+#[wasm_bindgen_test(unsupported = test)]
+fn test_evaluate_batch_returns_one_result_per_fact_document() {
+    let load_result = tenor_eval_wasm::load_contract(BASIC_BUNDLE);
+    let handle = serde_json::from_str::<serde_json::Value>(&load_result).unwrap()["handle"]
+        .as_u64()
+        .unwrap() as u32;
+
+    let result = tenor_eval_wasm::evaluate_batch(
+        handle,
+        r#"[{"is_active": true}, {}, {"is_active": false}]"#,
+    );
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let results = parsed.as_array().expect("expected results array");
+    assert_eq!(results.len(), 3);
+
+    // Each entry matches what a single evaluate call returns
+    let single = tenor_eval_wasm::evaluate(handle, r#"{"is_active": true}"#);
+    assert_eq!(
+        results[0],
+        serde_json::from_str::<serde_json::Value>(&single).unwrap()
+    );
+    assert!(
+        results[1].get("error").is_some(),
+        "expected error for missing fact: {}",
+        results[1]
+    );
+    assert_eq!(results[2]["verdicts"].as_array().unwrap().len(), 0);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_evaluate_batch_rejects_non_array_and_invalid_handle() {
+    let load_result = tenor_eval_wasm::load_contract(BASIC_BUNDLE);
+    let handle = serde_json::from_str::<serde_json::Value>(&load_result).unwrap()["handle"]
+        .as_u64()
+        .unwrap() as u32;
+
+    for result in [
+        tenor_eval_wasm::evaluate_batch(handle, r#"{"is_active": true}"#),
+        tenor_eval_wasm::evaluate_batch(9999, r#"[{"is_active": true}]"#),
+    ] {
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert!(
+            parsed.get("error").is_some(),
+            "expected error in: {}",
+            result
+        );
+    }
+}
+
 #[wasm_bindgen_test(unsupported = test)]
 fn test_simulate_flow_success() {
     let load_result = tenor_eval_wasm::load_contract(BASIC_BUNDLE);
@@ -306,6 +380,15 @@ fn test_inspect_invalid_handle() {
     assert!(parsed.get("error").is_some());
 }
 
+#[wasm_bindgen_test(unsupported = test)]
+fn test_supported_features_lists_all_v1_features() {
+    let result = tenor_eval_wasm::supported_features();
+    let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let features = parsed.as_array().unwrap();
+    assert!(features.iter().any(|f| f == "parallel-steps"));
+    assert!(features.iter().any(|f| f == "systems"));
+}
+
 #[wasm_bindgen_test(unsupported = test)]
 fn test_free_and_reuse() {
     let r1 = tenor_eval_wasm::load_contract(BASIC_BUNDLE);
@@ -715,3 +798,81 @@ fn test_simulate_flow_with_bindings_empty_bindings_backward_compat() {
     let transitions = parsed["would_transition"].as_array().unwrap();
     assert_eq!(transitions[0]["instance_id"], "_default");
 }
+
+// ──────────────────────────────────────────────
+// TypeScript typings
+// ──────────────────────────────────────────────
+
+const TS_TYPES: &str = include_str!("../src/types.d.ts");
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_typings_are_generated_from_result_types() {
+    assert!(
+        TS_TYPES == tenor_eval_wasm::results::typescript(),
+        "src/types.d.ts is stale; regenerate it with \
+         `cargo run --example typings > src/types.d.ts`"
+    );
+}
+
+/// Parse a result as `T`. Fails on a missing required field or on a field
+/// the result types, and so `types.d.ts`, do not declare, at any depth.
+fn parse<T: serde::de::DeserializeOwned>(json: &str) -> T {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        panic!(
+            "{} does not match {}: {}",
+            json,
+            std::any::type_name::<T>(),
+            e
+        )
+    })
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_typings_match_results() {
+    use tenor_eval_wasm::results::*;
+
+    let loaded: ContractHandle = parse(&tenor_eval_wasm::load_contract(BASIC_BUNDLE));
+    let handle = loaded.handle;
+
+    let verdicts: VerdictSet = parse(&tenor_eval_wasm::evaluate(handle, r#"{"is_active": true}"#));
+    assert_eq!(verdicts.verdicts.len(), 1);
+    let _: Vec<VerdictSet> = parse(&tenor_eval_wasm::evaluate_batch(
+        handle,
+        r#"[{"is_active": true}, {"is_active": false}]"#,
+    ));
+
+    let flow: FlowResult = parse(&tenor_eval_wasm::simulate_flow(
+        handle,
+        "approval_flow",
+        "admin",
+        r#"{"is_active": true}"#,
+        r#"{}"#,
+    ));
+    assert!(!flow.path.is_empty() && !flow.would_transition.is_empty());
+
+    let mut reasons = Vec::new();
+    for (persona, facts) in [
+        ("admin", r#"{"is_active": true}"#),
+        ("admin", r#"{"is_active": false}"#),
+        ("guest", r#"{"is_active": true}"#),
+    ] {
+        let space: ActionSpace = parse(&tenor_eval_wasm::compute_action_space(
+            handle,
+            facts,
+            r#"{"Order": "pending"}"#,
+            persona,
+        ));
+        reasons.extend(space.blocked_actions.into_iter().map(|b| b.reason));
+    }
+    assert!(reasons
+        .iter()
+        .any(|r| matches!(r, BlockedReason::PreconditionNotMet { .. })));
+    assert!(reasons
+        .iter()
+        .any(|r| matches!(r, BlockedReason::PersonaNotAuthorized { .. })));
+
+    let inspect: InspectResult = parse(&tenor_eval_wasm::inspect_contract(handle));
+    assert!(!inspect.operations[0].effects.is_empty());
+
+    let _: TenorError = parse(&tenor_eval_wasm::evaluate(9999, "{}"));
+}
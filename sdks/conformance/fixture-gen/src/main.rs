@@ -1,4 +1,3 @@
-use std::collections::BTreeMap;
 use std::fs;
 
 fn main() {
@@ -9,100 +8,189 @@ fn main() {
     println!("Generating conformance fixtures in: {}", fixtures_dir);
 
     // Read inputs
-    let bundle_str =
-        fs::read_to_string(format!("{}/escrow-bundle.json", fixtures_dir)).expect("escrow-bundle.json");
-    let bundle: serde_json::Value =
-        serde_json::from_str(&bundle_str).expect("parse escrow-bundle.json");
+    let read = |name: &str| read_json(&fixtures_dir, name);
+    let bundle = read("escrow-bundle.json");
+    let facts = read("escrow-facts.json");
+    let entity_states = read("escrow-entity-states.json");
+    let facts_inactive = read("escrow-facts-inactive.json");
+    let multi_instance_states = read("multi-instance-entity-states.json");
+    let multi_instance_states_approved = read("multi-instance-entity-states-approved.json");
+    let multi_instance_bindings = read("multi-instance-bindings.json");
+    let parallel_bundle = read("parallel-bundle.json");
+    let parallel_facts = read("parallel-facts.json");
+    let parallel_states_reviewed = read("parallel-entity-states-reviewed.json");
+    let compensate_bundle = read("compensate-bundle.json");
+    let compensate_facts = read("compensate-facts.json");
 
-    let facts_str =
-        fs::read_to_string(format!("{}/escrow-facts.json", fixtures_dir)).expect("escrow-facts.json");
-    let facts: serde_json::Value =
-        serde_json::from_str(&facts_str).expect("parse escrow-facts.json");
-
-    let states_str =
-        fs::read_to_string(format!("{}/escrow-entity-states.json", fixtures_dir)).expect("escrow-entity-states.json");
-    let entity_states: serde_json::Value =
-        serde_json::from_str(&states_str).expect("parse escrow-entity-states.json");
-
-    let facts_inactive_str = fs::read_to_string(format!("{}/escrow-facts-inactive.json", fixtures_dir))
-        .expect("escrow-facts-inactive.json");
-    let facts_inactive: serde_json::Value =
-        serde_json::from_str(&facts_inactive_str).expect("parse escrow-facts-inactive.json");
+    let no_states = serde_json::json!({});
+    let no_bindings = serde_json::Value::Null;
+    let write = |name: &str, value: &serde_json::Value| {
+        write_sorted(&format!("{}/{}", fixtures_dir, name), value)
+    };
 
-    // Parse entity states (flat format: {"Order": "pending"} -> {(Order, _default): pending})
-    let entity_state_map = parse_entity_states(&entity_states).expect("parse entity states");
+    let contract = tenor_eval::Contract::from_interchange(&bundle).expect("load contract");
+    let parallel_contract =
+        tenor_eval::Contract::from_interchange(&parallel_bundle).expect("load parallel contract");
+    let compensate_contract = tenor_eval::Contract::from_interchange(&compensate_bundle)
+        .expect("load compensate contract");
 
     // ──────────────────────────────────────────────────────────────────────────
     // 1. expected-verdicts.json — evaluate with is_active=true
     // ──────────────────────────────────────────────────────────────────────────
-    let contract =
-        tenor_eval::Contract::from_interchange(&bundle).expect("load contract");
-    let fact_set =
-        tenor_eval::assemble::assemble_facts(&contract, &facts).expect("assemble facts");
-    let verdict_set =
-        tenor_eval::rules::eval_strata(&contract, &fact_set).expect("eval strata");
-    let verdicts_json = verdict_set.to_json();
-    write_sorted(&format!("{}/expected-verdicts.json", fixtures_dir), &verdicts_json);
+    write("expected-verdicts.json", &evaluate(&contract, &facts));
 
     // ──────────────────────────────────────────────────────────────────────────
     // 2. expected-action-space.json — compute_action_space (active, admin)
     // ──────────────────────────────────────────────────────────────────────────
-    let action_space = tenor_eval::compute_action_space(
-        &contract,
-        &facts,
-        &entity_state_map,
-        "admin",
-    )
-    .expect("compute_action_space");
-    let action_space_json = serde_json::to_value(&action_space).expect("serialize action_space");
-    write_sorted(
-        &format!("{}/expected-action-space.json", fixtures_dir),
-        &action_space_json,
+    write(
+        "expected-action-space.json",
+        &action_space(&contract, &facts, &entity_states, "admin"),
     );
 
     // ──────────────────────────────────────────────────────────────────────────
     // 3. expected-flow-result.json — simulate_flow (active, admin, approval_flow)
     //    Output format matches simulate_flow_with_bindings in the WASM module.
     // ──────────────────────────────────────────────────────────────────────────
-    let flow_json = simulate_flow(&contract, &bundle, &facts, &entity_states, "approval_flow", "admin")
-        .expect("simulate_flow");
-    write_sorted(
-        &format!("{}/expected-flow-result.json", fixtures_dir),
-        &flow_json,
+    write(
+        "expected-flow-result.json",
+        &simulate_flow(
+            &contract,
+            &facts,
+            &entity_states,
+            &no_bindings,
+            "approval_flow",
+            "admin",
+        ),
     );
 
     // ──────────────────────────────────────────────────────────────────────────
     // 4. expected-verdicts-inactive.json — evaluate with is_active=false
     // ──────────────────────────────────────────────────────────────────────────
-    let fact_set_inactive =
-        tenor_eval::assemble::assemble_facts(&contract, &facts_inactive).expect("assemble facts inactive");
-    let verdict_set_inactive =
-        tenor_eval::rules::eval_strata(&contract, &fact_set_inactive).expect("eval strata inactive");
-    let verdicts_inactive_json = verdict_set_inactive.to_json();
-    write_sorted(
-        &format!("{}/expected-verdicts-inactive.json", fixtures_dir),
-        &verdicts_inactive_json,
+    write(
+        "expected-verdicts-inactive.json",
+        &evaluate(&contract, &facts_inactive),
     );
 
     // ──────────────────────────────────────────────────────────────────────────
     // 5. expected-action-space-blocked.json — compute_action_space (inactive, admin)
     // ──────────────────────────────────────────────────────────────────────────
-    let action_space_blocked = tenor_eval::compute_action_space(
-        &contract,
-        &facts_inactive,
-        &entity_state_map,
-        "admin",
-    )
-    .expect("compute_action_space blocked");
-    let blocked_json = serde_json::to_value(&action_space_blocked).expect("serialize blocked");
-    write_sorted(
-        &format!("{}/expected-action-space-blocked.json", fixtures_dir),
-        &blocked_json,
+    write(
+        "expected-action-space-blocked.json",
+        &action_space(&contract, &facts_inactive, &entity_states, "admin"),
+    );
+
+    // ──────────────────────────────────────────────────────────────────────────
+    // 6. expected-action-space-multi-instance.json — nested entity states with
+    //    two of three Order instances in the source state; the action lists
+    //    exactly those instances in its instance_bindings.
+    // ──────────────────────────────────────────────────────────────────────────
+    write(
+        "expected-action-space-multi-instance.json",
+        &action_space(&contract, &facts, &multi_instance_states, "admin"),
+    );
+
+    // ──────────────────────────────────────────────────────────────────────────
+    // 7. expected-action-space-multi-instance-blocked.json — every Order
+    //    instance already approved; blocked as EntityNotInSourceState with the
+    //    blocking instances.
+    // ──────────────────────────────────────────────────────────────────────────
+    write(
+        "expected-action-space-multi-instance-blocked.json",
+        &action_space(&contract, &facts, &multi_instance_states_approved, "admin"),
+    );
+
+    // ──────────────────────────────────────────────────────────────────────────
+    // 8. expected-flow-result-multi-instance.json — approval_flow bound to
+    //    Order ord-003; only that instance transitions.
+    // ──────────────────────────────────────────────────────────────────────────
+    write(
+        "expected-flow-result-multi-instance.json",
+        &simulate_flow(
+            &contract,
+            &facts,
+            &multi_instance_states,
+            &multi_instance_bindings,
+            "approval_flow",
+            "admin",
+        ),
+    );
+
+    // ──────────────────────────────────────────────────────────────────────────
+    // 9. expected-flow-result-parallel.json — publish_flow: ParallelStep whose
+    //    branches both succeed, joined by on_all_success.
+    // ──────────────────────────────────────────────────────────────────────────
+    write(
+        "expected-flow-result-parallel.json",
+        &simulate_flow(
+            &parallel_contract,
+            &parallel_facts,
+            &no_states,
+            &no_bindings,
+            "publish_flow",
+            "editor",
+        ),
+    );
+
+    // ──────────────────────────────────────────────────────────────────────────
+    // 10. expected-flow-result-parallel-branch-failure.json — publish_flow with
+    //     Document already reviewed: the review branch takes its on_failure
+    //     handler, the index branch still transitions, and the join reports
+    //     the per-branch outcomes.
+    // ──────────────────────────────────────────────────────────────────────────
+    write(
+        "expected-flow-result-parallel-branch-failure.json",
+        &simulate_flow(
+            &parallel_contract,
+            &parallel_facts,
+            &parallel_states_reviewed,
+            &no_bindings,
+            "publish_flow",
+            "editor",
+        ),
+    );
+
+    // ──────────────────────────────────────────────────────────────────────────
+    // 11. expected-flow-result-compensate.json — standard_release where
+    //     release_escrow's precondition fails; the Compensate handler reverts
+    //     the delivery confirmation before Terminal(failure).
+    // ──────────────────────────────────────────────────────────────────────────
+    write(
+        "expected-flow-result-compensate.json",
+        &simulate_flow(
+            &compensate_contract,
+            &compensate_facts,
+            &no_states,
+            &no_bindings,
+            "standard_release",
+            "seller",
+        ),
     );
 
     println!("Conformance fixtures generated successfully.");
 }
 
+/// Evaluate rules and return the VerdictSet JSON.
+fn evaluate(contract: &tenor_eval::Contract, facts: &serde_json::Value) -> serde_json::Value {
+    let fact_set = tenor_eval::assemble::assemble_facts(contract, facts).expect("assemble facts");
+    let verdict_set = tenor_eval::rules::eval_strata(contract, &fact_set).expect("eval strata");
+    verdict_set.to_json()
+}
+
+/// Compute the action space for `persona` and return it as JSON.
+fn action_space(
+    contract: &tenor_eval::Contract,
+    facts: &serde_json::Value,
+    entity_states: &serde_json::Value,
+    persona: &str,
+) -> serde_json::Value {
+    let entity_state_map =
+        tenor_eval::simulate::parse_entity_states(entity_states).expect("parse entity states");
+    let action_space =
+        tenor_eval::compute_action_space(contract, facts, &entity_state_map, persona)
+            .expect("compute_action_space");
+    serde_json::to_value(&action_space).expect("serialize action_space")
+}
+
 /// Simulate a flow and return JSON matching the WASM simulate_flow_with_bindings output format.
 ///
 /// Format:
@@ -120,21 +208,21 @@ fn main() {
 /// ```
 fn simulate_flow(
     contract: &tenor_eval::Contract,
-    _bundle: &serde_json::Value,
     facts: &serde_json::Value,
-    entity_states_json: &serde_json::Value,
+    entity_states: &serde_json::Value,
+    instance_bindings: &serde_json::Value,
     flow_id: &str,
     persona: &str,
-) -> Result<serde_json::Value, String> {
-    // Parse entity states (supports flat and nested formats)
+) -> serde_json::Value {
+    // Entity states may be flat or nested; bindings may be null
     let provided_states =
-        parse_entity_states(entity_states_json)?;
+        tenor_eval::simulate::parse_entity_states(entity_states).expect("parse entity states");
+    let instance_bindings = tenor_eval::simulate::parse_instance_bindings(instance_bindings)
+        .expect("parse instance bindings");
 
     // Assemble facts and evaluate rules
-    let fact_set = tenor_eval::assemble::assemble_facts(contract, facts)
-        .map_err(|e| format!("fact assembly: {}", e))?;
-    let verdict_set = tenor_eval::rules::eval_strata(contract, &fact_set)
-        .map_err(|e| format!("eval strata: {}", e))?;
+    let fact_set = tenor_eval::assemble::assemble_facts(contract, facts).expect("assemble facts");
+    let verdict_set = tenor_eval::rules::eval_strata(contract, &fact_set).expect("eval strata");
 
     // Create snapshot
     let snapshot = tenor_eval::Snapshot {
@@ -144,118 +232,35 @@ fn simulate_flow(
 
     // Merge contract defaults with provided states
     let mut merged_states = tenor_eval::operation::init_entity_states(contract);
-    for (key, state) in provided_states {
-        merged_states.insert(key, state);
-    }
+    merged_states.extend(provided_states);
 
-    // Find the flow
     let target_flow = contract
         .get_flow(flow_id)
-        .ok_or_else(|| format!("flow '{}' not found", flow_id))?;
-
-    // Use empty instance_bindings (backward compat => _default)
-    let instance_bindings = BTreeMap::new();
+        .unwrap_or_else(|| panic!("flow '{}' not found", flow_id));
 
-    // Execute the flow
     let flow_result = tenor_eval::flow::execute_flow(
         target_flow,
         contract,
         &snapshot,
         &mut merged_states,
+        &mut tenor_eval::EntityAttributeMap::new(),
         &instance_bindings,
-        None,
+        tenor_eval::FlowOptions::default(),
     )
-    .map_err(|e| format!("flow execution: {}", e))?;
+    .expect("execute flow");
 
-    // Build path — match WASM format (include instance_bindings if non-empty)
-    let path: Vec<serde_json::Value> = flow_result
-        .steps_executed
-        .iter()
-        .map(|s| {
-            let mut step_json = serde_json::json!({
-                "step_id": s.step_id,
-                "step_type": s.step_type,
-                "result": s.result,
-            });
-            if !s.instance_bindings.is_empty() {
-                step_json["instance_bindings"] = serde_json::to_value(&s.instance_bindings)
-                    .unwrap_or(serde_json::Value::Null);
-            }
-            step_json
-        })
-        .collect();
-
-    // Build would_transition — match WASM format
-    let would_transition: Vec<serde_json::Value> = flow_result
-        .entity_state_changes
-        .iter()
-        .map(|e| {
-            serde_json::json!({
-                "entity_id": e.entity_id,
-                "instance_id": e.instance_id,
-                "from_state": e.from_state,
-                "to_state": e.to_state,
-            })
-        })
-        .collect();
-
-    Ok(serde_json::json!({
-        "simulation": true,
-        "flow_id": flow_id,
-        "persona": persona,
-        "outcome": flow_result.outcome,
-        "path": path,
-        "would_transition": would_transition,
-        "verdicts": verdict_set.to_json()["verdicts"],
-        "instance_bindings": instance_bindings,
-    }))
-}
-
-/// Parse entity_states JSON with auto-detection of flat and nested formats.
-///
-/// Flat: `{"Order": "pending"}` -> `{("Order", "_default"): "pending"}`
-/// Nested: `{"Order": {"ord-001": "pending"}}` -> `{("Order", "ord-001"): "pending"}`
-fn parse_entity_states(
-    json: &serde_json::Value,
-) -> Result<tenor_eval::EntityStateMap, String> {
-    let obj = match json.as_object() {
-        Some(o) => o,
-        None => return Err("entity_states must be a JSON object".to_string()),
+    let result = tenor_eval::FlowEvalResult {
+        verdicts: verdict_set,
+        flow_result,
     };
+    tenor_eval::simulate::simulation_json(flow_id, persona, &result, &instance_bindings)
+}
 
-    let mut entity_states = tenor_eval::EntityStateMap::new();
-
-    for (entity_id, value) in obj {
-        if let Some(state_str) = value.as_str() {
-            entity_states.insert(
-                (entity_id.clone(), tenor_eval::DEFAULT_INSTANCE_ID.to_string()),
-                state_str.to_string(),
-            );
-        } else if let Some(instance_map) = value.as_object() {
-            for (instance_id, state_val) in instance_map {
-                let state_str = match state_val.as_str() {
-                    Some(s) => s,
-                    None => {
-                        return Err(format!(
-                            "entity_states[{}][{}] must be a string",
-                            entity_id, instance_id
-                        ))
-                    }
-                };
-                entity_states.insert(
-                    (entity_id.clone(), instance_id.clone()),
-                    state_str.to_string(),
-                );
-            }
-        } else {
-            return Err(format!(
-                "entity_states[{}] must be a string or object",
-                entity_id
-            ));
-        }
-    }
-
-    Ok(entity_states)
+/// Read and parse an input fixture.
+fn read_json(fixtures_dir: &str, name: &str) -> serde_json::Value {
+    let path = format!("{}/{}", fixtures_dir, name);
+    let text = fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {}: {}", path, e));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("parse {}: {}", path, e))
 }
 
 /// Write a serde_json::Value to a file with sorted keys and pretty formatting.
@@ -270,8 +275,7 @@ fn write_sorted(path: &str, value: &serde_json::Value) {
 fn sort_keys(value: &serde_json::Value) -> serde_json::Value {
     match value {
         serde_json::Value::Object(map) => {
-            let mut sorted: serde_json::Map<String, serde_json::Value> =
-                serde_json::Map::new();
+            let mut sorted: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
             let mut keys: Vec<&String> = map.keys().collect();
             keys.sort();
             for key in keys {
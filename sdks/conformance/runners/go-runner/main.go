@@ -17,116 +17,90 @@ func main() {
 	if len(os.Args) > 1 {
 		fixturesDir = os.Args[1]
 	}
+	read := func(name string) map[string]interface{} {
+		return mustReadObj(fixturesDir + "/" + name)
+	}
 
-	bundle := mustRead(fixturesDir + "/escrow-bundle.json")
-	facts := mustReadObj(fixturesDir + "/escrow-facts.json")
-	entityStates := mustReadObj(fixturesDir + "/escrow-entity-states.json")
-	factsInactive := mustReadObj(fixturesDir + "/escrow-facts-inactive.json")
+	facts := toFactSet(read("escrow-facts.json"))
+	factsInactive := toFactSet(read("escrow-facts-inactive.json"))
+	entityStateFlat := toEntityStateMap(read("escrow-entity-states.json"))
+	multiInstanceStates := toEntityStateMapNested(read("multi-instance-entity-states.json"))
+	multiInstanceStatesApproved := toEntityStateMapNested(read("multi-instance-entity-states-approved.json"))
+	multiInstanceBindings := toInstanceBindings(read("multi-instance-bindings.json"))
 
-	eval, err := tenor.NewEvaluatorFromBundle([]byte(bundle))
-	if err != nil {
-		fmt.Fprintf(os.Stderr, "Failed to load contract: %v\n", err)
-		os.Exit(1)
-	}
+	eval := mustLoad(fixturesDir + "/escrow-bundle.json")
 	defer eval.Close()
+	parallel := mustLoad(fixturesDir + "/parallel-bundle.json")
+	defer parallel.Close()
+	compensate := mustLoad(fixturesDir + "/compensate-bundle.json")
+	defer compensate.Close()
 
 	passed, failed := 0, 0
-
-	// Test 1: Evaluate (active)
-	verdicts, err := eval.Evaluate(toFactSet(facts))
-	if err != nil {
-		fmt.Printf("FAIL: evaluate (active) — error: %v\n", err)
-		failed++
-	} else {
-		expected := mustReadObj(fixturesDir + "/expected-verdicts.json")
-		got := toObj(verdicts)
-		if jsonEqual(got, expected) {
-			fmt.Println("PASS: evaluate (active)")
-			passed++
-		} else {
-			fmt.Println("FAIL: evaluate (active)")
-			fmt.Printf("  expected: %s\n", mustMarshal(expected))
-			fmt.Printf("  actual:   %s\n", mustMarshal(got))
+	check := func(name, expectedFile string, got interface{}, err error) {
+		if err != nil {
+			fmt.Printf("FAIL: %s — error: %v\n", name, err)
 			failed++
+			return
 		}
-	}
-
-	// Test 2: Evaluate (inactive)
-	verdictsInactive, err := eval.Evaluate(toFactSet(factsInactive))
-	if err != nil {
-		fmt.Printf("FAIL: evaluate (inactive) — error: %v\n", err)
-		failed++
-	} else {
-		expected := mustReadObj(fixturesDir + "/expected-verdicts-inactive.json")
-		got := toObj(verdictsInactive)
-		if jsonEqual(got, expected) {
-			fmt.Println("PASS: evaluate (inactive)")
+		expected := read(expectedFile)
+		actual := toObj(got)
+		if jsonEqual(actual, expected) {
+			fmt.Printf("PASS: %s\n", name)
 			passed++
 		} else {
-			fmt.Println("FAIL: evaluate (inactive)")
+			fmt.Printf("FAIL: %s\n", name)
 			fmt.Printf("  expected: %s\n", mustMarshal(expected))
-			fmt.Printf("  actual:   %s\n", mustMarshal(got))
+			fmt.Printf("  actual:   %s\n", mustMarshal(actual))
 			failed++
 		}
 	}
 
+	// Test 1: Evaluate (active)
+	verdicts, err := eval.Evaluate(facts)
+	check("evaluate (active)", "expected-verdicts.json", verdicts, err)
+
+	// Test 2: Evaluate (inactive)
+	verdictsInactive, err := eval.Evaluate(factsInactive)
+	check("evaluate (inactive)", "expected-verdicts-inactive.json", verdictsInactive, err)
+
 	// Test 3: ComputeActionSpace (active, admin)
-	entityStateFlat := toEntityStateMap(entityStates)
-	actionSpace, err := eval.ComputeActionSpace(toFactSet(facts), entityStateFlat, "admin")
-	if err != nil {
-		fmt.Printf("FAIL: computeActionSpace — error: %v\n", err)
-		failed++
-	} else {
-		expected := mustReadObj(fixturesDir + "/expected-action-space.json")
-		got := toObj(actionSpace)
-		if jsonEqual(got, expected) {
-			fmt.Println("PASS: computeActionSpace")
-			passed++
-		} else {
-			fmt.Println("FAIL: computeActionSpace")
-			fmt.Printf("  expected: %s\n", mustMarshal(expected))
-			fmt.Printf("  actual:   %s\n", mustMarshal(got))
-			failed++
-		}
-	}
+	actionSpace, err := eval.ComputeActionSpace(facts, entityStateFlat, "admin")
+	check("computeActionSpace", "expected-action-space.json", actionSpace, err)
 
 	// Test 4: ComputeActionSpace (blocked — admin + inactive)
-	actionSpaceBlocked, err := eval.ComputeActionSpace(toFactSet(factsInactive), entityStateFlat, "admin")
-	if err != nil {
-		fmt.Printf("FAIL: computeActionSpace (blocked) — error: %v\n", err)
-		failed++
-	} else {
-		expected := mustReadObj(fixturesDir + "/expected-action-space-blocked.json")
-		got := toObj(actionSpaceBlocked)
-		if jsonEqual(got, expected) {
-			fmt.Println("PASS: computeActionSpace (blocked)")
-			passed++
-		} else {
-			fmt.Println("FAIL: computeActionSpace (blocked)")
-			fmt.Printf("  expected: %s\n", mustMarshal(expected))
-			fmt.Printf("  actual:   %s\n", mustMarshal(got))
-			failed++
-		}
-	}
+	actionSpaceBlocked, err := eval.ComputeActionSpace(factsInactive, entityStateFlat, "admin")
+	check("computeActionSpace (blocked)", "expected-action-space-blocked.json", actionSpaceBlocked, err)
 
 	// Test 5: ExecuteFlow
-	flowResult, err := eval.ExecuteFlow("approval_flow", toFactSet(facts), entityStateFlat, "admin")
-	if err != nil {
-		fmt.Printf("FAIL: executeFlow — error: %v\n", err)
-		failed++
-	} else {
-		expected := mustReadObj(fixturesDir + "/expected-flow-result.json")
-		got := toObj(flowResult)
-		if jsonEqual(got, expected) {
-			fmt.Println("PASS: executeFlow")
-			passed++
-		} else {
-			fmt.Println("FAIL: executeFlow")
-			fmt.Printf("  expected: %s\n", mustMarshal(expected))
-			fmt.Printf("  actual:   %s\n", mustMarshal(got))
-			failed++
-		}
-	}
+	flowResult, err := eval.ExecuteFlow("approval_flow", facts, entityStateFlat, "admin")
+	check("executeFlow", "expected-flow-result.json", flowResult, err)
+
+	// Test 6: ComputeActionSpaceNested (multi-instance)
+	multiSpace, err := eval.ComputeActionSpaceNested(facts, multiInstanceStates, "admin")
+	check("computeActionSpace (multi-instance)", "expected-action-space-multi-instance.json", multiSpace, err)
+
+	// Test 7: ComputeActionSpaceNested (multi-instance, blocked)
+	multiBlocked, err := eval.ComputeActionSpaceNested(facts, multiInstanceStatesApproved, "admin")
+	check("computeActionSpace (multi-instance, blocked)", "expected-action-space-multi-instance-blocked.json", multiBlocked, err)
+
+	// Test 8: ExecuteFlowWithBindings (multi-instance)
+	multiFlow, err := eval.ExecuteFlowWithBindings("approval_flow", facts, multiInstanceStates, "admin", multiInstanceBindings)
+	check("executeFlowWithBindings (multi-instance)", "expected-flow-result-multi-instance.json", multiFlow, err)
+
+	// Test 9: ExecuteFlow (parallel step, all branches succeed)
+	parallelFacts := toFactSet(read("parallel-facts.json"))
+	parallelFlow, err := parallel.ExecuteFlow("publish_flow", parallelFacts, tenor.EntityStateMap{}, "editor")
+	check("executeFlow (parallel)", "expected-flow-result-parallel.json", parallelFlow, err)
+
+	// Test 10: ExecuteFlow (parallel step, one branch fails)
+	reviewed := toEntityStateMap(read("parallel-entity-states-reviewed.json"))
+	branchFailure, err := parallel.ExecuteFlow("publish_flow", parallelFacts, reviewed, "editor")
+	check("executeFlow (parallel, branch failure)", "expected-flow-result-parallel-branch-failure.json", branchFailure, err)
+
+	// Test 11: ExecuteFlow (compensation path)
+	compensateFacts := toFactSet(read("compensate-facts.json"))
+	compensated, err := compensate.ExecuteFlow("standard_release", compensateFacts, tenor.EntityStateMap{}, "seller")
+	check("executeFlow (compensate)", "expected-flow-result-compensate.json", compensated, err)
 
 	fmt.Printf("\nGo SDK: %d passed, %d failed\n", passed, failed)
 	if failed > 0 {
@@ -136,6 +110,15 @@ func main() {
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
+func mustLoad(path string) *tenor.Evaluator {
+	eval, err := tenor.NewEvaluatorFromBundle([]byte(mustRead(path)))
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Failed to load contract %s: %v\n", path, err)
+		os.Exit(1)
+	}
+	return eval
+}
+
 func mustRead(path string) string {
 	data, err := os.ReadFile(path)
 	if err != nil {
@@ -184,6 +167,36 @@ func toEntityStateMap(m map[string]interface{}) tenor.EntityStateMap {
 	return result
 }
 
+// toEntityStateMapNested converts a generic map to a tenor.EntityStateMapNested.
+// Assumes nested format: {"Order": {"ord-001": "pending"}}.
+func toEntityStateMapNested(m map[string]interface{}) tenor.EntityStateMapNested {
+	result := make(tenor.EntityStateMapNested, len(m))
+	for entity, v := range m {
+		instances, ok := v.(map[string]interface{})
+		if !ok {
+			continue
+		}
+		result[entity] = make(map[string]string, len(instances))
+		for instance, state := range instances {
+			if s, ok := state.(string); ok {
+				result[entity][instance] = s
+			}
+		}
+	}
+	return result
+}
+
+// toInstanceBindings converts a generic map to tenor.InstanceBindings.
+func toInstanceBindings(m map[string]interface{}) tenor.InstanceBindings {
+	result := make(tenor.InstanceBindings, len(m))
+	for k, v := range m {
+		if s, ok := v.(string); ok {
+			result[k] = s
+		}
+	}
+	return result
+}
+
 // toObj marshals a value to JSON then unmarshals to map[string]interface{}.
 // This normalises the types (e.g. bool stays bool, numbers become float64).
 func toObj(v interface{}) map[string]interface{} {
@@ -3,9 +3,9 @@
 Compares Python SDK output against expected fixtures generated from the Rust evaluator.
 """
 import json
-import os
 import sys
 from pathlib import Path
+from typing import Any, Callable
 
 # Add the Python SDK to path so tenor can be imported without installation.
 # This uses the PYTHONPATH set by run-python.sh or falls back to the .venv.
@@ -16,81 +16,140 @@ if str(SDK_DIR / "python") not in sys.path:
 from tenor import TenorEvaluator  # noqa: E402
 
 
-def main() -> None:
-    fixtures_dir = Path(sys.argv[1]) if len(sys.argv) > 1 else Path("fixtures")
-
-    bundle = (fixtures_dir / "escrow-bundle.json").read_text()
-    facts = json.loads((fixtures_dir / "escrow-facts.json").read_text())
-    entity_states = json.loads((fixtures_dir / "escrow-entity-states.json").read_text())
-    facts_inactive = json.loads((fixtures_dir / "escrow-facts-inactive.json").read_text())
+class Runner:
+    def __init__(self, fixtures_dir: Path) -> None:
+        self.fixtures_dir = fixtures_dir
+        self.passed = 0
+        self.failed = 0
+
+    def read(self, name: str) -> Any:
+        return json.loads((self.fixtures_dir / name).read_text())
+
+    def check(self, name: str, expected_file: str, run: Callable[[], Any]) -> None:
+        try:
+            actual = run()
+        except Exception as e:  # noqa: BLE001 — report every SDK error as a failure
+            print(f"FAIL: {name} — error: {e}")
+            self.failed += 1
+            return
+        expected = self.read(expected_file)
+        if json_equal(actual, expected):
+            print(f"PASS: {name}")
+            self.passed += 1
+        else:
+            print(f"FAIL: {name}")
+            print(f"  expected: {json.dumps(sort_keys_recursive(expected), sort_keys=True)}")
+            print(f"  actual:   {json.dumps(sort_keys_recursive(actual), sort_keys=True)}")
+            self.failed += 1
 
-    evaluator = TenorEvaluator.from_bundle_json(bundle)
 
-    passed = 0
-    failed = 0
+def main() -> None:
+    fixtures_dir = Path(sys.argv[1]) if len(sys.argv) > 1 else Path("fixtures")
+    r = Runner(fixtures_dir)
+
+    facts = r.read("escrow-facts.json")
+    entity_states = r.read("escrow-entity-states.json")
+    facts_inactive = r.read("escrow-facts-inactive.json")
+    multi_instance_states = r.read("multi-instance-entity-states.json")
+    multi_instance_states_approved = r.read("multi-instance-entity-states-approved.json")
+    multi_instance_bindings = r.read("multi-instance-bindings.json")
+
+    evaluator = TenorEvaluator.from_bundle_json((fixtures_dir / "escrow-bundle.json").read_text())
+    parallel = TenorEvaluator.from_bundle_json((fixtures_dir / "parallel-bundle.json").read_text())
+    compensate = TenorEvaluator.from_bundle_json(
+        (fixtures_dir / "compensate-bundle.json").read_text()
+    )
 
     # Test 1: evaluate (active)
-    verdicts = evaluator.evaluate(facts)
-    expected = json.loads((fixtures_dir / "expected-verdicts.json").read_text())
-    if json_equal(verdicts, expected):
-        print("PASS: evaluate (active)")
-        passed += 1
-    else:
-        print("FAIL: evaluate (active)")
-        print(f"  expected: {json.dumps(sort_keys_recursive(expected), sort_keys=True)}")
-        print(f"  actual:   {json.dumps(sort_keys_recursive(verdicts), sort_keys=True)}")
-        failed += 1
+    r.check("evaluate (active)", "expected-verdicts.json", lambda: evaluator.evaluate(facts))
 
     # Test 2: evaluate (inactive)
-    verdicts_inactive = evaluator.evaluate(facts_inactive)
-    expected_inactive = json.loads((fixtures_dir / "expected-verdicts-inactive.json").read_text())
-    if json_equal(verdicts_inactive, expected_inactive):
-        print("PASS: evaluate (inactive)")
-        passed += 1
-    else:
-        print("FAIL: evaluate (inactive)")
-        print(f"  expected: {json.dumps(sort_keys_recursive(expected_inactive), sort_keys=True)}")
-        print(f"  actual:   {json.dumps(sort_keys_recursive(verdicts_inactive), sort_keys=True)}")
-        failed += 1
+    r.check(
+        "evaluate (inactive)",
+        "expected-verdicts-inactive.json",
+        lambda: evaluator.evaluate(facts_inactive),
+    )
 
     # Test 3: compute_action_space (active, admin)
-    action_space = evaluator.compute_action_space(facts, entity_states, "admin")
-    expected_as = json.loads((fixtures_dir / "expected-action-space.json").read_text())
-    if json_equal(action_space, expected_as):
-        print("PASS: compute_action_space")
-        passed += 1
-    else:
-        print("FAIL: compute_action_space")
-        print(f"  expected: {json.dumps(sort_keys_recursive(expected_as), sort_keys=True)}")
-        print(f"  actual:   {json.dumps(sort_keys_recursive(action_space), sort_keys=True)}")
-        failed += 1
+    r.check(
+        "compute_action_space",
+        "expected-action-space.json",
+        lambda: evaluator.compute_action_space(facts, entity_states, "admin"),
+    )
 
     # Test 4: compute_action_space (blocked — admin + inactive)
-    action_space_blocked = evaluator.compute_action_space(facts_inactive, entity_states, "admin")
-    expected_blocked = json.loads((fixtures_dir / "expected-action-space-blocked.json").read_text())
-    if json_equal(action_space_blocked, expected_blocked):
-        print("PASS: compute_action_space (blocked)")
-        passed += 1
-    else:
-        print("FAIL: compute_action_space (blocked)")
-        print(f"  expected: {json.dumps(sort_keys_recursive(expected_blocked), sort_keys=True)}")
-        print(f"  actual:   {json.dumps(sort_keys_recursive(action_space_blocked), sort_keys=True)}")
-        failed += 1
+    r.check(
+        "compute_action_space (blocked)",
+        "expected-action-space-blocked.json",
+        lambda: evaluator.compute_action_space(facts_inactive, entity_states, "admin"),
+    )
 
     # Test 5: execute_flow
-    flow_result = evaluator.execute_flow("approval_flow", facts, entity_states, "admin")
-    expected_flow = json.loads((fixtures_dir / "expected-flow-result.json").read_text())
-    if json_equal(flow_result, expected_flow):
-        print("PASS: execute_flow")
-        passed += 1
-    else:
-        print("FAIL: execute_flow")
-        print(f"  expected: {json.dumps(sort_keys_recursive(expected_flow), sort_keys=True)}")
-        print(f"  actual:   {json.dumps(sort_keys_recursive(flow_result), sort_keys=True)}")
-        failed += 1
-
-    print(f"\nPython SDK: {passed} passed, {failed} failed")
-    sys.exit(1 if failed > 0 else 0)
+    r.check(
+        "execute_flow",
+        "expected-flow-result.json",
+        lambda: evaluator.execute_flow("approval_flow", facts, entity_states, "admin"),
+    )
+
+    # Test 6: compute_action_space (multi-instance — nested entity states)
+    r.check(
+        "compute_action_space (multi-instance)",
+        "expected-action-space-multi-instance.json",
+        lambda: evaluator.compute_action_space(facts, multi_instance_states, "admin"),
+    )
+
+    # Test 7: compute_action_space (multi-instance, blocked)
+    r.check(
+        "compute_action_space (multi-instance, blocked)",
+        "expected-action-space-multi-instance-blocked.json",
+        lambda: evaluator.compute_action_space(facts, multi_instance_states_approved, "admin"),
+    )
+
+    # Test 8: execute_flow (multi-instance, with instance bindings)
+    r.check(
+        "execute_flow (multi-instance)",
+        "expected-flow-result-multi-instance.json",
+        lambda: evaluator.execute_flow(
+            "approval_flow",
+            facts,
+            multi_instance_states,
+            "admin",
+            instance_bindings=multi_instance_bindings,
+        ),
+    )
+
+    # Test 9: execute_flow (parallel step, all branches succeed)
+    r.check(
+        "execute_flow (parallel)",
+        "expected-flow-result-parallel.json",
+        lambda: parallel.execute_flow(
+            "publish_flow", r.read("parallel-facts.json"), {}, "editor"
+        ),
+    )
+
+    # Test 10: execute_flow (parallel step, one branch fails)
+    r.check(
+        "execute_flow (parallel, branch failure)",
+        "expected-flow-result-parallel-branch-failure.json",
+        lambda: parallel.execute_flow(
+            "publish_flow",
+            r.read("parallel-facts.json"),
+            r.read("parallel-entity-states-reviewed.json"),
+            "editor",
+        ),
+    )
+
+    # Test 11: execute_flow (compensation path)
+    r.check(
+        "execute_flow (compensate)",
+        "expected-flow-result-compensate.json",
+        lambda: compensate.execute_flow(
+            "standard_release", r.read("compensate-facts.json"), {}, "seller"
+        ),
+    )
+
+    print(f"\nPython SDK: {r.passed} passed, {r.failed} failed")
+    sys.exit(1 if r.failed > 0 else 0)
 
 
 def json_equal(a: object, b: object) -> bool:
@@ -3,116 +3,153 @@ import { TenorEvaluator } from "../../typescript/src/index";
 
 const fixturesDir = process.argv[2] || "fixtures";
 
+let passed = 0;
+let failed = 0;
+
 async function main() {
   const bundle = readFileSync(`${fixturesDir}/escrow-bundle.json`, "utf-8");
-  const facts = JSON.parse(
-    readFileSync(`${fixturesDir}/escrow-facts.json`, "utf-8"),
-  );
-  const entityStates = JSON.parse(
-    readFileSync(`${fixturesDir}/escrow-entity-states.json`, "utf-8"),
-  );
-  const factsInactive = JSON.parse(
-    readFileSync(`${fixturesDir}/escrow-facts-inactive.json`, "utf-8"),
+  const facts = readJson("escrow-facts.json");
+  const entityStates = readJson("escrow-entity-states.json");
+  const factsInactive = readJson("escrow-facts-inactive.json");
+  const multiInstanceStates = readJson("multi-instance-entity-states.json");
+  const multiInstanceStatesApproved = readJson(
+    "multi-instance-entity-states-approved.json",
   );
+  const multiInstanceBindings = readJson("multi-instance-bindings.json");
 
   const evaluator = TenorEvaluator.fromJson(bundle);
-
-  let passed = 0;
-  let failed = 0;
+  const parallel = TenorEvaluator.fromJson(
+    readFileSync(`${fixturesDir}/parallel-bundle.json`, "utf-8"),
+  );
+  const compensate = TenorEvaluator.fromJson(
+    readFileSync(`${fixturesDir}/compensate-bundle.json`, "utf-8"),
+  );
 
   // Test 1: evaluate (active)
-  const verdicts = evaluator.evaluate(facts);
-  const expectedVerdicts = JSON.parse(
-    readFileSync(`${fixturesDir}/expected-verdicts.json`, "utf-8"),
+  check("evaluate (active)", "expected-verdicts.json", () =>
+    evaluator.evaluate(facts),
   );
-  if (jsonEqual(verdicts, expectedVerdicts)) {
-    console.log("PASS: evaluate (active)");
-    passed++;
-  } else {
-    console.log("FAIL: evaluate (active)");
-    console.log("  expected:", JSON.stringify(sortKeys(expectedVerdicts)));
-    console.log("  actual:  ", JSON.stringify(sortKeys(verdicts)));
-    failed++;
-  }
 
   // Test 2: evaluate (inactive)
-  const verdictsInactive = evaluator.evaluate(factsInactive);
-  const expectedVerdictsInactive = JSON.parse(
-    readFileSync(`${fixturesDir}/expected-verdicts-inactive.json`, "utf-8"),
+  check("evaluate (inactive)", "expected-verdicts-inactive.json", () =>
+    evaluator.evaluate(factsInactive),
   );
-  if (jsonEqual(verdictsInactive, expectedVerdictsInactive)) {
-    console.log("PASS: evaluate (inactive)");
-    passed++;
-  } else {
-    console.log("FAIL: evaluate (inactive)");
-    console.log(
-      "  expected:",
-      JSON.stringify(sortKeys(expectedVerdictsInactive)),
-    );
-    console.log("  actual:  ", JSON.stringify(sortKeys(verdictsInactive)));
-    failed++;
-  }
 
   // Test 3: computeActionSpace (active, admin)
-  const actionSpace = evaluator.computeActionSpace(facts, entityStates, "admin");
-  const expectedActionSpace = JSON.parse(
-    readFileSync(`${fixturesDir}/expected-action-space.json`, "utf-8"),
+  check("computeActionSpace", "expected-action-space.json", () =>
+    evaluator.computeActionSpace(facts, entityStates, "admin"),
   );
-  if (jsonEqual(actionSpace, expectedActionSpace)) {
-    console.log("PASS: computeActionSpace");
-    passed++;
-  } else {
-    console.log("FAIL: computeActionSpace");
-    console.log("  expected:", JSON.stringify(sortKeys(expectedActionSpace)));
-    console.log("  actual:  ", JSON.stringify(sortKeys(actionSpace)));
-    failed++;
-  }
 
   // Test 4: computeActionSpace (blocked — admin + inactive)
-  const actionSpaceBlocked = evaluator.computeActionSpace(
-    factsInactive,
-    entityStates,
-    "admin",
+  check(
+    "computeActionSpace (blocked)",
+    "expected-action-space-blocked.json",
+    () => evaluator.computeActionSpace(factsInactive, entityStates, "admin"),
   );
-  const expectedBlocked = JSON.parse(
-    readFileSync(`${fixturesDir}/expected-action-space-blocked.json`, "utf-8"),
-  );
-  if (jsonEqual(actionSpaceBlocked, expectedBlocked)) {
-    console.log("PASS: computeActionSpace (blocked)");
-    passed++;
-  } else {
-    console.log("FAIL: computeActionSpace (blocked)");
-    console.log("  expected:", JSON.stringify(sortKeys(expectedBlocked)));
-    console.log("  actual:  ", JSON.stringify(sortKeys(actionSpaceBlocked)));
-    failed++;
-  }
 
   // Test 5: executeFlow
-  const flowResult = evaluator.executeFlow(
-    "approval_flow",
-    facts,
-    entityStates,
-    "admin",
+  check("executeFlow", "expected-flow-result.json", () =>
+    evaluator.executeFlow("approval_flow", facts, entityStates, "admin"),
+  );
+
+  // Test 6: computeActionSpace (multi-instance — nested entity states)
+  check(
+    "computeActionSpace (multi-instance)",
+    "expected-action-space-multi-instance.json",
+    () => evaluator.computeActionSpace(facts, multiInstanceStates, "admin"),
   );
-  const expectedFlow = JSON.parse(
-    readFileSync(`${fixturesDir}/expected-flow-result.json`, "utf-8"),
+
+  // Test 7: computeActionSpace (multi-instance, blocked)
+  check(
+    "computeActionSpace (multi-instance, blocked)",
+    "expected-action-space-multi-instance-blocked.json",
+    () =>
+      evaluator.computeActionSpace(facts, multiInstanceStatesApproved, "admin"),
+  );
+
+  // Test 8: executeFlowWithBindings (multi-instance)
+  check(
+    "executeFlowWithBindings (multi-instance)",
+    "expected-flow-result-multi-instance.json",
+    () =>
+      evaluator.executeFlowWithBindings(
+        "approval_flow",
+        facts,
+        multiInstanceStates,
+        "admin",
+        multiInstanceBindings,
+      ),
+  );
+
+  // Test 9: executeFlow (parallel step, all branches succeed)
+  check("executeFlow (parallel)", "expected-flow-result-parallel.json", () =>
+    parallel.executeFlow(
+      "publish_flow",
+      readJson("parallel-facts.json"),
+      {},
+      "editor",
+    ),
+  );
+
+  // Test 10: executeFlow (parallel step, one branch fails)
+  check(
+    "executeFlow (parallel, branch failure)",
+    "expected-flow-result-parallel-branch-failure.json",
+    () =>
+      parallel.executeFlow(
+        "publish_flow",
+        readJson("parallel-facts.json"),
+        readJson("parallel-entity-states-reviewed.json"),
+        "editor",
+      ),
+  );
+
+  // Test 11: executeFlow (compensation path)
+  check(
+    "executeFlow (compensate)",
+    "expected-flow-result-compensate.json",
+    () =>
+      compensate.executeFlow(
+        "standard_release",
+        readJson("compensate-facts.json"),
+        {},
+        "seller",
+      ),
   );
-  if (jsonEqual(flowResult, expectedFlow)) {
-    console.log("PASS: executeFlow");
-    passed++;
-  } else {
-    console.log("FAIL: executeFlow");
-    console.log("  expected:", JSON.stringify(sortKeys(expectedFlow)));
-    console.log("  actual:  ", JSON.stringify(sortKeys(flowResult)));
-    failed++;
-  }
 
   evaluator.free();
+  parallel.free();
+  compensate.free();
 
   console.log(`\nTypeScript SDK: ${passed} passed, ${failed} failed`);
   process.exit(failed > 0 ? 1 : 0);
 }
 
+function readJson(name: string) {
+  return JSON.parse(readFileSync(`${fixturesDir}/${name}`, "utf-8"));
+}
+
+function check(name: string, expectedFile: string, run: () => unknown) {
+  let actual: unknown;
+  try {
+    actual = run();
+  } catch (e) {
+    console.log(`FAIL: ${name} — error: ${e}`);
+    failed++;
+    return;
+  }
+  const expected = readJson(expectedFile);
+  if (jsonEqual(actual, expected)) {
+    console.log(`PASS: ${name}`);
+    passed++;
+  } else {
+    console.log(`FAIL: ${name}`);
+    console.log("  expected:", JSON.stringify(sortKeys(expected)));
+    console.log("  actual:  ", JSON.stringify(sortKeys(actual)));
+    failed++;
+  }
+}
+
 function jsonEqual(a: unknown, b: unknown): boolean {
   return JSON.stringify(sortKeys(a)) === JSON.stringify(sortKeys(b));
 }
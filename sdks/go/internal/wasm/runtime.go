@@ -257,6 +257,42 @@ func (rt *Runtime) CallHandleFourArgs(
 	return rt.readResult()
 }
 
+// CallU32 calls a WASM function that takes no arguments and returns a u32
+// directly, without using the result buffer.
+func (rt *Runtime) CallU32(funcName string) (uint32, error) {
+	rt.mu.Lock()
+	defer rt.mu.Unlock()
+
+	fn := rt.module.ExportedFunction(funcName)
+	if fn == nil {
+		return 0, fmt.Errorf("WASM function %q not found", funcName)
+	}
+
+	results, err := fn.Call(rt.ctx)
+	if err != nil {
+		return 0, fmt.Errorf("WASM call %q failed: %w", funcName, err)
+	}
+	return uint32(results[0]), nil
+}
+
+// CallNoArgs calls a WASM function that takes no arguments and writes its
+// result to the result buffer.
+func (rt *Runtime) CallNoArgs(funcName string) (string, error) {
+	rt.mu.Lock()
+	defer rt.mu.Unlock()
+
+	fn := rt.module.ExportedFunction(funcName)
+	if fn == nil {
+		return "", fmt.Errorf("WASM function %q not found", funcName)
+	}
+
+	if _, err := fn.Call(rt.ctx); err != nil {
+		return "", fmt.Errorf("WASM call %q failed: %w", funcName, err)
+	}
+
+	return rt.readResult()
+}
+
 // Close releases all WASM runtime resources.
 func (rt *Runtime) Close() error {
 	return rt.runtime.Close(rt.ctx)
@@ -39,8 +39,15 @@ import (
 )
 
 // Evaluator wraps the Tenor contract evaluator running in a WASM module.
-// It is safe to call multiple methods concurrently; the underlying WASM
-// runtime serialises calls with a mutex.
+// It is safe to call multiple methods concurrently, and to share one
+// Evaluator across goroutines; the underlying WASM runtime serialises calls
+// with a mutex.
+//
+// Each Evaluator owns its own WASM module instance, and a contract handle is
+// only meaningful inside the instance that loaded it. Share the Evaluator
+// itself between goroutines rather than its handle. Because calls are
+// serialised, goroutines that need evaluation to run in parallel should use
+// one Evaluator each, for example from a pool.
 //
 // Close() must be called when the Evaluator is no longer needed.
 type Evaluator struct {
@@ -51,20 +58,33 @@ type Evaluator struct {
 // NewEvaluatorFromBundle creates a new Evaluator from an interchange bundle
 // JSON byte slice. The bundle must be a valid Tenor interchange bundle.
 //
-// Each call creates a new isolated WASM runtime instance. For applications
-// that evaluate many contracts concurrently, create one Evaluator per goroutine
-// or use a pool.
+// Each call creates a new isolated WASM runtime instance with its own
+// handle space. For applications that evaluate many contracts in parallel,
+// create one Evaluator per goroutine or use a pool.
 func NewEvaluatorFromBundle(bundleJSON []byte) (*Evaluator, error) {
+	return newEvaluator("load_contract", bundleJSON)
+}
+
+// NewEvaluatorFromCBORBundle creates a new Evaluator from a CBOR-encoded
+// interchange bundle, as produced by tenor-interchange's to_cbor. Decoding
+// CBOR is considerably faster than parsing JSON for large bundles.
+func NewEvaluatorFromCBORBundle(bundleCBOR []byte) (*Evaluator, error) {
+	return newEvaluator("load_contract_cbor", bundleCBOR)
+}
+
+// newEvaluator creates a WASM runtime and loads a bundle into it with the
+// given load export.
+func newEvaluator(loadFunc string, bundle []byte) (*Evaluator, error) {
 	ctx := context.Background()
 	rt, err := wasm.NewRuntime(ctx)
 	if err != nil {
 		return nil, fmt.Errorf("failed to create WASM runtime: %w", err)
 	}
 
-	result, err := rt.CallOneArg("load_contract", string(bundleJSON))
+	result, err := rt.CallOneArg(loadFunc, string(bundle))
 	if err != nil {
 		_ = rt.Close()
-		return nil, fmt.Errorf("failed to call load_contract: %w", err)
+		return nil, fmt.Errorf("failed to call %s: %w", loadFunc, err)
 	}
 
 	var loadResult struct {
@@ -73,7 +93,7 @@ func NewEvaluatorFromBundle(bundleJSON []byte) (*Evaluator, error) {
 	}
 	if err := json.Unmarshal([]byte(result), &loadResult); err != nil {
 		_ = rt.Close()
-		return nil, fmt.Errorf("failed to parse load_contract result: %w", err)
+		return nil, fmt.Errorf("failed to parse %s result: %w", loadFunc, err)
 	}
 	if loadResult.Error != nil {
 		_ = rt.Close()
@@ -81,7 +101,7 @@ func NewEvaluatorFromBundle(bundleJSON []byte) (*Evaluator, error) {
 	}
 	if loadResult.Handle == nil {
 		_ = rt.Close()
-		return nil, fmt.Errorf("load_contract returned neither handle nor error")
+		return nil, fmt.Errorf("%s returned neither handle nor error", loadFunc)
 	}
 
 	return &Evaluator{
@@ -115,6 +135,49 @@ func (e *Evaluator) Evaluate(facts FactSet) (*VerdictSet, error) {
 	return &verdicts, nil
 }
 
+// EvaluateBatch runs stratified rule evaluation against each fact set in a
+// single WASM call, returning one VerdictSet per fact set in input order.
+// Batching amortizes the per-call marshalling overhead, which dominates for
+// small contracts. If any fact set fails to evaluate, the error names its
+// index and no results are returned.
+func (e *Evaluator) EvaluateBatch(batch []FactSet) ([]*VerdictSet, error) {
+	batchJSON, err := json.Marshal(batch)
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal facts batch: %w", err)
+	}
+
+	result, err := e.runtime.CallHandleOneArg("evaluate_batch", e.handle, string(batchJSON))
+	if err != nil {
+		return nil, fmt.Errorf("evaluate_batch WASM call failed: %w", err)
+	}
+
+	if errMsg := extractError(result); errMsg != "" {
+		return nil, fmt.Errorf("evaluation error: %s", errMsg)
+	}
+
+	var entries []json.RawMessage
+	if err := json.Unmarshal([]byte(result), &entries); err != nil {
+		return nil, fmt.Errorf("failed to parse batch result: %w", err)
+	}
+	if len(entries) != len(batch) {
+		return nil, fmt.Errorf("batch result has %d entries for %d fact sets", len(entries), len(batch))
+	}
+
+	verdictSets := make([]*VerdictSet, len(entries))
+	for i, entry := range entries {
+		if errMsg := extractError(string(entry)); errMsg != "" {
+			return nil, fmt.Errorf("evaluation error in fact set %d: %s", i, errMsg)
+		}
+		var verdicts VerdictSet
+		if err := json.Unmarshal(entry, &verdicts); err != nil {
+			return nil, fmt.Errorf("failed to parse VerdictSet %d: %w", i, err)
+		}
+		verdictSets[i] = &verdicts
+	}
+
+	return verdictSets, nil
+}
+
 // ComputeActionSpace computes the set of available and blocked actions for a
 // persona given the current facts and entity states.
 //
@@ -305,6 +368,56 @@ func (e *Evaluator) ExecuteFlowWithBindings(
 	return &flowResult, nil
 }
 
+// ContractCount reports how many contracts are loaded in this Evaluator's
+// WASM module instance. It is intended for diagnostics, such as checking
+// that contracts are not leaking.
+func (e *Evaluator) ContractCount() (uint32, error) {
+	count, err := e.runtime.CallU32("contract_count")
+	if err != nil {
+		return 0, fmt.Errorf("contract_count WASM call failed: %w", err)
+	}
+	return count, nil
+}
+
+// ResultCapacity reports how many bytes the WASM module's result buffer
+// currently reserves. The buffer keeps its largest size between calls, so a
+// single huge result inflates it until ShrinkResult is called.
+func (e *Evaluator) ResultCapacity() (uint32, error) {
+	capacity, err := e.runtime.CallU32("result_capacity")
+	if err != nil {
+		return 0, fmt.Errorf("result_capacity WASM call failed: %w", err)
+	}
+	return capacity, nil
+}
+
+// ShrinkResult releases the memory held by the WASM module's result buffer
+// and reports how many bytes were released. Long-running processes can call
+// it after an unusually large result, or whenever ResultCapacity exceeds a
+// bound.
+func (e *Evaluator) ShrinkResult() (uint32, error) {
+	released, err := e.runtime.CallU32("shrink_result")
+	if err != nil {
+		return 0, fmt.Errorf("shrink_result WASM call failed: %w", err)
+	}
+	return released, nil
+}
+
+// MemoryStats reports the memory usage of this Evaluator's WASM module
+// instance, for monitoring long-running processes.
+func (e *Evaluator) MemoryStats() (*MemoryStats, error) {
+	result, err := e.runtime.CallNoArgs("memory_stats")
+	if err != nil {
+		return nil, fmt.Errorf("memory_stats WASM call failed: %w", err)
+	}
+
+	var stats MemoryStats
+	if err := json.Unmarshal([]byte(result), &stats); err != nil {
+		return nil, fmt.Errorf("failed to parse MemoryStats: %w", err)
+	}
+
+	return &stats, nil
+}
+
 // Close releases all resources held by the Evaluator, including the WASM runtime.
 // It should be called via defer after creating an Evaluator.
 func (e *Evaluator) Close() error {
@@ -1,6 +1,9 @@
 package tenor_test
 
 import (
+	"fmt"
+	"strings"
+	"sync"
 	"testing"
 
 	tenor "github.com/riverline-labs/tenor-go"
@@ -111,6 +114,20 @@ func TestLoadInvalidBundle(t *testing.T) {
 	}
 }
 
+func TestLoadCBORBundle(t *testing.T) {
+	_, err := tenor.NewEvaluatorFromCBORBundle([]byte{0xff, 0x00})
+	if err == nil || !strings.Contains(err.Error(), "invalid CBOR") {
+		t.Fatalf("expected invalid CBOR error, got: %v", err)
+	}
+
+	// {"not": "a bundle"} encoded as CBOR: decodes, then fails validation.
+	notABundle := []byte{0xa1, 0x63, 'n', 'o', 't', 0x68, 'a', ' ', 'b', 'u', 'n', 'd', 'l', 'e'}
+	_, err = tenor.NewEvaluatorFromCBORBundle(notABundle)
+	if err == nil || !strings.Contains(err.Error(), "invalid contract") {
+		t.Fatalf("expected invalid contract error, got: %v", err)
+	}
+}
+
 // ── Evaluate ──
 
 func TestEvaluate(t *testing.T) {
@@ -178,6 +195,131 @@ func TestEvaluateMissingRequiredFact(t *testing.T) {
 	}
 }
 
+func TestEvaluateBatch(t *testing.T) {
+	eval, err := tenor.NewEvaluatorFromBundle([]byte(basicBundle))
+	if err != nil {
+		t.Fatalf("failed to load: %v", err)
+	}
+	defer eval.Close()
+
+	results, err := eval.EvaluateBatch([]tenor.FactSet{
+		{"is_active": true},
+		{"is_active": false},
+	})
+	if err != nil {
+		t.Fatalf("EvaluateBatch failed: %v", err)
+	}
+	if len(results) != 2 {
+		t.Fatalf("expected 2 verdict sets, got %d", len(results))
+	}
+	if len(results[0].Verdicts) != 1 || results[0].Verdicts[0].Type != "account_active" {
+		t.Errorf("expected account_active verdict for first fact set, got %+v", results[0].Verdicts)
+	}
+	if len(results[1].Verdicts) != 0 {
+		t.Errorf("expected 0 verdicts for second fact set, got %d", len(results[1].Verdicts))
+	}
+}
+
+func TestEvaluateBatchReportsFailingIndex(t *testing.T) {
+	eval, err := tenor.NewEvaluatorFromBundle([]byte(basicBundle))
+	if err != nil {
+		t.Fatalf("failed to load: %v", err)
+	}
+	defer eval.Close()
+
+	_, err = eval.EvaluateBatch([]tenor.FactSet{{"is_active": true}, {}})
+	if err == nil {
+		t.Fatal("expected error for missing required fact, got nil")
+	}
+	if !strings.Contains(err.Error(), "fact set 1") {
+		t.Errorf("expected error to name fact set 1, got %v", err)
+	}
+}
+
+func TestEvaluatorSharedAcrossGoroutines(t *testing.T) {
+	eval, err := tenor.NewEvaluatorFromBundle([]byte(basicBundle))
+	if err != nil {
+		t.Fatalf("failed to load: %v", err)
+	}
+	defer eval.Close()
+
+	var wg sync.WaitGroup
+	errs := make(chan error, 8)
+	for i := 0; i < 8; i++ {
+		wg.Add(1)
+		go func(active bool) {
+			defer wg.Done()
+			result, err := eval.Evaluate(tenor.FactSet{"is_active": active})
+			if err != nil {
+				errs <- err
+				return
+			}
+			want := 0
+			if active {
+				want = 1
+			}
+			if len(result.Verdicts) != want {
+				errs <- fmt.Errorf("is_active=%v: expected %d verdicts, got %d", active, want, len(result.Verdicts))
+			}
+		}(i%2 == 0)
+	}
+	wg.Wait()
+	close(errs)
+	for err := range errs {
+		t.Error(err)
+	}
+
+	count, err := eval.ContractCount()
+	if err != nil {
+		t.Fatalf("ContractCount failed: %v", err)
+	}
+	if count != 1 {
+		t.Errorf("expected 1 loaded contract, got %d", count)
+	}
+}
+
+func TestShrinkResultReleasesBuffer(t *testing.T) {
+	eval, err := tenor.NewEvaluatorFromBundle([]byte(basicBundle))
+	if err != nil {
+		t.Fatalf("failed to load: %v", err)
+	}
+	defer eval.Close()
+
+	if _, err := eval.Evaluate(tenor.FactSet{"is_active": true}); err != nil {
+		t.Fatalf("Evaluate failed: %v", err)
+	}
+	before, err := eval.ResultCapacity()
+	if err != nil {
+		t.Fatalf("ResultCapacity failed: %v", err)
+	}
+	if before == 0 {
+		t.Fatal("expected a non-empty result buffer after Evaluate")
+	}
+
+	released, err := eval.ShrinkResult()
+	if err != nil {
+		t.Fatalf("ShrinkResult failed: %v", err)
+	}
+	if released != before {
+		t.Errorf("expected %d bytes released, got %d", before, released)
+	}
+	after, err := eval.ResultCapacity()
+	if err != nil {
+		t.Fatalf("ResultCapacity failed: %v", err)
+	}
+	if after != 0 {
+		t.Errorf("expected an empty result buffer after ShrinkResult, got %d bytes", after)
+	}
+
+	stats, err := eval.MemoryStats()
+	if err != nil {
+		t.Fatalf("MemoryStats failed: %v", err)
+	}
+	if stats.Contracts != 1 || stats.ResultLen != 0 || stats.ResultCapacity != 0 {
+		t.Errorf("unexpected memory stats: %+v", *stats)
+	}
+}
+
 // ── ComputeActionSpace ──
 
 func TestComputeActionSpaceAvailable(t *testing.T) {
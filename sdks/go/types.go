@@ -66,12 +66,22 @@ type Action struct {
 // The Type field contains one of: PersonaNotAuthorized, PreconditionNotMet,
 // EntityNotInSourceState, MissingFacts.
 type BlockedReason struct {
-	Type            string   `json:"type"`
-	MissingVerdicts []string `json:"missing_verdicts,omitempty"`
-	EntityID        string   `json:"entity_id,omitempty"`
-	CurrentState    string   `json:"current_state,omitempty"`
-	RequiredState   string   `json:"required_state,omitempty"`
-	FactIDs         []string `json:"fact_ids,omitempty"`
+	Type            string         `json:"type"`
+	AllowedPersonas []string       `json:"allowed_personas,omitempty"`
+	MissingVerdicts []string       `json:"missing_verdicts,omitempty"`
+	FailedClauses   []FailedClause `json:"failed_clauses,omitempty"`
+	EntityID        string         `json:"entity_id,omitempty"`
+	CurrentState    string         `json:"current_state,omitempty"`
+	RequiredState   string         `json:"required_state,omitempty"`
+	ExpectedStates  []string       `json:"expected_states,omitempty"`
+	FactIDs         []string       `json:"fact_ids,omitempty"`
+}
+
+// FailedClause is a top-level precondition or guard clause that evaluated to false.
+type FailedClause struct {
+	Clause          string                 `json:"clause"`
+	Facts           map[string]interface{} `json:"facts"`
+	MissingVerdicts []string               `json:"missing_verdicts"`
 }
 
 // BlockedAction represents an action that exists but cannot currently be executed.
@@ -116,3 +126,12 @@ type FlowResult struct {
 	Verdicts         []Verdict           `json:"verdicts"`
 	InstanceBindings InstanceBindings    `json:"instance_bindings"`
 }
+
+// MemoryStats describes the memory usage of an Evaluator's WASM module
+// instance. Result buffer sizes are in bytes, measured before the stats
+// themselves were written.
+type MemoryStats struct {
+	Contracts      uint32 `json:"contracts"`
+	ResultLen      uint32 `json:"result_len"`
+	ResultCapacity uint32 `json:"result_capacity"`
+}
@@ -13,10 +13,19 @@
 //!
 //! Functions that take a contract handle receive it as the first `u32` argument,
 //! followed by string arguments as `(ptr, len)` pairs.
+//!
+//! # Handles
+//!
+//! Loaded contracts live in one lock-protected table per module instance, so
+//! a handle is valid on any thread of the instance that issued it. Handles are
+//! not shared between module instances: each instance has its own linear
+//! memory and its own table. The result buffer is per-thread, so a caller
+//! reads the result of its own last call.
 
 use slab::Slab;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use tenor_eval::Contract;
 
 struct StoredContract {
@@ -26,11 +35,19 @@ struct StoredContract {
     bundle: serde_json::Value,
 }
 
+// Entries are shared so an evaluation can run after the lock is released:
+// a long evaluation then blocks neither loads, frees nor other evaluations.
+static CONTRACTS: Mutex<Slab<Arc<StoredContract>>> = Mutex::new(Slab::new());
+
 thread_local! {
-    static CONTRACTS: RefCell<Slab<StoredContract>> = RefCell::new(Slab::new());
     static RESULT_BUF: RefCell<Vec<u8>> = RefCell::new(Vec::new());
 }
 
+fn contracts() -> MutexGuard<'static, Slab<Arc<StoredContract>>> {
+    // A panic while the lock was held cannot leave the slab half-updated
+    CONTRACTS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 fn set_result(s: &str) {
     RESULT_BUF.with(|buf| {
         let mut buf = buf.borrow_mut();
@@ -47,14 +64,13 @@ fn with_contract<F>(handle: u32, f: F)
 where
     F: FnOnce(&StoredContract) -> String,
 {
-    let result = CONTRACTS.with(|contracts| {
-        let contracts = contracts.borrow();
-        match contracts.get(handle as usize) {
-            Some(stored) => f(stored),
-            None => serde_json::json!({ "error": format!("invalid contract handle: {}", handle) })
-                .to_string(),
-        }
-    });
+    // Clone the entry and drop the guard before `f` runs
+    let stored = contracts().get(handle as usize).cloned();
+    let result = match stored {
+        Some(stored) => f(&stored),
+        None => serde_json::json!({ "error": format!("invalid contract handle: {}", handle) })
+            .to_string(),
+    };
     set_result(&result);
 }
 
@@ -62,9 +78,7 @@ where
 ///
 /// Old format (flat):  `{ "Order": "pending" }`
 /// New format (nested): `{ "Order": { "ord-001": "pending" } }`
-fn parse_entity_states(
-    json: &serde_json::Value,
-) -> Result<tenor_eval::EntityStateMap, String> {
+fn parse_entity_states(json: &serde_json::Value) -> Result<tenor_eval::EntityStateMap, String> {
     let obj = match json.as_object() {
         Some(o) => o,
         None => return Err("entity_states must be a JSON object".to_string()),
@@ -75,11 +89,8 @@ fn parse_entity_states(
     for (entity_id, value) in obj {
         if let Some(state_str) = value.as_str() {
             entity_states.insert(
-                (
-                    entity_id.clone(),
-                    tenor_eval::DEFAULT_INSTANCE_ID.to_string(),
-                ),
-                state_str.to_string(),
+                tenor_eval::instance_key(entity_id, tenor_eval::DEFAULT_INSTANCE_ID),
+                state_str.into(),
             );
         } else if let Some(instance_map) = value.as_object() {
             for (instance_id, state_val) in instance_map {
@@ -93,8 +104,8 @@ fn parse_entity_states(
                     }
                 };
                 entity_states.insert(
-                    (entity_id.clone(), instance_id.clone()),
-                    state_str.to_string(),
+                    tenor_eval::instance_key(entity_id, instance_id),
+                    state_str.into(),
                 );
             }
         } else {
@@ -173,6 +184,47 @@ pub extern "C" fn get_result_len() -> u32 {
     RESULT_BUF.with(|buf| buf.borrow().len() as u32)
 }
 
+/// Bytes currently reserved by this thread's result buffer.
+///
+/// The buffer keeps its largest size between calls, so one huge result
+/// inflates it until `shrink_result` is called. Returned directly, without
+/// touching the result buffer.
+#[no_mangle]
+pub extern "C" fn result_capacity() -> u32 {
+    RESULT_BUF.with(|buf| buf.borrow().capacity() as u32)
+}
+
+/// Empty this thread's result buffer and release its memory to the
+/// allocator. Returns the number of bytes released.
+///
+/// Invalidates the pointer returned by `get_result_ptr`, so call it only
+/// after the previous result has been read.
+#[no_mangle]
+pub extern "C" fn shrink_result() -> u32 {
+    RESULT_BUF.with(|buf| std::mem::take(&mut *buf.borrow_mut()).capacity() as u32)
+}
+
+/// Memory usage of this module instance, for monitoring long-lived
+/// embedders.
+///
+/// Result: `{"contracts": N, "result_len": N, "result_capacity": N}`,
+/// measured before the stats themselves are written to the result buffer.
+#[no_mangle]
+pub extern "C" fn memory_stats() {
+    let (result_len, result_capacity) = RESULT_BUF.with(|buf| {
+        let buf = buf.borrow();
+        (buf.len(), buf.capacity())
+    });
+    set_result(
+        &serde_json::json!({
+            "contracts": contracts().len(),
+            "result_len": result_len,
+            "result_capacity": result_capacity,
+        })
+        .to_string(),
+    );
+}
+
 // ── Contract management exports ──
 
 /// Load a contract from interchange bundle JSON.
@@ -189,10 +241,31 @@ pub unsafe extern "C" fn load_contract(ptr: *const u8, len: u32) {
         }
     };
 
-    let bundle: serde_json::Value = match serde_json::from_str(json_str) {
-        Ok(v) => v,
+    match serde_json::from_str(json_str) {
+        Ok(bundle) => store_contract(bundle),
+        Err(e) => error_result(&format!("invalid JSON: {}", e)),
+    }
+}
+
+/// Load a contract from a CBOR-encoded interchange bundle.
+///
+/// Input:  CBOR bytes at `ptr[0..len]`
+/// Result: `{"handle": N}` or `{"error": "..."}`
+#[no_mangle]
+pub unsafe extern "C" fn load_contract_cbor(ptr: *const u8, len: u32) {
+    let bytes = std::slice::from_raw_parts(ptr, len as usize);
+    match tenor_interchange::from_cbor(bytes) {
+        Ok(bundle) => store_contract(bundle),
+        Err(e) => error_result(&e.to_string()),
+    }
+}
+
+fn store_contract(bundle: serde_json::Value) {
+    // Store the plain form so inspection never sees type_refs
+    let bundle = match tenor_interchange::into_inlined(bundle) {
+        Ok(b) => b,
         Err(e) => {
-            error_result(&format!("invalid JSON: {}", e));
+            error_result(&format!("invalid contract: {}", e));
             return;
         }
     };
@@ -205,11 +278,7 @@ pub unsafe extern "C" fn load_contract(ptr: *const u8, len: u32) {
         }
     };
 
-    let handle = CONTRACTS.with(|contracts| {
-        contracts
-            .borrow_mut()
-            .insert(StoredContract { contract, bundle })
-    });
+    let handle = contracts().insert(Arc::new(StoredContract { contract, bundle }));
 
     set_result(&serde_json::json!({ "handle": handle }).to_string());
 }
@@ -219,15 +288,21 @@ pub unsafe extern "C" fn load_contract(ptr: *const u8, len: u32) {
 /// No-op if the handle is invalid.
 #[no_mangle]
 pub extern "C" fn free_contract(handle: u32) {
-    CONTRACTS.with(|contracts| {
-        let mut contracts = contracts.borrow_mut();
-        if contracts.contains(handle as usize) {
-            contracts.remove(handle as usize);
-        }
-    });
+    let mut contracts = contracts();
+    if contracts.contains(handle as usize) {
+        contracts.remove(handle as usize);
+    }
     set_result("{}");
 }
 
+/// Number of contracts currently loaded in this module instance.
+///
+/// Diagnostic only: returned directly, without touching the result buffer.
+#[no_mangle]
+pub extern "C" fn contract_count() -> u32 {
+    contracts().len() as u32
+}
+
 // ── Evaluation exports ──
 
 /// Evaluate rules against facts.
@@ -252,28 +327,56 @@ pub unsafe extern "C" fn evaluate(handle: u32, ptr: *const u8, len: u32) {
         }
     };
 
-    with_contract(handle, |stored| {
-        let fact_set =
-            match tenor_eval::assemble::assemble_facts(&stored.contract, &facts) {
-                Ok(fs) => fs,
-                Err(e) => {
-                    return serde_json::json!({ "error": format!("fact assembly error: {}", e) })
-                        .to_string()
-                }
-            };
+    with_contract(handle, |stored| evaluate_facts(stored, &facts).to_string());
+}
 
-        let verdict_set = match tenor_eval::rules::eval_strata(&stored.contract, &fact_set) {
-            Ok(vs) => vs,
-            Err(e) => {
-                return serde_json::json!({ "error": format!("evaluation error: {}", e) })
-                    .to_string()
-            }
-        };
+/// Evaluate rules against each fact document in a JSON array, in one call.
+///
+/// Args:   handle, batch_ptr, batch_len
+/// Result: JSON array with one entry per fact document -- its VerdictSet, or
+///         `{"error": "..."}` if that document failed -- or a top-level
+///         `{"error": "..."}` for a malformed batch or invalid handle
+#[no_mangle]
+pub unsafe extern "C" fn evaluate_batch(handle: u32, ptr: *const u8, len: u32) {
+    let batch_str = match std::str::from_utf8(std::slice::from_raw_parts(ptr, len as usize)) {
+        Ok(s) => s,
+        Err(e) => {
+            error_result(&format!("invalid UTF-8 in facts batch: {}", e));
+            return;
+        }
+    };
+
+    let batch: Vec<serde_json::Value> = match serde_json::from_str(batch_str) {
+        Ok(v) => v,
+        Err(e) => {
+            error_result(&format!("invalid facts batch JSON: {}", e));
+            return;
+        }
+    };
 
-        verdict_set.to_json().to_string()
+    with_contract(handle, |stored| {
+        let results: Vec<serde_json::Value> = batch
+            .iter()
+            .map(|facts| evaluate_facts(stored, facts))
+            .collect();
+        serde_json::Value::Array(results).to_string()
     });
 }
 
+/// Evaluate rules against one fact document: the VerdictSet JSON, or an
+/// `{"error": "..."}` object.
+fn evaluate_facts(stored: &StoredContract, facts: &serde_json::Value) -> serde_json::Value {
+    let fact_set = match tenor_eval::assemble::assemble_facts(&stored.contract, facts) {
+        Ok(fs) => fs,
+        Err(e) => return serde_json::json!({ "error": format!("fact assembly error: {}", e) }),
+    };
+
+    match tenor_eval::rules::eval_strata(&stored.contract, &fact_set) {
+        Ok(vs) => vs.to_json(),
+        Err(e) => serde_json::json!({ "error": format!("evaluation error: {}", e) }),
+    }
+}
+
 /// Compute the action space for a persona.
 ///
 /// Args:   handle, facts_ptr, facts_len, entity_states_ptr, entity_states_len, persona_ptr, persona_len
@@ -306,14 +409,16 @@ pub unsafe extern "C" fn compute_action_space(
             }
         };
 
-    let persona_str =
-        match std::str::from_utf8(std::slice::from_raw_parts(persona_ptr, persona_len as usize)) {
-            Ok(s) => s,
-            Err(e) => {
-                error_result(&format!("invalid UTF-8 in persona: {}", e));
-                return;
-            }
-        };
+    let persona_str = match std::str::from_utf8(std::slice::from_raw_parts(
+        persona_ptr,
+        persona_len as usize,
+    )) {
+        Ok(s) => s,
+        Err(e) => {
+            error_result(&format!("invalid UTF-8 in persona: {}", e));
+            return;
+        }
+    };
 
     let facts: serde_json::Value = match serde_json::from_str(facts_str) {
         Ok(v) => v,
@@ -350,10 +455,8 @@ pub unsafe extern "C" fn compute_action_space(
         match result {
             Ok(action_space) => match serde_json::to_string(&action_space) {
                 Ok(json) => json,
-                Err(e) => {
-                    serde_json::json!({ "error": format!("serialization error: {}", e) })
-                        .to_string()
-                }
+                Err(e) => serde_json::json!({ "error": format!("serialization error: {}", e) })
+                    .to_string(),
             },
             Err(e) => {
                 serde_json::json!({ "error": format!("action space error: {}", e) }).to_string()
@@ -521,8 +624,7 @@ unsafe fn simulate_flow_with_bindings_impl(
             verdicts: verdict_set.clone(),
         };
 
-        let mut merged_entity_states =
-            tenor_eval::operation::init_entity_states(&stored.contract);
+        let mut merged_entity_states = tenor_eval::operation::init_entity_states(&stored.contract);
         for (key, state) in entity_states {
             merged_entity_states.insert(key, state);
         }
@@ -540,8 +642,9 @@ unsafe fn simulate_flow_with_bindings_impl(
             &stored.contract,
             &snapshot,
             &mut merged_entity_states,
+            &mut tenor_eval::EntityAttributeMap::new(),
             &instance_bindings,
-            None,
+            tenor_eval::FlowOptions::default(),
         ) {
             Ok(r) => r,
             Err(e) => {
@@ -560,9 +663,8 @@ unsafe fn simulate_flow_with_bindings_impl(
                     "result": s.result,
                 });
                 if !s.instance_bindings.is_empty() {
-                    step_json["instance_bindings"] =
-                        serde_json::to_value(&s.instance_bindings)
-                            .unwrap_or(serde_json::Value::Null);
+                    step_json["instance_bindings"] = serde_json::to_value(&s.instance_bindings)
+                        .unwrap_or(serde_json::Value::Null);
                 }
                 step_json
             })
@@ -594,3 +696,40 @@ unsafe fn simulate_flow_with_bindings_impl(
         .to_string()
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_result() -> serde_json::Value {
+        RESULT_BUF.with(|buf| serde_json::from_slice(&buf.borrow()).unwrap())
+    }
+
+    #[test]
+    fn evaluation_runs_without_holding_the_contract_table() {
+        store_contract(serde_json::json!({
+            "constructs": [],
+            "id": "empty",
+            "kind": "Bundle",
+            "tenor": "1.0",
+            "tenor_version": "1.0.0"
+        }));
+        let handle = last_result()["handle"].as_u64().unwrap() as u32;
+
+        // Touching the table from inside an evaluation deadlocked while
+        // `with_contract` held the lock; freeing the handle mid-evaluation
+        // must leave the running evaluation's contract alive.
+        with_contract(handle, |stored| {
+            free_contract(handle);
+            serde_json::json!({
+                "count": contract_count(),
+                "id": stored.bundle["id"],
+            })
+            .to_string()
+        });
+        assert_eq!(
+            last_result(),
+            serde_json::json!({ "count": 0, "id": "empty" })
+        );
+    }
+}
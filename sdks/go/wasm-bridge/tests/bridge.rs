@@ -0,0 +1,224 @@
+//! Drives the bridge's C-ABI exports the way the Go SDK does: input bytes
+//! passed as `(ptr, len)`, results read back through `get_result_ptr` and
+//! `get_result_len`.
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tenor_wasm_bridge::*;
+
+// Uses the entity_operation_basic conformance fixture — it has
+// Fact, Entity, Rule, Operation, and Flow constructs.
+const BASIC_BUNDLE: &str = r#"{
+  "constructs": [
+    {
+      "id": "is_active",
+      "kind": "Fact",
+      "provenance": { "file": "test.tenor", "line": 11 },
+      "source": { "field": "active", "system": "account" },
+      "tenor": "1.0",
+      "type": { "base": "Bool" }
+    },
+    {
+      "id": "Order",
+      "initial": "pending",
+      "kind": "Entity",
+      "provenance": { "file": "test.tenor", "line": 3 },
+      "states": ["pending", "approved"],
+      "tenor": "1.0",
+      "transitions": [{ "from": "pending", "to": "approved" }]
+    },
+    {
+      "body": {
+        "produce": {
+          "payload": { "type": { "base": "Bool" }, "value": true },
+          "verdict_type": "account_active"
+        },
+        "when": {
+          "left": { "fact_ref": "is_active" },
+          "op": "=",
+          "right": { "literal": true, "type": { "base": "Bool" } }
+        }
+      },
+      "id": "check_active",
+      "kind": "Rule",
+      "provenance": { "file": "test.tenor", "line": 16 },
+      "stratum": 0,
+      "tenor": "1.0"
+    },
+    {
+      "allowed_personas": ["admin"],
+      "effects": [{ "entity_id": "Order", "from": "pending", "to": "approved" }],
+      "error_contract": ["precondition_failed"],
+      "id": "approve_order",
+      "kind": "Operation",
+      "precondition": { "verdict_present": "account_active" },
+      "provenance": { "file": "test.tenor", "line": 22 },
+      "tenor": "1.0"
+    },
+    {
+      "entry": "step_approve",
+      "id": "approval_flow",
+      "kind": "Flow",
+      "provenance": { "file": "test.tenor", "line": 29 },
+      "snapshot": "at_initiation",
+      "steps": [
+        {
+          "id": "step_approve",
+          "kind": "OperationStep",
+          "on_failure": { "kind": "Terminate", "outcome": "approval_failed" },
+          "op": "approve_order",
+          "outcomes": {
+            "success": { "kind": "Terminal", "outcome": "order_approved" }
+          },
+          "persona": "admin"
+        }
+      ],
+      "tenor": "1.0"
+    }
+  ],
+  "id": "entity_operation_basic",
+  "kind": "Bundle",
+  "tenor": "1.0",
+  "tenor_version": "1.0.0"
+}"#;
+
+/// Held by every test: the contract table is shared by the test threads, so
+/// counts are only exact while no other test loads or frees.
+static TABLE: Mutex<()> = Mutex::new(());
+
+fn table() -> MutexGuard<'static, ()> {
+    TABLE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The current thread's last result, parsed.
+fn result() -> serde_json::Value {
+    let bytes = unsafe { std::slice::from_raw_parts(get_result_ptr(), get_result_len() as usize) };
+    serde_json::from_str(std::str::from_utf8(bytes).unwrap()).unwrap()
+}
+
+fn load(bundle: &str) -> u32 {
+    unsafe { load_contract(bundle.as_ptr(), bundle.len() as u32) };
+    let loaded = result();
+    loaded["handle"]
+        .as_u64()
+        .unwrap_or_else(|| panic!("expected a handle: {}", loaded)) as u32
+}
+
+fn eval(handle: u32, facts: &str) -> serde_json::Value {
+    unsafe { evaluate(handle, facts.as_ptr(), facts.len() as u32) };
+    result()
+}
+
+fn eval_batch(handle: u32, batch: &str) -> serde_json::Value {
+    unsafe { evaluate_batch(handle, batch.as_ptr(), batch.len() as u32) };
+    result()
+}
+
+#[test]
+fn evaluate_batch_returns_one_result_per_fact_document() {
+    let _table = table();
+    let handle = load(BASIC_BUNDLE);
+
+    let results = eval_batch(handle, r#"[{"is_active": true}, {}, {"is_active": false}]"#);
+    let results = results.as_array().expect("expected results array");
+    assert_eq!(results.len(), 3);
+
+    // Each entry matches what a single evaluate call returns
+    assert_eq!(results[0], eval(handle, r#"{"is_active": true}"#));
+    assert_eq!(results[0]["verdicts"][0]["type"], "account_active");
+    assert!(
+        results[1].get("error").is_some(),
+        "expected error for missing fact: {}",
+        results[1]
+    );
+    assert_eq!(results[2]["verdicts"].as_array().unwrap().len(), 0);
+
+    free_contract(handle);
+}
+
+#[test]
+fn evaluate_batch_rejects_non_array_and_invalid_handle() {
+    let _table = table();
+    let handle = load(BASIC_BUNDLE);
+
+    for parsed in [
+        eval_batch(handle, r#"{"is_active": true}"#),
+        eval_batch(9999, r#"[{"is_active": true}]"#),
+    ] {
+        assert!(
+            parsed.get("error").is_some(),
+            "expected error in: {}",
+            parsed
+        );
+    }
+
+    free_contract(handle);
+}
+
+#[test]
+fn handles_are_valid_on_every_thread() {
+    let _table = table();
+    let before = contract_count();
+    let handle = load(BASIC_BUNDLE);
+    assert_eq!(contract_count(), before + 1);
+
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let active = i % 2 == 0;
+                let verdicts = eval(handle, &format!(r#"{{"is_active": {}}}"#, active));
+                // Each thread reads its own result, whatever the others wrote
+                assert_eq!(
+                    verdicts["verdicts"].as_array().unwrap().len(),
+                    usize::from(active),
+                    "is_active={}: {}",
+                    active,
+                    verdicts
+                );
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // Freed on another thread, the handle is gone for this one too
+    std::thread::spawn(move || free_contract(handle))
+        .join()
+        .unwrap();
+    assert_eq!(contract_count(), before);
+    assert!(eval(handle, r#"{"is_active": true}"#)
+        .get("error")
+        .is_some());
+}
+
+#[test]
+fn shrink_result_releases_what_a_large_result_reserved() {
+    let _table = table();
+    let handle = load(BASIC_BUNDLE);
+
+    let batch = format!("[{}]", vec![r#"{"is_active": true}"#; 200].join(","));
+    eval_batch(handle, &batch);
+    let large = result_capacity();
+    assert!(large >= get_result_len());
+
+    // A small result keeps the buffer at its largest size
+    eval(handle, r#"{"is_active": false}"#);
+    assert!(get_result_len() < large);
+    assert_eq!(result_capacity(), large);
+
+    assert_eq!(shrink_result(), large);
+    assert_eq!(result_capacity(), 0);
+    assert_eq!(get_result_len(), 0);
+
+    memory_stats();
+    assert_eq!(
+        result(),
+        serde_json::json!({
+            "contracts": contract_count(),
+            "result_len": 0,
+            "result_capacity": 0,
+        })
+    );
+
+    free_contract(handle);
+}
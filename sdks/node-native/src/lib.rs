@@ -0,0 +1,178 @@
+//! Tenor contract evaluator as a Node.js native addon.
+//!
+//! Exposes the same evaluator surface as the WASM module behind
+//! `@tenor/sdk` (load, evaluate, simulate, action space, inspect), compiled
+//! into the Node process for server-side use where WASM sandboxing is not
+//! needed. Inputs and results are plain JavaScript values with the same JSON
+//! shapes the WASM functions return; failures throw an `Error` instead of
+//! returning `{"error": ...}`.
+
+use napi::bindgen_prelude::Buffer;
+use napi::{Error, Result};
+use napi_derive::napi;
+use serde_json::Value;
+
+/// A loaded contract. Holds the parsed contract for repeated evaluation.
+#[napi]
+pub struct TenorEvaluator {
+    contract: tenor_eval::Contract,
+    bundle: Value,
+}
+
+fn err(context: &str, e: impl std::fmt::Display) -> Error {
+    Error::from_reason(format!("{}: {}", context, e))
+}
+
+#[napi]
+impl TenorEvaluator {
+    /// Load a contract from interchange JSON text.
+    #[napi(factory)]
+    pub fn from_bundle_json(json: String) -> Result<Self> {
+        let bundle: Value = serde_json::from_str(&json).map_err(|e| err("invalid JSON", e))?;
+        Self::from_bundle(bundle)
+    }
+
+    /// Load a contract from a CBOR-encoded interchange bundle.
+    #[napi(factory)]
+    pub fn from_bundle_cbor(cbor: Buffer) -> Result<Self> {
+        let bundle =
+            tenor_interchange::from_cbor(&cbor).map_err(|e| Error::from_reason(e.to_string()))?;
+        Self::from_bundle(bundle)
+    }
+
+    /// Load a contract from an interchange bundle object.
+    #[napi(factory)]
+    pub fn from_bundle(bundle: Value) -> Result<Self> {
+        // Keep the plain form so inspection never sees type_refs
+        let bundle =
+            tenor_interchange::into_inlined(bundle).map_err(|e| err("invalid contract", e))?;
+        let contract = tenor_eval::Contract::from_interchange(&bundle)
+            .map_err(|e| err("invalid contract", e))?;
+        Ok(TenorEvaluator { contract, bundle })
+    }
+
+    /// Evaluate rules against `facts`. Returns the VerdictSet.
+    #[napi]
+    pub fn evaluate(&self, facts: Value) -> Result<Value> {
+        self.evaluate_facts(&facts)
+    }
+
+    /// Evaluate rules against each fact document in `facts_batch`. Returns
+    /// one VerdictSet or `{"error": ...}` per document, so one failing
+    /// document does not fail the batch.
+    #[napi]
+    pub fn evaluate_batch(&self, facts_batch: Vec<Value>) -> Value {
+        facts_batch
+            .iter()
+            .map(|facts| {
+                self.evaluate_facts(facts)
+                    .unwrap_or_else(|e| serde_json::json!({ "error": e.reason }))
+            })
+            .collect()
+    }
+
+    /// Simulate `flow_id` as `persona_id`. `entity_states` accepts the flat
+    /// and the nested format and is overlaid on the contract's initial
+    /// states; `instance_bindings` maps entity_id to instance_id.
+    #[napi]
+    pub fn simulate_flow(
+        &self,
+        flow_id: String,
+        persona_id: String,
+        facts: Value,
+        entity_states: Value,
+        instance_bindings: Option<Value>,
+    ) -> Result<Value> {
+        let entity_states = tenor_eval::simulate::parse_entity_states(&entity_states)
+            .map_err(|e| err("invalid entity states", e))?;
+        let instance_bindings = match instance_bindings {
+            Some(b) => tenor_eval::simulate::parse_instance_bindings(&b)
+                .map_err(|e| err("invalid instance bindings", e))?,
+            None => Default::default(),
+        };
+
+        let fact_set = tenor_eval::assemble::assemble_facts(&self.contract, &facts)
+            .map_err(|e| err("fact assembly error", e))?;
+        let verdict_set = tenor_eval::rules::eval_strata(&self.contract, &fact_set)
+            .map_err(|e| err("evaluation error", e))?;
+        let snapshot = tenor_eval::Snapshot {
+            facts: fact_set,
+            verdicts: verdict_set.clone(),
+        };
+
+        let mut merged_entity_states = tenor_eval::operation::init_entity_states(&self.contract);
+        merged_entity_states.extend(entity_states);
+
+        let target_flow = self
+            .contract
+            .get_flow(&flow_id)
+            .ok_or_else(|| Error::from_reason(format!("flow '{}' not found", flow_id)))?;
+        let flow_result = tenor_eval::flow::execute_flow(
+            target_flow,
+            &self.contract,
+            &snapshot,
+            &mut merged_entity_states,
+            &mut tenor_eval::EntityAttributeMap::new(),
+            &instance_bindings,
+            tenor_eval::FlowOptions::default(),
+        )
+        .map_err(|e| err("flow execution error", e))?;
+
+        let result = tenor_eval::FlowEvalResult {
+            verdicts: verdict_set,
+            flow_result,
+        };
+        Ok(tenor_eval::simulate::simulation_json(
+            &flow_id,
+            &persona_id,
+            &result,
+            &instance_bindings,
+        ))
+    }
+
+    /// Compute the action space for `persona_id`.
+    #[napi]
+    pub fn compute_action_space(
+        &self,
+        facts: Value,
+        entity_states: Value,
+        persona_id: String,
+    ) -> Result<Value> {
+        let entity_states = tenor_eval::simulate::parse_entity_states(&entity_states)
+            .map_err(|e| err("invalid entity states", e))?;
+        let action_space = tenor_eval::action_space::compute_action_space(
+            &self.contract,
+            &facts,
+            &entity_states,
+            &persona_id,
+        )
+        .map_err(|e| err("action space error", e))?;
+        serde_json::to_value(&action_space).map_err(|e| err("serialization error", e))
+    }
+
+    /// Summarize the contract's constructs, as `tenor inspect` does.
+    #[napi]
+    pub fn inspect(&self) -> Result<Value> {
+        tenor_interchange::build_inspect(&self.bundle).map_err(|e| err("inspect error", e))
+    }
+}
+
+impl TenorEvaluator {
+    fn evaluate_facts(&self, facts: &Value) -> Result<Value> {
+        let fact_set = tenor_eval::assemble::assemble_facts(&self.contract, facts)
+            .map_err(|e| err("fact assembly error", e))?;
+        let verdict_set = tenor_eval::rules::eval_strata(&self.contract, &fact_set)
+            .map_err(|e| err("evaluation error", e))?;
+        Ok(verdict_set.to_json())
+    }
+}
+
+/// The evaluator features this build supports. Contracts whose `requires`
+/// list names a feature outside this set fail to load.
+#[napi]
+pub fn supported_features() -> Vec<String> {
+    tenor_eval::SUPPORTED_FEATURES
+        .iter()
+        .map(|f| f.to_string())
+        .collect()
+}
@@ -0,0 +1,152 @@
+/**
+ * TenorEvaluator native addon tests.
+ *
+ * These tests use the same BASIC_BUNDLE fixture as crates/tenor-eval-wasm/tests/wasm.rs
+ * and check that the addon returns the same shapes as the WASM evaluator.
+ * Run `npm run build:debug` first.
+ */
+
+import { describe, it } from "node:test";
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+
+const require = createRequire(import.meta.url);
+const { TenorEvaluator, supportedFeatures } = require("../index.js");
+
+// ---------------------------------------------------------------------------
+// Fixture: entity_operation_basic contract (same as wasm.rs BASIC_BUNDLE)
+// ---------------------------------------------------------------------------
+
+const BASIC_BUNDLE = {
+  id: "entity_operation_basic",
+  kind: "Bundle",
+  tenor: "1.0",
+  tenor_version: "1.0.0",
+  constructs: [
+    {
+      id: "is_active",
+      kind: "Fact",
+      provenance: { file: "test.tenor", line: 11 },
+      source: { field: "active", system: "account" },
+      tenor: "1.0",
+      type: { base: "Bool" },
+    },
+    {
+      id: "Order",
+      initial: "pending",
+      kind: "Entity",
+      provenance: { file: "test.tenor", line: 3 },
+      states: ["pending", "approved"],
+      tenor: "1.0",
+      transitions: [{ from: "pending", to: "approved" }],
+    },
+    {
+      body: {
+        produce: {
+          payload: { type: { base: "Bool" }, value: true },
+          verdict_type: "account_active",
+        },
+        when: {
+          left: { fact_ref: "is_active" },
+          op: "=",
+          right: { literal: true, type: { base: "Bool" } },
+        },
+      },
+      id: "check_active",
+      kind: "Rule",
+      provenance: { file: "test.tenor", line: 16 },
+      stratum: 0,
+      tenor: "1.0",
+    },
+    {
+      allowed_personas: ["admin"],
+      effects: [{ entity_id: "Order", from: "pending", to: "approved" }],
+      error_contract: ["precondition_failed"],
+      id: "approve_order",
+      kind: "Operation",
+      precondition: { verdict_present: "account_active" },
+      provenance: { file: "test.tenor", line: 22 },
+      tenor: "1.0",
+    },
+    {
+      entry: "step_approve",
+      id: "approval_flow",
+      kind: "Flow",
+      provenance: { file: "test.tenor", line: 29 },
+      snapshot: "at_initiation",
+      steps: [
+        {
+          id: "step_approve",
+          kind: "OperationStep",
+          on_failure: { kind: "Terminate", outcome: "approval_failed" },
+          op: "approve_order",
+          outcomes: {
+            success: { kind: "Terminal", outcome: "order_approved" },
+          },
+          persona: "admin",
+        },
+      ],
+      tenor: "1.0",
+    },
+  ],
+};
+
+describe("TenorEvaluator", () => {
+  it("loads from an object or JSON text", () => {
+    TenorEvaluator.fromBundle(BASIC_BUNDLE);
+    TenorEvaluator.fromBundleJson(JSON.stringify(BASIC_BUNDLE));
+    assert.throws(() => TenorEvaluator.fromBundleJson("not json"), /invalid JSON/);
+  });
+
+  it("evaluates verdicts", () => {
+    const evaluator = TenorEvaluator.fromBundle(BASIC_BUNDLE);
+    const result = evaluator.evaluate({ is_active: true });
+    assert.equal(result.verdicts.length, 1);
+    assert.equal(result.verdicts[0].type, "account_active");
+    assert.equal(result.verdicts[0].provenance.rule, "check_active");
+
+    assert.deepEqual(evaluator.evaluate({ is_active: false }).verdicts, []);
+    assert.throws(() => evaluator.evaluate({}), /fact assembly error/);
+  });
+
+  it("evaluates a batch, reporting failures per document", () => {
+    const evaluator = TenorEvaluator.fromBundle(BASIC_BUNDLE);
+    const results = evaluator.evaluateBatch([{ is_active: true }, {}]);
+    assert.equal(results.length, 2);
+    assert.equal(results[0].verdicts.length, 1);
+    assert.match(results[1].error, /fact assembly error/);
+  });
+
+  it("simulates a flow", () => {
+    const evaluator = TenorEvaluator.fromBundle(BASIC_BUNDLE);
+    const result = evaluator.simulateFlow(
+      "approval_flow",
+      "admin",
+      { is_active: true },
+      { Order: "pending" },
+    );
+    assert.equal(result.simulation, true);
+    assert.equal(result.outcome, "order_approved");
+    assert.equal(result.would_transition[0].to_state, "approved");
+    assert.throws(
+      () => evaluator.simulateFlow("missing", "admin", { is_active: true }, {}),
+      /flow 'missing' not found/,
+    );
+  });
+
+  it("computes the action space", () => {
+    const evaluator = TenorEvaluator.fromBundle(BASIC_BUNDLE);
+    const admin = evaluator.computeActionSpace({ is_active: true }, { Order: "pending" }, "admin");
+    assert.equal(admin.actions[0].flow_id, "approval_flow");
+    const guest = evaluator.computeActionSpace({ is_active: true }, { Order: "pending" }, "guest");
+    assert.equal(guest.blocked_actions[0].reason.type, "PersonaNotAuthorized");
+  });
+
+  it("inspects the contract", () => {
+    const evaluator = TenorEvaluator.fromBundle(BASIC_BUNDLE);
+    const inspect = evaluator.inspect();
+    assert.equal(inspect.facts[0].id, "is_active");
+    assert.equal(inspect.entities[0].initial, "pending");
+    assert.ok(supportedFeatures().includes("systems"));
+  });
+});
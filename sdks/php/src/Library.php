@@ -0,0 +1,98 @@
+<?php
+
+declare(strict_types=1);
+
+namespace Tenor;
+
+use FFI;
+
+/**
+ * Loads libtenor_capi (built from crates/capi) once per process.
+ */
+final class Library
+{
+    /** Version of the C API these declarations match (`TENOR_ABI_VERSION`). */
+    public const ABI_VERSION = 1;
+
+    /** The declarations from crates/capi/include/tenor.h, without the preprocessor lines FFI cannot parse. */
+    private const CDEF = <<<'C'
+        typedef enum TenorStatus {
+            TENOR_OK = 0,
+            TENOR_NULL_ARGUMENT = 1,
+            TENOR_INVALID_UTF8 = 2,
+            TENOR_INVALID_JSON = 3,
+            TENOR_INVALID_CONTRACT = 4,
+            TENOR_EVALUATION = 5,
+            TENOR_FLOW_NOT_FOUND = 6,
+            TENOR_FLOW_EXECUTION = 7,
+            TENOR_PANIC = 8
+        } TenorStatus;
+
+        typedef struct TenorContract TenorContract;
+
+        uint32_t tenor_abi_version(void);
+        const char *tenor_last_error(void);
+        void tenor_string_free(char *s);
+
+        TenorStatus tenor_load_contract(const char *bundle_json, TenorContract **out);
+        TenorStatus tenor_load_contract_cbor(const uint8_t *bytes, size_t len, TenorContract **out);
+        void tenor_contract_free(TenorContract *contract);
+
+        TenorStatus tenor_evaluate(const TenorContract *contract,
+                                   const char *facts_json,
+                                   char **out_json);
+        TenorStatus tenor_simulate_flow(const TenorContract *contract,
+                                        const char *flow_id,
+                                        const char *persona_id,
+                                        const char *facts_json,
+                                        const char *entity_states_json,
+                                        const char *instance_bindings_json,
+                                        char **out_json);
+        TenorStatus tenor_compute_action_space(const TenorContract *contract,
+                                               const char *facts_json,
+                                               const char *entity_states_json,
+                                               const char *persona_id,
+                                               char **out_json);
+        TenorStatus tenor_inspect_contract(const TenorContract *contract, char **out_json);
+        C;
+
+    private static ?FFI $ffi = null;
+
+    /**
+     * The loaded library.
+     *
+     * The library path is, in order: `$path`, the `TENOR_CAPI_LIB`
+     * environment variable, or the platform's library name resolved through
+     * the dynamic loader search path. The path is only used by the first
+     * call in a process.
+     */
+    public static function ffi(?string $path = null): FFI
+    {
+        if (self::$ffi !== null) {
+            return self::$ffi;
+        }
+
+        $path ??= getenv('TENOR_CAPI_LIB') ?: self::defaultName();
+        $ffi = FFI::cdef(self::CDEF, $path);
+
+        $version = $ffi->tenor_abi_version();
+        if ($version !== self::ABI_VERSION) {
+            throw new \RuntimeException(sprintf(
+                'libtenor_capi implements C API version %d, this SDK needs version %d',
+                $version,
+                self::ABI_VERSION,
+            ));
+        }
+
+        return self::$ffi = $ffi;
+    }
+
+    private static function defaultName(): string
+    {
+        return match (PHP_OS_FAMILY) {
+            'Darwin' => 'libtenor_capi.dylib',
+            'Windows' => 'tenor_capi.dll',
+            default => 'libtenor_capi.so',
+        };
+    }
+}
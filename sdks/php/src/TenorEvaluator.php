@@ -0,0 +1,191 @@
+<?php
+
+declare(strict_types=1);
+
+namespace Tenor;
+
+use FFI;
+use FFI\CData;
+
+/**
+ * Tenor contract evaluator.
+ *
+ * Wraps the C API with a PHP-friendly surface. Inputs are PHP arrays;
+ * results are associative arrays with the same shapes the WASM evaluator
+ * returns. Failures throw a {@see TenorException}.
+ */
+final class TenorEvaluator
+{
+    private function __construct(
+        private readonly FFI $ffi,
+        private readonly CData $contract,
+    ) {
+    }
+
+    public function __destruct()
+    {
+        $this->ffi->tenor_contract_free($this->contract);
+    }
+
+    /**
+     * Load a contract from interchange JSON text.
+     *
+     * @param string|null $library Path to libtenor_capi; see {@see Library::ffi()}.
+     */
+    public static function fromBundleJson(string $json, ?string $library = null): self
+    {
+        $ffi = Library::ffi($library);
+        $out = $ffi->new('TenorContract*');
+        self::check($ffi, $ffi->tenor_load_contract($json, FFI::addr($out)));
+
+        return new self($ffi, $out);
+    }
+
+    /**
+     * Load a contract from a decoded interchange bundle.
+     *
+     * @param array<string, mixed> $bundle
+     */
+    public static function fromBundle(array $bundle, ?string $library = null): self
+    {
+        return self::fromBundleJson(self::encode($bundle), $library);
+    }
+
+    /**
+     * Load a contract from a CBOR-encoded interchange bundle.
+     */
+    public static function fromBundleCbor(string $cbor, ?string $library = null): self
+    {
+        $ffi = Library::ffi($library);
+        $len = strlen($cbor);
+        $bytes = $ffi->new('uint8_t[' . max($len, 1) . ']');
+        FFI::memcpy($bytes, $cbor, $len);
+
+        $out = $ffi->new('TenorContract*');
+        self::check($ffi, $ffi->tenor_load_contract_cbor($bytes, $len, FFI::addr($out)));
+
+        return new self($ffi, $out);
+    }
+
+    /**
+     * Evaluate rules against the provided facts.
+     *
+     * @param array<string, mixed> $facts fact_id => value
+     * @return array<string, mixed> the VerdictSet: `["verdicts" => [...]]`
+     */
+    public function evaluate(array $facts): array
+    {
+        $out = $this->ffi->new('char*');
+        $status = $this->ffi->tenor_evaluate($this->contract, self::encode($facts), FFI::addr($out));
+
+        return $this->result($status, $out);
+    }
+
+    /**
+     * Simulate a flow without side effects.
+     *
+     * `$entityStates` is flat (`["Order" => "pending"]`) or nested by
+     * instance (`["Order" => ["ord-001" => "pending"]]`) and is overlaid on
+     * the contract's initial states. `$instanceBindings` maps entity_id to
+     * instance_id.
+     *
+     * @param array<string, mixed> $facts
+     * @param array<string, mixed> $entityStates
+     * @param array<string, string>|null $instanceBindings
+     * @return array<string, mixed> with "flow_id", "persona", "outcome", "path",
+     *     "would_transition", "verdicts" and "instance_bindings"
+     */
+    public function simulateFlow(
+        string $flowId,
+        string $personaId,
+        array $facts,
+        array $entityStates = [],
+        ?array $instanceBindings = null,
+    ): array {
+        $out = $this->ffi->new('char*');
+        $status = $this->ffi->tenor_simulate_flow(
+            $this->contract,
+            $flowId,
+            $personaId,
+            self::encode($facts),
+            self::encode($entityStates),
+            $instanceBindings === null ? null : self::encode($instanceBindings),
+            FFI::addr($out),
+        );
+
+        return $this->result($status, $out);
+    }
+
+    /**
+     * Compute the action space for a persona.
+     *
+     * @param array<string, mixed> $facts
+     * @param array<string, mixed> $entityStates flat or nested, as for {@see simulateFlow()}
+     * @return array<string, mixed> with "persona_id", "actions", "blocked_actions"
+     *     and "current_verdicts"
+     */
+    public function computeActionSpace(array $facts, array $entityStates, string $personaId): array
+    {
+        $out = $this->ffi->new('char*');
+        $status = $this->ffi->tenor_compute_action_space(
+            $this->contract,
+            self::encode($facts),
+            self::encode($entityStates),
+            $personaId,
+            FFI::addr($out),
+        );
+
+        return $this->result($status, $out);
+    }
+
+    /**
+     * Summarize the contract's constructs, as `tenor inspect` does.
+     *
+     * @return array<string, mixed>
+     */
+    public function inspect(): array
+    {
+        $out = $this->ffi->new('char*');
+        $status = $this->ffi->tenor_inspect_contract($this->contract, FFI::addr($out));
+
+        return $this->result($status, $out);
+    }
+
+    /**
+     * Decode and free a JSON result.
+     *
+     * @return array<string, mixed>
+     */
+    private function result(int $status, CData $out): array
+    {
+        self::check($this->ffi, $status);
+        try {
+            return json_decode(FFI::string($out), true, flags: JSON_THROW_ON_ERROR);
+        } finally {
+            $this->ffi->tenor_string_free($out);
+        }
+    }
+
+    private static function check(FFI $ffi, int $status): void
+    {
+        if ($status === 0) {
+            return;
+        }
+        $message = $ffi->tenor_last_error();
+        if ($message instanceof CData) {
+            $message = FFI::string($message);
+        }
+        throw new TenorException($message ?? 'unknown error', $status);
+    }
+
+    /**
+     * Encode an input document. Empty arrays become `{}`, since every
+     * document the C API accepts is a JSON object.
+     *
+     * @param array<mixed> $value
+     */
+    private static function encode(array $value): string
+    {
+        return $value === [] ? '{}' : json_encode($value, JSON_THROW_ON_ERROR | JSON_PRESERVE_ZERO_FRACTION);
+    }
+}
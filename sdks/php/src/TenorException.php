@@ -0,0 +1,23 @@
+<?php
+
+declare(strict_types=1);
+
+namespace Tenor;
+
+/**
+ * A failed evaluator call.
+ *
+ * The exception code is the C API's `TenorStatus`; compare it with the
+ * constants below.
+ */
+final class TenorException extends \RuntimeException
+{
+    public const NULL_ARGUMENT = 1;
+    public const INVALID_UTF8 = 2;
+    public const INVALID_JSON = 3;
+    public const INVALID_CONTRACT = 4;
+    public const EVALUATION = 5;
+    public const FLOW_NOT_FOUND = 6;
+    public const FLOW_EXECUTION = 7;
+    public const PANIC = 8;
+}
@@ -0,0 +1,200 @@
+<?php
+
+declare(strict_types=1);
+
+namespace Tenor\Tests;
+
+use PHPUnit\Framework\TestCase;
+use Tenor\TenorEvaluator;
+use Tenor\TenorException;
+
+/**
+ * Tests for Tenor\TenorEvaluator.
+ *
+ * Mirrors the WASM test suite from crates/tenor-eval-wasm/tests/wasm.rs
+ * using the same BASIC_BUNDLE fixture to prove cross-SDK consistency.
+ * Needs libtenor_capi: build it with `cargo build -p tenor-capi` and point
+ * TENOR_CAPI_LIB at it.
+ */
+final class TenorEvaluatorTest extends TestCase
+{
+    // Same bundle as crates/tenor-eval-wasm/tests/wasm.rs BASIC_BUNDLE
+    private const BASIC_BUNDLE = <<<'JSON'
+        {
+          "constructs": [
+            {
+              "id": "is_active",
+              "kind": "Fact",
+              "provenance": { "file": "test.tenor", "line": 11 },
+              "source": { "field": "active", "system": "account" },
+              "tenor": "1.0",
+              "type": { "base": "Bool" }
+            },
+            {
+              "id": "Order",
+              "initial": "pending",
+              "kind": "Entity",
+              "provenance": { "file": "test.tenor", "line": 3 },
+              "states": ["pending", "approved"],
+              "tenor": "1.0",
+              "transitions": [{ "from": "pending", "to": "approved" }]
+            },
+            {
+              "body": {
+                "produce": {
+                  "payload": { "type": { "base": "Bool" }, "value": true },
+                  "verdict_type": "account_active"
+                },
+                "when": {
+                  "left": { "fact_ref": "is_active" },
+                  "op": "=",
+                  "right": { "literal": true, "type": { "base": "Bool" } }
+                }
+              },
+              "id": "check_active",
+              "kind": "Rule",
+              "provenance": { "file": "test.tenor", "line": 16 },
+              "stratum": 0,
+              "tenor": "1.0"
+            },
+            {
+              "allowed_personas": ["admin"],
+              "effects": [{ "entity_id": "Order", "from": "pending", "to": "approved" }],
+              "error_contract": ["precondition_failed"],
+              "id": "approve_order",
+              "kind": "Operation",
+              "precondition": { "verdict_present": "account_active" },
+              "provenance": { "file": "test.tenor", "line": 22 },
+              "tenor": "1.0"
+            },
+            {
+              "entry": "step_approve",
+              "id": "approval_flow",
+              "kind": "Flow",
+              "provenance": { "file": "test.tenor", "line": 29 },
+              "snapshot": "at_initiation",
+              "steps": [
+                {
+                  "id": "step_approve",
+                  "kind": "OperationStep",
+                  "on_failure": { "kind": "Terminate", "outcome": "approval_failed" },
+                  "op": "approve_order",
+                  "outcomes": {
+                    "success": { "kind": "Terminal", "outcome": "order_approved" }
+                  },
+                  "persona": "admin"
+                }
+              ],
+              "tenor": "1.0"
+            }
+          ],
+          "id": "entity_operation_basic",
+          "kind": "Bundle",
+          "tenor": "1.0",
+          "tenor_version": "1.0.0"
+        }
+        JSON;
+
+    private function evaluator(): TenorEvaluator
+    {
+        return TenorEvaluator::fromBundleJson(self::BASIC_BUNDLE);
+    }
+
+    public function testLoadFromJsonAndArray(): void
+    {
+        $this->assertInstanceOf(TenorEvaluator::class, $this->evaluator());
+        $bundle = json_decode(self::BASIC_BUNDLE, true);
+        $this->assertInstanceOf(TenorEvaluator::class, TenorEvaluator::fromBundle($bundle));
+    }
+
+    public function testLoadInvalidJson(): void
+    {
+        $this->expectException(TenorException::class);
+        $this->expectExceptionCode(TenorException::INVALID_JSON);
+        TenorEvaluator::fromBundleJson('not json');
+    }
+
+    public function testLoadInvalidContract(): void
+    {
+        $this->expectException(TenorException::class);
+        $this->expectExceptionCode(TenorException::INVALID_CONTRACT);
+        TenorEvaluator::fromBundle(['kind' => 'Bundle']);
+    }
+
+    public function testEvaluateProducesVerdicts(): void
+    {
+        $result = $this->evaluator()->evaluate(['is_active' => true]);
+        $this->assertCount(1, $result['verdicts']);
+        $verdict = $result['verdicts'][0];
+        $this->assertSame('account_active', $verdict['type']);
+        $this->assertSame('check_active', $verdict['provenance']['rule']);
+
+        $this->assertSame([], $this->evaluator()->evaluate(['is_active' => false])['verdicts']);
+    }
+
+    public function testEvaluateMissingRequiredFact(): void
+    {
+        $this->expectException(TenorException::class);
+        $this->expectExceptionCode(TenorException::EVALUATION);
+        $this->expectExceptionMessageMatches('/fact assembly error/');
+        $this->evaluator()->evaluate([]);
+    }
+
+    public function testComputeActionSpace(): void
+    {
+        $evaluator = $this->evaluator();
+        $space = $evaluator->computeActionSpace(['is_active' => true], ['Order' => 'pending'], 'admin');
+        $this->assertSame('admin', $space['persona_id']);
+        $this->assertSame('approval_flow', $space['actions'][0]['flow_id']);
+
+        $blocked = $evaluator->computeActionSpace(['is_active' => true], ['Order' => 'pending'], 'guest');
+        $this->assertSame([], $blocked['actions']);
+        $this->assertSame('PersonaNotAuthorized', $blocked['blocked_actions'][0]['reason']['type']);
+    }
+
+    public function testComputeActionSpaceNestedFormat(): void
+    {
+        $space = $this->evaluator()->computeActionSpace(
+            ['is_active' => true],
+            ['Order' => ['ord-001' => 'pending']],
+            'admin',
+        );
+        $this->assertContains('ord-001', $space['actions'][0]['instance_bindings']['Order']);
+    }
+
+    public function testSimulateFlow(): void
+    {
+        $result = $this->evaluator()->simulateFlow('approval_flow', 'admin', ['is_active' => true]);
+        $this->assertTrue($result['simulation']);
+        $this->assertSame('order_approved', $result['outcome']);
+        $this->assertSame('approved', $result['would_transition'][0]['to_state']);
+    }
+
+    public function testSimulateFlowWithInstanceBindings(): void
+    {
+        $result = $this->evaluator()->simulateFlow(
+            'approval_flow',
+            'admin',
+            ['is_active' => true],
+            ['Order' => ['ord-001' => 'pending']],
+            ['Order' => 'ord-001'],
+        );
+        $this->assertSame('ord-001', $result['would_transition'][0]['instance_id']);
+        $this->assertSame(['Order' => 'ord-001'], $result['instance_bindings']);
+    }
+
+    public function testSimulateFlowNotFound(): void
+    {
+        $this->expectException(TenorException::class);
+        $this->expectExceptionCode(TenorException::FLOW_NOT_FOUND);
+        $this->expectExceptionMessage("flow 'missing' not found");
+        $this->evaluator()->simulateFlow('missing', 'admin', ['is_active' => true]);
+    }
+
+    public function testInspect(): void
+    {
+        $inspect = $this->evaluator()->inspect();
+        $this->assertSame('approval_flow', $inspect['flows'][0]['id']);
+        $this->assertSame(['pending', 'approved'], $inspect['entities'][0]['states']);
+    }
+}
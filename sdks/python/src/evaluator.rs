@@ -1,5 +1,4 @@
 use pyo3::prelude::*;
-use std::collections::BTreeMap;
 
 use crate::types::{json_to_py, py_to_json};
 
@@ -106,8 +105,11 @@ impl TenorEvaluator {
     ///   Provided states are overlaid on contract defaults — an empty dict
     ///   uses contract initial states for all entities.
     /// `persona`: persona ID for provenance recording
+    /// `instance_bindings`: optional dict of {entity_id: instance_id} selecting
+    ///   the instance each operation acts on (multi-instance contracts)
     ///
     /// Returns a dict with "flow_id", "persona", "outcome", "path", "would_transition", "verdicts".
+    #[pyo3(signature = (flow_id, facts, entity_states, persona, instance_bindings=None))]
     fn execute_flow(
         &self,
         py: Python<'_>,
@@ -115,6 +117,7 @@ impl TenorEvaluator {
         facts: &Bound<'_, PyAny>,
         entity_states: &Bound<'_, PyAny>,
         persona: &str,
+        instance_bindings: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Py<PyAny>> {
         let facts_json = py_to_json(facts)?;
         let states_json = py_to_json(entity_states)?;
@@ -158,8 +161,17 @@ impl TenorEvaluator {
                 ))
             })?;
 
-        // Use empty instance_bindings — falls back to DEFAULT_INSTANCE_ID (backward compat)
-        let instance_bindings = BTreeMap::new();
+        // Without bindings, operations fall back to DEFAULT_INSTANCE_ID (backward compat)
+        let instance_bindings = match instance_bindings {
+            Some(b) => tenor_eval::simulate::parse_instance_bindings(&py_to_json(b)?)
+                .map_err(|e| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "Invalid instance bindings: {}",
+                        e
+                    ))
+                })?,
+            None => tenor_eval::InstanceBindingMap::new(),
+        };
 
         // Execute the flow
         let mut flow_result = tenor_eval::flow::execute_flow(
@@ -167,8 +179,9 @@ impl TenorEvaluator {
             &self.contract,
             &snapshot,
             &mut merged_states,
+            &mut tenor_eval::EntityAttributeMap::new(),
             &instance_bindings,
-            None,
+            tenor_eval::FlowOptions::default(),
         )
         .map_err(|e| {
             pyo3::exceptions::PyRuntimeError::new_err(format!("Flow execution error: {}", e))
@@ -251,8 +264,8 @@ fn parse_entity_states(
         if let Some(state_str) = value.as_str() {
             // Flat format: entity_id -> state string; convert to single instance
             entity_states.insert(
-                (entity_id.clone(), tenor_eval::DEFAULT_INSTANCE_ID.to_string()),
-                state_str.to_string(),
+                tenor_eval::instance_key(entity_id, tenor_eval::DEFAULT_INSTANCE_ID),
+                state_str.into(),
             );
         } else if let Some(instance_map) = value.as_object() {
             // Nested format: entity_id -> { instance_id -> state }
@@ -267,8 +280,8 @@ fn parse_entity_states(
                     }
                 };
                 entity_states.insert(
-                    (entity_id.clone(), instance_id.clone()),
-                    state_str.to_string(),
+                    tenor_eval::instance_key(entity_id, instance_id),
+                    state_str.into(),
                 );
             }
         } else {
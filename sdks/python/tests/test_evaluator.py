@@ -252,6 +252,19 @@ class TestExecuteFlow:
         )
         assert result["outcome"] == "approval_failed"
 
+    def test_instance_bindings_select_instance(self):
+        evaluator = TenorEvaluator.from_bundle_json(BASIC_BUNDLE)
+        result = evaluator.execute_flow(
+            "approval_flow",
+            {"is_active": True},
+            {"Order": {"ord-001": "approved", "ord-002": "pending"}},
+            "admin",
+            instance_bindings={"Order": "ord-002"},
+        )
+        assert result["outcome"] == "order_approved"
+        assert result["would_transition"][0]["instance_id"] == "ord-002"
+        assert result["instance_bindings"] == {"Order": "ord-002"}
+
 
 class TestCrossSDKConsistency:
     """Verify Python SDK produces identical results to Rust evaluator.
@@ -0,0 +1,174 @@
+use magnus::{Error, Ruby, Value};
+
+use crate::types::{json_to_ruby, ruby_to_json};
+
+/// Tenor contract evaluator.
+///
+/// Wraps the Rust evaluator with a Ruby-friendly API.
+/// All inputs are Ruby hashes/arrays/strings; all outputs are Ruby hashes/arrays
+/// with string keys.
+#[magnus::wrap(class = "Tenor::TenorEvaluator", free_immediately, size)]
+pub struct TenorEvaluator {
+    contract: tenor_eval::Contract,
+}
+
+impl TenorEvaluator {
+    /// Load a contract from interchange JSON string.
+    pub fn from_bundle_json(ruby: &Ruby, json: String) -> Result<Self, Error> {
+        let bundle: serde_json::Value = serde_json::from_str(&json)
+            .map_err(|e| Error::new(ruby.exception_arg_error(), format!("Invalid JSON: {}", e)))?;
+        Self::load(ruby, &bundle)
+    }
+
+    /// Load a contract from a Ruby hash (interchange bundle).
+    pub fn from_bundle(ruby: &Ruby, bundle: Value) -> Result<Self, Error> {
+        let bundle_json = ruby_to_json(ruby, bundle)?;
+        Self::load(ruby, &bundle_json)
+    }
+
+    fn load(ruby: &Ruby, bundle: &serde_json::Value) -> Result<Self, Error> {
+        let contract = tenor_eval::Contract::from_interchange(bundle).map_err(|e| {
+            Error::new(
+                ruby.exception_arg_error(),
+                format!("Invalid contract: {}", e),
+            )
+        })?;
+        Ok(TenorEvaluator { contract })
+    }
+
+    /// Evaluate rules against the provided facts.
+    /// Returns a hash with "verdicts" array.
+    pub fn evaluate(ruby: &Ruby, rb_self: &Self, facts: Value) -> Result<Value, Error> {
+        let facts_json = ruby_to_json(ruby, facts)?;
+        let (_, verdict_set) = rb_self.evaluate_facts(ruby, &facts_json)?;
+        json_to_ruby(ruby, &verdict_set.to_json())
+    }
+
+    /// Compute the action space for a persona.
+    ///
+    /// `facts`: hash of {fact_id => value}
+    /// `entity_states`: hash of {entity_id => state_string} (single-instance flat format)
+    ///   OR {entity_id => {instance_id => state_string}} (multi-instance format)
+    /// `persona`: persona ID string
+    ///
+    /// Returns a hash with "persona_id", "actions", "blocked_actions", "current_verdicts".
+    pub fn compute_action_space(
+        ruby: &Ruby,
+        rb_self: &Self,
+        facts: Value,
+        entity_states: Value,
+        persona: String,
+    ) -> Result<Value, Error> {
+        let facts_json = ruby_to_json(ruby, facts)?;
+        let entity_map = parse_entity_states(ruby, entity_states)?;
+
+        let action_space =
+            tenor_eval::compute_action_space(&rb_self.contract, &facts_json, &entity_map, &persona)
+                .map_err(|e| runtime_error(ruby, "Action space error", e))?;
+
+        let result = serde_json::to_value(&action_space)
+            .map_err(|e| runtime_error(ruby, "Serialization error", e))?;
+        json_to_ruby(ruby, &result)
+    }
+
+    /// Simulate a flow.
+    ///
+    /// `flow_id`: ID of the flow to execute
+    /// `facts`: hash of {fact_id => value}
+    /// `entity_states`: hash of {entity_id => state_string} (flat) or
+    ///   {entity_id => {instance_id => state}} (multi-instance).
+    ///   Provided states are overlaid on contract defaults — an empty hash
+    ///   uses contract initial states for all entities.
+    /// `persona`: persona ID for provenance recording
+    ///
+    /// Returns a hash with "flow_id", "persona", "outcome", "path", "would_transition", "verdicts".
+    pub fn simulate_flow(
+        ruby: &Ruby,
+        rb_self: &Self,
+        flow_id: String,
+        facts: Value,
+        entity_states: Value,
+        persona: String,
+    ) -> Result<Value, Error> {
+        let facts_json = ruby_to_json(ruby, facts)?;
+        let provided_states = parse_entity_states(ruby, entity_states)?;
+
+        let (fact_set, verdict_set) = rb_self.evaluate_facts(ruby, &facts_json)?;
+
+        // Create frozen snapshot
+        let snapshot = tenor_eval::Snapshot {
+            facts: fact_set,
+            verdicts: verdict_set.clone(),
+        };
+
+        // Merge contract defaults with provided states (provided states override defaults)
+        let mut merged_states = tenor_eval::operation::init_entity_states(&rb_self.contract);
+        merged_states.extend(provided_states);
+
+        let target_flow = rb_self.contract.get_flow(&flow_id).ok_or_else(|| {
+            Error::new(
+                ruby.exception_runtime_error(),
+                format!(
+                    "Flow execution error: flow '{}' not found in contract",
+                    flow_id
+                ),
+            )
+        })?;
+
+        // Use empty instance_bindings — falls back to DEFAULT_INSTANCE_ID (backward compat)
+        let instance_bindings = tenor_eval::InstanceBindingMap::new();
+
+        let mut flow_result = tenor_eval::flow::execute_flow(
+            target_flow,
+            &rb_self.contract,
+            &snapshot,
+            &mut merged_states,
+            &mut tenor_eval::EntityAttributeMap::new(),
+            &instance_bindings,
+            tenor_eval::FlowOptions::default(),
+        )
+        .map_err(|e| runtime_error(ruby, "Flow execution error", e))?;
+
+        // Record persona for provenance
+        flow_result.initiating_persona = Some(persona.clone());
+
+        // Same output as the WASM simulate_flow_with_bindings, for cross-SDK compatibility
+        let result = tenor_eval::FlowEvalResult {
+            verdicts: verdict_set,
+            flow_result,
+        };
+        let json =
+            tenor_eval::simulate::simulation_json(&flow_id, &persona, &result, &instance_bindings);
+        json_to_ruby(ruby, &json)
+    }
+
+    fn evaluate_facts(
+        &self,
+        ruby: &Ruby,
+        facts: &serde_json::Value,
+    ) -> Result<(tenor_eval::FactSet, tenor_eval::VerdictSet), Error> {
+        let fact_set = tenor_eval::assemble::assemble_facts(&self.contract, facts)
+            .map_err(|e| runtime_error(ruby, "Fact assembly error", e))?;
+        let verdict_set = tenor_eval::rules::eval_strata(&self.contract, &fact_set)
+            .map_err(|e| runtime_error(ruby, "Evaluation error", e))?;
+        Ok((fact_set, verdict_set))
+    }
+}
+
+fn runtime_error(ruby: &Ruby, context: &str, e: impl std::fmt::Display) -> Error {
+    Error::new(
+        ruby.exception_runtime_error(),
+        format!("{}: {}", context, e),
+    )
+}
+
+/// Parse entity states in the flat or the nested format.
+fn parse_entity_states(ruby: &Ruby, states: Value) -> Result<tenor_eval::EntityStateMap, Error> {
+    let states_json = ruby_to_json(ruby, states)?;
+    tenor_eval::simulate::parse_entity_states(&states_json).map_err(|e| {
+        Error::new(
+            ruby.exception_arg_error(),
+            format!("Invalid entity states: {}", e),
+        )
+    })
+}
@@ -0,0 +1,29 @@
+use magnus::{function, method, prelude::*, Error, Ruby};
+
+mod evaluator;
+mod types;
+
+/// Tenor contract evaluator Ruby extension.
+#[magnus::init]
+fn init(ruby: &Ruby) -> Result<(), Error> {
+    let module = ruby.define_module("Tenor")?;
+    let class = module.define_class("TenorEvaluator", ruby.class_object())?;
+    class.define_singleton_method(
+        "from_bundle_json",
+        function!(evaluator::TenorEvaluator::from_bundle_json, 1),
+    )?;
+    class.define_singleton_method(
+        "from_bundle",
+        function!(evaluator::TenorEvaluator::from_bundle, 1),
+    )?;
+    class.define_method("evaluate", method!(evaluator::TenorEvaluator::evaluate, 1))?;
+    class.define_method(
+        "compute_action_space",
+        method!(evaluator::TenorEvaluator::compute_action_space, 3),
+    )?;
+    class.define_method(
+        "simulate_flow",
+        method!(evaluator::TenorEvaluator::simulate_flow, 4),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use magnus::r_hash::ForEach;
+use magnus::{prelude::*, Error, Float, Integer, RArray, RHash, RString, Ruby, Symbol, Value};
+
+/// Convert a serde_json::Value to a Ruby object. Object keys become strings.
+pub fn json_to_ruby(ruby: &Ruby, value: &serde_json::Value) -> Result<Value, Error> {
+    match value {
+        serde_json::Value::Null => Ok(ruby.qnil().as_value()),
+        serde_json::Value::Bool(true) => Ok(ruby.qtrue().as_value()),
+        serde_json::Value::Bool(false) => Ok(ruby.qfalse().as_value()),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Ok(ruby.integer_from_i64(i).as_value())
+            } else if let Some(f) = n.as_f64() {
+                Ok(ruby.float_from_f64(f).as_value())
+            } else {
+                Ok(ruby.qnil().as_value())
+            }
+        }
+        serde_json::Value::String(s) => Ok(ruby.str_new(s).as_value()),
+        serde_json::Value::Array(arr) => {
+            let ary = ruby.ary_new_capa(arr.len());
+            for item in arr {
+                ary.push(json_to_ruby(ruby, item)?)?;
+            }
+            Ok(ary.as_value())
+        }
+        serde_json::Value::Object(map) => {
+            let hash = ruby.hash_new();
+            for (k, v) in map {
+                hash.aset(ruby.str_new(k), json_to_ruby(ruby, v)?)?;
+            }
+            Ok(hash.as_value())
+        }
+    }
+}
+
+/// Convert a Ruby object to a serde_json::Value. Hash keys may be strings
+/// or symbols.
+pub fn ruby_to_json(ruby: &Ruby, obj: Value) -> Result<serde_json::Value, Error> {
+    if obj.is_nil() {
+        Ok(serde_json::Value::Null)
+    } else if obj.is_kind_of(ruby.class_true_class()) {
+        Ok(serde_json::Value::Bool(true))
+    } else if obj.is_kind_of(ruby.class_false_class()) {
+        Ok(serde_json::Value::Bool(false))
+    } else if let Some(s) = RString::from_value(obj) {
+        Ok(serde_json::Value::String(s.to_string()?))
+    } else if let Some(sym) = Symbol::from_value(obj) {
+        Ok(serde_json::Value::String(sym.name()?.into_owned()))
+    } else if let Some(i) = Integer::from_value(obj) {
+        Ok(serde_json::json!(i.to_i64()?))
+    } else if let Some(f) = Float::from_value(obj) {
+        Ok(serde_json::json!(f.to_f64()))
+    } else if let Some(hash) = RHash::from_value(obj) {
+        let mut map = serde_json::Map::new();
+        hash.foreach(|k: Value, v: Value| {
+            let key = match ruby_to_json(ruby, k)? {
+                serde_json::Value::String(key) => key,
+                other => {
+                    return Err(Error::new(
+                        ruby.exception_type_error(),
+                        format!("Hash keys must be strings or symbols, got {}", other),
+                    ))
+                }
+            };
+            map.insert(key, ruby_to_json(ruby, v)?);
+            Ok(ForEach::Continue)
+        })?;
+        Ok(serde_json::Value::Object(map))
+    } else if let Some(ary) = RArray::from_value(obj) {
+        ary.to_vec::<Value>()?
+            .into_iter()
+            .map(|item| ruby_to_json(ruby, item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(serde_json::Value::Array)
+    } else {
+        Err(Error::new(
+            ruby.exception_type_error(),
+            format!("Cannot convert Ruby type '{}' to JSON", unsafe {
+                obj.classname()
+            }),
+        ))
+    }
+}
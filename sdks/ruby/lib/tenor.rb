@@ -0,0 +1,6 @@
+# frozen_string_literal: true
+
+# Tenor contract evaluator for Ruby.
+
+require_relative "tenor/version"
+require_relative "tenor/tenor"
@@ -0,0 +1,152 @@
+# frozen_string_literal: true
+
+# Tests for Tenor::TenorEvaluator.
+#
+# Mirrors the WASM test suite from crates/tenor-eval-wasm/tests/wasm.rs
+# using the same BASIC_BUNDLE fixture to prove cross-SDK consistency.
+
+require "json"
+require "minitest/autorun"
+require "tenor"
+
+# Same bundle as crates/tenor-eval-wasm/tests/wasm.rs BASIC_BUNDLE
+BASIC_BUNDLE = <<~JSON
+  {
+    "constructs": [
+      {
+        "id": "is_active",
+        "kind": "Fact",
+        "provenance": { "file": "test.tenor", "line": 11 },
+        "source": { "field": "active", "system": "account" },
+        "tenor": "1.0",
+        "type": { "base": "Bool" }
+      },
+      {
+        "id": "Order",
+        "initial": "pending",
+        "kind": "Entity",
+        "provenance": { "file": "test.tenor", "line": 3 },
+        "states": ["pending", "approved"],
+        "tenor": "1.0",
+        "transitions": [{ "from": "pending", "to": "approved" }]
+      },
+      {
+        "body": {
+          "produce": {
+            "payload": { "type": { "base": "Bool" }, "value": true },
+            "verdict_type": "account_active"
+          },
+          "when": {
+            "left": { "fact_ref": "is_active" },
+            "op": "=",
+            "right": { "literal": true, "type": { "base": "Bool" } }
+          }
+        },
+        "id": "check_active",
+        "kind": "Rule",
+        "provenance": { "file": "test.tenor", "line": 16 },
+        "stratum": 0,
+        "tenor": "1.0"
+      },
+      {
+        "allowed_personas": ["admin"],
+        "effects": [{ "entity_id": "Order", "from": "pending", "to": "approved" }],
+        "error_contract": ["precondition_failed"],
+        "id": "approve_order",
+        "kind": "Operation",
+        "precondition": { "verdict_present": "account_active" },
+        "provenance": { "file": "test.tenor", "line": 22 },
+        "tenor": "1.0"
+      },
+      {
+        "entry": "step_approve",
+        "id": "approval_flow",
+        "kind": "Flow",
+        "provenance": { "file": "test.tenor", "line": 29 },
+        "snapshot": "at_initiation",
+        "steps": [
+          {
+            "id": "step_approve",
+            "kind": "OperationStep",
+            "on_failure": { "kind": "Terminate", "outcome": "approval_failed" },
+            "op": "approve_order",
+            "outcomes": {
+              "success": { "kind": "Terminal", "outcome": "order_approved" }
+            },
+            "persona": "admin"
+          }
+        ],
+        "tenor": "1.0"
+      }
+    ],
+    "id": "entity_operation_basic",
+    "kind": "Bundle",
+    "tenor": "1.0",
+    "tenor_version": "1.0.0"
+  }
+JSON
+
+class TestTenorEvaluator < Minitest::Test
+  def evaluator
+    Tenor::TenorEvaluator.from_bundle_json(BASIC_BUNDLE)
+  end
+
+  def test_load_from_json_and_hash
+    assert_instance_of Tenor::TenorEvaluator, evaluator
+    assert_instance_of Tenor::TenorEvaluator,
+                       Tenor::TenorEvaluator.from_bundle(JSON.parse(BASIC_BUNDLE))
+    assert_raises(ArgumentError) { Tenor::TenorEvaluator.from_bundle_json("not json") }
+    assert_raises(ArgumentError) { Tenor::TenorEvaluator.from_bundle({ "kind" => "Bundle" }) }
+  end
+
+  def test_evaluate_produces_verdicts
+    result = evaluator.evaluate({ "is_active" => true })
+    assert_equal 1, result["verdicts"].length
+    verdict = result["verdicts"][0]
+    assert_equal "account_active", verdict["type"]
+    assert_equal "check_active", verdict["provenance"]["rule"]
+  end
+
+  def test_evaluate_accepts_symbol_keys
+    assert_equal 1, evaluator.evaluate({ is_active: true })["verdicts"].length
+    assert_empty evaluator.evaluate({ is_active: false })["verdicts"]
+  end
+
+  def test_evaluate_missing_required_fact
+    error = assert_raises(RuntimeError) { evaluator.evaluate({}) }
+    assert_match(/Fact assembly error/, error.message)
+  end
+
+  def test_compute_action_space
+    space = evaluator.compute_action_space({ "is_active" => true }, { "Order" => "pending" }, "admin")
+    assert_equal "admin", space["persona_id"]
+    assert_equal "approval_flow", space["actions"][0]["flow_id"]
+
+    blocked = evaluator.compute_action_space({ "is_active" => true }, { "Order" => "pending" }, "guest")
+    assert_empty blocked["actions"]
+    assert_equal "PersonaNotAuthorized", blocked["blocked_actions"][0]["reason"]["type"]
+  end
+
+  def test_compute_action_space_nested_format
+    space = evaluator.compute_action_space(
+      { "is_active" => true },
+      { "Order" => { "ord-001" => "pending" } },
+      "admin"
+    )
+    assert_includes space["actions"][0]["instance_bindings"]["Order"], "ord-001"
+  end
+
+  def test_simulate_flow
+    result = evaluator.simulate_flow("approval_flow", { "is_active" => true }, {}, "admin")
+    assert_equal true, result["simulation"]
+    assert_equal "order_approved", result["outcome"]
+    assert_equal "approved", result["would_transition"][0]["to_state"]
+  end
+
+  def test_simulate_flow_not_found
+    error = assert_raises(RuntimeError) do
+      evaluator.simulate_flow("missing", { "is_active" => true }, {}, "admin")
+    end
+    assert_match(/flow 'missing' not found/, error.message)
+  end
+end
@@ -22,6 +22,7 @@ import type {
 
 interface WasmModule {
   load_contract(interchange_json: string): string;
+  load_contract_cbor(interchange_cbor: Uint8Array): string;
   free_contract(handle: number): void;
   evaluate(handle: number, facts_json: string): string;
   simulate_flow(
@@ -150,6 +151,23 @@ export class TenorEvaluator {
     return new TenorEvaluator(wasm, handle);
   }
 
+  /**
+   * Load a contract from a CBOR-encoded interchange bundle. Decoding CBOR is
+   * much faster than parsing JSON for large bundles.
+   *
+   * @param cbor - The interchange bundle encoded as CBOR.
+   * @returns A loaded TenorEvaluator ready for evaluation.
+   * @throws {Error} If the bytes are not valid CBOR or not a valid contract bundle.
+   */
+  static fromCbor(cbor: Uint8Array): TenorEvaluator {
+    const wasm = getWasmModule();
+    const resultStr = wasm.load_contract_cbor(cbor);
+    const result = parseResult(resultStr);
+    checkError(result, "Failed to load contract");
+    const handle = (result as { handle: number }).handle;
+    return new TenorEvaluator(wasm, handle);
+  }
+
   /**
    * Evaluate rules against the provided facts and return the verdict set.
    *
@@ -63,6 +63,7 @@ export type {
   Action,
   BlockedAction,
   BlockedReason,
+  FailedClause,
   VerdictSummary,
   EntitySummary,
   // Flow result types
@@ -154,15 +154,30 @@ export interface Action {
   instance_bindings: Record<string, string[]>;
 }
 
+/** A top-level precondition or guard clause that evaluated to false. */
+export interface FailedClause {
+  /** The clause in Tenor predicate syntax, e.g. `amount <= limit`. */
+  clause: string;
+  /** Facts the clause read, with their current values. */
+  facts: Record<string, unknown>;
+  /** Verdicts the clause requires that are absent. */
+  missing_verdicts: string[];
+}
+
 /** Why an action is currently blocked. */
 export type BlockedReason =
-  | { type: "PersonaNotAuthorized" }
-  | { type: "PreconditionNotMet"; missing_verdicts: string[] }
+  | { type: "PersonaNotAuthorized"; allowed_personas: string[] }
+  | {
+      type: "PreconditionNotMet";
+      missing_verdicts: string[];
+      failed_clauses: FailedClause[];
+    }
   | {
       type: "EntityNotInSourceState";
       entity_id: string;
       current_state: string;
       required_state: string;
+      expected_states: string[];
     }
   | { type: "MissingFacts"; fact_ids: string[] };
 
@@ -63,7 +63,7 @@ function makeAction(
 function makeBlockedPersona(flowId: string): BlockedAction {
   return {
     flow_id: flowId,
-    reason: { type: "PersonaNotAuthorized" },
+    reason: { type: "PersonaNotAuthorized", allowed_personas: ["admin"] },
     instance_bindings: {},
   };
 }
@@ -74,7 +74,11 @@ function makeBlockedPrecondition(
 ): BlockedAction {
   return {
     flow_id: flowId,
-    reason: { type: "PreconditionNotMet", missing_verdicts: missingVerdicts },
+    reason: {
+      type: "PreconditionNotMet",
+      missing_verdicts: missingVerdicts,
+      failed_clauses: [],
+    },
     instance_bindings: {},
   };
 }
@@ -92,6 +96,7 @@ function makeBlockedEntityState(
       entity_id: entityId,
       current_state: currentState,
       required_state: requiredState,
+      expected_states: [requiredState],
     },
     instance_bindings: { [entityId]: ["_default"] },
   };
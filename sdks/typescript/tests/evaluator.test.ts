@@ -391,4 +391,12 @@ describe("TenorEvaluator", () => {
     expect(v1.verdicts).toHaveLength(v2.verdicts.length);
     expect(v1.verdicts[0].type).toBe(v2.verdicts[0].type);
   });
+
+  // ── Additional: fromCbor ───────────────────────────────────────────────────
+
+  it("fromCbor_rejects_invalid_cbor", () => {
+    expect(() =>
+      TenorEvaluator.fromCbor(new Uint8Array([0xff, 0x00])),
+    ).toThrow(/invalid CBOR/);
+  });
 });